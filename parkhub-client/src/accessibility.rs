@@ -0,0 +1,68 @@
+//! Accessibility settings (theme mode, font scale, reduce motion)
+//!
+//! The server keeps them in the user's preferences, so they follow the user
+//! to any machine and are applied on login. The local `accessibility.toml`
+//! holds the last applied settings for use before login and while offline.
+
+use std::path::PathBuf;
+
+use parkhub_common::AccessibilityPreferences;
+use slint::ComponentHandle;
+use tracing::{info, warn};
+
+use crate::{MainWindow, ThemeSettings};
+
+fn config_path() -> PathBuf {
+    directories::ProjectDirs::from("com", "parkhub", "ParkHub Client")
+        .map_or_else(
+            || PathBuf::from(".").join("config"),
+            |p| p.config_dir().to_path_buf(),
+        )
+        .join("accessibility.toml")
+}
+
+/// Settings saved on this machine, if any.
+pub fn load_local() -> Option<AccessibilityPreferences> {
+    let path = config_path();
+    let content = std::fs::read_to_string(&path).ok()?;
+    let settings = toml::from_str(&content).ok()?;
+    info!("Loaded accessibility settings from {:?}", path);
+    Some(settings)
+}
+
+/// Save `settings` as the offline fallback.
+pub fn save_local(settings: &AccessibilityPreferences) {
+    let path = config_path();
+    if let Some(dir) = path.parent()
+        && let Err(e) = std::fs::create_dir_all(dir)
+    {
+        warn!("Failed to create config dir: {}", e);
+        return;
+    }
+    match toml::to_string_pretty(settings) {
+        Ok(content) => {
+            if let Err(e) = std::fs::write(&path, content) {
+                warn!("Failed to save accessibility settings: {}", e);
+            }
+        }
+        Err(e) => warn!("Failed to serialize accessibility settings: {}", e),
+    }
+}
+
+/// The settings currently applied to the UI.
+pub fn current(ui: &MainWindow) -> AccessibilityPreferences {
+    let theme = ui.global::<ThemeSettings>();
+    AccessibilityPreferences {
+        theme_mode: theme.get_mode(),
+        font_scale: theme.get_font_scale(),
+        reduce_motion: theme.get_reduce_motion(),
+    }
+}
+
+/// Apply `settings` to the UI.
+pub fn apply(ui: &MainWindow, settings: &AccessibilityPreferences) {
+    let theme = ui.global::<ThemeSettings>();
+    theme.set_mode(settings.theme_mode);
+    theme.set_font_scale(settings.font_scale);
+    theme.set_reduce_motion(settings.reduce_motion);
+}
@@ -0,0 +1,124 @@
+//! Booking countdowns
+//!
+//! The server reports how many seconds remain on each booking. Those counts
+//! are turned into monotonic [`Instant`] deadlines when the response arrives,
+//! so the countdown stays correct even when the local wall clock is skewed.
+//! Bookings from servers without countdown data fall back to the local clock.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use parkhub_common::{BookingCountdown, BookingStatus};
+
+use crate::server_connection::ListedBooking;
+
+/// Warning window used when the server does not report one.
+pub const DEFAULT_EXPIRY_WARNING_MINUTES: u32 = 15;
+
+struct Deadline {
+    booking_id: String,
+    ends_at: Instant,
+    /// Pending, confirmed or active — only these warn before expiry
+    live: bool,
+    warned: bool,
+}
+
+struct Board {
+    deadlines: Vec<Deadline>,
+    warning_minutes: u32,
+}
+
+static BOARD: Mutex<Board> = Mutex::new(Board {
+    deadlines: Vec::new(),
+    warning_minutes: DEFAULT_EXPIRY_WARNING_MINUTES,
+});
+
+/// Countdown state of one booking.
+pub struct CountdownView {
+    pub booking_id: String,
+    pub remaining: String,
+    pub expiring_soon: bool,
+}
+
+/// Countdown for a booking, from the server when available.
+fn countdown_of(listed: &ListedBooking, warning_minutes: u32) -> BookingCountdown {
+    listed
+        .countdown
+        .unwrap_or_else(|| BookingCountdown::new(&listed.booking, Utc::now(), warning_minutes))
+}
+
+/// Replace the tracked bookings with a fresh list that arrived at `received`.
+///
+/// Bookings that were already warned about keep that state, so a refresh
+/// does not repeat the warning.
+pub fn track(bookings: &[ListedBooking], warning_minutes: u32, received: Instant) {
+    let mut board = BOARD
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let deadlines = bookings
+        .iter()
+        .map(|listed| {
+            let booking_id = listed.booking.id.to_string();
+            let remaining = countdown_of(listed, warning_minutes).remaining_seconds;
+            let warned = board
+                .deadlines
+                .iter()
+                .any(|d| d.booking_id == booking_id && d.warned);
+            Deadline {
+                ends_at: received + Duration::from_secs(remaining.max(0).unsigned_abs()),
+                live: matches!(
+                    listed.booking.status,
+                    BookingStatus::Pending | BookingStatus::Confirmed | BookingStatus::Active
+                ),
+                warned,
+                booking_id,
+            }
+        })
+        .collect();
+    board.deadlines = deadlines;
+    board.warning_minutes = warning_minutes;
+}
+
+/// Countdowns of all tracked bookings at `now`, plus the ids of bookings
+/// that entered the expiry warning window since the last tick.
+pub fn tick(now: Instant) -> (Vec<CountdownView>, Vec<String>) {
+    let mut board = BOARD
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let window = Duration::from_secs(u64::from(board.warning_minutes) * 60);
+    let mut newly_warned = Vec::new();
+    let views = board
+        .deadlines
+        .iter_mut()
+        .map(|d| {
+            let remaining = d.ends_at.saturating_duration_since(now);
+            let expiring_soon = d.live && !remaining.is_zero() && remaining <= window;
+            if expiring_soon && !d.warned {
+                d.warned = true;
+                newly_warned.push(d.booking_id.clone());
+            }
+            CountdownView {
+                booking_id: d.booking_id.clone(),
+                remaining: remaining_label(remaining),
+                expiring_soon,
+            }
+        })
+        .collect();
+    (views, newly_warned)
+}
+
+/// Label such as `Ends in 2 h 05 min`, `Ends in 4 min` or `Ends in 45 s`.
+fn remaining_label(remaining: Duration) -> String {
+    let secs = remaining.as_secs();
+    if secs == 0 {
+        "Ended".to_string()
+    } else if secs < 60 {
+        format!("Ends in {secs} s")
+    } else if secs < 3600 {
+        format!("Ends in {} min", secs.div_ceil(60))
+    } else {
+        let minutes = secs.div_ceil(60);
+        format!("Ends in {} h {:02} min", minutes / 60, minutes % 60)
+    }
+}
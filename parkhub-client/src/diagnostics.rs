@@ -0,0 +1,173 @@
+//! Diagnostics bundle
+//!
+//! The client keeps its most recent log lines and API errors in memory.
+//! "Export diagnostics" writes them, together with the connection details,
+//! into a ZIP the user can attach to a bug report. Access and refresh tokens
+//! are left out of the connection details and blanked in log lines.
+
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use parkhub_common::{ApiError, PROTOCOL_VERSION};
+use serde::Serialize;
+use tracing_subscriber::fmt::MakeWriter;
+
+use crate::server_connection::ConnectionSummary;
+
+/// Log lines kept for the bundle
+const MAX_LOG_LINES: usize = 2000;
+
+/// API errors kept for the bundle
+const MAX_API_ERRORS: usize = 50;
+
+static LOG_LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+static API_ERRORS: Mutex<VecDeque<RecordedError>> = Mutex::new(VecDeque::new());
+
+/// A failed API request
+#[derive(Debug, Clone, Serialize)]
+struct RecordedError {
+    at: DateTime<Utc>,
+    /// What the client was doing, e.g. "Failed to list users"
+    request: String,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+/// Remember a failed API request for the bundle
+pub fn record_api_error(request: &str, error: Option<&ApiError>) {
+    let recorded = RecordedError {
+        at: Utc::now(),
+        request: request.to_string(),
+        code: error.map(|e| e.code.to_string()),
+        message: error.map(|e| redact(&e.message)),
+    };
+    if let Ok(mut errors) = API_ERRORS.lock() {
+        if errors.len() == MAX_API_ERRORS {
+            errors.pop_front();
+        }
+        errors.push_back(recorded);
+    }
+}
+
+/// Tracing writer that keeps formatted log lines for the bundle
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogBuffer;
+
+impl Write for LogBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Ok(mut lines) = LOG_LINES.lock() {
+            for line in String::from_utf8_lossy(buf).lines() {
+                if lines.len() == MAX_LOG_LINES {
+                    lines.pop_front();
+                }
+                lines.push_back(redact(line));
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl MakeWriter<'_> for LogBuffer {
+    type Writer = Self;
+
+    fn make_writer(&self) -> Self::Writer {
+        *self
+    }
+}
+
+/// Blank bearer tokens and JWTs in `text`
+fn redact(text: &str) -> String {
+    let mut out = Vec::new();
+    let mut after_bearer = false;
+    for word in text.split(' ') {
+        if after_bearer || looks_like_jwt(word) {
+            out.push("[redacted]");
+        } else {
+            out.push(word);
+        }
+        after_bearer = word.eq_ignore_ascii_case("bearer");
+    }
+    out.join(" ")
+}
+
+/// Three base64url segments separated by dots
+fn looks_like_jwt(word: &str) -> bool {
+    let word = word.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+    let segments: Vec<&str> = word.split('.').collect();
+    segments.len() == 3
+        && word.len() > 40
+        && segments.iter().all(|s| {
+            !s.is_empty()
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
+
+/// `diagnostics.json` in the bundle
+#[derive(Serialize)]
+struct Report<'a> {
+    generated_at: DateTime<Utc>,
+    client_version: &'static str,
+    protocol_version: &'static str,
+    os: &'static str,
+    arch: &'static str,
+    connection: Option<&'a ConnectionSummary>,
+    api_errors: Vec<RecordedError>,
+}
+
+/// Where to save a bundle made now: the downloads folder, or the temporary
+/// directory on systems without one
+pub fn default_bundle_path() -> PathBuf {
+    let dir = directories::UserDirs::new()
+        .and_then(|dirs| dirs.download_dir().map(Path::to_path_buf))
+        .unwrap_or_else(std::env::temp_dir);
+    dir.join(format!(
+        "parkhub-diagnostics-{}.zip",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Write the bundle to `path`
+pub fn write_bundle(path: &Path, connection: Option<&ConnectionSummary>) -> Result<()> {
+    let report = Report {
+        generated_at: Utc::now(),
+        client_version: env!("CARGO_PKG_VERSION"),
+        protocol_version: PROTOCOL_VERSION,
+        os: std::env::consts::OS,
+        arch: std::env::consts::ARCH,
+        connection,
+        api_errors: API_ERRORS
+            .lock()
+            .map(|errors| errors.iter().cloned().collect())
+            .unwrap_or_default(),
+    };
+    let log = LOG_LINES
+        .lock()
+        .map(|lines| {
+            lines
+                .iter()
+                .flat_map(|line| [line.as_str(), "\n"])
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+
+    let file =
+        std::fs::File::create(path).with_context(|| format!("Cannot create {}", path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file("diagnostics.json", options)?;
+    zip.write_all(&serde_json::to_vec_pretty(&report)?)?;
+    zip.start_file("client.log", options)?;
+    zip.write_all(log.as_bytes())?;
+    zip.finish()?;
+    Ok(())
+}
@@ -1,18 +1,94 @@
 //! Server Discovery
 //!
 //! Discovers `ParkHub` servers on the local network using mDNS/DNS-SD
-//! with fallback to localhost probing.
+//! with fallback to localhost probing. When a scan finds nothing, the
+//! likely cause is kept as a [`DiscoveryIssue`] for the connect screen,
+//! which also offers a scan of the local /24 subnet on request.
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use mdns_sd::{ServiceDaemon, ServiceEvent};
+use std::net::{IpAddr, Ipv4Addr, UdpSocket};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, Semaphore};
+use tokio::task::JoinSet;
 use tokio::time::timeout;
 use tracing::{debug, info, warn};
 
 use crate::AppState;
 
+/// IPv4 group mDNS queries and answers are sent to
+const MDNS_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+
+/// Subnet scan requests in flight at once
+const SUBNET_SCAN_CONCURRENCY: usize = 64;
+
+/// Why a scan found no servers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiscoveryIssue {
+    /// The computer has no network address besides loopback
+    NoNetwork,
+    /// mDNS could not start, e.g. its UDP port is taken or not allowed
+    MdnsUnavailable,
+    /// The network interface refuses to join the mDNS multicast group
+    MulticastFailed,
+    /// Queries went out but no server answered
+    NoAnswer,
+    /// The subnet scan found no server in the given network
+    SubnetEmpty(String),
+}
+
+impl DiscoveryIssue {
+    /// Hint shown under the empty server list
+    pub fn hint(&self) -> String {
+        match self {
+            Self::NoNetwork => {
+                "This computer is not connected to a network. Connect it, or enter the \
+                 server address below."
+                    .to_string()
+            }
+            Self::MdnsUnavailable => {
+                "Network discovery (mDNS) is not available on this computer. Scan the \
+                 subnet or connect manually."
+                    .to_string()
+            }
+            Self::MulticastFailed => {
+                "Multicast blocked — this network does not let discovery through. Scan \
+                 the subnet or connect manually."
+                    .to_string()
+            }
+            Self::NoAnswer => "No server answered. If a firewall or the network blocks multicast, \
+                 scan the subnet or connect manually."
+                .to_string(),
+            Self::SubnetEmpty(subnet) => format!(
+                "No server found on port {} in {subnet}. Connect manually with the \
+                 server's address.",
+                parkhub_common::DEFAULT_PORT
+            ),
+        }
+    }
+}
+
+/// This computer's IPv4 address on the local network, if it has one.
+///
+/// Connecting a UDP socket only picks the outgoing interface; nothing is sent.
+fn local_ipv4() -> Option<Ipv4Addr> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    match socket.local_addr().ok()?.ip() {
+        IpAddr::V4(ip) if !ip.is_loopback() && !ip.is_unspecified() => Some(ip),
+        _ => None,
+    }
+}
+
+/// Whether the interface with address `ip` can join the mDNS group, which
+/// fails where multicast is disabled on it.
+fn multicast_available(ip: Ipv4Addr) -> bool {
+    UdpSocket::bind((ip, 0))
+        .and_then(|socket| socket.join_multicast_v4(&MDNS_GROUP, &ip))
+        .is_ok()
+}
+
 /// Probe localhost for a running server
 async fn probe_localhost(state: Arc<RwLock<AppState>>) -> bool {
     let ports = [7878u16, 8080, 3000];
@@ -39,6 +115,8 @@ async fn probe_localhost(state: Arc<RwLock<AppState>>) -> bool {
                     port,
                     tls: false,
                     fingerprint: None,
+                    organization: None,
+                    standby: false,
                 };
 
                 let mut state = state.write().await;
@@ -80,6 +158,8 @@ async fn probe_localhost(state: Arc<RwLock<AppState>>) -> bool {
                     port,
                     tls: false,
                     fingerprint: None,
+                    organization: None,
+                    standby: false,
                 };
 
                 let mut state = state.write().await;
@@ -108,6 +188,7 @@ async fn probe_localhost(state: Arc<RwLock<AppState>>) -> bool {
 /// Returns after initial discovery phase (doesn't block indefinitely)
 pub async fn discover_servers(state: Arc<RwLock<AppState>>) -> Result<()> {
     info!("Starting server discovery...");
+    state.write().await.discovery_issue = None;
 
     // First, probe localhost for a local server (fast and reliable)
     // Run synchronously to ensure we find local servers immediately
@@ -117,11 +198,31 @@ pub async fn discover_servers(state: Arc<RwLock<AppState>>) -> Result<()> {
     }
 
     // Then try mDNS discovery with a timeout
+    let mdns_issue = browse_mdns(&state).await;
+
+    let local_ip = local_ipv4();
+    let mut state = state.write().await;
+    if state.discovered_servers.is_empty() {
+        let issue = match (local_ip, mdns_issue) {
+            (None, _) => DiscoveryIssue::NoNetwork,
+            (Some(_), Some(issue)) => issue,
+            (Some(ip), None) if !multicast_available(ip) => DiscoveryIssue::MulticastFailed,
+            (Some(_), None) => DiscoveryIssue::NoAnswer,
+        };
+        info!("No servers found: {:?}", issue);
+        state.discovery_issue = Some(issue);
+    }
+    Ok(())
+}
+
+/// Browse for servers via mDNS for a few seconds. Returns an issue when
+/// mDNS could not run at all.
+async fn browse_mdns(state: &Arc<RwLock<AppState>>) -> Option<DiscoveryIssue> {
     let daemon = match ServiceDaemon::new() {
         Ok(d) => d,
         Err(e) => {
             warn!("mDNS not available: {}. Using localhost probe only.", e);
-            return Ok(());
+            return Some(DiscoveryIssue::MdnsUnavailable);
         }
     };
 
@@ -130,7 +231,7 @@ pub async fn discover_servers(state: Arc<RwLock<AppState>>) -> Result<()> {
         Ok(r) => r,
         Err(e) => {
             warn!("mDNS browse failed: {}. Using localhost probe only.", e);
-            return Ok(());
+            return Some(DiscoveryIssue::MdnsUnavailable);
         }
     };
 
@@ -157,6 +258,10 @@ pub async fn discover_servers(state: Arc<RwLock<AppState>>) -> Result<()> {
                     let tls = properties
                         .get_property_val_str("tls")
                         .is_some_and(|s| s == "true");
+                    let organization = properties.get_property_val_str("org").map(str::to_string);
+                    let standby = properties
+                        .get_property_val_str("role")
+                        .is_some_and(|s| s == "standby");
 
                     // Get first address
                     let host = info.get_addresses().iter().next().map_or_else(
@@ -172,6 +277,8 @@ pub async fn discover_servers(state: Arc<RwLock<AppState>>) -> Result<()> {
                         port: info.get_port(),
                         tls,
                         fingerprint: None,
+                        organization,
+                        standby,
                     };
 
                     // Add to discovered servers
@@ -210,5 +317,95 @@ pub async fn discover_servers(state: Arc<RwLock<AppState>>) -> Result<()> {
     tokio::time::sleep(Duration::from_millis(100)).await;
 
     info!("Discovery scan complete");
-    Ok(())
+    None
+}
+
+/// Probe every address of the local /24 subnet for a server on the default
+/// port, for networks where multicast does not get through. Found servers
+/// are added to the discovered list; returns how many were found.
+pub async fn scan_subnet(state: Arc<RwLock<AppState>>) -> Result<usize> {
+    let Some(ip) = local_ipv4() else {
+        state.write().await.discovery_issue = Some(DiscoveryIssue::NoNetwork);
+        bail!("No network connection");
+    };
+    let [a, b, c, own] = ip.octets();
+    let subnet = format!("{a}.{b}.{c}.0/24");
+    info!(
+        "Scanning {} for servers on port {}",
+        subnet,
+        parkhub_common::DEFAULT_PORT
+    );
+
+    // Servers use self-signed certificates by default; this only checks
+    // that something answers like a ParkHub server
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .timeout(Duration::from_millis(800))
+        .build()?;
+    let permits = Arc::new(Semaphore::new(SUBNET_SCAN_CONCURRENCY));
+    let mut probes = JoinSet::new();
+    for host in (1..=254u8).filter(|host| *host != own) {
+        let client = client.clone();
+        let permits = permits.clone();
+        probes.spawn(async move {
+            let _permit = permits.acquire_owned().await.ok()?;
+            probe_host(&client, Ipv4Addr::new(a, b, c, host)).await
+        });
+    }
+
+    let mut found = 0;
+    while let Some(result) = probes.join_next().await {
+        let Ok(Some(server_info)) = result else {
+            continue;
+        };
+        info!("Found server at {}:{}", server_info.host, server_info.port);
+        let mut state = state.write().await;
+        if !state
+            .discovered_servers
+            .iter()
+            .any(|s| s.host == server_info.host && s.port == server_info.port)
+        {
+            state.discovered_servers.push(server_info);
+            found += 1;
+        }
+    }
+
+    let mut state = state.write().await;
+    state.discovery_issue = state
+        .discovered_servers
+        .is_empty()
+        .then_some(DiscoveryIssue::SubnetEmpty(subnet));
+    Ok(found)
+}
+
+/// Ask `ip` for the public discovery handshake, over HTTPS and then HTTP.
+async fn probe_host(client: &reqwest::Client, ip: Ipv4Addr) -> Option<parkhub_common::ServerInfo> {
+    let port = parkhub_common::DEFAULT_PORT;
+    for tls in [true, false] {
+        let scheme = if tls { "https" } else { "http" };
+        let url = format!("{scheme}://{ip}:{port}/api/v1/discover");
+        let Ok(resp) = client.get(&url).send().await else {
+            continue;
+        };
+        if !resp.status().is_success() {
+            continue;
+        }
+        let Ok(body) = resp.json::<serde_json::Value>().await else {
+            continue;
+        };
+        let data = &body["data"];
+        let name = data["name"].as_str()?;
+        return Some(parkhub_common::ServerInfo {
+            name: format!("{name} ({ip})"),
+            version: data["version"].as_str().unwrap_or("unknown").to_string(),
+            protocol_version: parkhub_common::PROTOCOL_VERSION.to_string(),
+            host: ip.to_string(),
+            port,
+            tls,
+            fingerprint: None,
+            organization: None,
+            standby: false,
+        });
+    }
+    None
 }
@@ -0,0 +1,205 @@
+//! User-facing error messages
+//!
+//! Server messages are English and written for developers. The client shows
+//! its own text for each [`ErrorCode`] in the UI language instead; codes it
+//! has no text for, e.g. from a newer server, get a generic message naming
+//! the code so support can still tell what happened.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use parkhub_common::{ApiError, ErrorCode};
+
+/// Whether messages are shown in English rather than German
+static ENGLISH: AtomicBool = AtomicBool::new(false);
+
+/// Follow the UI language (`"de"` or `"en"`)
+pub fn set_locale(locale: &str) {
+    ENGLISH.store(locale == "en", Ordering::Relaxed);
+}
+
+/// The message to show for `error`
+pub fn message(error: &ApiError) -> String {
+    let english = ENGLISH.load(Ordering::Relaxed);
+    match text(&error.code) {
+        Some((de, en)) => (if english { en } else { de }).to_string(),
+        None if english => format!("The server could not process the request ({}).", error.code),
+        None => format!(
+            "Der Server konnte die Anfrage nicht verarbeiten ({}).",
+            error.code
+        ),
+    }
+}
+
+/// German and English text for `code`
+const fn text(code: &ErrorCode) -> Option<(&'static str, &'static str)> {
+    Some(match code {
+        ErrorCode::InvalidCredentials => (
+            "Benutzername oder Passwort ist falsch.",
+            "Wrong username or password.",
+        ),
+        ErrorCode::AccountDisabled => (
+            "Ihr Konto ist deaktiviert. Wenden Sie sich an einen Administrator.",
+            "Your account is disabled. Contact an administrator.",
+        ),
+        ErrorCode::Unauthorized
+        | ErrorCode::InvalidToken
+        | ErrorCode::TokenExpired
+        | ErrorCode::InvalidRefreshToken
+        | ErrorCode::RefreshTokenReused
+        | ErrorCode::SessionIdleTimeout => (
+            "Ihre Sitzung ist abgelaufen. Bitte melden Sie sich erneut an.",
+            "Your session has expired. Please sign in again.",
+        ),
+        ErrorCode::SessionLimitReached => (
+            "Zu viele gleichzeitige Sitzungen. Melden Sie sich zuerst auf einem anderen Gerät ab.",
+            "Too many concurrent sessions. Sign out on another device first.",
+        ),
+        ErrorCode::Forbidden => (
+            "Dafür fehlt Ihnen die Berechtigung.",
+            "You do not have permission to do this.",
+        ),
+        ErrorCode::PasswordChangeRequired => (
+            "Bitte ändern Sie zuerst Ihr Passwort.",
+            "Please change your password first.",
+        ),
+        ErrorCode::InvalidPassword => (
+            "Das aktuelle Passwort ist falsch.",
+            "The current password is wrong.",
+        ),
+        ErrorCode::WeakPassword => (
+            "Das Passwort braucht mindestens 8 Zeichen mit Groß- und Kleinbuchstaben und einer Ziffer.",
+            "The password needs at least 8 characters with upper and lower case letters and a digit.",
+        ),
+        ErrorCode::PasswordMismatch => (
+            "Die Passwörter stimmen nicht überein.",
+            "The passwords do not match.",
+        ),
+        ErrorCode::PasswordUnchanged => (
+            "Das neue Passwort muss sich vom alten unterscheiden.",
+            "The new password must differ from the old one.",
+        ),
+        ErrorCode::TooManyAttempts | ErrorCode::RateLimited => (
+            "Zu viele Versuche. Bitte warten Sie einen Moment.",
+            "Too many attempts. Please wait a moment.",
+        ),
+        ErrorCode::ProtocolMismatch => (
+            "Diese App-Version passt nicht zum Server. Bitte aktualisieren Sie die App.",
+            "This app version does not match the server. Please update the app.",
+        ),
+        ErrorCode::RegistrationDisabled => (
+            "Die Selbstregistrierung ist deaktiviert. Wenden Sie sich an einen Administrator.",
+            "Self-registration is disabled. Contact an administrator.",
+        ),
+        ErrorCode::EmailExists | ErrorCode::EmailTaken => (
+            "Diese E-Mail-Adresse wird bereits verwendet.",
+            "This email address is already in use.",
+        ),
+        ErrorCode::CannotDeleteSelf => (
+            "Sie können Ihr eigenes Konto hier nicht löschen.",
+            "You cannot delete your own account here.",
+        ),
+        ErrorCode::SlotUnavailable | ErrorCode::SlotNotAvailable | ErrorCode::SlotOccupied => (
+            "Der Stellplatz ist zu dieser Zeit nicht verfügbar.",
+            "The slot is not available at this time.",
+        ),
+        ErrorCode::SlotHeld => (
+            "Der Stellplatz wird gerade von jemand anderem gebucht.",
+            "Someone else is booking this slot right now.",
+        ),
+        ErrorCode::NoSlot | ErrorCode::NoSlotAvailable | ErrorCode::NoSlotsAvailable => (
+            "Es ist kein passender Stellplatz frei.",
+            "No suitable slot is free.",
+        ),
+        ErrorCode::InvalidBookingTime | ErrorCode::InvalidTimeRange => (
+            "Die gewählte Zeit ist ungültig.",
+            "The chosen time is not valid.",
+        ),
+        ErrorCode::DurationTooShort => ("Die Buchung ist zu kurz.", "The booking is too short."),
+        ErrorCode::DurationTooLong => ("Die Buchung ist zu lang.", "The booking is too long."),
+        ErrorCode::LeadTimeTooShort => (
+            "Auf diesem Parkplatz muss früher im Voraus gebucht werden.",
+            "This lot must be booked further in advance.",
+        ),
+        ErrorCode::SameDayOnly => (
+            "Auf diesem Parkplatz kann nur für heute gebucht werden.",
+            "This lot only accepts bookings for today.",
+        ),
+        ErrorCode::OutsideOperatingHours => (
+            "Der Parkplatz ist zu dieser Zeit geschlossen.",
+            "The lot is closed at this time.",
+        ),
+        ErrorCode::MaxBookingsReached => (
+            "Sie haben die maximale Anzahl an Buchungen erreicht.",
+            "You have reached the maximum number of bookings.",
+        ),
+        ErrorCode::DepartmentQuotaReached => (
+            "Das Kontingent Ihrer Abteilung ist ausgeschöpft.",
+            "Your department's quota is used up.",
+        ),
+        ErrorCode::InsufficientCredits => (
+            "Ihr Guthaben reicht nicht aus.",
+            "You do not have enough credits.",
+        ),
+        ErrorCode::BookingNotModifiable => (
+            "Diese Buchung kann nicht mehr geändert werden.",
+            "This booking can no longer be changed.",
+        ),
+        ErrorCode::CancellationCutoff => (
+            "So kurz vor Beginn kann die Buchung nicht mehr storniert werden.",
+            "The booking can no longer be cancelled this close to its start.",
+        ),
+        ErrorCode::AlreadyCancelled => (
+            "Die Buchung ist bereits storniert.",
+            "The booking is already cancelled.",
+        ),
+        ErrorCode::AlreadyCheckedIn => (
+            "Sie sind bereits eingecheckt.",
+            "You are already checked in.",
+        ),
+        ErrorCode::CheckinTooEarly => (
+            "Für diese Buchung ist das Einchecken noch nicht möglich.",
+            "It is too early to check in for this booking.",
+        ),
+        ErrorCode::PermitRequired => (
+            "Für diesen Parkplatz brauchen Sie eine Parkberechtigung.",
+            "This lot requires a parking permit.",
+        ),
+        ErrorCode::VehicleRequired | ErrorCode::LicensePlateRequired => (
+            "Bitte geben Sie ein Kennzeichen an.",
+            "Please enter a license plate.",
+        ),
+        ErrorCode::LotArchived => ("Dieser Parkplatz ist archiviert.", "This lot is archived."),
+        ErrorCode::ChargerUnavailable => (
+            "Die Ladesäule ist nicht verfügbar.",
+            "The charger is not available.",
+        ),
+        ErrorCode::ModuleDisabled => (
+            "Diese Funktion ist auf dem Server deaktiviert.",
+            "This feature is disabled on the server.",
+        ),
+        ErrorCode::NotFound => (
+            "Der Eintrag wurde nicht gefunden.",
+            "The item was not found.",
+        ),
+        ErrorCode::AlreadyExists | ErrorCode::Duplicate => {
+            ("Der Eintrag existiert bereits.", "The item already exists.")
+        }
+        ErrorCode::PayloadTooLarge => ("Die Datei ist zu groß.", "The file is too large."),
+        ErrorCode::Invalid
+        | ErrorCode::InvalidInput
+        | ErrorCode::Validation
+        | ErrorCode::ValidationError
+        | ErrorCode::ValidationFailed => (
+            "Bitte überprüfen Sie Ihre Eingaben.",
+            "Please check your input.",
+        ),
+        ErrorCode::ServerError
+        | ErrorCode::InternalError
+        | ErrorCode::DatabaseError
+        | ErrorCode::DbError => (
+            "Auf dem Server ist ein Fehler aufgetreten. Bitte versuchen Sie es später erneut.",
+            "The server ran into an error. Please try again later.",
+        ),
+        _ => return None,
+    })
+}
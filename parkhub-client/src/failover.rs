@@ -0,0 +1,190 @@
+//! Failover to a standby server
+//!
+//! Servers advertise their organization and whether they are a standby in
+//! their mDNS record. Servers of one organization serve the same data, so
+//! while the primary is unreachable a standby can answer reads in its place.
+//! Standbys refuse changes; the client shows that writes are unavailable for
+//! as long as it is connected to one.
+//!
+//! While connected to a primary, the client probes its health. After
+//! [`FAILURES_BEFORE_FAILOVER`] failed probes in a row it moves the session
+//! to the first healthy standby of the organization, and back to the primary
+//! once that answers again.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use parkhub_common::ServerInfo;
+use slint::SharedString;
+use tokio::sync::RwLock;
+use tracing::{info, warn};
+
+use crate::server_connection::ServerConnection;
+use crate::{AppState, MainWindow};
+
+/// Time between health probes of the primary
+const PROBE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How long a health probe may take
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Failed probes in a row after which the primary counts as down
+const FAILURES_BEFORE_FAILOVER: u32 = 2;
+
+/// Whether `server` answers its health check
+pub async fn is_healthy(server: &ServerInfo) -> bool {
+    // Servers use self-signed certificates by default; this only checks
+    // that the server is up
+    let Ok(client) = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .timeout(PROBE_TIMEOUT)
+        .build()
+    else {
+        return false;
+    };
+    let scheme = if server.tls { "https" } else { "http" };
+    client
+        .get(format!("{scheme}://{}:{}/health", server.host, server.port))
+        .send()
+        .await
+        .is_ok_and(|resp| resp.status().is_success())
+}
+
+/// The standbys among `servers` that can stand in for `primary`
+pub fn standbys_for<'a>(
+    primary: &'a ServerInfo,
+    servers: &'a [ServerInfo],
+) -> impl Iterator<Item = &'a ServerInfo> {
+    servers.iter().filter(move |server| {
+        server.standby
+            && primary.organization.is_some()
+            && server.organization == primary.organization
+            && (server.host != primary.host || server.port != primary.port)
+    })
+}
+
+/// Connect to `primary`, or to a healthy standby of its organization when
+/// the primary cannot be reached
+pub async fn connect(
+    primary: &ServerInfo,
+    servers: &[ServerInfo],
+) -> anyhow::Result<ServerConnection> {
+    let error = match ServerConnection::connect(primary.clone()).await {
+        Ok(conn) => return Ok(conn),
+        Err(e) => e,
+    };
+    for standby in standbys_for(primary, servers) {
+        if !is_healthy(standby).await {
+            continue;
+        }
+        match ServerConnection::connect(standby.clone()).await {
+            Ok(conn) => {
+                warn!(
+                    "Primary server {} unreachable ({}), connected to standby {}",
+                    primary.name, error, standby.name
+                );
+                return Ok(conn);
+            }
+            Err(e) => warn!("Standby {} unreachable: {}", standby.name, e),
+        }
+    }
+    Err(error)
+}
+
+/// Probe the primary of the current connection for as long as the client
+/// runs, switching to a standby and back as it goes down and comes back
+pub async fn watch(state: Arc<RwLock<AppState>>, ui_weak: slint::Weak<MainWindow>) {
+    let mut interval = tokio::time::interval(PROBE_INTERVAL);
+    let mut failures = 0;
+    loop {
+        interval.tick().await;
+        let (primary, on_standby, servers) = {
+            let state = state.read().await;
+            match (&state.server, &state.primary_server) {
+                (Some(server), Some(primary)) => (
+                    primary.clone(),
+                    server.is_standby(),
+                    state.discovered_servers.clone(),
+                ),
+                _ => {
+                    failures = 0;
+                    continue;
+                }
+            }
+        };
+
+        let primary_up = is_healthy(&primary).await;
+        if on_standby {
+            if primary_up && switch_to(&state, &ui_weak, &primary).await {
+                info!("Primary server {} is back, switched to it", primary.name);
+            }
+            continue;
+        }
+
+        failures = if primary_up { 0 } else { failures + 1 };
+        if failures < FAILURES_BEFORE_FAILOVER {
+            continue;
+        }
+        for standby in standbys_for(&primary, &servers) {
+            if is_healthy(standby).await && switch_to(&state, &ui_weak, standby).await {
+                warn!(
+                    "Primary server {} unreachable, switched to standby {}",
+                    primary.name, standby.name
+                );
+                failures = 0;
+                break;
+            }
+        }
+    }
+}
+
+/// Move the session to `target`. Returns whether that worked.
+async fn switch_to(
+    state: &Arc<RwLock<AppState>>,
+    ui_weak: &slint::Weak<MainWindow>,
+    target: &ServerInfo,
+) -> bool {
+    let mut conn = match ServerConnection::connect(target.clone()).await {
+        Ok(conn) => conn,
+        Err(e) => {
+            warn!("Cannot switch to {}: {}", target.name, e);
+            return false;
+        }
+    };
+    {
+        let mut state = state.write().await;
+        // The user may have disconnected in the meantime
+        let Some(previous) = state.server.take() else {
+            return false;
+        };
+        conn.take_over(previous);
+        state.server = Some(conn);
+    }
+    show_connection(state, ui_weak).await;
+    true
+}
+
+/// Show the current connection's server and whether it is a standby
+pub async fn show_connection(state: &Arc<RwLock<AppState>>, ui_weak: &slint::Weak<MainWindow>) {
+    let (base_url, mode, standby) = {
+        let state = state.read().await;
+        let Some(server) = state.server.as_ref() else {
+            return;
+        };
+        let standby = if server.is_standby() {
+            server.server_info().name.clone()
+        } else {
+            String::new()
+        };
+        (
+            server.base_url().to_string(),
+            crate::compatibility_mode_name(server.compatibility()),
+            standby,
+        )
+    };
+    let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+        ui.set_server_url(SharedString::from(base_url));
+        ui.set_compatibility_mode(SharedString::from(mode));
+        ui.set_standby_server(SharedString::from(standby));
+    });
+}
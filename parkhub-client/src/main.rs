@@ -8,62 +8,57 @@
 
 use anyhow::{Context, Result};
 use rand::distr::{Alphanumeric, SampleString};
-use serde::{Deserialize, Serialize};
 use slint::{ModelRc, SharedString, VecModel};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
+mod accessibility;
+mod countdown;
+mod diagnostics;
 mod discovery;
+mod error_text;
+mod failover;
+mod paged_model;
 #[allow(dead_code)]
 mod server_connection;
+mod waitlist;
 
 slint::include_modules!();
 
-/// Accessibility settings stored locally
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct AccessibilitySettings {
-    /// Theme mode: 0=Dark, 1=Light, 2=High Contrast, 3=Deuteranopia, 4=Protanopia, 5=Tritanopia
-    #[serde(default)]
-    theme_mode: i32,
-    /// Font scale: 1.0=Normal, 1.25=Large, 1.5=Extra Large
-    #[serde(default = "default_font_scale")]
-    font_scale: f32,
-    /// Reduce motion animations
-    #[serde(default)]
-    reduce_motion: bool,
-}
-
-const fn default_font_scale() -> f32 {
-    1.0
-}
-
-impl Default for AccessibilitySettings {
-    fn default() -> Self {
-        Self {
-            theme_mode: 0,
-            font_scale: 1.0,
-            reduce_motion: false,
-        }
-    }
-}
-
 /// Application state
 struct AppState {
     /// Connected server (if any)
     server: Option<server_connection::ServerConnection>,
     /// Discovered servers on the network
     discovered_servers: Vec<parkhub_common::ServerInfo>,
+    /// Primary server the user chose; differs from the connected server
+    /// while failed over to a standby
+    primary_server: Option<parkhub_common::ServerInfo>,
     /// Whether we're currently scanning
     is_scanning: bool,
-    /// Cached full user list for search filtering
+    /// Likely reason the last scan found no servers
+    discovery_issue: Option<discovery::DiscoveryIssue>,
+    /// Users loaded into the admin user list so far
     admin_users_cache: Vec<parkhub_common::User>,
+    /// Floor picked in the parking view of a multi-floor lot
+    selected_floor: Option<String>,
+}
+
+/// Value of the UI `compatibility-mode` property for a negotiated mode
+const fn compatibility_mode_name(mode: parkhub_common::CompatibilityMode) -> &'static str {
+    match mode {
+        parkhub_common::CompatibilityMode::Full => "full",
+        parkhub_common::CompatibilityMode::Compatible => "compatible",
+        parkhub_common::CompatibilityMode::ReadOnly => "read_only",
+    }
 }
 
 fn role_label(role: &parkhub_common::UserRole) -> &'static str {
     match role {
         parkhub_common::UserRole::User => "User",
         parkhub_common::UserRole::Premium => "Premium",
+        parkhub_common::UserRole::Reporter => "Reporter",
         parkhub_common::UserRole::Admin => "Admin",
         parkhub_common::UserRole::SuperAdmin => "SuperAdmin",
     }
@@ -92,19 +87,291 @@ fn build_admin_user_info(user: &parkhub_common::User) -> AdminUserInfo {
     }
 }
 
-fn render_admin_users(ui: &MainWindow, users: &[parkhub_common::User]) {
-    let user_data: Vec<AdminUserInfo> = users.iter().map(build_admin_user_info).collect();
-    ui.set_admin_users(ModelRc::new(VecModel::from(user_data)));
+fn build_admin_booking_info(booking: &server_connection::AdminBooking) -> AdminBookingInfo {
+    AdminBookingInfo {
+        id: SharedString::from(&booking.id),
+        user_name: SharedString::from(&booking.user_name),
+        lot_name: SharedString::from(&booking.lot_name),
+        slot_number: SharedString::from(&booking.slot_number),
+        vehicle_plate: SharedString::from(&booking.vehicle_plate),
+        period: SharedString::from(format!(
+            "{} – {}",
+            booking.start_time.format("%d.%m.%Y %H:%M"),
+            booking.end_time.format("%H:%M")
+        )),
+        status: SharedString::from(&booking.status),
+    }
+}
+
+/// Show the admin user list from its first page on, matching the current
+/// search. Further pages load as the list is scrolled.
+fn load_admin_users(ui: &MainWindow, state: &Arc<RwLock<AppState>>) {
+    let search = ui.get_admin_user_search().to_string();
+    let state = state.clone();
+    let ui_weak = ui.as_weak();
+    let model = paged_model::PagedModel::new(move |id, cursor| {
+        tokio::spawn(fetch_admin_users(
+            state.clone(),
+            ui_weak.clone(),
+            id,
+            cursor,
+            search.clone(),
+        ));
+    });
+    ui.set_admin_users_total(0);
+    ui.set_admin_users(ModelRc::new(model));
+}
+
+/// Load one page of the admin user list with model `id`
+async fn fetch_admin_users(
+    state: Arc<RwLock<AppState>>,
+    ui_weak: slint::Weak<MainWindow>,
+    id: u64,
+    cursor: Option<String>,
+    search: String,
+) {
+    let result = {
+        let state = state.read().await;
+        let Some(ref server) = state.server else {
+            return;
+        };
+        server
+            .list_users_page(cursor.as_deref(), &search, paged_model::PAGE_SIZE)
+            .await
+    };
+
+    match result {
+        Ok(page) => {
+            {
+                let mut state = state.write().await;
+                if cursor.is_none() {
+                    state.admin_users_cache.clear();
+                }
+                state.admin_users_cache.extend(page.items.iter().cloned());
+            }
+            let rows: Vec<AdminUserInfo> = page.items.iter().map(build_admin_user_info).collect();
+            let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                let users = ui.get_admin_users();
+                if let Some(model) = paged_model::PagedModel::find(&users, id) {
+                    model.deliver(
+                        rows,
+                        usize::try_from(page.total).unwrap_or_default(),
+                        page.next_cursor,
+                    );
+                    ui.set_admin_users_total(page.total);
+                }
+            });
+        }
+        Err(e) => {
+            warn!("Failed to load users: {}", e);
+            let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                let users = ui.get_admin_users();
+                if let Some(model) = paged_model::PagedModel::find(&users, id) {
+                    model.failed();
+                }
+            });
+            show_admin_error(
+                &state,
+                ui_weak,
+                "Benutzerliste konnte nicht geladen werden",
+                &e,
+            );
+        }
+    }
+}
+
+/// Show all bookings, newest first, from the first page on. Further pages
+/// load as the list is scrolled.
+fn load_admin_bookings(ui: &MainWindow, state: &Arc<RwLock<AppState>>) {
+    let state = state.clone();
+    let ui_weak = ui.as_weak();
+    let model = paged_model::PagedModel::new(move |id, cursor| {
+        tokio::spawn(fetch_admin_bookings(
+            state.clone(),
+            ui_weak.clone(),
+            id,
+            cursor,
+        ));
+    });
+    ui.set_admin_bookings_total(0);
+    ui.set_admin_bookings(ModelRc::new(model));
+}
+
+/// Load one page of the admin bookings list with model `id`
+async fn fetch_admin_bookings(
+    state: Arc<RwLock<AppState>>,
+    ui_weak: slint::Weak<MainWindow>,
+    id: u64,
+    cursor: Option<String>,
+) {
+    let result = {
+        let state = state.read().await;
+        let Some(ref server) = state.server else {
+            return;
+        };
+        server
+            .list_admin_bookings_page(cursor.as_deref(), paged_model::PAGE_SIZE)
+            .await
+    };
+
+    match result {
+        Ok(page) => {
+            let rows: Vec<AdminBookingInfo> =
+                page.items.iter().map(build_admin_booking_info).collect();
+            let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                let bookings = ui.get_admin_bookings();
+                if let Some(model) = paged_model::PagedModel::find(&bookings, id) {
+                    model.deliver(
+                        rows,
+                        usize::try_from(page.total).unwrap_or_default(),
+                        page.next_cursor,
+                    );
+                    ui.set_admin_bookings_total(page.total);
+                }
+            });
+        }
+        Err(e) => {
+            warn!("Failed to load bookings: {}", e);
+            let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                let bookings = ui.get_admin_bookings();
+                if let Some(model) = paged_model::PagedModel::find(&bookings, id) {
+                    model.failed();
+                }
+            });
+            show_admin_error(
+                &state,
+                ui_weak,
+                "Buchungen konnten nicht geladen werden",
+                &e,
+            );
+        }
+    }
+}
+
+fn build_activity_item(entry: &parkhub_common::ActivityEntry) -> ActivityItem {
+    use parkhub_common::ActivityKind;
+
+    let (kind, title) = match entry.kind {
+        ActivityKind::BookingCreated => ("booking_created", "Buchung erstellt"),
+        ActivityKind::BookingCheckedIn => ("booking_checked_in", "Eingecheckt"),
+        ActivityKind::BookingCheckedOut => ("booking_checked_out", "Ausgecheckt"),
+        ActivityKind::BookingCancelled => ("booking_cancelled", "Buchung storniert"),
+        ActivityKind::BookingNoShow => ("booking_no_show", "Nicht erschienen"),
+        ActivityKind::Payment => ("payment", "Guthabenbuchung"),
+        ActivityKind::Notification => ("notification", "Benachrichtigung"),
+    };
+    let title = match entry.kind {
+        ActivityKind::Payment | ActivityKind::Notification => {
+            entry.title.as_deref().unwrap_or(title)
+        }
+        _ => title,
+    };
+    let detail = match (&entry.booking, &entry.message) {
+        (_, Some(message)) => message.clone(),
+        (Some(booking), None) => {
+            let slot = if booking.slot_number > 0 {
+                format!(" • Platz {}", booking.slot_number)
+            } else {
+                String::new()
+            };
+            format!(
+                "{}{slot} • {} – {}",
+                booking.lot_name,
+                booking.start_time.format("%d.%m.%Y %H:%M"),
+                booking.end_time.format("%H:%M")
+            )
+        }
+        (None, None) => String::new(),
+    };
+    ActivityItem {
+        id: SharedString::from(&entry.id),
+        kind: SharedString::from(kind),
+        title: SharedString::from(title),
+        detail: SharedString::from(detail),
+        time: SharedString::from(entry.at.format("%d.%m.%Y %H:%M").to_string()),
+        credits: SharedString::from(
+            entry
+                .credits
+                .map_or_else(String::new, |credits| format!("{credits:+}")),
+        ),
+    }
+}
+
+/// Show the user's activity feed from its first page on. Further pages load
+/// as the list is scrolled.
+fn load_activity(ui: &MainWindow, state: &Arc<RwLock<AppState>>) {
+    let state = state.clone();
+    let ui_weak = ui.as_weak();
+    let model = paged_model::PagedModel::new(move |id, cursor| {
+        tokio::spawn(fetch_activity(state.clone(), ui_weak.clone(), id, cursor));
+    });
+    ui.set_activity_total(0);
+    ui.set_activity_entries(ModelRc::new(model));
+}
+
+/// Load one page of the activity feed with model `id`
+async fn fetch_activity(
+    state: Arc<RwLock<AppState>>,
+    ui_weak: slint::Weak<MainWindow>,
+    id: u64,
+    cursor: Option<String>,
+) {
+    let result = {
+        let state = state.read().await;
+        let Some(ref server) = state.server else {
+            return;
+        };
+        server
+            .list_activity_page(cursor.as_deref(), paged_model::PAGE_SIZE)
+            .await
+    };
+
+    match result {
+        Ok(page) => {
+            let rows: Vec<ActivityItem> = page.items.iter().map(build_activity_item).collect();
+            let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                let entries = ui.get_activity_entries();
+                if let Some(model) = paged_model::PagedModel::find(&entries, id) {
+                    model.deliver(
+                        rows,
+                        usize::try_from(page.total).unwrap_or_default(),
+                        page.next_cursor,
+                    );
+                    ui.set_activity_total(page.total);
+                }
+            });
+        }
+        Err(e) => {
+            warn!("Failed to load activity: {}", e);
+            let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+                let entries = ui.get_activity_entries();
+                if let Some(model) = paged_model::PagedModel::find(&entries, id) {
+                    model.failed();
+                }
+            });
+            show_error_dialog(
+                ui_weak,
+                "Aktivität konnte nicht geladen werden",
+                e.to_string(),
+            );
+        }
+    }
+}
+
+/// Reload the admin user list after a change, from the UI thread
+fn reload_admin_users(ui_weak: &slint::Weak<MainWindow>) {
+    let _ = ui_weak.upgrade_in_event_loop(|ui| ui.invoke_admin_load_users());
 }
 
 fn normalize_admin_role(role: &str) -> Result<&'static str> {
     match role.trim().to_ascii_lowercase().as_str() {
         "user" => Ok("user"),
         "premium" => Ok("premium"),
+        "reporter" => Ok("reporter"),
         "admin" => Ok("admin"),
         "superadmin" | "super_admin" => Ok("superadmin"),
         other => Err(anyhow::anyhow!(
-            "Unsupported role '{other}'. Use user, premium, admin, or superadmin."
+            "Unsupported role '{other}'. Use user, premium, reporter, admin, or superadmin."
         )),
     }
 }
@@ -143,6 +410,74 @@ fn show_error_dialog(
     });
 }
 
+/// Show which admin functions the user may reach, and leave the admin view
+/// if they may no longer use it.
+fn apply_permissions(ui: &MainWindow, permissions: &parkhub_common::ResolvedPermissions) {
+    use parkhub_common::Permission;
+
+    let can_manage_users = permissions.has_global(Permission::ManageUsers);
+    let can_manage_config = permissions.has_global(Permission::ManageConfig);
+    // Booking for visitors is not an admin function
+    let can_open_admin = Permission::ALL
+        .iter()
+        .filter(|permission| **permission != Permission::BookForVisitors)
+        .any(|permission| permissions.has_any(*permission));
+    ui.set_can_manage_users(can_manage_users);
+    ui.set_can_manage_config(can_manage_config);
+    ui.set_can_open_admin(can_open_admin);
+    if !can_open_admin && ui.get_current_view() == AppView::Admin {
+        ui.set_current_view(AppView::Parking);
+    }
+}
+
+/// Fetch the user's permissions and update the UI to match. Servers
+/// without the permissions endpoint get those of the user's role.
+async fn refresh_permissions(state: Arc<RwLock<AppState>>, ui_weak: slint::Weak<MainWindow>) {
+    let permissions = {
+        let state = state.read().await;
+        let Some(ref server) = state.server else {
+            return;
+        };
+        match server.my_permissions().await {
+            Ok(permissions) => permissions,
+            Err(e) => {
+                warn!("Failed to load permissions, using the role's: {}", e);
+                server
+                    .get_current_user()
+                    .await
+                    .map(|user| parkhub_common::ResolvedPermissions::for_role(&user.role))
+                    .unwrap_or_default()
+            }
+        }
+    };
+    let _ = slint::invoke_from_event_loop(move || {
+        if let Some(ui) = ui_weak.upgrade() {
+            apply_permissions(&ui, &permissions);
+        }
+    });
+}
+
+/// Report a failed admin request. A refusal means the user's permissions
+/// changed during the session, so they are fetched again and the UI is
+/// narrowed down accordingly.
+fn show_admin_error(
+    state: &Arc<RwLock<AppState>>,
+    ui_weak: slint::Weak<MainWindow>,
+    title: &str,
+    error: &anyhow::Error,
+) {
+    if error.is::<server_connection::PermissionDenied>() {
+        tokio::spawn(refresh_permissions(state.clone(), ui_weak.clone()));
+        show_error_dialog(
+            ui_weak,
+            "Keine Berechtigung",
+            "Ihre Berechtigungen wurden geändert. Die Ansicht wurde angepasst.",
+        );
+    } else {
+        show_error_dialog(ui_weak, title, error.to_string());
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     // Set DPI awareness before creating any windows (Windows-specific)
@@ -161,7 +496,19 @@ async fn main() -> Result<()> {
     unsafe { std::env::set_var("SLINT_BACKEND", "winit-skia") };
 
     // Initialize logging
-    tracing_subscriber::fmt().with_env_filter("info").init();
+    // Log to the console and keep recent lines for the diagnostics bundle
+    {
+        use tracing_subscriber::prelude::*;
+        tracing_subscriber::registry()
+            .with(tracing_subscriber::EnvFilter::new("info"))
+            .with(tracing_subscriber::fmt::layer())
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .with_ansi(false)
+                    .with_writer(diagnostics::LogBuffer),
+            )
+            .init();
+    }
 
     info!("Starting ParkHub Client v{}", env!("CARGO_PKG_VERSION"));
 
@@ -169,13 +516,26 @@ async fn main() -> Result<()> {
     let state = Arc::new(RwLock::new(AppState {
         server: None,
         discovered_servers: vec![],
+        primary_server: None,
         is_scanning: false,
+        discovery_issue: None,
         admin_users_cache: vec![],
+        selected_floor: None,
     }));
 
     // Create UI
     let ui = MainWindow::new().context("Failed to create main window")?;
 
+    // Server errors are shown in the UI language
+    error_text::set_locale(&ui.global::<Tr>().get_locale());
+    let ui_weak = ui.as_weak();
+    ui.on_change_language(move |locale| {
+        error_text::set_locale(&locale);
+        if let Some(ui) = ui_weak.upgrade() {
+            ui.global::<Tr>().set_locale(locale);
+        }
+    });
+
     // Set up periodic UI update timer to sync discovered servers
     let ui_weak = ui.as_weak();
     let state_for_timer = state.clone();
@@ -197,15 +557,49 @@ async fn main() -> Result<()> {
                             port: i32::from(s.port),
                             tls: s.tls,
                             version: SharedString::from(&s.version),
+                            standby: s.standby,
                         })
                         .collect();
                     ui.set_discovered_servers(ModelRc::new(VecModel::from(servers)));
                     ui.set_is_scanning_servers(state.is_scanning);
+                    ui.set_discovery_hint(SharedString::from(
+                        state
+                            .discovery_issue
+                            .as_ref()
+                            .map(discovery::DiscoveryIssue::hint)
+                            .unwrap_or_default(),
+                    ));
                 }
             }
         },
     );
 
+    // Tick booking countdowns once a second and warn before bookings expire
+    let ui_weak_countdown = ui.as_weak();
+    let countdown_timer = slint::Timer::default();
+    countdown_timer.start(
+        slint::TimerMode::Repeated,
+        std::time::Duration::from_secs(1),
+        move || {
+            if let Some(ui) = ui_weak_countdown.upgrade() {
+                apply_countdowns(&ui);
+            }
+        },
+    );
+
+    let ui_weak_toast = ui.as_weak();
+    ui.on_dismiss_toast(move |toast_id| {
+        use slint::Model;
+        if let Some(ui) = ui_weak_toast.upgrade() {
+            let remaining: Vec<ToastData> = ui
+                .get_toasts()
+                .iter()
+                .filter(|t| t.id != toast_id)
+                .collect();
+            ui.set_toasts(ModelRc::new(VecModel::from(remaining)));
+        }
+    });
+
     // Start server discovery in background
     let discovery_state = state.clone();
     tokio::spawn(async move {
@@ -222,6 +616,12 @@ async fn main() -> Result<()> {
         }
     });
 
+    // Watch the primary server and fail over to a standby while it is down
+    tokio::spawn(failover::watch(state.clone(), ui.as_weak()));
+
+    // Keep the waitlist positions current while signed in
+    tokio::spawn(waitlist::watch(state.clone(), ui.as_weak()));
+
     // Set up window control callbacks
 
     // Minimize window
@@ -298,6 +698,27 @@ async fn main() -> Result<()> {
         });
     });
 
+    // Scan the local subnet when multicast discovery finds nothing
+    let state_for_subnet = state.clone();
+    ui.on_scan_subnet(move || {
+        info!("Scanning local subnet for servers...");
+        let state = state_for_subnet.clone();
+        tokio::spawn(async move {
+            {
+                let mut state = state.write().await;
+                if state.is_scanning {
+                    return;
+                }
+                state.is_scanning = true;
+            }
+            match discovery::scan_subnet(state.clone()).await {
+                Ok(found) => info!("Subnet scan found {} server(s)", found),
+                Err(e) => warn!("Subnet scan failed: {}", e),
+            }
+            state.write().await.is_scanning = false;
+        });
+    });
+
     // Set up connect to discovered server callback
     let ui_weak2 = ui.as_weak();
     let state_for_connect = state.clone();
@@ -314,28 +735,33 @@ async fn main() -> Result<()> {
 
             tokio::spawn(async move {
                 // Find the server info
-                let server_info = {
+                let (server_info, servers) = {
                     let state = state.read().await;
-                    state
-                        .discovered_servers
-                        .iter()
-                        .find(|s| s.name == server_id)
-                        .cloned()
+                    (
+                        state
+                            .discovered_servers
+                            .iter()
+                            .find(|s| s.name == server_id)
+                            .cloned(),
+                        state.discovered_servers.clone(),
+                    )
                 };
 
                 if let Some(info) = server_info {
-                    match server_connection::ServerConnection::connect(info.clone()).await {
+                    // An unreachable primary falls back to a standby of its
+                    // organization
+                    match failover::connect(&info, &servers).await {
                         Ok(conn) => {
-                            let base_url = conn.base_url().to_string();
                             {
                                 let mut state = state.write().await;
                                 state.server = Some(conn);
+                                state.primary_server = (!info.standby).then_some(info);
                             }
+                            failover::show_connection(&state, &ui_weak).await;
                             let _ = slint::invoke_from_event_loop(move || {
                                 if let Some(ui) = ui_weak.upgrade() {
                                     ui.set_is_connecting_to_server(false);
                                     ui.set_is_connected(true);
-                                    ui.set_server_url(SharedString::from(base_url));
                                     ui.set_current_view(AppView::Login);
                                 }
                             });
@@ -386,20 +812,24 @@ async fn main() -> Result<()> {
                     port: u16::try_from(port).unwrap_or(8443),
                     tls,
                     fingerprint: None,
+                    organization: None,
+                    standby: false,
                 };
 
                 match server_connection::ServerConnection::connect(server_info).await {
                     Ok(conn) => {
-                        let base_url = conn.base_url().to_string();
                         {
+                            // Manually entered servers carry no organization
+                            // to fail over within
                             let mut state = state.write().await;
                             state.server = Some(conn);
+                            state.primary_server = None;
                         }
+                        failover::show_connection(&state, &ui_weak).await;
                         let _ = slint::invoke_from_event_loop(move || {
                             if let Some(ui) = ui_weak.upgrade() {
                                 ui.set_is_connecting_to_server(false);
                                 ui.set_is_connected(true);
-                                ui.set_server_url(SharedString::from(base_url));
                                 ui.set_current_view(AppView::Login);
                             }
                         });
@@ -428,10 +858,24 @@ async fn main() -> Result<()> {
             let state = state_for_disconnect.clone();
             tokio::spawn(async move {
                 let mut state = state.write().await;
-                state.server = None;
+                state.primary_server = None;
+                state.selected_floor = None;
+                // End the session server-side instead of letting it live
+                // until expiry; the local state is cleared either way.
+                if let Some(mut server) = state.server.take()
+                    && let Err(e) = server.logout(false).await
+                {
+                    warn!("Server logout failed: {}", e);
+                }
             });
             ui.set_is_connected(false);
             ui.set_is_authenticated(false);
+            ui.set_login_must_change_password(false);
+            ui.set_compatibility_mode(SharedString::from("full"));
+            ui.set_standby_server(SharedString::from(""));
+            ui.set_current_user_avatar(slint::Image::default());
+            ui.set_waitlist_entries(ModelRc::default());
+            apply_permissions(&ui, &parkhub_common::ResolvedPermissions::default());
             ui.set_current_view(AppView::Connect);
         }
     });
@@ -468,24 +912,12 @@ async fn main() -> Result<()> {
                         let _ = slint::invoke_from_event_loop(move || {
                             if let Some(ui) = ui_weak.upgrade() {
                                 ui.set_login_loading(false);
-                                ui.set_is_authenticated(true);
-                                ui.set_current_user(CurrentUser {
-                                    id: SharedString::from(user.id.to_string()),
-                                    email: SharedString::from(&user.email),
-                                    name: SharedString::from(&user.name),
-                                    initial: SharedString::from(
-                                        user.name.chars().next().unwrap_or('?').to_string(),
-                                    ),
-                                    picture: SharedString::from(""),
-                                    role: SharedString::from(format!("{:?}", user.role)),
-                                });
-                                ui.set_current_view(AppView::Parking);
-
-                                // Load parking data
-                                let ui_weak_load = ui.as_weak();
-                                tokio::spawn(async move {
-                                    load_parking_data(state_for_load, ui_weak_load).await;
-                                });
+                                if user.must_change_password {
+                                    // The server refuses everything else until then
+                                    ui.set_login_must_change_password(true);
+                                } else {
+                                    show_signed_in(&ui, state_for_load, user);
+                                }
                             }
                         });
                     }
@@ -546,24 +978,7 @@ async fn main() -> Result<()> {
                         let _ = slint::invoke_from_event_loop(move || {
                             if let Some(ui) = ui_weak.upgrade() {
                                 ui.set_login_loading(false);
-                                ui.set_is_authenticated(true);
-                                ui.set_current_user(CurrentUser {
-                                    id: SharedString::from(user.id.to_string()),
-                                    email: SharedString::from(&user.email),
-                                    name: SharedString::from(&user.name),
-                                    initial: SharedString::from(
-                                        user.name.chars().next().unwrap_or('?').to_string(),
-                                    ),
-                                    picture: SharedString::from(""),
-                                    role: SharedString::from(format!("{:?}", user.role)),
-                                });
-                                ui.set_current_view(AppView::Parking);
-
-                                // Load parking data
-                                let ui_weak_load = ui.as_weak();
-                                tokio::spawn(async move {
-                                    load_parking_data(state_for_load, ui_weak_load).await;
-                                });
+                                show_signed_in(&ui, state_for_load, user);
                             }
                         });
                     }
@@ -590,6 +1005,64 @@ async fn main() -> Result<()> {
         }
     });
 
+    // Set up the forced password change shown after a seeded-password login
+    let ui_weak_pw = ui.as_weak();
+    let state_for_pw = state.clone();
+    ui.on_change_initial_password(move |current, new| {
+        let current = current.to_string();
+        let new = new.to_string();
+
+        if let Some(ui) = ui_weak_pw.upgrade() {
+            let username = ui.get_login_username().to_string();
+            ui.set_login_loading(true);
+            ui.set_login_error(SharedString::from(""));
+
+            let state = state_for_pw.clone();
+            let ui_weak = ui.as_weak();
+
+            tokio::spawn(async move {
+                // Changing the password ends the session, so sign in again
+                let result = {
+                    let mut state = state.write().await;
+                    match state.server {
+                        Some(ref mut server) => {
+                            Some(match server.change_password(&current, &new).await {
+                                Ok(()) => server.login(&username, &new).await,
+                                Err(e) => Err(e),
+                            })
+                        }
+                        None => None,
+                    }
+                };
+
+                let state_for_load = state.clone();
+                let _ = slint::invoke_from_event_loop(move || {
+                    let Some(ui) = ui_weak.upgrade() else {
+                        return;
+                    };
+                    ui.set_login_loading(false);
+                    match result {
+                        Some(Ok(user)) => {
+                            info!("Password changed for user: {}", user.username);
+                            ui.set_login_must_change_password(false);
+                            ui.set_login_password(SharedString::from(""));
+                            ui.set_login_new_password(SharedString::from(""));
+                            ui.set_login_new_password_confirm(SharedString::from(""));
+                            show_signed_in(&ui, state_for_load, user);
+                        }
+                        Some(Err(e)) => {
+                            warn!("Password change failed: {}", e);
+                            ui.set_login_error(SharedString::from(format!("{e}")));
+                        }
+                        None => {
+                            ui.set_login_error(SharedString::from("Not connected to server"));
+                        }
+                    }
+                });
+            });
+        }
+    });
+
     // Set up toggle register callback
     let ui_weak7 = ui.as_weak();
     ui.on_toggle_register(move || {
@@ -609,98 +1082,336 @@ async fn main() -> Result<()> {
             let state = state_for_logout.clone();
             tokio::spawn(async move {
                 let mut state = state.write().await;
-                state.server = None;
+                // Revoke the session server-side before dropping the
+                // connection, so the tokens stop working right away
+                if let Some(mut server) = state.server.take()
+                    && let Err(e) = server.logout(false).await
+                {
+                    warn!("Server logout failed: {}", e);
+                }
             });
             ui.set_is_authenticated(false);
+            ui.set_login_must_change_password(false);
             ui.set_is_connected(false);
+            ui.set_current_user_avatar(slint::Image::default());
+            ui.set_waitlist_entries(ModelRc::default());
+            apply_permissions(&ui, &parkhub_common::ResolvedPermissions::default());
             ui.set_current_view(AppView::Connect);
         }
     });
 
+    // Refresh parking data: cached lot data shows instantly, fresh data follows
+    let ui_weak_refresh = ui.as_weak();
+    let state_for_refresh = state.clone();
+    ui.on_refresh_parking(move || {
+        let state = state_for_refresh.clone();
+        let ui_weak = ui_weak_refresh.clone();
+        tokio::spawn(async move {
+            load_parking_data(state, ui_weak).await;
+        });
+    });
+
+    // Switch floors in a multi-floor lot
+    let ui_weak_floor = ui.as_weak();
+    let state_for_floor = state.clone();
+    ui.on_select_floor(move |floor_id| {
+        if let Some(ui) = ui_weak_floor.upgrade() {
+            ui.set_selected_floor_id(floor_id.clone());
+            ui.set_selected_slot_number(-1);
+            ui.set_show_booking_panel(false);
+            tokio::spawn(show_floor(
+                state_for_floor.clone(),
+                ui.as_weak(),
+                ui.get_lot_id().to_string(),
+                floor_id.to_string(),
+            ));
+        }
+    });
+
     // =========================================================================
-    // Admin User Management Callbacks
+    // Booking Price Quote
     // =========================================================================
 
-    // Load users callback
-    let ui_weak_admin1 = ui.as_weak();
-    let state_for_admin_users = state.clone();
-    ui.on_admin_load_users(move || {
-        info!("Loading admin users list");
-        let state = state_for_admin_users.clone();
-        let ui_weak = ui_weak_admin1.clone();
+    // Re-quote whenever the selected slot, the start or the duration changes
+    let ui_weak_quote_slot = ui.as_weak();
+    let state_for_quote_slot = state.clone();
+    ui.on_slot_tapped(move |slot_number| {
+        if let Some(ui) = ui_weak_quote_slot.upgrade() {
+            let duration = ui.get_selected_duration();
+            request_price_quote(&ui, state_for_quote_slot.clone(), slot_number, duration);
+        }
+    });
 
-        tokio::spawn(async move {
-            let users_result = {
-                let state = state.read().await;
-                if let Some(ref server) = state.server {
-                    Some(server.list_users().await)
-                } else {
-                    None
-                }
-            };
+    let ui_weak_quote_duration = ui.as_weak();
+    let state_for_quote_duration = state.clone();
+    ui.on_booking_duration_changed(move |minutes| {
+        if let Some(ui) = ui_weak_quote_duration.upgrade() {
+            let slot_number = ui.get_selected_slot_number();
+            request_price_quote(&ui, state_for_quote_duration.clone(), slot_number, minutes);
+            if ui.get_selected_start_offset() > 0 {
+                request_window_availability(&ui, state_for_quote_duration.clone());
+            }
+        }
+    });
 
-            if let Some(result) = users_result {
-                match result {
-                    Ok(users) => {
-                        // Save to cache for search filtering
-                        {
-                            let mut state = state.write().await;
-                            state.admin_users_cache.clone_from(&users);
-                        }
+    // A later start shows which slots are free for that window
+    let ui_weak_start = ui.as_weak();
+    let state_for_start = state.clone();
+    ui.on_booking_start_changed(move |offset| {
+        if let Some(ui) = ui_weak_start.upgrade() {
+            let slot_number = ui.get_selected_slot_number();
+            let duration = ui.get_selected_duration();
+            request_price_quote(&ui, state_for_start.clone(), slot_number, duration);
+            if offset > 0 {
+                request_window_availability(&ui, state_for_start.clone());
+            } else {
+                ui.invoke_refresh_parking();
+            }
+        }
+    });
 
-                        if let Some(ui) = ui_weak.upgrade() {
-                            render_admin_users(&ui, &users);
-                        }
-                    }
-                    Err(e) => {
-                        warn!("Failed to load users: {}", e);
+    // Extend a booking by an hour from the booking list; the server refuses
+    // when the slot is booked right after it
+    let ui_weak_extend = ui.as_weak();
+    let state_for_extend = state.clone();
+    ui.on_extend_booking(move |booking_id| {
+        let booking_id = booking_id.to_string();
+        let state = state_for_extend.clone();
+        let ui_weak = ui_weak_extend.clone();
+        tokio::spawn(async move {
+            let state = state.read().await;
+            let Some(ref server) = state.server else {
+                return;
+            };
+            let result = match server.list_bookings().await {
+                Ok(bookings) => match bookings
+                    .iter()
+                    .find(|listed| listed.booking.id.to_string() == booking_id)
+                {
+                    Some(listed) => {
+                        let end_time = listed.booking.end_time + chrono::TimeDelta::hours(1);
+                        server.extend_booking(&booking_id, end_time).await
                     }
+                    None => Err(anyhow::anyhow!("Booking not found")),
+                },
+                Err(e) => Err(e),
+            };
+            match result {
+                Ok(booking) => {
+                    info!("Booking {} extended until {}", booking.id, booking.end_time);
+                    load_bookings(server, &ui_weak).await;
+                }
+                Err(e) => {
+                    warn!("Failed to extend booking: {}", e);
+                    show_error_dialog(ui_weak, "Verlängern fehlgeschlagen", e.to_string());
                 }
             }
         });
     });
 
-    // Edit user callback
-    let ui_weak_admin2 = ui.as_weak();
-    let state_for_edit = state.clone();
-    ui.on_admin_edit_user(move |user_id| {
-        let user_id = user_id.to_string();
-        info!("Edit user: {}", user_id);
-        let state = state_for_edit.clone();
-        let ui_weak = ui_weak_admin2.clone();
-
+    // Export diagnostics callback: recent logs, connection details and API
+    // errors as a ZIP for bug reports
+    let ui_weak_diagnostics = ui.as_weak();
+    let state_for_diagnostics = state.clone();
+    ui.on_export_diagnostics(move || {
+        let state = state_for_diagnostics.clone();
+        let ui_weak = ui_weak_diagnostics.clone();
         tokio::spawn(async move {
-            let user = {
-                let state = state.read().await;
-                state
-                    .admin_users_cache
-                    .iter()
-                    .find(|u| u.id.to_string() == user_id)
-                    .cloned()
-            };
-
-            match user {
-                Some(user) => {
-                    let _ = slint::invoke_from_event_loop(move || {
-                        if let Some(ui) = ui_weak.upgrade() {
-                            ui.set_admin_user_edit_mode(true);
-                            ui.set_admin_user_form_id(SharedString::from(user.id.to_string()));
-                            ui.set_admin_user_form_title(SharedString::from("Benutzer bearbeiten"));
-                            ui.set_admin_user_form_username(SharedString::from(user.username));
-                            ui.set_admin_user_form_name(SharedString::from(user.name));
-                            ui.set_admin_user_form_email(SharedString::from(user.email));
-                            ui.set_admin_user_form_role(SharedString::from(
-                                normalize_admin_role(role_label(&user.role)).unwrap_or("user"),
-                            ));
-                            ui.set_show_admin_user_dialog(true);
-                        }
-                    });
+            let connection = state
+                .read()
+                .await
+                .server
+                .as_ref()
+                .map(server_connection::ServerConnection::summary);
+            let path = diagnostics::default_bundle_path();
+            match diagnostics::write_bundle(&path, connection.as_ref()) {
+                Ok(()) => {
+                    info!("Diagnostics written to {}", path.display());
+                    show_success_dialog(
+                        ui_weak,
+                        "Diagnose exportiert",
+                        format!(
+                            "Die Datei {} kann einem Fehlerbericht beigefügt werden.",
+                            path.display()
+                        ),
+                    );
+                }
+                Err(e) => {
+                    warn!("Failed to export diagnostics: {:#}", e);
+                    show_error_dialog(ui_weak, "Export fehlgeschlagen", format!("{e:#}"));
                 }
-                None => show_error_dialog(
-                    ui_weak,
-                    "Benutzer nicht gefunden",
-                    "Der ausgewählte Benutzer ist nicht mehr im lokalen Admin-Cache vorhanden.",
-                ),
+            }
+        });
+    });
+
+    // Dismiss announcement callback: hide the banner right away, then tell
+    // the server so it stays hidden on the next load
+    let ui_weak_dismiss = ui.as_weak();
+    let state_for_dismiss = state.clone();
+    ui.on_dismiss_announcement(move |announcement_id| {
+        use slint::Model;
+
+        if let Some(ui) = ui_weak_dismiss.upgrade() {
+            let remaining: Vec<AnnouncementData> = ui
+                .get_announcements()
+                .iter()
+                .filter(|a| a.id != announcement_id)
+                .collect();
+            ui.set_announcements(ModelRc::new(VecModel::from(remaining)));
+        }
+
+        let announcement_id = announcement_id.to_string();
+        let state = state_for_dismiss.clone();
+        tokio::spawn(async move {
+            let state = state.read().await;
+            if let Some(ref server) = state.server
+                && let Err(e) = server.dismiss_announcement(&announcement_id).await
+            {
+                warn!("Failed to dismiss announcement: {}", e);
+            }
+        });
+    });
+
+    // Join the waitlist of the shown lot, which is fully booked
+    let ui_weak_join_waitlist = ui.as_weak();
+    let state_for_join_waitlist = state.clone();
+    ui.on_join_waitlist(move || {
+        let Some(ui) = ui_weak_join_waitlist.upgrade() else {
+            return;
+        };
+        let lot_id = ui.get_lot_id().to_string();
+        let state = state_for_join_waitlist.clone();
+        let ui_weak = ui_weak_join_waitlist.clone();
+        tokio::spawn(async move {
+            let state = state.read().await;
+            let Some(ref server) = state.server else {
+                return;
+            };
+            match server.join_waitlist(&lot_id).await {
+                Ok(()) => {
+                    info!("Joined waitlist of lot {}", lot_id);
+                    waitlist::refresh(server, &ui_weak).await;
+                }
+                Err(e) => {
+                    warn!("Failed to join waitlist: {}", e);
+                    show_error_dialog(ui_weak, "Warteliste fehlgeschlagen", e.to_string());
+                }
+            }
+        });
+    });
+
+    // Leave a waitlist
+    let ui_weak_leave_waitlist = ui.as_weak();
+    let state_for_leave_waitlist = state.clone();
+    ui.on_leave_waitlist(move |entry_id| {
+        let entry_id = entry_id.to_string();
+        let state = state_for_leave_waitlist.clone();
+        let ui_weak = ui_weak_leave_waitlist.clone();
+        tokio::spawn(async move {
+            let state = state.read().await;
+            let Some(ref server) = state.server else {
+                return;
+            };
+            match server.leave_waitlist(&entry_id).await {
+                Ok(()) => waitlist::refresh(server, &ui_weak).await,
+                Err(e) => {
+                    warn!("Failed to leave waitlist: {}", e);
+                    show_error_dialog(ui_weak, "Warteliste fehlgeschlagen", e.to_string());
+                }
+            }
+        });
+    });
+
+    // Open the admin dashboard; the header only offers it to users with
+    // admin permissions. Permissions are checked again on the way in, as
+    // they may have changed since login.
+    let ui_weak_admin0 = ui.as_weak();
+    let state_for_admin = state.clone();
+    ui.on_open_admin(move || {
+        if let Some(ui) = ui_weak_admin0.upgrade() {
+            ui.set_current_view(AppView::Admin);
+        }
+        tokio::spawn(refresh_permissions(
+            state_for_admin.clone(),
+            ui_weak_admin0.clone(),
+        ));
+    });
+
+    // Open the activity feed, loading it afresh
+    let ui_weak_activity = ui.as_weak();
+    let state_for_activity = state.clone();
+    ui.on_open_activity(move || {
+        if let Some(ui) = ui_weak_activity.upgrade() {
+            ui.set_current_view(AppView::Activity);
+            load_activity(&ui, &state_for_activity);
+        }
+    });
+
+    // =========================================================================
+    // Admin User Management Callbacks
+    // =========================================================================
+
+    // Load users callback
+    let ui_weak_admin1 = ui.as_weak();
+    let state_for_admin_users = state.clone();
+    ui.on_admin_load_users(move || {
+        info!("Loading admin users list");
+        if let Some(ui) = ui_weak_admin1.upgrade() {
+            load_admin_users(&ui, &state_for_admin_users);
+        }
+    });
+
+    // Load bookings callback
+    let ui_weak_admin_bookings = ui.as_weak();
+    let state_for_admin_bookings = state.clone();
+    ui.on_admin_load_bookings(move || {
+        info!("Loading admin bookings list");
+        if let Some(ui) = ui_weak_admin_bookings.upgrade() {
+            load_admin_bookings(&ui, &state_for_admin_bookings);
+        }
+    });
+
+    // Edit user callback
+    let ui_weak_admin2 = ui.as_weak();
+    let state_for_edit = state.clone();
+    ui.on_admin_edit_user(move |user_id| {
+        let user_id = user_id.to_string();
+        info!("Edit user: {}", user_id);
+        let state = state_for_edit.clone();
+        let ui_weak = ui_weak_admin2.clone();
+
+        tokio::spawn(async move {
+            let user = {
+                let state = state.read().await;
+                state
+                    .admin_users_cache
+                    .iter()
+                    .find(|u| u.id.to_string() == user_id)
+                    .cloned()
+            };
+
+            match user {
+                Some(user) => {
+                    let _ = slint::invoke_from_event_loop(move || {
+                        if let Some(ui) = ui_weak.upgrade() {
+                            ui.set_admin_user_edit_mode(true);
+                            ui.set_admin_user_form_id(SharedString::from(user.id.to_string()));
+                            ui.set_admin_user_form_title(SharedString::from("Benutzer bearbeiten"));
+                            ui.set_admin_user_form_username(SharedString::from(user.username));
+                            ui.set_admin_user_form_name(SharedString::from(user.name));
+                            ui.set_admin_user_form_email(SharedString::from(user.email));
+                            ui.set_admin_user_form_role(SharedString::from(
+                                normalize_admin_role(role_label(&user.role)).unwrap_or("user"),
+                            ));
+                            ui.set_show_admin_user_dialog(true);
+                        }
+                    });
+                }
+                None => show_error_dialog(
+                    ui_weak,
+                    "Benutzer nicht gefunden",
+                    "Der ausgewählte Benutzer ist nicht mehr im lokalen Admin-Cache vorhanden.",
+                ),
             }
         });
     });
@@ -716,45 +1427,17 @@ async fn main() -> Result<()> {
         let ui_weak = ui_weak_admin3.clone();
 
         tokio::spawn(async move {
-            let refresh_result = {
-                let state = state.read().await;
-                if let Some(ref server) = state.server {
-                    match server.delete_user(&user_id).await {
-                        Ok(()) => {
-                            info!("User {} deleted successfully", user_id);
-                            Some(server.list_users().await)
-                        }
-                        Err(e) => {
-                            warn!("Failed to delete user: {}", e);
-                            show_error_dialog(
-                                ui_weak.clone(),
-                                "Löschen fehlgeschlagen",
-                                e.to_string(),
-                            );
-                            None
-                        }
+            let state_guard = state.read().await;
+            if let Some(ref server) = state_guard.server {
+                match server.delete_user(&user_id).await {
+                    Ok(()) => {
+                        info!("User {} deleted successfully", user_id);
+                        reload_admin_users(&ui_weak);
                     }
-                } else {
-                    None
-                }
-            };
-
-            if let Some(result) = refresh_result {
-                match result {
-                    Ok(users) => {
-                        {
-                            let mut state = state.write().await;
-                            state.admin_users_cache.clone_from(&users);
-                        }
-                        if let Some(ui) = ui_weak.upgrade() {
-                            render_admin_users(&ui, &users);
-                        }
+                    Err(e) => {
+                        warn!("Failed to delete user: {}", e);
+                        show_admin_error(&state, ui_weak, "Löschen fehlgeschlagen", &e);
                     }
-                    Err(e) => show_error_dialog(
-                        ui_weak,
-                        "Benutzerliste konnte nicht aktualisiert werden",
-                        e.to_string(),
-                    ),
                 }
             }
         });
@@ -772,8 +1455,8 @@ async fn main() -> Result<()> {
         let temporary_password = Alphanumeric.sample_string(&mut rand::rng(), 20);
 
         tokio::spawn(async move {
-            let state = state.read().await;
-            if let Some(ref server) = state.server {
+            let state_guard = state.read().await;
+            if let Some(ref server) = state_guard.server {
                 match server
                     .reset_user_password(&user_id, &temporary_password)
                     .await
@@ -791,11 +1474,7 @@ async fn main() -> Result<()> {
                     }
                     Err(e) => {
                         warn!("Failed to reset password: {}", e);
-                        show_error_dialog(
-                            ui_weak.clone(),
-                            "Password reset failed",
-                            e.to_string(),
-                        );
+                        show_admin_error(&state, ui_weak.clone(), "Password reset failed", &e);
                     }
                 }
             }
@@ -831,47 +1510,19 @@ async fn main() -> Result<()> {
                 return;
             };
 
-            let refresh_result = {
-                let state = state.read().await;
-                if let Some(ref server) = state.server {
-                    let new_active = !user.is_active;
-                    let updates = serde_json::json!({ "is_active": new_active });
-                    match server.update_user(&user_id, updates).await {
-                        Ok(_) => {
-                            info!("User {} active toggled to {}", user_id, new_active);
-                            Some(server.list_users().await)
-                        }
-                        Err(e) => {
-                            warn!("Failed to toggle user active: {}", e);
-                            show_error_dialog(
-                                ui_weak.clone(),
-                                "Statuswechsel fehlgeschlagen",
-                                e.to_string(),
-                            );
-                            None
-                        }
+            let state_guard = state.read().await;
+            if let Some(ref server) = state_guard.server {
+                let new_active = !user.is_active;
+                let updates = serde_json::json!({ "is_active": new_active });
+                match server.update_user(&user_id, updates).await {
+                    Ok(_) => {
+                        info!("User {} active toggled to {}", user_id, new_active);
+                        reload_admin_users(&ui_weak);
                     }
-                } else {
-                    None
-                }
-            };
-
-            if let Some(result) = refresh_result {
-                match result {
-                    Ok(users) => {
-                        {
-                            let mut state = state.write().await;
-                            state.admin_users_cache.clone_from(&users);
-                        }
-                        if let Some(ui) = ui_weak.upgrade() {
-                            render_admin_users(&ui, &users);
-                        }
+                    Err(e) => {
+                        warn!("Failed to toggle user active: {}", e);
+                        show_admin_error(&state, ui_weak, "Statuswechsel fehlgeschlagen", &e);
                     }
-                    Err(e) => show_error_dialog(
-                        ui_weak,
-                        "Benutzerliste konnte nicht aktualisiert werden",
-                        e.to_string(),
-                    ),
                 }
             }
         });
@@ -956,9 +1607,9 @@ async fn main() -> Result<()> {
         let ui_weak = ui_weak_admin9.clone();
         tokio::spawn(async move {
             let temporary_password = Alphanumeric.sample_string(&mut rand::rng(), 20);
-            let users_result = {
-                let state = state.read().await;
-                if let Some(ref server) = state.server {
+            let saved = {
+                let state_guard = state.read().await;
+                if let Some(ref server) = state_guard.server {
                     let result = if is_edit {
                         let updates = serde_json::json!({
                             "name": name,
@@ -973,18 +1624,19 @@ async fn main() -> Result<()> {
                     };
 
                     match result {
-                        Ok(()) => Some(server.list_users().await),
+                        Ok(()) => true,
                         Err(e) => {
-                            show_error_dialog(
+                            show_admin_error(
+                                &state,
                                 ui_weak.clone(),
                                 if is_edit {
                                     "Benutzer konnte nicht gespeichert werden"
                                 } else {
                                     "Benutzer konnte nicht angelegt werden"
                                 },
-                                e.to_string(),
+                                &e,
                             );
-                            None
+                            false
                         }
                     }
                 } else {
@@ -993,96 +1645,41 @@ async fn main() -> Result<()> {
                         "Keine Verbindung",
                         "Es ist aktuell kein Server verbunden.",
                     );
-                    None
+                    false
                 }
             };
 
-            if let Some(result) = users_result {
-                match result {
-                    Ok(users) => {
-                        {
-                            let mut state = state.write().await;
-                            state.admin_users_cache.clone_from(&users);
-                        }
-                        if let Some(ui) = ui_weak.upgrade() {
-                            render_admin_users(&ui, &users);
-                        }
+            if saved {
+                reload_admin_users(&ui_weak);
 
-                        if is_edit {
-                            show_success_dialog(
-                                ui_weak,
-                                "Benutzer gespeichert",
-                                format!("Die Änderungen für {} wurden übernommen.", name),
-                            );
-                        } else {
-                            show_success_dialog(
-                                ui_weak,
-                                "Benutzer angelegt",
-                                format!(
-                                    "Benutzer {} wurde angelegt.\n\nTemporäres Passwort:\n{}\n\nBitte sicher übermitteln und beim ersten Login rotieren.",
-                                    username, temporary_password
-                                ),
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        show_error_dialog(
-                            ui_weak,
-                            "Benutzerliste konnte nicht aktualisiert werden",
-                            e.to_string(),
-                        );
-                    }
+                if is_edit {
+                    show_success_dialog(
+                        ui_weak,
+                        "Benutzer gespeichert",
+                        format!("Die Änderungen für {} wurden übernommen.", name),
+                    );
+                } else {
+                    show_success_dialog(
+                        ui_weak,
+                        "Benutzer angelegt",
+                        format!(
+                            "Benutzer {} wurde angelegt.\n\nTemporäres Passwort:\n{}\n\nBitte sicher übermitteln und beim ersten Login rotieren.",
+                            username, temporary_password
+                        ),
+                    );
                 }
             }
         });
     });
 
-    // Search users callback
+    // Search users callback: the server filters, so the list starts over
     let ui_weak_admin7 = ui.as_weak();
-    let state_for_search = state.clone();
     ui.on_admin_search_users(move |query| {
-        let query = query.to_lowercase();
         info!("Search users: {}", query);
-        let state = state_for_search.clone();
-        let ui_weak = ui_weak_admin7.clone();
-
-        tokio::spawn(async move {
-            let state = state.read().await;
-            let users = &state.admin_users_cache;
-            let filtered: Vec<AdminUserInfo> = users
-                .iter()
-                .filter(|u| {
-                    query.is_empty()
-                        || u.username.to_lowercase().contains(&query)
-                        || u.email.to_lowercase().contains(&query)
-                        || u.name.to_lowercase().contains(&query)
-                })
-                .map(|u| AdminUserInfo {
-                    id: SharedString::from(u.id.to_string()),
-                    username: SharedString::from(&u.username),
-                    email: SharedString::from(&u.email),
-                    name: SharedString::from(&u.name),
-                    initial: SharedString::from(
-                        u.name
-                            .chars()
-                            .next()
-                            .or_else(|| u.username.chars().next())
-                            .map_or_else(|| "?".to_string(), |c| c.to_uppercase().to_string()),
-                    ),
-                    role: SharedString::from(format!("{:?}", u.role)),
-                    is_active: u.is_active,
-                    last_login: SharedString::from(u.last_login.map_or_else(
-                        || "-".to_string(),
-                        |dt| dt.format("%d.%m.%Y %H:%M").to_string(),
-                    )),
-                    created_at: SharedString::from(u.created_at.format("%d.%m.%Y").to_string()),
-                })
-                .collect();
-
-            if let Some(ui) = ui_weak.upgrade() {
-                ui.set_admin_users(ModelRc::new(VecModel::from(filtered)));
-            }
-        });
+        if let Some(ui) = ui_weak_admin7.upgrade() {
+            ui.set_admin_user_search(SharedString::from(query.trim()));
+            ui.invoke_admin_load_users();
+        }
     });
 
     // =========================================================================
@@ -1098,8 +1695,8 @@ async fn main() -> Result<()> {
         let ui_weak = ui_weak_config1.clone();
 
         tokio::spawn(async move {
-            let state = state.read().await;
-            if let Some(ref server) = state.server {
+            let state_guard = state.read().await;
+            if let Some(ref server) = state_guard.server {
                 match server.get_server_config().await {
                     Ok(config) => {
                         if let Some(ui) = ui_weak.upgrade() {
@@ -1148,6 +1745,12 @@ async fn main() -> Result<()> {
                     }
                     Err(e) => {
                         warn!("Failed to load server config: {}", e);
+                        show_admin_error(
+                            &state,
+                            ui_weak,
+                            "Einstellungen konnten nicht geladen werden",
+                            &e,
+                        );
                     }
                 }
             }
@@ -1156,11 +1759,11 @@ async fn main() -> Result<()> {
 
     // Save server config callback
     let ui_weak_config2 = ui.as_weak();
-    let state_for_save = state;
+    let state_for_save = state.clone();
     ui.on_admin_save_server_config(move |config| {
         info!("Saving server configuration");
         let state = state_for_save.clone();
-        let _ui_weak = ui_weak_config2.clone();
+        let ui_weak = ui_weak_config2.clone();
 
         let updates = serde_json::json!({
             "server_name": config.server_name.to_string(),
@@ -1179,201 +1782,680 @@ async fn main() -> Result<()> {
         });
 
         tokio::spawn(async move {
-            let state = state.read().await;
-            if let Some(ref server) = state.server {
+            let state_guard = state.read().await;
+            if let Some(ref server) = state_guard.server {
                 match server.update_server_config(updates).await {
                     Ok(()) => {
                         info!("Server config saved successfully");
                     }
                     Err(e) => {
                         warn!("Failed to save server config: {}", e);
+                        show_admin_error(
+                            &state,
+                            ui_weak,
+                            "Einstellungen konnten nicht gespeichert werden",
+                            &e,
+                        );
                     }
                 }
             }
         });
     });
 
-    // Load accessibility settings from local config
-    let config_dir = directories::ProjectDirs::from("com", "parkhub", "ParkHub Client")
-        .map_or_else(
-            || std::path::PathBuf::from(".").join("config"),
-            |p| p.config_dir().to_path_buf(),
-        );
-    let config_path = config_dir.join("accessibility.toml");
-
-    if config_path.exists()
-        && let Ok(content) = std::fs::read_to_string(&config_path)
-        && let Ok(settings) = toml::from_str::<AccessibilitySettings>(&content)
-    {
-        info!("Loaded accessibility settings from {:?}", config_path);
-        ui.global::<ThemeSettings>().set_mode(settings.theme_mode);
-        ui.global::<ThemeSettings>()
-            .set_font_scale(settings.font_scale);
-        ui.global::<ThemeSettings>()
-            .set_reduce_motion(settings.reduce_motion);
+    // Apply the accessibility settings saved on this machine until login
+    // brings the user's own
+    if let Some(settings) = accessibility::load_local() {
+        accessibility::apply(&ui, &settings);
     }
 
-    // Save accessibility settings when changed
+    // Save accessibility settings locally and to the user's preferences
     let ui_weak_a11y = ui.as_weak();
+    let state_a11y = state.clone();
     ui.on_setting_changed(move |key, value| {
-        let key = key.to_string();
-        let value = value.to_string();
-
+        // Only handle accessibility-related settings
+        if key != "theme_mode" && key != "font_scale" && key != "reduce_motion" {
+            return;
+        }
         if let Some(ui) = ui_weak_a11y.upgrade() {
-            // Only handle accessibility-related settings
-            if key == "theme_mode" || key == "font_scale" || key == "reduce_motion" {
-                let settings = AccessibilitySettings {
-                    theme_mode: ui.global::<ThemeSettings>().get_mode(),
-                    font_scale: ui.global::<ThemeSettings>().get_font_scale(),
-                    reduce_motion: ui.global::<ThemeSettings>().get_reduce_motion(),
-                };
+            let settings = accessibility::current(&ui);
+            accessibility::save_local(&settings);
+            info!("Saved accessibility settings: {} = {}", key, value);
+            if ui.get_is_authenticated() {
+                tokio::spawn(push_accessibility(state_a11y.clone(), settings));
+            }
+        }
+    });
 
-                // Save to file
-                let config_dir = directories::ProjectDirs::from("com", "parkhub", "ParkHub Client")
-                    .map_or_else(
-                        || std::path::PathBuf::from(".").join("config"),
-                        |p| p.config_dir().to_path_buf(),
-                    );
+    // Run UI event loop
+    ui.run().context("UI event loop error")?;
 
-                if let Err(e) = std::fs::create_dir_all(&config_dir) {
-                    warn!("Failed to create config dir: {}", e);
-                    return;
+    Ok(())
+}
+
+/// Start of a booking made now with the selected start offset. Immediate
+/// bookings start a few minutes ahead so they are not in the past on arrival.
+fn selected_start_time(ui: &MainWindow) -> chrono::DateTime<chrono::Utc> {
+    chrono::Utc::now() + chrono::Duration::minutes(i64::from(ui.get_selected_start_offset().max(5)))
+}
+
+/// Fetch the server-side price for booking `slot_number` for `minutes` and
+/// show it in the booking panel. The server prices exactly as it would on
+/// booking creation, so the client never computes cost itself.
+fn request_price_quote(
+    ui: &MainWindow,
+    state: Arc<RwLock<AppState>>,
+    slot_number: i32,
+    minutes: i32,
+) {
+    use slint::Model;
+
+    let Ok(lot_id) = ui.get_lot_id().parse::<uuid::Uuid>() else {
+        return;
+    };
+    let slot_id = ui
+        .get_slots()
+        .iter()
+        .find(|s| s.slot_number == slot_number)
+        .and_then(|s| s.id.parse::<uuid::Uuid>().ok());
+    if slot_id.is_none() {
+        return;
+    }
+
+    ui.set_estimated_cost(SharedString::from("…"));
+    ui.set_price_quote(PriceQuote::default());
+
+    let request = parkhub_common::BookingQuoteRequest {
+        lot_id,
+        slot_id,
+        start_time: selected_start_time(ui),
+        duration_minutes: minutes,
+    };
+    let start_offset = ui.get_selected_start_offset();
+    let ui_weak = ui.as_weak();
+    tokio::spawn(async move {
+        let result = {
+            let state = state.read().await;
+            match state.server {
+                Some(ref server) => server.quote_booking(&request).await,
+                None => return,
+            }
+        };
+
+        let _ = slint::invoke_from_event_loop(move || {
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+            // Ignore stale answers after the user picked another slot/start/duration
+            if ui.get_selected_slot_number() != slot_number
+                || ui.get_selected_duration() != minutes
+                || ui.get_selected_start_offset() != start_offset
+            {
+                return;
+            }
+            match result {
+                Ok(quote) => {
+                    let money = |v: f64| format!("{v:.2} {}", quote.currency);
+                    ui.set_estimated_cost(SharedString::from(money(quote.total)));
+                    ui.set_price_quote(PriceQuote {
+                        net: money(quote.net).into(),
+                        discount: if quote.discount > 0.0 {
+                            money(quote.discount).into()
+                        } else {
+                            SharedString::new()
+                        },
+                        tax: money(quote.tax).into(),
+                        vat_rate: format!("{}%", (quote.vat_rate * 100.0).round()).into(),
+                        daily_max_applied: quote.daily_max_applied,
+                    });
                 }
+                Err(e) => {
+                    warn!("Failed to fetch price quote: {}", e);
+                    ui.set_estimated_cost(SharedString::from("—"));
+                }
+            }
+        });
+    });
+}
 
-                let config_path = config_dir.join("accessibility.toml");
-                if let Ok(content) = toml::to_string_pretty(&settings) {
-                    if let Err(e) = std::fs::write(&config_path, content) {
-                        warn!("Failed to save accessibility settings: {}", e);
-                    } else {
-                        info!("Saved accessibility settings: {} = {}", key, value);
+/// Show which slots are free for the selected start and duration. The
+/// live slot status only covers right now, so the server checks the
+/// bookings overlapping the window instead.
+fn request_window_availability(ui: &MainWindow, state: Arc<RwLock<AppState>>) {
+    let lot_id = ui.get_lot_id().to_string();
+    if lot_id.is_empty() {
+        return;
+    }
+    let start_offset = ui.get_selected_start_offset();
+    let minutes = ui.get_selected_duration();
+    let start = selected_start_time(ui);
+    let end = start + chrono::Duration::minutes(i64::from(minutes));
+
+    let ui_weak = ui.as_weak();
+    tokio::spawn(async move {
+        let result = {
+            let state = state.read().await;
+            match state.server {
+                Some(ref server) => server.get_lot_availability(&lot_id, start, end).await,
+                None => return,
+            }
+        };
+        let availability = match result {
+            Ok(availability) => availability,
+            Err(e) => {
+                warn!("Failed to fetch lot availability: {}", e);
+                return;
+            }
+        };
+
+        let _ = slint::invoke_from_event_loop(move || {
+            use slint::Model;
+
+            let Some(ui) = ui_weak.upgrade() else {
+                return;
+            };
+            // Ignore stale answers after the user picked another start/duration
+            if ui.get_selected_start_offset() != start_offset
+                || ui.get_selected_duration() != minutes
+            {
+                return;
+            }
+            let slots: Vec<ParkingSlotData> = ui
+                .get_slots()
+                .iter()
+                .map(|mut slot| {
+                    if let Some(window) = availability
+                        .slots
+                        .iter()
+                        .find(|s| s.slot_id.to_string() == slot.id.as_str())
+                    {
+                        slot.status = if window.available {
+                            SlotStatus::Available
+                        } else if window.booked.is_empty() {
+                            SlotStatus::Disabled
+                        } else {
+                            SlotStatus::Occupied
+                        };
+                        slot.license_plate = SharedString::new();
+                        slot.booked_by = SharedString::new();
+                        slot.end_time = window
+                            .booked
+                            .last()
+                            .map(|period| period.end.format("%H:%M").to_string())
+                            .unwrap_or_default()
+                            .into();
                     }
-                }
+                    slot
+                })
+                .collect();
+            ui.set_slots(ModelRc::new(VecModel::from(slots)));
+            ui.set_available_slots(i32::try_from(availability.available_slots).unwrap_or(i32::MAX));
+        });
+    });
+}
+
+/// Load parking data from server
+/// Decode an avatar (PNG or JPEG) into a pixel buffer the UI thread can
+/// turn into an image.
+fn decode_avatar(bytes: &[u8]) -> Option<slint::SharedPixelBuffer<slint::Rgba8Pixel>> {
+    let rgba = image::load_from_memory(bytes).ok()?.into_rgba8();
+    Some(slint::SharedPixelBuffer::clone_from_slice(
+        rgba.as_raw(),
+        rgba.width(),
+        rgba.height(),
+    ))
+}
+
+/// Fetch the signed-in user's picture and show it in the header. Falls back
+/// to the initial-letter placeholder when there is none or it can't be loaded.
+async fn load_user_avatar(
+    state: Arc<RwLock<AppState>>,
+    ui_weak: slint::Weak<MainWindow>,
+    picture: Option<String>,
+) {
+    let Some(picture) = picture.filter(|p| !p.is_empty()) else {
+        return;
+    };
+    let bytes = {
+        let state = state.read().await;
+        let Some(ref server) = state.server else {
+            return;
+        };
+        match server.fetch_picture(&picture).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                warn!("Failed to load profile picture: {}", e);
+                return;
             }
         }
+    };
+    let Some(buffer) = decode_avatar(&bytes) else {
+        warn!("Profile picture could not be decoded");
+        return;
+    };
+    let _ = slint::invoke_from_event_loop(move || {
+        if let Some(ui) = ui_weak.upgrade() {
+            ui.set_current_user_avatar(slint::Image::from_rgba8(buffer));
+        }
     });
+}
 
-    // Run UI event loop
-    ui.run().context("UI event loop error")?;
+/// The floor of `lot` to show: `preferred` if the lot still has it, else
+/// its lowest floor. `None` for lots with at most one floor, which show all
+/// their slots.
+fn shown_floor(lot: &parkhub_common::ParkingLot, preferred: Option<&str>) -> Option<String> {
+    if lot.floors.len() < 2 {
+        return None;
+    }
+    lot.floors
+        .iter()
+        .find(|f| preferred == Some(f.id.to_string().as_str()))
+        .or_else(|| lot.floors.iter().min_by_key(|f| f.floor_number))
+        .map(|f| f.id.to_string())
+}
 
-    Ok(())
+/// The slots on `floor`, or all of them when no floor is picked.
+fn slots_on_floor(
+    mut slots: Vec<parkhub_common::ParkingSlot>,
+    floor: Option<&str>,
+) -> Vec<parkhub_common::ParkingSlot> {
+    if let Some(floor) = floor {
+        slots.retain(|s| s.floor_id.to_string() == floor);
+    }
+    slots
 }
 
-/// Load parking data from server
+/// Show a lot's name, occupancy and floors in the parking view, with
+/// `floor` selected in the floor switcher.
+fn render_lot(
+    ui_weak: &slint::Weak<MainWindow>,
+    lot: &parkhub_common::ParkingLot,
+    floor: Option<&str>,
+) {
+    let lot_name = lot.name.clone();
+    let total_slots = lot.total_slots;
+    let available_slots = lot.available_slots;
+    let lot_id = lot.id.to_string();
+    let mut floors: Vec<_> = lot.floors.iter().collect();
+    floors.sort_by_key(|f| f.floor_number);
+    let floors: Vec<FloorInfo> = floors
+        .into_iter()
+        .map(|f| FloorInfo {
+            id: SharedString::from(f.id.to_string()),
+            name: SharedString::from(&f.name),
+            floor_number: f.floor_number,
+            total_slots: f.total_slots,
+            available_slots: f.available_slots,
+            is_full: f.total_slots > 0 && f.available_slots == 0,
+        })
+        .collect();
+    let floor = SharedString::from(floor.unwrap_or_default());
+    let ui_weak_lot = ui_weak.clone();
+    let _ = slint::invoke_from_event_loop(move || {
+        if let Some(ui) = ui_weak_lot.upgrade() {
+            ui.set_lot_id(SharedString::from(lot_id));
+            ui.set_lot_name(SharedString::from(&lot_name));
+            ui.set_total_slots(total_slots);
+            ui.set_available_slots(available_slots);
+            ui.set_floors(ModelRc::new(VecModel::from(floors)));
+            ui.set_selected_floor_id(floor);
+        }
+    });
+}
+
+/// Show a lot's slots in the parking grid.
+fn render_slots(ui_weak: &slint::Weak<MainWindow>, mut slots: Vec<parkhub_common::ParkingSlot>) {
+    // Sort slots by slot_number to ensure proper display order
+    slots.sort_by_key(|s| s.slot_number);
+    let slot_data: Vec<ParkingSlotData> = slots
+        .iter()
+        .map(|s| {
+            let (license_plate, end_time, booked_by) = s
+                .current_booking
+                .as_ref()
+                .map(|b| {
+                    (
+                        b.license_plate.clone(),
+                        b.end_time.format("%H:%M").to_string(),
+                        if b.is_own_booking {
+                            "You".to_string()
+                        } else {
+                            "Other".to_string()
+                        },
+                    )
+                })
+                .unwrap_or_default();
+
+            info!(
+                "Slot {}: row={}, col={}, status={:?}",
+                s.slot_number, s.row, s.column, s.status
+            );
+            ParkingSlotData {
+                id: SharedString::from(s.id.to_string()),
+                slot_number: s.slot_number,
+                row: s.row,
+                col: s.column,
+                status: match s.status {
+                    parkhub_common::SlotStatus::Available => SlotStatus::Available,
+                    parkhub_common::SlotStatus::Occupied | parkhub_common::SlotStatus::Reserved => {
+                        SlotStatus::Occupied
+                    }
+                    parkhub_common::SlotStatus::Maintenance
+                    | parkhub_common::SlotStatus::Disabled => SlotStatus::Disabled,
+                },
+                license_plate: SharedString::from(license_plate),
+                end_time: SharedString::from(end_time),
+                booked_by: SharedString::from(booked_by),
+            }
+        })
+        .collect();
+    let ui_weak_slots = ui_weak.clone();
+    let _ = slint::invoke_from_event_loop(move || {
+        if let Some(ui) = ui_weak_slots.upgrade() {
+            info!("Setting {} slots in UI", slot_data.len());
+            ui.set_slots(ModelRc::new(VecModel::from(slot_data)));
+        }
+    });
+}
+
+/// Load everything the parking view shows. Lots, announcements and bookings
+/// are requested concurrently and each section renders as soon as its
+/// response arrives.
 async fn load_parking_data(state: Arc<RwLock<AppState>>, ui_weak: slint::Weak<MainWindow>) {
     let state = state.read().await;
     if let Some(ref server) = state.server {
-        // Load parking lots
-        match server.list_lots().await {
-            Ok(lots) => {
-                if let Some(lot) = lots.first() {
-                    let lot_name = lot.name.clone();
-                    let total_slots = lot.total_slots;
-                    let available_slots = lot.available_slots;
-                    let ui_weak_lot = ui_weak.clone();
-                    let _ = slint::invoke_from_event_loop(move || {
-                        if let Some(ui) = ui_weak_lot.upgrade() {
-                            ui.set_lot_name(SharedString::from(&lot_name));
-                            ui.set_total_slots(total_slots);
-                            ui.set_available_slots(available_slots);
-                        }
-                    });
+        tokio::join!(
+            // Waitlist entries name their lot and are matched against the
+            // shown one, so they follow the lot
+            async {
+                load_lot(server, &ui_weak, state.selected_floor.as_deref()).await;
+                waitlist::refresh(server, &ui_weak).await;
+            },
+            load_announcements(server, &ui_weak),
+            load_bookings(server, &ui_weak),
+        );
+    }
+}
 
-                    // Load slots for the first lot
-                    match server.get_lot_slots(&lot.id.to_string()).await {
-                        Ok(mut slots) => {
-                            // Sort slots by slot_number to ensure proper display order
-                            slots.sort_by_key(|s| s.slot_number);
-                            info!("Loaded {} slots from server", slots.len());
-                            let slot_data: Vec<ParkingSlotData> = slots
-                                .iter()
-                                .map(|s| {
-                                    let (license_plate, end_time, booked_by) = s
-                                        .current_booking
-                                        .as_ref()
-                                        .map(|b| {
-                                            (
-                                                b.license_plate.clone(),
-                                                b.end_time.format("%H:%M").to_string(),
-                                                if b.is_own_booking {
-                                                    "You".to_string()
-                                                } else {
-                                                    "Other".to_string()
-                                                },
-                                            )
-                                        })
-                                        .unwrap_or_default();
-
-                                    info!(
-                                        "Slot {}: row={}, col={}, status={:?}",
-                                        s.slot_number, s.row, s.column, s.status
-                                    );
-                                    ParkingSlotData {
-                                        id: SharedString::from(s.id.to_string()),
-                                        slot_number: s.slot_number,
-                                        row: s.row,
-                                        col: s.column,
-                                        status: match s.status {
-                                            parkhub_common::SlotStatus::Available => {
-                                                SlotStatus::Available
-                                            }
-                                            parkhub_common::SlotStatus::Occupied
-                                            | parkhub_common::SlotStatus::Reserved => {
-                                                SlotStatus::Occupied
-                                            }
-                                            parkhub_common::SlotStatus::Maintenance
-                                            | parkhub_common::SlotStatus::Disabled => {
-                                                SlotStatus::Disabled
-                                            }
-                                        },
-                                        license_plate: SharedString::from(license_plate),
-                                        end_time: SharedString::from(end_time),
-                                        booked_by: SharedString::from(booked_by),
-                                    }
-                                })
-                                .collect();
-                            let ui_weak_slots = ui_weak.clone();
-                            let _ = slint::invoke_from_event_loop(move || {
-                                if let Some(ui) = ui_weak_slots.upgrade() {
-                                    info!("Setting {} slots in UI", slot_data.len());
-                                    ui.set_slots(ModelRc::new(VecModel::from(slot_data)));
-                                }
-                            });
-                        }
-                        Err(e) => {
-                            warn!("Failed to load slots: {}", e);
-                        }
+/// Load the first parking lot, then its slots and booking rules in parallel.
+/// Multi-floor lots show the slots on `preferred_floor`, or on their lowest
+/// floor.
+async fn load_lot(
+    server: &server_connection::ServerConnection,
+    ui_weak: &slint::Weak<MainWindow>,
+    preferred_floor: Option<&str>,
+) {
+    // Serve the last lot and slot responses right away; the requests
+    // below revalidate them and replace what is shown
+    if let Some(lot) = server
+        .cached_lots()
+        .and_then(|lots| lots.into_iter().next())
+    {
+        let floor = shown_floor(&lot, preferred_floor);
+        render_lot(ui_weak, &lot, floor.as_deref());
+        if let Some(slots) = server.cached_lot_slots(&lot.id.to_string()) {
+            render_slots(ui_weak, slots_on_floor(slots, floor.as_deref()));
+        }
+    }
+
+    let lots = match server.list_lots().await {
+        Ok(lots) => lots,
+        Err(e) => {
+            warn!("Failed to load lots: {}", e);
+            return;
+        }
+    };
+    let Some(lot) = lots.first() else {
+        return;
+    };
+    let floor = shown_floor(lot, preferred_floor);
+    render_lot(ui_weak, lot, floor.as_deref());
+
+    let lot_id = lot.id.to_string();
+    let (slots, rules) = tokio::join!(server.get_lot_slots(&lot_id), server.get_lot_rules(&lot_id));
+
+    match slots {
+        Ok(slots) => {
+            info!("Loaded {} slots from server", slots.len());
+            render_slots(ui_weak, slots_on_floor(slots, floor.as_deref()));
+        }
+        Err(e) => {
+            warn!("Failed to load slots: {}", e);
+        }
+    }
+
+    // Lot booking rules: durations outside the limits are disabled
+    match rules {
+        Ok(rules) => {
+            let min = rules.min_duration_minutes.unwrap_or(0);
+            let max = rules.max_duration_minutes.unwrap_or(0);
+            let ui_weak_rules = ui_weak.clone();
+            let _ = slint::invoke_from_event_loop(move || {
+                if let Some(ui) = ui_weak_rules.upgrade() {
+                    ui.set_min_duration(min);
+                    ui.set_max_duration(max);
+                    let selected = ui.get_selected_duration();
+                    if selected < min {
+                        ui.set_selected_duration(min);
+                    } else if max > 0 && selected > max {
+                        ui.set_selected_duration(max);
                     }
                 }
-            }
+            });
+        }
+        Err(e) => {
+            warn!("Failed to load lot rules: {}", e);
+        }
+    }
+}
+
+/// Show the slots on floor `floor_id` of lot `lot_id`, falling back to the
+/// lot's cached slots when the floor cannot be loaded.
+async fn show_floor(
+    state: Arc<RwLock<AppState>>,
+    ui_weak: slint::Weak<MainWindow>,
+    lot_id: String,
+    floor_id: String,
+) {
+    state.write().await.selected_floor = Some(floor_id.clone());
+    {
+        let state = state.read().await;
+        let Some(ref server) = state.server else {
+            return;
+        };
+        let slots = match server.get_floor_slots(&lot_id, &floor_id).await {
+            Ok(slots) => slots,
             Err(e) => {
-                warn!("Failed to load lots: {}", e);
+                warn!("Failed to load floor slots: {}", e);
+                let Some(slots) = server.cached_lot_slots(&lot_id) else {
+                    return;
+                };
+                slots_on_floor(slots, Some(floor_id.as_str()))
             }
+        };
+        render_slots(&ui_weak, slots);
+    }
+
+    // Keep showing which slots are free for a later start
+    let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+        if ui.get_selected_start_offset() > 0 {
+            request_window_availability(&ui, state);
         }
+    });
+}
 
-        // Load user's bookings
-        match server.list_bookings().await {
-            Ok(bookings) => {
-                let booking_data: Vec<BookingData> = bookings
-                    .iter()
-                    .map(|b| BookingData {
+/// Load announcements for the banner.
+async fn load_announcements(
+    server: &server_connection::ServerConnection,
+    ui_weak: &slint::Weak<MainWindow>,
+) {
+    if !server.supports("announcements") {
+        return;
+    }
+    match server.list_announcements().await {
+        Ok(announcements) => {
+            let announcement_data: Vec<AnnouncementData> = announcements
+                .iter()
+                .map(|a| AnnouncementData {
+                    id: SharedString::from(a.id.to_string()),
+                    title: SharedString::from(&a.title),
+                    message: SharedString::from(&a.message),
+                    severity: SharedString::from(
+                        serde_json::to_value(&a.severity)
+                            .ok()
+                            .and_then(|v| v.as_str().map(str::to_string))
+                            .unwrap_or_default(),
+                    ),
+                })
+                .collect();
+            let ui_weak_announcements = ui_weak.clone();
+            let _ = slint::invoke_from_event_loop(move || {
+                if let Some(ui) = ui_weak_announcements.upgrade() {
+                    ui.set_announcements(ModelRc::new(VecModel::from(announcement_data)));
+                }
+            });
+        }
+        Err(e) => {
+            warn!("Failed to load announcements: {}", e);
+        }
+    }
+}
+
+/// Switch to the parking view for a freshly signed-in user and start
+/// loading their data.
+fn show_signed_in(ui: &MainWindow, state: Arc<RwLock<AppState>>, user: parkhub_common::User) {
+    ui.set_is_authenticated(true);
+    ui.set_current_user(CurrentUser {
+        id: SharedString::from(user.id.to_string()),
+        email: SharedString::from(&user.email),
+        name: SharedString::from(&user.name),
+        initial: SharedString::from(user.name.chars().next().unwrap_or('?').to_string()),
+        picture: SharedString::from(user.picture.clone().unwrap_or_default()),
+        role: SharedString::from(format!("{:?}", user.role)),
+    });
+    ui.set_current_view(AppView::Parking);
+    sync_accessibility(ui, state.clone(), user.preferences.accessibility);
+
+    // Load parking data and the avatar
+    let ui_weak = ui.as_weak();
+    tokio::spawn(async move {
+        tokio::join!(
+            load_user_avatar(state.clone(), ui_weak.clone(), user.picture),
+            refresh_permissions(state.clone(), ui_weak.clone()),
+            load_parking_data(state, ui_weak),
+        );
+    });
+}
+
+/// Apply the accessibility settings stored with the user's account, or
+/// upload the ones in use if the account has none yet.
+fn sync_accessibility(
+    ui: &MainWindow,
+    state: Arc<RwLock<AppState>>,
+    stored: Option<parkhub_common::AccessibilityPreferences>,
+) {
+    if let Some(settings) = stored {
+        accessibility::apply(ui, &settings);
+        accessibility::save_local(&settings);
+    } else {
+        tokio::spawn(push_accessibility(state, accessibility::current(ui)));
+    }
+}
+
+/// Store accessibility settings in the user's server-side preferences.
+async fn push_accessibility(
+    state: Arc<RwLock<AppState>>,
+    settings: parkhub_common::AccessibilityPreferences,
+) {
+    let state = state.read().await;
+    if let Some(ref server) = state.server
+        && let Err(e) = server.update_accessibility(settings).await
+    {
+        warn!("Failed to sync accessibility settings: {}", e);
+    }
+}
+
+/// Load the user's bookings and start their countdowns.
+async fn load_bookings(
+    server: &server_connection::ServerConnection,
+    ui_weak: &slint::Weak<MainWindow>,
+) {
+    let (bookings, server_time) = tokio::join!(server.list_bookings(), server.server_time());
+    let received = std::time::Instant::now();
+    match bookings {
+        Ok(bookings) => {
+            let warning_minutes = server_time
+                .map_or(countdown::DEFAULT_EXPIRY_WARNING_MINUTES, |t| {
+                    t.expiry_warning_minutes
+                });
+            countdown::track(&bookings, warning_minutes, received);
+            let booking_data: Vec<BookingData> = bookings
+                .iter()
+                .map(|listed| {
+                    let b = &listed.booking;
+                    BookingData {
                         id: SharedString::from(b.id.to_string()),
                         slot_number: b.slot_number,
                         start_time: SharedString::from(b.start_time.format("%H:%M").to_string()),
                         end_time: SharedString::from(b.end_time.format("%H:%M").to_string()),
                         license_plate: SharedString::from(&b.vehicle.license_plate),
                         status: SharedString::from(format!("{:?}", b.status)),
-                    })
-                    .collect();
-                let ui_weak_bookings = ui_weak.clone();
-                let _ = slint::invoke_from_event_loop(move || {
-                    if let Some(ui) = ui_weak_bookings.upgrade() {
-                        ui.set_my_bookings(ModelRc::new(VecModel::from(booking_data)));
+                        remaining: SharedString::new(),
+                        expiring_soon: false,
                     }
-                });
-            }
-            Err(e) => {
-                warn!("Failed to load bookings: {}", e);
-            }
+                })
+                .collect();
+            let ui_weak_bookings = ui_weak.clone();
+            let _ = slint::invoke_from_event_loop(move || {
+                if let Some(ui) = ui_weak_bookings.upgrade() {
+                    ui.set_my_bookings(ModelRc::new(VecModel::from(booking_data)));
+                    apply_countdowns(&ui);
+                }
+            });
+        }
+        Err(e) => {
+            warn!("Failed to load bookings: {}", e);
         }
     }
 }
+
+/// Refresh the remaining-time labels of the booking list and raise a warning
+/// toast for bookings that just entered the expiry warning window.
+fn apply_countdowns(ui: &MainWindow) {
+    use slint::Model;
+
+    let (views, newly_warned) = countdown::tick(std::time::Instant::now());
+    let bookings = ui.get_my_bookings();
+    for i in 0..bookings.row_count() {
+        let Some(mut row) = bookings.row_data(i) else {
+            continue;
+        };
+        let Some(view) = views.iter().find(|v| row.id == v.booking_id) else {
+            continue;
+        };
+        if row.remaining != view.remaining || row.expiring_soon != view.expiring_soon {
+            row.remaining = SharedString::from(view.remaining.as_str());
+            row.expiring_soon = view.expiring_soon;
+            bookings.set_row_data(i, row);
+        }
+    }
+
+    if newly_warned.is_empty() {
+        return;
+    }
+    let mut toasts: Vec<ToastData> = ui.get_toasts().iter().collect();
+    for booking_id in newly_warned {
+        let Some(row) = bookings.iter().find(|b| b.id == booking_id) else {
+            continue;
+        };
+        info!("Booking {} is about to expire", booking_id);
+        toasts.push(ToastData {
+            id: SharedString::from(format!("expiry-{booking_id}")),
+            toast_type: ToastType::Warning,
+            title: SharedString::from(format!("Slot {} expires soon", row.slot_number)),
+            message: row.remaining.clone(),
+            duration_ms: 0,
+            dismissible: true,
+        });
+    }
+    ui.set_toasts(ModelRc::new(VecModel::from(toasts)));
+}
@@ -0,0 +1,123 @@
+//! Lazily loaded list models
+//!
+//! The admin tables can hold thousands of rows. A [`PagedModel`] starts out
+//! with the first page of a paginated endpoint and asks for the next one when
+//! the list view shows its last loaded rows, so only what the user scrolls to
+//! is fetched and turned into UI rows.
+
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use slint::{Model, ModelNotify, ModelRc, ModelTracker};
+
+/// Rows per request
+pub const PAGE_SIZE: i32 = 50;
+
+/// How close to the last loaded row the next page is requested
+const PREFETCH_ROWS: usize = 10;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Starts loading the page after the cursor (`None` for the first page) for
+/// the model with the given id. Runs on the UI thread, so it only spawns the
+/// request; the result comes back through [`PagedModel::deliver`] or
+/// [`PagedModel::failed`].
+type Fetch = Box<dyn Fn(u64, Option<String>)>;
+
+/// Slint model over a cursor-paginated endpoint
+pub struct PagedModel<T> {
+    /// Tells responses for this model apart from those of a replaced one
+    id: u64,
+    rows: RefCell<Vec<T>>,
+    total: Cell<usize>,
+    /// Cursor of the next page; `None` once the last page is in
+    next_cursor: RefCell<Option<String>>,
+    loading: Cell<bool>,
+    fetch: Fetch,
+    notify: ModelNotify,
+}
+
+impl<T: Clone + 'static> PagedModel<T> {
+    /// An empty model that starts loading its first page right away
+    pub fn new(fetch: impl Fn(u64, Option<String>) + 'static) -> Self {
+        let model = Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            rows: RefCell::new(Vec::new()),
+            total: Cell::new(0),
+            next_cursor: RefCell::new(None),
+            loading: Cell::new(true),
+            fetch: Box::new(fetch),
+            notify: ModelNotify::default(),
+        };
+        (model.fetch)(model.id, None);
+        model
+    }
+
+    /// The model behind `model` if it is still the one with `id`
+    pub fn find(model: &ModelRc<T>, id: u64) -> Option<&Self> {
+        model
+            .as_any()
+            .downcast_ref::<Self>()
+            .filter(|paged| paged.id == id)
+    }
+
+    /// Rows on the server, loaded or not
+    pub const fn total(&self) -> usize {
+        self.total.get()
+    }
+
+    /// Append a loaded page
+    pub fn deliver(&self, items: Vec<T>, total: usize, next_cursor: Option<String>) {
+        let first = self.rows.borrow().len();
+        let count = items.len();
+        self.rows.borrow_mut().extend(items);
+        self.total.set(total);
+        *self.next_cursor.borrow_mut() = next_cursor;
+        self.loading.set(false);
+        if count > 0 {
+            self.notify.row_added(first, count);
+        }
+    }
+
+    /// Stop after a page failed to load; reloading the list starts over
+    pub fn failed(&self) {
+        self.next_cursor.borrow_mut().take();
+        self.loading.set(false);
+    }
+
+    /// Request the next page if `row` is close to the end of the loaded rows
+    fn load_more_near(&self, row: usize) {
+        if self.loading.get() || row + PREFETCH_ROWS < self.rows.borrow().len() {
+            return;
+        }
+        let Some(cursor) = self.next_cursor.borrow().clone() else {
+            return;
+        };
+        self.loading.set(true);
+        (self.fetch)(self.id, Some(cursor));
+    }
+}
+
+impl<T: Clone + 'static> Model for PagedModel<T> {
+    type Data = T;
+
+    fn row_count(&self) -> usize {
+        self.rows.borrow().len()
+    }
+
+    fn row_data(&self, row: usize) -> Option<T> {
+        let data = self.rows.borrow().get(row).cloned();
+        if data.is_some() {
+            self.load_more_near(row);
+        }
+        data
+    }
+
+    fn model_tracker(&self) -> &dyn ModelTracker {
+        &self.notify
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
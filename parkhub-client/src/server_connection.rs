@@ -2,22 +2,98 @@
 //!
 //! Handles HTTP API communication with the `ParkHub` server.
 
+use std::collections::HashMap;
+use std::sync::Mutex;
+
 use anyhow::{Context, Result};
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use parkhub_common::{
-    ApiResponse, AuthTokens, Booking, CreateBookingRequest, HandshakeRequest, HandshakeResponse,
-    LoginRequest, LoginResponse, PROTOCOL_VERSION, PaginatedResponse, ParkingLot, ParkingSlot,
-    RegisterRequest, ServerInfo, User, UserRole, models::UserPreferences,
+    AccessibilityPreferences, ActivityEntry, Announcement, ApiError, ApiResponse, AuthTokens,
+    Booking, BookingCountdown, BookingQuote, BookingQuoteRequest, CompatibilityMode,
+    CreateBookingRequest, ErrorCode, HandshakeRequest, HandshakeResponse, LoginRequest,
+    LoginResponse, LotAvailability, LotBookingRules, LotCalendar, PROTOCOL_VERSION,
+    PaginatedResponse, ParkingLot, ParkingSlot, RegisterRequest, ResolvedPermissions, ServerInfo,
+    ServerTime, SlotCalendar, User, UserRole, WaitlistEntry, WaitlistPosition,
+    models::UserPreferences,
 };
 
+use crate::{diagnostics, error_text};
+
 /// Connection to a `ParkHub` server
 pub struct ServerConnection {
     client: Client,
     base_url: String,
     server_info: ServerInfo,
     auth_tokens: Option<AuthTokens>,
+    lot_cache: Mutex<LotCache>,
+    /// Negotiated during the handshake
+    compatibility: CompatibilityMode,
+    /// Modules the server has enabled (empty: not advertised)
+    features: Vec<String>,
+    /// Server and protocol version reported in the handshake
+    server_version: String,
+    server_protocol: String,
+}
+
+/// Last lot and slot responses, served instantly while a fresh request runs
+/// (stale-while-revalidate).
+#[derive(Default)]
+struct LotCache {
+    lots: Option<Vec<ParkingLot>>,
+    slots: HashMap<String, Vec<ParkingSlot>>,
+}
+
+/// A booking from the bookings list. Servers that predate countdown data
+/// omit `countdown`.
+#[derive(Debug, Deserialize)]
+pub struct ListedBooking {
+    #[serde(flatten)]
+    pub booking: Booking,
+    #[serde(default)]
+    pub countdown: Option<BookingCountdown>,
+}
+
+/// The server refused a request for lack of permission (`FORBIDDEN`), e.g.
+/// because the user's role changed during the session.
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct PermissionDenied(pub String);
+
+/// The server rejected a request. Displays the client's own text for the
+/// error code in the UI language rather than the server's message.
+#[derive(Debug, thiserror::Error)]
+#[error("{}", error_text::message(.0))]
+pub struct ServerError(pub ApiError);
+
+/// Error for a failed request: [`PermissionDenied`] if the server refused
+/// it, a [`ServerError`] if it said why, otherwise just `what`.
+fn request_failed(what: &str, error: Option<ApiError>) -> anyhow::Error {
+    diagnostics::record_api_error(what, error.as_ref());
+    match error {
+        Some(error) if error.code == ErrorCode::Forbidden => {
+            PermissionDenied(error_text::message(&error)).into()
+        }
+        Some(error) => ServerError(error).into(),
+        None => anyhow::anyhow!("{what}"),
+    }
+}
+
+/// Connection details for the diagnostics bundle. Deliberately holds no
+/// tokens.
+#[derive(Debug, Serialize)]
+pub struct ConnectionSummary {
+    pub base_url: String,
+    pub server_name: String,
+    pub server_version: String,
+    pub protocol_version: String,
+    pub compatibility: CompatibilityMode,
+    pub features: Vec<String>,
+    /// Connected to a standby, e.g. while the primary is unreachable
+    pub standby: bool,
+    pub signed_in: bool,
+    pub session_expires_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -31,6 +107,19 @@ struct AdminUserRecord {
     created_at: chrono::DateTime<chrono::Utc>,
 }
 
+/// A booking from the admin bookings list
+#[derive(Debug, Deserialize)]
+pub struct AdminBooking {
+    pub id: String,
+    pub user_name: String,
+    pub lot_name: String,
+    pub slot_number: String,
+    pub vehicle_plate: String,
+    pub start_time: chrono::DateTime<chrono::Utc>,
+    pub end_time: chrono::DateTime<chrono::Utc>,
+    pub status: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct DataImportError {
     message: String,
@@ -46,6 +135,7 @@ struct DataImportResult {
 fn parse_admin_role(role: &str) -> UserRole {
     match role.to_ascii_lowercase().as_str() {
         "premium" => UserRole::Premium,
+        "reporter" => UserRole::Reporter,
         "admin" => UserRole::Admin,
         "superadmin" => UserRole::SuperAdmin,
         _ => UserRole::User,
@@ -76,6 +166,7 @@ impl From<AdminUserRecord> for User {
             cost_center: None,
             department: None,
             settings: None,
+            must_change_password: false,
         }
     }
 }
@@ -96,15 +187,20 @@ impl ServerConnection {
             .build()
             .context("Failed to create HTTP client")?;
 
-        let conn = Self {
+        let mut conn = Self {
             client,
             base_url,
             server_info,
             auth_tokens: None,
+            lot_cache: Mutex::default(),
+            compatibility: CompatibilityMode::Full,
+            features: Vec::new(),
+            server_version: String::new(),
+            server_protocol: String::new(),
         };
 
         // Perform handshake
-        conn.handshake().await?;
+        conn.apply_handshake(conn.handshake().await?);
 
         Ok(conn)
     }
@@ -123,17 +219,81 @@ impl ServerConnection {
             .build()
             .context("Failed to create HTTP client with custom cert")?;
 
-        let conn = Self {
+        let mut conn = Self {
             client,
             base_url,
             server_info,
             auth_tokens: None,
+            lot_cache: Mutex::default(),
+            compatibility: CompatibilityMode::Full,
+            features: Vec::new(),
+            server_version: String::new(),
+            server_protocol: String::new(),
         };
 
-        conn.handshake().await?;
+        conn.apply_handshake(conn.handshake().await?);
         Ok(conn)
     }
 
+    /// Remember what the handshake negotiated
+    fn apply_handshake(&mut self, handshake: HandshakeResponse) {
+        if handshake.compatibility != CompatibilityMode::Full {
+            tracing::warn!(
+                server_protocol = %handshake.protocol_version,
+                client_protocol = PROTOCOL_VERSION,
+                mode = ?handshake.compatibility,
+                "Server speaks a different protocol version"
+            );
+        }
+        self.compatibility = handshake.compatibility;
+        self.features = handshake.features;
+        self.server_version = handshake.server_version;
+        self.server_protocol = handshake.protocol_version;
+    }
+
+    /// How this client may use the server
+    pub const fn compatibility(&self) -> CompatibilityMode {
+        self.compatibility
+    }
+
+    /// Whether the server offers the module `feature`. Servers that don't
+    /// advertise their modules are assumed to offer everything.
+    pub fn supports(&self, feature: &str) -> bool {
+        self.features.is_empty() || self.features.iter().any(|f| f == feature)
+    }
+
+    /// Refuse changes when connected read-only
+    fn ensure_writable(&self) -> Result<()> {
+        if self.server_info.standby {
+            Err(anyhow::anyhow!(
+                "Connected to a standby server: changes are possible again once the primary \
+                 server is back"
+            ))
+        } else if self.compatibility.allows_writes() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(
+                "Connected read-only: the server runs an incompatible protocol version"
+            ))
+        }
+    }
+
+    /// The server this connection goes to
+    pub const fn server_info(&self) -> &ServerInfo {
+        &self.server_info
+    }
+
+    /// Whether the server is a standby, which serves reads only
+    pub const fn is_standby(&self) -> bool {
+        self.server_info.standby
+    }
+
+    /// Continue the session of `previous`, a connection to another server
+    /// of the same organization, on this one
+    pub fn take_over(&mut self, previous: Self) {
+        self.auth_tokens = previous.auth_tokens;
+    }
+
     /// Perform protocol handshake
     async fn handshake(&self) -> Result<HandshakeResponse> {
         let request = HandshakeRequest {
@@ -154,7 +314,7 @@ impl ServerConnection {
 
         response
             .data
-            .ok_or_else(|| anyhow::anyhow!("Handshake failed: {:?}", response.error))
+            .ok_or_else(|| request_failed("Handshake failed", response.error))
     }
 
     /// Login with username and password
@@ -175,17 +335,49 @@ impl ServerConnection {
             .await
             .context("Invalid login response")?;
 
-        let login_response = response.data.ok_or_else(|| {
-            let error_msg = response
-                .error
-                .map_or_else(|| "Login failed".to_string(), |e| e.message);
-            anyhow::anyhow!(error_msg)
-        })?;
+        let login_response = response
+            .data
+            .ok_or_else(|| request_failed("Login failed", response.error))?;
 
         self.auth_tokens = Some(login_response.tokens);
+        self.clear_lot_cache();
         Ok(login_response.user)
     }
 
+    /// Replace the signed-in user's password. The server ends every session
+    /// of the user afterwards, so the tokens are dropped and the caller has
+    /// to log in again with `new_password`.
+    pub async fn change_password(
+        &mut self,
+        current_password: &str,
+        new_password: &str,
+    ) -> Result<()> {
+        let Some(auth) = self.auth_header() else {
+            return Err(anyhow::anyhow!("Not logged in"));
+        };
+
+        let response: ApiResponse<()> = self
+            .client
+            .patch(format!("{}/api/v1/users/me/password", self.base_url))
+            .json(&serde_json::json!({
+                "current_password": current_password,
+                "new_password": new_password,
+            }))
+            .header("Authorization", auth)
+            .send()
+            .await
+            .context("Password change request failed")?
+            .json()
+            .await
+            .context("Invalid password change response")?;
+
+        if !response.success {
+            return Err(request_failed("Password change failed", response.error));
+        }
+        self.auth_tokens = None;
+        Ok(())
+    }
+
     /// Register a new user
     pub async fn register(
         &mut self,
@@ -212,22 +404,75 @@ impl ServerConnection {
             .await
             .context("Invalid registration response")?;
 
-        let login_response = response.data.ok_or_else(|| {
-            let error_msg = response
-                .error
-                .map_or_else(|| "Registration failed".to_string(), |e| e.message);
-            anyhow::anyhow!(error_msg)
-        })?;
+        let login_response = response
+            .data
+            .ok_or_else(|| request_failed("Registration failed", response.error))?;
 
         self.auth_tokens = Some(login_response.tokens);
+        self.clear_lot_cache();
         Ok(login_response.user)
     }
 
+    /// Log out: revoke the current session on the server (or every session
+    /// of this user when `all_sessions` is set) and forget the tokens.
+    pub async fn logout(&mut self, all_sessions: bool) -> Result<()> {
+        let Some(auth) = self.auth_header() else {
+            return Ok(());
+        };
+        self.auth_tokens = None;
+        self.clear_lot_cache();
+
+        let response: ApiResponse<()> = self
+            .client
+            .post(format!("{}/api/v1/auth/logout", self.base_url))
+            .query(&[("all", all_sessions)])
+            .header("Authorization", auth)
+            .send()
+            .await
+            .context("Logout request failed")?
+            .json()
+            .await
+            .context("Invalid logout response")?;
+
+        if response.success {
+            Ok(())
+        } else {
+            Err(request_failed("Logout failed", response.error))
+        }
+    }
+
     /// Get the base URL
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
 
+    /// Whether a user is signed in on this connection
+    pub const fn is_signed_in(&self) -> bool {
+        self.auth_tokens.is_some()
+    }
+
+    /// The connection as reported in a diagnostics bundle, without tokens
+    pub fn summary(&self) -> ConnectionSummary {
+        ConnectionSummary {
+            base_url: self.base_url.clone(),
+            server_name: self.server_info.name.clone(),
+            server_version: self.server_version.clone(),
+            protocol_version: self.server_protocol.clone(),
+            compatibility: self.compatibility,
+            features: self.features.clone(),
+            standby: self.server_info.standby,
+            signed_in: self.auth_tokens.is_some(),
+            session_expires_at: self.auth_tokens.as_ref().map(|t| t.expires_at),
+        }
+    }
+
+    /// Drop cached lot data; slot bookings are rendered per user
+    fn clear_lot_cache(&self) {
+        if let Ok(mut cache) = self.lot_cache.lock() {
+            *cache = LotCache::default();
+        }
+    }
+
     /// Get authorization header
     fn auth_header(&self) -> Option<String> {
         self.auth_tokens
@@ -255,7 +500,56 @@ impl ServerConnection {
 
         response
             .data
-            .ok_or_else(|| anyhow::anyhow!("Failed: {:?}", response.error))
+            .ok_or_else(|| request_failed("Request failed", response.error))
+    }
+
+    /// Get what the current user may do. Servers without custom roles do
+    /// not have this endpoint; see [`ResolvedPermissions::for_role`].
+    pub async fn my_permissions(&self) -> Result<ResolvedPermissions> {
+        let mut request = self
+            .client
+            .get(format!("{}/api/v1/users/me/permissions", self.base_url));
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<ResolvedPermissions> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        response
+            .data
+            .ok_or_else(|| request_failed("Failed to get permissions", response.error))
+    }
+
+    /// Download a profile picture. Server-relative URLs (uploaded pictures)
+    /// are fetched with this session's credentials; absolute URLs as-is.
+    pub async fn fetch_picture(&self, picture: &str) -> Result<Vec<u8>> {
+        let request = if picture.starts_with('/') {
+            let mut request = self.client.get(format!("{}{}", self.base_url, picture));
+            if let Some(auth) = self.auth_header() {
+                request = request.header("Authorization", auth);
+            }
+            request
+        } else {
+            self.client.get(picture)
+        };
+
+        let bytes = request
+            .send()
+            .await
+            .context("Request failed")?
+            .error_for_status()
+            .context("Picture not available")?
+            .bytes()
+            .await
+            .context("Invalid response")?;
+        Ok(bytes.to_vec())
     }
 
     /// List parking lots
@@ -274,7 +568,16 @@ impl ServerConnection {
             .await
             .context("Invalid response")?;
 
-        Ok(response.data.unwrap_or_default())
+        let lots = response.data.unwrap_or_default();
+        if let Ok(mut cache) = self.lot_cache.lock() {
+            cache.lots = Some(lots.clone());
+        }
+        Ok(lots)
+    }
+
+    /// Lots from the last successful [`Self::list_lots`], if any
+    pub fn cached_lots(&self) -> Option<Vec<ParkingLot>> {
+        self.lot_cache.lock().ok()?.lots.clone()
     }
 
     /// Get slots for a parking lot
@@ -295,11 +598,156 @@ impl ServerConnection {
             .await
             .context("Invalid response")?;
 
+        let slots = response.data.unwrap_or_default();
+        if let Ok(mut cache) = self.lot_cache.lock() {
+            cache.slots.insert(lot_id.to_string(), slots.clone());
+        }
+        Ok(slots)
+    }
+
+    /// Get the slots on one floor of a parking lot
+    pub async fn get_floor_slots(&self, lot_id: &str, floor_id: &str) -> Result<Vec<ParkingSlot>> {
+        let mut request = self.client.get(format!(
+            "{}/api/v1/lots/{}/floors/{}/slots",
+            self.base_url, lot_id, floor_id
+        ));
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<Vec<ParkingSlot>> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        response
+            .data
+            .ok_or_else(|| request_failed("Request failed", response.error))
+    }
+
+    /// Slots of `lot_id` from the last successful [`Self::get_lot_slots`], if any
+    pub fn cached_lot_slots(&self, lot_id: &str) -> Option<Vec<ParkingSlot>> {
+        self.lot_cache.lock().ok()?.slots.get(lot_id).cloned()
+    }
+
+    /// Get the booking rules of a parking lot
+    pub async fn get_lot_rules(&self, lot_id: &str) -> Result<LotBookingRules> {
+        let mut request = self
+            .client
+            .get(format!("{}/api/v1/lots/{}/rules", self.base_url, lot_id));
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<LotBookingRules> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
         Ok(response.data.unwrap_or_default())
     }
 
+    /// Per-day availability of a lot for picking overnight and multi-day ranges
+    pub async fn get_lot_calendar(
+        &self,
+        lot_id: &str,
+        start_date: chrono::NaiveDate,
+        days: u32,
+    ) -> Result<LotCalendar> {
+        let mut request = self.client.get(format!(
+            "{}/api/v1/lots/{}/calendar?start_date={}&days={}",
+            self.base_url, lot_id, start_date, days
+        ));
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<LotCalendar> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        response
+            .data
+            .ok_or_else(|| request_failed("Request failed", response.error))
+    }
+
+    /// Free and booked stretches of one slot for each day of `month`
+    pub async fn get_slot_calendar(
+        &self,
+        slot_id: &str,
+        month: chrono::NaiveDate,
+    ) -> Result<SlotCalendar> {
+        let mut request = self.client.get(format!(
+            "{}/api/v1/slots/{}/calendar?month={}",
+            self.base_url,
+            slot_id,
+            month.format("%Y-%m")
+        ));
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<SlotCalendar> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        response
+            .data
+            .ok_or_else(|| request_failed("Request failed", response.error))
+    }
+
+    /// Which slots of a lot are free for the whole window `[start, end)`
+    pub async fn get_lot_availability(
+        &self,
+        lot_id: &str,
+        start: chrono::DateTime<chrono::Utc>,
+        end: chrono::DateTime<chrono::Utc>,
+    ) -> Result<LotAvailability> {
+        let mut request = self
+            .client
+            .get(format!(
+                "{}/api/v1/lots/{}/availability",
+                self.base_url, lot_id
+            ))
+            .query(&[("start", start.to_rfc3339()), ("end", end.to_rfc3339())]);
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<LotAvailability> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        response
+            .data
+            .ok_or_else(|| request_failed("Request failed", response.error))
+    }
+
     /// List bookings
-    pub async fn list_bookings(&self) -> Result<Vec<Booking>> {
+    pub async fn list_bookings(&self) -> Result<Vec<ListedBooking>> {
         let mut request = self
             .client
             .get(format!("{}/api/v1/bookings", self.base_url));
@@ -308,7 +756,7 @@ impl ServerConnection {
             request = request.header("Authorization", auth);
         }
 
-        let response: ApiResponse<Vec<Booking>> = request
+        let response: ApiResponse<Vec<ListedBooking>> = request
             .send()
             .await
             .context("Request failed")?
@@ -319,8 +767,27 @@ impl ServerConnection {
         Ok(response.data.unwrap_or_default())
     }
 
+    /// Read the server clock and its booking expiry warning window
+    pub async fn server_time(&self) -> Result<ServerTime> {
+        let response: ApiResponse<ServerTime> = self
+            .client
+            .get(format!("{}/api/v1/time", self.base_url))
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        response
+            .data
+            .ok_or_else(|| request_failed("Request failed", response.error))
+    }
+
     /// Create a booking
     pub async fn create_booking(&self, request: CreateBookingRequest) -> Result<Booking> {
+        self.ensure_writable()?;
+
         let mut req = self
             .client
             .post(format!("{}/api/v1/bookings", self.base_url))
@@ -340,11 +807,68 @@ impl ServerConnection {
 
         response
             .data
-            .ok_or_else(|| anyhow::anyhow!("Failed: {:?}", response.error))
+            .ok_or_else(|| request_failed("Request failed", response.error))
+    }
+
+    /// Get an itemized price quote for a prospective booking
+    pub async fn quote_booking(&self, request: &BookingQuoteRequest) -> Result<BookingQuote> {
+        let mut req = self
+            .client
+            .post(format!("{}/api/v1/bookings/quote", self.base_url))
+            .json(request);
+
+        if let Some(auth) = self.auth_header() {
+            req = req.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<BookingQuote> = req
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        response
+            .data
+            .ok_or_else(|| request_failed("Request failed", response.error))
+    }
+
+    /// Move the end of a booking, e.g. to extend it. The server re-checks
+    /// the slot for the new time and reprices the booking.
+    pub async fn extend_booking(
+        &self,
+        booking_id: &str,
+        end_time: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Booking> {
+        self.ensure_writable()?;
+
+        let mut request = self
+            .client
+            .patch(format!("{}/api/v1/bookings/{}", self.base_url, booking_id))
+            .json(&serde_json::json!({ "end_time": end_time }));
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<Booking> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        response
+            .data
+            .ok_or_else(|| request_failed("Failed to extend booking", response.error))
     }
 
     /// Cancel a booking
     pub async fn cancel_booking(&self, booking_id: &str) -> Result<()> {
+        self.ensure_writable()?;
+
         let mut request = self
             .client
             .delete(format!("{}/api/v1/bookings/{}", self.base_url, booking_id));
@@ -364,23 +888,244 @@ impl ServerConnection {
         if response.success {
             Ok(())
         } else {
-            Err(anyhow::anyhow!("Failed: {:?}", response.error))
+            Err(request_failed("Request failed", response.error))
         }
     }
 
-    // ==================== ADMIN: User Management ====================
+    /// Live announcements addressed to the current user that they have not
+    /// dismissed, newest first
+    pub async fn list_announcements(&self) -> Result<Vec<Announcement>> {
+        let mut request = self
+            .client
+            .get(format!("{}/api/v1/announcements", self.base_url));
 
-    /// List all users (admin only)
-    pub async fn list_users(&self) -> Result<Vec<User>> {
-        let mut request = self.client.get(format!(
-            "{}/api/v1/admin/users?page=1&per_page=1000",
-            self.base_url
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<Vec<Announcement>> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        Ok(response.data.unwrap_or_default())
+    }
+
+    /// Hide an announcement for the current user
+    pub async fn dismiss_announcement(&self, announcement_id: &str) -> Result<()> {
+        self.ensure_writable()?;
+
+        let mut request = self.client.post(format!(
+            "{}/api/v1/announcements/{}/dismiss",
+            self.base_url, announcement_id
         ));
 
         if let Some(auth) = self.auth_header() {
             request = request.header("Authorization", auth);
         }
 
+        let response: ApiResponse<()> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        if response.success {
+            Ok(())
+        } else {
+            Err(request_failed("Request failed", response.error))
+        }
+    }
+
+    // ==================== Waitlist ====================
+
+    /// The current user's waitlist entries with their place in line
+    pub async fn list_waitlist(&self) -> Result<Vec<WaitlistPosition>> {
+        let mut request = self
+            .client
+            .get(format!("{}/api/v1/waitlist", self.base_url));
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<Vec<WaitlistPosition>> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        response
+            .data
+            .ok_or_else(|| request_failed("Failed to load waitlist", response.error))
+    }
+
+    /// Wait for a free slot in lot `lot_id`
+    pub async fn join_waitlist(&self, lot_id: &str) -> Result<()> {
+        self.ensure_writable()?;
+
+        let mut request = self
+            .client
+            .post(format!("{}/api/v1/waitlist", self.base_url))
+            .json(&serde_json::json!({ "lot_id": lot_id }));
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<WaitlistEntry> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        if response.success {
+            Ok(())
+        } else {
+            Err(request_failed("Failed to join waitlist", response.error))
+        }
+    }
+
+    /// Leave the waitlist entry `entry_id`
+    pub async fn leave_waitlist(&self, entry_id: &str) -> Result<()> {
+        self.ensure_writable()?;
+
+        let mut request = self
+            .client
+            .delete(format!("{}/api/v1/waitlist/{}", self.base_url, entry_id));
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<()> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        if response.success {
+            Ok(())
+        } else {
+            Err(request_failed("Failed to leave waitlist", response.error))
+        }
+    }
+
+    /// Store the accessibility settings in the current user's preferences
+    pub async fn update_accessibility(&self, settings: AccessibilityPreferences) -> Result<()> {
+        self.ensure_writable()?;
+
+        let mut request = self
+            .client
+            .put(format!("{}/api/v1/user/preferences", self.base_url))
+            .json(&serde_json::json!({ "accessibility": settings }));
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<serde_json::Value> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        if response.success {
+            Ok(())
+        } else {
+            Err(request_failed("Request failed", response.error))
+        }
+    }
+
+    // ==================== Activity ====================
+
+    /// One page of the user's activity feed, newest first: the first for no
+    /// `cursor`, otherwise the one after it
+    pub async fn list_activity_page(
+        &self,
+        cursor: Option<&str>,
+        per_page: i32,
+    ) -> Result<PaginatedResponse<ActivityEntry>> {
+        let mut query = vec![("per_page", per_page.to_string())];
+        if let Some(cursor) = cursor {
+            query.push(("cursor", cursor.to_string()));
+        }
+
+        let mut request = self
+            .client
+            .get(format!("{}/api/v1/users/me/activity", self.base_url))
+            .query(&query);
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<Vec<ActivityEntry>> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        let items = response
+            .data
+            .ok_or_else(|| request_failed("Failed to load activity", response.error))?;
+        let meta = response.meta;
+        let total = meta
+            .as_ref()
+            .and_then(|m| m.total)
+            .unwrap_or_else(|| i32::try_from(items.len()).unwrap_or(i32::MAX));
+        Ok(PaginatedResponse {
+            items,
+            page: meta.as_ref().and_then(|m| m.page).unwrap_or(1),
+            per_page,
+            total,
+            total_pages: meta.as_ref().and_then(|m| m.total_pages).unwrap_or(1),
+            next_cursor: meta.and_then(|m| m.next_cursor),
+        })
+    }
+
+    // ==================== ADMIN: User Management ====================
+
+    /// One page of users (admin only): the first for no `cursor`, otherwise
+    /// the one after it. An empty `search` matches everyone.
+    pub async fn list_users_page(
+        &self,
+        cursor: Option<&str>,
+        search: &str,
+        per_page: i32,
+    ) -> Result<PaginatedResponse<User>> {
+        let mut query = vec![("per_page", per_page.to_string())];
+        if let Some(cursor) = cursor {
+            query.push(("cursor", cursor.to_string()));
+        }
+        if !search.is_empty() {
+            query.push(("search", search.to_string()));
+        }
+
+        let mut request = self
+            .client
+            .get(format!("{}/api/v1/admin/users", self.base_url))
+            .query(&query);
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
         let response: ApiResponse<PaginatedResponse<AdminUserRecord>> = request
             .send()
             .await
@@ -389,10 +1134,55 @@ impl ServerConnection {
             .await
             .context("Invalid response")?;
 
-        Ok(response
+        let page = response
+            .data
+            .ok_or_else(|| request_failed("Failed to list users", response.error))?;
+        Ok(PaginatedResponse {
+            items: page.items.into_iter().map(User::from).collect(),
+            page: page.page,
+            per_page: page.per_page,
+            total: page.total,
+            total_pages: page.total_pages,
+            next_cursor: page.next_cursor,
+        })
+    }
+
+    /// One page of all bookings, newest first (admin and lot managers): the
+    /// first for no `cursor`, otherwise the one after it
+    pub async fn list_admin_bookings_page(
+        &self,
+        cursor: Option<&str>,
+        per_page: i32,
+    ) -> Result<PaginatedResponse<AdminBooking>> {
+        let mut query = vec![
+            ("sort", "start_time".to_string()),
+            ("order", "desc".to_string()),
+            ("per_page", per_page.to_string()),
+        ];
+        if let Some(cursor) = cursor {
+            query.push(("cursor", cursor.to_string()));
+        }
+
+        let mut request = self
+            .client
+            .get(format!("{}/api/v1/admin/bookings", self.base_url))
+            .query(&query);
+
+        if let Some(auth) = self.auth_header() {
+            request = request.header("Authorization", auth);
+        }
+
+        let response: ApiResponse<PaginatedResponse<AdminBooking>> = request
+            .send()
+            .await
+            .context("Request failed")?
+            .json()
+            .await
+            .context("Invalid response")?;
+
+        response
             .data
-            .map(|page| page.items.into_iter().map(User::from).collect())
-            .unwrap_or_default())
+            .ok_or_else(|| request_failed("Failed to list bookings", response.error))
     }
 
     /// Get a specific user (admin only)
@@ -415,11 +1205,13 @@ impl ServerConnection {
 
         response
             .data
-            .ok_or_else(|| anyhow::anyhow!("User not found: {:?}", response.error))
+            .ok_or_else(|| request_failed("User not found", response.error))
     }
 
     /// Update a user (admin only)
     pub async fn update_user(&self, user_id: &str, updates: serde_json::Value) -> Result<()> {
+        self.ensure_writable()?;
+
         let mut request = self
             .client
             .put(format!(
@@ -443,7 +1235,7 @@ impl ServerConnection {
         if response.success {
             Ok(())
         } else {
-            Err(anyhow::anyhow!("Update failed: {:?}", response.error))
+            Err(request_failed("Update failed", response.error))
         }
     }
 
@@ -456,6 +1248,8 @@ impl ServerConnection {
         role: &str,
         password: &str,
     ) -> Result<()> {
+        self.ensure_writable()?;
+
         let payload = serde_json::json!({
             "format": "json",
             "data": serde_json::to_string(&vec![serde_json::json!({
@@ -485,7 +1279,7 @@ impl ServerConnection {
             .context("Invalid response")?;
 
         if !response.success {
-            return Err(anyhow::anyhow!("Create failed: {:?}", response.error));
+            return Err(request_failed("Create failed", response.error));
         }
 
         let result = response
@@ -511,6 +1305,8 @@ impl ServerConnection {
 
     /// Delete a user (admin only)
     pub async fn delete_user(&self, user_id: &str) -> Result<()> {
+        self.ensure_writable()?;
+
         let mut request = self
             .client
             .delete(format!("{}/api/v1/admin/users/{}", self.base_url, user_id));
@@ -530,12 +1326,14 @@ impl ServerConnection {
         if response.success {
             Ok(())
         } else {
-            Err(anyhow::anyhow!("Delete failed: {:?}", response.error))
+            Err(request_failed("Delete failed", response.error))
         }
     }
 
     /// Reset user password (admin only)
     pub async fn reset_user_password(&self, user_id: &str, new_password: &str) -> Result<()> {
+        self.ensure_writable()?;
+
         let mut request = self
             .client
             .post(format!(
@@ -559,10 +1357,7 @@ impl ServerConnection {
         if response.success {
             Ok(())
         } else {
-            Err(anyhow::anyhow!(
-                "Password reset failed: {:?}",
-                response.error
-            ))
+            Err(request_failed("Password reset failed", response.error))
         }
     }
 
@@ -588,11 +1383,13 @@ impl ServerConnection {
 
         response
             .data
-            .ok_or_else(|| anyhow::anyhow!("Failed to get config: {:?}", response.error))
+            .ok_or_else(|| request_failed("Failed to get config", response.error))
     }
 
     /// Update server configuration (admin only)
     pub async fn update_server_config(&self, updates: serde_json::Value) -> Result<()> {
+        self.ensure_writable()?;
+
         let mut request = self
             .client
             .patch(format!("{}/api/v1/admin/config", self.base_url))
@@ -613,10 +1410,7 @@ impl ServerConnection {
         if response.success {
             Ok(())
         } else {
-            Err(anyhow::anyhow!(
-                "Config update failed: {:?}",
-                response.error
-            ))
+            Err(request_failed("Config update failed", response.error))
         }
     }
 
@@ -640,6 +1434,6 @@ impl ServerConnection {
 
         response
             .data
-            .ok_or_else(|| anyhow::anyhow!("Failed to get stats: {:?}", response.error))
+            .ok_or_else(|| request_failed("Failed to get stats", response.error))
     }
 }
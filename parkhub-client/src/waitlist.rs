@@ -0,0 +1,87 @@
+//! The user's place on parking lot waitlists
+//!
+//! When the shown lot is fully booked the user can join its waitlist. The
+//! server reports each entry's place in line and when a slot is expected to
+//! free up, based on the lot's recent turnover. Both change as others leave
+//! the line or slots are released, so they are refreshed while signed in.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use parkhub_common::models::{WaitlistPosition, WaitlistStatus};
+use slint::{ModelRc, SharedString, VecModel};
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::server_connection::ServerConnection;
+use crate::{AppState, MainWindow, WaitlistInfo};
+
+/// Time between refreshes of the waitlist positions
+const REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Refresh the waitlist positions for as long as the client runs
+pub async fn watch(state: Arc<RwLock<AppState>>, ui_weak: slint::Weak<MainWindow>) {
+    let mut interval = tokio::time::interval(REFRESH_INTERVAL);
+    loop {
+        interval.tick().await;
+        let state = state.read().await;
+        if let Some(ref server) = state.server
+            && server.is_signed_in()
+        {
+            refresh(server, &ui_weak).await;
+        }
+    }
+}
+
+/// Load the user's waitlist entries and show those still in line
+pub async fn refresh(server: &ServerConnection, ui_weak: &slint::Weak<MainWindow>) {
+    if !server.supports("waitlist") {
+        show(ui_weak, false, Vec::new());
+        return;
+    }
+    match server.list_waitlist().await {
+        Ok(positions) => show(ui_weak, true, waiting(server, positions)),
+        Err(e) => warn!("Failed to load waitlist: {}", e),
+    }
+}
+
+/// The entries of `positions` still in line, with their lot ids
+fn waiting(
+    server: &ServerConnection,
+    positions: Vec<WaitlistPosition>,
+) -> Vec<(String, WaitlistInfo)> {
+    let lots = server.cached_lots().unwrap_or_default();
+    positions
+        .into_iter()
+        .filter(|p| p.position > 0)
+        .map(|p| {
+            let lot_name = lots
+                .iter()
+                .find(|lot| lot.id == p.entry.lot_id)
+                .map(|lot| lot.name.clone())
+                .unwrap_or_default();
+            let eta = p
+                .estimated_available_at
+                .map(|at| at.format("%H:%M").to_string())
+                .unwrap_or_default();
+            let info = WaitlistInfo {
+                id: SharedString::from(p.entry.id.to_string()),
+                lot_name: SharedString::from(lot_name),
+                position: i32::try_from(p.position).unwrap_or(i32::MAX),
+                offered: p.entry.status == WaitlistStatus::Offered,
+                eta: SharedString::from(eta),
+            };
+            (p.entry.lot_id.to_string(), info)
+        })
+        .collect()
+}
+
+fn show(ui_weak: &slint::Weak<MainWindow>, available: bool, entries: Vec<(String, WaitlistInfo)>) {
+    let _ = ui_weak.upgrade_in_event_loop(move |ui| {
+        let lot_id = ui.get_lot_id();
+        ui.set_waiting_for_lot(entries.iter().any(|(lot, _)| *lot == lot_id.as_str()));
+        let entries: Vec<WaitlistInfo> = entries.into_iter().map(|(_, info)| info).collect();
+        ui.set_waitlist_entries(ModelRc::new(VecModel::from(entries)));
+        ui.set_waitlist_available(available);
+    });
+}
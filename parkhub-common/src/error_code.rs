@@ -0,0 +1,322 @@
+//! API Error Codes
+//!
+//! The machine-readable `code` of an [`ApiError`](crate::ApiError). On the
+//! wire a code is a SCREAMING_SNAKE_CASE string; clients match on the enum
+//! to show their own message instead of the server's English text.
+
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! error_codes {
+    ($($(#[$attr:meta])* $variant:ident = $code:literal,)*) => {
+        /// Error code of a failed API request
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum ErrorCode {
+            $($(#[$attr])* $variant,)*
+            /// A code this version does not know, e.g. sent by a newer server
+            Other(String),
+        }
+
+        impl ErrorCode {
+            /// Every known code, in declaration order
+            pub const ALL: &[Self] = &[$(Self::$variant,)*];
+
+            /// The code as sent on the wire
+            pub const fn as_str(&self) -> &str {
+                match self {
+                    $(Self::$variant => $code,)*
+                    Self::Other(code) => code.as_str(),
+                }
+            }
+
+            /// Look up a wire code; unknown codes become [`ErrorCode::Other`]
+            pub fn from_code(code: &str) -> Self {
+                match code {
+                    $($code => Self::$variant,)*
+                    other => Self::Other(other.to_string()),
+                }
+            }
+        }
+    };
+}
+
+error_codes! {
+    // ── Authentication and sessions ─────────────────────────────────────────
+    InvalidCredentials = "INVALID_CREDENTIALS",
+    AccountDisabled = "ACCOUNT_DISABLED",
+    Unauthorized = "UNAUTHORIZED",
+    Forbidden = "FORBIDDEN",
+    InvalidToken = "INVALID_TOKEN",
+    TokenExpired = "TOKEN_EXPIRED",
+    InvalidRefreshToken = "INVALID_REFRESH_TOKEN",
+    RefreshTokenReused = "REFRESH_TOKEN_REUSED",
+    InvalidTempToken = "INVALID_TEMP_TOKEN",
+    Invalid2faCode = "INVALID_2FA_CODE",
+    TwoFactorAlreadyEnabled = "2FA_ALREADY_ENABLED",
+    InvalidChallenge = "INVALID_CHALLENGE",
+    InvalidCode = "INVALID_CODE",
+    NoPendingSetup = "NO_PENDING_SETUP",
+    SessionError = "SESSION_ERROR",
+    SessionIdleTimeout = "SESSION_IDLE_TIMEOUT",
+    SessionRevoked = "SESSION_REVOKED",
+    SessionLimitReached = "SESSION_LIMIT_REACHED",
+    NoActiveSession = "NO_ACTIVE_SESSION",
+    PasswordChangeRequired = "PASSWORD_CHANGE_REQUIRED",
+    InvalidPassword = "INVALID_PASSWORD",
+    WeakPassword = "WEAK_PASSWORD",
+    PasswordMismatch = "PASSWORD_MISMATCH",
+    PasswordUnchanged = "PASSWORD_UNCHANGED",
+    TooManyAttempts = "TOO_MANY_ATTEMPTS",
+    RateLimited = "RATE_LIMITED",
+    CsrfValidationFailed = "CSRF_VALIDATION_FAILED",
+    PasskeysUnavailable = "PASSKEYS_UNAVAILABLE",
+    PasskeyLimitReached = "PASSKEY_LIMIT_REACHED",
+    PasskeyRejected = "PASSKEY_REJECTED",
+    OauthError = "OAUTH_ERROR",
+    OauthNotConfigured = "OAUTH_NOT_CONFIGURED",
+    OidcError = "OIDC_ERROR",
+    OidcNotConfigured = "OIDC_NOT_CONFIGURED",
+    SsoParseError = "SSO_PARSE_ERROR",
+    SsoProviderNotFound = "SSO_PROVIDER_NOT_FOUND",
+    ProtocolMismatch = "PROTOCOL_MISMATCH",
+
+    // ── Accounts ────────────────────────────────────────────────────────────
+    RegistrationDisabled = "REGISTRATION_DISABLED",
+    EmailExists = "EMAIL_EXISTS",
+    EmailTaken = "EMAIL_TAKEN",
+    UsernameExhausted = "USERNAME_EXHAUSTED",
+    UserCreateError = "USER_CREATE_ERROR",
+    CannotDeleteSelf = "CANNOT_DELETE_SELF",
+    MissingRole = "MISSING_ROLE",
+    InvalidRoleIds = "INVALID_ROLE_IDS",
+    InvalidPermissions = "INVALID_PERMISSIONS",
+    FieldNotEditable = "FIELD_NOT_EDITABLE",
+    RectificationPending = "RECTIFICATION_PENDING",
+    InviteExpired = "INVITE_EXPIRED",
+    NoUsers = "NO_USERS",
+
+    // ── Bookings and slots ──────────────────────────────────────────────────
+    SlotUnavailable = "SLOT_UNAVAILABLE",
+    SlotNotAvailable = "SLOT_NOT_AVAILABLE",
+    SlotHeld = "SLOT_HELD",
+    SlotOccupied = "SLOT_OCCUPIED",
+    SlotNotAssigned = "SLOT_NOT_ASSIGNED",
+    SlotUpdateFailed = "SLOT_UPDATE_FAILED",
+    SlotsAvailable = "SLOTS_AVAILABLE",
+    NoSlot = "NO_SLOT",
+    NoSlotAvailable = "NO_SLOT_AVAILABLE",
+    NoSlotsAvailable = "NO_SLOTS_AVAILABLE",
+    InvalidBookingTime = "INVALID_BOOKING_TIME",
+    BookingNotModifiable = "BOOKING_NOT_MODIFIABLE",
+    DurationTooShort = "DURATION_TOO_SHORT",
+    DurationTooLong = "DURATION_TOO_LONG",
+    LeadTimeTooShort = "LEAD_TIME_TOO_SHORT",
+    SameDayOnly = "SAME_DAY_ONLY",
+    OutsideOperatingHours = "OUTSIDE_OPERATING_HOURS",
+    MaxBookingsReached = "MAX_BOOKINGS_REACHED",
+    DepartmentQuotaReached = "DEPARTMENT_QUOTA_REACHED",
+    InsufficientCredits = "INSUFFICIENT_CREDITS",
+    CancellationCutoff = "CANCELLATION_CUTOFF",
+    AlreadyCancelled = "ALREADY_CANCELLED",
+    AlreadyCheckedIn = "ALREADY_CHECKED_IN",
+    CheckinTooEarly = "CHECKIN_TOO_EARLY",
+    PermitRequired = "PERMIT_REQUIRED",
+    VehicleRequired = "VEHICLE_REQUIRED",
+    LicensePlateRequired = "LICENSE_PLATE_REQUIRED",
+    GuestBookingsDisabled = "GUEST_BOOKINGS_DISABLED",
+    VisitorsDisabled = "VISITORS_DISABLED",
+    WaitlistDisabled = "WAITLIST_DISABLED",
+    AlreadyWaitlisted = "ALREADY_WAITLISTED",
+    NotOffered = "NOT_OFFERED",
+    OfferExpired = "OFFER_EXPIRED",
+    InvalidSwap = "INVALID_SWAP",
+    OwnProposal = "OWN_PROPOSAL",
+    ChargerUnavailable = "CHARGER_UNAVAILABLE",
+    AlgorithmicDisabled = "ALGORITHMIC_DISABLED",
+    AuditTracePersistFailed = "AUDIT_TRACE_PERSIST_FAILED",
+    LotArchived = "LOT_ARCHIVED",
+    LotHasActiveBookings = "LOT_HAS_ACTIVE_BOOKINGS",
+    AlreadyArchived = "ALREADY_ARCHIVED",
+    PhotoLimitReached = "PHOTO_LIMIT_REACHED",
+    AttachmentLimitReached = "ATTACHMENT_LIMIT_REACHED",
+    VisitorPassesExhausted = "VISITOR_PASSES_EXHAUSTED",
+    BelowStatutoryMinimum = "BELOW_STATUTORY_MINIMUM",
+
+    // ── Request validation ──────────────────────────────────────────────────
+    Invalid = "INVALID",
+    InvalidInput = "INVALID_INPUT",
+    Validation = "VALIDATION",
+    ValidationError = "VALIDATION_ERROR",
+    ValidationFailed = "VALIDATION_FAILED",
+    ImportValidationFailed = "IMPORT_VALIDATION_FAILED",
+    InvalidAction = "INVALID_ACTION",
+    InvalidBody = "INVALID_BODY",
+    InvalidJson = "INVALID_JSON",
+    InvalidFormat = "INVALID_FORMAT",
+    InvalidId = "INVALID_ID",
+    InvalidKey = "INVALID_KEY",
+    InvalidName = "INVALID_NAME",
+    InvalidDate = "INVALID_DATE",
+    InvalidDateRange = "INVALID_DATE_RANGE",
+    InvalidTimeRange = "INVALID_TIME_RANGE",
+    InvalidCoordinates = "INVALID_COORDINATES",
+    InvalidRadius = "INVALID_RADIUS",
+    InvalidCredits = "INVALID_CREDITS",
+    InvalidCursor = "INVALID_CURSOR",
+    InvalidMultiplier = "INVALID_MULTIPLIER",
+    InvalidNeed = "INVALID_NEED",
+    InvalidPolicy = "INVALID_POLICY",
+    InvalidReason = "INVALID_REASON",
+    InvalidSort = "INVALID_SORT",
+    InvalidState = "INVALID_STATE",
+    InvalidStatus = "INVALID_STATUS",
+    InvalidStep = "INVALID_STEP",
+    InvalidTags = "INVALID_TAGS",
+    InvalidTarget = "INVALID_TARGET",
+    InvalidTheme = "INVALID_THEME",
+    InvalidVersion = "INVALID_VERSION",
+    ConfirmationRequired = "CONFIRMATION_REQUIRED",
+    PayloadTooLarge = "PAYLOAD_TOO_LARGE",
+    TooManyItems = "TOO_MANY_ITEMS",
+    TooManyRows = "TOO_MANY_ROWS",
+    EmptyCsv = "EMPTY_CSV",
+    EmptyIcal = "EMPTY_ICAL",
+    ParseError = "PARSE_ERROR",
+    SignatureMissing = "SIGNATURE_MISSING",
+    SignatureInvalid = "SIGNATURE_INVALID",
+    WebhookSecretMissing = "WEBHOOK_SECRET_MISSING",
+
+    // ── Resources and state ─────────────────────────────────────────────────
+    NotFound = "NOT_FOUND",
+    AlreadyExists = "ALREADY_EXISTS",
+    AlreadyResolved = "ALREADY_RESOLVED",
+    Conflict = "CONFLICT",
+    Duplicate = "DUPLICATE",
+    DuplicateId = "DUPLICATE_ID",
+    DuplicateName = "DUPLICATE_NAME",
+    NotPending = "NOT_PENDING",
+    BuiltIn = "BUILT_IN",
+    BulkRejected = "BULK_REJECTED",
+    SetupCompleted = "SETUP_COMPLETED",
+
+    // ── Modules and configuration ───────────────────────────────────────────
+    ModuleDisabled = "MODULE_DISABLED",
+    UnknownModule = "UNKNOWN_MODULE",
+    UnknownClass = "UNKNOWN_CLASS",
+    NoConfigSchema = "NO_CONFIG_SCHEMA",
+    NotRuntimeToggleable = "NOT_RUNTIME_TOGGLEABLE",
+    NotConfigured = "NOT_CONFIGURED",
+    NotImplemented = "NOT_IMPLEMENTED",
+
+    // ── Server faults ───────────────────────────────────────────────────────
+    ServerError = "SERVER_ERROR",
+    InternalError = "INTERNAL_ERROR",
+    DatabaseError = "DATABASE_ERROR",
+    DbError = "DB_ERROR",
+    EngineError = "ENGINE_ERROR",
+    IdempotencyStoreError = "IDEMPOTENCY_STORE_ERROR",
+    PdfError = "PDF_ERROR",
+    UpstreamError = "UPSTREAM_ERROR",
+}
+
+impl From<&str> for ErrorCode {
+    fn from(code: &str) -> Self {
+        Self::from_code(code)
+    }
+}
+
+impl From<String> for ErrorCode {
+    fn from(code: String) -> Self {
+        Self::from_code(&code)
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq<str> for ErrorCode {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for ErrorCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for ErrorCode {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl Serialize for ErrorCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for ErrorCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_codes_round_trip() {
+        for code in ErrorCode::ALL {
+            assert_eq!(&ErrorCode::from_code(code.as_str()), code);
+            let json = serde_json::to_string(code).unwrap();
+            assert_eq!(json, format!("\"{}\"", code.as_str()));
+            assert_eq!(&serde_json::from_str::<ErrorCode>(&json).unwrap(), code);
+        }
+    }
+
+    #[test]
+    fn wire_codes_are_unique_and_uppercase() {
+        let mut seen = std::collections::HashSet::new();
+        for code in ErrorCode::ALL {
+            let s = code.as_str();
+            assert!(seen.insert(s), "duplicate code {s}");
+            assert!(
+                s.chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_'),
+                "{s} is not SCREAMING_SNAKE_CASE"
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_code_is_kept() {
+        let code: ErrorCode = serde_json::from_str("\"QUOTA_MELTED\"").unwrap();
+        assert_eq!(code, ErrorCode::Other("QUOTA_MELTED".to_string()));
+        assert_eq!(serde_json::to_string(&code).unwrap(), "\"QUOTA_MELTED\"");
+    }
+
+    #[test]
+    fn compares_with_str() {
+        assert_eq!(ErrorCode::SlotHeld, "SLOT_HELD");
+        assert_eq!(ErrorCode::from("NOT_FOUND"), ErrorCode::NotFound);
+        assert_eq!(ErrorCode::Forbidden.to_string(), "FORBIDDEN");
+    }
+
+    #[test]
+    fn api_error_serializes_code_as_string() {
+        let response = crate::ApiResponse::<()>::error(ErrorCode::SlotHeld, "held");
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["error"]["code"], "SLOT_HELD");
+
+        let back: crate::ApiResponse<()> = serde_json::from_value(json).unwrap();
+        assert_eq!(back.error.unwrap().code, ErrorCode::SlotHeld);
+    }
+}
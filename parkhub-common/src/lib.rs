@@ -4,12 +4,16 @@
 //! the server and client applications.
 
 pub mod error;
+pub mod error_code;
 pub mod models;
+pub mod permissions;
 pub mod protocol;
 pub mod validation;
 
 pub use error::*;
+pub use error_code::ErrorCode;
 pub use models::*;
+pub use permissions::{LotScope, Permission, ResolvedPermissions, Role};
 pub use protocol::*;
 pub use validation::{
     MAX_BOOKING_MINUTES, MIN_BOOKING_MINUTES, TimeRange, is_valid_booking_duration,
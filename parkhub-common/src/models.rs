@@ -2,10 +2,14 @@
 //!
 //! All shared data structures for the `ParkHub` system.
 
-use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error_code::ErrorCode;
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // USER & AUTHENTICATION MODELS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -53,6 +57,11 @@ pub struct User {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[cfg_attr(feature = "gen-types", ts(type = "Record<string, unknown> | null"))]
     pub settings: Option<serde_json::Value>,
+    /// Set on accounts whose password was seeded, imported or reset by an
+    /// admin. Until the user picks a new one, only the password-change
+    /// endpoint accepts their session.
+    #[serde(default)]
+    pub must_change_password: bool,
 }
 
 const fn default_credits_quota() -> i32 {
@@ -67,6 +76,9 @@ pub enum UserRole {
     #[default]
     User,
     Premium,
+    /// Read-only access to reports, analytics, exports and all bookings,
+    /// for staff who pull reports but must not change anything
+    Reporter,
     Admin,
     SuperAdmin,
 }
@@ -81,6 +93,53 @@ pub struct UserPreferences {
     pub email_reminders: bool,
     pub language: String,
     pub theme: String,
+    /// Desktop client display settings. `None` until a client first syncs
+    /// them, so clients can tell "never set" apart from the defaults.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accessibility: Option<AccessibilityPreferences>,
+}
+
+/// Display settings shared by the desktop client and the server GUI.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
+pub struct AccessibilityPreferences {
+    /// 0=Dark, 1=Light, 2=High Contrast, 3=Deuteranopia, 4=Protanopia, 5=Tritanopia
+    #[serde(default)]
+    pub theme_mode: i32,
+    /// 1.0=Normal, 1.25=Large, 1.5=Extra Large
+    #[serde(default = "default_font_scale")]
+    pub font_scale: f32,
+    /// Reduce motion animations
+    #[serde(default)]
+    pub reduce_motion: bool,
+}
+
+impl AccessibilityPreferences {
+    /// Highest valid `theme_mode`
+    pub const MAX_THEME_MODE: i32 = 5;
+    /// Accepted `font_scale` range
+    pub const FONT_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.75..=2.0;
+
+    /// Whether every setting is within the range the clients can render.
+    #[must_use]
+    pub fn is_valid(&self) -> bool {
+        (0..=Self::MAX_THEME_MODE).contains(&self.theme_mode)
+            && Self::FONT_SCALE_RANGE.contains(&self.font_scale)
+    }
+}
+
+impl Default for AccessibilityPreferences {
+    fn default() -> Self {
+        Self {
+            theme_mode: 0,
+            font_scale: default_font_scale(),
+            reduce_motion: false,
+        }
+    }
+}
+
+const fn default_font_scale() -> f32 {
+    1.0
 }
 
 /// Authentication tokens
@@ -155,7 +214,7 @@ pub struct ParkingSlot {
 }
 
 /// Slot type classification
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default, utoipa::ToSchema)]
 #[serde(rename_all = "snake_case")]
 #[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
 pub enum SlotType {
@@ -393,6 +452,25 @@ pub struct Booking {
     /// Multi-tenant isolation: tenant ID (None = global scope)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tenant_id: Option<String>,
+    /// Cost attribution tags (e.g. `cost_center` → `CC-4711`), keyed by
+    /// an admin-defined booking tag set
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    /// Visitor the booking was made for; `None` when `user_id` parks
+    /// themselves. `user_id` stays the account that booked and manages it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub booked_for: Option<BookedFor>,
+}
+
+/// A visitor without an account a booking was made for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
+pub struct BookedFor {
+    pub name: String,
+    pub license_plate: String,
+    /// Where the visitor's pass was sent, if anywhere
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
 }
 
 /// Booking status
@@ -421,6 +499,9 @@ pub struct BookingPricing {
     pub currency: String,
     pub payment_status: PaymentStatus,
     pub payment_method: Option<String>,
+    /// Fee kept under the lot's cancellation policy (set on cancellation)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cancellation_fee: Option<f64>,
 }
 
 /// Payment status
@@ -494,12 +575,463 @@ pub enum FuelType {
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct CreateBookingRequest {
     pub lot_id: Uuid,
+    /// Slot to book; may be omitted when booking by `slot_type`
+    #[serde(default)]
     pub slot_id: Uuid,
+    /// Book any slot of this type in a late-binding lot instead of a
+    /// specific one; the slot is assigned shortly before the start
+    #[serde(default)]
+    pub slot_type: Option<SlotType>,
     pub start_time: DateTime<Utc>,
     pub duration_minutes: i32,
     pub vehicle_id: Uuid,
     pub license_plate: String,
     pub notes: Option<String>,
+    /// Booking tags, validated against the configured tag sets
+    #[serde(default)]
+    pub tags: BTreeMap<String, String>,
+}
+
+/// Request for a price quote before booking
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BookingQuoteRequest {
+    pub lot_id: Uuid,
+    pub slot_id: Option<Uuid>,
+    pub start_time: DateTime<Utc>,
+    pub duration_minutes: i32,
+}
+
+/// Itemized price for a prospective booking, computed exactly as
+/// booking creation would
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+pub struct BookingQuote {
+    pub hourly_rate: f64,
+    pub duration_minutes: i32,
+    /// Price before discount and tax
+    pub base_price: f64,
+    pub discount: f64,
+    /// `base_price - discount`
+    pub net: f64,
+    /// VAT rate as a fraction (e.g. `0.19`)
+    pub vat_rate: f64,
+    pub tax: f64,
+    pub total: f64,
+    pub currency: String,
+    /// Whether the lot's daily maximum capped the price on any day
+    pub daily_max_applied: bool,
+    /// Per calendar day (UTC) breakdown of `base_price`. Overnight and
+    /// multi-day bookings have one entry per day they touch.
+    #[serde(default)]
+    pub days: Vec<BookingQuoteDay>,
+}
+
+/// One calendar day (UTC) of a booking quote
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+pub struct BookingQuoteDay {
+    pub date: NaiveDate,
+    /// Booked minutes falling on this day
+    pub minutes: i32,
+    /// Price for the day after the daily maximum
+    pub price: f64,
+    /// Whether the daily maximum capped this day
+    pub daily_max_applied: bool,
+}
+
+/// Day-by-day availability of a lot, for calendar-style range selection
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+pub struct LotCalendar {
+    pub lot_id: Uuid,
+    pub currency: String,
+    pub days: Vec<LotCalendarDay>,
+}
+
+/// Availability and full-day price of a lot on one calendar day (UTC)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, utoipa::ToSchema)]
+pub struct LotCalendarDay {
+    pub date: NaiveDate,
+    /// Slots that can be booked at all (not in maintenance or disabled)
+    pub total_slots: u32,
+    /// Slots with no booking touching this day
+    pub free_slots: u32,
+    /// Price of a full 24-hour day, after the daily maximum
+    pub day_price: f64,
+    /// Whether a booking can start or continue on this day
+    pub bookable: bool,
+}
+
+/// Which slots of a lot are free for a whole time window
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct LotAvailability {
+    pub lot_id: Uuid,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Slots that can be booked at all (not in maintenance or disabled)
+    pub total_slots: u32,
+    /// Slots with no booking overlapping the window or the lot's buffer
+    /// around it
+    pub available_slots: u32,
+    /// Whether a booking for the window can be made (lot open for booking
+    /// and a slot free)
+    pub bookable: bool,
+    /// Turnover time the lot keeps free between bookings on a slot
+    #[serde(default)]
+    pub buffer_minutes: u32,
+    pub slots: Vec<SlotAvailability>,
+}
+
+/// One slot of a lot within an availability window
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct SlotAvailability {
+    pub slot_id: Uuid,
+    pub slot_number: i32,
+    pub floor_id: Uuid,
+    #[schema(value_type = String)]
+    pub slot_type: SlotType,
+    /// Free for the whole window and the lot's buffer around it
+    pub available: bool,
+    /// Booked periods overlapping the window or its buffer, earliest
+    /// first. Empty for slots in maintenance or disabled, which are never
+    /// available.
+    pub booked: Vec<BookedPeriod>,
+}
+
+/// Time a slot is held by a pending, confirmed or active booking
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct BookedPeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+/// Day-by-day bookings of one slot over a month, for a per-slot calendar
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct SlotCalendar {
+    pub slot_id: Uuid,
+    pub lot_id: Uuid,
+    pub slot_number: i32,
+    /// First day of the month
+    pub month: NaiveDate,
+    /// Whether the slot can be booked at all (not in maintenance or disabled)
+    pub in_service: bool,
+    /// Turnover time the lot keeps free between bookings; booked segments
+    /// include it on both sides of each booking
+    #[serde(default)]
+    pub buffer_minutes: u32,
+    pub days: Vec<SlotCalendarDay>,
+}
+
+/// One calendar day (UTC) of a slot calendar
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct SlotCalendarDay {
+    pub date: NaiveDate,
+    /// Minutes of the day no booking holds the slot
+    pub free_minutes: u32,
+    /// Free and booked stretches covering the whole day, in order
+    pub segments: Vec<SlotSegment>,
+}
+
+/// A stretch of a day during which a slot is either free or booked
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct SlotSegment {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub booked: bool,
+}
+
+/// Free slot offered in place of one that was just taken
+/// (`error.details.alternatives` on `SLOT_UNAVAILABLE`)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct SlotAlternative {
+    pub slot_id: Uuid,
+    pub slot_number: i32,
+    pub floor_id: Uuid,
+    #[schema(value_type = String)]
+    pub slot_type: SlotType,
+}
+
+impl From<&ParkingSlot> for SlotAlternative {
+    fn from(slot: &ParkingSlot) -> Self {
+        Self {
+            slot_id: slot.id,
+            slot_number: slot.slot_number,
+            floor_id: slot.floor_id,
+            slot_type: slot.slot_type.clone(),
+        }
+    }
+}
+
+impl BookingQuote {
+    /// Pricing record to persist on a booking created from this quote
+    #[must_use]
+    pub fn into_pricing(self) -> BookingPricing {
+        BookingPricing {
+            base_price: self.base_price,
+            discount: self.discount,
+            tax: self.tax,
+            total: self.total,
+            currency: self.currency,
+            payment_status: PaymentStatus::Pending,
+            payment_method: None,
+            cancellation_fee: None,
+        }
+    }
+}
+
+/// Per-lot booking rules. Unset limits and `same_day_only = false` impose
+/// no restriction, so the default rules allow everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(default)]
+pub struct LotBookingRules {
+    /// Shortest allowed booking
+    pub min_duration_minutes: Option<i32>,
+    /// Longest allowed booking
+    pub max_duration_minutes: Option<i32>,
+    /// How far ahead of its start a booking must be made
+    pub min_lead_time_minutes: Option<i32>,
+    /// Bookings can no longer be cancelled this close to their start
+    pub cancellation_cutoff_minutes: Option<i32>,
+    /// Only bookings starting today (UTC) are accepted
+    pub same_day_only: bool,
+    /// Fee for late cancellations; `None` keeps cancellations free
+    pub cancellation_policy: Option<CancellationPolicy>,
+    /// Only holders of a valid [`Permit`] covering this lot may book
+    pub permit_required: bool,
+    /// Check-in opens this many minutes before the start; unset allows
+    /// check-in at any time
+    pub early_checkin_minutes: Option<i32>,
+    /// Whether bookings name a slot or only a slot type
+    pub slot_assignment: SlotAssignmentMode,
+    /// Late-binding lots assign the slot this many minutes before the
+    /// start; unset uses [`DEFAULT_ASSIGNMENT_LEAD_MINUTES`]
+    pub assignment_lead_minutes: Option<i32>,
+    /// Turnover time kept free on a slot between the end of one booking
+    /// and the start of the next
+    pub buffer_minutes: Option<i32>,
+}
+
+/// Minutes before the start at which a late-binding lot assigns the slot,
+/// unless the lot sets `assignment_lead_minutes`.
+pub const DEFAULT_ASSIGNMENT_LEAD_MINUTES: i32 = 15;
+
+/// How the slots of a lot are allocated to bookings.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SlotAssignmentMode {
+    /// Bookings are for a specific slot chosen by the user
+    #[default]
+    Fixed,
+    /// Bookings are for a slot type; the server picks the concrete slot
+    /// shortly before the start and tells the user its number
+    LateBinding,
+}
+
+/// Cancellation fee policy: free until `free_until_minutes` before the
+/// start, `fee_percent` of the booking total after that.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct CancellationPolicy {
+    /// Cancellation is free until this many minutes before the start
+    pub free_until_minutes: i32,
+    /// Percentage of the booking total charged after that (0-100)
+    pub fee_percent: u8,
+}
+
+impl CancellationPolicy {
+    /// Fee percentage that applies when cancelling at `now`.
+    #[must_use]
+    pub fn fee_percent_at(&self, start_time: DateTime<Utc>, now: DateTime<Utc>) -> u8 {
+        if now > start_time - chrono::Duration::minutes(i64::from(self.free_until_minutes)) {
+            self.fee_percent.min(100)
+        } else {
+            0
+        }
+    }
+
+    /// Fee for cancelling a booking worth `total` at `now`, rounded to cents.
+    #[must_use]
+    pub fn fee_for(&self, total: f64, start_time: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+        let percent = f64::from(self.fee_percent_at(start_time, now));
+        (total * percent).round() / 100.0
+    }
+}
+
+/// A booking or cancellation that breaks a lot's [`LotBookingRules`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LotRuleViolation {
+    DurationTooShort(i32),
+    DurationTooLong(i32),
+    LeadTimeTooShort(i32),
+    NotSameDay,
+    CancellationCutoff(i32),
+    CheckInTooEarly(i32),
+}
+
+impl LotRuleViolation {
+    /// Machine-readable error code
+    #[must_use]
+    pub const fn code(self) -> ErrorCode {
+        match self {
+            Self::DurationTooShort(_) => ErrorCode::DurationTooShort,
+            Self::DurationTooLong(_) => ErrorCode::DurationTooLong,
+            Self::LeadTimeTooShort(_) => ErrorCode::LeadTimeTooShort,
+            Self::NotSameDay => ErrorCode::SameDayOnly,
+            Self::CancellationCutoff(_) => ErrorCode::CancellationCutoff,
+            Self::CheckInTooEarly(_) => ErrorCode::CheckinTooEarly,
+        }
+    }
+
+    /// Human-readable explanation
+    #[must_use]
+    pub fn message(self) -> String {
+        match self {
+            Self::DurationTooShort(m) => {
+                format!("Bookings in this lot must last at least {m} minutes")
+            }
+            Self::DurationTooLong(m) => {
+                format!("Bookings in this lot may last at most {m} minutes")
+            }
+            Self::LeadTimeTooShort(m) => {
+                format!("Bookings in this lot must be made at least {m} minutes in advance")
+            }
+            Self::NotSameDay => "This lot only accepts bookings for today".to_string(),
+            Self::CancellationCutoff(m) => {
+                format!("Bookings in this lot cannot be cancelled within {m} minutes of the start")
+            }
+            Self::CheckInTooEarly(m) => {
+                format!("Check-in in this lot opens {m} minutes before the booking starts")
+            }
+        }
+    }
+}
+
+impl LotBookingRules {
+    /// Check that the rules are self-consistent.
+    ///
+    /// # Errors
+    /// Returns a message for negative limits, `min > max` or a fee above 100%.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let limits = [
+            self.min_duration_minutes,
+            self.max_duration_minutes,
+            self.min_lead_time_minutes,
+            self.cancellation_cutoff_minutes,
+            self.early_checkin_minutes,
+            self.assignment_lead_minutes,
+            self.buffer_minutes,
+        ];
+        if limits.iter().flatten().any(|m| *m < 0) {
+            return Err("Rule limits must not be negative");
+        }
+        if let (Some(min), Some(max)) = (self.min_duration_minutes, self.max_duration_minutes)
+            && min > max
+        {
+            return Err("Minimum duration must not exceed maximum duration");
+        }
+        if let Some(policy) = self.cancellation_policy {
+            if policy.free_until_minutes < 0 {
+                return Err("Rule limits must not be negative");
+            }
+            if policy.fee_percent > 100 {
+                return Err("Cancellation fee must be between 0 and 100 percent");
+            }
+        }
+        Ok(())
+    }
+
+    /// Check a prospective booking against these rules.
+    ///
+    /// # Errors
+    /// Returns the first rule the booking breaks.
+    pub fn check_booking(
+        &self,
+        start_time: DateTime<Utc>,
+        duration_minutes: i32,
+        now: DateTime<Utc>,
+    ) -> Result<(), LotRuleViolation> {
+        self.check_duration(duration_minutes)?;
+        if let Some(lead) = self.min_lead_time_minutes
+            && start_time < now + chrono::Duration::minutes(i64::from(lead))
+        {
+            return Err(LotRuleViolation::LeadTimeTooShort(lead));
+        }
+        if self.same_day_only && start_time.date_naive() != now.date_naive() {
+            return Err(LotRuleViolation::NotSameDay);
+        }
+        Ok(())
+    }
+
+    /// Check the length of a booking against these rules, e.g. when a
+    /// running booking only moves its end.
+    ///
+    /// # Errors
+    /// Returns [`LotRuleViolation::DurationTooShort`] or
+    /// [`LotRuleViolation::DurationTooLong`].
+    pub fn check_duration(&self, duration_minutes: i32) -> Result<(), LotRuleViolation> {
+        if let Some(min) = self.min_duration_minutes
+            && duration_minutes < min
+        {
+            return Err(LotRuleViolation::DurationTooShort(min));
+        }
+        if let Some(max) = self.max_duration_minutes
+            && duration_minutes > max
+        {
+            return Err(LotRuleViolation::DurationTooLong(max));
+        }
+        Ok(())
+    }
+
+    /// Check whether a booking starting at `start_time` may still be
+    /// cancelled.
+    ///
+    /// # Errors
+    /// Returns [`LotRuleViolation::CancellationCutoff`] inside the cutoff.
+    pub fn check_cancellation(
+        &self,
+        start_time: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(), LotRuleViolation> {
+        if let Some(cutoff) = self.cancellation_cutoff_minutes
+            && now > start_time - chrono::Duration::minutes(i64::from(cutoff))
+        {
+            return Err(LotRuleViolation::CancellationCutoff(cutoff));
+        }
+        Ok(())
+    }
+
+    /// Check whether a booking starting at `start_time` may be checked in
+    /// at `now`.
+    ///
+    /// # Errors
+    /// Returns [`LotRuleViolation::CheckInTooEarly`] before the early
+    /// check-in window opens.
+    pub fn check_checkin(
+        &self,
+        start_time: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Result<(), LotRuleViolation> {
+        if let Some(early) = self.early_checkin_minutes
+            && now < start_time - chrono::Duration::minutes(i64::from(early))
+        {
+            return Err(LotRuleViolation::CheckInTooEarly(early));
+        }
+        Ok(())
+    }
+
+    /// Time kept free between consecutive bookings on a slot; zero when
+    /// unset.
+    #[must_use]
+    pub fn buffer(&self) -> chrono::Duration {
+        chrono::Duration::minutes(i64::from(self.buffer_minutes.unwrap_or(0).max(0)))
+    }
+
+    /// Whether a late-binding lot assigns the slot of a booking starting
+    /// at `start_time` by `now`. Always `false` for fixed-slot lots.
+    #[must_use]
+    pub fn assignment_due(&self, start_time: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        let lead = self
+            .assignment_lead_minutes
+            .unwrap_or(DEFAULT_ASSIGNMENT_LEAD_MINUTES);
+        self.slot_assignment == SlotAssignmentMode::LateBinding
+            && now >= start_time - chrono::Duration::minutes(i64::from(lead))
+    }
 }
 
 /// Request to extend a booking
@@ -551,6 +1083,63 @@ pub enum NotificationType {
     PromotionAvailable,
     SystemMessage,
     WaitlistOffer,
+    FavoriteSlotAvailable,
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// ACTIVITY MODELS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// One event in a user's activity feed (`GET /api/v1/users/me/activity`)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
+pub struct ActivityEntry {
+    /// Unique within the feed: the kind and the id of the record behind it
+    pub id: String,
+    pub kind: ActivityKind,
+    pub at: DateTime<Utc>,
+    /// The booking the event concerns, as it is now
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub booking: Option<ActivityBooking>,
+    /// Credits a payment added (positive) or spent (negative)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credits: Option<i32>,
+    /// A notification's title, or a payment's description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// A notification's text
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// What happened in an activity feed entry
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
+pub enum ActivityKind {
+    BookingCreated,
+    BookingCheckedIn,
+    BookingCheckedOut,
+    BookingCancelled,
+    BookingNoShow,
+    /// Credits deducted, refunded or granted
+    Payment,
+    Notification,
+}
+
+/// Booking details shown with an activity feed entry
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
+pub struct ActivityBooking {
+    pub id: Uuid,
+    pub lot_name: String,
+    /// 0 while a late-binding booking waits for its slot
+    pub slot_number: i32,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub status: BookingStatus,
+    pub total: f64,
+    pub currency: String,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -695,12 +1284,40 @@ pub struct WaitlistEntry {
     /// Booking ID created when the offer is accepted
     #[serde(default)]
     pub accepted_booking_id: Option<Uuid>,
+    /// Start of the window the user wants to park in; `None` waits for
+    /// any free slot
+    #[serde(default)]
+    pub start_time: Option<DateTime<Utc>>,
+    /// End of the wanted window
+    #[serde(default)]
+    pub end_time: Option<DateTime<Utc>>,
+    /// Slot held for the user while an offer for a window is outstanding
+    #[serde(default)]
+    pub offered_slot_id: Option<Uuid>,
 }
 
 const fn default_waitlist_status() -> WaitlistStatus {
     WaitlistStatus::Waiting
 }
 
+/// A waitlist entry with its place in line and when a slot is expected to
+/// free up for it (`GET /api/v1/waitlist`)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct WaitlistPosition {
+    #[serde(flatten)]
+    pub entry: WaitlistEntry,
+    /// Place in line, 1 for the entry served next; 0 once the entry left
+    /// the line (accepted, declined or expired)
+    pub position: usize,
+    /// Entries ahead of this one
+    pub total_ahead: usize,
+    /// Minutes until a slot is expected to free up, from the lot's recent
+    /// turnover; 0 while a slot is offered, `None` without booking history
+    pub estimated_wait_minutes: Option<i64>,
+    /// When a slot is expected to free up
+    pub estimated_available_at: Option<DateTime<Utc>>,
+}
+
 /// Guest booking (visitor parking)
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 #[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
@@ -773,6 +1390,21 @@ pub struct Announcement {
     pub created_by: Option<Uuid>,
     pub expires_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
+    /// Who sees the announcement; older records default to everyone
+    #[serde(default)]
+    pub audience: AnnouncementAudience,
+}
+
+/// Audience of an announcement
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
+pub enum AnnouncementAudience {
+    /// Every user
+    #[default]
+    All,
+    /// Users with a current or upcoming booking in the lot
+    Lot { lot_id: Uuid },
 }
 
 /// Announcement severity level
@@ -874,6 +1506,90 @@ pub struct Visitor {
     pub updated_at: DateTime<Utc>,
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// PERMIT MODELS
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Kind of parking permit
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PermitType {
+    Resident,
+    Contractor,
+}
+
+/// Parking permit granting access to restricted lots for a period.
+///
+/// A permit is held by a user, a license plate, or both. An empty `lot_ids`
+/// covers every lot.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Permit {
+    pub id: Uuid,
+    pub permit_type: PermitType,
+    /// Holder account; `None` for plate-only permits (e.g. contractors)
+    pub user_id: Option<Uuid>,
+    /// Plate the permit is issued for; any plate of the holder when `None`
+    pub license_plate: Option<String>,
+    /// Name shown to gate staff for permits without an account
+    pub holder_name: Option<String>,
+    /// Lots the permit is valid in; empty means every lot
+    #[serde(default)]
+    pub lot_ids: Vec<Uuid>,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+    #[serde(default)]
+    pub revoked: bool,
+    pub notes: Option<String>,
+    /// When the expiry reminder went out
+    #[serde(default)]
+    pub reminder_sent_at: Option<DateTime<Utc>>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Plate in a comparable form: uppercase letters and digits only, so
+/// `m-ab 1234` and `MAB1234` match.
+#[must_use]
+pub fn normalize_license_plate(plate: &str) -> String {
+    plate
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+impl Permit {
+    /// Whether the permit is in force for the whole of `start..end`.
+    #[must_use]
+    pub fn valid_for(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+        !self.revoked && self.valid_from <= start && end <= self.valid_until
+    }
+
+    /// Whether the permit is in force at `at`.
+    #[must_use]
+    pub fn valid_at(&self, at: DateTime<Utc>) -> bool {
+        !self.revoked && self.valid_from <= at && at < self.valid_until
+    }
+
+    /// Whether the permit applies to `lot_id`.
+    #[must_use]
+    pub fn covers_lot(&self, lot_id: Uuid) -> bool {
+        self.lot_ids.is_empty() || self.lot_ids.contains(&lot_id)
+    }
+
+    /// Whether the permit was issued for `plate` (compared normalized).
+    #[must_use]
+    pub fn matches_plate(&self, plate: &str) -> bool {
+        let plate = normalize_license_plate(plate);
+        !plate.is_empty()
+            && self
+                .license_plate
+                .as_deref()
+                .is_some_and(|p| normalize_license_plate(p) == plate)
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // EV CHARGING MODELS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1133,6 +1849,185 @@ impl FleetEvent {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_lot_booking_rules_default_allows_everything() {
+        let rules = LotBookingRules::default();
+        let now = Utc::now();
+        let start = now + chrono::Duration::days(3);
+        assert_eq!(rules.check_booking(start, 5, now), Ok(()));
+        assert_eq!(rules.check_cancellation(now, now), Ok(()));
+        assert_eq!(rules.check_checkin(start, now), Ok(()));
+        assert_eq!(rules.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_lot_booking_rules_check_booking() {
+        let rules = LotBookingRules {
+            min_duration_minutes: Some(60),
+            max_duration_minutes: Some(240),
+            min_lead_time_minutes: Some(30),
+            ..Default::default()
+        };
+        let now = Utc::now();
+        let start = now + chrono::Duration::hours(2);
+        assert_eq!(
+            rules.check_booking(start, 30, now),
+            Err(LotRuleViolation::DurationTooShort(60))
+        );
+        assert_eq!(
+            rules.check_booking(start, 300, now),
+            Err(LotRuleViolation::DurationTooLong(240))
+        );
+        assert_eq!(
+            rules.check_booking(now + chrono::Duration::minutes(10), 60, now),
+            Err(LotRuleViolation::LeadTimeTooShort(30))
+        );
+        assert_eq!(rules.check_booking(start, 120, now), Ok(()));
+    }
+
+    #[test]
+    fn test_lot_booking_rules_same_day_only() {
+        let rules = LotBookingRules {
+            same_day_only: true,
+            ..Default::default()
+        };
+        let now = "2026-03-02T08:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let today = "2026-03-02T15:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let tomorrow = "2026-03-03T08:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(rules.check_booking(today, 60, now), Ok(()));
+        let err = rules.check_booking(tomorrow, 60, now).unwrap_err();
+        assert_eq!(err, LotRuleViolation::NotSameDay);
+        assert_eq!(err.code(), "SAME_DAY_ONLY");
+    }
+
+    #[test]
+    fn test_lot_booking_rules_cancellation_cutoff() {
+        let rules = LotBookingRules {
+            cancellation_cutoff_minutes: Some(60),
+            ..Default::default()
+        };
+        let now = Utc::now();
+        assert_eq!(
+            rules.check_cancellation(now + chrono::Duration::hours(2), now),
+            Ok(())
+        );
+        assert_eq!(
+            rules.check_cancellation(now + chrono::Duration::minutes(30), now),
+            Err(LotRuleViolation::CancellationCutoff(60))
+        );
+    }
+
+    #[test]
+    fn test_lot_booking_rules_early_checkin_window() {
+        let rules = LotBookingRules {
+            early_checkin_minutes: Some(15),
+            ..Default::default()
+        };
+        let start = "2026-03-02T09:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let at = |t: &str| t.parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            rules.check_checkin(start, at("2026-03-02T08:30:00Z")),
+            Err(LotRuleViolation::CheckInTooEarly(15))
+        );
+        assert_eq!(
+            rules.check_checkin(start, at("2026-03-02T08:45:00Z")),
+            Ok(())
+        );
+        assert_eq!(
+            rules.check_checkin(start, at("2026-03-02T09:20:00Z")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_lot_booking_rules_validate() {
+        let bad = LotBookingRules {
+            min_duration_minutes: Some(120),
+            max_duration_minutes: Some(60),
+            ..Default::default()
+        };
+        assert!(bad.validate().is_err());
+        let negative = LotBookingRules {
+            min_lead_time_minutes: Some(-5),
+            ..Default::default()
+        };
+        assert!(negative.validate().is_err());
+        let negative_buffer = LotBookingRules {
+            buffer_minutes: Some(-10),
+            ..Default::default()
+        };
+        assert!(negative_buffer.validate().is_err());
+        assert_eq!(
+            LotBookingRules::default().buffer(),
+            chrono::Duration::zero()
+        );
+        // Missing fields deserialize to "no restriction".
+        let parsed: LotBookingRules = serde_json::from_str(r#"{"same_day_only":true}"#).unwrap();
+        assert!(parsed.same_day_only);
+        assert!(parsed.max_duration_minutes.is_none());
+    }
+
+    #[test]
+    fn test_lot_booking_rules_assignment_due() {
+        let start = "2026-03-02T09:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let at = |t: &str| t.parse::<DateTime<Utc>>().unwrap();
+        // Fixed lots never assign.
+        assert!(!LotBookingRules::default().assignment_due(start, start));
+
+        let rules = LotBookingRules {
+            slot_assignment: SlotAssignmentMode::LateBinding,
+            ..Default::default()
+        };
+        assert!(!rules.assignment_due(start, at("2026-03-02T08:44:00Z")));
+        assert!(rules.assignment_due(start, at("2026-03-02T08:45:00Z")));
+
+        let rules = LotBookingRules {
+            assignment_lead_minutes: Some(60),
+            ..rules
+        };
+        assert!(rules.assignment_due(start, at("2026-03-02T08:00:00Z")));
+        let parsed: LotBookingRules =
+            serde_json::from_str(r#"{"slot_assignment":"late_binding"}"#).unwrap();
+        assert_eq!(parsed.slot_assignment, SlotAssignmentMode::LateBinding);
+    }
+
+    #[test]
+    fn test_create_booking_request_by_slot_type() {
+        let lot_id = Uuid::new_v4();
+        let json = format!(
+            r#"{{"lot_id":"{lot_id}","slot_type":"electric","start_time":"2026-03-02T09:00:00Z",
+               "duration_minutes":60,"vehicle_id":"{}","license_plate":"M-AB 123","notes":null}}"#,
+            Uuid::nil()
+        );
+        let req: CreateBookingRequest = serde_json::from_str(&json).unwrap();
+        assert!(req.slot_id.is_nil());
+        assert_eq!(req.slot_type, Some(SlotType::Electric));
+    }
+
+    #[test]
+    fn test_cancellation_policy_fee() {
+        let policy = CancellationPolicy {
+            free_until_minutes: 24 * 60,
+            fee_percent: 50,
+        };
+        let now = Utc::now();
+        let far = now + chrono::Duration::days(2);
+        let near = now + chrono::Duration::hours(3);
+        assert_eq!(policy.fee_percent_at(far, now), 0);
+        assert!(policy.fee_for(10.0, far, now).abs() < f64::EPSILON);
+        assert_eq!(policy.fee_percent_at(near, now), 50);
+        assert!((policy.fee_for(12.5, near, now) - 6.25).abs() < 1e-9);
+
+        let rules = LotBookingRules {
+            cancellation_policy: Some(CancellationPolicy {
+                free_until_minutes: 60,
+                fee_percent: 150,
+            }),
+            ..Default::default()
+        };
+        assert!(rules.validate().is_err());
+    }
+
     #[test]
     fn test_user_role_default() {
         let role = UserRole::default();
@@ -1146,6 +2041,10 @@ mod tests {
             serde_json::to_string(&UserRole::Premium).unwrap(),
             "\"premium\""
         );
+        assert_eq!(
+            serde_json::to_string(&UserRole::Reporter).unwrap(),
+            "\"reporter\""
+        );
         assert_eq!(
             serde_json::to_string(&UserRole::Admin).unwrap(),
             "\"admin\""
@@ -1310,11 +2209,13 @@ mod tests {
         let request = CreateBookingRequest {
             lot_id: Uuid::new_v4(),
             slot_id: Uuid::new_v4(),
+            slot_type: None,
             start_time: Utc::now(),
             duration_minutes: 60,
             vehicle_id: Uuid::new_v4(),
             license_plate: "ABC-123".to_string(),
             notes: Some("Test booking".to_string()),
+            tags: BTreeMap::new(),
         };
 
         let json = serde_json::to_string(&request).expect("Failed to serialize");
@@ -1571,6 +2472,49 @@ mod tests {
         );
     }
 
+    // ── Permit ────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_permit_validity_lot_and_plate_matching() {
+        let now = Utc::now();
+        let lot = Uuid::new_v4();
+        let mut permit = Permit {
+            id: Uuid::new_v4(),
+            permit_type: PermitType::Contractor,
+            user_id: None,
+            license_plate: Some("M-AB 1234".to_string()),
+            holder_name: Some("Acme Plumbing".to_string()),
+            lot_ids: vec![lot],
+            valid_from: now - chrono::Duration::days(1),
+            valid_until: now + chrono::Duration::days(1),
+            revoked: false,
+            notes: None,
+            reminder_sent_at: None,
+            created_by: Uuid::new_v4(),
+            created_at: now,
+            updated_at: now,
+        };
+
+        assert!(permit.valid_at(now));
+        assert!(permit.valid_for(now, now + chrono::Duration::hours(8)));
+        assert!(!permit.valid_for(now, now + chrono::Duration::days(2)));
+        assert!(permit.covers_lot(lot));
+        assert!(!permit.covers_lot(Uuid::new_v4()));
+        assert!(permit.matches_plate("mab1234"));
+        assert!(!permit.matches_plate("M-AB 1235"));
+        assert!(!permit.matches_plate(""));
+
+        permit.lot_ids.clear();
+        assert!(permit.covers_lot(Uuid::new_v4()));
+
+        permit.revoked = true;
+        assert!(!permit.valid_at(now));
+        assert_eq!(
+            serde_json::to_string(&PermitType::Resident).unwrap(),
+            "\"resident\""
+        );
+    }
+
     // ── ConnectorType / EvChargerStatus / ChargingSessionStatus ─────────────
 
     #[test]
@@ -1811,6 +2755,7 @@ mod tests {
             email_reminders: false,
             language: "de".to_string(),
             theme: "dark".to_string(),
+            accessibility: None,
         };
         let json = serde_json::to_string(&prefs).unwrap();
         let back: UserPreferences = serde_json::from_str(&json).unwrap();
@@ -1819,6 +2764,44 @@ mod tests {
         assert!(back.notifications_enabled);
         assert_eq!(back.language, "de");
         assert_eq!(back.theme, "dark");
+        assert!(back.accessibility.is_none());
+    }
+
+    #[test]
+    fn test_user_preferences_accessibility_roundtrip_and_legacy() {
+        let prefs = UserPreferences {
+            accessibility: Some(AccessibilityPreferences {
+                theme_mode: 2,
+                font_scale: 1.25,
+                reduce_motion: true,
+            }),
+            ..UserPreferences::default()
+        };
+        let json = serde_json::to_string(&prefs).unwrap();
+        let back: UserPreferences = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.accessibility, prefs.accessibility);
+
+        // Records stored before the field existed still load.
+        let legacy = r#"{"default_duration_minutes":null,"favorite_slots":[],
+            "notifications_enabled":true,"email_reminders":false,
+            "language":"en","theme":"system"}"#;
+        let back: UserPreferences = serde_json::from_str(legacy).unwrap();
+        assert!(back.accessibility.is_none());
+    }
+
+    #[test]
+    fn test_accessibility_preferences_validation() {
+        assert!(AccessibilityPreferences::default().is_valid());
+        let mut prefs = AccessibilityPreferences {
+            theme_mode: 6,
+            ..AccessibilityPreferences::default()
+        };
+        assert!(!prefs.is_valid());
+        prefs.theme_mode = 5;
+        prefs.font_scale = 3.0;
+        assert!(!prefs.is_valid());
+        prefs.font_scale = f32::NAN;
+        assert!(!prefs.is_valid());
     }
 
     // ── FleetEvent (T-1946 SSE) ───────────────────────────────────────────────
@@ -0,0 +1,359 @@
+//! Role permission matrix and custom roles.
+//!
+//! Access control is expressed as granular capabilities instead of
+//! comparing roles directly. Each built-in [`UserRole`] maps to a fixed set
+//! of [`Permission`]s. On top of that, admins can define custom [`Role`]s
+//! that grant lot-scoped capabilities — "Lot Manager for Lot X" — to any
+//! user. A request's [`ResolvedPermissions`] combine both, each permission
+//! with the [`LotScope`] it applies to.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::models::UserRole;
+
+/// A capability a role can hold.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    utoipa::ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// List, edit, deactivate and delete user accounts
+    ManageUsers,
+    /// Create, edit, price and delete parking lots; scoped to lots, manage
+    /// their slots, maintenance windows and booking rules
+    ManageLots,
+    /// See every user's bookings, occupancy stats and reports
+    ViewAllBookings,
+    /// Change server settings, policies and feature toggles
+    ManageConfig,
+    /// Check in, edit and cancel other users' bookings
+    ManageBookings,
+    /// Book for visitors without an account, by name and license plate
+    BookForVisitors,
+}
+
+impl Permission {
+    /// Every permission, in declaration order
+    pub const ALL: [Self; 6] = [
+        Self::ManageUsers,
+        Self::ManageLots,
+        Self::ViewAllBookings,
+        Self::ManageConfig,
+        Self::ManageBookings,
+        Self::BookForVisitors,
+    ];
+
+    /// Wire name, as used in JSON
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::ManageUsers => "manage_users",
+            Self::ManageLots => "manage_lots",
+            Self::ViewAllBookings => "view_all_bookings",
+            Self::ManageConfig => "manage_config",
+            Self::ManageBookings => "manage_bookings",
+            Self::BookForVisitors => "book_for_visitors",
+        }
+    }
+
+    /// Whether custom roles may grant this permission. User management and
+    /// server configuration stay with the built-in admin roles.
+    pub const fn is_lot_scoped(self) -> bool {
+        matches!(
+            self,
+            Self::ManageLots | Self::ViewAllBookings | Self::ManageBookings | Self::BookForVisitors
+        )
+    }
+}
+
+impl std::fmt::Display for Permission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl UserRole {
+    /// Permissions granted to this role
+    pub const fn permissions(&self) -> &'static [Permission] {
+        match self {
+            Self::User => &[],
+            Self::Premium => &[Permission::BookForVisitors],
+            Self::Reporter => &[Permission::ViewAllBookings],
+            Self::Admin | Self::SuperAdmin => &Permission::ALL,
+        }
+    }
+
+    /// Whether this role holds `permission`
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.permissions().contains(&permission)
+    }
+}
+
+/// Longest accepted custom role name, in characters.
+pub const MAX_ROLE_NAME_LEN: usize = 64;
+
+/// An admin-defined role granting lot-scoped permissions to its members.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Role {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Granted permissions; only lot-scoped ones are allowed
+    pub permissions: Vec<Permission>,
+    /// Lots the permissions apply to; empty for every lot
+    #[serde(default)]
+    pub lot_ids: Vec<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Role {
+    /// The lots this role's permissions apply to
+    pub fn scope(&self) -> LotScope {
+        if self.lot_ids.is_empty() {
+            LotScope::All
+        } else {
+            LotScope::Lots(self.lot_ids.iter().copied().collect())
+        }
+    }
+
+    /// Check the role is well-formed.
+    ///
+    /// # Errors
+    /// Returns a message describing the first problem found.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let name = self.name.trim();
+        if name.is_empty() || name.chars().count() > MAX_ROLE_NAME_LEN {
+            return Err("Role name must be 1 to 64 characters");
+        }
+        if self.permissions.is_empty() {
+            return Err("A role must grant at least one permission");
+        }
+        if self.permissions.iter().any(|p| !p.is_lot_scoped()) {
+            return Err(
+                "Custom roles may only grant manage_lots, view_all_bookings, manage_bookings \
+                 and book_for_visitors",
+            );
+        }
+        Ok(())
+    }
+}
+
+/// The lots a granted permission applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LotScope {
+    /// Every lot
+    All,
+    /// Only these lots
+    Lots(BTreeSet<Uuid>),
+}
+
+impl LotScope {
+    /// Whether the scope covers `lot_id`
+    pub fn allows(&self, lot_id: Uuid) -> bool {
+        match self {
+            Self::All => true,
+            Self::Lots(lots) => lots.contains(&lot_id),
+        }
+    }
+
+    /// Widen the scope to also cover `other`
+    pub fn extend(&mut self, other: Self) {
+        match (&mut *self, other) {
+            (Self::All, _) => {}
+            (_, Self::All) => *self = Self::All,
+            (Self::Lots(lots), Self::Lots(more)) => lots.extend(more),
+        }
+    }
+}
+
+/// Everything a user may do: their built-in role's permissions plus the
+/// grants of their custom roles.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ResolvedPermissions {
+    grants: BTreeMap<Permission, LotScope>,
+}
+
+impl ResolvedPermissions {
+    /// The permissions of a built-in role, all on every lot
+    pub fn for_role(role: &UserRole) -> Self {
+        let mut resolved = Self::default();
+        for permission in role.permissions() {
+            resolved.grant(*permission, LotScope::All);
+        }
+        resolved
+    }
+
+    /// Add `permission` on `scope`, widening any existing grant
+    pub fn grant(&mut self, permission: Permission, scope: LotScope) {
+        match self.grants.get_mut(&permission) {
+            Some(existing) => existing.extend(scope),
+            None => {
+                self.grants.insert(permission, scope);
+            }
+        }
+    }
+
+    /// Add the grants of a custom role
+    pub fn grant_role(&mut self, role: &Role) {
+        for permission in &role.permissions {
+            self.grant(*permission, role.scope());
+        }
+    }
+
+    /// The lots `permission` is held on, if held at all
+    pub fn scope(&self, permission: Permission) -> Option<&LotScope> {
+        self.grants.get(&permission)
+    }
+
+    /// Whether `permission` is held on at least one lot
+    pub fn has_any(&self, permission: Permission) -> bool {
+        self.grants.contains_key(&permission)
+    }
+
+    /// Whether `permission` is held on every lot
+    pub fn has_global(&self, permission: Permission) -> bool {
+        matches!(self.scope(permission), Some(LotScope::All))
+    }
+
+    /// Whether `permission` is held on `lot_id`
+    pub fn allows_lot(&self, permission: Permission, lot_id: Uuid) -> bool {
+        self.scope(permission)
+            .is_some_and(|scope| scope.allows(lot_id))
+    }
+
+    /// Whether no permission is held at all
+    pub fn is_empty(&self) -> bool {
+        self.grants.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom_role(permissions: Vec<Permission>, lot_ids: Vec<Uuid>) -> Role {
+        Role {
+            id: Uuid::new_v4(),
+            name: "Lot Manager".to_string(),
+            description: None,
+            permissions,
+            lot_ids,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_admin_roles_hold_every_permission() {
+        for role in [UserRole::Admin, UserRole::SuperAdmin] {
+            for permission in Permission::ALL {
+                assert!(role.has_permission(permission), "{role:?} {permission}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_regular_roles_hold_no_permission() {
+        assert!(UserRole::User.permissions().is_empty());
+        for role in [UserRole::User, UserRole::Premium] {
+            assert!(!role.has_permission(Permission::ViewAllBookings));
+        }
+    }
+
+    #[test]
+    fn test_premium_only_books_for_visitors() {
+        assert_eq!(
+            UserRole::Premium.permissions(),
+            &[Permission::BookForVisitors]
+        );
+        assert!(!UserRole::User.has_permission(Permission::BookForVisitors));
+        assert!(UserRole::Admin.has_permission(Permission::BookForVisitors));
+    }
+
+    #[test]
+    fn test_reporter_only_views() {
+        assert_eq!(
+            UserRole::Reporter.permissions(),
+            &[Permission::ViewAllBookings]
+        );
+        for permission in [
+            Permission::ManageUsers,
+            Permission::ManageLots,
+            Permission::ManageConfig,
+            Permission::ManageBookings,
+        ] {
+            assert!(!UserRole::Reporter.has_permission(permission));
+        }
+    }
+
+    #[test]
+    fn test_permission_wire_names_match_serde() {
+        for permission in Permission::ALL {
+            let json = serde_json::to_string(&permission).unwrap();
+            assert_eq!(json, format!("\"{}\"", permission.as_str()));
+            let back: Permission = serde_json::from_str(&json).unwrap();
+            assert_eq!(back, permission);
+        }
+    }
+
+    #[test]
+    fn test_role_validate() {
+        let lot = Uuid::new_v4();
+        assert!(
+            custom_role(vec![Permission::ManageBookings], vec![lot])
+                .validate()
+                .is_ok()
+        );
+        assert!(custom_role(vec![], vec![lot]).validate().is_err());
+        assert!(
+            custom_role(vec![Permission::ManageUsers], vec![])
+                .validate()
+                .is_err()
+        );
+        let mut unnamed = custom_role(vec![Permission::ManageLots], vec![]);
+        unnamed.name = "  ".to_string();
+        assert!(unnamed.validate().is_err());
+    }
+
+    #[test]
+    fn test_resolved_permissions_scope_custom_roles_to_lots() {
+        let (lot_a, lot_b) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut resolved = ResolvedPermissions::for_role(&UserRole::User);
+        assert!(resolved.is_empty());
+
+        resolved.grant_role(&custom_role(
+            vec![Permission::ManageBookings, Permission::ManageLots],
+            vec![lot_a],
+        ));
+        assert!(resolved.allows_lot(Permission::ManageBookings, lot_a));
+        assert!(!resolved.allows_lot(Permission::ManageBookings, lot_b));
+        assert!(resolved.has_any(Permission::ManageLots));
+        assert!(!resolved.has_global(Permission::ManageLots));
+        assert!(!resolved.has_any(Permission::ViewAllBookings));
+
+        // Grants from several roles add up; an unscoped grant covers all lots
+        resolved.grant_role(&custom_role(vec![Permission::ManageBookings], vec![lot_b]));
+        assert!(resolved.allows_lot(Permission::ManageBookings, lot_b));
+        resolved.grant_role(&custom_role(vec![Permission::ManageLots], vec![]));
+        assert!(resolved.has_global(Permission::ManageLots));
+
+        let admin = ResolvedPermissions::for_role(&UserRole::Admin);
+        assert!(Permission::ALL.iter().all(|p| admin.has_global(*p)));
+    }
+}
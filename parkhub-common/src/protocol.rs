@@ -6,7 +6,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::models::{AuthTokens, Booking, Notification, SlotBookingInfo, SlotStatus, User};
+use crate::error_code::ErrorCode;
+use crate::models::{
+    AuthTokens, Booking, BookingStatus, Notification, SlotBookingInfo, SlotStatus, User,
+};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // API REQUEST/RESPONSE
@@ -32,7 +35,7 @@ impl<T> ApiResponse<T> {
         }
     }
 
-    pub fn error(code: impl Into<String>, message: impl Into<String>) -> Self {
+    pub fn error(code: impl Into<ErrorCode>, message: impl Into<String>) -> Self {
         Self {
             success: false,
             data: None,
@@ -50,7 +53,8 @@ impl<T> ApiResponse<T> {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
 pub struct ApiError {
-    pub code: String,
+    #[cfg_attr(feature = "gen-types", ts(type = "string"))]
+    pub code: ErrorCode,
     pub message: String,
     pub details: Option<serde_json::Value>,
 }
@@ -63,6 +67,9 @@ pub struct ResponseMeta {
     pub per_page: Option<i32>,
     pub total: Option<i32>,
     pub total_pages: Option<i32>,
+    /// Cursor of the following page; `None` on the last page.
+    #[serde(default)]
+    pub next_cursor: Option<String>,
 }
 
 /// Paginated response
@@ -74,6 +81,9 @@ pub struct PaginatedResponse<T> {
     pub per_page: i32,
     pub total: i32,
     pub total_pages: i32,
+    /// Cursor of the following page; `None` on the last page.
+    #[serde(default)]
+    pub next_cursor: Option<String>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -127,6 +137,14 @@ pub struct ServerInfo {
     pub port: u16,
     pub tls: bool,
     pub fingerprint: Option<String>,
+    /// Organization the server belongs to. Servers of one organization
+    /// serve the same data, so a standby can stand in for the primary.
+    #[serde(default)]
+    pub organization: Option<String>,
+    /// Whether the server is a standby, used for reads while the
+    /// organization's primary is unreachable
+    #[serde(default)]
+    pub standby: bool,
 }
 
 /// Server handshake request from client
@@ -144,6 +162,148 @@ pub struct HandshakeResponse {
     pub protocol_version: String,
     pub requires_auth: bool,
     pub certificate_fingerprint: String,
+    /// How the client may use this server given both protocol versions
+    #[serde(default)]
+    pub compatibility: CompatibilityMode,
+    /// Server modules and feature flags that are enabled. Empty when the server predates
+    /// capability negotiation, in which case every feature is assumed.
+    #[serde(default)]
+    pub features: Vec<String>,
+}
+
+/// How a client may use a server whose protocol version differs from its own
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityMode {
+    /// Same major and minor version: everything works
+    #[default]
+    Full,
+    /// Same major version: shared features work, newer ones are unavailable
+    Compatible,
+    /// Different major version: data can be viewed but not changed
+    ReadOnly,
+}
+
+impl CompatibilityMode {
+    /// Whether the client may create, change or delete data
+    pub const fn allows_writes(self) -> bool {
+        !matches!(self, Self::ReadOnly)
+    }
+}
+
+/// Negotiate the mode for a client speaking `client_version` with a server
+/// speaking `server_version` (both `major.minor.patch`).
+///
+/// Returns `None` when either version cannot be parsed.
+pub fn negotiate_protocol(client_version: &str, server_version: &str) -> Option<CompatibilityMode> {
+    fn major_minor(version: &str) -> Option<(u32, u32)> {
+        let mut parts = version.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        parts.next()?.parse::<u32>().ok()?;
+        parts.next().is_none().then_some((major, minor))
+    }
+
+    let (client_major, client_minor) = major_minor(client_version)?;
+    let (server_major, server_minor) = major_minor(server_version)?;
+    Some(if client_major != server_major {
+        CompatibilityMode::ReadOnly
+    } else if client_minor != server_minor {
+        CompatibilityMode::Compatible
+    } else {
+        CompatibilityMode::Full
+    })
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// BOOKING COUNTDOWN
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Server-computed timing for a booking.
+///
+/// Clients render countdowns from the relative second counts rather than from
+/// `end_time` and their local clock, so a skewed client clock does not shift
+/// the displayed time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
+pub struct BookingCountdown {
+    /// Server clock when the response was built
+    pub server_time: DateTime<Utc>,
+    /// Seconds until the booking starts (0 once it has started)
+    pub starts_in_seconds: i64,
+    /// Seconds until the booking ends (0 once it has ended)
+    pub remaining_seconds: i64,
+    /// The booking is running or upcoming and ends within the server's
+    /// expiry warning window
+    pub expiring_soon: bool,
+}
+
+impl BookingCountdown {
+    /// Countdown for `booking` as seen at `now`, flagging bookings that end
+    /// within `warning_minutes`.
+    #[must_use]
+    pub fn new(booking: &Booking, now: DateTime<Utc>, warning_minutes: u32) -> Self {
+        let remaining_seconds = (booking.end_time - now).num_seconds().max(0);
+        let live = matches!(
+            booking.status,
+            BookingStatus::Pending | BookingStatus::Confirmed | BookingStatus::Active
+        );
+        Self {
+            server_time: now,
+            starts_in_seconds: (booking.start_time - now).num_seconds().max(0),
+            remaining_seconds,
+            expiring_soon: live
+                && remaining_seconds > 0
+                && remaining_seconds <= i64::from(warning_minutes) * 60,
+        }
+    }
+}
+
+/// A booking as returned by the booking endpoints, with its countdown.
+///
+/// The booking fields are flattened, so clients that only know [`Booking`]
+/// can still deserialize the response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
+pub struct BookingWithCountdown {
+    #[serde(flatten)]
+    pub booking: Booking,
+    pub countdown: BookingCountdown,
+}
+
+impl BookingWithCountdown {
+    #[must_use]
+    pub fn new(booking: Booking, now: DateTime<Utc>, warning_minutes: u32) -> Self {
+        let countdown = BookingCountdown::new(&booking, now, warning_minutes);
+        Self { booking, countdown }
+    }
+}
+
+/// Server clock reading (`GET /api/v1/time`)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
+pub struct ServerTime {
+    pub server_time: DateTime<Utc>,
+    /// Milliseconds since the Unix epoch, for offset calculation
+    pub unix_millis: i64,
+    /// Minutes before a booking ends at which `expiring_soon` is set
+    pub expiry_warning_minutes: u32,
+}
+
+/// Server build and capabilities (`GET /api/v1/version`)
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[cfg_attr(feature = "gen-types", derive(ts_rs::TS), ts(export))]
+pub struct ServerVersion {
+    /// Semantic version of the server build
+    pub version: String,
+    /// Commit the server was built from, or `unknown`
+    pub git_hash: String,
+    /// Client/server protocol version, see [`PROTOCOL_VERSION`](crate::PROTOCOL_VERSION)
+    pub protocol_version: String,
+    /// Server modules that are enabled
+    pub features: Vec<String>,
+    /// Storage engine holding the data, e.g. `redb`
+    pub storage_backend: String,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -212,6 +372,25 @@ pub struct ServerStatus {
     pub total_users: u32,
     pub total_bookings: u32,
     pub database_size_bytes: u64,
+    #[serde(default)]
+    pub backup: BackupStatus,
+}
+
+/// Where scheduled automatic backups stand
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackupStatus {
+    /// Whether automatic backups are on
+    pub enabled: bool,
+    /// Cron expression (with seconds, UTC) backups run on
+    pub schedule: String,
+    /// When the newest backup kept was taken
+    pub last_backup_at: Option<DateTime<Utc>>,
+    /// Size of the newest backup kept in bytes
+    pub last_backup_bytes: Option<u64>,
+    /// Number of backups kept
+    pub backup_count: u32,
+    /// When the last scheduled backup failed, if it did
+    pub last_failure_at: Option<DateTime<Utc>>,
 }
 
 #[cfg(test)]
@@ -276,6 +455,7 @@ mod tests {
             per_page: 10,
             total: 2,
             total_pages: 1,
+            next_cursor: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         let parsed: PaginatedResponse<String> = serde_json::from_str(&json).unwrap();
@@ -293,6 +473,7 @@ mod tests {
             per_page: 10,
             total: 0,
             total_pages: 0,
+            next_cursor: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         let parsed: PaginatedResponse<i32> = serde_json::from_str(&json).unwrap();
@@ -343,6 +524,8 @@ mod tests {
             port: 7878,
             tls: true,
             fingerprint: Some("AA:BB:CC".into()),
+            organization: None,
+            standby: false,
         };
         let json = serde_json::to_string(&info).unwrap();
         let parsed: ServerInfo = serde_json::from_str(&json).unwrap();
@@ -352,6 +535,15 @@ mod tests {
         assert_eq!(parsed.fingerprint.unwrap(), "AA:BB:CC");
     }
 
+    #[test]
+    fn server_info_without_organization_fields_is_a_primary() {
+        let json = r#"{"name":"old","version":"1.0","protocol_version":"1.0.0",
+            "host":"10.0.0.2","port":8443,"tls":true,"fingerprint":null}"#;
+        let info: ServerInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(info.organization, None);
+        assert!(!info.standby);
+    }
+
     #[test]
     fn server_info_no_fingerprint() {
         let info = ServerInfo {
@@ -362,6 +554,8 @@ mod tests {
             port: 8080,
             tls: false,
             fingerprint: None,
+            organization: None,
+            standby: false,
         };
         let json = serde_json::to_string(&info).unwrap();
         assert!(json.contains("\"fingerprint\":null"));
@@ -383,6 +577,8 @@ mod tests {
             protocol_version: "1.0.0".into(),
             requires_auth: true,
             certificate_fingerprint: "AA:BB".into(),
+            compatibility: CompatibilityMode::Full,
+            features: vec!["bookings".into()],
         };
         let json = serde_json::to_string(&resp).unwrap();
         let parsed: HandshakeResponse = serde_json::from_str(&json).unwrap();
@@ -458,6 +654,7 @@ mod tests {
             total_users: 100,
             total_bookings: 500,
             database_size_bytes: 1_048_576,
+            backup: BackupStatus::default(),
         };
         let json = serde_json::to_string(&status).unwrap();
         let parsed: ServerStatus = serde_json::from_str(&json).unwrap();
@@ -474,6 +671,7 @@ mod tests {
             per_page: None,
             total: None,
             total_pages: None,
+            next_cursor: None,
         };
         let json = serde_json::to_string(&meta).unwrap();
         let parsed: ResponseMeta = serde_json::from_str(&json).unwrap();
@@ -487,6 +685,7 @@ mod tests {
             per_page: Some(25),
             total: Some(100),
             total_pages: Some(4),
+            next_cursor: None,
         };
         let json = serde_json::to_string(&meta).unwrap();
         let parsed: ResponseMeta = serde_json::from_str(&json).unwrap();
@@ -575,6 +774,7 @@ mod tests {
             per_page: 10,
             total: 3,
             total_pages: 1,
+            next_cursor: None,
         };
         let json = serde_json::to_string(&pr).unwrap();
         let back: PaginatedResponse<i32> = serde_json::from_str(&json).unwrap();
@@ -593,6 +793,7 @@ mod tests {
             per_page: 20,
             total: 0,
             total_pages: 0,
+            next_cursor: None,
         };
         assert!(pr.items.is_empty());
         assert_eq!(pr.total, 0);
@@ -607,6 +808,7 @@ mod tests {
             per_page: None,
             total: None,
             total_pages: None,
+            next_cursor: None,
         };
         let json = serde_json::to_string(&meta).unwrap();
         let back: ResponseMeta = serde_json::from_str(&json).unwrap();
@@ -621,6 +823,7 @@ mod tests {
             per_page: Some(25),
             total: Some(100),
             total_pages: Some(4),
+            next_cursor: None,
         };
         let json = serde_json::to_string(&meta).unwrap();
         let back: ResponseMeta = serde_json::from_str(&json).unwrap();
@@ -687,7 +890,7 @@ mod tests {
     #[test]
     fn test_ws_message_error_tag() {
         let api_err = ApiError {
-            code: "SERVER_ERROR".to_string(),
+            code: ErrorCode::ServerError,
             message: "Something went wrong".to_string(),
             details: None,
         };
@@ -719,12 +922,43 @@ mod tests {
             protocol_version: "1.0.0".to_string(),
             requires_auth: true,
             certificate_fingerprint: "aa:bb:cc".to_string(),
+            compatibility: CompatibilityMode::ReadOnly,
+            features: Vec::new(),
         };
         let json = serde_json::to_string(&resp).unwrap();
         let back: HandshakeResponse = serde_json::from_str(&json).unwrap();
         assert_eq!(back.server_name, "ParkHub");
         assert!(back.requires_auth);
         assert_eq!(back.certificate_fingerprint, "aa:bb:cc");
+        assert_eq!(back.compatibility, CompatibilityMode::ReadOnly);
+    }
+
+    #[test]
+    fn test_handshake_response_without_negotiation_fields() {
+        let json = r#"{"server_name":"ParkHub","server_version":"1.0.0","protocol_version":"1.0.0","requires_auth":true,"certificate_fingerprint":""}"#;
+        let back: HandshakeResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(back.compatibility, CompatibilityMode::Full);
+        assert!(back.features.is_empty());
+    }
+
+    #[test]
+    fn test_negotiate_protocol() {
+        assert_eq!(
+            negotiate_protocol("1.0.0", "1.0.3"),
+            Some(CompatibilityMode::Full)
+        );
+        assert_eq!(
+            negotiate_protocol("1.2.0", "1.0.0"),
+            Some(CompatibilityMode::Compatible)
+        );
+        assert_eq!(
+            negotiate_protocol("2.0.0", "1.0.0"),
+            Some(CompatibilityMode::ReadOnly)
+        );
+        assert_eq!(negotiate_protocol("0.0.1-invalid", "1.0.0"), None);
+        assert_eq!(negotiate_protocol("1.0", "1.0.0"), None);
+        assert!(!CompatibilityMode::ReadOnly.allows_writes());
+        assert!(CompatibilityMode::Compatible.allows_writes());
     }
 
     // ── Copilot: ServerInfo ─────────────────────────────────────────────────
@@ -739,6 +973,8 @@ mod tests {
             port: 8080,
             tls: true,
             fingerprint: Some("fp123".to_string()),
+            organization: None,
+            standby: false,
         };
         let json = serde_json::to_string(&info).unwrap();
         let back: ServerInfo = serde_json::from_str(&json).unwrap();
@@ -758,6 +994,7 @@ mod tests {
             total_users: 42,
             total_bookings: 1024,
             database_size_bytes: 204_800,
+            backup: BackupStatus::default(),
         };
         let json = serde_json::to_string(&status).unwrap();
         let back: ServerStatus = serde_json::from_str(&json).unwrap();
@@ -787,4 +1024,106 @@ mod tests {
         assert!(back.enable_tls);
         assert!(!back.enable_mdns);
     }
+
+    // ── Booking countdown ───────────────────────────────────────────────────
+
+    fn booking_between(start: DateTime<Utc>, end: DateTime<Utc>) -> Booking {
+        use crate::models::{BookingPricing, Vehicle};
+        use uuid::Uuid;
+        Booking {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            lot_id: Uuid::new_v4(),
+            slot_id: Uuid::new_v4(),
+            slot_number: 7,
+            floor_name: "Ground".to_string(),
+            vehicle: Vehicle {
+                id: Uuid::new_v4(),
+                user_id: Uuid::new_v4(),
+                license_plate: "M-AB 123".to_string(),
+                make: None,
+                model: None,
+                color: None,
+                vehicle_type: crate::models::VehicleType::Car,
+                fuel_type: crate::models::FuelType::default(),
+                is_default: true,
+                created_at: start,
+            },
+            start_time: start,
+            end_time: end,
+            status: BookingStatus::Confirmed,
+            pricing: BookingPricing {
+                base_price: 0.0,
+                discount: 0.0,
+                tax: 0.0,
+                total: 0.0,
+                currency: "EUR".to_string(),
+                payment_status: crate::models::PaymentStatus::Pending,
+                payment_method: None,
+                cancellation_fee: None,
+            },
+            created_at: start,
+            updated_at: start,
+            check_in_time: None,
+            check_out_time: None,
+            qr_code: None,
+            notes: None,
+            tenant_id: None,
+            tags: std::collections::BTreeMap::new(),
+            booked_for: None,
+        }
+    }
+
+    #[test]
+    fn test_booking_countdown_upcoming_and_running() {
+        let now = Utc::now();
+        let upcoming = booking_between(
+            now + chrono::Duration::minutes(30),
+            now + chrono::Duration::minutes(90),
+        );
+        let c = BookingCountdown::new(&upcoming, now, 15);
+        assert_eq!(c.server_time, now);
+        assert_eq!(c.starts_in_seconds, 1800);
+        assert_eq!(c.remaining_seconds, 5400);
+        assert!(!c.expiring_soon);
+
+        let running = booking_between(
+            now - chrono::Duration::hours(1),
+            now + chrono::Duration::minutes(10),
+        );
+        let c = BookingCountdown::new(&running, now, 15);
+        assert_eq!(c.starts_in_seconds, 0);
+        assert_eq!(c.remaining_seconds, 600);
+        assert!(c.expiring_soon);
+    }
+
+    #[test]
+    fn test_booking_countdown_ended_or_cancelled_never_warns() {
+        let now = Utc::now();
+        let ended = booking_between(
+            now - chrono::Duration::hours(2),
+            now - chrono::Duration::hours(1),
+        );
+        let c = BookingCountdown::new(&ended, now, 15);
+        assert_eq!(c.remaining_seconds, 0);
+        assert!(!c.expiring_soon);
+
+        let mut cancelled = booking_between(now, now + chrono::Duration::minutes(5));
+        cancelled.status = BookingStatus::Cancelled;
+        assert!(!BookingCountdown::new(&cancelled, now, 15).expiring_soon);
+    }
+
+    #[test]
+    fn test_booking_with_countdown_flattens_booking_fields() {
+        let now = Utc::now();
+        let booking = booking_between(now, now + chrono::Duration::hours(1));
+        let id = booking.id;
+        let json = serde_json::to_value(BookingWithCountdown::new(booking, now, 15)).unwrap();
+        assert_eq!(json["id"], id.to_string());
+        assert_eq!(json["countdown"]["remaining_seconds"], 3600);
+
+        // Clients that only know `Booking` still parse the response.
+        let plain: Booking = serde_json::from_value(json).unwrap();
+        assert_eq!(plain.id, id);
+    }
 }
@@ -15,7 +15,7 @@
 //! `parkhub-common/tests/validation_properties.rs` for the proptest
 //! coverage.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
 
 // ───────────────────────────────────────────────────────────────────────────
 // Email
@@ -123,8 +123,9 @@ pub fn is_valid_e164_phone(candidate: &str) -> bool {
 /// Minimum booking length in minutes (mirrors the server-side rule).
 pub const MIN_BOOKING_MINUTES: i32 = 15;
 
-/// Maximum booking length in minutes (24 hours).
-pub const MAX_BOOKING_MINUTES: i32 = 24 * 60;
+/// Maximum booking length in minutes (14 days). Bookings may span several
+/// calendar days; pricing and daily limits are applied per day.
+pub const MAX_BOOKING_MINUTES: i32 = 14 * 24 * 60;
 
 /// Returns `true` when `minutes` is a valid booking duration.
 ///
@@ -188,6 +189,51 @@ impl TimeRange {
     pub fn contains(&self, instant: DateTime<Utc>) -> bool {
         self.start <= instant && instant < self.end
     }
+
+    /// The range widened by `by` on both sides. A range padded by a lot's
+    /// buffer overlaps exactly the bookings that end or start less than
+    /// `by` away from it. A negative `by` leaves the range as it is.
+    #[must_use]
+    pub fn padded(&self, by: chrono::Duration) -> Self {
+        let by = by.max(chrono::Duration::zero());
+        Self {
+            start: self.start - by,
+            end: self.end + by,
+        }
+    }
+
+    /// The whole calendar day `date` (UTC midnight to midnight).
+    #[must_use]
+    pub fn day(date: NaiveDate) -> Self {
+        let start = date.and_time(NaiveTime::MIN).and_utc();
+        Self {
+            start,
+            end: start + chrono::Duration::days(1),
+        }
+    }
+
+    /// Returns `true` if any part of the range falls on calendar day
+    /// `date` (UTC). An overnight range touches both days.
+    #[must_use]
+    pub fn touches_day(&self, date: NaiveDate) -> bool {
+        self.overlaps(&Self::day(date))
+    }
+
+    /// Split the range at every UTC midnight it crosses. Each piece is
+    /// paired with its calendar day; the pieces are contiguous and in
+    /// order, and an invalid range yields no pieces.
+    #[must_use]
+    pub fn split_by_day(&self) -> Vec<(NaiveDate, Self)> {
+        let mut pieces = Vec::new();
+        let mut cursor = self.start;
+        while cursor < self.end {
+            let date = cursor.date_naive();
+            let end = Self::day(date).end.min(self.end);
+            pieces.push((date, Self { start: cursor, end }));
+            cursor = end;
+        }
+        pieces
+    }
 }
 
 #[cfg(test)]
@@ -246,7 +292,9 @@ mod tests {
         assert!(!is_valid_booking_duration(14));
         assert!(is_valid_booking_duration(15));
         assert!(is_valid_booking_duration(24 * 60));
-        assert!(!is_valid_booking_duration(24 * 60 + 1));
+        assert!(is_valid_booking_duration(24 * 60 + 1));
+        assert!(is_valid_booking_duration(MAX_BOOKING_MINUTES));
+        assert!(!is_valid_booking_duration(MAX_BOOKING_MINUTES + 1));
         assert!(!is_valid_booking_duration(-1));
     }
 
@@ -270,4 +318,49 @@ mod tests {
         assert!(!r1.overlaps(&r3));
         assert!(!r3.overlaps(&r1));
     }
+
+    #[test]
+    fn time_range_padded_catches_close_neighbours() {
+        let t = |h: u32, m: u32| Utc.with_ymd_and_hms(2026, 4, 17, h, m, 0).unwrap();
+        let booked = TimeRange::new(t(10, 0), t(12, 0)).unwrap();
+        let next = TimeRange::new(t(12, 10), t(13, 0)).unwrap();
+        let buffer = chrono::Duration::minutes(15);
+        assert!(!booked.overlaps(&next));
+        assert!(next.padded(buffer).overlaps(&booked));
+        assert!(booked.padded(buffer).overlaps(&next));
+        let later = TimeRange::new(t(12, 15), t(13, 0)).unwrap();
+        assert!(!later.padded(buffer).overlaps(&booked));
+        assert_eq!(booked.padded(-buffer), booked);
+    }
+
+    #[test]
+    fn time_range_split_by_day_across_midnight() {
+        let start = Utc.with_ymd_and_hms(2026, 4, 17, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 4, 19, 6, 0, 0).unwrap();
+        let range = TimeRange::new(start, end).unwrap();
+
+        let pieces = range.split_by_day();
+        let d = |day: u32| NaiveDate::from_ymd_opt(2026, 4, day).unwrap();
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[0].0, d(17));
+        assert_eq!(pieces[0].1.duration().num_hours(), 2);
+        assert_eq!(pieces[1].0, d(18));
+        assert_eq!(pieces[1].1, TimeRange::day(d(18)));
+        assert_eq!(pieces[2].0, d(19));
+        assert_eq!(pieces[2].1.duration().num_hours(), 6);
+
+        assert!(range.touches_day(d(17)));
+        assert!(range.touches_day(d(19)));
+        assert!(!range.touches_day(d(16)));
+        assert!(!range.touches_day(d(20)));
+    }
+
+    #[test]
+    fn time_range_ending_at_midnight_stays_on_one_day() {
+        let start = Utc.with_ymd_and_hms(2026, 4, 17, 20, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2026, 4, 18, 0, 0, 0).unwrap();
+        let range = TimeRange::new(start, end).unwrap();
+        assert_eq!(range.split_by_day().len(), 1);
+        assert!(!range.touches_day(NaiveDate::from_ymd_opt(2026, 4, 18).unwrap()));
+    }
 }
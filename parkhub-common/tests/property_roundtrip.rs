@@ -106,6 +106,7 @@ fn arb_user_role() -> impl Strategy<Value = UserRole> {
     prop_oneof![
         Just(UserRole::User),
         Just(UserRole::Premium),
+        Just(UserRole::Reporter),
         Just(UserRole::Admin),
         Just(UserRole::SuperAdmin),
     ]
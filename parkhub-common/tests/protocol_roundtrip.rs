@@ -22,7 +22,7 @@ fn arb_api_error() -> impl Strategy<Value = ApiError> {
         any::<Option<String>>(),
     )
         .prop_map(|(code, message, details)| ApiError {
-            code,
+            code: code.into(),
             message,
             details: details.map(Value::String),
         })
@@ -34,13 +34,17 @@ fn arb_response_meta() -> impl Strategy<Value = ResponseMeta> {
         proptest::option::of(any::<i32>()),
         proptest::option::of(any::<i32>()),
         proptest::option::of(any::<i32>()),
+        proptest::option::of(arb_small_string()),
     )
-        .prop_map(|(page, per_page, total, total_pages)| ResponseMeta {
-            page,
-            per_page,
-            total,
-            total_pages,
-        })
+        .prop_map(
+            |(page, per_page, total, total_pages, next_cursor)| ResponseMeta {
+                page,
+                per_page,
+                total,
+                total_pages,
+                next_cursor,
+            },
+        )
 }
 
 fn arb_login_request() -> impl Strategy<Value = LoginRequest> {
@@ -83,6 +87,7 @@ proptest! {
         prop_assert_eq!(meta.per_page, decoded.per_page);
         prop_assert_eq!(meta.total, decoded.total);
         prop_assert_eq!(meta.total_pages, decoded.total_pages);
+        prop_assert_eq!(meta.next_cursor, decoded.next_cursor);
     }
 
     #[test]
@@ -9,6 +9,9 @@ fn main() {
     // when hooks are already installed (.git/hooks/pre-commit exists).
     print_local_ci_hint();
 
+    // Commit hash for `GET /api/v1/version` and the startup banner
+    emit_git_sha();
+
     // Only compile Slint UI when GUI feature is enabled
     #[cfg(feature = "gui")]
     {
@@ -51,6 +54,41 @@ fn main() {
     }
 }
 
+/// Set `GIT_SHA` from the git checkout unless the build environment (CI,
+/// Dockerfile) already provides it. Outside a checkout, or without git, it
+/// stays unset and the server reports `unknown`.
+fn emit_git_sha() {
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    println!("cargo:rerun-if-env-changed=GIT_SHA");
+    if std::env::var_os("GIT_SHA").is_some() {
+        return;
+    }
+
+    // Rebuild when HEAD moves to another commit
+    if let Some(manifest_dir) = std::env::var_os("CARGO_MANIFEST_DIR") {
+        let git_dir = PathBuf::from(manifest_dir).join("..").join(".git");
+        for path in [git_dir.join("HEAD"), git_dir.join("refs").join("heads")] {
+            if path.exists() {
+                println!("cargo:rerun-if-changed={}", path.display());
+            }
+        }
+    }
+
+    let output = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .output();
+    if let Ok(output) = output
+        && output.status.success()
+    {
+        let sha = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !sha.is_empty() {
+            println!("cargo:rustc-env=GIT_SHA={sha}");
+        }
+    }
+}
+
 /// Print a one-time-per-build hint reminding contributors to install
 /// the local-first CI git hooks (lefthook). No-op in CI environments
 /// and when the pre-push hook is already installed.
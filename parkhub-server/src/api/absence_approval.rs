@@ -21,9 +21,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode};
 
-use super::{AuthUser, SharedState};
+use super::{AuthUser, SharedState, check_admin};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // TYPES
@@ -169,7 +169,7 @@ pub async fn submit_absence_request(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "Dates must be in YYYY-MM-DD format",
             )),
         );
@@ -179,7 +179,7 @@ pub async fn submit_absence_request(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "start_date must not be after end_date",
             )),
         );
@@ -188,14 +188,20 @@ pub async fn submit_absence_request(
     if !is_valid_absence_type(&req.absence_type) {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_INPUT", "Invalid absence type")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "Invalid absence type",
+            )),
         );
     }
 
     if req.reason.trim().is_empty() {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_INPUT", "Reason is required")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "Reason is required",
+            )),
         );
     }
 
@@ -235,7 +241,10 @@ pub async fn submit_absence_request(
             tracing::error!("Failed to serialize absence request: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Serialization error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Serialization error",
+                )),
             );
         }
     };
@@ -245,7 +254,7 @@ pub async fn submit_absence_request(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to save absence request",
             )),
         );
@@ -285,10 +294,13 @@ pub async fn list_pending_absences(
     let state_guard = state.read().await;
 
     // Verify admin
-    if !is_admin(&state_guard, &auth_user).await {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -327,10 +339,13 @@ pub async fn approve_absence(
 ) -> (StatusCode, Json<ApiResponse<AbsenceRequest>>) {
     let state_guard = state.read().await;
 
-    if !is_admin(&state_guard, &auth_user).await {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -340,7 +355,10 @@ pub async fn approve_absence(
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Absence request not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Absence request not found",
+                )),
             );
         }
     };
@@ -350,7 +368,10 @@ pub async fn approve_absence(
         Err(_) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Corrupt request data")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Corrupt request data",
+                )),
             );
         }
     };
@@ -359,7 +380,7 @@ pub async fn approve_absence(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_STATE",
+                ErrorCode::InvalidState,
                 "Request is not pending",
             )),
         );
@@ -407,10 +428,13 @@ pub async fn reject_absence(
 ) -> (StatusCode, Json<ApiResponse<AbsenceRequest>>) {
     let state_guard = state.read().await;
 
-    if !is_admin(&state_guard, &auth_user).await {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -418,7 +442,7 @@ pub async fn reject_absence(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "Rejection reason is required",
             )),
         );
@@ -430,7 +454,10 @@ pub async fn reject_absence(
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Absence request not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Absence request not found",
+                )),
             );
         }
     };
@@ -440,7 +467,10 @@ pub async fn reject_absence(
         Err(_) => {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Corrupt request data")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Corrupt request data",
+                )),
             );
         }
     };
@@ -449,7 +479,7 @@ pub async fn reject_absence(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_STATE",
+                ErrorCode::InvalidState,
                 "Request is not pending",
             )),
         );
@@ -515,14 +545,6 @@ pub async fn my_absence_requests(
 // INTERNAL HELPERS
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Check if user is admin
-async fn is_admin(state: &crate::AppState, auth_user: &AuthUser) -> bool {
-    match state.db.get_user(&auth_user.user_id.to_string()).await {
-        Ok(Some(u)) => u.role == UserRole::Admin || u.role == UserRole::SuperAdmin,
-        _ => false,
-    }
-}
-
 /// Load a list of UUIDs from a setting key
 async fn load_id_list(db: &crate::db::Database, key: &str) -> Vec<Uuid> {
     match db.get_setting(key).await {
@@ -10,9 +10,9 @@ use serde::Deserialize;
 use uuid::Uuid;
 
 use parkhub_common::models::{Absence, AbsencePattern, AbsenceType};
-use parkhub_common::{ApiResponse, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode};
 
-use super::{AuthUser, SharedState};
+use super::{AuthUser, SharedState, check_admin};
 
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct AbsenceQuery {
@@ -53,7 +53,7 @@ pub async fn list_absences(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list absences",
                 )),
             )
@@ -90,7 +90,7 @@ pub async fn create_absence(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "Dates must be in YYYY-MM-DD format",
             )),
         );
@@ -100,7 +100,7 @@ pub async fn create_absence(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "start_date must not be after end_date",
             )),
         );
@@ -125,7 +125,7 @@ pub async fn create_absence(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to create absence",
                 )),
             )
@@ -153,14 +153,17 @@ pub async fn delete_absence(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Absence not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Absence not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error fetching absence: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -168,7 +171,7 @@ pub async fn delete_absence(
     if absence.user_id != auth_user.user_id {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     }
 
@@ -176,14 +179,14 @@ pub async fn delete_absence(
         Ok(true) => (StatusCode::OK, Json(ApiResponse::success(()))),
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Absence not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Absence not found")),
         ),
         Err(e) => {
             tracing::error!("Failed to delete absence: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to delete absence",
                 )),
             )
@@ -212,7 +215,7 @@ pub async fn list_team_absences(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list team absences",
                 )),
             )
@@ -246,7 +249,7 @@ pub async fn get_absence_pattern(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to get absence pattern",
                 )),
             )
@@ -276,7 +279,10 @@ pub async fn save_absence_pattern(
             tracing::error!("Failed to serialize absence pattern: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Serialization error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Serialization error",
+                )),
             );
         }
     };
@@ -288,7 +294,7 @@ pub async fn save_absence_pattern(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to save absence pattern",
                 )),
             )
@@ -327,34 +333,26 @@ pub async fn update_absence(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Absence not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Absence not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error fetching absence: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
     // Check ownership or admin
-    let Ok(Some(caller)) = state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    else {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
-        );
-    };
-    let is_admin = caller.role == UserRole::Admin || caller.role == UserRole::SuperAdmin;
-    if absence.user_id != auth_user.user_id && !is_admin {
+    if absence.user_id != auth_user.user_id && check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     }
 
@@ -366,7 +364,7 @@ pub async fn update_absence(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "INVALID_INPUT",
+                    ErrorCode::InvalidInput,
                     "start_date must be in YYYY-MM-DD format",
                 )),
             );
@@ -378,7 +376,7 @@ pub async fn update_absence(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "INVALID_INPUT",
+                    ErrorCode::InvalidInput,
                     "end_date must be in YYYY-MM-DD format",
                 )),
             );
@@ -393,7 +391,7 @@ pub async fn update_absence(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "start_date must not be after end_date",
             )),
         );
@@ -406,7 +404,7 @@ pub async fn update_absence(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to update absence",
                 )),
             )
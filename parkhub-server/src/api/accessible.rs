@@ -19,7 +19,7 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use parkhub_common::{ApiResponse, BookingStatus};
+use parkhub_common::{ApiResponse, BookingStatus, ErrorCode};
 
 use super::{AuthUser, check_admin};
 use crate::AppState;
@@ -104,14 +104,17 @@ pub async fn list_accessible_slots(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Lot not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Lot not found")),
             );
         }
         Err(e) => {
             tracing::error!("Failed to get lot {lot_id}: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to get lot")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to get lot",
+                )),
             );
         }
     }
@@ -122,7 +125,10 @@ pub async fn list_accessible_slots(
             tracing::error!("Failed to list slots for lot {lot_id}: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to list slots")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list slots",
+                )),
             );
         }
     };
@@ -165,8 +171,8 @@ pub async fn admin_set_slot_accessible(
     Json(req): Json<SetAccessibleRequest>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let mut slot = match state_guard.db.get_parking_slot(&slot_id).await {
@@ -174,14 +180,17 @@ pub async fn admin_set_slot_accessible(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Slot not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Slot not found")),
             );
         }
         Err(e) => {
             tracing::error!("Failed to get slot {slot_id}: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to get slot")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to get slot",
+                )),
             );
         }
     };
@@ -192,7 +201,10 @@ pub async fn admin_set_slot_accessible(
         tracing::error!("Failed to save slot {slot_id}: {e}");
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to update slot")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to update slot",
+            )),
         );
     }
 
@@ -218,8 +230,8 @@ pub async fn accessible_stats(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<AccessibleStats>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let lots = state_guard.db.list_parking_lots().await.unwrap_or_default();
@@ -307,7 +319,7 @@ pub async fn update_accessibility_needs(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_NEED",
+                ErrorCode::InvalidNeed,
                 "Invalid accessibility need. Valid values: wheelchair, reduced_mobility, visual, hearing, none",
             )),
         );
@@ -323,14 +335,17 @@ pub async fn update_accessibility_needs(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!("Failed to get user: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to get user")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to get user",
+                )),
             );
         }
     };
@@ -346,7 +361,7 @@ pub async fn update_accessibility_needs(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update accessibility needs",
             )),
         );
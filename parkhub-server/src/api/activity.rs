@@ -0,0 +1,216 @@
+//! Per-user activity feed.
+//!
+//! - `GET /api/v1/users/me/activity` — the caller's bookings, check-ins,
+//!   cancellations, payments and notifications as one feed, newest first
+//!
+//! Nothing is stored for the feed: it is put together on each request from
+//! the bookings, credit transactions and notifications of the caller, so it
+//! always matches them. Entries that concern a booking carry the booking as
+//! it is now, which lets the feed be filtered down to one booking.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use parkhub_common::{
+    ActivityBooking, ActivityEntry, ActivityKind, ApiResponse, Booking, BookingStatus,
+    CreditTransaction, ErrorCode, Notification,
+};
+
+use crate::db::{Database, PageQuery, time_key};
+use crate::requests::{ActivityFilters, ListParams};
+
+use super::{AuthUser, SharedState};
+
+/// `GET /api/v1/users/me/activity` — the caller's activity feed
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/activity",
+    tag = "Users",
+    summary = "My activity feed",
+    description = "Returns what happened to the caller's bookings (created, checked in, \
+        checked out, cancelled, no-show), their credit payments and their notifications as \
+        one feed, newest first. Always paged; `meta` holds the totals and the `next_cursor`. \
+        `sort` and `order` are ignored. Filter by `booking_id` to follow a single booking.",
+    security(("bearer_auth" = [])),
+    params(ListParams, ActivityFilters),
+    responses(
+        (status = 200, description = "One page of the feed", body = Vec<ActivityEntry>),
+        (status = 400, description = "Malformed cursor"),
+    )
+)]
+#[tracing::instrument(skip(state), fields(user_id = %auth_user.user_id))]
+pub async fn my_activity(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(params): Query<ListParams>,
+    Query(filters): Query<ActivityFilters>,
+) -> (StatusCode, Json<ApiResponse<Vec<ActivityEntry>>>) {
+    let Some(query) = PageQuery::new(
+        params.page(),
+        params.per_page(),
+        params.cursor.as_deref(),
+        true,
+    ) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidCursor,
+                "Malformed cursor",
+            )),
+        );
+    };
+    let state = state.read().await;
+
+    let entries = match load_feed(&state.db, auth_user.user_id).await {
+        Ok(entries) => entries,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to load activity");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to load activity",
+                )),
+            );
+        }
+    };
+    let page = crate::db::page(
+        entries,
+        |e| e.id.clone(),
+        |e| time_key(e.at),
+        |e| filters.matches(e),
+        &query,
+    );
+    let mut response = ApiResponse::success(page.items);
+    response.meta = Some(params.meta(page.total, page.next_cursor));
+    (StatusCode::OK, Json(response))
+}
+
+/// Every feed entry of `user_id`, unordered
+async fn load_feed(db: &Database, user_id: Uuid) -> anyhow::Result<Vec<ActivityEntry>> {
+    let bookings = db.list_bookings_by_user(&user_id.to_string()).await?;
+    let transactions = db.list_credit_transactions_for_user(user_id).await?;
+    let notifications = db.list_notifications_by_user(&user_id.to_string()).await?;
+    let lot_ids: HashSet<Uuid> = bookings.iter().map(|b| b.lot_id).collect();
+    let mut lot_names = HashMap::new();
+    for lot_id in lot_ids {
+        let name = db
+            .get_parking_lot(&lot_id.to_string())
+            .await?
+            .map(|lot| lot.name)
+            .unwrap_or_default();
+        lot_names.insert(lot_id, name);
+    }
+    Ok(feed(&bookings, &lot_names, transactions, notifications))
+}
+
+/// The feed entries of the given records, unordered
+fn feed(
+    bookings: &[Booking],
+    lot_names: &HashMap<Uuid, String>,
+    transactions: Vec<CreditTransaction>,
+    notifications: Vec<Notification>,
+) -> Vec<ActivityEntry> {
+    let details: HashMap<Uuid, ActivityBooking> = bookings
+        .iter()
+        .map(|b| {
+            let lot_name = lot_names.get(&b.lot_id).cloned().unwrap_or_default();
+            (b.id, activity_booking(b, lot_name))
+        })
+        .collect();
+    let entry = |kind: ActivityKind, record: Uuid, at: DateTime<Utc>, booking: Option<Uuid>| {
+        ActivityEntry {
+            id: format!("{}:{record}", kind_name(kind)),
+            kind,
+            at,
+            booking: booking.and_then(|id| details.get(&id).cloned()),
+            credits: None,
+            title: None,
+            message: None,
+        }
+    };
+
+    let mut entries = Vec::new();
+    for b in bookings {
+        entries.push(entry(
+            ActivityKind::BookingCreated,
+            b.id,
+            b.created_at,
+            Some(b.id),
+        ));
+        if let Some(at) = b.check_in_time {
+            entries.push(entry(ActivityKind::BookingCheckedIn, b.id, at, Some(b.id)));
+        }
+        if let Some(at) = b.check_out_time {
+            entries.push(entry(ActivityKind::BookingCheckedOut, b.id, at, Some(b.id)));
+        }
+        // Bookings do not record when they ended up cancelled or as a no-show;
+        // that was their last change
+        let ended = match b.status {
+            BookingStatus::Cancelled => Some(ActivityKind::BookingCancelled),
+            BookingStatus::NoShow => Some(ActivityKind::BookingNoShow),
+            _ => None,
+        };
+        if let Some(kind) = ended {
+            entries.push(entry(kind, b.id, b.updated_at, Some(b.id)));
+        }
+    }
+    for t in transactions {
+        entries.push(ActivityEntry {
+            credits: Some(t.amount),
+            title: t.description,
+            ..entry(ActivityKind::Payment, t.id, t.created_at, t.booking_id)
+        });
+    }
+    for n in notifications {
+        let booking_id = n
+            .data
+            .as_ref()
+            .and_then(|data| data.get("booking_id"))
+            .and_then(serde_json::Value::as_str)
+            .and_then(|id| id.parse().ok());
+        entries.push(ActivityEntry {
+            title: Some(n.title),
+            message: Some(n.message),
+            ..entry(ActivityKind::Notification, n.id, n.created_at, booking_id)
+        });
+    }
+    entries
+}
+
+fn activity_booking(booking: &Booking, lot_name: String) -> ActivityBooking {
+    ActivityBooking {
+        id: booking.id,
+        lot_name,
+        slot_number: booking.slot_number,
+        start_time: booking.start_time,
+        end_time: booking.end_time,
+        status: booking.status.clone(),
+        total: booking.pricing.total,
+        currency: booking.pricing.currency.clone(),
+    }
+}
+
+/// The serialized name of `kind`, used in entry ids
+const fn kind_name(kind: ActivityKind) -> &'static str {
+    match kind {
+        ActivityKind::BookingCreated => "booking_created",
+        ActivityKind::BookingCheckedIn => "booking_checked_in",
+        ActivityKind::BookingCheckedOut => "booking_checked_out",
+        ActivityKind::BookingCancelled => "booking_cancelled",
+        ActivityKind::BookingNoShow => "booking_no_show",
+        ActivityKind::Payment => "payment",
+        ActivityKind::Notification => "notification",
+    }
+}
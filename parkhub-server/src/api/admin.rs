@@ -87,6 +87,7 @@ mod tests {
                 email_reminders: false,
                 default_duration_minutes: None,
                 favorite_slots: Vec::new(),
+                accessibility: None,
             },
             credits_balance: 5,
             credits_monthly_quota: 10,
@@ -99,6 +100,7 @@ mod tests {
             cost_center: None,
             department: None,
             settings: None,
+            must_change_password: false,
         }
     }
 
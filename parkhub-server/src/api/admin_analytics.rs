@@ -8,7 +8,7 @@
 // db access goes through its own inner RwLock. See workspace lint config.
 #![allow(clippy::significant_drop_tightening)]
 
-use axum::{Extension, Json, extract::State, http::StatusCode};
+use axum::{Json, extract::State, http::StatusCode};
 use chrono::{Duration, Timelike, Utc};
 use parkhub_common::{ApiResponse, BookingStatus};
 use serde::Serialize;
@@ -18,7 +18,7 @@ use tokio::sync::RwLock;
 
 use crate::AppState;
 
-use super::{AuthUser, check_admin};
+use super::permissions::{CanViewAllBookings, RequirePermission};
 
 type SharedState = Arc<RwLock<AppState>>;
 
@@ -79,13 +79,9 @@ pub struct PopularLotEntry {
 #[tracing::instrument(skip(state), fields(admin_id = %auth_user.user_id))]
 pub async fn admin_occupancy(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanViewAllBookings>,
 ) -> (StatusCode, Json<ApiResponse<Vec<OccupancyPoint>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
-
     let now = Utc::now();
     let cutoff = now - Duration::days(7);
 
@@ -152,13 +148,9 @@ pub async fn admin_occupancy(
 #[tracing::instrument(skip(state), fields(admin_id = %auth_user.user_id))]
 pub async fn admin_revenue_summary(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanViewAllBookings>,
 ) -> (StatusCode, Json<ApiResponse<Vec<RevenueSummaryPoint>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
-
     let now = Utc::now();
     let cutoff = now - Duration::days(30);
 
@@ -215,13 +207,9 @@ pub async fn admin_revenue_summary(
 #[tracing::instrument(skip(state), fields(admin_id = %auth_user.user_id))]
 pub async fn admin_popular_lots(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanViewAllBookings>,
 ) -> (StatusCode, Json<ApiResponse<Vec<PopularLotEntry>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
-
     let bookings = state_guard.db.list_bookings().await.unwrap_or_default();
     let lots = state_guard.db.list_parking_lots().await.unwrap_or_default();
 
@@ -5,15 +5,22 @@
 // db access goes through its own inner RwLock. See workspace lint config.
 #![allow(clippy::significant_drop_tightening)]
 
+use std::collections::{BTreeMap, HashMap};
+
 use axum::{Extension, Json, extract::State, http::StatusCode};
-use chrono::{DateTime, Datelike, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, BookingStatus, UserRole};
+use parkhub_common::{
+    ApiResponse, Booking, BookingPricing, BookingStatus, ErrorCode, PaymentStatus, UserRole,
+};
 
 use crate::audit::{AuditEntry, AuditEventType};
 
-use super::{AuthUser, SharedState, check_admin};
+use super::permissions::{CanManageConfig, CanManageUsers, CanViewAllBookings, RequirePermission};
+use super::tax::{self, InvoiceAmounts, ResolvedRate};
+use super::{AuthUser, SharedState};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // BULK ADMIN OPERATIONS
@@ -55,23 +62,17 @@ pub struct BulkOperationResult {
 )]
 pub async fn bulk_update_users(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
     Json(req): Json<BulkUserUpdateRequest>,
 ) -> (StatusCode, Json<ApiResponse<BulkOperationResult>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-        );
-    }
 
     let valid_actions = ["activate", "deactivate", "set_role"];
     if !valid_actions.contains(&req.action.as_str()) {
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_ACTION",
+                ErrorCode::InvalidAction,
                 "Action must be one of: activate, deactivate, set_role",
             )),
         );
@@ -81,7 +82,7 @@ pub async fn bulk_update_users(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "MISSING_ROLE",
+                ErrorCode::MissingRole,
                 "Role is required for set_role action",
             )),
         );
@@ -102,6 +103,7 @@ pub async fn bulk_update_users(
                             match role.as_str() {
                                 "user" => user.role = UserRole::User,
                                 "premium" => user.role = UserRole::Premium,
+                                "reporter" => user.role = UserRole::Reporter,
                                 "admin" => user.role = UserRole::Admin,
                                 _ => {
                                     errors.push(format!("Invalid role for user {user_id}: {role}"));
@@ -116,6 +118,12 @@ pub async fn bulk_update_users(
                 if let Err(e) = state_guard.db.save_user(&user).await {
                     errors.push(format!("Failed to update user {user_id}: {e}"));
                 } else {
+                    if !user.is_active
+                        && let Err(e) =
+                            super::session_tokens::end_user_sessions(&state_guard, user.id).await
+                    {
+                        tracing::warn!("Failed to end sessions of user {user_id}: {e}");
+                    }
                     succeeded += 1;
                 }
             }
@@ -169,16 +177,10 @@ pub struct BulkDeleteRequest {
 )]
 pub async fn bulk_delete_users(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
     Json(req): Json<BulkDeleteRequest>,
 ) -> (StatusCode, Json<ApiResponse<BulkOperationResult>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-        );
-    }
 
     let total = req.user_ids.len();
     let mut succeeded = 0;
@@ -192,7 +194,14 @@ pub async fn bulk_delete_users(
         }
 
         match state_guard.db.delete_user(user_id).await {
-            Ok(true) => succeeded += 1,
+            Ok(true) => {
+                if let Ok(id) = Uuid::parse_str(user_id)
+                    && let Err(e) = super::session_tokens::end_user_sessions(&state_guard, id).await
+                {
+                    tracing::warn!("Failed to end sessions of deleted user {user_id}: {e}");
+                }
+                succeeded += 1;
+            }
             Ok(false) => errors.push(format!("User {user_id} not found")),
             Err(e) => errors.push(format!("Failed to delete user {user_id}: {e}")),
         }
@@ -234,96 +243,276 @@ fn parse_date(s: &str) -> Option<DateTime<Utc>> {
         .map(|dt| dt.and_utc())
 }
 
-/// Revenue report entry.
-#[derive(Debug, Serialize, utoipa::ToSchema)]
-pub struct RevenueReportEntry {
-    pub period: String,
-    pub lot_name: String,
-    pub total_revenue: f64,
+/// Query params for the revenue report.
+#[derive(Debug, Deserialize)]
+pub struct RevenueReportQuery {
+    /// First invoice date, inclusive (`YYYY-MM-DD`, default 30 days ago)
+    #[serde(alias = "start_date")]
+    pub from: Option<String>,
+    /// Last invoice date, inclusive (`YYYY-MM-DD`, default today)
+    #[serde(alias = "end_date")]
+    pub to: Option<String>,
+    /// "day" (default), "week", "month", "lot" or "tag:<key>"
+    pub group_by: Option<String>,
+}
+
+/// Revenue report row for bookings without the grouped-by tag.
+const UNTAGGED: &str = "(untagged)";
+
+/// Money totals of a revenue report row, rounded to cents.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct RevenueTotals {
+    /// Invoiced bookings
     pub booking_count: usize,
+    /// Net amount as invoiced
+    pub net: f64,
+    /// VAT as invoiced (zero for reverse-charge buyers)
+    pub vat: f64,
+    /// Net + VAT
+    pub gross: f64,
+    /// Amount paid back on cancelled bookings
+    pub refunds: f64,
+    /// Gross amount of invoices not yet paid
+    pub outstanding: f64,
+}
+
+impl RevenueTotals {
+    fn add(&mut self, pricing: &BookingPricing, amounts: &InvoiceAmounts) {
+        self.booking_count += 1;
+        self.net += amounts.net;
+        self.vat += amounts.vat;
+        self.gross += amounts.gross;
+        match pricing.payment_status {
+            PaymentStatus::Refunded => self.refunds += amounts.gross,
+            PaymentStatus::PartialRefund => {
+                let kept = pricing.cancellation_fee.unwrap_or(0.0);
+                self.refunds += (amounts.gross - kept).max(0.0);
+            }
+            PaymentStatus::Pending | PaymentStatus::Failed => self.outstanding += amounts.gross,
+            PaymentStatus::Paid => {}
+        }
+    }
+
+    fn merge(&mut self, other: &Self) {
+        self.booking_count += other.booking_count;
+        self.net += other.net;
+        self.vat += other.vat;
+        self.gross += other.gross;
+        self.refunds += other.refunds;
+        self.outstanding += other.outstanding;
+    }
+
+    fn rounded(mut self) -> Self {
+        let cents = |v: f64| (v * 100.0).round() / 100.0;
+        self.net = cents(self.net);
+        self.vat = cents(self.vat);
+        self.gross = cents(self.gross);
+        self.refunds = cents(self.refunds);
+        self.outstanding = cents(self.outstanding);
+        self
+    }
 }
 
-/// `GET /api/v1/admin/reports/revenue` — Revenue by lot, by time period.
+/// One period or lot of the revenue report.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RevenueReportRow {
+    /// Period (`2026-03-22`, `2026-W12`, `2026-03`), lot ID or tag value
+    /// (empty for untagged bookings)
+    pub key: String,
+    /// Period, the lot name when grouped by lot, or the tag value
+    pub label: String,
+    #[serde(flatten)]
+    pub totals: RevenueTotals,
+    /// Invoice numbers already issued for the bookings in this row
+    pub invoice_numbers: Vec<String>,
+}
+
+/// Revenue report matching the issued invoices.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct RevenueReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub group_by: String,
+    pub rows: Vec<RevenueReportRow>,
+    pub totals: RevenueTotals,
+}
+
+/// Whether a booking is on the books: everything except cancellations that
+/// were never charged.
+fn is_invoiced(booking: &Booking) -> bool {
+    booking.status != BookingStatus::Cancelled
+        || !matches!(
+            booking.pricing.payment_status,
+            PaymentStatus::Pending | PaymentStatus::Failed
+        )
+}
+
+/// Row key and label of `booking` under `group_by`.
+fn revenue_group(
+    booking: &Booking,
+    group_by: &str,
+    lot_names: &HashMap<Uuid, String>,
+) -> (String, String) {
+    if let Some(tag) = group_by.strip_prefix("tag:") {
+        return booking.tags.get(tag).map_or_else(
+            || (String::new(), UNTAGGED.to_string()),
+            |value| (value.clone(), value.clone()),
+        );
+    }
+    let at = booking.created_at;
+    let period = match group_by {
+        "week" => format!("{}-W{:02}", at.iso_week().year(), at.iso_week().week()),
+        "month" => at.format("%Y-%m").to_string(),
+        "lot" => {
+            let name = lot_names
+                .get(&booking.lot_id)
+                .cloned()
+                .unwrap_or_else(|| "Unknown".to_string());
+            return (booking.lot_id.to_string(), name);
+        }
+        _ => at.format("%Y-%m-%d").to_string(),
+    };
+    (period.clone(), period)
+}
+
+/// `GET /api/v1/admin/reports/revenue` — Revenue by day, week, month, lot or tag.
 #[utoipa::path(
     get,
     path = "/api/v1/admin/reports/revenue",
     tag = "Admin",
     summary = "Revenue report",
-    description = "Revenue breakdown by lot and time period.",
+    description = "Net, VAT and gross amounts, refunds and outstanding payments for bookings \
+        invoiced between `from` and `to` (invoice date, inclusive), grouped by `day`, `week`, \
+        `month`, `lot` or a booking tag (`tag:<key>`, e.g. `tag:cost_center`; untagged bookings \
+        form one row with an empty key). Amounts are computed exactly like the invoices, and \
+        each row lists the invoice numbers already issued so exports can be reconciled.",
     security(("bearer_auth" = [])),
-    responses((status = 200, description = "Revenue report")),
+    params(
+        ("from" = Option<String>, Query, description = "First invoice date (YYYY-MM-DD), default 30 days ago"),
+        ("to" = Option<String>, Query, description = "Last invoice date (YYYY-MM-DD), default today"),
+        ("group_by" = Option<String>, Query, description = "day (default), week, month, lot or tag:<key>"),
+    ),
+    responses(
+        (status = 200, description = "Revenue report", body = RevenueReport),
+        (status = 400, description = "Invalid date range or grouping"),
+        (status = 403, description = "Admin access required"),
+    ),
 )]
 pub async fn revenue_report(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
-    axum::extract::Query(query): axum::extract::Query<AdvancedReportQuery>,
-) -> (StatusCode, Json<ApiResponse<Vec<RevenueReportEntry>>>) {
+    _: RequirePermission<CanViewAllBookings>,
+    axum::extract::Query(query): axum::extract::Query<RevenueReportQuery>,
+) -> (StatusCode, Json<ApiResponse<RevenueReport>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
+
+    let today = Utc::now().date_naive();
+    let parse = |value: Option<&str>, default: NaiveDate| {
+        value.map_or(Some(default), |v| {
+            NaiveDate::parse_from_str(v, "%Y-%m-%d").ok()
+        })
+    };
+    let (Some(from), Some(to)) = (
+        parse(query.from.as_deref(), today - chrono::Duration::days(30)),
+        parse(query.to.as_deref(), today),
+    ) else {
         return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "from and to must be dates in YYYY-MM-DD format",
+            )),
+        );
+    };
+    if from > to {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "from must not be after to",
+            )),
         );
     }
-
-    let start = query
-        .start_date
-        .as_deref()
-        .and_then(parse_date)
-        .unwrap_or_else(|| Utc::now() - chrono::Duration::days(30));
-    let end = query
-        .end_date
-        .as_deref()
-        .and_then(parse_date)
-        .unwrap_or_else(Utc::now);
     let group_by = query.group_by.as_deref().unwrap_or("day");
+    let by_tag = group_by
+        .strip_prefix("tag:")
+        .is_some_and(|key| !key.is_empty());
+    if !by_tag && !matches!(group_by, "day" | "week" | "month" | "lot") {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "group_by must be one of: day, week, month, lot, tag:<key>",
+            )),
+        );
+    }
 
     let bookings = state_guard.db.list_bookings().await.unwrap_or_default();
-    let lots = state_guard.db.list_parking_lots().await.unwrap_or_default();
-
-    let lot_names: std::collections::HashMap<String, String> = lots
-        .iter()
-        .map(|l| (l.id.to_string(), l.name.clone()))
+    let lot_names: HashMap<Uuid, String> = state_guard
+        .db
+        .list_parking_lots()
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|l| (l.id, l.name))
         .collect();
 
-    let mut revenue_map: std::collections::BTreeMap<(String, String), (f64, usize)> =
-        std::collections::BTreeMap::new();
-
-    for b in &bookings {
-        if b.created_at < start || b.created_at > end {
+    let mut rates: HashMap<Uuid, ResolvedRate> = HashMap::new();
+    let mut groups: BTreeMap<(String, String), (RevenueTotals, Vec<String>)> = BTreeMap::new();
+    for booking in &bookings {
+        let day = booking.created_at.date_naive();
+        if day < from || day > to || !is_invoiced(booking) {
             continue;
         }
-        let period = match group_by {
-            "week" => {
-                let week = b.created_at.iso_week().week();
-                format!("{}-W{:02}", b.created_at.year(), week)
+        let rate = match rates.get(&booking.user_id) {
+            Some(rate) => *rate,
+            None => {
+                let rate = tax::resolve_buyer_rate(&state_guard, booking.user_id).await;
+                rates.insert(booking.user_id, rate);
+                rate
             }
-            "month" => b.created_at.format("%Y-%m").to_string(),
-            _ => b.created_at.format("%Y-%m-%d").to_string(),
         };
-        let lot_name = lot_names
-            .get(&b.lot_id.to_string())
-            .cloned()
-            .unwrap_or_else(|| "Unknown".to_string());
-        let price = b.pricing.total;
-        let entry = revenue_map.entry((period, lot_name)).or_insert((0.0, 0));
-        entry.0 += price;
-        entry.1 += 1;
+        let amounts = InvoiceAmounts::new(booking, rate);
+
+        let (key, label) = revenue_group(booking, group_by, &lot_names);
+        let (totals, invoice_numbers) = groups.entry((key, label)).or_default();
+        totals.add(&booking.pricing, &amounts);
+        if let Ok(Some(number)) = state_guard
+            .db
+            .get_assigned_invoice_number(&booking.id.to_string())
+            .await
+        {
+            invoice_numbers.push(number);
+        }
     }
 
-    let entries: Vec<RevenueReportEntry> = revenue_map
+    let mut totals = RevenueTotals::default();
+    let mut rows: Vec<RevenueReportRow> = groups
         .into_iter()
-        .map(
-            |((period, lot_name), (total_revenue, booking_count))| RevenueReportEntry {
-                period,
-                lot_name,
-                total_revenue: (total_revenue * 100.0).round() / 100.0,
-                booking_count,
-            },
-        )
+        .map(|((key, label), (row_totals, mut invoice_numbers))| {
+            totals.merge(&row_totals);
+            invoice_numbers.sort();
+            RevenueReportRow {
+                key,
+                label,
+                totals: row_totals.rounded(),
+                invoice_numbers,
+            }
+        })
         .collect();
+    if group_by == "lot" {
+        rows.sort_by(|a, b| a.label.cmp(&b.label));
+    }
 
-    (StatusCode::OK, Json(ApiResponse::success(entries)))
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(RevenueReport {
+            from,
+            to,
+            group_by: group_by.to_string(),
+            rows,
+            totals: totals.rounded(),
+        })),
+    )
 }
 
 /// Occupancy report entry.
@@ -349,16 +538,10 @@ pub struct OccupancyReportEntry {
 )]
 pub async fn occupancy_report(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanViewAllBookings>,
     axum::extract::Query(query): axum::extract::Query<AdvancedReportQuery>,
 ) -> (StatusCode, Json<ApiResponse<Vec<OccupancyReportEntry>>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-        );
-    }
 
     let start = query
         .start_date
@@ -461,16 +644,10 @@ pub struct UserReportEntry {
 )]
 pub async fn user_report(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanViewAllBookings>,
     axum::extract::Query(query): axum::extract::Query<AdvancedReportQuery>,
 ) -> (StatusCode, Json<ApiResponse<Vec<UserReportEntry>>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-        );
-    }
 
     let start = query
         .start_date
@@ -564,6 +741,13 @@ pub struct NotificationPreferences {
     /// Phone number for SMS/WhatsApp (E.164 format, e.g. "+491234567890")
     #[serde(default)]
     pub phone_number: Option<String>,
+    /// Email: sign-in from a device or IP not seen before
+    #[serde(default = "default_true")]
+    pub email_new_login: bool,
+}
+
+const fn default_true() -> bool {
+    true
 }
 
 impl Default for NotificationPreferences {
@@ -580,6 +764,7 @@ impl Default for NotificationPreferences {
             whatsapp_booking_reminder: false,
             whatsapp_booking_cancelled: false,
             phone_number: None,
+            email_new_login: true,
         }
     }
 }
@@ -629,7 +814,7 @@ pub async fn update_notification_preferences(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to save preferences",
             )),
         );
@@ -722,7 +907,7 @@ pub async fn update_design_theme_preference(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_THEME",
+                ErrorCode::InvalidTheme,
                 "Invalid design theme. Valid: classic, glass, bento, brutalist, neon, warm, liquid, mono, ocean, forest",
             )),
         );
@@ -735,7 +920,7 @@ pub async fn update_design_theme_preference(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to save design theme",
             )),
         );
@@ -814,15 +999,9 @@ pub async fn load_booking_policies(db: &crate::db::Database) -> BookingPolicies
 )]
 pub async fn get_booking_policies(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanManageConfig>,
 ) -> (StatusCode, Json<ApiResponse<BookingPolicies>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-        );
-    }
     let policies = load_booking_policies(&state_guard.db).await;
     (StatusCode::OK, Json(ApiResponse::success(policies)))
 }
@@ -839,16 +1018,10 @@ pub async fn get_booking_policies(
 )]
 pub async fn update_booking_policies(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageConfig>,
     Json(policies): Json<BookingPolicies>,
 ) -> (StatusCode, Json<ApiResponse<BookingPolicies>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-        );
-    }
 
     let json = serde_json::to_string(&policies).unwrap_or_default();
     if let Err(e) = state_guard.db.set_setting("booking_policies", &json).await {
@@ -856,7 +1029,7 @@ pub async fn update_booking_policies(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to save policies",
             )),
         );
@@ -964,7 +1137,7 @@ pub async fn detailed_health_check(
     Json(ExtendedHealthResponse {
         status: overall.to_string(),
         version: env!("CARGO_PKG_VERSION").to_string(),
-        git_sha: option_env!("GIT_SHA").unwrap_or("unknown").to_string(),
+        git_sha: crate::build_info::GIT_HASH.to_string(),
         build_time: option_env!("BUILD_TIME").unwrap_or("unknown").to_string(),
         uptime_seconds: 0, // would need start_time in state
         db_healthy,
@@ -1037,19 +1210,85 @@ mod tests {
 
     // ─── Advanced Report Tests ───────────────────────────────────────────
 
+    fn pricing(base_price: f64, payment_status: PaymentStatus) -> BookingPricing {
+        BookingPricing {
+            base_price,
+            discount: 0.0,
+            tax: 0.0,
+            total: base_price,
+            currency: "EUR".to_string(),
+            payment_status,
+            payment_method: None,
+            cancellation_fee: None,
+        }
+    }
+
+    fn amounts(net: f64, rate: f64) -> InvoiceAmounts {
+        InvoiceAmounts {
+            rate: ResolvedRate::Standard(rate),
+            net,
+            vat: net * rate,
+            gross: net * (1.0 + rate),
+        }
+    }
+
     #[test]
-    fn test_revenue_report_entry_serialization() {
-        let entry = RevenueReportEntry {
-            period: "2026-03-22".to_string(),
-            lot_name: "Main Garage".to_string(),
-            total_revenue: 150.50,
-            booking_count: 10,
+    fn test_revenue_totals_refunds_and_outstanding() {
+        let mut totals = RevenueTotals::default();
+        totals.add(&pricing(10.0, PaymentStatus::Paid), &amounts(10.0, 0.19));
+        totals.add(&pricing(10.0, PaymentStatus::Pending), &amounts(10.0, 0.19));
+        totals.add(
+            &pricing(10.0, PaymentStatus::Refunded),
+            &amounts(10.0, 0.19),
+        );
+        let mut partial = pricing(10.0, PaymentStatus::PartialRefund);
+        partial.cancellation_fee = Some(5.0);
+        totals.add(&partial, &amounts(10.0, 0.19));
+        let totals = totals.rounded();
+
+        assert_eq!(totals.booking_count, 4);
+        assert_eq!(totals.net, 40.0);
+        assert_eq!(totals.vat, 7.6);
+        assert_eq!(totals.gross, 47.6);
+        assert_eq!(totals.outstanding, 11.9);
+        assert_eq!(totals.refunds, 18.8);
+    }
+
+    #[test]
+    fn test_revenue_totals_reverse_charge_has_no_vat() {
+        let mut totals = RevenueTotals::default();
+        let reverse_charge = InvoiceAmounts {
+            rate: ResolvedRate::ReverseCharge,
+            net: 20.0,
+            vat: 0.0,
+            gross: 20.0,
         };
-        let json = serde_json::to_value(&entry).unwrap();
-        assert_eq!(json["period"], "2026-03-22");
-        assert_eq!(json["lot_name"], "Main Garage");
-        assert_eq!(json["total_revenue"], 150.5);
-        assert_eq!(json["booking_count"], 10);
+        totals.add(&pricing(20.0, PaymentStatus::Paid), &reverse_charge);
+        assert_eq!(totals.vat, 0.0);
+        assert_eq!(totals.gross, 20.0);
+    }
+
+    #[test]
+    fn test_revenue_report_row_serialization() {
+        let row = RevenueReportRow {
+            key: "2026-03-22".to_string(),
+            label: "2026-03-22".to_string(),
+            totals: RevenueTotals {
+                booking_count: 2,
+                net: 20.0,
+                vat: 3.8,
+                gross: 23.8,
+                refunds: 0.0,
+                outstanding: 11.9,
+            },
+            invoice_numbers: vec!["2026-0000001".to_string()],
+        };
+        let json = serde_json::to_value(&row).unwrap();
+        assert_eq!(json["key"], "2026-03-22");
+        assert_eq!(json["booking_count"], 2);
+        assert_eq!(json["gross"], 23.8);
+        assert_eq!(json["outstanding"], 11.9);
+        assert_eq!(json["invoice_numbers"][0], "2026-0000001");
     }
 
     #[test]
@@ -1123,6 +1362,15 @@ mod tests {
         assert!(back.push_enabled);
     }
 
+    #[test]
+    fn test_notification_preferences_new_login_defaults_on_for_stored_prefs() {
+        // Preferences saved before the new-login alert existed must opt in.
+        let json = r#"{"email_booking_confirm":true,"email_booking_reminder":true,
+            "email_swap_request":true,"push_enabled":true}"#;
+        let prefs: NotificationPreferences = serde_json::from_str(json).unwrap();
+        assert!(prefs.email_new_login);
+    }
+
     // ─── Booking Policies Tests ──────────────────────────────────────────
 
     #[test]
@@ -13,25 +13,27 @@ use axum::{
     http::StatusCode,
 };
 use chrono::{Datelike, TimeDelta, Timelike, Utc};
-use parkhub_common::{ApiResponse, BookingStatus, PaginatedResponse, User, UserRole};
+use parkhub_common::{
+    ApiResponse, Booking, BookingStatus, ErrorCode, PaginatedResponse, Permission, User, UserRole,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use crate::AppState;
 use crate::audit::{AuditEntry, AuditEventType};
-use crate::requests::PaginationParams;
+use crate::requests::{
+    BOOKING_SORT_FIELDS, BookingListFilters, ListParams, USER_SORT_FIELDS, UserListFilters,
+};
 
 use super::admin::AdminUserResponse;
+use super::lot_managers::{AdminScope, admin_scope};
+use super::permissions::{CanManageConfig, CanManageUsers, CanViewAllBookings, RequirePermission};
+use super::plate_display::PlateViewer;
 use super::{AuthUser, check_admin, hash_password_simple, read_admin_setting};
 
 type SharedState = Arc<RwLock<AppState>>;
 
-/// Compute total number of pages (minimum 1) for a given total item count and page size.
-fn total_pages(total: usize, per_page: i32) -> i32 {
-    ((total as i32 + per_page - 1) / per_page).max(1)
-}
-
 // ═══════════════════════════════════════════════════════════════════════════════
 // ADMIN — USER MANAGEMENT
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -56,66 +58,67 @@ pub struct AdminResetPasswordRequest {
 
 /// `GET /api/v1/admin/users` — list all users (admin only)
 #[utoipa::path(get, path = "/api/v1/admin/users", tag = "Admin",
-    summary = "List all users (admin)", description = "Returns paginated registered users. Admin only.",
+    summary = "List all users (admin)",
+    description = "Returns paginated registered users. Admin only. \
+        Sortable by `created_at` (default), `username`, `email`, `name` and `role`; \
+        `next_cursor` continues after the last user of the page.",
     security(("bearer_auth" = [])),
-    params(PaginationParams),
-    responses((status = 200, description = "User list"), (status = 403, description = "Forbidden"))
+    params(ListParams, UserListFilters),
+    responses(
+        (status = 200, description = "User list"),
+        (status = 400, description = "Unknown sort field or malformed cursor"),
+        (status = 403, description = "Forbidden"),
+    )
 )]
 #[tracing::instrument(skip(state), fields(admin_id = %auth_user.user_id))]
 pub async fn admin_list_users(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
-    Query(pagination): Query<PaginationParams>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Query(params): Query<ListParams>,
+    Query(filters): Query<UserListFilters>,
 ) -> (
     StatusCode,
     Json<ApiResponse<PaginatedResponse<AdminUserResponse>>>,
 ) {
+    let (query, sort) = match super::list_request(&params, USER_SORT_FIELDS, true) {
+        Ok(request) => request,
+        Err(response) => return response,
+    };
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     // T-1731: resolve caller tenant so non-platform admins only see their own
     // tenant's users.  Platform admins (tenant_id == None) see everything,
     // matching the PHP global-scope no-op.
     let caller_tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
 
-    let result = if caller_tenant_id.is_some() {
-        // Tenant-bound admin: load all users, filter, then paginate in memory
-        // so `total`/`total_pages` reflect the tenant-scoped set.
-        state_guard.db.list_users().await.map(|all| {
-            let filtered: Vec<User> = all
-                .into_iter()
-                .filter(|u| {
-                    super::matches_tenant(u.tenant_id.as_deref(), caller_tenant_id.as_deref())
-                })
-                .collect();
-            let total = filtered.len();
-            let skip = usize::try_from((pagination.page - 1).max(0))
-                .unwrap_or(0)
-                .saturating_mul(usize::try_from(pagination.per_page.max(1)).unwrap_or(1));
-            let take = usize::try_from(pagination.per_page.max(1)).unwrap_or(1);
-            let page_items: Vec<User> = filtered.into_iter().skip(skip).take(take).collect();
-            (page_items, total)
-        })
-    } else {
-        state_guard
-            .db
-            .list_users_paginated(pagination.page, pagination.per_page)
-            .await
-    };
+    let result = state_guard
+        .db
+        .list_users_page(
+            (caller_tenant_id.is_some() || !filters.is_empty()).then_some(|u: &User| {
+                super::matches_tenant(u.tenant_id.as_deref(), caller_tenant_id.as_deref())
+                    && filters.matches(u)
+            }),
+            sort,
+            &query,
+        )
+        .await;
 
     match result {
-        Ok((users, total)) => {
-            tracing::debug!(count = users.len(), total, "Admin listed users");
-            let items: Vec<AdminUserResponse> = users.iter().map(AdminUserResponse::from).collect();
-            let total_pages = total_pages(total, pagination.per_page);
+        Ok(page) => {
+            tracing::debug!(
+                count = page.items.len(),
+                total = page.total,
+                "Admin listed users"
+            );
+            let items: Vec<AdminUserResponse> =
+                page.items.iter().map(AdminUserResponse::from).collect();
             let response = PaginatedResponse {
                 items,
-                page: pagination.page,
-                per_page: pagination.per_page,
-                total: total as i32,
-                total_pages,
+                page: params.page(),
+                per_page: params.per_page(),
+                total: page.total as i32,
+                total_pages: params.total_pages(page.total),
+                next_cursor: page.next_cursor,
             };
             (StatusCode::OK, Json(ApiResponse::success(response)))
         }
@@ -123,7 +126,10 @@ pub async fn admin_list_users(
             tracing::error!("Failed to list users: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to list users")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list users",
+                )),
             )
         }
     }
@@ -138,14 +144,11 @@ pub async fn admin_list_users(
 #[tracing::instrument(skip(state, req), fields(admin_id = %auth_user.user_id, target_user_id = %id, new_role = %req.role))]
 pub async fn admin_update_user_role(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
     Path(id): Path<String>,
     Json(req): Json<UpdateUserRoleRequest>,
 ) -> (StatusCode, Json<ApiResponse<AdminUserResponse>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     // Fetch the caller to check their role for privilege escalation prevention
     let Ok(Some(caller)) = state_guard
@@ -155,7 +158,7 @@ pub async fn admin_update_user_role(
     else {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     };
 
@@ -164,7 +167,7 @@ pub async fn admin_update_user_role(
         return (
             StatusCode::FORBIDDEN,
             Json(ApiResponse::error(
-                "FORBIDDEN",
+                ErrorCode::Forbidden,
                 "Only a SuperAdmin can assign the SuperAdmin role",
             )),
         );
@@ -175,14 +178,17 @@ pub async fn admin_update_user_role(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -198,7 +204,7 @@ pub async fn admin_update_user_role(
     {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     }
 
@@ -206,6 +212,7 @@ pub async fn admin_update_user_role(
     user.role = match req.role.as_str() {
         "admin" => UserRole::Admin,
         "superadmin" => UserRole::SuperAdmin,
+        "reporter" => UserRole::Reporter,
         _ => UserRole::User,
     };
     user.updated_at = Utc::now();
@@ -214,7 +221,10 @@ pub async fn admin_update_user_role(
         tracing::error!("Failed to update user role: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to update user")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to update user",
+            )),
         );
     }
 
@@ -254,28 +264,28 @@ pub async fn admin_update_user_role(
 #[tracing::instrument(skip(state, req), fields(admin_id = %auth_user.user_id, target_user_id = %id))]
 pub async fn admin_update_user_status(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
     Path(id): Path<String>,
     Json(req): Json<UpdateUserStatusRequest>,
 ) -> (StatusCode, Json<ApiResponse<AdminUserResponse>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let mut user = match state_guard.db.get_user(&id).await {
         Ok(Some(u)) => u,
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -287,7 +297,7 @@ pub async fn admin_update_user_status(
     {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     }
 
@@ -298,13 +308,16 @@ pub async fn admin_update_user_status(
         tracing::error!("Failed to update user status: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to update user")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to update user",
+            )),
         );
     }
 
     // Revoke all sessions when a user is disabled
     if !user.is_active
-        && let Err(e) = state_guard.db.delete_sessions_by_user(user.id).await
+        && let Err(e) = super::session_tokens::end_user_sessions(&state_guard, user.id).await
     {
         tracing::error!("Failed to revoke sessions for disabled user {}: {}", id, e);
     }
@@ -343,20 +356,17 @@ pub async fn admin_update_user_status(
 #[tracing::instrument(skip(state), fields(admin_id = %auth_user.user_id, target_user_id = %id))]
 pub async fn admin_delete_user(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     // Prevent admin from deleting their own account via admin panel
     if id == auth_user.user_id.to_string() {
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "CANNOT_DELETE_SELF",
+                ErrorCode::CannotDeleteSelf,
                 "You cannot delete your own account",
             )),
         );
@@ -373,14 +383,17 @@ pub async fn admin_delete_user(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -388,7 +401,7 @@ pub async fn admin_delete_user(
     if !super::matches_tenant(target.tenant_id.as_deref(), caller_tenant_id.as_deref()) {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     }
 
@@ -403,6 +416,12 @@ pub async fn admin_delete_user(
 
     match state_guard.db.anonymize_user(&id).await {
         Ok(true) => {
+            if let Ok(user_id) = uuid::Uuid::parse_str(&id)
+                && let Err(e) =
+                    super::session_tokens::end_user_sessions(&state_guard, user_id).await
+            {
+                tracing::warn!("Failed to end sessions of anonymized user {}: {}", id, e);
+            }
             AuditEntry::new(AuditEventType::UserDeleted)
                 .user(auth_user.user_id, &admin_username)
                 .resource("user", &id)
@@ -416,13 +435,16 @@ pub async fn admin_delete_user(
         }
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         ),
         Err(e) => {
             tracing::error!("Failed to anonymize user {}: {}", id, e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to delete user")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to delete user",
+                )),
             )
         }
     }
@@ -452,64 +474,70 @@ pub struct AdminBookingResponse {
 
 /// `GET /api/v1/admin/bookings` — list all bookings (admin only)
 #[utoipa::path(get, path = "/api/v1/admin/bookings", tag = "Admin",
-    summary = "List all bookings (admin)", description = "Returns paginated bookings with enriched details. Admin only.",
+    summary = "List all bookings (admin)",
+    description = "Returns paginated bookings with enriched details. Admin only; lot managers see their lots' bookings. \
+        Sortable by `start_time` (default), `end_time`, `created_at` and `status`; \
+        `next_cursor` continues after the last booking of the page.",
     security(("bearer_auth" = [])),
-    params(PaginationParams),
-    responses((status = 200, description = "All bookings"), (status = 403, description = "Forbidden"))
+    params(ListParams, BookingListFilters),
+    responses(
+        (status = 200, description = "All bookings"),
+        (status = 400, description = "Unknown sort field or malformed cursor"),
+        (status = 403, description = "Forbidden"),
+    )
 )]
 pub async fn admin_list_bookings(
     State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
-    Query(pagination): Query<PaginationParams>,
+    Query(params): Query<ListParams>,
+    Query(filters): Query<BookingListFilters>,
 ) -> (
     StatusCode,
     Json<ApiResponse<PaginatedResponse<AdminBookingResponse>>>,
 ) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
+    let scope = match admin_scope(&auth_user, Permission::ViewAllBookings) {
+        Ok(scope) => scope,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg))),
+    };
+    let (query, sort) = match super::list_request(&params, BOOKING_SORT_FIELDS, true) {
+        Ok(request) => request,
+        Err(response) => return response,
+    };
 
     // T-1731: tenant-scope the booking list for non-platform admins.
     let caller_tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
 
-    let bookings_result = if caller_tenant_id.is_some() {
-        state_guard.db.list_bookings().await.map(|all| {
-            let filtered: Vec<parkhub_common::Booking> = all
-                .into_iter()
-                .filter(|b| {
-                    super::matches_tenant(b.tenant_id.as_deref(), caller_tenant_id.as_deref())
-                })
-                .collect();
-            let total = filtered.len();
-            let skip = usize::try_from((pagination.page - 1).max(0))
-                .unwrap_or(0)
-                .saturating_mul(usize::try_from(pagination.per_page.max(1)).unwrap_or(1));
-            let take = usize::try_from(pagination.per_page.max(1)).unwrap_or(1);
-            let page_items: Vec<parkhub_common::Booking> =
-                filtered.into_iter().skip(skip).take(take).collect();
-            (page_items, total)
-        })
-    } else {
-        state_guard
-            .db
-            .list_bookings_paginated(pagination.page, pagination.per_page)
-            .await
-    };
+    // Lot managers only see bookings in the lots they manage.
+    let restricted =
+        caller_tenant_id.is_some() || scope != AdminScope::Global || !filters.is_empty();
+    let bookings_result = state_guard
+        .db
+        .list_bookings_page(
+            restricted.then_some(|b: &Booking| {
+                super::matches_tenant(b.tenant_id.as_deref(), caller_tenant_id.as_deref())
+                    && scope.allows_lot(&b.lot_id)
+                    && filters.matches(b)
+            }),
+            sort,
+            &query,
+        )
+        .await;
 
-    let (bookings, total) = match bookings_result {
-        Ok(result) => result,
+    let page = match bookings_result {
+        Ok(page) => page,
         Err(e) => {
             tracing::error!("Failed to list bookings: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list bookings",
                 )),
             );
         }
     };
+    let (bookings, total) = (page.items, page.total);
 
     // Batch-load all users and lots upfront to avoid N+1 queries
     let all_users = state_guard.db.list_users().await.unwrap_or_default();
@@ -524,6 +552,7 @@ pub async fn admin_list_bookings(
         .map(|l| (l.id.to_string(), l))
         .collect();
 
+    let viewer = PlateViewer::load(&state_guard, auth_user.user_id).await;
     let mut items = Vec::with_capacity(bookings.len());
     for booking in bookings {
         let (user_name, user_email) = match user_map.get(&booking.user_id.to_string()) {
@@ -545,7 +574,7 @@ pub async fn admin_list_bookings(
             lot_name,
             slot_id: booking.slot_id.to_string(),
             slot_number: booking.slot_number.to_string(),
-            vehicle_plate: booking.vehicle.license_plate.clone(),
+            vehicle_plate: viewer.plate(booking.user_id, &booking.vehicle.license_plate),
             start_time: booking.start_time,
             end_time: booking.end_time,
             status: format!("{:?}", booking.status).to_lowercase(),
@@ -553,13 +582,13 @@ pub async fn admin_list_bookings(
         });
     }
 
-    let total_pages = total_pages(total, pagination.per_page);
     let response = PaginatedResponse {
         items,
-        page: pagination.page,
-        per_page: pagination.per_page,
+        page: params.page(),
+        per_page: params.per_page(),
         total: total as i32,
-        total_pages,
+        total_pages: params.total_pages(total),
+        next_cursor: page.next_cursor,
     };
     (StatusCode::OK, Json(ApiResponse::success(response)))
 }
@@ -589,12 +618,9 @@ pub struct AdminStatsResponse {
 #[tracing::instrument(skip(state), fields(admin_id = %auth_user.user_id))]
 pub async fn admin_stats(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanViewAllBookings>,
 ) -> (StatusCode, Json<ApiResponse<AdminStatsResponse>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let db_stats = state_guard
         .db
@@ -607,6 +633,7 @@ pub async fn admin_stats(
             slots: 0,
             sessions: 0,
             vehicles: 0,
+            dummy_users: 0,
         });
 
     // Count active bookings
@@ -660,7 +687,7 @@ pub struct DailyBookingStat {
 /// `GET /api/v1/admin/reports` — booking stats by day for last N days
 #[utoipa::path(get, path = "/api/v1/admin/reports", tag = "Admin",
     summary = "Booking reports (admin)",
-    description = "Returns daily booking stats.",
+    description = "Returns daily booking stats. Lot managers get stats for their lots only.",
     security(("bearer_auth" = [])),
     responses((status = 200, description = "Success"))
 )]
@@ -670,9 +697,10 @@ pub async fn admin_reports(
     Query(query): Query<ReportsQuery>,
 ) -> (StatusCode, Json<ApiResponse<Vec<DailyBookingStat>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
+    let scope = match admin_scope(&auth_user, Permission::ViewAllBookings) {
+        Ok(scope) => scope,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg))),
+    };
 
     let days = query.days.unwrap_or(30);
     let cutoff = Utc::now() - TimeDelta::days(days);
@@ -682,7 +710,7 @@ pub async fn admin_reports(
     // Group by date
     let mut by_date: std::collections::BTreeMap<String, usize> = std::collections::BTreeMap::new();
     for b in &bookings {
-        if b.created_at >= cutoff {
+        if b.created_at >= cutoff && scope.allows_lot(&b.lot_id) {
             let date = b.created_at.format("%Y-%m-%d").to_string();
             *by_date.entry(date).or_insert(0) += 1;
         }
@@ -713,12 +741,9 @@ pub struct HeatmapCell {
 )]
 pub async fn admin_heatmap(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanViewAllBookings>,
 ) -> (StatusCode, Json<ApiResponse<Vec<HeatmapCell>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let bookings = state_guard.db.list_bookings().await.unwrap_or_default();
 
@@ -777,8 +802,8 @@ pub async fn admin_audit_log(
     axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
 ) -> (StatusCode, Json<ApiResponse<PaginatedAuditLog>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let page = params
@@ -851,7 +876,7 @@ pub async fn admin_audit_log(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list audit log",
                 )),
             )
@@ -875,7 +900,7 @@ pub async fn admin_audit_log_export(
     axum::extract::Query(params): axum::extract::Query<std::collections::HashMap<String, String>>,
 ) -> impl axum::response::IntoResponse {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
+    if let Err((status, msg)) = check_admin(&auth_user) {
         return (
             status,
             [
@@ -1000,19 +1025,16 @@ pub struct AdminResetRequest {
 )]
 pub async fn admin_reset(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageConfig>,
     Json(req): Json<AdminResetRequest>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
     let state_guard = state.write().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     if req.confirm != "RESET" {
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "CONFIRMATION_REQUIRED",
+                ErrorCode::ConfirmationRequired,
                 "Body must contain {\"confirm\": \"RESET\"}",
             )),
         );
@@ -1027,7 +1049,7 @@ pub async fn admin_reset(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to read admin user before reset",
             )),
         );
@@ -1038,7 +1060,7 @@ pub async fn admin_reset(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to reset database",
             )),
         );
@@ -1067,6 +1089,7 @@ pub async fn admin_reset(
         cost_center: None,
         department: None,
         settings: admin.settings,
+        must_change_password: false,
     };
 
     if let Err(e) = state_guard.db.save_user(&admin_user).await {
@@ -1074,7 +1097,7 @@ pub async fn admin_reset(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Database reset succeeded but admin re-creation failed",
             )),
         );
@@ -1108,12 +1131,9 @@ pub async fn admin_reset(
 )]
 pub async fn admin_get_auto_release(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanManageConfig>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let enabled = read_admin_setting(&state_guard.db, "auto_release_enabled").await;
     let minutes = read_admin_setting(&state_guard.db, "auto_release_minutes").await;
@@ -1145,13 +1165,10 @@ pub struct AutoReleaseSettingsRequest {
 )]
 pub async fn admin_update_auto_release(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanManageConfig>,
     Json(req): Json<AutoReleaseSettingsRequest>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     if let Some(enabled) = req.auto_release_enabled
         && let Err(e) = state_guard
@@ -1162,7 +1179,10 @@ pub async fn admin_update_auto_release(
         tracing::error!("Failed to save auto_release_enabled: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save setting")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save setting",
+            )),
         );
     }
 
@@ -1171,7 +1191,7 @@ pub async fn admin_update_auto_release(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "INVALID_INPUT",
+                    ErrorCode::InvalidInput,
                     "auto_release_minutes must be >= 1",
                 )),
             );
@@ -1184,7 +1204,10 @@ pub async fn admin_update_auto_release(
             tracing::error!("Failed to save auto_release_minutes: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to save setting")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to save setting",
+                )),
             );
         }
     }
@@ -1217,12 +1240,9 @@ pub async fn admin_update_auto_release(
 )]
 pub async fn admin_get_email_settings(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanManageConfig>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let host = state_guard
         .db
@@ -1308,13 +1328,10 @@ pub struct EmailSettingsRequest {
 )]
 pub async fn admin_update_email_settings(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageConfig>,
     Json(req): Json<EmailSettingsRequest>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let db = &state_guard.db;
 
@@ -1368,12 +1385,9 @@ pub async fn admin_update_email_settings(
 )]
 pub async fn admin_get_privacy(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanManageConfig>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let db = &state_guard.db;
 
@@ -1433,13 +1447,10 @@ pub struct PrivacySettingsRequest {
 )]
 pub async fn admin_update_privacy(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageConfig>,
     Json(req): Json<PrivacySettingsRequest>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let db = &state_guard.db;
 
@@ -1451,7 +1462,7 @@ pub async fn admin_update_privacy(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "INVALID_INPUT",
+                    ErrorCode::InvalidInput,
                     "data_retention_days must be >= 1",
                 )),
             );
@@ -1537,28 +1548,28 @@ pub struct AdminUpdateUserRequest {
 )]
 pub async fn admin_update_user(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
     Path(id): Path<String>,
     Json(req): Json<AdminUpdateUserRequest>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let mut user = match state_guard.db.get_user(&id).await {
         Ok(Some(u)) => u,
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -1570,7 +1581,7 @@ pub async fn admin_update_user(
     {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     }
 
@@ -1582,7 +1593,10 @@ pub async fn admin_update_user(
         if !email.contains('@') || email.len() < 5 {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("INVALID_INPUT", "Invalid email address")),
+                Json(ApiResponse::error(
+                    ErrorCode::InvalidInput,
+                    "Invalid email address",
+                )),
             );
         }
         user.email = email;
@@ -1591,6 +1605,7 @@ pub async fn admin_update_user(
         let new_role = match role_str.to_lowercase().as_str() {
             "user" => UserRole::User,
             "premium" => UserRole::Premium,
+            "reporter" => UserRole::Reporter,
             "admin" => UserRole::Admin,
             "superadmin" => {
                 // Only SuperAdmin can assign SuperAdmin
@@ -1604,7 +1619,7 @@ pub async fn admin_update_user(
                     return (
                         StatusCode::FORBIDDEN,
                         Json(ApiResponse::error(
-                            "FORBIDDEN",
+                            ErrorCode::Forbidden,
                             "Only SuperAdmin can assign SuperAdmin role",
                         )),
                     );
@@ -1615,7 +1630,7 @@ pub async fn admin_update_user(
                 return (
                     StatusCode::BAD_REQUEST,
                     Json(ApiResponse::error(
-                        "INVALID_INPUT",
+                        ErrorCode::InvalidInput,
                         "Role must be user, premium, admin, or superadmin",
                     )),
                 );
@@ -1623,6 +1638,7 @@ pub async fn admin_update_user(
         };
         user.role = new_role;
     }
+    let was_active = user.is_active;
     if let Some(active) = req.is_active {
         user.is_active = active;
     }
@@ -1632,10 +1648,21 @@ pub async fn admin_update_user(
         tracing::error!("Failed to update user: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to update user")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to update user",
+            )),
         );
     }
 
+    // Revoke all sessions when a user is disabled
+    if was_active
+        && !user.is_active
+        && let Err(e) = super::session_tokens::end_user_sessions(&state_guard, user.id).await
+    {
+        tracing::error!("Failed to revoke sessions for disabled user {}: {}", id, e);
+    }
+
     AuditEntry::new(AuditEventType::UserUpdated)
         .user(auth_user.user_id, "admin")
         .resource("user", &id)
@@ -1660,12 +1687,12 @@ pub async fn admin_update_user(
     path = "/api/v1/admin/users/{id}/reset-password",
     tag = "Admin",
     summary = "Reset user password",
-    description = "Admin can set a new password for any user.",
+    description = "Admin can set a new password for any user. The user has to change it on their next login.",
     security(("bearer_auth" = []))
 )]
 pub async fn admin_reset_user_password(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
     Path(id): Path<String>,
     Json(req): Json<AdminResetPasswordRequest>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
@@ -1673,30 +1700,30 @@ pub async fn admin_reset_user_password(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "New password must be at least 8 characters",
             )),
         );
     }
 
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let mut user = match state_guard.db.get_user(&id).await {
         Ok(Some(u)) => u,
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -1710,7 +1737,7 @@ pub async fn admin_reset_user_password(
     {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     }
 
@@ -1720,12 +1747,17 @@ pub async fn admin_reset_user_password(
             tracing::error!("Password hashing failed: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
     user.password_hash = new_hash;
+    // The admin knows this password, so the user has to replace it
+    user.must_change_password = user.id != auth_user.user_id;
     user.updated_at = Utc::now();
 
     match state_guard.db.save_user(&user).await {
@@ -1740,7 +1772,10 @@ pub async fn admin_reset_user_password(
             tracing::error!("Failed to persist password reset: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
@@ -1953,7 +1988,7 @@ mod tests {
     // T-1737: cross-tenant admin-write guard tests
     // ─────────────────────────────────────────────────────────────────────────
     // These tests drive the four flagged user-write handlers (role, status,
-    // delete, update) directly with `State`/`Extension` arguments.  They seed
+    // delete, update) directly with `State`/`RequirePermission` arguments.  They seed
     // callers and targets with distinct tenant_ids in an in-memory database
     // and assert that a tenant-bound admin gets a 404 for cross-tenant targets
     // while a platform admin (tenant_id = None) retains full access.
@@ -1961,9 +1996,11 @@ mod tests {
 
     use crate::config::ServerConfig;
     use crate::db::{Database, DatabaseConfig};
+    use axum::Json;
     use axum::extract::{Path, State};
-    use axum::{Extension, Json};
+    use parkhub_common::ResolvedPermissions;
     use parkhub_common::models::{User, UserPreferences, UserRole};
+    use std::marker::PhantomData;
     use std::sync::Arc;
     use tokio::sync::RwLock;
 
@@ -1986,7 +2023,7 @@ mod tests {
             db,
             mdns: None,
             scheduler: None,
-            ws_events: crate::api::ws::EventBroadcaster::new(),
+            ws_events: crate::api::events::EventBroadcaster::new(),
             fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
             revocation_store: crate::jwt::TokenRevocationList::new(),
         }));
@@ -2016,6 +2053,7 @@ mod tests {
             cost_center: None,
             department: None,
             settings: None,
+            must_change_password: false,
         }
     }
 
@@ -2029,11 +2067,17 @@ mod tests {
             .expect("save user");
     }
 
-    fn auth(u: &User) -> AuthUser {
-        AuthUser {
-            user_id: u.id,
-            api_key_id: None,
-        }
+    fn auth(u: &User) -> RequirePermission<CanManageUsers> {
+        RequirePermission(
+            AuthUser {
+                user_id: u.id,
+                api_key_id: None,
+                impersonator_id: None,
+                role: u.role.clone(),
+                permissions: ResolvedPermissions::for_role(&u.role),
+            },
+            PhantomData,
+        )
     }
 
     // ── admin_update_user_role ────────────────────────────────────────────────
@@ -2048,7 +2092,7 @@ mod tests {
 
         let (status, body) = admin_update_user_role(
             State(h.state.clone()),
-            Extension(auth(&caller)),
+            auth(&caller),
             Path(target.id.to_string()),
             Json(UpdateUserRoleRequest {
                 role: "admin".into(),
@@ -2084,7 +2128,7 @@ mod tests {
 
         let (status, body) = admin_update_user_status(
             State(h.state.clone()),
-            Extension(auth(&caller)),
+            auth(&caller),
             Path(target.id.to_string()),
             Json(UpdateUserStatusRequest {
                 status: "inactive".into(),
@@ -2118,7 +2162,7 @@ mod tests {
 
         let (status, body) = admin_delete_user(
             State(h.state.clone()),
-            Extension(auth(&caller)),
+            auth(&caller),
             Path(target.id.to_string()),
         )
         .await;
@@ -2150,7 +2194,7 @@ mod tests {
 
         let (status, body) = admin_update_user(
             State(h.state.clone()),
-            Extension(auth(&caller)),
+            auth(&caller),
             Path(target.id.to_string()),
             Json(AdminUpdateUserRequest {
                 name: Some("Hacked".into()),
@@ -2188,7 +2232,7 @@ mod tests {
 
         let (status, _body) = admin_update_user(
             State(h.state.clone()),
-            Extension(auth(&caller)),
+            auth(&caller),
             Path(target.id.to_string()),
             Json(AdminUpdateUserRequest {
                 name: Some("Renamed".into()),
@@ -2223,7 +2267,7 @@ mod tests {
 
         let (status, _body) = admin_update_user(
             State(h.state.clone()),
-            Extension(auth(&caller)),
+            auth(&caller),
             Path(target.id.to_string()),
             Json(AdminUpdateUserRequest {
                 name: Some("Platform Edit".into()),
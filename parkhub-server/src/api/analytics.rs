@@ -9,12 +9,12 @@
 #![allow(clippy::significant_drop_tightening)]
 
 use axum::{
-    Extension, Json,
+    Json,
     extract::{Query, State},
     http::StatusCode,
 };
-use chrono::{Duration, TimeDelta, Timelike, Utc};
-use parkhub_common::ApiResponse;
+use chrono::{DateTime, Duration, TimeDelta, Timelike, Utc};
+use parkhub_common::{ApiResponse, Booking};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
@@ -22,7 +22,7 @@ use tokio::sync::RwLock;
 
 use crate::AppState;
 
-use super::{AuthUser, check_admin};
+use super::permissions::{CanViewAllBookings, RequirePermission};
 
 type SharedState = Arc<RwLock<AppState>>;
 
@@ -86,6 +86,35 @@ pub struct AnalyticsOverview {
 // Handler
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Bookings created per day over the `days` days up to `now`, oldest first,
+/// with a zero entry for days without bookings. Also feeds the usage chart
+/// of the server status window.
+pub(crate) fn daily_booking_counts(
+    bookings: &[Booking],
+    days: i64,
+    now: DateTime<Utc>,
+) -> Vec<DailyDataPoint> {
+    let cutoff = now - TimeDelta::days(days);
+    let mut counts: BTreeMap<String, u64> = (0..days)
+        .map(|i| {
+            let day = (now - Duration::days(days - 1 - i)).format("%Y-%m-%d");
+            (day.to_string(), 0)
+        })
+        .collect();
+    for b in bookings.iter().filter(|b| b.created_at >= cutoff) {
+        *counts
+            .entry(b.created_at.format("%Y-%m-%d").to_string())
+            .or_insert(0) += 1;
+    }
+    counts
+        .into_iter()
+        .map(|(date, value)| DailyDataPoint {
+            date,
+            value: value as f64,
+        })
+        .collect()
+}
+
 /// `GET /api/v1/admin/analytics/overview`
 ///
 /// Returns a comprehensive analytics overview including daily bookings,
@@ -95,22 +124,17 @@ pub struct AnalyticsOverview {
 #[tracing::instrument(skip(state), fields(admin_id = %auth_user.user_id))]
 pub async fn analytics_overview(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanViewAllBookings>,
     Query(query): Query<AnalyticsQuery>,
 ) -> (StatusCode, Json<ApiResponse<AnalyticsOverview>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
-
     let days = query.days.unwrap_or(30);
     let cutoff = Utc::now() - TimeDelta::days(days);
     let bookings = state_guard.db.list_bookings().await.unwrap_or_default();
     let users = state_guard.db.list_users().await.unwrap_or_default();
     let lots = state_guard.db.list_parking_lots().await.unwrap_or_default();
 
-    // ── Daily bookings ──────────────────────────────────────────────────────
-    let mut daily_bookings_map: BTreeMap<String, u64> = BTreeMap::new();
+    // ── Daily revenue and totals ────────────────────────────────────────────
     let mut daily_revenue_map: BTreeMap<String, f64> = BTreeMap::new();
     let mut peak_hours: [u64; 24] = [0; 24];
     let mut lot_booking_count: HashMap<uuid::Uuid, u64> = HashMap::new();
@@ -124,14 +148,12 @@ pub async fn analytics_overview(
         let day = (Utc::now() - Duration::days(days - 1 - i))
             .format("%Y-%m-%d")
             .to_string();
-        daily_bookings_map.entry(day.clone()).or_insert(0);
         daily_revenue_map.entry(day).or_insert(0.0);
     }
 
     for b in &bookings {
         if b.created_at >= cutoff {
             let date = b.created_at.format("%Y-%m-%d").to_string();
-            *daily_bookings_map.entry(date.clone()).or_insert(0) += 1;
             total_bookings_in_range += 1;
 
             // Revenue from booking pricing
@@ -157,13 +179,7 @@ pub async fn analytics_overview(
         }
     }
 
-    let daily_bookings: Vec<DailyDataPoint> = daily_bookings_map
-        .into_iter()
-        .map(|(date, value)| DailyDataPoint {
-            date,
-            value: value as f64,
-        })
-        .collect();
+    let daily_bookings = daily_booking_counts(&bookings, days, Utc::now());
 
     let daily_revenue: Vec<DailyDataPoint> = daily_revenue_map
         .into_iter()
@@ -280,6 +296,71 @@ mod tests {
         assert!(json.contains("42.5"));
     }
 
+    fn created_booking(created_at: DateTime<Utc>) -> Booking {
+        use parkhub_common::{BookingPricing, BookingStatus, PaymentStatus, Vehicle};
+        use uuid::Uuid;
+        Booking {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            lot_id: Uuid::new_v4(),
+            slot_id: Uuid::new_v4(),
+            slot_number: 1,
+            floor_name: "Ground".to_string(),
+            vehicle: Vehicle {
+                id: Uuid::new_v4(),
+                user_id: Uuid::new_v4(),
+                license_plate: "M-AB 123".to_string(),
+                make: None,
+                model: None,
+                color: None,
+                vehicle_type: parkhub_common::VehicleType::Car,
+                fuel_type: parkhub_common::FuelType::Unknown,
+                is_default: true,
+                created_at,
+            },
+            start_time: created_at,
+            end_time: created_at + TimeDelta::hours(2),
+            status: BookingStatus::Confirmed,
+            pricing: BookingPricing {
+                base_price: 0.0,
+                discount: 0.0,
+                tax: 0.0,
+                total: 0.0,
+                currency: "EUR".to_string(),
+                payment_status: PaymentStatus::Pending,
+                payment_method: None,
+                cancellation_fee: None,
+            },
+            created_at,
+            updated_at: created_at,
+            check_in_time: None,
+            check_out_time: None,
+            qr_code: None,
+            notes: None,
+            tenant_id: None,
+            tags: std::collections::BTreeMap::new(),
+            booked_for: None,
+        }
+    }
+
+    #[test]
+    fn daily_booking_counts_fill_empty_days() {
+        use chrono::TimeZone;
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let bookings = [
+            created_booking(now),
+            created_booking(now - TimeDelta::minutes(1)),
+            created_booking(now - TimeDelta::days(2)),
+            created_booking(now - TimeDelta::days(30)),
+        ];
+        let days = daily_booking_counts(&bookings, 3, now);
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[0].date, "2026-03-08");
+        assert_eq!(days[2].date, "2026-03-10");
+        let values: Vec<f64> = days.iter().map(|d| d.value).collect();
+        assert_eq!(values, [1.0, 0.0, 2.0]);
+    }
+
     #[test]
     fn hour_bin_covers_full_day() {
         let bins: Vec<HourBin> = (0..24)
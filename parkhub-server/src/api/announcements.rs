@@ -1,4 +1,12 @@
-//! Announcement handlers: public active list, admin CRUD.
+//! Announcement handlers: public active list, per-user banner feed, admin CRUD.
+//!
+//! Admins target an announcement at everyone or at one lot's users (anyone
+//! with a current or upcoming booking there). When an announcement goes live
+//! it is delivered as an in-app notification to every recipient and, with
+//! `send_email`, through the email queue. Clients show live announcements as
+//! a banner (`GET /api/v1/announcements`) until the user dismisses them;
+//! dismissals are kept per user in the `dismissed_announcements:{user_id}`
+//! setting.
 
 // AppState read/write guards are held across handler duration by design —
 // db access goes through its own inner RwLock. See workspace lint config.
@@ -9,21 +17,256 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
 };
+use std::collections::HashSet;
+
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
-use parkhub_common::models::{Announcement, AnnouncementSeverity};
+use parkhub_common::models::{
+    Announcement, AnnouncementAudience, AnnouncementSeverity, Booking, BookingStatus, Notification,
+    NotificationType, User,
+};
+use parkhub_common::{ApiResponse, ErrorCode};
 
+use crate::AppState;
 use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::Database;
 
 use super::{AuthUser, SharedState, check_admin};
 
+/// Whether an announcement is active and not yet expired.
+fn is_live(announcement: &Announcement, now: DateTime<Utc>) -> bool {
+    announcement.active && announcement.expires_at.is_none_or(|exp| exp > now)
+}
+
+/// Whether a booking puts its user in the audience of `lot_id` announcements:
+/// a current or upcoming booking in that lot.
+fn booking_targets_lot(booking: &Booking, lot_id: Uuid, now: DateTime<Utc>) -> bool {
+    booking.lot_id == lot_id
+        && matches!(
+            booking.status,
+            BookingStatus::Pending | BookingStatus::Confirmed | BookingStatus::Active
+        )
+        && booking.end_time > now
+}
+
+/// Whether a user whose current/upcoming bookings are in `user_lots` belongs
+/// to `audience`.
+fn in_audience(audience: &AnnouncementAudience, user_lots: &HashSet<Uuid>) -> bool {
+    match audience {
+        AnnouncementAudience::All => true,
+        AnnouncementAudience::Lot { lot_id } => user_lots.contains(lot_id),
+    }
+}
+
+/// Active users an announcement is delivered to.
+async fn audience_users(
+    state: &AppState,
+    audience: &AnnouncementAudience,
+) -> anyhow::Result<Vec<User>> {
+    let users = state.db.list_users().await?;
+    let targeted: Option<HashSet<Uuid>> = match audience {
+        AnnouncementAudience::All => None,
+        AnnouncementAudience::Lot { lot_id } => {
+            let now = Utc::now();
+            Some(
+                state
+                    .db
+                    .list_bookings()
+                    .await?
+                    .iter()
+                    .filter(|b| booking_targets_lot(b, *lot_id, now))
+                    .map(|b| b.user_id)
+                    .collect(),
+            )
+        }
+    };
+    Ok(users
+        .into_iter()
+        .filter(|u| u.is_active && targeted.as_ref().is_none_or(|ids| ids.contains(&u.id)))
+        .collect())
+}
+
+/// Deliver a live announcement as an in-app notification (and optionally an
+/// email) to every user in its audience. Returns the number of recipients.
+async fn deliver_announcement(
+    state: &AppState,
+    announcement: &Announcement,
+    send_email: bool,
+) -> anyhow::Result<usize> {
+    let recipients = audience_users(state, &announcement.audience).await?;
+    for user in &recipients {
+        let notification = Notification {
+            id: Uuid::new_v4(),
+            user_id: user.id,
+            notification_type: NotificationType::SystemMessage,
+            title: announcement.title.clone(),
+            message: announcement.message.clone(),
+            data: Some(serde_json::json!({
+                "announcement_id": announcement.id,
+                "severity": announcement.severity,
+            })),
+            read: false,
+            created_at: Utc::now(),
+        };
+        if let Err(e) = state.db.save_notification(&notification).await {
+            tracing::warn!(user_id = %user.id, "Failed to save announcement notification: {e}");
+        }
+
+        #[cfg(feature = "mod-email")]
+        if send_email {
+            let html = crate::email::build_announcement_email(
+                &user.name,
+                &announcement.title,
+                &announcement.message,
+                &state.config.organization_name,
+            );
+            if let Err(e) =
+                crate::email_queue::enqueue(&state.db, &user.email, &announcement.title, &html)
+                    .await
+            {
+                tracing::warn!(user_id = %user.id, "Failed to queue announcement email: {e}");
+            }
+        }
+    }
+    #[cfg(not(feature = "mod-email"))]
+    let _ = send_email;
+    Ok(recipients.len())
+}
+
+fn dismissed_key(user_id: Uuid) -> String {
+    format!("dismissed_announcements:{user_id}")
+}
+
+/// Announcement IDs the user has dismissed.
+async fn load_dismissed(db: &Database, user_id: Uuid) -> HashSet<Uuid> {
+    match db.get_setting(&dismissed_key(user_id)).await {
+        Ok(Some(val)) => serde_json::from_str(&val).unwrap_or_default(),
+        _ => HashSet::new(),
+    }
+}
+
+/// `GET /api/v1/announcements` — live announcements for the caller's banner
+#[utoipa::path(get, path = "/api/v1/announcements", tag = "Notifications",
+    summary = "Get my announcements",
+    description = "Returns live announcements addressed to the caller (everyone, or a lot \
+        they have a current or upcoming booking in) that they have not dismissed, newest first.",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Announcements to show", body = Vec<Announcement>))
+)]
+pub async fn get_my_announcements(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<Vec<Announcement>>>) {
+    let state_guard = state.read().await;
+    let announcements = match state_guard.db.list_announcements().await {
+        Ok(a) => a,
+        Err(e) => {
+            tracing::error!("Failed to list announcements: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list announcements",
+                )),
+            );
+        }
+    };
+
+    let now = Utc::now();
+    let user_lots: HashSet<Uuid> = state_guard
+        .db
+        .list_bookings_by_user(&auth_user.user_id.to_string())
+        .await
+        .unwrap_or_default()
+        .iter()
+        .filter(|b| booking_targets_lot(b, b.lot_id, now))
+        .map(|b| b.lot_id)
+        .collect();
+    let dismissed = load_dismissed(&state_guard.db, auth_user.user_id).await;
+
+    let mut visible: Vec<Announcement> = announcements
+        .into_iter()
+        .filter(|a| {
+            is_live(a, now) && in_audience(&a.audience, &user_lots) && !dismissed.contains(&a.id)
+        })
+        .collect();
+    visible.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    (StatusCode::OK, Json(ApiResponse::success(visible)))
+}
+
+/// `POST /api/v1/announcements/{id}/dismiss` — hide an announcement's banner
+#[utoipa::path(post, path = "/api/v1/announcements/{id}/dismiss", tag = "Notifications",
+    summary = "Dismiss an announcement",
+    description = "Hides the announcement from the caller's banner. Other users are unaffected.",
+    params(("id" = String, Path, description = "Announcement ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Dismissed"),
+        (status = 404, description = "Announcement not found"),
+    )
+)]
+pub async fn dismiss_announcement(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.read().await;
+    let existing: HashSet<Uuid> = match state_guard.db.list_announcements().await {
+        Ok(a) => a.into_iter().map(|a| a.id).collect(),
+        Err(e) => {
+            tracing::error!("Failed to list announcements: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    let Some(announcement_id) = Uuid::parse_str(&id).ok().filter(|i| existing.contains(i)) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Announcement not found",
+            )),
+        );
+    };
+
+    // Drop dismissals of deleted announcements while we are at it
+    let mut dismissed = load_dismissed(&state_guard.db, auth_user.user_id).await;
+    dismissed.retain(|i| existing.contains(i));
+    dismissed.insert(announcement_id);
+
+    let value = serde_json::to_string(&dismissed).unwrap_or_default();
+    match state_guard
+        .db
+        .set_setting(&dismissed_key(auth_user.user_id), &value)
+        .await
+    {
+        Ok(()) => (StatusCode::OK, Json(ApiResponse::success(()))),
+        Err(e) => {
+            tracing::error!("Failed to save dismissed announcements: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
 /// `GET /api/v1/announcements/active` — public, return active non-expired announcements
 #[utoipa::path(get, path = "/api/v1/announcements/active", tag = "Public",
     summary = "Get active announcements",
-    description = "Returns active non-expired announcements. No auth required.",
+    description = "Returns active non-expired announcements addressed to everyone. \
+        Lot-targeted announcements are only returned by `GET /api/v1/announcements`. \
+        No auth required.",
     responses((status = 200, description = "Success"))
 )]
 pub async fn get_active_announcements(
@@ -35,7 +278,7 @@ pub async fn get_active_announcements(
             let now = Utc::now();
             let active: Vec<Announcement> = announcements
                 .into_iter()
-                .filter(|a| a.active && a.expires_at.is_none_or(|exp| exp > now))
+                .filter(|a| is_live(a, now) && a.audience == AnnouncementAudience::All)
                 .collect();
             (StatusCode::OK, Json(ApiResponse::success(active)))
         }
@@ -44,7 +287,7 @@ pub async fn get_active_announcements(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list announcements",
                 )),
             )
@@ -64,8 +307,8 @@ pub async fn admin_list_announcements(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<Vec<Announcement>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     match state_guard.db.list_announcements().await {
@@ -75,7 +318,7 @@ pub async fn admin_list_announcements(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list announcements",
                 )),
             )
@@ -90,6 +333,32 @@ pub struct CreateAnnouncementRequest {
     severity: AnnouncementSeverity,
     active: Option<bool>,
     expires_at: Option<DateTime<Utc>>,
+    /// Defaults to everyone
+    audience: Option<AnnouncementAudience>,
+    /// Also email the announcement to every recipient
+    send_email: Option<bool>,
+}
+
+/// Reject lot audiences that point at a lot that does not exist.
+async fn validate_audience(
+    state: &AppState,
+    audience: &AnnouncementAudience,
+) -> Result<(), (StatusCode, Json<ApiResponse<Announcement>>)> {
+    if let AnnouncementAudience::Lot { lot_id } = audience
+        && !matches!(
+            state.db.get_parking_lot(&lot_id.to_string()).await,
+            Ok(Some(_))
+        )
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::ValidationError,
+                "Audience lot does not exist",
+            )),
+        ));
+    }
+    Ok(())
 }
 
 /// `POST /api/v1/admin/announcements` — admin: create announcement
@@ -98,7 +367,9 @@ pub struct CreateAnnouncementRequest {
     path = "/api/v1/admin/announcements",
     tag = "Admin",
     summary = "Create announcement",
-    description = "Create a new system announcement. Admin only.",
+    description = "Create a new system announcement for everyone or one lot's users. \
+        Active announcements are delivered as in-app notifications right away, and by email \
+        when `send_email` is set. Admin only.",
     security(("bearer_auth" = []))
 )]
 pub async fn admin_create_announcement(
@@ -107,8 +378,13 @@ pub async fn admin_create_announcement(
     Json(req): Json<CreateAnnouncementRequest>,
 ) -> (StatusCode, Json<ApiResponse<Announcement>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let audience = req.audience.unwrap_or_default();
+    if let Err(rejection) = validate_audience(&state_guard, &audience).await {
+        return rejection;
     }
 
     let announcement = Announcement {
@@ -120,14 +396,29 @@ pub async fn admin_create_announcement(
         created_by: Some(auth_user.user_id),
         expires_at: req.expires_at,
         created_at: Utc::now(),
+        audience,
     };
 
     match state_guard.db.save_announcement(&announcement).await {
         Ok(()) => {
+            let recipients = if is_live(&announcement, Utc::now()) {
+                deliver_announcement(&state_guard, &announcement, req.send_email.unwrap_or(false))
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::error!("Failed to deliver announcement: {e}");
+                        0
+                    })
+            } else {
+                0
+            };
             let audit = AuditEntry::new(AuditEventType::ConfigChanged)
                 .user(auth_user.user_id, "admin")
                 .resource("announcement", &announcement.id.to_string())
-                .details(serde_json::json!({ "action": "create", "title": &announcement.title }))
+                .details(serde_json::json!({
+                    "action": "create",
+                    "title": &announcement.title,
+                    "recipients": recipients,
+                }))
                 .log();
             audit.persist(&state_guard.db).await;
             (
@@ -140,7 +431,7 @@ pub async fn admin_create_announcement(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to create announcement",
                 )),
             )
@@ -179,6 +470,9 @@ pub struct UpdateAnnouncementRequest {
     #[schema(value_type = Option<String>)]
     #[serde(default)]
     expires_at: NullableField<DateTime<Utc>>,
+    audience: Option<AnnouncementAudience>,
+    /// Email recipients if this update makes the announcement go live
+    send_email: Option<bool>,
 }
 
 /// `PUT /api/v1/admin/announcements/{id}` — admin: update announcement
@@ -187,7 +481,8 @@ pub struct UpdateAnnouncementRequest {
     path = "/api/v1/admin/announcements/{id}",
     tag = "Admin",
     summary = "Update announcement",
-    description = "Update an existing announcement by ID. Admin only.",
+    description = "Update an existing announcement by ID. Activating an inactive \
+        announcement delivers it to its audience like a newly created one. Admin only.",
     security(("bearer_auth" = []))
 )]
 pub async fn admin_update_announcement(
@@ -197,8 +492,8 @@ pub async fn admin_update_announcement(
     Json(req): Json<UpdateAnnouncementRequest>,
 ) -> (StatusCode, Json<ApiResponse<Announcement>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     // Fetch all announcements and find by ID
@@ -208,7 +503,10 @@ pub async fn admin_update_announcement(
             tracing::error!("Failed to list announcements: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -216,10 +514,22 @@ pub async fn admin_update_announcement(
     let Some(mut announcement) = announcements.into_iter().find(|a| a.id.to_string() == id) else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Announcement not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Announcement not found",
+            )),
         );
     };
 
+    if let Some(audience) = req.audience {
+        if let Err(rejection) = validate_audience(&state_guard, &audience).await {
+            return rejection;
+        }
+        announcement.audience = audience;
+    }
+
+    let now = Utc::now();
+    let was_live = is_live(&announcement, now);
     if let Some(title) = req.title {
         announcement.title = title;
     }
@@ -239,13 +549,26 @@ pub async fn admin_update_announcement(
     }
 
     match state_guard.db.save_announcement(&announcement).await {
-        Ok(()) => (StatusCode::OK, Json(ApiResponse::success(announcement))),
+        Ok(()) => {
+            if !was_live
+                && is_live(&announcement, now)
+                && let Err(e) = deliver_announcement(
+                    &state_guard,
+                    &announcement,
+                    req.send_email.unwrap_or(false),
+                )
+                .await
+            {
+                tracing::error!("Failed to deliver announcement: {e}");
+            }
+            (StatusCode::OK, Json(ApiResponse::success(announcement)))
+        }
         Err(e) => {
             tracing::error!("Failed to update announcement: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to update announcement",
                 )),
             )
@@ -268,22 +591,25 @@ pub async fn admin_delete_announcement(
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     match state_guard.db.delete_announcement(&id).await {
         Ok(true) => (StatusCode::OK, Json(ApiResponse::success(()))),
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Announcement not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Announcement not found",
+            )),
         ),
         Err(e) => {
             tracing::error!("Failed to delete announcement: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to delete announcement",
                 )),
             )
@@ -317,6 +643,73 @@ mod tests {
         let req: CreateAnnouncementRequest = serde_json::from_str(json).unwrap();
         assert!(req.active.is_none());
         assert!(req.expires_at.is_none());
+        assert!(req.audience.is_none());
+        assert!(req.send_email.is_none());
+    }
+
+    #[test]
+    fn test_create_announcement_request_lot_audience() {
+        let lot_id = Uuid::new_v4();
+        let json = format!(
+            r#"{{"title":"Closed","message":"Garage closed","severity":"warning",
+                "audience":{{"type":"lot","lot_id":"{lot_id}"}},"send_email":true}}"#
+        );
+        let req: CreateAnnouncementRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(req.audience, Some(AnnouncementAudience::Lot { lot_id }));
+        assert_eq!(req.send_email, Some(true));
+    }
+
+    // ── Audience targeting ──────────────────────────────────────────────────
+
+    #[test]
+    fn in_audience_matches_all_and_booked_lots() {
+        let lot_a = Uuid::new_v4();
+        let lot_b = Uuid::new_v4();
+        let user_lots = HashSet::from([lot_a]);
+
+        assert!(in_audience(&AnnouncementAudience::All, &user_lots));
+        assert!(in_audience(&AnnouncementAudience::All, &HashSet::new()));
+        assert!(in_audience(
+            &AnnouncementAudience::Lot { lot_id: lot_a },
+            &user_lots
+        ));
+        assert!(!in_audience(
+            &AnnouncementAudience::Lot { lot_id: lot_b },
+            &user_lots
+        ));
+    }
+
+    #[test]
+    fn is_live_respects_active_flag_and_expiry() {
+        let now = Utc::now();
+        let mut ann = Announcement {
+            id: Uuid::new_v4(),
+            title: "T".to_string(),
+            message: "M".to_string(),
+            severity: AnnouncementSeverity::Info,
+            active: true,
+            created_by: None,
+            expires_at: None,
+            created_at: now,
+            audience: AnnouncementAudience::All,
+        };
+        assert!(is_live(&ann, now));
+        ann.expires_at = Some(now - chrono::Duration::minutes(1));
+        assert!(!is_live(&ann, now));
+        ann.expires_at = Some(now + chrono::Duration::minutes(1));
+        ann.active = false;
+        assert!(!is_live(&ann, now));
+    }
+
+    #[test]
+    fn audience_defaults_to_all_for_stored_records() {
+        let json = format!(
+            r#"{{"id":"{}","title":"T","message":"M","severity":"info","active":true,
+                "created_by":null,"expires_at":null,"created_at":"2026-01-01T00:00:00Z"}}"#,
+            Uuid::new_v4()
+        );
+        let ann: Announcement = serde_json::from_str(&json).unwrap();
+        assert_eq!(ann.audience, AnnouncementAudience::All);
     }
 
     // ── NullableField deserialization ────────────────────────────────────────
@@ -26,7 +26,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{AuthUser, check_admin};
 
@@ -183,10 +183,13 @@ pub async fn enhanced_audit_export(
     Query(params): Query<AuditExportParams>,
 ) -> (StatusCode, Json<ApiResponse<ExportResponse>>) {
     let state_guard = state.read().await;
-    if let Err((status, _msg)) = check_admin(&state_guard, &auth_user).await {
+    if let Err((status, _msg)) = check_admin(&auth_user) {
         return (
             status,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -200,7 +203,7 @@ pub async fn enhanced_audit_export(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_FORMAT",
+                ErrorCode::InvalidFormat,
                 "Supported formats: csv, json, pdf",
             )),
         );
@@ -213,7 +216,7 @@ pub async fn enhanced_audit_export(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_DATE",
+                ErrorCode::InvalidDate,
                 "Invalid 'from' date format. Use YYYY-MM-DD",
             )),
         );
@@ -224,7 +227,7 @@ pub async fn enhanced_audit_export(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_DATE",
+                ErrorCode::InvalidDate,
                 "Invalid 'to' date format. Use YYYY-MM-DD",
             )),
         );
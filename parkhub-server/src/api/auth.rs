@@ -2,29 +2,36 @@
 
 use axum::{
     Extension, Json,
-    extract::State,
-    http::{StatusCode, header},
+    extract::{ConnectInfo, Query, State},
+    http::{HeaderMap, StatusCode, header},
     response::{IntoResponse, Response},
 };
 use chrono::{Duration, Utc};
 use serde::Deserialize;
+use std::net::SocketAddr;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use parkhub_common::{
-    ApiResponse, AuthTokens, LoginRequest, LoginResponse, RefreshTokenRequest, RegisterRequest,
-    User, UserPreferences, UserRole,
+    ApiResponse, AuthTokens, ErrorCode, LoginRequest, LoginResponse, RefreshTokenRequest,
+    RegisterRequest, User, UserPreferences, UserRole,
 };
 
+use crate::AppState;
 use crate::audit::{AuditEntry, AuditEventType};
-use crate::db::Session;
+use crate::db::{Database, Session};
 #[cfg(feature = "mod-email")]
 use crate::email;
 use crate::metrics;
+use crate::validation::ValidatedJson;
 
-use super::security::{TwoFactorRequiredResponse, TwoFactorTempTokenStore, is_2fa_enabled};
+use super::auth_guard::{AuthGuard, GuardScope, too_many_attempts};
+use super::security::{
+    LoginClient, TwoFactorRequiredResponse, TwoFactorTempTokenStore, is_2fa_enabled, record_login,
+};
 use super::{
-    SharedState, generate_access_token, hash_password, hash_password_simple, verify_password,
+    SharedState, generate_access_token, hash_password, hash_password_simple, session_tokens,
+    verify_password,
 };
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -127,6 +134,19 @@ struct PasswordResetToken {
 // Handlers
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Sign-in refused because the user is at `max_concurrent_sessions` and
+/// the session limit policy is `reject`.
+pub(super) fn session_limit_response() -> Response {
+    (
+        StatusCode::CONFLICT,
+        Json(ApiResponse::<LoginResponse>::error(
+            ErrorCode::SessionLimitReached,
+            "Maximum number of concurrent sessions reached. Sign out on another device first.",
+        )),
+    )
+        .into_response()
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/auth/login",
@@ -138,12 +158,20 @@ struct PasswordResetToken {
         (status = 200, description = "Login successful"),
         (status = 401, description = "Invalid credentials"),
         (status = 403, description = "Account disabled"),
+        (status = 409, description = "Session limit reached (`session_limit_policy = \"reject\"`)"),
+        (status = 429, description = "Too many failed attempts for this account; see `Retry-After`"),
     )
 )]
-#[tracing::instrument(skip(state, temp_token_store, request), fields(username = %request.username))]
+#[tracing::instrument(
+    skip(state, temp_token_store, auth_guard, headers, connect_info, request),
+    fields(username = %request.username)
+)]
 pub async fn login(
     State(state): State<SharedState>,
     Extension(temp_token_store): Extension<Arc<TwoFactorTempTokenStore>>,
+    Extension(auth_guard): Extension<Arc<AuthGuard>>,
+    headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
     Json(request): Json<LoginRequest>,
 ) -> Response {
     // ── Input length validation (issue #115) ────────────────────────────────
@@ -151,7 +179,7 @@ pub async fn login(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::<LoginResponse>::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "Username/email must be at most 254 characters",
             )),
         )
@@ -168,6 +196,12 @@ pub async fn login(
             if let Ok(Some(u)) = state_guard.db.get_user_by_email(&request.username).await {
                 u
             } else {
+                // Unknown accounts are delayed like real ones, so the delay
+                // does not reveal which accounts exist
+                if let Err(retry_after) = auth_guard.check(GuardScope::Login, &request.username) {
+                    return too_many_attempts(retry_after);
+                }
+                auth_guard.record_failure(GuardScope::Login, &request.username);
                 AuditEntry::new(AuditEventType::LoginFailed)
                     .error("User not found")
                     .log();
@@ -175,7 +209,7 @@ pub async fn login(
                 return (
                     StatusCode::UNAUTHORIZED,
                     Json(ApiResponse::<LoginResponse>::error(
-                        "INVALID_CREDENTIALS",
+                        ErrorCode::InvalidCredentials,
                         "Invalid username or password",
                     )),
                 )
@@ -187,7 +221,7 @@ pub async fn login(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<LoginResponse>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Internal server error",
                 )),
             )
@@ -200,36 +234,48 @@ pub async fn login(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::<LoginResponse>::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "Password must not exceed 256 characters",
             )),
         )
             .into_response();
     }
 
+    // Known accounts are tracked by ID, so username and email share one count
+    let guard_account = user.id.to_string();
+    if let Err(retry_after) = auth_guard.check(GuardScope::Login, &guard_account) {
+        return too_many_attempts(retry_after);
+    }
+
+    let client = LoginClient::from_request(&headers, connect_info.map(|Extension(ci)| ci.0));
+
     // Verify password
     if !verify_password(&request.password, &user.password_hash).await {
+        auth_guard.record_failure(GuardScope::Login, &guard_account);
         AuditEntry::new(AuditEventType::LoginFailed)
             .user(user.id, &user.username)
             .error("Invalid password")
             .log();
+        record_login(&state_guard, &user, &client, Some("invalid_password")).await;
         metrics::record_auth_event("login", false);
         return (
             StatusCode::UNAUTHORIZED,
             Json(ApiResponse::<LoginResponse>::error(
-                "INVALID_CREDENTIALS",
+                ErrorCode::InvalidCredentials,
                 "Invalid username or password",
             )),
         )
             .into_response();
     }
+    auth_guard.clear(GuardScope::Login, &guard_account);
 
     // Check if user is active
     if !user.is_active {
+        record_login(&state_guard, &user, &client, Some("account_disabled")).await;
         return (
             StatusCode::FORBIDDEN,
             Json(ApiResponse::<LoginResponse>::error(
-                "ACCOUNT_DISABLED",
+                ErrorCode::AccountDisabled,
                 "This account has been disabled",
             )),
         )
@@ -239,7 +285,7 @@ pub async fn login(
     // ── 2FA enforcement ────────────────────────────────────────────────────────
     // If the user has 2FA enabled, issue a short-lived temp token instead of a
     // full session. The client must complete the flow via POST /api/v1/auth/2fa/login.
-    let session_hours = i64::from(state_guard.config.session_timeout_minutes).max(60) / 60;
+    let session_hours = i64::from(state_guard.config.session_lifetime_hours).max(1);
     let role_str = format!("{:?}", user.role).to_lowercase();
 
     if is_2fa_enabled(&state_guard, user.id).await {
@@ -270,25 +316,47 @@ pub async fn login(
     }
 
     // ── Normal login (no 2FA) ──────────────────────────────────────────────────
-    let session = Session::new(user.id, session_hours, &user.username, &role_str);
-    let access_token = generate_access_token();
-
-    if let Err(e) = state_guard.db.save_session(&access_token, &session).await {
-        tracing::error!("Failed to save session: {}", e);
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<LoginResponse>::error(
-                "SERVER_ERROR",
-                "Failed to create session",
-            )),
-        )
-            .into_response();
+    match session_tokens::make_room_for_session(&state_guard, user.id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            record_login(&state_guard, &user, &client, Some("session_limit_reached")).await;
+            metrics::record_auth_event("login", false);
+            return session_limit_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to check session limit: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<LoginResponse>::error(
+                    ErrorCode::ServerError,
+                    "Failed to create session",
+                )),
+            )
+                .into_response();
+        }
     }
+    let session = Session::new(user.id, session_hours, &user.username, &role_str)
+        .with_client(Some(client.ip.clone()), client.user_agent.clone());
+    let access_token = match session_tokens::start_session(&state_guard.db, &session).await {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to save session: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<LoginResponse>::error(
+                    ErrorCode::ServerError,
+                    "Failed to create session",
+                )),
+            )
+                .into_response();
+        }
+    };
 
     let audit = AuditEntry::new(AuditEventType::LoginSuccess)
         .user(user.id, &user.username)
         .log();
     audit.persist(&state_guard.db).await;
+    record_login(&state_guard, &user, &client, None).await;
     drop(state_guard);
     metrics::record_auth_event("login", true);
 
@@ -326,14 +394,77 @@ pub async fn login(
         (status = 200, description = "Login successful"),
         (status = 401, description = "Invalid credentials"),
         (status = 403, description = "Account disabled"),
+        (status = 409, description = "Session limit reached (`session_limit_policy = \"reject\"`)"),
+        (status = 429, description = "Too many failed attempts for this account; see `Retry-After`"),
     )
 )]
 pub async fn login_alias(
     State(state): State<SharedState>,
     Extension(temp_token_store): Extension<Arc<TwoFactorTempTokenStore>>,
+    Extension(auth_guard): Extension<Arc<AuthGuard>>,
+    headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
     Json(request): Json<LoginRequest>,
 ) -> Response {
-    login(State(state), Extension(temp_token_store), Json(request)).await
+    login(
+        State(state),
+        Extension(temp_token_store),
+        Extension(auth_guard),
+        headers,
+        connect_info,
+        Json(request),
+    )
+    .await
+}
+
+/// Validate a password chosen at sign-up; `Err` carries the error code and
+/// message to answer with.
+pub(super) fn check_new_password(
+    password: &str,
+    confirmation: &str,
+) -> Result<(), (ErrorCode, &'static str)> {
+    // Password confirmation must match
+    if password != confirmation {
+        return Err((
+            ErrorCode::PasswordMismatch,
+            "Password and confirmation do not match",
+        ));
+    }
+
+    // Password complexity: min 8 chars, at least one lowercase, uppercase, digit
+    if password.len() < 8
+        || !password.chars().any(|c| c.is_ascii_lowercase())
+        || !password.chars().any(|c| c.is_ascii_uppercase())
+        || !password.chars().any(|c| c.is_ascii_digit())
+    {
+        return Err((
+            ErrorCode::WeakPassword,
+            "Password must be at least 8 characters with uppercase, lowercase, and a digit",
+        ));
+    }
+
+    // Reject excessively long passwords before hashing (Argon2 CPU DoS prevention)
+    if password.len() > 256 {
+        return Err((
+            ErrorCode::InvalidInput,
+            "Password must not exceed 256 characters",
+        ));
+    }
+    Ok(())
+}
+
+/// Username for a new account: the local part of `email`, with a number
+/// appended if it is taken. `None` once 99 numbered variants are taken too.
+pub(super) async fn username_for_email(db: &Database, email: &str) -> Option<String> {
+    let username = email.split('@').next().unwrap_or("user").to_string();
+
+    let mut final_username = username.clone();
+    let mut counter = 1u32;
+    while counter <= 99 && matches!(db.get_user_by_username(&final_username).await, Ok(Some(_))) {
+        final_username = format!("{username}{counter}");
+        counter += 1;
+    }
+    (counter <= 99).then_some(final_username)
 }
 
 #[utoipa::path(
@@ -350,34 +481,14 @@ pub async fn login_alias(
         (status = 409, description = "Email already exists"),
     )
 )]
-#[tracing::instrument(skip(state, request), fields(email = %request.email))]
+#[tracing::instrument(skip(state, headers, connect_info, request), fields(email = %request.email))]
 #[allow(clippy::too_many_lines)]
 pub async fn register(
     State(state): State<SharedState>,
-    Json(request): Json<RegisterRequest>,
+    headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    ValidatedJson(request): ValidatedJson<RegisterRequest>,
 ) -> Response {
-    // ── Input length validation (issue #115) ────────────────────────────────
-    if request.email.len() > 254 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::<LoginResponse>::error(
-                "INVALID_INPUT",
-                "Email must be at most 254 characters",
-            )),
-        )
-            .into_response();
-    }
-    if request.name.len() > 100 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::<LoginResponse>::error(
-                "INVALID_INPUT",
-                "Name must be at most 100 characters",
-            )),
-        )
-            .into_response();
-    }
-
     let state_guard = state.read().await;
 
     // Enforce allow_self_registration setting
@@ -385,50 +496,19 @@ pub async fn register(
         return (
             StatusCode::FORBIDDEN,
             Json(ApiResponse::<LoginResponse>::error(
-                "REGISTRATION_DISABLED",
+                ErrorCode::RegistrationDisabled,
                 "Self-registration is disabled. Contact an administrator.",
             )),
         )
             .into_response();
     }
 
-    // Password confirmation must match
-    if request.password != request.password_confirmation {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::<LoginResponse>::error(
-                "PASSWORD_MISMATCH",
-                "Password and confirmation do not match",
-            )),
-        )
-            .into_response();
-    }
-
-    // Password complexity: min 8 chars, at least one lowercase, uppercase, digit
-    let pw = &request.password;
-    if pw.len() < 8
-        || !pw.chars().any(|c| c.is_ascii_lowercase())
-        || !pw.chars().any(|c| c.is_ascii_uppercase())
-        || !pw.chars().any(|c| c.is_ascii_digit())
+    if let Err((code, message)) =
+        check_new_password(&request.password, &request.password_confirmation)
     {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::<LoginResponse>::error(
-                "WEAK_PASSWORD",
-                "Password must be at least 8 characters with uppercase, lowercase, and a digit",
-            )),
-        )
-            .into_response();
-    }
-
-    // Reject excessively long passwords before hashing (Argon2 CPU DoS prevention)
-    if request.password.len() > 256 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::<LoginResponse>::error(
-                "INVALID_INPUT",
-                "Password must not exceed 256 characters",
-            )),
+            Json(ApiResponse::<LoginResponse>::error(code, message)),
         )
             .into_response();
     }
@@ -438,43 +518,23 @@ pub async fn register(
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::<LoginResponse>::error(
-                "EMAIL_EXISTS",
+                ErrorCode::EmailExists,
                 "An account with this email already exists",
             )),
         )
             .into_response();
     }
 
-    // Generate username from email
-    let username = request
-        .email
-        .split('@')
-        .next()
-        .unwrap_or("user")
-        .to_string();
-
-    // Check if username already exists, append number if needed (cap at 99)
-    let mut final_username = username.clone();
-    let mut counter = 1u32;
-    while counter <= 99
-        && matches!(
-            state_guard.db.get_user_by_username(&final_username).await,
-            Ok(Some(_))
-        )
-    {
-        final_username = format!("{username}{counter}");
-        counter += 1;
-    }
-    if counter > 99 {
+    let Some(final_username) = username_for_email(&state_guard.db, &request.email).await else {
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::<LoginResponse>::error(
-                "USERNAME_EXHAUSTED",
+                ErrorCode::UsernameExhausted,
                 "Too many accounts with this email prefix. Please use a different email address.",
             )),
         )
             .into_response();
-    }
+    };
 
     // Hash password
     let password_hash = match hash_password(&request.password).await {
@@ -510,6 +570,7 @@ pub async fn register(
         cost_center: None,
         department: None,
         settings: None,
+        must_change_password: false,
     };
 
     if let Err(e) = state_guard.db.save_user(&user).await {
@@ -517,7 +578,7 @@ pub async fn register(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::<LoginResponse>::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to create account",
             )),
         )
@@ -550,37 +611,42 @@ pub async fn register(
         let user_email = user.email.clone();
         let user_name = user.name.clone();
         let org_name = state_guard.config.organization_name.clone();
+        let db = state_guard.db.clone();
         tokio::spawn(async move {
             let email_html = crate::email::build_welcome_email(&user_name, &org_name);
-            if let Err(e) = crate::email::send_email(
+            if let Err(e) = crate::email_queue::enqueue(
+                &db,
                 &user_email,
                 &format!("Welcome to {org_name}"),
                 &email_html,
             )
             .await
             {
-                tracing::warn!("Failed to send welcome email: {}", e);
+                tracing::warn!("Failed to queue welcome email: {}", e);
             }
         });
     }
 
-    // Create session using configured timeout (converted from minutes to hours, minimum 1h)
-    let session_hours = i64::from(state_guard.config.session_timeout_minutes).max(60) / 60;
+    // Create session using the configured lifetime (minimum 1 h)
+    let session_hours = i64::from(state_guard.config.session_lifetime_hours).max(1);
     let role_str = format!("{:?}", user.role).to_lowercase();
-    let session = Session::new(user.id, session_hours, &user.username, &role_str);
-    let access_token = generate_access_token();
-
-    if let Err(e) = state_guard.db.save_session(&access_token, &session).await {
-        tracing::error!("Failed to save session: {}", e);
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<LoginResponse>::error(
-                "SERVER_ERROR",
-                "Failed to create session",
-            )),
-        )
-            .into_response();
-    }
+    let client = LoginClient::from_request(&headers, connect_info.map(|Extension(ci)| ci.0));
+    let session = Session::new(user.id, session_hours, &user.username, &role_str)
+        .with_client(Some(client.ip), client.user_agent);
+    let access_token = match session_tokens::start_session(&state_guard.db, &session).await {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to save session: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<LoginResponse>::error(
+                    ErrorCode::ServerError,
+                    "Failed to create session",
+                )),
+            )
+                .into_response();
+        }
+    };
     drop(state_guard);
 
     // Create response — never send password_hash to clients
@@ -622,9 +688,11 @@ pub async fn register(
 )]
 pub async fn register_alias(
     State(state): State<SharedState>,
-    Json(request): Json<RegisterRequest>,
+    headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    ValidatedJson(request): ValidatedJson<RegisterRequest>,
 ) -> Response {
-    register(State(state), Json(request)).await
+    register(State(state), headers, connect_info, ValidatedJson(request)).await
 }
 
 #[utoipa::path(
@@ -632,11 +700,13 @@ pub async fn register_alias(
     path = "/api/v1/auth/refresh",
     tag = "Authentication",
     summary = "Refresh access token",
-    description = "Exchange a valid refresh token for a new access/refresh token pair.",
+    description = "Exchange a valid refresh token for a new access/refresh token pair. \
+        Each refresh token works once: presenting a spent one again signs the user out \
+        everywhere (`REFRESH_TOKEN_REUSED`).",
     request_body = RefreshTokenRequest,
     responses(
         (status = 200, description = "Token refreshed successfully"),
-        (status = 401, description = "Invalid or expired refresh token"),
+        (status = 401, description = "Invalid, expired or reused refresh token"),
     )
 )]
 #[tracing::instrument(skip(state, request))]
@@ -647,17 +717,30 @@ pub async fn refresh_token(
     let state_guard = state.read().await;
 
     // Look up the session that holds this refresh token
-    let (old_access_token, session) = match state_guard
+    let (old_session_id, session) = match state_guard
         .db
         .get_session_by_refresh_token(&request.refresh_token)
         .await
     {
         Ok(Some(pair)) => pair,
         Ok(None) => {
+            // A token that was already exchanged is being replayed
+            match state_guard
+                .db
+                .get_rotated_refresh_token(&request.refresh_token)
+                .await
+            {
+                Ok(Some(rotated)) => {
+                    return refresh_token_reused(&state_guard, rotated.user_id, rotated.family_id)
+                        .await;
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Failed to check for refresh token reuse: {}", e),
+            }
             return (
                 StatusCode::UNAUTHORIZED,
                 Json(ApiResponse::<AuthTokens>::error(
-                    "INVALID_REFRESH_TOKEN",
+                    ErrorCode::InvalidRefreshToken,
                     "Refresh token is invalid or expired",
                 )),
             )
@@ -668,7 +751,7 @@ pub async fn refresh_token(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<AuthTokens>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Internal server error",
                 )),
             )
@@ -676,6 +759,34 @@ pub async fn refresh_token(
         }
     };
 
+    // Impersonation sessions are never handed a refresh token and must not
+    // outlive their fixed lifetime
+    if session.impersonator_id.is_some() {
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::<AuthTokens>::error(
+                ErrorCode::InvalidRefreshToken,
+                "Refresh token is invalid or expired",
+            )),
+        )
+            .into_response();
+    }
+
+    // An idle session cannot be revived by refreshing it
+    if session_tokens::idle_timeout(&state_guard.config).is_some_and(|t| session.is_idle(t)) {
+        if let Err(e) = session_tokens::end_session(&state_guard, &old_session_id).await {
+            tracing::warn!("Failed to end idle session: {}", e);
+        }
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::<AuthTokens>::error(
+                ErrorCode::SessionIdleTimeout,
+                "Session ended after inactivity",
+            )),
+        )
+            .into_response();
+    }
+
     // Re-query the database to get the current role and verify the user is still active.
     // This prevents stale role claims (issue #55): a role change takes effect on the next
     // refresh rather than being carried forward from the old session indefinitely.
@@ -685,7 +796,7 @@ pub async fn refresh_token(
             return (
                 StatusCode::UNAUTHORIZED,
                 Json(ApiResponse::<AuthTokens>::error(
-                    "INVALID_REFRESH_TOKEN",
+                    ErrorCode::InvalidRefreshToken,
                     "User account no longer exists",
                 )),
             )
@@ -696,7 +807,7 @@ pub async fn refresh_token(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<AuthTokens>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Internal server error",
                 )),
             )
@@ -708,7 +819,7 @@ pub async fn refresh_token(
         return (
             StatusCode::UNAUTHORIZED,
             Json(ApiResponse::<AuthTokens>::error(
-                "ACCOUNT_DISABLED",
+                ErrorCode::AccountDisabled,
                 "This account has been disabled",
             )),
         )
@@ -717,41 +828,56 @@ pub async fn refresh_token(
 
     let current_role = format!("{:?}", current_user.role).to_lowercase();
 
-    // Create a fresh session using the configured session timeout (minimum 1 h)
-    let session_hours = i64::from(state_guard.config.session_timeout_minutes).max(60) / 60;
+    // Spend the refresh token before issuing its successor. Losing the race
+    // to a concurrent refresh means the same token was presented twice.
+    match session_tokens::rotate_session(&state_guard, &old_session_id, &session).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return refresh_token_reused(&state_guard, session.user_id, session.family_id).await;
+        }
+        Err(e) => {
+            tracing::error!("Failed to rotate refresh token: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<AuthTokens>::error(
+                    ErrorCode::ServerError,
+                    "Failed to refresh token",
+                )),
+            )
+                .into_response();
+        }
+    }
+
+    // Create a fresh session using the configured session lifetime (minimum 1 h)
+    let session_hours = i64::from(state_guard.config.session_lifetime_hours).max(1);
+    // The refreshed session belongs to the same device, so it keeps the
+    // client details of the one it replaces
     let new_session = Session::new(
         session.user_id,
         session_hours,
         &session.username,
         &current_role,
-    );
-    let new_access_token = generate_access_token();
-
+    )
+    .with_client(session.ip_address.clone(), session.user_agent.clone())
+    .in_family(session.family_id);
     // Save new session
-    if let Err(e) = state_guard
-        .db
-        .save_session(&new_access_token, &new_session)
-        .await
+    let new_access_token = match session_tokens::start_session(&state_guard.db, &new_session).await
     {
-        tracing::error!("Failed to save refreshed session: {}", e);
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<AuthTokens>::error(
-                "SERVER_ERROR",
-                "Failed to refresh token",
-            )),
-        )
-            .into_response();
-    }
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to save refreshed session: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<AuthTokens>::error(
+                    ErrorCode::ServerError,
+                    "Failed to refresh token",
+                )),
+            )
+                .into_response();
+        }
+    };
 
-    // Invalidate old session
     drop(state_guard);
-    {
-        let state_guard = state.read().await;
-        if let Err(e) = state_guard.db.delete_session(&old_access_token).await {
-            tracing::warn!("Failed to delete old session during refresh: {}", e);
-        }
-    }
 
     tracing::info!(
         user_id = %session.user_id,
@@ -775,6 +901,65 @@ pub async fn refresh_token(
     )
 }
 
+/// Answer a refresh with an already spent token. The token must have been
+/// copied from its owner, so every session of the user is ended and the
+/// incident is logged as a security alert.
+async fn refresh_token_reused(state: &AppState, user_id: Uuid, family_id: Uuid) -> Response {
+    // The family's current refresh token is as compromised as the replayed
+    // one, so it is rotated out rather than just dropped: presenting it
+    // later is reported as reuse too.
+    let mut revoked = 0;
+    match state.db.list_sessions_by_user(user_id).await {
+        Ok(sessions) => {
+            for (session_id, session) in sessions.iter().filter(|(_, s)| s.family_id == family_id) {
+                match session_tokens::rotate_session(state, session_id, session).await {
+                    Ok(true) => revoked += 1,
+                    Ok(false) => {}
+                    Err(e) => tracing::error!("Failed to retire refresh token family: {}", e),
+                }
+            }
+        }
+        Err(e) => tracing::error!("Failed to list sessions after refresh token reuse: {}", e),
+    }
+    revoked += session_tokens::end_user_sessions(state, user_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("Failed to revoke sessions after refresh token reuse: {}", e);
+            0
+        });
+    let username = match state.db.get_user(&user_id.to_string()).await {
+        Ok(Some(user)) => user.username,
+        _ => String::new(),
+    };
+    tracing::warn!(
+        %user_id,
+        %family_id,
+        revoked_sessions = revoked,
+        "SECURITY: refresh token reuse detected, all sessions of the user revoked"
+    );
+    AuditEntry::new(AuditEventType::RefreshTokenReused)
+        .user(user_id, &username)
+        .resource("user", &user_id.to_string())
+        .details(serde_json::json!({
+            "family_id": family_id,
+            "revoked_sessions": revoked,
+        }))
+        .error("Refresh token reused")
+        .log()
+        .persist(&state.db)
+        .await;
+    metrics::record_auth_event("refresh_reuse", false);
+
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ApiResponse::<AuthTokens>::error(
+            ErrorCode::RefreshTokenReused,
+            "Refresh token was already used; all sessions have been signed out",
+        )),
+    )
+        .into_response()
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/refresh",
@@ -799,7 +984,8 @@ pub async fn refresh_token_alias(
 /// Accepts `{"email": "..."}`, generates a one-time reset token (UUID),
 /// stores it in the database with a 1-hour expiry, and sends a reset link
 /// to the user's email address.  Always returns 200 to prevent user
-/// enumeration attacks.
+/// enumeration attacks, unless the address has requested too many resets
+/// recently (429, whether or not it belongs to an account).
 #[utoipa::path(
     post,
     path = "/api/v1/auth/forgot-password",
@@ -809,13 +995,22 @@ pub async fn refresh_token_alias(
     request_body = ForgotPasswordRequest,
     responses(
         (status = 200, description = "Reset email sent (always succeeds to prevent enumeration)"),
+        (status = 429, description = "Too many reset requests for this address; see `Retry-After`"),
     )
 )]
-#[tracing::instrument(skip(state, request), fields(email = %request.email))]
+#[tracing::instrument(skip(state, auth_guard, request), fields(email = %request.email))]
 pub async fn forgot_password(
     State(state): State<SharedState>,
+    Extension(auth_guard): Extension<Arc<AuthGuard>>,
     Json(request): Json<ForgotPasswordRequest>,
-) -> (StatusCode, Json<ApiResponse<()>>) {
+) -> Response {
+    // Every request counts: there is no failure to wait for, and each one
+    // may send an email
+    if let Err(retry_after) = auth_guard.check(GuardScope::PasswordReset, &request.email) {
+        return too_many_attempts(retry_after);
+    }
+    auth_guard.record_failure(GuardScope::PasswordReset, &request.email);
+
     let state_guard = state.read().await;
 
     // Look up the user — silently succeed even if not found (anti-enumeration)
@@ -824,7 +1019,7 @@ pub async fn forgot_password(
             email = %request.email,
             "Forgot-password request for unknown email — silently accepted"
         );
-        return (StatusCode::OK, Json(ApiResponse::success(())));
+        return (StatusCode::OK, Json(ApiResponse::success(()))).into_response();
     };
 
     // Generate a cryptographically random token (32 bytes, hex-encoded)
@@ -844,8 +1039,12 @@ pub async fn forgot_password(
             tracing::error!("Failed to serialize reset token: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
-            );
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+                .into_response();
         }
     };
 
@@ -855,14 +1054,19 @@ pub async fn forgot_password(
         tracing::error!("Failed to store reset token: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
-        );
+            Json(ApiResponse::<()>::error(
+                ErrorCode::ServerError,
+                "Internal server error",
+            )),
+        )
+            .into_response();
     }
 
     // Build and send the reset email (gracefully degraded if SMTP not configured)
     let app_url = std::env::var("APP_URL").unwrap_or_else(|_| "http://localhost:8443".to_string());
     let reset_url = format!("{app_url}/reset-password?token={reset_token}");
     let org_name = state_guard.config.organization_name.clone();
+    let db = state_guard.db.clone();
 
     drop(state_guard);
 
@@ -870,19 +1074,21 @@ pub async fn forgot_password(
     {
         let html = email::build_password_reset_email(&reset_url, &org_name);
 
-        // Fire-and-forget: email errors are logged but do not fail the request
-        if let Err(e) = email::send_email(&user.email, "Reset your password", &html).await {
+        // Queued for delivery with retries; errors are logged but do not fail the request
+        if let Err(e) =
+            crate::email_queue::enqueue(&db, &user.email, "Reset your password", &html).await
+        {
             tracing::warn!(
                 user_id = %user.id,
                 error = %e,
-                "Failed to send password-reset email"
+                "Failed to queue password-reset email"
             );
         }
     }
 
     #[cfg(not(feature = "mod-email"))]
     {
-        let _ = (&reset_url, &org_name);
+        let _ = (&reset_url, &org_name, &db);
         tracing::info!(
             user_id = %user.id,
             "Email module disabled — password reset email not sent"
@@ -894,13 +1100,14 @@ pub async fn forgot_password(
         "Password reset token generated"
     );
 
-    (StatusCode::OK, Json(ApiResponse::success(())))
+    (StatusCode::OK, Json(ApiResponse::success(()))).into_response()
 }
 
 /// `POST /api/v1/auth/reset-password`
 ///
 /// Accepts `{"token": "...", "password": "..."}`, validates the token,
-/// updates the user's password, and invalidates the token.
+/// updates the user's password, and invalidates the token. Lifts any login
+/// delay on the account.
 #[utoipa::path(
     post,
     path = "/api/v1/auth/reset-password",
@@ -913,9 +1120,10 @@ pub async fn forgot_password(
         (status = 400, description = "Invalid or expired token"),
     )
 )]
-#[tracing::instrument(skip(state, request))]
+#[tracing::instrument(skip(state, auth_guard, request))]
 pub async fn reset_password(
     State(state): State<SharedState>,
+    Extension(auth_guard): Extension<Arc<AuthGuard>>,
     Json(request): Json<ResetPasswordRequest>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
     let state_guard = state.read().await;
@@ -926,7 +1134,7 @@ pub async fn reset_password(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_TOKEN",
+                ErrorCode::InvalidToken,
                 "Reset token is invalid or has already been used",
             )),
         );
@@ -938,7 +1146,10 @@ pub async fn reset_password(
             tracing::error!("Failed to deserialize reset token: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -952,7 +1163,7 @@ pub async fn reset_password(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "TOKEN_EXPIRED",
+                ErrorCode::TokenExpired,
                 "Reset token has expired",
             )),
         );
@@ -963,7 +1174,7 @@ pub async fn reset_password(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "Password must not exceed 256 characters",
             )),
         );
@@ -977,7 +1188,7 @@ pub async fn reset_password(
         );
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_PASSWORD", msg)),
+            Json(ApiResponse::error(ErrorCode::InvalidPassword, msg)),
         );
     }
 
@@ -985,7 +1196,10 @@ pub async fn reset_password(
     let Ok(Some(mut user)) = state_guard.db.get_user(&token_data.user_id).await else {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_TOKEN", "User not found")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidToken,
+                "User not found",
+            )),
         );
     };
 
@@ -996,12 +1210,17 @@ pub async fn reset_password(
             tracing::error!("Password hashing failed: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
     user.password_hash = new_hash;
+    // The user picked this password themselves
+    user.must_change_password = false;
     user.updated_at = Utc::now();
 
     if let Err(e) = state_guard.db.save_user(&user).await {
@@ -1009,7 +1228,7 @@ pub async fn reset_password(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update password",
             )),
         );
@@ -1022,11 +1241,15 @@ pub async fn reset_password(
 
     // Invalidate all existing sessions for this user — a password change must
     // force re-authentication on every device.
-    if let Err(e) = state_guard.db.delete_sessions_by_user(user.id).await {
+    if let Err(e) = session_tokens::end_user_sessions(&state_guard, user.id).await {
         tracing::warn!(user_id = %user.id, error = %e, "Failed to invalidate sessions after password reset");
     }
     drop(state_guard);
 
+    // The owner proved control of the account; failures by others no longer count
+    auth_guard.clear(GuardScope::Login, &user.id.to_string());
+    auth_guard.clear(GuardScope::PasswordReset, &user.email);
+
     AuditEntry::new(AuditEventType::PasswordChanged)
         .user(user.id, &user.username)
         .log();
@@ -1043,23 +1266,34 @@ pub async fn reset_password(
 // Logout
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// Query parameters for `POST /api/v1/auth/logout`.
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct LogoutParams {
+    /// Also revoke every other session of the same user ("log out everywhere").
+    #[serde(default)]
+    pub all: bool,
+}
+
 /// `POST /api/v1/auth/logout`
 ///
 /// Clears the httpOnly auth cookie. If a Bearer token is present in the
 /// Authorization header, the corresponding session is also invalidated
-/// server-side.
+/// server-side; with `?all=true` every session of that user is.
 #[utoipa::path(
     post,
     path = "/api/v1/auth/logout",
     tag = "Authentication",
     summary = "Log out",
-    description = "Clear the auth cookie and optionally invalidate the server session.",
+    description = "Clear the auth cookie and invalidate the current server session. \
+        Pass `all=true` to revoke all of the user's sessions.",
+    params(LogoutParams),
     responses(
         (status = 200, description = "Logged out successfully"),
     )
 )]
 pub async fn logout(
     State(state): State<SharedState>,
+    Query(params): Query<LogoutParams>,
     request: axum::http::Request<axum::body::Body>,
 ) -> Response {
     // Try to extract the token from the Authorization header or cookie,
@@ -1072,12 +1306,45 @@ pub async fn logout(
         .map(String::from)
         .or_else(|| extract_cookie_token(request.headers()));
 
-    if let Some(tok) = token {
-        let state_guard = state.read().await;
-        if let Err(e) = state_guard.db.delete_session(&tok).await {
+    let state_guard = state.read().await;
+    let identity = match token {
+        Some(tok) => session_tokens::authenticate(&state_guard, &tok).await,
+        None => None,
+    };
+
+    if let Some(identity) = identity {
+        let session = state_guard
+            .db
+            .get_session(&identity.session_id)
+            .await
+            .ok()
+            .flatten();
+
+        let result = if params.all {
+            session_tokens::end_user_sessions(&state_guard, identity.user_id)
+                .await
+                .map(|_| ())
+        } else {
+            session_tokens::end_session(&state_guard, &identity.session_id)
+                .await
+                .map(|_| ())
+        };
+        if let Err(e) = result {
             tracing::warn!("Failed to delete session during logout: {}", e);
         }
+
+        if let Some(session) = session {
+            AuditEntry::new(AuditEventType::Logout)
+                .user(session.user_id, &session.username)
+                .detail(if params.all {
+                    "Logged out of all sessions"
+                } else {
+                    "Logged out"
+                })
+                .log();
+        }
     }
+    drop(state_guard);
 
     let cookie = build_clear_auth_cookie();
     with_auth_cookie(
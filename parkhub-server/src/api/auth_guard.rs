@@ -0,0 +1,259 @@
+//! Per-account brute-force protection with progressive delays.
+//!
+//! The per-IP and per-identity rate limiters cap request volume, but an
+//! attacker rotating IPs can still grind through one account's password at
+//! the full allowed rate. [`AuthGuard`] counts failures per account and,
+//! after [`FREE_FAILURES`], makes the caller wait before the next attempt —
+//! one second, doubling with every further failure up to [`MAX_DELAY`].
+//! Attempts made while the delay runs are rejected with 429 and a
+//! `Retry-After` header; they do not extend it.
+//!
+//! Login counts wrong passwords and unknown accounts (so the delay does not
+//! reveal which accounts exist). Password-reset requests count every
+//! request for an address, which paces reset emails to one inbox. A
+//! completed password reset clears both, so the account owner is never
+//! locked out by somebody else's failures.
+//!
+//! Counters are exported as `auth_guard_failures_total{scope}` and
+//! `auth_guard_blocked_total{scope}`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+
+use parkhub_common::{ApiResponse, ErrorCode};
+
+use crate::metrics;
+
+/// Failures allowed before any delay applies.
+pub const FREE_FAILURES: u32 = 3;
+
+/// Delay after the first failure past [`FREE_FAILURES`].
+const BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound for the delay, however many failures pile up.
+pub const MAX_DELAY: Duration = Duration::from_secs(15 * 60);
+
+/// An account's failures are forgotten this long after the last one.
+const FAILURE_MEMORY: Duration = Duration::from_secs(60 * 60);
+
+/// Bookkeeping is pruned once it tracks this many accounts.
+const MAX_TRACKED_ACCOUNTS: usize = 10_000;
+
+/// Which endpoint family an attempt belongs to; each is counted separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GuardScope {
+    Login,
+    PasswordReset,
+}
+
+impl GuardScope {
+    const fn as_str(self) -> &'static str {
+        match self {
+            Self::Login => "login",
+            Self::PasswordReset => "password_reset",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Failures {
+    count: u32,
+    last: Instant,
+}
+
+impl Failures {
+    /// When the next attempt is allowed.
+    fn next_attempt(&self) -> Instant {
+        self.last + delay_after(self.count)
+    }
+}
+
+/// How long to wait after `failures` consecutive failures.
+fn delay_after(failures: u32) -> Duration {
+    if failures < FREE_FAILURES {
+        return Duration::ZERO;
+    }
+    let doublings = (failures - FREE_FAILURES).min(16);
+    BASE_DELAY.saturating_mul(1 << doublings).min(MAX_DELAY)
+}
+
+/// Failure counts per account, shared by the login and password-reset
+/// routes.
+#[derive(Default)]
+pub struct AuthGuard {
+    failures: Mutex<HashMap<(GuardScope, String), Failures>>,
+}
+
+/// Accounts are matched case-insensitively, like usernames and emails.
+fn account_key(scope: GuardScope, account: &str) -> (GuardScope, String) {
+    (scope, account.trim().to_lowercase())
+}
+
+impl AuthGuard {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<(GuardScope, String), Failures>> {
+        self.failures
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    /// Whether `account` may attempt `scope` now.
+    ///
+    /// # Errors
+    /// Returns how long the caller has to wait while a delay is running.
+    pub fn check(&self, scope: GuardScope, account: &str) -> Result<(), Duration> {
+        self.check_at(scope, account, Instant::now())
+    }
+
+    fn check_at(&self, scope: GuardScope, account: &str, now: Instant) -> Result<(), Duration> {
+        let next_attempt = self
+            .lock()
+            .get(&account_key(scope, account))
+            .map(Failures::next_attempt);
+        match next_attempt {
+            Some(at) if at > now => {
+                metrics::record_auth_guard_blocked(scope.as_str());
+                Err(at - now)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Count a failed attempt by `account`.
+    pub fn record_failure(&self, scope: GuardScope, account: &str) {
+        self.record_failure_at(scope, account, Instant::now());
+    }
+
+    fn record_failure_at(&self, scope: GuardScope, account: &str, now: Instant) {
+        metrics::record_auth_guard_failure(scope.as_str());
+        let mut failures = self.lock();
+        if failures.len() >= MAX_TRACKED_ACCOUNTS {
+            failures.retain(|_, f| now.duration_since(f.last) < FAILURE_MEMORY);
+        }
+        let entry = failures
+            .entry(account_key(scope, account))
+            .or_insert(Failures {
+                count: 0,
+                last: now,
+            });
+        if now.duration_since(entry.last) >= FAILURE_MEMORY {
+            entry.count = 0;
+        }
+        entry.count = entry.count.saturating_add(1);
+        entry.last = now;
+        if entry.count >= FREE_FAILURES {
+            tracing::warn!(
+                scope = scope.as_str(),
+                failures = entry.count,
+                delay_secs = delay_after(entry.count).as_secs(),
+                "Repeated authentication failures, delaying further attempts"
+            );
+        }
+    }
+
+    /// Forget the failures of `account`, e.g. after a successful login.
+    pub fn clear(&self, scope: GuardScope, account: &str) {
+        self.lock().remove(&account_key(scope, account));
+    }
+}
+
+/// `429 TOO_MANY_ATTEMPTS` telling the client to retry after `retry_after`.
+pub fn too_many_attempts(retry_after: Duration) -> Response {
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(ApiResponse::<()>::error(
+            ErrorCode::TooManyAttempts,
+            "Too many failed attempts. Please wait before trying again.",
+        )),
+    )
+        .into_response();
+    // Round up so clients never retry a moment too early
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    if let Ok(value) = HeaderValue::from_str(&secs.max(1).to_string()) {
+        response.headers_mut().insert(header::RETRY_AFTER, value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_grows_exponentially_and_is_capped() {
+        assert_eq!(delay_after(0), Duration::ZERO);
+        assert_eq!(delay_after(FREE_FAILURES - 1), Duration::ZERO);
+        assert_eq!(delay_after(FREE_FAILURES), Duration::from_secs(1));
+        assert_eq!(delay_after(FREE_FAILURES + 1), Duration::from_secs(2));
+        assert_eq!(delay_after(FREE_FAILURES + 3), Duration::from_secs(8));
+        assert_eq!(delay_after(FREE_FAILURES + 20), MAX_DELAY);
+        assert_eq!(delay_after(u32::MAX), MAX_DELAY);
+    }
+
+    #[test]
+    fn test_guard_delays_after_repeated_failures() {
+        let guard = AuthGuard::default();
+        let now = Instant::now();
+        for _ in 0..FREE_FAILURES - 1 {
+            guard.record_failure_at(GuardScope::Login, "alice", now);
+            assert!(guard.check_at(GuardScope::Login, "alice", now).is_ok());
+        }
+        guard.record_failure_at(GuardScope::Login, "alice", now);
+        assert_eq!(
+            guard.check_at(GuardScope::Login, "ALICE", now),
+            Err(Duration::from_secs(1))
+        );
+        // Other accounts and scopes are unaffected
+        assert!(guard.check_at(GuardScope::Login, "bob", now).is_ok());
+        assert!(
+            guard
+                .check_at(GuardScope::PasswordReset, "alice", now)
+                .is_ok()
+        );
+
+        // The delay runs out, and the next failure doubles it
+        let later = now + Duration::from_secs(1);
+        assert!(guard.check_at(GuardScope::Login, "alice", later).is_ok());
+        guard.record_failure_at(GuardScope::Login, "alice", later);
+        assert_eq!(
+            guard.check_at(GuardScope::Login, "alice", later),
+            Err(Duration::from_secs(2))
+        );
+
+        guard.clear(GuardScope::Login, "Alice");
+        assert!(guard.check_at(GuardScope::Login, "alice", later).is_ok());
+    }
+
+    #[test]
+    fn test_guard_forgets_old_failures() {
+        let guard = AuthGuard::default();
+        let now = Instant::now();
+        for _ in 0..FREE_FAILURES {
+            guard.record_failure_at(GuardScope::Login, "carol", now);
+        }
+        let much_later = now + FAILURE_MEMORY;
+        guard.record_failure_at(GuardScope::Login, "carol", much_later);
+        assert!(
+            guard
+                .check_at(GuardScope::Login, "carol", much_later)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_too_many_attempts_sets_retry_after() {
+        let response = too_many_attempts(Duration::from_millis(1500));
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "2");
+    }
+}
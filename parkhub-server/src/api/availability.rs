@@ -0,0 +1,455 @@
+//! Time-window availability of slots.
+//!
+//! - `GET /api/v1/lots/{id}/availability?start=&end=` — free slots for a window
+//! - `GET /api/v1/slots/{id}/calendar?month=` — one slot's bookings per day
+//!
+//! A slot's live `SlotStatus` only says whether it is taken right now, so it
+//! cannot answer whether a slot is free next Tuesday. Here a slot is free
+//! for a window when no pending, confirmed or active booking on it overlaps
+//! the window, looked up through the database's time index of booked slots.
+//! Only slots in maintenance or disabled are unavailable regardless of
+//! bookings. Lots with a buffer in their booking rules also treat a slot as
+//! taken when a booking ends or starts within the buffer of the window.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::{HashMap, HashSet};
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use parkhub_common::{
+    ApiResponse, BookedPeriod, Booking, ErrorCode, LotAvailability, MAX_BOOKING_MINUTES,
+    ParkingSlot, SlotAvailability, SlotCalendar, SlotCalendarDay, SlotSegment, SlotStatus,
+    TimeRange,
+};
+
+use super::lot_rules::load_lot_rules;
+use super::{AuthUser, SharedState};
+
+/// Query parameters for `GET /api/v1/lots/{id}/availability`
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct AvailabilityQuery {
+    /// Window start (RFC 3339)
+    pub start: DateTime<Utc>,
+    /// Window end (RFC 3339), after `start` and at most 14 days later
+    pub end: DateTime<Utc>,
+}
+
+/// Whether `slot` can be booked at all.
+pub(crate) const fn is_usable(slot: &ParkingSlot) -> bool {
+    !matches!(slot.status, SlotStatus::Maintenance | SlotStatus::Disabled)
+}
+
+/// Buffer the lot keeps free between consecutive bookings on a slot.
+pub(crate) async fn lot_buffer(db: &crate::db::Database, lot_id: &str) -> Duration {
+    load_lot_rules(db, lot_id).await.buffer()
+}
+
+/// Usable slots of `lot_id` with no live booking overlapping `range` or
+/// the lot's buffer around it, ordered by slot number.
+pub(crate) async fn free_slots(
+    db: &crate::db::Database,
+    lot_id: &str,
+    range: &TimeRange,
+) -> anyhow::Result<Vec<ParkingSlot>> {
+    let slots = db.list_slots_by_lot(lot_id).await?;
+    let slot_ids: Vec<Uuid> = slots.iter().map(|slot| slot.id).collect();
+    let padded = range.padded(lot_buffer(db, lot_id).await);
+    let booked: HashSet<Uuid> = db
+        .list_slot_bookings_overlapping(&slot_ids, &padded)
+        .await?
+        .into_iter()
+        .map(|booking| booking.slot_id)
+        .collect();
+    let mut free: Vec<ParkingSlot> = slots
+        .into_iter()
+        .filter(|slot| is_usable(slot) && !booked.contains(&slot.id))
+        .collect();
+    free.sort_by_key(|slot| slot.slot_number);
+    Ok(free)
+}
+
+/// Availability of `lot_slots` for `range`, given the `bookings` overlapping
+/// it padded by the lot's `buffer`.
+fn build_lot_availability(
+    lot_id: Uuid,
+    lot_slots: &[ParkingSlot],
+    bookings: &[Booking],
+    range: TimeRange,
+    buffer: Duration,
+    now: DateTime<Utc>,
+) -> LotAvailability {
+    let mut booked: HashMap<Uuid, Vec<BookedPeriod>> = HashMap::new();
+    for booking in bookings {
+        booked
+            .entry(booking.slot_id)
+            .or_default()
+            .push(BookedPeriod {
+                start: booking.start_time,
+                end: booking.end_time,
+            });
+    }
+
+    let mut slots: Vec<SlotAvailability> = lot_slots
+        .iter()
+        .map(|slot| {
+            let usable = is_usable(slot);
+            let mut periods = if usable {
+                booked.remove(&slot.id).unwrap_or_default()
+            } else {
+                Vec::new()
+            };
+            periods.sort_by_key(|period| period.start);
+            SlotAvailability {
+                slot_id: slot.id,
+                slot_number: slot.slot_number,
+                floor_id: slot.floor_id,
+                slot_type: slot.slot_type.clone(),
+                available: usable && periods.is_empty(),
+                booked: periods,
+            }
+        })
+        .collect();
+    slots.sort_by_key(|slot| slot.slot_number);
+
+    let total_slots = lot_slots.iter().filter(|slot| is_usable(slot)).count();
+    let available_slots = slots.iter().filter(|slot| slot.available).count();
+    LotAvailability {
+        lot_id,
+        start: range.start,
+        end: range.end,
+        total_slots: u32::try_from(total_slots).unwrap_or(u32::MAX),
+        available_slots: u32::try_from(available_slots).unwrap_or(u32::MAX),
+        bookable: available_slots > 0 && range.end > now,
+        buffer_minutes: u32::try_from(buffer.num_minutes()).unwrap_or(0),
+        slots,
+    }
+}
+
+/// `GET /api/v1/lots/{id}/availability` — free slots for a time window
+#[utoipa::path(get, path = "/api/v1/lots/{id}/availability", tag = "Bookings",
+    summary = "Lot availability for a time window",
+    description = "Which slots of a lot have no booking overlapping `[start, end)` or the \
+        lot's buffer around it, with the booked periods of the others. Works for any future \
+        window, independent of the slots' current status.",
+    params(("id" = String, Path, description = "Parking lot ID"), AvailabilityQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Slot availability", body = LotAvailability),
+        (status = 400, description = "Empty or too long window"),
+        (status = 404, description = "Parking lot not found"),
+    )
+)]
+#[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+pub async fn lot_availability(
+    State(state): State<SharedState>,
+    Extension(_auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<String>,
+    Query(query): Query<AvailabilityQuery>,
+) -> (StatusCode, Json<ApiResponse<LotAvailability>>) {
+    let Some(range) = TimeRange::new(query.start, query.end)
+        .filter(|range| range.duration().num_minutes() <= i64::from(MAX_BOOKING_MINUTES))
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                format!(
+                    "end must be after start and at most {} days later",
+                    MAX_BOOKING_MINUTES / (24 * 60)
+                ),
+            )),
+        );
+    };
+
+    let state_guard = state.read().await;
+    let Ok(Some(lot)) = state_guard.db.get_parking_lot(&lot_id).await else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
+        );
+    };
+
+    let slots = match state_guard.db.list_slots_by_lot(&lot_id).await {
+        Ok(slots) => slots,
+        Err(e) => {
+            tracing::error!("Failed to list slots for availability: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    let slot_ids: Vec<Uuid> = slots.iter().map(|slot| slot.id).collect();
+    let buffer = lot_buffer(&state_guard.db, &lot_id).await;
+    let bookings = match state_guard
+        .db
+        .list_slot_bookings_overlapping(&slot_ids, &range.padded(buffer))
+        .await
+    {
+        Ok(bookings) => bookings,
+        Err(e) => {
+            tracing::error!("Failed to look up bookings for availability: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let mut availability =
+        build_lot_availability(lot.id, &slots, &bookings, range, buffer, Utc::now());
+
+    // Archived lots stay visible but take no new bookings
+    if super::lot_archive::is_archived(&state_guard.db, lot.id).await {
+        availability.bookable = false;
+    }
+
+    (StatusCode::OK, Json(ApiResponse::success(availability)))
+}
+
+/// Query parameters for `GET /api/v1/slots/{id}/calendar`
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct SlotCalendarQuery {
+    /// Month as `YYYY-MM` (UTC); defaults to the current month
+    pub month: Option<String>,
+}
+
+/// First day of the month `YYYY-MM`.
+fn parse_month(month: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&format!("{month}-01"), "%Y-%m-%d").ok()
+}
+
+/// Split `day` into free and booked segments, given the slot's `booked`
+/// ranges. Overlapping or touching bookings merge into one segment.
+fn day_segments(day: TimeRange, booked: &[TimeRange]) -> Vec<SlotSegment> {
+    let mut clipped: Vec<TimeRange> = booked
+        .iter()
+        .filter(|range| range.overlaps(&day))
+        .filter_map(|range| TimeRange::new(range.start.max(day.start), range.end.min(day.end)))
+        .collect();
+    clipped.sort_by_key(|range| range.start);
+
+    let mut segments: Vec<SlotSegment> = Vec::new();
+    let mut cursor = day.start;
+    for range in clipped {
+        if range.end <= cursor {
+            continue;
+        }
+        if range.start > cursor {
+            segments.push(SlotSegment {
+                start: cursor,
+                end: range.start,
+                booked: false,
+            });
+        }
+        match segments.last_mut() {
+            Some(last) if last.booked => last.end = range.end,
+            _ => segments.push(SlotSegment {
+                start: range.start.max(cursor),
+                end: range.end,
+                booked: true,
+            }),
+        }
+        cursor = range.end;
+    }
+    if cursor < day.end {
+        segments.push(SlotSegment {
+            start: cursor,
+            end: day.end,
+            booked: false,
+        });
+    }
+    segments
+}
+
+/// Calendar of `slot` for the month starting on `month`, given the
+/// `bookings` overlapping it. Each booking holds the slot for the lot's
+/// `buffer` before and after it.
+fn build_slot_calendar(
+    slot: &ParkingSlot,
+    month: NaiveDate,
+    bookings: &[Booking],
+    buffer: Duration,
+) -> SlotCalendar {
+    let booked: Vec<TimeRange> = bookings
+        .iter()
+        .filter_map(|booking| TimeRange::new(booking.start_time, booking.end_time))
+        .map(|range| range.padded(buffer))
+        .collect();
+    let days = month
+        .iter_days()
+        .take_while(|date| date.month() == month.month())
+        .map(|date| {
+            let segments = day_segments(TimeRange::day(date), &booked);
+            let free_minutes = segments
+                .iter()
+                .filter(|segment| !segment.booked)
+                .map(|segment| (segment.end - segment.start).num_minutes())
+                .sum::<i64>();
+            SlotCalendarDay {
+                date,
+                free_minutes: u32::try_from(free_minutes).unwrap_or(0),
+                segments,
+            }
+        })
+        .collect();
+    SlotCalendar {
+        slot_id: slot.id,
+        lot_id: slot.lot_id,
+        slot_number: slot.slot_number,
+        month,
+        in_service: is_usable(slot),
+        buffer_minutes: u32::try_from(buffer.num_minutes()).unwrap_or(0),
+        days,
+    }
+}
+
+/// `GET /api/v1/slots/{id}/calendar` — one slot's bookings per day of a month
+#[utoipa::path(get, path = "/api/v1/slots/{id}/calendar", tag = "Bookings",
+    summary = "Slot reservation calendar",
+    description = "Free and booked stretches of one slot for each day (UTC) of a month, for \
+        picking a familiar slot on a later day.",
+    params(("id" = String, Path, description = "Parking slot ID"), SlotCalendarQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Per-day segments", body = SlotCalendar),
+        (status = 400, description = "Malformed month"),
+        (status = 404, description = "Parking slot not found"),
+    )
+)]
+#[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+pub async fn slot_calendar(
+    State(state): State<SharedState>,
+    Extension(_auth_user): Extension<AuthUser>,
+    Path(slot_id): Path<String>,
+    Query(query): Query<SlotCalendarQuery>,
+) -> (StatusCode, Json<ApiResponse<SlotCalendar>>) {
+    let month = match query.month.as_deref() {
+        None => Utc::now().date_naive().with_day(1),
+        Some(month) => parse_month(month),
+    };
+    let range = month.and_then(|month| {
+        let next_month = month.checked_add_months(chrono::Months::new(1))?;
+        TimeRange::new(
+            TimeRange::day(month).start,
+            TimeRange::day(next_month).start,
+        )
+    });
+    let (Some(month), Some(range)) = (month, range) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "month must be formatted as YYYY-MM",
+            )),
+        );
+    };
+
+    let state_guard = state.read().await;
+    let Ok(Some(slot)) = state_guard.db.get_parking_slot(&slot_id).await else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking slot not found",
+            )),
+        );
+    };
+
+    let buffer = lot_buffer(&state_guard.db, &slot.lot_id.to_string()).await;
+    let bookings = match state_guard
+        .db
+        .list_slot_bookings_overlapping(&[slot.id], &range.padded(buffer))
+        .await
+    {
+        Ok(bookings) => bookings,
+        Err(e) => {
+            tracing::error!("Failed to build slot calendar: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(build_slot_calendar(
+            &slot, month, &bookings, buffer,
+        ))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: NaiveDate, hour: u32, minute: u32) -> DateTime<Utc> {
+        day.and_hms_opt(hour, minute, 0).unwrap().and_utc()
+    }
+
+    #[test]
+    fn test_day_segments_cover_the_whole_day() {
+        let day = NaiveDate::from_ymd_opt(2026, 5, 7).unwrap();
+        let booked = [
+            // Overnight from the day before
+            TimeRange::new(at(day, 0, 0) - chrono::Duration::hours(2), at(day, 7, 0)).unwrap(),
+            // Back to back bookings merge
+            TimeRange::new(at(day, 9, 0), at(day, 12, 0)).unwrap(),
+            TimeRange::new(at(day, 12, 0), at(day, 13, 30)).unwrap(),
+            // Another day
+            TimeRange::new(
+                at(day, 0, 0) + chrono::Duration::days(1),
+                at(day, 0, 0) + chrono::Duration::days(2),
+            )
+            .unwrap(),
+        ];
+        let segments = day_segments(TimeRange::day(day), &booked);
+        let spans: Vec<(DateTime<Utc>, DateTime<Utc>, bool)> = segments
+            .iter()
+            .map(|s| (s.start, s.end, s.booked))
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                (at(day, 0, 0), at(day, 7, 0), true),
+                (at(day, 7, 0), at(day, 9, 0), false),
+                (at(day, 9, 0), at(day, 13, 30), true),
+                (at(day, 13, 30), TimeRange::day(day).end, false),
+            ]
+        );
+
+        let free = day_segments(TimeRange::day(day), &[]);
+        assert_eq!(free.len(), 1);
+        assert!(!free[0].booked);
+    }
+
+    #[test]
+    fn test_parse_month() {
+        assert_eq!(parse_month("2026-02"), NaiveDate::from_ymd_opt(2026, 2, 1));
+        assert!(parse_month("2026-13").is_none());
+        assert!(parse_month("May").is_none());
+    }
+}
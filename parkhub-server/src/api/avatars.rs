@@ -0,0 +1,392 @@
+//! Profile picture upload and serving.
+//!
+//! - `PUT /api/v1/users/me/picture` — multipart upload (JPEG or PNG, max 2 MB)
+//! - `DELETE /api/v1/users/me/picture` — remove the uploaded picture
+//! - `GET /api/v1/users/{id}/picture` — the stored picture as PNG
+//!
+//! Uploads are decoded, centre-cropped and resized to
+//! [`AVATAR_SIZE`]×[`AVATAR_SIZE`], and re-encoded as PNG before storage, so
+//! only server-generated bytes are ever served back. On upload `User.picture`
+//! is set to the stable [`picture_url`] for the user; the URL requires
+//! authentication like every other `/api/v1` route and is revalidated via
+//! `ETag`.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::io::Cursor;
+
+use axum::{
+    Extension, Json,
+    extract::{Multipart, Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use image::{ImageFormat, ImageReader, Limits, imageops::FilterType};
+use sha2::{Digest, Sha256};
+
+use parkhub_common::{ApiResponse, ErrorCode, User};
+
+use crate::audit::{AuditEntry, AuditEventType};
+
+use super::{AuthUser, SharedState};
+
+/// Maximum accepted upload size in bytes (2 MB).
+pub const MAX_AVATAR_UPLOAD_BYTES: usize = 2 * 1024 * 1024;
+
+/// Edge length of the stored square picture in pixels.
+pub const AVATAR_SIZE: u32 = 256;
+
+/// Largest source dimension accepted before decoding is refused.
+const MAX_SOURCE_DIMENSION: u32 = 8192;
+
+/// Multipart field names accepted for the image part.
+const UPLOAD_FIELD_NAMES: &[&str] = &["picture", "file", "avatar"];
+
+/// The stable URL a user's uploaded picture is served from.
+#[must_use]
+pub fn picture_url(user_id: &str) -> String {
+    format!("/api/v1/users/{user_id}/picture")
+}
+
+/// Decode an uploaded JPEG/PNG, crop it to a square and resize it to
+/// [`AVATAR_SIZE`], returning PNG bytes.
+fn process_avatar(raw: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let format = image::guess_format(raw).map_err(|_| "Unrecognised image data")?;
+    if !matches!(format, ImageFormat::Jpeg | ImageFormat::Png) {
+        return Err("Unsupported image format. Only JPEG and PNG are accepted.");
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+    let mut reader = ImageReader::with_format(Cursor::new(raw), format);
+    reader.limits(limits);
+    let img = reader.decode().map_err(|_| "Image could not be decoded")?;
+
+    let resized = img.resize_to_fill(AVATAR_SIZE, AVATAR_SIZE, FilterType::Lanczos3);
+    let mut out = Cursor::new(Vec::new());
+    resized
+        .write_to(&mut out, ImageFormat::Png)
+        .map_err(|_| "Image could not be encoded")?;
+    Ok(out.into_inner())
+}
+
+/// Strong `ETag` for stored picture bytes.
+fn picture_etag(png: &[u8]) -> String {
+    format!("\"{}\"", &hex::encode(Sha256::digest(png))[..16])
+}
+
+/// Pull the image part out of a multipart body.
+async fn read_upload(multipart: &mut Multipart) -> Result<Vec<u8>, (StatusCode, &'static str)> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Malformed multipart body"))?
+    {
+        if !field
+            .name()
+            .is_some_and(|n| UPLOAD_FIELD_NAMES.contains(&n))
+        {
+            continue;
+        }
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|_| (StatusCode::PAYLOAD_TOO_LARGE, "Picture exceeds 2 MB limit"))?;
+        if bytes.len() > MAX_AVATAR_UPLOAD_BYTES {
+            return Err((StatusCode::PAYLOAD_TOO_LARGE, "Picture exceeds 2 MB limit"));
+        }
+        return Ok(bytes.to_vec());
+    }
+    Err((StatusCode::BAD_REQUEST, "Missing 'picture' file field"))
+}
+
+/// `PUT /api/v1/users/me/picture` — upload a profile picture.
+#[utoipa::path(put, path = "/api/v1/users/me/picture", tag = "Users",
+    summary = "Upload profile picture",
+    description = "Multipart upload with a `picture` file field (JPEG or PNG, max 2 MB). \
+        The image is cropped to a square and resized to 256×256. Returns the updated user.",
+    security(("bearer_auth" = [])),
+    request_body(content_type = "multipart/form-data", description = "`picture` file field"),
+    responses(
+        (status = 200, description = "Picture stored"),
+        (status = 400, description = "Missing or invalid image"),
+        (status = 413, description = "Image too large"),
+    )
+)]
+pub async fn upload_my_picture(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    mut multipart: Multipart,
+) -> (StatusCode, Json<ApiResponse<User>>) {
+    let raw = match read_upload(&mut multipart).await {
+        Ok(raw) => raw,
+        Err((status, msg)) => {
+            let code = if status == StatusCode::PAYLOAD_TOO_LARGE {
+                ErrorCode::PayloadTooLarge
+            } else {
+                ErrorCode::InvalidInput
+            };
+            return (status, Json(ApiResponse::error(code, msg)));
+        }
+    };
+
+    // Decoding and resampling are CPU-bound; keep them off the async workers.
+    let processed = match tokio::task::spawn_blocking(move || process_avatar(&raw)).await {
+        Ok(Ok(png)) => png,
+        Ok(Err(msg)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::InvalidInput, msg)),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Avatar processing task failed: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let state_guard = state.read().await;
+    let user_id = auth_user.user_id.to_string();
+    let mut user = match state_guard.db.get_user(&user_id).await {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    if let Err(e) = state_guard.db.save_user_picture(&user_id, &processed).await {
+        tracing::error!("Failed to save profile picture: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save picture",
+            )),
+        );
+    }
+
+    user.picture = Some(picture_url(&user_id));
+    user.updated_at = Utc::now();
+    if let Err(e) = state_guard.db.save_user(&user).await {
+        tracing::error!("Failed to update user after picture upload: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save picture",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::UserUpdated)
+        .user(user.id, &user.username)
+        .resource("user", &user_id)
+        .detail("Profile picture uploaded")
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    tracing::info!(user_id = %user_id, bytes = processed.len(), "Profile picture uploaded");
+    (StatusCode::OK, Json(ApiResponse::success(user)))
+}
+
+/// `DELETE /api/v1/users/me/picture` — remove the uploaded profile picture.
+#[utoipa::path(delete, path = "/api/v1/users/me/picture", tag = "Users",
+    summary = "Remove profile picture",
+    description = "Deletes the uploaded picture and clears `picture` on the user.",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Picture removed"))
+)]
+pub async fn delete_my_picture(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<User>>) {
+    let state_guard = state.read().await;
+    let user_id = auth_user.user_id.to_string();
+    let mut user = match state_guard.db.get_user(&user_id).await {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    if let Err(e) = state_guard.db.delete_user_picture(&user_id).await {
+        tracing::error!("Failed to delete profile picture: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to delete picture",
+            )),
+        );
+    }
+
+    if user.picture.is_some() {
+        user.picture = None;
+        user.updated_at = Utc::now();
+        if let Err(e) = state_guard.db.save_user(&user).await {
+            tracing::error!("Failed to update user after picture removal: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to delete picture",
+                )),
+            );
+        }
+    }
+
+    AuditEntry::new(AuditEventType::UserUpdated)
+        .user(user.id, &user.username)
+        .resource("user", &user_id)
+        .detail("Profile picture removed")
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(user)))
+}
+
+/// `GET /api/v1/users/{id}/picture` — serve a user's uploaded picture.
+#[utoipa::path(get, path = "/api/v1/users/{id}/picture", tag = "Users",
+    summary = "Get profile picture",
+    description = "Returns the user's uploaded picture as a 256×256 PNG. Honours \
+        `If-None-Match`. Any authenticated user may fetch pictures.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "User UUID")),
+    responses(
+        (status = 200, description = "PNG bytes", content_type = "image/png"),
+        (status = 304, description = "Not modified"),
+        (status = 404, description = "No uploaded picture"),
+    )
+)]
+pub async fn get_user_picture(
+    State(state): State<SharedState>,
+    Extension(_auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Response {
+    let state_guard = state.read().await;
+    let png = match state_guard.db.get_user_picture(&id).await {
+        Ok(Some(png)) => png,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::NotFound,
+                    "No picture found",
+                )),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to load profile picture: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+                .into_response();
+        }
+    };
+
+    let etag = picture_etag(&png);
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (header::CACHE_CONTROL, "private, no-cache".to_string()),
+    ];
+    if headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|t| t.trim() == etag))
+    {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "image/png")],
+        cache_headers,
+        png,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(img: &image::DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut out = Cursor::new(Vec::new());
+        img.write_to(&mut out, format).unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_process_avatar_crops_and_resizes_to_png() {
+        let src = image::DynamicImage::new_rgb8(640, 320);
+        let png = process_avatar(&encode(&src, ImageFormat::Png)).unwrap();
+        assert_eq!(image::guess_format(&png).unwrap(), ImageFormat::Png);
+        let out = image::load_from_memory(&png).unwrap();
+        assert_eq!((out.width(), out.height()), (AVATAR_SIZE, AVATAR_SIZE));
+    }
+
+    #[test]
+    fn test_process_avatar_accepts_jpeg() {
+        let src = image::DynamicImage::new_rgb8(100, 100);
+        let png = process_avatar(&encode(&src, ImageFormat::Jpeg)).unwrap();
+        assert_eq!(image::guess_format(&png).unwrap(), ImageFormat::Png);
+    }
+
+    #[test]
+    fn test_process_avatar_rejects_garbage() {
+        assert!(process_avatar(b"definitely not an image").is_err());
+        // Valid PNG signature, truncated body.
+        assert!(process_avatar(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]).is_err());
+    }
+
+    #[test]
+    fn test_picture_etag_is_stable() {
+        assert_eq!(picture_etag(b"abc"), picture_etag(b"abc"));
+        assert_ne!(picture_etag(b"abc"), picture_etag(b"abd"));
+        assert!(picture_etag(b"abc").starts_with('"'));
+    }
+}
@@ -0,0 +1,324 @@
+//! Online backup and restore of the whole database.
+//!
+//! - `POST /api/v1/admin/backup` — download an encrypted backup archive
+//! - `POST /api/v1/admin/restore` — replace all data with an uploaded archive
+//!
+//! Both are SuperAdmin only: a backup holds every password hash, and a
+//! restore replaces every account and its role.
+//!
+//! The server keeps running during both. A backup is a consistent snapshot
+//! (see [`crate::db::Database::backup`]) sealed with a passphrase the
+//! SuperAdmin picks; the same passphrase opens it again on restore. Backups
+//! carry no sessions, and a restore replaces every table at once and signs
+//! everyone out.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Json,
+    extract::{Multipart, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode, UserRole};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{open_backup, seal_backup};
+use crate::requests::BackupRequest;
+use crate::validation::ValidatedJson;
+
+use super::permissions::{CanManageConfig, RequirePermission};
+use super::{SharedState, server_error};
+
+/// Path of the backup download endpoint.
+pub const BACKUP_PATH: &str = "/api/v1/admin/backup";
+
+/// Path of the restore endpoint, which accepts larger bodies than the rest
+/// of the API.
+pub const RESTORE_PATH: &str = "/api/v1/admin/restore";
+
+/// Maximum accepted backup archive size in bytes (256 MiB).
+pub const MAX_BACKUP_BYTES: usize = 256 * 1024 * 1024;
+
+/// Value the `confirm` field of a restore must have.
+const RESTORE_CONFIRMATION: &str = "RESTORE";
+
+/// `POST /api/v1/admin/backup` — download a backup of all data.
+#[utoipa::path(post, path = "/api/v1/admin/backup", tag = "Admin",
+    summary = "Download a database backup (SuperAdmin)",
+    description = "A consistent snapshot of all data, taken while the server keeps running \
+        and encrypted with the given passphrase. The same passphrase is needed to restore \
+        it. Sessions and booking attachment files are not included.",
+    security(("bearer_auth" = [])),
+    request_body = BackupRequest,
+    responses(
+        (status = 200, description = "Backup archive", content_type = "application/octet-stream"),
+        (status = 400, description = "Passphrase too short or too long"),
+        (status = 403, description = "SuperAdmin only"),
+    )
+)]
+pub async fn admin_backup(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageConfig>,
+    ValidatedJson(req): ValidatedJson<BackupRequest>,
+) -> Response {
+    if auth_user.role != UserRole::SuperAdmin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<()>::error(
+                ErrorCode::Forbidden,
+                "Only a SuperAdmin can download a backup",
+            )),
+        )
+            .into_response();
+    }
+    let state_guard = state.read().await;
+    let db = &state_guard.db;
+
+    // Dot-prefixed, so it is never taken for a finished backup
+    let path = db
+        .backup_dir()
+        .join(format!(".download-{}.redb", Uuid::new_v4()));
+    let snapshot = match db.backup(&path).await {
+        Ok(()) => tokio::fs::read(&path).await,
+        Err(e) => {
+            tracing::error!("Backup failed: {e}");
+            return server_error::<()>("Backup failed").into_response();
+        }
+    };
+    let _ = tokio::fs::remove_file(&path).await;
+    let snapshot = match snapshot {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            tracing::error!("Failed to read backup {}: {e}", path.display());
+            return server_error::<()>("Backup failed").into_response();
+        }
+    };
+
+    // Key derivation and encryption are CPU-bound; keep them off the async workers.
+    let sealed = tokio::task::spawn_blocking(move || seal_backup(&snapshot, &req.passphrase)).await;
+    let archive = match sealed {
+        Ok(Ok(archive)) => archive,
+        Ok(Err(e)) => {
+            tracing::error!("Failed to encrypt backup: {e}");
+            return server_error::<()>("Backup failed").into_response();
+        }
+        Err(e) => {
+            tracing::error!("Backup encryption task failed: {e}");
+            return server_error::<()>("Backup failed").into_response();
+        }
+    };
+
+    let username = db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    AuditEntry::new(AuditEventType::BackupCreated)
+        .user(auth_user.user_id, &username)
+        .detail(&format!("Backup downloaded ({} bytes)", archive.len()))
+        .log()
+        .persist(db)
+        .await;
+    drop(state_guard);
+
+    let filename = format!(
+        "parkhub-backup-{}.phbak",
+        Utc::now().format("%Y%m%d-%H%M%S")
+    );
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            (header::CACHE_CONTROL, "private, no-store".to_string()),
+        ],
+        archive,
+    )
+        .into_response()
+}
+
+/// Fields of a restore upload.
+#[derive(Default)]
+struct RestoreUpload {
+    archive: Option<Vec<u8>>,
+    passphrase: String,
+    confirm: String,
+}
+
+/// Pull the archive, passphrase and confirmation out of a multipart body.
+async fn read_restore_upload(
+    multipart: &mut Multipart,
+) -> Result<RestoreUpload, (StatusCode, &'static str)> {
+    let malformed = |_| (StatusCode::BAD_REQUEST, "Malformed multipart body");
+    let mut upload = RestoreUpload::default();
+    while let Some(field) = multipart.next_field().await.map_err(malformed)? {
+        match field.name() {
+            Some("file") => {
+                let bytes = field
+                    .bytes()
+                    .await
+                    .map_err(|_| (StatusCode::PAYLOAD_TOO_LARGE, "File exceeds 256 MB limit"))?;
+                upload.archive = Some(bytes.to_vec());
+            }
+            Some("passphrase") => upload.passphrase = field.text().await.map_err(malformed)?,
+            Some("confirm") => upload.confirm = field.text().await.map_err(malformed)?,
+            _ => {}
+        }
+    }
+    Ok(upload)
+}
+
+/// `POST /api/v1/admin/restore` — replace all data with a backup.
+#[utoipa::path(post, path = "/api/v1/admin/restore", tag = "Admin",
+    summary = "Restore a database backup (SuperAdmin)",
+    description = "Replaces all data with an archive from `POST /api/v1/admin/backup`. \
+        Multipart fields: `file` (the archive, max 256 MB), `passphrase` and `confirm`, \
+        which must be `RESTORE`. Destructive. Every session is ended, so all users sign in again. \
+        The archive must come from a server with the same at-rest encryption key.",
+    security(("bearer_auth" = [])),
+    request_body(content_type = "multipart/form-data",
+        description = "`file`, `passphrase` and `confirm` fields"),
+    responses(
+        (status = 200, description = "Data restored"),
+        (status = 400, description = "Missing confirmation or file, not a backup archive, or wrong passphrase"),
+        (status = 403, description = "SuperAdmin only"),
+        (status = 409, description = "Backup was taken with a different at-rest encryption key"),
+        (status = 413, description = "Archive too large"),
+    )
+)]
+pub async fn admin_restore(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageConfig>,
+    mut multipart: Multipart,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    if auth_user.role != UserRole::SuperAdmin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Only a SuperAdmin can restore a backup",
+            )),
+        );
+    }
+    let upload = match read_restore_upload(&mut multipart).await {
+        Ok(upload) => upload,
+        Err((status, msg)) => {
+            let code = if status == StatusCode::PAYLOAD_TOO_LARGE {
+                ErrorCode::PayloadTooLarge
+            } else {
+                ErrorCode::InvalidInput
+            };
+            return (status, Json(ApiResponse::error(code, msg)));
+        }
+    };
+    if upload.confirm != RESTORE_CONFIRMATION {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::ConfirmationRequired,
+                "The 'confirm' field must be \"RESTORE\"",
+            )),
+        );
+    }
+    let Some(archive) = upload.archive else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "Missing 'file' field",
+            )),
+        );
+    };
+
+    let passphrase = upload.passphrase;
+    let opened = tokio::task::spawn_blocking(move || open_backup(&archive, &passphrase)).await;
+    let snapshot = match opened {
+        Ok(Some(snapshot)) => snapshot,
+        Ok(None) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ErrorCode::InvalidInput,
+                    "Not a backup archive, damaged, or wrong passphrase",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Backup decryption task failed: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    // Write lock: no handler runs between its reads and writes while the
+    // data underneath it is replaced.
+    let state_guard = state.write().await;
+    let db = &state_guard.db;
+    // Looked up now: the restored data may not know the admin
+    let username = db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    let path = db
+        .backup_dir()
+        .join(format!(".upload-{}.redb", Uuid::new_v4()));
+    let written = match tokio::fs::create_dir_all(db.backup_dir()).await {
+        Ok(()) => tokio::fs::write(&path, &snapshot).await,
+        Err(e) => Err(e),
+    };
+    let restored = match written {
+        Ok(()) => db.restore(&path).await,
+        Err(e) => Err(e.into()),
+    };
+    let _ = tokio::fs::remove_file(&path).await;
+
+    match restored {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::Conflict,
+                    "The backup was taken with different encryption settings",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Restore failed: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(ErrorCode::ServerError, "Restore failed")),
+            );
+        }
+    }
+
+    AuditEntry::new(AuditEventType::BackupRestored)
+        .user(auth_user.user_id, &username)
+        .detail(&format!("Database restored ({} bytes)", snapshot.len()))
+        .log()
+        .persist(db)
+        .await;
+    tracing::warn!(admin = %username, "Database restored from backup");
+
+    (StatusCode::OK, Json(ApiResponse::success(())))
+}
@@ -21,7 +21,7 @@ use std::fmt::Write;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use parkhub_common::{ApiResponse, BookingStatus};
+use parkhub_common::{ApiResponse, BookingStatus, ErrorCode};
 
 use super::{AuthUser, check_admin};
 use crate::AppState;
@@ -92,8 +92,8 @@ pub async fn billing_by_cost_center(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<Vec<CostCenterSummary>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let users = state_guard.db.list_users().await.unwrap_or_default();
@@ -171,8 +171,8 @@ pub async fn billing_by_department(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<Vec<DepartmentSummary>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let users = state_guard.db.list_users().await.unwrap_or_default();
@@ -244,7 +244,7 @@ pub async fn billing_export_csv(
     Extension(auth_user): Extension<AuthUser>,
 ) -> impl IntoResponse {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
+    if let Err((status, msg)) = check_admin(&auth_user) {
         return (
             status,
             [(header::CONTENT_TYPE, "application/json")],
@@ -316,15 +316,15 @@ pub async fn billing_allocate(
     Json(req): Json<AllocateCreditsRequest>,
 ) -> (StatusCode, Json<ApiResponse<AllocationResult>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     if req.credits <= 0 {
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_CREDITS",
+                ErrorCode::InvalidCredits,
                 "Credits must be positive",
             )),
         );
@@ -340,7 +340,7 @@ pub async fn billing_allocate(
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::error(
-                "NO_USERS",
+                ErrorCode::NoUsers,
                 "No users found in this cost center",
             )),
         );
@@ -0,0 +1,550 @@
+//! Files attached to bookings, e.g. a photo of a damaged slot.
+//!
+//! - `POST /api/v1/bookings/{id}/attachments` — multipart upload (JPEG, PNG
+//!   or PDF, max 3 MB)
+//! - `GET /api/v1/bookings/{id}/attachments` — the booking's attachments
+//! - `GET /api/v1/bookings/{id}/attachments/{attachment_id}` — download one
+//! - `DELETE /api/v1/bookings/{id}/attachments/{attachment_id}` — remove one
+//!
+//! Whoever may manage a booking may attach and remove files; whoever may see
+//! it may list and download them. The type is taken from the file's contents,
+//! never from its name or the declared content type. Images go through the
+//! same pipeline as lot photos, which re-encodes them as JPEG and drops
+//! their EXIF metadata (GPS position, camera serials); PDFs are stored as
+//! uploaded. Downloads are always served as attachments with `nosniff`, so a
+//! browser never renders them inline.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Extension, Json,
+    extract::{Multipart, Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use image::ImageFormat;
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::BookingAttachment;
+
+use super::booking_notes::load_booking;
+use super::lot_photos::process_photo;
+use super::permissions::{may_manage_booking, may_view_booking};
+use super::{AuthUser, SharedState};
+
+/// Maximum accepted upload size in bytes (3 MB).
+pub const MAX_ATTACHMENT_BYTES: usize = 3 * 1024 * 1024;
+
+/// Maximum number of attachments per booking.
+pub const MAX_ATTACHMENTS_PER_BOOKING: usize = 5;
+
+/// Longest stored file name in characters.
+const MAX_FILENAME_CHARS: usize = 100;
+
+/// Multipart field names accepted for the file part.
+const UPLOAD_FIELD_NAMES: &[&str] = &["file", "attachment", "photo"];
+
+/// Bring an uploaded file name into a form that is safe to store and to
+/// quote in `Content-Disposition`: no directories, control characters or
+/// quotes, at most [`MAX_FILENAME_CHARS`] characters.
+fn sanitize_filename(name: Option<&str>) -> String {
+    let base = name
+        .unwrap_or_default()
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default();
+    let clean: String = base
+        .chars()
+        .filter(|c| !c.is_control() && !matches!(c, '"' | ';'))
+        .take(MAX_FILENAME_CHARS)
+        .collect();
+    let clean = clean.trim();
+    if clean.is_empty() || clean.chars().all(|c| c == '.') {
+        "attachment".to_string()
+    } else {
+        clean.to_string()
+    }
+}
+
+/// Check the type of an upload from its contents and prepare it for
+/// storage. Returns the bytes to store, their content type and the file
+/// name to store them under.
+fn process_attachment(
+    raw: &[u8],
+    filename: &str,
+) -> Result<(Vec<u8>, &'static str, String), &'static str> {
+    if raw.starts_with(b"%PDF-") {
+        return Ok((raw.to_vec(), "application/pdf", filename.to_string()));
+    }
+    match image::guess_format(raw) {
+        Ok(ImageFormat::Jpeg | ImageFormat::Png) => {
+            let jpeg = process_photo(raw)?;
+            // Images are stored re-encoded, so the name must say JPEG
+            let stem = filename.rsplit_once('.').map_or(filename, |(stem, _)| stem);
+            let stem = if stem.is_empty() { "attachment" } else { stem };
+            Ok((jpeg, "image/jpeg", format!("{stem}.jpg")))
+        }
+        _ => Err("Unsupported file type. Only JPEG, PNG and PDF are accepted."),
+    }
+}
+
+/// Pull the file part and its name out of a multipart body.
+async fn read_upload(
+    multipart: &mut Multipart,
+) -> Result<(Vec<u8>, String), (StatusCode, &'static str)> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Malformed multipart body"))?
+    {
+        if !field
+            .name()
+            .is_some_and(|n| UPLOAD_FIELD_NAMES.contains(&n))
+        {
+            continue;
+        }
+        let filename = sanitize_filename(field.file_name());
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|_| (StatusCode::PAYLOAD_TOO_LARGE, "File exceeds 3 MB limit"))?;
+        if bytes.len() > MAX_ATTACHMENT_BYTES {
+            return Err((StatusCode::PAYLOAD_TOO_LARGE, "File exceeds 3 MB limit"));
+        }
+        if bytes.is_empty() {
+            return Err((StatusCode::BAD_REQUEST, "File is empty"));
+        }
+        return Ok((bytes.to_vec(), filename));
+    }
+    Err((StatusCode::BAD_REQUEST, "Missing 'file' field"))
+}
+
+/// `POST /api/v1/bookings/{id}/attachments` — attach a file to a booking.
+#[utoipa::path(post, path = "/api/v1/bookings/{id}/attachments", tag = "Bookings",
+    summary = "Upload a booking attachment",
+    description = "Multipart upload with a `file` field (JPEG, PNG or PDF, max 3 MB), \
+        e.g. a photo of a damaged slot. The type is detected from the contents. Images \
+        are re-encoded as JPEG, which strips all EXIF metadata. At most 5 attachments \
+        per booking. Booking owner or someone who manages bookings in its lot.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Booking ID")),
+    request_body(content_type = "multipart/form-data", description = "`file` field"),
+    responses(
+        (status = 201, description = "File stored", body = BookingAttachment),
+        (status = 400, description = "Missing file or unsupported type"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Booking not found"),
+        (status = 409, description = "Attachment limit reached"),
+        (status = 413, description = "File too large"),
+    )
+)]
+pub async fn upload_booking_attachment(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> (StatusCode, Json<ApiResponse<BookingAttachment>>) {
+    // Check access before reading the body
+    {
+        let state_guard = state.read().await;
+        let booking = match load_booking(&state_guard.db, &id).await {
+            Ok(booking) => booking,
+            Err(response) => return response,
+        };
+        if !may_manage_booking(&auth_user, &booking) {
+            return (
+                StatusCode::FORBIDDEN,
+                Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
+            );
+        }
+    }
+
+    let (raw, filename) = match read_upload(&mut multipart).await {
+        Ok(upload) => upload,
+        Err((status, msg)) => {
+            let code = if status == StatusCode::PAYLOAD_TOO_LARGE {
+                ErrorCode::PayloadTooLarge
+            } else {
+                ErrorCode::InvalidInput
+            };
+            return (status, Json(ApiResponse::error(code, msg)));
+        }
+    };
+
+    // Image decoding is CPU-bound; keep it off the async workers.
+    let processed = tokio::task::spawn_blocking(move || process_attachment(&raw, &filename)).await;
+    let (bytes, content_type, filename) = match processed {
+        Ok(Ok(processed)) => processed,
+        Ok(Err(msg)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::InvalidInput, msg)),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Attachment processing task failed: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    // Write lock: the limit check and the insert must not interleave with a
+    // concurrent upload to the same booking. The booking is loaded again in
+    // case it was deleted while the body was read.
+    let state_guard = state.write().await;
+    let booking = match load_booking(&state_guard.db, &id).await {
+        Ok(booking) => booking,
+        Err(response) => return response,
+    };
+    match state_guard.db.list_booking_attachments(&id).await {
+        Ok(existing) if existing.len() >= MAX_ATTACHMENTS_PER_BOOKING => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::AttachmentLimitReached,
+                    format!("A booking can have at most {MAX_ATTACHMENTS_PER_BOOKING} attachments"),
+                )),
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Failed to list booking attachments: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    }
+
+    let attachment = BookingAttachment {
+        id: Uuid::new_v4(),
+        booking_id: booking.id,
+        uploaded_by: auth_user.user_id,
+        filename,
+        content_type: content_type.to_string(),
+        size_bytes: bytes.len() as u64,
+        created_at: Utc::now(),
+    };
+    if let Err(e) = state_guard
+        .db
+        .save_booking_attachment(&attachment, &bytes)
+        .await
+    {
+        tracing::error!("Failed to save booking attachment: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save attachment",
+            )),
+        );
+    }
+
+    let username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    AuditEntry::new(AuditEventType::BookingUpdated)
+        .user(auth_user.user_id, &username)
+        .resource("booking", &id)
+        .detail(&format!(
+            "Attachment {} uploaded ({content_type}, {} bytes)",
+            attachment.id, attachment.size_bytes
+        ))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::CREATED, Json(ApiResponse::success(attachment)))
+}
+
+/// `GET /api/v1/bookings/{id}/attachments` — the booking's attachments.
+#[utoipa::path(get, path = "/api/v1/bookings/{id}/attachments", tag = "Bookings",
+    summary = "List booking attachments",
+    description = "The files attached to a booking, oldest first. Booking owner or someone \
+        who sees bookings in its lot.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Booking ID")),
+    responses(
+        (status = 200, description = "Attachments of the booking", body = Vec<BookingAttachment>),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Booking not found"),
+    )
+)]
+pub async fn list_booking_attachments(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<BookingAttachment>>>) {
+    let state_guard = state.read().await;
+    let booking = match load_booking(&state_guard.db, &id).await {
+        Ok(booking) => booking,
+        Err(response) => return response,
+    };
+    if !may_view_booking(&auth_user, &booking) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
+        );
+    }
+
+    match state_guard.db.list_booking_attachments(&id).await {
+        Ok(attachments) => (StatusCode::OK, Json(ApiResponse::success(attachments))),
+        Err(e) => {
+            tracing::error!("Failed to list booking attachments: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
+/// `GET /api/v1/bookings/{id}/attachments/{attachment_id}` — download a file.
+#[utoipa::path(get, path = "/api/v1/bookings/{id}/attachments/{attachment_id}", tag = "Bookings",
+    summary = "Download a booking attachment",
+    description = "The stored file, served with `Content-Disposition: attachment`. \
+        Booking owner or someone who sees bookings in its lot.",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = String, Path, description = "Booking ID"),
+        ("attachment_id" = String, Path, description = "Attachment ID"),
+    ),
+    responses(
+        (status = 200, description = "File contents"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Booking or attachment not found"),
+    )
+)]
+pub async fn download_booking_attachment(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((id, attachment_id)): Path<(String, String)>,
+) -> Response {
+    let server_error = || {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::<()>::error(
+                ErrorCode::ServerError,
+                "Internal server error",
+            )),
+        )
+            .into_response()
+    };
+
+    let state_guard = state.read().await;
+    let booking = match load_booking::<()>(&state_guard.db, &id).await {
+        Ok(booking) => booking,
+        Err(response) => return response.into_response(),
+    };
+    if !may_view_booking(&auth_user, &booking) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<()>::error(
+                ErrorCode::Forbidden,
+                "Access denied",
+            )),
+        )
+            .into_response();
+    }
+    let attachment = match state_guard
+        .db
+        .get_booking_attachment(&id, &attachment_id)
+        .await
+    {
+        Ok(Some(attachment)) => attachment,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::NotFound,
+                    "Attachment not found",
+                )),
+            )
+                .into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to load booking attachment: {e}");
+            return server_error();
+        }
+    };
+    let bytes = match state_guard.db.read_booking_attachment(&attachment).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::error!("Failed to read booking attachment {attachment_id}: {e}");
+            return server_error();
+        }
+    };
+    drop(state_guard);
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, attachment.content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", attachment.filename),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+            (header::CACHE_CONTROL, "private, no-store".to_string()),
+        ],
+        bytes,
+    )
+        .into_response()
+}
+
+/// `DELETE /api/v1/bookings/{id}/attachments/{attachment_id}` — remove a file.
+#[utoipa::path(delete, path = "/api/v1/bookings/{id}/attachments/{attachment_id}", tag = "Bookings",
+    summary = "Delete a booking attachment",
+    description = "Deletes the attachment and its file. Booking owner or someone who \
+        manages bookings in its lot.",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = String, Path, description = "Booking ID"),
+        ("attachment_id" = String, Path, description = "Attachment ID"),
+    ),
+    responses(
+        (status = 200, description = "Attachment deleted"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Booking or attachment not found"),
+    )
+)]
+pub async fn delete_booking_attachment(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((id, attachment_id)): Path<(String, String)>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.write().await;
+    let booking = match load_booking(&state_guard.db, &id).await {
+        Ok(booking) => booking,
+        Err(response) => return response,
+    };
+    if !may_manage_booking(&auth_user, &booking) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
+        );
+    }
+
+    let deleted = match state_guard
+        .db
+        .get_booking_attachment(&id, &attachment_id)
+        .await
+    {
+        Ok(Some(attachment)) => state_guard.db.delete_booking_attachment(&attachment).await,
+        Ok(None) => Ok(false),
+        Err(e) => Err(e),
+    };
+    match deleted {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Attachment not found",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Failed to delete booking attachment: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    }
+
+    let username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    AuditEntry::new(AuditEventType::BookingUpdated)
+        .user(auth_user.user_id, &username)
+        .resource("booking", &id)
+        .detail(&format!("Attachment {attachment_id} deleted"))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(())))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use image::{DynamicImage, RgbImage};
+
+    use super::*;
+
+    fn png() -> Vec<u8> {
+        let mut out = Cursor::new(Vec::new());
+        DynamicImage::ImageRgb8(RgbImage::new(8, 8))
+            .write_to(&mut out, ImageFormat::Png)
+            .unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_sanitize_filename_strips_directories_and_quotes() {
+        assert_eq!(sanitize_filename(Some("../../etc/passwd")), "passwd");
+        assert_eq!(
+            sanitize_filename(Some("C:\\Users\\me\\dent.png")),
+            "dent.png"
+        );
+        assert_eq!(sanitize_filename(Some("a\"b;\r\nc.pdf")), "abc.pdf");
+        assert_eq!(sanitize_filename(Some("..")), "attachment");
+        assert_eq!(sanitize_filename(None), "attachment");
+        let long = "x".repeat(300);
+        assert_eq!(
+            sanitize_filename(Some(&long)).chars().count(),
+            MAX_FILENAME_CHARS
+        );
+    }
+
+    #[test]
+    fn test_process_attachment_detects_type_from_contents() {
+        let (bytes, content_type, name) = process_attachment(&png(), "dent.png").unwrap();
+        assert_eq!(content_type, "image/jpeg");
+        assert_eq!(name, "dent.jpg");
+        assert_eq!(image::guess_format(&bytes).unwrap(), ImageFormat::Jpeg);
+
+        let pdf = b"%PDF-1.4\n%fake\n".to_vec();
+        let (bytes, content_type, name) = process_attachment(&pdf, "report.pdf").unwrap();
+        assert_eq!(content_type, "application/pdf");
+        assert_eq!(name, "report.pdf");
+        assert_eq!(bytes, pdf);
+
+        // A renamed script is still rejected
+        assert!(process_attachment(b"<script>alert(1)</script>", "photo.png").is_err());
+    }
+}
@@ -0,0 +1,135 @@
+//! Keyed locks for booking creation.
+//!
+//! Booking creation re-checks and commits under the `AppState` read lock
+//! plus the locks of what it touches: the slot (or the lot's slot type for
+//! late-binding lots), the booking user, whose credits it spends, and their
+//! department, whose quota it counts. Bookings of different users on
+//! different slots proceed in parallel; two bookings of the same slot are
+//! still checked and committed one after the other. Handlers that take the
+//! `AppState` write lock (holds, lot archiving, ...) keep all bookings out
+//! while they run.
+//!
+//! Keys are locked in sorted order, so bookings sharing several keys cannot
+//! deadlock. A key's entry is dropped once no booking holds or waits for it.
+
+#![cfg_attr(
+    not(any(feature = "mod-bookings", feature = "mod-guest")),
+    allow(dead_code)
+)]
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, PoisonError};
+
+use parkhub_common::SlotType;
+use uuid::Uuid;
+
+type KeyLock = Arc<tokio::sync::Mutex<()>>;
+
+static LOCKS: LazyLock<Mutex<HashMap<String, KeyLock>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Lock key of a parking slot.
+#[must_use]
+pub fn slot_key(slot_id: Uuid) -> String {
+    format!("slot:{slot_id}")
+}
+
+/// Lock key of the slots of `slot_type` in a late-binding lot.
+#[must_use]
+pub fn slot_type_key(lot_id: Uuid, slot_type: &SlotType) -> String {
+    format!("slot-type:{lot_id}:{slot_type:?}")
+}
+
+/// Lock key of a user's credits.
+#[must_use]
+pub fn user_key(user_id: Uuid) -> String {
+    format!("user:{user_id}")
+}
+
+/// Lock key of a department's quota.
+#[must_use]
+pub fn department_key(department: &str) -> String {
+    format!("department:{department}")
+}
+
+/// Held locks; released on drop.
+pub struct BookingLocks {
+    keys: Vec<String>,
+    guards: Vec<tokio::sync::OwnedMutexGuard<()>>,
+}
+
+/// Lock all of `keys`, waiting for bookings that hold any of them.
+pub async fn lock(keys: impl IntoIterator<Item = String>) -> BookingLocks {
+    let mut keys: Vec<String> = keys.into_iter().collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    let mut guards = Vec::with_capacity(keys.len());
+    for key in &keys {
+        let lock = LOCKS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key.clone())
+            .or_default()
+            .clone();
+        guards.push(lock.lock_owned().await);
+    }
+    BookingLocks { keys, guards }
+}
+
+impl Drop for BookingLocks {
+    fn drop(&mut self) {
+        self.guards.clear();
+        let mut locks = LOCKS.lock().unwrap_or_else(PoisonError::into_inner);
+        for key in &self.keys {
+            // Only the map's own reference left: nobody holds or awaits it
+            if locks
+                .get(key)
+                .is_some_and(|lock| Arc::strong_count(lock) == 1)
+            {
+                locks.remove(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn registered(key: &str) -> bool {
+        LOCKS.lock().unwrap().contains_key(key)
+    }
+
+    #[tokio::test]
+    async fn test_same_key_waits_other_keys_do_not() {
+        let slot = slot_key(Uuid::new_v4());
+        let user = user_key(Uuid::new_v4());
+        let held = lock([slot.clone(), user.clone()]).await;
+
+        // A different slot and user are free right away
+        let other = tokio::time::timeout(
+            Duration::from_millis(200),
+            lock([slot_key(Uuid::new_v4()), user_key(Uuid::new_v4())]),
+        )
+        .await;
+        assert!(other.is_ok());
+
+        // Sharing only the user still waits
+        let waiting = tokio::spawn(lock([user.clone(), slot_key(Uuid::new_v4())]));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+
+        drop(held);
+        let next = tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .expect("released lock must be handed on")
+            .unwrap();
+        assert!(!registered(&slot));
+        assert!(registered(&user));
+        drop(next);
+        assert!(!registered(&user));
+    }
+}
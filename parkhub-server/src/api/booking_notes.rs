@@ -0,0 +1,187 @@
+//! Internal booking notes.
+//!
+//! - `GET /api/v1/admin/bookings/{id}/notes` — the booking's notes, oldest first
+//! - `POST /api/v1/admin/bookings/{id}/notes` — add a note
+//!
+//! Notes are for admins handling a booking (e.g. "slot damaged on arrival,
+//! refund agreed"). They are stored apart from the booking, so the booking's
+//! owner never sees them; they are deleted with the booking. Admins bound to
+//! a tenant only reach bookings of that tenant.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, Booking, ErrorCode};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{BookingNote, Database};
+use crate::requests::AddBookingNoteRequest;
+use crate::validation::ValidatedJson;
+
+use super::{AuthUser, SharedState, check_admin};
+
+/// Load booking `booking_id`, answering 404 if it does not exist.
+pub(super) async fn load_booking<T>(
+    db: &Database,
+    booking_id: &str,
+) -> Result<Booking, (StatusCode, Json<ApiResponse<T>>)> {
+    match db.get_booking(booking_id).await {
+        Ok(Some(booking)) => Ok(booking),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::NotFound, "Booking not found")),
+        )),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            ))
+        }
+    }
+}
+
+/// Load booking `booking_id` for an admin, answering 404 if it does not
+/// exist or belongs to another tenant than the caller.
+async fn load_booking_for_admin<T>(
+    state: &crate::AppState,
+    auth_user: &AuthUser,
+    booking_id: &str,
+) -> Result<Booking, (StatusCode, Json<ApiResponse<T>>)> {
+    if let Err((status, msg)) = check_admin(auth_user) {
+        return Err((status, Json(ApiResponse::error(ErrorCode::Forbidden, msg))));
+    }
+    let booking = load_booking(&state.db, booking_id).await?;
+    let caller_tenant_id = super::resolve_tenant_id(state, auth_user.user_id).await;
+    if !super::matches_tenant(booking.tenant_id.as_deref(), caller_tenant_id.as_deref()) {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::NotFound, "Booking not found")),
+        ));
+    }
+    Ok(booking)
+}
+
+/// `GET /api/v1/admin/bookings/{id}/notes` — the booking's internal notes.
+#[utoipa::path(get, path = "/api/v1/admin/bookings/{id}/notes", tag = "Admin",
+    summary = "List internal booking notes",
+    description = "The internal notes on a booking, oldest first. Notes are never shown \
+        to the booking's owner. Admin only.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Booking ID")),
+    responses(
+        (status = 200, description = "Notes of the booking", body = Vec<BookingNote>),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Booking not found"),
+    )
+)]
+pub async fn list_booking_notes(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<BookingNote>>>) {
+    let state_guard = state.read().await;
+    if let Err(response) = load_booking_for_admin(&state_guard, &auth_user, &id).await {
+        return response;
+    }
+
+    match state_guard.db.list_booking_notes(&id).await {
+        Ok(notes) => (StatusCode::OK, Json(ApiResponse::success(notes))),
+        Err(e) => {
+            tracing::error!("Failed to list booking notes: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
+/// `POST /api/v1/admin/bookings/{id}/notes` — add an internal note.
+#[utoipa::path(post, path = "/api/v1/admin/bookings/{id}/notes", tag = "Admin",
+    summary = "Add an internal booking note",
+    description = "Adds a note (1-2000 characters) to a booking. Notes are never shown \
+        to the booking's owner. Admin only.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Booking ID")),
+    request_body = AddBookingNoteRequest,
+    responses(
+        (status = 201, description = "Note added", body = BookingNote),
+        (status = 400, description = "Invalid note text"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Booking not found"),
+    )
+)]
+pub async fn add_booking_note(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    ValidatedJson(req): ValidatedJson<AddBookingNoteRequest>,
+) -> (StatusCode, Json<ApiResponse<BookingNote>>) {
+    let state_guard = state.read().await;
+    let booking = match load_booking_for_admin(&state_guard, &auth_user, &id).await {
+        Ok(booking) => booking,
+        Err(response) => return response,
+    };
+    let text = req.text.trim();
+    if text.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::ValidationError,
+                "Note must not be blank",
+            )),
+        );
+    }
+
+    let note = BookingNote {
+        id: Uuid::new_v4(),
+        booking_id: booking.id,
+        author_id: auth_user.user_id,
+        text: text.to_string(),
+        created_at: Utc::now(),
+    };
+    if let Err(e) = state_guard.db.save_booking_note(&note).await {
+        tracing::error!("Failed to save booking note: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save note",
+            )),
+        );
+    }
+
+    let username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    AuditEntry::new(AuditEventType::BookingUpdated)
+        .user(auth_user.user_id, &username)
+        .resource("booking", &id)
+        .detail(&format!("Internal note {} added", note.id))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::CREATED, Json(ApiResponse::success(note)))
+}
@@ -0,0 +1,325 @@
+//! Booking tags for internal cost attribution.
+//!
+//! - `GET /api/v1/bookings/tags` — configured tag sets, for booking forms
+//! - `PUT /api/v1/admin/booking-tags` — replace all tag sets
+//!
+//! A tag set defines one tag bookings may carry, e.g. `cost_center` or
+//! `project`. Sets with a list of `values` only accept those values
+//! (matched case-insensitively, stored in the configured spelling); sets
+//! without one accept free text. Tags are given on `POST /api/v1/bookings`
+//! and can be replaced with `PATCH /api/v1/bookings/{id}`. The bookings CSV
+//! exports get one `tag:<key>` column per tag, and the revenue report can be
+//! grouped by a tag with `group_by=tag:<key>`.
+//!
+//! Removing a tag set does not touch existing bookings: their tags still
+//! show up in exports and reports, they just cannot be set any more.
+//!
+//! # Settings keys
+//! - `booking_tag_sets` — `Vec<BookingTagSet>` as JSON. Missing or
+//!   unreadable means "no tag sets", i.e. bookings cannot be tagged.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use axum::{Extension, Json, extract::State, http::StatusCode};
+use serde::{Deserialize, Serialize};
+
+use parkhub_common::{ApiResponse, Booking, ErrorCode};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::Database;
+
+use super::{AuthUser, SharedState, check_admin};
+
+/// Settings key holding all booking tag sets.
+const TAG_SETS_KEY: &str = "booking_tag_sets";
+
+/// Most tag sets that can be configured.
+const MAX_TAG_SETS: usize = 20;
+
+/// Most allowed values per tag set.
+const MAX_TAG_VALUES: usize = 500;
+
+/// Longest tag key, label or value, in characters.
+const MAX_TAG_LEN: usize = 100;
+
+/// One tag bookings may carry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BookingTagSet {
+    /// Tag key on bookings: lowercase letters, digits, `_` and `-`
+    pub key: String,
+    /// Name shown in booking forms and reports
+    pub label: String,
+    /// Allowed values; empty accepts any value
+    #[serde(default)]
+    pub values: Vec<String>,
+}
+
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_TAG_LEN
+        && key
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+}
+
+/// Reject malformed keys, blank labels or values, and duplicates.
+fn validate_tag_sets(sets: &[BookingTagSet]) -> Result<(), String> {
+    if sets.len() > MAX_TAG_SETS {
+        return Err(format!("At most {MAX_TAG_SETS} tag sets can be configured"));
+    }
+    let mut keys = HashSet::new();
+    for set in sets {
+        if !is_valid_key(&set.key) {
+            return Err(format!(
+                "Tag key '{}' must be 1-{MAX_TAG_LEN} lowercase letters, digits, '_' or '-'",
+                set.key
+            ));
+        }
+        if !keys.insert(set.key.as_str()) {
+            return Err(format!("Duplicate tag set '{}'", set.key));
+        }
+        let label = set.label.trim();
+        if label.is_empty() || label.chars().count() > MAX_TAG_LEN {
+            return Err(format!(
+                "Label of tag set '{}' must be 1-{MAX_TAG_LEN} characters",
+                set.key
+            ));
+        }
+        if set.values.len() > MAX_TAG_VALUES {
+            return Err(format!(
+                "Tag set '{}' has more than {MAX_TAG_VALUES} values",
+                set.key
+            ));
+        }
+        let mut values = HashSet::new();
+        for value in &set.values {
+            let value = value.trim();
+            if value.is_empty() || value.chars().count() > MAX_TAG_LEN {
+                return Err(format!(
+                    "Values of tag set '{}' must be 1-{MAX_TAG_LEN} characters",
+                    set.key
+                ));
+            }
+            if !values.insert(value.to_lowercase()) {
+                return Err(format!(
+                    "Duplicate value '{value}' in tag set '{}'",
+                    set.key
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Load all booking tag sets; empty when unset.
+pub async fn load_booking_tag_sets(db: &Database) -> Vec<BookingTagSet> {
+    db.get_setting(TAG_SETS_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Check `tags` against the configured `sets` and return them as stored:
+/// values trimmed, and listed values in their configured spelling.
+#[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+pub fn check_booking_tags(
+    sets: &[BookingTagSet],
+    tags: BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>, String> {
+    tags.into_iter()
+        .map(|(key, value)| {
+            let Some(set) = sets.iter().find(|set| set.key == key) else {
+                return Err(format!("Unknown booking tag '{key}'"));
+            };
+            let value = value.trim();
+            if value.is_empty() || value.chars().count() > MAX_TAG_LEN {
+                return Err(format!("Tag '{key}' must be 1-{MAX_TAG_LEN} characters"));
+            }
+            if set.values.is_empty() {
+                return Ok((key, value.to_string()));
+            }
+            set.values
+                .iter()
+                .map(|allowed| allowed.trim())
+                .find(|allowed| allowed.eq_ignore_ascii_case(value))
+                .map(|allowed| (key.clone(), allowed.to_string()))
+                .ok_or_else(|| format!("'{value}' is not an allowed value for tag '{key}'"))
+        })
+        .collect()
+}
+
+/// Tag keys for CSV columns: the configured sets in order, then any other
+/// key found on `bookings` (left over from a removed set), alphabetically.
+#[cfg_attr(
+    not(any(feature = "mod-export", feature = "mod-data-import")),
+    allow(dead_code)
+)]
+pub fn tag_columns<'a>(
+    sets: &[BookingTagSet],
+    bookings: impl IntoIterator<Item = &'a Booking>,
+) -> Vec<String> {
+    let mut columns: Vec<String> = sets.iter().map(|set| set.key.clone()).collect();
+    let extra: BTreeSet<&String> = bookings
+        .into_iter()
+        .flat_map(|booking| booking.tags.keys())
+        .filter(|key| !columns.contains(*key))
+        .collect();
+    columns.extend(extra.into_iter().cloned());
+    columns
+}
+
+/// `GET /api/v1/bookings/tags` — list booking tag sets
+#[utoipa::path(
+    get,
+    path = "/api/v1/bookings/tags",
+    tag = "Bookings",
+    summary = "List booking tag sets",
+    description = "Returns the tags bookings can carry (e.g. cost center or project code) \
+        and, where restricted, their allowed values.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Booking tag sets", body = Vec<BookingTagSet>),
+    )
+)]
+#[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+pub async fn list_booking_tag_sets(
+    State(state): State<SharedState>,
+) -> (StatusCode, Json<ApiResponse<Vec<BookingTagSet>>>) {
+    let state_guard = state.read().await;
+    let sets = load_booking_tag_sets(&state_guard.db).await;
+    (StatusCode::OK, Json(ApiResponse::success(sets)))
+}
+
+/// `PUT /api/v1/admin/booking-tags` — replace booking tag sets
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/booking-tags",
+    tag = "Admin",
+    summary = "Update booking tag sets",
+    description = "Replaces all booking tag sets. Tags already on bookings are kept even \
+        if their set is removed. Admin only.",
+    request_body = Vec<BookingTagSet>,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Updated tag sets", body = Vec<BookingTagSet>),
+        (status = 400, description = "Malformed key, blank label or value, or duplicate"),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn update_booking_tag_sets(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(sets): Json<Vec<BookingTagSet>>,
+) -> (StatusCode, Json<ApiResponse<Vec<BookingTagSet>>>) {
+    let state_guard = state.read().await;
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    if let Err(msg) = validate_tag_sets(&sets) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+        );
+    }
+
+    let sets: Vec<BookingTagSet> = sets
+        .into_iter()
+        .map(|set| BookingTagSet {
+            key: set.key,
+            label: set.label.trim().to_string(),
+            values: set.values.iter().map(|v| v.trim().to_string()).collect(),
+        })
+        .collect();
+
+    let raw = match serde_json::to_string(&sets) {
+        Ok(raw) => raw,
+        Err(e) => {
+            tracing::error!("Failed to serialize booking tag sets: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    if let Err(e) = state_guard.db.set_setting(TAG_SETS_KEY, &raw).await {
+        tracing::error!("Failed to save booking tag sets: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save tag sets",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::SettingsChanged)
+        .user(auth_user.user_id, "")
+        .resource("settings", TAG_SETS_KEY)
+        .detail(&format!("Booking tag sets updated ({} sets)", sets.len()))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(sets)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(key: &str, values: &[&str]) -> BookingTagSet {
+        BookingTagSet {
+            key: key.to_string(),
+            label: key.replace('_', " "),
+            values: values.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn tags(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), (*v).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_keys_and_duplicates() {
+        assert!(
+            validate_tag_sets(&[set("cost_center", &["CC-1", "CC-2"]), set("project", &[])])
+                .is_ok()
+        );
+        assert!(validate_tag_sets(&[set("Cost Center", &[])]).is_err());
+        assert!(validate_tag_sets(&[set("project", &[]), set("project", &[])]).is_err());
+        assert!(validate_tag_sets(&[set("cost_center", &["CC-1", " cc-1 "])]).is_err());
+        assert!(validate_tag_sets(&[set("cost_center", &[" "])]).is_err());
+    }
+
+    #[test]
+    fn test_check_booking_tags_canonicalizes_listed_values() {
+        let sets = [set("cost_center", &["CC-4711"]), set("project", &[])];
+        let checked = check_booking_tags(
+            &sets,
+            tags(&[("cost_center", " cc-4711"), ("project", " Apollo ")]),
+        )
+        .unwrap();
+        assert_eq!(
+            checked,
+            tags(&[("cost_center", "CC-4711"), ("project", "Apollo")])
+        );
+
+        assert!(check_booking_tags(&sets, tags(&[("cost_center", "CC-9999")])).is_err());
+        assert!(check_booking_tags(&sets, tags(&[("team", "ops")])).is_err());
+        assert!(check_booking_tags(&sets, tags(&[("project", "  ")])).is_err());
+        assert!(check_booking_tags(&[], BTreeMap::new()).unwrap().is_empty());
+    }
+}
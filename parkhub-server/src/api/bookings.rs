@@ -10,107 +10,165 @@
 
 use axum::{
     Extension, Json,
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::{StatusCode, header},
     response::IntoResponse,
 };
-use chrono::{DateTime, Datelike, TimeDelta, Utc};
+use chrono::{DateTime, Datelike, NaiveDate, TimeDelta, Utc};
 use parkhub_common::FuelType;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use std::fmt::Write as _;
 use uuid::Uuid;
 
 use parkhub_common::{
-    ApiResponse, Booking, BookingPricing, BookingStatus, CreateBookingRequest, CreditTransaction,
-    CreditTransactionType, PaymentStatus, SlotStatus, User, UserRole, Vehicle, VehicleType,
+    ApiResponse, BookedFor, Booking, BookingPricing, BookingQuote, BookingQuoteDay,
+    BookingQuoteRequest, BookingStatus, BookingWithCountdown, CreateBookingRequest,
+    CreditTransaction, CreditTransactionType, ErrorCode, LotCalendar, LotCalendarDay, Notification,
+    NotificationType, ParkingLot, ParkingSlot, PaymentStatus, SlotAlternative, SlotStatus,
+    TimeRange, User, UserRole, Vehicle, VehicleType,
 };
 
+use crate::AppState;
 use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{SlotAssignment, SlotChangeContext, WriteBatch};
 #[cfg(feature = "mod-email")]
 use crate::email;
 use crate::metrics;
+use crate::requests::{BOOKING_SORT_FIELDS, BookingListFilters, ListParams};
 use crate::utils::html_escape;
+use crate::validation::ValidatedJson;
 
-use super::{AuthUser, SharedState, check_admin, read_admin_setting};
+use super::availability::{is_usable, lot_buffer};
+use super::booking_locks;
+use super::lot_settings::LotConfig;
+use super::permissions::{may_manage_booking, may_view_booking};
+use super::plate_display::PlateViewer;
+use super::slot_assignment::{Placement, resolve_placement, type_unavailable_response};
+use super::{AuthUser, SharedState};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // BOOKINGS
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Attach countdown data measured against the server clock, so clients can
+/// render remaining time without trusting their own clock.
+fn with_countdown(state: &AppState, booking: Booking) -> BookingWithCountdown {
+    BookingWithCountdown::new(
+        booking,
+        Utc::now(),
+        state.config.booking_expiry_warning_minutes,
+    )
+}
+
 #[utoipa::path(get, path = "/api/v1/bookings", tag = "Bookings",
     summary = "List current user's bookings",
-    description = "Returns all bookings for the authenticated user.",
+    description = "Returns the bookings of the authenticated user. Without `page`, `per_page` \
+        or `cursor` every booking is returned; with them, one page is returned and `meta` \
+        holds the totals and the `next_cursor`. Sortable by `start_time` (default), \
+        `end_time`, `created_at` and `status`.",
     security(("bearer_auth" = [])),
-    responses((status = 200, description = "List of bookings"))
+    params(ListParams, BookingListFilters),
+    responses(
+        (status = 200, description = "List of bookings, each with a `countdown` object"),
+        (status = 400, description = "Unknown sort field or malformed cursor"),
+    )
 )]
 #[tracing::instrument(skip(state), fields(user_id = %auth_user.user_id))]
 #[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
 pub async fn list_bookings(
     State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
-) -> Json<ApiResponse<Vec<Booking>>> {
+    Query(params): Query<ListParams>,
+    Query(filters): Query<BookingListFilters>,
+) -> (StatusCode, Json<ApiResponse<Vec<BookingWithCountdown>>>) {
+    let (query, sort) = match super::list_request(&params, BOOKING_SORT_FIELDS, false) {
+        Ok(request) => request,
+        Err(response) => return response,
+    };
     let state = state.read().await;
 
     match state
         .db
-        .list_bookings_by_user(&auth_user.user_id.to_string())
+        .list_bookings_by_user_page(
+            &auth_user.user_id.to_string(),
+            (!filters.is_empty()).then_some(|b: &Booking| filters.matches(b)),
+            sort,
+            &query,
+        )
         .await
     {
-        Ok(bookings) => {
-            tracing::debug!(count = bookings.len(), "Listed bookings");
-            Json(ApiResponse::success(bookings))
+        Ok(page) => {
+            tracing::debug!(
+                count = page.items.len(),
+                total = page.total,
+                "Listed bookings"
+            );
+            let now = Utc::now();
+            let warning_minutes = state.config.booking_expiry_warning_minutes;
+            let mut response = ApiResponse::success(
+                page.items
+                    .into_iter()
+                    .map(|b| BookingWithCountdown::new(b, now, warning_minutes))
+                    .collect::<Vec<_>>(),
+            );
+            if params.is_paged() {
+                response.meta = Some(params.meta(page.total, page.next_cursor));
+            }
+            (StatusCode::OK, Json(response))
         }
         Err(e) => {
             tracing::error!(error = %e, "Failed to list bookings");
-            Json(ApiResponse::error(
-                "SERVER_ERROR",
-                "Failed to list bookings",
-            ))
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list bookings",
+                )),
+            )
         }
     }
 }
 
 #[utoipa::path(post, path = "/api/v1/bookings", tag = "Bookings",
     summary = "Create a new booking",
-    description = "Books a parking slot for the authenticated user.",
+    description = "Books a parking slot for the authenticated user. If the slot is taken, the \
+        409 `SLOT_UNAVAILABLE` error lists up to three free slots of the same type in \
+        `error.details.alternatives`, nearest first. Late-binding lots book a `slot_type` \
+        instead: the booking is created without a slot (`slot_number` 0) if the type has \
+        capacity, and the slot is assigned shortly before the start.",
     security(("bearer_auth" = [])),
     request_body = CreateBookingRequest,
-    responses((status = 201, description = "Booking created"), (status = 404, description = "Not found"), (status = 409, description = "Slot unavailable"))
+    responses((status = 201, description = "Booking created"), (status = 404, description = "Not found"), (status = 409, description = "Slot unavailable (alternatives in error details), or no slot of the type free"))
 )]
 #[tracing::instrument(skip(state, req), fields(user_id = %auth_user.user_id, slot_id = %req.slot_id))]
 #[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
 pub async fn create_booking(
     State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
-    Json(req): Json<CreateBookingRequest>,
-) -> (StatusCode, Json<ApiResponse<Booking>>) {
-    // ── Input length validation (issue #115) ────────────────────────────────
-    if req.license_plate.len() > 20 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error(
-                "INVALID_INPUT",
-                "License plate must be at most 20 characters",
-            )),
-        );
-    }
-    if let Some(ref notes) = req.notes
-        && notes.len() > 500
-    {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error(
-                "INVALID_INPUT",
-                "Notes must be at most 500 characters",
-            )),
-        );
-    }
+    ValidatedJson(req): ValidatedJson<CreateBookingRequest>,
+) -> (StatusCode, Json<ApiResponse<BookingWithCountdown>>) {
+    place_booking(state, auth_user, req, None).await
+}
+
+/// Validate, price and store a booking for `auth_user`, or for the visitor
+/// in `booked_for` with `auth_user` as the booking's owner.
+#[cfg_attr(
+    not(any(feature = "mod-bookings", feature = "mod-guest")),
+    allow(dead_code)
+)]
+pub(crate) async fn place_booking(
+    state: SharedState,
+    auth_user: AuthUser,
+    req: CreateBookingRequest,
+    booked_for: Option<BookedFor>,
+) -> (StatusCode, Json<ApiResponse<BookingWithCountdown>>) {
     // ── Phase 1: reads under a read lock ──────────────────────────────────────
     // Collect all data needed to validate and price the booking.  A read lock
     // allows concurrent readers; we release it before any mutation.
     #[allow(unused_variables)]
     let (
-        slot,
+        placement,
         vehicle,
         require_vehicle,
         plate_mode,
@@ -123,35 +181,58 @@ pub async fn create_booking(
         credits_per_booking,
         mut booking_user,
         lot_opt,
+        lot_rules,
         org_name,
         vat_rate,
+        tags,
     ) = {
         let rg = state.read().await;
 
-        // Check if slot exists and is available
-        let slot = match rg.db.get_parking_slot(&req.slot_id.to_string()).await {
-            Ok(Some(s)) => s,
-            Ok(None) => {
-                return (
-                    StatusCode::NOT_FOUND,
-                    Json(ApiResponse::error("NOT_FOUND", "Slot not found")),
-                );
-            }
-            Err(e) => {
-                tracing::error!("Database error: {}", e);
+        let tag_sets = super::booking_tags::load_booking_tag_sets(&rg.db).await;
+        let tags = match super::booking_tags::check_booking_tags(&tag_sets, req.tags.clone()) {
+            Ok(tags) => tags,
+            Err(msg) => {
                 return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(ErrorCode::InvalidTags, msg)),
                 );
             }
         };
 
-        if slot.status != SlotStatus::Available {
+        // Check if slot exists and is free for the requested time. Late-binding
+        // lots book a slot type; the slot itself is assigned shortly before
+        // the start.
+        let placement = match resolve_placement(&rg.db, &req).await {
+            Ok(placement) => placement,
+            Err(response) => return response,
+        };
+        if let Placement::Slot(ref slot) = placement {
+            // The live status only says whether the slot is taken right now;
+            // for the requested time only bookings overlapping it or the
+            // lot's buffer around it count.
+            let end_time =
+                req.start_time + TimeDelta::minutes(i64::from(req.duration_minutes.max(0)));
+            let buffer = lot_buffer(&rg.db, &slot.lot_id.to_string()).await;
+            let taken = match TimeRange::new(req.start_time, end_time) {
+                Some(range) => rg
+                    .db
+                    .list_slot_bookings_overlapping(&[slot.id], &range.padded(buffer))
+                    .await
+                    .is_ok_and(|bookings| !bookings.is_empty()),
+                None => false,
+            };
+            if taken || !is_usable(slot) {
+                return slot_unavailable_response(&rg.db, slot, req.start_time, end_time).await;
+            }
+        }
+
+        // Archived lots are closed to new bookings.
+        if super::lot_archive::is_archived(&rg.db, placement.lot_id()).await {
             return (
                 StatusCode::CONFLICT,
                 Json(ApiResponse::error(
-                    "SLOT_UNAVAILABLE",
-                    "This slot is not available",
+                    ErrorCode::LotArchived,
+                    "This parking lot is archived and no longer accepts bookings",
                 )),
             );
         }
@@ -163,7 +244,7 @@ pub async fn create_booking(
                     return (
                         StatusCode::FORBIDDEN,
                         Json(ApiResponse::error(
-                            "FORBIDDEN",
+                            ErrorCode::Forbidden,
                             "Vehicle does not belong to you",
                         )),
                     );
@@ -184,41 +265,42 @@ pub async fn create_booking(
             },
         };
 
-        // Admin settings
-        let require_vehicle = read_admin_setting(&rg.db, "require_vehicle").await;
-        let plate_mode = read_admin_setting(&rg.db, "license_plate_mode").await;
+        // Admin settings, as overridden for the lot
+        let lot_config = LotConfig::resolve(&rg, &placement.lot_id().to_string()).await;
+        let require_vehicle = lot_config.require_vehicle;
+        let plate_mode = lot_config.license_plate_mode;
         let duration_hours = f64::from(req.duration_minutes) / 60.0;
-        let min_hours: f64 = read_admin_setting(&rg.db, "min_booking_duration_hours")
-            .await
-            .parse()
-            .unwrap_or(0.0);
-        let max_hours: f64 = read_admin_setting(&rg.db, "max_booking_duration_hours")
-            .await
-            .parse()
-            .unwrap_or(0.0);
-        let max_per_day: i32 = rg
-            .db
-            .get_setting("max_bookings_per_day")
-            .await
-            .ok()
-            .flatten()
-            .and_then(|v| v.parse().ok())
-            .unwrap_or(0);
-
-        let same_day_count = if max_per_day > 0 {
-            let booking_date = req.start_time.date_naive();
-            rg.db
-                .count_bookings_for_user_on_day(&auth_user.user_id.to_string(), booking_date)
-                .await
-                .unwrap_or_else(|e| {
-                    tracing::warn!(
-                        error = %e,
-                        user_id = %auth_user.user_id,
-                        booking_date = %booking_date,
-                        "Failed to count same-day bookings from canonical table"
-                    );
-                    0
-                })
+        let min_hours = lot_config.min_booking_duration_hours;
+        let max_hours = lot_config.max_booking_duration_hours;
+        let max_per_day = lot_config.max_bookings_per_day;
+
+        // A multi-day booking must fit under the limit on every day it touches.
+        // The limit is on the user's own parking; visitor bookings neither
+        // count towards it nor are held to it.
+        let same_day_count = if max_per_day > 0 && booked_for.is_none() {
+            let end_time =
+                req.start_time + TimeDelta::minutes(i64::from(req.duration_minutes.max(0)));
+            let mut busiest = 0;
+            for (booking_date, _) in TimeRange::new(req.start_time, end_time)
+                .map(|range| range.split_by_day())
+                .unwrap_or_default()
+            {
+                let count = rg
+                    .db
+                    .count_bookings_for_user_on_day(&auth_user.user_id.to_string(), booking_date)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!(
+                            error = %e,
+                            user_id = %auth_user.user_id,
+                            booking_date = %booking_date,
+                            "Failed to count same-day bookings from canonical table"
+                        );
+                        0
+                    });
+                busiest = busiest.max(count);
+            }
+            busiest
         } else {
             0
         };
@@ -244,7 +326,10 @@ pub async fn create_booking(
         let Ok(Some(booking_user)) = rg.db.get_user(&auth_user.user_id.to_string()).await else {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to load user")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to load user",
+                )),
             );
         };
 
@@ -254,6 +339,24 @@ pub async fn create_booking(
             .await
             .ok()
             .flatten();
+        let lot_rules =
+            super::lot_rules::load_lot_rules(&rg.db, &placement.lot_id().to_string()).await;
+
+        // Restricted lots: the user or the booked plate needs a permit
+        // covering the whole booking.
+        if lot_rules.permit_required
+            && !super::permits::has_booking_permit(
+                &rg.db,
+                &booking_user,
+                placement.lot_id(),
+                &vehicle.license_plate,
+                req.start_time,
+                req.start_time + TimeDelta::minutes(i64::from(req.duration_minutes)),
+            )
+            .await
+        {
+            return super::permits::permit_required_response();
+        }
 
         let org_name = rg.config.organization_name.clone();
 
@@ -265,7 +368,7 @@ pub async fn create_booking(
         let vat_rate = super::tax::resolve_standard_rate(&rg).await;
 
         (
-            slot,
+            placement,
             vehicle,
             require_vehicle,
             plate_mode,
@@ -278,43 +381,42 @@ pub async fn create_booking(
             credits_per_booking,
             booking_user,
             lot_opt,
+            lot_rules,
             org_name,
             vat_rate,
+            tags,
         )
     };
     // Read lock released here.
 
     // ── Stateless validation (no lock needed) ─────────────────────────────────
-
-    // Validate duration is positive before arithmetic
-    if req.duration_minutes <= 0 {
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error(
-                "INVALID_INPUT",
-                "Duration must be positive",
-            )),
-        );
-    }
+    // Field formats and the duration range were checked on extraction.
 
     // Validate start_time is in the future (at least 1 minute from now)
     if req.start_time <= Utc::now() {
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_BOOKING_TIME",
+                ErrorCode::InvalidBookingTime,
                 "Booking start time must be in the future",
             )),
         );
     }
 
+    // ── Per-lot booking rules ───────────────────────────────────────────────
+    if let Err(violation) =
+        lot_rules.check_booking(req.start_time, req.duration_minutes, Utc::now())
+    {
+        return super::lot_rules::violation_response(violation);
+    }
+
     // ── Admin settings enforcement ─────────────────────────────────────────
 
-    if require_vehicle == "true" && req.vehicle_id == Uuid::nil() {
+    if require_vehicle && req.vehicle_id == Uuid::nil() {
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VEHICLE_REQUIRED",
+                ErrorCode::VehicleRequired,
                 "A vehicle is required for booking",
             )),
         );
@@ -324,7 +426,7 @@ pub async fn create_booking(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "LICENSE_PLATE_REQUIRED",
+                ErrorCode::LicensePlateRequired,
                 "A license plate is required for booking",
             )),
         );
@@ -334,7 +436,7 @@ pub async fn create_booking(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "DURATION_TOO_SHORT",
+                ErrorCode::DurationTooShort,
                 format!("Minimum booking duration is {min_hours} hour(s)"),
             )),
         );
@@ -344,17 +446,20 @@ pub async fn create_booking(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "DURATION_TOO_LONG",
+                ErrorCode::DurationTooLong,
                 format!("Maximum booking duration is {max_hours} hour(s)"),
             )),
         );
     }
 
-    if max_per_day > 0 && same_day_count >= usize::try_from(max_per_day).unwrap_or(0) {
+    if max_per_day > 0
+        && booked_for.is_none()
+        && same_day_count >= usize::try_from(max_per_day).unwrap_or(usize::MAX)
+    {
         return (
             StatusCode::UNPROCESSABLE_ENTITY,
             Json(ApiResponse::error(
-                "MAX_BOOKINGS_REACHED",
+                ErrorCode::MaxBookingsReached,
                 format!("Maximum of {max_per_day} booking(s) per day reached"),
             )),
         );
@@ -371,7 +476,7 @@ pub async fn create_booking(
         ) {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("OUTSIDE_OPERATING_HOURS", msg)),
+                Json(ApiResponse::error(ErrorCode::OutsideOperatingHours, msg)),
             );
         }
     }
@@ -385,7 +490,7 @@ pub async fn create_booking(
         return (
             StatusCode::UNPROCESSABLE_ENTITY,
             Json(ApiResponse::error(
-                "INSUFFICIENT_CREDITS",
+                ErrorCode::InsufficientCredits,
                 "Not enough credits for this booking",
             )),
         );
@@ -394,53 +499,44 @@ pub async fn create_booking(
     // Calculate pricing (no lock needed)
     let end_time = req.start_time + TimeDelta::minutes(i64::from(req.duration_minutes));
 
-    let hourly_rate = lot_opt
-        .as_ref()
-        .and_then(|lot| lot.pricing.rates.iter().find(|r| r.duration_minutes == 60))
-        .map_or(2.0, |r| r.price);
-    let daily_max = lot_opt.as_ref().and_then(|lot| lot.pricing.daily_max);
-    let lot_currency = lot_opt
-        .as_ref()
-        .map_or_else(|| "EUR".to_string(), |lot| lot.pricing.currency.clone());
-
-    // Cap at daily_max if configured (e.g. all-day price ceiling)
-    let raw_price = (f64::from(req.duration_minutes) / 60.0) * hourly_rate;
-    let base_price = daily_max.map_or(raw_price, |cap| raw_price.min(cap));
     // `vat_rate` resolved above from the seller-country tax profile.
-    let tax = base_price * vat_rate;
-    let total = base_price + tax;
-
-    let floor_name = lot_opt.as_ref().map_or_else(
-        || "Level 1".to_string(),
-        |lot| {
-            lot.floors
-                .iter()
-                .find(|f| f.id == slot.floor_id)
-                .map_or_else(|| "Level 1".to_string(), |f| f.name.clone())
-        },
+    let quote = price_booking(
+        lot_opt.as_ref(),
+        req.start_time,
+        req.duration_minutes,
+        vat_rate,
     );
 
+    // Late-binding bookings get their slot number and floor on assignment.
+    let (slot_id, slot_number, floor_name) = match placement {
+        Placement::Slot(ref slot) => {
+            let floor_name = lot_opt.as_ref().map_or_else(
+                || "Level 1".to_string(),
+                |lot| {
+                    lot.floors
+                        .iter()
+                        .find(|f| f.id == slot.floor_id)
+                        .map_or_else(|| "Level 1".to_string(), |f| f.name.clone())
+                },
+            );
+            (slot.id, slot.slot_number, floor_name)
+        }
+        Placement::Type { .. } => (Uuid::nil(), 0, String::new()),
+    };
+
     let now = Utc::now();
     let booking = Booking {
         id: Uuid::new_v4(),
         user_id: auth_user.user_id,
         lot_id: req.lot_id,
-        slot_id: req.slot_id,
-        slot_number: slot.slot_number,
+        slot_id,
+        slot_number,
         floor_name,
         vehicle,
         start_time: req.start_time,
         end_time,
         status: BookingStatus::Confirmed,
-        pricing: BookingPricing {
-            base_price,
-            discount: 0.0,
-            tax,
-            total,
-            currency: lot_currency,
-            payment_status: PaymentStatus::Pending,
-            payment_method: None,
-        },
+        pricing: quote.into_pricing(),
         created_at: now,
         updated_at: now,
         check_in_time: None,
@@ -451,64 +547,237 @@ pub async fn create_booking(
         // today so this is typically None (flag-off default), but once the flag
         // flips the record is already correctly partitioned.
         tenant_id: booking_user.tenant_id.clone(),
+        tags,
+        booked_for,
     };
 
-    // ── Phase 2: mutations under a write lock ──────────────────────────────────
-    // Re-check slot availability and commit all mutations atomically.
-    // The write lock serialises concurrent booking attempts for the same slot,
-    // preventing double-booking between the availability check and the insert.
+    // ── Phase 2: mutations under the booking's locks ──────────────────────────
+    // Re-check the slot and commit all mutations atomically. The claim below
+    // checks for overlapping bookings in the same storage transaction as the
+    // insert, so two bookings can never hold the slot at the same time. The
+    // keyed locks order this booking against others on the same slot, of the
+    // same user or from the same department; bookings elsewhere run in
+    // parallel. They are taken before the read lock, so a booking waiting
+    // for them never holds up a queued writer.
+    let placement_key = match placement {
+        Placement::Slot(ref slot) => booking_locks::slot_key(slot.id),
+        Placement::Type { ref slot_type, .. } => {
+            booking_locks::slot_type_key(placement.lot_id(), slot_type)
+        }
+    };
+    let locks = booking_locks::lock(
+        [
+            Some(placement_key),
+            Some(booking_locks::user_key(auth_user.user_id)),
+            super::department_quotas::quota_department(&booking_user)
+                .map(|d| booking_locks::department_key(&d)),
+        ]
+        .into_iter()
+        .flatten(),
+    )
+    .await;
     #[allow(unused_variables)]
     let user_info_opt = {
-        let state_guard = state.write().await;
+        let state_guard = state.read().await;
+
+        // Re-check the slot is still in service now that we hold its lock.
+        let mut current_slot = None;
+        if let Placement::Slot(ref slot) = placement {
+            match state_guard.db.get_parking_slot(&slot.id.to_string()).await {
+                Ok(Some(s)) if !is_usable(&s) => {
+                    return slot_unavailable_response(
+                        &state_guard.db,
+                        &s,
+                        booking.start_time,
+                        booking.end_time,
+                    )
+                    .await;
+                }
+                Ok(s) => current_slot = s,
+                Err(e) => {
+                    tracing::error!("Database error on slot re-check: {}", e);
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ApiResponse::error(
+                            ErrorCode::ServerError,
+                            "Internal server error",
+                        )),
+                    );
+                }
+            }
+        }
 
-        // Re-check slot availability now that we hold the write lock.
-        match state_guard
-            .db
-            .get_parking_slot(&req.slot_id.to_string())
-            .await
+        // Department quota is counted under the department's lock so two
+        // members cannot both take the department's last slot for the day.
+        if let Err(response) = super::department_quotas::check_department_quota(
+            &state_guard.db,
+            &booking_user,
+            booking.start_time,
+            booking.end_time,
+            None,
+        )
+        .await
+        {
+            return response;
+        }
+
+        // The booking, its slot's status and the credit deduction commit in
+        // one storage write transaction, which also re-checks overlapping
+        // bookings, so a crash cannot leave only some of them stored.
+        // Slots held for someone else's waitlist offer are not bookable
+        // until the offer is claimed or runs out.
+        let held = match TimeRange::new(booking.start_time, booking.end_time) {
+            Some(range) => {
+                super::noshow::slots_held_for_others(
+                    &state_guard,
+                    booking.lot_id,
+                    &range,
+                    booking.user_id,
+                )
+                .await
+            }
+            None => HashSet::new(),
+        };
+        // Nor are slots another user holds while filling out the booking form
+        let on_hold = match super::slot_holds::slots_held_by_others(
+            &state_guard.db,
+            booking.lot_id,
+            booking.user_id,
+            Utc::now(),
+        )
+        .await
         {
-            Ok(Some(s)) if s.status != SlotStatus::Available => {
+            Ok(held) => held,
+            Err(e) => {
+                tracing::error!("Failed to read slot holds: {e}");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Internal server error",
+                    )),
+                );
+            }
+        };
+        // Spend from the current balance: another booking of this user may
+        // have spent credits since it was read above.
+        if credits_enabled && !is_admin_user {
+            match state_guard
+                .db
+                .get_user(&auth_user.user_id.to_string())
+                .await
+            {
+                Ok(Some(user)) => booking_user = user,
+                _ => {
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ApiResponse::error(
+                            ErrorCode::ServerError,
+                            "Failed to load user",
+                        )),
+                    );
+                }
+            }
+            if booking_user.credits_balance < credits_per_booking {
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(ApiResponse::error(
+                        ErrorCode::InsufficientCredits,
+                        "Not enough credits for this booking",
+                    )),
+                );
+            }
+        }
+
+        let mut batch = WriteBatch::new();
+        match placement {
+            Placement::Slot(_) if held.contains(&booking.slot_id) => {
                 return (
                     StatusCode::CONFLICT,
                     Json(ApiResponse::error(
-                        "SLOT_UNAVAILABLE",
-                        "This slot is not available",
+                        ErrorCode::SlotHeld,
+                        "This slot is held for a waitlist offer",
                     )),
                 );
             }
-            Err(e) => {
-                tracing::error!("Database error on slot re-check: {}", e);
+            Placement::Slot(_) if on_hold.contains(&booking.slot_id) => {
                 return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                    StatusCode::CONFLICT,
+                    Json(ApiResponse::error(
+                        ErrorCode::SlotHeld,
+                        "This slot is held by another user",
+                    )),
                 );
             }
-            _ => {}
+            Placement::Slot(_) => batch.claim_slot(&booking),
+            Placement::Type {
+                ref slot_type,
+                ref slots,
+                ..
+            } => {
+                let slot_ids: Vec<Uuid> = slots
+                    .iter()
+                    .map(|s| s.id)
+                    .filter(|id| !held.contains(id) && !on_hold.contains(id))
+                    .collect();
+                batch.claim_slot_type(&booking, slot_type, &slot_ids)
+            }
+        };
+
+        // A free slot shows as reserved once it has a booking; occupied
+        // slots keep their status until the current occupant leaves.
+        if let Some(mut updated_slot) = current_slot
+            && updated_slot.status == SlotStatus::Available
+        {
+            updated_slot.status = SlotStatus::Reserved;
+            let change = SlotChangeContext::user(auth_user.user_id, "booking")
+                .reason(format!("Booking {}", booking.id));
+            batch.save_parking_slot(&updated_slot, &change);
         }
 
-        if let Err(e) = state_guard.db.save_booking(&booking).await {
-            tracing::error!("Failed to save booking: {}", e);
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(
-                    "SERVER_ERROR",
-                    "Failed to create booking",
-                )),
-            );
+        // Deduct credits if enabled and user is not admin
+        if credits_enabled && !is_admin_user {
+            booking_user.credits_balance -= credits_per_booking;
+            batch
+                .save_user(&booking_user)
+                .save_credit_transaction(&CreditTransaction {
+                    id: Uuid::new_v4(),
+                    user_id: auth_user.user_id,
+                    booking_id: Some(booking.id),
+                    amount: -credits_per_booking,
+                    transaction_type: CreditTransactionType::Deduction,
+                    description: Some(format!("Booking {}", booking.id)),
+                    granted_by: None,
+                    created_at: Utc::now(),
+                });
         }
 
-        // Update slot status atomically within the write-lock scope.
-        let mut updated_slot = slot;
-        updated_slot.status = SlotStatus::Reserved;
-        if let Err(e) = state_guard.db.save_parking_slot(&updated_slot).await {
-            tracing::error!("Failed to update slot status after booking: {}", e);
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error(
-                    "SLOT_UPDATE_FAILED",
-                    "Booking created but slot status could not be updated. Please contact support.",
-                )),
-            );
+        match state_guard.db.apply_batch(&batch).await {
+            Ok(true) => {}
+            Ok(false) => {
+                return match placement {
+                    Placement::Slot(ref slot) => {
+                        slot_unavailable_response(
+                            &state_guard.db,
+                            slot,
+                            booking.start_time,
+                            booking.end_time,
+                        )
+                        .await
+                    }
+                    Placement::Type { ref slot_type, .. } => type_unavailable_response(slot_type),
+                };
+            }
+            Err(e) => {
+                tracing::error!("Failed to save booking: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Failed to create booking",
+                    )),
+                );
+            }
         }
 
         tracing::info!(
@@ -518,25 +787,14 @@ pub async fn create_booking(
             "Booking created"
         );
 
-        // Deduct credits if enabled and user is not admin
-        if credits_enabled && !is_admin_user {
-            booking_user.credits_balance -= credits_per_booking;
-            if let Err(e) = state_guard.db.save_user(&booking_user).await {
-                tracing::warn!("Failed to save user credit deduction: {e}");
-            }
-            let tx = CreditTransaction {
-                id: Uuid::new_v4(),
-                user_id: auth_user.user_id,
-                booking_id: Some(booking.id),
-                amount: -credits_per_booking,
-                transaction_type: CreditTransactionType::Deduction,
-                description: Some(format!("Booking {}", booking.id)),
-                granted_by: None,
-                created_at: Utc::now(),
-            };
-            if let Err(e) = state_guard.db.save_credit_transaction(&tx).await {
-                tracing::warn!("Failed to save credit transaction: {e}");
-            }
+        // The hold did its job; free the slot's entry right away.
+        if matches!(placement, Placement::Slot(_))
+            && let Err(e) = state_guard
+                .db
+                .release_slot_hold(&booking.slot_id.to_string(), auth_user.user_id)
+                .await
+        {
+            tracing::warn!("Failed to release hold on slot {}: {e}", booking.slot_id);
         }
 
         // Fetch user details for audit log and confirmation email
@@ -554,16 +812,18 @@ pub async fn create_booking(
         };
         audit_entry.persist(&state_guard.db).await;
 
-        // Write lock released at end of this block.
+        // Read lock released at end of this block, the booking's locks
+        // right after it.
         user_info_opt
     };
+    drop(locks);
 
     // Broadcast WebSocket event for real-time updates
     {
         let state_r = state.read().await;
         state_r
             .ws_events
-            .broadcast(crate::api::ws::WsEvent::booking_created(
+            .broadcast(crate::api::events::LiveEvent::booking_created(
                 &booking.lot_id.to_string(),
                 &booking.slot_id.to_string(),
                 &auth_user.user_id.to_string(),
@@ -598,11 +858,12 @@ pub async fn create_booking(
     if let Some(u) = user_info_opt {
         let booking_id_str = booking.id.to_string();
         let floor_name = booking.floor_name.clone();
-        let slot_number = booking.slot_number;
+        let slot_number = (!booking.slot_id.is_nil()).then_some(booking.slot_number);
         let start_time_str = booking.start_time.format("%Y-%m-%d %H:%M UTC").to_string();
         let end_time_str = booking.end_time.format("%Y-%m-%d %H:%M UTC").to_string();
         let user_email = u.email.clone();
         let user_name = u.name;
+        let db = state.read().await.db.clone();
         tokio::spawn(async move {
             let email_html = email::build_booking_confirmation_email(
                 &user_name,
@@ -613,14 +874,20 @@ pub async fn create_booking(
                 &end_time_str,
                 &org_name,
             );
-            if let Err(e) =
-                email::send_email(&user_email, "Booking Confirmation — ParkHub", &email_html).await
+            if let Err(e) = crate::email_queue::enqueue(
+                &db,
+                &user_email,
+                "Booking Confirmation — ParkHub",
+                &email_html,
+            )
+            .await
             {
-                tracing::warn!("Failed to send booking confirmation email: {}", e);
+                tracing::warn!("Failed to queue booking confirmation email: {}", e);
             }
         });
     }
 
+    let booking = with_countdown(&*state.read().await, booking);
     (StatusCode::CREATED, Json(ApiResponse::success(booking)))
 }
 
@@ -637,28 +904,34 @@ pub async fn get_booking(
     State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(id): Path<String>,
-) -> (StatusCode, Json<ApiResponse<Booking>>) {
+) -> (StatusCode, Json<ApiResponse<BookingWithCountdown>>) {
     let state = state.read().await;
 
     match state.db.get_booking(&id).await {
         Ok(Some(booking)) => {
-            if booking.user_id != auth_user.user_id {
+            if !may_view_booking(&auth_user, &booking) {
                 return (
                     StatusCode::FORBIDDEN,
-                    Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+                    Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
                 );
             }
-            (StatusCode::OK, Json(ApiResponse::success(booking)))
+            (
+                StatusCode::OK,
+                Json(ApiResponse::success(with_countdown(&state, booking))),
+            )
         }
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Booking not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Booking not found")),
         ),
         Err(e) => {
             tracing::error!("Database error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
@@ -687,22 +960,25 @@ pub async fn cancel_booking(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Booking not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Booking not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
-    if booking.user_id != auth_user.user_id {
+    if !may_manage_booking(&auth_user, &booking) {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     }
 
@@ -711,39 +987,59 @@ pub async fn cancel_booking(
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "ALREADY_CANCELLED",
+                ErrorCode::AlreadyCancelled,
                 "Booking is already cancelled",
             )),
         );
     }
 
+    // Per-lot cancellation cutoff and fee policy
+    let now = Utc::now();
+    let lot_rules =
+        super::lot_rules::load_lot_rules(&state_guard.db, &booking.lot_id.to_string()).await;
+    if let Err(violation) = lot_rules.check_cancellation(booking.start_time, now) {
+        return super::lot_rules::violation_response(violation);
+    }
+    let fee_percent = lot_rules
+        .cancellation_policy
+        .map_or(0, |p| p.fee_percent_at(booking.start_time, now));
+    let cancellation_fee = lot_rules.cancellation_policy.map_or(0.0, |p| {
+        p.fee_for(booking.pricing.total, booking.start_time, now)
+    });
+
     let mut updated_booking = booking.clone();
     updated_booking.status = BookingStatus::Cancelled;
-    updated_booking.updated_at = Utc::now();
-
-    if let Err(e) = state_guard.db.save_booking(&updated_booking).await {
-        tracing::error!("Failed to update booking: {}", e);
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(
-                "SERVER_ERROR",
-                "Failed to cancel booking",
-            )),
-        );
+    updated_booking.updated_at = now;
+    if fee_percent > 0 {
+        updated_booking.pricing.cancellation_fee = Some(cancellation_fee);
+    }
+    // Paid bookings are refunded minus the fee
+    if booking.pricing.payment_status == PaymentStatus::Paid {
+        updated_booking.pricing.payment_status = match fee_percent {
+            0 => PaymentStatus::Refunded,
+            100.. => PaymentStatus::Paid,
+            _ => PaymentStatus::PartialRefund,
+        };
     }
 
-    // Free up the slot — only restore to Available if it was Reserved.
-    // Slots in Maintenance or Disabled state must remain as-is.
+    // The cancellation, the freed slot and the refund commit together.
+    let mut batch = WriteBatch::new();
+    batch.save_booking(&updated_booking);
+
+    // Free up the slot — only restore to Available if it was Reserved and
+    // no other booking still holds it. Slots in Maintenance or Disabled
+    // state must remain as-is.
     if let Ok(Some(mut slot)) = state_guard
         .db
         .get_parking_slot(&booking.slot_id.to_string())
         .await
         && slot.status == SlotStatus::Reserved
+        && !has_upcoming_bookings(&state_guard.db, slot.id, booking.id).await
     {
         slot.status = SlotStatus::Available;
-        if let Err(e) = state_guard.db.save_parking_slot(&slot).await {
-            tracing::error!("Failed to restore slot status after cancellation: {}", e);
-        }
+        let change = SlotChangeContext::user(auth_user.user_id, "cancellation")
+            .reason(format!("Booking {} cancelled", booking.id));
+        batch.save_parking_slot(&slot, &change);
     }
 
     // Refund credits if credits system is enabled
@@ -764,33 +1060,48 @@ pub async fn cancel_booking(
             .flatten()
             .and_then(|v| v.parse().ok())
             .unwrap_or(1);
-        if let Ok(Some(mut user)) = state_guard
-            .db
-            .get_user(&auth_user.user_id.to_string())
-            .await
+        if let Ok(Some(mut user)) = state_guard.db.get_user(&booking.user_id.to_string()).await
             && user.role != UserRole::Admin
             && user.role != UserRole::SuperAdmin
         {
-            user.credits_balance += credits_per_booking;
-            if let Err(e) = state_guard.db.save_user(&user).await {
-                tracing::warn!("Failed to save user credit refund: {e}");
-            }
-            let tx = CreditTransaction {
-                id: Uuid::new_v4(),
-                user_id: auth_user.user_id,
-                booking_id: Some(booking.id),
-                amount: credits_per_booking,
-                transaction_type: CreditTransactionType::Refund,
-                description: Some(format!("Cancelled booking {}", booking.id)),
-                granted_by: None,
-                created_at: Utc::now(),
+            // The owner is refunded, also when staff cancel for them. The
+            // cancellation fee is kept as a share of the credits
+            let refund = credits_per_booking * i32::from(100 - fee_percent.min(100)) / 100;
+            user.credits_balance += refund;
+            let description = if fee_percent > 0 {
+                format!(
+                    "Cancelled booking {} ({fee_percent}% cancellation fee)",
+                    booking.id
+                )
+            } else {
+                format!("Cancelled booking {}", booking.id)
             };
-            if let Err(e) = state_guard.db.save_credit_transaction(&tx).await {
-                tracing::warn!("Failed to save credit transaction: {e}");
-            }
+            batch
+                .save_user(&user)
+                .save_credit_transaction(&CreditTransaction {
+                    id: Uuid::new_v4(),
+                    user_id: booking.user_id,
+                    booking_id: Some(booking.id),
+                    amount: refund,
+                    transaction_type: CreditTransactionType::Refund,
+                    description: Some(description),
+                    granted_by: None,
+                    created_at: Utc::now(),
+                });
         }
     }
 
+    if let Err(e) = state_guard.db.apply_batch(&batch).await {
+        tracing::error!("Failed to update booking: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to cancel booking",
+            )),
+        );
+    }
+
     // Fetch user for audit log + cancellation email
     let user = state_guard
         .db
@@ -814,8 +1125,9 @@ pub async fn cancel_booking(
         "Booking cancelled"
     );
 
-    // P1-2: promote the next FIFO waitlist entry to Offered status (AI-Act
-    // compliant — strict FIFO by created_at, no reordering).
+    // P1-2: promote the next FIFO waitlist entry to Offered status and tell
+    // them in-app and by email (AI-Act compliant — strict FIFO by
+    // created_at, no reordering).
     {
         let lot_id = booking.lot_id;
         let claim_window =
@@ -823,6 +1135,10 @@ pub async fn cancel_booking(
         crate::api::noshow::promote_next_waitlist_offer(&state_guard, lot_id, claim_window).await;
     }
 
+    // Let users who pinned this slot know it is free around their usual arrival.
+    #[cfg(feature = "mod-favorites")]
+    crate::api::favorites::notify_favorite_slot_freed(&state_guard, &booking).await;
+
     // Send cancellation confirmation email (async, best-effort)
     #[cfg(feature = "mod-email")]
     if let Some(ref user) = user {
@@ -834,6 +1150,13 @@ pub async fn cancel_booking(
         let end_time = booking.end_time.format("%Y-%m-%d %H:%M").to_string();
         let floor = booking.floor_name.clone();
         let slot = booking.slot_number;
+        let fee = (fee_percent > 0).then(|| {
+            format!(
+                "{cancellation_fee:.2} {} ({fee_percent}%)",
+                booking.pricing.currency
+            )
+        });
+        let db = state_guard.db.clone();
         tokio::spawn(async move {
             let email_html = email::build_booking_cancellation_email(
                 &user_name,
@@ -843,62 +1166,17 @@ pub async fn cancel_booking(
                 &start_time,
                 &end_time,
                 &org_name,
+                fee.as_deref(),
             );
-            if let Err(e) =
-                email::send_email(&user_email, "Booking Cancelled — ParkHub", &email_html).await
-            {
-                tracing::warn!("Failed to send cancellation email: {}", e);
-            }
-        });
-    }
-
-    // Notify the first waitlist member that a slot is now available (async, best-effort)
-    #[cfg(feature = "mod-email")]
-    {
-        let state_clone = state.clone();
-        let lot_id_str = booking.lot_id.to_string();
-        let org_name_wl = state_guard.config.organization_name.clone();
-        tokio::spawn(async move {
-            let state_r = state_clone.read().await;
-            let lot_name = state_r
-                .db
-                .get_parking_lot(&lot_id_str)
-                .await
-                .ok()
-                .flatten()
-                .map_or_else(|| lot_id_str.clone(), |l| l.name);
-
-            let waitlist = state_r
-                .db
-                .list_waitlist_by_lot(&lot_id_str)
-                .await
-                .unwrap_or_default();
-
-            // Notify the earliest-queued user who has not yet been notified
-            if let Some(entry) = waitlist.iter().find(|e| e.notified_at.is_none())
-                && let Ok(Some(wl_user)) = state_r.db.get_user(&entry.user_id.to_string()).await
+            if let Err(e) = crate::email_queue::enqueue(
+                &db,
+                &user_email,
+                "Booking Cancelled — ParkHub",
+                &email_html,
+            )
+            .await
             {
-                let email_html = email::build_waitlist_slot_available_email(
-                    &wl_user.name,
-                    &lot_name,
-                    &org_name_wl,
-                );
-                let subject = format!("Parking slot available at {lot_name} — ParkHub");
-                if let Err(e) = email::send_email(&wl_user.email, &subject, &email_html).await {
-                    tracing::warn!("Failed to send waitlist notification: {}", e);
-                } else {
-                    // Mark the entry as notified
-                    let mut updated = entry.clone();
-                    updated.notified_at = Some(Utc::now());
-                    if let Err(e) = state_r.db.save_waitlist_entry(&updated).await {
-                        tracing::warn!("Failed to update waitlist notified_at: {}", e);
-                    }
-                    tracing::info!(
-                        user_id = %wl_user.id,
-                        lot_id = %lot_id_str,
-                        "Waitlist slot-available notification sent"
-                    );
-                }
+                tracing::warn!("Failed to queue cancellation email: {}", e);
             }
         });
     }
@@ -906,7 +1184,7 @@ pub async fn cancel_booking(
     // Broadcast WebSocket event for real-time updates
     state_guard
         .ws_events
-        .broadcast(crate::api::ws::WsEvent::booking_cancelled(
+        .broadcast(crate::api::events::LiveEvent::booking_cancelled(
             &booking.lot_id.to_string(),
             &booking.slot_id.to_string(),
         ));
@@ -941,7 +1219,8 @@ pub async fn cancel_booking(
 /// `GET /api/v1/bookings/{id}/invoice`
 ///
 /// Returns an HTML invoice for the given booking.  The authenticated user must
-/// own the booking (admin users may retrieve any invoice).
+/// own the booking (admin users may retrieve any invoice).  The invoice is
+/// issued and archived on first request; see `invoice_archive`.
 ///
 /// The invoice includes:
 /// - Company/organisation name from server config
@@ -950,7 +1229,6 @@ pub async fn cancel_booking(
 /// - Parking lot name and slot number
 /// - Start / end time and duration
 /// - Itemised pricing: base price, VAT at 19% (German standard), total
-#[allow(clippy::format_in_format_args)]
 #[utoipa::path(get, path = "/api/v1/bookings/{id}/invoice", tag = "Bookings",
     summary = "Download booking invoice",
     description = "Generates a text invoice for a booking.",
@@ -985,7 +1263,8 @@ pub async fn get_booking_invoice(
         }
     };
 
-    // Ownership check — only the booking owner (or admin) may fetch the invoice
+    // Ownership check — only the booking owner or someone who sees bookings in
+    // its lot may fetch the invoice
     let Ok(Some(caller)) = state_guard
         .db
         .get_user(&auth_user.user_id.to_string())
@@ -998,8 +1277,7 @@ pub async fn get_booking_invoice(
         );
     };
 
-    let is_admin = caller.role == UserRole::Admin || caller.role == UserRole::SuperAdmin;
-    if booking.user_id != auth_user.user_id && !is_admin {
+    if !may_view_booking(&auth_user, &booking) {
         return (
             StatusCode::FORBIDDEN,
             [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
@@ -1007,300 +1285,326 @@ pub async fn get_booking_invoice(
         );
     }
 
-    // Fetch user details for the invoice
-    let booking_user = match state_guard.db.get_user(&booking.user_id.to_string()).await {
-        Ok(Some(u)) => u,
-        _ => caller.clone(),
-    };
-
-    // Fetch parking lot name
-    let lot_name = match state_guard
-        .db
-        .get_parking_lot(&booking.lot_id.to_string())
-        .await
-    {
-        Ok(Some(lot)) => lot.name,
-        _ => "Unknown Parking Lot".to_string(),
+    // Issued once, then served from the archive so later price or tax
+    // changes do not alter it
+    let invoice = match super::invoice_archive::issue_invoice(&state_guard, &booking).await {
+        Ok(invoice) => invoice,
+        Err(e) => {
+            tracing::error!("Failed to issue invoice: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
+                "Failed to issue invoice".to_string(),
+            );
+        }
     };
+    // Admins fetching another user's invoice see the plate per the lot's
+    // license_plate_display
+    let plate_display = LotConfig::resolve(&state_guard, &booking.lot_id.to_string())
+        .await
+        .license_plate_display;
+    let viewer = PlateViewer::new(auth_user.user_id, &caller.role, plate_display);
+    let html = super::invoice_archive::invoice_html_for(&invoice, viewer);
 
-    let org_name = state_guard.config.organization_name.clone();
-    let company = if org_name.is_empty() {
-        "ParkHub".to_string()
-    } else {
-        org_name
-    };
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+        html,
+    )
+}
 
-    // Calculate duration in minutes
-    let duration_minutes = (booking.end_time - booking.start_time).num_minutes();
-    let duration_hours = duration_minutes / 60;
-    let duration_mins_part = duration_minutes % 60;
-
-    // VAT breakdown — seller country is resolved from the tax settings, so
-    // the MwSt. row matches whatever jurisdiction the operator configured.
-    // Historical invoices still show `booking.pricing.tax` when available
-    // (see resolved gross below); this path is exercised on rerender so the
-    // rendered rate reflects the current configuration.
-    let seller_country = super::tax::resolve_seller_country_from_settings(&state_guard).await;
-    let buyer_vat_key = format!("user_vat_id_{}", booking.user_id);
-    let buyer_country_key = format!("user_country_{}", booking.user_id);
-    let buyer_vat_id = state_guard
-        .db
-        .get_setting(&buyer_vat_key)
-        .await
-        .ok()
-        .flatten()
-        .filter(|v| !v.trim().is_empty());
-    let buyer_country = state_guard
+// ═══════════════════════════════════════════════════════════════════════════════
+// PRICE QUOTE
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Hourly rate used when a lot has no 60-minute pricing entry.
+const DEFAULT_HOURLY_RATE: f64 = 2.0;
+
+/// Price a booking of `duration_minutes` starting at `start_time` in `lot`.
+/// Shared by booking creation, quick-book and the quote endpoint so the
+/// quoted price is the booked price.
+///
+/// The range is split at every UTC midnight it crosses and each calendar day
+/// is priced and capped at the lot's `daily_max` separately, so a three-day
+/// booking costs at most three daily maximums.
+pub(crate) fn price_booking(
+    lot: Option<&ParkingLot>,
+    start_time: DateTime<Utc>,
+    duration_minutes: i32,
+    vat_rate: f64,
+) -> BookingQuote {
+    let hourly_rate = lot
+        .and_then(|lot| lot.pricing.rates.iter().find(|r| r.duration_minutes == 60))
+        .map_or(DEFAULT_HOURLY_RATE, |r| r.price);
+    let daily_max = lot.and_then(|lot| lot.pricing.daily_max);
+    let currency = lot.map_or_else(|| "EUR".to_string(), |lot| lot.pricing.currency.clone());
+
+    let end_time = start_time + TimeDelta::minutes(i64::from(duration_minutes.max(0)));
+    let days: Vec<BookingQuoteDay> = TimeRange::new(start_time, end_time)
+        .map(|range| range.split_by_day())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|(date, piece)| {
+            let minutes = i32::try_from(piece.duration().num_minutes()).unwrap_or(i32::MAX);
+            let raw = (f64::from(minutes) / 60.0) * hourly_rate;
+            // Cap at daily_max if configured (e.g. all-day price ceiling)
+            let price = daily_max.map_or(raw, |cap| raw.min(cap));
+            BookingQuoteDay {
+                date,
+                minutes,
+                price,
+                daily_max_applied: price < raw,
+            }
+        })
+        .collect();
+
+    let base_price: f64 = days.iter().map(|d| d.price).sum();
+    let daily_max_applied = days.iter().any(|d| d.daily_max_applied);
+    let discount = 0.0;
+    let net = base_price - discount;
+    let tax = net * vat_rate;
+
+    BookingQuote {
+        hourly_rate,
+        duration_minutes,
+        base_price,
+        discount,
+        net,
+        vat_rate,
+        tax,
+        total: net + tax,
+        currency,
+        daily_max_applied,
+        days,
+    }
+}
+
+/// `POST /api/v1/bookings/quote` — itemized price for a prospective booking
+#[utoipa::path(post, path = "/api/v1/bookings/quote", tag = "Bookings",
+    summary = "Quote a booking price",
+    description = "Returns the itemized price (net, discount, VAT, total) a booking with these \
+        parameters would be charged. Nothing is reserved.",
+    security(("bearer_auth" = [])),
+    request_body = BookingQuoteRequest,
+    responses(
+        (status = 200, description = "Price quote", body = BookingQuote),
+        (status = 400, description = "Invalid duration"),
+        (status = 404, description = "Lot or slot not found"),
+        (status = 409, description = "Lot archived"),
+    )
+)]
+#[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+pub async fn quote_booking(
+    State(state): State<SharedState>,
+    Extension(_auth_user): Extension<AuthUser>,
+    Json(req): Json<BookingQuoteRequest>,
+) -> (StatusCode, Json<ApiResponse<BookingQuote>>) {
+    if !parkhub_common::is_valid_booking_duration(req.duration_minutes) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "Duration must be between 15 minutes and 14 days",
+            )),
+        );
+    }
+
+    let state_guard = state.read().await;
+
+    let Ok(Some(lot)) = state_guard
         .db
-        .get_setting(&buyer_country_key)
+        .get_parking_lot(&req.lot_id.to_string())
         .await
-        .ok()
-        .flatten()
-        .filter(|v| !v.trim().is_empty())
-        .unwrap_or_else(|| seller_country.clone());
-    let resolved_rate =
-        super::tax::resolve_rate(&seller_country, &buyer_country, buyer_vat_id.as_deref());
-    let net_price = booking.pricing.base_price;
-    let vat_amount = net_price * resolved_rate.as_rate();
-    let gross_total = net_price + vat_amount;
-    let vat_label = if resolved_rate.is_reverse_charge() {
-        "MwSt. 0% (Reverse Charge, Art. 194 VAT Directive)".to_string()
-    } else {
-        let pct = resolved_rate.as_rate() * 100.0;
-        if (pct - pct.round()).abs() < f64::EPSILON {
-            format!("MwSt. {}% (§ 12 UStG)", pct.round() as i64)
-        } else {
-            format!("MwSt. {pct:.1}% (§ 12 UStG)")
-        }
+    else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
+        );
     };
-    let reverse_charge_html = if resolved_rate.is_reverse_charge() {
-        "<p><strong>Hinweis:</strong> Reverse charge per Art. 194 VAT Directive.</p>"
-    } else {
-        ""
+
+    if super::lot_archive::is_archived(&state_guard.db, lot.id).await {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::LotArchived,
+                "This parking lot is archived and no longer accepts bookings",
+            )),
+        );
+    }
+
+    if let Some(slot_id) = req.slot_id {
+        match state_guard.db.get_parking_slot(&slot_id.to_string()).await {
+            Ok(Some(slot)) if slot.lot_id == lot.id => {}
+            _ => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::error(ErrorCode::NotFound, "Slot not found")),
+                );
+            }
+        }
+    }
+
+    let vat_rate = super::tax::resolve_standard_rate(&state_guard).await;
+    let quote = price_booking(Some(&lot), req.start_time, req.duration_minutes, vat_rate);
+
+    (StatusCode::OK, Json(ApiResponse::success(quote)))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// LOT CALENDAR
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Default number of days returned by the lot calendar (the longest booking).
+const DEFAULT_CALENDAR_DAYS: u32 = 14;
+
+/// Largest window the lot calendar returns.
+const MAX_CALENDAR_DAYS: u32 = 62;
+
+/// Query parameters for `GET /api/v1/lots/{id}/calendar`
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct LotCalendarQuery {
+    /// First day (UTC, `YYYY-MM-DD`); defaults to today
+    pub start_date: Option<NaiveDate>,
+    /// Number of days to return (1–62, default 14)
+    pub days: Option<u32>,
+}
+
+/// Per-day availability of `lot` for `days` days from `start_date`. A slot is
+/// free on a day when no pending, confirmed or active booking touches that
+/// day, so an overnight booking blocks its slot on both days.
+fn build_lot_calendar(
+    lot: &ParkingLot,
+    slots: &[ParkingSlot],
+    bookings: &[Booking],
+    start_date: NaiveDate,
+    days: u32,
+    today: NaiveDate,
+    vat_rate: f64,
+) -> LotCalendar {
+    let usable: HashSet<Uuid> = slots
+        .iter()
+        .filter(|s| !matches!(s.status, SlotStatus::Maintenance | SlotStatus::Disabled))
+        .map(|s| s.id)
+        .collect();
+    let held: Vec<(Uuid, TimeRange)> = bookings
+        .iter()
+        .filter(|b| {
+            b.lot_id == lot.id
+                && usable.contains(&b.slot_id)
+                && matches!(
+                    b.status,
+                    BookingStatus::Pending | BookingStatus::Confirmed | BookingStatus::Active
+                )
+        })
+        .filter_map(|b| TimeRange::new(b.start_time, b.end_time).map(|r| (b.slot_id, r)))
+        .collect();
+    let total_slots = u32::try_from(usable.len()).unwrap_or(u32::MAX);
+
+    let days = start_date
+        .iter_days()
+        .take(days as usize)
+        .map(|date| {
+            let busy: HashSet<Uuid> = held
+                .iter()
+                .filter(|(_, range)| range.touches_day(date))
+                .map(|(slot_id, _)| *slot_id)
+                .collect();
+            let free_slots =
+                total_slots.saturating_sub(u32::try_from(busy.len()).unwrap_or(u32::MAX));
+            let day_start = TimeRange::day(date).start;
+            LotCalendarDay {
+                date,
+                total_slots,
+                free_slots,
+                day_price: price_booking(Some(lot), day_start, 24 * 60, vat_rate).total,
+                bookable: date >= today && free_slots > 0,
+            }
+        })
+        .collect();
+
+    LotCalendar {
+        lot_id: lot.id,
+        currency: lot.pricing.currency.clone(),
+        days,
+    }
+}
+
+/// `GET /api/v1/lots/{id}/calendar` — per-day availability for range selection
+#[utoipa::path(get, path = "/api/v1/lots/{id}/calendar", tag = "Bookings",
+    summary = "Lot availability calendar",
+    description = "Free slots and the full-day price (VAT included) of a lot for each calendar \
+        day (UTC). Clients use it to pick overnight and multi-day booking ranges.",
+    params(("id" = String, Path, description = "Parking lot ID"), LotCalendarQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Per-day availability", body = LotCalendar),
+        (status = 400, description = "Invalid number of days"),
+        (status = 404, description = "Parking lot not found"),
+    )
+)]
+#[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+pub async fn lot_calendar(
+    State(state): State<SharedState>,
+    Extension(_auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<String>,
+    Query(query): Query<LotCalendarQuery>,
+) -> (StatusCode, Json<ApiResponse<LotCalendar>>) {
+    let days = query.days.unwrap_or(DEFAULT_CALENDAR_DAYS);
+    if days == 0 || days > MAX_CALENDAR_DAYS {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                format!("days must be between 1 and {MAX_CALENDAR_DAYS}"),
+            )),
+        );
+    }
+
+    let state_guard = state.read().await;
+    let Ok(Some(lot)) = state_guard.db.get_parking_lot(&lot_id).await else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
+        );
     };
 
-    let invoice_date = booking.created_at.format("%d.%m.%Y").to_string();
-    let start_str = booking.start_time.format("%d.%m.%Y %H:%M").to_string();
-    let end_str = booking.end_time.format("%d.%m.%Y %H:%M").to_string();
-
-    // Sequential invoice number per § 14 UStG (fortlaufende Rechnungsnummer).
-    // Allocated once per booking from the per-year SETTINGS counter and then
-    // reused on every re-download so the series remains gap-free.
-    let booking_year: i32 = booking
-        .created_at
-        .format("%Y")
-        .to_string()
-        .parse()
-        .unwrap_or(2026);
-    let invoice_number = match state_guard
-        .db
-        .get_or_assign_invoice_number(&booking.id.to_string(), booking_year)
-        .await
-    {
-        Ok(n) => n,
-        Err(e) => {
-            tracing::error!("Failed to allocate invoice number: {e}");
+    let (slots, bookings) = match (
+        state_guard.db.list_slots_by_lot(&lot_id).await,
+        state_guard.db.list_bookings().await,
+    ) {
+        (Ok(slots), Ok(bookings)) => (slots, bookings),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Failed to build lot calendar: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                [(header::CONTENT_TYPE, "text/plain; charset=utf-8")],
-                "Failed to allocate invoice number".to_string(),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
-    // HTML-escape all user-controlled values to prevent stored XSS
-    let company = html_escape(&company);
-    let user_name = html_escape(&booking_user.name);
-    let user_email = html_escape(&booking_user.email);
-    let lot_name = html_escape(&lot_name);
-    let floor_name = html_escape(&booking.floor_name);
-    let license_plate = html_escape(&booking.vehicle.license_plate);
-
-    let html = format!(
-        r#"<!DOCTYPE html>
-<html lang="de">
-<head>
-  <meta charset="UTF-8" />
-  <meta name="viewport" content="width=device-width, initial-scale=1.0" />
-  <title>Rechnung {invoice_number}</title>
-  <style>
-    * {{ box-sizing: border-box; margin: 0; padding: 0; }}
-    body {{ font-family: 'Helvetica Neue', Arial, sans-serif; color: #1a1a2e; background: #f8f9fa; }}
-    .page {{ max-width: 800px; margin: 40px auto; background: #ffffff; padding: 60px;
-             box-shadow: 0 4px 20px rgba(0,0,0,0.08); border-radius: 4px; }}
-    .header {{ display: flex; justify-content: space-between; align-items: flex-start;
-               border-bottom: 3px solid #1a73e8; padding-bottom: 24px; margin-bottom: 40px; }}
-    .company-name {{ font-size: 28px; font-weight: 700; color: #1a73e8; }}
-    .company-sub {{ font-size: 12px; color: #666; margin-top: 4px; }}
-    .invoice-meta {{ text-align: right; }}
-    .invoice-meta h2 {{ font-size: 22px; color: #333; }}
-    .invoice-meta p {{ font-size: 13px; color: #666; margin-top: 4px; }}
-    .section {{ margin-bottom: 32px; }}
-    .section-title {{ font-size: 11px; font-weight: 700; color: #999; text-transform: uppercase;
-                      letter-spacing: 0.1em; margin-bottom: 8px; }}
-    .bill-to {{ background: #f8f9fa; padding: 16px 20px; border-radius: 4px; border-left: 3px solid #1a73e8; }}
-    .bill-to p {{ font-size: 14px; line-height: 1.6; color: #333; }}
-    table {{ width: 100%; border-collapse: collapse; margin-bottom: 0; }}
-    thead tr {{ background: #1a73e8; color: white; }}
-    thead th {{ padding: 12px 16px; text-align: left; font-size: 13px; font-weight: 600; }}
-    tbody tr {{ border-bottom: 1px solid #e8ecf0; }}
-    tbody tr:hover {{ background: #f8f9fa; }}
-    tbody td {{ padding: 14px 16px; font-size: 14px; color: #333; }}
-    .text-right {{ text-align: right; }}
-    .totals {{ margin-top: 0; border-top: 2px solid #e8ecf0; }}
-    .totals tr td {{ padding: 10px 16px; font-size: 14px; }}
-    .totals .total-row td {{ font-size: 16px; font-weight: 700; color: #1a73e8;
-                              border-top: 2px solid #1a73e8; padding-top: 14px; }}
-    .badge {{ display: inline-block; padding: 4px 10px; border-radius: 20px; font-size: 12px;
-              font-weight: 600; }}
-    .badge-confirmed {{ background: #e8f5e9; color: #2e7d32; }}
-    .footer {{ margin-top: 48px; padding-top: 24px; border-top: 1px solid #e8ecf0;
-               font-size: 11px; color: #999; text-align: center; line-height: 1.6; }}
-  </style>
-</head>
-<body>
-  <div class="page">
-
-    <!-- Header -->
-    <div class="header">
-      <div>
-        <div class="company-name">{company}</div>
-        <div class="company-sub">Parkverwaltungssystem</div>
-      </div>
-      <div class="invoice-meta">
-        <h2>RECHNUNG</h2>
-        <p><strong>{invoice_number}</strong></p>
-        <p>Datum: {invoice_date}</p>
-      </div>
-    </div>
-
-    <!-- Bill To -->
-    <div class="section">
-      <div class="section-title">Rechnungsempfänger</div>
-      <div class="bill-to">
-        <p><strong>{user_name}</strong></p>
-        <p>{user_email}</p>
-      </div>
-    </div>
-
-    <!-- Booking Details -->
-    <div class="section">
-      <div class="section-title">Buchungsdetails</div>
-      <table>
-        <thead>
-          <tr>
-            <th>Beschreibung</th>
-            <th>Details</th>
-          </tr>
-        </thead>
-        <tbody>
-          <tr>
-            <td>Buchungsnummer</td>
-            <td>{booking_id}</td>
-          </tr>
-          <tr>
-            <td>Parkhaus</td>
-            <td>{lot_name}</td>
-          </tr>
-          <tr>
-            <td>Stellplatz</td>
-            <td>Nr. {slot_number} &nbsp;·&nbsp; {floor_name}</td>
-          </tr>
-          <tr>
-            <td>Fahrzeug (Kennzeichen)</td>
-            <td>{license_plate}</td>
-          </tr>
-          <tr>
-            <td>Beginn</td>
-            <td>{start_str}</td>
-          </tr>
-          <tr>
-            <td>Ende</td>
-            <td>{end_str}</td>
-          </tr>
-          <tr>
-            <td>Dauer</td>
-            <td>{duration_hours} Std. {duration_mins_part} Min.</td>
-          </tr>
-          <tr>
-            <td>Status</td>
-            <td><span class="badge badge-confirmed">{status}</span></td>
-          </tr>
-        </tbody>
-      </table>
-    </div>
-
-    <!-- Pricing -->
-    <div class="section">
-      <div class="section-title">Rechnungsbetrag</div>
-      <table>
-        <thead>
-          <tr>
-            <th>Position</th>
-            <th class="text-right">Betrag ({currency})</th>
-          </tr>
-        </thead>
-        <tbody>
-          <tr>
-            <td>Parkgebühr (Netto)</td>
-            <td class="text-right">{net_price:.2}</td>
-          </tr>
-        </tbody>
-        <tbody class="totals">
-          <tr>
-            <td>Zwischensumme (Netto)</td>
-            <td class="text-right">{net_price:.2}</td>
-          </tr>
-          <tr>
-            <td>{vat_label}</td>
-            <td class="text-right">{vat_amount:.2}</td>
-          </tr>
-          <tr class="total-row">
-            <td>Gesamtbetrag (Brutto)</td>
-            <td class="text-right">{gross_total:.2}</td>
-          </tr>
-        </tbody>
-      </table>
-      {reverse_charge_html}
-    </div>
-
-    <!-- Footer -->
-    <div class="footer">
-      <p>{company} · Parkverwaltungssystem · Automatisch generierte Rechnung</p>
-      <p>Diese Rechnung wurde automatisch erstellt und ist ohne Unterschrift gültig.</p>
-    </div>
-
-  </div>
-</body>
-</html>"#,
-        invoice_number = invoice_number,
-        invoice_date = invoice_date,
-        company = company,
-        user_name = user_name,
-        user_email = user_email,
-        booking_id = booking.id,
-        lot_name = lot_name,
-        slot_number = booking.slot_number,
-        floor_name = floor_name,
-        license_plate = license_plate,
-        start_str = start_str,
-        end_str = end_str,
-        duration_hours = duration_hours,
-        duration_mins_part = duration_mins_part,
-        status = format!("{:?}", booking.status),
-        currency = booking.pricing.currency,
-        net_price = net_price,
-        vat_amount = vat_amount,
-        vat_label = vat_label,
-        reverse_charge_html = reverse_charge_html,
-        gross_total = gross_total,
-    );
+    let today = Utc::now().date_naive();
+    let start_date = query.start_date.unwrap_or(today);
+    let vat_rate = super::tax::resolve_standard_rate(&state_guard).await;
+    let mut calendar =
+        build_lot_calendar(&lot, &slots, &bookings, start_date, days, today, vat_rate);
 
-    (
-        StatusCode::OK,
-        [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
-        html,
-    )
+    // Archived lots stay visible but take no new bookings
+    if super::lot_archive::is_archived(&state_guard.db, lot.id).await {
+        for day in &mut calendar.days {
+            day.bookable = false;
+        }
+    }
+
+    (StatusCode::OK, Json(ApiResponse::success(calendar)))
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1328,7 +1632,7 @@ pub async fn quick_book(
     State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
     Json(req): Json<QuickBookRequest>,
-) -> (StatusCode, Json<ApiResponse<Booking>>) {
+) -> (StatusCode, Json<ApiResponse<BookingWithCountdown>>) {
     let state_guard = state.write().await;
 
     // T-1731: resolve the caller's tenant_id up-front so the booking inherits
@@ -1346,18 +1650,44 @@ pub async fn quick_book(
             tracing::error!("Failed to list slots: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to list slots")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list slots",
+                )),
             );
         }
     };
 
-    let available_slot = match slots.iter().find(|s| s.status == SlotStatus::Available) {
+    let on_hold = match super::slot_holds::slots_held_by_others(
+        &state_guard.db,
+        req.lot_id,
+        auth_user.user_id,
+        Utc::now(),
+    )
+    .await
+    {
+        Ok(held) => held,
+        Err(e) => {
+            tracing::error!("Failed to read slot holds: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    let available_slot = match slots
+        .iter()
+        .find(|s| s.status == SlotStatus::Available && !on_hold.contains(&s.id))
+    {
         Some(s) => s.clone(),
         None => {
             return (
                 StatusCode::CONFLICT,
                 Json(ApiResponse::error(
-                    "NO_SLOTS_AVAILABLE",
+                    ErrorCode::NoSlotsAvailable,
                     "No available slots in this lot",
                 )),
             );
@@ -1424,22 +1754,47 @@ pub async fn quick_book(
         },
     );
 
-    let hourly_rate = lot_opt
-        .as_ref()
-        .and_then(|lot| lot.pricing.rates.iter().find(|r| r.duration_minutes == 60))
-        .map_or(2.0, |r| r.price);
-    let daily_max_gs = lot_opt.as_ref().and_then(|lot| lot.pricing.daily_max);
-    let lot_currency_gs = lot_opt
-        .as_ref()
-        .map_or_else(|| "EUR".to_string(), |lot| lot.pricing.currency.clone());
+    let duration_minutes = i32::try_from((end_time - start_time).num_minutes()).unwrap_or(i32::MAX);
+    let lot_rules =
+        super::lot_rules::load_lot_rules(&state_guard.db, &req.lot_id.to_string()).await;
+    if let Err(violation) = lot_rules.check_booking(start_time, duration_minutes, now) {
+        return super::lot_rules::violation_response(violation);
+    }
+
+    if let Ok(Some(user)) = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+    {
+        if lot_rules.permit_required
+            && !super::permits::has_booking_permit(
+                &state_guard.db,
+                &user,
+                req.lot_id,
+                &vehicle.license_plate,
+                start_time,
+                end_time,
+            )
+            .await
+        {
+            return super::permits::permit_required_response();
+        }
+        if let Err(response) = super::department_quotas::check_department_quota(
+            &state_guard.db,
+            &user,
+            start_time,
+            end_time,
+            None,
+        )
+        .await
+        {
+            return response;
+        }
+    }
 
-    #[allow(clippy::cast_precision_loss)]
-    let raw_price_gs = ((end_time - start_time).num_minutes() as f64 / 60.0) * hourly_rate;
-    let base_price = daily_max_gs.map_or(raw_price_gs, |cap| raw_price_gs.min(cap));
     // Seller-country VAT rate resolved under the held write lock.
     let vat_rate = super::tax::resolve_standard_rate(&state_guard).await;
-    let tax = base_price * vat_rate;
-    let total = base_price + tax;
+    let quote = price_booking(lot_opt.as_ref(), start_time, duration_minutes, vat_rate);
 
     let booking = Booking {
         id: Uuid::new_v4(),
@@ -1452,15 +1807,7 @@ pub async fn quick_book(
         start_time,
         end_time,
         status: BookingStatus::Confirmed,
-        pricing: BookingPricing {
-            base_price,
-            discount: 0.0,
-            tax,
-            total,
-            currency: lot_currency_gs,
-            payment_status: PaymentStatus::Pending,
-            payment_method: None,
-        },
+        pricing: quote.into_pricing(),
         created_at: now,
         updated_at: now,
         check_in_time: None,
@@ -1469,30 +1816,50 @@ pub async fn quick_book(
         notes: Some(format!("Quick book ({booking_type})")),
         // T-1731: propagate caller's tenant_id.
         tenant_id: caller_tenant_id.clone(),
+        tags: BTreeMap::new(),
+        booked_for: None,
     };
 
-    if let Err(e) = state_guard.db.save_booking(&booking).await {
-        tracing::error!("Failed to save quick booking: {}", e);
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(
-                "SERVER_ERROR",
-                "Failed to create booking",
-            )),
-        );
+    match state_guard.db.claim_slot(&booking).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return slot_unavailable_response(
+                &state_guard.db,
+                &available_slot,
+                booking.start_time,
+                booking.end_time,
+            )
+            .await;
+        }
+        Err(e) => {
+            tracing::error!("Failed to save quick booking: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to create booking",
+                )),
+            );
+        }
     }
 
     // Update slot status — fail the booking if slot update fails to prevent double-booking
     let mut updated_slot = available_slot;
     updated_slot.status = SlotStatus::Reserved;
-    if let Err(e) = state_guard.db.save_parking_slot(&updated_slot).await {
+    let change = SlotChangeContext::user(auth_user.user_id, "booking")
+        .reason(format!("Quick booking {}", booking.id));
+    if let Err(e) = state_guard
+        .db
+        .save_parking_slot_with(&updated_slot, &change)
+        .await
+    {
         tracing::error!("Failed to update slot status after quick booking: {}", e);
         // Roll back the booking to avoid inconsistent state
         let _ = state_guard.db.delete_booking(&booking.id.to_string()).await;
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SLOT_UPDATE_FAILED",
+                ErrorCode::SlotUpdateFailed,
                 "Failed to reserve slot",
             )),
         );
@@ -1505,19 +1872,174 @@ pub async fn quick_book(
         "Quick booking created"
     );
 
-    (StatusCode::CREATED, Json(ApiResponse::success(booking)))
+    (
+        StatusCode::CREATED,
+        Json(ApiResponse::success(with_countdown(&state_guard, booking))),
+    )
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // BOOKING CHECKIN
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Whether another booking is still checked in to `booking`'s slot.
+/// Database errors count as "free" so an outage never blocks check-in.
+async fn has_previous_occupant(db: &crate::db::Database, booking: &Booking) -> bool {
+    db.list_bookings().await.is_ok_and(|bookings| {
+        bookings.iter().any(|b| {
+            b.id != booking.id
+                && b.slot_id == booking.slot_id
+                && b.status == BookingStatus::Active
+                && b.check_out_time.is_none()
+        })
+    })
+}
+
+/// Whether any pending, confirmed or active booking other than `except`
+/// holds `slot_id` now or later. Errors count as held, so the slot is not
+/// freed by mistake.
+async fn has_upcoming_bookings(db: &crate::db::Database, slot_id: Uuid, except: Uuid) -> bool {
+    let Some(from_now) = TimeRange::new(Utc::now(), DateTime::<Utc>::MAX_UTC) else {
+        return true;
+    };
+    !db.list_slot_bookings_overlapping(&[slot_id], &from_now)
+        .await
+        .is_ok_and(|bookings| bookings.iter().all(|b| b.id == except))
+}
+
+/// Order `candidates` as alternatives to `slot`: available slots of the same
+/// type in the same lot, same floor first, then nearest slot number.
+fn rank_alternative_slots(
+    slot: &ParkingSlot,
+    candidates: Vec<ParkingSlot>,
+    limit: usize,
+) -> Vec<ParkingSlot> {
+    rank_slots(
+        slot,
+        candidates,
+        |s| s.status == SlotStatus::Available,
+        limit,
+    )
+}
+
+/// Order the `candidates` that are free as alternatives to `slot`: same
+/// type in the same lot, same floor first, then nearest slot number.
+fn rank_slots(
+    slot: &ParkingSlot,
+    candidates: Vec<ParkingSlot>,
+    is_free: impl Fn(&ParkingSlot) -> bool,
+    limit: usize,
+) -> Vec<ParkingSlot> {
+    let mut free: Vec<ParkingSlot> = candidates
+        .into_iter()
+        .filter(|s| {
+            s.id != slot.id
+                && s.lot_id == slot.lot_id
+                && s.slot_type == slot.slot_type
+                && is_free(s)
+        })
+        .collect();
+    free.sort_by_key(|s| {
+        (
+            s.floor_id != slot.floor_id,
+            (s.slot_number - slot.slot_number).abs(),
+            s.slot_number,
+        )
+    });
+    free.truncate(limit);
+    free
+}
+
+/// Up to `limit` slots that can stand in for `slot` for the whole of
+/// `[start, end)`: in service and without a booking overlapping it or the
+/// lot's buffer around it.
+async fn alternative_slots_during(
+    db: &crate::db::Database,
+    slot: &ParkingSlot,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    limit: usize,
+) -> Vec<ParkingSlot> {
+    let Some(range) = TimeRange::new(start, end) else {
+        return Vec::new();
+    };
+    let slots = match db.list_slots_by_lot(&slot.lot_id.to_string()).await {
+        Ok(slots) => slots,
+        Err(e) => {
+            tracing::warn!("Failed to list alternative slots: {}", e);
+            return Vec::new();
+        }
+    };
+    let slot_ids: Vec<Uuid> = slots.iter().map(|s| s.id).collect();
+    let range = range.padded(lot_buffer(db, &slot.lot_id.to_string()).await);
+    let taken: HashSet<Uuid> = match db.list_slot_bookings_overlapping(&slot_ids, &range).await {
+        Ok(bookings) => bookings.iter().map(|b| b.slot_id).collect(),
+        Err(e) => {
+            tracing::warn!("Failed to look up bookings of alternative slots: {}", e);
+            return Vec::new();
+        }
+    };
+    rank_slots(
+        slot,
+        slots,
+        |s| is_usable(s) && !taken.contains(&s.id),
+        limit,
+    )
+}
+
+/// Up to `limit` free slots that can stand in for `slot`.
+pub(crate) async fn alternative_slots(
+    db: &crate::db::Database,
+    slot: &ParkingSlot,
+    limit: usize,
+) -> Vec<ParkingSlot> {
+    match db.list_slots_by_lot(&slot.lot_id.to_string()).await {
+        Ok(slots) => rank_alternative_slots(slot, slots, limit),
+        Err(e) => {
+            tracing::warn!("Failed to list alternative slots: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Alternatives listed in a `SLOT_UNAVAILABLE` response.
+const MAX_SLOT_ALTERNATIVES: usize = 3;
+
+/// `409 SLOT_UNAVAILABLE` for booking `slot` for `[start, end)`, with slots
+/// of the same type free for that time in `error.details.alternatives` so
+/// clients can offer one without another round trip.
+async fn slot_unavailable_response<T>(
+    db: &crate::db::Database,
+    slot: &ParkingSlot,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> (StatusCode, Json<ApiResponse<T>>) {
+    let alternatives: Vec<SlotAlternative> =
+        alternative_slots_during(db, slot, start, end, MAX_SLOT_ALTERNATIVES)
+            .await
+            .iter()
+            .map(SlotAlternative::from)
+            .collect();
+    let mut response = ApiResponse::error(ErrorCode::SlotUnavailable, "This slot is not available");
+    if let Some(error) = response.error.as_mut() {
+        error.details = Some(serde_json::json!({ "alternatives": alternatives }));
+    }
+    (StatusCode::CONFLICT, Json(response))
+}
+
 /// `POST /api/v1/bookings/{id}/checkin` — mark booking as checked in
 #[utoipa::path(post, path = "/api/v1/bookings/{id}/checkin", tag = "Bookings",
     summary = "Check in to a booking",
-    description = "Marks a booking as checked-in.",
+    description = "Marks a booking as checked-in. Lots may open check-in only a set number of \
+        minutes before the start (`early_checkin_minutes`). If the previous booking is still \
+        checked in to the slot, the booking moves to a free slot of the same type. Late-binding \
+        bookings without a slot yet are assigned one.",
     security(("bearer_auth" = [])),
-    responses((status = 200, description = "Success"))
+    responses(
+        (status = 200, description = "Success"),
+        (status = 409, description = "Check-in window not open yet, or slot occupied with no \
+            alternative, or no slot free to assign"),
+    )
 )]
 pub async fn booking_checkin(
     State(state): State<SharedState>,
@@ -1531,23 +2053,28 @@ pub async fn booking_checkin(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Booking not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Booking not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
-    // Only booking owner or admin can check in
-    if booking.user_id != auth_user.user_id
-        && let Err((status, msg)) = check_admin(&state_guard, &auth_user).await
-    {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    // Only the booking owner or someone managing bookings in its lot can
+    // check in
+    if !may_manage_booking(&auth_user, &booking) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
+        );
     }
 
     // Only Confirmed or Pending bookings can be checked in
@@ -1555,31 +2082,143 @@ pub async fn booking_checkin(
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "INVALID_STATUS",
+                ErrorCode::InvalidStatus,
                 "Only confirmed or pending bookings can be checked in",
             )),
         );
     }
 
+    let now = Utc::now();
+    let rules =
+        super::lot_rules::load_lot_rules(&state_guard.db, &booking.lot_id.to_string()).await;
+    if let Err(violation) = rules.check_checkin(booking.start_time, now) {
+        return super::lot_rules::violation_response(violation);
+    }
+
+    // Late-binding booking checking in before the assignment job reached it.
+    if booking.slot_id.is_nil() {
+        match super::slot_assignment::assign_booking(&state_guard.db, booking.id).await {
+            Ok(SlotAssignment::Assigned(assigned)) => booking = *assigned,
+            Ok(_) => {
+                return (
+                    StatusCode::CONFLICT,
+                    Json(ApiResponse::error(
+                        ErrorCode::NoSlotAvailable,
+                        "No slot of the booked type is free right now",
+                    )),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to assign slot at check-in: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Internal server error",
+                    )),
+                );
+            }
+        }
+    }
+
+    // The previous driver has not left the slot yet: move this booking to a
+    // free slot of the same type rather than turning the arrival away.
+    let moved_from = if has_previous_occupant(&state_guard.db, &booking).await {
+        let current = match state_guard
+            .db
+            .get_parking_slot(&booking.slot_id.to_string())
+            .await
+        {
+            Ok(Some(slot)) => slot,
+            _ => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::error(ErrorCode::NotFound, "Slot not found")),
+                );
+            }
+        };
+        let Some(alternative) = alternative_slots(&state_guard.db, &current, 1)
+            .await
+            .into_iter()
+            .next()
+        else {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::SlotOccupied,
+                    "Your slot is still occupied and no other slot of the same type is free",
+                )),
+            );
+        };
+
+        if let Ok(Some(lot)) = state_guard
+            .db
+            .get_parking_lot(&booking.lot_id.to_string())
+            .await
+            && let Some(floor) = lot.floors.iter().find(|f| f.id == alternative.floor_id)
+        {
+            booking.floor_name.clone_from(&floor.name);
+        }
+        booking.slot_id = alternative.id;
+        booking.slot_number = alternative.slot_number;
+
+        let mut reserved = alternative;
+        reserved.status = SlotStatus::Reserved;
+        let change = SlotChangeContext::user(auth_user.user_id, "checkin")
+            .reason(format!("Booking {} moved from occupied slot", booking.id));
+        if let Err(e) = state_guard
+            .db
+            .save_parking_slot_with(&reserved, &change)
+            .await
+        {
+            tracing::warn!("Failed to reserve alternative slot {}: {}", reserved.id, e);
+        }
+        Some(current.slot_number)
+    } else {
+        None
+    };
+
     booking.status = BookingStatus::Active;
-    booking.check_in_time = Some(Utc::now());
-    booking.updated_at = Utc::now();
+    booking.check_in_time = Some(now);
+    booking.updated_at = now;
 
     if let Err(e) = state_guard.db.save_booking(&booking).await {
         tracing::error!("Failed to save booking checkin: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to check in booking",
             )),
         );
     }
 
+    if let Some(from) = moved_from {
+        let note = Notification {
+            id: Uuid::new_v4(),
+            user_id: booking.user_id,
+            notification_type: NotificationType::SystemMessage,
+            title: "Slot changed".to_string(),
+            message: format!(
+                "Slot {from} was still occupied, so you have been checked in to slot {} instead.",
+                booking.slot_number
+            ),
+            data: Some(serde_json::json!({"booking_id": booking.id})),
+            read: false,
+            created_at: now,
+        };
+        if let Err(e) = state_guard.db.save_notification(&note).await {
+            tracing::warn!("Failed to notify user {}: {}", booking.user_id, e);
+        }
+    }
+
     AuditEntry::new(AuditEventType::BookingUpdated)
         .user(auth_user.user_id, "")
         .resource("booking", &id)
-        .details(serde_json::json!({"action": "checkin"}))
+        .details(serde_json::json!({
+            "action": "checkin",
+            "moved_from_slot": moved_from,
+        }))
         .log();
 
     // T-1946: broadcast SSE fleet event AFTER DB commit. `.ok()` swallows
@@ -1602,17 +2241,88 @@ pub async fn booking_checkin(
 pub struct PatchBookingRequest {
     pub notes: Option<String>,
     pub start_time: Option<DateTime<Utc>>,
+    /// Later to extend the booking, earlier to shorten it
     pub end_time: Option<DateTime<Utc>>,
+    /// Book another of the booking user's vehicles
+    pub vehicle_id: Option<Uuid>,
+    /// Replace the plate on the booking, e.g. for a rental car
+    pub license_plate: Option<String>,
+    /// Replaces all tags; `{}` removes them
+    pub tags: Option<BTreeMap<String, String>>,
+}
+
+/// Check moving `booking` to `[start, end)` at `now`.
+///
+/// Only upcoming or running bookings can be moved, and a running booking
+/// keeps its start. Returns the status, error code and message to reply with.
+#[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+fn check_time_change(
+    booking: &Booking,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<(), (StatusCode, ErrorCode, &'static str)> {
+    if !matches!(
+        booking.status,
+        BookingStatus::Pending | BookingStatus::Confirmed | BookingStatus::Active
+    ) {
+        return Err((
+            StatusCode::CONFLICT,
+            ErrorCode::InvalidStatus,
+            "Only upcoming or active bookings can be rescheduled",
+        ));
+    }
+    if start != booking.start_time && (booking.start_time <= now || start <= now) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidBookingTime,
+            "The start can only be moved before the booking begins, and not into the past",
+        ));
+    }
+    if end <= start {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidInput,
+            "End time must be after start time",
+        ));
+    }
+    if end <= now {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidBookingTime,
+            "End time must be in the future",
+        ));
+    }
+    if (end - start).num_minutes() > i64::from(parkhub_common::MAX_BOOKING_MINUTES) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidInput,
+            "Duration must not exceed 14 days",
+        ));
+    }
+    Ok(())
 }
 
-/// `PATCH /api/v1/bookings/{id}` — update notes/times on an existing booking
+/// `PATCH /api/v1/bookings/{id}` — modify an existing booking
 #[utoipa::path(
     patch,
     path = "/api/v1/bookings/{id}",
     tag = "Bookings",
     summary = "Update a booking",
-    description = "Update notes and/or times on a booking. Only the booking owner or an admin may update.",
-    security(("bearer_auth" = []))
+    description = "Extend or shorten a booking, switch its vehicle, or update notes and tags. \
+        New times are checked like a new booking (lot rules, slot holds, department quota, \
+        other bookings of the slot) and the booking is repriced, charging or refunding the \
+        credit difference; a running booking can only change its end. Tags replace the existing ones \
+        and must match the configured booking tag sets. Only the booking owner or an admin may update.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Updated booking"),
+        (status = 400, description = "Invalid times, plate or tags"),
+        (status = 403, description = "Not your booking, or vehicle of another user"),
+        (status = 404, description = "Booking or vehicle not found"),
+        (status = 409, description = "Slot taken or held for the new times, or booking already ended"),
+        (status = 422, description = "Not enough credits or department quota for the new times"),
+    )
 )]
 #[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
 pub async fn update_booking(
@@ -1621,6 +2331,67 @@ pub async fn update_booking(
     Path(id): Path<String>,
     Json(req): Json<PatchBookingRequest>,
 ) -> (StatusCode, Json<ApiResponse<Booking>>) {
+    if let Some(ref notes) = req.notes
+        && notes.len() > 500
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "Notes must be at most 500 characters",
+            )),
+        );
+    }
+    if let Some(ref plate) = req.license_plate
+        && (plate.trim().is_empty() || plate.trim().len() > 20)
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "License plate must be 1-20 characters",
+            )),
+        );
+    }
+
+    // The booking's locks, as for a new booking: its slot, and its owner's
+    // credits and department quota, all of which a reschedule touches.
+    // Taken before the read lock, like `place_booking` does.
+    let lock_keys = {
+        let state_guard = state.read().await;
+        let booking = match state_guard.db.get_booking(&id).await {
+            Ok(Some(b)) => b,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::error(ErrorCode::NotFound, "Booking not found")),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Database error fetching booking: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Internal server error",
+                    )),
+                );
+            }
+        };
+        let department = state_guard
+            .db
+            .get_user(&booking.user_id.to_string())
+            .await
+            .ok()
+            .flatten()
+            .and_then(|owner| super::department_quotas::quota_department(&owner));
+        [
+            (!booking.slot_id.is_nil()).then(|| booking_locks::slot_key(booking.slot_id)),
+            Some(booking_locks::user_key(booking.user_id)),
+            department.map(|d| booking_locks::department_key(&d)),
+        ]
+    };
+    let locks = booking_locks::lock(lock_keys.into_iter().flatten()).await;
     let state_guard = state.read().await;
 
     let mut booking = match state_guard.db.get_booking(&id).await {
@@ -1628,19 +2399,22 @@ pub async fn update_booking(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Booking not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Booking not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error fetching booking: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
-    // Check ownership or admin
+    // Owner, or someone managing bookings in its lot
     let Ok(Some(caller)) = state_guard
         .db
         .get_user(&auth_user.user_id.to_string())
@@ -1648,53 +2422,361 @@ pub async fn update_booking(
     else {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     };
-    let is_admin = caller.role == UserRole::Admin || caller.role == UserRole::SuperAdmin;
-    if booking.user_id != auth_user.user_id && !is_admin {
+    if !may_manage_booking(&auth_user, &booking) {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     }
 
+    let mut changed = Vec::new();
     if let Some(notes) = req.notes {
         booking.notes = Some(notes);
+        changed.push("notes");
     }
-    if let Some(start_time) = req.start_time {
-        booking.start_time = start_time;
+    if let Some(tags) = req.tags {
+        let tag_sets = super::booking_tags::load_booking_tag_sets(&state_guard.db).await;
+        match super::booking_tags::check_booking_tags(&tag_sets, tags) {
+            Ok(tags) => booking.tags = tags,
+            Err(msg) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(ErrorCode::InvalidTags, msg)),
+                );
+            }
+        }
+        changed.push("tags");
+    }
+
+    // The vehicle has to belong to the booking's user, not to the caller, so
+    // an admin can switch it on someone else's booking.
+    if let Some(vehicle_id) = req.vehicle_id {
+        match state_guard.db.get_vehicle(&vehicle_id.to_string()).await {
+            Ok(Some(v)) if v.user_id == booking.user_id => booking.vehicle = v,
+            Ok(Some(_)) => {
+                return (
+                    StatusCode::FORBIDDEN,
+                    Json(ApiResponse::error(
+                        ErrorCode::Forbidden,
+                        "Vehicle does not belong to the booking's user",
+                    )),
+                );
+            }
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::error(ErrorCode::NotFound, "Vehicle not found")),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Database error fetching vehicle: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Internal server error",
+                    )),
+                );
+            }
+        }
+        changed.push("vehicle");
+    }
+    if let Some(plate) = req.license_plate {
+        booking.vehicle.license_plate = plate.trim().to_string();
+        changed.push("license_plate");
     }
-    if let Some(end_time) = req.end_time {
+
+    let start_time = req.start_time.unwrap_or(booking.start_time);
+    let end_time = req.end_time.unwrap_or(booking.end_time);
+    let rescheduled = start_time != booking.start_time || end_time != booking.end_time;
+    let old_total = booking.pricing.total;
+    let mut batch = WriteBatch::new();
+    if rescheduled {
+        let now = Utc::now();
+        if let Err((status, code, msg)) = check_time_change(&booking, start_time, end_time, now) {
+            return (status, Json(ApiResponse::error(code, msg)));
+        }
+        // Unassigned late-binding bookings hold capacity of a slot type
+        // rather than a slot; they are moved once the assignment ran.
+        if booking.slot_id.is_nil() {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::SlotNotAssigned,
+                    "This booking can be rescheduled once its slot has been assigned",
+                )),
+            );
+        }
+
+        let duration_minutes =
+            i32::try_from((end_time - start_time).num_minutes()).unwrap_or(i32::MAX);
+        let max_hours = LotConfig::resolve(&state_guard, &booking.lot_id.to_string())
+            .await
+            .max_booking_duration_hours;
+        if max_hours > 0.0 && f64::from(duration_minutes) / 60.0 > max_hours {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ErrorCode::DurationTooLong,
+                    format!("Maximum booking duration is {max_hours} hour(s)"),
+                )),
+            );
+        }
+
+        // Per-lot booking rules. A running booking keeps its start, so only
+        // its new length is checked.
+        let lot_rules =
+            super::lot_rules::load_lot_rules(&state_guard.db, &booking.lot_id.to_string()).await;
+        let rule_check = if start_time == booking.start_time {
+            lot_rules.check_duration(duration_minutes)
+        } else {
+            lot_rules.check_booking(start_time, duration_minutes, now)
+        };
+        if let Err(violation) = rule_check {
+            return super::lot_rules::violation_response(violation);
+        }
+
+        let lot = state_guard
+            .db
+            .get_parking_lot(&booking.lot_id.to_string())
+            .await
+            .ok()
+            .flatten();
+        #[cfg(feature = "mod-operating-hours")]
+        if let Some(ref lot) = lot
+            && let Some(msg) = super::operating_hours::validate_booking_hours(
+                &lot.operating_hours,
+                &start_time,
+                &end_time,
+            )
+        {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::OutsideOperatingHours, msg)),
+            );
+        }
+
+        // The slot may be held for someone else's waitlist offer or booking
+        // form, as when booking it anew.
+        let held = match TimeRange::new(start_time, end_time) {
+            Some(range) => {
+                super::noshow::slots_held_for_others(
+                    &state_guard,
+                    booking.lot_id,
+                    &range,
+                    booking.user_id,
+                )
+                .await
+            }
+            None => HashSet::new(),
+        };
+        let on_hold = match super::slot_holds::slots_held_by_others(
+            &state_guard.db,
+            booking.lot_id,
+            booking.user_id,
+            now,
+        )
+        .await
+        {
+            Ok(held) => held,
+            Err(e) => {
+                tracing::error!("Failed to read slot holds: {e}");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Internal server error",
+                    )),
+                );
+            }
+        };
+        if held.contains(&booking.slot_id) || on_hold.contains(&booking.slot_id) {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::SlotHeld,
+                    "This slot is held for another user at the new times",
+                )),
+            );
+        }
+
+        let Ok(Some(mut owner)) = state_guard.db.get_user(&booking.user_id.to_string()).await
+        else {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to load user",
+                )),
+            );
+        };
+        // Counted under the department's lock; the booking's old times do
+        // not count against its new ones.
+        if let Err(response) = super::department_quotas::check_department_quota(
+            &state_guard.db,
+            &owner,
+            start_time,
+            end_time,
+            Some(booking.id),
+        )
+        .await
+        {
+            return response;
+        }
+
+        // Reprice for the new times; the payment method and status stay.
+        let vat_rate = super::tax::resolve_standard_rate(&state_guard).await;
+        let pricing =
+            price_booking(lot.as_ref(), start_time, duration_minutes, vat_rate).into_pricing();
+        booking.pricing = BookingPricing {
+            payment_status: booking.pricing.payment_status.clone(),
+            payment_method: booking.pricing.payment_method.clone(),
+            cancellation_fee: booking.pricing.cancellation_fee,
+            ..pricing
+        };
+
+        // The owner pays or gets back the credits the new price differs by,
+        // in the same batch as the new times.
+        let credits_enabled = state_guard
+            .db
+            .get_setting("credits_enabled")
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default()
+            == "true";
+        if credits_enabled && !matches!(owner.role, UserRole::Admin | UserRole::SuperAdmin) {
+            let spent = match state_guard
+                .db
+                .list_credit_transactions_for_user(owner.id)
+                .await
+            {
+                Ok(transactions) => -transactions
+                    .iter()
+                    .filter(|tx| tx.booking_id == Some(booking.id))
+                    .map(|tx| tx.amount)
+                    .sum::<i32>(),
+                Err(e) => {
+                    tracing::error!("Failed to load credit transactions: {}", e);
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ApiResponse::error(
+                            ErrorCode::ServerError,
+                            "Internal server error",
+                        )),
+                    );
+                }
+            };
+            let difference = repriced_credits(spent, old_total, booking.pricing.total) - spent;
+            if difference > owner.credits_balance {
+                return (
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    Json(ApiResponse::error(
+                        ErrorCode::InsufficientCredits,
+                        "Not enough credits for the new booking times",
+                    )),
+                );
+            }
+            if difference != 0 {
+                owner.credits_balance -= difference;
+                batch
+                    .save_user(&owner)
+                    .save_credit_transaction(&CreditTransaction {
+                        id: Uuid::new_v4(),
+                        user_id: owner.id,
+                        booking_id: Some(booking.id),
+                        amount: -difference,
+                        transaction_type: if difference > 0 {
+                            CreditTransactionType::Deduction
+                        } else {
+                            CreditTransactionType::Refund
+                        },
+                        description: Some(format!("Rescheduled booking {}", booking.id)),
+                        granted_by: None,
+                        created_at: now,
+                    });
+            }
+        }
+
+        booking.start_time = start_time;
         booking.end_time = end_time;
+        changed.push("times");
     }
     booking.updated_at = Utc::now();
 
-    if let Err(e) = state_guard.db.save_booking(&booking).await {
-        tracing::error!("Failed to update booking: {}", e);
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(
-                "SERVER_ERROR",
-                "Failed to update booking",
-            )),
-        );
+    // New times go through the slot claim, which re-checks the other
+    // bookings of the slot in the same storage transaction as the write
+    // and the credit change.
+    if rescheduled {
+        batch.claim_slot(&booking);
+    } else {
+        batch.save_booking(&booking);
+    }
+    match state_guard.db.apply_batch(&batch).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return match state_guard
+                .db
+                .get_parking_slot(&booking.slot_id.to_string())
+                .await
+            {
+                Ok(Some(slot)) => {
+                    slot_unavailable_response(&state_guard.db, &slot, start_time, end_time).await
+                }
+                _ => (
+                    StatusCode::CONFLICT,
+                    Json(ApiResponse::error(
+                        ErrorCode::SlotUnavailable,
+                        "This slot is not available",
+                    )),
+                ),
+            };
+        }
+        Err(e) => {
+            tracing::error!("Failed to update booking: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to update booking",
+                )),
+            );
+        }
     }
+    drop(locks);
 
     AuditEntry::new(AuditEventType::BookingUpdated)
         .user(auth_user.user_id, &caller.username)
         .resource("booking", &id)
-        .details(serde_json::json!({"action": "patch"}))
+        .details(serde_json::json!({
+            "action": "patch",
+            "changed": changed,
+            "old_total": old_total,
+            "new_total": booking.pricing.total,
+        }))
         .log();
 
     (StatusCode::OK, Json(ApiResponse::success(booking)))
 }
 
+/// Credits a booking that has cost `spent` credits at `old_total` costs at
+/// `new_total`: its credits follow its price. Free bookings stay free.
+fn repriced_credits(spent: i32, old_total: f64, new_total: f64) -> i32 {
+    if spent <= 0 || old_total <= 0.0 {
+        return spent;
+    }
+    (f64::from(spent) * new_total / old_total).round() as i32
+}
+
 #[cfg(test)]
 mod tests {
+    use chrono::{DateTime, TimeZone, Utc};
     use parkhub_common::{
-        Booking, BookingPricing, BookingStatus, FuelType, GuestBooking, PaymentStatus, Vehicle,
-        VehicleType,
+        Booking, BookingPricing, BookingStatus, FuelType, GuestBooking, ParkingLot, ParkingSlot,
+        PaymentStatus, SlotPosition, SlotStatus, SlotType, Vehicle, VehicleType,
     };
     use uuid::Uuid;
 
@@ -1722,6 +2804,7 @@ mod tests {
             currency: "EUR".to_string(),
             payment_status: PaymentStatus::Pending,
             payment_method: None,
+            cancellation_fee: None,
         }
     }
 
@@ -1846,6 +2929,8 @@ mod tests {
             qr_code: Some("QR_DATA".to_string()),
             notes: None,
             tenant_id: None,
+            tags: std::collections::BTreeMap::new(),
+            booked_for: None,
         };
 
         let json = serde_json::to_string(&booking).unwrap();
@@ -1880,6 +2965,8 @@ mod tests {
             qr_code: None,
             notes: Some("late arrival".to_string()),
             tenant_id: None,
+            tags: std::collections::BTreeMap::new(),
+            booked_for: None,
         };
 
         let json = serde_json::to_string(&booking).unwrap();
@@ -1944,4 +3031,218 @@ mod tests {
         assert_eq!(back.vehicle_plate.as_deref(), Some("MUC-AB-123"));
         assert_eq!(back.status, BookingStatus::Pending);
     }
+
+    #[test]
+    fn test_price_booking_default_rate() {
+        let quote = super::price_booking(None, noon(), 90, 0.19);
+        assert!((quote.hourly_rate - 2.0).abs() < 1e-9);
+        assert!((quote.base_price - 3.0).abs() < 1e-9);
+        assert!((quote.net - 3.0).abs() < 1e-9);
+        assert!((quote.tax - 0.57).abs() < 1e-9);
+        assert!((quote.total - 3.57).abs() < 1e-9);
+        assert_eq!(quote.currency, "EUR");
+        assert!(!quote.daily_max_applied);
+        assert_eq!(quote.days.len(), 1);
+    }
+
+    #[test]
+    fn test_price_booking_into_pricing_matches_quote() {
+        let quote = super::price_booking(None, noon(), 60, 0.2);
+        let total = quote.total;
+        let pricing = quote.into_pricing();
+        assert!((pricing.total - total).abs() < 1e-9);
+        assert_eq!(pricing.payment_status, PaymentStatus::Pending);
+    }
+
+    #[test]
+    fn test_price_booking_applies_daily_max_per_day() {
+        let mut lot = lot_with_pricing(Some(15.0));
+        lot.pricing.rates[0].price = 2.0;
+        // Sat 18:00 → Mon 09:00: 6 h + 24 h + 9 h
+        let start = Utc.with_ymd_and_hms(2026, 5, 2, 18, 0, 0).unwrap();
+        let quote = super::price_booking(Some(&lot), start, 39 * 60, 0.0);
+
+        let prices: Vec<f64> = quote.days.iter().map(|d| d.price).collect();
+        assert_eq!(prices, vec![12.0, 15.0, 15.0]);
+        assert_eq!(quote.days[0].minutes, 6 * 60);
+        assert!(!quote.days[0].daily_max_applied);
+        assert!(quote.days[1].daily_max_applied);
+        assert!(quote.days[2].daily_max_applied);
+        assert!(quote.daily_max_applied);
+        assert!((quote.base_price - 42.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_price_booking_overnight_short_stay_is_not_capped() {
+        let lot = lot_with_pricing(Some(20.0));
+        // 22:00 → 02:00 crosses midnight but stays far below the cap
+        let start = Utc.with_ymd_and_hms(2026, 5, 2, 22, 0, 0).unwrap();
+        let quote = super::price_booking(Some(&lot), start, 4 * 60, 0.0);
+
+        assert_eq!(quote.days.len(), 2);
+        assert_eq!(quote.days[0].minutes, 120);
+        assert_eq!(quote.days[1].minutes, 120);
+        assert!(!quote.daily_max_applied);
+        assert!((quote.base_price - 12.0).abs() < 1e-9);
+    }
+
+    fn upcoming_booking(start: DateTime<Utc>, status: BookingStatus) -> Booking {
+        Booking {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            lot_id: Uuid::new_v4(),
+            slot_id: Uuid::new_v4(),
+            slot_number: 1,
+            floor_name: "Level 1".to_string(),
+            vehicle: make_vehicle(),
+            start_time: start,
+            end_time: start + chrono::Duration::hours(2),
+            status,
+            pricing: make_pricing(),
+            created_at: start,
+            updated_at: start,
+            check_in_time: None,
+            check_out_time: None,
+            qr_code: None,
+            notes: None,
+            tenant_id: None,
+            tags: std::collections::BTreeMap::new(),
+            booked_for: None,
+        }
+    }
+
+    #[test]
+    fn test_check_time_change_allows_extending_running_booking() {
+        let now = noon();
+        let booking = upcoming_booking(now - chrono::Duration::hours(1), BookingStatus::Active);
+        let later = booking.end_time + chrono::Duration::hours(1);
+        assert!(super::check_time_change(&booking, booking.start_time, later, now).is_ok());
+
+        // A running booking keeps its start
+        let err = super::check_time_change(&booking, now, later, now).unwrap_err();
+        assert_eq!(err.1, "INVALID_BOOKING_TIME");
+    }
+
+    #[test]
+    fn test_check_time_change_rejects_bad_ranges_and_ended_bookings() {
+        let now = noon();
+        let booking = upcoming_booking(now + chrono::Duration::hours(1), BookingStatus::Confirmed);
+        let start = booking.start_time;
+
+        let err = super::check_time_change(&booking, start, start, now).unwrap_err();
+        assert_eq!(err.1, "INVALID_INPUT");
+        let too_long = start + chrono::Duration::days(15);
+        let err = super::check_time_change(&booking, start, too_long, now).unwrap_err();
+        assert_eq!(err.1, "INVALID_INPUT");
+        let past = now - chrono::Duration::hours(1);
+        let err = super::check_time_change(&booking, past, booking.end_time, now).unwrap_err();
+        assert_eq!(err.1, "INVALID_BOOKING_TIME");
+
+        let done = upcoming_booking(now + chrono::Duration::hours(1), BookingStatus::Cancelled);
+        let err = super::check_time_change(&done, done.start_time, done.end_time, now).unwrap_err();
+        assert_eq!(err.0, axum::http::StatusCode::CONFLICT);
+    }
+
+    fn noon() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, 4, 12, 0, 0).unwrap()
+    }
+
+    fn lot_with_pricing(daily_max: Option<f64>) -> ParkingLot {
+        ParkingLot {
+            id: Uuid::new_v4(),
+            name: "Test Lot".to_string(),
+            address: "1 Test St".to_string(),
+            latitude: 0.0,
+            longitude: 0.0,
+            total_slots: 10,
+            available_slots: 10,
+            floors: vec![],
+            amenities: vec![],
+            pricing: parkhub_common::PricingInfo {
+                currency: "EUR".to_string(),
+                rates: vec![parkhub_common::PricingRate {
+                    duration_minutes: 60,
+                    price: 3.0,
+                    label: "1 hour".to_string(),
+                }],
+                daily_max,
+                monthly_pass: None,
+            },
+            operating_hours: parkhub_common::OperatingHours {
+                is_24h: true,
+                monday: None,
+                tuesday: None,
+                wednesday: None,
+                thursday: None,
+                friday: None,
+                saturday: None,
+                sunday: None,
+            },
+            images: vec![],
+            status: parkhub_common::LotStatus::Open,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            tenant_id: None,
+        }
+    }
+
+    fn make_slot(
+        lot_id: Uuid,
+        floor_id: Uuid,
+        slot_number: i32,
+        slot_type: SlotType,
+    ) -> ParkingSlot {
+        ParkingSlot {
+            id: Uuid::new_v4(),
+            lot_id,
+            floor_id,
+            slot_number,
+            row: 1,
+            column: slot_number,
+            slot_type,
+            status: SlotStatus::Available,
+            current_booking: None,
+            features: Vec::new(),
+            position: SlotPosition {
+                x: 0.0,
+                y: 0.0,
+                width: 2.5,
+                height: 5.0,
+                rotation: 0.0,
+            },
+            is_accessible: false,
+        }
+    }
+
+    #[test]
+    fn test_rank_alternative_slots_same_type_same_floor_nearest_first() {
+        let lot = Uuid::new_v4();
+        let floor = Uuid::new_v4();
+        let other_floor = Uuid::new_v4();
+        let wanted = make_slot(lot, floor, 12, SlotType::Standard);
+
+        let mut taken = make_slot(lot, floor, 13, SlotType::Standard);
+        taken.status = SlotStatus::Reserved;
+        let candidates = vec![
+            wanted.clone(),
+            taken,
+            make_slot(lot, floor, 11, SlotType::Electric),
+            make_slot(lot, other_floor, 12, SlotType::Standard),
+            make_slot(lot, floor, 17, SlotType::Standard),
+            make_slot(lot, floor, 14, SlotType::Standard),
+            make_slot(Uuid::new_v4(), floor, 12, SlotType::Standard),
+        ];
+
+        let ranked = super::rank_alternative_slots(&wanted, candidates.clone(), 5);
+        let numbers: Vec<(i32, bool)> = ranked
+            .iter()
+            .map(|s| (s.slot_number, s.floor_id == floor))
+            .collect();
+        assert_eq!(numbers, vec![(14, true), (17, true), (12, false)]);
+
+        assert_eq!(
+            super::rank_alternative_slots(&wanted, candidates, 1).len(),
+            1
+        );
+    }
 }
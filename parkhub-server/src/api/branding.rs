@@ -19,9 +19,9 @@ use axum::{
 use base64::Engine as _;
 use serde::{Deserialize, Serialize};
 
-use parkhub_common::{ApiResponse, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode};
 
-use super::{AuthUser, SharedState};
+use super::{AuthUser, SharedState, check_admin};
 
 const MAX_LOGO_BYTES: usize = 2 * 1024 * 1024; // 2 MB raw
 
@@ -85,18 +85,14 @@ pub async fn admin_get_branding(
     let state_guard = state.read().await;
 
     // Admin-only
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     let app_name = state_guard
@@ -160,18 +156,14 @@ pub async fn admin_update_branding(
     let state_guard = state.read().await;
 
     // Admin-only
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     if let Some(ref name) = req.app_name
@@ -181,7 +173,7 @@ pub async fn admin_update_branding(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to save branding",
             )),
         );
@@ -197,7 +189,7 @@ pub async fn admin_update_branding(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to save branding",
             )),
         );
@@ -265,18 +257,14 @@ pub async fn admin_upload_logo(
     let state_guard = state.read().await;
 
     // Admin-only
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     let b64 = strip_data_uri(&req.logo);
@@ -284,7 +272,10 @@ pub async fn admin_upload_logo(
     let Ok(raw_bytes) = base64::engine::general_purpose::STANDARD.decode(b64) else {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_INPUT", "Invalid base64 data")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "Invalid base64 data",
+            )),
         );
     };
 
@@ -292,7 +283,7 @@ pub async fn admin_upload_logo(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "PAYLOAD_TOO_LARGE",
+                ErrorCode::PayloadTooLarge,
                 "Logo exceeds 2 MB limit",
             )),
         );
@@ -302,7 +293,7 @@ pub async fn admin_upload_logo(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "Unsupported image format. Only JPEG and PNG are accepted.",
             )),
         );
@@ -316,7 +307,10 @@ pub async fn admin_upload_logo(
         tracing::error!("Failed to save branding logo: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save logo")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save logo",
+            )),
         );
     }
 
@@ -350,7 +344,10 @@ pub async fn get_branding_logo(State(state): State<SharedState>) -> Response {
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::<()>::error("NOT_FOUND", "No logo configured")),
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::NotFound,
+                    "No logo configured",
+                )),
             )
                 .into_response();
         }
@@ -364,7 +361,7 @@ pub async fn get_branding_logo(State(state): State<SharedState>) -> Response {
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::<()>::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Corrupt logo data",
             )),
         )
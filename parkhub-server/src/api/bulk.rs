@@ -0,0 +1,650 @@
+//! Bulk admin actions on users and bookings.
+//!
+//! - `POST /api/v1/admin/users/bulk` — deactivate, delete (anonymize) or change the role of users
+//! - `POST /api/v1/admin/bookings/bulk` — cancel bookings
+//!
+//! Every item is checked before anything is written. If any item fails, no
+//! change is made and the `422` response carries the per-item report, so the
+//! admin can fix the selection and retry. Otherwise all writes of the request
+//! commit in one storage transaction (see [`WriteBatch`]); follow-up effects
+//! such as ending sessions, audit entries and waitlist offers run afterwards.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+
+use axum::{Extension, Json, extract::State, http::StatusCode};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::{
+    ApiResponse, Booking, BookingStatus, CreditTransaction, CreditTransactionType, ErrorCode,
+    PaymentStatus, Permission, SlotStatus, TimeRange, User, UserRole,
+};
+
+use crate::AppState;
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{SlotChangeContext, WriteBatch};
+
+use super::lot_managers::admin_scope;
+use super::permissions::{CanManageUsers, RequirePermission};
+use super::settings::read_admin_setting;
+use super::{AuthUser, SharedState, server_error};
+
+/// Most items one bulk request may name.
+pub const MAX_BULK_ITEMS: usize = 500;
+
+/// Action of a bulk user request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkUserAction {
+    /// Disable the accounts and end their sessions
+    Deactivate,
+    /// Anonymize the accounts, as `DELETE /api/v1/admin/users/{id}`
+    Delete,
+    /// Set `role` on every account
+    ChangeRole,
+}
+
+/// `POST /api/v1/admin/users/bulk` body
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct BulkUserRequest {
+    /// User IDs, at most 500
+    pub ids: Vec<String>,
+    pub action: BulkUserAction,
+    /// New role; required for `change_role`
+    #[schema(value_type = Option<String>, example = "premium")]
+    pub role: Option<UserRole>,
+}
+
+/// Action of a bulk booking request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BulkBookingAction {
+    /// Cancel the bookings with a full refund and free their slots
+    Cancel,
+}
+
+/// `POST /api/v1/admin/bookings/bulk` body
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct BulkBookingRequest {
+    /// Booking IDs, at most 500
+    pub ids: Vec<String>,
+    pub action: BulkBookingAction,
+}
+
+/// Outcome of one item of a bulk request.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BulkItemResult {
+    pub id: String,
+    pub ok: bool,
+    /// Error code when the item was rejected
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl BulkItemResult {
+    fn ok(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            ok: true,
+            code: None,
+            message: None,
+        }
+    }
+
+    fn rejected(id: &str, code: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            ok: false,
+            code: Some(code.to_string()),
+            message: Some(message.into()),
+        }
+    }
+}
+
+/// Per-item report of a bulk request.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct BulkReport {
+    /// Whether the changes were written; `false` when any item was rejected
+    pub applied: bool,
+    pub total: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub results: Vec<BulkItemResult>,
+}
+
+impl BulkReport {
+    fn new(results: Vec<BulkItemResult>) -> Self {
+        let succeeded = results.iter().filter(|r| r.ok).count();
+        Self {
+            applied: false,
+            total: results.len(),
+            succeeded,
+            failed: results.len() - succeeded,
+            results,
+        }
+    }
+}
+
+/// `400` unless `ids` names between 1 and [`MAX_BULK_ITEMS`] items.
+#[allow(clippy::result_large_err)]
+fn check_item_count<T>(ids: &[String]) -> Result<(), (StatusCode, Json<ApiResponse<T>>)> {
+    if ids.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "ids must not be empty",
+            )),
+        ));
+    }
+    if ids.len() > MAX_BULK_ITEMS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::TooManyItems,
+                format!("At most {MAX_BULK_ITEMS} items per bulk request"),
+            )),
+        ));
+    }
+    Ok(())
+}
+
+/// `422` response naming the rejected items; nothing was written.
+fn rejected_response(report: BulkReport) -> (StatusCode, Json<ApiResponse<BulkReport>>) {
+    let mut response = ApiResponse::error(
+        ErrorCode::BulkRejected,
+        format!(
+            "{} of {} items cannot be processed; nothing was changed",
+            report.failed, report.total
+        ),
+    );
+    response.data = Some(report);
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(response))
+}
+
+fn applied_response(mut report: BulkReport) -> (StatusCode, Json<ApiResponse<BulkReport>>) {
+    report.applied = true;
+    (StatusCode::OK, Json(ApiResponse::success(report)))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Users
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Check `id` for a bulk user action by `caller` and stage its change.
+async fn stage_user(
+    state: &AppState,
+    caller: &User,
+    caller_tenant_id: Option<&str>,
+    req: &BulkUserRequest,
+    id: &str,
+    batch: &mut WriteBatch,
+) -> Result<User, BulkItemResult> {
+    let not_found = || BulkItemResult::rejected(id, "NOT_FOUND", "User not found");
+    let mut user = match state.db.get_user(id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return Err(not_found()),
+        Err(e) => {
+            tracing::error!("Failed to load user {id}: {e}");
+            return Err(BulkItemResult::rejected(
+                id,
+                "SERVER_ERROR",
+                "Internal server error",
+            ));
+        }
+    };
+    // T-1737: tenant-bound admins only reach users of their own tenant
+    if !super::matches_tenant(user.tenant_id.as_deref(), caller_tenant_id) {
+        return Err(not_found());
+    }
+    if user.id == caller.id {
+        return Err(BulkItemResult::rejected(
+            id,
+            "CANNOT_MODIFY_SELF",
+            "You cannot change your own account in a bulk action",
+        ));
+    }
+    if user.role == UserRole::SuperAdmin && caller.role != UserRole::SuperAdmin {
+        return Err(BulkItemResult::rejected(
+            id,
+            "FORBIDDEN",
+            "Only a SuperAdmin can change a SuperAdmin account",
+        ));
+    }
+
+    match req.action {
+        BulkUserAction::Deactivate => {
+            user.is_active = false;
+            user.updated_at = Utc::now();
+            batch.save_user(&user);
+        }
+        BulkUserAction::Delete => {
+            batch.anonymize_user(&user);
+        }
+        BulkUserAction::ChangeRole => {
+            user.role = req.role.clone().unwrap_or_default();
+            user.updated_at = Utc::now();
+            batch.save_user(&user);
+        }
+    }
+    Ok(user)
+}
+
+/// `POST /api/v1/admin/users/bulk` — deactivate, delete or re-role users at once
+#[utoipa::path(post, path = "/api/v1/admin/users/bulk", tag = "Admin",
+    summary = "Bulk user action (admin)",
+    description = "Deactivates, deletes (GDPR-anonymizes) or changes the role of up to 500 users. \
+        All items are checked first: if any is rejected nothing is changed and the 422 response \
+        lists the failing items. Otherwise all changes commit in one transaction.",
+    security(("bearer_auth" = [])),
+    request_body = BulkUserRequest,
+    responses(
+        (status = 200, description = "All items applied", body = BulkReport),
+        (status = 400, description = "No ids, too many ids, or change_role without role"),
+        (status = 403, description = "Forbidden"),
+        (status = 422, description = "Some items rejected; nothing changed", body = BulkReport),
+    )
+)]
+#[tracing::instrument(skip(state, req), fields(admin_id = %auth_user.user_id, action = ?req.action, items = req.ids.len()))]
+pub async fn bulk_users(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Json(req): Json<BulkUserRequest>,
+) -> (StatusCode, Json<ApiResponse<BulkReport>>) {
+    if let Err(response) = check_item_count(&req.ids) {
+        return response;
+    }
+    if req.action == BulkUserAction::ChangeRole && req.role.is_none() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::MissingRole,
+                "role is required for change_role",
+            )),
+        );
+    }
+
+    // Write lock: no other request may change the items between the
+    // checks and the commit.
+    let state_guard = state.write().await;
+    let Ok(Some(caller)) = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+    else {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
+        );
+    };
+    // Only SuperAdmin may promote users to SuperAdmin (prevent privilege escalation)
+    if req.role == Some(UserRole::SuperAdmin) && caller.role != UserRole::SuperAdmin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Only a SuperAdmin can assign the SuperAdmin role",
+            )),
+        );
+    }
+    let caller_tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
+
+    let mut batch = WriteBatch::new();
+    let mut results = Vec::with_capacity(req.ids.len());
+    let mut users = Vec::with_capacity(req.ids.len());
+    let mut seen = HashSet::new();
+    for id in &req.ids {
+        if !seen.insert(id.as_str()) {
+            results.push(BulkItemResult::rejected(
+                id,
+                "DUPLICATE_ID",
+                "Listed more than once",
+            ));
+            continue;
+        }
+        match stage_user(
+            &state_guard,
+            &caller,
+            caller_tenant_id.as_deref(),
+            &req,
+            id,
+            &mut batch,
+        )
+        .await
+        {
+            Ok(user) => {
+                results.push(BulkItemResult::ok(id));
+                users.push(user);
+            }
+            Err(result) => results.push(result),
+        }
+    }
+
+    let report = BulkReport::new(results);
+    if report.failed > 0 {
+        return rejected_response(report);
+    }
+    if let Err(e) = state_guard.db.apply_batch(&batch).await {
+        tracing::error!("Failed to apply bulk user action: {e}");
+        return server_error("Failed to update users");
+    }
+
+    let event_type = match req.action {
+        BulkUserAction::Deactivate => AuditEventType::UserDeactivated,
+        BulkUserAction::Delete => AuditEventType::UserDeleted,
+        BulkUserAction::ChangeRole => AuditEventType::RoleChanged,
+    };
+    for user in &users {
+        if req.action != BulkUserAction::ChangeRole
+            && let Err(e) = super::session_tokens::end_user_sessions(&state_guard, user.id).await
+        {
+            tracing::warn!("Failed to end sessions of user {}: {}", user.id, e);
+        }
+        AuditEntry::new(event_type.clone())
+            .user(auth_user.user_id, &caller.username)
+            .resource("user", &user.id.to_string())
+            .detail("bulk action")
+            .log();
+    }
+    tracing::info!(
+        admin_id = %auth_user.user_id,
+        action = ?req.action,
+        count = users.len(),
+        "Admin applied bulk user action"
+    );
+    applied_response(report)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Bookings
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Whether a booking other than `except` still holds `slot_id` from now on.
+async fn slot_still_booked(
+    db: &crate::db::Database,
+    slot_id: Uuid,
+    except: &HashSet<Uuid>,
+) -> bool {
+    let Some(from_now) = TimeRange::new(Utc::now(), DateTime::<Utc>::MAX_UTC) else {
+        return true;
+    };
+    !db.list_slot_bookings_overlapping(&[slot_id], &from_now)
+        .await
+        .is_ok_and(|bookings| bookings.iter().all(|b| except.contains(&b.id)))
+}
+
+/// Check booking `id` for cancellation by the caller.
+async fn check_booking(
+    state: &AppState,
+    auth_user: &AuthUser,
+    caller_tenant_id: Option<&str>,
+    id: &str,
+) -> Result<Booking, BulkItemResult> {
+    let not_found = || BulkItemResult::rejected(id, "NOT_FOUND", "Booking not found");
+    let booking = match state.db.get_booking(id).await {
+        Ok(Some(booking)) => booking,
+        Ok(None) => return Err(not_found()),
+        Err(e) => {
+            tracing::error!("Failed to load booking {id}: {e}");
+            return Err(BulkItemResult::rejected(
+                id,
+                "SERVER_ERROR",
+                "Internal server error",
+            ));
+        }
+    };
+    // Outside the caller's tenant or managed lots: as if it did not exist
+    if !super::matches_tenant(booking.tenant_id.as_deref(), caller_tenant_id)
+        || !auth_user
+            .permissions
+            .allows_lot(Permission::ManageBookings, booking.lot_id)
+    {
+        return Err(not_found());
+    }
+    match booking.status {
+        BookingStatus::Pending | BookingStatus::Confirmed | BookingStatus::Active => Ok(booking),
+        BookingStatus::Cancelled => Err(BulkItemResult::rejected(
+            id,
+            "ALREADY_CANCELLED",
+            "Booking is already cancelled",
+        )),
+        BookingStatus::Completed | BookingStatus::Expired | BookingStatus::NoShow => Err(
+            BulkItemResult::rejected(id, "NOT_CANCELLABLE", "Booking has already ended"),
+        ),
+    }
+}
+
+/// Stage the cancellation of `bookings` — full refunds, freed slots and
+/// credit refunds — into one batch.
+pub(super) async fn stage_cancellations(
+    state: &AppState,
+    admin_id: Uuid,
+    bookings: &[Booking],
+) -> anyhow::Result<WriteBatch> {
+    let now = Utc::now();
+    let mut batch = WriteBatch::new();
+    for booking in bookings {
+        let mut cancelled = booking.clone();
+        cancelled.status = BookingStatus::Cancelled;
+        cancelled.updated_at = now;
+        // Cancelled by the operator: no cancellation fee
+        if cancelled.pricing.payment_status == PaymentStatus::Paid {
+            cancelled.pricing.payment_status = PaymentStatus::Refunded;
+        }
+        batch.save_booking(&cancelled);
+    }
+
+    // Free each slot once no booking outside this request holds it.
+    let cancelled_ids: HashSet<Uuid> = bookings.iter().map(|b| b.id).collect();
+    let slot_ids: HashSet<Uuid> = bookings.iter().map(|b| b.slot_id).collect();
+    for slot_id in slot_ids {
+        if let Some(mut slot) = state.db.get_parking_slot(&slot_id.to_string()).await?
+            && slot.status == SlotStatus::Reserved
+            && !slot_still_booked(&state.db, slot_id, &cancelled_ids).await
+        {
+            slot.status = SlotStatus::Available;
+            let change = SlotChangeContext::user(admin_id, "cancellation")
+                .reason("Bookings cancelled in bulk");
+            batch.save_parking_slot(&slot, &change);
+        }
+    }
+
+    // Refund each owner's credits in one balance update.
+    if read_admin_setting(&state.db, "credits_enabled").await == "true" {
+        let credits_per_booking: i32 = read_admin_setting(&state.db, "credits_per_booking")
+            .await
+            .parse()
+            .unwrap_or(1);
+        let mut owners: HashMap<Uuid, User> = HashMap::new();
+        for booking in bookings {
+            let owner = match owners.entry(booking.user_id) {
+                Entry::Occupied(known) => known.into_mut(),
+                // Admins book without credits, so they get none back
+                Entry::Vacant(vacant) => {
+                    match state.db.get_user(&booking.user_id.to_string()).await? {
+                        Some(user)
+                            if user.role != UserRole::Admin
+                                && user.role != UserRole::SuperAdmin =>
+                        {
+                            vacant.insert(user)
+                        }
+                        _ => continue,
+                    }
+                }
+            };
+            owner.credits_balance += credits_per_booking;
+            batch.save_credit_transaction(&CreditTransaction {
+                id: Uuid::new_v4(),
+                user_id: booking.user_id,
+                booking_id: Some(booking.id),
+                amount: credits_per_booking,
+                transaction_type: CreditTransactionType::Refund,
+                description: Some(format!("Cancelled booking {}", booking.id)),
+                granted_by: Some(admin_id),
+                created_at: now,
+            });
+        }
+        for owner in owners.values() {
+            batch.save_user(owner);
+        }
+    }
+    Ok(batch)
+}
+
+/// `POST /api/v1/admin/bookings/bulk` — cancel bookings at once
+#[utoipa::path(post, path = "/api/v1/admin/bookings/bulk", tag = "Admin",
+    summary = "Bulk booking action (admin)",
+    description = "Cancels up to 500 pending, confirmed or active bookings with a full refund and \
+        frees their slots. Lot managers may cancel bookings in their lots. All items are checked \
+        first: if any is rejected nothing is changed and the 422 response lists the failing items. \
+        Otherwise all cancellations, slot changes and credit refunds commit in one transaction.",
+    security(("bearer_auth" = [])),
+    request_body = BulkBookingRequest,
+    responses(
+        (status = 200, description = "All items applied", body = BulkReport),
+        (status = 400, description = "No ids or too many ids"),
+        (status = 403, description = "Forbidden"),
+        (status = 422, description = "Some items rejected; nothing changed", body = BulkReport),
+    )
+)]
+#[tracing::instrument(skip(state, req), fields(admin_id = %auth_user.user_id, action = ?req.action, items = req.ids.len()))]
+pub async fn bulk_bookings(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<BulkBookingRequest>,
+) -> (StatusCode, Json<ApiResponse<BulkReport>>) {
+    if let Err((status, msg)) = admin_scope(&auth_user, Permission::ManageBookings) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+    if let Err(response) = check_item_count(&req.ids) {
+        return response;
+    }
+
+    // Write lock so no booking is created on a freed slot, or changed,
+    // between the checks and the commit — as in `cancel_booking`.
+    let state_guard = state.write().await;
+    let caller_tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
+
+    let mut results = Vec::with_capacity(req.ids.len());
+    let mut bookings = Vec::with_capacity(req.ids.len());
+    let mut seen = HashSet::new();
+    for id in &req.ids {
+        if !seen.insert(id.as_str()) {
+            results.push(BulkItemResult::rejected(
+                id,
+                "DUPLICATE_ID",
+                "Listed more than once",
+            ));
+            continue;
+        }
+        match check_booking(&state_guard, &auth_user, caller_tenant_id.as_deref(), id).await {
+            Ok(booking) => {
+                results.push(BulkItemResult::ok(id));
+                bookings.push(booking);
+            }
+            Err(result) => results.push(result),
+        }
+    }
+
+    let report = BulkReport::new(results);
+    if report.failed > 0 {
+        return rejected_response(report);
+    }
+    let applied = match stage_cancellations(&state_guard, auth_user.user_id, &bookings).await {
+        Ok(batch) => state_guard.db.apply_batch(&batch).await,
+        Err(e) => Err(e),
+    };
+    if let Err(e) = applied {
+        tracing::error!("Failed to apply bulk booking cancellation: {e}");
+        return server_error("Failed to cancel bookings");
+    }
+
+    let admin_username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    for booking in &bookings {
+        AuditEntry::new(AuditEventType::BookingCancelled)
+            .user(auth_user.user_id, &admin_username)
+            .resource("booking", &booking.id.to_string())
+            .detail("bulk action")
+            .log();
+        state_guard
+            .ws_events
+            .broadcast(crate::api::events::LiveEvent::booking_cancelled(
+                &booking.lot_id.to_string(),
+                &booking.slot_id.to_string(),
+            ));
+        // P1-2: each freed booking makes room for the next waitlist entry
+        let claim_window =
+            crate::api::noshow::lot_claim_window_minutes(&state_guard, &booking.lot_id.to_string())
+                .await;
+        crate::api::noshow::promote_next_waitlist_offer(&state_guard, booking.lot_id, claim_window)
+            .await;
+        #[cfg(feature = "mod-favorites")]
+        crate::api::favorites::notify_favorite_slot_freed(&state_guard, booking).await;
+    }
+    tracing::info!(
+        admin_id = %auth_user.user_id,
+        count = bookings.len(),
+        "Admin cancelled bookings in bulk"
+    );
+    applied_response(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bulk_user_request_deserializes_actions() {
+        let req: BulkUserRequest =
+            serde_json::from_str(r#"{"ids":["a","b"],"action":"change_role","role":"premium"}"#)
+                .unwrap();
+        assert_eq!(req.action, BulkUserAction::ChangeRole);
+        assert_eq!(req.role, Some(UserRole::Premium));
+
+        let req: BulkUserRequest =
+            serde_json::from_str(r#"{"ids":["a"],"action":"deactivate"}"#).unwrap();
+        assert_eq!(req.action, BulkUserAction::Deactivate);
+        assert!(req.role.is_none());
+
+        assert!(
+            serde_json::from_str::<BulkUserRequest>(r#"{"ids":["a"],"action":"explode"}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn report_counts_rejected_items() {
+        let report = BulkReport::new(vec![
+            BulkItemResult::ok("a"),
+            BulkItemResult::rejected("b", "NOT_FOUND", "User not found"),
+        ]);
+        assert!(!report.applied);
+        assert_eq!((report.total, report.succeeded, report.failed), (2, 1, 1));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json["results"][0].get("code").is_none());
+        assert_eq!(json["results"][1]["code"], "NOT_FOUND");
+    }
+
+    #[test]
+    fn item_count_is_bounded() {
+        assert!(check_item_count::<()>(&[]).is_err());
+        assert!(check_item_count::<()>(&["a".to_string()]).is_ok());
+        let too_many = vec!["a".to_string(); MAX_BULK_ITEMS + 1];
+        assert!(check_item_count::<()>(&too_many).is_err());
+    }
+}
@@ -18,7 +18,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, BookingStatus};
+use parkhub_common::{ApiResponse, BookingStatus, ErrorCode};
 
 use super::{AuthUser, SharedState};
 
@@ -120,7 +120,7 @@ pub async fn reschedule_booking(
     if let Err(msg) = check_time_range_valid(&req.new_start, &req.new_end) {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_INPUT", &msg)),
+            Json(ApiResponse::error(ErrorCode::InvalidInput, &msg)),
         );
     }
 
@@ -132,7 +132,7 @@ pub async fn reschedule_booking(
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Booking not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Booking not found")),
             );
         }
     };
@@ -141,7 +141,7 @@ pub async fn reschedule_booking(
     if booking.user_id != auth_user.user_id {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Not your booking")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Not your booking")),
         );
     }
 
@@ -150,7 +150,7 @@ pub async fn reschedule_booking(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_STATE",
+                ErrorCode::InvalidState,
                 "Cannot reschedule a cancelled booking",
             )),
         );
@@ -25,7 +25,7 @@ use axum::{
     extract::{Query, State},
 };
 use chrono::{DateTime, Duration, Utc};
-use parkhub_common::{ApiResponse, FuelType, VehicleType};
+use parkhub_common::{ApiResponse, ErrorCode, FuelType, VehicleType};
 use serde::{Deserialize, Serialize};
 
 use super::{AuthUser, SharedState};
@@ -103,7 +103,7 @@ pub async fn co2_summary(
     let user_id = auth_user.user_id;
     let bookings = match rg.db.list_bookings().await {
         Ok(b) => b,
-        Err(e) => return Json(ApiResponse::error("DB_ERROR", format!("db: {e}"))),
+        Err(e) => return Json(ApiResponse::error(ErrorCode::DbError, format!("db: {e}"))),
     };
 
     // Scope: per-user only for v1. Lot-wide admin scope can be layered on
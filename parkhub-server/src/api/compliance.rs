@@ -21,7 +21,7 @@ use printpdf::{
 };
 use serde::{Deserialize, Serialize};
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::SharedState;
 
@@ -623,7 +623,7 @@ pub async fn compliance_report_pdf(State(_state): State<SharedState>) -> impl In
         Err(e) => (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::<()>::error(
-                "PDF_ERROR",
+                ErrorCode::PdfError,
                 format!("Failed to generate compliance PDF: {e}"),
             )),
         )
@@ -9,7 +9,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, CreditTransaction, CreditTransactionType, UserRole};
+use parkhub_common::{ApiResponse, CreditTransaction, CreditTransactionType, ErrorCode, UserRole};
 
 use super::{AuthUser, SharedState, admin::AdminUserResponse, check_admin};
 use crate::audit::{AuditEntry, AuditEventType};
@@ -64,7 +64,7 @@ pub async fn get_user_credits(
     else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     };
 
@@ -114,8 +114,8 @@ pub async fn admin_grant_credits(
 ) -> (StatusCode, Json<ApiResponse<()>>) {
     let state_guard = state.read().await;
 
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let mut target_user = match state_guard.db.get_user(&user_id).await {
@@ -123,14 +123,17 @@ pub async fn admin_grant_credits(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -139,7 +142,7 @@ pub async fn admin_grant_credits(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "Amount must be between 1 and 10000",
             )),
         );
@@ -151,7 +154,7 @@ pub async fn admin_grant_credits(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update credits",
             )),
         );
@@ -398,8 +401,8 @@ pub async fn admin_refill_all_credits(
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
 
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let users = match state_guard.db.list_users().await {
@@ -408,7 +411,10 @@ pub async fn admin_refill_all_credits(
             tracing::error!("Failed to list users: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to list users")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list users",
+                )),
             );
         }
     };
@@ -482,7 +488,7 @@ pub async fn admin_update_user_quota(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 format!("Invalid quota: {e}"),
             )),
         );
@@ -490,8 +496,8 @@ pub async fn admin_update_user_quota(
 
     let state_guard = state.read().await;
 
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let mut target_user = match state_guard.db.get_user(&user_id).await {
@@ -499,14 +505,17 @@ pub async fn admin_update_user_quota(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -519,7 +528,10 @@ pub async fn admin_update_user_quota(
         tracing::error!("Failed to save user quota: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to update quota")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to update quota",
+            )),
         );
     }
 
@@ -587,10 +599,10 @@ pub async fn admin_list_credit_transactions(
 ) -> (StatusCode, Json<ApiResponse<Vec<CreditTransaction>>>) {
     let state_guard = state.read().await;
 
-    if let Err(resp) = check_admin(&state_guard, &auth_user).await {
+    if let Err(resp) = check_admin(&auth_user) {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", resp.1)),
+            Json(ApiResponse::error(ErrorCode::Forbidden, resp.1)),
         );
     }
 
@@ -610,7 +622,7 @@ pub async fn admin_list_credit_transactions(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list transactions",
                 )),
             )
@@ -23,10 +23,13 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 
 use parkhub_common::{
-    ApiResponse, DayHours, LotStatus, OperatingHours, ParkingFloor, ParkingLot, ParkingSlot,
-    PricingInfo, PricingRate, SlotPosition, SlotStatus, SlotType, User, UserPreferences, UserRole,
+    ApiResponse, DayHours, ErrorCode, LotStatus, OperatingHours, ParkingFloor, ParkingLot,
+    ParkingSlot, PricingInfo, PricingRate, SlotPosition, SlotStatus, SlotType, User,
+    UserPreferences, UserRole,
 };
 
+use super::permissions::{CanViewAllBookings, RequirePermission};
+use super::plate_display::PlateViewer;
 use super::{AuthUser, check_admin};
 use crate::AppState;
 
@@ -153,6 +156,7 @@ fn parse_role(s: &str) -> UserRole {
         "admin" => UserRole::Admin,
         "superadmin" | "super_admin" => UserRole::SuperAdmin,
         "premium" => UserRole::Premium,
+        "reporter" => UserRole::Reporter,
         _ => UserRole::User,
     }
 }
@@ -178,8 +182,8 @@ pub async fn import_users(
     Json(req): Json<ImportRequest>,
 ) -> (StatusCode, Json<ApiResponse<DataImportResult>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     // T-1731: imported users inherit the caller's tenant_id.
@@ -191,7 +195,7 @@ pub async fn import_users(
             Err(e) => {
                 return (
                     StatusCode::BAD_REQUEST,
-                    Json(ApiResponse::error("INVALID_JSON", e.to_string())),
+                    Json(ApiResponse::error(ErrorCode::InvalidJson, e.to_string())),
                 );
             }
         }
@@ -225,7 +229,7 @@ pub async fn import_users(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "TOO_MANY_ROWS",
+                ErrorCode::TooManyRows,
                 format!("Maximum {MAX_IMPORT_ROWS} rows per import"),
             )),
         );
@@ -320,6 +324,8 @@ pub async fn import_users(
             cost_center: None,
             department: None,
             settings: None,
+            // Imported with an admin-chosen or the default password
+            must_change_password: true,
         };
 
         match state_guard.db.save_user(&user).await {
@@ -356,8 +362,8 @@ pub async fn import_lots(
     Json(req): Json<ImportRequest>,
 ) -> (StatusCode, Json<ApiResponse<DataImportResult>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     // T-1731: imported lots inherit the caller's tenant_id.
@@ -369,7 +375,7 @@ pub async fn import_lots(
             Err(e) => {
                 return (
                     StatusCode::BAD_REQUEST,
-                    Json(ApiResponse::error("INVALID_JSON", e.to_string())),
+                    Json(ApiResponse::error(ErrorCode::InvalidJson, e.to_string())),
                 );
             }
         }
@@ -403,7 +409,7 @@ pub async fn import_lots(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "TOO_MANY_ROWS",
+                ErrorCode::TooManyRows,
                 format!("Maximum {MAX_IMPORT_ROWS} rows per import"),
             )),
         );
@@ -526,22 +532,18 @@ pub async fn import_lots(
 /// `GET /api/v1/admin/data/export/lots` — export lots with stats as CSV
 #[utoipa::path(get, path = "/api/v1/admin/data/export/lots", tag = "Admin",
     summary = "Export lots as CSV",
-    description = "Download all parking lots with stats as CSV. Admin only.",
+    description = "Download all parking lots with stats as CSV. Admins and reporters.",
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "CSV file", content_type = "text/csv"),
-        (status = 403, description = "Admin access required"),
+        (status = 403, description = "Insufficient permissions"),
     )
 )]
 pub async fn export_lots_csv(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanViewAllBookings>,
 ) -> impl IntoResponse {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return error_response(status, msg);
-    }
-
     let lots = match state_guard.db.list_parking_lots().await {
         Ok(l) => l,
         Err(e) => {
@@ -586,23 +588,19 @@ pub async fn export_lots_csv(
 /// `GET /api/v1/admin/data/export/bookings` — export bookings as CSV (date range)
 #[utoipa::path(get, path = "/api/v1/admin/data/export/bookings", tag = "Admin",
     summary = "Export bookings as CSV (enhanced)",
-    description = "Download bookings as CSV with date range filter. Admin only.",
+    description = "Download bookings as CSV with date range filter, with one `tag:<key>` column per booking tag. Admins and reporters.",
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "CSV file", content_type = "text/csv"),
-        (status = 403, description = "Admin access required"),
+        (status = 403, description = "Insufficient permissions"),
     )
 )]
 pub async fn export_bookings_csv(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanViewAllBookings>,
     Query(params): Query<ExportParams>,
 ) -> impl IntoResponse {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return error_response(status, msg);
-    }
-
     let bookings = match state_guard.db.list_bookings().await {
         Ok(b) => b,
         Err(e) => {
@@ -614,28 +612,34 @@ pub async fn export_bookings_csv(
         }
     };
 
+    let bookings: Vec<_> = bookings
+        .into_iter()
+        .filter(|b| {
+            params
+                .from
+                .is_none_or(|from| b.start_time.date_naive() >= from)
+                && params.to.is_none_or(|to| b.start_time.date_naive() <= to)
+        })
+        .collect();
+    let tag_sets = super::booking_tags::load_booking_tag_sets(&state_guard.db).await;
+    let tag_columns = super::booking_tags::tag_columns(&tag_sets, &bookings);
+
+    let viewer = PlateViewer::load(&state_guard, auth_user.user_id).await;
     let mut csv = String::from(
-        "id,user_id,lot_id,slot_number,start_time,end_time,status,vehicle_plate,total,currency,payment_status\n",
+        "id,user_id,lot_id,slot_number,start_time,end_time,status,vehicle_plate,total,currency,payment_status",
     );
+    for key in &tag_columns {
+        let _ = write!(csv, ",{}", csv_escape(&format!("tag:{key}")));
+    }
+    csv.push('\n');
 
     for b in &bookings {
-        if let Some(from) = params.from
-            && b.start_time.date_naive() < from
-        {
-            continue;
-        }
-        if let Some(to) = params.to
-            && b.start_time.date_naive() > to
-        {
-            continue;
-        }
-
         let lot_name = match state_guard.db.get_parking_lot(&b.lot_id.to_string()).await {
             Ok(Some(l)) => l.name,
             _ => b.lot_id.to_string(),
         };
 
-        let _ = writeln!(
+        let _ = write!(
             csv,
             "{},{},{},{},{},{},{},{},{:.2},{},{}",
             b.id,
@@ -645,11 +649,19 @@ pub async fn export_bookings_csv(
             b.start_time.to_rfc3339(),
             b.end_time.to_rfc3339(),
             csv_escape(&format!("{:?}", b.status).to_lowercase()),
-            csv_escape(&b.vehicle.license_plate),
+            csv_escape(&viewer.plate(b.user_id, &b.vehicle.license_plate)),
             b.pricing.total,
             csv_escape(&b.pricing.currency),
             csv_escape(&format!("{:?}", b.pricing.payment_status).to_lowercase()),
         );
+        for key in &tag_columns {
+            let _ = write!(
+                csv,
+                ",{}",
+                csv_escape(b.tags.get(key).map_or("", String::as_str))
+            );
+        }
+        csv.push('\n');
     }
     drop(state_guard);
 
@@ -675,7 +687,7 @@ pub async fn export_users_csv(
     Extension(auth_user): Extension<AuthUser>,
 ) -> impl IntoResponse {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
+    if let Err((status, msg)) = check_admin(&auth_user) {
         return error_response(status, msg);
     }
 
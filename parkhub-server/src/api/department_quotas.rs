@@ -0,0 +1,540 @@
+//! Per-department daily slot quotas and utilization reporting.
+//!
+//! - `GET /api/v1/admin/department-quotas` — configured quotas
+//! - `PUT /api/v1/admin/department-quotas` — replace all quotas
+//! - `GET /api/v1/admin/reports/department-utilization` — bookings against
+//!   quota per department over a date range, for chargeback
+//!
+//! A quota caps how many bookings members of a department (`User::department`)
+//! may hold on one calendar day (UTC), across all lots. Overnight and
+//! multi-day bookings take a slot on every day they touch. It is enforced in
+//! `create_booking` and `quick_book`; departments without a quota are
+//! unrestricted. Department names are matched case-insensitively, ignoring
+//! surrounding whitespace.
+//!
+//! # Settings keys
+//! - `department_quotas` — `Vec<DepartmentQuota>` as JSON. Missing or
+//!   unreadable means "no quotas".
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::{BTreeMap, HashSet};
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, Booking, BookingStatus, ErrorCode, TimeRange, User};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::Database;
+
+use super::permissions::{CanViewAllBookings, RequirePermission};
+use super::{AuthUser, SharedState, check_admin};
+
+/// Settings key holding all department quotas.
+const QUOTAS_KEY: &str = "department_quotas";
+
+/// Report row for users without a department.
+const UNASSIGNED: &str = "Unassigned";
+
+/// Longest date range the utilization report accepts, in days.
+const MAX_REPORT_DAYS: i64 = 366;
+
+/// Daily slot allocation for one department.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DepartmentQuota {
+    /// Department name as set on users
+    pub department: String,
+    /// Bookings the department's members may start per day; `0` blocks
+    /// booking entirely
+    pub slots_per_day: u32,
+}
+
+/// Case- and whitespace-insensitive department key.
+fn normalize(department: &str) -> String {
+    department.trim().to_lowercase()
+}
+
+/// The user's department, if set to something non-blank.
+fn department_of(user: &User) -> Option<&str> {
+    user.department
+        .as_deref()
+        .map(str::trim)
+        .filter(|d| !d.is_empty())
+}
+
+/// The department whose quota `user`'s bookings count against, normalized;
+/// bookings of its members take its lock in `booking_locks`.
+#[cfg_attr(
+    not(any(feature = "mod-bookings", feature = "mod-guest")),
+    allow(dead_code)
+)]
+#[must_use]
+pub fn quota_department(user: &User) -> Option<String> {
+    department_of(user).map(normalize)
+}
+
+/// Whether a booking uses up part of its department's allocation.
+/// Cancelled and expired bookings never held a slot.
+const fn counts_toward_quota(status: &BookingStatus) -> bool {
+    !matches!(status, BookingStatus::Cancelled | BookingStatus::Expired)
+}
+
+/// Reject blank names, duplicates and absurd limits.
+fn validate_quotas(quotas: &[DepartmentQuota]) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for quota in quotas {
+        let key = normalize(&quota.department);
+        if key.is_empty() {
+            return Err("Department name must not be empty".to_string());
+        }
+        if quota.department.len() > 100 {
+            return Err("Department name must be at most 100 characters".to_string());
+        }
+        if quota.slots_per_day > 10_000 {
+            return Err("slots_per_day must be at most 10000".to_string());
+        }
+        if !seen.insert(key) {
+            return Err(format!(
+                "Duplicate quota for department '{}'",
+                quota.department.trim()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Load all department quotas; empty when unset.
+pub async fn load_department_quotas(db: &Database) -> Vec<DepartmentQuota> {
+    db.get_setting(QUOTAS_KEY)
+        .await
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Check that `user` may book `[start, end)` under their department's quota.
+/// An overnight or multi-day booking takes one slot on every day it touches,
+/// so each of those days must have room. Call holding the department's
+/// booking lock (see [`quota_department`]) so concurrent bookings from the
+/// same department cannot both take the last slot.
+///
+/// A booking being rescheduled is passed as `replacing`, so its old times
+/// do not count against its new ones.
+///
+/// Database errors are logged and let the booking through, like the per-user
+/// daily limit.
+pub async fn check_department_quota<T>(
+    db: &Database,
+    user: &User,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    replacing: Option<Uuid>,
+) -> Result<(), (StatusCode, Json<ApiResponse<T>>)> {
+    let Some(department) = department_of(user) else {
+        return Ok(());
+    };
+    let key = normalize(department);
+    let Some(quota) = load_department_quotas(db)
+        .await
+        .into_iter()
+        .find(|q| normalize(&q.department) == key)
+    else {
+        return Ok(());
+    };
+
+    let (users, bookings) = match (db.list_users().await, db.list_bookings().await) {
+        (Ok(users), Ok(bookings)) => (users, bookings),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::warn!(error = %e, department, "Failed to count department bookings");
+            return Ok(());
+        }
+    };
+    let members: HashSet<Uuid> = users
+        .iter()
+        .filter(|u| department_of(u).is_some_and(|d| normalize(d) == key))
+        .map(|u| u.id)
+        .collect();
+    let member_ranges: Vec<TimeRange> = bookings
+        .iter()
+        .filter(|b| {
+            members.contains(&b.user_id)
+                && counts_toward_quota(&b.status)
+                && Some(b.id) != replacing
+        })
+        .filter_map(|b| TimeRange::new(b.start_time, b.end_time))
+        .collect();
+
+    let days = TimeRange::new(start, end).map_or_else(Vec::new, |range| range.split_by_day());
+    for (day, _) in days {
+        let used = member_ranges.iter().filter(|r| r.touches_day(day)).count();
+        if used >= quota.slots_per_day as usize {
+            return Err((
+                StatusCode::UNPROCESSABLE_ENTITY,
+                Json(ApiResponse::error(
+                    ErrorCode::DepartmentQuotaReached,
+                    format!(
+                        "Department '{}' has used all {} slot(s) for {day}",
+                        quota.department.trim(),
+                        quota.slots_per_day
+                    ),
+                )),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// `GET /api/v1/admin/department-quotas` — list department quotas
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/department-quotas",
+    tag = "Admin",
+    summary = "List department quotas",
+    description = "Returns the daily slot quota of every department that has one. \
+        Departments without a quota are unrestricted. Admin only.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Department quotas", body = Vec<DepartmentQuota>),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn get_department_quotas(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<Vec<DepartmentQuota>>>) {
+    let state_guard = state.read().await;
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let quotas = load_department_quotas(&state_guard.db).await;
+    (StatusCode::OK, Json(ApiResponse::success(quotas)))
+}
+
+/// `PUT /api/v1/admin/department-quotas` — replace department quotas
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/department-quotas",
+    tag = "Admin",
+    summary = "Update department quotas",
+    description = "Replaces all department quotas. Departments left out become unrestricted. \
+        Admin only.",
+    request_body = Vec<DepartmentQuota>,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Updated quotas", body = Vec<DepartmentQuota>),
+        (status = 400, description = "Blank or duplicate department"),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn update_department_quotas(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(quotas): Json<Vec<DepartmentQuota>>,
+) -> (StatusCode, Json<ApiResponse<Vec<DepartmentQuota>>>) {
+    let state_guard = state.read().await;
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    if let Err(msg) = validate_quotas(&quotas) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+        );
+    }
+
+    let quotas: Vec<DepartmentQuota> = quotas
+        .into_iter()
+        .map(|q| DepartmentQuota {
+            department: q.department.trim().to_string(),
+            slots_per_day: q.slots_per_day,
+        })
+        .collect();
+
+    let raw = match serde_json::to_string(&quotas) {
+        Ok(raw) => raw,
+        Err(e) => {
+            tracing::error!("Failed to serialize department quotas: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    if let Err(e) = state_guard.db.set_setting(QUOTAS_KEY, &raw).await {
+        tracing::error!("Failed to save department quotas: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save quotas",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::SettingsChanged)
+        .user(auth_user.user_id, "")
+        .resource("settings", QUOTAS_KEY)
+        .detail(&format!(
+            "Department quotas updated ({} departments)",
+            quotas.len()
+        ))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(quotas)))
+}
+
+/// Query parameters for the utilization report.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct DepartmentUtilizationQuery {
+    /// First day, inclusive (`YYYY-MM-DD`); defaults to 29 days before `end_date`
+    pub start_date: Option<NaiveDate>,
+    /// Last day, inclusive (`YYYY-MM-DD`); defaults to today
+    pub end_date: Option<NaiveDate>,
+}
+
+/// Bookings against quota for one department.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct DepartmentUtilization {
+    pub department: String,
+    pub user_count: usize,
+    /// Configured daily quota; `None` when the department is unrestricted
+    pub slots_per_day: Option<u32>,
+    /// `slots_per_day` times the days in the range
+    pub allocated_slots: Option<u64>,
+    /// Booking-days in the range; a multi-day booking counts once per day
+    /// (cancelled and expired excluded)
+    pub used_slots: usize,
+    /// `used_slots` as a share of `allocated_slots`
+    pub utilization_percent: Option<f64>,
+    pub booked_hours: f64,
+    pub total_amount: f64,
+    pub currency: String,
+}
+
+/// Department utilization over a date range.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct DepartmentUtilizationReport {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub days: i64,
+    pub departments: Vec<DepartmentUtilization>,
+}
+
+/// Aggregate bookings per department. Every department with a quota or at
+/// least one member gets a row; users without a department are reported as
+/// [`UNASSIGNED`].
+fn build_report(
+    quotas: &[DepartmentQuota],
+    users: &[User],
+    bookings: &[Booking],
+    start_date: NaiveDate,
+    end_date: NaiveDate,
+) -> DepartmentUtilizationReport {
+    let days = (end_date - start_date).num_days() + 1;
+    let mut rows: BTreeMap<String, DepartmentUtilization> = BTreeMap::new();
+    let blank_row = |department: &str, slots_per_day: Option<u32>| DepartmentUtilization {
+        department: department.to_string(),
+        user_count: 0,
+        slots_per_day,
+        allocated_slots: slots_per_day.map(|q| u64::from(q) * days.unsigned_abs()),
+        used_slots: 0,
+        utilization_percent: None,
+        booked_hours: 0.0,
+        total_amount: 0.0,
+        currency: "EUR".to_string(),
+    };
+
+    for quota in quotas {
+        rows.insert(
+            normalize(&quota.department),
+            blank_row(quota.department.trim(), Some(quota.slots_per_day)),
+        );
+    }
+
+    let mut user_rows: BTreeMap<Uuid, String> = BTreeMap::new();
+    for user in users {
+        let department = department_of(user).unwrap_or(UNASSIGNED);
+        let key = normalize(department);
+        rows.entry(key.clone())
+            .or_insert_with(|| blank_row(department, None))
+            .user_count += 1;
+        user_rows.insert(user.id, key);
+    }
+
+    for booking in bookings {
+        if !counts_toward_quota(&booking.status) {
+            continue;
+        }
+        let booked_days = TimeRange::new(booking.start_time, booking.end_time)
+            .map_or_else(Vec::new, |range| range.split_by_day())
+            .into_iter()
+            .filter(|(day, _)| (start_date..=end_date).contains(day))
+            .count();
+        if booked_days == 0 {
+            continue;
+        }
+        let Some(row) = user_rows
+            .get(&booking.user_id)
+            .and_then(|k| rows.get_mut(k))
+        else {
+            continue;
+        };
+        row.used_slots += booked_days;
+        #[allow(clippy::cast_precision_loss)]
+        let hours = (booking.end_time - booking.start_time).num_minutes() as f64 / 60.0;
+        row.booked_hours += hours;
+        row.total_amount += booking.pricing.total;
+        row.currency.clone_from(&booking.pricing.currency);
+    }
+
+    let departments = rows
+        .into_values()
+        .map(|mut row| {
+            #[allow(clippy::cast_precision_loss)]
+            {
+                row.utilization_percent = row
+                    .allocated_slots
+                    .filter(|&a| a > 0)
+                    .map(|a| (row.used_slots as f64 / a as f64 * 1000.0).round() / 10.0);
+            }
+            row.booked_hours = (row.booked_hours * 100.0).round() / 100.0;
+            row.total_amount = (row.total_amount * 100.0).round() / 100.0;
+            row
+        })
+        .collect();
+
+    DepartmentUtilizationReport {
+        start_date,
+        end_date,
+        days,
+        departments,
+    }
+}
+
+/// `GET /api/v1/admin/reports/department-utilization` — usage per department
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/reports/department-utilization",
+    tag = "Admin",
+    summary = "Department utilization report",
+    description = "Bookings, booked hours and spend per department over a date range, \
+        compared with each department's daily quota. Intended for chargeback. Admins and reporters.",
+    params(DepartmentUtilizationQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Utilization per department", body = DepartmentUtilizationReport),
+        (status = 400, description = "Invalid date range"),
+        (status = 403, description = "Insufficient permissions"),
+    )
+)]
+pub async fn department_utilization_report(
+    State(state): State<SharedState>,
+    _: RequirePermission<CanViewAllBookings>,
+    Query(query): Query<DepartmentUtilizationQuery>,
+) -> (StatusCode, Json<ApiResponse<DepartmentUtilizationReport>>) {
+    let state_guard = state.read().await;
+    let end_date = query.end_date.unwrap_or_else(|| Utc::now().date_naive());
+    let start_date = query
+        .start_date
+        .unwrap_or_else(|| end_date - chrono::Duration::days(29));
+    if end_date < start_date {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidDateRange,
+                "end_date must not be before start_date",
+            )),
+        );
+    }
+    if (end_date - start_date).num_days() >= MAX_REPORT_DAYS {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidDateRange,
+                format!("Date range must not exceed {MAX_REPORT_DAYS} days"),
+            )),
+        );
+    }
+
+    let quotas = load_department_quotas(&state_guard.db).await;
+    let (users, bookings) = match (
+        state_guard.db.list_users().await,
+        state_guard.db.list_bookings().await,
+    ) {
+        (Ok(users), Ok(bookings)) => (users, bookings),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Failed to build department utilization report: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let report = build_report(&quotas, &users, &bookings, start_date, end_date);
+    (StatusCode::OK, Json(ApiResponse::success(report)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quota(department: &str, slots_per_day: u32) -> DepartmentQuota {
+        DepartmentQuota {
+            department: department.to_string(),
+            slots_per_day,
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_blank_and_duplicate_departments() {
+        assert!(validate_quotas(&[quota("Engineering", 3), quota("Sales", 0)]).is_ok());
+        assert!(validate_quotas(&[quota("  ", 1)]).is_err());
+        assert!(validate_quotas(&[quota("Engineering", 3), quota(" engineering ", 1)]).is_err());
+        assert!(validate_quotas(&[quota("Engineering", 10_001)]).is_err());
+    }
+
+    #[test]
+    fn test_counts_toward_quota() {
+        assert!(counts_toward_quota(&BookingStatus::Confirmed));
+        assert!(counts_toward_quota(&BookingStatus::Completed));
+        assert!(counts_toward_quota(&BookingStatus::NoShow));
+        assert!(!counts_toward_quota(&BookingStatus::Cancelled));
+        assert!(!counts_toward_quota(&BookingStatus::Expired));
+    }
+
+    #[test]
+    fn test_report_without_data_lists_quota_departments() {
+        let day = NaiveDate::from_ymd_opt(2026, 5, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 5, 10).unwrap();
+        let report = build_report(&[quota("Engineering", 2)], &[], &[], day, end);
+
+        assert_eq!(report.days, 10);
+        assert_eq!(report.departments.len(), 1);
+        let row = &report.departments[0];
+        assert_eq!(row.department, "Engineering");
+        assert_eq!(row.allocated_slots, Some(20));
+        assert_eq!(row.used_slots, 0);
+        assert_eq!(row.utilization_percent, Some(0.0));
+    }
+}
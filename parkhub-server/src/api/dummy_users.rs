@@ -0,0 +1,166 @@
+//! Dummy accounts generated during setup.
+//!
+//! - `GET    /api/v1/admin/dummy-users` — list the generated accounts that remain
+//! - `DELETE /api/v1/admin/dummy-users` — delete them all
+//!
+//! The setup wizard (and `--unattended`) can create 50 test users sharing one
+//! password. They are tracked in the database so they can be removed before
+//! the server goes live; the admin UI shows a warning banner while any remain.
+//! Tenant-bound admins only see and purge the accounts of their own tenant.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{Json, extract::State, http::StatusCode};
+use serde::Serialize;
+
+use parkhub_common::{ApiResponse, ErrorCode, User};
+
+use crate::AppState;
+use crate::audit::{AuditEntry, AuditEventType};
+
+use super::SharedState;
+use super::admin::AdminUserResponse;
+use super::permissions::{CanManageUsers, RequirePermission};
+
+/// Generated dummy accounts that still exist
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DummyUsersResponse {
+    pub count: usize,
+    pub users: Vec<AdminUserResponse>,
+}
+
+/// Result of purging the dummy accounts
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct DummyUsersPurged {
+    pub purged: usize,
+}
+
+/// Dummy accounts visible to the admin `user_id`.
+async fn visible_dummy_users(state: &AppState, user_id: uuid::Uuid) -> anyhow::Result<Vec<User>> {
+    let caller_tenant_id = super::resolve_tenant_id(state, user_id).await;
+    Ok(state
+        .db
+        .list_dummy_users()
+        .await?
+        .into_iter()
+        .filter(|user| {
+            super::matches_tenant(user.tenant_id.as_deref(), caller_tenant_id.as_deref())
+        })
+        .collect())
+}
+
+/// `GET /api/v1/admin/dummy-users` — generated dummy accounts
+#[utoipa::path(get, path = "/api/v1/admin/dummy-users", tag = "Admin",
+    summary = "List dummy users (admin)",
+    description = "Test accounts generated during setup that share one password. Purge them before going live.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Remaining dummy accounts", body = DummyUsersResponse),
+        (status = 403, description = "Forbidden"),
+    )
+)]
+pub async fn list_dummy_users(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+) -> (StatusCode, Json<ApiResponse<DummyUsersResponse>>) {
+    let state_guard = state.read().await;
+    match visible_dummy_users(&state_guard, auth_user.user_id).await {
+        Ok(users) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(DummyUsersResponse {
+                count: users.len(),
+                users: users.iter().map(AdminUserResponse::from).collect(),
+            })),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to list dummy users: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
+/// `DELETE /api/v1/admin/dummy-users` — delete every generated dummy account
+#[utoipa::path(delete, path = "/api/v1/admin/dummy-users", tag = "Admin",
+    summary = "Purge dummy users (admin)",
+    description = "Deletes the test accounts generated during setup and ends their sessions. \
+        Unlike deleting a user, the accounts are removed rather than anonymized.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Accounts deleted", body = DummyUsersPurged),
+        (status = 403, description = "Forbidden"),
+    )
+)]
+pub async fn purge_dummy_users(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+) -> (StatusCode, Json<ApiResponse<DummyUsersPurged>>) {
+    let state_guard = state.read().await;
+    let users = match visible_dummy_users(&state_guard, auth_user.user_id).await {
+        Ok(users) => users,
+        Err(e) => {
+            tracing::error!("Failed to list dummy users: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let admin_username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+
+    let mut purged = 0;
+    for user in &users {
+        if let Err(e) = super::session_tokens::end_user_sessions(&state_guard, user.id).await {
+            tracing::warn!("Failed to end sessions of dummy user {}: {}", user.id, e);
+        }
+        match state_guard.db.delete_user(&user.id.to_string()).await {
+            Ok(true) => {
+                purged += 1;
+                AuditEntry::new(AuditEventType::UserDeleted)
+                    .user(auth_user.user_id, &admin_username)
+                    .resource("user", &user.id.to_string())
+                    .detail("dummy account purged")
+                    .log();
+            }
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!("Failed to delete dummy user {}: {}", user.id, e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Failed to delete dummy users",
+                    )),
+                );
+            }
+        }
+    }
+
+    tracing::info!(
+        admin_id = %auth_user.user_id,
+        purged,
+        "Admin purged dummy users"
+    );
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(DummyUsersPurged { purged })),
+    )
+}
@@ -10,7 +10,7 @@ use axum::{
     http::StatusCode,
 };
 
-use parkhub_common::{ApiResponse, DynamicPriceResult, DynamicPricingRules};
+use parkhub_common::{ApiResponse, DynamicPriceResult, DynamicPricingRules, ErrorCode};
 
 use super::SharedState;
 
@@ -55,14 +55,20 @@ pub async fn get_dynamic_pricing(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!(error = %e, "Failed to get parking lot");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -114,14 +120,20 @@ pub async fn admin_get_dynamic_pricing_rules(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!(error = %e, "Failed to get parking lot");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     }
@@ -181,14 +193,20 @@ pub async fn admin_update_dynamic_pricing_rules(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!(error = %e, "Failed to get parking lot");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     }
@@ -208,7 +226,7 @@ pub async fn admin_update_dynamic_pricing_rules(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "base_price must be >= 0",
                 )),
             );
@@ -220,7 +238,7 @@ pub async fn admin_update_dynamic_pricing_rules(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "surge_multiplier must be >= 1.0",
                 )),
             );
@@ -232,7 +250,7 @@ pub async fn admin_update_dynamic_pricing_rules(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "discount_multiplier must be > 0 and <= 1.0",
                 )),
             );
@@ -244,7 +262,7 @@ pub async fn admin_update_dynamic_pricing_rules(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "surge_threshold must be 0-100",
                 )),
             );
@@ -256,7 +274,7 @@ pub async fn admin_update_dynamic_pricing_rules(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "discount_threshold must be 0-100",
                 )),
             );
@@ -269,7 +287,7 @@ pub async fn admin_update_dynamic_pricing_rules(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "discount_threshold must be less than surge_threshold",
             )),
         );
@@ -282,7 +300,7 @@ pub async fn admin_update_dynamic_pricing_rules(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to save dynamic pricing rules",
             )),
         );
@@ -0,0 +1,177 @@
+//! Outbound email queue for admins.
+//!
+//! - `GET /api/v1/admin/email-queue` — undelivered emails (pending retries and
+//!   dead letters), oldest first; `?status=failed` for dead letters only
+//! - `POST /api/v1/admin/email-queue/{id}/retry` — reset a message's retry
+//!   budget and attempt delivery again
+//!
+//! Message bodies are never returned: they may contain password-reset or
+//! email-confirmation links. See [`crate::email_queue`] for delivery.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{QueuedEmail, QueuedEmailStatus};
+
+use super::{AuthUser, SharedState, check_admin};
+
+/// Query parameters for the queue listing.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct EmailQueueQuery {
+    /// Only return messages in this state (`pending` or `failed`)
+    pub status: Option<QueuedEmailStatus>,
+}
+
+/// A queued email without its body.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct QueuedEmailSummary {
+    pub id: Uuid,
+    pub to: String,
+    pub subject: String,
+    pub status: QueuedEmailStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Next automatic attempt; `None` for dead letters
+    pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+impl From<QueuedEmail> for QueuedEmailSummary {
+    fn from(email: QueuedEmail) -> Self {
+        Self {
+            id: email.id,
+            to: email.to,
+            subject: email.subject,
+            status: email.status,
+            attempts: email.attempts,
+            last_error: email.last_error,
+            created_at: email.created_at,
+            updated_at: email.updated_at,
+            next_attempt_at: (email.status == QueuedEmailStatus::Pending)
+                .then_some(email.next_attempt_at),
+        }
+    }
+}
+
+/// `GET /api/v1/admin/email-queue`
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/email-queue",
+    tag = "Admin",
+    summary = "List undelivered emails",
+    description = "Returns emails that have not been delivered yet: pending retries and dead \
+        letters whose retries are exhausted, oldest first. Bodies are omitted. Admin only.",
+    params(EmailQueueQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Queued emails", body = Vec<QueuedEmailSummary>),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn list_email_queue(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<EmailQueueQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<QueuedEmailSummary>>>) {
+    let state_guard = state.read().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let emails = match state_guard.db.list_queued_emails().await {
+        Ok(emails) => emails,
+        Err(e) => {
+            tracing::error!("Failed to list email queue: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let summaries = emails
+        .into_iter()
+        .filter(|e| query.status.is_none_or(|s| e.status == s))
+        .map(QueuedEmailSummary::from)
+        .collect();
+    (StatusCode::OK, Json(ApiResponse::success(summaries)))
+}
+
+/// `POST /api/v1/admin/email-queue/{id}/retry`
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/email-queue/{id}/retry",
+    tag = "Admin",
+    summary = "Retry a queued email",
+    description = "Resets the message's retry budget and attempts delivery again in the \
+        background. Works for pending and dead-lettered messages. Admin only.",
+    params(("id" = String, Path, description = "Queued email ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Retry scheduled", body = QueuedEmailSummary),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Email not in the queue (already delivered or purged)"),
+    )
+)]
+pub async fn retry_queued_email(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<QueuedEmailSummary>>) {
+    let state_guard = state.read().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let email = match crate::email_queue::retry(&state_guard.db, &id).await {
+        Ok(Some(email)) => email,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Email not in queue",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Failed to retry queued email: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    AuditEntry::new(AuditEventType::SettingsChanged)
+        .user(auth_user.user_id, "")
+        .resource("email", &id)
+        .detail("Queued email retried")
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(email.into())))
+}
@@ -0,0 +1,415 @@
+//! Monthly energy and CO2 reports for EV charging.
+//!
+//! - `GET /api/v1/admin/analytics/energy` — a month's charged energy per lot
+//!   and per user
+//! - `GET /api/v1/user/energy` — the caller's own month, per lot
+//!
+//! Completed charging sessions count towards the month they ended in. The
+//! CO2 figure is an estimate: the charged energy times the grid emission
+//! factor of the `ev_co2_grams_per_kwh` admin setting. The same totals feed
+//! the energy section of the monthly summary email (see
+//! [`super::monthly_summary`]).
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+#![cfg_attr(not(feature = "mod-ev-charging"), allow(dead_code))]
+
+use std::collections::{BTreeMap, HashMap};
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Datelike, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::models::{ChargingSession, ChargingSessionStatus};
+use parkhub_common::{ApiResponse, ErrorCode};
+
+use crate::db::Database;
+
+use super::permissions::{CanViewAllBookings, RequirePermission};
+use super::{AuthUser, SharedState};
+
+/// Admin setting holding the grid emission factor in grams of CO2 per kWh.
+pub const CO2_FACTOR_SETTING: &str = "ev_co2_grams_per_kwh";
+
+/// Charged energy over a month.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct EnergyTotals {
+    /// Completed charging sessions
+    pub sessions: u32,
+    pub kwh: f64,
+    /// Estimated CO2 emitted producing the energy, in kg
+    pub co2_kg: f64,
+}
+
+impl EnergyTotals {
+    fn add(&mut self, session: &ChargingSession, grams_per_kwh: f64) {
+        self.sessions += 1;
+        self.kwh += session.kwh_consumed;
+        self.co2_kg += session.kwh_consumed * grams_per_kwh / 1000.0;
+    }
+}
+
+/// Charged energy at one lot.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct LotEnergy {
+    pub lot_id: Uuid,
+    pub lot_name: String,
+    #[serde(flatten)]
+    pub totals: EnergyTotals,
+}
+
+/// Energy one user charged.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct UserEnergy {
+    pub user_id: Uuid,
+    pub username: String,
+    #[serde(flatten)]
+    pub totals: EnergyTotals,
+}
+
+/// A month's energy report. `users` is only filled for admins.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct EnergyReport {
+    /// Month (YYYY-MM)
+    pub month: String,
+    /// Emission factor the CO2 figures were computed with
+    pub co2_grams_per_kwh: f64,
+    pub totals: EnergyTotals,
+    /// Per lot, most energy first
+    pub lots: Vec<LotEnergy>,
+    /// Per user, most energy first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub users: Vec<UserEnergy>,
+}
+
+/// Query parameters of the energy reports.
+#[derive(Debug, Deserialize)]
+pub struct EnergyReportQuery {
+    /// Month (YYYY-MM), default the current month
+    pub month: Option<String>,
+}
+
+/// A calendar month in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Month {
+    pub first_day: NaiveDate,
+}
+
+impl Month {
+    /// Parse a `YYYY-MM` month.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        NaiveDate::parse_from_str(&format!("{value}-01"), "%Y-%m-%d")
+            .ok()
+            .map(|first_day| Self { first_day })
+    }
+
+    /// The month `at` falls in.
+    #[must_use]
+    pub fn containing(at: DateTime<Utc>) -> Self {
+        let date = at.date_naive();
+        Self {
+            first_day: date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// The month before this one.
+    #[must_use]
+    pub fn previous(self) -> Self {
+        Self {
+            first_day: self
+                .first_day
+                .checked_sub_months(Months::new(1))
+                .unwrap_or(self.first_day),
+        }
+    }
+
+    /// Whether `at` falls in the month.
+    #[must_use]
+    pub fn contains(self, at: DateTime<Utc>) -> bool {
+        let next = self
+            .first_day
+            .checked_add_months(Months::new(1))
+            .unwrap_or(NaiveDate::MAX);
+        (self.first_day..next).contains(&at.date_naive())
+    }
+
+    /// `YYYY-MM`
+    #[must_use]
+    pub fn label(self) -> String {
+        self.first_day.format("%Y-%m").to_string()
+    }
+}
+
+/// The emission factor from the admin settings, in g CO2 per kWh.
+pub async fn co2_grams_per_kwh(db: &Database) -> f64 {
+    super::settings::read_admin_setting(db, CO2_FACTOR_SETTING)
+        .await
+        .parse()
+        .unwrap_or(0.0)
+}
+
+/// Completed sessions that ended in `month`.
+pub fn sessions_in_month(
+    sessions: &[ChargingSession],
+    month: Month,
+) -> impl Iterator<Item = &ChargingSession> {
+    sessions.iter().filter(move |s| {
+        s.status == ChargingSessionStatus::Completed
+            && s.end_time.is_some_and(|end| month.contains(end))
+    })
+}
+
+/// Totals of `sessions` by `key`, most energy first.
+fn totals_by<K: Ord + Copy>(
+    sessions: &[&ChargingSession],
+    grams_per_kwh: f64,
+    key: impl Fn(&ChargingSession) -> Option<K>,
+) -> Vec<(K, EnergyTotals)> {
+    let mut totals: BTreeMap<K, EnergyTotals> = BTreeMap::new();
+    for session in sessions {
+        if let Some(k) = key(session) {
+            totals.entry(k).or_default().add(session, grams_per_kwh);
+        }
+    }
+    let mut totals: Vec<_> = totals.into_iter().collect();
+    totals.sort_by(|a, b| b.1.kwh.total_cmp(&a.1.kwh));
+    totals
+}
+
+/// Energy `user_id` charged in `month`.
+#[must_use]
+pub fn user_totals(
+    sessions: &[ChargingSession],
+    user_id: Uuid,
+    month: Month,
+    grams_per_kwh: f64,
+) -> EnergyTotals {
+    let mut totals = EnergyTotals::default();
+    for session in sessions_in_month(sessions, month).filter(|s| s.user_id == user_id) {
+        totals.add(session, grams_per_kwh);
+    }
+    totals
+}
+
+/// Build the report of `month`, for `user_id` only if given.
+async fn build_report(
+    db: &Database,
+    month: Month,
+    user_id: Option<Uuid>,
+) -> anyhow::Result<EnergyReport> {
+    let grams_per_kwh = co2_grams_per_kwh(db).await;
+    let all_sessions = db.list_all_charging_sessions().await?;
+    let sessions: Vec<&ChargingSession> = sessions_in_month(&all_sessions, month)
+        .filter(|s| user_id.is_none_or(|id| s.user_id == id))
+        .collect();
+
+    let charger_lots: HashMap<Uuid, Uuid> = db
+        .list_all_chargers()
+        .await?
+        .into_iter()
+        .map(|c| (c.id, c.lot_id))
+        .collect();
+    let lot_names: HashMap<Uuid, String> = db
+        .list_parking_lots()
+        .await?
+        .into_iter()
+        .map(|l| (l.id, l.name))
+        .collect();
+
+    let mut totals = EnergyTotals::default();
+    for session in &sessions {
+        totals.add(session, grams_per_kwh);
+    }
+    let lots = totals_by(&sessions, grams_per_kwh, |s| {
+        charger_lots.get(&s.charger_id).copied()
+    })
+    .into_iter()
+    .map(|(lot_id, totals)| LotEnergy {
+        lot_id,
+        lot_name: lot_names.get(&lot_id).cloned().unwrap_or_default(),
+        totals,
+    })
+    .collect();
+
+    let mut users = Vec::new();
+    if user_id.is_none() {
+        for (user_id, totals) in totals_by(&sessions, grams_per_kwh, |s| Some(s.user_id)) {
+            let username = db
+                .get_user(&user_id.to_string())
+                .await?
+                .map(|u| u.username)
+                .unwrap_or_default();
+            users.push(UserEnergy {
+                user_id,
+                username,
+                totals,
+            });
+        }
+    }
+
+    Ok(EnergyReport {
+        month: month.label(),
+        co2_grams_per_kwh: grams_per_kwh,
+        totals,
+        lots,
+        users,
+    })
+}
+
+/// Month of `query`, or an error response if it is malformed.
+#[allow(clippy::result_large_err)]
+fn requested_month<T>(
+    query: &EnergyReportQuery,
+) -> Result<Month, (StatusCode, Json<ApiResponse<T>>)> {
+    match query.month.as_deref() {
+        None => Ok(Month::containing(Utc::now())),
+        Some(value) => Month::parse(value).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ErrorCode::InvalidDate,
+                    "month must be in YYYY-MM format",
+                )),
+            )
+        }),
+    }
+}
+
+async fn report_response(
+    db: &Database,
+    month: Month,
+    user_id: Option<Uuid>,
+) -> (StatusCode, Json<ApiResponse<EnergyReport>>) {
+    match build_report(db, month, user_id).await {
+        Ok(report) => (StatusCode::OK, Json(ApiResponse::success(report))),
+        Err(e) => {
+            tracing::error!("Failed to build energy report: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
+/// `GET /api/v1/admin/analytics/energy` — a month's energy per lot and user.
+#[utoipa::path(get, path = "/api/v1/admin/analytics/energy", tag = "Admin",
+    summary = "EV energy and CO2 report",
+    description = "Energy charged in completed EV charging sessions during `month` \
+        (default: the current month), in total, per lot and per user, with the estimated \
+        CO2 from the `ev_co2_grams_per_kwh` setting. Sessions count towards the month \
+        they ended in. Admin only.",
+    security(("bearer_auth" = [])),
+    params(("month" = Option<String>, Query, description = "Month (YYYY-MM)")),
+    responses(
+        (status = 200, description = "Energy report", body = EnergyReport),
+        (status = 400, description = "Invalid month"),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn admin_energy_report(
+    State(state): State<SharedState>,
+    _: RequirePermission<CanViewAllBookings>,
+    Query(query): Query<EnergyReportQuery>,
+) -> (StatusCode, Json<ApiResponse<EnergyReport>>) {
+    let month = match requested_month(&query) {
+        Ok(month) => month,
+        Err(response) => return response,
+    };
+    let state_guard = state.read().await;
+    report_response(&state_guard.db, month, None).await
+}
+
+/// `GET /api/v1/user/energy` — the caller's energy of a month, per lot.
+#[utoipa::path(get, path = "/api/v1/user/energy", tag = "EV Charging",
+    summary = "My EV energy and CO2 report",
+    description = "Energy the caller charged in completed EV charging sessions during \
+        `month` (default: the current month), in total and per lot, with the estimated \
+        CO2.",
+    security(("bearer_auth" = [])),
+    params(("month" = Option<String>, Query, description = "Month (YYYY-MM)")),
+    responses(
+        (status = 200, description = "Energy report", body = EnergyReport),
+        (status = 400, description = "Invalid month"),
+    )
+)]
+pub async fn my_energy_report(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<EnergyReportQuery>,
+) -> (StatusCode, Json<ApiResponse<EnergyReport>>) {
+    let month = match requested_month(&query) {
+        Ok(month) => month,
+        Err(response) => return response,
+    };
+    let state_guard = state.read().await;
+    report_response(&state_guard.db, month, Some(auth_user.user_id)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn session(user_id: Uuid, kwh: f64, ended: DateTime<Utc>) -> ChargingSession {
+        ChargingSession {
+            id: Uuid::new_v4(),
+            charger_id: Uuid::new_v4(),
+            user_id,
+            booking_id: None,
+            start_time: ended - chrono::Duration::hours(1),
+            end_time: Some(ended),
+            kwh_consumed: kwh,
+            status: ChargingSessionStatus::Completed,
+            created_at: ended,
+        }
+    }
+
+    #[test]
+    fn test_month_bounds() {
+        let march = Month::parse("2026-03").unwrap();
+        assert!(march.contains(Utc.with_ymd_and_hms(2026, 3, 31, 23, 59, 0).unwrap()));
+        assert!(!march.contains(Utc.with_ymd_and_hms(2026, 4, 1, 0, 0, 0).unwrap()));
+        assert_eq!(march.previous().label(), "2026-02");
+        assert_eq!(
+            Month::parse("2026-01").unwrap().previous().label(),
+            "2025-12"
+        );
+        assert!(Month::parse("2026-13").is_none());
+    }
+
+    #[test]
+    fn test_user_totals_count_completed_sessions_of_the_month() {
+        let user = Uuid::new_v4();
+        let march = Month::parse("2026-03").unwrap();
+        let in_march = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        let mut active = session(user, 5.0, in_march);
+        active.status = ChargingSessionStatus::Active;
+        let sessions = [
+            session(user, 10.0, in_march),
+            session(user, 20.0, in_march),
+            session(Uuid::new_v4(), 40.0, in_march),
+            session(
+                user,
+                80.0,
+                Utc.with_ymd_and_hms(2026, 4, 1, 8, 0, 0).unwrap(),
+            ),
+            active,
+        ];
+
+        let totals = user_totals(&sessions, user, march, 400.0);
+        assert_eq!(totals.sessions, 2);
+        assert!((totals.kwh - 30.0).abs() < 1e-9);
+        assert!((totals.co2_kg - 12.0).abs() < 1e-9);
+    }
+}
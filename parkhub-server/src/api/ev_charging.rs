@@ -16,10 +16,10 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
 use parkhub_common::models::{
     ChargingSession, ChargingSessionStatus, ConnectorType, EvCharger, EvChargerStatus,
 };
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{AuthUser, SharedState, check_admin};
 
@@ -72,7 +72,7 @@ pub async fn list_lot_chargers(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list chargers",
                 )),
             )
@@ -102,14 +102,17 @@ pub async fn start_charging(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Charger not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Charger not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -118,7 +121,7 @@ pub async fn start_charging(
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "CHARGER_UNAVAILABLE",
+                ErrorCode::ChargerUnavailable,
                 "Charger is not available",
             )),
         );
@@ -143,7 +146,7 @@ pub async fn start_charging(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to start charging",
             )),
         );
@@ -153,7 +156,10 @@ pub async fn start_charging(
         tracing::error!("Failed to save charging session: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save session")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save session",
+            )),
         );
     }
 
@@ -195,7 +201,10 @@ pub async fn stop_charging(
             tracing::error!("Failed to list sessions: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -208,7 +217,7 @@ pub async fn stop_charging(
             return (
                 StatusCode::NOT_FOUND,
                 Json(ApiResponse::error(
-                    "NO_ACTIVE_SESSION",
+                    ErrorCode::NoActiveSession,
                     "No active charging session found",
                 )),
             );
@@ -228,7 +237,10 @@ pub async fn stop_charging(
         tracing::error!("Failed to update session: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to stop session")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to stop session",
+            )),
         );
     }
 
@@ -276,7 +288,7 @@ pub async fn charging_history(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list sessions",
                 )),
             )
@@ -297,8 +309,8 @@ pub async fn admin_charger_overview(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<ChargerUtilizationStats>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let chargers = state_guard.db.list_all_chargers().await.unwrap_or_default();
@@ -343,8 +355,8 @@ pub async fn admin_add_charger(
     Json(req): Json<AddChargerRequest>,
 ) -> (StatusCode, Json<ApiResponse<EvCharger>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let charger = EvCharger {
@@ -362,7 +374,10 @@ pub async fn admin_add_charger(
         tracing::error!("Failed to save charger: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to add charger")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to add charger",
+            )),
         );
     }
 
@@ -0,0 +1,648 @@
+//! The live event bus behind the realtime channels.
+//!
+//! Handlers publish [`LiveEvent`]s — bookings, slot status, lot changes,
+//! occupancy, announcements — with `state.ws_events.broadcast(event)` after
+//! their write commits. A `tokio::sync::broadcast` channel fans them out to
+//! every connected client, over either transport:
+//!
+//! - `GET /api/v1/ws` — WebSocket, see [`super::ws`]
+//! - `GET /api/v1/events` — Server-Sent Events, for clients behind proxies
+//!   that break WebSocket upgrades
+//!
+//! Both send the same JSON events, start with the current occupancy of the
+//! subscribed lots and accept `?lots=<id>,<id>` to receive only those lots'
+//! events ([`LotFilter`]). Only the WebSocket can change its lots later;
+//! an SSE client reconnects with a new `?lots=`.
+//!
+//! ## Authentication
+//!
+//! Anonymous connections are allowed, for public occupancy displays. A
+//! session token — `?token=...`, or on the SSE stream also the bearer header
+//! or auth cookie — must be valid and belong to an active user.
+
+use async_stream::stream;
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{
+        Sse,
+        sse::{Event, KeepAlive},
+    },
+};
+use chrono::Utc;
+use futures_util::Stream;
+use parkhub_common::{ApiResponse, ErrorCode};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{RwLock, broadcast};
+use tracing::{debug, warn};
+
+use crate::AppState;
+
+/// Capacity of the broadcast channel. Slow readers that fall behind will miss
+/// messages (lagged), which is acceptable for real-time UI updates.
+const BROADCAST_CAPACITY: usize = 256;
+
+/// Keep-alive comment interval on the SSE stream, so idle proxies keep it open.
+const SSE_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Event types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Event types published on the bus.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveEventType {
+    BookingCreated,
+    BookingCancelled,
+    OccupancyChanged,
+    AnnouncementPublished,
+    SlotStatusChange,
+    LotUpdated,
+    LotDeleted,
+}
+
+/// An event as sent to connected clients, over WebSocket or SSE.
+///
+/// The `data` field carries event-specific detail as freeform JSON.
+/// This allows adding new event types without changing the wire format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveEvent {
+    pub event: LiveEventType,
+    pub data: serde_json::Value,
+    pub timestamp: String,
+}
+
+impl LiveEvent {
+    /// Create a new event with the current UTC timestamp.
+    pub fn new(event: LiveEventType, data: serde_json::Value) -> Self {
+        Self {
+            event,
+            data,
+            timestamp: Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Create a `BookingCreated` event.
+    pub fn booking_created(lot_id: &str, slot_id: &str, user_id: &str) -> Self {
+        Self::new(
+            LiveEventType::BookingCreated,
+            serde_json::json!({
+                "lot_id": lot_id,
+                "slot_id": slot_id,
+                "user_id": user_id,
+            }),
+        )
+    }
+
+    /// Create a `BookingCancelled` event.
+    pub fn booking_cancelled(lot_id: &str, slot_id: &str) -> Self {
+        Self::new(
+            LiveEventType::BookingCancelled,
+            serde_json::json!({
+                "lot_id": lot_id,
+                "slot_id": slot_id,
+            }),
+        )
+    }
+
+    /// Create an `OccupancyChanged` event.
+    pub fn occupancy_update(lot_id: &str, available: u32, total: u32) -> Self {
+        Self::new(
+            LiveEventType::OccupancyChanged,
+            serde_json::json!({
+                "lot_id": lot_id,
+                "available": available,
+                "total": total,
+            }),
+        )
+    }
+
+    /// Create an `AnnouncementPublished` event.
+    pub fn announcement_published(id: &str, title: &str) -> Self {
+        Self::new(
+            LiveEventType::AnnouncementPublished,
+            serde_json::json!({
+                "id": id,
+                "title": title,
+            }),
+        )
+    }
+
+    /// Create a `SlotStatusChange` event.
+    pub fn slot_status_change(lot_id: &str, slot_id: &str, status: &str) -> Self {
+        Self::new(
+            LiveEventType::SlotStatusChange,
+            serde_json::json!({
+                "lot_id": lot_id,
+                "slot_id": slot_id,
+                "status": status,
+            }),
+        )
+    }
+
+    /// Create a `LotUpdated` event.
+    pub fn lot_updated(lot_id: &str, name: &str, status: &str) -> Self {
+        Self::new(
+            LiveEventType::LotUpdated,
+            serde_json::json!({
+                "lot_id": lot_id,
+                "name": name,
+                "status": status,
+            }),
+        )
+    }
+
+    /// Create a `LotDeleted` event.
+    pub fn lot_deleted(lot_id: &str) -> Self {
+        Self::new(
+            LiveEventType::LotDeleted,
+            serde_json::json!({
+                "lot_id": lot_id,
+            }),
+        )
+    }
+
+    /// The lot this event is about, if any.
+    pub fn lot_id(&self) -> Option<&str> {
+        self.data.get("lot_id").and_then(serde_json::Value::as_str)
+    }
+}
+
+/// How a unit enum such as `SlotStatus` is written on the wire, for event data.
+pub fn wire_name(value: &impl Serialize) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Lot subscriptions
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The lots a connection wants events for. `None` means all lots.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LotFilter {
+    lots: Option<HashSet<String>>,
+}
+
+impl LotFilter {
+    /// Parse the comma-separated `?lots=` parameter; absent or empty means all lots.
+    pub fn from_query(lots: Option<&str>) -> Self {
+        let lots: HashSet<String> = lots
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(str::to_string)
+            .collect();
+        Self {
+            lots: (!lots.is_empty()).then_some(lots),
+        }
+    }
+
+    /// A filter for just `lots`.
+    pub fn only(lots: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            lots: Some(lots.into_iter().collect()),
+        }
+    }
+
+    /// Whether the connection wants events about `lot_id`.
+    pub fn includes(&self, lot_id: &str) -> bool {
+        self.lots.as_ref().is_none_or(|lots| lots.contains(lot_id))
+    }
+
+    /// Whether `event` should be forwarded to the connection.
+    pub fn matches(&self, event: &LiveEvent) -> bool {
+        event.lot_id().is_none_or(|lot_id| self.includes(lot_id))
+    }
+
+    /// Add `lots` to the filter. A connection that receives all lots is
+    /// narrowed down to just these.
+    pub fn subscribe(&mut self, lots: &[String]) {
+        self.lots
+            .get_or_insert_with(HashSet::new)
+            .extend(lots.iter().cloned());
+    }
+
+    /// Stop receiving events about `lots`.
+    pub fn unsubscribe(&mut self, lots: &[String]) {
+        if let Some(subscribed) = &mut self.lots {
+            for lot in lots {
+                subscribed.remove(lot);
+            }
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Broadcast channel
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Holds the broadcast sender for WebSocket events.
+#[derive(Debug, Clone)]
+pub struct EventBroadcaster {
+    sender: broadcast::Sender<LiveEvent>,
+}
+
+impl EventBroadcaster {
+    /// Create a new broadcaster with the default channel capacity.
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+        Self { sender }
+    }
+
+    /// Broadcast an event to all connected clients.
+    /// Returns the number of receivers that will get the message.
+    /// Returns 0 if there are no active subscribers (which is fine).
+    pub fn broadcast(&self, event: LiveEvent) -> usize {
+        self.sender.send(event).unwrap_or_default()
+    }
+
+    /// Subscribe to receive events.
+    pub fn subscribe(&self) -> broadcast::Receiver<LiveEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Get the current number of active receivers.
+    pub fn receiver_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for EventBroadcaster {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Authentication
+// ─────────────────────────────────────────────────────────────────────────────
+
+type SharedState = Arc<RwLock<AppState>>;
+
+/// Check an optional session token presented by a realtime client. No token
+/// is fine; a token must be valid and its user active.
+pub(crate) async fn check_token(
+    state: &SharedState,
+    token: Option<&str>,
+) -> Result<(), (StatusCode, Json<ApiResponse<()>>)> {
+    let Some(token) = token else {
+        return Ok(());
+    };
+    let state_guard = state.read().await;
+    let Some(session) = super::session_tokens::authenticate(&state_guard, token).await else {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::error(
+                ErrorCode::Unauthorized,
+                "Invalid or expired token",
+            )),
+        ));
+    };
+    match state_guard.db.get_user(&session.user_id.to_string()).await {
+        Ok(Some(u)) if u.is_active => {
+            debug!(user_id = %session.user_id, "Realtime client authenticated");
+            Ok(())
+        }
+        _ => Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::error(
+                ErrorCode::Unauthorized,
+                "Invalid or disabled user",
+            )),
+        )),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// SSE handler
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Query parameters for `GET /api/v1/events`.
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    /// Session token, for `EventSource` clients that cannot set headers.
+    pub token: Option<String>,
+    /// Comma-separated lot IDs to receive events for (default: all lots).
+    pub lots: Option<String>,
+}
+
+/// Handler for `GET /api/v1/events` — the bus as a `text/event-stream`.
+///
+/// Each event's type is the SSE `event:` field and the full JSON event its
+/// `data:`, so `EventSource.addEventListener("booking_created", …)` works.
+pub async fn events_handler(
+    State(state): State<SharedState>,
+    Query(params): Query<EventsQuery>,
+    headers: HeaderMap,
+) -> Result<
+    Sse<impl Stream<Item = Result<Event, std::convert::Infallible>>>,
+    (StatusCode, Json<ApiResponse<()>>),
+> {
+    let token = params.token.or_else(|| super::sse::extract_token(&headers));
+    check_token(&state, token.as_deref()).await?;
+
+    let filter = LotFilter::from_query(params.lots.as_deref());
+    let mut rx = state.read().await.ws_events.subscribe();
+    let snapshot = occupancy_snapshot(&state, &filter).await;
+
+    let body = stream! {
+        for event in snapshot {
+            if let Some(sse_event) = to_sse(&event) {
+                yield Ok(sse_event);
+            }
+        }
+        loop {
+            match rx.recv().await {
+                Ok(event) if filter.matches(&event) => {
+                    if let Some(sse_event) = to_sse(&event) {
+                        yield Ok(sse_event);
+                    }
+                }
+                Ok(_) => {} // Another lot
+                Err(broadcast::error::RecvError::Lagged(n)) => {
+                    warn!("SSE events client lagged, skipped {n} messages");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    };
+
+    Ok(Sse::new(body).keep_alive(
+        KeepAlive::new()
+            .interval(SSE_KEEPALIVE_INTERVAL)
+            .text("keep-alive"),
+    ))
+}
+
+/// `event` as an SSE message named after its type.
+fn to_sse(event: &LiveEvent) -> Option<Event> {
+    let json = serde_json::to_string(event).ok()?;
+    Some(Event::default().event(wire_name(&event.event)).data(json))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Occupancy
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Current occupancy of the lots `filter` includes.
+pub(crate) async fn occupancy_snapshot(state: &SharedState, filter: &LotFilter) -> Vec<LiveEvent> {
+    let s = state.read().await;
+    let Ok(lots) = s.db.list_parking_lots().await else {
+        return Vec::new();
+    };
+    let mut events = Vec::new();
+    for lot in &lots {
+        let lot_id = lot.id.to_string();
+        if !filter.includes(&lot_id) {
+            continue;
+        }
+        if let Ok(slots) = s.db.list_slots_by_lot(&lot_id).await {
+            let total = u32::try_from(slots.len()).unwrap_or(u32::MAX);
+            let available = u32::try_from(
+                slots
+                    .iter()
+                    .filter(|sl| sl.status == parkhub_common::SlotStatus::Available)
+                    .count(),
+            )
+            .unwrap_or(u32::MAX);
+            events.push(LiveEvent::occupancy_update(&lot_id, available, total));
+        }
+    }
+    events
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn event_serialization() {
+        let event = LiveEvent::new(
+            LiveEventType::BookingCreated,
+            serde_json::json!({"booking_id": "abc-123", "slot": 5}),
+        );
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"event\":\"booking_created\""));
+        assert!(json.contains("\"booking_id\":\"abc-123\""));
+        assert!(json.contains("\"timestamp\""));
+
+        // Roundtrip
+        let parsed: LiveEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.event, LiveEventType::BookingCreated);
+    }
+
+    #[test]
+    fn event_types_serialize_snake_case() {
+        let cases = vec![
+            (LiveEventType::BookingCreated, "\"booking_created\""),
+            (LiveEventType::BookingCancelled, "\"booking_cancelled\""),
+            (LiveEventType::OccupancyChanged, "\"occupancy_changed\""),
+            (
+                LiveEventType::AnnouncementPublished,
+                "\"announcement_published\"",
+            ),
+            (LiveEventType::SlotStatusChange, "\"slot_status_change\""),
+            (LiveEventType::LotUpdated, "\"lot_updated\""),
+            (LiveEventType::LotDeleted, "\"lot_deleted\""),
+        ];
+        for (variant, expected) in cases {
+            let json = serde_json::to_string(&variant).unwrap();
+            assert_eq!(json, expected);
+        }
+    }
+
+    #[test]
+    fn broadcaster_no_receivers() {
+        let broadcaster = EventBroadcaster::new();
+        let event = LiveEvent::new(
+            LiveEventType::OccupancyChanged,
+            serde_json::json!({"lot_id": "lot-1", "available": 42}),
+        );
+        assert_eq!(broadcaster.broadcast(event), 0);
+        assert_eq!(broadcaster.receiver_count(), 0);
+    }
+
+    #[test]
+    fn broadcaster_fan_out() {
+        let broadcaster = EventBroadcaster::new();
+        let mut rx1 = broadcaster.subscribe();
+        let mut rx2 = broadcaster.subscribe();
+
+        let event = LiveEvent::new(
+            LiveEventType::BookingCancelled,
+            serde_json::json!({"booking_id": "xyz-789"}),
+        );
+
+        let count = broadcaster.broadcast(event.clone());
+        assert_eq!(count, 2);
+
+        let received1 = rx1.try_recv().unwrap();
+        assert_eq!(received1.event, LiveEventType::BookingCancelled);
+
+        let received2 = rx2.try_recv().unwrap();
+        assert_eq!(received2.event, LiveEventType::BookingCancelled);
+    }
+
+    #[test]
+    fn broadcaster_lagged_receiver() {
+        let (sender, _) = broadcast::channel::<LiveEvent>(2);
+        let broadcaster = EventBroadcaster { sender };
+
+        let mut rx = broadcaster.subscribe();
+
+        for i in 0..3 {
+            broadcaster.broadcast(LiveEvent::new(
+                LiveEventType::OccupancyChanged,
+                serde_json::json!({"seq": i}),
+            ));
+        }
+
+        match rx.try_recv() {
+            Err(broadcast::error::TryRecvError::Lagged(n)) => {
+                assert_eq!(n, 1);
+            }
+            other => panic!("Expected Lagged, got {:?}", other),
+        }
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.data["seq"], 1);
+    }
+
+    #[test]
+    fn broadcaster_default_impl() {
+        let b = EventBroadcaster::default();
+        assert_eq!(b.receiver_count(), 0);
+    }
+
+    #[test]
+    fn broadcaster_dropped_receiver() {
+        let broadcaster = EventBroadcaster::new();
+        let rx = broadcaster.subscribe();
+        assert_eq!(broadcaster.receiver_count(), 1);
+
+        drop(rx);
+        assert_eq!(broadcaster.receiver_count(), 0);
+
+        let event = LiveEvent::new(LiveEventType::BookingCreated, serde_json::json!({}));
+        assert_eq!(broadcaster.broadcast(event), 0);
+    }
+
+    #[test]
+    fn booking_created_event_factory() {
+        let event = LiveEvent::booking_created("lot-1", "slot-2", "user-3");
+        assert_eq!(event.event, LiveEventType::BookingCreated);
+        assert_eq!(event.data["lot_id"], "lot-1");
+        assert_eq!(event.data["slot_id"], "slot-2");
+        assert_eq!(event.data["user_id"], "user-3");
+    }
+
+    #[test]
+    fn booking_cancelled_event_factory() {
+        let event = LiveEvent::booking_cancelled("lot-1", "slot-2");
+        assert_eq!(event.event, LiveEventType::BookingCancelled);
+        assert_eq!(event.data["lot_id"], "lot-1");
+        assert_eq!(event.data["slot_id"], "slot-2");
+    }
+
+    #[test]
+    fn occupancy_update_event_factory() {
+        let event = LiveEvent::occupancy_update("lot-1", 5, 10);
+        assert_eq!(event.event, LiveEventType::OccupancyChanged);
+        assert_eq!(event.data["lot_id"], "lot-1");
+        assert_eq!(event.data["available"], 5);
+        assert_eq!(event.data["total"], 10);
+    }
+
+    #[test]
+    fn announcement_published_event_factory() {
+        let event = LiveEvent::announcement_published("ann-1", "Important Notice");
+        assert_eq!(event.event, LiveEventType::AnnouncementPublished);
+        assert_eq!(event.data["id"], "ann-1");
+        assert_eq!(event.data["title"], "Important Notice");
+    }
+
+    #[test]
+    fn slot_status_change_event_factory() {
+        let event = LiveEvent::slot_status_change("lot-1", "slot-2", "maintenance");
+        assert_eq!(event.event, LiveEventType::SlotStatusChange);
+        assert_eq!(event.data["lot_id"], "lot-1");
+        assert_eq!(event.data["slot_id"], "slot-2");
+        assert_eq!(event.data["status"], "maintenance");
+    }
+
+    #[test]
+    fn event_roundtrip_all_types() {
+        let events = vec![
+            LiveEvent::booking_created("l", "s", "u"),
+            LiveEvent::booking_cancelled("l", "s"),
+            LiveEvent::occupancy_update("l", 1, 2),
+            LiveEvent::announcement_published("a", "t"),
+            LiveEvent::slot_status_change("l", "s", "ok"),
+            LiveEvent::lot_updated("l", "n", "open"),
+            LiveEvent::lot_deleted("l"),
+        ];
+        for event in events {
+            let json = serde_json::to_string(&event).unwrap();
+            let parsed: LiveEvent = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed.event, event.event);
+            assert_eq!(parsed.data, event.data);
+        }
+    }
+
+    #[test]
+    fn lot_updated_event_factory() {
+        let event = LiveEvent::lot_updated("lot-1", "Garage", "maintenance");
+        assert_eq!(event.event, LiveEventType::LotUpdated);
+        assert_eq!(event.lot_id(), Some("lot-1"));
+        assert_eq!(event.data["name"], "Garage");
+        assert_eq!(event.data["status"], "maintenance");
+    }
+
+    #[test]
+    fn wire_name_uses_serde_name() {
+        assert_eq!(
+            wire_name(&parkhub_common::SlotStatus::Maintenance),
+            "maintenance"
+        );
+    }
+
+    #[test]
+    fn lot_filter_defaults_to_all_lots() {
+        let filter = LotFilter::from_query(None);
+        assert!(filter.matches(&LiveEvent::booking_created("lot-1", "s", "u")));
+        assert_eq!(LotFilter::from_query(Some(" , ")), filter);
+    }
+
+    #[test]
+    fn lot_filter_from_query_keeps_listed_lots() {
+        let filter = LotFilter::from_query(Some("lot-1, lot-2"));
+        assert!(filter.matches(&LiveEvent::occupancy_update("lot-2", 1, 2)));
+        assert!(!filter.matches(&LiveEvent::occupancy_update("lot-3", 1, 2)));
+        // Events without a lot reach everyone
+        assert!(filter.matches(&LiveEvent::announcement_published("a", "t")));
+    }
+
+    #[test]
+    fn lot_filter_subscribe_and_unsubscribe() {
+        let mut filter = LotFilter::default();
+        filter.subscribe(&["lot-1".to_string()]);
+        assert!(filter.includes("lot-1"));
+        assert!(!filter.includes("lot-2"));
+
+        filter.unsubscribe(&["lot-1".to_string()]);
+        assert!(!filter.includes("lot-1"));
+        assert!(filter.matches(&LiveEvent::announcement_published("a", "t")));
+    }
+}
@@ -14,6 +14,8 @@ use chrono::{DateTime, NaiveDate, Utc};
 use serde::Deserialize;
 use std::fmt::Write;
 
+use super::permissions::{CanViewAllBookings, RequirePermission};
+use super::plate_display::PlateViewer;
 use super::{AuthUser, SharedState, check_admin};
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -114,30 +116,26 @@ fn in_date_range(dt: &DateTime<Utc>, range: &ExportDateRange) -> bool {
 // Bookings CSV
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// `GET /api/v1/admin/export/bookings` — export all bookings as CSV (admin only)
+/// `GET /api/v1/admin/export/bookings` — export all bookings as CSV (admins and reporters)
 #[utoipa::path(
     get,
     path = "/api/v1/admin/export/bookings",
     tag = "Admin",
     summary = "Export bookings as CSV",
-    description = "Download all bookings as a CSV file. Supports optional date filtering via from and to query params (YYYY-MM-DD). Admin only.",
+    description = "Download all bookings as a CSV file, with one `tag:<key>` column per booking tag. Supports optional date filtering via from and to query params (YYYY-MM-DD). Admins and reporters.",
     params(ExportDateRange),
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "CSV file", content_type = "text/csv"),
-        (status = 403, description = "Admin access required"),
+        (status = 403, description = "Insufficient permissions"),
     )
 )]
 pub async fn admin_export_bookings_csv(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanViewAllBookings>,
     Query(range): Query<ExportDateRange>,
 ) -> impl IntoResponse {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return error_response(status, msg);
-    }
-
     let bookings = match state_guard.db.list_bookings().await {
         Ok(b) => b,
         Err(e) => {
@@ -149,15 +147,23 @@ pub async fn admin_export_bookings_csv(
         }
     };
 
+    let bookings: Vec<_> = bookings
+        .into_iter()
+        .filter(|b| in_date_range(&b.start_time, &range))
+        .collect();
+    let tag_sets = super::booking_tags::load_booking_tag_sets(&state_guard.db).await;
+    let tag_columns = super::booking_tags::tag_columns(&tag_sets, &bookings);
+
+    let viewer = PlateViewer::load(&state_guard, auth_user.user_id).await;
     let mut csv = String::from(
-        "id,user_id,lot_name,slot_number,start_time,end_time,status,vehicle_plate,total,currency,payment_status\n",
+        "id,user_id,lot_name,slot_number,start_time,end_time,status,vehicle_plate,total,currency,payment_status",
     );
+    for key in &tag_columns {
+        let _ = write!(csv, ",{}", csv_escape(&format!("tag:{key}")));
+    }
+    csv.push('\n');
 
     for b in &bookings {
-        if !in_date_range(&b.start_time, &range) {
-            continue;
-        }
-
         // Resolve lot name (best-effort)
         let lot_name = match state_guard.db.get_parking_lot(&b.lot_id.to_string()).await {
             Ok(Some(l)) => l.name,
@@ -178,7 +184,9 @@ pub async fn admin_export_bookings_csv(
         csv.push(',');
         csv.push_str(&csv_escape(&format!("{:?}", b.status).to_lowercase()));
         csv.push(',');
-        csv.push_str(&csv_escape(&b.vehicle.license_plate));
+        csv.push_str(&csv_escape(
+            &viewer.plate(b.user_id, &b.vehicle.license_plate),
+        ));
         csv.push(',');
         let _ = write!(csv, "{:.2}", b.pricing.total);
         csv.push(',');
@@ -187,6 +195,10 @@ pub async fn admin_export_bookings_csv(
         csv.push_str(&csv_escape(
             &format!("{:?}", b.pricing.payment_status).to_lowercase(),
         ));
+        for key in &tag_columns {
+            csv.push(',');
+            csv.push_str(&csv_escape(b.tags.get(key).map_or("", String::as_str)));
+        }
         csv.push('\n');
     }
     drop(state_guard);
@@ -218,7 +230,7 @@ pub async fn admin_export_users_csv(
     Query(range): Query<ExportDateRange>,
 ) -> impl IntoResponse {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
+    if let Err((status, msg)) = check_admin(&auth_user) {
         return error_response(status, msg);
     }
 
@@ -263,30 +275,26 @@ pub async fn admin_export_users_csv(
 // Revenue CSV
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// `GET /api/v1/admin/export/revenue` — export revenue summary as CSV (admin only)
+/// `GET /api/v1/admin/export/revenue` — export revenue summary as CSV (admins and reporters)
 #[utoipa::path(
     get,
     path = "/api/v1/admin/export/revenue",
     tag = "Admin",
     summary = "Export revenue summary as CSV",
-    description = "Download a daily revenue summary as a CSV file. Groups completed/active bookings by date. Supports optional date filtering via from and to query params (YYYY-MM-DD). Admin only.",
+    description = "Download a daily revenue summary as a CSV file. Groups completed/active bookings by date. Supports optional date filtering via from and to query params (YYYY-MM-DD). Admins and reporters.",
     params(ExportDateRange),
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "CSV file", content_type = "text/csv"),
-        (status = 403, description = "Admin access required"),
+        (status = 403, description = "Insufficient permissions"),
     )
 )]
 pub async fn admin_export_revenue_csv(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanViewAllBookings>,
     Query(range): Query<ExportDateRange>,
 ) -> impl IntoResponse {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return error_response(status, msg);
-    }
-
     let bookings = match state_guard.db.list_bookings().await {
         Ok(b) => b,
         Err(e) => {
@@ -40,7 +40,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use utoipa::ToSchema;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{AuthUser, SharedState, check_admin};
 use crate::api::retention::RetentionClass;
@@ -470,8 +470,8 @@ pub async fn get_fairness_report(
     Query(params): Query<FairnessReportParams>,
 ) -> (StatusCode, Json<ApiResponse<FairnessReport>>) {
     let state_read = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_read, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let window_to = params.to.unwrap_or_else(Utc::now);
@@ -486,7 +486,7 @@ pub async fn get_fairness_report(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "INTERNAL_ERROR",
+                    ErrorCode::InternalError,
                     "Failed to load audit log",
                 )),
             );
@@ -515,12 +515,10 @@ pub async fn get_fairness_report(
     security(("bearer_auth" = []))
 )]
 pub async fn get_data_collection_disclosure(
-    State(state): State<SharedState>,
     axum::Extension(auth_user): axum::Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<DataCollectionDisclosure>>) {
-    let state_read = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_read, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
     let disclosure = build_disclosure();
     (StatusCode::OK, Json(ApiResponse::success(disclosure)))
@@ -861,138 +859,31 @@ mod tests {
 
     // ── RBAC: non-admin should get 403 ────────────────────────────────────────
     // Full HTTP integration test is deferred to the integration test suite.
-    // Here we test the check_admin helper using an in-memory DB.
-
-    use crate::config::ServerConfig;
-    use crate::db::{Database, DatabaseConfig};
-    use std::sync::Arc;
-    use tokio::sync::RwLock;
-
-    fn make_db() -> (Database, tempfile::TempDir) {
-        let dir = tempfile::tempdir().expect("tempdir");
-        let cfg = DatabaseConfig {
-            path: dir.path().to_path_buf(),
-            encryption_enabled: false,
-            passphrase: None,
-            create_if_missing: true,
-        };
-        (Database::open(&cfg).expect("open db"), dir)
-    }
+    // Here we test the check_admin helper on the resolved caller.
 
-    fn make_state(db: Database) -> super::SharedState {
-        use crate::AppState;
-        Arc::new(RwLock::new(AppState {
-            config: ServerConfig::default(),
-            db,
-            mdns: None,
-            scheduler: None,
-            ws_events: crate::api::ws::EventBroadcaster::new(),
-            fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
-            revocation_store: crate::jwt::TokenRevocationList::new(),
-        }))
+    fn caller(role: parkhub_common::UserRole) -> AuthUser {
+        AuthUser {
+            user_id: Uuid::new_v4(),
+            api_key_id: None,
+            impersonator_id: None,
+            permissions: parkhub_common::ResolvedPermissions::for_role(&role),
+            role,
+        }
     }
 
     /// Non-admin user gets Forbidden from check_admin.
-    #[tokio::test]
-    async fn rbac_non_admin_is_forbidden() {
-        use parkhub_common::User;
-        use parkhub_common::UserRole;
-        use parkhub_common::models::UserPreferences;
-
-        let (db, _dir) = make_db();
-        let user_id = Uuid::new_v4();
-        let regular_user = User {
-            id: user_id,
-            username: "regularuser".to_string(),
-            email: "user@example.com".to_string(),
-            name: "Regular User".to_string(),
-            password_hash: "hash".to_string(),
-            role: UserRole::User,
-            is_active: true,
-            phone: None,
-            picture: None,
-            preferences: UserPreferences {
-                language: "de".to_string(),
-                theme: "system".to_string(),
-                notifications_enabled: true,
-                email_reminders: false,
-                default_duration_minutes: None,
-                favorite_slots: Vec::new(),
-            },
-            credits_balance: 0,
-            credits_monthly_quota: 0,
-            credits_last_refilled: None,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-            last_login: None,
-            tenant_id: None,
-            accessibility_needs: None,
-            cost_center: None,
-            department: None,
-            settings: None,
-        };
-        db.save_user(&regular_user).await.expect("save user");
-
-        let state = make_state(db);
-        let auth_user = AuthUser {
-            user_id,
-            api_key_id: None,
-        };
-        let state_read = state.read().await;
-        let result = check_admin(&state_read, &auth_user).await;
+    #[test]
+    fn rbac_non_admin_is_forbidden() {
+        let result = check_admin(&caller(parkhub_common::UserRole::User));
         assert!(result.is_err(), "non-admin should be rejected");
         let (status, _) = result.unwrap_err();
         assert_eq!(status, StatusCode::FORBIDDEN);
     }
 
     /// Admin user passes check_admin.
-    #[tokio::test]
-    async fn rbac_admin_is_allowed() {
-        use parkhub_common::User;
-        use parkhub_common::UserRole;
-        use parkhub_common::models::UserPreferences;
-
-        let (db, _dir) = make_db();
-        let user_id = Uuid::new_v4();
-        let admin_user = User {
-            id: user_id,
-            username: "adminuser".to_string(),
-            email: "admin@example.com".to_string(),
-            name: "Admin User".to_string(),
-            password_hash: "hash".to_string(),
-            role: UserRole::Admin,
-            is_active: true,
-            phone: None,
-            picture: None,
-            preferences: UserPreferences {
-                language: "de".to_string(),
-                theme: "system".to_string(),
-                notifications_enabled: true,
-                email_reminders: false,
-                default_duration_minutes: None,
-                favorite_slots: Vec::new(),
-            },
-            credits_balance: 0,
-            credits_monthly_quota: 0,
-            credits_last_refilled: None,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-            last_login: None,
-            tenant_id: None,
-            accessibility_needs: None,
-            cost_center: None,
-            department: None,
-            settings: None,
-        };
-        db.save_user(&admin_user).await.expect("save user");
-
-        let state = make_state(db);
-        let auth_user = AuthUser {
-            user_id,
-            api_key_id: None,
-        };
-        let state_read = state.read().await;
-        let result = check_admin(&state_read, &auth_user).await;
+    #[test]
+    fn rbac_admin_is_allowed() {
+        let result = check_admin(&caller(parkhub_common::UserRole::Admin));
         assert!(result.is_ok(), "admin should be allowed");
     }
 }
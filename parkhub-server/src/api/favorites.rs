@@ -1,20 +1,39 @@
-//! Favorites handlers: users can pin preferred parking slots.
+//! Favorites handlers: users can pin preferred parking slots and lots.
+//!
+//! Favorites feed back into availability: `GET /api/v1/lots` and
+//! `GET /api/v1/lots/{id}/slots` list the caller's favorites first, and a
+//! cancelled booking on a favorited slot notifies the users who pinned it
+//! when the freed time falls inside their typical arrival window.
+
+use std::collections::HashSet;
 
 use axum::{
     Extension, Json,
     extract::{Path, State},
     http::StatusCode,
 };
-use chrono::Utc;
-use serde::Deserialize;
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{
+    ApiResponse, Booking, BookingStatus, ErrorCode,
+    models::{Notification, NotificationType},
+};
 
-use crate::db::Favorite;
+use crate::AppState;
+use crate::db::{Favorite, FavoriteLot};
 
 use super::{AuthUser, SharedState};
 
+/// Hours either side of a user's typical arrival hour that still count as
+/// "their" window for favorite-slot notifications.
+const ARRIVAL_WINDOW_HOURS: u32 = 1;
+
+/// Minimum number of past bookings before a typical arrival hour is derived.
+/// Below this the history is too thin to tell habit from coincidence.
+const MIN_BOOKINGS_FOR_ARRIVAL_WINDOW: usize = 3;
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Request DTOs
 // ─────────────────────────────────────────────────────────────────────────────
@@ -27,10 +46,364 @@ pub struct AddFavoriteRequest {
     pub lot_id: Uuid,
 }
 
+/// Request body for `POST /api/v1/users/me/favorites`.
+///
+/// Omitting `slot_id` pins the whole lot; supplying it pins the slot.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AddUserFavoriteRequest {
+    /// Parking lot ID (the lot itself, or the lot the slot belongs to)
+    pub lot_id: Uuid,
+    /// Optional parking slot ID — when absent the lot is favorited
+    #[serde(default)]
+    pub slot_id: Option<Uuid>,
+}
+
+/// Combined favorites view for `GET /api/v1/users/me/favorites`.
+#[derive(Debug, Default, Serialize, utoipa::ToSchema)]
+pub struct UserFavoritesResponse {
+    #[schema(value_type = Vec<Object>)]
+    pub lots: Vec<FavoriteLot>,
+    #[schema(value_type = Vec<Object>)]
+    pub slots: Vec<Favorite>,
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Stable-partition `items` so that entries whose key is in `favorites` come
+/// first. Relative order within both groups is preserved.
+pub fn favorites_first<T>(items: &mut [T], favorites: &HashSet<Uuid>, key: impl Fn(&T) -> Uuid) {
+    items.sort_by_key(|item| !favorites.contains(&key(item)));
+}
+
+/// Favorite lot IDs for `user_id` — lots pinned directly plus lots that
+/// contain a pinned slot. Errors degrade to an empty set.
+pub async fn favorite_lot_ids(state: &AppState, user_id: Uuid) -> HashSet<Uuid> {
+    let uid = user_id.to_string();
+    let mut ids: HashSet<Uuid> = state
+        .db
+        .list_favorite_lots_by_user(&uid)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|f| f.lot_id)
+        .collect();
+    ids.extend(
+        state
+            .db
+            .list_favorites_by_user(&uid)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|f| f.lot_id),
+    );
+    ids
+}
+
+/// Favorite slot IDs for `user_id`. Errors degrade to an empty set.
+pub async fn favorite_slot_ids(state: &AppState, user_id: Uuid) -> HashSet<Uuid> {
+    state
+        .db
+        .list_favorites_by_user(&user_id.to_string())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .map(|f| f.slot_id)
+        .collect()
+}
+
+/// Most common booking start hour (UTC) across a user's non-cancelled
+/// bookings, or `None` when there is not enough history.
+pub fn typical_arrival_hour(bookings: &[Booking]) -> Option<u32> {
+    let mut counts = [0usize; 24];
+    let mut total = 0usize;
+    for b in bookings
+        .iter()
+        .filter(|b| !matches!(b.status, BookingStatus::Cancelled))
+    {
+        counts[b.start_time.hour() as usize] += 1;
+        total += 1;
+    }
+    if total < MIN_BOOKINGS_FOR_ARRIVAL_WINDOW {
+        return None;
+    }
+    counts
+        .iter()
+        .enumerate()
+        .max_by_key(|(hour, count)| (**count, std::cmp::Reverse(*hour)))
+        .and_then(|(hour, _)| u32::try_from(hour).ok())
+}
+
+/// Whether `time` falls within `ARRIVAL_WINDOW_HOURS` of `typical_hour`,
+/// wrapping around midnight.
+pub fn within_arrival_window(typical_hour: u32, time: DateTime<Utc>) -> bool {
+    let diff = typical_hour.abs_diff(time.hour());
+    diff.min(24 - diff) <= ARRIVAL_WINDOW_HOURS
+}
+
+/// Notify users who favorited the slot of a just-freed booking, when the freed
+/// time starts in the future and inside their typical arrival window.
+///
+/// Best-effort: failures are logged and never surface to the caller.
+pub async fn notify_favorite_slot_freed(state: &AppState, freed: &Booking) {
+    if freed.start_time <= Utc::now() {
+        return;
+    }
+    let favs = match state
+        .db
+        .list_favorites_by_slot(&freed.slot_id.to_string())
+        .await
+    {
+        Ok(f) => f,
+        Err(e) => {
+            tracing::warn!(slot_id = %freed.slot_id, "Failed to list slot favorites: {e}");
+            return;
+        }
+    };
+
+    for fav in favs.into_iter().filter(|f| f.user_id != freed.user_id) {
+        let history = state
+            .db
+            .list_bookings_by_user(&fav.user_id.to_string())
+            .await
+            .unwrap_or_default();
+        let Some(hour) = typical_arrival_hour(&history) else {
+            continue;
+        };
+        if !within_arrival_window(hour, freed.start_time) {
+            continue;
+        }
+
+        let notification = Notification {
+            id: Uuid::new_v4(),
+            user_id: fav.user_id,
+            notification_type: NotificationType::FavoriteSlotAvailable,
+            title: "Favorite slot available".to_string(),
+            message: format!(
+                "Your favorite slot {} is free from {}.",
+                freed.slot_number,
+                freed.start_time.format("%Y-%m-%d %H:%M UTC")
+            ),
+            data: Some(serde_json::json!({
+                "lot_id": freed.lot_id,
+                "slot_id": freed.slot_id,
+                "start_time": freed.start_time,
+                "end_time": freed.end_time,
+            })),
+            read: false,
+            created_at: Utc::now(),
+        };
+        if let Err(e) = state.db.save_notification(&notification).await {
+            tracing::warn!(user_id = %fav.user_id, "Failed to save favorite notification: {e}");
+        }
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Handlers
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// `GET /api/v1/users/me/favorites` — list favorite lots and slots
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/favorites",
+    tag = "Favorites",
+    summary = "List favorite lots and slots",
+    description = "Returns the authenticated user's favorite lots and favorite slots.",
+    responses(
+        (status = 200, description = "Favorite lots and slots", body = UserFavoritesResponse),
+    )
+)]
+pub async fn list_my_favorites(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<UserFavoritesResponse>>) {
+    let state = state.read().await;
+    let uid = auth_user.user_id.to_string();
+
+    let lots = state.db.list_favorite_lots_by_user(&uid).await;
+    let slots = state.db.list_favorites_by_user(&uid).await;
+    match (lots, slots) {
+        (Ok(lots), Ok(slots)) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(UserFavoritesResponse { lots, slots })),
+        ),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Database error: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list favorites",
+                )),
+            )
+        }
+    }
+}
+
+/// `POST /api/v1/users/me/favorites` — favorite a lot or a slot
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/me/favorites",
+    tag = "Favorites",
+    summary = "Add a favorite lot or slot",
+    description = "Pin a parking lot (omit `slot_id`) or a specific slot as a favorite. \
+        Favorites are listed first in lot and slot availability responses.",
+    request_body = AddUserFavoriteRequest,
+    responses(
+        (status = 201, description = "Favorite added"),
+        (status = 404, description = "Parking lot or slot not found"),
+        (status = 409, description = "Already a favorite"),
+    )
+)]
+pub async fn add_my_favorite(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<AddUserFavoriteRequest>,
+) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
+    let Some(slot_id) = req.slot_id else {
+        return add_favorite_lot(&state, auth_user.user_id, req.lot_id).await;
+    };
+
+    let (status, Json(resp)) = add_favorite(
+        State(state),
+        Extension(auth_user),
+        Json(AddFavoriteRequest {
+            slot_id,
+            lot_id: req.lot_id,
+        }),
+    )
+    .await;
+    (
+        status,
+        Json(ApiResponse {
+            success: resp.success,
+            data: resp.data.map(|fav| serde_json::json!(fav)),
+            error: resp.error,
+            meta: resp.meta,
+        }),
+    )
+}
+
+async fn add_favorite_lot(
+    state: &SharedState,
+    user_id: Uuid,
+    lot_id: Uuid,
+) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
+    let state_guard = state.read().await;
+
+    match state_guard.db.get_parking_lot(&lot_id.to_string()).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    }
+
+    if let Ok(existing) = state_guard
+        .db
+        .list_favorite_lots_by_user(&user_id.to_string())
+        .await
+        && existing.iter().any(|f| f.lot_id == lot_id)
+    {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::Duplicate,
+                "Lot already in favorites",
+            )),
+        );
+    }
+
+    let fav = FavoriteLot {
+        user_id,
+        lot_id,
+        created_at: Utc::now(),
+    };
+    if let Err(e) = state_guard.db.save_favorite_lot(&fav).await {
+        tracing::error!("Failed to save favorite lot: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to add favorite",
+            )),
+        );
+    }
+    drop(state_guard);
+
+    tracing::info!("User {} favorited lot {}", fav.user_id, fav.lot_id);
+    (
+        StatusCode::CREATED,
+        Json(ApiResponse::success(serde_json::json!(fav))),
+    )
+}
+
+/// `DELETE /api/v1/users/me/favorites/lots/{lot_id}` — remove a favorite lot
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/me/favorites/lots/{lot_id}",
+    tag = "Favorites",
+    summary = "Remove a favorite lot",
+    description = "Unpin a parking lot from the user's favorites.",
+    params(("lot_id" = String, Path, description = "Parking lot ID")),
+    responses(
+        (status = 200, description = "Favorite removed"),
+        (status = 404, description = "Favorite not found"),
+    )
+)]
+pub async fn remove_my_favorite_lot(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.read().await;
+
+    match state_guard
+        .db
+        .delete_favorite_lot(&auth_user.user_id.to_string(), &lot_id)
+        .await
+    {
+        Ok(true) => {
+            tracing::info!("User {} removed favorite lot {}", auth_user.user_id, lot_id);
+            (StatusCode::OK, Json(ApiResponse::success(())))
+        }
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Favorite not found",
+            )),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to delete favorite lot: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to remove favorite",
+                )),
+            )
+        }
+    }
+}
+
 /// `GET /api/v1/user/favorites` — list the authenticated user's favorite slots
 #[utoipa::path(
     get,
@@ -57,7 +430,7 @@ pub async fn list_favorites(
         Err(e) => {
             tracing::error!("Database error: {}", e);
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to list favorites",
             ))
         }
@@ -94,14 +467,20 @@ pub async fn add_favorite(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking slot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking slot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     }
@@ -115,7 +494,10 @@ pub async fn add_favorite(
     {
         return (
             StatusCode::CONFLICT,
-            Json(ApiResponse::error("DUPLICATE", "Slot already in favorites")),
+            Json(ApiResponse::error(
+                ErrorCode::Duplicate,
+                "Slot already in favorites",
+            )),
         );
     }
 
@@ -130,7 +512,10 @@ pub async fn add_favorite(
         tracing::error!("Failed to save favorite: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to add favorite")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to add favorite",
+            )),
         );
     }
     drop(state_guard);
@@ -180,14 +565,17 @@ pub async fn remove_favorite(
         }
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Favorite not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Favorite not found",
+            )),
         ),
         Err(e) => {
             tracing::error!("Failed to delete favorite: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to remove favorite",
                 )),
             )
@@ -241,4 +629,108 @@ mod tests {
         assert_eq!(fav.slot_id, deserialized.slot_id);
         assert_eq!(fav.lot_id, deserialized.lot_id);
     }
+
+    fn booking_at(hour: u32, status: BookingStatus) -> Booking {
+        use chrono::TimeZone;
+        use parkhub_common::{BookingPricing, PaymentStatus, Vehicle};
+        let start = Utc.with_ymd_and_hms(2026, 3, 2, hour, 15, 0).unwrap();
+        Booking {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            lot_id: Uuid::new_v4(),
+            slot_id: Uuid::new_v4(),
+            slot_number: 1,
+            floor_name: "Ground".to_string(),
+            vehicle: Vehicle {
+                id: Uuid::new_v4(),
+                user_id: Uuid::new_v4(),
+                license_plate: "M-AB 123".to_string(),
+                make: None,
+                model: None,
+                color: None,
+                vehicle_type: parkhub_common::VehicleType::Car,
+                fuel_type: parkhub_common::FuelType::Unknown,
+                is_default: true,
+                created_at: start,
+            },
+            start_time: start,
+            end_time: start + chrono::Duration::hours(8),
+            status,
+            pricing: BookingPricing {
+                base_price: 0.0,
+                discount: 0.0,
+                tax: 0.0,
+                total: 0.0,
+                currency: "EUR".to_string(),
+                payment_status: PaymentStatus::Pending,
+                payment_method: None,
+                cancellation_fee: None,
+            },
+            created_at: start,
+            updated_at: start,
+            check_in_time: None,
+            check_out_time: None,
+            qr_code: None,
+            notes: None,
+            tenant_id: None,
+            tags: std::collections::BTreeMap::new(),
+            booked_for: None,
+        }
+    }
+
+    #[test]
+    fn test_add_user_favorite_request_slot_optional() {
+        let json = r#"{"lot_id":"660e8400-e29b-41d4-a716-446655440001"}"#;
+        let req: AddUserFavoriteRequest = serde_json::from_str(json).unwrap();
+        assert!(req.slot_id.is_none());
+    }
+
+    #[test]
+    fn test_favorites_first_is_stable() {
+        let a = Uuid::new_v4();
+        let b = Uuid::new_v4();
+        let c = Uuid::new_v4();
+        let mut items = vec![a, b, c];
+        let favs: HashSet<Uuid> = std::iter::once(c).collect();
+        favorites_first(&mut items, &favs, |id| *id);
+        assert_eq!(items, vec![c, a, b]);
+    }
+
+    #[test]
+    fn test_typical_arrival_hour_needs_history() {
+        let bookings = vec![
+            booking_at(8, BookingStatus::Completed),
+            booking_at(8, BookingStatus::Completed),
+        ];
+        assert_eq!(typical_arrival_hour(&bookings), None);
+    }
+
+    #[test]
+    fn test_typical_arrival_hour_ignores_cancelled() {
+        let bookings = vec![
+            booking_at(8, BookingStatus::Completed),
+            booking_at(8, BookingStatus::Confirmed),
+            booking_at(9, BookingStatus::Completed),
+            booking_at(17, BookingStatus::Cancelled),
+            booking_at(17, BookingStatus::Cancelled),
+            booking_at(17, BookingStatus::Cancelled),
+        ];
+        assert_eq!(typical_arrival_hour(&bookings), Some(8));
+    }
+
+    #[test]
+    fn test_within_arrival_window_wraps_midnight() {
+        assert!(within_arrival_window(
+            23,
+            booking_at(0, BookingStatus::Pending).start_time
+        ));
+        assert!(within_arrival_window(
+            8,
+            booking_at(9, BookingStatus::Pending).start_time
+        ));
+        assert!(!within_arrival_window(
+            8,
+            booking_at(11, BookingStatus::Pending).start_time
+        ));
+    }
 }
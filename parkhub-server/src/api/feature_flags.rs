@@ -0,0 +1,360 @@
+//! Feature flags: per-deployment switches for whole subsystems.
+//!
+//! - `GET /api/v1/admin/feature-flags` — every flag and whether it is on
+//! - `PUT /api/v1/admin/feature-flags/{key}` — switch a flag on or off
+//!
+//! Cargo features decide what is compiled in and module runtime toggles
+//! (see [`super::modules`]) only cover low-risk modules; flags let an
+//! operator hold back any subsystem listed in [`FLAGS`] on one deployment,
+//! and let a new subsystem ship dark behind a flag that is off by default.
+//! Switches are stored in the database, so they apply to this server only
+//! and survive restarts and demo resets.
+//!
+//! Handlers of a flagged subsystem take a [`RequireFlag`] extractor, which
+//! answers `404 MODULE_DISABLED` while the flag is off, the same as a
+//! module that is not there. Clients learn the outcome from the handshake
+//! `features`, see [`handshake_features`].
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::marker::PhantomData;
+
+use axum::{
+    Json,
+    extract::{FromRequestParts, Path, State},
+    http::{StatusCode, request::Parts},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{Database, FeatureFlagSwitch};
+use crate::requests::SetFeatureFlagRequest;
+use crate::validation::ValidatedJson;
+
+use super::SharedState;
+use super::modules::module_registry;
+use super::permissions::{CanManageConfig, RequirePermission};
+
+/// A feature flag and what it covers.
+pub struct FlagDef {
+    /// Stable kebab-case key, as used in the API and the handshake
+    pub key: &'static str,
+    pub description: &'static str,
+    /// State until an admin switches the flag
+    pub default_enabled: bool,
+    /// Modules left out of the handshake `features` while the flag is off
+    pub modules: &'static [&'static str],
+}
+
+/// Every feature flag. Adding a subsystem = add a row, a marker type
+/// implementing [`Flag`] and a [`RequireFlag`] on its handlers.
+pub const FLAGS: &[FlagDef] = &[
+    FlagDef {
+        key: "waitlist",
+        description: "Waitlists for full lots, including offers of freed slots.",
+        default_enabled: true,
+        modules: &["waitlist", "waitlist-ext"],
+    },
+    FlagDef {
+        key: "payments",
+        description: "Payments and Stripe checkout. The Stripe webhook stays \
+            reachable so payments already started are still settled.",
+        default_enabled: true,
+        modules: &["payments", "stripe"],
+    },
+    FlagDef {
+        key: "kiosk-mode",
+        description: "Per-lot kiosk / lobby display data for screens at the entrance.",
+        default_enabled: true,
+        modules: &["lobby-display"],
+    },
+    FlagDef {
+        key: "public-signage",
+        description: "Public occupancy feed and HTML page for digital signage.",
+        default_enabled: true,
+        modules: &[],
+    },
+];
+
+fn find_flag(key: &str) -> Option<&'static FlagDef> {
+    FLAGS.iter().find(|flag| flag.key == key)
+}
+
+/// Whether flag `key` is on: the stored switch if an admin set one,
+/// otherwise the flag's default. Unknown keys are off; lookup errors fall
+/// back to the default.
+pub async fn is_enabled(db: &Database, key: &str) -> bool {
+    let Some(flag) = find_flag(key) else {
+        return false;
+    };
+    match db.get_feature_flag(key).await {
+        Ok(Some(switch)) => switch.enabled,
+        Ok(None) => flag.default_enabled,
+        Err(e) => {
+            tracing::warn!(flag = key, "Failed to read feature flag: {e}");
+            flag.default_enabled
+        }
+    }
+}
+
+/// A feature flag with its current state.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct FeatureFlagInfo {
+    pub key: String,
+    pub description: String,
+    pub enabled: bool,
+    pub default_enabled: bool,
+    /// Modules left out of the handshake `features` while the flag is off
+    pub modules: Vec<String>,
+    /// Admin who last switched the flag; `None` while it has its default
+    pub updated_by: Option<Uuid>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl FeatureFlagInfo {
+    fn new(flag: &FlagDef, switch: Option<&FeatureFlagSwitch>) -> Self {
+        Self {
+            key: flag.key.to_string(),
+            description: flag.description.to_string(),
+            enabled: switch.map_or(flag.default_enabled, |s| s.enabled),
+            default_enabled: flag.default_enabled,
+            modules: flag.modules.iter().map(ToString::to_string).collect(),
+            updated_by: switch.map(|s| s.updated_by),
+            updated_at: switch.map(|s| s.updated_at),
+        }
+    }
+}
+
+/// Every flag in [`FLAGS`] order with its current state. Switches for
+/// flags that no longer exist are ignored.
+pub async fn flag_states(db: &Database) -> Vec<FeatureFlagInfo> {
+    let switches = db.list_feature_flags().await.unwrap_or_else(|e| {
+        tracing::warn!("Failed to read feature flags: {e}");
+        Vec::new()
+    });
+    FLAGS
+        .iter()
+        .map(|flag| FeatureFlagInfo::new(flag, switches.iter().find(|s| s.key == flag.key)))
+        .collect()
+}
+
+/// Capabilities for the handshake: runtime-enabled modules, except those a
+/// switched-off flag covers, followed by the keys of the flags that are on.
+pub async fn handshake_features(db: &Database) -> Vec<String> {
+    let flags = flag_states(db).await;
+    let hidden = |module: &str| {
+        flags
+            .iter()
+            .any(|flag| !flag.enabled && flag.modules.iter().any(|m| m == module))
+    };
+    let mut features: Vec<String> = module_registry(db)
+        .await
+        .into_iter()
+        .filter(|module| module.runtime_enabled && !hidden(module.name.as_str()))
+        .map(|module| module.name)
+        .collect();
+    for flag in flags.into_iter().filter(|flag| flag.enabled) {
+        if !features.contains(&flag.key) {
+            features.push(flag.key);
+        }
+    }
+    features
+}
+
+/// Type-level name of a flag in [`FLAGS`], for use with [`RequireFlag`].
+pub trait Flag: Send + Sync {
+    const KEY: &'static str;
+}
+
+/// The `waitlist` flag
+pub struct WaitlistFlag;
+/// The `payments` flag
+#[cfg_attr(
+    not(any(feature = "mod-payments", feature = "mod-stripe")),
+    allow(dead_code)
+)]
+pub struct PaymentsFlag;
+/// The `kiosk-mode` flag
+#[cfg_attr(not(feature = "mod-lobby-display"), allow(dead_code))]
+pub struct KioskModeFlag;
+/// The `public-signage` flag
+pub struct PublicSignageFlag;
+
+impl Flag for WaitlistFlag {
+    const KEY: &'static str = "waitlist";
+}
+impl Flag for PaymentsFlag {
+    const KEY: &'static str = "payments";
+}
+impl Flag for KioskModeFlag {
+    const KEY: &'static str = "kiosk-mode";
+}
+impl Flag for PublicSignageFlag {
+    const KEY: &'static str = "public-signage";
+}
+
+/// Extractor that admits the request only while flag `F` is on.
+pub struct RequireFlag<F: Flag>(pub PhantomData<F>);
+
+impl<F: Flag> FromRequestParts<SharedState> for RequireFlag<F> {
+    type Rejection = (StatusCode, Json<ApiResponse<()>>);
+
+    async fn from_request_parts(
+        _parts: &mut Parts,
+        state: &SharedState,
+    ) -> Result<Self, Self::Rejection> {
+        let state = state.read().await;
+        if is_enabled(&state.db, F::KEY).await {
+            return Ok(Self(PhantomData));
+        }
+        Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::ModuleDisabled,
+                format!("Feature '{}' is disabled", F::KEY),
+            )),
+        ))
+    }
+}
+
+/// `GET /api/v1/admin/feature-flags` — list feature flags.
+#[utoipa::path(get, path = "/api/v1/admin/feature-flags", tag = "Admin",
+    summary = "List feature flags (admin)",
+    description = "Every feature flag of this deployment, whether it is on, and who last switched it.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Feature flags", body = Vec<FeatureFlagInfo>),
+        (status = 403, description = "Admin only"),
+    )
+)]
+pub async fn admin_list_feature_flags(
+    State(state): State<SharedState>,
+    _: RequirePermission<CanManageConfig>,
+) -> (StatusCode, Json<ApiResponse<Vec<FeatureFlagInfo>>>) {
+    let state_guard = state.read().await;
+    let flags = flag_states(&state_guard.db).await;
+    (StatusCode::OK, Json(ApiResponse::success(flags)))
+}
+
+/// `PUT /api/v1/admin/feature-flags/{key}` — switch a feature flag.
+#[utoipa::path(put, path = "/api/v1/admin/feature-flags/{key}", tag = "Admin",
+    summary = "Switch a feature flag (admin)",
+    description = "Turns a feature flag on or off for this deployment. While a flag is off its \
+        endpoints answer 404 `MODULE_DISABLED` and it is left out of the handshake `features`. \
+        Audit-logged.",
+    security(("bearer_auth" = [])),
+    params(("key" = String, Path, description = "Flag key, e.g. `kiosk-mode`")),
+    request_body = SetFeatureFlagRequest,
+    responses(
+        (status = 200, description = "Updated flag", body = FeatureFlagInfo),
+        (status = 403, description = "Admin only"),
+        (status = 404, description = "Unknown flag"),
+    )
+)]
+pub async fn admin_set_feature_flag(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageConfig>,
+    Path(key): Path<String>,
+    ValidatedJson(req): ValidatedJson<SetFeatureFlagRequest>,
+) -> (StatusCode, Json<ApiResponse<FeatureFlagInfo>>) {
+    let Some(flag) = find_flag(&key) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                format!("Unknown feature flag: {key}"),
+            )),
+        );
+    };
+
+    let state_guard = state.read().await;
+    let switch = FeatureFlagSwitch {
+        key: flag.key.to_string(),
+        enabled: req.enabled,
+        updated_by: auth_user.user_id,
+        updated_at: Utc::now(),
+    };
+    if let Err(e) = state_guard.db.save_feature_flag(&switch).await {
+        tracing::error!(flag = flag.key, "Failed to save feature flag: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save feature flag",
+            )),
+        );
+    }
+
+    if state_guard.config.audit_logging_enabled {
+        let username = state_guard
+            .db
+            .get_user(&auth_user.user_id.to_string())
+            .await
+            .ok()
+            .flatten()
+            .map(|u| u.username)
+            .unwrap_or_default();
+        AuditEntry::new(AuditEventType::ConfigChanged)
+            .user(auth_user.user_id, &username)
+            .resource("feature_flag", flag.key)
+            .details(serde_json::json!({ "enabled": req.enabled }))
+            .log()
+            .persist(&state_guard.db)
+            .await;
+    }
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(FeatureFlagInfo::new(
+            flag,
+            Some(&switch),
+        ))),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_keys_are_unique_kebab_case() {
+        let mut seen = std::collections::HashSet::new();
+        for flag in FLAGS {
+            assert!(seen.insert(flag.key), "duplicate flag {}", flag.key);
+            assert!(
+                flag.key.chars().all(|c| c.is_ascii_lowercase() || c == '-'),
+                "flag key {} is not kebab-case",
+                flag.key
+            );
+        }
+    }
+
+    #[test]
+    fn test_markers_and_modules_name_known_entries() {
+        for key in [
+            WaitlistFlag::KEY,
+            PaymentsFlag::KEY,
+            KioskModeFlag::KEY,
+            PublicSignageFlag::KEY,
+        ] {
+            assert!(find_flag(key).is_some(), "marker for unknown flag {key}");
+        }
+        let modules = crate::api::modules::module_registry_static();
+        for flag in FLAGS {
+            for module in flag.modules {
+                assert!(
+                    modules.iter().any(|m| m.name == *module),
+                    "flag {} covers unknown module {module}",
+                    flag.key
+                );
+            }
+        }
+    }
+}
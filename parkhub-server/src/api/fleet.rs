@@ -20,7 +20,7 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, VehicleType};
+use parkhub_common::{ApiResponse, ErrorCode, VehicleType};
 
 use super::{AuthUser, check_admin};
 use crate::AppState;
@@ -87,8 +87,8 @@ pub async fn admin_fleet_list(
     axum::extract::Query(params): axum::extract::Query<HashMap<String, String>>,
 ) -> (StatusCode, Json<ApiResponse<Vec<FleetVehicle>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let vehicles = match state_guard.db.list_all_vehicles().await {
@@ -98,7 +98,7 @@ pub async fn admin_fleet_list(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list vehicles",
                 )),
             );
@@ -199,8 +199,8 @@ pub async fn admin_fleet_stats(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<FleetStats>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let vehicles = match state_guard.db.list_all_vehicles().await {
@@ -210,7 +210,7 @@ pub async fn admin_fleet_stats(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to get fleet stats",
                 )),
             );
@@ -281,8 +281,8 @@ pub async fn admin_fleet_flag(
     Json(req): Json<FlagVehicleRequest>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     // Verify vehicle exists
@@ -291,7 +291,7 @@ pub async fn admin_fleet_flag(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Vehicle not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Vehicle not found")),
             );
         }
         Err(e) => {
@@ -299,7 +299,7 @@ pub async fn admin_fleet_flag(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to look up vehicle",
                 )),
             );
@@ -313,7 +313,10 @@ pub async fn admin_fleet_flag(
             tracing::error!("Failed to flag vehicle {id}: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to flag vehicle")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to flag vehicle",
+                )),
             );
         }
     } else if let Err(e) = state_guard.db.set_setting(&flag_key, "").await {
@@ -321,7 +324,7 @@ pub async fn admin_fleet_flag(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to unflag vehicle",
             )),
         );
@@ -12,7 +12,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, BookingStatus};
+use parkhub_common::{ApiResponse, BookingStatus, ErrorCode};
 
 use super::{AuthUser, SharedState, check_admin};
 
@@ -173,13 +173,16 @@ pub async fn get_lot_geofence(
         }
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Lot not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Lot not found")),
         ),
         Err(e) => {
             tracing::error!(error = %e, "Failed to get lot geofence");
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to get geofence")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to get geofence",
+                )),
             )
         }
     }
@@ -194,8 +197,8 @@ pub async fn admin_set_geofence(
     Json(req): Json<SetGeofenceRequest>,
 ) -> (StatusCode, Json<ApiResponse<GeofenceConfig>>) {
     let state = state.read().await;
-    if let Err((status, msg)) = check_admin(&state, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     // Validate radius
@@ -203,7 +206,7 @@ pub async fn admin_set_geofence(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_RADIUS",
+                ErrorCode::InvalidRadius,
                 "Radius must be between 0 and 10000 meters",
             )),
         );
@@ -218,7 +221,7 @@ pub async fn admin_set_geofence(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_COORDINATES",
+                ErrorCode::InvalidCoordinates,
                 "Invalid latitude or longitude",
             )),
         );
@@ -248,13 +251,16 @@ pub async fn admin_set_geofence(
         }
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Lot not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Lot not found")),
         ),
         Err(e) => {
             tracing::error!(error = %e, "Failed to set geofence");
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to set geofence")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to set geofence",
+                )),
             )
         }
     }
@@ -14,9 +14,9 @@ use serde::Deserialize;
 use uuid::Uuid;
 
 use parkhub_common::models::GuestBooking;
-use parkhub_common::{ApiResponse, BookingStatus};
+use parkhub_common::{ApiResponse, BookingStatus, ErrorCode};
 
-use super::settings::read_admin_setting;
+use super::lot_settings::LotConfig;
 use super::{AuthUser, SharedState, check_admin};
 
 /// Request body for creating a guest booking
@@ -60,13 +60,15 @@ pub async fn create_guest_booking(
 ) -> (StatusCode, Json<ApiResponse<GuestBooking>>) {
     let state_guard = state.read().await;
 
-    // Check allow_guest_bookings setting
-    let allowed = read_admin_setting(&state_guard.db, "allow_guest_bookings").await;
-    if allowed != "true" {
+    // Check allow_guest_bookings setting, as overridden for the lot
+    let allowed = LotConfig::resolve(&state_guard, &req.lot_id.to_string())
+        .await
+        .allow_guest_bookings;
+    if !allowed {
         return (
             StatusCode::UNPROCESSABLE_ENTITY,
             Json(ApiResponse::error(
-                "GUEST_BOOKINGS_DISABLED",
+                ErrorCode::GuestBookingsDisabled,
                 "Guest bookings are not enabled",
             )),
         );
@@ -92,7 +94,7 @@ pub async fn create_guest_booking(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to create guest booking",
             )),
         );
@@ -143,7 +145,7 @@ pub async fn list_user_guest_bookings(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list guest bookings",
                 )),
             )
@@ -165,8 +167,8 @@ pub async fn admin_list_guest_bookings(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<Vec<GuestBooking>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     match state_guard.db.list_guest_bookings().await {
@@ -176,7 +178,7 @@ pub async fn admin_list_guest_bookings(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list guest bookings",
                 )),
             )
@@ -199,8 +201,8 @@ pub async fn admin_cancel_guest_booking(
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<GuestBooking>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let mut booking = match state_guard.db.get_guest_booking(&id).await {
@@ -208,14 +210,20 @@ pub async fn admin_cancel_guest_booking(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Guest booking not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Guest booking not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -227,7 +235,7 @@ pub async fn admin_cancel_guest_booking(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to cancel guest booking",
             )),
         );
@@ -17,7 +17,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, Booking, BookingStatus, CreditTransactionType};
+use parkhub_common::{ApiResponse, Booking, BookingStatus, CreditTransactionType, ErrorCode};
 
 use super::{AuthUser, SharedState};
 
@@ -86,7 +86,10 @@ pub async fn booking_history(
             tracing::error!(error = %e, "Failed to list booking history");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to load history")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to load history",
+                )),
             );
         }
     };
@@ -0,0 +1,230 @@
+//! Impersonation for support admins.
+//!
+//! - `POST /api/v1/admin/users/:id/impersonate` — start a session as a user
+//!
+//! The session belongs to the impersonated user, so the admin sees and does
+//! exactly what that user can. It lasts at most [`IMPERSONATION_LIFETIME`],
+//! comes without a refresh token and never sets the auth cookie, so the
+//! admin's own browser session stays intact. Its access token names the
+//! admin in the `impersonator` claim; the auth middleware writes every
+//! request made with it to the audit log via [`audit_request`], with both
+//! identities.
+//!
+//! Admins may impersonate users within their tenant. Impersonating another
+//! admin needs a SuperAdmin, and an impersonation session cannot start
+//! another one.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::net::SocketAddr;
+
+use axum::{
+    Extension, Json,
+    extract::{ConnectInfo, Path, State},
+    http::{HeaderMap, Method, StatusCode},
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode, UserRole};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::Session;
+
+use super::admin::AdminUserResponse;
+use super::permissions::{CanManageUsers, RequirePermission};
+use super::security::LoginClient;
+use super::{SharedState, session_tokens};
+
+/// Longest an impersonation session lasts.
+pub const IMPERSONATION_LIFETIME: chrono::Duration = chrono::Duration::minutes(30);
+
+/// A started impersonation session.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct ImpersonationResponse {
+    /// Bearer token acting as the user
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_at: DateTime<Utc>,
+    /// The impersonated user
+    pub user: AdminUserResponse,
+    pub impersonator_id: Uuid,
+}
+
+fn error(
+    status: StatusCode,
+    code: ErrorCode,
+    message: &str,
+) -> (StatusCode, Json<ApiResponse<ImpersonationResponse>>) {
+    (status, Json(ApiResponse::error(code, message)))
+}
+
+/// `POST /api/v1/admin/users/{id}/impersonate` — act as a user for support
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/users/{id}/impersonate",
+    tag = "Admin",
+    summary = "Impersonate a user (admin)",
+    description = "Issues a short-lived access token for the user, tagged with the calling admin. Every request made with it is audited with both identities.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "User UUID")),
+    responses(
+        (status = 200, description = "Impersonation session started", body = ImpersonationResponse),
+        (status = 400, description = "Target is the caller or disabled"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Not found"),
+    )
+)]
+#[tracing::instrument(skip(state, headers, connect_info), fields(admin_id = %auth_user.user_id, target_user_id = %id))]
+pub async fn impersonate_user(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<ImpersonationResponse>>) {
+    if auth_user.impersonator_id.is_some() {
+        return error(
+            StatusCode::FORBIDDEN,
+            ErrorCode::Forbidden,
+            "Cannot impersonate from an impersonation session",
+        );
+    }
+
+    let state_guard = state.read().await;
+    let target = match state_guard.db.get_user(&id).await {
+        Ok(Some(u)) => u,
+        Ok(None) => return error(StatusCode::NOT_FOUND, ErrorCode::NotFound, "User not found"),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorCode::ServerError,
+                "Internal server error",
+            );
+        }
+    };
+
+    // T-1737: cross-tenant admin-write guard — see `admin_update_user_role`
+    let caller_tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
+    if !super::matches_tenant(target.tenant_id.as_deref(), caller_tenant_id.as_deref()) {
+        return error(StatusCode::NOT_FOUND, ErrorCode::NotFound, "User not found");
+    }
+    if target.id == auth_user.user_id {
+        return error(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::ValidationError,
+            "Cannot impersonate yourself",
+        );
+    }
+    if !target.is_active {
+        return error(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::AccountDisabled,
+            "Cannot impersonate a disabled account",
+        );
+    }
+    // Impersonating an admin would hand their privileges to the caller
+    if matches!(target.role, UserRole::Admin | UserRole::SuperAdmin)
+        && auth_user.role != UserRole::SuperAdmin
+    {
+        return error(
+            StatusCode::FORBIDDEN,
+            ErrorCode::Forbidden,
+            "Only a SuperAdmin can impersonate an admin",
+        );
+    }
+
+    let client = LoginClient::from_request(&headers, connect_info.map(|Extension(ci)| ci.0));
+    let session_hours = i64::from(state_guard.config.session_lifetime_hours).max(1);
+    let role_str = format!("{:?}", target.role).to_lowercase();
+    let session = Session::new(target.id, session_hours, &target.username, &role_str)
+        .with_client(Some(client.ip.clone()), client.user_agent.clone())
+        .impersonated_by(auth_user.user_id, IMPERSONATION_LIFETIME);
+    let access_token = match session_tokens::start_session(&state_guard.db, &session).await {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to save impersonation session: {}", e);
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorCode::ServerError,
+                "Failed to create session",
+            );
+        }
+    };
+
+    let admin_username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    let mut audit = AuditEntry::new(AuditEventType::ImpersonationStarted)
+        .user(auth_user.user_id, &admin_username)
+        .resource("user", &target.id.to_string())
+        .details(serde_json::json!({
+            "impersonated_user_id": target.id,
+            "impersonated_username": target.username,
+            "expires_at": session.expires_at,
+        }));
+    if let Ok(ip) = client.ip.parse() {
+        audit = audit.ip(ip);
+    }
+    audit.log().persist(&state_guard.db).await;
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(ImpersonationResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_at: session.expires_at,
+            user: AdminUserResponse::from(&target),
+            impersonator_id: auth_user.user_id,
+        })),
+    )
+}
+
+/// A request made with an impersonation session.
+pub struct ImpersonatedRequest<'a> {
+    pub user_id: Uuid,
+    pub impersonator_id: Uuid,
+    pub client_ip: &'a str,
+    pub method: &'a Method,
+    pub path: &'a str,
+}
+
+/// Write `request` and the status it was answered with to the audit log,
+/// naming both the impersonated user and the admin.
+pub async fn audit_request(
+    state: &SharedState,
+    request: ImpersonatedRequest<'_>,
+    status: StatusCode,
+) {
+    let state_guard = state.read().await;
+    let username = state_guard
+        .db
+        .get_user(&request.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    let mut audit = AuditEntry::new(AuditEventType::ImpersonatedRequest)
+        .user(request.user_id, &username)
+        .details(serde_json::json!({
+            "impersonator_id": request.impersonator_id,
+            "method": request.method.as_str(),
+            "path": request.path,
+            "status": status.as_u16(),
+        }))
+        .success(!status.is_client_error() && !status.is_server_error());
+    if let Ok(ip) = request.client_ip.parse() {
+        audit = audit.ip(ip);
+    }
+    audit.log().persist(&state_guard.db).await;
+}
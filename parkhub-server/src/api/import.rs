@@ -1,15 +1,25 @@
 //! Import endpoints: bulk CSV user creation and iCal absence import.
 //!
 //! - `POST /api/v1/admin/users/import` — import users from CSV (admin only)
+//! - `POST /api/v1/admin/lots/import` — import lots, floors, and slots from CSV/JSON (admin only)
 //! - `POST /api/v1/absences/import/ical` — import absences from iCal (user-scoped)
 
-use axum::{Extension, Json, extract::State, http::StatusCode};
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header},
+};
 use base64::Engine;
 use chrono::Utc;
 use uuid::Uuid;
 
-use parkhub_common::models::{Absence, AbsenceType};
-use parkhub_common::{ApiResponse, User, UserPreferences, UserRole};
+use parkhub_common::models::{Absence, AbsenceType, SlotPosition, SlotType};
+use parkhub_common::{
+    ApiResponse, ErrorCode, LotStatus, OperatingHours, ParkingFloor, ParkingLot, ParkingSlot,
+    PricingInfo, PricingRate, SlotStatus, User, UserPreferences, UserRole,
+};
 
 use super::hash_password_simple;
 use super::{AuthUser, SharedState, check_admin};
@@ -109,6 +119,7 @@ fn parse_csv_line(line: &str) -> Result<CsvRow, (String, String)> {
 fn parse_role(role_str: &str) -> UserRole {
     match role_str.to_lowercase().as_str() {
         "premium" => UserRole::Premium,
+        "reporter" => UserRole::Reporter,
         "admin" => UserRole::Admin,
         "superadmin" | "super_admin" => UserRole::SuperAdmin,
         _ => UserRole::User,
@@ -155,8 +166,8 @@ pub async fn import_users_csv(
 ) -> (StatusCode, Json<ApiResponse<ImportResult>>) {
     let state_guard = state.read().await;
 
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     // T-1731: imported users inherit the caller's tenant_id (bulk CSV import
@@ -173,7 +184,7 @@ pub async fn import_users_csv(
     if lines.is_empty() {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("EMPTY_CSV", "CSV body is empty")),
+            Json(ApiResponse::error(ErrorCode::EmptyCsv, "CSV body is empty")),
         );
     }
 
@@ -200,7 +211,7 @@ pub async fn import_users_csv(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "TOO_MANY_ROWS",
+                ErrorCode::TooManyRows,
                 format!("CSV exceeds maximum of {MAX_IMPORT_ROWS} rows"),
             )),
         );
@@ -308,6 +319,7 @@ pub async fn import_users_csv(
                 email_reminders: true,
                 language: "en".to_string(),
                 theme: "dark".to_string(),
+                accessibility: None,
             },
             is_active: true,
             credits_balance: 40,
@@ -319,6 +331,8 @@ pub async fn import_users_csv(
             cost_center: None,
             department: None,
             settings: None,
+            // The password came from the CSV or was generated for it
+            must_change_password: true,
         };
 
         // Persist
@@ -493,7 +507,10 @@ pub async fn import_absences_ical(
     if body.trim().is_empty() {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("EMPTY_ICAL", "iCal body is empty")),
+            Json(ApiResponse::error(
+                ErrorCode::EmptyIcal,
+                "iCal body is empty",
+            )),
         );
     }
 
@@ -556,6 +573,607 @@ pub async fn import_absences_ical(
     )
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Bulk lot / slot import
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Maximum number of slots accepted in a single lot import.
+const MAX_IMPORT_SLOTS: usize = 5_000;
+
+/// Maximum number of slots per imported lot (matches `POST /api/v1/lots`).
+const MAX_SLOTS_PER_LOT: usize = 500;
+
+/// JSON body for `POST /api/v1/admin/lots/import`.
+#[derive(Debug, Default, serde::Deserialize, utoipa::ToSchema)]
+pub struct LotImportDocument {
+    pub lots: Vec<LotImportDef>,
+}
+
+/// One lot to import, with its floors and slots.
+#[derive(Debug, Default, serde::Deserialize, utoipa::ToSchema)]
+pub struct LotImportDef {
+    pub name: String,
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub latitude: Option<f64>,
+    #[serde(default)]
+    pub longitude: Option<f64>,
+    /// ISO 4217 currency code (defaults to EUR)
+    #[serde(default)]
+    pub currency: Option<String>,
+    #[serde(default)]
+    pub hourly_rate: Option<f64>,
+    #[serde(default)]
+    pub daily_max: Option<f64>,
+    pub floors: Vec<FloorImportDef>,
+    /// CSV line the lot was first seen on (CSV imports only).
+    #[serde(skip)]
+    source_row: Option<usize>,
+}
+
+/// One floor within an imported lot.
+#[derive(Debug, Default, serde::Deserialize, utoipa::ToSchema)]
+pub struct FloorImportDef {
+    pub name: String,
+    #[serde(default)]
+    pub floor_number: Option<i32>,
+    pub slots: Vec<SlotImportDef>,
+}
+
+/// One slot within an imported floor.
+#[derive(Debug, Default, serde::Deserialize, utoipa::ToSchema)]
+pub struct SlotImportDef {
+    /// Slot number, unique within the lot
+    pub number: i32,
+    /// `standard` (default), `compact`, `large`, `handicap`, `electric`,
+    /// `motorcycle`, `reserved`, `vip`
+    #[serde(default)]
+    pub slot_type: Option<String>,
+    #[serde(default)]
+    pub row: Option<i32>,
+    #[serde(default)]
+    pub column: Option<i32>,
+    /// Layout position in meters; derived from row/column when omitted
+    #[serde(default)]
+    pub x: Option<f32>,
+    #[serde(default)]
+    pub y: Option<f32>,
+    #[serde(default)]
+    pub features: Vec<String>,
+    /// CSV line the slot came from (CSV imports only).
+    #[serde(skip)]
+    source_row: Option<usize>,
+}
+
+/// Outcome of a lot import. On any validation error nothing is written.
+#[derive(Debug, Default, serde::Serialize, utoipa::ToSchema)]
+pub struct LotImportReport {
+    pub dry_run: bool,
+    pub lots_created: usize,
+    pub floors_created: usize,
+    pub slots_created: usize,
+    /// Created lot IDs (empty on failure or dry run)
+    pub lot_ids: Vec<Uuid>,
+    pub errors: Vec<LotImportError>,
+}
+
+/// A single validation problem in a lot import.
+#[derive(Debug, Clone, serde::Serialize, utoipa::ToSchema)]
+pub struct LotImportError {
+    /// Where the problem is: `line N` for CSV, a JSON path such as
+    /// `lots[0].floors[1].slots[3]` for JSON.
+    pub location: String,
+    /// The offending field (empty string if row-level).
+    pub field: String,
+    /// Human-readable error message.
+    pub message: String,
+}
+
+/// Query parameters for the lot import.
+#[derive(Debug, Default, serde::Deserialize, utoipa::IntoParams)]
+pub struct LotImportParams {
+    /// Validate only; do not write anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+fn csv_opt<T: std::str::FromStr>(
+    fields: &[&str],
+    idx: usize,
+    name: &str,
+    line: usize,
+    errors: &mut Vec<LotImportError>,
+) -> Option<T> {
+    let raw = fields.get(idx).map_or("", |s| s.trim());
+    if raw.is_empty() {
+        return None;
+    }
+    raw.parse().map_or_else(
+        |_| {
+            errors.push(LotImportError {
+                location: format!("line {line}"),
+                field: name.to_string(),
+                message: format!("Invalid value '{raw}'"),
+            });
+            None
+        },
+        Some,
+    )
+}
+
+/// Parse the CSV import format into the same structure as the JSON body.
+///
+/// Column order (one row per slot):
+/// `lot_name,floor_name,floor_number,slot_number,slot_type,row,column,x,y,address,latitude,longitude,currency,hourly_rate`.
+/// Lot-level columns are taken from the first row of each lot; features are
+/// not supported in CSV. A header row starting with `lot_name` is skipped.
+fn parse_lot_import_csv(body: &str) -> (LotImportDocument, Vec<LotImportError>) {
+    let mut doc = LotImportDocument::default();
+    let mut errors = Vec::new();
+
+    for (idx, raw_line) in body.lines().enumerate() {
+        let line = idx + 1;
+        let trimmed = raw_line.trim();
+        if trimmed.is_empty() || (idx == 0 && trimmed.to_lowercase().starts_with("lot_name")) {
+            continue;
+        }
+        let fields: Vec<&str> = trimmed.split(',').map(str::trim).collect();
+        let lot_name = fields.first().copied().unwrap_or_default();
+        let floor_name = fields.get(1).copied().unwrap_or_default();
+        if lot_name.is_empty() || floor_name.is_empty() {
+            errors.push(LotImportError {
+                location: format!("line {line}"),
+                field: String::new(),
+                message: "lot_name and floor_name are required".to_string(),
+            });
+            continue;
+        }
+        let Some(number) = csv_opt::<i32>(&fields, 3, "slot_number", line, &mut errors) else {
+            if fields.get(3).is_none_or(|s| s.is_empty()) {
+                errors.push(LotImportError {
+                    location: format!("line {line}"),
+                    field: "slot_number".to_string(),
+                    message: "slot_number is required".to_string(),
+                });
+            }
+            continue;
+        };
+
+        let lot_idx = if let Some(i) = doc.lots.iter().position(|l| l.name == lot_name) {
+            i
+        } else {
+            doc.lots.push(LotImportDef {
+                name: lot_name.to_string(),
+                address: fields
+                    .get(9)
+                    .filter(|s| !s.is_empty())
+                    .map(|s| (*s).to_string()),
+                latitude: csv_opt(&fields, 10, "latitude", line, &mut errors),
+                longitude: csv_opt(&fields, 11, "longitude", line, &mut errors),
+                currency: fields
+                    .get(12)
+                    .filter(|s| !s.is_empty())
+                    .map(|s| (*s).to_string()),
+                hourly_rate: csv_opt(&fields, 13, "hourly_rate", line, &mut errors),
+                daily_max: None,
+                floors: Vec::new(),
+                source_row: Some(line),
+            });
+            doc.lots.len() - 1
+        };
+        let lot = &mut doc.lots[lot_idx];
+
+        let floor_idx = if let Some(i) = lot.floors.iter().position(|f| f.name == floor_name) {
+            i
+        } else {
+            lot.floors.push(FloorImportDef {
+                name: floor_name.to_string(),
+                floor_number: csv_opt(&fields, 2, "floor_number", line, &mut errors),
+                slots: Vec::new(),
+            });
+            lot.floors.len() - 1
+        };
+
+        lot.floors[floor_idx].slots.push(SlotImportDef {
+            number,
+            slot_type: fields
+                .get(4)
+                .filter(|s| !s.is_empty())
+                .map(|s| (*s).to_string()),
+            row: csv_opt(&fields, 5, "row", line, &mut errors),
+            column: csv_opt(&fields, 6, "column", line, &mut errors),
+            x: csv_opt(&fields, 7, "x", line, &mut errors),
+            y: csv_opt(&fields, 8, "y", line, &mut errors),
+            features: Vec::new(),
+            source_row: Some(line),
+        });
+    }
+
+    (doc, errors)
+}
+
+/// Validate an import document and build the lots and slots it describes.
+///
+/// Every problem is collected rather than stopping at the first, so the
+/// caller gets a complete report to fix the spreadsheet in one pass.
+#[allow(clippy::too_many_lines)]
+fn build_lot_import(
+    doc: LotImportDocument,
+    existing_names: &HashSet<String>,
+    tenant_id: Option<&str>,
+) -> Result<(Vec<ParkingLot>, Vec<ParkingSlot>), Vec<LotImportError>> {
+    let mut errors = Vec::new();
+    let mut lots = Vec::new();
+    let mut slots = Vec::new();
+    let mut seen_names: HashSet<String> = HashSet::new();
+    let now = Utc::now();
+
+    if doc.lots.is_empty() {
+        errors.push(LotImportError {
+            location: "lots".to_string(),
+            field: String::new(),
+            message: "No lots to import".to_string(),
+        });
+    }
+    let total_slots: usize = doc
+        .lots
+        .iter()
+        .flat_map(|l| &l.floors)
+        .map(|f| f.slots.len())
+        .sum();
+    if total_slots > MAX_IMPORT_SLOTS {
+        errors.push(LotImportError {
+            location: "lots".to_string(),
+            field: String::new(),
+            message: format!("Import exceeds maximum of {MAX_IMPORT_SLOTS} slots"),
+        });
+        return Err(errors);
+    }
+
+    for (li, def) in doc.lots.into_iter().enumerate() {
+        let lot_loc = def
+            .source_row
+            .map_or_else(|| format!("lots[{li}]"), |r| format!("line {r}"));
+        let mut err = |location: &str, field: &str, message: &str| {
+            errors.push(LotImportError {
+                location: location.to_string(),
+                field: field.to_string(),
+                message: message.to_string(),
+            });
+        };
+
+        let name = def.name.trim().to_string();
+        if name.is_empty() || name.chars().count() > 100 {
+            err(&lot_loc, "name", "Name must be 1-100 characters");
+        } else if existing_names.contains(&name.to_lowercase()) {
+            err(
+                &lot_loc,
+                "name",
+                "A parking lot with this name already exists",
+            );
+        } else if !seen_names.insert(name.to_lowercase()) {
+            err(&lot_loc, "name", "Duplicate lot name in import");
+        }
+        let (latitude, longitude) = match (def.latitude, def.longitude) {
+            (Some(lat), Some(lon)) => {
+                if let Err(msg) = crate::geo::validate_coordinates(lat, lon) {
+                    err(&lot_loc, "latitude", msg);
+                }
+                (lat, lon)
+            }
+            (None, None) => (0.0, 0.0),
+            _ => {
+                err(
+                    &lot_loc,
+                    "latitude",
+                    "Latitude and longitude must be provided together",
+                );
+                (0.0, 0.0)
+            }
+        };
+        let currency = def.currency.unwrap_or_else(|| "EUR".to_string());
+        if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_alphabetic()) {
+            err(&lot_loc, "currency", "Currency must be a 3-letter ISO code");
+        }
+        if def.hourly_rate.is_some_and(|r| !r.is_finite() || r < 0.0) {
+            err(&lot_loc, "hourly_rate", "hourly_rate must be non-negative");
+        }
+        if def.floors.is_empty() {
+            err(&lot_loc, "floors", "Lot must have at least one floor");
+        }
+        let lot_slot_count: usize = def.floors.iter().map(|f| f.slots.len()).sum();
+        if lot_slot_count > MAX_SLOTS_PER_LOT {
+            err(
+                &lot_loc,
+                "floors",
+                &format!("Lot exceeds maximum of {MAX_SLOTS_PER_LOT} slots"),
+            );
+        }
+
+        let lot_id = Uuid::new_v4();
+        let mut floors = Vec::new();
+        let mut slot_numbers: HashSet<i32> = HashSet::new();
+        let mut floor_names: HashSet<String> = HashSet::new();
+
+        for (fi, floor_def) in def.floors.into_iter().enumerate() {
+            let floor_loc = if def.source_row.is_some() {
+                lot_loc.clone()
+            } else {
+                format!("lots[{li}].floors[{fi}]")
+            };
+            if floor_def.name.trim().is_empty() {
+                err(&floor_loc, "name", "Floor name is required");
+            } else if !floor_names.insert(floor_def.name.trim().to_lowercase()) {
+                err(&floor_loc, "name", "Duplicate floor name in lot");
+            }
+            if floor_def.slots.is_empty() {
+                err(&floor_loc, "slots", "Floor must have at least one slot");
+            }
+
+            let floor_id = Uuid::new_v4();
+            let floor_slot_count = i32::try_from(floor_def.slots.len()).unwrap_or(i32::MAX);
+            for (si, slot_def) in floor_def.slots.into_iter().enumerate() {
+                let slot_loc = slot_def.source_row.map_or_else(
+                    || format!("lots[{li}].floors[{fi}].slots[{si}]"),
+                    |r| format!("line {r}"),
+                );
+                if slot_def.number < 1 {
+                    err(&slot_loc, "number", "Slot number must be positive");
+                } else if !slot_numbers.insert(slot_def.number) {
+                    err(&slot_loc, "number", "Duplicate slot number in lot");
+                }
+                let slot_type = match slot_def.slot_type.as_deref() {
+                    None => SlotType::Standard,
+                    Some(t) => super::lots::parse_slot_type(t).unwrap_or_else(|| {
+                        err(&slot_loc, "slot_type", "Invalid slot_type");
+                        SlotType::Standard
+                    }),
+                };
+                let mut features = Vec::new();
+                for f in &slot_def.features {
+                    match super::lots::parse_slot_feature(f) {
+                        Some(feature) => features.push(feature),
+                        None => err(&slot_loc, "features", &format!("Invalid feature '{f}'")),
+                    }
+                }
+                let ordinal = i32::try_from(si).unwrap_or(i32::MAX);
+                let row = slot_def.row.unwrap_or(ordinal / 10 + 1);
+                let column = slot_def.column.unwrap_or(ordinal % 10 + 1);
+                #[allow(clippy::cast_precision_loss)]
+                let position = SlotPosition {
+                    x: slot_def.x.unwrap_or(((column - 1) as f32) * 3.0),
+                    y: slot_def.y.unwrap_or(((row - 1) as f32) * 5.0),
+                    width: 2.5,
+                    height: 5.0,
+                    rotation: 0.0,
+                };
+                slots.push(ParkingSlot {
+                    id: Uuid::new_v4(),
+                    lot_id,
+                    floor_id,
+                    slot_number: slot_def.number,
+                    row,
+                    column,
+                    is_accessible: slot_type == SlotType::Handicap,
+                    slot_type,
+                    status: SlotStatus::Available,
+                    current_booking: None,
+                    features,
+                    position,
+                });
+            }
+
+            floors.push(ParkingFloor {
+                id: floor_id,
+                lot_id,
+                name: floor_def.name.trim().to_string(),
+                floor_number: floor_def
+                    .floor_number
+                    .unwrap_or_else(|| i32::try_from(fi + 1).unwrap_or(i32::MAX)),
+                total_slots: floor_slot_count,
+                available_slots: floor_slot_count,
+                slots: Vec::new(),
+            });
+        }
+
+        let total = i32::try_from(lot_slot_count).unwrap_or(i32::MAX);
+        let mut rates = Vec::new();
+        if let Some(hourly) = def.hourly_rate {
+            rates.push(PricingRate {
+                duration_minutes: 60,
+                price: hourly,
+                label: "1 hour".to_string(),
+            });
+        }
+        lots.push(ParkingLot {
+            id: lot_id,
+            name,
+            address: def.address.unwrap_or_default(),
+            latitude,
+            longitude,
+            total_slots: total,
+            available_slots: total,
+            floors,
+            amenities: Vec::new(),
+            pricing: PricingInfo {
+                currency: currency.to_uppercase(),
+                rates,
+                daily_max: def.daily_max,
+                monthly_pass: None,
+            },
+            operating_hours: OperatingHours {
+                is_24h: true,
+                monday: None,
+                tuesday: None,
+                wednesday: None,
+                thursday: None,
+                friday: None,
+                saturday: None,
+                sunday: None,
+            },
+            images: Vec::new(),
+            status: LotStatus::Open,
+            created_at: now,
+            updated_at: now,
+            tenant_id: tenant_id.map(str::to_string),
+        });
+    }
+
+    if errors.is_empty() {
+        Ok((lots, slots))
+    } else {
+        Err(errors)
+    }
+}
+
+/// 422 response carrying the full error report alongside the error envelope.
+fn import_failed(report: LotImportReport) -> (StatusCode, Json<ApiResponse<LotImportReport>>) {
+    let mut resp = ApiResponse::error(
+        ErrorCode::ImportValidationFailed,
+        format!(
+            "Import rejected with {} error(s); nothing was written",
+            report.errors.len()
+        ),
+    );
+    resp.data = Some(report);
+    (StatusCode::UNPROCESSABLE_ENTITY, Json(resp))
+}
+
+/// `POST /api/v1/admin/lots/import` — create lots, floors, and slots in bulk (admin only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/lots/import",
+    tag = "Admin",
+    summary = "Bulk import lots and slots",
+    description = "Create parking lots with their floors and slots from a JSON document \
+        (`application/json`, see `LotImportDocument`) or CSV (`text/csv`, one row per slot: \
+        `lot_name,floor_name,floor_number,slot_number,slot_type,row,column,x,y,address,latitude,longitude,currency,hourly_rate`). \
+        Everything is validated first and written in a single transaction; any error aborts \
+        the whole import and is listed in the report. Admin only.",
+    params(LotImportParams),
+    request_body(
+        content = LotImportDocument,
+        description = "JSON import document, or CSV text with `Content-Type: text/csv`"
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Import completed", body = LotImportReport),
+        (status = 200, description = "Dry run passed validation", body = LotImportReport),
+        (status = 400, description = "Unparseable body"),
+        (status = 403, description = "Admin access required"),
+        (status = 422, description = "Validation failed; nothing was imported", body = LotImportReport),
+    )
+)]
+pub async fn import_lots(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(params): Query<LotImportParams>,
+    headers: HeaderMap,
+    body: String,
+) -> (StatusCode, Json<ApiResponse<LotImportReport>>) {
+    let state_guard = state.read().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.contains("json"));
+    let (doc, parse_errors) = if is_json {
+        match serde_json::from_str::<LotImportDocument>(&body) {
+            Ok(doc) => (doc, Vec::new()),
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(
+                        ErrorCode::InvalidJson,
+                        format!("Invalid import document: {e}"),
+                    )),
+                );
+            }
+        }
+    } else {
+        parse_lot_import_csv(&body)
+    };
+
+    let existing_names: HashSet<String> = match state_guard.db.list_parking_lots().await {
+        Ok(lots) => lots.into_iter().map(|l| l.name.to_lowercase()).collect(),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    let tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
+
+    let mut report = LotImportReport {
+        dry_run: params.dry_run,
+        ..LotImportReport::default()
+    };
+    let (lots, slots) = match build_lot_import(doc, &existing_names, tenant_id.as_deref()) {
+        Ok(built) if parse_errors.is_empty() => built,
+        Ok(_) => {
+            report.errors = parse_errors;
+            return import_failed(report);
+        }
+        Err(mut errors) => {
+            report.errors = parse_errors;
+            report.errors.append(&mut errors);
+            return import_failed(report);
+        }
+    };
+
+    report.lots_created = lots.len();
+    report.floors_created = lots.iter().map(|l| l.floors.len()).sum();
+    report.slots_created = slots.len();
+    if params.dry_run {
+        return (StatusCode::OK, Json(ApiResponse::success(report)));
+    }
+
+    if let Err(e) = state_guard.db.import_lots(&lots, &slots).await {
+        tracing::error!("Failed to import parking lots: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to import parking lots",
+            )),
+        );
+    }
+    report.lot_ids = lots.iter().map(|l| l.id).collect();
+
+    let username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    crate::audit::AuditEntry::new(crate::audit::AuditEventType::LotCreated)
+        .user(auth_user.user_id, &username)
+        .detail(&format!(
+            "Imported {} lots with {} slots",
+            report.lots_created, report.slots_created
+        ))
+        .log();
+
+    tracing::info!(
+        lots = report.lots_created,
+        slots = report.slots_created,
+        "Bulk-imported parking lots"
+    );
+    (StatusCode::CREATED, Json(ApiResponse::success(report)))
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Tests
 // ─────────────────────────────────────────────────────────────────────────────
@@ -660,4 +1278,98 @@ mod tests {
     fn test_max_rows_constant() {
         assert_eq!(MAX_IMPORT_ROWS, 500);
     }
+
+    // ── Lot import ──────────────────────────────────────────────────────────
+
+    const LOT_CSV: &str = "lot_name,floor_name,floor_number,slot_number,slot_type,row,column,x,y,address,latitude,longitude,currency,hourly_rate
+HQ,Level 1,1,1,standard,,,,,Main St 1,48.1,11.5,EUR,2.5
+HQ,Level 1,1,2,electric
+HQ,Level 2,2,3,handicap,1,1,0,0
+Annex,Ground,0,1,compact";
+
+    #[test]
+    fn test_parse_lot_import_csv_groups_rows() {
+        let (doc, errors) = parse_lot_import_csv(LOT_CSV);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(doc.lots.len(), 2);
+        let hq = &doc.lots[0];
+        assert_eq!(hq.name, "HQ");
+        assert_eq!(hq.latitude, Some(48.1));
+        assert_eq!(hq.hourly_rate, Some(2.5));
+        assert_eq!(hq.floors.len(), 2);
+        assert_eq!(hq.floors[0].slots.len(), 2);
+        assert_eq!(hq.floors[1].floor_number, Some(2));
+        assert_eq!(hq.floors[1].slots[0].source_row, Some(4));
+    }
+
+    #[test]
+    fn test_parse_lot_import_csv_reports_bad_numbers() {
+        let (_, errors) = parse_lot_import_csv("HQ,Level 1,1,abc\nHQ,Level 1,1,2,,x");
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].location, "line 1");
+        assert_eq!(errors[0].field, "slot_number");
+        assert_eq!(errors[1].field, "row");
+    }
+
+    #[test]
+    fn test_build_lot_import_creates_lots_and_slots() {
+        let (doc, _) = parse_lot_import_csv(LOT_CSV);
+        let (lots, slots) = build_lot_import(doc, &HashSet::new(), None).unwrap();
+        assert_eq!(lots.len(), 2);
+        assert_eq!(slots.len(), 4);
+        assert_eq!(lots[0].total_slots, 3);
+        assert_eq!(lots[0].floors.len(), 2);
+        assert!(
+            slots
+                .iter()
+                .all(|s| s.lot_id == lots[0].id || s.lot_id == lots[1].id)
+        );
+        let handicap = slots
+            .iter()
+            .find(|s| s.slot_type == SlotType::Handicap)
+            .unwrap();
+        assert!(handicap.is_accessible);
+        assert_eq!(handicap.floor_id, lots[0].floors[1].id);
+    }
+
+    #[test]
+    fn test_build_lot_import_collects_all_errors() {
+        let json = r#"{"lots":[
+            {"name":"HQ","latitude":48.1,"floors":[{"name":"L1","slots":[{"number":1},{"number":1,"slot_type":"boat"}]}]},
+            {"name":"hq","floors":[]}
+        ]}"#;
+        let doc: LotImportDocument = serde_json::from_str(json).unwrap();
+        let errors = build_lot_import(doc, &HashSet::new(), None).unwrap_err();
+        let fields: Vec<(&str, &str)> = errors
+            .iter()
+            .map(|e| (e.location.as_str(), e.field.as_str()))
+            .collect();
+        assert!(fields.contains(&("lots[0]", "latitude")));
+        assert!(fields.contains(&("lots[0].floors[0].slots[1]", "number")));
+        assert!(fields.contains(&("lots[0].floors[0].slots[1]", "slot_type")));
+        assert!(fields.contains(&("lots[1]", "name")));
+        assert!(fields.contains(&("lots[1]", "floors")));
+    }
+
+    #[test]
+    fn test_build_lot_import_rejects_existing_name() {
+        let doc = LotImportDocument {
+            lots: vec![LotImportDef {
+                name: "Garage".to_string(),
+                floors: vec![FloorImportDef {
+                    name: "L1".to_string(),
+                    floor_number: None,
+                    slots: vec![SlotImportDef {
+                        number: 1,
+                        ..SlotImportDef::default()
+                    }],
+                }],
+                ..LotImportDef::default()
+            }],
+        };
+        let existing: HashSet<String> = std::iter::once("garage".to_string()).collect();
+        let errors = build_lot_import(doc, &existing, None).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("already exists"));
+    }
 }
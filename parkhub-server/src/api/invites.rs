@@ -0,0 +1,598 @@
+//! Invite-based onboarding.
+//!
+//! - `POST   /api/v1/admin/invites`      — invite someone by email
+//! - `GET    /api/v1/admin/invites`      — list pending invitations
+//! - `DELETE /api/v1/admin/invites/:id`  — revoke an invitation
+//! - `POST   /api/v1/auth/accept-invite` — create the account (public)
+//!
+//! An invitation fixes the new account's email, role and tenant; the invitee
+//! only picks a name and password. The signup link carries a one-time token
+//! that expires after [`DEFAULT_INVITE_HOURS`] unless the admin chooses
+//! otherwise. Invitations work even when `allow_self_registration` is off —
+//! that is what they are for.
+//!
+//! Inviting someone again replaces their pending invitation. Only a
+//! SuperAdmin may invite a SuperAdmin.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::net::SocketAddr;
+
+use axum::{
+    Extension, Json,
+    extract::{ConnectInfo, Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::{
+    ApiResponse, AuthTokens, ErrorCode, LoginResponse, User, UserPreferences, UserRole,
+};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{Invite, Session};
+use crate::metrics;
+
+use super::auth::{build_auth_cookie, check_new_password, username_for_email, with_auth_cookie};
+use super::permissions::{CanManageUsers, RequirePermission};
+use super::security::LoginClient;
+use super::{SharedState, generate_access_token, hash_password, session_tokens};
+
+/// How long an invitation stays valid unless the admin says otherwise.
+pub const DEFAULT_INVITE_HOURS: i64 = 72;
+
+/// Longest validity an admin may choose (30 days).
+pub const MAX_INVITE_HOURS: i64 = 30 * 24;
+
+/// Request body for `POST /api/v1/admin/invites`
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct CreateInviteRequest {
+    pub email: String,
+    /// `user` (default), `premium`, `admin` or `superadmin`
+    #[serde(default)]
+    pub role: Option<String>,
+    /// Validity in hours (default 72, at most 720)
+    #[serde(default)]
+    pub expires_in_hours: Option<i64>,
+}
+
+/// Request body for `POST /api/v1/auth/accept-invite`
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AcceptInviteRequest {
+    pub token: String,
+    pub name: String,
+    pub password: String,
+    pub password_confirmation: String,
+}
+
+/// A pending invitation as shown to admins.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct InviteResponse {
+    pub id: Uuid,
+    pub email: String,
+    pub role: String,
+    pub invited_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// The link has expired and can no longer be used
+    pub expired: bool,
+}
+
+impl From<&Invite> for InviteResponse {
+    fn from(invite: &Invite) -> Self {
+        Self {
+            id: invite.id,
+            email: invite.email.clone(),
+            role: format!("{:?}", invite.role).to_lowercase(),
+            invited_by: invite.invited_by,
+            created_at: invite.created_at,
+            expires_at: invite.expires_at,
+            expired: invite.is_expired(),
+        }
+    }
+}
+
+/// A freshly created invitation.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct CreatedInviteResponse {
+    #[serde(flatten)]
+    pub invite: InviteResponse,
+    /// The signup link that was emailed, for sharing it another way
+    pub invite_url: String,
+}
+
+fn parse_role(role: &str) -> Option<UserRole> {
+    match role {
+        "user" => Some(UserRole::User),
+        "premium" => Some(UserRole::Premium),
+        "reporter" => Some(UserRole::Reporter),
+        "admin" => Some(UserRole::Admin),
+        "superadmin" => Some(UserRole::SuperAdmin),
+        _ => None,
+    }
+}
+
+fn error<T>(
+    status: StatusCode,
+    code: ErrorCode,
+    message: &str,
+) -> (StatusCode, Json<ApiResponse<T>>) {
+    (status, Json(ApiResponse::error(code, message)))
+}
+
+/// `POST /api/v1/admin/invites` — invite a new user by email
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/invites",
+    tag = "Admin",
+    summary = "Invite a user (admin)",
+    description = "Emails a one-time signup link for an account with the given role. Replaces any pending invitation for the same address.",
+    security(("bearer_auth" = [])),
+    request_body = CreateInviteRequest,
+    responses(
+        (status = 201, description = "Invitation sent", body = CreatedInviteResponse),
+        (status = 400, description = "Invalid email, role or validity"),
+        (status = 403, description = "Forbidden"),
+        (status = 409, description = "An account with this email already exists"),
+    )
+)]
+#[tracing::instrument(skip(state, req), fields(admin_id = %auth_user.user_id))]
+#[allow(clippy::too_many_lines)]
+pub async fn create_invite(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Json(req): Json<CreateInviteRequest>,
+) -> (StatusCode, Json<ApiResponse<CreatedInviteResponse>>) {
+    let email = req.email.trim().to_string();
+    if email.len() > 254 || !parkhub_common::is_valid_email(&email) {
+        return error(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidInput,
+            "A valid email address is required",
+        );
+    }
+    let Some(role) = parse_role(req.role.as_deref().unwrap_or("user")) else {
+        return error(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidInput,
+            "Role must be one of user, premium, admin, superadmin",
+        );
+    };
+    // Only SuperAdmin may create SuperAdmins (prevent privilege escalation)
+    if role == UserRole::SuperAdmin && auth_user.role != UserRole::SuperAdmin {
+        return error(
+            StatusCode::FORBIDDEN,
+            ErrorCode::Forbidden,
+            "Only a SuperAdmin can invite a SuperAdmin",
+        );
+    }
+    let hours = req.expires_in_hours.unwrap_or(DEFAULT_INVITE_HOURS);
+    if !(1..=MAX_INVITE_HOURS).contains(&hours) {
+        return error(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidInput,
+            "expires_in_hours must be between 1 and 720",
+        );
+    }
+
+    let state_guard = state.read().await;
+    match state_guard.db.get_user_by_email(&email).await {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            return error(
+                StatusCode::CONFLICT,
+                ErrorCode::EmailExists,
+                "An account with this email already exists",
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorCode::ServerError,
+                "Internal server error",
+            );
+        }
+    }
+
+    // SAFETY(T-1731): the invitee joins the inviting admin's tenant
+    let tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
+    let now = Utc::now();
+    let invite = Invite {
+        id: Uuid::new_v4(),
+        email,
+        role,
+        invited_by: auth_user.user_id,
+        tenant_id,
+        created_at: now,
+        expires_at: now + Duration::hours(hours),
+    };
+    let token = generate_access_token();
+    if let Err(e) = state_guard.db.delete_invites_for_email(&invite.email).await {
+        tracing::warn!("Failed to replace pending invitations: {}", e);
+    }
+    if let Err(e) = state_guard.db.save_invite(&token, &invite).await {
+        tracing::error!("Failed to save invite: {}", e);
+        return error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::ServerError,
+            "Failed to create invitation",
+        );
+    }
+
+    let admin_username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    AuditEntry::new(AuditEventType::InviteCreated)
+        .user(auth_user.user_id, &admin_username)
+        .resource("invite", &invite.id.to_string())
+        .details(serde_json::json!({
+            "email": invite.email,
+            "role": format!("{:?}", invite.role).to_lowercase(),
+            "expires_at": invite.expires_at,
+        }))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    let app_url = std::env::var("APP_URL").unwrap_or_else(|_| "http://localhost:8443".to_string());
+    let invite_url = format!("{app_url}/accept-invite?token={token}");
+    let org_name = state_guard.config.organization_name.clone();
+    let db = state_guard.db.clone();
+    drop(state_guard);
+
+    #[cfg(feature = "mod-email")]
+    {
+        let html = crate::email::build_invite_email(&invite_url, &org_name, hours);
+        let org = if org_name.is_empty() {
+            "ParkHub"
+        } else {
+            org_name.as_str()
+        };
+        let subject = format!("You're invited to {org}");
+        // Queued for delivery with retries; the admin still gets the link
+        if let Err(e) = crate::email_queue::enqueue(&db, &invite.email, &subject, &html).await {
+            tracing::warn!(invite_id = %invite.id, error = %e, "Failed to queue invitation email");
+        }
+    }
+
+    #[cfg(not(feature = "mod-email"))]
+    {
+        let _ = (&org_name, &db, hours);
+        tracing::info!(
+            invite_id = %invite.id,
+            "Email module disabled — invitation email not sent"
+        );
+    }
+
+    (
+        StatusCode::CREATED,
+        Json(ApiResponse::success(CreatedInviteResponse {
+            invite: InviteResponse::from(&invite),
+            invite_url,
+        })),
+    )
+}
+
+/// `GET /api/v1/admin/invites` — list pending invitations
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/invites",
+    tag = "Admin",
+    summary = "List invitations (admin)",
+    description = "Invitations that have not been accepted or revoked, newest first, including expired ones.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Pending invitations", body = Vec<InviteResponse>),
+        (status = 403, description = "Forbidden"),
+    )
+)]
+pub async fn list_invites(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+) -> (StatusCode, Json<ApiResponse<Vec<InviteResponse>>>) {
+    let state_guard = state.read().await;
+    let invites = match state_guard.db.list_invites().await {
+        Ok(invites) => invites,
+        Err(e) => {
+            tracing::error!("Failed to list invites: {}", e);
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorCode::ServerError,
+                "Failed to list invitations",
+            );
+        }
+    };
+    let caller_tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
+    let invites = invites
+        .iter()
+        .filter(|i| super::matches_tenant(i.tenant_id.as_deref(), caller_tenant_id.as_deref()))
+        .map(InviteResponse::from)
+        .collect();
+    (StatusCode::OK, Json(ApiResponse::success(invites)))
+}
+
+/// `DELETE /api/v1/admin/invites/{id}` — revoke an invitation
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/invites/{id}",
+    tag = "Admin",
+    summary = "Revoke an invitation (admin)",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Invitation UUID")),
+    responses(
+        (status = 200, description = "Invitation revoked"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Not found"),
+    )
+)]
+#[tracing::instrument(skip(state), fields(admin_id = %auth_user.user_id, invite_id = %id))]
+pub async fn revoke_invite(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.read().await;
+    let invite = match state_guard.db.list_invites().await {
+        Ok(invites) => invites.into_iter().find(|i| i.id == id),
+        Err(e) => {
+            tracing::error!("Failed to list invites: {}", e);
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorCode::ServerError,
+                "Internal server error",
+            );
+        }
+    };
+    // T-1737: cross-tenant admin-write guard — see `admin_update_user_role`
+    let caller_tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
+    let Some(invite) = invite
+        .filter(|i| super::matches_tenant(i.tenant_id.as_deref(), caller_tenant_id.as_deref()))
+    else {
+        return error(
+            StatusCode::NOT_FOUND,
+            ErrorCode::NotFound,
+            "Invitation not found",
+        );
+    };
+
+    match state_guard.db.delete_invite(id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return error(
+                StatusCode::NOT_FOUND,
+                ErrorCode::NotFound,
+                "Invitation not found",
+            );
+        }
+        Err(e) => {
+            tracing::error!("Failed to delete invite: {}", e);
+            return error(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorCode::ServerError,
+                "Failed to revoke invitation",
+            );
+        }
+    }
+
+    let admin_username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    AuditEntry::new(AuditEventType::InviteRevoked)
+        .user(auth_user.user_id, &admin_username)
+        .resource("invite", &id.to_string())
+        .details(serde_json::json!({ "email": invite.email }))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(())))
+}
+
+/// `POST /api/v1/auth/accept-invite` — create an account from an invitation
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/accept-invite",
+    tag = "Authentication",
+    summary = "Accept an invitation",
+    description = "Creates the invited account with the email and role from the invitation and signs it in. The token can be used once.",
+    request_body = AcceptInviteRequest,
+    responses(
+        (status = 201, description = "Account created"),
+        (status = 400, description = "Invalid input, or invalid or expired token"),
+        (status = 409, description = "Email already exists"),
+    )
+)]
+#[tracing::instrument(skip(state, headers, connect_info, request))]
+#[allow(clippy::too_many_lines)]
+pub async fn accept_invite(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    Json(request): Json<AcceptInviteRequest>,
+) -> Response {
+    if request.name.trim().is_empty() || request.name.len() > 100 {
+        return error::<LoginResponse>(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidInput,
+            "Name must be between 1 and 100 characters",
+        )
+        .into_response();
+    }
+    // Checked before the token is spent, so a typo does not burn the invite
+    if let Err((code, message)) =
+        check_new_password(&request.password, &request.password_confirmation)
+    {
+        return error::<LoginResponse>(StatusCode::BAD_REQUEST, code, message).into_response();
+    }
+    let password_hash = match hash_password(&request.password).await {
+        Ok(h) => h,
+        Err(e) => return e.into_response(),
+    };
+
+    let state_guard = state.read().await;
+    let invite = match state_guard.db.take_invite(&request.token).await {
+        Ok(Some(invite)) if !invite.is_expired() => invite,
+        Ok(Some(_)) => {
+            return error::<LoginResponse>(
+                StatusCode::BAD_REQUEST,
+                ErrorCode::InviteExpired,
+                "This invitation has expired. Ask an administrator for a new one.",
+            )
+            .into_response();
+        }
+        Ok(None) => {
+            return error::<LoginResponse>(
+                StatusCode::BAD_REQUEST,
+                ErrorCode::InvalidToken,
+                "Invalid or already used invitation",
+            )
+            .into_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to load invite: {}", e);
+            return error::<LoginResponse>(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorCode::ServerError,
+                "Internal server error",
+            )
+            .into_response();
+        }
+    };
+
+    if let Ok(Some(_)) = state_guard.db.get_user_by_email(&invite.email).await {
+        return error::<LoginResponse>(
+            StatusCode::CONFLICT,
+            ErrorCode::EmailExists,
+            "An account with this email already exists",
+        )
+        .into_response();
+    }
+    let Some(username) = username_for_email(&state_guard.db, &invite.email).await else {
+        return error::<LoginResponse>(
+            StatusCode::CONFLICT,
+            ErrorCode::UsernameExhausted,
+            "Too many accounts with this email prefix. Ask an administrator to invite a different address.",
+        )
+        .into_response();
+    };
+
+    let now = Utc::now();
+    let user = User {
+        id: Uuid::new_v4(),
+        username,
+        email: invite.email.clone(),
+        password_hash,
+        name: request.name.trim().to_string(),
+        picture: None,
+        phone: None,
+        role: invite.role.clone(),
+        created_at: now,
+        updated_at: now,
+        last_login: Some(now),
+        preferences: UserPreferences::default(),
+        is_active: true,
+        credits_balance: 40,
+        credits_monthly_quota: 40,
+        credits_last_refilled: Some(now),
+        tenant_id: invite.tenant_id.clone(),
+        accessibility_needs: None,
+        cost_center: None,
+        department: None,
+        settings: None,
+        must_change_password: false,
+    };
+
+    if let Err(e) = state_guard.db.save_user(&user).await {
+        tracing::error!("Failed to save user: {}", e);
+        // Give the invitee another try
+        if let Err(e) = state_guard.db.save_invite(&request.token, &invite).await {
+            tracing::error!("Failed to restore invite: {}", e);
+        }
+        return error::<LoginResponse>(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorCode::ServerError,
+            "Failed to create account",
+        )
+        .into_response();
+    }
+
+    AuditEntry::new(AuditEventType::UserCreated)
+        .user(user.id, &user.username)
+        .resource("user", &user.id.to_string())
+        .details(serde_json::json!({
+            "invite_id": invite.id,
+            "invited_by": invite.invited_by,
+            "role": format!("{:?}", user.role).to_lowercase(),
+        }))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+    metrics::record_auth_event("accept_invite", true);
+
+    #[cfg(feature = "mod-webhooks")]
+    {
+        let state_clone = state.clone();
+        let payload = serde_json::json!({
+            "user_id": user.id,
+            "username": user.username,
+        });
+        tokio::spawn(async move {
+            crate::api::webhooks::dispatch_webhook_event(&state_clone, "user.created", payload)
+                .await;
+        });
+    }
+
+    // Sign the new account in, exactly like registration
+    let session_hours = i64::from(state_guard.config.session_lifetime_hours).max(1);
+    let role_str = format!("{:?}", user.role).to_lowercase();
+    let client = LoginClient::from_request(&headers, connect_info.map(|Extension(ci)| ci.0));
+    let session = Session::new(user.id, session_hours, &user.username, &role_str)
+        .with_client(Some(client.ip), client.user_agent);
+    let access_token = match session_tokens::start_session(&state_guard.db, &session).await {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to save session: {}", e);
+            return error::<LoginResponse>(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorCode::ServerError,
+                "Failed to create session",
+            )
+            .into_response();
+        }
+    };
+    drop(state_guard);
+
+    let mut response_user = user;
+    response_user.password_hash = String::new();
+    let cookie = build_auth_cookie(&access_token, session_hours * 3600);
+
+    with_auth_cookie(
+        StatusCode::CREATED,
+        Json(ApiResponse::success(LoginResponse {
+            user: response_user,
+            tokens: AuthTokens {
+                access_token,
+                refresh_token: session.refresh_token,
+                expires_at: session.expires_at,
+                token_type: "Bearer".to_string(),
+            },
+        })),
+        &cookie,
+    )
+}
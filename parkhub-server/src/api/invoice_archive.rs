@@ -0,0 +1,539 @@
+//! Invoice issuing and the invoice archive.
+//!
+//! - `GET /api/v1/users/me/invoices` — invoices issued to the caller, newest first
+//! - `GET /api/v1/users/me/invoices/{number}` — archived HTML invoice
+//!
+//! An invoice is issued the first time it is requested, through any of the
+//! invoice endpoints: its number is allocated, the HTML (and, with
+//! `mod-invoices`, the PDF) is rendered and both are archived together with
+//! the totals. Every later request serves the archived copy, so re-downloading
+//! an old invoice after a price or tax change returns the original numbers.
+//!
+//! Viewers who see the booking's plate masked (see `plate_display`) get the
+//! invoice re-rendered from the archived details with the masked plate.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+#![cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Datelike, Utc};
+use serde::Serialize;
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, Booking, ErrorCode};
+
+use crate::AppState;
+use crate::db::{ArchivedInvoice, InvoiceDetails};
+use crate::utils::html_escape;
+
+use super::plate_display::PlateViewer;
+use super::tax::{self, ResolvedRate};
+use super::{AuthUser, SharedState};
+
+/// Recipient name used when the booking owner no longer exists.
+const UNKNOWN_CUSTOMER: &str = "[Deleted User]";
+
+impl InvoiceDetails {
+    /// VAT rate the invoice was issued with.
+    pub(crate) fn rate(&self) -> ResolvedRate {
+        if self.reverse_charge {
+            ResolvedRate::ReverseCharge
+        } else {
+            ResolvedRate::Standard(self.vat_rate)
+        }
+    }
+
+    /// Booked duration as whole hours and remaining minutes.
+    pub(crate) fn duration(&self) -> (i64, i64) {
+        let minutes = (self.end_time - self.start_time).num_minutes();
+        (minutes / 60, minutes % 60)
+    }
+}
+
+/// Collect what goes on the invoice for `booking` from the current records.
+async fn invoice_details(state: &AppState, booking: &Booking) -> anyhow::Result<InvoiceDetails> {
+    let (customer_name, customer_email) =
+        match state.db.get_user(&booking.user_id.to_string()).await? {
+            Some(user) => (user.name, user.email),
+            None => (UNKNOWN_CUSTOMER.to_string(), String::new()),
+        };
+    let lot_name = match state
+        .db
+        .get_parking_lot(&booking.lot_id.to_string())
+        .await?
+    {
+        Some(lot) => lot.name,
+        None => "Unknown Parking Lot".to_string(),
+    };
+    let company = if state.config.organization_name.is_empty() {
+        "ParkHub".to_string()
+    } else {
+        state.config.organization_name.clone()
+    };
+    let amounts = tax::resolve_invoice_amounts(state, booking).await;
+
+    Ok(InvoiceDetails {
+        company,
+        customer_name,
+        customer_email,
+        lot_name,
+        slot_number: booking.slot_number,
+        floor_name: booking.floor_name.clone(),
+        license_plate: booking.vehicle.license_plate.clone(),
+        invoice_date: booking.created_at,
+        start_time: booking.start_time,
+        end_time: booking.end_time,
+        status: format!("{:?}", booking.status),
+        currency: booking.pricing.currency.clone(),
+        net: amounts.net,
+        vat: amounts.vat,
+        gross: amounts.gross,
+        vat_rate: amounts.rate.as_rate(),
+        reverse_charge: amounts.rate.is_reverse_charge(),
+    })
+}
+
+/// The archived invoice of `booking`, issuing it first if needed.
+///
+/// The invoice number is allocated once per booking (see
+/// `db/invoice_counters.rs`); the first call for that number renders and
+/// archives the documents, later calls return the archive unchanged.
+pub(crate) async fn issue_invoice(
+    state: &AppState,
+    booking: &Booking,
+) -> anyhow::Result<ArchivedInvoice> {
+    let booking_id = booking.id.to_string();
+    let invoice_number = state
+        .db
+        .get_or_assign_invoice_number(&booking_id, booking.created_at.year())
+        .await?;
+    if let Some(invoice) = state.db.get_archived_invoice(&invoice_number).await? {
+        return Ok(invoice);
+    }
+
+    let details = invoice_details(state, booking).await?;
+    let html = render_invoice_html(
+        &invoice_number,
+        booking.id,
+        &details,
+        &details.license_plate,
+    );
+    #[cfg(feature = "mod-invoices")]
+    let pdf_base64 = match super::invoices::render_invoice_pdf(
+        &invoice_number,
+        &details,
+        &details.license_plate,
+    ) {
+        Ok(pdf) => Some(base64::Engine::encode(
+            &base64::engine::general_purpose::STANDARD,
+            pdf,
+        )),
+        Err(e) => {
+            tracing::warn!("Failed to render PDF of invoice {invoice_number}: {e}");
+            None
+        }
+    };
+    #[cfg(not(feature = "mod-invoices"))]
+    let pdf_base64 = None;
+
+    let invoice = ArchivedInvoice {
+        invoice_number,
+        booking_id: booking.id,
+        user_id: booking.user_id,
+        issued_at: Utc::now(),
+        details,
+        html,
+        pdf_base64,
+    };
+    state.db.archive_invoice(&invoice).await
+}
+
+/// The plate on `invoice` as `viewer` may see it, or `None` if that is the
+/// archived plate and the archived documents can be served as they are.
+pub(crate) fn masked_plate(invoice: &ArchivedInvoice, viewer: PlateViewer) -> Option<String> {
+    let plate = viewer.plate(invoice.user_id, &invoice.details.license_plate);
+    (plate != invoice.details.license_plate).then_some(plate)
+}
+
+/// The HTML invoice as `viewer` may see it: the archived copy, or one
+/// re-rendered from the archived details with the plate masked.
+pub(crate) fn invoice_html_for(invoice: &ArchivedInvoice, viewer: PlateViewer) -> String {
+    match masked_plate(invoice, viewer) {
+        None => invoice.html.clone(),
+        Some(plate) => render_invoice_html(
+            &invoice.invoice_number,
+            invoice.booking_id,
+            &invoice.details,
+            &plate,
+        ),
+    }
+}
+
+/// Render the HTML invoice. All user-controlled values are escaped.
+pub(crate) fn render_invoice_html(
+    invoice_number: &str,
+    booking_id: Uuid,
+    details: &InvoiceDetails,
+    license_plate: &str,
+) -> String {
+    let rate = details.rate();
+    let vat_label = if rate.is_reverse_charge() {
+        "MwSt. 0% (Reverse Charge, Art. 194 VAT Directive)".to_string()
+    } else {
+        let pct = rate.as_rate() * 100.0;
+        if (pct - pct.round()).abs() < f64::EPSILON {
+            format!("MwSt. {}% (§ 12 UStG)", pct.round() as i64)
+        } else {
+            format!("MwSt. {pct:.1}% (§ 12 UStG)")
+        }
+    };
+    let reverse_charge_html = if rate.is_reverse_charge() {
+        "<p><strong>Hinweis:</strong> Reverse charge per Art. 194 VAT Directive.</p>"
+    } else {
+        ""
+    };
+
+    let invoice_date = details.invoice_date.format("%d.%m.%Y").to_string();
+    let start_str = details.start_time.format("%d.%m.%Y %H:%M").to_string();
+    let end_str = details.end_time.format("%d.%m.%Y %H:%M").to_string();
+    let (duration_hours, duration_mins_part) = details.duration();
+
+    let invoice_number = html_escape(invoice_number);
+    let company = html_escape(&details.company);
+    let user_name = html_escape(&details.customer_name);
+    let user_email = html_escape(&details.customer_email);
+    let lot_name = html_escape(&details.lot_name);
+    let floor_name = html_escape(&details.floor_name);
+    let license_plate = html_escape(license_plate);
+    let status = html_escape(&details.status);
+    let currency = html_escape(&details.currency);
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="de">
+<head>
+  <meta charset="UTF-8" />
+  <meta name="viewport" content="width=device-width, initial-scale=1.0" />
+  <title>Rechnung {invoice_number}</title>
+  <style>
+    * {{ box-sizing: border-box; margin: 0; padding: 0; }}
+    body {{ font-family: 'Helvetica Neue', Arial, sans-serif; color: #1a1a2e; background: #f8f9fa; }}
+    .page {{ max-width: 800px; margin: 40px auto; background: #ffffff; padding: 60px;
+             box-shadow: 0 4px 20px rgba(0,0,0,0.08); border-radius: 4px; }}
+    .header {{ display: flex; justify-content: space-between; align-items: flex-start;
+               border-bottom: 3px solid #1a73e8; padding-bottom: 24px; margin-bottom: 40px; }}
+    .company-name {{ font-size: 28px; font-weight: 700; color: #1a73e8; }}
+    .company-sub {{ font-size: 12px; color: #666; margin-top: 4px; }}
+    .invoice-meta {{ text-align: right; }}
+    .invoice-meta h2 {{ font-size: 22px; color: #333; }}
+    .invoice-meta p {{ font-size: 13px; color: #666; margin-top: 4px; }}
+    .section {{ margin-bottom: 32px; }}
+    .section-title {{ font-size: 11px; font-weight: 700; color: #999; text-transform: uppercase;
+                      letter-spacing: 0.1em; margin-bottom: 8px; }}
+    .bill-to {{ background: #f8f9fa; padding: 16px 20px; border-radius: 4px; border-left: 3px solid #1a73e8; }}
+    .bill-to p {{ font-size: 14px; line-height: 1.6; color: #333; }}
+    table {{ width: 100%; border-collapse: collapse; margin-bottom: 0; }}
+    thead tr {{ background: #1a73e8; color: white; }}
+    thead th {{ padding: 12px 16px; text-align: left; font-size: 13px; font-weight: 600; }}
+    tbody tr {{ border-bottom: 1px solid #e8ecf0; }}
+    tbody tr:hover {{ background: #f8f9fa; }}
+    tbody td {{ padding: 14px 16px; font-size: 14px; color: #333; }}
+    .text-right {{ text-align: right; }}
+    .totals {{ margin-top: 0; border-top: 2px solid #e8ecf0; }}
+    .totals tr td {{ padding: 10px 16px; font-size: 14px; }}
+    .totals .total-row td {{ font-size: 16px; font-weight: 700; color: #1a73e8;
+                              border-top: 2px solid #1a73e8; padding-top: 14px; }}
+    .badge {{ display: inline-block; padding: 4px 10px; border-radius: 20px; font-size: 12px;
+              font-weight: 600; }}
+    .badge-confirmed {{ background: #e8f5e9; color: #2e7d32; }}
+    .footer {{ margin-top: 48px; padding-top: 24px; border-top: 1px solid #e8ecf0;
+               font-size: 11px; color: #999; text-align: center; line-height: 1.6; }}
+  </style>
+</head>
+<body>
+  <div class="page">
+
+    <!-- Header -->
+    <div class="header">
+      <div>
+        <div class="company-name">{company}</div>
+        <div class="company-sub">Parkverwaltungssystem</div>
+      </div>
+      <div class="invoice-meta">
+        <h2>RECHNUNG</h2>
+        <p><strong>{invoice_number}</strong></p>
+        <p>Datum: {invoice_date}</p>
+      </div>
+    </div>
+
+    <!-- Bill To -->
+    <div class="section">
+      <div class="section-title">Rechnungsempfänger</div>
+      <div class="bill-to">
+        <p><strong>{user_name}</strong></p>
+        <p>{user_email}</p>
+      </div>
+    </div>
+
+    <!-- Booking Details -->
+    <div class="section">
+      <div class="section-title">Buchungsdetails</div>
+      <table>
+        <thead>
+          <tr>
+            <th>Beschreibung</th>
+            <th>Details</th>
+          </tr>
+        </thead>
+        <tbody>
+          <tr>
+            <td>Buchungsnummer</td>
+            <td>{booking_id}</td>
+          </tr>
+          <tr>
+            <td>Parkhaus</td>
+            <td>{lot_name}</td>
+          </tr>
+          <tr>
+            <td>Stellplatz</td>
+            <td>Nr. {slot_number} &nbsp;·&nbsp; {floor_name}</td>
+          </tr>
+          <tr>
+            <td>Fahrzeug (Kennzeichen)</td>
+            <td>{license_plate}</td>
+          </tr>
+          <tr>
+            <td>Beginn</td>
+            <td>{start_str}</td>
+          </tr>
+          <tr>
+            <td>Ende</td>
+            <td>{end_str}</td>
+          </tr>
+          <tr>
+            <td>Dauer</td>
+            <td>{duration_hours} Std. {duration_mins_part} Min.</td>
+          </tr>
+          <tr>
+            <td>Status</td>
+            <td><span class="badge badge-confirmed">{status}</span></td>
+          </tr>
+        </tbody>
+      </table>
+    </div>
+
+    <!-- Pricing -->
+    <div class="section">
+      <div class="section-title">Rechnungsbetrag</div>
+      <table>
+        <thead>
+          <tr>
+            <th>Position</th>
+            <th class="text-right">Betrag ({currency})</th>
+          </tr>
+        </thead>
+        <tbody>
+          <tr>
+            <td>Parkgebühr (Netto)</td>
+            <td class="text-right">{net_price:.2}</td>
+          </tr>
+        </tbody>
+        <tbody class="totals">
+          <tr>
+            <td>Zwischensumme (Netto)</td>
+            <td class="text-right">{net_price:.2}</td>
+          </tr>
+          <tr>
+            <td>{vat_label}</td>
+            <td class="text-right">{vat_amount:.2}</td>
+          </tr>
+          <tr class="total-row">
+            <td>Gesamtbetrag (Brutto)</td>
+            <td class="text-right">{gross_total:.2}</td>
+          </tr>
+        </tbody>
+      </table>
+      {reverse_charge_html}
+    </div>
+
+    <!-- Footer -->
+    <div class="footer">
+      <p>{company} · Parkverwaltungssystem · Automatisch generierte Rechnung</p>
+      <p>Diese Rechnung wurde automatisch erstellt und ist ohne Unterschrift gültig.</p>
+    </div>
+
+  </div>
+</body>
+</html>"#,
+        invoice_number = invoice_number,
+        invoice_date = invoice_date,
+        company = company,
+        user_name = user_name,
+        user_email = user_email,
+        booking_id = booking_id,
+        lot_name = lot_name,
+        slot_number = details.slot_number,
+        floor_name = floor_name,
+        license_plate = license_plate,
+        start_str = start_str,
+        end_str = end_str,
+        duration_hours = duration_hours,
+        duration_mins_part = duration_mins_part,
+        status = status,
+        currency = currency,
+        net_price = details.net,
+        vat_amount = details.vat,
+        vat_label = vat_label,
+        reverse_charge_html = reverse_charge_html,
+        gross_total = details.gross,
+    )
+}
+
+/// An issued invoice in the archive listing.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct InvoiceSummary {
+    pub invoice_number: String,
+    pub booking_id: Uuid,
+    pub issued_at: DateTime<Utc>,
+    pub invoice_date: DateTime<Utc>,
+    pub lot_name: String,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub currency: String,
+    pub net: f64,
+    pub vat: f64,
+    pub gross: f64,
+    /// Whether an archived PDF is stored
+    pub has_pdf: bool,
+}
+
+impl From<&ArchivedInvoice> for InvoiceSummary {
+    fn from(invoice: &ArchivedInvoice) -> Self {
+        let details = &invoice.details;
+        Self {
+            invoice_number: invoice.invoice_number.clone(),
+            booking_id: invoice.booking_id,
+            issued_at: invoice.issued_at,
+            invoice_date: details.invoice_date,
+            lot_name: details.lot_name.clone(),
+            start_time: details.start_time,
+            end_time: details.end_time,
+            currency: details.currency.clone(),
+            net: details.net,
+            vat: details.vat,
+            gross: details.gross,
+            has_pdf: invoice.pdf_base64.is_some(),
+        }
+    }
+}
+
+/// The caller's archived invoice `invoice_number`, or an error response.
+/// Invoices of other users are reported as not found.
+pub(crate) async fn own_invoice(
+    state: &AppState,
+    auth_user: &AuthUser,
+    invoice_number: &str,
+) -> Result<ArchivedInvoice, Response> {
+    match state.db.get_archived_invoice(invoice_number).await {
+        Ok(Some(invoice)) if invoice.user_id == auth_user.user_id => Ok(invoice),
+        Ok(_) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error(
+                ErrorCode::NotFound,
+                "Invoice not found",
+            )),
+        )
+            .into_response()),
+        Err(e) => {
+            tracing::error!("Failed to load invoice {invoice_number}: {e}");
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+                .into_response())
+        }
+    }
+}
+
+/// `GET /api/v1/users/me/invoices` — invoices issued to the caller.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/invoices",
+    tag = "Invoices",
+    summary = "List my invoices",
+    description = "Invoices issued to the authenticated user, newest first. An invoice is \
+        issued the first time it is downloaded.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Issued invoices", body = Vec<InvoiceSummary>),
+    )
+)]
+pub async fn list_my_invoices(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<Vec<InvoiceSummary>>>) {
+    let state_guard = state.read().await;
+    match state_guard
+        .db
+        .list_archived_invoices_by_user(auth_user.user_id)
+        .await
+    {
+        Ok(invoices) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(
+                invoices.iter().map(InvoiceSummary::from).collect(),
+            )),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to list invoices: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
+/// `GET /api/v1/users/me/invoices/{number}` — archived HTML invoice.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/invoices/{number}",
+    tag = "Invoices",
+    summary = "Download an archived invoice",
+    description = "Returns the HTML invoice exactly as it was issued.",
+    params(("number" = String, Path, description = "Invoice number, e.g. 2026-0000001")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "HTML invoice", content_type = "text/html"),
+        (status = 404, description = "No such invoice issued to the caller"),
+    )
+)]
+pub async fn get_my_invoice(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(number): Path<String>,
+) -> Response {
+    let state_guard = state.read().await;
+    match own_invoice(&state_guard, &auth_user, &number).await {
+        Ok(invoice) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, "text/html; charset=utf-8")],
+            invoice.html,
+        )
+            .into_response(),
+        Err(response) => response,
+    }
+}
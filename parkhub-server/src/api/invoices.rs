@@ -4,6 +4,10 @@
 //!
 //! Endpoints:
 //! - `GET /api/v1/bookings/:id/invoice/pdf` — download PDF receipt for a booking
+//! - `GET /api/v1/users/me/invoices/:number/pdf` — archived PDF of an issued invoice
+//!
+//! PDFs are rendered when the invoice is issued and archived with it; see
+//! `invoice_archive`.
 
 use axum::{
     Extension, Json,
@@ -11,50 +15,21 @@ use axum::{
     http::{StatusCode, header},
     response::{IntoResponse, Response},
 };
+use base64::{Engine, engine::general_purpose::STANDARD};
 use printpdf::{
     BuiltinFont, Color, Line, LinePoint, Mm, Op, PdfDocument, PdfFontHandle, PdfPage,
     PdfSaveOptions, Point, Pt, Rgb, TextItem,
 };
 
-use parkhub_common::{ApiResponse, UserRole};
-
-use super::tax::{self, REVERSE_CHARGE_NOTE, ResolvedRate};
-use super::{AuthUser, SharedState};
+use parkhub_common::{ApiResponse, ErrorCode, UserRole};
 
-/// Resolve the buyer country ISO code for a specific user.
-///
-/// Stored in a per-user setting `user_country_{user_id}` so operators can
-/// flag international customers without a schema migration. Falls back to
-/// the `tax_default_country` setting, then to the seller country so
-/// domestic buyers never trigger reverse-charge by accident.
-async fn resolve_buyer_country(state: &crate::AppState, user_id: uuid::Uuid) -> String {
-    let user_key = format!("user_country_{user_id}");
-    if let Ok(Some(v)) = state.db.get_setting(&user_key).await {
-        let trimmed = v.trim();
-        if !trimmed.is_empty() {
-            return trimmed.to_string();
-        }
-    }
-    if let Ok(Some(v)) = state.db.get_setting("tax_default_country").await {
-        let trimmed = v.trim();
-        if !trimmed.is_empty() {
-            return trimmed.to_string();
-        }
-    }
-    tax::resolve_seller_country_from_settings(state).await
-}
+use crate::db::{ArchivedInvoice, Database, InvoiceDetails};
 
-/// Resolve the buyer VAT ID for a specific user.
-///
-/// Stored in a per-user setting `user_vat_id_{user_id}`; absent/empty means
-/// B2C sale (no reverse-charge eligibility).
-async fn resolve_buyer_vat_id(state: &crate::AppState, user_id: uuid::Uuid) -> Option<String> {
-    let key = format!("user_vat_id_{user_id}");
-    match state.db.get_setting(&key).await {
-        Ok(Some(v)) if !v.trim().is_empty() => Some(v.trim().to_string()),
-        _ => None,
-    }
-}
+use super::invoice_archive;
+use super::lot_settings::LotConfig;
+use super::plate_display::PlateViewer;
+use super::tax::{REVERSE_CHARGE_NOTE, ResolvedRate};
+use super::{AuthUser, SharedState, check_admin};
 
 /// Format the VAT line label based on the resolved rate.
 ///
@@ -104,7 +79,10 @@ pub async fn get_booking_invoice_pdf(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::<()>::error("NOT_FOUND", "Booking not found")),
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::NotFound,
+                    "Booking not found",
+                )),
             )
                 .into_response();
         }
@@ -113,7 +91,7 @@ pub async fn get_booking_invoice_pdf(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Internal server error",
                 )),
             )
@@ -122,142 +100,99 @@ pub async fn get_booking_invoice_pdf(
     };
 
     // Ownership check — only the booking owner (or admin) may fetch the invoice
-    let Ok(Some(caller)) = state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    else {
+    if booking.user_id != auth_user.user_id && check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::<()>::error("FORBIDDEN", "Access denied")),
-        )
-            .into_response();
-    };
-
-    let is_admin = caller.role == UserRole::Admin || caller.role == UserRole::SuperAdmin;
-    if booking.user_id != auth_user.user_id && !is_admin {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::<()>::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::<()>::error(
+                ErrorCode::Forbidden,
+                "Access denied",
+            )),
         )
             .into_response();
     }
 
-    // Fetch user details
-    let booking_user = match state_guard.db.get_user(&booking.user_id.to_string()).await {
-        Ok(Some(u)) => u,
-        _ => caller.clone(),
-    };
-
-    // Fetch lot name
-    let lot_name = match state_guard
-        .db
-        .get_parking_lot(&booking.lot_id.to_string())
-        .await
-    {
-        Ok(Some(lot)) => lot.name,
-        _ => "Parking Lot".to_string(),
-    };
-
-    // Company info
-    let org_name = state_guard.config.organization_name.clone();
-    let company = if org_name.is_empty() {
-        "ParkHub".to_string()
-    } else {
-        org_name
-    };
-
-    // Invoice metadata — sequential invoice number per § 14 UStG
-    // (fortlaufende Rechnungsnummer). Allocated once per booking from the
-    // per-year counter in the SETTINGS table and then reused on re-download.
-    let year = booking
-        .created_at
-        .format("%Y")
-        .to_string()
-        .parse::<i32>()
-        .unwrap_or(2026);
-    let invoice_number = match state_guard
-        .db
-        .get_or_assign_invoice_number(&booking.id.to_string(), year)
-        .await
-    {
-        Ok(n) => n,
+    // Issued once, then served from the archive so later price or tax
+    // changes do not alter it
+    let invoice = match invoice_archive::issue_invoice(&state_guard, &booking).await {
+        Ok(invoice) => invoice,
         Err(e) => {
-            tracing::error!("Failed to allocate invoice number: {e}");
+            tracing::error!("Failed to issue invoice: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "SERVER_ERROR",
-                    "Failed to allocate invoice number",
+                    ErrorCode::ServerError,
+                    "Failed to issue invoice",
                 )),
             )
                 .into_response();
         }
     };
-    let invoice_date = booking.created_at.format("%d.%m.%Y").to_string();
-    let start_str = booking.start_time.format("%d.%m.%Y %H:%M").to_string();
-    let end_str = booking.end_time.format("%d.%m.%Y %H:%M").to_string();
-
-    // Duration
-    let duration_minutes = (booking.end_time - booking.start_time).num_minutes();
-    let duration_hours = duration_minutes / 60;
-    let duration_mins_part = duration_minutes % 60;
-
-    // Pricing — resolve the rate from the configured seller country,
-    // applying EU B2B reverse-charge if the buyer supplied a VAT ID from a
-    // different EU member state. See `api::tax` for the full policy.
-    let seller_country = tax::resolve_seller_country_from_settings(&state_guard).await;
-    let buyer_country = resolve_buyer_country(&state_guard, booking.user_id).await;
-    let buyer_vat_id = resolve_buyer_vat_id(&state_guard, booking.user_id).await;
-    let resolved_rate = tax::resolve_rate(&seller_country, &buyer_country, buyer_vat_id.as_deref());
-    let net_price = booking.pricing.base_price;
-    let vat_amount = net_price * resolved_rate.as_rate();
-    let gross_total = net_price + vat_amount;
-    let currency = &booking.pricing.currency;
-    let vat_label = format_vat_label(resolved_rate);
-    let reverse_charge_note = resolved_rate
-        .is_reverse_charge()
-        .then(|| REVERSE_CHARGE_NOTE.to_string());
-
-    drop(state_guard);
-
-    // Generate PDF
-    let pdf_bytes = match generate_pdf(
-        &company,
-        &invoice_number,
-        &invoice_date,
-        &booking_user.name,
-        &booking_user.email,
-        &lot_name,
-        booking.slot_number,
-        &booking.floor_name,
-        &booking.vehicle.license_plate,
-        &start_str,
-        &end_str,
-        duration_hours,
-        duration_mins_part,
-        &format!("{:?}", booking.status),
-        net_price,
-        vat_amount,
-        gross_total,
-        currency,
-        &vat_label,
-        reverse_charge_note.as_deref(),
-    ) {
+
+    // Admins fetching another user's invoice see the plate per the lot's
+    // license_plate_display
+    let plate_display = LotConfig::resolve(&state_guard, &booking.lot_id.to_string())
+        .await
+        .license_plate_display;
+    let viewer = PlateViewer::new(auth_user.user_id, &auth_user.role, plate_display);
+    pdf_response(&state_guard.db, &invoice, viewer).await
+}
+
+/// `GET /api/v1/users/me/invoices/:number/pdf` — archived PDF invoice.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/invoices/{number}/pdf",
+    tag = "Invoices",
+    summary = "Download an archived PDF invoice",
+    description = "Returns the PDF invoice exactly as it was issued.",
+    params(("number" = String, Path, description = "Invoice number, e.g. 2026-0000001")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "PDF invoice", content_type = "application/pdf"),
+        (status = 404, description = "No such invoice issued to the caller"),
+    )
+)]
+pub async fn get_my_invoice_pdf(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(number): Path<String>,
+) -> Response {
+    let state_guard = state.read().await;
+    let invoice = match invoice_archive::own_invoice(&state_guard, &auth_user, &number).await {
+        Ok(invoice) => invoice,
+        Err(response) => return response,
+    };
+    // The recipient always sees their own plate
+    let viewer = PlateViewer::new(
+        auth_user.user_id,
+        &UserRole::User,
+        state_guard.config.license_plate_display,
+    );
+    pdf_response(&state_guard.db, &invoice, viewer).await
+}
+
+/// Serve the PDF of `invoice` as `viewer` may see it: the archived copy, or
+/// one rendered from the archived details with the plate masked. An invoice
+/// archived without a PDF gets one rendered and archived now.
+async fn pdf_response(db: &Database, invoice: &ArchivedInvoice, viewer: PlateViewer) -> Response {
+    let pdf = match invoice_archive::masked_plate(invoice, viewer) {
+        Some(plate) => render_invoice_pdf(&invoice.invoice_number, &invoice.details, &plate),
+        None => archived_pdf(db, invoice).await,
+    };
+    let pdf_bytes = match pdf {
         Ok(bytes) => bytes,
         Err(e) => {
             tracing::error!("PDF generation failed: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "PDF_ERROR",
+                    ErrorCode::PdfError,
                     "Failed to generate PDF",
                 )),
             )
                 .into_response();
         }
     };
-
+    let invoice_number = &invoice.invoice_number;
     let filename = format!("{invoice_number}.pdf");
 
     (
@@ -274,6 +209,55 @@ pub async fn get_booking_invoice_pdf(
         .into_response()
 }
 
+/// The archived PDF of `invoice`, rendering and archiving it first if the
+/// invoice was issued without one.
+async fn archived_pdf(db: &Database, invoice: &ArchivedInvoice) -> anyhow::Result<Vec<u8>> {
+    if let Some(pdf) = &invoice.pdf_base64 {
+        return Ok(STANDARD.decode(pdf)?);
+    }
+    let pdf = render_invoice_pdf(
+        &invoice.invoice_number,
+        &invoice.details,
+        &invoice.details.license_plate,
+    )?;
+    db.archive_invoice_pdf(&invoice.invoice_number, STANDARD.encode(&pdf))
+        .await?;
+    Ok(pdf)
+}
+
+/// Render the PDF of an invoice with the plate shown as `license_plate`.
+pub(crate) fn render_invoice_pdf(
+    invoice_number: &str,
+    details: &InvoiceDetails,
+    license_plate: &str,
+) -> anyhow::Result<Vec<u8>> {
+    let (duration_hours, duration_mins_part) = details.duration();
+    let rate = details.rate();
+    generate_pdf(
+        &details.company,
+        invoice_number,
+        &details.invoice_date.format("%d.%m.%Y").to_string(),
+        &details.customer_name,
+        &details.customer_email,
+        &details.lot_name,
+        details.slot_number,
+        &details.floor_name,
+        license_plate,
+        &details.start_time.format("%d.%m.%Y %H:%M").to_string(),
+        &details.end_time.format("%d.%m.%Y %H:%M").to_string(),
+        duration_hours,
+        duration_mins_part,
+        &details.status,
+        details.net,
+        details.vat,
+        details.gross,
+        &details.currency,
+        &format_vat_label(rate),
+        rate.is_reverse_charge().then_some(REVERSE_CHARGE_NOTE),
+    )
+    .map_err(|e| anyhow::anyhow!("{e}"))
+}
+
 /// Generate a PDF invoice document.
 #[allow(clippy::too_many_arguments)]
 fn generate_pdf(
@@ -14,10 +14,12 @@ use serde::Serialize;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use crate::AppState;
 
+use super::feature_flags::{KioskModeFlag, RequireFlag};
+
 type SharedState = Arc<RwLock<AppState>>;
 
 /// Color status for occupancy indicator
@@ -82,6 +84,7 @@ fn occupancy_color(occupancy_percent: f64) -> OccupancyColor {
 )]
 pub async fn lot_display(
     State(state): State<SharedState>,
+    _: RequireFlag<KioskModeFlag>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<LotDisplayData>>) {
     let state_guard = state.read().await;
@@ -91,14 +94,20 @@ pub async fn lot_display(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!(lot_id = %id, error = %e, "Failed to load lot for display");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -0,0 +1,699 @@
+//! Lot archival — retire a parking lot without losing its history.
+//!
+//! - `POST   /api/v1/admin/lots/:id/archive` — archive a lot, migrating future bookings
+//! - `DELETE /api/v1/admin/lots/:id/archive` — restore an archived lot
+//! - `GET    /api/v1/admin/lots/archived`    — list archived lots with their archive records
+//!
+//! Archiving closes the lot to new bookings and hides it from lot listings,
+//! search, and the map. Future bookings are moved to a slot of the same type
+//! in the target lot when one is free; the rest are cancelled. Affected users
+//! are notified either way. The lot, its slots, and past bookings stay in the
+//! database so invoices and reports keep resolving.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::models::{Notification, NotificationType};
+use parkhub_common::{
+    ApiResponse, Booking, BookingStatus, ErrorCode, LotStatus, ParkingSlot, SlotStatus,
+};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{Database, SlotChangeContext};
+
+use super::{AuthUser, SharedState, check_admin};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Request body for archiving a lot.
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+pub struct ArchiveLotRequest {
+    /// Lot to move future bookings to. When omitted, future bookings are
+    /// cancelled.
+    #[serde(default)]
+    pub target_lot_id: Option<Uuid>,
+}
+
+/// A booking moved to another lot during archival.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[allow(clippy::struct_field_names)]
+pub struct MigratedBooking {
+    pub booking_id: Uuid,
+    pub user_id: Uuid,
+    pub from_slot_id: Uuid,
+    pub to_lot_id: Uuid,
+    pub to_slot_id: Uuid,
+}
+
+/// Persisted record of a lot archival.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LotArchiveRecord {
+    pub lot_id: Uuid,
+    pub lot_name: String,
+    pub archived_at: DateTime<Utc>,
+    pub archived_by: Uuid,
+    pub target_lot_id: Option<Uuid>,
+    pub migrated: Vec<MigratedBooking>,
+    /// Future bookings that could not be moved and were cancelled
+    pub cancelled: Vec<Uuid>,
+}
+
+/// Settings key holding the JSON list of archived lot IDs.
+const ARCHIVED_LOT_IDS_KEY: &str = "archived_lot_ids";
+/// Settings key prefix for per-lot archive records.
+const LOT_ARCHIVE_PREFIX: &str = "lot_archive:";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// IDs of all archived lots. Errors degrade to an empty set.
+pub async fn archived_lot_ids(db: &Database) -> HashSet<Uuid> {
+    let raw = db
+        .get_setting(ARCHIVED_LOT_IDS_KEY)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    serde_json::from_str(&raw).unwrap_or_default()
+}
+
+/// Whether `lot_id` is archived.
+pub async fn is_archived(db: &Database, lot_id: Uuid) -> bool {
+    archived_lot_ids(db).await.contains(&lot_id)
+}
+
+async fn save_archived_lot_ids(db: &Database, ids: &HashSet<Uuid>) -> anyhow::Result<()> {
+    let mut sorted: Vec<&Uuid> = ids.iter().collect();
+    sorted.sort();
+    db.set_setting(ARCHIVED_LOT_IDS_KEY, &serde_json::to_string(&sorted)?)
+        .await
+}
+
+async fn load_archive_record(db: &Database, lot_id: Uuid) -> Option<LotArchiveRecord> {
+    let raw = db
+        .get_setting(&format!("{LOT_ARCHIVE_PREFIX}{lot_id}"))
+        .await
+        .ok()??;
+    serde_json::from_str(&raw).ok()
+}
+
+/// Pair each booking with a free target slot of the same type.
+///
+/// Bookings are handled in start-time order so the earliest ones get first
+/// pick; each target slot is handed out at most once. `source_slots` is used
+/// to look up each booking's slot type — bookings whose slot is unknown are
+/// treated as standard.
+pub fn plan_migration(
+    bookings: &[Booking],
+    source_slots: &[ParkingSlot],
+    target_slots: &[ParkingSlot],
+) -> Vec<(Uuid, Option<Uuid>)> {
+    let mut ordered: Vec<&Booking> = bookings.iter().collect();
+    ordered.sort_by_key(|b| b.start_time);
+
+    let mut taken: HashSet<Uuid> = HashSet::new();
+    ordered
+        .into_iter()
+        .map(|booking| {
+            let slot_type = source_slots
+                .iter()
+                .find(|s| s.id == booking.slot_id)
+                .map(|s| s.slot_type.clone())
+                .unwrap_or_default();
+            let target = target_slots
+                .iter()
+                .filter(|s| s.status == SlotStatus::Available && !taken.contains(&s.id))
+                .find(|s| s.slot_type == slot_type)
+                .map(|s| s.id);
+            if let Some(id) = target {
+                taken.insert(id);
+            }
+            (booking.id, target)
+        })
+        .collect()
+}
+
+async fn username(db: &Database, user_id: Uuid) -> String {
+    db.get_user(&user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default()
+}
+
+fn notification(user_id: Uuid, title: &str, message: String) -> Notification {
+    Notification {
+        id: Uuid::new_v4(),
+        user_id,
+        notification_type: NotificationType::SystemMessage,
+        title: title.to_string(),
+        message,
+        data: None,
+        read: false,
+        created_at: Utc::now(),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `POST /api/v1/admin/lots/{id}/archive` — archive a lot and migrate future bookings
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/lots/{id}/archive",
+    tag = "Lots",
+    summary = "Archive a parking lot",
+    description = "Close a lot to new bookings and hide it from listings. Future bookings are \
+        moved to a free slot of the same type in `target_lot_id` where possible and cancelled \
+        otherwise; affected users are notified. History is preserved. Admin only.",
+    params(("id" = String, Path, description = "Parking lot ID")),
+    request_body = ArchiveLotRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Lot archived", body = LotArchiveRecord),
+        (status = 400, description = "Invalid target lot"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Parking lot not found"),
+        (status = 409, description = "Lot already archived"),
+    )
+)]
+#[allow(clippy::too_many_lines)]
+pub async fn archive_lot(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(req): Json<ArchiveLotRequest>,
+) -> (StatusCode, Json<ApiResponse<LotArchiveRecord>>) {
+    // Write lock: booking creation re-checks slots under the read lock, so
+    // holding the write lock here keeps new bookings out while we migrate.
+    let state_guard = state.write().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let mut lot = match state_guard.db.get_parking_lot(&id).await {
+        Ok(Some(lot)) => lot,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let mut archived = archived_lot_ids(&state_guard.db).await;
+    if archived.contains(&lot.id) {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::AlreadyArchived,
+                "Parking lot is already archived",
+            )),
+        );
+    }
+
+    let target_lot = match req.target_lot_id {
+        Some(target_id) if target_id == lot.id || archived.contains(&target_id) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ErrorCode::InvalidTarget,
+                    "Target lot must be a different, active lot",
+                )),
+            );
+        }
+        Some(target_id) => match state_guard.db.get_parking_lot(&target_id.to_string()).await {
+            Ok(Some(t)) => Some(t),
+            Ok(None) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(
+                        ErrorCode::InvalidTarget,
+                        "Target lot not found",
+                    )),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Database error: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Internal server error",
+                    )),
+                );
+            }
+        },
+        None => None,
+    };
+
+    let now = Utc::now();
+    let future: Vec<Booking> = match state_guard.db.list_bookings().await {
+        Ok(all) => all
+            .into_iter()
+            .filter(|b| {
+                b.lot_id == lot.id
+                    && b.start_time > now
+                    && matches!(b.status, BookingStatus::Pending | BookingStatus::Confirmed)
+            })
+            .collect(),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let source_slots = state_guard
+        .db
+        .list_slots_by_lot(&id)
+        .await
+        .unwrap_or_default();
+    let target_slots = match &target_lot {
+        Some(t) => state_guard
+            .db
+            .list_slots_by_lot(&t.id.to_string())
+            .await
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    let plan = plan_migration(&future, &source_slots, &target_slots);
+    let mut record = LotArchiveRecord {
+        lot_id: lot.id,
+        lot_name: lot.name.clone(),
+        archived_at: now,
+        archived_by: auth_user.user_id,
+        target_lot_id: target_lot.as_ref().map(|t| t.id),
+        migrated: Vec::new(),
+        cancelled: Vec::new(),
+    };
+
+    for (booking_id, target_slot_id) in plan {
+        let Some(mut booking) = future.iter().find(|b| b.id == booking_id).cloned() else {
+            continue;
+        };
+        let from_slot_id = booking.slot_id;
+        let target = target_slot_id
+            .and_then(|sid| target_slots.iter().find(|s| s.id == sid))
+            .zip(target_lot.as_ref());
+
+        if let Some((slot, target_lot)) = target {
+            booking.lot_id = target_lot.id;
+            booking.slot_id = slot.id;
+            booking.slot_number = slot.slot_number;
+            booking.floor_name = target_lot
+                .floors
+                .iter()
+                .find(|f| f.id == slot.floor_id)
+                .map_or_else(|| booking.floor_name.clone(), |f| f.name.clone());
+            booking.updated_at = now;
+            if let Err(e) = state_guard.db.save_booking(&booking).await {
+                tracing::error!("Failed to migrate booking {}: {}", booking.id, e);
+                continue;
+            }
+            let mut reserved = slot.clone();
+            reserved.status = SlotStatus::Reserved;
+            let change = SlotChangeContext::user(auth_user.user_id, "lot_archive")
+                .reason(format!("Booking {} moved from {}", booking.id, lot.name));
+            if let Err(e) = state_guard
+                .db
+                .save_parking_slot_with(&reserved, &change)
+                .await
+            {
+                tracing::warn!("Failed to reserve migrated slot {}: {}", slot.id, e);
+            }
+            let note = notification(
+                booking.user_id,
+                "Booking moved",
+                format!(
+                    "{} is closing. Your booking on {} was moved to {}, slot {}.",
+                    lot.name,
+                    booking.start_time.format("%Y-%m-%d %H:%M UTC"),
+                    target_lot.name,
+                    slot.slot_number
+                ),
+            );
+            if let Err(e) = state_guard.db.save_notification(&note).await {
+                tracing::warn!("Failed to notify user {}: {}", booking.user_id, e);
+            }
+            record.migrated.push(MigratedBooking {
+                booking_id: booking.id,
+                user_id: booking.user_id,
+                from_slot_id,
+                to_lot_id: target_lot.id,
+                to_slot_id: slot.id,
+            });
+        } else {
+            booking.status = BookingStatus::Cancelled;
+            booking.updated_at = now;
+            if let Err(e) = state_guard.db.save_booking(&booking).await {
+                tracing::error!("Failed to cancel booking {}: {}", booking.id, e);
+                continue;
+            }
+            let note = notification(
+                booking.user_id,
+                "Booking cancelled",
+                format!(
+                    "{} is closing and no matching slot was free elsewhere. Your booking on {} \
+                     was cancelled — please book a new slot.",
+                    lot.name,
+                    booking.start_time.format("%Y-%m-%d %H:%M UTC"),
+                ),
+            );
+            if let Err(e) = state_guard.db.save_notification(&note).await {
+                tracing::warn!("Failed to notify user {}: {}", booking.user_id, e);
+            }
+            record.cancelled.push(booking.id);
+        }
+
+        // The archived lot's slot is no longer held by this booking.
+        if let Err(e) = state_guard
+            .db
+            .update_slot_status(
+                &from_slot_id.to_string(),
+                SlotStatus::Available,
+                &SlotChangeContext::user(auth_user.user_id, "lot_archive")
+                    .reason(format!("Lot {} archived", lot.name)),
+            )
+            .await
+        {
+            tracing::warn!("Failed to release slot {}: {}", from_slot_id, e);
+        }
+    }
+
+    lot.status = LotStatus::Closed;
+    lot.updated_at = now;
+    if let Err(e) = state_guard.db.save_parking_lot(&lot).await {
+        tracing::error!("Failed to close archived lot: {}", e);
+    }
+
+    let saved = async {
+        state_guard
+            .db
+            .set_setting(
+                &format!("{LOT_ARCHIVE_PREFIX}{}", lot.id),
+                &serde_json::to_string(&record)?,
+            )
+            .await?;
+        archived.insert(lot.id);
+        save_archived_lot_ids(&state_guard.db, &archived).await
+    }
+    .await;
+    if let Err(e) = saved {
+        tracing::error!("Failed to persist lot archive: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to archive parking lot",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::LotUpdated)
+        .user(
+            auth_user.user_id,
+            &username(&state_guard.db, auth_user.user_id).await,
+        )
+        .resource("lot", &lot.id.to_string())
+        .detail(&format!(
+            "Archived lot; {} bookings migrated, {} cancelled",
+            record.migrated.len(),
+            record.cancelled.len()
+        ))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    tracing::info!(
+        lot_id = %lot.id,
+        migrated = record.migrated.len(),
+        cancelled = record.cancelled.len(),
+        "Archived parking lot"
+    );
+
+    (StatusCode::OK, Json(ApiResponse::success(record)))
+}
+
+/// `DELETE /api/v1/admin/lots/{id}/archive` — restore an archived lot
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/lots/{id}/archive",
+    tag = "Lots",
+    summary = "Restore an archived parking lot",
+    description = "Reopen an archived lot and show it in listings again. Migrated or cancelled \
+        bookings are not moved back. Admin only.",
+    params(("id" = String, Path, description = "Parking lot ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Lot restored"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Lot is not archived"),
+    )
+)]
+pub async fn unarchive_lot(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.write().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let Ok(lot_id) = Uuid::parse_str(&id) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot is not archived",
+            )),
+        );
+    };
+    let mut archived = archived_lot_ids(&state_guard.db).await;
+    if !archived.remove(&lot_id) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot is not archived",
+            )),
+        );
+    }
+    if let Err(e) = save_archived_lot_ids(&state_guard.db, &archived).await {
+        tracing::error!("Failed to update archived lots: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Internal server error",
+            )),
+        );
+    }
+
+    if let Ok(Some(mut lot)) = state_guard.db.get_parking_lot(&id).await {
+        lot.status = LotStatus::Open;
+        lot.updated_at = Utc::now();
+        if let Err(e) = state_guard.db.save_parking_lot(&lot).await {
+            tracing::error!("Failed to reopen lot: {}", e);
+        }
+    }
+
+    AuditEntry::new(AuditEventType::LotUpdated)
+        .user(
+            auth_user.user_id,
+            &username(&state_guard.db, auth_user.user_id).await,
+        )
+        .resource("lot", &id)
+        .detail("Restored archived lot")
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(())))
+}
+
+/// `GET /api/v1/admin/lots/archived` — list archive records
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/lots/archived",
+    tag = "Lots",
+    summary = "List archived parking lots",
+    description = "Returns the archive record of every archived lot. Admin only.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Archive records", body = Vec<LotArchiveRecord>),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn list_archived_lots(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<Vec<LotArchiveRecord>>>) {
+    let state_guard = state.read().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let mut records = Vec::new();
+    for lot_id in archived_lot_ids(&state_guard.db).await {
+        if let Some(record) = load_archive_record(&state_guard.db, lot_id).await {
+            records.push(record);
+        }
+    }
+    records.sort_by_key(|r| std::cmp::Reverse(r.archived_at));
+
+    (StatusCode::OK, Json(ApiResponse::success(records)))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parkhub_common::models::{SlotPosition, SlotType};
+
+    fn slot(slot_type: SlotType, status: SlotStatus) -> ParkingSlot {
+        ParkingSlot {
+            id: Uuid::new_v4(),
+            lot_id: Uuid::new_v4(),
+            floor_id: Uuid::new_v4(),
+            slot_number: 1,
+            row: 1,
+            column: 1,
+            slot_type,
+            status,
+            current_booking: None,
+            features: Vec::new(),
+            position: SlotPosition {
+                x: 0.0,
+                y: 0.0,
+                width: 2.5,
+                height: 5.0,
+                rotation: 0.0,
+            },
+            is_accessible: false,
+        }
+    }
+
+    fn booking(slot_id: Uuid, hours_ahead: i64) -> Booking {
+        let start = Utc::now() + chrono::Duration::hours(hours_ahead);
+        let json = serde_json::json!({
+            "id": Uuid::new_v4(),
+            "user_id": Uuid::new_v4(),
+            "lot_id": Uuid::new_v4(),
+            "slot_id": slot_id,
+            "slot_number": 1,
+            "floor_name": "Ground",
+            "vehicle": {
+                "id": Uuid::new_v4(),
+                "user_id": Uuid::new_v4(),
+                "license_plate": "M-AB 123",
+                "make": null, "model": null, "color": null,
+                "vehicle_type": "car",
+                "is_default": true,
+                "created_at": start,
+            },
+            "start_time": start,
+            "end_time": start + chrono::Duration::hours(2),
+            "status": "confirmed",
+            "pricing": {
+                "base_price": 0.0, "discount": 0.0, "tax": 0.0, "total": 0.0,
+                "currency": "EUR", "payment_status": "pending", "payment_method": null,
+            },
+            "created_at": start,
+            "updated_at": start,
+            "check_in_time": null,
+            "check_out_time": null,
+            "qr_code": null,
+            "notes": null,
+        });
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_plan_migration_matches_slot_type() {
+        let src_ev = slot(SlotType::Electric, SlotStatus::Reserved);
+        let src_std = slot(SlotType::Standard, SlotStatus::Reserved);
+        let dst_std = slot(SlotType::Standard, SlotStatus::Available);
+        let dst_ev = slot(SlotType::Electric, SlotStatus::Available);
+        let b_ev = booking(src_ev.id, 2);
+        let b_std = booking(src_std.id, 3);
+
+        let plan = plan_migration(
+            &[b_std.clone(), b_ev.clone()],
+            &[src_ev, src_std],
+            &[dst_std.clone(), dst_ev.clone()],
+        );
+        assert_eq!(
+            plan,
+            vec![(b_ev.id, Some(dst_ev.id)), (b_std.id, Some(dst_std.id))]
+        );
+    }
+
+    #[test]
+    fn test_plan_migration_uses_each_target_once_earliest_first() {
+        let src_a = slot(SlotType::Standard, SlotStatus::Reserved);
+        let src_b = slot(SlotType::Standard, SlotStatus::Reserved);
+        let dst = slot(SlotType::Standard, SlotStatus::Available);
+        let later = booking(src_a.id, 5);
+        let earlier = booking(src_b.id, 1);
+
+        let plan = plan_migration(
+            &[later.clone(), earlier.clone()],
+            &[src_a, src_b],
+            std::slice::from_ref(&dst),
+        );
+        assert_eq!(plan, vec![(earlier.id, Some(dst.id)), (later.id, None)]);
+    }
+
+    #[test]
+    fn test_plan_migration_skips_unavailable_targets() {
+        let src = slot(SlotType::Standard, SlotStatus::Reserved);
+        let dst = slot(SlotType::Standard, SlotStatus::Maintenance);
+        let b = booking(src.id, 1);
+        let plan = plan_migration(std::slice::from_ref(&b), &[src], &[dst]);
+        assert_eq!(plan, vec![(b.id, None)]);
+    }
+}
@@ -0,0 +1,313 @@
+//! Floors of a parking lot.
+//!
+//! - `GET /api/v1/lots/{id}/floors` — the lot's floors, lowest first
+//! - `POST /api/v1/lots/{id}/floors` — add a floor (admin or lot manager)
+//! - `PATCH /api/v1/lots/{id}/floors/{floor_id}` — rename or renumber a
+//!   floor (admin or lot manager)
+//! - `GET /api/v1/lots/{id}/floors/{floor_id}/slots` — the floor's slots,
+//!   with the filters of `GET /api/v1/lots/{id}/slots`
+//!
+//! Floors are stored on their [`ParkingLot`]; slots reference them by
+//! `floor_id` and are moved between floors through the slot endpoints. Floor
+//! numbers are unique within a lot and order the floors; the floor's slot
+//! counts are recomputed whenever it is saved.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode, ParkingFloor, ParkingLot, ParkingSlot};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::Database;
+use crate::requests::{CreateFloorRequest, UpdateFloorRequest};
+use crate::validation::ValidatedJson;
+
+use super::lot_managers::check_lot_admin;
+use super::lots::{SlotFilterParams, list_lot_slots};
+use super::{AuthUser, SharedState, load_lot};
+
+/// The floor `floor_id` of `lot`, answering 404 if the lot has none such.
+#[allow(clippy::result_large_err)]
+fn find_floor<T>(
+    lot: &ParkingLot,
+    floor_id: &str,
+) -> Result<ParkingFloor, (StatusCode, Json<ApiResponse<T>>)> {
+    Uuid::parse_str(floor_id)
+        .ok()
+        .and_then(|id| lot.floors.iter().find(|f| f.id == id))
+        .cloned()
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Floor not found in this lot",
+                )),
+            )
+        })
+}
+
+fn floor_number_taken() -> (StatusCode, Json<ApiResponse<ParkingFloor>>) {
+    (
+        StatusCode::CONFLICT,
+        Json(ApiResponse::error(
+            ErrorCode::Duplicate,
+            "A floor with this number already exists in this lot",
+        )),
+    )
+}
+
+/// Save `floor` and answer with it as stored, counts included.
+async fn save_floor(
+    db: &Database,
+    floor: &ParkingFloor,
+) -> Result<ParkingFloor, (StatusCode, Json<ApiResponse<ParkingFloor>>)> {
+    match db.save_lot_floor(floor).await {
+        Ok(lot) => lot
+            .and_then(|lot| lot.floors.into_iter().find(|f| f.id == floor.id))
+            .ok_or_else(|| {
+                (
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::error(
+                        ErrorCode::NotFound,
+                        "Parking lot not found",
+                    )),
+                )
+            }),
+        Err(e) => {
+            tracing::error!("Failed to save floor: {}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to save floor",
+                )),
+            ))
+        }
+    }
+}
+
+/// `GET /api/v1/lots/{id}/floors` — list a lot's floors
+#[utoipa::path(
+    get,
+    path = "/api/v1/lots/{id}/floors",
+    tag = "Lots",
+    summary = "List floors of a parking lot",
+    description = "Returns the lot's floors ordered by floor number, each with its slot counts. \
+        The floors' `slots` are left empty; see `GET /api/v1/lots/{id}/floors/{floor_id}/slots`.",
+    params(("id" = String, Path, description = "Parking lot ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Floors of the lot"),
+        (status = 404, description = "Parking lot not found"),
+    )
+)]
+pub async fn list_floors(
+    State(state): State<SharedState>,
+    Extension(_auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<ParkingFloor>>>) {
+    let state_guard = state.read().await;
+    let mut lot = match load_lot(&state_guard.db, &lot_id).await {
+        Ok(lot) => lot,
+        Err(response) => return response,
+    };
+    lot.floors.sort_by_key(|f| f.floor_number);
+    for floor in &mut lot.floors {
+        floor.slots.clear();
+    }
+    (StatusCode::OK, Json(ApiResponse::success(lot.floors)))
+}
+
+/// `POST /api/v1/lots/{id}/floors` — add a floor to a lot
+#[utoipa::path(
+    post,
+    path = "/api/v1/lots/{id}/floors",
+    tag = "Lots",
+    summary = "Add a floor to a parking lot",
+    description = "Adds an empty floor. `floor_number` defaults to one above the lot's highest \
+        floor. Admins and managers of the lot only.",
+    params(("id" = String, Path, description = "Parking lot ID")),
+    request_body = CreateFloorRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Floor created"),
+        (status = 400, description = "Invalid name or floor number"),
+        (status = 403, description = "Admin or lot manager access required"),
+        (status = 404, description = "Parking lot not found"),
+        (status = 409, description = "Floor number already used in this lot"),
+    )
+)]
+pub async fn create_floor(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<String>,
+    ValidatedJson(req): ValidatedJson<CreateFloorRequest>,
+) -> (StatusCode, Json<ApiResponse<ParkingFloor>>) {
+    let state_guard = state.read().await;
+
+    // Admin or manager of this lot
+    if let Err((status, msg)) = check_lot_admin(&auth_user, &lot_id) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let lot = match load_lot(&state_guard.db, &lot_id).await {
+        Ok(lot) => lot,
+        Err(response) => return response,
+    };
+    let floor_number = req.floor_number.unwrap_or_else(|| {
+        lot.floors
+            .iter()
+            .map(|f| f.floor_number + 1)
+            .max()
+            .unwrap_or(1)
+    });
+    if lot.floors.iter().any(|f| f.floor_number == floor_number) {
+        return floor_number_taken();
+    }
+
+    let floor = ParkingFloor {
+        id: Uuid::new_v4(),
+        lot_id: lot.id,
+        name: req.name.trim().to_string(),
+        floor_number,
+        total_slots: 0,
+        available_slots: 0,
+        slots: Vec::new(),
+    };
+    let floor = match save_floor(&state_guard.db, &floor).await {
+        Ok(floor) => floor,
+        Err(response) => return response,
+    };
+
+    AuditEntry::new(AuditEventType::LotUpdated)
+        .user(auth_user.user_id, "")
+        .resource("lot", &lot_id)
+        .detail(&format!("Floor '{}' added", floor.name))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::CREATED, Json(ApiResponse::success(floor)))
+}
+
+/// `PATCH /api/v1/lots/{id}/floors/{floor_id}` — rename or renumber a floor
+#[utoipa::path(
+    patch,
+    path = "/api/v1/lots/{id}/floors/{floor_id}",
+    tag = "Lots",
+    summary = "Update a floor of a parking lot",
+    description = "Changes the given floor properties (`name`, `floor_number`). Admins and \
+        managers of the lot only.",
+    params(
+        ("id" = String, Path, description = "Parking lot ID"),
+        ("floor_id" = String, Path, description = "Floor ID"),
+    ),
+    request_body = UpdateFloorRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Floor updated"),
+        (status = 400, description = "Invalid name or floor number"),
+        (status = 403, description = "Admin or lot manager access required"),
+        (status = 404, description = "Parking lot or floor not found"),
+        (status = 409, description = "Floor number already used in this lot"),
+    )
+)]
+pub async fn update_floor(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((lot_id, floor_id)): Path<(String, String)>,
+    ValidatedJson(req): ValidatedJson<UpdateFloorRequest>,
+) -> (StatusCode, Json<ApiResponse<ParkingFloor>>) {
+    let state_guard = state.read().await;
+
+    // Admin or manager of this lot
+    if let Err((status, msg)) = check_lot_admin(&auth_user, &lot_id) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let lot = match load_lot(&state_guard.db, &lot_id).await {
+        Ok(lot) => lot,
+        Err(response) => return response,
+    };
+    let mut floor = match find_floor(&lot, &floor_id) {
+        Ok(floor) => floor,
+        Err(response) => return response,
+    };
+
+    if let Some(name) = &req.name {
+        floor.name = name.trim().to_string();
+    }
+    if let Some(number) = req.floor_number {
+        if lot
+            .floors
+            .iter()
+            .any(|f| f.id != floor.id && f.floor_number == number)
+        {
+            return floor_number_taken();
+        }
+        floor.floor_number = number;
+    }
+    let floor = match save_floor(&state_guard.db, &floor).await {
+        Ok(floor) => floor,
+        Err(response) => return response,
+    };
+
+    AuditEntry::new(AuditEventType::LotUpdated)
+        .user(auth_user.user_id, "")
+        .resource("lot", &lot_id)
+        .detail(&format!("Floor '{}' updated", floor.name))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(floor)))
+}
+
+/// `GET /api/v1/lots/{id}/floors/{floor_id}/slots` — list a floor's slots
+#[utoipa::path(
+    get,
+    path = "/api/v1/lots/{id}/floors/{floor_id}/slots",
+    tag = "Lots",
+    summary = "List slots on a floor",
+    description = "Returns the slots on one floor of the lot, with the same filters and \
+        ordering as `GET /api/v1/lots/{id}/slots`.",
+    params(
+        ("id" = String, Path, description = "Parking lot ID"),
+        ("floor_id" = String, Path, description = "Floor ID"),
+        SlotFilterParams,
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Slots on the floor"),
+        (status = 400, description = "Invalid filter value"),
+        (status = 404, description = "Parking lot or floor not found"),
+    )
+)]
+pub async fn get_floor_slots(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((lot_id, floor_id)): Path<(String, String)>,
+    Query(filters): Query<SlotFilterParams>,
+) -> (StatusCode, Json<ApiResponse<Vec<ParkingSlot>>>) {
+    let floor = {
+        let state_guard = state.read().await;
+        let lot = match load_lot(&state_guard.db, &lot_id).await {
+            Ok(lot) => lot,
+            Err(response) => return response,
+        };
+        match find_floor(&lot, &floor_id) {
+            Ok(floor) => floor,
+            Err(response) => return response,
+        }
+    };
+    list_lot_slots(&state, &auth_user, &lot_id, &filters, Some(floor.id)).await
+}
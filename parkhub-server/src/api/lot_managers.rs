@@ -0,0 +1,498 @@
+//! Lot managers — delegate administration of individual lots.
+//!
+//! - `GET    /api/v1/admin/lots/:id/managers`           — list a lot's managers
+//! - `POST   /api/v1/admin/lots/:id/managers`           — assign a manager
+//! - `DELETE /api/v1/admin/lots/:id/managers/:user_id`  — remove a manager
+//! - `GET    /api/v1/users/me/managed-lots`             — lots the caller manages
+//!
+//! A lot manager is a regular user who may administer the slots, maintenance
+//! windows, bookings, and reports of their assigned lots only. Assignments
+//! are made by global admins. The auth middleware folds them into the
+//! caller's resolved permissions as [`LOT_MANAGER_PERMISSIONS`] on those
+//! lots, alongside any custom roles (see `api::roles`). Lot-scoped admin
+//! handlers resolve the caller's [`AdminScope`] instead of calling
+//! `check_admin` directly, so a manager's reach is enforced per request
+//! against the lot being touched.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode, LotScope, Permission};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::Database;
+
+use super::{AuthUser, SharedState, check_admin};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Request body for assigning a lot manager.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AssignLotManagerRequest {
+    pub user_id: Uuid,
+}
+
+/// A user assigned as manager of a lot.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct LotManagerEntry {
+    pub user_id: Uuid,
+    pub username: String,
+    pub name: String,
+    pub email: String,
+}
+
+/// What an authenticated caller may administer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdminScope {
+    /// Every lot.
+    Global,
+    /// Only the listed lots.
+    Lots(HashSet<Uuid>),
+}
+
+impl AdminScope {
+    /// Whether this scope covers `lot_id`.
+    #[must_use]
+    pub fn allows_lot(&self, lot_id: &Uuid) -> bool {
+        match self {
+            Self::Global => true,
+            Self::Lots(ids) => ids.contains(lot_id),
+        }
+    }
+
+    /// Whether this scope covers the lot with the given string ID.
+    /// Unparseable IDs are only covered by the global scope.
+    #[must_use]
+    pub fn allows_lot_str(&self, lot_id: &str) -> bool {
+        match self {
+            Self::Global => true,
+            Self::Lots(_) => Uuid::parse_str(lot_id).is_ok_and(|id| self.allows_lot(&id)),
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Permissions a lot manager holds on their assigned lots.
+pub const LOT_MANAGER_PERMISSIONS: [Permission; 3] = [
+    Permission::ManageLots,
+    Permission::ViewAllBookings,
+    Permission::ManageBookings,
+];
+
+/// Resolve which lots the caller holds `permission` on.
+///
+/// Admins — and custom roles without a lot restriction — get
+/// [`AdminScope::Global`]; lot managers and lot-scoped custom roles get
+/// [`AdminScope::Lots`]. Everyone else is rejected with 403.
+///
+/// # Errors
+/// Returns `(403, message)` when the caller does not hold `permission` on
+/// any lot.
+pub fn admin_scope(
+    auth_user: &AuthUser,
+    permission: Permission,
+) -> Result<AdminScope, (StatusCode, &'static str)> {
+    match auth_user.permissions.scope(permission) {
+        Some(LotScope::All) => Ok(AdminScope::Global),
+        Some(LotScope::Lots(lots)) => Ok(AdminScope::Lots(lots.iter().copied().collect())),
+        None => Err((StatusCode::FORBIDDEN, "Admin access required")),
+    }
+}
+
+/// Verify the caller may administer `lot_id` — either globally or through a
+/// lot manager assignment or custom role covering it.
+///
+/// # Errors
+/// Returns `(403, message)` when the caller has no admin rights over the lot.
+pub fn check_lot_admin(
+    auth_user: &AuthUser,
+    lot_id: &str,
+) -> Result<(), (StatusCode, &'static str)> {
+    let scope = admin_scope(auth_user, Permission::ManageLots)?;
+    if scope.allows_lot_str(lot_id) {
+        Ok(())
+    } else {
+        Err((StatusCode::FORBIDDEN, "Not a manager of this lot"))
+    }
+}
+
+/// Paths under the globally guarded `/api/v1/admin/*` group that lot
+/// managers may reach. Handlers behind them scope their results with
+/// [`admin_scope`].
+pub const LOT_MANAGER_ADMIN_PATHS: &[&str] = &[
+    "/api/v1/admin/bookings",
+    "/api/v1/admin/bookings/bulk",
+    "/api/v1/admin/reports",
+];
+
+/// Whether a lot manager may pass the admin middleware for `path`.
+#[must_use]
+pub fn lot_manager_may_access(path: &str) -> bool {
+    LOT_MANAGER_ADMIN_PATHS.contains(&path)
+}
+
+async fn username(db: &Database, user_id: Uuid) -> String {
+    db.get_user(&user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `GET /api/v1/admin/lots/{id}/managers` — list managers of a lot
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/lots/{id}/managers",
+    tag = "Lots",
+    summary = "List lot managers",
+    description = "Returns the users allowed to administer this lot. Admin only.",
+    params(("id" = String, Path, description = "Parking lot ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Lot managers", body = Vec<LotManagerEntry>),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Lot not found"),
+    )
+)]
+pub async fn list_lot_managers(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<Vec<LotManagerEntry>>>) {
+    let state_guard = state.read().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let lot = match state_guard.db.get_parking_lot(&id).await {
+        Ok(Some(lot)) => lot,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let manager_ids = match state_guard.db.list_lot_managers(lot.id).await {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::error!("Failed to load lot managers: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    let mut entries = Vec::new();
+    for user_id in manager_ids {
+        if let Ok(Some(user)) = state_guard.db.get_user(&user_id.to_string()).await {
+            entries.push(LotManagerEntry {
+                user_id: user.id,
+                username: user.username,
+                name: user.name,
+                email: user.email,
+            });
+        }
+    }
+    entries.sort_by(|a, b| a.username.cmp(&b.username));
+
+    (StatusCode::OK, Json(ApiResponse::success(entries)))
+}
+
+/// `POST /api/v1/admin/lots/{id}/managers` — assign a lot manager
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/lots/{id}/managers",
+    tag = "Lots",
+    summary = "Assign a lot manager",
+    description = "Lets a user administer this lot's slots, maintenance, bookings, and reports \
+        without global admin rights. Idempotent. Admin only.",
+    params(("id" = String, Path, description = "Parking lot ID")),
+    request_body = AssignLotManagerRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Manager assigned", body = LotManagerEntry),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Lot or user not found"),
+    )
+)]
+pub async fn assign_lot_manager(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(req): Json<AssignLotManagerRequest>,
+) -> (StatusCode, Json<ApiResponse<LotManagerEntry>>) {
+    let state_guard = state.write().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let lot = match state_guard.db.get_parking_lot(&id).await {
+        Ok(Some(lot)) => lot,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let user = match state_guard.db.get_user(&req.user_id.to_string()).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let newly_assigned = match state_guard.db.assign_lot_manager(user.id, lot.id).await {
+        Ok(added) => added,
+        Err(e) => {
+            tracing::error!("Failed to save lot manager: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    if newly_assigned {
+        AuditEntry::new(AuditEventType::RoleChanged)
+            .user(
+                auth_user.user_id,
+                &username(&state_guard.db, auth_user.user_id).await,
+            )
+            .resource("lot", &lot.id.to_string())
+            .detail(&format!("Assigned {} as lot manager", user.username))
+            .log()
+            .persist(&state_guard.db)
+            .await;
+    }
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(LotManagerEntry {
+            user_id: user.id,
+            username: user.username,
+            name: user.name,
+            email: user.email,
+        })),
+    )
+}
+
+/// `DELETE /api/v1/admin/lots/{id}/managers/{user_id}` — remove a lot manager
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/lots/{id}/managers/{user_id}",
+    tag = "Lots",
+    summary = "Remove a lot manager",
+    description = "Revokes a user's manager rights for this lot. Admin only.",
+    params(
+        ("id" = String, Path, description = "Parking lot ID"),
+        ("user_id" = String, Path, description = "Manager user ID"),
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Manager removed"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "User is not a manager of this lot"),
+    )
+)]
+pub async fn remove_lot_manager(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((id, user_id)): Path<(String, String)>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.write().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let (Ok(lot_id), Ok(manager_id)) = (Uuid::parse_str(&id), Uuid::parse_str(&user_id)) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "User is not a manager of this lot",
+            )),
+        );
+    };
+
+    match state_guard
+        .db
+        .unassign_lot_manager(manager_id, lot_id)
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "User is not a manager of this lot",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Failed to remove lot manager: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    }
+
+    AuditEntry::new(AuditEventType::RoleChanged)
+        .user(
+            auth_user.user_id,
+            &username(&state_guard.db, auth_user.user_id).await,
+        )
+        .resource("lot", &id)
+        .detail(&format!(
+            "Removed {} as lot manager",
+            username(&state_guard.db, manager_id).await
+        ))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(())))
+}
+
+/// `GET /api/v1/users/me/managed-lots` — IDs of lots the caller manages
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/managed-lots",
+    tag = "Users",
+    summary = "List lots I manage",
+    description = "Returns the IDs of lots the caller is assigned to manage. \
+        Empty for users without assignments; global admins are not listed here.",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Managed lot IDs", body = Vec<Uuid>))
+)]
+pub async fn my_managed_lots(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<Vec<Uuid>>>) {
+    let state_guard = state.read().await;
+    match state_guard.db.list_managed_lots(auth_user.user_id).await {
+        Ok(mut ids) => {
+            ids.sort();
+            (StatusCode::OK, Json(ApiResponse::success(ids)))
+        }
+        Err(e) => {
+            tracing::error!("Failed to load managed lots: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scope_allows_lot() {
+        let lot = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let scope = AdminScope::Lots(HashSet::from([lot]));
+
+        assert!(scope.allows_lot(&lot));
+        assert!(!scope.allows_lot(&other));
+        assert!(scope.allows_lot_str(&lot.to_string()));
+        assert!(!scope.allows_lot_str("not-a-uuid"));
+        assert!(AdminScope::Global.allows_lot(&other));
+        assert!(AdminScope::Global.allows_lot_str("not-a-uuid"));
+    }
+
+    #[test]
+    fn test_lot_manager_paths() {
+        assert!(lot_manager_may_access("/api/v1/admin/bookings"));
+        assert!(lot_manager_may_access("/api/v1/admin/bookings/bulk"));
+        assert!(lot_manager_may_access("/api/v1/admin/reports"));
+        assert!(!lot_manager_may_access("/api/v1/admin/users"));
+        assert!(!lot_manager_may_access("/api/v1/admin/users/bulk"));
+        assert!(!lot_manager_may_access("/api/v1/admin/reports/revenue"));
+    }
+}
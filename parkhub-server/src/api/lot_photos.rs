@@ -0,0 +1,511 @@
+//! Parking lot photos.
+//!
+//! - `POST /api/v1/admin/lots/{id}/photos` — multipart upload (JPEG or PNG, max 3 MB)
+//! - `DELETE /api/v1/admin/lots/{id}/photos/{photo_id}` — remove a photo
+//! - `GET /api/v1/lots/{id}/photos/{photo_id}` — the stored photo as JPEG (public)
+//!
+//! Uploads are decoded, rotated upright according to their EXIF orientation,
+//! scaled down to at most [`MAX_PHOTO_EDGE`] pixels and re-encoded as JPEG.
+//! Only the pixels survive re-encoding, so EXIF metadata (GPS position,
+//! camera serials, timestamps) is never stored or served. The photo id is
+//! derived from the stored bytes, which makes every photo URL immutable: the
+//! public route is cacheable for a year and rate-limited per IP so the
+//! selection screen can show photos before login.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::io::Cursor;
+
+use axum::{
+    Extension, Json,
+    extract::{Multipart, Path, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use chrono::Utc;
+use image::codecs::jpeg::JpegEncoder;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits, imageops::FilterType};
+use sha2::{Digest, Sha256};
+
+use parkhub_common::{ApiResponse, ErrorCode, ParkingLot};
+
+use crate::audit::{AuditEntry, AuditEventType};
+
+use super::{AuthUser, SharedState, check_admin};
+
+/// Maximum accepted upload size in bytes (3 MB).
+pub const MAX_LOT_PHOTO_UPLOAD_BYTES: usize = 3 * 1024 * 1024;
+
+/// Longest edge of a stored photo in pixels.
+pub const MAX_PHOTO_EDGE: u32 = 1600;
+
+/// Maximum number of photos per lot.
+pub const MAX_PHOTOS_PER_LOT: usize = 10;
+
+/// Largest source dimension accepted before decoding is refused.
+const MAX_SOURCE_DIMENSION: u32 = 10_000;
+
+/// JPEG quality of stored photos.
+const JPEG_QUALITY: u8 = 85;
+
+/// Multipart field names accepted for the image part.
+const UPLOAD_FIELD_NAMES: &[&str] = &["photo", "file", "image"];
+
+/// The public URL a lot photo is served from.
+#[must_use]
+pub fn photo_url(lot_id: &str, photo_id: &str) -> String {
+    format!("/api/v1/lots/{lot_id}/photos/{photo_id}")
+}
+
+/// Content-derived id for stored photo bytes.
+fn photo_id(jpeg: &[u8]) -> String {
+    hex::encode(Sha256::digest(jpeg))[..16].to_string()
+}
+
+/// Decode an uploaded JPEG/PNG, apply its EXIF orientation, bound its size
+/// and re-encode it as JPEG without metadata.
+pub(super) fn process_photo(raw: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let format = image::guess_format(raw).map_err(|_| "Unrecognised image data")?;
+    if !matches!(format, ImageFormat::Jpeg | ImageFormat::Png) {
+        return Err("Unsupported image format. Only JPEG and PNG are accepted.");
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_SOURCE_DIMENSION);
+    limits.max_image_height = Some(MAX_SOURCE_DIMENSION);
+    let mut reader = ImageReader::with_format(Cursor::new(raw), format);
+    reader.limits(limits);
+    let mut decoder = reader
+        .into_decoder()
+        .map_err(|_| "Image could not be decoded")?;
+    let orientation = decoder
+        .orientation()
+        .map_err(|_| "Image could not be decoded")?;
+    let mut img = DynamicImage::from_decoder(decoder).map_err(|_| "Image could not be decoded")?;
+    img.apply_orientation(orientation);
+
+    if img.width().max(img.height()) > MAX_PHOTO_EDGE {
+        img = img.resize(MAX_PHOTO_EDGE, MAX_PHOTO_EDGE, FilterType::Lanczos3);
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    DynamicImage::ImageRgb8(img.to_rgb8())
+        .write_with_encoder(JpegEncoder::new_with_quality(&mut out, JPEG_QUALITY))
+        .map_err(|_| "Image could not be encoded")?;
+    Ok(out.into_inner())
+}
+
+async fn username(db: &crate::db::Database, user_id: uuid::Uuid) -> String {
+    db.get_user(&user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default()
+}
+
+/// Pull the image part out of a multipart body.
+async fn read_upload(multipart: &mut Multipart) -> Result<Vec<u8>, (StatusCode, &'static str)> {
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Malformed multipart body"))?
+    {
+        if !field
+            .name()
+            .is_some_and(|n| UPLOAD_FIELD_NAMES.contains(&n))
+        {
+            continue;
+        }
+        let bytes = field
+            .bytes()
+            .await
+            .map_err(|_| (StatusCode::PAYLOAD_TOO_LARGE, "Photo exceeds 3 MB limit"))?;
+        if bytes.len() > MAX_LOT_PHOTO_UPLOAD_BYTES {
+            return Err((StatusCode::PAYLOAD_TOO_LARGE, "Photo exceeds 3 MB limit"));
+        }
+        return Ok(bytes.to_vec());
+    }
+    Err((StatusCode::BAD_REQUEST, "Missing 'photo' file field"))
+}
+
+/// `POST /api/v1/admin/lots/{id}/photos` — add a photo to a lot.
+#[utoipa::path(post, path = "/api/v1/admin/lots/{id}/photos", tag = "Lots",
+    summary = "Upload a lot photo",
+    description = "Multipart upload with a `photo` file field (JPEG or PNG, max 3 MB). The \
+        image is rotated upright, scaled to at most 1600 px and re-encoded as JPEG, which \
+        strips all EXIF metadata. Its public URL is appended to the lot's `images`. \
+        At most 10 photos per lot. Admin only.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Parking lot ID")),
+    request_body(content_type = "multipart/form-data", description = "`photo` file field"),
+    responses(
+        (status = 200, description = "Photo stored; returns the updated lot"),
+        (status = 400, description = "Missing or invalid image"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Parking lot not found"),
+        (status = 409, description = "Photo limit reached"),
+        (status = 413, description = "Image too large"),
+    )
+)]
+pub async fn upload_lot_photo(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    mut multipart: Multipart,
+) -> (StatusCode, Json<ApiResponse<ParkingLot>>) {
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let raw = match read_upload(&mut multipart).await {
+        Ok(raw) => raw,
+        Err((status, msg)) => {
+            let code = if status == StatusCode::PAYLOAD_TOO_LARGE {
+                ErrorCode::PayloadTooLarge
+            } else {
+                ErrorCode::InvalidInput
+            };
+            return (status, Json(ApiResponse::error(code, msg)));
+        }
+    };
+
+    // Decoding and resampling are CPU-bound; keep them off the async workers.
+    let jpeg = match tokio::task::spawn_blocking(move || process_photo(&raw)).await {
+        Ok(Ok(jpeg)) => jpeg,
+        Ok(Err(msg)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::InvalidInput, msg)),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Lot photo processing task failed: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    // Write lock: the photo count check and the `images` update must not
+    // interleave with a concurrent upload to the same lot.
+    let state_guard = state.write().await;
+    let mut lot = match state_guard.db.get_parking_lot(&id).await {
+        Ok(Some(lot)) => lot,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let photo_id = photo_id(&jpeg);
+    let url = photo_url(&id, &photo_id);
+    if lot.images.contains(&url) {
+        return (StatusCode::OK, Json(ApiResponse::success(lot)));
+    }
+    if lot.images.len() >= MAX_PHOTOS_PER_LOT {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::PhotoLimitReached,
+                format!("A lot can have at most {MAX_PHOTOS_PER_LOT} photos"),
+            )),
+        );
+    }
+
+    if let Err(e) = state_guard.db.save_lot_photo(&id, &photo_id, &jpeg).await {
+        tracing::error!("Failed to save lot photo: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save photo",
+            )),
+        );
+    }
+    lot.images.push(url);
+    lot.updated_at = Utc::now();
+    if let Err(e) = state_guard.db.save_parking_lot(&lot).await {
+        tracing::error!("Failed to update lot after photo upload: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save photo",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::LotUpdated)
+        .user(
+            auth_user.user_id,
+            &username(&state_guard.db, auth_user.user_id).await,
+        )
+        .resource("lot", &id)
+        .detail(&format!("Lot photo {photo_id} uploaded"))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    tracing::info!(lot_id = %id, photo_id = %photo_id, bytes = jpeg.len(), "Lot photo uploaded");
+    (StatusCode::OK, Json(ApiResponse::success(lot)))
+}
+
+/// `DELETE /api/v1/admin/lots/{id}/photos/{photo_id}` — remove a lot photo.
+#[utoipa::path(delete, path = "/api/v1/admin/lots/{id}/photos/{photo_id}", tag = "Lots",
+    summary = "Delete a lot photo",
+    description = "Deletes the photo and removes its URL from the lot's `images`. Admin only.",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = String, Path, description = "Parking lot ID"),
+        ("photo_id" = String, Path, description = "Photo ID"),
+    ),
+    responses(
+        (status = 200, description = "Photo removed; returns the updated lot"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Parking lot or photo not found"),
+    )
+)]
+pub async fn delete_lot_photo(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((id, photo_id)): Path<(String, String)>,
+) -> (StatusCode, Json<ApiResponse<ParkingLot>>) {
+    let state_guard = state.write().await;
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let mut lot = match state_guard.db.get_parking_lot(&id).await {
+        Ok(Some(lot)) => lot,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let url = photo_url(&id, &photo_id);
+    let Some(index) = lot.images.iter().position(|u| *u == url) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::NotFound, "Photo not found")),
+        );
+    };
+
+    if let Err(e) = state_guard.db.delete_lot_photo(&id, &photo_id).await {
+        tracing::error!("Failed to delete lot photo: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to delete photo",
+            )),
+        );
+    }
+    lot.images.remove(index);
+    lot.updated_at = Utc::now();
+    if let Err(e) = state_guard.db.save_parking_lot(&lot).await {
+        tracing::error!("Failed to update lot after photo removal: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to delete photo",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::LotUpdated)
+        .user(
+            auth_user.user_id,
+            &username(&state_guard.db, auth_user.user_id).await,
+        )
+        .resource("lot", &id)
+        .detail(&format!("Lot photo {photo_id} removed"))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(lot)))
+}
+
+/// `GET /api/v1/lots/{id}/photos/{photo_id}` — serve a lot photo.
+#[utoipa::path(get, path = "/api/v1/lots/{id}/photos/{photo_id}", tag = "Lots",
+    summary = "Get a lot photo",
+    description = "Returns the photo as JPEG. Public and rate-limited per IP. Photo URLs are \
+        content-addressed, so responses are cacheable for a year; `If-None-Match` is honoured.",
+    params(
+        ("id" = String, Path, description = "Parking lot ID"),
+        ("photo_id" = String, Path, description = "Photo ID"),
+    ),
+    responses(
+        (status = 200, description = "JPEG bytes", content_type = "image/jpeg"),
+        (status = 304, description = "Not modified"),
+        (status = 404, description = "Photo not found"),
+        (status = 429, description = "Rate limit exceeded"),
+    )
+)]
+pub async fn get_lot_photo(
+    State(state): State<SharedState>,
+    Path((id, photo_id)): Path<(String, String)>,
+    headers: HeaderMap,
+) -> Response {
+    let etag = format!("\"{photo_id}\"");
+    let cache_headers = [
+        (header::ETAG, etag.clone()),
+        (
+            header::CACHE_CONTROL,
+            "public, max-age=31536000, immutable".to_string(),
+        ),
+    ];
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::<()>::error(
+                ErrorCode::NotFound,
+                "Photo not found",
+            )),
+        )
+            .into_response()
+    };
+    if photo_id.len() != 16 || !photo_id.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return not_found();
+    }
+
+    let state_guard = state.read().await;
+    let jpeg = match state_guard.db.get_lot_photo(&id, &photo_id).await {
+        Ok(Some(jpeg)) => jpeg,
+        Ok(None) => return not_found(),
+        Err(e) => {
+            tracing::error!("Failed to load lot photo: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+                .into_response();
+        }
+    };
+    drop(state_guard);
+
+    if headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|t| t.trim() == etag))
+    {
+        return (StatusCode::NOT_MODIFIED, cache_headers).into_response();
+    }
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "image/jpeg")],
+        cache_headers,
+        jpeg,
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(img: &DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut out = Cursor::new(Vec::new());
+        img.write_to(&mut out, format).unwrap();
+        out.into_inner()
+    }
+
+    #[test]
+    fn test_process_photo_downscales_large_images() {
+        let src = DynamicImage::new_rgb8(3200, 1200);
+        let jpeg = process_photo(&encode(&src, ImageFormat::Png)).unwrap();
+        assert_eq!(image::guess_format(&jpeg).unwrap(), ImageFormat::Jpeg);
+        let out = image::load_from_memory(&jpeg).unwrap();
+        assert_eq!((out.width(), out.height()), (MAX_PHOTO_EDGE, 600));
+    }
+
+    #[test]
+    fn test_process_photo_keeps_small_images() {
+        let src = DynamicImage::new_rgba8(320, 200);
+        let jpeg = process_photo(&encode(&src, ImageFormat::Png)).unwrap();
+        let out = image::load_from_memory(&jpeg).unwrap();
+        assert_eq!((out.width(), out.height()), (320, 200));
+    }
+
+    #[test]
+    fn test_process_photo_strips_exif() {
+        let src = DynamicImage::new_rgb8(64, 48);
+        let plain = encode(&src, ImageFormat::Jpeg);
+        // Splice an APP1/Exif segment (orientation: rotate 90° CW) after SOI.
+        let tiff: &[u8] = &[
+            b'M', b'M', 0, 42, 0, 0, 0, 8, // big-endian TIFF header, IFD at 8
+            0, 1, // one entry
+            0x01, 0x12, 0, 3, 0, 0, 0, 1, 0, 6, 0, 0, // Orientation = 6
+            0, 0, 0, 0, // no next IFD
+        ];
+        let mut app1 = vec![0xFF, 0xE1];
+        let len = u16::try_from(2 + 6 + tiff.len()).unwrap();
+        app1.extend_from_slice(&len.to_be_bytes());
+        app1.extend_from_slice(b"Exif\0\0");
+        app1.extend_from_slice(tiff);
+        let mut with_exif = plain[..2].to_vec();
+        with_exif.extend_from_slice(&app1);
+        with_exif.extend_from_slice(&plain[2..]);
+
+        let jpeg = process_photo(&with_exif).unwrap();
+        assert!(!jpeg.windows(4).any(|w| w == b"Exif"));
+        // The orientation was applied before the metadata was dropped.
+        let out = image::load_from_memory(&jpeg).unwrap();
+        assert_eq!((out.width(), out.height()), (48, 64));
+    }
+
+    #[test]
+    fn test_process_photo_rejects_garbage() {
+        assert!(process_photo(b"not a photo").is_err());
+    }
+
+    #[test]
+    fn test_photo_id_is_content_derived() {
+        assert_eq!(photo_id(b"abc"), photo_id(b"abc"));
+        assert_ne!(photo_id(b"abc"), photo_id(b"abd"));
+        assert_eq!(photo_id(b"abc").len(), 16);
+    }
+}
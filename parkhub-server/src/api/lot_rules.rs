@@ -0,0 +1,195 @@
+//! Per-lot booking rules.
+//!
+//! - `GET /api/v1/lots/{id}/rules` — the lot's rules (any authenticated user,
+//!   so clients can disable invalid durations and start times up front)
+//! - `PUT /api/v1/lots/{id}/rules` — replace the rules (admin or lot manager)
+//!
+//! Rules are enforced in `create_booking`, `quick_book`, `cancel_booking` and
+//! `booking_checkin`;
+//! the checks themselves live on [`LotBookingRules`] in `parkhub-common` so
+//! the desktop client applies exactly the same logic. The buffer between
+//! bookings is enforced where slots are claimed, in the database layer.
+//!
+//! # Settings keys
+//! - `lot_booking_rules:{lot_id}` — [`LotBookingRules`] as JSON. Missing or
+//!   unreadable means "no restrictions".
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+
+use parkhub_common::{ApiResponse, ErrorCode, LotBookingRules, LotRuleViolation};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{Database, lot_rules_key};
+
+use super::lot_managers::check_lot_admin;
+use super::{AuthUser, SharedState};
+
+/// Load a lot's booking rules; defaults (no restrictions) when unset.
+pub async fn load_lot_rules(db: &Database, lot_id: &str) -> LotBookingRules {
+    db.get_setting(&lot_rules_key(lot_id))
+        .await
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// Map a rule violation to the handler error tuple used across the API.
+#[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+pub fn violation_response<T>(violation: LotRuleViolation) -> (StatusCode, Json<ApiResponse<T>>) {
+    let status = match violation {
+        LotRuleViolation::CancellationCutoff(_) | LotRuleViolation::CheckInTooEarly(_) => {
+            StatusCode::CONFLICT
+        }
+        _ => StatusCode::BAD_REQUEST,
+    };
+    (
+        status,
+        Json(ApiResponse::error(violation.code(), violation.message())),
+    )
+}
+
+/// `GET /api/v1/lots/{id}/rules` — read a lot's booking rules
+#[utoipa::path(
+    get,
+    path = "/api/v1/lots/{id}/rules",
+    tag = "Lots",
+    summary = "Get lot booking rules",
+    description = "Returns the lot's booking rules (duration limits, lead time, cancellation \
+        cutoff, same-day only, buffer between bookings). Unset limits impose no restriction.",
+    params(("id" = String, Path, description = "Parking lot ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Booking rules", body = LotBookingRules),
+        (status = 404, description = "Parking lot not found"),
+    )
+)]
+pub async fn get_lot_rules(
+    State(state): State<SharedState>,
+    Extension(_auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<LotBookingRules>>) {
+    let state_guard = state.read().await;
+    if !matches!(state_guard.db.get_parking_lot(&lot_id).await, Ok(Some(_))) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
+        );
+    }
+    let rules = load_lot_rules(&state_guard.db, &lot_id).await;
+    (StatusCode::OK, Json(ApiResponse::success(rules)))
+}
+
+/// `PUT /api/v1/lots/{id}/rules` — replace a lot's booking rules
+#[utoipa::path(
+    put,
+    path = "/api/v1/lots/{id}/rules",
+    tag = "Lots",
+    summary = "Update lot booking rules",
+    description = "Replaces the lot's booking rules. Omitted fields are cleared. \
+        Admins and managers of the lot only.",
+    params(("id" = String, Path, description = "Parking lot ID")),
+    request_body = LotBookingRules,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Updated booking rules", body = LotBookingRules),
+        (status = 400, description = "Inconsistent rules"),
+        (status = 403, description = "Not an admin or manager of this lot"),
+        (status = 404, description = "Parking lot not found"),
+    )
+)]
+pub async fn update_lot_rules(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<String>,
+    Json(rules): Json<LotBookingRules>,
+) -> (StatusCode, Json<ApiResponse<LotBookingRules>>) {
+    let state_guard = state.read().await;
+
+    // Admin or manager of this lot
+    if let Err((status, msg)) = check_lot_admin(&auth_user, &lot_id) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    if !matches!(state_guard.db.get_parking_lot(&lot_id).await, Ok(Some(_))) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
+        );
+    }
+
+    if let Err(msg) = rules.validate() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+        );
+    }
+
+    let raw = match serde_json::to_string(&rules) {
+        Ok(raw) => raw,
+        Err(e) => {
+            tracing::error!("Failed to serialize lot rules: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    if let Err(e) = state_guard
+        .db
+        .set_setting(&lot_rules_key(&lot_id), &raw)
+        .await
+    {
+        tracing::error!("Failed to save lot rules: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save rules",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::LotUpdated)
+        .user(auth_user.user_id, "")
+        .resource("lot", &lot_id)
+        .detail("Booking rules updated")
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(rules)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_violation_response_status() {
+        let (status, Json(body)) =
+            violation_response::<()>(LotRuleViolation::CancellationCutoff(60));
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body.error.unwrap().code, "CANCELLATION_CUTOFF");
+
+        let (status, _) = violation_response::<()>(LotRuleViolation::NotSameDay);
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,420 @@
+//! Per-lot overrides of global settings.
+//!
+//! - `GET /api/v1/lots/{id}/settings` — the lot's overrides and the values in
+//!   effect for it (admin or lot manager)
+//! - `PUT /api/v1/lots/{id}/settings` — replace the overrides (admin or lot
+//!   manager)
+//!
+//! Settings resolve in layers: the lot's override, then the admin setting,
+//! then the server config or built-in default. Handlers read them through
+//! [`LotConfig::resolve`] instead of the global settings, so a visitor lot
+//! can require a plate while the staff garage does not.
+//!
+//! Settings that apply before a lot is known — self-registration, session
+//! timeouts — stay global.
+//!
+//! # Settings keys
+//! - `lot_settings:{lot_id}` — [`LotSettingsOverrides`] as JSON. Missing or
+//!   unreadable means "no overrides".
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+use parkhub_common::{ApiResponse, ErrorCode};
+
+use crate::AppState;
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::Database;
+
+use super::lot_managers::check_lot_admin;
+use super::{AuthUser, SharedState, read_admin_setting};
+
+/// Values accepted for `license_plate_mode`.
+const PLATE_MODES: &[&str] = &["required", "optional", "disabled"];
+
+/// Settings key for a lot's overrides.
+fn lot_settings_key(lot_id: &str) -> String {
+    format!("lot_settings:{lot_id}")
+}
+
+/// Settings a lot overrides; `None` inherits the global value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(default)]
+pub struct LotSettingsOverrides {
+    /// Plates on other users' bookings: 0 show, 1 blur, 2 redact, 3 hide
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_plate_display: Option<u8>,
+    /// `required`, `optional` or `disabled`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_plate_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_vehicle: Option<bool>,
+    /// `0` means no minimum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_booking_duration_hours: Option<f64>,
+    /// `0` means no maximum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_booking_duration_hours: Option<f64>,
+    /// Per user and day; `0` means unlimited
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_bookings_per_day: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_guest_bookings: Option<bool>,
+}
+
+impl LotSettingsOverrides {
+    /// Reject values the global settings would not accept either.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.license_plate_display.is_some_and(|mode| mode > 3) {
+            return Err("license_plate_display must be 0-3");
+        }
+        if let Some(ref mode) = self.license_plate_mode
+            && !PLATE_MODES.contains(&mode.as_str())
+        {
+            return Err("license_plate_mode must be required, optional, or disabled");
+        }
+        let min = self.min_booking_duration_hours;
+        let max = self.max_booking_duration_hours;
+        if [min, max]
+            .into_iter()
+            .flatten()
+            .any(|hours| !hours.is_finite() || hours < 0.0)
+        {
+            return Err("Booking durations must be zero or positive");
+        }
+        if let (Some(min), Some(max)) = (min, max)
+            && max > 0.0
+            && min > max
+        {
+            return Err("min_booking_duration_hours must not exceed max_booking_duration_hours");
+        }
+        Ok(())
+    }
+}
+
+/// Settings in effect for one lot, after applying its overrides.
+#[derive(Debug, Clone, PartialEq, Serialize, utoipa::ToSchema)]
+pub struct LotConfig {
+    pub license_plate_display: u8,
+    pub license_plate_mode: String,
+    pub require_vehicle: bool,
+    pub min_booking_duration_hours: f64,
+    pub max_booking_duration_hours: f64,
+    pub max_bookings_per_day: u32,
+    pub allow_guest_bookings: bool,
+}
+
+impl LotConfig {
+    /// The global values: admin settings, then the server config.
+    pub async fn global(state: &AppState) -> Self {
+        let db = &state.db;
+        let plate_mode = read_admin_setting(db, "license_plate_mode").await;
+        Self {
+            license_plate_display: state.config.license_plate_display,
+            license_plate_mode: if plate_mode.is_empty() {
+                "optional".to_string()
+            } else {
+                plate_mode
+            },
+            require_vehicle: read_admin_setting(db, "require_vehicle").await == "true",
+            min_booking_duration_hours: read_admin_setting(db, "min_booking_duration_hours")
+                .await
+                .parse()
+                .unwrap_or(0.0),
+            max_booking_duration_hours: read_admin_setting(db, "max_booking_duration_hours")
+                .await
+                .parse()
+                .unwrap_or(0.0),
+            max_bookings_per_day: read_admin_setting(db, "max_bookings_per_day")
+                .await
+                .parse()
+                .unwrap_or(0),
+            allow_guest_bookings: read_admin_setting(db, "allow_guest_bookings").await == "true",
+        }
+    }
+
+    /// Replace the values `overrides` sets.
+    #[must_use]
+    pub fn with_overrides(self, overrides: &LotSettingsOverrides) -> Self {
+        Self {
+            license_plate_display: overrides
+                .license_plate_display
+                .unwrap_or(self.license_plate_display),
+            license_plate_mode: overrides
+                .license_plate_mode
+                .clone()
+                .unwrap_or(self.license_plate_mode),
+            require_vehicle: overrides.require_vehicle.unwrap_or(self.require_vehicle),
+            min_booking_duration_hours: overrides
+                .min_booking_duration_hours
+                .unwrap_or(self.min_booking_duration_hours),
+            max_booking_duration_hours: overrides
+                .max_booking_duration_hours
+                .unwrap_or(self.max_booking_duration_hours),
+            max_bookings_per_day: overrides
+                .max_bookings_per_day
+                .unwrap_or(self.max_bookings_per_day),
+            allow_guest_bookings: overrides
+                .allow_guest_bookings
+                .unwrap_or(self.allow_guest_bookings),
+        }
+    }
+
+    /// The settings in effect for `lot_id`.
+    pub async fn resolve(state: &AppState, lot_id: &str) -> Self {
+        let overrides = load_lot_settings(&state.db, lot_id).await;
+        Self::global(state).await.with_overrides(&overrides)
+    }
+}
+
+/// Load a lot's overrides; none when unset.
+pub async fn load_lot_settings(db: &Database, lot_id: &str) -> LotSettingsOverrides {
+    db.get_setting(&lot_settings_key(lot_id))
+        .await
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+/// A lot's overrides together with the values in effect
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct LotSettingsResponse {
+    pub overrides: LotSettingsOverrides,
+    pub effective: LotConfig,
+}
+
+/// `GET /api/v1/lots/{id}/settings` — overrides and effective settings
+#[utoipa::path(
+    get,
+    path = "/api/v1/lots/{id}/settings",
+    tag = "Lots",
+    summary = "Get lot settings",
+    description = "Returns the settings the lot overrides and the values in effect for it. \
+        Settings without an override inherit the global admin setting. \
+        Admins and managers of the lot only.",
+    params(("id" = String, Path, description = "Parking lot ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Lot settings", body = LotSettingsResponse),
+        (status = 403, description = "Not an admin or manager of the lot"),
+        (status = 404, description = "Parking lot not found"),
+    )
+)]
+pub async fn get_lot_settings(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<LotSettingsResponse>>) {
+    let state_guard = state.read().await;
+    if let Err((status, msg)) = check_lot_admin(&auth_user, &lot_id) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+    if !matches!(state_guard.db.get_parking_lot(&lot_id).await, Ok(Some(_))) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
+        );
+    }
+    let overrides = load_lot_settings(&state_guard.db, &lot_id).await;
+    let effective = LotConfig::global(&state_guard)
+        .await
+        .with_overrides(&overrides);
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(LotSettingsResponse {
+            overrides,
+            effective,
+        })),
+    )
+}
+
+/// `PUT /api/v1/lots/{id}/settings` — replace the lot's overrides
+#[utoipa::path(
+    put,
+    path = "/api/v1/lots/{id}/settings",
+    tag = "Lots",
+    summary = "Update lot settings",
+    description = "Replaces the lot's overrides. Omitted fields inherit the global setting \
+        again; `{}` removes all overrides. Admins and managers of the lot only.",
+    params(("id" = String, Path, description = "Parking lot ID")),
+    request_body = LotSettingsOverrides,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Updated lot settings", body = LotSettingsResponse),
+        (status = 400, description = "Invalid value"),
+        (status = 403, description = "Not an admin or manager of the lot"),
+        (status = 404, description = "Parking lot not found"),
+    )
+)]
+pub async fn update_lot_settings(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<String>,
+    Json(overrides): Json<LotSettingsOverrides>,
+) -> (StatusCode, Json<ApiResponse<LotSettingsResponse>>) {
+    let state_guard = state.read().await;
+
+    if let Err((status, msg)) = check_lot_admin(&auth_user, &lot_id) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+    if !matches!(state_guard.db.get_parking_lot(&lot_id).await, Ok(Some(_))) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
+        );
+    }
+    if let Err(msg) = overrides.validate() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+        );
+    }
+
+    let raw = match serde_json::to_string(&overrides) {
+        Ok(raw) => raw,
+        Err(e) => {
+            tracing::error!("Failed to serialize lot settings: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    if let Err(e) = state_guard
+        .db
+        .set_setting(&lot_settings_key(&lot_id), &raw)
+        .await
+    {
+        tracing::error!("Failed to save lot settings: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save settings",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::LotUpdated)
+        .user(auth_user.user_id, "")
+        .resource("lot", &lot_id)
+        .detail("Lot settings updated")
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    let effective = LotConfig::global(&state_guard)
+        .await
+        .with_overrides(&overrides);
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(LotSettingsResponse {
+            overrides,
+            effective,
+        })),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn global() -> LotConfig {
+        LotConfig {
+            license_plate_display: 0,
+            license_plate_mode: "optional".to_string(),
+            require_vehicle: false,
+            min_booking_duration_hours: 0.0,
+            max_booking_duration_hours: 8.0,
+            max_bookings_per_day: 2,
+            allow_guest_bookings: false,
+        }
+    }
+
+    #[test]
+    fn test_overrides_replace_only_what_they_set() {
+        assert_eq!(
+            global().with_overrides(&LotSettingsOverrides::default()),
+            global()
+        );
+
+        let overrides = LotSettingsOverrides {
+            license_plate_display: Some(3),
+            license_plate_mode: Some("required".to_string()),
+            max_bookings_per_day: Some(0),
+            ..LotSettingsOverrides::default()
+        };
+        let config = global().with_overrides(&overrides);
+        assert_eq!(config.license_plate_display, 3);
+        assert_eq!(config.license_plate_mode, "required");
+        assert_eq!(config.max_bookings_per_day, 0);
+        assert!((config.max_booking_duration_hours - 8.0).abs() < f64::EPSILON);
+        assert!(!config.allow_guest_bookings);
+    }
+
+    #[test]
+    fn test_validate_rejects_values_global_settings_reject() {
+        assert!(LotSettingsOverrides::default().validate().is_ok());
+
+        let bad = [
+            LotSettingsOverrides {
+                license_plate_display: Some(4),
+                ..LotSettingsOverrides::default()
+            },
+            LotSettingsOverrides {
+                license_plate_mode: Some("mandatory".to_string()),
+                ..LotSettingsOverrides::default()
+            },
+            LotSettingsOverrides {
+                min_booking_duration_hours: Some(-1.0),
+                ..LotSettingsOverrides::default()
+            },
+            LotSettingsOverrides {
+                min_booking_duration_hours: Some(4.0),
+                max_booking_duration_hours: Some(2.0),
+                ..LotSettingsOverrides::default()
+            },
+        ];
+        for overrides in bad {
+            assert!(overrides.validate().is_err(), "{overrides:?}");
+        }
+
+        // A maximum of 0 means "no maximum", so any minimum fits
+        let unlimited = LotSettingsOverrides {
+            min_booking_duration_hours: Some(4.0),
+            max_booking_duration_hours: Some(0.0),
+            ..LotSettingsOverrides::default()
+        };
+        assert!(unlimited.validate().is_ok());
+    }
+
+    #[test]
+    fn test_overrides_json_omits_inherited_settings() {
+        let overrides = LotSettingsOverrides {
+            require_vehicle: Some(true),
+            ..LotSettingsOverrides::default()
+        };
+        let json = serde_json::to_value(&overrides).unwrap();
+        assert_eq!(json, serde_json::json!({ "require_vehicle": true }));
+        let back: LotSettingsOverrides = serde_json::from_value(json).unwrap();
+        assert_eq!(back, overrides);
+    }
+}
@@ -9,21 +9,28 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
 };
-use chrono::Utc;
-use serde::Deserialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use validator::Validate;
 
-use parkhub_common::models::{SlotFeature, SlotPosition, SlotType};
+use parkhub_common::models::{SlotBookingInfo, SlotFeature, SlotPosition, SlotType};
 use parkhub_common::{
-    ApiResponse, LotStatus, OperatingHours, ParkingFloor, ParkingLot, ParkingSlot, PricingInfo,
-    PricingRate, SlotStatus,
+    ApiResponse, Booking, BookingStatus, ErrorCode, LotStatus, OperatingHours, ParkingFloor,
+    ParkingLot, ParkingSlot, Permission, PricingInfo, PricingRate, SlotStatus,
 };
 
-use crate::requests::{CreateParkingLotRequest, UpdateParkingLotRequest, parse_lot_status};
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{SlotChangeContext, WriteBatch};
+use crate::requests::{
+    CloneParkingLotRequest, CreateParkingLotRequest, LOT_SORT_FIELDS, ListParams, LotListFilters,
+    UpdateParkingLotRequest, parse_lot_status,
+};
+use crate::validation::ValidatedJson;
 
+use super::lot_managers::check_lot_admin;
+use super::plate_display::PlateViewer;
 use super::{AuthUser, SharedState};
-use parkhub_common::UserRole;
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Query params
@@ -33,7 +40,8 @@ use parkhub_common::UserRole;
 #[derive(Debug, Deserialize, Default, utoipa::IntoParams)]
 pub struct SlotFilterParams {
     /// Filter by slot type: `standard`, `compact`, `large`, `handicap`,
-    /// `electric`, `motorcycle`, `reserved`, `vip`
+    /// `electric`, `motorcycle`, `reserved`, `vip`. Also accepted as `type`.
+    #[serde(alias = "type")]
     pub slot_type: Option<String>,
     /// Filter by slot status: `available`, `occupied`, `reserved`,
     /// `maintenance`, `disabled`
@@ -43,11 +51,44 @@ pub struct SlotFilterParams {
     pub feature: Option<String>,
 }
 
+/// Query parameters for `DELETE /api/v1/lots/{id}`.
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct DeleteLotParams {
+    /// Cancel the lot's pending, confirmed and active bookings instead of
+    /// refusing the delete.
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Default search radius for `GET /api/v1/lots/search`, in meters.
+const DEFAULT_SEARCH_RADIUS_M: f64 = 5_000.0;
+/// Upper bound on the search radius, in meters.
+const MAX_SEARCH_RADIUS_M: f64 = 100_000.0;
+
+/// Query parameters for `GET /api/v1/lots/search`.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct LotSearchParams {
+    /// Latitude of the search origin
+    pub lat: f64,
+    /// Longitude of the search origin
+    pub lon: f64,
+    /// Search radius in meters (default 5000, max 100000)
+    pub radius: Option<f64>,
+}
+
+/// A lot matched by a location search, with its distance from the origin.
+#[derive(Debug, Serialize)]
+pub struct LotSearchResult {
+    #[serde(flatten)]
+    pub lot: ParkingLot,
+    pub distance_meters: f64,
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Helpers
 // ─────────────────────────────────────────────────────────────────────────────
 
-fn parse_slot_type(s: &str) -> Option<SlotType> {
+pub(super) fn parse_slot_type(s: &str) -> Option<SlotType> {
     match s.to_lowercase().as_str() {
         "standard" => Some(SlotType::Standard),
         "compact" => Some(SlotType::Compact),
@@ -72,7 +113,59 @@ fn parse_slot_status(s: &str) -> Option<SlotStatus> {
     }
 }
 
-fn parse_slot_feature(s: &str) -> Option<SlotFeature> {
+/// The optional `slot_number` of a slot create/update body.
+fn requested_slot_number(req: &serde_json::Value) -> Result<Option<i32>, &'static str> {
+    match req.get("slot_number") {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .filter(|n| *n > 0)
+            .map(Some)
+            .ok_or("slot_number must be a positive integer"),
+    }
+}
+
+/// The optional `floor_id` of a slot create/update body; it must name one of
+/// `lot`'s floors.
+fn requested_floor_id(
+    req: &serde_json::Value,
+    lot: &ParkingLot,
+) -> Result<Option<Uuid>, &'static str> {
+    match req.get("floor_id") {
+        None | Some(serde_json::Value::Null) => Ok(None),
+        Some(v) => v
+            .as_str()
+            .and_then(|s| Uuid::parse_str(s).ok())
+            .filter(|id| lot.floors.iter().any(|f| f.id == *id))
+            .map(Some)
+            .ok_or("floor_id must be a floor of this lot"),
+    }
+}
+
+/// Save a slot write together with the recomputed slot counts of its lot,
+/// so the lot's `total_slots`/`available_slots` match its slots.
+async fn apply_slot_write(
+    db: &crate::db::Database,
+    mut batch: WriteBatch,
+    lot_id: Uuid,
+) -> anyhow::Result<()> {
+    batch.recount_lot_slots(lot_id);
+    db.apply_batch(&batch).await?;
+    Ok(())
+}
+
+fn slot_number_taken() -> (StatusCode, Json<ApiResponse<ParkingSlot>>) {
+    (
+        StatusCode::CONFLICT,
+        Json(ApiResponse::error(
+            ErrorCode::Duplicate,
+            "A slot with this number already exists in this lot",
+        )),
+    )
+}
+
+pub(super) fn parse_slot_feature(s: &str) -> Option<SlotFeature> {
     match s.to_lowercase().as_str() {
         "near_exit" => Some(SlotFeature::NearExit),
         "near_elevator" => Some(SlotFeature::NearElevator),
@@ -86,6 +179,34 @@ fn parse_slot_feature(s: &str) -> Option<SlotFeature> {
     }
 }
 
+/// Fill `current_booking` on each slot from the confirmed or active booking
+/// covering `now`, with the plate rendered for `viewer`.
+fn attach_current_bookings(
+    slots: &mut [ParkingSlot],
+    bookings: &[Booking],
+    viewer: &PlateViewer,
+    now: DateTime<Utc>,
+) {
+    for slot in slots {
+        slot.current_booking = bookings
+            .iter()
+            .find(|b| {
+                b.slot_id == slot.id
+                    && matches!(b.status, BookingStatus::Confirmed | BookingStatus::Active)
+                    && b.start_time <= now
+                    && b.end_time > now
+            })
+            .map(|b| SlotBookingInfo {
+                booking_id: b.id,
+                user_id: b.user_id,
+                license_plate: viewer.plate(b.user_id, &b.vehicle.license_plate),
+                start_time: b.start_time,
+                end_time: b.end_time,
+                is_own_booking: viewer.owns(b.user_id),
+            });
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Handlers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -95,30 +216,147 @@ fn parse_slot_feature(s: &str) -> Option<SlotFeature> {
     path = "/api/v1/lots",
     tag = "Lots",
     summary = "List all parking lots",
-    description = "Returns all parking lots with their configuration and status.",
+    description = "Returns all active parking lots with their configuration and status. \
+        Archived lots are omitted. Lots are sorted by `name` (default), `created_at`, \
+        `total_slots` or `available_slots`; without an explicit `sort` or paging the \
+        caller's favorite lots are listed first. With `page`, `per_page` or `cursor` one \
+        page is returned and `meta` holds the totals and the `next_cursor`.",
+    params(ListParams, LotListFilters),
     responses(
         (status = 200, description = "List of all parking lots"),
+        (status = 400, description = "Unknown sort field or malformed cursor"),
     )
 )]
-#[tracing::instrument(skip(state))]
-pub async fn list_lots(State(state): State<SharedState>) -> Json<ApiResponse<Vec<ParkingLot>>> {
+#[tracing::instrument(skip(state, auth_user))]
+pub async fn list_lots(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(params): Query<ListParams>,
+    Query(filters): Query<LotListFilters>,
+) -> (StatusCode, Json<ApiResponse<Vec<ParkingLot>>>) {
+    let (query, sort) = match super::list_request(&params, LOT_SORT_FIELDS, false) {
+        Ok(request) => request,
+        Err(response) => return response,
+    };
     let state = state.read().await;
 
-    match state.db.list_parking_lots().await {
-        Ok(lots) => {
+    let archived = super::lot_archive::archived_lot_ids(&state.db).await;
+    let result = state
+        .db
+        .list_parking_lots_page(
+            (!archived.is_empty() || !filters.is_empty())
+                .then_some(|lot: &ParkingLot| !archived.contains(&lot.id) && filters.matches(lot)),
+            sort,
+            &query,
+        )
+        .await;
+    match result {
+        Ok(page) => {
+            #[cfg_attr(not(feature = "mod-favorites"), allow(unused_mut))]
+            let mut lots = page.items;
+            // Favorites first would break cursors, so only whole lists in
+            // the default order get them.
+            #[cfg(feature = "mod-favorites")]
+            if !params.is_paged() && params.sort.is_none() {
+                let favorites = super::favorites::favorite_lot_ids(&state, auth_user.user_id).await;
+                super::favorites::favorites_first(&mut lots, &favorites, |lot| lot.id);
+            }
+            #[cfg(not(feature = "mod-favorites"))]
+            let _ = &auth_user;
             tracing::debug!(count = lots.len(), "Listed parking lots");
-            Json(ApiResponse::success(lots))
+            let mut response = ApiResponse::success(lots);
+            if params.is_paged() {
+                response.meta = Some(params.meta(page.total, page.next_cursor));
+            }
+            (StatusCode::OK, Json(response))
         }
         Err(e) => {
             tracing::error!(error = %e, "Failed to list parking lots");
-            Json(ApiResponse::error(
-                "SERVER_ERROR",
-                "Failed to list parking lots",
-            ))
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list parking lots",
+                )),
+            )
         }
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/v1/lots/search",
+    tag = "Lots",
+    summary = "Search parking lots by location",
+    description = "Returns parking lots within `radius` meters of `lat`/`lon`, closest first. \
+        Lots without coordinates are not included.",
+    params(LotSearchParams),
+    responses(
+        (status = 200, description = "Matching lots sorted by distance"),
+        (status = 400, description = "Invalid coordinates or radius"),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn search_lots(
+    State(state): State<SharedState>,
+    Query(params): Query<LotSearchParams>,
+) -> (StatusCode, Json<ApiResponse<Vec<LotSearchResult>>>) {
+    if let Err(msg) = crate::geo::validate_coordinates(params.lat, params.lon) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+        );
+    }
+    let radius = params.radius.unwrap_or(DEFAULT_SEARCH_RADIUS_M);
+    if !radius.is_finite() || radius <= 0.0 || radius > MAX_SEARCH_RADIUS_M {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::ValidationError,
+                "Radius must be between 0 and 100000 meters",
+            )),
+        );
+    }
+
+    let state = state.read().await;
+    let lots = match state.db.list_parking_lots().await {
+        Ok(lots) => lots,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to list parking lots");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list parking lots",
+                )),
+            );
+        }
+    };
+
+    let archived = super::lot_archive::archived_lot_ids(&state.db).await;
+    let mut results: Vec<LotSearchResult> = lots
+        .into_iter()
+        .filter(|lot| !archived.contains(&lot.id))
+        .filter(|lot| !crate::geo::is_unset(lot.latitude, lot.longitude))
+        .filter_map(|lot| {
+            let distance_meters =
+                crate::geo::haversine_distance(params.lat, params.lon, lot.latitude, lot.longitude);
+            (distance_meters <= radius).then_some(LotSearchResult {
+                lot,
+                distance_meters,
+            })
+        })
+        .collect();
+    results.sort_by(|a, b| a.distance_meters.total_cmp(&b.distance_meters));
+
+    tracing::debug!(
+        count = results.len(),
+        radius,
+        "Searched parking lots by location"
+    );
+    (StatusCode::OK, Json(ApiResponse::success(results)))
+}
+
 #[utoipa::path(
     post,
     path = "/api/v1/lots",
@@ -137,30 +375,32 @@ pub async fn list_lots(State(state): State<SharedState>) -> Json<ApiResponse<Vec
 pub async fn create_lot(
     State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
-    Json(req): Json<CreateParkingLotRequest>,
+    ValidatedJson(req): ValidatedJson<CreateParkingLotRequest>,
 ) -> (StatusCode, Json<ApiResponse<ParkingLot>>) {
-    // Validate the request DTO
-    if let Err(errors) = req.validate() {
-        let msg = errors
-            .field_errors()
-            .values()
-            .flat_map(|errs| errs.iter().filter_map(|e| e.message.as_deref()))
-            .collect::<Vec<_>>()
-            .join("; ");
-        return (
-            StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error(
-                "VALIDATION_ERROR",
-                if msg.is_empty() {
-                    "Invalid request"
-                } else {
-                    &msg
-                },
-            )),
-        );
-    }
+    // Coordinates come as a pair; a lone latitude or longitude is a client bug.
+    let coordinates = match (req.latitude, req.longitude) {
+        (Some(lat), Some(lon)) => {
+            if let Err(msg) = crate::geo::validate_coordinates(lat, lon) {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+                );
+            }
+            Some((lat, lon))
+        }
+        (None, None) => None,
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ErrorCode::ValidationError,
+                    "Latitude and longitude must be provided together",
+                )),
+            );
+        }
+    };
 
-    let state_guard = state.read().await;
+    let mut state_guard = state.read().await;
 
     // Check if user is admin
     let Ok(Some(user)) = state_guard
@@ -170,17 +410,35 @@ pub async fn create_lot(
     else {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     };
 
-    if user.role != UserRole::Admin && user.role != UserRole::SuperAdmin {
+    if !auth_user.permissions.has_global(Permission::ManageLots) {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
+    // No coordinates supplied: try the configured geocoder with the address.
+    // The state lock is released for the lookup so a slow geocoder does not
+    // stall writers.
+    let (latitude, longitude) = if let Some(coords) = coordinates {
+        coords
+    } else {
+        drop(state_guard);
+        let geocoded = match req.address.as_deref() {
+            Some(address) => crate::geo::geocode_address(address).await,
+            None => None,
+        };
+        state_guard = state.read().await;
+        geocoded.unwrap_or((0.0, 0.0))
+    };
+
     let now = Utc::now();
     let lot_id = Uuid::new_v4();
 
@@ -230,8 +488,8 @@ pub async fn create_lot(
         id: lot_id,
         name: req.name,
         address: req.address.unwrap_or_default(),
-        latitude: req.latitude.unwrap_or(0.0),
-        longitude: req.longitude.unwrap_or(0.0),
+        latitude,
+        longitude,
         total_slots: req.total_slots,
         available_slots: req.total_slots,
         floors: vec![default_floor],
@@ -257,7 +515,7 @@ pub async fn create_lot(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to create parking lot",
             )),
         );
@@ -351,7 +609,7 @@ pub async fn update_lot(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 if msg.is_empty() {
                     "Invalid request"
                 } else {
@@ -363,22 +621,13 @@ pub async fn update_lot(
 
     let state_guard = state.read().await;
 
-    // Check if user is admin
-    let Ok(Some(user)) = state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    else {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
-        );
-    };
-
-    if user.role != UserRole::Admin && user.role != UserRole::SuperAdmin {
+    if !auth_user.permissions.has_global(Permission::ManageLots) {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -388,14 +637,20 @@ pub async fn update_lot(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -423,7 +678,7 @@ pub async fn update_lot(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "Invalid status. Valid: open, closed, full, maintenance",
                 )),
             );
@@ -465,11 +720,18 @@ pub async fn update_lot(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update parking lot",
             )),
         );
     }
+    state_guard
+        .ws_events
+        .broadcast(super::events::LiveEvent::lot_updated(
+            &lot.id.to_string(),
+            &lot.name,
+            &super::events::wire_name(&lot.status),
+        ));
     drop(state_guard);
 
     tracing::info!("Updated parking lot '{}' ({})", lot.name, lot.id);
@@ -482,19 +744,222 @@ pub async fn update_lot(
     path = "/api/v1/lots/{id}",
     tag = "Lots",
     summary = "Delete a parking lot",
-    description = "Permanently remove a parking lot and all its slots. Admin only.",
-    params(("id" = String, Path, description = "Parking lot ID")),
+    description = "Permanently remove a parking lot and all its slots. Refused while the lot has \
+        pending, confirmed or active bookings, unless `force=true`: those bookings are then \
+        cancelled with a full refund in the same transaction. Admin only.",
+    params(
+        ("id" = String, Path, description = "Parking lot ID"),
+        DeleteLotParams,
+    ),
     responses(
         (status = 200, description = "Parking lot deleted"),
         (status = 403, description = "Admin access required"),
         (status = 404, description = "Parking lot not found"),
+        (status = 409, description = "The lot has active bookings and `force` was not set"),
     )
 )]
+#[tracing::instrument(skip(state, params), fields(admin_id = %auth_user.user_id, lot_id = %id, force = params.force))]
 pub async fn delete_lot(
     State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(id): Path<String>,
+    Query(params): Query<DeleteLotParams>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
+    if !auth_user.permissions.has_global(Permission::ManageLots) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
+    }
+
+    // Write lock so no booking is made in the lot between the check and
+    // the delete — as in `cancel_booking`.
+    let state_guard = state.write().await;
+
+    let lot = match state_guard.db.get_parking_lot(&id).await {
+        Ok(Some(lot)) => lot,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let active: Vec<Booking> = match state_guard.db.list_bookings().await {
+        Ok(bookings) => bookings
+            .into_iter()
+            .filter(|b| {
+                b.lot_id == lot.id
+                    && matches!(
+                        b.status,
+                        BookingStatus::Pending | BookingStatus::Confirmed | BookingStatus::Active
+                    )
+            })
+            .collect(),
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    if !active.is_empty() && !params.force {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::LotHasActiveBookings,
+                format!(
+                    "The parking lot has {} active booking(s). Pass force=true to cancel them \
+                     and delete the lot.",
+                    active.len()
+                ),
+            )),
+        );
+    }
+
+    // Cancellations, slots and the lot go in one transaction
+    let applied =
+        match super::bulk::stage_cancellations(&state_guard, auth_user.user_id, &active).await {
+            Ok(mut batch) => {
+                batch.delete_parking_lot(lot.id);
+                state_guard.db.apply_batch(&batch).await
+            }
+            Err(e) => Err(e),
+        };
+    if let Err(e) = applied {
+        tracing::error!("Failed to delete parking lot: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to delete parking lot",
+            )),
+        );
+    }
+    if let Err(e) = state_guard.db.delete_lot_photos(&id).await {
+        tracing::warn!("Failed to delete photos for lot {}: {}", id, e);
+    }
+
+    let admin_username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    for booking in &active {
+        AuditEntry::new(AuditEventType::BookingCancelled)
+            .user(auth_user.user_id, &admin_username)
+            .resource("booking", &booking.id.to_string())
+            .detail("parking lot deleted")
+            .log();
+        state_guard
+            .ws_events
+            .broadcast(super::events::LiveEvent::booking_cancelled(
+                &id,
+                &booking.slot_id.to_string(),
+            ));
+    }
+    AuditEntry::new(AuditEventType::LotDeleted)
+        .user(auth_user.user_id, &admin_username)
+        .resource("lot", &id)
+        .detail(&format!(
+            "{} ({} booking(s) cancelled)",
+            lot.name,
+            active.len()
+        ))
+        .log();
+    state_guard
+        .ws_events
+        .broadcast(super::events::LiveEvent::lot_deleted(&id));
+    drop(state_guard);
+
+    tracing::info!(
+        "Deleted parking lot '{}' ({}), cancelled {} booking(s)",
+        lot.name,
+        id,
+        active.len()
+    );
+    (StatusCode::OK, Json(ApiResponse::success(())))
+}
+
+/// `POST /api/v1/admin/lots/{id}/clone` — duplicate a lot's layout under a new name
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/lots/{id}/clone",
+    tag = "Lots",
+    summary = "Clone a parking lot",
+    description = "Create a new lot with the same floors, slots (numbers, types, features, \
+        positions), pricing, and operating hours as an existing lot. Bookings are not \
+        copied and every slot starts out available. Admin only.",
+    params(("id" = String, Path, description = "Source parking lot ID")),
+    request_body = CloneParkingLotRequest,
+    responses(
+        (status = 201, description = "Parking lot cloned"),
+        (status = 400, description = "Validation error"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Parking lot not found"),
+        (status = 409, description = "A lot with this name already exists"),
+    )
+)]
+#[tracing::instrument(skip(state, req), fields(admin_id = %auth_user.user_id, source_lot = %id))]
+pub async fn clone_lot(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(req): Json<CloneParkingLotRequest>,
+) -> (StatusCode, Json<ApiResponse<ParkingLot>>) {
+    if let Err(errors) = req.validate() {
+        let msg = errors
+            .field_errors()
+            .values()
+            .flat_map(|errs| errs.iter().filter_map(|e| e.message.as_deref()))
+            .collect::<Vec<_>>()
+            .join("; ");
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::ValidationError,
+                if msg.is_empty() {
+                    "Invalid request"
+                } else {
+                    &msg
+                },
+            )),
+        );
+    }
+    if req.latitude.is_some() != req.longitude.is_some() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::ValidationError,
+                "Latitude and longitude must be provided together",
+            )),
+        );
+    }
+
     let state_guard = state.read().await;
 
     // Check if user is admin
@@ -505,43 +970,191 @@ pub async fn delete_lot(
     else {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     };
 
-    if user.role != UserRole::Admin && user.role != UserRole::SuperAdmin {
+    if !auth_user.permissions.has_global(Permission::ManageLots) {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
-    let result = state_guard.db.delete_parking_lot(&id).await;
-    match result {
-        Ok(true) => {
-            // Cascade-delete orphaned slots belonging to this lot
-            if let Err(e) = state_guard.db.delete_slots_by_lot(&id).await {
-                tracing::error!("Failed to cascade-delete slots for lot {}: {}", id, e);
-            }
-            drop(state_guard);
-            tracing::info!("Deleted parking lot: {}", id);
-            (StatusCode::OK, Json(ApiResponse::success(())))
+    let source = match state_guard.db.get_parking_lot(&id).await {
+        Ok(Some(lot)) => lot,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
+            );
         }
-        Ok(false) => (
-            StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
-        ),
         Err(e) => {
-            tracing::error!("Failed to delete parking lot: {}", e);
-            (
+            tracing::error!("Database error: {}", e);
+            return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
-                    "Failed to delete parking lot",
+                    ErrorCode::ServerError,
+                    "Internal server error",
                 )),
-            )
+            );
+        }
+    };
+
+    let name = req.name.trim().to_string();
+    match state_guard.db.list_parking_lots().await {
+        Ok(lots) if lots.iter().any(|l| l.name.eq_ignore_ascii_case(&name)) => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::Duplicate,
+                    "A parking lot with this name already exists",
+                )),
+            );
         }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    }
+
+    let source_slots = match state_guard.db.list_slots_by_lot(&id).await {
+        Ok(slots) => slots,
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let now = Utc::now();
+    let lot_id = Uuid::new_v4();
+    let floor_ids: std::collections::HashMap<Uuid, Uuid> = source
+        .floors
+        .iter()
+        .map(|f| (f.id, Uuid::new_v4()))
+        .collect();
+
+    // Slots keep their layout but not their occupancy; maintenance and
+    // disabled slots stay out of service in the copy.
+    let slots: Vec<ParkingSlot> = source_slots
+        .into_iter()
+        .map(|slot| ParkingSlot {
+            id: Uuid::new_v4(),
+            lot_id,
+            floor_id: floor_ids
+                .get(&slot.floor_id)
+                .copied()
+                .unwrap_or(slot.floor_id),
+            status: match slot.status {
+                SlotStatus::Maintenance | SlotStatus::Disabled => slot.status,
+                _ => SlotStatus::Available,
+            },
+            current_booking: None,
+            ..slot
+        })
+        .collect();
+    let available = slots
+        .iter()
+        .filter(|s| s.status == SlotStatus::Available)
+        .count();
+    let total_slots = i32::try_from(slots.len()).unwrap_or(i32::MAX);
+
+    let floors = source
+        .floors
+        .iter()
+        .map(|floor| {
+            let new_id = floor_ids[&floor.id];
+            let floor_slots = slots.iter().filter(|s| s.floor_id == new_id);
+            ParkingFloor {
+                id: new_id,
+                lot_id,
+                name: floor.name.clone(),
+                floor_number: floor.floor_number,
+                total_slots: i32::try_from(floor_slots.clone().count()).unwrap_or(i32::MAX),
+                available_slots: i32::try_from(
+                    floor_slots
+                        .filter(|s| s.status == SlotStatus::Available)
+                        .count(),
+                )
+                .unwrap_or(i32::MAX),
+                slots: Vec::new(),
+            }
+        })
+        .collect();
+
+    let (latitude, longitude) = match (req.latitude, req.longitude) {
+        (Some(lat), Some(lon)) => (lat, lon),
+        _ => (source.latitude, source.longitude),
+    };
+    let lot = ParkingLot {
+        id: lot_id,
+        name,
+        address: req.address.unwrap_or_else(|| source.address.clone()),
+        latitude,
+        longitude,
+        total_slots,
+        available_slots: i32::try_from(available).unwrap_or(i32::MAX),
+        floors,
+        amenities: source.amenities.clone(),
+        pricing: source.pricing.clone(),
+        operating_hours: source.operating_hours.clone(),
+        images: Vec::new(),
+        status: LotStatus::Open,
+        created_at: now,
+        updated_at: now,
+        tenant_id: user.tenant_id.clone(),
+    };
+
+    if let Err(e) = state_guard
+        .db
+        .import_lots(std::slice::from_ref(&lot), &slots)
+        .await
+    {
+        tracing::error!("Failed to save cloned parking lot: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to clone parking lot",
+            )),
+        );
     }
+
+    AuditEntry::new(AuditEventType::LotCreated)
+        .user(auth_user.user_id, &user.username)
+        .resource("lot", &lot.id.to_string())
+        .detail(&format!("Cloned from lot {id}"))
+        .log();
+    drop(state_guard);
+
+    tracing::info!(
+        "Cloned parking lot {} into '{}' ({}) with {} slots",
+        id,
+        lot.name,
+        lot.id,
+        slots.len(),
+    );
+
+    (StatusCode::CREATED, Json(ApiResponse::success(lot)))
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -585,13 +1198,19 @@ pub async fn get_lot_pricing(
         Ok(Some(lot)) => (StatusCode::OK, Json(ApiResponse::success(lot.pricing))),
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
         ),
         Err(e) => {
             tracing::error!("Database error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
@@ -622,19 +1241,14 @@ pub async fn update_lot_pricing(
 ) -> (StatusCode, Json<ApiResponse<PricingInfo>>) {
     let state_guard = state.write().await;
 
-    // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if !auth_user.permissions.has_global(Permission::ManageLots) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     let mut lot = match state_guard.db.get_parking_lot(&id).await {
@@ -642,14 +1256,20 @@ pub async fn update_lot_pricing(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -660,7 +1280,7 @@ pub async fn update_lot_pricing(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "hourly_rate must be >= 0",
                 )),
             );
@@ -685,7 +1305,7 @@ pub async fn update_lot_pricing(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "daily_max must be >= 0",
                 )),
             );
@@ -697,7 +1317,7 @@ pub async fn update_lot_pricing(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "monthly_pass must be >= 0",
                 )),
             );
@@ -715,7 +1335,7 @@ pub async fn update_lot_pricing(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update pricing",
             )),
         );
@@ -747,13 +1367,19 @@ pub async fn get_lot(
         Ok(Some(lot)) => (StatusCode::OK, Json(ApiResponse::success(lot))),
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
         ),
         Err(e) => {
             tracing::error!("Database error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
@@ -765,10 +1391,10 @@ pub async fn get_lot(
     tag = "Lots",
     summary = "List slots in a parking lot",
     description = "Returns parking slots in the specified lot. Optionally filter by \
-        `slot_type` (standard, compact, large, handicap, electric, motorcycle, reserved, vip), \
+        `slot_type` or `type` (standard, compact, large, handicap, electric, motorcycle, reserved, vip), \
         `status` (available, occupied, reserved, maintenance, disabled), or \
         `feature` (near_exit, near_elevator, near_stairs, covered, security_camera, \
-        well_lit, wide_lane, charging_station).",
+        well_lit, wide_lane, charging_station). The caller's favorite slots are listed first.",
     params(
         ("id" = String, Path, description = "Parking lot ID"),
         SlotFilterParams,
@@ -780,8 +1406,22 @@ pub async fn get_lot(
 )]
 pub async fn get_lot_slots(
     State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
     Path(id): Path<String>,
     Query(filters): Query<SlotFilterParams>,
+) -> (StatusCode, Json<ApiResponse<Vec<ParkingSlot>>>) {
+    list_lot_slots(&state, &auth_user, &id, &filters, None).await
+}
+
+/// The slots of lot `id` matching `filters`, restricted to floor `floor_id`
+/// if given, with their current bookings; the body of `get_lot_slots` and
+/// `lot_floors::get_floor_slots`.
+pub(super) async fn list_lot_slots(
+    state: &SharedState,
+    auth_user: &AuthUser,
+    id: &str,
+    filters: &SlotFilterParams,
+    floor_id: Option<Uuid>,
 ) -> (StatusCode, Json<ApiResponse<Vec<ParkingSlot>>>) {
     // Validate filter params upfront so we can return 400 on unknown values
     let type_filter = if let Some(ref t) = filters.slot_type {
@@ -791,7 +1431,7 @@ pub async fn get_lot_slots(
                 return (
                     StatusCode::BAD_REQUEST,
                     Json(ApiResponse::error(
-                        "VALIDATION_ERROR",
+                        ErrorCode::ValidationError,
                         "Invalid slot_type. Valid: standard, compact, large, handicap, electric, motorcycle, reserved, vip",
                     )),
                 );
@@ -808,7 +1448,7 @@ pub async fn get_lot_slots(
                 return (
                     StatusCode::BAD_REQUEST,
                     Json(ApiResponse::error(
-                        "VALIDATION_ERROR",
+                        ErrorCode::ValidationError,
                         "Invalid status. Valid: available, occupied, reserved, maintenance, disabled",
                     )),
                 );
@@ -825,7 +1465,7 @@ pub async fn get_lot_slots(
                 return (
                     StatusCode::BAD_REQUEST,
                     Json(ApiResponse::error(
-                        "VALIDATION_ERROR",
+                        ErrorCode::ValidationError,
                         "Invalid feature. Valid: near_exit, near_elevator, near_stairs, covered, security_camera, well_lit, wide_lane, charging_station",
                     )),
                 );
@@ -837,20 +1477,29 @@ pub async fn get_lot_slots(
 
     let state = state.read().await;
 
-    let slots = match state.db.list_slots_by_lot(&id).await {
+    // A type filter reads only the matching slots from the type index
+    let slots = match &type_filter {
+        Some(slot_type) => state.db.list_slots_by_lot_and_type(id, slot_type).await,
+        None => state.db.list_slots_by_lot(id).await,
+    };
+    let slots = match slots {
         Ok(s) => s,
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to list slots")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list slots",
+                )),
             );
         }
     };
 
     // Apply in-memory filters
-    let filtered: Vec<ParkingSlot> = slots
+    let mut filtered: Vec<ParkingSlot> = slots
         .into_iter()
+        .filter(|s| floor_id.is_none_or(|f| s.floor_id == f))
         .filter(|s| type_filter.as_ref().is_none_or(|t| &s.slot_type == t))
         .filter(|s| status_filter.as_ref().is_none_or(|st| &s.status == st))
         .filter(|s| {
@@ -860,8 +1509,23 @@ pub async fn get_lot_slots(
         })
         .collect();
 
+    match state.db.list_bookings().await {
+        Ok(bookings) => {
+            let viewer = PlateViewer::load_for_lot(&state, auth_user.user_id, id).await;
+            attach_current_bookings(&mut filtered, &bookings, &viewer, Utc::now());
+        }
+        Err(e) => tracing::warn!("Failed to load bookings for slot occupancy: {e}"),
+    }
+
+    #[cfg(feature = "mod-favorites")]
+    {
+        let favorites = super::favorites::favorite_slot_ids(&state, auth_user.user_id).await;
+        super::favorites::favorites_first(&mut filtered, &favorites, |slot| slot.id);
+    }
+
     tracing::debug!(
         lot_id = %id,
+        floor_id = ?floor_id,
         total = filtered.len(),
         slot_type = ?filters.slot_type,
         status = ?filters.status,
@@ -873,7 +1537,7 @@ pub async fn get_lot_slots(
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Slot CRUD (admin only)
+// Slot CRUD (admins and lot managers)
 // ─────────────────────────────────────────────────────────────────────────────
 
 /// `POST /api/v1/lots/{lot_id}/slots` — create a new slot in a lot
@@ -882,12 +1546,16 @@ pub async fn get_lot_slots(
     path = "/api/v1/lots/{lot_id}/slots",
     tag = "Lots",
     summary = "Create a parking slot",
-    description = "Add a new slot to a parking lot. Admin only.",
+    description = "Add a new slot to a parking lot and recompute the lot's slot counts. \
+        `slot_number` defaults to the next free number, `slot_type` to `standard` and \
+        `floor_id` to the lot's first floor. Admins and managers of the lot only.",
     params(("lot_id" = String, Path, description = "Parking lot ID")),
     responses(
         (status = 201, description = "Slot created"),
-        (status = 403, description = "Admin access required"),
+        (status = 400, description = "Unknown slot type, invalid slot number or unknown floor"),
+        (status = 403, description = "Admin or lot manager access required"),
         (status = 404, description = "Parking lot not found"),
+        (status = 409, description = "Slot number already used in this lot"),
     )
 )]
 pub async fn create_slot(
@@ -898,19 +1566,9 @@ pub async fn create_slot(
 ) -> (StatusCode, Json<ApiResponse<ParkingSlot>>) {
     let state_guard = state.read().await;
 
-    // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    // Admin or manager of this lot
+    if let Err((status, msg)) = check_lot_admin(&auth_user, &lot_id) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     // Verify lot exists
@@ -919,24 +1577,47 @@ pub async fn create_slot(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
-    let floor_id = lot.floors.first().map_or_else(Uuid::new_v4, |f| f.id);
-    let existing_slots = state_guard
-        .db
-        .list_slots_by_lot(&lot_id)
-        .await
-        .unwrap_or_default();
+    let floor_id = match requested_floor_id(&req, &lot) {
+        Ok(Some(floor_id)) => floor_id,
+        Ok(None) => lot.floors.first().map_or_else(Uuid::new_v4, |f| f.id),
+        Err(msg) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+            );
+        }
+    };
+    let existing_slots = match state_guard.db.list_slots_by_lot(&lot_id).await {
+        Ok(slots) => slots,
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
     let next_number = existing_slots
         .iter()
         .map(|s| s.slot_number)
@@ -944,26 +1625,34 @@ pub async fn create_slot(
         .unwrap_or(0)
         + 1;
 
-    let slot_type_str = req
-        .get("slot_type")
-        .and_then(|v| v.as_str())
-        .unwrap_or("standard");
-    let slot_type = match slot_type_str {
-        "compact" => SlotType::Compact,
-        "large" => SlotType::Large,
-        "handicap" => SlotType::Handicap,
-        "electric" => SlotType::Electric,
-        "motorcycle" => SlotType::Motorcycle,
-        "vip" => SlotType::Vip,
-        _ => SlotType::Standard,
+    let slot_type = match req.get("slot_type").and_then(|v| v.as_str()) {
+        None => SlotType::Standard,
+        Some(s) => match parse_slot_type(s) {
+            Some(t) => t,
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(
+                        ErrorCode::ValidationError,
+                        "Unknown slot_type",
+                    )),
+                );
+            }
+        },
     };
 
-    let raw_slot_number = req
-        .get("slot_number")
-        .and_then(serde_json::Value::as_i64)
-        .unwrap_or_else(|| i64::from(next_number));
-    #[allow(clippy::cast_possible_truncation)]
-    let slot_number = raw_slot_number as i32;
+    let slot_number = match requested_slot_number(&req) {
+        Ok(number) => number.unwrap_or(next_number),
+        Err(msg) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+            );
+        }
+    };
+    if existing_slots.iter().any(|s| s.slot_number == slot_number) {
+        return slot_number_taken();
+    }
 
     let slot = ParkingSlot {
         id: Uuid::new_v4(),
@@ -988,11 +1677,16 @@ pub async fn create_slot(
         is_accessible: false,
     };
 
-    if let Err(e) = state_guard.db.save_parking_slot(&slot).await {
+    let mut batch = WriteBatch::new();
+    batch.save_parking_slot(&slot, &SlotChangeContext::user(auth_user.user_id, "admin"));
+    if let Err(e) = apply_slot_write(&state_guard.db, batch, lot.id).await {
         tracing::error!("Failed to save slot: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to create slot")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to create slot",
+            )),
         );
     }
     drop(state_guard);
@@ -1000,21 +1694,25 @@ pub async fn create_slot(
     (StatusCode::CREATED, Json(ApiResponse::success(slot)))
 }
 
-/// `PUT /api/v1/lots/{lot_id}/slots/{slot_id}` — update a slot
+/// `PUT`/`PATCH /api/v1/lots/{lot_id}/slots/{slot_id}` — update a slot
 #[utoipa::path(
-    put,
+    method(put, patch),
     path = "/api/v1/lots/{lot_id}/slots/{slot_id}",
     tag = "Lots",
     summary = "Update a parking slot",
-    description = "Update slot properties (status, type, label, etc.). Admin only.",
+    description = "Update the given slot properties (`status`, `slot_type`, `slot_number`, \
+        `floor_id`) and recompute the lot's slot counts. An optional `reason` is recorded in the slot's status \
+        history. Admins and managers of the lot only.",
     params(
         ("lot_id" = String, Path, description = "Parking lot ID"),
         ("slot_id" = String, Path, description = "Slot ID"),
     ),
     responses(
         (status = 200, description = "Slot updated"),
-        (status = 403, description = "Admin access required"),
+        (status = 400, description = "Unknown status or slot type, invalid slot number or unknown floor"),
+        (status = 403, description = "Admin or lot manager access required"),
         (status = 404, description = "Slot not found"),
+        (status = 409, description = "Slot number already used in this lot"),
     )
 )]
 pub async fn update_slot(
@@ -1025,19 +1723,9 @@ pub async fn update_slot(
 ) -> (StatusCode, Json<ApiResponse<ParkingSlot>>) {
     let state_guard = state.read().await;
 
-    // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    // Admin or manager of this lot
+    if let Err((status, msg)) = check_lot_admin(&auth_user, &lot_id) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let mut slot = match state_guard.db.get_parking_slot(&slot_id).await {
@@ -1046,7 +1734,7 @@ pub async fn update_slot(
             return (
                 StatusCode::NOT_FOUND,
                 Json(ApiResponse::error(
-                    "NOT_FOUND",
+                    ErrorCode::NotFound,
                     "Slot not found in this lot",
                 )),
             );
@@ -1054,55 +1742,142 @@ pub async fn update_slot(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Slot not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Slot not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
     // Update fields if provided
+    let previous_status = slot.status.clone();
     if let Some(status) = req.get("status").and_then(|v| v.as_str()) {
-        slot.status = match status {
-            "available" => SlotStatus::Available,
-            "occupied" => SlotStatus::Occupied,
-            "reserved" => SlotStatus::Reserved,
-            "maintenance" => SlotStatus::Maintenance,
-            "disabled" => SlotStatus::Disabled,
-            _ => slot.status,
+        let Some(status) = parse_slot_status(status) else {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ErrorCode::ValidationError,
+                    "Unknown status",
+                )),
+            );
         };
+        slot.status = status;
     }
+    let status_changed = slot.status != previous_status;
 
     if let Some(slot_type) = req.get("slot_type").and_then(|v| v.as_str()) {
-        slot.slot_type = match slot_type {
-            "compact" => SlotType::Compact,
-            "large" => SlotType::Large,
-            "handicap" => SlotType::Handicap,
-            "electric" => SlotType::Electric,
-            "motorcycle" => SlotType::Motorcycle,
-            "vip" => SlotType::Vip,
-            _ => SlotType::Standard,
+        let Some(slot_type) = parse_slot_type(slot_type) else {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ErrorCode::ValidationError,
+                    "Unknown slot_type",
+                )),
+            );
         };
+        slot.slot_type = slot_type;
+    }
+
+    match requested_slot_number(&req) {
+        Ok(Some(number)) if number != slot.slot_number => {
+            match state_guard.db.list_slots_by_lot(&lot_id).await {
+                Ok(slots) if slots.iter().any(|s| s.slot_number == number) => {
+                    return slot_number_taken();
+                }
+                Ok(_) => slot.slot_number = number,
+                Err(e) => {
+                    tracing::error!("Database error: {}", e);
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ApiResponse::error(
+                            ErrorCode::ServerError,
+                            "Internal server error",
+                        )),
+                    );
+                }
+            }
+        }
+        Ok(_) => {}
+        Err(msg) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+            );
+        }
     }
 
-    if let Some(number) = req.get("slot_number").and_then(serde_json::Value::as_i64) {
-        #[allow(clippy::cast_possible_truncation)]
-        let num = number as i32;
-        slot.slot_number = num;
+    if req.get("floor_id").is_some_and(|v| !v.is_null()) {
+        let lot = match state_guard.db.get_parking_lot(&lot_id).await {
+            Ok(Some(lot)) => lot,
+            Ok(None) => {
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::error(
+                        ErrorCode::NotFound,
+                        "Parking lot not found",
+                    )),
+                );
+            }
+            Err(e) => {
+                tracing::error!("Database error: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Internal server error",
+                    )),
+                );
+            }
+        };
+        match requested_floor_id(&req, &lot) {
+            Ok(floor_id) => slot.floor_id = floor_id.unwrap_or(slot.floor_id),
+            Err(msg) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+                );
+            }
+        }
     }
 
-    if let Err(e) = state_guard.db.save_parking_slot(&slot).await {
+    let mut change = SlotChangeContext::user(auth_user.user_id, "admin");
+    if let Some(reason) = req
+        .get("reason")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+    {
+        change = change.reason(reason);
+    }
+    let mut batch = WriteBatch::new();
+    batch.save_parking_slot(&slot, &change);
+    if let Err(e) = apply_slot_write(&state_guard.db, batch, slot.lot_id).await {
         tracing::error!("Failed to update slot: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to update slot")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to update slot",
+            )),
         );
     }
+    if status_changed {
+        state_guard
+            .ws_events
+            .broadcast(super::events::LiveEvent::slot_status_change(
+                &lot_id,
+                &slot_id,
+                &super::events::wire_name(&slot.status),
+            ));
+    }
     drop(state_guard);
 
     (StatusCode::OK, Json(ApiResponse::success(slot)))
@@ -1114,14 +1889,15 @@ pub async fn update_slot(
     path = "/api/v1/lots/{lot_id}/slots/{slot_id}",
     tag = "Lots",
     summary = "Delete a parking slot",
-    description = "Remove a slot from a parking lot. Admin only.",
+    description = "Remove a slot from a parking lot and recompute the lot's slot counts. \
+        Admins and managers of the lot only.",
     params(
         ("lot_id" = String, Path, description = "Parking lot ID"),
         ("slot_id" = String, Path, description = "Slot ID"),
     ),
     responses(
         (status = 200, description = "Slot deleted"),
-        (status = 403, description = "Admin access required"),
+        (status = 403, description = "Admin or lot manager access required"),
         (status = 404, description = "Slot not found"),
     )
 )]
@@ -1132,19 +1908,9 @@ pub async fn delete_slot(
 ) -> (StatusCode, Json<ApiResponse<()>>) {
     let state_guard = state.read().await;
 
-    // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    // Admin or manager of this lot
+    if let Err((status, msg)) = check_lot_admin(&auth_user, &lot_id) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     // Verify slot belongs to lot
@@ -1154,7 +1920,7 @@ pub async fn delete_slot(
             return (
                 StatusCode::NOT_FOUND,
                 Json(ApiResponse::error(
-                    "NOT_FOUND",
+                    ErrorCode::NotFound,
                     "Slot not found in this lot",
                 )),
             );
@@ -1162,14 +1928,17 @@ pub async fn delete_slot(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Slot not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Slot not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     }
@@ -1178,7 +1947,10 @@ pub async fn delete_slot(
         tracing::error!("Failed to delete slot: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to delete slot")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to delete slot",
+            )),
         );
     }
     drop(state_guard);
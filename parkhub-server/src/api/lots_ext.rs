@@ -22,11 +22,12 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use crate::AppState;
 
-use super::{AuthUser, check_admin, read_admin_setting};
+use super::permissions::{CanViewAllBookings, RequirePermission};
+use super::{AuthUser, read_admin_setting};
 
 type SharedState = Arc<RwLock<AppState>>;
 
@@ -71,14 +72,20 @@ pub async fn lot_qr_code(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     }
@@ -101,7 +108,10 @@ pub async fn lot_qr_code(
             tracing::error!("Lot QR generation failed: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "QR generation failed")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "QR generation failed",
+                )),
             );
         }
     };
@@ -176,13 +186,9 @@ pub struct DashboardCharts {
 )]
 pub async fn admin_dashboard_charts(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanViewAllBookings>,
 ) -> (StatusCode, Json<ApiResponse<DashboardCharts>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
-
     let bookings = state_guard.db.list_bookings().await.unwrap_or_default();
     let lots = state_guard.db.list_parking_lots().await.unwrap_or_default();
     let users = state_guard.db.list_users().await.unwrap_or_default();
@@ -5,6 +5,8 @@
 //! - `PUT    /api/v1/admin/maintenance/:id` — update maintenance window
 //! - `DELETE /api/v1/admin/maintenance/:id` — cancel maintenance window
 //! - `GET    /api/v1/maintenance/active` — current active maintenance (public)
+//!
+//! Lot managers may schedule and edit windows for the lots they manage.
 
 // AppState read/write guards are held across handler duration by design —
 // db access goes through its own inner RwLock. See workspace lint config.
@@ -21,9 +23,10 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode, Permission};
 
-use super::{AuthUser, check_admin};
+use super::AuthUser;
+use super::lot_managers::{AdminScope, admin_scope};
 use crate::AppState;
 
 type SharedState = Arc<RwLock<AppState>>;
@@ -174,7 +177,7 @@ async fn delete_maintenance_by_id(state: &AppState, id: &str) -> anyhow::Result<
 /// `POST /api/v1/admin/maintenance` — create maintenance window
 #[utoipa::path(post, path = "/api/v1/admin/maintenance", tag = "Maintenance",
     summary = "Create maintenance window",
-    description = "Schedule a maintenance window for a lot. Admin only.",
+    description = "Schedule a maintenance window for a lot. Admins and managers of the lot only.",
     security(("bearer_auth" = [])),
     responses(
         (status = 201, description = "Maintenance window created"),
@@ -188,15 +191,16 @@ pub async fn create_maintenance(
     Json(req): Json<CreateMaintenanceRequest>,
 ) -> (StatusCode, Json<ApiResponse<MaintenanceWindow>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
+    let scope = match admin_scope(&auth_user, Permission::ManageLots) {
+        Ok(scope) => scope,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg))),
+    };
 
     if req.end_time <= req.start_time {
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_TIME_RANGE",
+                ErrorCode::InvalidTimeRange,
                 "End time must be after start time",
             )),
         );
@@ -205,7 +209,20 @@ pub async fn create_maintenance(
     if req.reason.trim().is_empty() {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_REASON", "Reason is required")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidReason,
+                "Reason is required",
+            )),
+        );
+    }
+
+    if !scope.allows_lot_str(&req.lot_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Not a manager of this lot",
+            )),
         );
     }
 
@@ -215,14 +232,17 @@ pub async fn create_maintenance(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Lot not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Lot not found")),
             );
         }
         Err(e) => {
             tracing::error!("Failed to get lot: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to get lot")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to get lot",
+                )),
             );
         }
     };
@@ -254,7 +274,7 @@ pub async fn create_maintenance(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to create maintenance",
             )),
         );
@@ -270,7 +290,7 @@ pub async fn create_maintenance(
 /// `GET /api/v1/admin/maintenance` — list all maintenance windows
 #[utoipa::path(get, path = "/api/v1/admin/maintenance", tag = "Maintenance",
     summary = "List all maintenance",
-    description = "List all scheduled maintenance windows. Admin only.",
+    description = "List scheduled maintenance windows. Lot managers only see their own lots.",
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "Maintenance list"),
@@ -282,11 +302,16 @@ pub async fn list_maintenance(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<Vec<MaintenanceWindow>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
+    let scope = match admin_scope(&auth_user, Permission::ManageLots) {
+        Ok(scope) => scope,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg))),
+    };
 
-    let windows = list_all_maintenance(&state_guard).await;
+    let windows: Vec<MaintenanceWindow> = list_all_maintenance(&state_guard)
+        .await
+        .into_iter()
+        .filter(|w| scope.allows_lot(&w.lot_id))
+        .collect();
     (StatusCode::OK, Json(ApiResponse::success(windows)))
 }
 
@@ -311,9 +336,10 @@ pub async fn update_maintenance(
     Json(req): Json<UpdateMaintenanceRequest>,
 ) -> (StatusCode, Json<ApiResponse<MaintenanceWindow>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
+    let scope = match admin_scope(&auth_user, Permission::ManageLots) {
+        Ok(scope) => scope,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg))),
+    };
 
     let key = format!("{MAINTENANCE_PREFIX}{id}");
     let mut window = match state_guard.db.get_setting(&key).await {
@@ -322,18 +348,34 @@ pub async fn update_maintenance(
             Err(_) => {
                 return (
                     StatusCode::NOT_FOUND,
-                    Json(ApiResponse::error("NOT_FOUND", "Maintenance not found")),
+                    Json(ApiResponse::error(
+                        ErrorCode::NotFound,
+                        "Maintenance not found",
+                    )),
                 );
             }
         },
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Maintenance not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Maintenance not found",
+                )),
             );
         }
     };
 
+    if !scope.allows_lot(&window.lot_id) {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Not a manager of this lot",
+            )),
+        );
+    }
+
     if let Some(start) = req.start_time {
         window.start_time = start;
     }
@@ -357,7 +399,7 @@ pub async fn update_maintenance(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update maintenance",
             )),
         );
@@ -386,20 +428,38 @@ pub async fn delete_maintenance(
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
+    let scope = match admin_scope(&auth_user, Permission::ManageLots) {
+        Ok(scope) => scope,
+        Err((status, msg)) => return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg))),
+    };
 
     // Verify exists
     let key = format!("{MAINTENANCE_PREFIX}{id}");
-    match state_guard.db.get_setting(&key).await {
-        Ok(Some(val)) if !val.is_empty() => {}
+    let window = match state_guard.db.get_setting(&key).await {
+        Ok(Some(val)) if !val.is_empty() => serde_json::from_str::<MaintenanceWindow>(&val).ok(),
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Maintenance not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Maintenance not found",
+                )),
             );
         }
+    };
+    // Unreadable windows can only be cleaned up by global admins
+    let allowed = match &window {
+        Some(w) => scope.allows_lot(&w.lot_id),
+        None => scope == AdminScope::Global,
+    };
+    if !allowed {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Not a manager of this lot",
+            )),
+        );
     }
 
     if let Err(e) = delete_maintenance_by_id(&state_guard, &id).await {
@@ -407,7 +467,7 @@ pub async fn delete_maintenance(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to cancel maintenance",
             )),
         );
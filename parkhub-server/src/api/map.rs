@@ -1,14 +1,19 @@
 //! Map view handlers: lot markers with coordinates and live availability.
+//!
+//! `GET /api/v1/lots.geojson` exposes the same data as a GeoJSON
+//! `FeatureCollection` (RFC 7946) so Leaflet/MapLibre can render it directly.
+//! A lot is a `Polygon` when an admin has set its boundary, else a `Point`.
 
 use axum::{
     Extension, Json,
     extract::{Path, State},
-    http::StatusCode,
+    http::{StatusCode, header},
+    response::IntoResponse,
 };
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
-use parkhub_common::{ApiResponse, LotStatus, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode, LotStatus, ParkingLot, UserRole};
 
 use super::{AuthUser, SharedState};
 
@@ -52,6 +57,25 @@ pub struct SetLocationRequest {
     pub longitude: f64,
 }
 
+/// Request body for setting a lot's boundary polygon.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SetBoundaryRequest {
+    /// Outer ring as `[longitude, latitude]` pairs (GeoJSON order). The ring
+    /// is closed automatically; send an empty list to clear the boundary.
+    pub coordinates: Vec<[f64; 2]>,
+}
+
+/// Settings key under which a lot's boundary ring is stored.
+fn boundary_key(lot_id: &str) -> String {
+    format!("lot_boundary:{lot_id}")
+}
+
+/// Load a lot's stored boundary ring, if any.
+async fn lot_boundary(db: &crate::db::Database, lot_id: &str) -> Option<Vec<[f64; 2]>> {
+    let raw = db.get_setting(&boundary_key(lot_id)).await.ok()??;
+    serde_json::from_str(&raw).ok()
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Helpers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -80,6 +104,68 @@ pub fn marker_color(status: &LotStatus, available: i32, total: i32) -> MarkerCol
     }
 }
 
+/// Validate a boundary ring and close it (first point repeated at the end).
+///
+/// # Errors
+/// Returns a message when the ring has fewer than three distinct points or
+/// contains an out-of-range coordinate.
+pub fn close_boundary_ring(mut ring: Vec<[f64; 2]>) -> Result<Vec<[f64; 2]>, &'static str> {
+    if let Some(bad) = ring
+        .iter()
+        .find(|[lon, lat]| crate::geo::validate_coordinates(*lat, *lon).is_err())
+    {
+        tracing::debug!(?bad, "Rejected boundary point");
+        return Err("Boundary coordinates must be [longitude, latitude] within range");
+    }
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    if ring.len() < 3 {
+        return Err("Boundary needs at least 3 points");
+    }
+    ring.push(ring[0]);
+    Ok(ring)
+}
+
+/// Build the GeoJSON `Feature` for a lot. `boundary` must be a closed ring.
+pub fn lot_feature(lot: &ParkingLot, boundary: Option<&[[f64; 2]]>) -> serde_json::Value {
+    let geometry = boundary.map_or_else(
+        || {
+            serde_json::json!({
+                "type": "Point",
+                "coordinates": [lot.longitude, lot.latitude],
+            })
+        },
+        |ring| {
+            serde_json::json!({
+                "type": "Polygon",
+                "coordinates": [ring],
+            })
+        },
+    );
+    let occupancy_percent = if lot.total_slots > 0 {
+        f64::from(lot.total_slots - lot.available_slots) / f64::from(lot.total_slots) * 100.0
+    } else {
+        0.0
+    };
+    serde_json::json!({
+        "type": "Feature",
+        "id": lot.id,
+        "geometry": geometry,
+        "properties": {
+            "id": lot.id,
+            "name": lot.name,
+            "address": lot.address,
+            "status": lot.status,
+            "available_slots": lot.available_slots,
+            "total_slots": lot.total_slots,
+            "occupancy_percent": occupancy_percent,
+            "color": marker_color(&lot.status, lot.available_slots, lot.total_slots),
+            "center": [lot.longitude, lot.latitude],
+        },
+    })
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Handlers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -105,8 +191,10 @@ pub async fn list_lot_markers(
 
     match state_guard.db.list_parking_lots().await {
         Ok(lots) => {
+            let archived = super::lot_archive::archived_lot_ids(&state_guard.db).await;
             let markers: Vec<LotMarker> = lots
                 .iter()
+                .filter(|lot| !archived.contains(&lot.id))
                 .filter(|lot| lot.latitude != 0.0 || lot.longitude != 0.0)
                 .map(|lot| {
                     let color = marker_color(&lot.status, lot.available_slots, lot.total_slots);
@@ -131,13 +219,67 @@ pub async fn list_lot_markers(
         Err(e) => {
             tracing::error!("Failed to list lots for map: {}", e);
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to load map data",
             ))
         }
     }
 }
 
+/// `GET /api/v1/lots.geojson` — lots and live availability as GeoJSON.
+///
+/// Lots without coordinates and without a boundary are omitted, matching the
+/// marker endpoint.
+#[utoipa::path(
+    get,
+    path = "/api/v1/lots.geojson",
+    tag = "Map",
+    summary = "Lots as GeoJSON",
+    description = "Returns a GeoJSON FeatureCollection of parking lots (points, or polygons when a \
+        boundary is set) with live availability in each feature's properties.",
+    responses(
+        (status = 200, description = "GeoJSON FeatureCollection (`application/geo+json`)"),
+    )
+)]
+pub async fn lots_geojson(State(state): State<SharedState>) -> impl IntoResponse {
+    let state_guard = state.read().await;
+
+    let lots = match state_guard.db.list_parking_lots().await {
+        Ok(lots) => lots,
+        Err(e) => {
+            tracing::error!("Failed to list lots for GeoJSON: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::ServerError,
+                    "Failed to load map data",
+                )),
+            )
+                .into_response();
+        }
+    };
+
+    let archived = super::lot_archive::archived_lot_ids(&state_guard.db).await;
+    let mut features = Vec::with_capacity(lots.len());
+    for lot in lots.iter().filter(|lot| !archived.contains(&lot.id)) {
+        let boundary = lot_boundary(&state_guard.db, &lot.id.to_string()).await;
+        if boundary.is_none() && crate::geo::is_unset(lot.latitude, lot.longitude) {
+            continue;
+        }
+        features.push(lot_feature(lot, boundary.as_deref()));
+    }
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/geo+json")],
+        Json(serde_json::json!({
+            "type": "FeatureCollection",
+            "features": features,
+        })),
+    )
+        .into_response()
+}
+
 /// `PUT /api/v1/admin/lots/{id}/location` — set a lot's geographic coordinates.
 ///
 /// Admin-only. Sets the latitude/longitude for a parking lot so it appears on the map.
@@ -172,13 +314,16 @@ pub async fn set_lot_location(
     else {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     };
     if user.role != UserRole::Admin && user.role != UserRole::SuperAdmin {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -187,7 +332,7 @@ pub async fn set_lot_location(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "Latitude must be -90..90, longitude must be -180..180",
             )),
         );
@@ -204,20 +349,130 @@ pub async fn set_lot_location(
                     tracing::error!("Failed to update lot location: {}", e);
                     (
                         StatusCode::INTERNAL_SERVER_ERROR,
-                        Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                        Json(ApiResponse::error(
+                            ErrorCode::ServerError,
+                            "Internal server error",
+                        )),
                     )
                 }
             }
         }
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
         ),
         Err(e) => {
             tracing::error!("Database error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
+/// `PUT /api/v1/admin/lots/{id}/boundary` — set or clear a lot's boundary polygon.
+///
+/// Admin-only. The boundary is rendered as the lot's geometry in
+/// `GET /api/v1/lots.geojson`.
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/lots/{id}/boundary",
+    tag = "Map",
+    summary = "Set lot boundary",
+    description = "Admin endpoint to set (or clear, with an empty list) a parking lot's boundary polygon.",
+    request_body = SetBoundaryRequest,
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Lot UUID")),
+    responses(
+        (status = 200, description = "Boundary updated"),
+        (status = 400, description = "Invalid polygon"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Lot not found"),
+    )
+)]
+pub async fn set_lot_boundary(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(req): Json<SetBoundaryRequest>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.read().await;
+
+    // Check admin
+    let Ok(Some(user)) = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+    else {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
+        );
+    };
+    if user.role != UserRole::Admin && user.role != UserRole::SuperAdmin {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
+    }
+
+    match state_guard.db.get_parking_lot(&id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    }
+
+    let value = if req.coordinates.is_empty() {
+        String::new()
+    } else {
+        match close_boundary_ring(req.coordinates) {
+            Ok(ring) => serde_json::to_string(&ring).unwrap_or_default(),
+            Err(msg) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+                );
+            }
+        }
+    };
+
+    match state_guard.db.set_setting(&boundary_key(&id), &value).await {
+        Ok(()) => (StatusCode::OK, Json(ApiResponse::success(()))),
+        Err(e) => {
+            tracing::error!("Failed to update lot boundary: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
@@ -324,4 +579,25 @@ mod tests {
             "\"gray\""
         );
     }
+
+    #[test]
+    fn test_close_boundary_ring_closes_open_ring() {
+        let ring = close_boundary_ring(vec![[11.0, 48.0], [11.1, 48.0], [11.1, 48.1]]).unwrap();
+        assert_eq!(ring.len(), 4);
+        assert_eq!(ring.first(), ring.last());
+    }
+
+    #[test]
+    fn test_close_boundary_ring_keeps_closed_ring() {
+        let ring =
+            close_boundary_ring(vec![[11.0, 48.0], [11.1, 48.0], [11.1, 48.1], [11.0, 48.0]])
+                .unwrap();
+        assert_eq!(ring.len(), 4);
+    }
+
+    #[test]
+    fn test_close_boundary_ring_rejects_degenerate_and_out_of_range() {
+        assert!(close_boundary_ring(vec![[11.0, 48.0], [11.1, 48.0], [11.0, 48.0]]).is_err());
+        assert!(close_boundary_ring(vec![[11.0, 95.0], [11.1, 48.0], [11.1, 48.1]]).is_err());
+    }
 }
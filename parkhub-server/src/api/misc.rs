@@ -11,17 +11,19 @@ use axum::{
     extract::State,
     http::{StatusCode, header},
 };
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt::Write as _;
 use std::sync::Arc;
 use tokio::sync::RwLock;
+use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, UserRole};
+use parkhub_common::{ApiResponse, Booking, BookingStatus, ErrorCode, UserRole};
 
 use crate::AppState;
 
 use super::AuthUser;
+use super::feature_flags::{PublicSignageFlag, RequireFlag};
 
 type SharedState = Arc<RwLock<AppState>>;
 
@@ -153,7 +155,7 @@ pub async fn update_impressum(
     let Ok(Some(user)) = state_guard.db.get_user(&user_id_str).await else {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin required")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Admin required")),
         );
     };
     drop(state_guard);
@@ -161,7 +163,7 @@ pub async fn update_impressum(
     if user.role != UserRole::Admin && user.role != UserRole::SuperAdmin {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin required")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Admin required")),
         );
     }
 
@@ -182,6 +184,21 @@ pub async fn update_impressum(
 // PUBLIC OCCUPANCY
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// Number of slots of lot `lot_id` held at `now` by a confirmed or active
+/// booking. Also shown in the server status window.
+pub(crate) fn occupied_slots(bookings: &[Booking], lot_id: Uuid, now: DateTime<Utc>) -> i32 {
+    let count = bookings
+        .iter()
+        .filter(|b| {
+            b.lot_id == lot_id
+                && b.start_time <= now
+                && b.end_time >= now
+                && matches!(b.status, BookingStatus::Confirmed | BookingStatus::Active)
+        })
+        .count();
+    i32::try_from(count).unwrap_or(i32::MAX)
+}
+
 /// Occupancy info for a single lot
 #[derive(Debug, Serialize)]
 pub struct LotOccupancy {
@@ -200,6 +217,7 @@ pub struct LotOccupancy {
 )]
 pub async fn public_occupancy(
     State(state): State<SharedState>,
+    _: RequireFlag<PublicSignageFlag>,
 ) -> (StatusCode, Json<ApiResponse<Vec<LotOccupancy>>>) {
     let state_guard = state.read().await;
 
@@ -210,7 +228,7 @@ pub async fn public_occupancy(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to get occupancy",
                 )),
             );
@@ -223,22 +241,7 @@ pub async fn public_occupancy(
 
     let mut occupancy = Vec::with_capacity(lots.len());
     for lot in &lots {
-        let occupied = i32::try_from(
-            bookings
-                .iter()
-                .filter(|b| {
-                    b.lot_id == lot.id
-                        && b.start_time <= now
-                        && b.end_time >= now
-                        && matches!(
-                            b.status,
-                            parkhub_common::BookingStatus::Confirmed
-                                | parkhub_common::BookingStatus::Active
-                        )
-                })
-                .count(),
-        )
-        .unwrap_or(i32::MAX);
+        let occupied = occupied_slots(&bookings, lot.id, now);
 
         let available = (lot.total_slots - occupied).max(0);
 
@@ -260,7 +263,10 @@ pub async fn public_occupancy(
     description = "Returns minimal HTML for digital signage.",
     responses((status = 200, description = "Success"))
 )]
-pub async fn public_display(State(state): State<SharedState>) -> impl axum::response::IntoResponse {
+pub async fn public_display(
+    State(state): State<SharedState>,
+    _: RequireFlag<PublicSignageFlag>,
+) -> impl axum::response::IntoResponse {
     let state_guard = state.read().await;
 
     let lots = state_guard.db.list_parking_lots().await.unwrap_or_default();
@@ -295,22 +301,7 @@ pub async fn public_display(State(state): State<SharedState>) -> impl axum::resp
     );
 
     for lot in &lots {
-        let occupied = i32::try_from(
-            bookings
-                .iter()
-                .filter(|b| {
-                    b.lot_id == lot.id
-                        && b.start_time <= now
-                        && b.end_time >= now
-                        && matches!(
-                            b.status,
-                            parkhub_common::BookingStatus::Confirmed
-                                | parkhub_common::BookingStatus::Active
-                        )
-                })
-                .count(),
-        )
-        .unwrap_or(i32::MAX);
+        let occupied = occupied_slots(&bookings, lot.id, now);
 
         let available = (lot.total_slots - occupied).max(0);
         let pct = if lot.total_slots > 0 {
@@ -20,8 +20,8 @@ use axum::{
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
-use parkhub_common::ApiResponse;
 use parkhub_common::models::BookingStatus;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{AuthUser, SharedState};
 
@@ -126,7 +126,10 @@ pub async fn nearby_lots(
             tracing::error!("Failed to list lots: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to list lots")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list lots",
+                )),
             );
         }
     };
@@ -221,7 +224,10 @@ pub async fn quick_book(
             tracing::error!("Failed to list lots: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to list lots")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list lots",
+                )),
             );
         }
     };
@@ -278,7 +284,7 @@ pub async fn active_booking(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to fetch bookings",
                 )),
             );
@@ -5,16 +5,14 @@
 use axum::{
     Extension, Json, Router,
     body::Body,
-    extract::State,
+    extract::{DefaultBodyLimit, State},
     http::{HeaderName, HeaderValue, Request, StatusCode, header},
     middleware::{self, Next},
-    response::Response,
+    response::{IntoResponse, Response},
     routing::{delete, get, post, put},
 };
 use std::sync::Arc;
-use std::time::Duration;
 use tokio::sync::RwLock;
-use tower::ServiceBuilder;
 use tower_http::compression::CompressionLayer;
 use tower_http::cors::CorsLayer;
 use tower_http::limit::RequestBodyLimitLayer;
@@ -50,9 +48,13 @@ pub const MAX_PHOTO_BYTES: usize = 2 * 1024 * 1024;
 // the multi-country tax profile layer shipped — callers should go through
 // the resolver so the seller country is honoured.
 
-use parkhub_common::{ApiResponse, LoginResponse, UserRole};
+use parkhub_common::{
+    ApiResponse, ErrorCode, LoginResponse, ParkingLot, Permission, ResolvedPermissions, UserRole,
+};
 
 use crate::AppState;
+use crate::db::{Database, PageQuery, SortBy};
+use crate::requests::{ListParams, SortFields};
 
 type SharedState = Arc<RwLock<AppState>>;
 
@@ -66,6 +68,7 @@ pub mod absence_approval;
 pub mod absences;
 #[cfg(feature = "mod-accessible")]
 pub mod accessible;
+pub mod activity;
 pub mod admin;
 #[cfg(feature = "mod-admin-analytics")]
 pub mod admin_analytics;
@@ -80,12 +83,22 @@ pub mod api_docs;
 #[cfg(feature = "mod-audit-export")]
 pub mod audit_export;
 pub mod auth;
+pub mod auth_guard;
+pub mod availability;
+pub mod avatars;
+pub mod backup;
 #[cfg(feature = "mod-cost-center")]
 pub mod billing;
 #[cfg(feature = "mod-bookings")]
+pub mod booking_attachments;
+pub mod booking_locks;
+pub mod booking_notes;
+pub mod booking_tags;
+#[cfg(feature = "mod-bookings")]
 pub mod bookings;
 #[cfg(feature = "mod-branding")]
 pub mod branding;
+pub mod bulk;
 #[cfg(feature = "mod-calendar")]
 pub mod calendar;
 #[cfg(feature = "mod-calendar-drag")]
@@ -98,16 +111,23 @@ pub mod compliance;
 pub mod credits;
 #[cfg(feature = "mod-data-import")]
 pub mod data_management;
+pub mod department_quotas;
+pub mod dummy_users;
 #[cfg(feature = "mod-dynamic-pricing")]
 pub mod dynamic_pricing;
+#[cfg(feature = "mod-email")]
+pub mod email_queue;
+pub mod energy_reports;
 #[cfg(feature = "mod-enhanced-pwa")]
 pub mod enhanced_pwa;
 #[cfg(feature = "mod-ev-charging")]
 pub mod ev_charging;
+pub mod events;
 #[cfg(feature = "mod-export")]
 pub mod export;
 #[cfg(feature = "mod-favorites")]
 pub mod favorites;
+pub mod feature_flags;
 #[cfg(feature = "mod-fleet")]
 pub mod fleet;
 #[cfg(feature = "mod-geofence")]
@@ -118,12 +138,21 @@ pub mod graphql;
 pub mod guest;
 #[cfg(feature = "mod-history")]
 pub mod history;
+pub mod impersonation;
 #[cfg(feature = "mod-import")]
 pub mod import;
+pub mod invites;
+pub mod invoice_archive;
 #[cfg(feature = "mod-invoices")]
 pub mod invoices;
 #[cfg(feature = "mod-lobby-display")]
 pub mod lobby;
+pub mod lot_archive;
+pub mod lot_floors;
+pub mod lot_managers;
+pub mod lot_photos;
+pub mod lot_rules;
+pub mod lot_settings;
 pub mod lots;
 pub mod lots_ext;
 #[cfg(feature = "mod-maintenance")]
@@ -134,6 +163,8 @@ pub mod misc;
 #[cfg(feature = "mod-mobile")]
 pub mod mobile;
 pub mod modules;
+#[cfg(feature = "mod-email")]
+pub mod monthly_summary;
 #[cfg(feature = "mod-notification-center")]
 pub mod notification_center;
 #[cfg(feature = "mod-notifications")]
@@ -143,14 +174,20 @@ pub mod notification_channels;
 pub mod notifications;
 #[cfg(feature = "mod-oauth")]
 pub mod oauth;
+#[cfg(feature = "mod-oauth")]
+pub mod oidc;
 #[cfg(feature = "mod-operating-hours")]
 pub mod operating_hours;
 #[cfg(feature = "mod-parking-pass")]
 pub mod parking_pass;
 #[cfg(feature = "mod-parking-zones")]
 pub mod parking_zones;
+pub mod passkeys;
 #[cfg(feature = "mod-payments")]
 pub mod payments;
+pub mod permissions;
+pub mod permits;
+pub mod plate_display;
 #[cfg(feature = "mod-plugins")]
 #[allow(dead_code)]
 pub mod plugins;
@@ -175,17 +212,24 @@ pub mod rbac;
 pub mod recommendation_allocation;
 #[cfg(feature = "mod-recommendations")]
 pub mod recommendations;
+pub mod rectification;
 #[cfg(feature = "mod-recurring")]
 pub mod recurring;
 pub mod retention;
+pub mod roles;
 #[cfg(feature = "mod-scheduled-reports")]
 pub mod scheduled_reports;
+pub mod search;
 pub mod security;
+pub mod session_tokens;
 #[cfg(feature = "mod-settings")]
 pub mod settings;
 pub mod setup;
 #[cfg(feature = "mod-sharing")]
 pub mod sharing;
+pub mod slot_assignment;
+pub mod slot_history;
+pub mod slot_holds;
 #[cfg(test)]
 mod snapshots;
 #[cfg(feature = "mod-social")]
@@ -202,8 +246,10 @@ pub mod system;
 pub mod tax;
 #[cfg(feature = "mod-team")]
 pub mod team;
+pub mod telemetry;
 #[cfg(feature = "mod-multi-tenant")]
 pub mod tenants;
+pub mod timeouts;
 #[cfg(feature = "mod-translations")]
 pub mod translations;
 pub mod updates;
@@ -212,12 +258,18 @@ pub mod users;
 pub mod vehicles;
 #[cfg(feature = "mod-api-versioning")]
 pub mod versioning;
+#[cfg(feature = "mod-guest")]
+pub mod visitor_bookings;
+#[cfg(feature = "mod-guest")]
+pub mod visitor_passes;
 #[cfg(feature = "mod-visitors")]
 pub mod visitors;
 #[cfg(feature = "mod-waitlist")]
 pub mod waitlist;
 #[cfg(feature = "mod-waitlist-ext")]
 pub mod waitlist_ext;
+#[cfg(any(feature = "mod-waitlist", feature = "mod-waitlist-ext"))]
+pub mod waitlist_position;
 #[cfg(feature = "mod-webhooks")]
 pub mod webhooks;
 #[cfg(feature = "mod-webhooks-v2")]
@@ -242,7 +294,8 @@ use absences::{
 #[cfg(feature = "mod-announcements")]
 use announcements::{
     admin_create_announcement, admin_delete_announcement, admin_list_announcements,
-    admin_update_announcement, get_active_announcements,
+    admin_update_announcement, dismiss_announcement, get_active_announcements,
+    get_my_announcements,
 };
 use auth::{
     forgot_password, login, login_alias, logout, refresh_token, refresh_token_alias, register,
@@ -251,7 +304,7 @@ use auth::{
 #[cfg(feature = "mod-bookings")]
 pub use bookings::{
     booking_checkin, cancel_booking, create_booking, get_booking, get_booking_invoice,
-    list_bookings, quick_book, update_booking,
+    list_bookings, lot_calendar, quick_book, quote_booking, update_booking,
 };
 #[cfg(feature = "mod-calendar")]
 use calendar::{
@@ -273,7 +326,10 @@ use ev_charging::{
 #[cfg(feature = "mod-export")]
 use export::{admin_export_bookings_csv, admin_export_revenue_csv, admin_export_users_csv};
 #[cfg(feature = "mod-favorites")]
-use favorites::{add_favorite, list_favorites, remove_favorite};
+use favorites::{
+    add_favorite, add_my_favorite, list_favorites, list_my_favorites, remove_favorite,
+    remove_my_favorite_lot,
+};
 #[cfg(feature = "mod-geofence")]
 use geofence::{admin_set_geofence, geofence_check_in, get_lot_geofence};
 #[cfg(feature = "mod-guest")]
@@ -284,10 +340,10 @@ use guest::{
 #[cfg(feature = "mod-history")]
 use history::{booking_history, booking_stats};
 #[cfg(feature = "mod-import")]
-use import::import_users_csv;
+use import::{import_lots, import_users_csv};
 use lots::{
-    create_lot, create_slot, delete_lot, delete_slot, get_lot, get_lot_pricing, get_lot_slots,
-    list_lots, update_lot, update_lot_pricing, update_slot,
+    clone_lot, create_lot, create_slot, delete_lot, delete_slot, get_lot, get_lot_pricing,
+    get_lot_slots, list_lots, search_lots, update_lot, update_lot_pricing, update_slot,
 };
 #[cfg(feature = "mod-mobile")]
 use mobile::{active_booking, nearby_lots, quick_book as mobile_quick_book};
@@ -366,8 +422,8 @@ use visitors::{
 use waitlist::{join_waitlist, leave_waitlist, list_waitlist};
 #[cfg(feature = "mod-waitlist-ext")]
 use waitlist_ext::{
-    accept_waitlist_offer, decline_waitlist_offer, get_lot_waitlist, leave_lot_waitlist,
-    subscribe_waitlist,
+    accept_waitlist_offer, decline_waitlist_offer, get_lot_waitlist, join_lot_waitlist,
+    leave_lot_waitlist, subscribe_waitlist,
 };
 #[cfg(feature = "mod-webhooks")]
 use webhooks::{create_webhook, delete_webhook, list_webhooks, test_webhook, update_webhook};
@@ -389,9 +445,10 @@ pub use misc::{
     get_impressum, get_impressum_admin, public_display, public_occupancy, update_impressum,
 };
 pub use users::{
-    auth_change_password, change_password, gdpr_delete_account, gdpr_export_data, get_current_user,
-    get_my_settings, get_user, get_user_preferences, update_current_user, update_my_settings,
-    update_user_preferences, user_stats,
+    auth_change_password, change_password, confirm_email_change, gdpr_delete_account,
+    gdpr_export_data, get_current_user, get_my_settings, get_user, get_user_preferences,
+    patch_current_user, update_current_user, update_my_settings, update_user_preferences,
+    user_stats,
 };
 
 /// User ID extracted from auth token.
@@ -400,23 +457,38 @@ pub use users::{
 /// downstream middleware (notably the per-identity rate limiter introduced
 /// in T-1743) can use the key id as the rate-limit bucket instead of the
 /// user id, giving each key its own quota.
+///
+/// `role` and `permissions` come from the user record the auth middleware
+/// loads anyway, so handlers can authorize without reading it again. Both
+/// are current as of the request: a role change applies to the next one.
 #[derive(Clone, Debug)]
 pub struct AuthUser {
     pub user_id: Uuid,
     /// API key id when the request authenticated via `X-API-Key` header.
     /// `None` for session/bearer/cookie auth.
     pub api_key_id: Option<Uuid>,
+    /// Admin acting as this user through an impersonation session; see
+    /// [`impersonation`].
+    pub impersonator_id: Option<Uuid>,
+    /// Built-in role of the caller
+    pub role: UserRole,
+    /// What the caller may do: their role's permissions plus custom role
+    /// and lot manager grants. Resolved once per request by the auth
+    /// middleware; see [`permissions`].
+    pub permissions: ResolvedPermissions,
 }
 
 /// Helper: verify the caller is an admin or superadmin.
 /// Returns `Ok(())` on success, `Err(forbidden_response)` otherwise.
-pub async fn check_admin(
-    state: &crate::AppState,
-    auth_user: &AuthUser,
-) -> Result<(), (StatusCode, &'static str)> {
-    match state.db.get_user(&auth_user.user_id.to_string()).await {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => Ok(()),
-        _ => Err((StatusCode::FORBIDDEN, "Admin access required")),
+///
+/// Endpoints that fit one of the capabilities in the permission matrix
+/// should require that instead; see [`permissions`].
+pub fn check_admin(auth_user: &AuthUser) -> Result<(), (StatusCode, &'static str)> {
+    match auth_user.role {
+        UserRole::Admin | UserRole::SuperAdmin => Ok(()),
+        UserRole::User | UserRole::Premium | UserRole::Reporter => {
+            Err((StatusCode::FORBIDDEN, "Admin access required"))
+        }
     }
 }
 
@@ -461,12 +533,92 @@ pub fn matches_tenant(entity_tenant: Option<&str>, caller_tenant: Option<&str>)
     }
 }
 
+/// Page query and order a list endpoint was asked for via `params`, given
+/// its sortable `fields`, or the 400 response for an unknown sort field or a
+/// malformed cursor. See [`ListParams::page_query`] for `paged_by_default`.
+#[allow(clippy::type_complexity, clippy::result_large_err)]
+pub(crate) fn list_request<T, R>(
+    params: &ListParams,
+    fields: &SortFields<T>,
+    paged_by_default: bool,
+) -> Result<(PageQuery, SortBy<T>), (StatusCode, Json<ApiResponse<R>>)> {
+    let Some(sort) = params.sort_by(fields) else {
+        let names: Vec<&str> = fields.iter().map(|(name, _)| *name).collect();
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidSort,
+                format!("sort must be one of: {}", names.join(", ")),
+            )),
+        ));
+    };
+    let Some(query) = params.page_query(paged_by_default) else {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidCursor,
+                "Malformed cursor",
+            )),
+        ));
+    };
+    Ok((query, sort))
+}
+
+/// 500 response carrying `message`. Callers log the underlying error first.
+pub(crate) fn server_error<T>(message: &str) -> (StatusCode, Json<ApiResponse<T>>) {
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(ApiResponse::error(ErrorCode::ServerError, message)),
+    )
+}
+
+/// Load lot `lot_id`, answering 404 if it does not exist.
+pub(crate) async fn load_lot<T>(
+    db: &Database,
+    lot_id: &str,
+) -> Result<ParkingLot, (StatusCode, Json<ApiResponse<T>>)> {
+    match db.get_parking_lot(lot_id).await {
+        Ok(Some(lot)) => Ok(lot),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
+        )),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            Err(server_error("Internal server error"))
+        }
+    }
+}
+
+/// Middleware that limits request bodies to [`MAX_REQUEST_BODY_BYTES`], or to
+/// [`backup::MAX_BACKUP_BYTES`] for backup restores.
+async fn request_body_limit_middleware(request: Request<Body>, next: Next) -> Response {
+    use tower::{ServiceBuilder, ServiceExt};
+
+    let limit = if request.uri().path() == backup::RESTORE_PATH {
+        backup::MAX_BACKUP_BYTES
+    } else {
+        MAX_REQUEST_BODY_BYTES
+    };
+    let limited = ServiceBuilder::new()
+        .layer(RequestBodyLimitLayer::new(limit))
+        .map_request(|request: Request<tower_http::body::Limited<Body>>| request.map(Body::new))
+        .service(next);
+    match limited.oneshot(request).await {
+        Ok(response) => response.into_response(),
+        Err(infallible) => match infallible {},
+    }
+}
+
 /// Middleware that enforces admin role for an entire route group (issue #109).
 ///
 /// Expects `AuthUser` to be in request extensions (set by `auth_middleware`).
-/// Returns 403 FORBIDDEN if the user is not an admin or superadmin.
+/// Returns 403 FORBIDDEN if the user is not an admin or superadmin, except on
+/// the lot-manager and reporting paths, which need `ViewAllBookings`.
 async fn admin_middleware(
-    State(state): State<SharedState>,
     request: Request<Body>,
     next: Next,
 ) -> Result<Response, (StatusCode, Json<ApiResponse<()>>)> {
@@ -477,15 +629,27 @@ async fn admin_middleware(
         .ok_or_else(|| {
             (
                 StatusCode::UNAUTHORIZED,
-                Json(ApiResponse::error("UNAUTHORIZED", "Not authenticated")),
+                Json(ApiResponse::error(
+                    ErrorCode::Unauthorized,
+                    "Not authenticated",
+                )),
             )
         })?;
 
-    let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return Err((status, Json(ApiResponse::error("FORBIDDEN", msg))));
+    // Lot managers and lot-scoped custom roles may reach a few lot-scoped
+    // admin endpoints; those handlers narrow their results with
+    // `lot_managers::admin_scope`. Reporters may reach the read-only reports.
+    let path = request.uri().path();
+    let result = if lot_managers::lot_manager_may_access(path) {
+        lot_managers::admin_scope(&auth_user, Permission::ViewAllBookings).map(|_| ())
+    } else if permissions::reporting_may_access(path) {
+        permissions::check_permission(&auth_user, Permission::ViewAllBookings)
+    } else {
+        check_admin(&auth_user)
+    };
+    if let Err((status, msg)) = result {
+        return Err((status, Json(ApiResponse::error(ErrorCode::Forbidden, msg))));
     }
-    drop(state_guard);
 
     Ok(next.run(request).await)
 }
@@ -520,10 +684,14 @@ async fn admin_middleware(
 /// Each sub-router has its own per-IP + per-identity rate-limit layers applied
 /// via `route_layer` (so only that route is affected), and `two_fa_store` is
 /// installed as an extension on the login + 2FA-login routes only.
+/// `passkey_service` likewise serves the passkey login routes, and
+/// `auth_guard` the login and password-reset routes.
 fn auth_rate_limited_routes(
     rate_limiters: &EndpointRateLimiters,
     identity_limiters: &Arc<IdentityRateLimiters>,
     two_fa_store: Arc<security::TwoFactorTempTokenStore>,
+    passkey_service: Arc<passkeys::PasskeyService>,
+    auth_guard: Arc<auth_guard::AuthGuard>,
 ) -> Router<SharedState> {
     // POST /api/v1/auth/login — 5/min per IP, Login bucket per identity
     let login_limiter = rate_limiters.login.clone();
@@ -532,6 +700,7 @@ fn auth_rate_limited_routes(
         .route("/api/v1/auth/login", post(login))
         .route("/api/v1/login", post(login_alias))
         .layer(Extension(two_fa_store.clone()))
+        .layer(Extension(auth_guard.clone()))
         .route_layer(middleware::from_fn(move |req, next| {
             identity_rate_limit_middleware(
                 login_identity.clone(),
@@ -562,12 +731,39 @@ fn auth_rate_limited_routes(
             ip_rate_limit_middleware(two_fa_login_limiter.clone(), req, next)
         }));
 
-    // POST /api/v1/auth/register — 3/min per IP, Register bucket per identity
+    // POST /api/v1/auth/webauthn/login/{start,finish} — 5/min per IP, Login
+    // bucket per identity
+    let passkey_login_limiter = rate_limiters.login.clone();
+    let passkey_login_identity = identity_limiters.clone();
+    let passkey_login_route = Router::new()
+        .route(
+            "/api/v1/auth/webauthn/login/start",
+            post(passkeys::start_login),
+        )
+        .route(
+            "/api/v1/auth/webauthn/login/finish",
+            post(passkeys::finish_login),
+        )
+        .layer(Extension(passkey_service))
+        .route_layer(middleware::from_fn(move |req, next| {
+            identity_rate_limit_middleware(
+                passkey_login_identity.clone(),
+                IdentityBucketKind::Login,
+                req,
+                next,
+            )
+        }))
+        .route_layer(middleware::from_fn(move |req, next| {
+            ip_rate_limit_middleware(passkey_login_limiter.clone(), req, next)
+        }));
+
+    // POST /api/v1/auth/register, /auth/accept-invite — 3/min per IP, Register bucket per identity
     let register_limiter = rate_limiters.register.clone();
     let register_identity = identity_limiters.clone();
     let register_route = Router::new()
         .route("/api/v1/auth/register", post(register))
         .route("/api/v1/register", post(register_alias))
+        .route("/api/v1/auth/accept-invite", post(invites::accept_invite))
         .route_layer(middleware::from_fn(move |req, next| {
             identity_rate_limit_middleware(
                 register_identity.clone(),
@@ -585,6 +781,7 @@ fn auth_rate_limited_routes(
     let forgot_identity = identity_limiters.clone();
     let forgot_route = Router::new()
         .route("/api/v1/auth/forgot-password", post(forgot_password))
+        .layer(Extension(auth_guard.clone()))
         .route_layer(middleware::from_fn(move |req, next| {
             identity_rate_limit_middleware(
                 forgot_identity.clone(),
@@ -615,11 +812,17 @@ fn auth_rate_limited_routes(
             ip_rate_limit_middleware(refresh_limiter.clone(), req, next)
         }));
 
-    // POST /api/v1/auth/reset-password — 5/15 min per IP, PasswordReset bucket
+    // POST /api/v1/auth/reset-password — 5/15 min per IP, PasswordReset bucket.
+    // Email-change confirmation is the same kind of one-shot token redemption.
     let reset_pw_limiter = rate_limiters.password_reset.clone();
     let reset_pw_identity = identity_limiters.clone();
     let reset_password_route = Router::new()
         .route("/api/v1/auth/reset-password", post(reset_password))
+        .route(
+            "/api/v1/auth/confirm-email-change",
+            post(confirm_email_change),
+        )
+        .layer(Extension(auth_guard))
         .route_layer(middleware::from_fn(move |req, next| {
             identity_rate_limit_middleware(
                 reset_pw_identity.clone(),
@@ -638,6 +841,7 @@ fn auth_rate_limited_routes(
     Router::new()
         .merge(login_route)
         .merge(two_fa_login_route)
+        .merge(passkey_login_route)
         .merge(register_route)
         .merge(forgot_route)
         .merge(refresh_route)
@@ -680,6 +884,7 @@ fn public_routes(state: &SharedState, rate_limiters: &EndpointRateLimiters) -> R
         .route("/status", get(server_status))
         .route("/api/v1/status", get(v1_server_status))
         .route("/api/v1/discover", get(v1_discover))
+        .route("/api/v1/time", get(v1_time))
         // Legal — public (DDG § 5 requires Impressum to be freely accessible)
         .route("/api/v1/legal/impressum", get(get_impressum))
         // Module registry — public (compile-time feature introspection
@@ -704,8 +909,11 @@ fn public_routes(state: &SharedState, rate_limiters: &EndpointRateLimiters) -> R
     #[cfg(feature = "mod-websocket")]
     {
         // Real-time WebSocket endpoint is part of the public contract only
-        // when the websocket module is compiled in.
-        router = router.route("/api/v1/ws", get(ws::ws_handler));
+        // when the websocket module is compiled in. The SSE stream carries
+        // the same events for clients whose proxies break WebSockets.
+        router = router
+            .route("/api/v1/ws", get(ws::ws_handler))
+            .route("/api/v1/events", get(events::events_handler));
     }
 
     // T-1946 — Server-Sent Events for fleet screens (Einchecken/EV/Tausch).
@@ -722,6 +930,21 @@ fn public_routes(state: &SharedState, rate_limiters: &EndpointRateLimiters) -> R
             .route("/api/v1/setup/wizard", post(system::wizard_step));
     }
 
+    // Lot photos — rate-limited public route (60 req/min per IP)
+    {
+        let photo_limiter = rate_limiters.lot_photos.clone();
+        let photo_route = Router::new()
+            .route(
+                "/api/v1/lots/{id}/photos/{photo_id}",
+                get(lot_photos::get_lot_photo),
+            )
+            .route_layer(middleware::from_fn(move |req, next| {
+                ip_rate_limit_middleware(photo_limiter.clone(), req, next)
+            }))
+            .with_state(state.clone());
+        router = router.merge(photo_route);
+    }
+
     // Lobby display — rate-limited public route (10 req/min per IP)
     #[cfg(feature = "mod-lobby-display")]
     {
@@ -780,6 +1003,10 @@ fn public_routes(state: &SharedState, rate_limiters: &EndpointRateLimiters) -> R
             .route("/api/v1/version", get(versioning::api_version))
             .route("/api/v1/changelog", get(versioning::api_changelog));
     }
+    #[cfg(not(feature = "mod-api-versioning"))]
+    {
+        router = router.route("/api/v1/version", get(system::v1_version));
+    }
 
     // Audit export download — token-based auth (no bearer needed)
     #[cfg(feature = "mod-audit-export")]
@@ -816,7 +1043,9 @@ fn public_routes(state: &SharedState, rate_limiters: &EndpointRateLimiters) -> R
     }
     #[cfg(feature = "mod-map")]
     {
-        router = router.route("/api/v1/lots/map", get(map::list_lot_markers));
+        router = router
+            .route("/api/v1/lots/map", get(map::list_lot_markers))
+            .route("/api/v1/lots.geojson", get(map::lots_geojson));
     }
     #[cfg(feature = "mod-stripe")]
     {
@@ -865,36 +1094,60 @@ fn public_routes(state: &SharedState, rate_limiters: &EndpointRateLimiters) -> R
             .route(
                 "/api/v1/auth/oauth/github/callback",
                 get(oauth::oauth_github_callback),
-            );
+            )
+            .route("/api/v1/auth/oidc/start", get(oidc::oidc_start))
+            .route("/api/v1/auth/oidc/callback", get(oidc::oidc_callback));
     }
 
     router
 }
 
-/// Core user routes: `/users/me`, `/me` alias, password change, GDPR
-/// export/delete, admin user lookup, plus the user stats + preferences,
+/// Core user routes: `/users/me`, `/me` alias, profile picture, password
+/// change, GDPR export/delete, admin user lookup, plus the user stats + preferences,
 /// security (2FA / sessions / API keys / login history), and
 /// notification/theme preference endpoints.
 fn user_core_routes() -> Router<SharedState> {
     Router::new()
         .route(
             "/api/v1/users/me",
-            get(get_current_user).put(update_current_user),
+            get(get_current_user)
+                .put(update_current_user)
+                .patch(patch_current_user),
         )
         // Alias: frontend may call /api/v1/me — keep both paths working
-        .route("/api/v1/me", get(get_current_user).put(update_current_user))
+        .route(
+            "/api/v1/me",
+            get(get_current_user)
+                .put(update_current_user)
+                .patch(patch_current_user),
+        )
         // v5 customization: opaque per-user settings JSON (theme, sidebar
         // variant, density, font, feature toggles, notifications, privacy).
         .route(
             "/api/v1/me/settings",
             get(get_my_settings).put(update_my_settings),
         )
+        .route(
+            "/api/v1/users/me/picture",
+            put(avatars::upload_my_picture).delete(avatars::delete_my_picture),
+        )
+        .route("/api/v1/users/{id}/picture", get(avatars::get_user_picture))
         .route("/api/v1/users/me/export", get(gdpr_export_data))
         .route("/api/v1/users/me/delete", delete(gdpr_delete_account))
+        .route(
+            "/api/v1/users/me/rectification",
+            get(rectification::list_my_rectifications).post(rectification::submit_rectification),
+        )
         .route(
             "/api/v1/users/me/password",
             axum::routing::patch(change_password),
         )
+        .route(
+            "/api/v1/users/me/managed-lots",
+            get(lot_managers::my_managed_lots),
+        )
+        .route("/api/v1/users/me/permissions", get(roles::my_permissions))
+        .route("/api/v1/users/me/activity", get(activity::my_activity))
         .route(
             "/api/v1/auth/change-password",
             axum::routing::patch(auth_change_password),
@@ -909,6 +1162,7 @@ fn user_core_routes() -> Router<SharedState> {
 fn lot_core_routes() -> Router<SharedState> {
     let mut router = Router::new()
         .route("/api/v1/lots", get(list_lots).post(create_lot))
+        .route("/api/v1/lots/search", get(search_lots))
         .route(
             "/api/v1/lots/{id}",
             get(get_lot).put(update_lot).delete(delete_lot),
@@ -919,13 +1173,41 @@ fn lot_core_routes() -> Router<SharedState> {
         )
         .route(
             "/api/v1/lots/{lot_id}/slots/{slot_id}",
-            put(update_slot).delete(delete_slot),
+            put(update_slot)
+                .patch(update_slot)
+                .delete(delete_slot),
+        )
+        // Floors
+        .route(
+            "/api/v1/lots/{id}/floors",
+            get(lot_floors::list_floors).post(lot_floors::create_floor),
+        )
+        .route(
+            "/api/v1/lots/{id}/floors/{floor_id}",
+            axum::routing::patch(lot_floors::update_floor),
+        )
+        .route(
+            "/api/v1/lots/{id}/floors/{floor_id}/slots",
+            get(lot_floors::get_floor_slots),
         )
         // Per-lot pricing
         .route(
             "/api/v1/lots/{id}/pricing",
             get(get_lot_pricing).put(update_lot_pricing),
-        );
+        )
+        // Per-lot booking rules
+        .route(
+            "/api/v1/lots/{id}/rules",
+            get(lot_rules::get_lot_rules).put(lot_rules::update_lot_rules),
+        )
+        // Per-lot overrides of global settings
+        .route(
+            "/api/v1/lots/{id}/settings",
+            get(lot_settings::get_lot_settings).put(lot_settings::update_lot_settings),
+        )
+        // Permits and gate entry
+        .route("/api/v1/permits/me", get(permits::list_my_permits))
+        .route("/api/v1/gate/check", post(permits::gate_check));
 
     // Dynamic pricing (occupancy-based) — user-facing read endpoint
     #[cfg(feature = "mod-dynamic-pricing")]
@@ -958,8 +1240,28 @@ fn lot_core_routes() -> Router<SharedState> {
 
 /// Per-user security + preference routes: 2FA lifecycle, login history,
 /// session management, API keys, notification preferences, design theme.
-fn user_security_routes() -> Router<SharedState> {
+fn user_security_routes(passkey_service: Arc<passkeys::PasskeyService>) -> Router<SharedState> {
+    let passkey_routes = Router::new()
+        .route(
+            "/api/v1/auth/webauthn/register/start",
+            post(passkeys::start_registration),
+        )
+        .route(
+            "/api/v1/auth/webauthn/register/finish",
+            post(passkeys::finish_registration),
+        )
+        .route(
+            "/api/v1/auth/webauthn/credentials",
+            get(passkeys::list_passkeys),
+        )
+        .route(
+            "/api/v1/auth/webauthn/credentials/{id}",
+            delete(passkeys::delete_passkey),
+        )
+        .layer(Extension(passkey_service));
+
     Router::new()
+        .merge(passkey_routes)
         // ── Security: 2FA ──
         .route("/api/v1/auth/2fa/setup", post(security::two_factor_setup))
         .route("/api/v1/auth/2fa/verify", post(security::two_factor_verify))
@@ -967,9 +1269,15 @@ fn user_security_routes() -> Router<SharedState> {
         .route("/api/v1/auth/2fa/status", get(security::two_factor_status))
         // ── Security: Login history ──
         .route("/api/v1/auth/login-history", get(security::get_login_history))
+        .route("/api/v1/users/me/logins", get(security::list_my_logins))
         // ── Security: Session management ──
         .route("/api/v1/auth/sessions", get(security::list_sessions))
         .route("/api/v1/auth/sessions/{id}", delete(security::revoke_session))
+        .route("/api/v1/users/me/sessions", get(security::list_my_sessions))
+        .route(
+            "/api/v1/users/me/sessions/{token_id}",
+            delete(security::revoke_my_session),
+        )
         // ── Security: API keys ──
         .route(
             "/api/v1/auth/api-keys",
@@ -993,7 +1301,7 @@ fn user_security_routes() -> Router<SharedState> {
 /// Admin sub-router — every `/api/v1/admin/*` route that is gated by the
 /// shared `admin_middleware` layer (issue #109). Returns the router *without*
 /// the middleware layer applied; `create_router` wraps the merged router in
-/// `from_fn_with_state(state, admin_middleware)` before merging it into the
+/// `from_fn(admin_middleware)` before merging it into the
 /// protected surface, matching the pre-split middleware order verbatim.
 #[allow(unused_mut, clippy::too_many_lines)]
 fn admin_core_routes() -> Router<SharedState> {
@@ -1012,7 +1320,67 @@ fn admin_core_routes() -> Router<SharedState> {
             axum::routing::patch(admin_update_user_status),
         )
         .route("/api/v1/admin/users/{id}", delete(admin_delete_user))
+        .route("/api/v1/admin/users/bulk", post(bulk::bulk_users))
+        .route(
+            "/api/v1/admin/dummy-users",
+            get(dummy_users::list_dummy_users).delete(dummy_users::purge_dummy_users),
+        )
         .route("/api/v1/admin/bookings", get(admin_list_bookings))
+        .route("/api/v1/admin/bookings/bulk", post(bulk::bulk_bookings))
+        .route(
+            "/api/v1/admin/bookings/{id}/notes",
+            get(booking_notes::list_booking_notes).post(booking_notes::add_booking_note),
+        )
+        .route("/api/v1/admin/search", get(search::admin_search))
+        .route("/api/v1/admin/lots/{id}/clone", post(clone_lot))
+        .route(
+            "/api/v1/admin/lots/{id}/photos",
+            post(lot_photos::upload_lot_photo).layer(DefaultBodyLimit::max(MAX_REQUEST_BODY_BYTES)),
+        )
+        .route(
+            "/api/v1/admin/lots/{id}/photos/{photo_id}",
+            delete(lot_photos::delete_lot_photo),
+        )
+        .route(
+            "/api/v1/admin/lots/{id}/archive",
+            post(lot_archive::archive_lot).delete(lot_archive::unarchive_lot),
+        )
+        .route(
+            "/api/v1/admin/lots/archived",
+            get(lot_archive::list_archived_lots),
+        )
+        .route(
+            "/api/v1/admin/lots/{id}/managers",
+            get(lot_managers::list_lot_managers).post(lot_managers::assign_lot_manager),
+        )
+        .route(
+            "/api/v1/admin/lots/{id}/managers/{user_id}",
+            delete(lot_managers::remove_lot_manager),
+        )
+        .route(
+            "/api/v1/admin/custom-roles",
+            get(roles::list_roles).post(roles::create_role),
+        )
+        .route(
+            "/api/v1/admin/custom-roles/{id}",
+            put(roles::update_role).delete(roles::delete_role),
+        )
+        .route(
+            "/api/v1/admin/custom-roles/{id}/members",
+            get(roles::list_role_members).post(roles::assign_role),
+        )
+        .route(
+            "/api/v1/admin/custom-roles/{id}/members/{user_id}",
+            delete(roles::unassign_role),
+        )
+        .route(
+            "/api/v1/admin/telemetry",
+            get(telemetry::get_telemetry).put(telemetry::update_telemetry),
+        )
+        .route(
+            "/api/v1/admin/slots/{id}/history",
+            get(slot_history::get_slot_history),
+        )
         .route("/api/v1/admin/stats", get(admin_stats))
         .route("/api/v1/admin/reports", get(admin_reports))
         .route("/api/v1/admin/heatmap", get(admin_heatmap))
@@ -1026,6 +1394,19 @@ fn admin_core_routes() -> Router<SharedState> {
             get(admin_audit_log_export),
         );
 
+    #[cfg(feature = "mod-email")]
+    {
+        admin_routes = admin_routes
+            .route(
+                "/api/v1/admin/email-queue",
+                get(email_queue::list_email_queue),
+            )
+            .route(
+                "/api/v1/admin/email-queue/{id}/retry",
+                post(email_queue::retry_queued_email),
+            );
+    }
+
     #[cfg(feature = "mod-audit-export")]
     {
         admin_routes = admin_routes.route(
@@ -1059,8 +1440,29 @@ fn admin_core_routes() -> Router<SharedState> {
             );
     }
 
+    #[cfg(feature = "mod-ev-charging")]
+    {
+        admin_routes = admin_routes.route(
+            "/api/v1/admin/analytics/energy",
+            get(energy_reports::admin_energy_report),
+        );
+    }
+
     admin_routes = admin_routes
         .route("/api/v1/admin/reset", post(admin_reset))
+        .route(backup::BACKUP_PATH, post(backup::admin_backup))
+        .route(
+            backup::RESTORE_PATH,
+            post(backup::admin_restore).layer(DefaultBodyLimit::max(backup::MAX_BACKUP_BYTES)),
+        )
+        .route(
+            "/api/v1/admin/feature-flags",
+            get(feature_flags::admin_list_feature_flags),
+        )
+        .route(
+            "/api/v1/admin/feature-flags/{key}",
+            put(feature_flags::admin_set_feature_flag),
+        )
         .route(
             "/api/v1/admin/settings/auto-release",
             get(admin_get_auto_release).put(admin_update_auto_release),
@@ -1074,10 +1476,56 @@ fn admin_core_routes() -> Router<SharedState> {
             get(admin_get_privacy).put(admin_update_privacy),
         )
         .route("/api/v1/admin/users/{id}/update", put(admin_update_user))
+        // ── Booking tags (cost attribution) ──
+        .route(
+            "/api/v1/admin/booking-tags",
+            put(booking_tags::update_booking_tag_sets),
+        )
+        // ── Department quotas and chargeback ──
+        .route(
+            "/api/v1/admin/department-quotas",
+            get(department_quotas::get_department_quotas)
+                .put(department_quotas::update_department_quotas),
+        )
+        .route(
+            "/api/v1/admin/reports/department-utilization",
+            get(department_quotas::department_utilization_report),
+        )
+        // ── Parking permits ──
+        .route(
+            "/api/v1/admin/permits",
+            get(permits::admin_list_permits).post(permits::admin_create_permit),
+        )
+        .route(
+            "/api/v1/admin/permits/{id}",
+            put(permits::admin_update_permit).delete(permits::admin_delete_permit),
+        )
         .route(
             "/api/v1/admin/users/{id}/reset-password",
             post(admin_handlers::admin_reset_user_password),
         )
+        .route(
+            "/api/v1/admin/users/{id}/impersonate",
+            post(impersonation::impersonate_user),
+        )
+        // ── Invitations ──
+        .route(
+            "/api/v1/admin/invites",
+            get(invites::list_invites).post(invites::create_invite),
+        )
+        .route(
+            "/api/v1/admin/invites/{id}",
+            delete(invites::revoke_invite),
+        )
+        // ── GDPR: rectification requests ──
+        .route(
+            "/api/v1/admin/rectifications",
+            get(rectification::admin_list_rectifications),
+        )
+        .route(
+            "/api/v1/admin/rectifications/{id}",
+            put(rectification::resolve_rectification),
+        )
         // ── Security: Admin password policy ──
         .route(
             "/api/v1/admin/settings/password-policy",
@@ -1088,6 +1536,11 @@ fn admin_core_routes() -> Router<SharedState> {
             "/api/v1/admin/users/{id}/login-history",
             get(security::admin_get_login_history),
         )
+        // ── Security: Admin force-logout ──
+        .route(
+            "/api/v1/admin/users/{id}/sessions",
+            delete(security::admin_revoke_user_sessions),
+        )
         // ── Bulk admin operations ──
         .route(
             "/api/v1/admin/users/bulk-update",
@@ -1319,7 +1772,44 @@ fn booking_protected_routes() -> Router<SharedState> {
                     .patch(update_booking),
             )
             .route("/api/v1/bookings/{id}/invoice", get(get_booking_invoice))
+            .route(
+                "/api/v1/bookings/{id}/attachments",
+                get(booking_attachments::list_booking_attachments)
+                    .post(booking_attachments::upload_booking_attachment)
+                    .layer(DefaultBodyLimit::max(MAX_REQUEST_BODY_BYTES)),
+            )
+            .route(
+                "/api/v1/bookings/{id}/attachments/{attachment_id}",
+                get(booking_attachments::download_booking_attachment)
+                    .delete(booking_attachments::delete_booking_attachment),
+            )
+            .route(
+                "/api/v1/users/me/invoices",
+                get(invoice_archive::list_my_invoices),
+            )
+            .route(
+                "/api/v1/users/me/invoices/{number}",
+                get(invoice_archive::get_my_invoice),
+            )
+            .route(
+                "/api/v1/bookings/tags",
+                get(booking_tags::list_booking_tag_sets),
+            )
             .route("/api/v1/bookings/quick", post(quick_book))
+            .route("/api/v1/bookings/quote", post(quote_booking))
+            .route("/api/v1/lots/{id}/calendar", get(lot_calendar))
+            .route(
+                "/api/v1/lots/{id}/availability",
+                get(availability::lot_availability),
+            )
+            .route(
+                "/api/v1/slots/{id}/calendar",
+                get(availability::slot_calendar),
+            )
+            .route(
+                "/api/v1/slots/{id}/hold",
+                post(slot_holds::hold_slot).delete(slot_holds::release_slot_hold),
+            )
             .route("/api/v1/bookings/{id}/checkin", post(booking_checkin))
             // P1-1: canonical hyphenated alias — idempotent, delegates to same handler
             .route("/api/v1/bookings/{id}/check-in", post(booking_checkin));
@@ -1372,7 +1862,9 @@ fn booking_protected_routes() -> Router<SharedState> {
             )
             .route(
                 "/api/v1/lots/{id}/waitlist",
-                get(get_lot_waitlist).delete(leave_lot_waitlist),
+                get(get_lot_waitlist)
+                    .post(join_lot_waitlist)
+                    .delete(leave_lot_waitlist),
             )
             .route(
                 "/api/v1/lots/{id}/waitlist/{entry_id}/accept",
@@ -1403,10 +1895,15 @@ fn booking_protected_routes() -> Router<SharedState> {
 
     #[cfg(feature = "mod-invoices")]
     {
-        router = router.route(
-            "/api/v1/bookings/{id}/invoice/pdf",
-            get(invoices::get_booking_invoice_pdf),
-        );
+        router = router
+            .route(
+                "/api/v1/bookings/{id}/invoice/pdf",
+                get(invoices::get_booking_invoice_pdf),
+            )
+            .route(
+                "/api/v1/users/me/invoices/{number}/pdf",
+                get(invoices::get_my_invoice_pdf),
+            );
     }
 
     #[cfg(feature = "mod-qr")]
@@ -1530,7 +2027,15 @@ fn settings_and_data_routes() -> Router<SharedState> {
                 "/api/v1/user/favorites",
                 get(list_favorites).post(add_favorite),
             )
-            .route("/api/v1/user/favorites/{slot_id}", delete(remove_favorite));
+            .route("/api/v1/user/favorites/{slot_id}", delete(remove_favorite))
+            .route(
+                "/api/v1/users/me/favorites",
+                get(list_my_favorites).post(add_my_favorite),
+            )
+            .route(
+                "/api/v1/users/me/favorites/lots/{lot_id}",
+                delete(remove_my_favorite_lot),
+            );
     }
 
     #[cfg(feature = "mod-settings")]
@@ -1566,7 +2071,9 @@ fn settings_and_data_routes() -> Router<SharedState> {
 
     #[cfg(feature = "mod-import")]
     {
-        router = router.route("/api/v1/admin/users/import", post(import_users_csv));
+        router = router
+            .route("/api/v1/admin/users/import", post(import_users_csv))
+            .route("/api/v1/admin/lots/import", post(import_lots));
     }
 
     #[cfg(feature = "mod-export")]
@@ -1718,6 +2225,7 @@ fn domain_feature_routes() -> Router<SharedState> {
             .route("/api/v1/chargers/{id}/start", post(start_charging))
             .route("/api/v1/chargers/{id}/stop", post(stop_charging))
             .route("/api/v1/chargers/sessions", get(charging_history))
+            .route("/api/v1/user/energy", get(energy_reports::my_energy_report))
             .route(
                 "/api/v1/admin/chargers",
                 get(admin_charger_overview).post(admin_add_charger),
@@ -1775,6 +2283,11 @@ fn domain_feature_routes() -> Router<SharedState> {
             .route(
                 "/api/v1/admin/announcements/{id}",
                 put(admin_update_announcement).delete(admin_delete_announcement),
+            )
+            .route("/api/v1/announcements", get(get_my_announcements))
+            .route(
+                "/api/v1/announcements/{id}/dismiss",
+                post(dismiss_announcement),
             );
     }
 
@@ -1864,6 +2377,30 @@ fn domain_feature_routes() -> Router<SharedState> {
             .route(
                 "/api/v1/admin/guest-bookings/{id}/cancel",
                 axum::routing::patch(admin_cancel_guest_booking),
+            )
+            .route(
+                "/api/v1/bookings/visitor",
+                post(visitor_bookings::create_visitor_booking),
+            )
+            .route(
+                "/api/v1/lots/{id}/visitor-pass-blocks",
+                get(visitor_passes::list_visitor_pass_blocks),
+            )
+            .route(
+                "/api/v1/lots/{id}/visitor-pass-blocks/{date}",
+                put(visitor_passes::set_visitor_pass_block),
+            )
+            .route(
+                "/api/v1/lots/{id}/visitor-passes",
+                get(visitor_passes::list_visitor_passes).post(visitor_passes::issue_visitor_pass),
+            )
+            .route(
+                "/api/v1/visitor-passes/{id}/check-in",
+                post(visitor_passes::check_in_visitor_pass),
+            )
+            .route(
+                "/api/v1/visitor-passes/{id}",
+                delete(visitor_passes::cancel_visitor_pass),
             );
     }
 
@@ -1950,10 +2487,15 @@ fn integration_routes() -> Router<SharedState> {
 
     #[cfg(feature = "mod-map")]
     {
-        router = router.route(
-            "/api/v1/admin/lots/{id}/location",
-            put(map::set_lot_location),
-        );
+        router = router
+            .route(
+                "/api/v1/admin/lots/{id}/location",
+                put(map::set_lot_location),
+            )
+            .route(
+                "/api/v1/admin/lots/{id}/boundary",
+                put(map::set_lot_boundary),
+            );
     }
 
     #[cfg(feature = "mod-payments")]
@@ -2055,13 +2597,24 @@ pub fn create_router(
 
     // 2FA temporary token store — shared between login and 2FA login routes
     let two_fa_store = security::TwoFactorTempTokenStore::new();
+    // Passkey relying party and pending ceremonies — shared between the
+    // public login routes and the authenticated registration routes
+    let passkey_service = passkeys::PasskeyService::from_env();
+    // Per-account failure counts — shared between login and password reset
+    let auth_guard = auth_guard::AuthGuard::new();
 
     // ── Compose route groups via helpers ──────────────────────────────────
     // Each helper returns a `Router<SharedState>` with its route-local layers
     // (per-route rate limiters, feature-gated endpoints) already applied.
     // Cross-cutting middleware (auth, admin, outer tower stack) is still
     // layered below so the order matches the pre-split implementation.
-    let auth_public = auth_rate_limited_routes(&rate_limiters, &identity_limiters, two_fa_store);
+    let auth_public = auth_rate_limited_routes(
+        &rate_limiters,
+        &identity_limiters,
+        two_fa_store,
+        passkey_service.clone(),
+        auth_guard,
+    );
     let public = public_routes(&state, &rate_limiters);
     let (demo, demo_state_ret) = demo_routes(&rate_limiters);
 
@@ -2073,15 +2626,12 @@ pub fn create_router(
     // added through `settings_and_data_routes` / `domain_feature_routes` are
     // intentionally NOT wrapped by admin_middleware — they rely on handler-
     // level `check_admin` only. See feedback_modular_refactor rationale.
-    let admin_with_guard = admin_core_routes().route_layer(middleware::from_fn_with_state(
-        state.clone(),
-        admin_middleware,
-    ));
+    let admin_with_guard = admin_core_routes().route_layer(middleware::from_fn(admin_middleware));
 
     let protected_routes = Router::new()
         .merge(user_core_routes())
         .merge(lot_core_routes())
-        .merge(user_security_routes())
+        .merge(user_security_routes(passkey_service))
         .merge(admin_with_guard)
         .merge(booking_protected_routes())
         .merge(vehicle_routes())
@@ -2124,6 +2674,9 @@ pub fn create_router(
 
     let x_request_id = HeaderName::from_static("x-request-id");
 
+    // Handler and body-read limits, from `PARKHUB_*_TIMEOUT*`
+    let request_timeouts = Arc::new(timeouts::RequestTimeouts::from_env());
+
     // ── Merge all route groups into the root router ──────────────────────
     let mut router = Router::new()
         .merge(public)
@@ -2215,7 +2768,7 @@ pub fn create_router(
 
     let router = router
         // Restrict request body size to prevent DoS via large payloads
-        .layer(RequestBodyLimitLayer::new(MAX_REQUEST_BODY_BYTES))
+        .layer(axum::middleware::from_fn(request_body_limit_middleware))
         // CORS: same-origin by default; no wildcard.
         // Set PARKHUB_CORS_ORIGINS for production deployments.
         .layer(
@@ -2260,22 +2813,10 @@ pub fn create_router(
         // this on every token validation.  The backend (in-memory vs
         // Redis) is decided once in `main::build_revocation_store`.
         .layer(Extension(revocation_store))
-        // 30-second request timeout — returns 408 on breach
-        .layer(
-            ServiceBuilder::new()
-                .layer(axum::error_handling::HandleErrorLayer::new(
-                    |_: tower::BoxError| async {
-                        (
-                            StatusCode::REQUEST_TIMEOUT,
-                            Json(serde_json::json!({
-                                "error": "REQUEST_TIMEOUT",
-                                "message": "Request timed out"
-                            })),
-                        )
-                    },
-                ))
-                .layer(tower::timeout::TimeoutLayer::new(Duration::from_secs(30))),
-        );
+        // Request and body-read timeouts — 504 / 408 problem details on breach
+        .layer(axum::middleware::from_fn(move |req, next| {
+            timeouts::timeout_middleware(request_timeouts.clone(), req, next)
+        }));
 
     (router, demo_state_ret)
 }
@@ -2287,6 +2828,11 @@ use system::{http_metrics_middleware, request_id_tracing_middleware, security_he
 // AUTH MIDDLEWARE
 // ═══════════════════════════════════════════════════════════════════════════════
 
+/// The only routes a session may call while its account has
+/// `must_change_password` set.
+const PASSWORD_CHANGE_PATHS: &[&str] =
+    &["/api/v1/users/me/password", "/api/v1/auth/change-password"];
+
 async fn auth_middleware(
     State(state): State<SharedState>,
     mut request: Request<Body>,
@@ -2303,26 +2849,35 @@ async fn auth_middleware(
             security::validate_api_key_detailed(&state_guard.db, api_key).await
         {
             // Verify user is still active
-            match state_guard.db.get_user(&user_id.to_string()).await {
-                Ok(Some(u)) if u.is_active => {
+            match session_tokens::user_access(&state_guard.db, user_id).await {
+                Some(access) if access.is_active => {
                     drop(state_guard);
                     request.extensions_mut().insert(AuthUser {
                         user_id,
                         api_key_id: Some(api_key_id),
+                        impersonator_id: None,
+                        role: access.role,
+                        permissions: access.permissions,
                     });
                     return Ok(next.run(request).await);
                 }
                 _ => {
                     return Err((
                         StatusCode::UNAUTHORIZED,
-                        Json(ApiResponse::error("UNAUTHORIZED", "Invalid API key")),
+                        Json(ApiResponse::error(
+                            ErrorCode::Unauthorized,
+                            "Invalid API key",
+                        )),
                     ));
                 }
             }
         }
         return Err((
             StatusCode::UNAUTHORIZED,
-            Json(ApiResponse::error("UNAUTHORIZED", "Invalid API key")),
+            Json(ApiResponse::error(
+                ErrorCode::Unauthorized,
+                "Invalid API key",
+            )),
         ));
     }
 
@@ -2355,7 +2910,7 @@ async fn auth_middleware(
             return Err((
                 StatusCode::UNAUTHORIZED,
                 Json(ApiResponse::error(
-                    "UNAUTHORIZED",
+                    ErrorCode::Unauthorized,
                     "Missing or invalid authorization header",
                 )),
             ));
@@ -2377,58 +2932,123 @@ async fn auth_middleware(
             return Err((
                 StatusCode::FORBIDDEN,
                 Json(ApiResponse::error(
-                    "CSRF_VALIDATION_FAILED",
+                    ErrorCode::CsrfValidationFailed,
                     "X-Requested-With header required for cookie authentication",
                 )),
             ));
         }
     }
 
-    // Validate session
+    // Validate the access token
     let state_guard = state.read().await;
-    let session = match state_guard.db.get_session(token).await {
-        Ok(Some(s)) if !s.is_expired() => s,
-        _ => {
+    let Some(identity) = session_tokens::authenticate(&state_guard, token).await else {
+        return Err((
+            StatusCode::UNAUTHORIZED,
+            Json(ApiResponse::error(
+                ErrorCode::Unauthorized,
+                "Invalid or expired token",
+            )),
+        ));
+    };
+
+    // Re-validate the user so disabled or deleted accounts are rejected
+    // even if one of their sessions survived
+    let access = match session_tokens::user_access(&state_guard.db, identity.user_id).await {
+        Some(access) if access.is_active => access,
+        Some(_) => {
             return Err((
                 StatusCode::UNAUTHORIZED,
                 Json(ApiResponse::error(
-                    "UNAUTHORIZED",
-                    "Invalid or expired token",
+                    ErrorCode::AccountDisabled,
+                    "Account is disabled",
+                )),
+            ));
+        }
+        None => {
+            return Err((
+                StatusCode::UNAUTHORIZED,
+                Json(ApiResponse::error(
+                    ErrorCode::Unauthorized,
+                    "User not found",
                 )),
             ));
         }
     };
 
-    // Re-validate the user against the DB: reject disabled or deleted accounts
-    // even when their session token is still technically valid. This prevents
-    // suspended users from continuing to make requests until their token expires.
-    match state_guard.db.get_user(&session.user_id.to_string()).await {
-        Ok(Some(u)) if u.is_active => {}
-        Ok(Some(_)) => {
+    // Keep the session's last-used time and IP current for session listings,
+    // and end it if it sat idle past `session_timeout_minutes`
+    let peer = request
+        .extensions()
+        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+        .map(|ci| ci.0);
+    let client = security::LoginClient::from_request(request.headers(), peer);
+    match session_tokens::touch_session(&state_guard, &identity.session_id, Some(&client.ip)).await
+    {
+        session_tokens::SessionUse::Live => {}
+        session_tokens::SessionUse::IdleTimedOut => {
             return Err((
                 StatusCode::UNAUTHORIZED,
                 Json(ApiResponse::error(
-                    "ACCOUNT_DISABLED",
-                    "Account is disabled",
+                    ErrorCode::SessionIdleTimeout,
+                    "Session ended after inactivity",
                 )),
             ));
         }
-        _ => {
+        session_tokens::SessionUse::Ended => {
             return Err((
                 StatusCode::UNAUTHORIZED,
-                Json(ApiResponse::error("UNAUTHORIZED", "User not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::SessionRevoked,
+                    "Session has ended or was revoked",
+                )),
             ));
         }
     }
+    // Seeded, imported and admin-reset passwords must be replaced before the
+    // account can do anything else. Impersonating admins are not held up.
+    if identity.impersonator_id.is_none()
+        && access.must_change_password
+        && !PASSWORD_CHANGE_PATHS.contains(&request.uri().path())
+    {
+        return Err((
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::PasswordChangeRequired,
+                "You must change your password before continuing",
+            )),
+        ));
+    }
     drop(state_guard);
 
     // Insert user info into request extensions
     request.extensions_mut().insert(AuthUser {
-        user_id: session.user_id,
+        user_id: identity.user_id,
         api_key_id: None,
+        impersonator_id: identity.impersonator_id,
+        role: access.role,
+        permissions: access.permissions,
     });
 
-    Ok(next.run(request).await)
+    let Some(impersonator_id) = identity.impersonator_id else {
+        return Ok(next.run(request).await);
+    };
+    // Everything done while impersonating is audited with both identities
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let response = next.run(request).await;
+    impersonation::audit_request(
+        &state,
+        impersonation::ImpersonatedRequest {
+            user_id: identity.user_id,
+            impersonator_id,
+            client_ip: &client.ip,
+            method: &method,
+            path: &path,
+        },
+        response.status(),
+    )
+    .await;
+    Ok(response)
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -2468,7 +3088,7 @@ async fn protected_identity_rate_limit_middleware(
 use system::{
     handshake, health_check, liveness_check, readiness_check, server_status, system_maintenance,
     system_version, v1_discover, v1_health, v1_health_detailed, v1_health_info, v1_health_live,
-    v1_health_ready, v1_server_status,
+    v1_health_ready, v1_server_status, v1_time,
 };
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -2514,7 +3134,10 @@ pub async fn hash_password(
             tracing::error!("spawn_blocking failed for password hashing: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         })?
 }
@@ -2556,7 +3179,10 @@ fn hash_password_sync(
             tracing::error!("Password hashing failed: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         })
 }
@@ -2622,7 +3248,7 @@ mod tenant_scope_tests {
             db,
             mdns: None,
             scheduler: None,
-            ws_events: crate::api::ws::EventBroadcaster::new(),
+            ws_events: crate::api::events::EventBroadcaster::new(),
             fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
             revocation_store: crate::jwt::TokenRevocationList::new(),
         };
@@ -2652,6 +3278,7 @@ mod tenant_scope_tests {
             cost_center: None,
             department: None,
             settings: None,
+            must_change_password: false,
         }
     }
 
@@ -16,7 +16,7 @@ use axum::{
     middleware::Next,
     response::{IntoResponse, Response},
 };
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{SharedState, module_registry};
 
@@ -33,8 +33,8 @@ use super::{SharedState, module_registry};
 /// Adding a module here is the final step that turns its admin-settings
 /// toggle into a real runtime kill-switch at the HTTP layer.
 pub const MODULE_ROUTES: &[(&str, &[&str])] = &[
-    // Map view: public lot markers endpoint.
-    ("map", &["/api/v1/lots/map"]),
+    // Map view: public lot markers and GeoJSON export.
+    ("map", &["/api/v1/lots/map", "/api/v1/lots.geojson"]),
     // GraphQL: playground + schema (public) and execute (protected) all
     // share the `/api/v1/graphql` prefix.
     ("graphql", &["/api/v1/graphql"]),
@@ -45,8 +45,11 @@ pub const MODULE_ROUTES: &[(&str, &[&str])] = &[
     // so admins can re-enable the module via the dashboard even when
     // the public surface is turned off.
     ("announcements", &["/api/v1/announcements"]),
-    // Personal favorites (pin a slot).
-    ("favorites", &["/api/v1/user/favorites"]),
+    // Personal favorites (pin a slot or lot).
+    (
+        "favorites",
+        &["/api/v1/user/favorites", "/api/v1/users/me/favorites"],
+    ),
 ];
 
 /// Look up the module slug that owns a given request path, if any.
@@ -108,7 +111,7 @@ pub async fn module_gate(
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::<()>::error(
-                "MODULE_DISABLED",
+                ErrorCode::ModuleDisabled,
                 format!("Module '{module_name}' is disabled"),
             )),
         )
@@ -13,7 +13,7 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
 };
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 use serde::Serialize;
 use std::collections::HashMap;
 use utoipa::ToSchema;
@@ -125,7 +125,7 @@ pub async fn get_module(
         None => (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::error(
-                "UNKNOWN_MODULE",
+                ErrorCode::UnknownModule,
                 format!("Unknown module slug: {name}"),
             )),
         ),
@@ -177,8 +177,8 @@ pub async fn patch_admin_module(
     let state_guard = state.read().await;
 
     // Admin guard (defense-in-depth on top of the admin_middleware layer).
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     // Resolve the module, defaulting to the compile-time-only view so we
@@ -191,7 +191,7 @@ pub async fn patch_admin_module(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "UNKNOWN_MODULE",
+                ErrorCode::UnknownModule,
                 format!("Unknown module slug: {name}"),
             )),
         );
@@ -201,7 +201,7 @@ pub async fn patch_admin_module(
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "NOT_RUNTIME_TOGGLEABLE",
+                ErrorCode::NotRuntimeToggleable,
                 format!(
                     "Module '{name}' cannot be toggled at runtime — rebuild the binary without its cargo feature to disable it."
                 ),
@@ -220,7 +220,7 @@ pub async fn patch_admin_module(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to save runtime toggle",
             )),
         );
@@ -259,7 +259,7 @@ pub async fn patch_admin_module(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Module disappeared after toggle",
             )),
         );
@@ -327,8 +327,8 @@ pub async fn get_module_config(
     let canonical_name = canonical_module_slug(&name).to_string();
     let state_guard = state.read().await;
 
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return Err((status, Json(ApiResponse::error("FORBIDDEN", msg))));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return Err((status, Json(ApiResponse::error(ErrorCode::Forbidden, msg))));
     }
 
     let (schema_value, schema) =
@@ -336,21 +336,21 @@ pub async fn get_module_config(
             StatusCode::NOT_FOUND => (
                 status,
                 Json(ApiResponse::error(
-                    "UNKNOWN_MODULE",
+                    ErrorCode::UnknownModule,
                     format!("Unknown module slug: {name}"),
                 )),
             ),
             StatusCode::BAD_REQUEST => (
                 status,
                 Json(ApiResponse::error(
-                    "NO_CONFIG_SCHEMA",
+                    ErrorCode::NoConfigSchema,
                     format!("Module '{name}' has no config schema"),
                 )),
             ),
             _ => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to resolve module schema",
                 )),
             ),
@@ -408,8 +408,8 @@ pub async fn patch_module_config(
     let canonical_name = canonical_module_slug(&name).to_string();
     let state_guard = state.read().await;
 
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let (schema_value, schema) = match resolve_schema(&canonical_name) {
@@ -418,7 +418,7 @@ pub async fn patch_module_config(
             return (
                 StatusCode::NOT_FOUND,
                 Json(ApiResponse::error(
-                    "UNKNOWN_MODULE",
+                    ErrorCode::UnknownModule,
                     format!("Unknown module slug: {name}"),
                 )),
             );
@@ -427,7 +427,7 @@ pub async fn patch_module_config(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "NO_CONFIG_SCHEMA",
+                    ErrorCode::NoConfigSchema,
                     format!("Module '{name}' has no config schema"),
                 )),
             );
@@ -436,7 +436,7 @@ pub async fn patch_module_config(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to resolve module schema",
                 )),
             );
@@ -454,7 +454,7 @@ pub async fn patch_module_config(
                 success: false,
                 data: None,
                 error: Some(parkhub_common::ApiError {
-                    code: "VALIDATION_FAILED".to_string(),
+                    code: ErrorCode::ValidationFailed,
                     message: "Request body failed schema validation".to_string(),
                     details: Some(serde_json::json!({ "errors": errors })),
                 }),
@@ -480,7 +480,7 @@ pub async fn patch_module_config(
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(ApiResponse::error(
-                        "SERVER_ERROR",
+                        ErrorCode::ServerError,
                         "Failed to encode config value",
                     )),
                 );
@@ -496,7 +496,7 @@ pub async fn patch_module_config(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to save module config",
                 )),
             );
@@ -46,7 +46,7 @@ fn test_state() -> (tempfile::TempDir, SharedState) {
         db,
         mdns: None,
         scheduler: None,
-        ws_events: crate::api::ws::EventBroadcaster::new(),
+        ws_events: crate::api::events::EventBroadcaster::new(),
         fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
         revocation_store: crate::jwt::TokenRevocationList::new(),
     }));
@@ -520,8 +520,8 @@ fn test_category_serializes_kebab_case() {
 use parkhub_common::{User, UserPreferences, UserRole};
 
 /// Seed a user with the given role and return their `AuthUser`
-/// handle. Writes the user directly through `db.save_user` so the
-/// `check_admin` lookup succeeds.
+/// handle. Writes the user directly through `db.save_user` so handlers
+/// that load the caller find them.
 async fn seed_user(state: &SharedState, role: UserRole) -> AuthUser {
     let user_id = uuid::Uuid::new_v4();
     let user = User {
@@ -546,6 +546,7 @@ async fn seed_user(state: &SharedState, role: UserRole) -> AuthUser {
         cost_center: None,
         department: None,
         settings: None,
+        must_change_password: false,
     };
     state
         .read()
@@ -557,6 +558,9 @@ async fn seed_user(state: &SharedState, role: UserRole) -> AuthUser {
     AuthUser {
         user_id,
         api_key_id: None,
+        impersonator_id: None,
+        permissions: parkhub_common::ResolvedPermissions::for_role(&user.role),
+        role: user.role,
     }
 }
 
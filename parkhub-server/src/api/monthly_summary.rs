@@ -0,0 +1,101 @@
+//! Monthly summary emails.
+//!
+//! Early each month, while the `monthly_summary_emails` admin setting is on,
+//! every active user who parked or charged during the previous month is
+//! emailed a summary of it: the bookings they used and the hours parked,
+//! plus the energy charged and its estimated CO2 when they charged an EV
+//! (see [`super::energy_reports`]). The month last sent is kept in the
+//! settings table, so each month goes out once.
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use parkhub_common::{Booking, BookingStatus};
+
+use crate::db::Database;
+
+use super::energy_reports::{self, Month};
+
+/// Admin setting switching the monthly summary emails on.
+pub const SUMMARY_SETTING: &str = "monthly_summary_emails";
+
+/// Settings key of the last month (YYYY-MM) summaries were sent for.
+const LAST_SENT_KEY: &str = "monthly_summary_last_month";
+
+/// Whether `booking` was used, i.e. not cancelled, expired or missed.
+const fn was_used(booking: &Booking) -> bool {
+    matches!(
+        booking.status,
+        BookingStatus::Confirmed | BookingStatus::Active | BookingStatus::Completed
+    )
+}
+
+/// Bookings used and hours parked per user in `month`.
+fn parking_by_user(bookings: &[Booking], month: Month) -> HashMap<Uuid, (u32, f64)> {
+    let mut parking: HashMap<Uuid, (u32, f64)> = HashMap::new();
+    for booking in bookings
+        .iter()
+        .filter(|b| was_used(b) && month.contains(b.start_time))
+    {
+        let entry = parking.entry(booking.user_id).or_default();
+        entry.0 += 1;
+        entry.1 += (booking.end_time - booking.start_time).num_minutes() as f64 / 60.0;
+    }
+    parking
+}
+
+/// Queue the summaries of the month before `now` unless already sent.
+/// Returns the number of emails queued.
+pub async fn send_monthly_summaries(
+    db: &Database,
+    org_name: &str,
+    now: DateTime<Utc>,
+) -> anyhow::Result<usize> {
+    if super::settings::read_admin_setting(db, SUMMARY_SETTING).await != "true" {
+        return Ok(0);
+    }
+    let month = Month::containing(now).previous();
+    let label = month.label();
+    if db.get_setting(LAST_SENT_KEY).await?.as_deref() == Some(label.as_str()) {
+        return Ok(0);
+    }
+
+    let parking = parking_by_user(&db.list_bookings().await?, month);
+    let sessions = db.list_all_charging_sessions().await?;
+    let grams_per_kwh = energy_reports::co2_grams_per_kwh(db).await;
+
+    let month_name = month.first_day.format("%B %Y").to_string();
+    let mut sent = 0;
+    for user in db.list_users().await? {
+        if !user.is_active || user.email.is_empty() {
+            continue;
+        }
+        let (bookings, hours) = parking.get(&user.id).copied().unwrap_or_default();
+        let energy = energy_reports::user_totals(&sessions, user.id, month, grams_per_kwh);
+        if bookings == 0 && energy.sessions == 0 {
+            continue;
+        }
+
+        let html = crate::email::build_monthly_summary_email(
+            &user.name,
+            &month_name,
+            bookings,
+            hours,
+            (energy.sessions > 0).then_some((energy.kwh, energy.co2_kg)),
+            org_name,
+        );
+        crate::email_queue::enqueue(
+            db,
+            &user.email,
+            &format!("Your parking summary for {month_name}"),
+            &html,
+        )
+        .await?;
+        sent += 1;
+    }
+
+    db.set_setting(LAST_SENT_KEY, &label).await?;
+    Ok(sent)
+}
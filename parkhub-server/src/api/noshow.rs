@@ -19,6 +19,8 @@
 // db access goes through its own inner RwLock. See workspace lint config.
 #![allow(clippy::significant_drop_tightening)]
 
+use std::collections::{BTreeMap, HashSet};
+
 use axum::{
     Extension, Json,
     extract::{Path, State},
@@ -29,14 +31,16 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use parkhub_common::{
-    ApiResponse, Booking, BookingPricing, BookingStatus, FuelType, PaymentStatus, SlotStatus,
-    Vehicle, VehicleType,
+    ApiResponse, Booking, BookingPricing, BookingStatus, ErrorCode, FuelType, PaymentStatus,
+    SlotStatus, TimeRange, Vehicle, VehicleType,
     models::{Notification, NotificationType, WaitlistEntry, WaitlistStatus},
 };
 
 use crate::AppState;
 use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{SlotChangeContext, WriteBatch};
 
+use super::feature_flags::{RequireFlag, WaitlistFlag};
 use super::{AuthUser, SharedState, check_admin};
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -48,6 +52,8 @@ use super::{AuthUser, SharedState, check_admin};
 pub const DEFAULT_DEADLINE_MINUTES: i64 = 30;
 /// Default minutes a promoted user has to claim their offer.
 pub const DEFAULT_CLAIM_WINDOW_MINUTES: i64 = 15;
+/// Hours booked by claiming an offer that names no window.
+const DEFAULT_CLAIM_HOURS: i64 = 2;
 
 /// Settings key for per-lot no-show deadline.
 pub fn lot_deadline_key(lot_id: &str) -> String {
@@ -128,7 +134,7 @@ pub async fn get_lot_noshow_config(
     {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Lot not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Lot not found")),
         );
     }
     let config = LotNoshowConfig {
@@ -170,8 +176,8 @@ pub async fn update_lot_noshow_config(
 ) -> (StatusCode, Json<ApiResponse<LotNoshowConfig>>) {
     let state_guard = state.read().await;
 
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     if state_guard
@@ -183,7 +189,7 @@ pub async fn update_lot_noshow_config(
     {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Lot not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Lot not found")),
         );
     }
 
@@ -191,7 +197,7 @@ pub async fn update_lot_noshow_config(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "check_in_deadline_minutes must be >= 0; claim_window_minutes must be >= 1",
             )),
         );
@@ -209,7 +215,7 @@ pub async fn update_lot_noshow_config(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update config",
             )),
         );
@@ -227,7 +233,7 @@ pub async fn update_lot_noshow_config(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update config",
             )),
         );
@@ -265,6 +271,7 @@ pub async fn update_lot_noshow_config(
 )]
 pub async fn list_my_offers(
     State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
     Extension(auth_user): Extension<AuthUser>,
 ) -> Json<ApiResponse<Vec<WaitlistEntry>>> {
     let state_guard = state.read().await;
@@ -283,26 +290,28 @@ pub async fn list_my_offers(
 /// Request body for claiming a waitlist offer
 #[derive(Debug, Deserialize, utoipa::ToSchema)]
 pub struct ClaimOfferRequest {
-    /// Booking start time (defaults to now if omitted).
+    /// Booking start time (defaults to the entry's window, else now).
     #[serde(default)]
     pub start_time: Option<DateTime<Utc>>,
-    /// Booking end time (defaults to `start_time + 2h` if omitted).
+    /// Booking end time (defaults to the entry's window, else `start_time + 2h`).
     #[serde(default)]
     pub end_time: Option<DateTime<Utc>>,
 }
 
 /// `POST /api/v1/waitlist/offers/{id}/claim` — convert a waitlist offer into a booking
 ///
-/// Finds the first available slot in the offered lot and creates a Confirmed
-/// booking. The claim is atomic under a write lock — double-claim is rejected
-/// with 409 (entry no longer in `offered` state).
+/// Books the slot held by the offer (or another free slot of the lot) for the
+/// entry's window and creates a Confirmed booking. The booking and the
+/// accepted entry commit in one transaction under the write lock —
+/// double-claim is rejected with 409 (entry no longer in `offered` state).
 ///
 /// Promotion order is strict FIFO (AI-Act compliant).
 #[utoipa::path(
     post, path = "/api/v1/waitlist/offers/{id}/claim", tag = "Waitlist",
     summary = "Claim a waitlist offer",
     description = "Converts an active waitlist offer into a confirmed booking. \
-                   Finds the first available slot in the offered lot. \
+                   Books the slot held by the offer, or another free slot of the lot, \
+                   for the entry's time window. \
                    Promotion order is strict FIFO by `created_at` (AI-Act compliant). \
                    Idempotent: if the entry is already Accepted the request is rejected \
                    with 409 so the caller must use the booking referenced in \
@@ -320,6 +329,7 @@ pub struct ClaimOfferRequest {
 )]
 pub async fn claim_offer(
     State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
     Extension(auth_user): Extension<AuthUser>,
     Path(entry_id): Path<String>,
     Json(req): Json<ClaimOfferRequest>,
@@ -333,14 +343,20 @@ pub async fn claim_offer(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Waitlist offer not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Waitlist offer not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("DB error fetching waitlist entry: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -348,7 +364,7 @@ pub async fn claim_offer(
     if entry.user_id != auth_user.user_id {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Not your offer")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Not your offer")),
         );
     }
 
@@ -356,7 +372,7 @@ pub async fn claim_offer(
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "NOT_OFFERED",
+                ErrorCode::NotOffered,
                 "This entry is not in offered state",
             )),
         );
@@ -373,26 +389,52 @@ pub async fn claim_offer(
         let _ = state_guard.db.save_waitlist_entry(&expired).await;
         return (
             StatusCode::GONE,
-            Json(ApiResponse::error("OFFER_EXPIRED", "The offer has expired")),
+            Json(ApiResponse::error(
+                ErrorCode::OfferExpired,
+                "The offer has expired",
+            )),
         );
     }
 
-    // Find an available slot in the lot (write lock held → race-safe).
-    let slots = state_guard
+    // The offer's window unless the request names one; offers without a
+    // window book from now.
+    let start_time = req.start_time.or(entry.start_time).unwrap_or(now);
+    let end_time = req
+        .end_time
+        .or(entry.end_time)
+        .unwrap_or_else(|| start_time + Duration::hours(DEFAULT_CLAIM_HOURS));
+    let Some(range) = TimeRange::new(start_time, end_time).filter(|range| range.end > now) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "end_time must be after start_time and in the future",
+            )),
+        );
+    };
+
+    // The slot the offer holds if it is still free, otherwise the first
+    // free slot not held for someone else.
+    let lot_entries = state_guard
         .db
-        .list_slots_by_lot(&entry.lot_id.to_string())
+        .list_waitlist_by_lot(&entry.lot_id.to_string())
         .await
         .unwrap_or_default();
-
-    let Some(slot) = slots
-        .into_iter()
-        .find(|s| s.status == SlotStatus::Available)
+    let held = held_slots(&lot_entries, &range, Some(auth_user.user_id), now);
+    let free = super::availability::free_slots(&state_guard.db, &entry.lot_id.to_string(), &range)
+        .await
+        .unwrap_or_default();
+    let Some(slot) = free
+        .iter()
+        .find(|s| Some(s.id) == entry.offered_slot_id)
+        .or_else(|| free.iter().find(|s| !held.contains(&s.id)))
+        .cloned()
     else {
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "NO_SLOT",
-                "No available slot in lot right now",
+                ErrorCode::NoSlot,
+                "No slot in the lot is free for this window",
             )),
         );
     };
@@ -436,11 +478,6 @@ pub async fn claim_offer(
         })
         .unwrap_or_else(|| "Level 1".to_string());
 
-    let start_time = req.start_time.unwrap_or(now);
-    let end_time = req
-        .end_time
-        .unwrap_or_else(|| start_time + Duration::hours(2));
-
     let booking = Booking {
         id: Uuid::new_v4(),
         user_id: auth_user.user_id,
@@ -460,6 +497,7 @@ pub async fn claim_offer(
             currency: "EUR".to_string(),
             payment_status: PaymentStatus::Pending,
             payment_method: None,
+            cancellation_fee: None,
         },
         created_at: now,
         updated_at: now,
@@ -468,34 +506,47 @@ pub async fn claim_offer(
         qr_code: None,
         notes: Some(format!("Claimed via waitlist offer {entry_id}")),
         tenant_id: None,
+        tags: BTreeMap::new(),
+        booked_for: None,
     };
 
-    if let Err(e) = state_guard.db.save_booking(&booking).await {
-        tracing::error!("Failed to save claimed booking: {e}");
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error(
-                "SERVER_ERROR",
-                "Failed to create booking",
-            )),
-        );
-    }
-
-    // Mark slot as Occupied.
-    if let Err(e) = state_guard
-        .db
-        .update_slot_status(&slot.id.to_string(), SlotStatus::Occupied)
-        .await
-    {
-        tracing::warn!("Failed to mark slot {} occupied: {e}", slot.id);
-    }
-
-    // Mark entry as Accepted with the new booking ID.
+    // The booking, the accepted entry and the slot status commit together;
+    // the claim re-checks the slot for overlapping bookings.
     let mut accepted = entry;
     accepted.status = WaitlistStatus::Accepted;
     accepted.accepted_booking_id = Some(booking.id);
-    if let Err(e) = state_guard.db.save_waitlist_entry(&accepted).await {
-        tracing::warn!("Failed to update waitlist entry to Accepted: {e}");
+    let mut batch = WriteBatch::new();
+    batch.claim_slot(&booking).save_waitlist_entry(&accepted);
+    if slot.status == SlotStatus::Available {
+        let mut reserved = slot.clone();
+        reserved.status = SlotStatus::Reserved;
+        batch.save_parking_slot(
+            &reserved,
+            &SlotChangeContext::user(auth_user.user_id, "waitlist")
+                .reason(format!("Waitlist offer {entry_id} claimed")),
+        );
+    }
+    match state_guard.db.apply_batch(&batch).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::NoSlot,
+                    "The slot was just booked by someone else",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Failed to save claimed booking: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to create booking",
+                )),
+            );
+        }
     }
 
     AuditEntry::new(AuditEventType::BookingCreated)
@@ -518,12 +569,57 @@ pub async fn claim_offer(
 // Shared promotion logic (called by jobs + cancel flow)
 // ─────────────────────────────────────────────────────────────────────────────
 
+/// The window `entry` waits for; `None` for entries waiting for any slot.
+pub fn entry_window(entry: &WaitlistEntry) -> Option<TimeRange> {
+    TimeRange::new(entry.start_time?, entry.end_time?)
+}
+
+/// Slots held by the outstanding offers among `entries` whose window overlaps
+/// `range`, leaving out offers to `except_user`.
+fn held_slots(
+    entries: &[WaitlistEntry],
+    range: &TimeRange,
+    except_user: Option<Uuid>,
+    now: DateTime<Utc>,
+) -> HashSet<Uuid> {
+    entries
+        .iter()
+        .filter(|e| {
+            e.status == WaitlistStatus::Offered
+                && e.offer_expires_at.is_some_and(|exp| now <= exp)
+                && Some(e.user_id) != except_user
+                && entry_window(e).is_some_and(|window| window.overlaps(range))
+        })
+        .filter_map(|e| e.offered_slot_id)
+        .collect()
+}
+
+/// Slots of `lot_id` that outstanding waitlist offers to users other than
+/// `user_id` hold during `range`. They cannot be booked until the offer is
+/// claimed, declined or runs out.
+pub async fn slots_held_for_others(
+    state: &AppState,
+    lot_id: Uuid,
+    range: &TimeRange,
+    user_id: Uuid,
+) -> HashSet<Uuid> {
+    let entries = state
+        .db
+        .list_waitlist_by_lot(&lot_id.to_string())
+        .await
+        .unwrap_or_default();
+    held_slots(&entries, range, Some(user_id), Utc::now())
+}
+
 /// Promote the next FIFO-ordered Waiting entry for `lot_id` to Offered status.
 ///
 /// Sets `status = Offered`, `notified_at = now`, `offer_expires_at = now +
-/// claim_window_minutes`, and creates an in-app notification.
+/// claim_window_minutes`, and notifies the user in-app and by email.
+/// Entries with a window are only offered once a slot is free for it; the
+/// offer then holds that slot (`offered_slot_id`) until it runs out.
 ///
-/// Returns `true` if an entry was promoted, `false` if no Waiting entry exists.
+/// Returns `true` if an entry was promoted, `false` if no Waiting entry can
+/// be served.
 ///
 /// **AI-Act compliance**: promotion is strict FIFO by `created_at`. The DB
 /// layer (`list_waitlist_by_lot`) sorts ascending by `created_at` — no further
@@ -532,29 +628,84 @@ pub async fn promote_next_waitlist_offer(
     state: &AppState,
     lot_id: Uuid,
     claim_window_minutes: i64,
+) -> bool {
+    offer_next(state, lot_id, claim_window_minutes, true).await
+}
+
+/// Offer to the first Waiting entry that can be served; entries without a
+/// window only when `include_open`. See [`promote_next_waitlist_offer`].
+async fn offer_next(
+    state: &AppState,
+    lot_id: Uuid,
+    claim_window_minutes: i64,
+    include_open: bool,
 ) -> bool {
     let entries = state
         .db
         .list_waitlist_by_lot(&lot_id.to_string())
         .await
         .unwrap_or_default();
+    let now = Utc::now();
 
-    // FIFO: take the first entry still Waiting (list is sorted by created_at asc).
-    let Some(next) = entries.iter().find(|e| e.status == WaitlistStatus::Waiting) else {
-        return false;
-    };
+    // FIFO: the list is sorted by created_at asc.
+    for next in entries
+        .iter()
+        .filter(|e| e.status == WaitlistStatus::Waiting)
+    {
+        let mut offered = next.clone();
+        if let Some(window) = entry_window(next) {
+            if window.end <= now {
+                continue;
+            }
+            let held = held_slots(&entries, &window, None, now);
+            let free = match super::availability::free_slots(
+                &state.db,
+                &lot_id.to_string(),
+                &window,
+            )
+            .await
+            {
+                Ok(free) => free,
+                Err(e) => {
+                    tracing::warn!(
+                        lot_id = %lot_id,
+                        "promote_next_waitlist_offer: slot lookup failed: {e}"
+                    );
+                    return false;
+                }
+            };
+            let Some(slot) = free.into_iter().find(|slot| !held.contains(&slot.id)) else {
+                continue;
+            };
+            offered.offered_slot_id = Some(slot.id);
+        } else if !include_open {
+            continue;
+        }
 
-    let now = Utc::now();
-    let mut offered = next.clone();
-    offered.status = WaitlistStatus::Offered;
-    offered.notified_at = Some(now);
-    offered.offer_expires_at = Some(now + Duration::minutes(claim_window_minutes));
+        offered.status = WaitlistStatus::Offered;
+        offered.notified_at = Some(now);
+        offered.offer_expires_at = Some(now + Duration::minutes(claim_window_minutes));
 
-    if let Err(e) = state.db.save_waitlist_entry(&offered).await {
-        tracing::warn!(entry_id = %offered.id, "promote_next_waitlist_offer: save failed: {e}");
-        return false;
+        if let Err(e) = state.db.save_waitlist_entry(&offered).await {
+            tracing::warn!(entry_id = %offered.id, "promote_next_waitlist_offer: save failed: {e}");
+            return false;
+        }
+        notify_offer(state, &offered, claim_window_minutes).await;
+
+        tracing::info!(
+            entry_id = %offered.id,
+            user_id = %offered.user_id,
+            lot_id = %lot_id,
+            expires_in_minutes = claim_window_minutes,
+            "Waitlist offer promoted (FIFO)"
+        );
+        return true;
     }
+    false
+}
 
+/// Tell the user about `offered` in-app and, with `mod-email`, by email.
+async fn notify_offer(state: &AppState, offered: &WaitlistEntry, claim_window_minutes: i64) {
     let notification = Notification {
         id: Uuid::new_v4(),
         user_id: offered.user_id,
@@ -564,24 +715,92 @@ pub async fn promote_next_waitlist_offer(
             "A spot has opened up. You have {claim_window_minutes} minutes to claim it."
         ),
         data: Some(serde_json::json!({
-            "lot_id": lot_id,
+            "lot_id": offered.lot_id,
             "entry_id": offered.id,
             "expires_at": offered.offer_expires_at,
+            "slot_id": offered.offered_slot_id,
         })),
         read: false,
-        created_at: now,
+        created_at: Utc::now(),
     };
     let _ = state.db.save_notification(&notification).await;
 
-    tracing::info!(
-        entry_id = %offered.id,
-        user_id = %offered.user_id,
-        lot_id = %lot_id,
-        expires_in_minutes = claim_window_minutes,
-        "Waitlist offer promoted (FIFO)"
-    );
+    #[cfg(feature = "mod-email")]
+    if let Ok(Some(user)) = state.db.get_user(&offered.user_id.to_string()).await {
+        let lot_name = state
+            .db
+            .get_parking_lot(&offered.lot_id.to_string())
+            .await
+            .ok()
+            .flatten()
+            .map_or_else(|| offered.lot_id.to_string(), |lot| lot.name);
+        let html = crate::email::build_waitlist_slot_available_email(
+            &user.name,
+            &lot_name,
+            &state.config.organization_name,
+            claim_window_minutes,
+        );
+        let subject = format!("Parking slot available at {lot_name} — ParkHub");
+        if let Err(e) = crate::email_queue::enqueue(&state.db, &user.email, &subject, &html).await {
+            tracing::warn!(entry_id = %offered.id, "Failed to queue waitlist offer email: {e}");
+        }
+    }
+}
 
-    true
+/// Offer free slots to waitlist entries with a window and expire the ones
+/// whose window has ended.
+///
+/// Slots freed other than by a cancellation or no-show release — a booking
+/// shortened, a slot back from maintenance, a new slot — only reach the
+/// waitlist through this matcher. Called by the background scheduler after
+/// [`expire_outstanding_offers`].
+pub async fn match_waitlist_windows(state: &AppState) -> anyhow::Result<()> {
+    let now = Utc::now();
+    let mut offered_count = 0u32;
+    for lot in state.db.list_parking_lots().await? {
+        let entries = state
+            .db
+            .list_waitlist_by_lot(&lot.id.to_string())
+            .await
+            .unwrap_or_default();
+
+        let mut waiting = 0;
+        for entry in entries
+            .iter()
+            .filter(|e| e.status == WaitlistStatus::Waiting)
+        {
+            match entry_window(entry) {
+                Some(window) if window.end <= now => {
+                    let mut expired = entry.clone();
+                    expired.status = WaitlistStatus::Expired;
+                    if let Err(e) = state.db.save_waitlist_entry(&expired).await {
+                        tracing::warn!(
+                            entry_id = %entry.id,
+                            "match_waitlist_windows: failed to expire entry: {e}"
+                        );
+                    }
+                }
+                Some(_) => waiting += 1,
+                None => {}
+            }
+        }
+        if waiting == 0 {
+            continue;
+        }
+
+        let claim_window = lot_claim_window_minutes(state, &lot.id.to_string()).await;
+        for _ in 0..waiting {
+            if !offer_next(state, lot.id, claim_window, false).await {
+                break;
+            }
+            offered_count += 1;
+        }
+    }
+
+    if offered_count > 0 {
+        tracing::info!("match_waitlist_windows: offered {offered_count} slot(s)");
+    }
+    Ok(())
 }
 
 /// Expire all outstanding offers whose `offer_expires_at` has passed, then
@@ -663,7 +882,7 @@ mod tests {
             db,
             mdns: None,
             scheduler: None,
-            ws_events: crate::api::ws::EventBroadcaster::new(),
+            ws_events: crate::api::events::EventBroadcaster::new(),
             fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
             revocation_store: crate::jwt::TokenRevocationList::new(),
         }));
@@ -680,6 +899,9 @@ mod tests {
             status: WaitlistStatus::Waiting,
             offer_expires_at: None,
             accepted_booking_id: None,
+            start_time: None,
+            end_time: None,
+            offered_slot_id: None,
         }
     }
 
@@ -718,6 +940,45 @@ mod tests {
         }
     }
 
+    fn timed_entry(
+        lot_id: Uuid,
+        user_id: Uuid,
+        age_minutes: i64,
+        hours_ahead: i64,
+    ) -> WaitlistEntry {
+        let start = Utc::now() + Duration::hours(hours_ahead);
+        WaitlistEntry {
+            start_time: Some(start),
+            end_time: Some(start + Duration::hours(2)),
+            ..waiting_entry(lot_id, user_id, age_minutes)
+        }
+    }
+
+    async fn save_slot(state: &AppState, lot_id: Uuid) -> Uuid {
+        let slot = parkhub_common::ParkingSlot {
+            id: Uuid::new_v4(),
+            lot_id,
+            floor_id: Uuid::new_v4(),
+            slot_number: 1,
+            row: 1,
+            column: 1,
+            slot_type: parkhub_common::SlotType::Standard,
+            status: SlotStatus::Available,
+            current_booking: None,
+            features: Vec::new(),
+            position: parkhub_common::SlotPosition {
+                x: 0.0,
+                y: 0.0,
+                width: 2.5,
+                height: 5.0,
+                rotation: 0.0,
+            },
+            is_accessible: false,
+        };
+        state.db.save_parking_slot(&slot).await.unwrap();
+        slot.id
+    }
+
     // ── promote_next_waitlist_offer ────────────────────────────────────────
 
     #[tokio::test]
@@ -1017,4 +1278,89 @@ mod tests {
         let mins = lot_claim_window_minutes(&guard, lot_id).await;
         assert_eq!(mins, 20);
     }
+
+    // ── match_waitlist_windows ─────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn timed_entry_is_offered_only_with_a_free_slot_and_holds_it() {
+        let (state, _dir) = make_test_state();
+        let guard = state.read().await;
+        let lot_id = Uuid::new_v4();
+        guard
+            .db
+            .save_parking_lot(&make_test_lot(lot_id))
+            .await
+            .unwrap();
+        let first = timed_entry(lot_id, Uuid::new_v4(), 20, 1);
+        let second = timed_entry(lot_id, Uuid::new_v4(), 10, 1);
+        guard.db.save_waitlist_entry(&first).await.unwrap();
+        guard.db.save_waitlist_entry(&second).await.unwrap();
+
+        // No slot in the lot: nothing to offer.
+        assert!(!promote_next_waitlist_offer(&guard, lot_id, 15).await);
+
+        let slot_id = save_slot(&guard, lot_id).await;
+        match_waitlist_windows(&guard).await.unwrap();
+
+        let first = guard
+            .db
+            .get_waitlist_entry(&first.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.status, WaitlistStatus::Offered);
+        assert_eq!(first.offered_slot_id, Some(slot_id));
+
+        // The only slot is held for the first entry, so the second waits.
+        let second = guard
+            .db
+            .get_waitlist_entry(&second.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.status, WaitlistStatus::Waiting);
+
+        let range = entry_window(&first).unwrap();
+        let held = slots_held_for_others(&guard, lot_id, &range, second.user_id).await;
+        assert!(held.contains(&slot_id));
+        let own = slots_held_for_others(&guard, lot_id, &range, first.user_id).await;
+        assert!(
+            own.is_empty(),
+            "an offer does not hold the slot against its own user"
+        );
+    }
+
+    #[tokio::test]
+    async fn match_expires_entries_whose_window_has_ended() {
+        let (state, _dir) = make_test_state();
+        let guard = state.read().await;
+        let lot_id = Uuid::new_v4();
+        guard
+            .db
+            .save_parking_lot(&make_test_lot(lot_id))
+            .await
+            .unwrap();
+        let ended = timed_entry(lot_id, Uuid::new_v4(), 300, -4);
+        let open = waiting_entry(lot_id, Uuid::new_v4(), 10);
+        guard.db.save_waitlist_entry(&ended).await.unwrap();
+        guard.db.save_waitlist_entry(&open).await.unwrap();
+
+        match_waitlist_windows(&guard).await.unwrap();
+
+        let ended = guard
+            .db
+            .get_waitlist_entry(&ended.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(ended.status, WaitlistStatus::Expired);
+        // Entries without a window are only offered when a booking is cancelled.
+        let open = guard
+            .db
+            .get_waitlist_entry(&open.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(open.status, WaitlistStatus::Waiting);
+    }
 }
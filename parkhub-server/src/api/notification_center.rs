@@ -23,7 +23,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{AuthUser, SharedState};
 
@@ -203,7 +203,7 @@ pub async fn list_center_notifications(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list notifications",
                 )),
             );
@@ -305,7 +305,7 @@ pub async fn unread_count(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to count notifications",
                 )),
             )
@@ -335,7 +335,10 @@ pub async fn delete_notification(
             tracing::error!("Failed to list notifications: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -344,7 +347,10 @@ pub async fn delete_notification(
     if !owns {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Notification not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Notification not found",
+            )),
         );
     }
 
@@ -352,14 +358,17 @@ pub async fn delete_notification(
         Ok(true) => (StatusCode::OK, Json(ApiResponse::success(()))),
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Notification not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Notification not found",
+            )),
         ),
         Err(e) => {
             tracing::error!("Failed to delete notification: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to delete notification",
                 )),
             )
@@ -388,7 +397,7 @@ pub async fn mark_all_read(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to mark all read",
                 )),
             );
@@ -103,6 +103,7 @@ mod tests {
             whatsapp_booking_reminder: true,
             whatsapp_booking_cancelled: true,
             phone_number: Some("+491234567890".to_string()),
+            email_new_login: true,
         }
     }
 
@@ -6,8 +6,8 @@ use axum::{
     http::StatusCode,
 };
 
-use parkhub_common::ApiResponse;
 use parkhub_common::models::Notification;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{AuthUser, SharedState};
 
@@ -39,7 +39,7 @@ pub async fn list_notifications(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list notifications",
                 )),
             )
@@ -72,7 +72,10 @@ pub async fn mark_notification_read(
             tracing::error!("Failed to list notifications: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -81,7 +84,10 @@ pub async fn mark_notification_read(
     if !owns {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Notification not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Notification not found",
+            )),
         );
     }
 
@@ -89,14 +95,17 @@ pub async fn mark_notification_read(
         Ok(true) => (StatusCode::OK, Json(ApiResponse::success(()))),
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Notification not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Notification not found",
+            )),
         ),
         Err(e) => {
             tracing::error!("Failed to mark notification read: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to mark notification read",
                 )),
             )
@@ -127,7 +136,7 @@ pub async fn mark_all_notifications_read(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to mark notifications read",
                 )),
             );
@@ -20,13 +20,16 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, AuthTokens, LoginResponse, User, UserPreferences, UserRole};
+use parkhub_common::{
+    ApiResponse, AuthTokens, ErrorCode, LoginResponse, User, UserPreferences, UserRole,
+};
 
+use crate::AppState;
 use crate::audit::{AuditEntry, AuditEventType};
 use crate::db::Session;
 use crate::metrics;
 
-use super::{SharedState, generate_access_token, hash_password_simple};
+use super::{SharedState, generate_access_token, hash_password_simple, session_tokens};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Configuration
@@ -266,7 +269,7 @@ pub async fn oauth_google_redirect() -> Response {
         return (
             StatusCode::SERVICE_UNAVAILABLE,
             Json(ApiResponse::<()>::error(
-                "OAUTH_NOT_CONFIGURED",
+                ErrorCode::OauthNotConfigured,
                 "Google OAuth is not configured",
             )),
         )
@@ -303,7 +306,7 @@ pub async fn oauth_google_callback(
         return (
             StatusCode::SERVICE_UNAVAILABLE,
             Json(ApiResponse::<()>::error(
-                "OAUTH_NOT_CONFIGURED",
+                ErrorCode::OauthNotConfigured,
                 "Google OAuth is not configured",
             )),
         )
@@ -410,7 +413,7 @@ pub async fn oauth_github_redirect() -> Response {
         return (
             StatusCode::SERVICE_UNAVAILABLE,
             Json(ApiResponse::<()>::error(
-                "OAUTH_NOT_CONFIGURED",
+                ErrorCode::OauthNotConfigured,
                 "GitHub OAuth is not configured",
             )),
         )
@@ -447,7 +450,7 @@ pub async fn oauth_github_callback(
         return (
             StatusCode::SERVICE_UNAVAILABLE,
             Json(ApiResponse::<()>::error(
-                "OAUTH_NOT_CONFIGURED",
+                ErrorCode::OauthNotConfigured,
                 "GitHub OAuth is not configured",
             )),
         )
@@ -587,124 +590,167 @@ async fn complete_oauth_login(
     let state_guard = state.read().await;
 
     // Try to find existing user by email
-    let user = match state_guard.db.get_user_by_email(email).await {
-        Ok(Some(existing)) => {
-            // Link OAuth provider info (store as JSON in settings for now)
-            let key = format!("oauth:{}:{}", provider.provider, existing.id);
-            let val = serde_json::to_string(provider).unwrap_or_default();
-            let _ = state_guard.db.set_setting(&key, &val).await;
-
-            AuditEntry::new(AuditEventType::LoginSuccess)
-                .user(existing.id, &existing.username)
-                .detail(&format!("oauth:{}", provider.provider))
-                .log();
-            metrics::record_auth_event("login", true);
-
-            existing
-        }
-        _ => {
-            // Enforce self-registration gate before creating a new account.
-            if !state_guard.config.allow_self_registration {
-                return (
-                    StatusCode::FORBIDDEN,
-                    Json(ApiResponse::<()>::error(
-                        "REGISTRATION_DISABLED",
-                        "Self-registration is disabled. Contact an administrator.",
-                    )),
-                )
-                    .into_response();
+    let user =
+        match state_guard.db.get_user_by_email(email).await {
+            Ok(Some(existing)) => {
+                // Link OAuth provider info (store as JSON in settings for now)
+                let key = format!("oauth:{}:{}", provider.provider, existing.id);
+                let val = serde_json::to_string(provider).unwrap_or_default();
+                let _ = state_guard.db.set_setting(&key, &val).await;
+
+                AuditEntry::new(AuditEventType::LoginSuccess)
+                    .user(existing.id, &existing.username)
+                    .detail(&format!("oauth:{}", provider.provider))
+                    .log();
+                metrics::record_auth_event("login", true);
+
+                existing
             }
+            _ => {
+                // Enforce self-registration gate before creating a new account.
+                if !state_guard.config.allow_self_registration {
+                    return (
+                        StatusCode::FORBIDDEN,
+                        Json(ApiResponse::<()>::error(
+                            ErrorCode::RegistrationDisabled,
+                            "Self-registration is disabled. Contact an administrator.",
+                        )),
+                    )
+                        .into_response();
+                }
 
-            // Create new user
-            let username = email.split('@').next().unwrap_or("user").to_string();
-
-            // Deduplicate username (cap at 99)
-            let mut final_username = username.clone();
-            let mut counter = 1u32;
-            while counter <= 99
-                && matches!(
-                    state_guard.db.get_user_by_username(&final_username).await,
-                    Ok(Some(_))
-                )
-            {
-                final_username = format!("{username}{counter}");
-                counter += 1;
-            }
-            if counter > 99 {
-                tracing::warn!("Username exhausted for OAuth email prefix: {username}");
-                return oauth_error_response("Too many accounts with this email prefix");
+                let new_user =
+                    match create_external_user(&state_guard, email, name, picture, UserRole::User)
+                        .await
+                    {
+                        Ok(user) => user,
+                        Err(response) => return response,
+                    };
+
+                // Store OAuth provider info
+                let key = format!("oauth:{}:{}", provider.provider, new_user.id);
+                let val = serde_json::to_string(provider).unwrap_or_default();
+                let _ = state_guard.db.set_setting(&key, &val).await;
+
+                let audit = AuditEntry::new(AuditEventType::UserCreated)
+                    .user(new_user.id, &new_user.username)
+                    .detail(&format!("oauth:{}", provider.provider))
+                    .log();
+                audit.persist(&state_guard.db).await;
+                metrics::record_auth_event("register", true);
+
+                new_user
             }
+        };
 
-            // Generate a random password hash (user logs in via OAuth, not password)
-            let random_pw = Uuid::new_v4().to_string();
-            let password_hash = match hash_password_simple(&random_pw).await {
-                Ok(h) => h,
-                Err(e) => {
-                    tracing::error!("Failed to hash OAuth placeholder password: {e}");
-                    return oauth_error_response("Internal server error");
-                }
-            };
-
-            let now = Utc::now();
-            let new_user = User {
-                id: Uuid::new_v4(),
-                username: final_username,
-                email: email.to_string(),
-                password_hash,
-                name: name.to_string(),
-                picture: picture.map(String::from),
-                phone: None,
-                role: UserRole::User,
-                created_at: now,
-                updated_at: now,
-                last_login: Some(now),
-                preferences: UserPreferences::default(),
-                is_active: true,
-                credits_balance: 40,
-                credits_monthly_quota: 40,
-                credits_last_refilled: Some(now),
-                // SAFETY(T-1731): OAuth callback provisions a new account for
-                // an unauthenticated caller; tenant binding is a separate
-                // (future) feature.  Matches the public-register path.
-                tenant_id: None,
-                accessibility_needs: None,
-                cost_center: None,
-                department: None,
-                settings: None,
-            };
-
-            if let Err(e) = state_guard.db.save_user(&new_user).await {
-                tracing::error!("Failed to save OAuth user: {e}");
-                return oauth_error_response("Failed to create account");
-            }
+    let response = match login_response(&state_guard, user).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::error!("Failed to save OAuth session: {e}");
+            return oauth_error_response("Failed to create session");
+        }
+    };
+    drop(state_guard);
+    response
+}
 
-            // Store OAuth provider info
-            let key = format!("oauth:{}:{}", provider.provider, new_user.id);
-            let val = serde_json::to_string(provider).unwrap_or_default();
-            let _ = state_guard.db.set_setting(&key, &val).await;
+/// Create the local account for a first-time external login, with a unique
+/// username derived from the email and an unusable random password.
+pub(super) async fn create_external_user(
+    state: &AppState,
+    email: &str,
+    name: &str,
+    picture: Option<&str>,
+    role: UserRole,
+) -> Result<User, Response> {
+    // One account per email: a second one would take over the first's
+    // email login and password resets
+    match state.db.get_user_by_email(email).await {
+        Ok(None) => {}
+        Ok(Some(_)) => return Err(email_exists_response()),
+        Err(e) => {
+            tracing::error!("Failed to look up external user email: {e}");
+            return Err(oauth_error_response("Internal server error"));
+        }
+    }
 
-            let audit = AuditEntry::new(AuditEventType::UserCreated)
-                .user(new_user.id, &new_user.username)
-                .detail(&format!("oauth:{}", provider.provider))
-                .log();
-            audit.persist(&state_guard.db).await;
-            metrics::record_auth_event("register", true);
+    let username = email.split('@').next().unwrap_or("user").to_string();
 
-            new_user
+    // Deduplicate username (cap at 99)
+    let mut final_username = username.clone();
+    let mut counter = 1u32;
+    while counter <= 99
+        && matches!(
+            state.db.get_user_by_username(&final_username).await,
+            Ok(Some(_))
+        )
+    {
+        final_username = format!("{username}{counter}");
+        counter += 1;
+    }
+    if counter > 99 {
+        tracing::warn!("Username exhausted for OAuth email prefix: {username}");
+        return Err(oauth_error_response(
+            "Too many accounts with this email prefix",
+        ));
+    }
+
+    // Generate a random password hash (user logs in via OAuth, not password)
+    let random_pw = Uuid::new_v4().to_string();
+    let password_hash = match hash_password_simple(&random_pw).await {
+        Ok(h) => h,
+        Err(e) => {
+            tracing::error!("Failed to hash OAuth placeholder password: {e}");
+            return Err(oauth_error_response("Internal server error"));
         }
     };
 
-    // Create session
-    let session_hours = i64::from(state_guard.config.session_timeout_minutes).max(60) / 60;
-    let role_str = format!("{:?}", user.role).to_lowercase();
-    let session = Session::new(user.id, session_hours, &user.username, &role_str);
-    let access_token = generate_access_token();
+    let now = Utc::now();
+    let new_user = User {
+        id: Uuid::new_v4(),
+        username: final_username,
+        email: email.to_string(),
+        password_hash,
+        name: name.to_string(),
+        picture: picture.map(String::from),
+        phone: None,
+        role,
+        created_at: now,
+        updated_at: now,
+        last_login: Some(now),
+        preferences: UserPreferences::default(),
+        is_active: true,
+        credits_balance: 40,
+        credits_monthly_quota: 40,
+        credits_last_refilled: Some(now),
+        // SAFETY(T-1731): External login provisions a new account for
+        // an unauthenticated caller; tenant binding is a separate
+        // (future) feature.  Matches the public-register path.
+        tenant_id: None,
+        accessibility_needs: None,
+        cost_center: None,
+        department: None,
+        settings: None,
+        must_change_password: false,
+    };
 
-    if let Err(e) = state_guard.db.save_session(&access_token, &session).await {
-        tracing::error!("Failed to save OAuth session: {e}");
-        return oauth_error_response("Failed to create session");
+    if let Err(e) = state.db.save_user(&new_user).await {
+        tracing::error!("Failed to save OAuth user: {e}");
+        return Err(oauth_error_response("Failed to create account"));
     }
-    drop(state_guard);
+    Ok(new_user)
+}
+
+/// Start a session for `user` and build the login response, with the auth
+/// cookie set. Shared by the social login and OIDC callbacks.
+pub(super) async fn login_response(state: &AppState, user: User) -> anyhow::Result<Response> {
+    if !session_tokens::make_room_for_session(state, user.id).await? {
+        return Ok(super::auth::session_limit_response());
+    }
+    let session_hours = i64::from(state.config.session_lifetime_hours).max(1);
+    let role_str = format!("{:?}", user.role).to_lowercase();
+    let session = Session::new(user.id, session_hours, &user.username, &role_str);
+    let access_token = session_tokens::start_session(&state.db, &session).await?;
 
     // Build auth cookie
     let cookie_max_age = session_hours * 3600;
@@ -728,14 +774,27 @@ async fn complete_oauth_login(
     if let Ok(hv) = header::HeaderValue::from_str(&cookie) {
         resp.headers_mut().insert(header::SET_COOKIE, hv);
     }
-    resp
+    Ok(resp)
 }
 
 /// Standard error response for OAuth failures.
 fn oauth_error_response(message: &str) -> Response {
     (
         StatusCode::BAD_REQUEST,
-        Json(ApiResponse::<()>::error("OAUTH_ERROR", message)),
+        Json(ApiResponse::<()>::error(ErrorCode::OauthError, message)),
+    )
+        .into_response()
+}
+
+/// 409 for an external login whose email already belongs to an account it
+/// may not sign in to.
+pub(super) fn email_exists_response() -> Response {
+    (
+        StatusCode::CONFLICT,
+        Json(ApiResponse::<()>::error(
+            ErrorCode::EmailExists,
+            "An account with this email already exists. Sign in to it directly.",
+        )),
     )
         .into_response()
 }
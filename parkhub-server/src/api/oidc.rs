@@ -0,0 +1,834 @@
+//! OpenID Connect login against a configurable identity provider.
+//!
+//! Configured through the `[oidc]` section of the server config
+//! ([`OidcConfig`]). Provider endpoints come from the issuer's discovery
+//! document; the authorization-code flow uses PKCE, and the ID token is
+//! verified against the provider's JWKS (signature, issuer, audience,
+//! expiry and nonce) before any account is touched.
+//!
+//! Users are matched by their OIDC subject, then by verified email, and are
+//! created on first login when `auto_provision` is on. Group claims are
+//! mapped to [`UserRole`] through `role_mappings`.
+//!
+//! Endpoints:
+//! - `GET /api/v1/auth/oidc/start`     — redirect to the provider's login page
+//! - `GET /api/v1/auth/oidc/callback`  — exchange code, verify ID token, sign in
+
+// AppState read guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::HashMap;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Redirect, Response},
+};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::Utc;
+use jsonwebtoken::jwk::{Jwk, JwkSet};
+use jsonwebtoken::{Algorithm, AlgorithmFamily, DecodingKey, Validation};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use parkhub_common::{ApiResponse, ErrorCode, User, UserRole};
+
+use crate::AppState;
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::config::OidcConfig;
+use crate::metrics;
+
+use super::oauth::{create_external_user, email_exists_response, login_response};
+use super::{SharedState, generate_access_token};
+
+/// Cookie carrying the pending flow (`state.nonce.code_verifier`).
+const OIDC_FLOW_COOKIE: &str = "oidc_flow";
+/// Lifetime of the flow cookie in seconds (10 minutes).
+const OIDC_FLOW_MAX_AGE: u32 = 600;
+const CALLBACK_PATH: &str = "/api/v1/auth/oidc/callback";
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The subset of the discovery document ParkHub uses.
+#[derive(Debug, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+/// Query parameters returned by the provider callback.
+#[derive(Debug, Deserialize)]
+pub struct OidcCallbackParams {
+    #[serde(default)]
+    pub code: Option<String>,
+    #[serde(default)]
+    pub state: Option<String>,
+    /// Set instead of `code` when the provider refused the login
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+/// Verified ID-token claims.
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    #[serde(default)]
+    nonce: Option<String>,
+    #[serde(default)]
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: Option<bool>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    picture: Option<String>,
+    /// Everything else, including the configured groups claim
+    #[serde(flatten)]
+    extra: HashMap<String, serde_json::Value>,
+}
+
+impl IdTokenClaims {
+    /// Groups from `claim`, which providers send as a list or a single string.
+    fn groups(&self, claim: &str) -> Vec<String> {
+        match self.extra.get(claim) {
+            Some(serde_json::Value::Array(values)) => values
+                .iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect(),
+            Some(serde_json::Value::String(group)) => vec![group.clone()],
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// State kept in the flow cookie between `start` and `callback`.
+struct PendingFlow {
+    state: String,
+    nonce: String,
+    code_verifier: String,
+}
+
+impl PendingFlow {
+    fn new() -> Self {
+        Self {
+            state: generate_access_token(),
+            nonce: generate_access_token(),
+            code_verifier: generate_access_token(),
+        }
+    }
+
+    fn cookie(&self) -> String {
+        let app_url = std::env::var("APP_URL").ok();
+        let secure_flag = super::auth::auth_cookie_secure_flag(app_url.as_deref());
+        let mut cookie = format!(
+            "{OIDC_FLOW_COOKIE}={}.{}.{}; HttpOnly; SameSite=Lax; Path=/api/v1/auth/oidc; Max-Age={OIDC_FLOW_MAX_AGE}",
+            self.state, self.nonce, self.code_verifier
+        );
+        if secure_flag {
+            cookie.push_str("; Secure");
+        }
+        cookie
+    }
+
+    fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let value = headers
+            .get(header::COOKIE)?
+            .to_str()
+            .ok()?
+            .split(';')
+            .find_map(|c| c.trim().strip_prefix(&format!("{OIDC_FLOW_COOKIE}=")))?;
+        let mut parts = value.split('.');
+        let flow = Self {
+            state: parts.next()?.to_string(),
+            nonce: parts.next()?.to_string(),
+            code_verifier: parts.next()?.to_string(),
+        };
+        parts.next().is_none().then_some(flow)
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Protocol helpers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// PKCE `S256` challenge for `verifier`.
+fn code_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+fn redirect_uri(config: &OidcConfig) -> String {
+    let base = if config.redirect_base_url.is_empty() {
+        std::env::var("APP_URL").unwrap_or_else(|_| "http://localhost:3000".to_string())
+    } else {
+        config.redirect_base_url.clone()
+    };
+    format!("{}{CALLBACK_PATH}", base.trim_end_matches('/'))
+}
+
+fn urlencoding(s: &str) -> String {
+    url::form_urlencoded::byte_serialize(s.as_bytes()).collect()
+}
+
+/// Build the provider's authorization URL for `flow`.
+fn authorization_url(
+    config: &OidcConfig,
+    metadata: &ProviderMetadata,
+    flow: &PendingFlow,
+) -> String {
+    let scope = std::iter::once("openid")
+        .chain(
+            config
+                .scopes
+                .iter()
+                .map(String::as_str)
+                .filter(|s| *s != "openid"),
+        )
+        .collect::<Vec<_>>()
+        .join(" ");
+    let separator = if metadata.authorization_endpoint.contains('?') {
+        '&'
+    } else {
+        '?'
+    };
+    format!(
+        "{}{separator}response_type=code&client_id={}&redirect_uri={}&scope={}&state={}&nonce={}&code_challenge={}&code_challenge_method=S256",
+        metadata.authorization_endpoint,
+        urlencoding(&config.client_id),
+        urlencoding(&redirect_uri(config)),
+        urlencoding(&scope),
+        flow.state,
+        flow.nonce,
+        code_challenge(&flow.code_verifier),
+    )
+}
+
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(15))
+        .build()
+        .unwrap_or_default()
+}
+
+/// Fetch the discovery document and check it belongs to the configured issuer.
+async fn discover(client: &reqwest::Client, issuer_url: &str) -> anyhow::Result<ProviderMetadata> {
+    let issuer = issuer_url.trim_end_matches('/');
+    let metadata: ProviderMetadata = client
+        .get(format!("{issuer}/.well-known/openid-configuration"))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    if metadata.issuer.trim_end_matches('/') != issuer {
+        anyhow::bail!("discovery document is for issuer {}", metadata.issuer);
+    }
+    Ok(metadata)
+}
+
+/// Algorithms an ID token signed with `jwk` may use.
+///
+/// Taken from the key's own `alg` when it names one, otherwise every
+/// asymmetric algorithm of the key's family — never from the token header,
+/// which the sender controls. Symmetric keys must name their algorithm.
+fn allowed_algorithms(jwk: &Jwk, key: &DecodingKey) -> anyhow::Result<Vec<Algorithm>> {
+    if let Some(alg) = jwk.common.key_algorithm {
+        return Ok(vec![alg.to_string().parse()?]);
+    }
+    Ok(match key.family() {
+        AlgorithmFamily::Rsa => vec![
+            Algorithm::RS256,
+            Algorithm::RS384,
+            Algorithm::RS512,
+            Algorithm::PS256,
+            Algorithm::PS384,
+            Algorithm::PS512,
+        ],
+        AlgorithmFamily::Ec => vec![Algorithm::ES256, Algorithm::ES384],
+        AlgorithmFamily::Ed => vec![Algorithm::EdDSA],
+        AlgorithmFamily::Hmac => anyhow::bail!("symmetric signing key does not name its algorithm"),
+    })
+}
+
+/// Verify `id_token` against the provider's keys and the pending flow.
+fn verify_id_token(
+    id_token: &str,
+    jwks: &JwkSet,
+    issuer: &str,
+    client_id: &str,
+    nonce: &str,
+) -> anyhow::Result<IdTokenClaims> {
+    let header = jsonwebtoken::decode_header(id_token)?;
+    let jwk = match header.kid.as_deref() {
+        Some(kid) => jwks.find(kid),
+        None if jwks.keys.len() == 1 => jwks.keys.first(),
+        None => None,
+    }
+    .ok_or_else(|| anyhow::anyhow!("no matching signing key in JWKS"))?;
+    let key = DecodingKey::from_jwk(jwk)?;
+
+    let mut validation = Validation::default();
+    validation.algorithms = allowed_algorithms(jwk, &key)?;
+    validation.set_issuer(&[issuer]);
+    validation.set_audience(&[client_id]);
+    validation.set_required_spec_claims(&["exp", "iss", "aud", "sub"]);
+    let claims: IdTokenClaims = jsonwebtoken::decode(id_token, &key, &validation)?.claims;
+
+    if claims.nonce.as_deref() != Some(nonce) {
+        anyhow::bail!("ID token nonce does not match the login request");
+    }
+    Ok(claims)
+}
+
+const fn role_rank(role: &UserRole) -> u8 {
+    match role {
+        UserRole::User => 0,
+        UserRole::Premium => 1,
+        UserRole::Reporter => 2,
+        UserRole::Admin => 3,
+        UserRole::SuperAdmin => 4,
+    }
+}
+
+/// Highest role mapped from `groups`, or `None` if no group is mapped.
+fn role_for_groups(config: &OidcConfig, groups: &[String]) -> Option<UserRole> {
+    groups
+        .iter()
+        .filter_map(|g| config.role_mappings.get(g))
+        .max_by_key(|role| role_rank(role))
+        .cloned()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `GET /api/v1/auth/oidc/start` — redirect to the identity provider.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/start",
+    tag = "OAuth",
+    summary = "Initiate OpenID Connect login",
+    responses(
+        (status = 302, description = "Redirect to the identity provider"),
+        (status = 502, description = "Provider discovery failed"),
+        (status = 503, description = "OIDC not configured"),
+    )
+)]
+pub async fn oidc_start(State(state): State<SharedState>) -> Response {
+    let config = state.read().await.config.oidc.clone();
+    if !config.is_enabled() {
+        return oidc_not_configured();
+    }
+
+    let metadata = match discover(&http_client(), &config.issuer_url).await {
+        Ok(m) => m,
+        Err(e) => {
+            tracing::error!("OIDC discovery failed: {e}");
+            return oidc_error_response(
+                StatusCode::BAD_GATEWAY,
+                "Identity provider is unreachable",
+            );
+        }
+    };
+
+    let flow = PendingFlow::new();
+    let mut resp =
+        Redirect::temporary(&authorization_url(&config, &metadata, &flow)).into_response();
+    if let Ok(hv) = header::HeaderValue::from_str(&flow.cookie()) {
+        resp.headers_mut().insert(header::SET_COOKIE, hv);
+    }
+    resp
+}
+
+/// `GET /api/v1/auth/oidc/callback` — finish the login started by `start`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/oidc/callback",
+    tag = "OAuth",
+    summary = "OpenID Connect callback",
+    description = "Exchanges the authorization code, verifies the ID token and signs the \
+        user in. Unknown users are created when `auto_provision` is enabled; group claims \
+        are mapped to roles through `role_mappings`.",
+    params(
+        ("code" = String, Query, description = "Authorization code"),
+        ("state" = String, Query, description = "CSRF state from the start redirect"),
+    ),
+    responses(
+        (status = 200, description = "Login successful"),
+        (status = 400, description = "Invalid state, code or ID token"),
+        (status = 403, description = "Account disabled or not provisioned"),
+        (status = 409, description = "Email already in use and not verified by the provider"),
+        (status = 503, description = "OIDC not configured"),
+    )
+)]
+pub async fn oidc_callback(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(params): Query<OidcCallbackParams>,
+) -> Response {
+    let config = state.read().await.config.oidc.clone();
+    if !config.is_enabled() {
+        return oidc_not_configured();
+    }
+    if let Some(error) = params.error {
+        tracing::warn!("OIDC provider returned error: {error}");
+        return oidc_error_response(StatusCode::BAD_REQUEST, "Login was cancelled or refused");
+    }
+
+    let Some(flow) = PendingFlow::from_headers(&headers)
+        .filter(|flow| params.state.as_deref() == Some(flow.state.as_str()))
+    else {
+        return oidc_error_response(
+            StatusCode::BAD_REQUEST,
+            "Invalid or missing CSRF state parameter",
+        );
+    };
+    let Some(code) = params.code else {
+        return oidc_error_response(StatusCode::BAD_REQUEST, "Missing authorization code");
+    };
+
+    let claims = match exchange_and_verify(&config, &flow, &code).await {
+        Ok(claims) => claims,
+        Err(e) => {
+            tracing::error!("OIDC login failed: {e}");
+            metrics::record_auth_event("login", false);
+            return oidc_error_response(StatusCode::BAD_REQUEST, "OIDC login failed");
+        }
+    };
+
+    let state_guard = state.read().await;
+    let user = match resolve_user(&state_guard, &config, &claims).await {
+        Ok(user) => user,
+        Err(response) => return response,
+    };
+
+    let mut response = match login_response(&state_guard, user).await {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::error!("Failed to save OIDC session: {e}");
+            return oidc_error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to create session",
+            );
+        }
+    };
+    // The flow is single-use.
+    if let Ok(hv) = header::HeaderValue::from_str(&format!(
+        "{OIDC_FLOW_COOKIE}=; HttpOnly; SameSite=Lax; Path=/api/v1/auth/oidc; Max-Age=0"
+    )) {
+        response.headers_mut().append(header::SET_COOKIE, hv);
+    }
+    response
+}
+
+/// Redeem `code` at the token endpoint and verify the returned ID token.
+async fn exchange_and_verify(
+    config: &OidcConfig,
+    flow: &PendingFlow,
+    code: &str,
+) -> anyhow::Result<IdTokenClaims> {
+    let client = http_client();
+    let metadata = discover(&client, &config.issuer_url).await?;
+
+    let redirect_uri = redirect_uri(config);
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", redirect_uri.as_str()),
+        ("client_id", config.client_id.as_str()),
+        ("code_verifier", flow.code_verifier.as_str()),
+    ];
+    if !config.client_secret.is_empty() {
+        form.push(("client_secret", config.client_secret.as_str()));
+    }
+    let tokens: TokenResponse = client
+        .post(&metadata.token_endpoint)
+        .header("Accept", "application/json")
+        .form(&form)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let jwks: JwkSet = client
+        .get(&metadata.jwks_uri)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    verify_id_token(
+        &tokens.id_token,
+        &jwks,
+        &metadata.issuer,
+        &config.client_id,
+        &flow.nonce,
+    )
+}
+
+/// Find or create the local user for `claims` and apply the role mapping.
+async fn resolve_user(
+    state: &AppState,
+    config: &OidcConfig,
+    claims: &IdTokenClaims,
+) -> Result<User, Response> {
+    let Some(email) = claims.email.as_deref().filter(|e| !e.is_empty()) else {
+        return Err(oidc_error_response(
+            StatusCode::BAD_REQUEST,
+            "The identity provider did not return an email address",
+        ));
+    };
+    let subject_key = format!("oidc_subject:{}", claims.sub);
+    let mapped_role = if config.role_mappings.is_empty() {
+        None
+    } else {
+        Some(role_for_groups(config, &claims.groups(&config.groups_claim)).unwrap_or_default())
+    };
+
+    // Linked subject first; fall back to the email only when the provider
+    // vouches for it, so an unverified address cannot take over an account.
+    let mut existing = match state.db.get_setting(&subject_key).await {
+        Ok(Some(user_id)) => state.db.get_user(&user_id).await.ok().flatten(),
+        _ => None,
+    };
+    if existing.is_none() {
+        match state.db.get_user_by_email(email).await.ok().flatten() {
+            Some(user) if claims.email_verified == Some(true) => existing = Some(user),
+            // Nor may it provision a second account under the same email
+            Some(_) => return Err(email_exists_response()),
+            None => {}
+        }
+    }
+
+    let user = if let Some(mut user) = existing {
+        if !user.is_active {
+            return Err(oidc_error_response(
+                StatusCode::FORBIDDEN,
+                "Account is disabled",
+            ));
+        }
+        if let Some(role) = mapped_role {
+            if role != user.role {
+                AuditEntry::new(AuditEventType::RoleChanged)
+                    .user(user.id, &user.username)
+                    .detail(&format!("oidc: {:?} -> {role:?}", user.role))
+                    .log()
+                    .persist(&state.db)
+                    .await;
+            }
+            user.role = role;
+        }
+        user.last_login = Some(Utc::now());
+        if let Err(e) = state.db.save_user(&user).await {
+            tracing::warn!("Failed to update OIDC user {}: {e}", user.id);
+        }
+
+        AuditEntry::new(AuditEventType::LoginSuccess)
+            .user(user.id, &user.username)
+            .detail("oidc")
+            .log();
+        metrics::record_auth_event("login", true);
+        user
+    } else {
+        if !config.auto_provision {
+            return Err(oidc_error_response(
+                StatusCode::FORBIDDEN,
+                "No ParkHub account exists for this identity. Contact an administrator.",
+            ));
+        }
+        let name = claims.name.as_deref().unwrap_or(email);
+        let user = create_external_user(
+            state,
+            email,
+            name,
+            claims.picture.as_deref(),
+            mapped_role.unwrap_or_default(),
+        )
+        .await?;
+
+        AuditEntry::new(AuditEventType::UserCreated)
+            .user(user.id, &user.username)
+            .detail("oidc")
+            .log()
+            .persist(&state.db)
+            .await;
+        metrics::record_auth_event("register", true);
+        user
+    };
+
+    if let Err(e) = state
+        .db
+        .set_setting(&subject_key, &user.id.to_string())
+        .await
+    {
+        tracing::warn!("Failed to link OIDC subject to user {}: {e}", user.id);
+    }
+    Ok(user)
+}
+
+fn oidc_not_configured() -> Response {
+    oidc_error_response(
+        StatusCode::SERVICE_UNAVAILABLE,
+        "OpenID Connect login is not configured",
+    )
+}
+
+fn oidc_error_response(status: StatusCode, message: &str) -> Response {
+    let code = if status == StatusCode::SERVICE_UNAVAILABLE {
+        ErrorCode::OidcNotConfigured
+    } else {
+        ErrorCode::OidcError
+    };
+    (status, Json(ApiResponse::<()>::error(code, message))).into_response()
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Tests
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonwebtoken::{EncodingKey, Header};
+
+    const SECRET: &[u8] = b"oidc-test-signing-key-0123456789abcdef";
+    const ISSUER: &str = "https://login.example.com";
+
+    fn jwks() -> JwkSet {
+        serde_json::from_value(serde_json::json!({
+            "keys": [{
+                "kty": "oct",
+                "kid": "k1",
+                "alg": "HS256",
+                "k": URL_SAFE_NO_PAD.encode(SECRET),
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn id_token(claims: &serde_json::Value) -> String {
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("k1".to_string());
+        jsonwebtoken::encode(&header, claims, &EncodingKey::from_secret(SECRET)).unwrap()
+    }
+
+    fn claims(overrides: &serde_json::Value) -> serde_json::Value {
+        let mut claims = serde_json::json!({
+            "iss": ISSUER,
+            "aud": "parkhub",
+            "sub": "user-123",
+            "exp": Utc::now().timestamp() + 300,
+            "nonce": "n0nce",
+            "email": "alice@example.com",
+            "groups": ["staff", "parking-admins"],
+        });
+        for (k, v) in overrides.as_object().unwrap() {
+            claims[k] = v.clone();
+        }
+        claims
+    }
+
+    #[test]
+    fn test_verify_id_token_accepts_valid_token() {
+        let token = id_token(&claims(&serde_json::json!({})));
+        let verified = verify_id_token(&token, &jwks(), ISSUER, "parkhub", "n0nce").unwrap();
+        assert_eq!(verified.sub, "user-123");
+        assert_eq!(verified.email.as_deref(), Some("alice@example.com"));
+        assert_eq!(verified.groups("groups"), ["staff", "parking-admins"]);
+    }
+
+    #[test]
+    fn test_verify_id_token_rejects_mismatches() {
+        let cases = [
+            serde_json::json!({ "iss": "https://evil.example.com" }),
+            serde_json::json!({ "aud": "someone-else" }),
+            serde_json::json!({ "nonce": "replayed" }),
+            serde_json::json!({ "exp": Utc::now().timestamp() - 3600 }),
+        ];
+        for overrides in cases {
+            let token = id_token(&claims(&overrides));
+            assert!(
+                verify_id_token(&token, &jwks(), ISSUER, "parkhub", "n0nce").is_err(),
+                "accepted token with {overrides}"
+            );
+        }
+
+        let mut header = Header::new(Algorithm::HS256);
+        header.kid = Some("k1".to_string());
+        let forged = jsonwebtoken::encode(
+            &header,
+            &claims(&serde_json::json!({})),
+            &EncodingKey::from_secret(b"wrong-key"),
+        )
+        .unwrap();
+        assert!(verify_id_token(&forged, &jwks(), ISSUER, "parkhub", "n0nce").is_err());
+    }
+
+    #[test]
+    fn test_verify_id_token_ignores_header_algorithm() {
+        // Signed with the right key, but not with the algorithm the key names
+        let mut header = Header::new(Algorithm::HS384);
+        header.kid = Some("k1".to_string());
+        let token = jsonwebtoken::encode(
+            &header,
+            &claims(&serde_json::json!({})),
+            &EncodingKey::from_secret(SECRET),
+        )
+        .unwrap();
+        assert!(verify_id_token(&token, &jwks(), ISSUER, "parkhub", "n0nce").is_err());
+
+        // A symmetric key that does not name its algorithm is not trusted
+        let unnamed: JwkSet = serde_json::from_value(serde_json::json!({
+            "keys": [{ "kty": "oct", "kid": "k1", "k": URL_SAFE_NO_PAD.encode(SECRET) }]
+        }))
+        .unwrap();
+        let token = id_token(&claims(&serde_json::json!({})));
+        assert!(verify_id_token(&token, &unnamed, ISSUER, "parkhub", "n0nce").is_err());
+    }
+
+    #[test]
+    fn test_role_for_groups_picks_highest() {
+        let mut config = OidcConfig::default();
+        config
+            .role_mappings
+            .insert("parking-admins".to_string(), UserRole::Admin);
+        config
+            .role_mappings
+            .insert("vip".to_string(), UserRole::Premium);
+
+        let groups = |g: &[&str]| g.iter().map(|s| (*s).to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            role_for_groups(&config, &groups(&["vip", "parking-admins"])),
+            Some(UserRole::Admin)
+        );
+        assert_eq!(
+            role_for_groups(&config, &groups(&["vip"])),
+            Some(UserRole::Premium)
+        );
+        assert_eq!(role_for_groups(&config, &groups(&["staff"])), None);
+    }
+
+    #[test]
+    fn test_groups_claim_accepts_single_string() {
+        let claims: IdTokenClaims = serde_json::from_value(serde_json::json!({
+            "sub": "u", "roles": "parking-admins"
+        }))
+        .unwrap();
+        assert_eq!(claims.groups("roles"), ["parking-admins"]);
+        assert!(claims.groups("groups").is_empty());
+    }
+
+    #[test]
+    fn test_authorization_url_uses_pkce() {
+        let config = OidcConfig {
+            issuer_url: ISSUER.to_string(),
+            client_id: "parkhub".to_string(),
+            redirect_base_url: "https://parking.example.com/".to_string(),
+            ..OidcConfig::default()
+        };
+        let metadata = ProviderMetadata {
+            issuer: ISSUER.to_string(),
+            authorization_endpoint: format!("{ISSUER}/authorize"),
+            token_endpoint: format!("{ISSUER}/token"),
+            jwks_uri: format!("{ISSUER}/jwks"),
+        };
+        let flow = PendingFlow::new();
+        let url = authorization_url(&config, &metadata, &flow);
+
+        assert!(url.starts_with("https://login.example.com/authorize?response_type=code"));
+        assert!(url.contains("scope=openid+email+profile"));
+        assert!(url.contains(&format!("state={}", flow.state)));
+        assert!(url.contains(&format!(
+            "code_challenge={}",
+            code_challenge(&flow.code_verifier)
+        )));
+        assert!(url.contains(&urlencoding(
+            "https://parking.example.com/api/v1/auth/oidc/callback"
+        )));
+        assert!(!url.contains(&flow.code_verifier));
+    }
+
+    #[test]
+    fn test_flow_cookie_roundtrip() {
+        let flow = PendingFlow::new();
+        let cookie = flow.cookie();
+        let value = cookie.split(';').next().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, format!("other=1; {value}").parse().unwrap());
+        let parsed = PendingFlow::from_headers(&headers).unwrap();
+        assert_eq!(parsed.state, flow.state);
+        assert_eq!(parsed.nonce, flow.nonce);
+        assert_eq!(parsed.code_verifier, flow.code_verifier);
+    }
+
+    fn test_state() -> (AppState, tempfile::TempDir) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let db = crate::db::Database::open(&crate::db::DatabaseConfig {
+            path: dir.path().to_path_buf(),
+            encryption_enabled: false,
+            passphrase: None,
+            create_if_missing: true,
+        })
+        .expect("open test db");
+        let state = AppState {
+            config: crate::config::ServerConfig::default(),
+            db,
+            mdns: None,
+            scheduler: None,
+            ws_events: crate::api::events::EventBroadcaster::new(),
+            fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
+            revocation_store: crate::jwt::TokenRevocationList::new(),
+        };
+        (state, dir)
+    }
+
+    #[tokio::test]
+    async fn test_unverified_email_of_existing_account_is_refused() {
+        let (state, _dir) = test_state();
+        let config = OidcConfig {
+            auto_provision: true,
+            ..OidcConfig::default()
+        };
+        let local =
+            create_external_user(&state, "alice@example.com", "Alice", None, UserRole::User)
+                .await
+                .unwrap();
+        let duplicate =
+            create_external_user(&state, "alice@example.com", "Alice", None, UserRole::User).await;
+        assert_eq!(duplicate.unwrap_err().status(), StatusCode::CONFLICT);
+
+        let unverified: IdTokenClaims =
+            serde_json::from_value(claims(&serde_json::json!({ "sub": "other-idp-user" })))
+                .unwrap();
+        let refused = resolve_user(&state, &config, &unverified).await;
+        assert_eq!(refused.unwrap_err().status(), StatusCode::CONFLICT);
+        let owner = state
+            .db
+            .get_user_by_email("alice@example.com")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(owner.id, local.id);
+
+        let verified: IdTokenClaims = serde_json::from_value(claims(
+            &serde_json::json!({ "sub": "other-idp-user", "email_verified": true }),
+        ))
+        .unwrap();
+        let linked = resolve_user(&state, &config, &verified).await.unwrap();
+        assert_eq!(linked.id, local.id);
+    }
+}
@@ -11,7 +11,7 @@ use axum::{
 };
 use chrono::{DateTime, Datelike, NaiveTime, Utc};
 
-use parkhub_common::{ApiResponse, DayHours, OperatingHours};
+use parkhub_common::{ApiResponse, DayHours, ErrorCode, OperatingHours};
 
 use super::SharedState;
 
@@ -167,13 +167,19 @@ pub async fn get_operating_hours(
         }
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking lot not found",
+            )),
         ),
         Err(e) => {
             tracing::error!(error = %e, "Failed to get parking lot");
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
@@ -221,7 +227,7 @@ pub async fn admin_update_operating_hours(
                     return (
                         StatusCode::BAD_REQUEST,
                         Json(ApiResponse::error(
-                            "VALIDATION_ERROR",
+                            ErrorCode::ValidationError,
                             format!("Invalid open time for {day_name}"),
                         )),
                     );
@@ -230,7 +236,7 @@ pub async fn admin_update_operating_hours(
                     return (
                         StatusCode::BAD_REQUEST,
                         Json(ApiResponse::error(
-                            "VALIDATION_ERROR",
+                            ErrorCode::ValidationError,
                             format!("Invalid close time for {day_name}"),
                         )),
                     );
@@ -245,14 +251,20 @@ pub async fn admin_update_operating_hours(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!(error = %e, "Failed to get parking lot");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -265,7 +277,7 @@ pub async fn admin_update_operating_hours(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update operating hours",
             )),
         );
@@ -19,9 +19,10 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, BookingStatus};
+use parkhub_common::{ApiResponse, Booking, BookingStatus, ErrorCode};
 
 use super::{AuthUser, SharedState};
+use crate::db::Database;
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // TYPES
@@ -119,6 +120,66 @@ fn generate_qr_base64(data: &str) -> String {
     format!("data:image/png;base64,{b64}")
 }
 
+/// Name printed on a booking's pass: the visitor's for a booking made on
+/// their behalf, otherwise the booking user's
+async fn holder_name(db: &Database, booking: &Booking) -> String {
+    if let Some(ref visitor) = booking.booked_for {
+        return visitor.name.clone();
+    }
+    if let Ok(Some(user)) = db.get_user(&booking.user_id.to_string()).await {
+        user.name
+    } else {
+        "Unknown".to_string()
+    }
+}
+
+/// Build the pass for `booking`
+pub(crate) async fn booking_pass(db: &Database, booking: &Booking) -> ParkingPass {
+    let lot_name = if let Ok(Some(lot)) = db.get_parking_lot(&booking.lot_id.to_string()).await {
+        lot.name
+    } else {
+        "Unknown Lot".to_string()
+    };
+
+    let slot_number =
+        if let Ok(Some(slot)) = db.get_parking_slot(&booking.slot_id.to_string()).await {
+            slot.slot_number.to_string()
+        } else {
+            "?".to_string()
+        };
+
+    // Generate verification code and QR
+    let verification_code = generate_verification_code(&booking.id);
+    let verify_url = format!("/api/v1/pass/verify/{}", verification_code);
+    let qr_data = generate_qr_base64(&verify_url);
+
+    // Determine status
+    let status = if booking.status == BookingStatus::Cancelled {
+        PassStatus::Revoked
+    } else if booking.end_time < Utc::now() {
+        PassStatus::Expired
+    } else if booking.check_in_time.is_some() {
+        PassStatus::Used
+    } else {
+        PassStatus::Active
+    };
+
+    ParkingPass {
+        id: Uuid::new_v4(),
+        booking_id: booking.id,
+        user_id: booking.user_id,
+        user_name: holder_name(db, booking).await,
+        lot_name,
+        slot_number,
+        valid_from: booking.start_time,
+        valid_until: booking.end_time,
+        verification_code,
+        qr_data,
+        status,
+        created_at: Utc::now(),
+    }
+}
+
 /// `GET /api/v1/bookings/:id/pass` — generate digital pass with QR code
 #[utoipa::path(get, path = "/api/v1/bookings/{id}/pass", tag = "Parking Pass",
     summary = "Generate parking pass",
@@ -143,7 +204,7 @@ pub async fn get_booking_pass(
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Booking not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Booking not found")),
             );
         }
     };
@@ -152,73 +213,11 @@ pub async fn get_booking_pass(
     if booking.user_id != auth_user.user_id {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Not your booking")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Not your booking")),
         );
     }
 
-    // Get lot and slot info
-    let lot_name = if let Ok(Some(lot)) = state_guard
-        .db
-        .get_parking_lot(&booking.lot_id.to_string())
-        .await
-    {
-        lot.name
-    } else {
-        "Unknown Lot".to_string()
-    };
-
-    let slot_number = if let Ok(Some(slot)) = state_guard
-        .db
-        .get_parking_slot(&booking.slot_id.to_string())
-        .await
-    {
-        slot.slot_number.to_string()
-    } else {
-        "?".to_string()
-    };
-
-    // Get user name
-    let user_name = if let Ok(Some(user)) = state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        user.name
-    } else {
-        "Unknown".to_string()
-    };
-
-    // Generate verification code and QR
-    let verification_code = generate_verification_code(&booking_id);
-    let verify_url = format!("/api/v1/pass/verify/{}", verification_code);
-    let qr_data = generate_qr_base64(&verify_url);
-
-    // Determine status
-    let status = if booking.status == BookingStatus::Cancelled {
-        PassStatus::Revoked
-    } else if booking.end_time < Utc::now() {
-        PassStatus::Expired
-    } else if booking.check_in_time.is_some() {
-        PassStatus::Used
-    } else {
-        PassStatus::Active
-    };
-
-    let pass = ParkingPass {
-        id: Uuid::new_v4(),
-        booking_id,
-        user_id: auth_user.user_id,
-        user_name,
-        lot_name,
-        slot_number,
-        valid_from: booking.start_time,
-        valid_until: booking.end_time,
-        verification_code,
-        qr_data,
-        status,
-        created_at: Utc::now(),
-    };
-
+    let pass = booking_pass(&state_guard.db, &booking).await;
     (StatusCode::OK, Json(ApiResponse::success(pass)))
 }
 
@@ -265,13 +264,7 @@ pub async fn verify_pass(
                 "?".to_string()
             };
 
-            let user_name = if let Ok(Some(user)) =
-                state_guard.db.get_user(&booking.user_id.to_string()).await
-            {
-                user.name
-            } else {
-                "Unknown".to_string()
-            };
+            let user_name = holder_name(&state_guard.db, booking).await;
 
             let status = if booking.status == BookingStatus::Cancelled {
                 PassStatus::Revoked
@@ -384,7 +377,10 @@ pub async fn list_my_passes(
             id: Uuid::new_v4(),
             booking_id: booking.id,
             user_id: auth_user.user_id,
-            user_name: user_name.clone(),
+            user_name: booking
+                .booked_for
+                .as_ref()
+                .map_or_else(|| user_name.clone(), |visitor| visitor.name.clone()),
             lot_name,
             slot_number,
             valid_from: booking.start_time,
@@ -21,11 +21,11 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use crate::audit::{AuditEntry, AuditEventType};
 
-use super::{AuthUser, SharedState};
+use super::{AuthUser, SharedState, check_admin};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Types
@@ -165,7 +165,10 @@ pub async fn list_zones_pricing(
         Ok(z) => z,
         Err(e) => {
             tracing::error!("Failed to list zones: {e}");
-            return Json(ApiResponse::error("SERVER_ERROR", "Failed to list zones"));
+            return Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to list zones",
+            ));
         }
     };
 
@@ -222,18 +225,14 @@ pub async fn set_zone_pricing(
     let state_guard = state.read().await;
 
     // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     let id = match Uuid::parse_str(&zone_id) {
@@ -241,7 +240,7 @@ pub async fn set_zone_pricing(
         Err(_) => {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("INVALID_ID", "Invalid zone ID")),
+                Json(ApiResponse::error(ErrorCode::InvalidId, "Invalid zone ID")),
             );
         }
     };
@@ -254,7 +253,7 @@ pub async fn set_zone_pricing(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_MULTIPLIER",
+                ErrorCode::InvalidMultiplier,
                 "Pricing multiplier must be positive",
             )),
         );
@@ -276,7 +275,10 @@ pub async fn set_zone_pricing(
         tracing::error!("Failed to save pricing: {e}");
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save pricing")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save pricing",
+            )),
         );
     }
 
@@ -316,7 +318,7 @@ pub async fn get_zone_price(
         Err(_) => {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("INVALID_ID", "Invalid zone ID")),
+                Json(ApiResponse::error(ErrorCode::InvalidId, "Invalid zone ID")),
             );
         }
     };
@@ -0,0 +1,945 @@
+//! Passkey (WebAuthn) registration and login.
+//!
+//! - `POST   /api/v1/auth/webauthn/register/start`    — begin adding a passkey
+//! - `POST   /api/v1/auth/webauthn/register/finish`   — store the new passkey
+//! - `POST   /api/v1/auth/webauthn/login/start`       — begin a passkey login
+//! - `POST   /api/v1/auth/webauthn/login/finish`      — verify it, start a session
+//! - `GET    /api/v1/auth/webauthn/credentials`       — list my passkeys
+//! - `DELETE /api/v1/auth/webauthn/credentials/:id`   — remove a passkey
+//!
+//! Each ceremony is two calls. `start` returns a `challenge_id` and the
+//! options to pass to `navigator.credentials.create()` / `.get()`; `finish`
+//! takes the browser's response along with the `challenge_id`. Pending
+//! ceremonies live in memory for [`CEREMONY_TTL`] and can be finished once;
+//! at most [`MAX_PENDING_CEREMONIES`] are kept, the oldest giving way first.
+//!
+//! `login/start` answers every username alike: unknown, disabled and
+//! passkey-less accounts get a challenge naming a made-up credential, stable
+//! per username, which no authenticator holds, so the endpoint does not
+//! tell which accounts exist or use passkeys.
+//!
+//! The relying party is derived from `APP_URL`: its host is the RP ID and
+//! the URL itself the only accepted origin, which matches the web frontend
+//! served by `static_files`. Passkeys verify the user on the device, so a
+//! passkey login skips the TOTP step. Password login stays available.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::{BTreeSet, HashMap};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration as StdDuration, Instant};
+
+use axum::{
+    Extension, Json,
+    extract::{ConnectInfo, Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use uuid::Uuid;
+use webauthn_rs::DEFAULT_AUTHENTICATOR_TIMEOUT;
+use webauthn_rs::prelude::{
+    PasskeyAuthentication, PasskeyRegistration, PublicKeyCredential, RegisterPublicKeyCredential,
+    Url, Webauthn, WebauthnBuilder,
+};
+
+use parkhub_common::{ApiResponse, AuthTokens, ErrorCode, LoginResponse};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{Session, StoredPasskey};
+use crate::metrics;
+
+use super::auth::{build_auth_cookie, session_limit_response, with_auth_cookie};
+use super::security::{LoginClient, record_login};
+use super::{AuthUser, SharedState, generate_access_token, server_error, session_tokens};
+
+/// How long a started ceremony can be finished.
+pub const CEREMONY_TTL: StdDuration = StdDuration::from_secs(300); // 5 minutes
+
+/// Started ceremonies kept at once. `login/start` needs no account, so the
+/// oldest are dropped beyond this rather than letting the map grow.
+pub const MAX_PENDING_CEREMONIES: usize = 10_000;
+
+/// Most passkeys one user may register.
+pub const MAX_PASSKEYS_PER_USER: usize = 20;
+
+/// Longest accepted passkey label, in characters.
+const MAX_PASSKEY_NAME_LEN: usize = 64;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Options for the browser's WebAuthn call, plus the ID to finish with.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct PasskeyChallenge {
+    pub challenge_id: String,
+    /// `PublicKeyCredentialCreationOptions` or `…RequestOptions`, wrapped in
+    /// `publicKey`
+    #[schema(value_type = Object)]
+    pub options: serde_json::Value,
+}
+
+/// Request body for finishing a passkey registration.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct FinishPasskeyRegistrationRequest {
+    pub challenge_id: String,
+    /// Label for the passkey, e.g. "Work laptop"
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Result of `navigator.credentials.create()`
+    #[schema(value_type = Object)]
+    pub credential: RegisterPublicKeyCredential,
+}
+
+/// Request body for starting a passkey login.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct StartPasskeyLoginRequest {
+    /// Username or email
+    pub username: String,
+}
+
+/// Request body for finishing a passkey login.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct FinishPasskeyLoginRequest {
+    pub challenge_id: String,
+    /// Result of `navigator.credentials.get()`
+    #[schema(value_type = Object)]
+    pub credential: PublicKeyCredential,
+}
+
+/// A registered passkey, without its key material.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct PasskeyInfo {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<&StoredPasskey> for PasskeyInfo {
+    fn from(passkey: &StoredPasskey) -> Self {
+        Self {
+            id: passkey.id,
+            name: passkey.name.clone(),
+            created_at: passkey.created_at,
+            last_used_at: passkey.last_used_at,
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Relying party and pending ceremonies
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A started, unfinished ceremony.
+enum Ceremony {
+    Registration {
+        user_id: Uuid,
+        state: PasskeyRegistration,
+    },
+    Authentication {
+        user_id: Uuid,
+        state: PasskeyAuthentication,
+    },
+    /// A login started for an account that cannot sign in with a passkey
+    Decoy,
+}
+
+/// Ceremonies in flight, keyed by challenge ID, with their start times in
+/// order so expiry and eviction only look at the oldest.
+#[derive(Default)]
+struct PendingCeremonies {
+    by_id: HashMap<String, (Ceremony, Instant)>,
+    by_start: BTreeSet<(Instant, String)>,
+}
+
+impl PendingCeremonies {
+    fn prune(&mut self, now: Instant) {
+        while let Some((started, _)) = self.by_start.first()
+            && now.duration_since(*started) >= CEREMONY_TTL
+        {
+            self.pop_oldest();
+        }
+    }
+
+    fn pop_oldest(&mut self) {
+        if let Some((_, id)) = self.by_start.pop_first() {
+            self.by_id.remove(&id);
+        }
+    }
+
+    fn insert(&mut self, id: String, ceremony: Ceremony, now: Instant) {
+        self.prune(now);
+        while self.by_id.len() >= MAX_PENDING_CEREMONIES {
+            self.pop_oldest();
+        }
+        self.by_start.insert((now, id.clone()));
+        self.by_id.insert(id, (ceremony, now));
+    }
+
+    fn take(&mut self, id: &str, now: Instant) -> Option<Ceremony> {
+        self.prune(now);
+        let (ceremony, started) = self.by_id.remove(id)?;
+        self.by_start.remove(&(started, id.to_string()));
+        Some(ceremony)
+    }
+}
+
+/// The WebAuthn relying party plus the ceremonies in flight.
+///
+/// Expired entries are pruned on each access, and the oldest is evicted
+/// when [`MAX_PENDING_CEREMONIES`] are pending.
+pub struct PasskeyService {
+    webauthn: Option<Webauthn>,
+    ceremonies: Mutex<PendingCeremonies>,
+}
+
+impl PasskeyService {
+    /// Build the relying party for `APP_URL` (default
+    /// `http://localhost:8443`). Passkeys stay disabled if it is not a
+    /// usable origin.
+    pub fn from_env() -> Arc<Self> {
+        let app_url =
+            std::env::var("APP_URL").unwrap_or_else(|_| "http://localhost:8443".to_string());
+        Arc::new(Self {
+            webauthn: relying_party(&app_url),
+            ceremonies: Mutex::new(PendingCeremonies::default()),
+        })
+    }
+
+    fn start(&self, ceremony: Ceremony) -> String {
+        let id = generate_access_token();
+        if let Ok(mut pending) = self.ceremonies.lock() {
+            pending.insert(id.clone(), ceremony, Instant::now());
+        }
+        id
+    }
+
+    /// Consume a ceremony if it exists and hasn't expired.
+    fn take(&self, id: &str) -> Option<Ceremony> {
+        self.ceremonies
+            .lock()
+            .ok()
+            .and_then(|mut pending| pending.take(id, Instant::now()))
+    }
+}
+
+/// Relying party for the origin `app_url`, named "ParkHub".
+fn relying_party(app_url: &str) -> Option<Webauthn> {
+    let origin = match Url::parse(app_url.trim_end_matches('/')) {
+        Ok(url) => url,
+        Err(e) => {
+            tracing::warn!("Passkeys disabled: APP_URL {app_url:?} is not a URL: {e}");
+            return None;
+        }
+    };
+    let rp_id = origin.host_str()?.to_string();
+    match WebauthnBuilder::new(&rp_id, &origin).and_then(|b| b.rp_name("ParkHub").build()) {
+        Ok(webauthn) => Some(webauthn),
+        Err(e) => {
+            tracing::warn!("Passkeys disabled: cannot use {app_url:?} as relying party: {e}");
+            None
+        }
+    }
+}
+
+/// Login options for an account that cannot sign in with a passkey, shaped
+/// like the ones `start_passkey_authentication` returns: a fresh challenge
+/// allowing one credential whose ID is derived from `username` and the
+/// server secret.
+fn decoy_login_options(webauthn: &Webauthn, secret: &str, username: &str) -> serde_json::Value {
+    let rp_id = webauthn
+        .get_allowed_origins()
+        .first()
+        .and_then(Url::host_str)
+        .unwrap_or_default();
+    let mut challenge = [0u8; 32];
+    rand::Rng::fill_bytes(&mut rand::rng(), &mut challenge);
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(b"passkey-decoy:");
+    mac.update(username.to_lowercase().as_bytes());
+    let credential_id = mac.finalize().into_bytes();
+    serde_json::json!({
+        "publicKey": {
+            "challenge": URL_SAFE_NO_PAD.encode(challenge),
+            "timeout": DEFAULT_AUTHENTICATOR_TIMEOUT.as_millis(),
+            "rpId": rp_id,
+            "allowCredentials": [{
+                "type": "public-key",
+                "id": URL_SAFE_NO_PAD.encode(credential_id),
+            }],
+            "userVerification": "required",
+        }
+    })
+}
+
+fn unavailable<T>() -> (StatusCode, Json<ApiResponse<T>>) {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(ApiResponse::error(
+            ErrorCode::PasskeysUnavailable,
+            "Passkeys are not configured on this server",
+        )),
+    )
+}
+
+fn invalid_challenge<T>(status: StatusCode) -> (StatusCode, Json<ApiResponse<T>>) {
+    (
+        status,
+        Json(ApiResponse::error(
+            ErrorCode::InvalidChallenge,
+            "Passkey challenge is invalid or expired",
+        )),
+    )
+}
+
+fn passkey_rejected() -> (StatusCode, Json<ApiResponse<LoginResponse>>) {
+    (
+        StatusCode::UNAUTHORIZED,
+        Json(ApiResponse::error(
+            ErrorCode::InvalidCredentials,
+            "The passkey could not be verified",
+        )),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Registration
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `POST /api/v1/auth/webauthn/register/start` — begin adding a passkey
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/webauthn/register/start",
+    tag = "Authentication",
+    summary = "Start passkey registration",
+    description = "Returns the options for `navigator.credentials.create()`. Passkeys the \
+        user already has are excluded, so an authenticator is not registered twice.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Creation options", body = PasskeyChallenge),
+        (status = 409, description = "Passkey limit reached"),
+        (status = 503, description = "Passkeys not configured"),
+    )
+)]
+pub async fn start_registration(
+    State(state): State<SharedState>,
+    Extension(service): Extension<Arc<PasskeyService>>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<PasskeyChallenge>>) {
+    let Some(webauthn) = &service.webauthn else {
+        return unavailable();
+    };
+    let state_guard = state.read().await;
+
+    let user = match state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    };
+    let existing = match state_guard.db.list_passkeys_for_user(user.id).await {
+        Ok(passkeys) => passkeys,
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    };
+    if existing.len() >= MAX_PASSKEYS_PER_USER {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::PasskeyLimitReached,
+                format!("At most {MAX_PASSKEYS_PER_USER} passkeys can be registered"),
+            )),
+        );
+    }
+
+    let exclude = existing
+        .iter()
+        .map(|p| p.credential.cred_id().clone())
+        .collect();
+    let (options, reg_state) = match webauthn.start_passkey_registration(
+        user.id,
+        &user.username,
+        &user.name,
+        Some(exclude),
+    ) {
+        Ok(started) => started,
+        Err(e) => {
+            tracing::error!("Failed to start passkey registration: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    let challenge_id = service.start(Ceremony::Registration {
+        user_id: user.id,
+        state: reg_state,
+    });
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(PasskeyChallenge {
+            challenge_id,
+            options: serde_json::to_value(options).unwrap_or_default(),
+        })),
+    )
+}
+
+/// `POST /api/v1/auth/webauthn/register/finish` — store the new passkey
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/webauthn/register/finish",
+    tag = "Authentication",
+    summary = "Finish passkey registration",
+    description = "Verifies the authenticator's response to a registration challenge and \
+        stores the passkey. The challenge must have been started by the same user.",
+    request_body = FinishPasskeyRegistrationRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Passkey registered", body = PasskeyInfo),
+        (status = 400, description = "Unknown or expired challenge, or response rejected"),
+        (status = 503, description = "Passkeys not configured"),
+    )
+)]
+pub async fn finish_registration(
+    State(state): State<SharedState>,
+    Extension(service): Extension<Arc<PasskeyService>>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<FinishPasskeyRegistrationRequest>,
+) -> (StatusCode, Json<ApiResponse<PasskeyInfo>>) {
+    let Some(webauthn) = &service.webauthn else {
+        return unavailable();
+    };
+    let reg_state = match service.take(&req.challenge_id) {
+        Some(Ceremony::Registration {
+            user_id,
+            state: reg_state,
+        }) if user_id == auth_user.user_id => reg_state,
+        _ => return invalid_challenge(StatusCode::BAD_REQUEST),
+    };
+
+    let name = req
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|n| !n.is_empty())
+        .unwrap_or("Passkey");
+    if name.chars().count() > MAX_PASSKEY_NAME_LEN {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::ValidationError,
+                "Passkey name must be at most 64 characters",
+            )),
+        );
+    }
+
+    let credential = match webauthn.finish_passkey_registration(&req.credential, &reg_state) {
+        Ok(credential) => credential,
+        Err(e) => {
+            tracing::debug!(user_id = %auth_user.user_id, "Passkey registration rejected: {}", e);
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ErrorCode::PasskeyRejected,
+                    "The authenticator response could not be verified",
+                )),
+            );
+        }
+    };
+
+    let state_guard = state.read().await;
+    let passkey = StoredPasskey {
+        id: Uuid::new_v4(),
+        user_id: auth_user.user_id,
+        name: name.to_string(),
+        credential,
+        created_at: Utc::now(),
+        last_used_at: None,
+    };
+    if let Err(e) = state_guard.db.save_passkey(&passkey).await {
+        tracing::error!("Database error: {e}");
+        return server_error("Internal server error");
+    }
+
+    let username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    AuditEntry::new(AuditEventType::PasskeyRegistered)
+        .user(auth_user.user_id, &username)
+        .resource("passkey", &passkey.id.to_string())
+        .detail(&format!("Registered passkey {}", passkey.name))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (
+        StatusCode::CREATED,
+        Json(ApiResponse::success(PasskeyInfo::from(&passkey))),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Login
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `POST /api/v1/auth/webauthn/login/start` — begin a passkey login
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/webauthn/login/start",
+    tag = "Authentication",
+    summary = "Start passkey login",
+    description = "Returns the options for `navigator.credentials.get()`, allowing the \
+        passkeys registered to the given account. Accounts that are unknown, disabled or \
+        without passkeys get options of the same shape, which no authenticator can answer.",
+    request_body = StartPasskeyLoginRequest,
+    responses(
+        (status = 200, description = "Request options", body = PasskeyChallenge),
+        (status = 503, description = "Passkeys not configured"),
+    )
+)]
+pub async fn start_login(
+    State(state): State<SharedState>,
+    Extension(service): Extension<Arc<PasskeyService>>,
+    Json(req): Json<StartPasskeyLoginRequest>,
+) -> (StatusCode, Json<ApiResponse<PasskeyChallenge>>) {
+    let Some(webauthn) = &service.webauthn else {
+        return unavailable();
+    };
+    if req.username.len() > 254 {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "Username/email must be at most 254 characters",
+            )),
+        );
+    }
+    let state_guard = state.read().await;
+
+    let user = match state_guard.db.get_user_by_username(&req.username).await {
+        Ok(Some(user)) => Some(user),
+        Ok(None) => state_guard
+            .db
+            .get_user_by_email(&req.username)
+            .await
+            .ok()
+            .flatten(),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    };
+    let credentials: Vec<_> = match user.as_ref().filter(|u| u.is_active) {
+        Some(user) => match state_guard.db.list_passkeys_for_user(user.id).await {
+            Ok(passkeys) => passkeys.into_iter().map(|p| p.credential).collect(),
+            Err(e) => {
+                tracing::error!("Database error: {e}");
+                return server_error("Internal server error");
+            }
+        },
+        None => Vec::new(),
+    };
+
+    // Unknown, disabled and passkey-less accounts look the same.
+    let Some(user) = user.filter(|_| !credentials.is_empty()) else {
+        let options = decoy_login_options(
+            webauthn,
+            state_guard.db.token_signing_secret(),
+            &req.username,
+        );
+        return (
+            StatusCode::OK,
+            Json(ApiResponse::success(PasskeyChallenge {
+                challenge_id: service.start(Ceremony::Decoy),
+                options,
+            })),
+        );
+    };
+
+    let (options, auth_state) = match webauthn.start_passkey_authentication(&credentials) {
+        Ok(started) => started,
+        Err(e) => {
+            tracing::error!("Failed to start passkey login: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    let challenge_id = service.start(Ceremony::Authentication {
+        user_id: user.id,
+        state: auth_state,
+    });
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(PasskeyChallenge {
+            challenge_id,
+            options: serde_json::to_value(options).unwrap_or_default(),
+        })),
+    )
+}
+
+/// `POST /api/v1/auth/webauthn/login/finish` — verify a passkey and sign in
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/webauthn/login/finish",
+    tag = "Authentication",
+    summary = "Finish passkey login",
+    description = "Verifies the authenticator's response to a login challenge and returns \
+        session tokens, like a password login. No TOTP code is asked for.",
+    request_body = FinishPasskeyLoginRequest,
+    responses(
+        (status = 200, description = "Login successful"),
+        (status = 401, description = "Unknown or expired challenge, or passkey rejected"),
+        (status = 403, description = "Account disabled"),
+        (status = 409, description = "Session limit reached (`session_limit_policy = \"reject\"`)"),
+        (status = 503, description = "Passkeys not configured"),
+    )
+)]
+#[tracing::instrument(skip(state, service, headers, connect_info, req))]
+pub async fn finish_login(
+    State(state): State<SharedState>,
+    Extension(service): Extension<Arc<PasskeyService>>,
+    headers: HeaderMap,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    Json(req): Json<FinishPasskeyLoginRequest>,
+) -> Response {
+    let Some(webauthn) = &service.webauthn else {
+        return unavailable::<LoginResponse>().into_response();
+    };
+    let (user_id, auth_state) = match service.take(&req.challenge_id) {
+        Some(Ceremony::Authentication { user_id, state }) => (user_id, state),
+        Some(Ceremony::Decoy) => {
+            metrics::record_auth_event("passkey_login", false);
+            return passkey_rejected().into_response();
+        }
+        _ => {
+            metrics::record_auth_event("passkey_login", false);
+            return invalid_challenge::<LoginResponse>(StatusCode::UNAUTHORIZED).into_response();
+        }
+    };
+
+    let state_guard = state.read().await;
+    let client = LoginClient::from_request(&headers, connect_info.map(|Extension(ci)| ci.0));
+    let mut user = match state_guard.db.get_user(&user_id.to_string()).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            metrics::record_auth_event("passkey_login", false);
+            return invalid_challenge::<LoginResponse>(StatusCode::UNAUTHORIZED).into_response();
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error::<LoginResponse>("Internal server error").into_response();
+        }
+    };
+
+    let result = match webauthn.finish_passkey_authentication(&req.credential, &auth_state) {
+        Ok(result) => result,
+        Err(e) => {
+            tracing::debug!(user_id = %user.id, "Passkey login rejected: {}", e);
+            AuditEntry::new(AuditEventType::LoginFailed)
+                .user(user.id, &user.username)
+                .error("Invalid passkey")
+                .log();
+            record_login(&state_guard, &user, &client, Some("invalid_passkey")).await;
+            metrics::record_auth_event("passkey_login", false);
+            return passkey_rejected().into_response();
+        }
+    };
+
+    if !user.is_active {
+        record_login(&state_guard, &user, &client, Some("account_disabled")).await;
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::<LoginResponse>::error(
+                ErrorCode::AccountDisabled,
+                "This account has been disabled",
+            )),
+        )
+            .into_response();
+    }
+
+    // Keep the signature counter current so cloned authenticators show up.
+    match state_guard.db.list_passkeys_for_user(user.id).await {
+        Ok(passkeys) => {
+            if let Some(mut passkey) = passkeys
+                .into_iter()
+                .find(|p| p.credential.cred_id() == result.cred_id())
+            {
+                passkey.credential.update_credential(&result);
+                passkey.last_used_at = Some(Utc::now());
+                if let Err(e) = state_guard.db.save_passkey(&passkey).await {
+                    tracing::warn!("Failed to update passkey {}: {}", passkey.id, e);
+                }
+            }
+        }
+        Err(e) => tracing::warn!("Failed to load passkeys of {}: {}", user.id, e),
+    }
+
+    match session_tokens::make_room_for_session(&state_guard, user.id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            record_login(&state_guard, &user, &client, Some("session_limit_reached")).await;
+            metrics::record_auth_event("passkey_login", false);
+            return session_limit_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to check session limit: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<LoginResponse>::error(
+                    ErrorCode::ServerError,
+                    "Failed to create session",
+                )),
+            )
+                .into_response();
+        }
+    }
+    let session_hours = i64::from(state_guard.config.session_lifetime_hours).max(1);
+    let role_str = format!("{:?}", user.role).to_lowercase();
+    let session = Session::new(user.id, session_hours, &user.username, &role_str)
+        .with_client(Some(client.ip.clone()), client.user_agent.clone());
+    let access_token = match session_tokens::start_session(&state_guard.db, &session).await {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to save session after passkey login: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<LoginResponse>::error(
+                    ErrorCode::ServerError,
+                    "Failed to create session",
+                )),
+            )
+                .into_response();
+        }
+    };
+
+    let audit = AuditEntry::new(AuditEventType::LoginSuccess)
+        .user(user.id, &user.username)
+        .detail("Passkey")
+        .log();
+    audit.persist(&state_guard.db).await;
+    record_login(&state_guard, &user, &client, None).await;
+    drop(state_guard);
+    metrics::record_auth_event("passkey_login", true);
+
+    user.password_hash = String::new();
+    let cookie = build_auth_cookie(&access_token, session_hours * 3600);
+
+    with_auth_cookie(
+        StatusCode::OK,
+        Json(ApiResponse::success(LoginResponse {
+            user,
+            tokens: AuthTokens {
+                access_token,
+                refresh_token: session.refresh_token,
+                expires_at: session.expires_at,
+                token_type: "Bearer".to_string(),
+            },
+        })),
+        &cookie,
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Management
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `GET /api/v1/auth/webauthn/credentials` — list my passkeys
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/webauthn/credentials",
+    tag = "Authentication",
+    summary = "List my passkeys",
+    description = "Returns the caller's registered passkeys, oldest first.",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Passkeys", body = Vec<PasskeyInfo>))
+)]
+pub async fn list_passkeys(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<Vec<PasskeyInfo>>>) {
+    let state_guard = state.read().await;
+    match state_guard
+        .db
+        .list_passkeys_for_user(auth_user.user_id)
+        .await
+    {
+        Ok(passkeys) => (
+            StatusCode::OK,
+            Json(ApiResponse::success(
+                passkeys.iter().map(PasskeyInfo::from).collect(),
+            )),
+        ),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            server_error("Internal server error")
+        }
+    }
+}
+
+/// `DELETE /api/v1/auth/webauthn/credentials/{id}` — remove a passkey
+#[utoipa::path(
+    delete,
+    path = "/api/v1/auth/webauthn/credentials/{id}",
+    tag = "Authentication",
+    summary = "Remove a passkey",
+    description = "Deletes one of the caller's passkeys. It can no longer be used to sign in.",
+    params(("id" = Uuid, Path, description = "Passkey ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Passkey removed"),
+        (status = 404, description = "Passkey not found"),
+    )
+)]
+pub async fn delete_passkey(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.read().await;
+    match state_guard.db.delete_passkey(auth_user.user_id, id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::NotFound, "Passkey not found")),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    }
+
+    let username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    AuditEntry::new(AuditEventType::PasskeyRemoved)
+        .user(auth_user.user_id, &username)
+        .resource("passkey", &id.to_string())
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relying_party_from_app_url() {
+        assert!(relying_party("https://parking.example.com").is_some());
+        assert!(relying_party("https://parking.example.com/").is_some());
+        assert!(relying_party("http://localhost:8443").is_some());
+        assert!(relying_party("not a url").is_none());
+    }
+
+    #[test]
+    fn test_ceremony_is_single_use() {
+        let service = PasskeyService {
+            webauthn: relying_party("http://localhost:8443"),
+            ceremonies: Mutex::new(PendingCeremonies::default()),
+        };
+        let webauthn = service.webauthn.as_ref().unwrap();
+        let (_, state) = webauthn
+            .start_passkey_registration(Uuid::new_v4(), "alice", "Alice", None)
+            .unwrap();
+        let id = service.start(Ceremony::Registration {
+            user_id: Uuid::new_v4(),
+            state,
+        });
+        assert!(matches!(
+            service.take(&id),
+            Some(Ceremony::Registration { .. })
+        ));
+        assert!(service.take(&id).is_none());
+        assert!(service.take("unknown").is_none());
+    }
+
+    #[test]
+    fn test_pending_ceremonies_are_capped() {
+        let service = PasskeyService {
+            webauthn: relying_party("http://localhost:8443"),
+            ceremonies: Mutex::new(PendingCeremonies::default()),
+        };
+        let webauthn = service.webauthn.as_ref().unwrap();
+        let registration = || Ceremony::Registration {
+            user_id: Uuid::new_v4(),
+            state: webauthn
+                .start_passkey_registration(Uuid::new_v4(), "alice", "Alice", None)
+                .unwrap()
+                .1,
+        };
+
+        let oldest = service.start(registration());
+        for _ in 1..MAX_PENDING_CEREMONIES {
+            service.start(registration());
+        }
+        let newest = service.start(registration());
+
+        assert_eq!(
+            service.ceremonies.lock().unwrap().by_id.len(),
+            MAX_PENDING_CEREMONIES
+        );
+        assert!(service.take(&oldest).is_none());
+        assert!(service.take(&newest).is_some());
+    }
+
+    #[test]
+    fn test_pending_ceremonies_expire() {
+        let mut pending = PendingCeremonies::default();
+        let now = Instant::now();
+        pending.insert("old".to_string(), Ceremony::Decoy, now);
+        pending.insert("new".to_string(), Ceremony::Decoy, now + CEREMONY_TTL / 2);
+
+        assert!(pending.take("old", now + CEREMONY_TTL).is_none());
+        assert_eq!(pending.by_id.len(), 1);
+        assert!(pending.take("new", now + CEREMONY_TTL).is_some());
+        assert_eq!(pending.by_start.len(), 0);
+    }
+}
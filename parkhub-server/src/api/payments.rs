@@ -26,10 +26,12 @@ use tokio::sync::RwLock;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use crate::AppState;
 
+use super::feature_flags::{PaymentsFlag, RequireFlag};
+
 type SharedState = Arc<RwLock<AppState>>;
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -133,6 +135,7 @@ pub struct PaymentStatusResponse {
 )]
 pub async fn create_payment_intent(
     State(_state): State<SharedState>,
+    _: RequireFlag<PaymentsFlag>,
     Extension(store): Extension<PaymentStore>,
     Json(req): Json<CreatePaymentIntentRequest>,
 ) -> impl IntoResponse {
@@ -140,7 +143,7 @@ pub async fn create_payment_intent(
         return (
             StatusCode::NOT_IMPLEMENTED,
             Json(serde_json::json!(ApiResponse::<()>::error(
-                "NOT_IMPLEMENTED",
+                ErrorCode::NotImplemented,
                 "Payment processing is not available in production mode"
             ))),
         )
@@ -182,6 +185,7 @@ pub async fn create_payment_intent(
 )]
 pub async fn confirm_payment(
     State(_state): State<SharedState>,
+    _: RequireFlag<PaymentsFlag>,
     Extension(store): Extension<PaymentStore>,
     Json(req): Json<ConfirmPaymentRequest>,
 ) -> impl IntoResponse {
@@ -189,7 +193,7 @@ pub async fn confirm_payment(
         return (
             StatusCode::NOT_IMPLEMENTED,
             Json(serde_json::json!(ApiResponse::<()>::error(
-                "NOT_IMPLEMENTED",
+                ErrorCode::NotImplemented,
                 "Payment processing is not available in production mode"
             ))),
         )
@@ -200,7 +204,7 @@ pub async fn confirm_payment(
         return (
             StatusCode::NOT_FOUND,
             Json(serde_json::json!(ApiResponse::<()>::error(
-                "NOT_FOUND",
+                ErrorCode::NotFound,
                 "Payment intent not found"
             ))),
         )
@@ -232,6 +236,7 @@ pub async fn confirm_payment(
 )]
 pub async fn payment_status(
     State(_state): State<SharedState>,
+    _: RequireFlag<PaymentsFlag>,
     Extension(store): Extension<PaymentStore>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
@@ -239,7 +244,7 @@ pub async fn payment_status(
         return (
             StatusCode::NOT_IMPLEMENTED,
             Json(serde_json::json!(ApiResponse::<()>::error(
-                "NOT_IMPLEMENTED",
+                ErrorCode::NotImplemented,
                 "Payment processing is not available in production mode"
             ))),
         )
@@ -250,7 +255,7 @@ pub async fn payment_status(
         return (
             StatusCode::NOT_FOUND,
             Json(serde_json::json!(ApiResponse::<()>::error(
-                "NOT_FOUND",
+                ErrorCode::NotFound,
                 "Payment intent not found"
             ))),
         )
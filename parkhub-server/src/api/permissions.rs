@@ -0,0 +1,173 @@
+//! Permission checks for handlers.
+//!
+//! The role → capability matrix and custom roles live in
+//! `parkhub_common::permissions`. The auth middleware resolves the caller's
+//! permissions once per request into [`AuthUser::permissions`] with
+//! [`resolve_permissions`].
+//!
+//! Handlers that need a permission on every lot state it by taking a
+//! [`RequirePermission`] extractor:
+//!
+//! ```ignore
+//! pub async fn admin_list_users(
+//!     State(state): State<SharedState>,
+//!     RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+//! ) -> ...
+//! ```
+//!
+//! or, when the required capability depends on the request, by calling
+//! [`check_permission`]. Lot-scoped checks go through
+//! `lot_managers::admin_scope` / `check_lot_admin` or the booking helpers
+//! below. All reject with `403 FORBIDDEN`.
+
+use std::marker::PhantomData;
+
+use axum::{Json, extract::FromRequestParts, http::StatusCode, http::request::Parts};
+
+use parkhub_common::{
+    ApiResponse, Booking, ErrorCode, LotScope, Permission, ResolvedPermissions, User,
+};
+
+use crate::db::Database;
+
+use super::lot_managers::LOT_MANAGER_PERMISSIONS;
+use super::{AuthUser, SharedState};
+
+/// Resolve everything `user` may do: the permissions of their built-in
+/// role, the grants of their custom roles and, for lot managers,
+/// [`LOT_MANAGER_PERMISSIONS`] on their lots. Lookup errors degrade to the
+/// built-in role's permissions.
+pub async fn resolve_permissions(db: &Database, user: &User) -> ResolvedPermissions {
+    let mut resolved = ResolvedPermissions::for_role(&user.role);
+    match db.list_roles_for_user(user.id).await {
+        Ok(roles) => {
+            for role in &roles {
+                resolved.grant_role(role);
+            }
+        }
+        Err(e) => tracing::warn!("Failed to load roles of {}: {}", user.id, e),
+    }
+    match db.list_managed_lots(user.id).await {
+        Ok(managed) if !managed.is_empty() => {
+            let scope = LotScope::Lots(managed.into_iter().collect());
+            for permission in LOT_MANAGER_PERMISSIONS {
+                resolved.grant(permission, scope.clone());
+            }
+        }
+        Ok(_) => {}
+        Err(e) => tracing::warn!("Failed to load managed lots of {}: {}", user.id, e),
+    }
+    resolved
+}
+
+/// Verify the caller holds `permission` on every lot.
+///
+/// # Errors
+/// Returns `(403, message)` when the caller lacks the permission or only
+/// holds it for some lots.
+pub fn check_permission(
+    auth_user: &AuthUser,
+    permission: Permission,
+) -> Result<(), (StatusCode, &'static str)> {
+    if auth_user.permissions.has_global(permission) {
+        Ok(())
+    } else {
+        tracing::debug!(user_id = %auth_user.user_id, %permission, "Permission denied");
+        Err((StatusCode::FORBIDDEN, "Insufficient permissions"))
+    }
+}
+
+/// Whether the caller may see `booking`: its owner, or someone who views or
+/// manages bookings in its lot.
+pub fn may_view_booking(auth_user: &AuthUser, booking: &Booking) -> bool {
+    booking.user_id == auth_user.user_id
+        || auth_user
+            .permissions
+            .allows_lot(Permission::ViewAllBookings, booking.lot_id)
+        || auth_user
+            .permissions
+            .allows_lot(Permission::ManageBookings, booking.lot_id)
+}
+
+/// Whether the caller may change `booking`: its owner, or someone who
+/// manages bookings in its lot.
+pub fn may_manage_booking(auth_user: &AuthUser, booking: &Booking) -> bool {
+    booking.user_id == auth_user.user_id
+        || auth_user
+            .permissions
+            .allows_lot(Permission::ManageBookings, booking.lot_id)
+}
+
+/// Read-only report paths under the globally guarded `/api/v1/admin/*`
+/// group. Callers holding [`Permission::ViewAllBookings`] on every lot —
+/// reporters among them — may reach them; the handlers require the same.
+pub const REPORTING_ADMIN_PATHS: &[&str] = &[
+    "/api/v1/admin/stats",
+    "/api/v1/admin/heatmap",
+    "/api/v1/admin/dashboard/charts",
+    "/api/v1/admin/analytics/overview",
+    "/api/v1/admin/analytics/occupancy",
+    "/api/v1/admin/analytics/revenue",
+    "/api/v1/admin/analytics/popular-lots",
+    "/api/v1/admin/reports/revenue",
+    "/api/v1/admin/reports/occupancy",
+    "/api/v1/admin/reports/users",
+    "/api/v1/admin/reports/department-utilization",
+];
+
+/// Whether a reporting caller may pass the admin middleware for `path`.
+#[must_use]
+pub fn reporting_may_access(path: &str) -> bool {
+    REPORTING_ADMIN_PATHS.contains(&path)
+}
+
+/// Type-level name of a [`Permission`], for use with [`RequirePermission`].
+pub trait Capability: Send + Sync {
+    const PERMISSION: Permission;
+}
+
+/// [`Permission::ManageUsers`]
+pub struct CanManageUsers;
+/// [`Permission::ViewAllBookings`]
+pub struct CanViewAllBookings;
+/// [`Permission::ManageConfig`]
+pub struct CanManageConfig;
+
+impl Capability for CanManageUsers {
+    const PERMISSION: Permission = Permission::ManageUsers;
+}
+impl Capability for CanViewAllBookings {
+    const PERMISSION: Permission = Permission::ViewAllBookings;
+}
+impl Capability for CanManageConfig {
+    const PERMISSION: Permission = Permission::ManageConfig;
+}
+
+/// Extractor that admits the request only if the authenticated caller holds
+/// `C`'s permission on every lot, yielding the caller.
+///
+/// Must run behind the auth middleware, which inserts [`AuthUser`].
+pub struct RequirePermission<C: Capability>(pub AuthUser, pub PhantomData<C>);
+
+impl<C: Capability> FromRequestParts<SharedState> for RequirePermission<C> {
+    type Rejection = (StatusCode, Json<ApiResponse<()>>);
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        _state: &SharedState,
+    ) -> Result<Self, Self::Rejection> {
+        let auth_user = parts.extensions.get::<AuthUser>().cloned().ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                Json(ApiResponse::error(
+                    ErrorCode::Unauthorized,
+                    "Not authenticated",
+                )),
+            )
+        })?;
+        check_permission(&auth_user, C::PERMISSION).map_err(|(status, msg)| {
+            (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)))
+        })?;
+        Ok(Self(auth_user, PhantomData))
+    }
+}
@@ -0,0 +1,740 @@
+//! Parking permits: resident and contractor permits with validity periods.
+//!
+//! - `GET    /api/v1/admin/permits` — all permits (admin)
+//! - `POST   /api/v1/admin/permits` — issue a permit (admin)
+//! - `PUT    /api/v1/admin/permits/{id}` — change or revoke a permit (admin)
+//! - `DELETE /api/v1/admin/permits/{id}` — delete a permit (admin)
+//! - `GET    /api/v1/permits/me` — the caller's own permits
+//! - `POST   /api/v1/gate/check` — entry decision for a plate at a lot gate
+//!   (ANPR camera or barrier controller; admin or manager of the lot)
+//!
+//! Lots whose [`LotBookingRules::permit_required`] is set only accept bookings
+//! from users holding a permit that covers the lot for the whole booking,
+//! either issued to their account or to the booked plate. Permit holders whose
+//! permit runs out within [`REMINDER_DAYS`] get one reminder email.
+//!
+//! [`LotBookingRules::permit_required`]: parkhub_common::LotBookingRules
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::{
+    ApiResponse, BookingStatus, ErrorCode, Permit, PermitType, User, normalize_license_plate,
+};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::Database;
+
+use super::lot_managers::check_lot_admin;
+use super::{AuthUser, SharedState, check_admin};
+
+/// Days before expiry at which the holder is reminded.
+pub const REMINDER_DAYS: i64 = 7;
+
+/// Whether `user` may book `lot_id` from `start` to `end` with `plate` under a
+/// permit. Database errors deny, since the lot is restricted.
+pub async fn has_booking_permit(
+    db: &Database,
+    user: &User,
+    lot_id: Uuid,
+    plate: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> bool {
+    let permits = match db.list_permits().await {
+        Ok(permits) => permits,
+        Err(e) => {
+            tracing::error!("Failed to load permits: {e}");
+            return false;
+        }
+    };
+    permits.iter().any(|p| {
+        p.covers_lot(lot_id)
+            && p.valid_for(start, end)
+            && (p.user_id == Some(user.id) || p.matches_plate(plate))
+    })
+}
+
+/// Handler error for bookings in a restricted lot without a permit.
+pub fn permit_required_response<T>() -> (StatusCode, Json<ApiResponse<T>>) {
+    (
+        StatusCode::FORBIDDEN,
+        Json(ApiResponse::error(
+            ErrorCode::PermitRequired,
+            "This lot requires a valid parking permit for the booking period",
+        )),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Admin CRUD
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Request body for issuing or changing a permit
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct PermitRequest {
+    pub permit_type: PermitType,
+    /// Holder account; at least one of `user_id` and `license_plate` is required
+    pub user_id: Option<Uuid>,
+    pub license_plate: Option<String>,
+    pub holder_name: Option<String>,
+    /// Lots the permit covers; empty or omitted for every lot
+    #[serde(default)]
+    pub lot_ids: Vec<Uuid>,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+    /// Withdraw the permit without deleting its record (update only)
+    #[serde(default)]
+    pub revoked: bool,
+    pub notes: Option<String>,
+}
+
+/// Check a permit request against the database.
+async fn validate_permit_request(db: &Database, req: &PermitRequest) -> Result<(), String> {
+    if req.valid_until <= req.valid_from {
+        return Err("valid_until must be after valid_from".to_string());
+    }
+    let plate = req.license_plate.as_deref().map(str::trim).unwrap_or("");
+    if plate.len() > 20 {
+        return Err("License plate must be at most 20 characters".to_string());
+    }
+    if req.user_id.is_none() && normalize_license_plate(plate).is_empty() {
+        return Err("A permit needs a user or a license plate".to_string());
+    }
+    if req.holder_name.as_deref().is_some_and(|n| n.len() > 200)
+        || req.notes.as_deref().is_some_and(|n| n.len() > 500)
+    {
+        return Err("Holder name or notes too long".to_string());
+    }
+    if let Some(user_id) = req.user_id
+        && !matches!(db.get_user(&user_id.to_string()).await, Ok(Some(_)))
+    {
+        return Err("Permit holder does not exist".to_string());
+    }
+    for lot_id in &req.lot_ids {
+        if !matches!(db.get_parking_lot(&lot_id.to_string()).await, Ok(Some(_))) {
+            return Err(format!("Parking lot {lot_id} does not exist"));
+        }
+    }
+    Ok(())
+}
+
+/// Trimmed, non-empty optional text.
+fn clean(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// `GET /api/v1/admin/permits` — list all permits
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/permits",
+    tag = "Admin",
+    summary = "List parking permits",
+    description = "All permits including expired and revoked ones, soonest expiry first. \
+        Admin only.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Permits", body = Vec<Permit>),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn admin_list_permits(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<Vec<Permit>>>) {
+    let state_guard = state.read().await;
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    match state_guard.db.list_permits().await {
+        Ok(permits) => (StatusCode::OK, Json(ApiResponse::success(permits))),
+        Err(e) => {
+            tracing::error!("Failed to list permits: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
+/// `POST /api/v1/admin/permits` — issue a permit
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/permits",
+    tag = "Admin",
+    summary = "Issue a parking permit",
+    description = "Issues a resident or contractor permit to a user, a license plate, or both. \
+        Admin only.",
+    request_body = PermitRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Permit issued", body = Permit),
+        (status = 400, description = "Invalid permit"),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn admin_create_permit(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<PermitRequest>,
+) -> (StatusCode, Json<ApiResponse<Permit>>) {
+    let state_guard = state.read().await;
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    if let Err(msg) = validate_permit_request(&state_guard.db, &req).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+        );
+    }
+
+    let now = Utc::now();
+    let permit = Permit {
+        id: Uuid::new_v4(),
+        permit_type: req.permit_type,
+        user_id: req.user_id,
+        license_plate: clean(req.license_plate),
+        holder_name: clean(req.holder_name),
+        lot_ids: req.lot_ids,
+        valid_from: req.valid_from,
+        valid_until: req.valid_until,
+        revoked: false,
+        notes: clean(req.notes),
+        reminder_sent_at: None,
+        created_by: auth_user.user_id,
+        created_at: now,
+        updated_at: now,
+    };
+
+    if let Err(e) = state_guard.db.save_permit(&permit).await {
+        tracing::error!("Failed to save permit: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save permit",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::SettingsChanged)
+        .user(auth_user.user_id, "")
+        .resource("permit", &permit.id.to_string())
+        .detail(&format!(
+            "Permit issued ({:?}, until {})",
+            permit.permit_type,
+            permit.valid_until.format("%Y-%m-%d")
+        ))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::CREATED, Json(ApiResponse::success(permit)))
+}
+
+/// `PUT /api/v1/admin/permits/{id}` — change or revoke a permit
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/permits/{id}",
+    tag = "Admin",
+    summary = "Update a parking permit",
+    description = "Replaces the permit's holder, lots and validity, or revokes it. Extending \
+        the validity re-arms the expiry reminder. Admin only.",
+    params(("id" = String, Path, description = "Permit ID")),
+    request_body = PermitRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Updated permit", body = Permit),
+        (status = 400, description = "Invalid permit"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Permit not found"),
+    )
+)]
+pub async fn admin_update_permit(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+    Json(req): Json<PermitRequest>,
+) -> (StatusCode, Json<ApiResponse<Permit>>) {
+    let state_guard = state.read().await;
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let mut permit = match state_guard.db.get_permit(&id).await {
+        Ok(Some(permit)) => permit,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::NotFound, "Permit not found")),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Failed to load permit: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    if let Err(msg) = validate_permit_request(&state_guard.db, &req).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+        );
+    }
+
+    if req.valid_until != permit.valid_until {
+        permit.reminder_sent_at = None;
+    }
+    permit.permit_type = req.permit_type;
+    permit.user_id = req.user_id;
+    permit.license_plate = clean(req.license_plate);
+    permit.holder_name = clean(req.holder_name);
+    permit.lot_ids = req.lot_ids;
+    permit.valid_from = req.valid_from;
+    permit.valid_until = req.valid_until;
+    permit.revoked = req.revoked;
+    permit.notes = clean(req.notes);
+    permit.updated_at = Utc::now();
+
+    if let Err(e) = state_guard.db.save_permit(&permit).await {
+        tracing::error!("Failed to save permit: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save permit",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::SettingsChanged)
+        .user(auth_user.user_id, "")
+        .resource("permit", &id)
+        .detail(if permit.revoked {
+            "Permit revoked"
+        } else {
+            "Permit updated"
+        })
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(permit)))
+}
+
+/// `DELETE /api/v1/admin/permits/{id}` — delete a permit
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/permits/{id}",
+    tag = "Admin",
+    summary = "Delete a parking permit",
+    description = "Removes the permit entirely. Use revocation to keep a record. Admin only.",
+    params(("id" = String, Path, description = "Permit ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Permit deleted"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Permit not found"),
+    )
+)]
+pub async fn admin_delete_permit(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.read().await;
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    match state_guard.db.delete_permit(&id).await {
+        Ok(true) => {
+            AuditEntry::new(AuditEventType::SettingsChanged)
+                .user(auth_user.user_id, "")
+                .resource("permit", &id)
+                .detail("Permit deleted")
+                .log()
+                .persist(&state_guard.db)
+                .await;
+            (StatusCode::OK, Json(ApiResponse::success(())))
+        }
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::NotFound, "Permit not found")),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to delete permit: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
+/// `GET /api/v1/permits/me` — the caller's permits
+#[utoipa::path(
+    get,
+    path = "/api/v1/permits/me",
+    tag = "Users",
+    summary = "List my permits",
+    description = "Permits issued to the current user's account, soonest expiry first.",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Permits", body = Vec<Permit>))
+)]
+pub async fn list_my_permits(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<Vec<Permit>>>) {
+    let state_guard = state.read().await;
+    match state_guard
+        .db
+        .list_permits_by_user(&auth_user.user_id.to_string())
+        .await
+    {
+        Ok(permits) => (StatusCode::OK, Json(ApiResponse::success(permits))),
+        Err(e) => {
+            tracing::error!("Failed to list permits: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Gate / ANPR
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Plate read at a lot entrance
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct GateCheckRequest {
+    pub lot_id: Uuid,
+    pub license_plate: String,
+}
+
+/// What let the vehicle in, if anything
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GateDecision {
+    /// A valid permit covers the plate for this lot
+    Permit,
+    /// The plate has a confirmed or active booking in this lot right now
+    Booking,
+    /// Neither; keep the barrier closed
+    Denied,
+}
+
+/// Entry decision for the gate controller
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct GateCheckResponse {
+    pub allowed: bool,
+    pub decision: GateDecision,
+    pub permit_id: Option<Uuid>,
+    pub permit_type: Option<PermitType>,
+    pub booking_id: Option<Uuid>,
+    /// Permit holder or booking owner, for gate staff
+    pub holder_name: Option<String>,
+}
+
+/// Find a permit letting `plate` into `lot_id` at `now`. Permits without a
+/// plate match any vehicle registered to the holder.
+async fn find_gate_permit(
+    db: &Database,
+    lot_id: Uuid,
+    plate: &str,
+    now: DateTime<Utc>,
+) -> anyhow::Result<Option<Permit>> {
+    let normalized = normalize_license_plate(plate);
+    for permit in db.list_permits().await? {
+        if !permit.valid_at(now) || !permit.covers_lot(lot_id) {
+            continue;
+        }
+        if permit.matches_plate(plate) {
+            return Ok(Some(permit));
+        }
+        if permit.license_plate.is_none()
+            && let Some(user_id) = permit.user_id
+        {
+            let vehicles = db.list_vehicles_by_user(&user_id.to_string()).await?;
+            if vehicles
+                .iter()
+                .any(|v| normalize_license_plate(&v.license_plate) == normalized)
+            {
+                return Ok(Some(permit));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// `POST /api/v1/gate/check` — entry decision for a plate
+#[utoipa::path(
+    post,
+    path = "/api/v1/gate/check",
+    tag = "Lots",
+    summary = "Check gate entry for a license plate",
+    description = "Called by ANPR cameras or barrier controllers. Grants entry for a valid \
+        permit covering the lot, or for a confirmed/active booking in the lot with that plate \
+        that covers the current time. Plates are compared ignoring case, spaces and dashes. \
+        Admins and managers of the lot only.",
+    request_body = GateCheckRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Entry decision", body = GateCheckResponse),
+        (status = 400, description = "Empty license plate"),
+        (status = 403, description = "Not an admin or manager of this lot"),
+    )
+)]
+pub async fn gate_check(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<GateCheckRequest>,
+) -> (StatusCode, Json<ApiResponse<GateCheckResponse>>) {
+    let state_guard = state.read().await;
+    if let Err((status, msg)) = check_lot_admin(&auth_user, &req.lot_id.to_string()) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let plate = normalize_license_plate(&req.license_plate);
+    if plate.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::ValidationError,
+                "License plate must not be empty",
+            )),
+        );
+    }
+
+    let now = Utc::now();
+    let db = &state_guard.db;
+    let permit = match find_gate_permit(db, req.lot_id, &plate, now).await {
+        Ok(permit) => permit,
+        Err(e) => {
+            tracing::error!("Gate check failed: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let response = if let Some(permit) = permit {
+        let holder_name = match (&permit.holder_name, permit.user_id) {
+            (Some(name), _) => Some(name.clone()),
+            (None, Some(user_id)) => db
+                .get_user(&user_id.to_string())
+                .await
+                .ok()
+                .flatten()
+                .map(|u| u.name),
+            (None, None) => None,
+        };
+        GateCheckResponse {
+            allowed: true,
+            decision: GateDecision::Permit,
+            permit_id: Some(permit.id),
+            permit_type: Some(permit.permit_type),
+            booking_id: None,
+            holder_name,
+        }
+    } else {
+        let booking = db
+            .list_bookings()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .find(|b| {
+                b.lot_id == req.lot_id
+                    && matches!(b.status, BookingStatus::Confirmed | BookingStatus::Active)
+                    && b.start_time <= now
+                    && now < b.end_time
+                    && normalize_license_plate(&b.vehicle.license_plate) == plate
+            });
+        match booking {
+            Some(booking) => GateCheckResponse {
+                allowed: true,
+                decision: GateDecision::Booking,
+                permit_id: None,
+                permit_type: None,
+                booking_id: Some(booking.id),
+                holder_name: db
+                    .get_user(&booking.user_id.to_string())
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|u| u.name),
+            },
+            None => GateCheckResponse {
+                allowed: false,
+                decision: GateDecision::Denied,
+                permit_id: None,
+                permit_type: None,
+                booking_id: None,
+                holder_name: None,
+            },
+        }
+    };
+
+    tracing::info!(
+        lot_id = %req.lot_id,
+        decision = ?response.decision,
+        "Gate check"
+    );
+    (StatusCode::OK, Json(ApiResponse::success(response)))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Expiry reminders
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Whether `permit` is due an expiry reminder at `now`.
+fn reminder_due(permit: &Permit, now: DateTime<Utc>) -> bool {
+    permit.user_id.is_some()
+        && permit.reminder_sent_at.is_none()
+        && !permit.revoked
+        && permit.valid_until > now
+        && permit.valid_until <= now + chrono::Duration::days(REMINDER_DAYS)
+}
+
+/// Email holders whose permits expire within [`REMINDER_DAYS`], once per
+/// permit. Returns how many reminders were queued.
+#[cfg(feature = "mod-email")]
+pub async fn send_expiry_reminders(
+    db: &Database,
+    org_name: &str,
+    now: DateTime<Utc>,
+) -> anyhow::Result<usize> {
+    let mut sent = 0;
+    for mut permit in db.list_permits().await? {
+        if !reminder_due(&permit, now) {
+            continue;
+        }
+        let Some(user_id) = permit.user_id else {
+            continue;
+        };
+        let Some(user) = db.get_user(&user_id.to_string()).await? else {
+            continue;
+        };
+        if !user.is_active || user.email.is_empty() {
+            continue;
+        }
+
+        let expires = permit.valid_until.format("%Y-%m-%d %H:%M UTC").to_string();
+        let html = crate::email::build_permit_expiry_email(
+            &user.name,
+            permit_type_label(permit.permit_type),
+            &expires,
+            org_name,
+        );
+        crate::email_queue::enqueue(db, &user.email, "Your parking permit expires soon", &html)
+            .await?;
+
+        permit.reminder_sent_at = Some(now);
+        db.save_permit(&permit).await?;
+        sent += 1;
+    }
+    Ok(sent)
+}
+
+/// Human-readable permit type for emails.
+#[cfg(feature = "mod-email")]
+const fn permit_type_label(permit_type: PermitType) -> &'static str {
+    match permit_type {
+        PermitType::Resident => "resident",
+        PermitType::Contractor => "contractor",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn permit(valid_until: DateTime<Utc>) -> Permit {
+        let now = Utc::now();
+        Permit {
+            id: Uuid::new_v4(),
+            permit_type: PermitType::Resident,
+            user_id: Some(Uuid::new_v4()),
+            license_plate: None,
+            holder_name: None,
+            lot_ids: Vec::new(),
+            valid_from: now - chrono::Duration::days(30),
+            valid_until,
+            revoked: false,
+            notes: None,
+            reminder_sent_at: None,
+            created_by: Uuid::new_v4(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    #[test]
+    fn test_reminder_due_only_inside_window_and_once() {
+        let now = Utc::now();
+        let mut expiring = permit(now + chrono::Duration::days(3));
+        assert!(reminder_due(&expiring, now));
+
+        expiring.reminder_sent_at = Some(now);
+        assert!(!reminder_due(&expiring, now));
+
+        assert!(!reminder_due(
+            &permit(now + chrono::Duration::days(30)),
+            now
+        ));
+        assert!(!reminder_due(&permit(now - chrono::Duration::days(1)), now));
+
+        let mut plate_only = permit(now + chrono::Duration::days(3));
+        plate_only.user_id = None;
+        assert!(!reminder_due(&plate_only, now));
+    }
+
+    #[test]
+    fn test_gate_decision_serialization() {
+        assert_eq!(
+            serde_json::to_string(&GateDecision::Permit).unwrap(),
+            "\"permit\""
+        );
+        assert_eq!(
+            serde_json::to_string(&GateDecision::Denied).unwrap(),
+            "\"denied\""
+        );
+    }
+}
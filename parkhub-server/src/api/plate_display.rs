@@ -0,0 +1,186 @@
+//! License plate masking per the `license_plate_display` server setting.
+//!
+//! Plates on bookings that belong to someone else are shown according to
+//! [`ServerConfig::license_plate_display`](crate::config::ServerConfig):
+//!
+//! | Setting | Mode | Example (`M-AB 1234`) |
+//! |---------|------|-----------------------|
+//! | 0 (show) | [`PlateDisplay::Full`] | `M-AB 1234` |
+//! | 1 (blur), 2 (redact) | [`PlateDisplay::Partial`] | `M-A* ****` |
+//! | 3 (hide) | [`PlateDisplay::Hidden`] | empty |
+//!
+//! The booking owner always sees their own plate, and super-admins always see
+//! every plate. Regular admins are subject to the setting like everyone else,
+//! so admin exports and invoices follow it too.
+//!
+//! Views of a single lot or booking use the lot's override of the setting
+//! (see [`super::lot_settings`]); views spanning lots use the global value.
+
+use uuid::Uuid;
+
+use parkhub_common::UserRole;
+
+use crate::AppState;
+
+use super::lot_settings::LotConfig;
+
+/// Leading alphanumeric characters kept visible in [`PlateDisplay::Partial`].
+const PARTIAL_VISIBLE_CHARS: usize = 2;
+
+/// How a license plate is rendered to a viewer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlateDisplay {
+    /// Plate returned verbatim
+    Full,
+    /// First characters visible, remaining letters and digits masked
+    Partial,
+    /// Plate omitted entirely
+    Hidden,
+}
+
+impl PlateDisplay {
+    /// Map the numeric `license_plate_display` config value to a mode.
+    /// Unknown values fall back to the most restrictive mode.
+    #[must_use]
+    pub const fn from_config(mode: u8) -> Self {
+        match mode {
+            0 => Self::Full,
+            1 | 2 => Self::Partial,
+            _ => Self::Hidden,
+        }
+    }
+
+    /// Render `plate` in this mode. Separators (spaces, dashes) are kept in
+    /// partial mode so the plate's shape stays recognisable.
+    #[must_use]
+    pub fn apply(self, plate: &str) -> String {
+        match self {
+            Self::Full => plate.to_string(),
+            Self::Hidden => String::new(),
+            Self::Partial => {
+                let mut visible = 0;
+                plate
+                    .chars()
+                    .map(|c| {
+                        if !c.is_alphanumeric() {
+                            c
+                        } else if visible < PARTIAL_VISIBLE_CHARS {
+                            visible += 1;
+                            c
+                        } else {
+                            '*'
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Plate visibility for one requesting user.
+#[derive(Debug, Clone, Copy)]
+pub struct PlateViewer {
+    user_id: Uuid,
+    display: PlateDisplay,
+}
+
+impl PlateViewer {
+    /// Viewer with the given role under the configured `license_plate_display`.
+    #[must_use]
+    pub fn new(user_id: Uuid, role: &UserRole, config_mode: u8) -> Self {
+        let display = if *role == UserRole::SuperAdmin {
+            PlateDisplay::Full
+        } else {
+            PlateDisplay::from_config(config_mode)
+        };
+        Self { user_id, display }
+    }
+
+    /// Resolve the viewer's role from the database. Unknown users get the
+    /// configured mode.
+    pub async fn load(state: &AppState, user_id: Uuid) -> Self {
+        let role = viewer_role(state, user_id).await;
+        Self::new(user_id, &role, state.config.license_plate_display)
+    }
+
+    /// Like [`Self::load`], with the mode in effect for `lot_id`.
+    pub async fn load_for_lot(state: &AppState, user_id: Uuid, lot_id: &str) -> Self {
+        let role = viewer_role(state, user_id).await;
+        let mode = LotConfig::resolve(state, lot_id)
+            .await
+            .license_plate_display;
+        Self::new(user_id, &role, mode)
+    }
+
+    /// Whether a booking owned by `owner_id` is the viewer's own.
+    #[must_use]
+    pub fn owns(&self, owner_id: Uuid) -> bool {
+        owner_id == self.user_id
+    }
+
+    /// Whether the viewer sees every plate in full, so may look records up
+    /// by plate.
+    #[must_use]
+    pub fn sees_full_plates(&self) -> bool {
+        self.display == PlateDisplay::Full
+    }
+
+    /// Render the plate of a booking owned by `owner_id`.
+    #[must_use]
+    pub fn plate(&self, owner_id: Uuid, plate: &str) -> String {
+        if self.owns(owner_id) {
+            plate.to_string()
+        } else {
+            self.display.apply(plate)
+        }
+    }
+}
+
+async fn viewer_role(state: &AppState, user_id: Uuid) -> UserRole {
+    state
+        .db
+        .get_user(&user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map_or(UserRole::User, |u| u.role)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_config() {
+        assert_eq!(PlateDisplay::from_config(0), PlateDisplay::Full);
+        assert_eq!(PlateDisplay::from_config(1), PlateDisplay::Partial);
+        assert_eq!(PlateDisplay::from_config(2), PlateDisplay::Partial);
+        assert_eq!(PlateDisplay::from_config(3), PlateDisplay::Hidden);
+        assert_eq!(PlateDisplay::from_config(42), PlateDisplay::Hidden);
+    }
+
+    #[test]
+    fn test_apply_modes() {
+        assert_eq!(PlateDisplay::Full.apply("M-AB 1234"), "M-AB 1234");
+        assert_eq!(PlateDisplay::Partial.apply("M-AB 1234"), "M-A* ****");
+        assert_eq!(PlateDisplay::Partial.apply("AB123"), "AB***");
+        assert_eq!(PlateDisplay::Partial.apply(""), "");
+        assert_eq!(PlateDisplay::Hidden.apply("M-AB 1234"), "");
+    }
+
+    #[test]
+    fn test_viewer_owner_and_super_admin_see_full_plate() {
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+
+        let user = PlateViewer::new(owner, &UserRole::User, 3);
+        assert_eq!(user.plate(owner, "M-AB 1234"), "M-AB 1234");
+        assert_eq!(user.plate(other, "M-AB 1234"), "");
+
+        let admin = PlateViewer::new(Uuid::new_v4(), &UserRole::Admin, 1);
+        assert_eq!(admin.plate(other, "M-AB 1234"), "M-A* ****");
+
+        let super_admin = PlateViewer::new(Uuid::new_v4(), &UserRole::SuperAdmin, 3);
+        assert_eq!(super_admin.plate(other, "M-AB 1234"), "M-AB 1234");
+    }
+}
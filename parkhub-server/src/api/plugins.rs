@@ -16,7 +16,7 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::SharedState;
 
@@ -456,7 +456,7 @@ pub async fn toggle_plugin(
         ),
         None => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Plugin not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Plugin not found")),
         ),
     }
 }
@@ -475,7 +475,7 @@ pub async fn get_plugin_config(
         Some(config) => (StatusCode::OK, Json(ApiResponse::success(config))),
         None => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Plugin not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Plugin not found")),
         ),
     }
 }
@@ -495,7 +495,7 @@ pub async fn update_plugin_config(
         Some(config) => (StatusCode::OK, Json(ApiResponse::success(config))),
         None => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Plugin not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Plugin not found")),
         ),
     }
 }
@@ -10,7 +10,7 @@ use web_push::{
     WebPushMessage, WebPushMessageBuilder,
 };
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use crate::db::PushSubscription;
 
@@ -89,7 +89,7 @@ pub async fn get_vapid_key() -> (StatusCode, Json<ApiResponse<VapidKeyResponse>>
         _ => (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::error(
-                "NOT_CONFIGURED",
+                ErrorCode::NotConfigured,
                 "VAPID keys are not configured",
             )),
         ),
@@ -119,7 +119,7 @@ pub async fn subscribe(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "endpoint must not be empty",
             )),
         );
@@ -128,7 +128,7 @@ pub async fn subscribe(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "keys.p256dh and keys.auth must not be empty",
             )),
         );
@@ -153,7 +153,10 @@ pub async fn subscribe(
             tracing::error!("Failed to save push subscription: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
@@ -192,7 +195,10 @@ pub async fn unsubscribe(
             tracing::error!("Failed to delete push subscriptions: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
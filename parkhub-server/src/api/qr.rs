@@ -15,7 +15,7 @@ use qrcode::QrCode;
 use serde::Serialize;
 use std::io::Cursor;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{AuthUser, SharedState};
 
@@ -65,7 +65,10 @@ pub async fn booking_qr_code(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::<()>::error("NOT_FOUND", "Booking not found")),
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::NotFound,
+                    "Booking not found",
+                )),
             )
                 .into_response();
         }
@@ -74,7 +77,7 @@ pub async fn booking_qr_code(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Internal server error",
                 )),
             )
@@ -98,7 +101,10 @@ pub async fn booking_qr_code(
     if booking.user_id != auth_user.user_id && !is_admin {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::<()>::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::<()>::error(
+                ErrorCode::Forbidden,
+                "Access denied",
+            )),
         )
             .into_response();
     }
@@ -137,7 +143,7 @@ pub async fn booking_qr_code(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "QR generation failed",
                 )),
             )
@@ -153,7 +159,7 @@ pub async fn booking_qr_code(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "QR generation failed",
                 )),
             )
@@ -171,7 +177,7 @@ pub async fn booking_qr_code(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::<()>::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "QR generation failed",
             )),
         )
@@ -238,7 +244,7 @@ pub async fn slot_qr_code(
             return (
                 StatusCode::NOT_FOUND,
                 Json(ApiResponse::<()>::error(
-                    "NOT_FOUND",
+                    ErrorCode::NotFound,
                     "Parking slot not found",
                 )),
             )
@@ -249,7 +255,7 @@ pub async fn slot_qr_code(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Internal server error",
                 )),
             )
@@ -262,7 +268,7 @@ pub async fn slot_qr_code(
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::<()>::error(
-                "NOT_FOUND",
+                ErrorCode::NotFound,
                 "Parking slot not found in this lot",
             )),
         )
@@ -285,7 +291,7 @@ pub async fn slot_qr_code(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "QR generation failed",
                 )),
             )
@@ -301,7 +307,7 @@ pub async fn slot_qr_code(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<()>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "QR generation failed",
                 )),
             )
@@ -319,7 +325,7 @@ pub async fn slot_qr_code(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::<()>::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "QR generation failed",
             )),
         )
@@ -64,7 +64,7 @@ pub async fn admin_rate_limit_stats(
     Extension(auth_user): Extension<AuthUser>,
 ) -> Result<Json<ApiResponse<RateLimitStats>>, (axum::http::StatusCode, &'static str)> {
     let state_guard = state.read().await;
-    check_admin(&state_guard, &auth_user).await?;
+    check_admin(&auth_user)?;
 
     // Read blocked counts from settings (incremented by rate limit middleware)
     let blocked_auth = read_blocked_count(&state_guard, "rate_blocked:auth").await;
@@ -130,7 +130,7 @@ pub async fn admin_rate_limit_history(
     Extension(auth_user): Extension<AuthUser>,
 ) -> Result<Json<ApiResponse<BlockedRequestHistory>>, (axum::http::StatusCode, &'static str)> {
     let state_guard = state.read().await;
-    check_admin(&state_guard, &auth_user).await?;
+    check_admin(&auth_user)?;
 
     let now = Utc::now();
     let mut bins = Vec::with_capacity(24);
@@ -24,7 +24,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode, UserRole};
 
 use crate::audit::{AuditEntry, AuditEventType};
 
@@ -290,7 +290,7 @@ pub async fn create_role(
             return (
                 StatusCode::FORBIDDEN,
                 Json(ApiResponse::error(
-                    "FORBIDDEN",
+                    ErrorCode::Forbidden,
                     "Super-admin access required",
                 )),
             );
@@ -300,14 +300,17 @@ pub async fn create_role(
     if req.name.trim().is_empty() {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_NAME", "Role name is required")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidName,
+                "Role name is required",
+            )),
         );
     }
 
     if let Some(err) = validate_permissions(&req.permissions) {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_PERMISSIONS", &err)),
+            Json(ApiResponse::error(ErrorCode::InvalidPermissions, &err)),
         );
     }
 
@@ -318,7 +321,7 @@ pub async fn create_role(
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "DUPLICATE_NAME",
+                ErrorCode::DuplicateName,
                 "A role with this name already exists",
             )),
         );
@@ -341,7 +344,10 @@ pub async fn create_role(
         tracing::error!("Failed to save roles: {e}");
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save role")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save role",
+            )),
         );
     }
 
@@ -390,7 +396,7 @@ pub async fn update_role(
             return (
                 StatusCode::FORBIDDEN,
                 Json(ApiResponse::error(
-                    "FORBIDDEN",
+                    ErrorCode::Forbidden,
                     "Super-admin access required",
                 )),
             );
@@ -402,7 +408,7 @@ pub async fn update_role(
         Err(_) => {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("INVALID_ID", "Invalid role ID")),
+                Json(ApiResponse::error(ErrorCode::InvalidId, "Invalid role ID")),
             );
         }
     };
@@ -412,7 +418,7 @@ pub async fn update_role(
     {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_PERMISSIONS", &err)),
+            Json(ApiResponse::error(ErrorCode::InvalidPermissions, &err)),
         );
     }
 
@@ -420,7 +426,7 @@ pub async fn update_role(
     let Some(role) = roles.iter_mut().find(|r| r.id == id) else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Role not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Role not found")),
         );
     };
 
@@ -441,7 +447,10 @@ pub async fn update_role(
         tracing::error!("Failed to save roles: {e}");
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save role")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save role",
+            )),
         );
     }
 
@@ -488,7 +497,7 @@ pub async fn delete_role(
             return (
                 StatusCode::FORBIDDEN,
                 Json(ApiResponse::error(
-                    "FORBIDDEN",
+                    ErrorCode::Forbidden,
                     "Super-admin access required",
                 )),
             );
@@ -500,7 +509,7 @@ pub async fn delete_role(
         Err(_) => {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("INVALID_ID", "Invalid role ID")),
+                Json(ApiResponse::error(ErrorCode::InvalidId, "Invalid role ID")),
             );
         }
     };
@@ -509,7 +518,7 @@ pub async fn delete_role(
     let Some(idx) = roles.iter().position(|r| r.id == id) else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Role not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Role not found")),
         );
     };
 
@@ -517,7 +526,7 @@ pub async fn delete_role(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "BUILT_IN",
+                ErrorCode::BuiltIn,
                 "Cannot delete built-in roles",
             )),
         );
@@ -529,7 +538,10 @@ pub async fn delete_role(
         tracing::error!("Failed to save roles: {e}");
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save roles")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save roles",
+            )),
         );
     }
 
@@ -570,7 +582,7 @@ pub async fn get_user_roles(
         Err(_) => {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("INVALID_ID", "Invalid user ID")),
+                Json(ApiResponse::error(ErrorCode::InvalidId, "Invalid user ID")),
             );
         }
     };
@@ -580,7 +592,7 @@ pub async fn get_user_roles(
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
     }
@@ -641,7 +653,7 @@ pub async fn assign_user_roles(
             return (
                 StatusCode::FORBIDDEN,
                 Json(ApiResponse::error(
-                    "FORBIDDEN",
+                    ErrorCode::Forbidden,
                     "Super-admin access required",
                 )),
             );
@@ -653,7 +665,7 @@ pub async fn assign_user_roles(
         Err(_) => {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("INVALID_ID", "Invalid user ID")),
+                Json(ApiResponse::error(ErrorCode::InvalidId, "Invalid user ID")),
             );
         }
     };
@@ -664,7 +676,7 @@ pub async fn assign_user_roles(
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
     }
@@ -682,7 +694,7 @@ pub async fn assign_user_roles(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_ROLE_IDS",
+                ErrorCode::InvalidRoleIds,
                 "One or more role IDs do not exist",
             )),
         );
@@ -692,7 +704,10 @@ pub async fn assign_user_roles(
         tracing::error!("Failed to save user roles: {e}");
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save roles")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save roles",
+            )),
         );
     }
 
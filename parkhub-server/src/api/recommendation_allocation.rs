@@ -13,11 +13,10 @@ use std::collections::BTreeSet;
 use uuid::Uuid;
 
 use crate::db::Database;
-use parkhub_common::ApiResponse;
-use parkhub_common::models::UserRole;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{
-    AuthUser, SharedState,
+    AuthUser, SharedState, check_admin,
     recommendations::{
         AllocationTransparencyMode, AutomatedDecisionNotice, RecommendationAllocationConfig,
         RecommendationEngineConfig, automated_decision_for_allocation,
@@ -186,10 +185,10 @@ pub async fn solve_exact_cover_allocation(
     Extension(auth_user): Extension<AuthUser>,
     Json(request): Json<ExactCoverAllocationRequest>,
 ) -> (StatusCode, Json<ApiResponse<ExactCoverAllocationResponse>>) {
-    let db = database_from_shared_state(&state).await;
-    if let Err((status, msg)) = check_admin_db(&db, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
+    let db = database_from_shared_state(&state).await;
     let engine = RecommendationEngineConfig::load(&db).await;
 
     // EU AI Act Art. 50 gate: refuse the algorithmic path when fifo_only.
@@ -197,7 +196,7 @@ pub async fn solve_exact_cover_allocation(
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "ALGORITHMIC_DISABLED",
+                ErrorCode::AlgorithmicDisabled,
                 "Algorithmic allocation is disabled; use the waitlist for rule-based slot assignment",
             )),
         );
@@ -251,7 +250,7 @@ fn audit_persist_failure_response() -> (StatusCode, Json<ApiResponse<ExactCoverA
     (
         StatusCode::INTERNAL_SERVER_ERROR,
         Json(ApiResponse::error(
-            "AUDIT_TRACE_PERSIST_FAILED",
+            ErrorCode::AuditTracePersistFailed,
             "Failed to persist exact-cover allocation audit trace",
         )),
     )
@@ -261,16 +260,6 @@ async fn database_from_shared_state(state: &SharedState) -> Database {
     state.read().await.db.clone()
 }
 
-async fn check_admin_db(
-    db: &Database,
-    auth_user: &AuthUser,
-) -> Result<(), (StatusCode, &'static str)> {
-    match db.get_user(&auth_user.user_id.to_string()).await {
-        Ok(Some(user)) if matches!(user.role, UserRole::Admin | UserRole::SuperAdmin) => Ok(()),
-        _ => Err((StatusCode::FORBIDDEN, "Admin access required")),
-    }
-}
-
 fn effective_limits(
     request_limits: Option<ExactCoverLimits>,
     allocation: &RecommendationAllocationConfig,
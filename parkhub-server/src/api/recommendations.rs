@@ -26,8 +26,8 @@ use std::{
 };
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
 use parkhub_common::models::{BookingStatus, SlotFeature, SlotStatus};
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::modules::config_setting_key;
 use super::{AuthUser, SharedState, check_admin};
@@ -921,7 +921,7 @@ pub async fn get_recommendations(
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "ALGORITHMIC_DISABLED",
+                ErrorCode::AlgorithmicDisabled,
                 "Algorithmic slot recommendations are disabled; use the waitlist for rule-based slot assignment",
             )),
         );
@@ -1234,8 +1234,8 @@ pub async fn get_recommendation_stats(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<RecommendationStats>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let lots = state_guard.db.list_parking_lots().await.unwrap_or_default();
@@ -0,0 +1,449 @@
+//! Data rectification requests (GDPR Art. 16).
+//!
+//! - `POST /api/v1/users/me/rectification`       — report a wrong profile value
+//! - `GET  /api/v1/users/me/rectification`       — the caller's requests
+//! - `GET  /api/v1/admin/rectifications`         — requests to handle (admin)
+//! - `PUT  /api/v1/admin/rectifications/{id}`    — mark corrected or rejected
+//!
+//! Users fix their name, email, phone and picture themselves, but some
+//! fields are out of their reach: the username, department and cost center,
+//! or an email address that a directory (LDAP, an identity provider) keeps
+//! overwriting. For those a user files a request naming the field and the
+//! correct value; admins are notified and close it as corrected or
+//! rejected with a note, which notifies the user. Both steps are audited.
+//! Correcting the data itself happens where it is managed — the user admin
+//! or the directory — so closing a request changes nothing but its status.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use parkhub_common::models::{Notification, NotificationType};
+use parkhub_common::{ApiResponse, ErrorCode, User, UserRole};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{Database, RectificationRequest, RectificationStatus};
+use crate::requests::{ResolveRectificationRequest, SubmitRectificationRequest};
+use crate::validation::ValidatedJson;
+
+use super::permissions::{CanManageUsers, RequirePermission};
+use super::{AuthUser, SharedState, server_error};
+
+/// Profile fields a rectification request may name.
+pub const RECTIFIABLE_FIELDS: &[&str] = &["username", "email", "name", "department", "cost_center"];
+
+/// Query parameters for `GET /api/v1/admin/rectifications`
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct RectificationListQuery {
+    /// `open`, `corrected` or `rejected`; all requests when omitted
+    pub status: Option<RectificationStatus>,
+}
+
+/// Current value of `field` on `user`.
+fn field_value(user: &User, field: &str) -> Option<String> {
+    match field {
+        "username" => Some(user.username.clone()),
+        "email" => Some(user.email.clone()),
+        "name" => Some(user.name.clone()),
+        "department" => user.department.clone(),
+        "cost_center" => user.cost_center.clone(),
+        _ => None,
+    }
+}
+
+fn error<T>(
+    status: StatusCode,
+    code: ErrorCode,
+    message: &str,
+) -> (StatusCode, Json<ApiResponse<T>>) {
+    (status, Json(ApiResponse::error(code, message)))
+}
+
+fn notification(user_id: Uuid, title: &str, message: String, request_id: Uuid) -> Notification {
+    Notification {
+        id: Uuid::new_v4(),
+        user_id,
+        notification_type: NotificationType::SystemMessage,
+        title: title.to_string(),
+        message,
+        data: Some(serde_json::json!({ "rectification_id": request_id })),
+        read: false,
+        created_at: Utc::now(),
+    }
+}
+
+/// Tell the admins responsible for the requester that a request came in.
+async fn notify_admins(db: &Database, request: &RectificationRequest) {
+    let admins = match db.list_users().await {
+        Ok(users) => users,
+        Err(e) => {
+            tracing::warn!("Failed to list admins for rectification request: {e}");
+            return;
+        }
+    };
+    for admin in admins.iter().filter(|u| {
+        u.is_active
+            && matches!(u.role, UserRole::Admin | UserRole::SuperAdmin)
+            && super::matches_tenant(request.tenant_id.as_deref(), u.tenant_id.as_deref())
+    }) {
+        let note = notification(
+            admin.id,
+            "Data rectification request",
+            format!(
+                "{} reports that their {} is wrong.",
+                request.username, request.field
+            ),
+            request.id,
+        );
+        if let Err(e) = db.save_notification(&note).await {
+            tracing::warn!(admin_id = %admin.id, "Failed to notify admin: {e}");
+        }
+    }
+}
+
+/// `POST /api/v1/users/me/rectification` — report a wrong value in a locked profile field
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/me/rectification",
+    tag = "Users",
+    summary = "Request data rectification (Art. 16)",
+    description = "Reports that a profile field the user cannot edit holds a wrong value and \
+        what it should be. Admins are notified. One open request per field.",
+    security(("bearer_auth" = [])),
+    request_body = SubmitRectificationRequest,
+    responses(
+        (status = 201, description = "Request filed", body = RectificationRequest),
+        (status = 400, description = "Unknown field or invalid value"),
+        (status = 409, description = "An open request for this field exists"),
+    )
+)]
+#[tracing::instrument(skip(state, req), fields(user_id = %auth_user.user_id, field = %req.field))]
+pub async fn submit_rectification(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(req): ValidatedJson<SubmitRectificationRequest>,
+) -> (StatusCode, Json<ApiResponse<RectificationRequest>>) {
+    // The write lock keeps two requests for the same field from both passing
+    // the duplicate check.
+    let state_guard = state.write().await;
+    let user = match state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+    {
+        Ok(Some(user)) => user,
+        Ok(None) => return error(StatusCode::NOT_FOUND, ErrorCode::NotFound, "User not found"),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    };
+
+    let requested_value = req.requested_value.trim().to_string();
+    let current_value = field_value(&user, &req.field);
+    if current_value.as_deref() == Some(requested_value.as_str()) {
+        return error(
+            StatusCode::BAD_REQUEST,
+            ErrorCode::InvalidInput,
+            "That is already the current value",
+        );
+    }
+    match state_guard
+        .db
+        .list_rectification_requests_by_user(user.id)
+        .await
+    {
+        Ok(requests)
+            if requests
+                .iter()
+                .any(|r| r.field == req.field && r.status == RectificationStatus::Open) =>
+        {
+            return error(
+                StatusCode::CONFLICT,
+                ErrorCode::RectificationPending,
+                "There is already an open request for this field",
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    }
+
+    let request = RectificationRequest {
+        id: Uuid::new_v4(),
+        user_id: user.id,
+        username: user.username.clone(),
+        tenant_id: user.tenant_id.clone(),
+        field: req.field,
+        current_value,
+        requested_value,
+        reason: req
+            .reason
+            .map(|r| r.trim().to_string())
+            .filter(|r| !r.is_empty()),
+        status: RectificationStatus::Open,
+        resolution_note: None,
+        resolved_by: None,
+        created_at: Utc::now(),
+        resolved_at: None,
+    };
+    if let Err(e) = state_guard.db.save_rectification_request(&request).await {
+        tracing::error!("Database error: {e}");
+        return server_error("Internal server error");
+    }
+
+    AuditEntry::new(AuditEventType::RectificationRequested)
+        .user(user.id, &user.username)
+        .resource("rectification", &request.id.to_string())
+        .details(serde_json::json!({
+            "field": request.field,
+            "reason": request.reason,
+        }))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+    notify_admins(&state_guard.db, &request).await;
+
+    (StatusCode::CREATED, Json(ApiResponse::success(request)))
+}
+
+/// `GET /api/v1/users/me/rectification` — the caller's rectification requests
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/rectification",
+    tag = "Users",
+    summary = "List my rectification requests",
+    description = "The caller's rectification requests with their status, newest first.",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Requests", body = Vec<RectificationRequest>))
+)]
+pub async fn list_my_rectifications(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<Vec<RectificationRequest>>>) {
+    let state_guard = state.read().await;
+    match state_guard
+        .db
+        .list_rectification_requests_by_user(auth_user.user_id)
+        .await
+    {
+        Ok(requests) => (StatusCode::OK, Json(ApiResponse::success(requests))),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            server_error("Internal server error")
+        }
+    }
+}
+
+/// `GET /api/v1/admin/rectifications` — rectification requests to handle
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/rectifications",
+    tag = "Admin",
+    summary = "List rectification requests (admin)",
+    description = "Rectification requests of the admin's tenant, newest first, optionally \
+        filtered by status.",
+    params(RectificationListQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Requests", body = Vec<RectificationRequest>),
+        (status = 403, description = "Forbidden"),
+    )
+)]
+pub async fn admin_list_rectifications(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Query(query): Query<RectificationListQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<RectificationRequest>>>) {
+    let state_guard = state.read().await;
+    let caller_tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
+    match state_guard.db.list_rectification_requests().await {
+        Ok(requests) => {
+            let requests = requests
+                .into_iter()
+                .filter(|r| {
+                    super::matches_tenant(r.tenant_id.as_deref(), caller_tenant_id.as_deref())
+                        && query.status.is_none_or(|status| r.status == status)
+                })
+                .collect();
+            (StatusCode::OK, Json(ApiResponse::success(requests)))
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            server_error("Internal server error")
+        }
+    }
+}
+
+/// `PUT /api/v1/admin/rectifications/{id}` — close a rectification request
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/rectifications/{id}",
+    tag = "Admin",
+    summary = "Resolve a rectification request (admin)",
+    description = "Marks an open request as corrected or rejected and notifies the user. \
+        Correct the data itself in the user admin or the directory that manages it first.",
+    params(("id" = Uuid, Path, description = "Rectification request ID")),
+    request_body = ResolveRectificationRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Request resolved", body = RectificationRequest),
+        (status = 400, description = "Invalid status or missing note"),
+        (status = 403, description = "Forbidden"),
+        (status = 404, description = "Request not found"),
+        (status = 409, description = "Request already resolved"),
+    )
+)]
+#[tracing::instrument(skip(state, req), fields(admin_id = %auth_user.user_id))]
+pub async fn resolve_rectification(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Path(id): Path<Uuid>,
+    ValidatedJson(req): ValidatedJson<ResolveRectificationRequest>,
+) -> (StatusCode, Json<ApiResponse<RectificationRequest>>) {
+    let state_guard = state.write().await;
+    let caller_tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
+    let mut request = match state_guard
+        .db
+        .get_rectification_request(&id.to_string())
+        .await
+    {
+        // Requests of other tenants are reported as missing
+        Ok(Some(r))
+            if super::matches_tenant(r.tenant_id.as_deref(), caller_tenant_id.as_deref()) =>
+        {
+            r
+        }
+        Ok(_) => {
+            return error(
+                StatusCode::NOT_FOUND,
+                ErrorCode::NotFound,
+                "Rectification request not found",
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    };
+    if request.status != RectificationStatus::Open {
+        return error(
+            StatusCode::CONFLICT,
+            ErrorCode::AlreadyResolved,
+            "This request has already been resolved",
+        );
+    }
+
+    request.status = req.status;
+    request.resolution_note = req
+        .note
+        .map(|n| n.trim().to_string())
+        .filter(|n| !n.is_empty());
+    request.resolved_by = Some(auth_user.user_id);
+    request.resolved_at = Some(Utc::now());
+    if let Err(e) = state_guard.db.save_rectification_request(&request).await {
+        tracing::error!("Database error: {e}");
+        return server_error("Internal server error");
+    }
+
+    let admin_username = state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default();
+    AuditEntry::new(AuditEventType::RectificationResolved)
+        .user(auth_user.user_id, &admin_username)
+        .resource("rectification", &request.id.to_string())
+        .details(serde_json::json!({
+            "user_id": request.user_id,
+            "field": request.field,
+            "status": request.status,
+            "note": request.resolution_note,
+        }))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    let message = match request.status {
+        RectificationStatus::Corrected => {
+            format!("Your {} has been corrected.", request.field)
+        }
+        _ => format!(
+            "Your request to correct your {} was rejected: {}",
+            request.field,
+            request.resolution_note.as_deref().unwrap_or_default()
+        ),
+    };
+    let note = notification(
+        request.user_id,
+        "Data rectification request",
+        message,
+        request.id,
+    );
+    if let Err(e) = state_guard.db.save_notification(&note).await {
+        tracing::warn!(user_id = %request.user_id, "Failed to notify user: {e}");
+    }
+
+    (StatusCode::OK, Json(ApiResponse::success(request)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parkhub_common::UserPreferences;
+
+    fn user() -> User {
+        User {
+            id: Uuid::new_v4(),
+            username: "jdoe".to_string(),
+            email: "jdoe@example.com".to_string(),
+            name: "Jane Doe".to_string(),
+            password_hash: "x".to_string(),
+            role: UserRole::User,
+            is_active: true,
+            phone: Some("+49 30 1234567".to_string()),
+            picture: None,
+            preferences: UserPreferences::default(),
+            credits_balance: 0,
+            credits_monthly_quota: 0,
+            credits_last_refilled: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+            tenant_id: None,
+            accessibility_needs: None,
+            cost_center: None,
+            department: Some("Sales".to_string()),
+            settings: None,
+            must_change_password: false,
+        }
+    }
+
+    #[test]
+    fn test_field_value() {
+        let user = user();
+        assert_eq!(field_value(&user, "username").as_deref(), Some("jdoe"));
+        assert_eq!(
+            field_value(&user, "email").as_deref(),
+            Some("jdoe@example.com")
+        );
+        assert_eq!(field_value(&user, "department").as_deref(), Some("Sales"));
+        assert_eq!(field_value(&user, "cost_center"), None);
+        // Self-editable fields are not rectified
+        assert_eq!(field_value(&user, "phone"), None);
+    }
+}
@@ -14,9 +14,9 @@ use serde::Deserialize;
 use uuid::Uuid;
 
 use parkhub_common::models::RecurringBooking;
-use parkhub_common::{ApiResponse, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode};
 
-use super::{AuthUser, SharedState};
+use super::{AuthUser, SharedState, check_admin};
 
 /// `GET /api/v1/recurring-bookings` — list user's recurring bookings
 #[utoipa::path(
@@ -41,7 +41,7 @@ pub async fn list_recurring_bookings(
         Err(e) => {
             tracing::error!("Failed to list recurring bookings: {}", e);
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to list recurring bookings",
             ))
         }
@@ -97,7 +97,7 @@ pub async fn create_recurring_booking(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to create recurring booking",
             )),
         );
@@ -132,14 +132,17 @@ pub async fn delete_recurring_booking(
     let Ok(id_uuid) = Uuid::parse_str(&id) else {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_ID", "Invalid ID format")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidId,
+                "Invalid ID format",
+            )),
         );
     };
 
     if !user_bookings.iter().any(|b| b.id == id_uuid) {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     }
 
@@ -148,7 +151,7 @@ pub async fn delete_recurring_booking(
         Ok(false) => (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::error(
-                "NOT_FOUND",
+                ErrorCode::NotFound,
                 "Recurring booking not found",
             )),
         ),
@@ -157,7 +160,7 @@ pub async fn delete_recurring_booking(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to delete recurring booking",
                 )),
             )
@@ -191,24 +194,16 @@ pub async fn update_recurring_booking(
     let Ok(id_uuid) = Uuid::parse_str(&id) else {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_ID", "Invalid ID format")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidId,
+                "Invalid ID format",
+            )),
         );
     };
 
     let state_guard = state.read().await;
 
-    // Fetch caller to check admin status
-    let Ok(Some(caller)) = state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    else {
-        return (
-            StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
-        );
-    };
-    let is_admin = caller.role == UserRole::Admin || caller.role == UserRole::SuperAdmin;
+    let is_admin = check_admin(&auth_user).is_ok();
 
     // Try ownership lookup first
     let user_bookings = state_guard
@@ -221,13 +216,13 @@ pub async fn update_recurring_booking(
         if !is_admin {
             return (
                 StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+                Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
             );
         }
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::error(
-                "NOT_FOUND",
+                ErrorCode::NotFound,
                 "Recurring booking not found",
             )),
         );
@@ -248,7 +243,7 @@ pub async fn update_recurring_booking(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update recurring booking",
             )),
         );
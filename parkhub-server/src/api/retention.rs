@@ -2,9 +2,9 @@
 //!
 //! # Architecture
 //!
-//! The engine uses a registry of [`RetentionSurface`] implementations. Slice 1
-//! ships one surface: the audit log. Future slices add bookings, EV sessions,
-//! etc. by implementing the trait.
+//! The engine uses a registry of [`RetentionSurface`] implementations: the
+//! audit log and the slot status history (`operational_presence`). Future
+//! slices add bookings, EV sessions, etc. by implementing the trait.
 //!
 //! # Retention classes
 //!
@@ -32,7 +32,7 @@ use axum::{
     http::StatusCode,
 };
 use chrono::{DateTime, Duration, Utc};
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 use utoipa::ToSchema;
@@ -197,6 +197,12 @@ pub trait RetentionSurface: Send + Sync {
     /// Human-readable surface name for evidence log entries.
     fn name(&self) -> &'static str;
 
+    /// Whether this surface holds records of `class`. Surfaces are only
+    /// purged (and only produce evidence entries) for classes they hold.
+    fn handles(&self, _class: RetentionClass) -> bool {
+        true
+    }
+
     /// Purge (or count, when `dry_run = true`) records belonging to `class`
     /// that are older than `older_than`.
     async fn purge(
@@ -291,6 +297,92 @@ impl RetentionSurface for AuditLogSurface {
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// SlotHistorySurface implementation
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Retention surface for the slot status history. Every entry belongs to
+/// `operational_presence`.
+pub struct SlotHistorySurface;
+
+#[async_trait::async_trait]
+impl RetentionSurface for SlotHistorySurface {
+    fn name(&self) -> &'static str {
+        "slot_status_history"
+    }
+
+    fn handles(&self, class: RetentionClass) -> bool {
+        class == RetentionClass::OperationalPresence
+    }
+
+    async fn purge(
+        &self,
+        _class: RetentionClass,
+        older_than: DateTime<Utc>,
+        dry_run: bool,
+        db: &Database,
+    ) -> anyhow::Result<PurgeResult> {
+        let to_purge = db.list_slot_history_before(older_than).await?;
+
+        let oldest = to_purge.iter().map(|c| c.changed_at).min();
+        let newest = to_purge.iter().map(|c| c.changed_at).max();
+        let count = if dry_run {
+            to_purge.len() as u64
+        } else {
+            db.delete_slot_history(&to_purge).await?
+        };
+
+        Ok(PurgeResult {
+            record_count: count,
+            oldest_deleted_at: oldest,
+            newest_deleted_at: newest,
+        })
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// LoginHistorySurface implementation
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Retention surface for per-user login history (IP addresses, user agents).
+/// Every entry belongs to `security_audit_log`.
+pub struct LoginHistorySurface;
+
+#[async_trait::async_trait]
+impl RetentionSurface for LoginHistorySurface {
+    fn name(&self) -> &'static str {
+        "login_history"
+    }
+
+    fn handles(&self, class: RetentionClass) -> bool {
+        class == RetentionClass::SecurityAuditLog
+    }
+
+    async fn purge(
+        &self,
+        _class: RetentionClass,
+        older_than: DateTime<Utc>,
+        dry_run: bool,
+        db: &Database,
+    ) -> anyhow::Result<PurgeResult> {
+        let to_purge = db.list_login_events_before(older_than).await?;
+
+        let oldest = to_purge.iter().map(|e| e.at).min();
+        let newest = to_purge.iter().map(|e| e.at).max();
+        let count = if dry_run {
+            to_purge.len() as u64
+        } else {
+            db.delete_login_events(&to_purge).await?
+        };
+
+        Ok(PurgeResult {
+            record_count: count,
+            oldest_deleted_at: oldest,
+            newest_deleted_at: newest,
+        })
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // RetentionEngine
 // ─────────────────────────────────────────────────────────────────────────────
@@ -323,10 +415,14 @@ pub struct RetentionEngine {
 }
 
 impl RetentionEngine {
-    /// Build the engine with the default slice-1 surface registry.
+    /// Build the engine with the default surface registry.
     pub fn new() -> Self {
         Self {
-            surfaces: vec![Box::new(AuditLogSurface)],
+            surfaces: vec![
+                Box::new(AuditLogSurface),
+                Box::new(SlotHistorySurface),
+                Box::new(LoginHistorySurface),
+            ],
         }
     }
 
@@ -342,7 +438,7 @@ impl RetentionEngine {
             let ttl = effective_ttl_days(db, class).await;
             let cutoff = now - Duration::days(i64::from(ttl));
 
-            for surface in &self.surfaces {
+            for surface in self.surfaces.iter().filter(|s| s.handles(class)) {
                 let purge = surface.purge(class, cutoff, dry_run, db).await;
                 match purge {
                     Ok(r) => {
@@ -484,8 +580,8 @@ pub async fn list_retention_policies(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<Vec<RetentionPolicyResponse>>>) {
     let guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let mut policies = Vec::new();
@@ -526,8 +622,8 @@ pub async fn update_retention_policy(
     Json(req): Json<UpdatePolicyRequest>,
 ) -> (StatusCode, Json<ApiResponse<RetentionPolicyResponse>>) {
     let guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let class = match class_str.parse::<RetentionClass>() {
@@ -536,7 +632,7 @@ pub async fn update_retention_policy(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "UNKNOWN_CLASS",
+                    ErrorCode::UnknownClass,
                     format!("Unknown retention class: {class_str}"),
                 )),
             );
@@ -550,7 +646,7 @@ pub async fn update_retention_policy(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "BELOW_STATUTORY_MINIMUM",
+                ErrorCode::BelowStatutoryMinimum,
                 format!(
                     "TTL for {class} must be at least {min} days (statutory minimum); \
                      requested {ttl}",
@@ -567,7 +663,7 @@ pub async fn update_retention_policy(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to persist policy",
             )),
         );
@@ -604,8 +700,8 @@ pub async fn run_retention(
     Json(req): Json<RunRequest>,
 ) -> (StatusCode, Json<ApiResponse<RunReport>>) {
     let guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let engine = RetentionEngine::new();
@@ -616,7 +712,7 @@ pub async fn run_retention(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "ENGINE_ERROR",
+                    ErrorCode::EngineError,
                     "Retention engine failed",
                 )),
             )
@@ -646,8 +742,8 @@ pub async fn list_retention_evidence(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<Vec<EvidenceEntry>>>) {
     let guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let all = match guard.db.list_audit_log(200).await {
@@ -657,7 +753,7 @@ pub async fn list_retention_evidence(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to load evidence",
                 )),
             );
@@ -739,7 +835,7 @@ mod tests {
             db,
             mdns: None,
             scheduler: None,
-            ws_events: crate::api::ws::EventBroadcaster::new(),
+            ws_events: crate::api::events::EventBroadcaster::new(),
             fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
             revocation_store: crate::jwt::TokenRevocationList::new(),
         }))
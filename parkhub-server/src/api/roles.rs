@@ -0,0 +1,563 @@
+//! Custom roles — lot-scoped permissions for regular users.
+//!
+//! - `GET    /api/v1/admin/custom-roles`                         — list roles
+//! - `POST   /api/v1/admin/custom-roles`                         — create a role
+//! - `PUT    /api/v1/admin/custom-roles/:id`                     — replace a role
+//! - `DELETE /api/v1/admin/custom-roles/:id`                     — delete a role
+//! - `GET    /api/v1/admin/custom-roles/:id/members`             — list holders
+//! - `POST   /api/v1/admin/custom-roles/:id/members`             — assign a user
+//! - `DELETE /api/v1/admin/custom-roles/:id/members/:user_id`    — unassign a user
+//! - `GET    /api/v1/users/me/permissions`                       — caller's permissions
+//!
+//! A [`Role`] grants lot-scoped permissions (`manage_lots`,
+//! `view_all_bookings`, `manage_bookings`, `book_for_visitors`) on a list of
+//! lots, or on every lot when the list is empty — e.g. "Lot Manager for
+//! Lot X". The auth middleware folds a user's roles into
+//! [`AuthUser::permissions`] on every request, and the lot, slot and booking
+//! handlers check them against the lot being touched. Unlike the `mod-rbac`
+//! role catalogue, these grants are enforced.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode, Permission, ResolvedPermissions, Role};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::Database;
+
+use super::permissions::{CanManageUsers, RequirePermission};
+use super::{AuthUser, SharedState, server_error};
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Types
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Request body for creating or replacing a custom role.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct RoleRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub permissions: Vec<Permission>,
+    /// Lots the permissions apply to; empty for every lot
+    #[serde(default)]
+    pub lot_ids: Vec<Uuid>,
+}
+
+/// Request body for assigning a custom role.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AssignRoleRequest {
+    pub user_id: Uuid,
+}
+
+/// A user holding a custom role.
+#[derive(Debug, Clone, Serialize, utoipa::ToSchema)]
+pub struct RoleMember {
+    pub user_id: Uuid,
+    pub username: String,
+    pub name: String,
+    pub email: String,
+}
+
+fn role_not_found<T>() -> (StatusCode, Json<ApiResponse<T>>) {
+    (
+        StatusCode::NOT_FOUND,
+        Json(ApiResponse::error(ErrorCode::NotFound, "Role not found")),
+    )
+}
+
+async fn username(db: &Database, user_id: Uuid) -> String {
+    db.get_user(&user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default()
+}
+
+/// Build the role `req` describes and check it. Permissions and lots are
+/// deduplicated; every lot must exist.
+async fn build_role<T>(
+    db: &Database,
+    id: Uuid,
+    created_at: chrono::DateTime<Utc>,
+    req: RoleRequest,
+) -> Result<Role, (StatusCode, Json<ApiResponse<T>>)> {
+    let mut permissions = req.permissions;
+    permissions.sort_unstable();
+    permissions.dedup();
+    let mut lot_ids = req.lot_ids;
+    lot_ids.sort_unstable();
+    lot_ids.dedup();
+
+    let role = Role {
+        id,
+        name: req.name.trim().to_string(),
+        description: req
+            .description
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty()),
+        permissions,
+        lot_ids,
+        created_at,
+        updated_at: Utc::now(),
+    };
+    if let Err(msg) = role.validate() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+        ));
+    }
+
+    for lot_id in &role.lot_ids {
+        match db.get_parking_lot(&lot_id.to_string()).await {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(
+                        ErrorCode::ValidationError,
+                        format!("Parking lot {lot_id} does not exist"),
+                    )),
+                ));
+            }
+            Err(e) => {
+                tracing::error!("Database error: {e}");
+                return Err(server_error("Internal server error"));
+            }
+        }
+    }
+
+    let duplicate = match db.list_roles().await {
+        Ok(roles) => roles
+            .iter()
+            .any(|r| r.id != role.id && r.name.eq_ignore_ascii_case(&role.name)),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return Err(server_error("Internal server error"));
+        }
+    };
+    if duplicate {
+        return Err((
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::Conflict,
+                "A role with this name already exists",
+            )),
+        ));
+    }
+
+    Ok(role)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Handlers
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `GET /api/v1/admin/custom-roles` — list custom roles
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/custom-roles",
+    tag = "Admin",
+    summary = "List custom roles",
+    description = "Returns all custom roles, sorted by name. Admin only.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Custom roles", body = Vec<Role>),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn list_roles(
+    State(state): State<SharedState>,
+    _: RequirePermission<CanManageUsers>,
+) -> (StatusCode, Json<ApiResponse<Vec<Role>>>) {
+    let state_guard = state.read().await;
+    match state_guard.db.list_roles().await {
+        Ok(roles) => (StatusCode::OK, Json(ApiResponse::success(roles))),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            server_error("Internal server error")
+        }
+    }
+}
+
+/// `POST /api/v1/admin/custom-roles` — create a custom role
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/custom-roles",
+    tag = "Admin",
+    summary = "Create a custom role",
+    description = "Defines a role granting lot-scoped permissions (manage_lots, \
+        view_all_bookings, manage_bookings, book_for_visitors) on the given lots, or on every lot \
+        when `lot_ids` is empty. Admin only.",
+    request_body = RoleRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Role created", body = Role),
+        (status = 400, description = "Invalid name, permissions or lots"),
+        (status = 403, description = "Admin access required"),
+        (status = 409, description = "Name already taken"),
+    )
+)]
+pub async fn create_role(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Json(req): Json<RoleRequest>,
+) -> (StatusCode, Json<ApiResponse<Role>>) {
+    let state_guard = state.write().await;
+
+    let role = match build_role(&state_guard.db, Uuid::new_v4(), Utc::now(), req).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
+    if let Err(e) = state_guard.db.save_role(&role).await {
+        tracing::error!("Database error: {e}");
+        return server_error("Internal server error");
+    }
+
+    AuditEntry::new(AuditEventType::RoleChanged)
+        .user(
+            auth_user.user_id,
+            &username(&state_guard.db, auth_user.user_id).await,
+        )
+        .resource("role", &role.id.to_string())
+        .detail(&format!("Created custom role {}", role.name))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::CREATED, Json(ApiResponse::success(role)))
+}
+
+/// `PUT /api/v1/admin/custom-roles/{id}` — replace a custom role
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/custom-roles/{id}",
+    tag = "Admin",
+    summary = "Update a custom role",
+    description = "Replaces a role's name, description, permissions and lots. \
+        Members keep the role and get the new grants on their next request. Admin only.",
+    params(("id" = Uuid, Path, description = "Role ID")),
+    request_body = RoleRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Role updated", body = Role),
+        (status = 400, description = "Invalid name, permissions or lots"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Role not found"),
+        (status = 409, description = "Name already taken"),
+    )
+)]
+pub async fn update_role(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<RoleRequest>,
+) -> (StatusCode, Json<ApiResponse<Role>>) {
+    let state_guard = state.write().await;
+
+    let existing = match state_guard.db.get_role(id).await {
+        Ok(Some(role)) => role,
+        Ok(None) => return role_not_found(),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    };
+    let role = match build_role(&state_guard.db, id, existing.created_at, req).await {
+        Ok(role) => role,
+        Err(response) => return response,
+    };
+    if let Err(e) = state_guard.db.save_role(&role).await {
+        tracing::error!("Database error: {e}");
+        return server_error("Internal server error");
+    }
+
+    AuditEntry::new(AuditEventType::RoleChanged)
+        .user(
+            auth_user.user_id,
+            &username(&state_guard.db, auth_user.user_id).await,
+        )
+        .resource("role", &role.id.to_string())
+        .detail(&format!("Updated custom role {}", role.name))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(role)))
+}
+
+/// `DELETE /api/v1/admin/custom-roles/{id}` — delete a custom role
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/custom-roles/{id}",
+    tag = "Admin",
+    summary = "Delete a custom role",
+    description = "Deletes a role and takes it away from all members. Admin only.",
+    params(("id" = Uuid, Path, description = "Role ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Role deleted"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Role not found"),
+    )
+)]
+pub async fn delete_role(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.write().await;
+
+    let role = match state_guard.db.get_role(id).await {
+        Ok(Some(role)) => role,
+        Ok(None) => return role_not_found(),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    };
+    if let Err(e) = state_guard.db.delete_role(id).await {
+        tracing::error!("Database error: {e}");
+        return server_error("Internal server error");
+    }
+
+    AuditEntry::new(AuditEventType::RoleChanged)
+        .user(
+            auth_user.user_id,
+            &username(&state_guard.db, auth_user.user_id).await,
+        )
+        .resource("role", &id.to_string())
+        .detail(&format!("Deleted custom role {}", role.name))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(())))
+}
+
+/// `GET /api/v1/admin/custom-roles/{id}/members` — list a role's holders
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/custom-roles/{id}/members",
+    tag = "Admin",
+    summary = "List custom role members",
+    description = "Returns the users holding a role, sorted by username. Admin only.",
+    params(("id" = Uuid, Path, description = "Role ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Role members", body = Vec<RoleMember>),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Role not found"),
+    )
+)]
+pub async fn list_role_members(
+    State(state): State<SharedState>,
+    _: RequirePermission<CanManageUsers>,
+    Path(id): Path<Uuid>,
+) -> (StatusCode, Json<ApiResponse<Vec<RoleMember>>>) {
+    let state_guard = state.read().await;
+
+    match state_guard.db.get_role(id).await {
+        Ok(Some(_)) => {}
+        Ok(None) => return role_not_found(),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    }
+    let user_ids = match state_guard.db.list_role_members(id).await {
+        Ok(ids) => ids,
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    };
+
+    let mut members = Vec::new();
+    for user_id in user_ids {
+        if let Ok(Some(user)) = state_guard.db.get_user(&user_id.to_string()).await {
+            members.push(RoleMember {
+                user_id: user.id,
+                username: user.username,
+                name: user.name,
+                email: user.email,
+            });
+        }
+    }
+    members.sort_by(|a, b| a.username.cmp(&b.username));
+
+    (StatusCode::OK, Json(ApiResponse::success(members)))
+}
+
+/// `POST /api/v1/admin/custom-roles/{id}/members` — give a user a role
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/custom-roles/{id}/members",
+    tag = "Admin",
+    summary = "Assign a custom role",
+    description = "Gives a user the role's permissions on its lots. Idempotent. Admin only.",
+    params(("id" = Uuid, Path, description = "Role ID")),
+    request_body = AssignRoleRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Role assigned", body = RoleMember),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Role or user not found"),
+    )
+)]
+pub async fn assign_role(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Path(id): Path<Uuid>,
+    Json(req): Json<AssignRoleRequest>,
+) -> (StatusCode, Json<ApiResponse<RoleMember>>) {
+    let state_guard = state.write().await;
+
+    let role = match state_guard.db.get_role(id).await {
+        Ok(Some(role)) => role,
+        Ok(None) => return role_not_found(),
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    };
+    let user = match state_guard.db.get_user(&req.user_id.to_string()).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    };
+
+    match state_guard.db.assign_role(user.id, role.id).await {
+        Ok(true) => {
+            AuditEntry::new(AuditEventType::RoleChanged)
+                .user(
+                    auth_user.user_id,
+                    &username(&state_guard.db, auth_user.user_id).await,
+                )
+                .resource("user", &user.id.to_string())
+                .detail(&format!(
+                    "Assigned custom role {} to {}",
+                    role.name, user.username
+                ))
+                .log()
+                .persist(&state_guard.db)
+                .await;
+        }
+        Ok(false) => {}
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    }
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(RoleMember {
+            user_id: user.id,
+            username: user.username,
+            name: user.name,
+            email: user.email,
+        })),
+    )
+}
+
+/// `DELETE /api/v1/admin/custom-roles/{id}/members/{user_id}` — take a role away
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/custom-roles/{id}/members/{user_id}",
+    tag = "Admin",
+    summary = "Unassign a custom role",
+    description = "Takes a role away from a user. Admin only.",
+    params(
+        ("id" = Uuid, Path, description = "Role ID"),
+        ("user_id" = Uuid, Path, description = "Member user ID"),
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Role unassigned"),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "User does not hold this role"),
+    )
+)]
+pub async fn unassign_role(
+    State(state): State<SharedState>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageUsers>,
+    Path((id, user_id)): Path<(Uuid, Uuid)>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.write().await;
+
+    match state_guard.db.unassign_role(user_id, id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "User does not hold this role",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return server_error("Internal server error");
+        }
+    }
+
+    let role_name = match state_guard.db.get_role(id).await {
+        Ok(Some(role)) => role.name,
+        _ => id.to_string(),
+    };
+    AuditEntry::new(AuditEventType::RoleChanged)
+        .user(
+            auth_user.user_id,
+            &username(&state_guard.db, auth_user.user_id).await,
+        )
+        .resource("user", &user_id.to_string())
+        .detail(&format!(
+            "Unassigned custom role {} from {}",
+            role_name,
+            username(&state_guard.db, user_id).await
+        ))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(())))
+}
+
+/// `GET /api/v1/users/me/permissions` — what the caller may do
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/permissions",
+    tag = "Users",
+    summary = "Get my permissions",
+    description = "Returns the caller's resolved permissions: those of their built-in \
+        role, their custom roles and any lot manager assignments, each with the lots \
+        it applies to.",
+    security(("bearer_auth" = [])),
+    responses((status = 200, description = "Resolved permissions", body = ResolvedPermissions))
+)]
+pub async fn my_permissions(
+    Extension(auth_user): Extension<AuthUser>,
+) -> Json<ApiResponse<ResolvedPermissions>> {
+    Json(ApiResponse::success(auth_user.permissions))
+}
@@ -18,7 +18,7 @@ use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::SharedState;
 
@@ -223,7 +223,7 @@ pub async fn create_schedule(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "bad_request",
+                ErrorCode::InvalidInput,
                 "Schedule name is required",
             )),
         );
@@ -232,7 +232,7 @@ pub async fn create_schedule(
     if let Err(msg) = validate_recipients(&req.recipients) {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("bad_request", msg)),
+            Json(ApiResponse::error(ErrorCode::InvalidInput, msg)),
         );
     }
 
@@ -264,7 +264,7 @@ pub async fn update_schedule(
     {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("bad_request", msg)),
+            Json(ApiResponse::error(ErrorCode::InvalidInput, msg)),
         );
     }
 
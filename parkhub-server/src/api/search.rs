@@ -0,0 +1,226 @@
+//! Admin search across users, bookings and lots.
+//!
+//! - `GET /api/v1/admin/search?q=` — ranked results tagged with their type
+//!
+//! Matching runs against the token index in `db/search.rs`, which is kept
+//! current on every write. Users match on name, username, email and the
+//! plates of their vehicles; bookings on ID, plate, vehicle make, model and
+//! colour, floor and start day (weekday, month and date); lots on name and
+//! address. So "red golf tuesday" finds that booking.
+//!
+//! Booking titles are plates, masked per `license_plate_display` like other
+//! admin views (see [`super::plate_display`]). Admins who don't see plates in
+//! full can't search by plate either, as a hit would confirm the plate.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+use parkhub_common::{ApiResponse, ErrorCode};
+
+use crate::db::{Database, SearchHit, SearchKind};
+
+use super::plate_display::PlateViewer;
+use super::{AuthUser, SharedState, check_admin};
+
+/// Results returned when `limit` is not given.
+const DEFAULT_SEARCH_LIMIT: usize = 20;
+
+/// Upper bound for `limit`.
+const MAX_SEARCH_LIMIT: usize = 100;
+
+/// Longest accepted query, in characters.
+const MAX_QUERY_LEN: usize = 200;
+
+/// Query parameters for the admin search.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct SearchQuery {
+    /// Search text, e.g. a name, email, plate, booking ID or "red golf tuesday"
+    pub q: String,
+    /// Comma-separated result types to include (`user`, `booking`, `lot`);
+    /// all when omitted
+    pub types: Option<String>,
+    /// Maximum results (default 20, max 100)
+    pub limit: Option<usize>,
+}
+
+/// One search result.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct SearchResult {
+    #[serde(rename = "type")]
+    pub kind: SearchKind,
+    pub id: String,
+    /// Display title: user name, booking plate or lot name
+    pub title: String,
+    /// Secondary line: email, booking time and status, or lot address
+    pub subtitle: String,
+    /// Number of query words the record matched
+    pub matched_terms: usize,
+    /// Relevance within the matched-word tier; higher is better
+    pub score: u32,
+}
+
+/// Parse the `types` filter. Unknown names are an error, not ignored.
+fn parse_types(types: Option<&str>) -> Result<Vec<SearchKind>, String> {
+    types
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(|t| SearchKind::from_name(t).ok_or_else(|| format!("Unknown result type '{t}'")))
+        .collect()
+}
+
+/// Load the record behind `hit`, with plates as `viewer` may see them.
+/// `None` if it no longer exists.
+async fn resolve(
+    db: &Database,
+    viewer: &PlateViewer,
+    hit: SearchHit,
+) -> anyhow::Result<Option<SearchResult>> {
+    let (title, subtitle) = match hit.kind {
+        SearchKind::User => match db.get_user(&hit.id).await? {
+            Some(user) => (user.name, user.email),
+            None => return Ok(None),
+        },
+        SearchKind::Booking => match db.get_booking(&hit.id).await? {
+            Some(booking) => (
+                viewer.plate(booking.user_id, &booking.vehicle.license_plate),
+                format!(
+                    "{} – {} ({:?})",
+                    booking.start_time.format("%Y-%m-%d %H:%M"),
+                    booking.end_time.format("%H:%M"),
+                    booking.status
+                ),
+            ),
+            None => return Ok(None),
+        },
+        SearchKind::Lot => match db.get_parking_lot(&hit.id).await? {
+            Some(lot) => (lot.name, lot.address),
+            None => return Ok(None),
+        },
+    };
+    Ok(Some(SearchResult {
+        kind: hit.kind,
+        id: hit.id,
+        title,
+        subtitle,
+        matched_terms: hit.matched_terms,
+        score: hit.score,
+    }))
+}
+
+/// `GET /api/v1/admin/search`
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/search",
+    tag = "Admin",
+    summary = "Search users, bookings and lots",
+    description = "Full-text search over users (name, username, email, vehicle plates), \
+        bookings (ID, plate, vehicle, floor, start day) and lots (name, address). Words \
+        of three or more characters also match as prefixes. Results matching more words \
+        come first. Booking plates follow license_plate_display, and plates only match \
+        for admins who see them in full. Admin only.",
+    params(SearchQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Ranked results", body = Vec<SearchResult>),
+        (status = 400, description = "Empty or too long query, or unknown type"),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn admin_search(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<SearchQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<SearchResult>>>) {
+    let state_guard = state.read().await;
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let q = query.q.trim();
+    if q.is_empty() || q.chars().count() > MAX_QUERY_LEN {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                format!("Query must be 1 to {MAX_QUERY_LEN} characters"),
+            )),
+        );
+    }
+    let kinds = match parse_types(query.types.as_deref()) {
+        Ok(kinds) => kinds,
+        Err(msg) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::InvalidInput, msg)),
+            );
+        }
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+
+    let viewer = PlateViewer::load(&state_guard, auth_user.user_id).await;
+    let hits = match state_guard
+        .db
+        .search(q, &kinds, limit, viewer.sees_full_plates())
+        .await
+    {
+        Ok(hits) => hits,
+        Err(e) => {
+            tracing::error!("Search failed: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let mut results = Vec::with_capacity(hits.len());
+    for hit in hits {
+        match resolve(&state_guard.db, &viewer, hit).await {
+            Ok(Some(result)) => results.push(result),
+            Ok(None) => {}
+            Err(e) => {
+                tracing::error!("Failed to load search result: {e}");
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Internal server error",
+                    )),
+                );
+            }
+        }
+    }
+
+    (StatusCode::OK, Json(ApiResponse::success(results)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_types() {
+        assert!(parse_types(None).unwrap().is_empty());
+        assert_eq!(
+            parse_types(Some("user, lot")).unwrap(),
+            [SearchKind::User, SearchKind::Lot]
+        );
+        assert!(parse_types(Some("user,vehicle")).is_err());
+    }
+}
@@ -4,22 +4,30 @@
 // db access goes through its own inner RwLock. See workspace lint config.
 #![allow(clippy::significant_drop_tightening)]
 
-use axum::{Extension, Json, extract::State, http::StatusCode, response::Response};
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::Response,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration as StdDuration, Instant, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, AuthTokens, LoginResponse};
+use parkhub_common::{ApiResponse, AuthTokens, ErrorCode, LoginResponse, User};
 
 use crate::audit::{AuditEntry, AuditEventType};
-use crate::db::Session;
+use crate::db::{LoginEvent, MAX_LOGINS_PER_USER, Session};
 use crate::metrics;
 
 use super::auth::{build_auth_cookie, with_auth_cookie};
-use super::{AuthUser, SharedState, check_admin, generate_access_token, verify_password};
+use super::{
+    AuthUser, SharedState, check_admin, generate_access_token, session_tokens, verify_password,
+};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // 2FA / TOTP
@@ -154,12 +162,15 @@ impl TwoFactorTempTokenStore {
     responses(
         (status = 200, description = "Login successful"),
         (status = 401, description = "Invalid or expired temp token / invalid TOTP code"),
+        (status = 409, description = "Session limit reached (`session_limit_policy = \"reject\"`)"),
     )
 )]
-#[tracing::instrument(skip(state, store, request))]
+#[tracing::instrument(skip(state, store, headers, connect_info, request))]
 pub async fn two_factor_login(
     State(state): State<SharedState>,
     Extension(store): Extension<Arc<TwoFactorTempTokenStore>>,
+    headers: HeaderMap,
+    connect_info: Option<Extension<axum::extract::ConnectInfo<SocketAddr>>>,
     Json(request): Json<TwoFactorLoginRequest>,
 ) -> Response {
     use axum::response::IntoResponse;
@@ -172,7 +183,7 @@ pub async fn two_factor_login(
             return (
                 StatusCode::UNAUTHORIZED,
                 Json(ApiResponse::<LoginResponse>::error(
-                    "INVALID_TEMP_TOKEN",
+                    ErrorCode::InvalidTempToken,
                     "Temporary token is invalid or expired",
                 )),
             )
@@ -181,6 +192,7 @@ pub async fn two_factor_login(
     };
 
     let state_guard = state.read().await;
+    let client = LoginClient::from_request(&headers, connect_info.map(|Extension(ci)| ci.0));
 
     // Verify the TOTP code
     if !verify_2fa_code(&state_guard, entry.user_id, &entry.email, &request.code).await {
@@ -188,11 +200,14 @@ pub async fn two_factor_login(
             .user(entry.user_id, &entry.username)
             .error("Invalid 2FA code")
             .log();
+        if let Ok(Some(user)) = state_guard.db.get_user(&entry.user_id.to_string()).await {
+            record_login(&state_guard, &user, &client, Some("invalid_2fa_code")).await;
+        }
         metrics::record_auth_event("2fa_login", false);
         return (
             StatusCode::UNAUTHORIZED,
             Json(ApiResponse::<LoginResponse>::error(
-                "INVALID_2FA_CODE",
+                ErrorCode::Invalid2faCode,
                 "The verification code is invalid or expired",
             )),
         )
@@ -200,25 +215,48 @@ pub async fn two_factor_login(
     }
 
     // 2FA passed — issue full session (same path as normal login success)
+    match session_tokens::make_room_for_session(&state_guard, entry.user_id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            if let Ok(Some(user)) = state_guard.db.get_user(&entry.user_id.to_string()).await {
+                record_login(&state_guard, &user, &client, Some("session_limit_reached")).await;
+            }
+            metrics::record_auth_event("2fa_login", false);
+            return super::auth::session_limit_response();
+        }
+        Err(e) => {
+            tracing::error!("Failed to check session limit: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<LoginResponse>::error(
+                    ErrorCode::ServerError,
+                    "Failed to create session",
+                )),
+            )
+                .into_response();
+        }
+    }
     let session = Session::new(
         entry.user_id,
         entry.session_hours,
         &entry.username,
         &entry.role,
-    );
-    let access_token = generate_access_token();
-
-    if let Err(e) = state_guard.db.save_session(&access_token, &session).await {
-        tracing::error!("Failed to save session after 2FA: {}", e);
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<LoginResponse>::error(
-                "SERVER_ERROR",
-                "Failed to create session",
-            )),
-        )
-            .into_response();
-    }
+    )
+    .with_client(Some(client.ip.clone()), client.user_agent.clone());
+    let access_token = match session_tokens::start_session(&state_guard.db, &session).await {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to save session after 2FA: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<LoginResponse>::error(
+                    ErrorCode::ServerError,
+                    "Failed to create session",
+                )),
+            )
+                .into_response();
+        }
+    };
 
     // Fetch user for response (strip password hash)
     let mut response_user = match state_guard.db.get_user(&entry.user_id.to_string()).await {
@@ -227,13 +265,14 @@ pub async fn two_factor_login(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::<LoginResponse>::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to load user",
                 )),
             )
                 .into_response();
         }
     };
+    record_login(&state_guard, &response_user, &client, None).await;
     response_user.password_hash = String::new();
 
     let audit = AuditEntry::new(AuditEventType::LoginSuccess)
@@ -288,7 +327,7 @@ pub async fn two_factor_setup(
     else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     };
 
@@ -305,7 +344,7 @@ pub async fn two_factor_setup(
             return (
                 StatusCode::CONFLICT,
                 Json(ApiResponse::error(
-                    "2FA_ALREADY_ENABLED",
+                    ErrorCode::TwoFactorAlreadyEnabled,
                     "Two-factor authentication is already enabled",
                 )),
             );
@@ -329,7 +368,7 @@ pub async fn two_factor_setup(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to generate 2FA secret",
                 )),
             );
@@ -349,7 +388,7 @@ pub async fn two_factor_setup(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to generate QR code",
                 )),
             );
@@ -361,7 +400,10 @@ pub async fn two_factor_setup(
         tracing::error!("Failed to store TOTP secret: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Internal server error",
+            )),
         );
     }
 
@@ -404,7 +446,7 @@ pub async fn two_factor_verify(
     else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     };
 
@@ -416,7 +458,7 @@ pub async fn two_factor_verify(
             return (
                 StatusCode::NOT_FOUND,
                 Json(ApiResponse::error(
-                    "NO_PENDING_SETUP",
+                    ErrorCode::NoPendingSetup,
                     "No pending 2FA setup found. Call /2fa/setup first.",
                 )),
             );
@@ -430,7 +472,10 @@ pub async fn two_factor_verify(
             tracing::error!("Failed to decode TOTP secret: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -450,7 +495,10 @@ pub async fn two_factor_verify(
             tracing::error!("Failed to create TOTP for verification: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -461,7 +509,7 @@ pub async fn two_factor_verify(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_CODE",
+                ErrorCode::InvalidCode,
                 "The verification code is invalid, expired, or already used",
             )),
         );
@@ -473,7 +521,10 @@ pub async fn two_factor_verify(
         tracing::error!("Failed to enable 2FA: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to enable 2FA")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to enable 2FA",
+            )),
         );
     }
 
@@ -518,7 +569,7 @@ pub async fn two_factor_disable(
     else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     };
 
@@ -527,7 +578,7 @@ pub async fn two_factor_disable(
         return (
             StatusCode::UNAUTHORIZED,
             Json(ApiResponse::error(
-                "INVALID_PASSWORD",
+                ErrorCode::InvalidPassword,
                 "Current password is incorrect",
             )),
         );
@@ -776,10 +827,13 @@ pub async fn get_password_policy(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<PasswordPolicy>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
     let policy = load_password_policy(&state_guard.db).await;
@@ -804,10 +858,13 @@ pub async fn update_password_policy(
     Json(policy): Json<PasswordPolicy>,
 ) -> (StatusCode, Json<ApiResponse<PasswordPolicy>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -816,7 +873,7 @@ pub async fn update_password_policy(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_POLICY",
+                ErrorCode::InvalidPolicy,
                 "Minimum length must be between 4 and 128",
             )),
         );
@@ -827,7 +884,10 @@ pub async fn update_password_policy(
         tracing::error!("Failed to save password policy: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save policy")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save policy",
+            )),
         );
     }
 
@@ -866,32 +926,145 @@ impl LoginHistory {
     }
 }
 
-/// Record a login attempt in history.
-#[allow(dead_code)]
+impl LoginHistory {
+    /// The most recent [`Self::MAX_ENTRIES`] of `events` (given newest first).
+    #[must_use]
+    pub fn from_events(events: &[LoginEvent]) -> Self {
+        let mut history = Self::default();
+        for event in events.iter().take(Self::MAX_ENTRIES).rev() {
+            history.add(LoginHistoryEntry::from(event));
+        }
+        history
+    }
+}
+
+impl From<&LoginEvent> for LoginHistoryEntry {
+    fn from(event: &LoginEvent) -> Self {
+        Self {
+            timestamp: event.at,
+            ip_address: event.ip.clone(),
+            user_agent: event.user_agent.clone().unwrap_or_default(),
+            success: event.success,
+        }
+    }
+}
+
+/// Longest user agent stored with a login event.
+const MAX_USER_AGENT_LEN: usize = 256;
+
+/// Where a login attempt came from.
+#[derive(Debug, Clone)]
+pub struct LoginClient {
+    pub ip: String,
+    pub user_agent: Option<String>,
+}
+
+impl LoginClient {
+    /// Resolve the client IP (honouring `X-Forwarded-For` only behind a
+    /// trusted proxy, as the rate limiter does) and the `User-Agent` header.
+    #[must_use]
+    pub fn from_request(headers: &HeaderMap, peer: Option<SocketAddr>) -> Self {
+        let forwarded_for = headers.get("x-forwarded-for").and_then(|v| v.to_str().ok());
+        let ip = crate::rate_limit::per_ip::get_client_ip(peer.as_ref(), forwarded_for);
+        let user_agent = headers
+            .get(header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|ua| !ua.is_empty())
+            .map(|ua| ua.chars().take(MAX_USER_AGENT_LEN).collect());
+        Self {
+            ip: ip.to_string(),
+            user_agent,
+        }
+    }
+}
+
+/// Whether a successful login from `client` comes from a device not seen
+/// before. The very first login never counts as new: there is nothing to
+/// compare against.
+fn is_new_device(previous: &[LoginEvent], client: &LoginClient) -> bool {
+    let mut successes = previous.iter().filter(|e| e.success).peekable();
+    successes.peek().is_some()
+        && !successes.any(|e| e.ip == client.ip && e.user_agent == client.user_agent)
+}
+
+/// Record a login attempt in the user's history. `failure_reason` is `None`
+/// for successful logins. A successful login from an unseen IP/user-agent
+/// combination emails the user unless they opted out
+/// (`email_new_login` notification preference). Failures are logged, never
+/// propagated — history must not block a login.
 pub async fn record_login(
-    db: &crate::db::Database,
-    user_id: Uuid,
-    ip: &str,
-    user_agent: &str,
-    success: bool,
+    state: &crate::AppState,
+    user: &User,
+    client: &LoginClient,
+    failure_reason: Option<&str>,
 ) {
-    let key = format!("login_history:{user_id}");
-    let mut history = match db.get_setting(&key).await {
-        Ok(Some(val)) => serde_json::from_str::<LoginHistory>(&val).unwrap_or_default(),
-        _ => LoginHistory::default(),
-    };
+    let success = failure_reason.is_none();
+    let new_device = success
+        && match state
+            .db
+            .list_login_events(&user.id.to_string(), MAX_LOGINS_PER_USER)
+            .await
+        {
+            Ok(previous) => is_new_device(&previous, client),
+            Err(e) => {
+                tracing::warn!("Failed to load login history: {}", e);
+                false
+            }
+        };
 
-    history.add(LoginHistoryEntry {
-        timestamp: Utc::now(),
-        ip_address: ip.to_string(),
-        user_agent: user_agent.to_string(),
+    let event = LoginEvent {
+        id: Uuid::new_v4(),
+        user_id: user.id,
+        at: Utc::now(),
+        ip: client.ip.clone(),
+        user_agent: client.user_agent.clone(),
         success,
-    });
-
-    let json = serde_json::to_string(&history).unwrap_or_default();
-    if let Err(e) = db.set_setting(&key, &json).await {
+        failure_reason: failure_reason.map(str::to_string),
+        new_device,
+    };
+    if let Err(e) = state.db.record_login_event(&event).await {
         tracing::warn!("Failed to record login history: {}", e);
     }
+
+    #[cfg(feature = "mod-email")]
+    if new_device
+        && super::admin_ext::load_notification_preferences(&state.db, user.id)
+            .await
+            .email_new_login
+    {
+        let html = crate::email::build_new_login_email(
+            &user.name,
+            &event.at.format("%Y-%m-%d %H:%M UTC").to_string(),
+            &event.ip,
+            event.user_agent.as_deref().unwrap_or("Unknown"),
+            &state.config.organization_name,
+        );
+        if let Err(e) = crate::email_queue::enqueue(
+            &state.db,
+            &user.email,
+            "New sign-in to your account — ParkHub",
+            &html,
+        )
+        .await
+        {
+            tracing::warn!(user_id = %user.id, "Failed to queue new-login alert: {}", e);
+        }
+    }
+}
+
+/// Load the compact history returned by the legacy login-history endpoints.
+async fn load_login_history(db: &crate::db::Database, user_id: &str) -> Vec<LoginHistoryEntry> {
+    match db
+        .list_login_events(user_id, LoginHistory::MAX_ENTRIES)
+        .await
+    {
+        Ok(events) => LoginHistory::from_events(&events).entries,
+        Err(e) => {
+            tracing::warn!("Failed to load login history: {}", e);
+            Vec::new()
+        }
+    }
 }
 
 /// `GET /api/v1/auth/login-history` — Get login history for the current user.
@@ -900,7 +1073,8 @@ pub async fn record_login(
     path = "/api/v1/auth/login-history",
     tag = "Authentication",
     summary = "Get login history",
-    description = "Returns the last 10 login attempts for the current user.",
+    description = "Returns the last 10 login attempts for the current user. \
+        See `/api/v1/users/me/logins` for the full history.",
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "Login history"),
@@ -911,18 +1085,64 @@ pub async fn get_login_history(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<Vec<LoginHistoryEntry>>>) {
     let state_guard = state.read().await;
-    let key = format!("login_history:{}", auth_user.user_id);
-    let history = match state_guard.db.get_setting(&key).await {
-        Ok(Some(val)) => {
-            serde_json::from_str::<LoginHistory>(&val)
-                .unwrap_or_default()
-                .entries
-        }
-        _ => Vec::new(),
-    };
+    let history = load_login_history(&state_guard.db, &auth_user.user_id.to_string()).await;
     (StatusCode::OK, Json(ApiResponse::success(history)))
 }
 
+/// Default number of entries returned by `GET /api/v1/users/me/logins`.
+const DEFAULT_LOGINS_LIMIT: usize = 50;
+
+/// Query parameters for the login listing.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct LoginsQuery {
+    /// Maximum entries to return (default 50, at most 200)
+    pub limit: Option<usize>,
+}
+
+/// `GET /api/v1/users/me/logins` — Full login history of the current user.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/logins",
+    tag = "Users",
+    summary = "List my logins",
+    description = "Returns the current user's successful and failed login attempts with time, \
+        IP address and user agent, newest first. `new_device` marks successful logins from an \
+        IP/user-agent combination not seen before.",
+    params(LoginsQuery),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Login history", body = Vec<LoginEvent>),
+    )
+)]
+pub async fn list_my_logins(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Query(query): Query<LoginsQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<LoginEvent>>>) {
+    let state_guard = state.read().await;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_LOGINS_LIMIT)
+        .clamp(1, MAX_LOGINS_PER_USER);
+    match state_guard
+        .db
+        .list_login_events(&auth_user.user_id.to_string(), limit)
+        .await
+    {
+        Ok(events) => (StatusCode::OK, Json(ApiResponse::success(events))),
+        Err(e) => {
+            tracing::error!("Failed to list logins: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
+
 /// `GET /api/v1/admin/users/{id}/login-history` — Get login history for any user (admin).
 #[utoipa::path(
     get,
@@ -942,22 +1162,17 @@ pub async fn admin_get_login_history(
     axum::extract::Path(user_id): axum::extract::Path<String>,
 ) -> (StatusCode, Json<ApiResponse<Vec<LoginHistoryEntry>>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
-    let key = format!("login_history:{user_id}");
-    let history = match state_guard.db.get_setting(&key).await {
-        Ok(Some(val)) => {
-            serde_json::from_str::<LoginHistory>(&val)
-                .unwrap_or_default()
-                .entries
-        }
-        _ => Vec::new(),
-    };
+    let history = load_login_history(&state_guard.db, &user_id).await;
     (StatusCode::OK, Json(ApiResponse::success(history)))
 }
 
@@ -968,15 +1183,110 @@ pub async fn admin_get_login_history(
 /// Active session info for the user.
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct SessionInfo {
-    /// Session ID (the access token prefix — not the full token, for security)
+    /// Session ID (prefix of the session key — not the full key, for security)
     pub id: String,
+    /// Stable, non-secret session identifier, used to revoke the session
+    pub token_id: String,
     pub username: String,
     pub role: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
+    /// Last request made with the session (updated at most once a minute)
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Client IP the session was last used from
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
     pub is_current: bool,
 }
 
+impl SessionInfo {
+    fn new(session_id: &str, session: Session, is_current: bool) -> Self {
+        Self {
+            id: format!("{}...", &session_id[..8.min(session_id.len())]),
+            token_id: session_tokens::public_session_id(session_id),
+            username: session.username,
+            role: session.role,
+            created_at: session.created_at,
+            expires_at: session.expires_at,
+            last_used_at: session.last_used_at,
+            ip_address: session.ip_address,
+            user_agent: session.user_agent,
+            is_current,
+        }
+    }
+}
+
+/// Active sessions of `user_id`, flagging the one `headers` authenticate.
+async fn load_session_infos(
+    state: &crate::AppState,
+    user_id: Uuid,
+    headers: &HeaderMap,
+) -> anyhow::Result<Vec<SessionInfo>> {
+    let current_token = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "))
+        .unwrap_or("");
+    let current_session = session_tokens::authenticate(state, current_token)
+        .await
+        .map(|identity| identity.session_id);
+
+    Ok(state
+        .db
+        .list_sessions_by_user(user_id)
+        .await?
+        .into_iter()
+        .map(|(session_id, session)| {
+            let is_current = current_session.as_deref() == Some(session_id.as_str());
+            SessionInfo::new(&session_id, session, is_current)
+        })
+        .collect())
+}
+
+/// End the session of `user_id` whose key matches `matches`.
+async fn revoke_matching_session(
+    state: &crate::AppState,
+    user_id: Uuid,
+    matches: impl Fn(&str) -> bool,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let sessions = match state.db.list_sessions_by_user(user_id).await {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::error!("Failed to list sessions: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to list sessions",
+                )),
+            );
+        }
+    };
+
+    let Some((session_id, _)) = sessions.iter().find(|(id, _)| matches(id)) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::NotFound, "Session not found")),
+        );
+    };
+
+    if let Err(e) = session_tokens::end_session(state, session_id).await {
+        tracing::error!("Failed to revoke session: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to revoke session",
+            )),
+        );
+    }
+    AuditEntry::new(AuditEventType::Logout)
+        .user(user_id, "")
+        .detail("Session revoked manually")
+        .log();
+    (StatusCode::OK, Json(ApiResponse::success(())))
+}
+
 /// `GET /api/v1/auth/sessions` — List active sessions for the current user.
 #[utoipa::path(
     get,
@@ -992,51 +1302,22 @@ pub struct SessionInfo {
 pub async fn list_sessions(
     State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
-    req: axum::extract::Request,
+    headers: HeaderMap,
 ) -> (StatusCode, Json<ApiResponse<Vec<SessionInfo>>>) {
-    let current_token = req
-        .headers()
-        .get(axum::http::header::AUTHORIZATION)
-        .and_then(|h| h.to_str().ok())
-        .and_then(|h| h.strip_prefix("Bearer "))
-        .unwrap_or("")
-        .to_string();
-
     let state_guard = state.read().await;
-    let sessions = match state_guard
-        .db
-        .list_sessions_by_user(auth_user.user_id)
-        .await
-    {
-        Ok(s) => s,
+    match load_session_infos(&state_guard, auth_user.user_id, &headers).await {
+        Ok(infos) => (StatusCode::OK, Json(ApiResponse::success(infos))),
         Err(e) => {
             tracing::error!("Failed to list sessions: {}", e);
-            return (
+            (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list sessions",
                 )),
-            );
+            )
         }
-    };
-
-    let infos: Vec<SessionInfo> = sessions
-        .into_iter()
-        .map(|(token, session)| {
-            let is_current = token == current_token;
-            SessionInfo {
-                id: format!("{}...", &token[..8.min(token.len())]),
-                username: session.username,
-                role: session.role,
-                created_at: session.created_at,
-                expires_at: session.expires_at,
-                is_current,
-            }
-        })
-        .collect();
-
-    (StatusCode::OK, Json(ApiResponse::success(infos)))
+    }
 }
 
 /// `DELETE /api/v1/auth/sessions/{id}` — Revoke a specific session.
@@ -1059,56 +1340,159 @@ pub async fn revoke_session(
     axum::extract::Path(session_id): axum::extract::Path<String>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
     let state_guard = state.read().await;
+    // The session_id is a prefix like "ab12cd34..."
+    let prefix = session_id.trim_end_matches("...");
+    revoke_matching_session(&state_guard, auth_user.user_id, |id| id.starts_with(prefix)).await
+}
 
-    // Find the full token matching the prefix for this user
-    let sessions = match state_guard
-        .db
-        .list_sessions_by_user(auth_user.user_id)
-        .await
-    {
-        Ok(s) => s,
+/// `GET /api/v1/users/me/sessions` — Active sessions with client details.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/me/sessions",
+    tag = "Users",
+    summary = "List my sessions",
+    description = "Returns the current user's active sessions with creation and last-use \
+        times, IP address and user agent. `token_id` identifies a session for revocation.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Active sessions"),
+    )
+)]
+pub async fn list_my_sessions(
+    state: State<SharedState>,
+    auth_user: Extension<AuthUser>,
+    headers: HeaderMap,
+) -> (StatusCode, Json<ApiResponse<Vec<SessionInfo>>>) {
+    list_sessions(state, auth_user, headers).await
+}
+
+/// `DELETE /api/v1/users/me/sessions/{token_id}` — Sign out one session.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/me/sessions/{token_id}",
+    tag = "Users",
+    summary = "Revoke one of my sessions",
+    description = "Ends the session and invalidates its access and refresh tokens. \
+        Revoking the current session signs the caller out.",
+    security(("bearer_auth" = [])),
+    params(("token_id" = String, Path, description = "Session `token_id` from the session list")),
+    responses(
+        (status = 200, description = "Session revoked"),
+        (status = 404, description = "Session not found"),
+    )
+)]
+pub async fn revoke_my_session(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    axum::extract::Path(token_id): axum::extract::Path<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.read().await;
+    revoke_matching_session(&state_guard, auth_user.user_id, |id| {
+        session_tokens::public_session_id(id) == token_id
+    })
+    .await
+}
+
+/// Result of ending all sessions of a user.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct SessionsRevoked {
+    /// Sessions that were still active
+    pub revoked_sessions: u64,
+}
+
+/// `DELETE /api/v1/admin/users/{id}/sessions` — Sign a user out everywhere.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/admin/users/{id}/sessions",
+    tag = "Admin",
+    summary = "Revoke all sessions of a user (admin)",
+    description = "Ends every session of the user, e.g. when offboarding them or when their \
+        account is compromised. Their access and refresh tokens stop working with the next \
+        request, including tokens issued before a server restart. API keys are not affected.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "User UUID")),
+    responses(
+        (status = 200, description = "Sessions revoked", body = SessionsRevoked),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "User not found"),
+    )
+)]
+pub async fn admin_revoke_user_sessions(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    axum::extract::Path(id): axum::extract::Path<String>,
+) -> (StatusCode, Json<ApiResponse<SessionsRevoked>>) {
+    let state_guard = state.read().await;
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
+    }
+
+    let user = match state_guard.db.get_user(&id).await {
+        Ok(Some(user)) => user,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
+            );
+        }
         Err(e) => {
-            tracing::error!("Failed to list sessions: {}", e);
+            tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
-                    "Failed to list sessions",
+                    ErrorCode::ServerError,
+                    "Internal server error",
                 )),
             );
         }
     };
-
-    // The session_id is a prefix like "ab12cd34..."
-    let prefix = session_id.trim_end_matches("...");
-    let token = sessions
-        .iter()
-        .find(|(t, _)| t.starts_with(prefix))
-        .map(|(t, _)| t.clone());
-
-    match token {
-        Some(full_token) => {
-            if let Err(e) = state_guard.db.delete_session(&full_token).await {
-                tracing::error!("Failed to revoke session: {}", e);
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error(
-                        "SERVER_ERROR",
-                        "Failed to revoke session",
-                    )),
-                );
-            }
-            AuditEntry::new(AuditEventType::LoginFailed)
-                .user(auth_user.user_id, "")
-                .detail("Session revoked manually")
-                .log();
-            (StatusCode::OK, Json(ApiResponse::success(())))
-        }
-        None => (
+    // Admins of one tenant cannot sign out another tenant's users
+    let caller_tenant_id = super::resolve_tenant_id(&state_guard, auth_user.user_id).await;
+    if !super::matches_tenant(user.tenant_id.as_deref(), caller_tenant_id.as_deref()) {
+        return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Session not found")),
-        ),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
+        );
     }
+
+    let revoked_sessions = match session_tokens::end_user_sessions(&state_guard, user.id).await {
+        Ok(count) => count,
+        Err(e) => {
+            tracing::error!("Failed to revoke sessions of user {}: {}", user.id, e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to revoke sessions",
+                )),
+            );
+        }
+    };
+
+    AuditEntry::new(AuditEventType::SessionsRevoked)
+        .user(auth_user.user_id, "")
+        .resource("user", &user.id.to_string())
+        .details(serde_json::json!({ "revoked_sessions": revoked_sessions }))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+    tracing::info!(
+        admin_id = %auth_user.user_id,
+        target_user_id = %user.id,
+        revoked_sessions,
+        "Admin revoked all sessions of user"
+    );
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(SessionsRevoked { revoked_sessions })),
+    )
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -1185,7 +1569,7 @@ pub async fn create_api_key(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "Name must be between 1 and 100 characters",
             )),
         );
@@ -1206,7 +1590,7 @@ pub async fn create_api_key(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to create API key",
                 )),
             );
@@ -1243,7 +1627,10 @@ pub async fn create_api_key(
         tracing::error!("Failed to save API key: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save API key")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save API key",
+            )),
         );
     }
 
@@ -1347,7 +1734,10 @@ pub async fn revoke_api_key(
                 tracing::error!("Failed to revoke API key: {}", e);
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("SERVER_ERROR", "Failed to revoke key")),
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Failed to revoke key",
+                    )),
                 );
             }
             // Clear reverse index entry (empty value = deleted)
@@ -1361,7 +1751,7 @@ pub async fn revoke_api_key(
         }
         None => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "API key not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "API key not found")),
         ),
     }
 }
@@ -1622,6 +2012,72 @@ mod tests {
         assert_eq!(json["ip_address"], "10.0.0.1");
     }
 
+    fn login_event(ip: &str, user_agent: Option<&str>, success: bool) -> LoginEvent {
+        LoginEvent {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            at: Utc::now(),
+            ip: ip.to_string(),
+            user_agent: user_agent.map(str::to_string),
+            success,
+            failure_reason: (!success).then(|| "invalid_password".to_string()),
+            new_device: false,
+        }
+    }
+
+    #[test]
+    fn test_is_new_device() {
+        let client = LoginClient {
+            ip: "203.0.113.7".to_string(),
+            user_agent: Some("Firefox".to_string()),
+        };
+        // First ever login: nothing to compare against.
+        assert!(!is_new_device(&[], &client));
+
+        let seen = login_event("203.0.113.7", Some("Firefox"), true);
+        let other_ip = login_event("198.51.100.1", Some("Firefox"), true);
+        let failed_same = login_event("203.0.113.7", Some("Firefox"), false);
+        assert!(!is_new_device(&[other_ip.clone(), seen], &client));
+        assert!(is_new_device(std::slice::from_ref(&other_ip), &client));
+        // A failed attempt from the device does not make it known.
+        assert!(is_new_device(&[failed_same.clone(), other_ip], &client));
+        assert!(!is_new_device(&[failed_same], &client));
+    }
+
+    #[test]
+    fn test_login_client_from_request() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::USER_AGENT, "x".repeat(400).parse().unwrap());
+        headers.insert("x-forwarded-for", "198.51.100.9".parse().unwrap());
+
+        // Forwarded-for is only honoured behind a private proxy.
+        let public_peer: SocketAddr = "203.0.113.7:4000".parse().unwrap();
+        let client = LoginClient::from_request(&headers, Some(public_peer));
+        assert_eq!(client.ip, "203.0.113.7");
+        assert_eq!(client.user_agent.unwrap().len(), MAX_USER_AGENT_LEN);
+
+        let proxy: SocketAddr = "10.0.0.2:4000".parse().unwrap();
+        let client = LoginClient::from_request(&HeaderMap::new(), Some(proxy));
+        assert_eq!(client.ip, "10.0.0.2");
+        assert!(client.user_agent.is_none());
+        assert_eq!(
+            LoginClient::from_request(&headers, Some(proxy)).ip,
+            "198.51.100.9"
+        );
+    }
+
+    #[test]
+    fn test_login_history_from_events_keeps_newest_first() {
+        let events: Vec<LoginEvent> = (0..15)
+            .map(|i| login_event(&format!("10.0.0.{i}"), None, true))
+            .collect();
+        let history = LoginHistory::from_events(&events);
+        assert_eq!(history.entries.len(), 10);
+        assert_eq!(history.entries[0].ip_address, "10.0.0.0");
+        assert_eq!(history.entries[9].ip_address, "10.0.0.9");
+        assert_eq!(history.entries[0].user_agent, "");
+    }
+
     // ─── API Key Tests ───────────────────────────────────────────────────
 
     #[test]
@@ -1734,10 +2190,14 @@ mod tests {
     fn test_session_info_serialization() {
         let info = SessionInfo {
             id: "ab12cd34...".to_string(),
+            token_id: "0123456789abcdef".to_string(),
             username: "testuser".to_string(),
             role: "user".to_string(),
             created_at: Utc::now(),
             expires_at: Utc::now() + chrono::Duration::hours(24),
+            last_used_at: None,
+            ip_address: Some("203.0.113.7".to_string()),
+            user_agent: None,
             is_current: true,
         };
         let json = serde_json::to_value(&info).unwrap();
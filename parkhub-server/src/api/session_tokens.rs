@@ -0,0 +1,444 @@
+//! Signed access tokens for persisted sessions.
+//!
+//! Sessions stay in redb, keyed by a random session id, and own the refresh
+//! token — so refresh, listing and revocation remain server-side. The access
+//! token handed to clients is a short-lived HS256 JWT (one hour, or less if
+//! the session ends sooner) carrying the user id and role, with the session
+//! id as its `family_id`. [`authenticate`] checks it without reading the
+//! session; clients refresh it through the session, which is where a
+//! session that no longer exists is turned away.
+//!
+//! Ending a session ([`end_session`], [`end_user_sessions`]) deletes the
+//! record and revokes its token family in the in-memory
+//! [`TokenRevocationList`](crate::jwt::TokenRevocationList).
+//! [`end_user_sessions`] also stores a per-user cut-off (see
+//! `crate::db::Database::revoke_sessions_before`), so tokens issued to the
+//! user before it stay refused across restarts. A single ended session's
+//! tokens may outlive a restart until they expire. Opaque tokens issued
+//! before signed tokens were introduced are still resolved through the
+//! session table until they expire.
+//!
+//! Session ids are bearer secrets for legacy tokens, so they are never shown
+//! to users. [`public_session_id`] derives the stable id used to list and
+//! revoke sessions instead. [`touch_session`] records when and from where a
+//! session was last used, at most once per [`TOUCH_INTERVAL`], and ends
+//! sessions left unused for longer than `session_timeout_minutes` — an idle
+//! timeout on top of the absolute `session_lifetime_hours`. It also tells a
+//! session that timed out from one that was ended, so a token outliving
+//! its session is turned away once per [`TOUCH_INTERVAL`] at the latest.
+//!
+//! [`user_access`] keeps each user's account state and permissions for the
+//! same interval, so authenticating a request usually reads nothing.
+//!
+//! [`make_room_for_session`] applies `max_concurrent_sessions` before a
+//! sign-in opens a new session.
+//!
+//! Refreshing ends the session like [`end_session`] but goes through
+//! [`rotate_session`], which also remembers the spent refresh token so a
+//! replay of it can be recognised (see `crate::db::Database::rotate_refresh_token`).
+//!
+//! Sessions an admin opens to act as another user
+//! ([`impersonation`](super::impersonation)) also name that admin in the
+//! token's `impersonator` claim, so every request made with it can be
+//! attributed to both.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use parkhub_common::{ResolvedPermissions, UserRole};
+
+use crate::AppState;
+use crate::config::{ServerConfig, SessionLimitPolicy};
+use crate::db::{Database, Session};
+use crate::jwt::{JwtConfig, JwtManager, TokenType};
+
+use super::generate_access_token;
+
+/// Issuer claim stamped on every access token.
+const TOKEN_ISSUER: &str = "parkhub";
+
+/// Lifetime of an access token; clients refresh it through their session.
+const ACCESS_TOKEN_HOURS: i64 = 1;
+
+/// Minimum time between two `last_used_at` writes for one session.
+pub const TOUCH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Touch bookkeeping is pruned once it tracks this many sessions.
+const MAX_TRACKED_TOUCHES: usize = 10_000;
+
+/// When each session was last written by [`touch_session`].
+static LAST_TOUCH: LazyLock<Mutex<HashMap<String, Instant>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Who a validated access token belongs to.
+#[derive(Debug, Clone)]
+#[allow(clippy::struct_field_names)]
+pub struct TokenIdentity {
+    /// Key of the session in the sessions table
+    pub session_id: String,
+    pub user_id: Uuid,
+    /// Admin acting as `user_id`, for impersonation sessions
+    pub impersonator_id: Option<Uuid>,
+}
+
+fn token_manager(db: &Database) -> JwtManager {
+    JwtManager::new(JwtConfig {
+        secret: db.token_signing_secret().to_string(),
+        access_token_expiry_hours: ACCESS_TOKEN_HOURS,
+        refresh_token_expiry_days: 7,
+        issuer: TOKEN_ISSUER.to_string(),
+    })
+}
+
+/// JWTs are three dot-separated segments; opaque tokens are plain hex.
+fn is_signed_token(token: &str) -> bool {
+    token.split('.').count() == 3
+}
+
+/// Persist `session` under a fresh session id and return its signed access
+/// token, valid for [`ACCESS_TOKEN_HOURS`] or until the session expires.
+pub async fn start_session(db: &Database, session: &Session) -> Result<String> {
+    let session_id = generate_access_token();
+    db.save_session(&session_id, session).await?;
+    token_manager(db)
+        .generate_access_token(
+            &session.user_id,
+            &session.username,
+            &session.role,
+            &session_id,
+            session.expires_at,
+            session.impersonator_id.as_ref(),
+        )
+        .map_err(|e| anyhow::anyhow!("{e}"))
+}
+
+/// Resolve an access token to its session and user.
+///
+/// Signed tokens are checked against their signature, expiry, the
+/// revocation list and their user's revocation cut-off, without a database
+/// read. Legacy opaque tokens are resolved through the session table.
+pub async fn authenticate(state: &AppState, token: &str) -> Option<TokenIdentity> {
+    if is_signed_token(token) {
+        let claims = token_manager(&state.db)
+            .validate_token(token, Some(state.revocation_store.as_ref()))
+            .await
+            .ok()?;
+        if claims.token_type != TokenType::Access {
+            return None;
+        }
+        let user_id = Uuid::parse_str(&claims.sub).ok()?;
+        if is_revoked_for_user(&state.db, user_id, claims.iat) {
+            return None;
+        }
+        let impersonator_id = match claims.impersonator {
+            Some(id) => Some(Uuid::parse_str(&id).ok()?),
+            None => None,
+        };
+        return Some(TokenIdentity {
+            session_id: claims.family_id?,
+            user_id,
+            impersonator_id,
+        });
+    }
+
+    let session = state
+        .db
+        .get_session(token)
+        .await
+        .ok()
+        .flatten()
+        .filter(|s| !s.is_expired())?;
+    Some(TokenIdentity {
+        session_id: token.to_string(),
+        user_id: session.user_id,
+        impersonator_id: session.impersonator_id,
+    })
+}
+
+/// Whether a token issued to `user_id` at `issued_at` (Unix seconds) falls
+/// before the user's revocation cut-off.
+///
+/// The cut-off has second precision; a token issued within the second its
+/// sessions were ended is still caught by its revoked family.
+fn is_revoked_for_user(db: &Database, user_id: Uuid, issued_at: i64) -> bool {
+    db.sessions_revoked_before(user_id)
+        .is_some_and(|revoked_at| issued_at < revoked_at)
+}
+
+/// Non-secret identifier of a session, safe to show to its owner.
+pub fn public_session_id(session_id: &str) -> String {
+    let digest = Sha256::digest(format!("session:{session_id}").as_bytes());
+    hex::encode(&digest[..8])
+}
+
+/// Whether `session_id` is due a `last_used_at` write at `now`; marks it
+/// touched if so.
+fn claim_touch(session_id: &str, now: Instant) -> bool {
+    let mut touches = LAST_TOUCH
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if touches
+        .get(session_id)
+        .is_some_and(|last| now.duration_since(*last) < TOUCH_INTERVAL)
+    {
+        return false;
+    }
+    if touches.len() >= MAX_TRACKED_TOUCHES {
+        touches.retain(|_, last| now.duration_since(*last) < TOUCH_INTERVAL);
+    }
+    touches.insert(session_id.to_string(), now);
+    true
+}
+
+/// How long a session may go unused, or `None` if idle sessions never end.
+///
+/// `last_used_at` is only written once per [`TOUCH_INTERVAL`], so it can lag
+/// the latest request by that much; the timeout is widened to match.
+pub fn idle_timeout(config: &ServerConfig) -> Option<chrono::Duration> {
+    if config.session_timeout_minutes == 0 {
+        return None;
+    }
+    let touch_interval = chrono::Duration::from_std(TOUCH_INTERVAL).ok()?;
+    Some(chrono::Duration::minutes(i64::from(config.session_timeout_minutes)) + touch_interval)
+}
+
+/// What [`touch_session`] found out about a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionUse {
+    /// The session exists and may be used
+    Live,
+    /// The session had been idle for longer than [`idle_timeout`] and was
+    /// ended
+    IdleTimedOut,
+    /// The session no longer exists: logged out, revoked or rotated away
+    Ended,
+}
+
+/// Record that `session_id` was used from `ip`. Throttled to one write per
+/// [`TOUCH_INTERVAL`]; failures are logged, never propagated.
+///
+/// Sessions idle for longer than [`idle_timeout`] are ended instead.
+pub async fn touch_session(state: &AppState, session_id: &str, ip: Option<&str>) -> SessionUse {
+    if !claim_touch(session_id, Instant::now()) {
+        return SessionUse::Live;
+    }
+    if let Some(timeout) = idle_timeout(&state.config) {
+        match state.db.get_session(session_id).await {
+            Ok(Some(session)) if session.is_idle(timeout) => {
+                tracing::info!(user_id = %session.user_id, "Ending idle session");
+                if let Err(e) = end_session(state, session_id).await {
+                    tracing::warn!("Failed to end idle session: {}", e);
+                }
+                return SessionUse::IdleTimedOut;
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => return SessionUse::Ended,
+            Err(e) => tracing::warn!("Failed to load session: {}", e),
+        }
+    }
+    match state.db.touch_session(session_id, ip).await {
+        Ok(false) => SessionUse::Ended,
+        Ok(true) => SessionUse::Live,
+        Err(e) => {
+            tracing::warn!("Failed to record session use: {}", e);
+            SessionUse::Live
+        }
+    }
+}
+
+/// What [`auth_middleware`](super::auth_middleware) needs to know about the
+/// user behind a request.
+#[derive(Debug, Clone)]
+pub struct UserAccess {
+    pub role: UserRole,
+    pub is_active: bool,
+    pub must_change_password: bool,
+    pub permissions: ResolvedPermissions,
+}
+
+struct CachedAccess {
+    access: UserAccess,
+    epoch: u64,
+    loaded_at: Instant,
+}
+
+/// [`UserAccess`] loaded by [`user_access`], per user.
+static ACCESS_CACHE: LazyLock<Mutex<HashMap<Uuid, CachedAccess>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The account state and permissions of `user_id`, or `None` if the user
+/// no longer exists or cannot be read.
+///
+/// Loaded at most once per [`TOUCH_INTERVAL`] per user, and again as soon
+/// as a write to any account, role or lot manager assignment advances
+/// [`Database::access_epoch`], so changes apply to the next request.
+pub async fn user_access(db: &Database, user_id: Uuid) -> Option<UserAccess> {
+    let epoch = db.access_epoch();
+    let now = Instant::now();
+    if let Some(cached) = ACCESS_CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .get(&user_id)
+        .filter(|c| c.epoch == epoch && now.duration_since(c.loaded_at) < TOUCH_INTERVAL)
+    {
+        return Some(cached.access.clone());
+    }
+
+    let user = db.get_user(&user_id.to_string()).await.ok().flatten()?;
+    let access = UserAccess {
+        role: user.role.clone(),
+        is_active: user.is_active,
+        must_change_password: user.must_change_password,
+        permissions: super::permissions::resolve_permissions(db, &user).await,
+    };
+    let mut cache = ACCESS_CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if cache.len() >= MAX_TRACKED_TOUCHES {
+        cache.retain(|_, c| now.duration_since(c.loaded_at) < TOUCH_INTERVAL);
+    }
+    // Stamped with the epoch read before loading: a write racing the load
+    // leaves the entry already stale.
+    cache.insert(
+        user_id,
+        CachedAccess {
+            access: access.clone(),
+            epoch,
+            loaded_at: now,
+        },
+    );
+    Some(access)
+}
+
+/// Delete one session and revoke the access tokens issued for it.
+pub async fn end_session(state: &AppState, session_id: &str) -> Result<bool> {
+    state.revocation_store.revoke_family(session_id).await;
+    LAST_TOUCH
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .remove(session_id);
+    state.db.delete_session(session_id).await
+}
+
+/// End `session` (stored under `session_id`) because its refresh token was
+/// just exchanged, remembering the token as used.
+///
+/// Returns `false` if the session was already gone: a concurrent refresh
+/// spent the same token first.
+pub async fn rotate_session(state: &AppState, session_id: &str, session: &Session) -> Result<bool> {
+    let rotated = state.db.rotate_refresh_token(session_id, session).await?;
+    if rotated {
+        state.revocation_store.revoke_family(session_id).await;
+        LAST_TOUCH
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(session_id);
+    }
+    Ok(rotated)
+}
+
+/// Apply `max_concurrent_sessions` before signing `user_id` in.
+///
+/// Returns `false` when the user is at the limit under
+/// [`SessionLimitPolicy::Reject`]. Under [`SessionLimitPolicy::EvictOldest`]
+/// the oldest sessions are ended so the new one fits.
+pub async fn make_room_for_session(state: &AppState, user_id: Uuid) -> Result<bool> {
+    let limit = state.config.max_concurrent_sessions as usize;
+    if limit == 0 || state.db.count_sessions_by_user(user_id).await? < limit {
+        return Ok(true);
+    }
+    match state.config.session_limit_policy {
+        SessionLimitPolicy::Reject => Ok(false),
+        SessionLimitPolicy::EvictOldest => {
+            let sessions = state.db.list_sessions_by_user(user_id).await?;
+            let excess = (sessions.len() + 1).saturating_sub(limit);
+            for (session_id, _) in sessions.iter().take(excess) {
+                end_session(state, session_id).await?;
+            }
+            tracing::info!(%user_id, evicted = excess, "Session limit reached, ended oldest sessions");
+            Ok(true)
+        }
+    }
+}
+
+/// Delete every session of `user_id` and revoke their access tokens,
+/// including ones the revocation list no longer knows about.
+pub async fn end_user_sessions(state: &AppState, user_id: Uuid) -> Result<u64> {
+    state
+        .db
+        .revoke_sessions_before(user_id, chrono::Utc::now())
+        .await?;
+    for (session_id, _) in state.db.list_sessions_by_user(user_id).await? {
+        state.revocation_store.revoke_family(&session_id).await;
+    }
+    state.db.delete_sessions_by_user(user_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_signed_token() {
+        assert!(is_signed_token("aGVhZGVy.cGF5bG9hZA.c2ln"));
+        assert!(!is_signed_token(&generate_access_token()));
+        assert!(!is_signed_token("rt_abc"));
+    }
+
+    #[test]
+    fn test_public_session_id_is_stable_and_hides_the_key() {
+        let session_id = generate_access_token();
+        let public = public_session_id(&session_id);
+        assert_eq!(public, public_session_id(&session_id));
+        assert_eq!(public.len(), 16);
+        assert!(!session_id.contains(&public));
+        assert_ne!(public, public_session_id(&generate_access_token()));
+    }
+
+    #[test]
+    fn test_claim_touch_is_throttled() {
+        let session_id = generate_access_token();
+        let now = Instant::now();
+        assert!(claim_touch(&session_id, now));
+        assert!(!claim_touch(&session_id, now + Duration::from_secs(5)));
+        assert!(claim_touch(&session_id, now + TOUCH_INTERVAL));
+    }
+
+    #[tokio::test]
+    async fn test_revocation_cutoff_rejects_older_tokens() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&crate::db::DatabaseConfig {
+            path: dir.path().to_path_buf(),
+            encryption_enabled: false,
+            passphrase: None,
+            create_if_missing: true,
+        })
+        .unwrap();
+        let user_id = Uuid::new_v4();
+        let now = chrono::Utc::now();
+        let earlier = now.timestamp() - 60;
+        assert!(!is_revoked_for_user(&db, user_id, earlier));
+
+        db.revoke_sessions_before(user_id, now).await.unwrap();
+        assert!(is_revoked_for_user(&db, user_id, earlier));
+        // Signing in again right after is not affected
+        assert!(!is_revoked_for_user(&db, user_id, now.timestamp()));
+        assert!(!is_revoked_for_user(&db, Uuid::new_v4(), earlier));
+    }
+
+    #[test]
+    fn test_idle_timeout_covers_touch_interval() {
+        let mut config = ServerConfig {
+            session_timeout_minutes: 30,
+            ..ServerConfig::default()
+        };
+        assert_eq!(idle_timeout(&config), Some(chrono::Duration::minutes(31)));
+        config.session_timeout_minutes = 0;
+        assert_eq!(idle_timeout(&config), None);
+    }
+}
@@ -4,14 +4,15 @@
 // db access goes through its own inner RwLock. See workspace lint config.
 #![allow(clippy::significant_drop_tightening)]
 
-use axum::{Extension, Json, extract::State, http::StatusCode};
+use axum::{Json, extract::State, http::StatusCode};
 use serde::Deserialize;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use crate::audit::{AuditEntry, AuditEventType};
 
-use super::{AuthUser, SharedState, check_admin};
+use super::SharedState;
+use super::permissions::{CanManageConfig, RequirePermission};
 
 /// All admin settings with their default values.
 ///
@@ -19,6 +20,14 @@ use super::{AuthUser, SharedState, check_admin};
 /// multi-country VAT profile resolver in [`super::tax`]. Both default to
 /// `"DE"` so single-tenant German deployments keep behaving exactly as
 /// before; international operators override them via PUT.
+///
+/// `booking_anonymize_after_days` strips plates and notes from settled
+/// bookings that ended that many days ago (see `crate::jobs`); `0` keeps
+/// them until the account is anonymized.
+///
+/// `monthly_summary_emails` mails users a summary of their previous month
+/// (see [`super::energy_reports`] for its EV section, whose CO2 estimate
+/// uses the grid factor `ev_co2_grams_per_kwh`).
 pub const ADMIN_SETTINGS: &[(&str, &str)] = &[
     ("company_name", "ParkHub"),
     ("use_case", "company"),
@@ -37,6 +46,9 @@ pub const ADMIN_SETTINGS: &[(&str, &str)] = &[
     ("credits_per_booking", "1"),
     ("tax_default_country", "DE"),
     ("tax_seller_country", "DE"),
+    ("booking_anonymize_after_days", "0"),
+    ("monthly_summary_emails", "false"),
+    ("ev_co2_grams_per_kwh", "380"),
 ];
 
 /// Read a single admin setting from DB, falling back to its default.
@@ -138,12 +150,9 @@ pub fn use_case_theme(key: &str) -> serde_json::Value {
 )]
 pub async fn admin_get_use_case(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanManageConfig>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
     let current = read_admin_setting(&state_guard.db, "use_case").await;
     let theme = use_case_theme(&current);
     let all_options: Vec<serde_json::Value> =
@@ -169,12 +178,9 @@ pub async fn admin_get_use_case(
 )]
 pub async fn admin_get_settings(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanManageConfig>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let mut data = serde_json::Map::new();
     for (key, default_val) in ADMIN_SETTINGS {
@@ -207,7 +213,8 @@ fn validate_setting_value(key: &str, value: &str) -> Result<(), &'static str> {
         | "require_vehicle"
         | "waitlist_enabled"
         | "credits_enabled"
-        | "auto_release_enabled" => {
+        | "auto_release_enabled"
+        | "monthly_summary_emails" => {
             if value != "true" && value != "false" {
                 return Err("Value must be \"true\" or \"false\"");
             }
@@ -227,6 +234,19 @@ fn validate_setting_value(key: &str, value: &str) -> Result<(), &'static str> {
                 return Err("Value must be an integer");
             }
         }
+        "booking_anonymize_after_days" => {
+            if value.parse::<u32>().is_err() {
+                return Err("Value must be a non-negative integer");
+            }
+        }
+        "ev_co2_grams_per_kwh" => {
+            if !value
+                .parse::<f64>()
+                .is_ok_and(|v| v.is_finite() && v >= 0.0)
+            {
+                return Err("Value must be a non-negative number");
+            }
+        }
         "min_booking_duration_hours" | "max_booking_duration_hours" => {
             if value.parse::<f64>().is_err() {
                 return Err("Value must be a number");
@@ -257,19 +277,16 @@ fn validate_setting_value(key: &str, value: &str) -> Result<(), &'static str> {
 )]
 pub async fn admin_update_settings(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageConfig>,
     Json(payload): Json<serde_json::Value>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let Some(obj) = payload.as_object() else {
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "Request body must be a JSON object of key-value pairs",
             )),
         );
@@ -283,7 +300,7 @@ pub async fn admin_update_settings(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "INVALID_KEY",
+                    ErrorCode::InvalidKey,
                     format!("Unknown setting: {key}"),
                 )),
             );
@@ -297,7 +314,7 @@ pub async fn admin_update_settings(
         if let Err(msg) = validate_setting_value(key, &value_str) {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("VALIDATION_ERROR", msg)),
+                Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
             );
         }
 
@@ -305,7 +322,10 @@ pub async fn admin_update_settings(
             tracing::error!("Failed to save setting {}: {}", key, e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to save setting")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to save setting",
+                )),
             );
         }
 
@@ -435,12 +455,9 @@ pub async fn get_public_theme(
 )]
 pub async fn admin_get_features(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    _: RequirePermission<CanManageConfig>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     let enabled = read_features(&state_guard.db).await;
 
@@ -478,13 +495,10 @@ pub struct UpdateFeaturesRequest {
 )]
 pub async fn admin_update_features(
     State(state): State<SharedState>,
-    Extension(auth_user): Extension<AuthUser>,
+    RequirePermission(auth_user, _): RequirePermission<CanManageConfig>,
     Json(body): Json<UpdateFeaturesRequest>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.write().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
-    }
 
     // Validate: only accept known feature IDs
     let valid: Vec<String> = body
@@ -504,7 +518,7 @@ pub async fn admin_update_features(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to save features",
             )),
         );
@@ -572,6 +586,7 @@ mod tests {
             "waitlist_enabled",
             "credits_enabled",
             "auto_release_enabled",
+            "monthly_summary_emails",
         ];
         for key in boolean_keys {
             assert!(validate_setting_value(key, "true").is_ok(), "{key}=true");
@@ -625,6 +640,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn validate_booking_anonymize_after_days() {
+        assert!(validate_setting_value("booking_anonymize_after_days", "0").is_ok());
+        assert!(validate_setting_value("booking_anonymize_after_days", "365").is_ok());
+        assert!(validate_setting_value("booking_anonymize_after_days", "-1").is_err());
+        assert!(validate_setting_value("booking_anonymize_after_days", "a year").is_err());
+    }
+
+    #[test]
+    fn validate_ev_co2_grams_per_kwh() {
+        assert!(validate_setting_value("ev_co2_grams_per_kwh", "0").is_ok());
+        assert!(validate_setting_value("ev_co2_grams_per_kwh", "380.5").is_ok());
+        assert!(validate_setting_value("ev_co2_grams_per_kwh", "-1").is_err());
+        assert!(validate_setting_value("ev_co2_grams_per_kwh", "NaN").is_err());
+    }
+
     #[test]
     fn validate_float_settings() {
         for key in ["min_booking_duration_hours", "max_booking_duration_hours"] {
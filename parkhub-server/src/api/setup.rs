@@ -10,7 +10,7 @@ use tokio::sync::RwLock;
 use uuid::Uuid;
 
 use parkhub_common::models::UserPreferences;
-use parkhub_common::{ApiResponse, User, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode, User, UserRole};
 
 use crate::AppState;
 
@@ -99,7 +99,7 @@ pub async fn setup_init(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "SETUP_COMPLETED",
+                ErrorCode::SetupCompleted,
                 "Setup already completed",
             )),
         );
@@ -110,7 +110,7 @@ pub async fn setup_init(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "Username must be at least 3 characters",
             )),
         );
@@ -119,7 +119,7 @@ pub async fn setup_init(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "Password must be at least 8 characters",
             )),
         );
@@ -133,7 +133,7 @@ pub async fn setup_init(
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to hash password",
                 )),
             );
@@ -158,6 +158,7 @@ pub async fn setup_init(
             email_reminders: false,
             default_duration_minutes: None,
             favorite_slots: Vec::new(),
+            accessibility: None,
         },
         credits_balance: 0,
         credits_monthly_quota: 0,
@@ -174,6 +175,7 @@ pub async fn setup_init(
         cost_center: None,
         department: None,
         settings: None,
+        must_change_password: false,
     };
 
     if let Err(e) = state_guard.db.save_user(&admin).await {
@@ -181,7 +183,7 @@ pub async fn setup_init(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to create admin user",
             )),
         );
@@ -218,10 +220,13 @@ pub async fn setup_init(
         &admin.username,
         &format!("{:?}", admin.role).to_lowercase(),
     );
-    let token = session.refresh_token.clone();
-    if let Err(e) = state_guard.db.save_session(&token, &session).await {
-        tracing::error!("Failed to create admin session: {}", e);
-    }
+    let token = match super::session_tokens::start_session(&state_guard.db, &session).await {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to create admin session: {}", e);
+            String::new()
+        }
+    };
     drop(state_guard);
 
     tracing::info!(
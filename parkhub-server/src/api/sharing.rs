@@ -17,7 +17,7 @@ use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::SharedState;
 
@@ -188,7 +188,7 @@ pub async fn get_shared_booking(
         return (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::error(
-                "not_found",
+                ErrorCode::NotFound,
                 "Invalid or expired share link",
             )),
         );
@@ -217,7 +217,10 @@ pub async fn invite_guest(
     if req.email.is_empty() || !req.email.contains('@') {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("bad_request", "Invalid email address")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "Invalid email address",
+            )),
         );
     }
 
@@ -0,0 +1,260 @@
+//! Late-binding slot assignment.
+//!
+//! Lots whose rules set `slot_assignment = "late_binding"` take bookings for
+//! a slot type rather than a slot number. `create_booking` accepts them as
+//! long as the type has capacity left for the whole time range; the booking
+//! is stored without a slot. The `assign_slots` background job picks the
+//! concrete slot `assignment_lead_minutes` before the start (check-in does
+//! it immediately if the job has not run yet) and notifies the user of the
+//! slot number. Deferring the choice lets the server pack bookings tightly
+//! instead of fixing an arbitrary slot days in advance.
+
+// Booking creation is behind `mod-bookings`; assignment always runs.
+#![cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use axum::{Json, http::StatusCode};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use parkhub_common::{
+    ApiResponse, CreateBookingRequest, ErrorCode, Notification, NotificationType, ParkingSlot,
+    SlotAssignmentMode, SlotStatus, SlotType,
+};
+
+use crate::db::{Database, SlotAssignment, SlotChangeContext};
+
+use super::lot_rules::load_lot_rules;
+
+/// Where a new booking goes.
+pub(crate) enum Placement {
+    /// Fixed-slot lot: the requested slot
+    Slot(ParkingSlot),
+    /// Late-binding lot: any of `slots`, all of `slot_type`
+    Type {
+        lot_id: Uuid,
+        slot_type: SlotType,
+        slots: Vec<ParkingSlot>,
+    },
+}
+
+impl Placement {
+    pub(crate) const fn lot_id(&self) -> Uuid {
+        match self {
+            Self::Slot(slot) => slot.lot_id,
+            Self::Type { lot_id, .. } => *lot_id,
+        }
+    }
+}
+
+/// Slots of `slot_type` in `lot_id` that can take bookings at all.
+async fn type_slots(
+    db: &Database,
+    lot_id: Uuid,
+    slot_type: &SlotType,
+) -> anyhow::Result<Vec<ParkingSlot>> {
+    Ok(db
+        .list_slots_by_lot(&lot_id.to_string())
+        .await?
+        .into_iter()
+        .filter(|s| {
+            s.slot_type == *slot_type
+                && !matches!(s.status, SlotStatus::Maintenance | SlotStatus::Disabled)
+        })
+        .collect())
+}
+
+/// Work out where `req` books: its slot in a fixed-slot lot, or a slot type
+/// in a late-binding lot. A request naming a slot in a late-binding lot
+/// books that slot's type.
+pub(crate) async fn resolve_placement<T>(
+    db: &Database,
+    req: &CreateBookingRequest,
+) -> Result<Placement, (StatusCode, Json<ApiResponse<T>>)> {
+    let server_error = |e: anyhow::Error| {
+        tracing::error!("Database error: {}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Internal server error",
+            )),
+        )
+    };
+
+    let (lot_id, slot_type) = if let Some(slot_type) = &req.slot_type {
+        let rules = load_lot_rules(db, &req.lot_id.to_string()).await;
+        if rules.slot_assignment != SlotAssignmentMode::LateBinding {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ErrorCode::InvalidInput,
+                    "This lot books specific slots; pass slot_id instead of slot_type",
+                )),
+            ));
+        }
+        (req.lot_id, slot_type.clone())
+    } else {
+        let slot = match db.get_parking_slot(&req.slot_id.to_string()).await {
+            Ok(Some(slot)) => slot,
+            Ok(None) => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    Json(ApiResponse::error(ErrorCode::NotFound, "Slot not found")),
+                ));
+            }
+            Err(e) => return Err(server_error(e)),
+        };
+        let rules = load_lot_rules(db, &slot.lot_id.to_string()).await;
+        if rules.slot_assignment != SlotAssignmentMode::LateBinding {
+            return Ok(Placement::Slot(slot));
+        }
+        (slot.lot_id, slot.slot_type)
+    };
+
+    let slots = type_slots(db, lot_id, &slot_type)
+        .await
+        .map_err(server_error)?;
+    if slots.is_empty() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "This lot has no bookable slots of that type",
+            )),
+        ));
+    }
+    Ok(Placement::Type {
+        lot_id,
+        slot_type,
+        slots,
+    })
+}
+
+/// `409 NO_SLOT_AVAILABLE` for a late-binding booking that does not fit.
+pub(crate) fn type_unavailable_response<T>(
+    slot_type: &SlotType,
+) -> (StatusCode, Json<ApiResponse<T>>) {
+    (
+        StatusCode::CONFLICT,
+        Json(ApiResponse::error(
+            ErrorCode::NoSlotAvailable,
+            format!("No {slot_type:?} slot is free for this time"),
+        )),
+    )
+}
+
+/// Assign a slot to the pending late-binding booking `booking_id`, mark the
+/// slot reserved and tell the user its number.
+pub(crate) async fn assign_booking(
+    db: &Database,
+    booking_id: Uuid,
+) -> anyhow::Result<SlotAssignment> {
+    let Some(pending) = db.get_pending_assignment(booking_id).await? else {
+        return Ok(SlotAssignment::NotPending);
+    };
+    let lot = db.get_parking_lot(&pending.lot_id.to_string()).await?;
+    let candidates: Vec<(ParkingSlot, String)> = type_slots(db, pending.lot_id, &pending.slot_type)
+        .await?
+        .into_iter()
+        .filter(|s| matches!(s.status, SlotStatus::Available | SlotStatus::Reserved))
+        .map(|s| {
+            let floor_name = lot
+                .as_ref()
+                .and_then(|lot| lot.floors.iter().find(|f| f.id == s.floor_id))
+                .map_or_else(|| "Level 1".to_string(), |f| f.name.clone());
+            (s, floor_name)
+        })
+        .collect();
+
+    let outcome = db.assign_pending_slot(booking_id, &candidates).await?;
+    let SlotAssignment::Assigned(ref booking) = outcome else {
+        return Ok(outcome);
+    };
+
+    if let Some((slot, _)) = candidates.iter().find(|(s, _)| s.id == booking.slot_id)
+        && slot.status != SlotStatus::Reserved
+    {
+        let mut reserved = slot.clone();
+        reserved.status = SlotStatus::Reserved;
+        let change =
+            SlotChangeContext::system("slot_assignment").reason(format!("Booking {}", booking.id));
+        if let Err(e) = db.save_parking_slot_with(&reserved, &change).await {
+            tracing::warn!("Failed to reserve assigned slot {}: {}", reserved.id, e);
+        }
+    }
+
+    let lot_name = lot.map_or_else(String::new, |lot| format!(" at {}", lot.name));
+    let note = Notification {
+        id: Uuid::new_v4(),
+        user_id: booking.user_id,
+        notification_type: NotificationType::BookingReminder,
+        title: "Slot assigned".to_string(),
+        message: format!(
+            "Your booking{lot_name} starting {} is in slot {} ({}).",
+            booking.start_time.format("%Y-%m-%d %H:%M UTC"),
+            booking.slot_number,
+            booking.floor_name
+        ),
+        data: Some(serde_json::json!({
+            "booking_id": booking.id,
+            "slot_id": booking.slot_id,
+            "slot_number": booking.slot_number,
+        })),
+        read: false,
+        created_at: Utc::now(),
+    };
+    if let Err(e) = db.save_notification(&note).await {
+        tracing::warn!("Failed to notify user {}: {}", booking.user_id, e);
+    }
+    tracing::info!(
+        booking_id = %booking.id,
+        slot_number = booking.slot_number,
+        "Assigned late-binding slot"
+    );
+    Ok(outcome)
+}
+
+/// Assign slots to every pending booking whose lot's assignment lead time
+/// has been reached. Returns how many bookings got a slot.
+///
+/// Bookings are handled in start-time order, which keeps the greedy
+/// best-fit choice from stranding a later booking. Bookings in a lot that
+/// has since switched back to fixed slots are assigned right away.
+pub(crate) async fn assign_due_slots(db: &Database, now: DateTime<Utc>) -> anyhow::Result<usize> {
+    let mut due = Vec::new();
+    let mut rules_by_lot = HashMap::new();
+    for pending in db.list_pending_assignments().await? {
+        let Some(booking) = db.get_booking(&pending.booking_id.to_string()).await? else {
+            // Removes the stale record.
+            assign_booking(db, pending.booking_id).await?;
+            continue;
+        };
+        let rules = match rules_by_lot.entry(pending.lot_id) {
+            Entry::Occupied(known) => known.into_mut(),
+            Entry::Vacant(vacant) => {
+                vacant.insert(load_lot_rules(db, &pending.lot_id.to_string()).await)
+            }
+        };
+        if rules.slot_assignment != SlotAssignmentMode::LateBinding
+            || rules.assignment_due(booking.start_time, now)
+        {
+            due.push((booking.start_time, pending.booking_id));
+        }
+    }
+    due.sort_unstable();
+
+    let mut assigned = 0;
+    for (_, booking_id) in due {
+        match assign_booking(db, booking_id).await? {
+            SlotAssignment::Assigned(_) => assigned += 1,
+            SlotAssignment::NoSlotFree => {
+                tracing::warn!("No free slot for late-binding booking {booking_id}; will retry");
+            }
+            SlotAssignment::NotPending => {}
+        }
+    }
+    Ok(assigned)
+}
@@ -0,0 +1,131 @@
+//! Slot status history for admins.
+//!
+//! - `GET /api/v1/admin/slots/{id}/history` — who changed a slot's status,
+//!   when and why, newest first
+//!
+//! Entries are recorded by the database layer; see [`crate::db::SlotStatusChange`].
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode};
+
+use crate::db::{MAX_HISTORY_PER_SLOT, SlotStatusChange};
+
+use super::{AuthUser, SharedState, check_admin};
+
+/// Default number of entries returned.
+const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// Query parameters for the slot history.
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct SlotHistoryQuery {
+    /// Maximum entries to return (default 100)
+    pub limit: Option<usize>,
+}
+
+/// A status change with the acting user's name resolved.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct SlotHistoryEntry {
+    #[serde(flatten)]
+    pub change: SlotStatusChange,
+    /// Username of `changed_by`; `None` for system changes or deleted users
+    pub changed_by_name: Option<String>,
+}
+
+/// `GET /api/v1/admin/slots/{id}/history`
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/slots/{id}/history",
+    tag = "Admin",
+    summary = "Slot status history",
+    description = "Returns the slot's status changes (who or what changed it, when, and why), \
+        newest first. Entries are kept for the operational-presence retention period, at most \
+        500 per slot. Admin only.",
+    params(
+        ("id" = String, Path, description = "Slot ID"),
+        SlotHistoryQuery,
+    ),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Status history", body = Vec<SlotHistoryEntry>),
+        (status = 403, description = "Admin access required"),
+        (status = 404, description = "Slot not found"),
+    )
+)]
+pub async fn get_slot_history(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(slot_id): Path<String>,
+    Query(query): Query<SlotHistoryQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<SlotHistoryEntry>>>) {
+    let state_guard = state.read().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    if !matches!(state_guard.db.get_parking_slot(&slot_id).await, Ok(Some(_))) {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::NotFound, "Slot not found")),
+        );
+    }
+
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_HISTORY_LIMIT)
+        .clamp(1, MAX_HISTORY_PER_SLOT);
+    let changes = match state_guard.db.list_slot_history(&slot_id, limit).await {
+        Ok(changes) => changes,
+        Err(e) => {
+            tracing::error!("Failed to load slot history: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    let mut names: HashMap<Uuid, Option<String>> = HashMap::new();
+    let mut entries = Vec::with_capacity(changes.len());
+    for change in changes {
+        let changed_by_name = match change.changed_by {
+            Some(user_id) => match names.entry(user_id) {
+                Entry::Occupied(known) => known.get().clone(),
+                Entry::Vacant(vacant) => {
+                    let name = state_guard
+                        .db
+                        .get_user(&user_id.to_string())
+                        .await
+                        .ok()
+                        .flatten()
+                        .map(|u| u.username);
+                    vacant.insert(name).clone()
+                }
+            },
+            None => None,
+        };
+        entries.push(SlotHistoryEntry {
+            change,
+            changed_by_name,
+        });
+    }
+
+    (StatusCode::OK, Json(ApiResponse::success(entries)))
+}
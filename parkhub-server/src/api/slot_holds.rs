@@ -0,0 +1,182 @@
+//! Soft locks on a slot during the booking flow.
+//!
+//! - `POST /api/v1/slots/{id}/hold` — hold a slot for the caller
+//! - `DELETE /api/v1/slots/{id}/hold` — release the caller's hold early
+//!
+//! A client places a hold when the user picks a slot and books within
+//! [`SLOT_HOLD_SECONDS`]. While the hold runs, other users' bookings of the
+//! slot fail with `SLOT_HELD` and slot-type bookings skip it, so two users
+//! picking the same slot find out at the start instead of the last step.
+//! Holding again refreshes the hold; holding another slot releases the
+//! first. The holder's booking releases the hold.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::HashSet;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode};
+
+use crate::db::{Database, SlotHold};
+
+use super::availability::is_usable;
+use super::{AuthUser, SharedState};
+
+/// How long a hold keeps other users off a slot.
+pub const SLOT_HOLD_SECONDS: i64 = 120;
+
+/// Slots of `lot_id` held for users other than `user_id` at `now`. Callers
+/// refuse the booking on error rather than treat every slot as free.
+pub(crate) async fn slots_held_by_others(
+    db: &Database,
+    lot_id: Uuid,
+    user_id: Uuid,
+    now: DateTime<Utc>,
+) -> anyhow::Result<HashSet<Uuid>> {
+    Ok(db
+        .list_lot_slot_holds(lot_id)
+        .await?
+        .into_iter()
+        .filter(|hold| hold.user_id != user_id && hold.is_active(now))
+        .map(|hold| hold.slot_id)
+        .collect())
+}
+
+/// `POST /api/v1/slots/{id}/hold` — hold a slot while the booking form is filled out
+#[utoipa::path(post, path = "/api/v1/slots/{id}/hold", tag = "Bookings",
+    summary = "Hold a slot",
+    description = "Keeps the slot for the caller for two minutes so nobody else can book it \
+        meanwhile. Holding again refreshes the hold; holding another slot releases this one.",
+    params(("id" = String, Path, description = "Parking slot ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Slot held", body = SlotHold),
+        (status = 404, description = "Parking slot not found"),
+        (status = 409, description = "Slot held by another user or out of service"),
+    )
+)]
+#[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+pub async fn hold_slot(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(slot_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<SlotHold>>) {
+    // The write lock orders holds against bookings, which check holds
+    // under the read lock.
+    let state_guard = state.write().await;
+    let Ok(Some(slot)) = state_guard.db.get_parking_slot(&slot_id).await else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Parking slot not found",
+            )),
+        );
+    };
+    if !is_usable(&slot) {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::SlotUnavailable,
+                "This slot is not available",
+            )),
+        );
+    }
+
+    let now = Utc::now();
+    match state_guard.db.get_slot_hold(&slot_id).await {
+        Ok(Some(hold)) if hold.user_id != auth_user.user_id && hold.is_active(now) => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::SlotHeld,
+                    "This slot is held by another user",
+                )),
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!("Failed to read slot hold: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    }
+
+    let hold = SlotHold {
+        slot_id: slot.id,
+        lot_id: slot.lot_id,
+        user_id: auth_user.user_id,
+        created_at: now,
+        expires_at: now + Duration::seconds(SLOT_HOLD_SECONDS),
+    };
+    if let Err(e) = state_guard.db.place_slot_hold(&hold).await {
+        tracing::error!("Failed to hold slot: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to hold slot",
+            )),
+        );
+    }
+    (StatusCode::OK, Json(ApiResponse::success(hold)))
+}
+
+/// `DELETE /api/v1/slots/{id}/hold` — release the caller's hold on a slot
+#[utoipa::path(delete, path = "/api/v1/slots/{id}/hold", tag = "Bookings",
+    summary = "Release a slot hold",
+    description = "Ends the caller's hold on the slot before it runs out, e.g. when the \
+        booking form is closed.",
+    params(("id" = String, Path, description = "Parking slot ID")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Hold released"),
+        (status = 404, description = "The caller holds no such slot"),
+    )
+)]
+#[cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+pub async fn release_slot_hold(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(slot_id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.read().await;
+    match state_guard
+        .db
+        .release_slot_hold(&slot_id, auth_user.user_id)
+        .await
+    {
+        Ok(true) => (StatusCode::OK, Json(ApiResponse::success(()))),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "No hold on this slot",
+            )),
+        ),
+        Err(e) => {
+            tracing::error!("Failed to release slot hold: {e}");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            )
+        }
+    }
+}
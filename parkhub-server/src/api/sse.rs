@@ -26,7 +26,7 @@ use axum::{
     },
 };
 use futures_util::Stream;
-use parkhub_common::{ApiResponse, FleetEvent, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode, FleetEvent, UserRole};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{RwLock, broadcast};
@@ -107,7 +107,7 @@ impl Default for FleetEventBroadcaster {
 fn is_event_visible(event: &FleetEvent, viewer_id: &str, viewer_role: &UserRole) -> bool {
     match viewer_role {
         UserRole::Admin | UserRole::SuperAdmin => true,
-        UserRole::User | UserRole::Premium => match event.user_id.as_deref() {
+        UserRole::User | UserRole::Premium | UserRole::Reporter => match event.user_id.as_deref() {
             Some(owner) => owner == viewer_id,
             None => true,
         },
@@ -122,7 +122,7 @@ type SharedState = Arc<RwLock<AppState>>;
 
 /// Extract the session token either from the `Authorization: Bearer` header or
 /// from the auth cookie.
-fn extract_token(headers: &axum::http::HeaderMap) -> Option<String> {
+pub(crate) fn extract_token(headers: &axum::http::HeaderMap) -> Option<String> {
     // Prefer Authorization header
     if let Some(v) = headers
         .get(header::AUTHORIZATION)
@@ -164,7 +164,7 @@ pub async fn fleet_events_handler(
             return Err((
                 StatusCode::UNAUTHORIZED,
                 Json(ApiResponse::error(
-                    "UNAUTHORIZED",
+                    ErrorCode::Unauthorized,
                     "Missing or invalid authorization",
                 )),
             ));
@@ -176,8 +176,8 @@ pub async fn fleet_events_handler(
     // per-subscriber (RBAC — Codex PR #378 finding).
     let (viewer_id, viewer_role) = {
         let state_guard = state.read().await;
-        match state_guard.db.get_session(&token).await {
-            Ok(Some(s)) if !s.is_expired() => {
+        match super::session_tokens::authenticate(&state_guard, &token).await {
+            Some(s) => {
                 // Confirm user is still active.
                 match state_guard.db.get_user(&s.user_id.to_string()).await {
                     Ok(Some(u)) if u.is_active => {
@@ -188,7 +188,7 @@ pub async fn fleet_events_handler(
                         return Err((
                             StatusCode::UNAUTHORIZED,
                             Json(ApiResponse::error(
-                                "UNAUTHORIZED",
+                                ErrorCode::Unauthorized,
                                 "Invalid or disabled user",
                             )),
                         ));
@@ -199,7 +199,7 @@ pub async fn fleet_events_handler(
                 return Err((
                     StatusCode::UNAUTHORIZED,
                     Json(ApiResponse::error(
-                        "UNAUTHORIZED",
+                        ErrorCode::Unauthorized,
                         "Invalid or expired token",
                     )),
                 ));
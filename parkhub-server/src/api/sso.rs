@@ -25,13 +25,15 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, AuthTokens, LoginResponse, User, UserPreferences, UserRole};
+use parkhub_common::{
+    ApiResponse, AuthTokens, ErrorCode, LoginResponse, User, UserPreferences, UserRole,
+};
 
 use crate::audit::{AuditEntry, AuditEventType};
 use crate::db::Session;
 use crate::metrics;
 
-use super::{AuthUser, SharedState, generate_access_token, hash_password_simple};
+use super::{AuthUser, SharedState, hash_password_simple, session_tokens};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Types
@@ -291,7 +293,7 @@ pub async fn sso_callback(
         Err(e) => {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::<()>::error("SSO_PARSE_ERROR", &e)),
+                Json(ApiResponse::<()>::error(ErrorCode::SsoParseError, &e)),
             )
                 .into_response();
         }
@@ -315,7 +317,7 @@ pub async fn sso_callback(
                     return (
                         StatusCode::INTERNAL_SERVER_ERROR,
                         Json(ApiResponse::<()>::error(
-                            "SERVER_ERROR",
+                            ErrorCode::ServerError,
                             "Internal server error",
                         )),
                     )
@@ -350,6 +352,7 @@ pub async fn sso_callback(
                 cost_center: None,
                 department: None,
                 settings: None,
+                must_change_password: false,
             };
 
             if let Err(e) = state_guard.db.save_user(&new_user).await {
@@ -357,7 +360,7 @@ pub async fn sso_callback(
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(ApiResponse::<()>::error(
-                        "USER_CREATE_ERROR",
+                        ErrorCode::UserCreateError,
                         "Failed to create account",
                     )),
                 )
@@ -383,22 +386,38 @@ pub async fn sso_callback(
     };
 
     // Create session
-    let session_hours = i64::from(state_guard.config.session_timeout_minutes).max(60) / 60;
+    match session_tokens::make_room_for_session(&state_guard, user.id).await {
+        Ok(true) => {}
+        Ok(false) => return super::auth::session_limit_response(),
+        Err(e) => {
+            tracing::error!("Failed to check session limit: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::SessionError,
+                    "Failed to create session",
+                )),
+            )
+                .into_response();
+        }
+    }
+    let session_hours = i64::from(state_guard.config.session_lifetime_hours).max(1);
     let role_str = format!("{:?}", user.role).to_lowercase();
     let session = Session::new(user.id, session_hours, &user.username, &role_str);
-    let access_token = generate_access_token();
-
-    if let Err(e) = state_guard.db.save_session(&access_token, &session).await {
-        tracing::error!("Failed to save SSO session: {e}");
-        return (
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::<()>::error(
-                "SESSION_ERROR",
-                "Failed to create session",
-            )),
-        )
-            .into_response();
-    }
+    let access_token = match session_tokens::start_session(&state_guard.db, &session).await {
+        Ok(token) => token,
+        Err(e) => {
+            tracing::error!("Failed to save SSO session: {e}");
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::<()>::error(
+                    ErrorCode::SessionError,
+                    "Failed to create session",
+                )),
+            )
+                .into_response();
+        }
+    };
     drop(state_guard);
 
     metrics::record_auth_event("sso_login", true);
@@ -438,7 +457,7 @@ pub async fn sso_configure_provider(
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "display_name, entity_id, and sso_url are required",
             )),
         ));
@@ -448,7 +467,7 @@ pub async fn sso_configure_provider(
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "certificate is required",
             )),
         ));
@@ -509,7 +528,10 @@ pub async fn sso_delete_provider(
     if providers.len() == initial_len {
         return Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "SSO provider not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "SSO provider not found",
+            )),
         ));
     }
 
@@ -550,7 +572,7 @@ async fn get_provider(
             (
                 StatusCode::NOT_FOUND,
                 Json(ApiResponse::error(
-                    "SSO_PROVIDER_NOT_FOUND",
+                    ErrorCode::SsoProviderNotFound,
                     "SSO provider not found or disabled",
                 )),
             )
@@ -18,11 +18,12 @@ use tokio::sync::RwLock;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use crate::AppState;
 
 use super::AuthUser;
+use super::feature_flags::{PaymentsFlag, RequireFlag};
 
 type SharedState = Arc<RwLock<AppState>>;
 
@@ -248,6 +249,7 @@ fn verify_stripe_signature(
 )]
 pub async fn create_checkout(
     State(_state): State<SharedState>,
+    _: RequireFlag<PaymentsFlag>,
     Extension(auth_user): Extension<AuthUser>,
     Extension(store): Extension<CheckoutStore>,
     Json(req): Json<CreateCheckoutRequest>,
@@ -256,7 +258,7 @@ pub async fn create_checkout(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "credits must be greater than 0",
             )),
         );
@@ -332,7 +334,7 @@ pub async fn stripe_webhook(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "WEBHOOK_SECRET_MISSING",
+                    ErrorCode::WebhookSecretMissing,
                     "webhook secret not configured",
                 )),
             );
@@ -349,7 +351,7 @@ pub async fn stripe_webhook(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "SIGNATURE_MISSING",
+                    ErrorCode::SignatureMissing,
                     "missing Stripe-Signature header",
                 )),
             );
@@ -360,7 +362,7 @@ pub async fn stripe_webhook(
         tracing::warn!("Stripe webhook signature verification failed: {reason}");
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("SIGNATURE_INVALID", reason)),
+            Json(ApiResponse::error(ErrorCode::SignatureInvalid, reason)),
         );
     }
 
@@ -372,7 +374,7 @@ pub async fn stripe_webhook(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "INVALID_BODY",
+                    ErrorCode::InvalidBody,
                     "failed to parse webhook event",
                 )),
             );
@@ -405,7 +407,7 @@ pub async fn stripe_webhook(
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(ApiResponse::error(
-                        "IDEMPOTENCY_STORE_ERROR",
+                        ErrorCode::IdempotencyStoreError,
                         "failed to record event for idempotency",
                     )),
                 );
@@ -468,6 +470,7 @@ pub async fn stripe_webhook(
     )
 )]
 pub async fn payment_history(
+    _: RequireFlag<PaymentsFlag>,
     Extension(auth_user): Extension<AuthUser>,
     Extension(store): Extension<CheckoutStore>,
 ) -> Json<ApiResponse<Vec<PaymentHistoryEntry>>> {
@@ -503,7 +506,7 @@ pub async fn payment_history(
         (status = 200, description = "Stripe config"),
     )
 )]
-pub async fn stripe_config() -> Json<ApiResponse<StripeConfig>> {
+pub async fn stripe_config(_: RequireFlag<PaymentsFlag>) -> Json<ApiResponse<StripeConfig>> {
     let configured = is_stripe_configured();
     let publishable_key = std::env::var("STRIPE_PUBLISHABLE_KEY").ok();
 
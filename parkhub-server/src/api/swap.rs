@@ -13,8 +13,8 @@ use chrono::Utc;
 use serde::Deserialize;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
 use parkhub_common::models::{SwapRequest, SwapRequestStatus};
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{AuthUser, SharedState};
 
@@ -41,7 +41,7 @@ pub async fn list_swap_requests(
         Err(e) => {
             tracing::error!("Failed to list swap requests: {}", e);
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to list swap requests",
             ))
         }
@@ -78,14 +78,17 @@ pub async fn create_swap_request(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Booking not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Booking not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -95,7 +98,7 @@ pub async fn create_swap_request(
         return (
             StatusCode::FORBIDDEN,
             Json(ApiResponse::error(
-                "FORBIDDEN",
+                ErrorCode::Forbidden,
                 "You can only create swap requests for your own bookings",
             )),
         );
@@ -111,14 +114,20 @@ pub async fn create_swap_request(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Target booking not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Target booking not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -128,7 +137,7 @@ pub async fn create_swap_request(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_SWAP",
+                ErrorCode::InvalidSwap,
                 "Cannot swap with your own booking",
             )),
         );
@@ -139,7 +148,7 @@ pub async fn create_swap_request(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_SWAP",
+                ErrorCode::InvalidSwap,
                 "Bookings must be in the same lot",
             )),
         );
@@ -161,7 +170,7 @@ pub async fn create_swap_request(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to create swap request",
             )),
         );
@@ -211,14 +220,20 @@ pub async fn update_swap_request(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Swap request not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Swap request not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -228,7 +243,7 @@ pub async fn update_swap_request(
         return (
             StatusCode::FORBIDDEN,
             Json(ApiResponse::error(
-                "FORBIDDEN",
+                ErrorCode::Forbidden,
                 "Only the target user can respond to this swap request",
             )),
         );
@@ -238,7 +253,7 @@ pub async fn update_swap_request(
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "ALREADY_RESOLVED",
+                ErrorCode::AlreadyResolved,
                 "This swap request has already been resolved",
             )),
         );
@@ -255,7 +270,7 @@ pub async fn update_swap_request(
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(ApiResponse::error(
-                        "SERVER_ERROR",
+                        ErrorCode::ServerError,
                         "Requester booking not found",
                     )),
                 );
@@ -269,7 +284,7 @@ pub async fn update_swap_request(
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(ApiResponse::error(
-                        "SERVER_ERROR",
+                        ErrorCode::ServerError,
                         "Target booking not found",
                     )),
                 );
@@ -293,14 +308,20 @@ pub async fn update_swap_request(
                 tracing::error!("Failed to save requester booking during swap: {}", e);
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("SERVER_ERROR", "Failed to perform swap")),
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Failed to perform swap",
+                    )),
                 );
             }
             if let Err(e) = state_guard.db.save_booking(&target_booking).await {
                 tracing::error!("Failed to save target booking during swap: {}", e);
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("SERVER_ERROR", "Failed to perform swap")),
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Failed to perform swap",
+                    )),
                 );
             }
 
@@ -313,7 +334,7 @@ pub async fn update_swap_request(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "INVALID_ACTION",
+                    ErrorCode::InvalidAction,
                     "Action must be 'accept' or 'decline'",
                 )),
             );
@@ -325,7 +346,7 @@ pub async fn update_swap_request(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update swap request",
             )),
         );
@@ -22,10 +22,12 @@ use tracing::Instrument;
 use utoipa::ToSchema;
 
 use parkhub_common::{
-    ApiResponse, HandshakeRequest, HandshakeResponse, PROTOCOL_VERSION, ServerStatus,
+    ApiResponse, ErrorCode, HandshakeRequest, HandshakeResponse, PROTOCOL_VERSION, ServerStatus,
+    ServerTime, ServerVersion, negotiate_protocol,
 };
 
 use crate::AppState;
+use crate::api::feature_flags::handshake_features;
 use crate::api::modules::module_registry;
 
 type SharedState = Arc<RwLock<AppState>>;
@@ -381,6 +383,47 @@ pub async fn v1_discover(State(state): State<SharedState>) -> Json<ApiResponse<D
     }))
 }
 
+/// Server clock for client-side countdowns.
+#[utoipa::path(
+    get,
+    path = "/api/v1/time",
+    tag = "Public",
+    summary = "Server time",
+    description = "Returns the server clock and the booking expiry warning window. Clients \
+        compare it with their local clock to correct countdowns for clock skew.",
+    responses((status = 200, description = "Server time", body = ServerTime))
+)]
+pub async fn v1_time(State(state): State<SharedState>) -> Json<ApiResponse<ServerTime>> {
+    let expiry_warning_minutes = state.read().await.config.booking_expiry_warning_minutes;
+    let now = chrono::Utc::now();
+    Json(ApiResponse::success(ServerTime {
+        server_time: now,
+        unix_millis: now.timestamp_millis(),
+        expiry_warning_minutes,
+    }))
+}
+
+/// Build and capabilities of the server, for update checks and monitoring.
+///
+/// Served at `/api/v1/version` when `mod-api-versioning` is off; with it on,
+/// `versioning::api_version` answers there with the same fields plus API
+/// deprecation notices.
+#[utoipa::path(
+    get,
+    path = "/api/v1/version",
+    tag = "Public",
+    summary = "Server version",
+    description = "Returns the server version, commit, protocol version, enabled modules and \
+        storage backend. With `mod-api-versioning` the response also carries `api_prefix`, \
+        `status`, `deprecations` and `supported_versions`.",
+    responses((status = 200, description = "Server version", body = ServerVersion))
+)]
+#[cfg_attr(feature = "mod-api-versioning", allow(dead_code))]
+pub async fn v1_version(State(state): State<SharedState>) -> Json<ApiResponse<ServerVersion>> {
+    let version = crate::build_info::server_version(&state.read().await.db).await;
+    Json(ApiResponse::success(version))
+}
+
 /// `GET /api/v1/system/version` — server version information
 pub async fn system_version() -> Json<serde_json::Value> {
     Json(serde_json::json!({
@@ -407,7 +450,7 @@ pub async fn system_maintenance(State(state): State<SharedState>) -> Json<serde_
     path = "/handshake",
     tag = "Health",
     summary = "Protocol handshake",
-    description = "Verifies protocol version compatibility between client and server.",
+    description = "Negotiates protocol compatibility between client and server. A different minor version connects in `compatible` mode, a different major version in `read_only` mode; `features` lists the enabled server modules and feature flags.",
     responses((status = 200, description = "Handshake result"))
 )]
 pub async fn handshake(
@@ -416,16 +459,18 @@ pub async fn handshake(
 ) -> Json<ApiResponse<HandshakeResponse>> {
     let state = state.read().await;
 
-    // Check protocol version compatibility
-    if request.protocol_version != PROTOCOL_VERSION {
+    // Negotiate how far the client can go instead of refusing mismatches;
+    // only unparseable versions are rejected
+    let Some(compatibility) = negotiate_protocol(&request.protocol_version, PROTOCOL_VERSION)
+    else {
         return Json(ApiResponse::error(
-            "PROTOCOL_MISMATCH",
+            ErrorCode::ProtocolMismatch,
             format!(
                 "Protocol version mismatch: server={}, client={}",
                 PROTOCOL_VERSION, request.protocol_version
             ),
         ));
-    }
+    };
 
     Json(ApiResponse::success(HandshakeResponse {
         server_name: state.config.server_name.clone(),
@@ -433,6 +478,8 @@ pub async fn handshake(
         protocol_version: PROTOCOL_VERSION.to_string(),
         requires_auth: true,
         certificate_fingerprint: String::new(),
+        compatibility,
+        features: handshake_features(&state.db).await,
     }))
 }
 
@@ -441,20 +488,25 @@ pub async fn handshake(
     path = "/status",
     tag = "Health",
     summary = "Server status overview",
-    description = "Returns aggregate server statistics.",
+    description = "Returns aggregate server statistics and the state of automatic backups.",
     responses((status = 200, description = "Server status"))
 )]
 pub async fn server_status(State(state): State<SharedState>) -> Json<ApiResponse<ServerStatus>> {
-    let db_stats = {
+    let (db_stats, backup) = {
         let state = state.read().await;
-        state.db.stats().await.unwrap_or(crate::db::DatabaseStats {
+        let db_stats = state.db.stats().await.unwrap_or(crate::db::DatabaseStats {
             users: 0,
             bookings: 0,
             parking_lots: 0,
             slots: 0,
             sessions: 0,
             vehicles: 0,
-        })
+            dummy_users: 0,
+        });
+        (
+            db_stats,
+            crate::auto_backup::status(&state.db, &state.config),
+        )
     };
 
     Json(ApiResponse::success(ServerStatus {
@@ -463,6 +515,7 @@ pub async fn server_status(State(state): State<SharedState>) -> Json<ApiResponse
         total_users: u32::try_from(db_stats.users).unwrap_or(u32::MAX),
         total_bookings: u32::try_from(db_stats.bookings).unwrap_or(u32::MAX),
         database_size_bytes: 0,
+        backup,
     }))
 }
 
@@ -800,7 +853,7 @@ pub async fn wizard_step(
                     return (
                         StatusCode::BAD_REQUEST,
                         Json(ApiResponse::error(
-                            "VALIDATION_ERROR",
+                            ErrorCode::ValidationError,
                             "Company name is required",
                         )),
                     );
@@ -835,7 +888,7 @@ pub async fn wizard_step(
                     return (
                         StatusCode::BAD_REQUEST,
                         Json(ApiResponse::error(
-                            "VALIDATION_ERROR",
+                            ErrorCode::ValidationError,
                             "Lot name is required",
                         )),
                     );
@@ -900,7 +953,10 @@ pub async fn wizard_step(
                 tracing::error!("Wizard: failed to create lot: {e}");
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("SERVER_ERROR", "Failed to create lot")),
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Failed to create lot",
+                    )),
                 );
             }
 
@@ -947,7 +1003,7 @@ pub async fn wizard_step(
                 return (
                     StatusCode::BAD_REQUEST,
                     Json(ApiResponse::error(
-                        "INVALID_THEME",
+                        ErrorCode::InvalidTheme,
                         "Invalid theme selection",
                     )),
                 );
@@ -967,7 +1023,10 @@ pub async fn wizard_step(
         }
         _ => (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_STEP", "Step must be 1-4")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidStep,
+                "Step must be 1-4",
+            )),
         ),
     }
 }
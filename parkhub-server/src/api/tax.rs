@@ -306,6 +306,84 @@ pub async fn resolve_standard_rate(state: &crate::AppState) -> f64 {
     resolve_profile(&country).standard_rate
 }
 
+/// Resolve the buyer country ISO code for a specific user.
+///
+/// Stored in a per-user setting `user_country_{user_id}` so operators can
+/// flag international customers without a schema migration. Falls back to
+/// the `tax_default_country` setting, then to the seller country so
+/// domestic buyers never trigger reverse-charge by accident.
+pub async fn resolve_buyer_country(state: &crate::AppState, user_id: uuid::Uuid) -> String {
+    let user_key = format!("user_country_{user_id}");
+    if let Ok(Some(v)) = state.db.get_setting(&user_key).await {
+        let trimmed = v.trim();
+        if !trimmed.is_empty() {
+            return trimmed.to_string();
+        }
+    }
+    if let Ok(Some(v)) = state.db.get_setting("tax_default_country").await {
+        let trimmed = v.trim();
+        if !trimmed.is_empty() {
+            return trimmed.to_string();
+        }
+    }
+    resolve_seller_country_from_settings(state).await
+}
+
+/// Resolve the buyer VAT ID for a specific user.
+///
+/// Stored in a per-user setting `user_vat_id_{user_id}`; absent/empty means
+/// B2C sale (no reverse-charge eligibility).
+pub async fn resolve_buyer_vat_id(state: &crate::AppState, user_id: uuid::Uuid) -> Option<String> {
+    let key = format!("user_vat_id_{user_id}");
+    match state.db.get_setting(&key).await {
+        Ok(Some(v)) if !v.trim().is_empty() => Some(v.trim().to_string()),
+        _ => None,
+    }
+}
+
+/// Net, VAT and gross amounts of a booking exactly as printed on its invoice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvoiceAmounts {
+    pub rate: ResolvedRate,
+    pub net: f64,
+    pub vat: f64,
+    pub gross: f64,
+}
+
+impl InvoiceAmounts {
+    /// Tax the stored net price of `booking` at `rate`.
+    #[must_use]
+    pub fn new(booking: &parkhub_common::Booking, rate: ResolvedRate) -> Self {
+        let net = booking.pricing.base_price;
+        let vat = net * rate.as_rate();
+        Self {
+            rate,
+            net,
+            vat,
+            gross: net + vat,
+        }
+    }
+}
+
+/// Resolve the invoice amounts for `booking`: the stored net price taxed at
+/// the rate for the buyer's country and VAT ID. Shared by the invoice
+/// renderers and the revenue report so the two always reconcile.
+pub async fn resolve_invoice_amounts(
+    state: &crate::AppState,
+    booking: &parkhub_common::Booking,
+) -> InvoiceAmounts {
+    InvoiceAmounts::new(booking, resolve_buyer_rate(state, booking.user_id).await)
+}
+
+/// VAT rate invoiced to `user_id`, from the seller country and the buyer's
+/// country and VAT ID.
+pub async fn resolve_buyer_rate(state: &crate::AppState, user_id: uuid::Uuid) -> ResolvedRate {
+    let seller_country = resolve_seller_country_from_settings(state).await;
+    let buyer_country = resolve_buyer_country(state, user_id).await;
+    let buyer_vat_id = resolve_buyer_vat_id(state, user_id).await;
+    resolve_rate(&seller_country, &buyer_country, buyer_vat_id.as_deref())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Tests
 // ─────────────────────────────────────────────────────────────────────────────
@@ -10,7 +10,7 @@ use serde::Serialize;
 use uuid::Uuid;
 
 use parkhub_common::models::AbsenceType;
-use parkhub_common::{ApiResponse, BookingStatus};
+use parkhub_common::{ApiResponse, BookingStatus, ErrorCode};
 
 use super::{AuthUser, SharedState};
 
@@ -44,7 +44,10 @@ pub async fn team_today(
             tracing::error!("Failed to list users: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to load users")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to load users",
+                )),
             );
         }
     };
@@ -0,0 +1,188 @@
+//! Admin controls for opt-in usage telemetry.
+//!
+//! - `GET /api/v1/admin/telemetry` — current settings plus the exact report that would be sent
+//! - `PUT /api/v1/admin/telemetry` — enable/disable and set the endpoint
+//!
+//! See [`crate::telemetry`] for what is collected and when it is sent.
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use axum::{Extension, Json, extract::State, http::StatusCode};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::{ApiResponse, ErrorCode};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::telemetry::{self, TelemetryReport};
+
+use super::{AuthUser, SharedState, check_admin};
+
+/// Telemetry settings together with a preview of the report.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TelemetryStatus {
+    pub enabled: bool,
+    /// Stored endpoint (may be overridden by `PARKHUB_TELEMETRY_URL`).
+    pub endpoint: Option<String>,
+    /// Endpoint reports are actually sent to, if any.
+    pub effective_endpoint: Option<String>,
+    pub install_id: Uuid,
+    pub last_sent_at: Option<DateTime<Utc>>,
+    /// Exactly what the next report will contain.
+    pub preview: TelemetryReport,
+}
+
+/// Request body for updating telemetry settings.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct UpdateTelemetryRequest {
+    pub enabled: bool,
+    /// `http(s)://` URL to post reports to. Omit to keep the current one;
+    /// send an empty string to clear it.
+    pub endpoint: Option<String>,
+}
+
+/// Validate a telemetry endpoint. Empty clears it.
+fn normalize_endpoint(raw: &str) -> Result<Option<String>, &'static str> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    if !(trimmed.starts_with("https://") || trimmed.starts_with("http://")) {
+        return Err("Telemetry endpoint must be an http(s) URL");
+    }
+    Ok(Some(trimmed.to_string()))
+}
+
+async fn telemetry_status(db: &crate::db::Database) -> TelemetryStatus {
+    let settings = telemetry::load_settings(db).await;
+    let preview = telemetry::build_report(db, settings.install_id).await;
+    TelemetryStatus {
+        enabled: settings.enabled,
+        effective_endpoint: settings.effective_endpoint(),
+        endpoint: settings.endpoint,
+        install_id: settings.install_id,
+        last_sent_at: settings.last_sent_at,
+        preview,
+    }
+}
+
+/// `GET /api/v1/admin/telemetry`
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/telemetry",
+    tag = "Admin",
+    summary = "Get telemetry settings",
+    description = "Returns the opt-in telemetry settings and the exact report that would be \
+        sent. Admin only.",
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Telemetry settings", body = TelemetryStatus),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn get_telemetry(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> (StatusCode, Json<ApiResponse<TelemetryStatus>>) {
+    let state_guard = state.read().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            telemetry_status(&state_guard.db).await,
+        )),
+    )
+}
+
+/// `PUT /api/v1/admin/telemetry`
+#[utoipa::path(
+    put,
+    path = "/api/v1/admin/telemetry",
+    tag = "Admin",
+    summary = "Update telemetry settings",
+    description = "Enable or disable anonymous usage reports and set the endpoint they are \
+        sent to. Nothing is sent without an endpoint. Admin only.",
+    request_body = UpdateTelemetryRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Telemetry settings updated", body = TelemetryStatus),
+        (status = 400, description = "Invalid endpoint"),
+        (status = 403, description = "Admin access required"),
+    )
+)]
+pub async fn update_telemetry(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(req): Json<UpdateTelemetryRequest>,
+) -> (StatusCode, Json<ApiResponse<TelemetryStatus>>) {
+    let state_guard = state.read().await;
+
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+
+    let mut settings = telemetry::load_settings(&state_guard.db).await;
+    if let Some(raw) = req.endpoint.as_deref() {
+        match normalize_endpoint(raw) {
+            Ok(endpoint) => settings.endpoint = endpoint,
+            Err(msg) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(ApiResponse::error(ErrorCode::ValidationError, msg)),
+                );
+            }
+        }
+    }
+    settings.enabled = req.enabled;
+
+    if let Err(e) = telemetry::save_settings(&state_guard.db, &settings).await {
+        tracing::error!("Failed to save telemetry settings: {e}");
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save settings",
+            )),
+        );
+    }
+
+    AuditEntry::new(AuditEventType::SettingsChanged)
+        .user(auth_user.user_id, "")
+        .detail(if settings.enabled {
+            "Usage telemetry enabled"
+        } else {
+            "Usage telemetry disabled"
+        })
+        .log();
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(
+            telemetry_status(&state_guard.db).await,
+        )),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_endpoint() {
+        assert_eq!(normalize_endpoint(""), Ok(None));
+        assert_eq!(normalize_endpoint("   "), Ok(None));
+        assert_eq!(
+            normalize_endpoint(" https://example.com/t "),
+            Ok(Some("https://example.com/t".into()))
+        );
+        assert!(normalize_endpoint("ftp://example.com").is_err());
+        assert!(normalize_endpoint("example.com").is_err());
+    }
+}
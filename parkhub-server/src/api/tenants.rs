@@ -56,7 +56,7 @@ pub async fn list_tenants(
     Extension(auth_user): Extension<AuthUser>,
 ) -> Result<Json<ApiResponse<Vec<Tenant>>>, (StatusCode, &'static str)> {
     let state_guard = state.read().await;
-    check_admin(&state_guard, &auth_user).await?;
+    check_admin(&auth_user)?;
 
     let user = state_guard
         .db
@@ -145,7 +145,7 @@ pub async fn update_tenant(
     Json(req): Json<TenantRequest>,
 ) -> Result<Json<ApiResponse<Tenant>>, (StatusCode, &'static str)> {
     let state_guard = state.read().await;
-    check_admin(&state_guard, &auth_user).await?;
+    check_admin(&auth_user)?;
 
     let user = state_guard
         .db
@@ -0,0 +1,449 @@
+//! Request and body-read timeouts.
+//!
+//! A client that trickles its request body, or a handler stuck on a slow
+//! operation, would otherwise hold its task and whatever state it borrowed
+//! indefinitely. [`timeout_middleware`] bounds both:
+//!
+//! - reading the request body must finish within the body-read timeout,
+//!   or the request is answered with `408 Request Timeout`;
+//! - the handler must produce a response within the request timeout for
+//!   its route, or the request is answered with `504 Gateway Timeout`.
+//!
+//! Both answers use the problem-details format (RFC 9457,
+//! `application/problem+json`) and are logged with the offending method and
+//! path. Streaming responses (SSE, WebSocket upgrades) only need their
+//! headers out in time; the stream itself is not limited.
+//!
+//! Limits come from the environment (seconds, `0` disables the limit):
+//!
+//! - `PARKHUB_REQUEST_TIMEOUT_SECS`   — handler timeout (default 30)
+//! - `PARKHUB_BODY_READ_TIMEOUT_SECS` — body-read timeout (default 15)
+//! - `PARKHUB_ROUTE_TIMEOUTS`         — per-endpoint handler timeouts as
+//!   comma-separated `path-prefix=secs` pairs, e.g.
+//!   `/api/v1/admin/export=120,/api/v1/admin/import=300`. The longest
+//!   matching prefix wins.
+//!
+//! Backup downloads and restores take as long as the database is large, and
+//! a restore uploads an archive of up to
+//! [`MAX_BACKUP_BYTES`](super::backup::MAX_BACKUP_BYTES). Neither has a
+//! body-read limit, nor a handler limit unless `PARKHUB_ROUTE_TIMEOUTS`
+//! names their path.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use async_stream::stream;
+use axum::{
+    body::{Body, HttpBody},
+    extract::Request,
+    http::{Method, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use futures_util::StreamExt;
+
+use crate::metrics;
+
+use super::backup::{BACKUP_PATH, RESTORE_PATH};
+
+/// Routes without default body-read and handler limits; see the module docs.
+const UNLIMITED_PATHS: [&str; 2] = [BACKUP_PATH, RESTORE_PATH];
+
+/// Request and body-read limits, by route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestTimeouts {
+    /// Handler timeout for routes without an override (`None` = no limit)
+    pub request: Option<Duration>,
+    /// How long reading the request body may take (`None` = no limit)
+    pub body_read: Option<Duration>,
+    /// Path prefix → handler timeout, longest prefix first
+    pub routes: Vec<(String, Option<Duration>)>,
+}
+
+impl RequestTimeouts {
+    pub const DEFAULT_REQUEST_SECS: u64 = 30;
+    pub const DEFAULT_BODY_READ_SECS: u64 = 15;
+
+    /// Load from the `PARKHUB_*_TIMEOUT*` env vars, falling back to the
+    /// defaults on unset / unparsable values.
+    #[must_use]
+    pub fn from_env() -> Self {
+        fn secs(name: &str, default: u64) -> Option<Duration> {
+            let secs = std::env::var(name)
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+                .unwrap_or(default);
+            limit(secs)
+        }
+        Self {
+            request: secs("PARKHUB_REQUEST_TIMEOUT_SECS", Self::DEFAULT_REQUEST_SECS),
+            body_read: secs(
+                "PARKHUB_BODY_READ_TIMEOUT_SECS",
+                Self::DEFAULT_BODY_READ_SECS,
+            ),
+            routes: parse_routes(&std::env::var("PARKHUB_ROUTE_TIMEOUTS").unwrap_or_default()),
+        }
+    }
+
+    /// Handler timeout for `path`.
+    #[must_use]
+    pub fn for_path(&self, path: &str) -> Option<Duration> {
+        if UNLIMITED_PATHS.contains(&path) {
+            // Only an entry for the path itself, not a shorter prefix
+            return self
+                .routes
+                .iter()
+                .find(|(prefix, _)| prefix == path)
+                .and_then(|(_, timeout)| *timeout);
+        }
+        self.routes
+            .iter()
+            .find(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .map_or(self.request, |(_, timeout)| *timeout)
+    }
+
+    /// Body-read timeout for `path`.
+    #[must_use]
+    pub fn body_read_for_path(&self, path: &str) -> Option<Duration> {
+        if UNLIMITED_PATHS.contains(&path) {
+            None
+        } else {
+            self.body_read
+        }
+    }
+}
+
+impl Default for RequestTimeouts {
+    fn default() -> Self {
+        Self {
+            request: limit(Self::DEFAULT_REQUEST_SECS),
+            body_read: limit(Self::DEFAULT_BODY_READ_SECS),
+            routes: Vec::new(),
+        }
+    }
+}
+
+/// `secs` as a limit; `0` means none.
+const fn limit(secs: u64) -> Option<Duration> {
+    if secs == 0 {
+        None
+    } else {
+        Some(Duration::from_secs(secs))
+    }
+}
+
+/// Parse `prefix=secs,prefix=secs`, skipping (and logging) malformed pairs.
+/// Sorted longest prefix first so the most specific one matches.
+fn parse_routes(spec: &str) -> Vec<(String, Option<Duration>)> {
+    let mut routes: Vec<(String, Option<Duration>)> = spec
+        .split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .filter_map(|pair| {
+            let parsed = pair.split_once('=').and_then(|(prefix, secs)| {
+                let prefix = prefix.trim();
+                let secs = secs.trim().parse::<u64>().ok()?;
+                prefix
+                    .starts_with('/')
+                    .then(|| (prefix.to_string(), limit(secs)))
+            });
+            if parsed.is_none() {
+                tracing::warn!(
+                    entry = pair,
+                    "Ignoring malformed PARKHUB_ROUTE_TIMEOUTS entry"
+                );
+            }
+            parsed
+        })
+        .collect();
+    routes.sort_by(|(a, _), (b, _)| b.len().cmp(&a.len()));
+    routes
+}
+
+/// Apply `timeouts` to one request; see the module docs.
+pub async fn timeout_middleware(
+    timeouts: Arc<RequestTimeouts>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let body_timed_out = Arc::new(AtomicBool::new(false));
+
+    let body_read = timeouts.body_read_for_path(&path);
+    let request = match body_read {
+        Some(body_read) if !request.body().is_end_stream() => {
+            let (parts, body) = request.into_parts();
+            let body = with_read_deadline(body, body_read, body_timed_out.clone());
+            Request::from_parts(parts, body)
+        }
+        _ => request,
+    };
+
+    let result = match timeouts.for_path(&path) {
+        Some(limit) => tokio::time::timeout(limit, next.run(request))
+            .await
+            .map_err(|_| limit),
+        None => Ok(next.run(request).await),
+    };
+
+    // A handler that failed on the cut-off body answers with whatever its
+    // extractor made of it; the timeout is the real cause
+    if body_timed_out.load(Ordering::Relaxed)
+        && let Some(body_read) = body_read
+    {
+        return body_read_timeout(&method, &path, body_read);
+    }
+    match result {
+        Ok(response) => response,
+        Err(limit) => handler_timeout(&method, &path, limit),
+    }
+}
+
+/// Wrap `body` so it fails once `limit` has passed, setting `timed_out`.
+fn with_read_deadline(body: Body, limit: Duration, timed_out: Arc<AtomicBool>) -> Body {
+    let deadline = tokio::time::Instant::now() + limit;
+    let mut data = body.into_data_stream();
+    Body::from_stream(stream! {
+        loop {
+            match tokio::time::timeout_at(deadline, data.next()).await {
+                Ok(Some(chunk)) => yield chunk,
+                Ok(None) => break,
+                Err(_) => {
+                    timed_out.store(true, Ordering::Relaxed);
+                    yield Err(axum::Error::new(std::io::Error::from(
+                        std::io::ErrorKind::TimedOut,
+                    )));
+                    break;
+                }
+            }
+        }
+    })
+}
+
+fn body_read_timeout(method: &Method, path: &str, limit: Duration) -> Response {
+    tracing::warn!(
+        %method,
+        path,
+        timeout_secs = limit.as_secs(),
+        "Request body not received in time"
+    );
+    metrics::record_request_timeout("body_read");
+    problem(
+        StatusCode::REQUEST_TIMEOUT,
+        "REQUEST_TIMEOUT",
+        "Request Timeout",
+        &format!(
+            "The request body was not received within {}s",
+            limit.as_secs()
+        ),
+        path,
+    )
+}
+
+fn handler_timeout(method: &Method, path: &str, limit: Duration) -> Response {
+    tracing::warn!(
+        %method,
+        path,
+        timeout_secs = limit.as_secs(),
+        "Request timed out"
+    );
+    metrics::record_request_timeout("handler");
+    problem(
+        StatusCode::GATEWAY_TIMEOUT,
+        "GATEWAY_TIMEOUT",
+        "Gateway Timeout",
+        &format!("The request was not completed within {}s", limit.as_secs()),
+        path,
+    )
+}
+
+/// An RFC 9457 problem-details response. `code` carries the same error code
+/// the JSON envelope would, for clients that switch on it.
+fn problem(status: StatusCode, code: &str, title: &str, detail: &str, instance: &str) -> Response {
+    let body = serde_json::json!({
+        "type": "about:blank",
+        "title": title,
+        "status": status.as_u16(),
+        "detail": detail,
+        "instance": instance,
+        "code": code,
+    });
+    (
+        status,
+        [(header::CONTENT_TYPE, "application/problem+json")],
+        body.to_string(),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::Bytes, routing::post};
+    use tower::ServiceExt;
+
+    fn app(timeouts: RequestTimeouts) -> Router {
+        let timeouts = Arc::new(timeouts);
+        Router::new()
+            .route(
+                "/slow",
+                post(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    "done"
+                }),
+            )
+            .route("/echo", post(|body: Bytes| async move { body }))
+            .route(
+                RESTORE_PATH,
+                post(|body: Bytes| async move {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    body
+                }),
+            )
+            .layer(axum::middleware::from_fn(move |req, next| {
+                timeout_middleware(timeouts.clone(), req, next)
+            }))
+    }
+
+    fn short() -> RequestTimeouts {
+        RequestTimeouts {
+            request: Some(Duration::from_millis(500)),
+            body_read: Some(Duration::from_millis(50)),
+            routes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_routes_longest_prefix_wins() {
+        let timeouts = RequestTimeouts {
+            routes: parse_routes(" /api/v1/admin=60, /api/v1/admin/export=0 ,bogus,/x=abc"),
+            ..RequestTimeouts::default()
+        };
+        assert_eq!(timeouts.routes.len(), 2);
+        assert_eq!(timeouts.for_path("/api/v1/admin/export/csv"), None);
+        assert_eq!(
+            timeouts.for_path("/api/v1/admin/users"),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            timeouts.for_path("/api/v1/bookings"),
+            Some(Duration::from_secs(RequestTimeouts::DEFAULT_REQUEST_SECS))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slow_handler_gets_504_problem() {
+        let resp = app(short())
+            .oneshot(
+                axum::http::Request::post("/slow")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["status"], 504);
+        assert_eq!(json["instance"], "/slow");
+    }
+
+    #[tokio::test]
+    async fn test_route_override_lifts_the_limit() {
+        let timeouts = RequestTimeouts {
+            routes: parse_routes("/slow=0"),
+            ..short()
+        };
+        let resp = app(timeouts)
+            .oneshot(
+                axum::http::Request::post("/slow")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_stalled_body_gets_408_problem() {
+        let stalled = futures_util::stream::pending::<Result<Bytes, std::io::Error>>();
+        let resp = app(short())
+            .oneshot(
+                axum::http::Request::post("/echo")
+                    .body(Body::from_stream(stalled))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::REQUEST_TIMEOUT);
+        assert_eq!(
+            resp.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_is_exempt_from_default_limits() {
+        // Trickles well past the body-read limit, then the handler runs
+        // past the request limit
+        let chunks = futures_util::stream::iter(0..5).then(|i| async move {
+            tokio::time::sleep(Duration::from_millis(40)).await;
+            Ok::<_, std::io::Error>(Bytes::from(format!("chunk{i};")))
+        });
+        let resp = app(short())
+            .oneshot(
+                axum::http::Request::post(RESTORE_PATH)
+                    .body(Body::from_stream(chunks))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"chunk0;chunk1;chunk2;chunk3;chunk4;");
+
+        // A timeout for the path itself still applies; a shorter prefix doesn't
+        let timeouts = RequestTimeouts {
+            routes: parse_routes("/api/v1/admin=60"),
+            ..short()
+        };
+        assert_eq!(timeouts.for_path(BACKUP_PATH), None);
+        let timeouts = RequestTimeouts {
+            routes: parse_routes(&format!("{BACKUP_PATH}=300")),
+            ..short()
+        };
+        assert_eq!(
+            timeouts.for_path(BACKUP_PATH),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(timeouts.body_read_for_path(BACKUP_PATH), None);
+    }
+
+    #[tokio::test]
+    async fn test_body_within_limit_passes_through() {
+        let resp = app(short())
+            .oneshot(
+                axum::http::Request::post("/echo")
+                    .body(Body::from("hello"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body[..], b"hello");
+    }
+}
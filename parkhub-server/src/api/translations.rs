@@ -9,10 +9,10 @@ use chrono::Utc;
 use serde::Deserialize;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
 use parkhub_common::models::{
     ProposalStatus, TranslationOverride, TranslationProposal, TranslationVote,
 };
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{AuthUser, SharedState, check_admin};
 
@@ -89,7 +89,7 @@ pub async fn list_overrides(
         Err(e) => {
             tracing::error!("Failed to list translation overrides: {}", e);
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to list overrides",
             ))
         }
@@ -148,7 +148,7 @@ pub async fn list_proposals(
         Err(e) => {
             tracing::error!("Failed to list translation proposals: {}", e);
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to list proposals",
             ))
         }
@@ -177,13 +177,19 @@ pub async fn get_proposal(
         Ok(Some(p)) => (StatusCode::OK, Json(ApiResponse::success(p))),
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Proposal not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Proposal not found",
+            )),
         ),
         Err(e) => {
             tracing::error!("Failed to get proposal: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to get proposal")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to get proposal",
+                )),
             )
         }
     }
@@ -209,7 +215,7 @@ pub async fn create_proposal(
     if let Err(msg) = validate_proposal_input(&req) {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("VALIDATION", msg)),
+            Json(ApiResponse::error(ErrorCode::Validation, msg)),
         );
     }
 
@@ -245,7 +251,7 @@ pub async fn create_proposal(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to create proposal",
             )),
         );
@@ -286,7 +292,10 @@ pub async fn vote_on_proposal(
     if req.vote != "up" && req.vote != "down" {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID", "Vote must be 'up' or 'down'")),
+            Json(ApiResponse::error(
+                ErrorCode::Invalid,
+                "Vote must be 'up' or 'down'",
+            )),
         );
     }
 
@@ -297,14 +306,17 @@ pub async fn vote_on_proposal(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Proposal not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Proposal not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("DB error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Database error")),
+                Json(ApiResponse::error(ErrorCode::ServerError, "Database error")),
             );
         }
     };
@@ -313,7 +325,7 @@ pub async fn vote_on_proposal(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "NOT_PENDING",
+                ErrorCode::NotPending,
                 "Can only vote on pending proposals",
             )),
         );
@@ -323,7 +335,7 @@ pub async fn vote_on_proposal(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "OWN_PROPOSAL",
+                ErrorCode::OwnProposal,
                 "Cannot vote on your own proposal",
             )),
         );
@@ -347,7 +359,10 @@ pub async fn vote_on_proposal(
                 tracing::error!("Failed to delete vote: {}", e);
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("SERVER_ERROR", "Failed to update vote")),
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Failed to update vote",
+                    )),
                 );
             }
             if req.vote == "up" {
@@ -365,7 +380,10 @@ pub async fn vote_on_proposal(
                 tracing::error!("Failed to delete old vote: {}", e);
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("SERVER_ERROR", "Failed to update vote")),
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Failed to update vote",
+                    )),
                 );
             }
             let new_vote = TranslationVote {
@@ -379,7 +397,10 @@ pub async fn vote_on_proposal(
                 tracing::error!("Failed to save vote: {}", e);
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
-                    Json(ApiResponse::error("SERVER_ERROR", "Failed to save vote")),
+                    Json(ApiResponse::error(
+                        ErrorCode::ServerError,
+                        "Failed to save vote",
+                    )),
                 );
             }
             if req.vote == "up" {
@@ -403,7 +424,10 @@ pub async fn vote_on_proposal(
             tracing::error!("Failed to save vote: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to save vote")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to save vote",
+                )),
             );
         }
         if req.vote == "up" {
@@ -419,7 +443,7 @@ pub async fn vote_on_proposal(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update proposal",
             )),
         );
@@ -470,8 +494,8 @@ pub async fn review_proposal(
     let state = state.read().await;
 
     // Admin check
-    if let Err((status, msg)) = check_admin(&state, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     let new_status = match req.status.as_str() {
@@ -481,7 +505,7 @@ pub async fn review_proposal(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "INVALID",
+                    ErrorCode::Invalid,
                     "Status must be 'approved' or 'rejected'",
                 )),
             );
@@ -493,14 +517,17 @@ pub async fn review_proposal(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Proposal not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Proposal not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("DB error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Database error")),
+                Json(ApiResponse::error(ErrorCode::ServerError, "Database error")),
             );
         }
     };
@@ -509,7 +536,7 @@ pub async fn review_proposal(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "NOT_PENDING",
+                ErrorCode::NotPending,
                 "Proposal already reviewed",
             )),
         );
@@ -531,7 +558,10 @@ pub async fn review_proposal(
         tracing::error!("Failed to save reviewed proposal: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save review")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save review",
+            )),
         );
     }
 
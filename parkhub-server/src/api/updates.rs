@@ -8,7 +8,7 @@
 use axum::{Extension, Json, extract::State, http::StatusCode};
 use serde::{Deserialize, Serialize};
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::{AuthUser, SharedState, check_admin};
 
@@ -41,14 +41,15 @@ pub struct VersionHistoryEntry {
 
 /// Check GitHub for a newer version.
 pub async fn check_for_updates(
-    State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<UpdateCheckResponse>>) {
-    let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -66,7 +67,7 @@ pub async fn check_for_updates(
             return (
                 StatusCode::BAD_GATEWAY,
                 Json(ApiResponse::error(
-                    "UPSTREAM_ERROR",
+                    ErrorCode::UpstreamError,
                     "Failed to reach GitHub",
                 )),
             );
@@ -77,7 +78,7 @@ pub async fn check_for_updates(
         return (
             StatusCode::BAD_GATEWAY,
             Json(ApiResponse::error(
-                "UPSTREAM_ERROR",
+                ErrorCode::UpstreamError,
                 "GitHub API returned an error",
             )),
         );
@@ -89,7 +90,10 @@ pub async fn check_for_updates(
             tracing::error!("Failed to parse GitHub release: {e}");
             return (
                 StatusCode::BAD_GATEWAY,
-                Json(ApiResponse::error("PARSE_ERROR", "Invalid GitHub response")),
+                Json(ApiResponse::error(
+                    ErrorCode::ParseError,
+                    "Invalid GitHub response",
+                )),
             );
         }
     };
@@ -124,10 +128,13 @@ pub async fn apply_update(
     Json(req): Json<ApplyUpdateRequest>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
     drop(state_guard);
@@ -139,7 +146,7 @@ pub async fn apply_update(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_VERSION",
+                ErrorCode::InvalidVersion,
                 "Version must be 'latest' or a valid semver (e.g. 1.2.3)",
             )),
         );
@@ -165,14 +172,20 @@ pub async fn apply_update(
             Err(_) => {
                 return (
                     StatusCode::BAD_GATEWAY,
-                    Json(ApiResponse::error("PARSE_ERROR", "Invalid release data")),
+                    Json(ApiResponse::error(
+                        ErrorCode::ParseError,
+                        "Invalid release data",
+                    )),
                 );
             }
         },
         _ => {
             return (
                 StatusCode::BAD_GATEWAY,
-                Json(ApiResponse::error("UPSTREAM_ERROR", "Release not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::UpstreamError,
+                    "Release not found",
+                )),
             );
         }
     };
@@ -214,10 +227,13 @@ pub async fn update_history(
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<Vec<VersionHistoryEntry>>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -232,14 +248,15 @@ pub async fn update_history(
 
 /// List all available GitHub releases.
 pub async fn list_releases(
-    State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
 ) -> (StatusCode, Json<ApiResponse<Vec<serde_json::Value>>>) {
-    let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -280,10 +297,13 @@ pub async fn rollback_update(
     Json(req): Json<ApplyUpdateRequest>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
     let state_guard = state.read().await;
-    if check_admin(&state_guard, &auth_user).await.is_err() {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -294,7 +314,7 @@ pub async fn rollback_update(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_VERSION",
+                ErrorCode::InvalidVersion,
                 "Version must be 'previous' or a valid semver (e.g. 1.2.3)",
             )),
         );
@@ -1,4 +1,5 @@
 //! User handlers: profile, preferences, stats, password, GDPR export/delete.
+//! Rectification requests (Art. 16) are in `rectification.rs`.
 //!
 //! Extracted from mod.rs — Phase 3 API extraction.
 
@@ -18,7 +19,10 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, BookingStatus, CreditTransactionType, User, UserRole};
+use parkhub_common::{
+    AccessibilityPreferences, ApiResponse, BookingStatus, CreditTransactionType, ErrorCode, User,
+    UserRole,
+};
 
 use crate::AppState;
 use crate::audit::{AuditEntry, AuditEventType};
@@ -57,13 +61,16 @@ pub async fn get_current_user(
         }
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         ),
         Err(e) => {
             tracing::error!(error = %e, "Failed to fetch current user");
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
@@ -77,6 +84,56 @@ pub struct UpdateCurrentUserRequest {
     picture: Option<String>,
 }
 
+/// Validate and apply the self-editable profile fields to `user`, returning
+/// the names of the fields that were provided. Shared by `PUT` and `PATCH`.
+fn apply_profile_fields(
+    user: &mut User,
+    name: Option<String>,
+    phone: Option<String>,
+    picture: Option<String>,
+) -> Result<Vec<&'static str>, &'static str> {
+    // ── Input length validation (issue #115) ────────────────────────────────
+    if name.as_ref().is_some_and(|n| n.len() > 100) {
+        return Err("Name must be at most 100 characters");
+    }
+    if phone.as_ref().is_some_and(|p| p.len() > 20) {
+        return Err("Phone number must be at most 20 characters");
+    }
+    // Picture URL: must be empty, the user's own uploaded picture, or a
+    // well-formed http(s) URL capped at 2048 characters to prevent abuse.
+    if let Some(ref picture) = picture
+        && !picture.is_empty()
+        && *picture != super::avatars::picture_url(&user.id.to_string())
+    {
+        if picture.len() > 2048 {
+            return Err("Picture URL must be at most 2048 characters");
+        }
+        if !picture.starts_with("https://") && !picture.starts_with("http://") {
+            return Err("Picture must be a valid HTTP or HTTPS URL");
+        }
+    }
+
+    // Apply only the fields provided in the request
+    let mut changed = Vec::new();
+    if let Some(name) = name {
+        user.name = name;
+        changed.push("name");
+    }
+    if let Some(phone) = phone {
+        user.phone = Some(phone);
+        changed.push("phone");
+    }
+    if let Some(picture) = picture {
+        user.picture = if picture.is_empty() {
+            None
+        } else {
+            Some(picture)
+        };
+        changed.push("picture");
+    }
+    Ok(changed)
+}
+
 /// `PUT /api/v1/users/me` — update the authenticated user's own profile.
 ///
 /// Allows users to update their display name, phone number, and profile
@@ -111,97 +168,424 @@ pub async fn update_current_user(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error fetching user for update: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
-    // ── Input length validation (issue #115) ────────────────────────────────
-    if let Some(ref name) = req.name
-        && name.len() > 100
-    {
+    if let Err(msg) = apply_profile_fields(&mut user, req.name, req.phone, req.picture) {
         return (
             StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(ErrorCode::InvalidInput, msg)),
+        );
+    }
+    user.updated_at = Utc::now();
+
+    if let Err(e) = state_guard.db.save_user(&user).await {
+        tracing::error!("Failed to save user profile update: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
-                "Name must be at most 100 characters",
+                ErrorCode::ServerError,
+                "Failed to update profile",
             )),
         );
     }
-    if let Some(ref phone) = req.phone
-        && phone.len() > 20
-    {
+
+    AuditEntry::new(AuditEventType::UserUpdated)
+        .user(user.id, &user.username)
+        .log();
+
+    user.password_hash = String::new();
+    (StatusCode::OK, Json(ApiResponse::success(user)))
+}
+
+// ═══════════════════════════════════════════════════════════════════════════════
+// PARTIAL PROFILE UPDATE (PATCH) + EMAIL CHANGE CONFIRMATION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Fields a user may change on their own record. Anything else in a
+/// `PATCH /users/me` body (role, `is_active`, credits, username, …) is
+/// rejected rather than silently ignored.
+const SELF_EDITABLE_FIELDS: &[&str] = &["name", "phone", "picture", "email"];
+
+/// How long an email-change confirmation link stays valid.
+const EMAIL_CHANGE_TTL_HOURS: i64 = 24;
+
+/// Settings-key prefix for pending email changes (`email_change:<token>`).
+const EMAIL_CHANGE_PREFIX: &str = "email_change:";
+
+/// Request body for `PATCH /api/v1/users/me`
+#[derive(Debug, Default, Deserialize, utoipa::ToSchema)]
+pub struct PatchProfileRequest {
+    pub name: Option<String>,
+    pub phone: Option<String>,
+    /// `http(s)` URL, or empty to remove the picture
+    pub picture: Option<String>,
+    /// New email address. Not applied directly: a confirmation link is sent
+    /// to the new address and the change takes effect once it is opened.
+    pub email: Option<String>,
+}
+
+/// Response for `PATCH /api/v1/users/me`
+#[derive(Debug, serde::Serialize, utoipa::ToSchema)]
+pub struct PatchProfileResponse {
+    #[schema(value_type = Object)]
+    pub user: User,
+    /// Set when an email change is awaiting confirmation.
+    pub pending_email: Option<String>,
+}
+
+/// Pending email change stored under `email_change:<token>`.
+#[derive(Debug, serde::Serialize, Deserialize)]
+struct PendingEmailChange {
+    user_id: Uuid,
+    new_email: String,
+    expires_at: chrono::DateTime<Utc>,
+}
+
+/// Request body for confirming an email change.
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ConfirmEmailChangeRequest {
+    pub token: String,
+}
+
+/// Keys in `body` that are not on the self-edit whitelist.
+fn disallowed_profile_fields(body: &serde_json::Map<String, serde_json::Value>) -> Vec<String> {
+    body.keys()
+        .filter(|k| !SELF_EDITABLE_FIELDS.contains(&k.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// `PATCH /api/v1/users/me` — partial self-service profile update.
+#[utoipa::path(
+    patch,
+    path = "/api/v1/users/me",
+    tag = "Users",
+    summary = "Partially update current user profile",
+    description = "Updates any of `name`, `phone`, `picture`. Other fields (role, is_active, …) \
+        are rejected. A new `email` is confirmed by a link sent to that address before it \
+        takes effect.",
+    security(("bearer_auth" = [])),
+    request_body = PatchProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = PatchProfileResponse),
+        (status = 400, description = "Invalid input or non-editable field"),
+        (status = 404, description = "User not found"),
+        (status = 409, description = "Email already in use"),
+    )
+)]
+pub async fn patch_current_user(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(body): Json<serde_json::Value>,
+) -> (StatusCode, Json<ApiResponse<PatchProfileResponse>>) {
+    let Some(fields) = body.as_object() else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "Request body must be a JSON object",
+            )),
+        );
+    };
+    let disallowed = disallowed_profile_fields(fields);
+    if !disallowed.is_empty() {
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
-                "Phone number must be at most 20 characters",
+                ErrorCode::FieldNotEditable,
+                format!(
+                    "These fields cannot be changed here: {}",
+                    disallowed.join(", ")
+                ),
             )),
         );
     }
+    let req: PatchProfileRequest = match serde_json::from_value(body) {
+        Ok(r) => r,
+        Err(_) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(
+                    ErrorCode::InvalidInput,
+                    "Profile fields must be strings",
+                )),
+            );
+        }
+    };
 
-    // Apply only the fields provided in the request
-    if let Some(name) = req.name {
-        user.name = name;
-    }
-    if let Some(phone) = req.phone {
-        user.phone = Some(phone);
-    }
-    if let Some(picture) = req.picture {
-        // Validate picture URL: must be empty, or a well-formed http(s) URL
-        // capped at 2048 characters to prevent abuse.
-        if !picture.is_empty() {
-            if picture.len() > 2048 {
+    let state_guard = state.read().await;
+
+    let mut user = match state_guard
+        .db
+        .get_user(&auth_user.user_id.to_string())
+        .await
+    {
+        Ok(Some(u)) => u,
+        Ok(None) => {
+            return (
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Database error fetching user for update: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+
+    // Validate the requested email before touching anything else so a bad
+    // address does not leave a half-applied update behind.
+    let new_email = match req.email.as_deref().map(str::trim) {
+        Some(email) if !email.eq_ignore_ascii_case(&user.email) => {
+            if !parkhub_common::is_valid_email(email) {
                 return (
                     StatusCode::BAD_REQUEST,
                     Json(ApiResponse::error(
-                        "INVALID_INPUT",
-                        "Picture URL must be at most 2048 characters",
+                        ErrorCode::InvalidInput,
+                        "Invalid email address",
                     )),
                 );
             }
-            if !picture.starts_with("https://") && !picture.starts_with("http://") {
+            if let Ok(Some(other)) = state_guard.db.get_user_by_email(email).await
+                && other.id != user.id
+            {
                 return (
-                    StatusCode::BAD_REQUEST,
+                    StatusCode::CONFLICT,
                     Json(ApiResponse::error(
-                        "INVALID_INPUT",
-                        "Picture must be a valid HTTP or HTTPS URL",
+                        ErrorCode::EmailTaken,
+                        "This email address is already in use",
                     )),
                 );
             }
+            Some(email.to_string())
         }
-        user.picture = if picture.is_empty() {
-            None
-        } else {
-            Some(picture)
+        _ => None,
+    };
+
+    let mut changed = match apply_profile_fields(&mut user, req.name, req.phone, req.picture) {
+        Ok(changed) => changed,
+        Err(msg) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ApiResponse::error(ErrorCode::InvalidInput, msg)),
+            );
+        }
+    };
+
+    if !changed.is_empty() {
+        user.updated_at = Utc::now();
+        if let Err(e) = state_guard.db.save_user(&user).await {
+            tracing::error!("Failed to save user profile update: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to update profile",
+                )),
+            );
+        }
+    }
+
+    if let Some(ref new_email) = new_email {
+        let mut token_bytes = [0u8; 32];
+        rand::Rng::fill_bytes(&mut rand::rng(), &mut token_bytes);
+        let token = hex::encode(token_bytes);
+        let pending = PendingEmailChange {
+            user_id: user.id,
+            new_email: new_email.clone(),
+            expires_at: Utc::now() + chrono::Duration::hours(EMAIL_CHANGE_TTL_HOURS),
+        };
+        let stored = match serde_json::to_string(&pending) {
+            Ok(json) => state_guard
+                .db
+                .set_setting(&format!("{EMAIL_CHANGE_PREFIX}{token}"), &json)
+                .await
+                .map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
         };
+        if let Err(e) = stored {
+            tracing::error!("Failed to store email change token: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to start email change",
+                )),
+            );
+        }
+        changed.push("email (pending confirmation)");
+
+        let app_url =
+            std::env::var("APP_URL").unwrap_or_else(|_| "http://localhost:8443".to_string());
+        let confirm_url = format!("{app_url}/confirm-email?token={token}");
+        let org_name = state_guard.config.organization_name.clone();
+
+        #[cfg(feature = "mod-email")]
+        {
+            let html = crate::email::build_email_change_email(&confirm_url, &org_name);
+            if let Err(e) = crate::email_queue::enqueue(
+                &state_guard.db,
+                new_email,
+                "Confirm your new email address",
+                &html,
+            )
+            .await
+            {
+                tracing::warn!(
+                    user_id = %user.id,
+                    error = %e,
+                    "Failed to queue email-change confirmation"
+                );
+            }
+        }
+
+        #[cfg(not(feature = "mod-email"))]
+        {
+            let _ = (&confirm_url, &org_name);
+            tracing::info!(
+                user_id = %user.id,
+                "Email module disabled — email-change confirmation not sent"
+            );
+        }
     }
-    user.updated_at = Utc::now();
 
-    if let Err(e) = state_guard.db.save_user(&user).await {
-        tracing::error!("Failed to save user profile update: {}", e);
+    if !changed.is_empty() {
+        AuditEntry::new(AuditEventType::UserUpdated)
+            .user(user.id, &user.username)
+            .resource("user", &user.id.to_string())
+            .detail(&format!("Profile fields changed: {}", changed.join(", ")))
+            .log()
+            .persist(&state_guard.db)
+            .await;
+    }
+
+    user.password_hash = String::new();
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(PatchProfileResponse {
+            user,
+            pending_email: new_email,
+        })),
+    )
+}
+
+/// `POST /api/v1/auth/confirm-email-change` — apply a pending email change.
+///
+/// The token from the confirmation link is the credential, so this works
+/// without a session (the link may be opened on another device).
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/confirm-email-change",
+    tag = "Users",
+    summary = "Confirm an email change",
+    description = "Applies a pending email change using the token from the confirmation email.",
+    request_body = ConfirmEmailChangeRequest,
+    responses(
+        (status = 200, description = "Email changed"),
+        (status = 400, description = "Invalid or expired token"),
+        (status = 409, description = "Email already in use"),
+    )
+)]
+pub async fn confirm_email_change(
+    State(state): State<SharedState>,
+    Json(req): Json<ConfirmEmailChangeRequest>,
+) -> (StatusCode, Json<ApiResponse<()>>) {
+    let state_guard = state.read().await;
+
+    let key = format!("{EMAIL_CHANGE_PREFIX}{}", req.token);
+    let pending: Option<PendingEmailChange> = match state_guard.db.get_setting(&key).await {
+        Ok(Some(json)) => serde_json::from_str(&json).ok(),
+        _ => None,
+    };
+    let Some(pending) = pending else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidToken,
+                "Confirmation link is invalid or has already been used",
+            )),
+        );
+    };
+
+    // One-shot: consume the token whatever happens next.
+    if let Err(e) = state_guard.db.set_setting(&key, "").await {
+        tracing::warn!("Failed to delete email change token: {e}");
+    }
+
+    if pending.expires_at < Utc::now() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::TokenExpired,
+                "Confirmation link has expired",
+            )),
+        );
+    }
+
+    let Ok(Some(mut user)) = state_guard.db.get_user(&pending.user_id.to_string()).await else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidToken,
+                "User not found",
+            )),
+        );
+    };
+
+    // The address may have been claimed since the change was requested.
+    if let Ok(Some(other)) = state_guard.db.get_user_by_email(&pending.new_email).await
+        && other.id != user.id
+    {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::EmailTaken,
+                "This email address is already in use",
+            )),
+        );
+    }
+
+    let old_email = std::mem::replace(&mut user.email, pending.new_email);
+    user.updated_at = Utc::now();
+    if let Err(e) = state_guard.db.update_user_email(&user, &old_email).await {
+        tracing::error!("Failed to apply email change: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
-                "Failed to update profile",
+                ErrorCode::ServerError,
+                "Failed to change email",
             )),
         );
     }
 
     AuditEntry::new(AuditEventType::UserUpdated)
         .user(user.id, &user.username)
-        .log();
+        .resource("user", &user.id.to_string())
+        .detail("Email address changed after confirmation")
+        .log()
+        .persist(&state_guard.db)
+        .await;
 
-    user.password_hash = String::new();
-    (StatusCode::OK, Json(ApiResponse::success(user)))
+    (StatusCode::OK, Json(ApiResponse::success(())))
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -249,13 +633,16 @@ pub async fn get_my_settings(
         Ok(Some(user)) => (StatusCode::OK, Json(ApiResponse::success(user.settings))),
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         ),
         Err(e) => {
             tracing::error!(error = %e, "Failed to fetch user settings");
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
@@ -297,7 +684,7 @@ pub async fn update_my_settings(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "settings must be a JSON object",
             )),
         );
@@ -309,7 +696,7 @@ pub async fn update_my_settings(
         return (
             StatusCode::PAYLOAD_TOO_LARGE,
             Json(ApiResponse::error(
-                "PAYLOAD_TOO_LARGE",
+                ErrorCode::PayloadTooLarge,
                 "Settings payload must be ≤ 32 KB",
             )),
         );
@@ -325,14 +712,17 @@ pub async fn update_my_settings(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!(error = %e, "Database error fetching user for settings update");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -345,7 +735,7 @@ pub async fn update_my_settings(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update settings",
             )),
         );
@@ -380,14 +770,17 @@ pub async fn get_user(
     let Ok(Some(caller)) = state.db.get_user(&auth_user.user_id.to_string()).await else {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     };
 
     if caller.role != UserRole::Admin && caller.role != UserRole::SuperAdmin {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -398,13 +791,16 @@ pub async fn get_user(
         }
         Ok(None) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         ),
         Err(e) => {
             tracing::error!("Database error: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             )
         }
     }
@@ -431,8 +827,11 @@ pub async fn gdpr_export_data(
         return (
             StatusCode::NOT_FOUND,
             [(header::CONTENT_TYPE, "application/json")],
-            serde_json::to_string(&ApiResponse::<()>::error("NOT_FOUND", "User not found"))
-                .unwrap_or_default(),
+            serde_json::to_string(&ApiResponse::<()>::error(
+                ErrorCode::NotFound,
+                "User not found",
+            ))
+            .unwrap_or_default(),
         );
     };
 
@@ -462,6 +861,11 @@ pub async fn gdpr_export_data(
         .list_notifications_by_user(&user_id)
         .await
         .unwrap_or_default();
+    let rectification_requests = state
+        .db
+        .list_rectification_requests_by_user(auth_user.user_id)
+        .await
+        .unwrap_or_default();
 
     // Note: password_hash is intentionally excluded from GDPR exports.
     // Exporting a password hash would allow offline brute-force attacks
@@ -485,6 +889,7 @@ pub async fn gdpr_export_data(
         "absences": absences,
         "credit_transactions": credit_transactions,
         "notifications": notifications,
+        "rectification_requests": rectification_requests,
     });
 
     let json_str = serde_json::to_string_pretty(&export).unwrap_or_default();
@@ -523,6 +928,15 @@ pub async fn gdpr_delete_account(
 
     match state_guard.db.anonymize_user(&user_id).await {
         Ok(true) => {
+            if let Err(e) =
+                super::session_tokens::end_user_sessions(&state_guard, auth_user.user_id).await
+            {
+                tracing::warn!(
+                    "Failed to end sessions of anonymized user {}: {}",
+                    user_id,
+                    e
+                );
+            }
             AuditEntry::new(AuditEventType::UserDeleted)
                 .user(auth_user.user_id, &username)
                 .log();
@@ -530,14 +944,14 @@ pub async fn gdpr_delete_account(
         }
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         ),
         Err(e) => {
             tracing::error!("GDPR anonymization failed for {}: {}", user_id, e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to anonymize account",
                 )),
             )
@@ -574,7 +988,7 @@ pub async fn change_password(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "New password must be at least 8 characters",
             )),
         );
@@ -590,14 +1004,17 @@ pub async fn change_password(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "User not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -607,12 +1024,23 @@ pub async fn change_password(
         return (
             StatusCode::UNAUTHORIZED,
             Json(ApiResponse::error(
-                "INVALID_PASSWORD",
+                ErrorCode::InvalidPassword,
                 "Current password is incorrect",
             )),
         );
     }
 
+    // A forced change has to actually replace the seeded password
+    if user.must_change_password && verify_password(&req.new_password, &user.password_hash).await {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::PasswordUnchanged,
+                "New password must differ from the current one",
+            )),
+        );
+    }
+
     // Hash new password
     let new_hash = match hash_password_simple(&req.new_password).await {
         Ok(h) => h,
@@ -620,7 +1048,10 @@ pub async fn change_password(
             tracing::error!("Password hashing failed: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -628,6 +1059,7 @@ pub async fn change_password(
     // Update user
     let mut updated_user = user;
     updated_user.password_hash = new_hash;
+    updated_user.must_change_password = false;
     updated_user.updated_at = Utc::now();
 
     if let Err(e) = state_guard.db.save_user(&updated_user).await {
@@ -635,7 +1067,7 @@ pub async fn change_password(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update password",
             )),
         );
@@ -643,10 +1075,7 @@ pub async fn change_password(
 
     // Invalidate all existing sessions for this user — a password change must
     // force re-authentication on every device (issue #116).
-    if let Err(e) = state_guard
-        .db
-        .delete_sessions_by_user(auth_user.user_id)
-        .await
+    if let Err(e) = super::session_tokens::end_user_sessions(&state_guard, auth_user.user_id).await
     {
         tracing::warn!(
             user_id = %auth_user.user_id,
@@ -698,7 +1127,7 @@ pub async fn user_stats(
     let Ok(Some(user)) = state_guard.db.get_user(&uid).await else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     };
 
@@ -797,11 +1226,12 @@ pub async fn get_user_preferences(
                 "notifications_enabled": user.preferences.notifications_enabled,
                 "email_reminders": user.preferences.email_reminders,
                 "default_duration_minutes": user.preferences.default_duration_minutes,
+                "accessibility": user.preferences.accessibility,
             }))),
         ),
         _ => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         ),
     }
 }
@@ -814,12 +1244,16 @@ pub struct UpdatePreferencesRequest {
     notifications_enabled: Option<bool>,
     email_reminders: Option<bool>,
     default_duration_minutes: Option<i32>,
+    /// Desktop client display settings (theme mode, font scale, reduce motion)
+    accessibility: Option<AccessibilityPreferences>,
 }
 
 /// `PUT /api/v1/user/preferences` — update preferences
 #[utoipa::path(put, path = "/api/v1/user/preferences", tag = "Users",
     summary = "Update user preferences",
-    description = "Updates user preferences.",
+    description = "Updates user preferences. `accessibility` holds the desktop client's display \
+        settings so they follow the user to any machine; `theme_mode` must be 0-5 and \
+        `font_scale` between 0.75 and 2.0.",
     security(("bearer_auth" = [])),
     responses((status = 200, description = "Success"))
 )]
@@ -828,6 +1262,16 @@ pub async fn update_user_preferences(
     Extension(auth_user): Extension<AuthUser>,
     Json(req): Json<UpdatePreferencesRequest>,
 ) -> (StatusCode, Json<ApiResponse<serde_json::Value>>) {
+    if req.accessibility.is_some_and(|a| !a.is_valid()) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "theme_mode must be 0-5 and font_scale between 0.75 and 2.0",
+            )),
+        );
+    }
+
     let state_guard = state.read().await;
 
     let Ok(Some(mut user)) = state_guard
@@ -837,7 +1281,7 @@ pub async fn update_user_preferences(
     else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "User not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "User not found")),
         );
     };
 
@@ -856,6 +1300,9 @@ pub async fn update_user_preferences(
     if let Some(dur) = req.default_duration_minutes {
         user.preferences.default_duration_minutes = Some(dur);
     }
+    if let Some(accessibility) = req.accessibility {
+        user.preferences.accessibility = Some(accessibility);
+    }
     user.updated_at = Utc::now();
 
     if let Err(e) = state_guard.db.save_user(&user).await {
@@ -863,7 +1310,7 @@ pub async fn update_user_preferences(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to save preferences",
             )),
         );
@@ -877,6 +1324,7 @@ pub async fn update_user_preferences(
             "notifications_enabled": user.preferences.notifications_enabled,
             "email_reminders": user.preferences.email_reminders,
             "default_duration_minutes": user.preferences.default_duration_minutes,
+            "accessibility": user.preferences.accessibility,
         }))),
     )
 }
@@ -921,6 +1369,18 @@ mod tests {
         assert_eq!(req.notifications_enabled, Some(false));
         assert!(req.email_reminders.is_none());
         assert!(req.default_duration_minutes.is_none());
+        assert!(req.accessibility.is_none());
+    }
+
+    #[test]
+    fn test_update_preferences_request_accessibility() {
+        let json = r#"{"accessibility":{"theme_mode":2,"font_scale":1.5,"reduce_motion":true}}"#;
+        let req: UpdatePreferencesRequest = serde_json::from_str(json).unwrap();
+        let a11y = req.accessibility.unwrap();
+        assert_eq!(a11y.theme_mode, 2);
+        assert!((a11y.font_scale - 1.5).abs() < f32::EPSILON);
+        assert!(a11y.reduce_motion);
+        assert!(req.language.is_none());
     }
 
     #[test]
@@ -934,4 +1394,57 @@ mod tests {
         assert_eq!(entry.resource_id.as_deref(), Some(uid.to_string().as_str()));
         assert!(entry.success);
     }
+
+    #[test]
+    fn test_disallowed_profile_fields() {
+        let body = serde_json::json!({
+            "name": "A",
+            "email": "a@example.com",
+            "role": "admin",
+            "is_active": true,
+        });
+        let mut bad = disallowed_profile_fields(body.as_object().unwrap());
+        bad.sort();
+        assert_eq!(bad, vec!["is_active", "role"]);
+
+        let body = serde_json::json!({"phone": "+49", "picture": ""});
+        assert!(disallowed_profile_fields(body.as_object().unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_apply_profile_fields_rejects_bad_picture() {
+        let mut user: User = serde_json::from_value(serde_json::json!({
+            "id": Uuid::new_v4(),
+            "username": "alice",
+            "email": "alice@example.com",
+            "password_hash": "",
+            "name": "Alice",
+            "picture": null,
+            "phone": null,
+            "role": "user",
+            "created_at": Utc::now(),
+            "updated_at": Utc::now(),
+            "last_login": null,
+            "preferences": parkhub_common::UserPreferences::default(),
+            "is_active": true,
+        }))
+        .unwrap();
+
+        let err = apply_profile_fields(&mut user, None, None, Some("javascript:alert(1)".into()));
+        assert!(err.is_err());
+
+        let changed =
+            apply_profile_fields(&mut user, Some("Alicia".into()), None, Some(String::new()))
+                .unwrap();
+        assert_eq!(changed, vec!["name", "picture"]);
+        assert_eq!(user.name, "Alicia");
+        assert!(user.picture.is_none());
+
+        // The user's own uploaded picture URL round-trips; someone else's does not.
+        let own = crate::api::avatars::picture_url(&user.id.to_string());
+        assert!(apply_profile_fields(&mut user, None, None, Some(own.clone())).is_ok());
+        assert_eq!(user.picture.as_deref(), Some(own.as_str()));
+        let other = crate::api::avatars::picture_url(&Uuid::new_v4().to_string());
+        assert!(apply_profile_fields(&mut user, None, None, Some(other)).is_err());
+    }
 }
@@ -16,10 +16,11 @@ use chrono::Utc;
 use parkhub_common::FuelType;
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, Vehicle};
+use parkhub_common::{ApiResponse, ErrorCode, Vehicle};
 
 use crate::audit::{AuditEntry, AuditEventType};
 use crate::requests::VehicleRequest;
+use crate::validation::ValidatedJson;
 
 use super::{AuthUser, MAX_PHOTO_BYTES, SharedState};
 
@@ -88,7 +89,7 @@ pub async fn list_vehicles(
         Err(e) => {
             tracing::error!("Database error: {}", e);
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to list vehicles",
             ))
         }
@@ -105,7 +106,7 @@ pub async fn list_vehicles(
 pub async fn create_vehicle(
     State(state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
-    Json(req): Json<VehicleRequest>,
+    ValidatedJson(req): ValidatedJson<VehicleRequest>,
 ) -> (StatusCode, Json<ApiResponse<Vehicle>>) {
     let vehicle = Vehicle {
         id: Uuid::new_v4(),
@@ -132,7 +133,7 @@ pub async fn create_vehicle(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to create vehicle",
             )),
         );
@@ -172,14 +173,17 @@ pub async fn delete_vehicle(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Vehicle not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Vehicle not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error fetching vehicle: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -187,7 +191,7 @@ pub async fn delete_vehicle(
     if vehicle.user_id != auth_user.user_id {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     }
 
@@ -214,14 +218,14 @@ pub async fn delete_vehicle(
         }
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Vehicle not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Vehicle not found")),
         ),
         Err(e) => {
             tracing::error!("Failed to delete vehicle {}: {}", id, e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to delete vehicle",
                 )),
             )
@@ -248,14 +252,17 @@ pub async fn update_vehicle(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Vehicle not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Vehicle not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -263,7 +270,7 @@ pub async fn update_vehicle(
     if vehicle.user_id != auth_user.user_id {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     }
 
@@ -288,7 +295,7 @@ pub async fn update_vehicle(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update vehicle",
             )),
         );
@@ -317,14 +324,17 @@ pub async fn upload_vehicle_photo(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Vehicle not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Vehicle not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -332,7 +342,7 @@ pub async fn upload_vehicle_photo(
     if vehicle.user_id != auth_user.user_id {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
         );
     }
 
@@ -341,7 +351,10 @@ pub async fn upload_vehicle_photo(
     let Ok(raw_bytes) = base64::engine::general_purpose::STANDARD.decode(b64_payload) else {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("INVALID_INPUT", "Invalid base64 data")),
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                "Invalid base64 data",
+            )),
         );
     };
 
@@ -349,7 +362,7 @@ pub async fn upload_vehicle_photo(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "PAYLOAD_TOO_LARGE",
+                ErrorCode::PayloadTooLarge,
                 "Photo exceeds 2 MB limit",
             )),
         );
@@ -359,7 +372,7 @@ pub async fn upload_vehicle_photo(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "INVALID_INPUT",
+                ErrorCode::InvalidInput,
                 "Unsupported image format. Only JPEG and PNG are accepted.",
             )),
         );
@@ -372,7 +385,10 @@ pub async fn upload_vehicle_photo(
         tracing::error!("Failed to save vehicle photo: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save photo")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save photo",
+            )),
         );
     }
 
@@ -396,7 +412,10 @@ pub async fn get_vehicle_photo(
     let Ok(Some(vehicle)) = state_guard.db.get_vehicle(&id).await else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::<()>::error("NOT_FOUND", "Vehicle not found")),
+            Json(ApiResponse::<()>::error(
+                ErrorCode::NotFound,
+                "Vehicle not found",
+            )),
         )
             .into_response();
     };
@@ -404,7 +423,10 @@ pub async fn get_vehicle_photo(
     if vehicle.user_id != auth_user.user_id {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::<()>::error("FORBIDDEN", "Access denied")),
+            Json(ApiResponse::<()>::error(
+                ErrorCode::Forbidden,
+                "Access denied",
+            )),
         )
             .into_response();
     }
@@ -413,7 +435,10 @@ pub async fn get_vehicle_photo(
     let Ok(Some(stored)) = state_guard.db.get_setting(&key).await else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::<()>::error("NOT_FOUND", "No photo found")),
+            Json(ApiResponse::<()>::error(
+                ErrorCode::NotFound,
+                "No photo found",
+            )),
         )
             .into_response();
     };
@@ -424,7 +449,7 @@ pub async fn get_vehicle_photo(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::<()>::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Corrupt photo data",
             )),
         )
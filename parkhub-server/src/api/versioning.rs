@@ -3,7 +3,8 @@
 //! Provides version information, deprecation notices, and changelog
 //! for the ParkHub REST API.
 //!
-//! - `GET /api/v1/version`    — current API version + deprecation notices
+//! - `GET /api/v1/version`    — server build (see [`crate::build_info`]),
+//!   API version and deprecation notices
 //! - `GET /api/v1/changelog`  — API changelog (breaking changes, new endpoints)
 //!
 //! Additionally, an Axum middleware layer injects:
@@ -20,7 +21,7 @@ use axum::{
 };
 use serde::{Deserialize, Serialize};
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ServerVersion};
 
 use super::SharedState;
 
@@ -74,7 +75,9 @@ pub struct DeprecationNotice {
 /// API version info response
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ApiVersionInfo {
-    pub version: String,
+    /// Version, commit, protocol, features and storage of the server
+    #[serde(flatten)]
+    pub server: ServerVersion,
     pub api_prefix: String,
     pub status: String,
     pub deprecations: Vec<DeprecationNotice>,
@@ -222,12 +225,13 @@ pub async fn api_version_middleware(request: Request<Body>, next: Next) -> Respo
 // HANDLERS
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// `GET /api/v1/version` — current API version and deprecation notices.
+/// `GET /api/v1/version` — server build, API version and deprecation notices.
 pub async fn api_version(
-    State(_state): State<SharedState>,
+    State(state): State<SharedState>,
 ) -> (StatusCode, Json<ApiResponse<ApiVersionInfo>>) {
+    let server = crate::build_info::server_version(&state.read().await.db).await;
     let info = ApiVersionInfo {
-        version: API_VERSION.to_string(),
+        server,
         api_prefix: "/api/v1".to_string(),
         status: "stable".to_string(),
         deprecations: generate_deprecation_notices(),
@@ -327,7 +331,13 @@ mod tests {
     #[test]
     fn test_api_version_info_serialize() {
         let info = ApiVersionInfo {
-            version: "4.1.0".to_string(),
+            server: ServerVersion {
+                version: "4.1.0".to_string(),
+                git_hash: "abc1234".to_string(),
+                protocol_version: "1.0.0".to_string(),
+                features: vec!["bookings".to_string()],
+                storage_backend: "redb".to_string(),
+            },
             api_prefix: "/api/v1".to_string(),
             status: "stable".to_string(),
             deprecations: vec![],
@@ -337,6 +347,7 @@ mod tests {
         assert!(json.contains("\"version\":\"4.1.0\""));
         assert!(json.contains("\"status\":\"stable\""));
         assert!(json.contains("\"api_prefix\":\"/api/v1\""));
+        assert!(json.contains("\"git_hash\":\"abc1234\""));
     }
 
     #[test]
@@ -0,0 +1,150 @@
+//! Bookings on behalf of visitors.
+//!
+//! - `POST /api/v1/bookings/visitor` — book a slot for a visitor by name and
+//!   license plate and return their QR pass
+//!
+//! Unlike guest bookings, these are regular [`Booking`]s: they go through the
+//! same availability, lot rule and pricing checks as the caller's own
+//! bookings and hold the slot like them. The caller stays the booking's
+//! `user_id` and manages it; `booked_for` names the visitor, whose name the
+//! pass shows. Callers need the `book_for_visitors` permission on the lot,
+//! which Premium users and admins hold.
+//!
+//! [`Booking`]: parkhub_common::Booking
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::BTreeMap;
+
+use axum::{Extension, Json, extract::State, http::StatusCode};
+use serde::Serialize;
+use uuid::Uuid;
+
+use parkhub_common::{
+    ApiResponse, BookedFor, BookingWithCountdown, CreateBookingRequest, ErrorCode, Permission,
+};
+
+use super::parking_pass::{ParkingPass, booking_pass};
+use super::{AuthUser, SharedState};
+use crate::requests::VisitorBookingRequest;
+use crate::validation::ValidatedJson;
+
+/// A visitor booking and the pass to hand to the visitor
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct VisitorBookingResponse {
+    #[schema(value_type = Object)]
+    pub booking: BookingWithCountdown,
+    pub pass: ParkingPass,
+}
+
+/// `POST /api/v1/bookings/visitor` — book a slot for a visitor
+#[utoipa::path(
+    post,
+    path = "/api/v1/bookings/visitor",
+    tag = "Bookings",
+    summary = "Book for a visitor",
+    description = "Books a slot for a visitor without an account, by name and license plate, \
+        and returns the booking with a printable QR pass. The pass is also emailed when \
+        `email` is set. The caller owns the booking; it is subject to the same rules as \
+        their own bookings except the daily booking limit. Requires `book_for_visitors` \
+        on the lot (Premium users and admins).",
+    request_body = VisitorBookingRequest,
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 201, description = "Booking created", body = VisitorBookingResponse),
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Not allowed to book for visitors on this lot"),
+        (status = 409, description = "Slot unavailable"),
+    )
+)]
+#[tracing::instrument(skip(state, req), fields(user_id = %auth_user.user_id, lot_id = %req.lot_id))]
+pub async fn create_visitor_booking(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    ValidatedJson(req): ValidatedJson<VisitorBookingRequest>,
+) -> (StatusCode, Json<ApiResponse<VisitorBookingResponse>>) {
+    if !auth_user
+        .permissions
+        .allows_lot(Permission::BookForVisitors, req.lot_id)
+    {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "You may not book for visitors on this lot",
+            )),
+        );
+    }
+
+    let visitor = BookedFor {
+        name: req.visitor_name.trim().to_string(),
+        license_plate: req.license_plate.trim().to_string(),
+        email: req.email.clone(),
+    };
+    let booking_req = CreateBookingRequest {
+        lot_id: req.lot_id,
+        slot_id: req.slot_id,
+        slot_type: None,
+        start_time: req.start_time,
+        duration_minutes: req.duration_minutes,
+        // The visitor's car is not one of the caller's vehicles
+        vehicle_id: Uuid::nil(),
+        license_plate: visitor.license_plate.clone(),
+        notes: req.notes,
+        tags: BTreeMap::new(),
+    };
+
+    let (status, Json(response)) =
+        super::bookings::place_booking(state.clone(), auth_user, booking_req, Some(visitor)).await;
+    let Some(booking) = response.data else {
+        return (
+            status,
+            Json(ApiResponse {
+                success: false,
+                data: None,
+                error: response.error,
+                meta: None,
+            }),
+        );
+    };
+
+    let state_guard = state.read().await;
+    let pass = booking_pass(&state_guard.db, &booking.booking).await;
+
+    #[cfg(feature = "mod-email")]
+    if let Some(email) = req.email {
+        let html = crate::email::build_visitor_pass_email(
+            &pass.user_name,
+            &booking.booking.vehicle.license_plate,
+            &pass.lot_name,
+            &pass.slot_number,
+            &booking
+                .booking
+                .start_time
+                .format("%Y-%m-%d %H:%M UTC")
+                .to_string(),
+            &booking
+                .booking
+                .end_time
+                .format("%Y-%m-%d %H:%M UTC")
+                .to_string(),
+            &pass.qr_data,
+            &state_guard.config.organization_name,
+        );
+        if let Err(e) =
+            crate::email_queue::enqueue(&state_guard.db, &email, "Your Parking Pass", &html).await
+        {
+            tracing::warn!("Failed to queue visitor pass email: {}", e);
+        }
+    }
+
+    (
+        StatusCode::CREATED,
+        Json(ApiResponse::success(VisitorBookingResponse {
+            booking,
+            pass,
+        })),
+    )
+}
@@ -0,0 +1,817 @@
+//! Visitor day passes.
+//!
+//! - `GET /api/v1/lots/{id}/visitor-pass-blocks` — the lot's daily blocks and
+//!   how much of each is used
+//! - `PUT /api/v1/lots/{id}/visitor-pass-blocks/{date}` — set the number of
+//!   passes for a day (admin or lot manager)
+//! - `GET /api/v1/lots/{id}/visitor-passes` — the passes issued for a day
+//! - `POST /api/v1/lots/{id}/visitor-passes` — issue a pass to a visitor
+//! - `POST /api/v1/visitor-passes/{id}/check-in` — the visitor arrived;
+//!   assign a slot
+//! - `DELETE /api/v1/visitor-passes/{id}` — cancel a pass before check-in
+//!
+//! Admins set aside a block of passes per lot and day. Reception staff (the
+//! `book_for_visitors` permission on the lot) issue passes from it by
+//! visitor name and license plate. An issued pass holds no slot; it only
+//! takes a place in the block. At check-in the visitor gets the first free
+//! slot of the lot and a [`Booking`] owned by the issuer runs until the end
+//! of the day, like a visitor booking. Days and "today" are UTC dates.
+//!
+//! [`Booking`]: parkhub_common::Booking
+
+// AppState read/write guards are held across handler duration by design —
+// db access goes through its own inner RwLock. See workspace lint config.
+#![allow(clippy::significant_drop_tightening)]
+
+use std::collections::BTreeMap;
+
+use axum::{
+    Extension, Json,
+    extract::{Path, Query, State},
+    http::StatusCode,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::{
+    ApiResponse, BookedFor, Booking, BookingPricing, BookingStatus, ErrorCode, FuelType,
+    PaymentStatus, Permission, SlotStatus, TimeRange, Vehicle, VehicleType,
+};
+
+use crate::audit::{AuditEntry, AuditEventType};
+use crate::db::{
+    Database, SlotChangeContext, VisitorPass, VisitorPassBlock, VisitorPassStatus, WriteBatch,
+};
+use crate::requests::{IssueVisitorPassRequest, VisitorPassBlockRequest};
+use crate::validation::ValidatedJson;
+
+use super::availability::free_slots;
+use super::lot_managers::check_lot_admin;
+use super::{AuthUser, SharedState, load_lot, server_error};
+
+/// Longest range of days `GET .../visitor-pass-blocks` covers.
+const MAX_BLOCK_RANGE_DAYS: i64 = 366;
+
+/// A lot's block for one day and how much of it is used.
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct VisitorPassDay {
+    pub lot_id: Uuid,
+    pub date: NaiveDate,
+    /// Passes reception may issue for the day
+    pub passes: u32,
+    /// Passes issued and not cancelled, checked in or not
+    pub issued: u32,
+    pub checked_in: u32,
+    /// Passes left to issue
+    pub remaining: u32,
+}
+
+impl VisitorPassDay {
+    fn new(block: &VisitorPassBlock, passes: &[VisitorPass]) -> Self {
+        let count = |pred: fn(&VisitorPass) -> bool| {
+            let n = passes
+                .iter()
+                .filter(|p| p.date == block.date && pred(p))
+                .count();
+            u32::try_from(n).unwrap_or(u32::MAX)
+        };
+        let issued = count(VisitorPass::counts_against_block);
+        Self {
+            lot_id: block.lot_id,
+            date: block.date,
+            passes: block.passes,
+            issued,
+            checked_in: count(|p| p.status == VisitorPassStatus::CheckedIn),
+            remaining: block.passes.saturating_sub(issued),
+        }
+    }
+}
+
+/// Query parameters of `GET /api/v1/lots/{id}/visitor-pass-blocks`.
+#[derive(Debug, Deserialize)]
+pub struct BlockRangeQuery {
+    /// First day (YYYY-MM-DD), default today
+    pub from: Option<String>,
+    /// Last day (YYYY-MM-DD), default 30 days after `from`
+    pub to: Option<String>,
+}
+
+/// Query parameters of `GET /api/v1/lots/{id}/visitor-passes`.
+#[derive(Debug, Deserialize)]
+pub struct PassDayQuery {
+    /// Day (YYYY-MM-DD), default today
+    pub date: Option<String>,
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+fn invalid_date<T>() -> (StatusCode, Json<ApiResponse<T>>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(ApiResponse::error(
+            ErrorCode::InvalidDate,
+            "Dates must be in YYYY-MM-DD format",
+        )),
+    )
+}
+
+/// Allow admins and managers of lot `lot_id` and users who may book for
+/// visitors on it; answer 403 otherwise.
+#[allow(clippy::result_large_err)]
+fn check_reception<T>(
+    auth_user: &AuthUser,
+    lot_id: Uuid,
+) -> Result<(), (StatusCode, Json<ApiResponse<T>>)> {
+    if check_lot_admin(auth_user, &lot_id.to_string()).is_ok()
+        || auth_user
+            .permissions
+            .allows_lot(Permission::BookForVisitors, lot_id)
+    {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "You may not handle visitor passes on this lot",
+            )),
+        ))
+    }
+}
+
+/// Load visitor pass `pass_id` for a caller who may handle its lot's
+/// passes, answering 404 if it does not exist.
+async fn load_pass<T>(
+    db: &Database,
+    auth_user: &AuthUser,
+    pass_id: &str,
+) -> Result<VisitorPass, (StatusCode, Json<ApiResponse<T>>)> {
+    let pass = match db.get_visitor_pass(pass_id).await {
+        Ok(Some(pass)) => pass,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Visitor pass not found",
+                )),
+            ));
+        }
+        Err(e) => {
+            tracing::error!("Database error: {e}");
+            return Err(server_error("Internal server error"));
+        }
+    };
+    check_reception(auth_user, pass.lot_id)?;
+    Ok(pass)
+}
+
+async fn username_of(db: &Database, user_id: Uuid) -> String {
+    db.get_user(&user_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .map(|u| u.username)
+        .unwrap_or_default()
+}
+
+/// `GET /api/v1/lots/{id}/visitor-pass-blocks` — blocks and their use.
+#[utoipa::path(get, path = "/api/v1/lots/{id}/visitor-pass-blocks", tag = "Lots",
+    summary = "List visitor pass blocks",
+    description = "The lot's daily visitor pass blocks from `from` to `to` (default: the \
+        next 30 days), with the passes issued and checked in on each day. Days without a \
+        block are left out. Admins, lot managers and users who may book for visitors on \
+        the lot.",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = String, Path, description = "Lot ID"),
+        ("from" = Option<String>, Query, description = "First day (YYYY-MM-DD)"),
+        ("to" = Option<String>, Query, description = "Last day (YYYY-MM-DD)"),
+    ),
+    responses(
+        (status = 200, description = "Blocks of the lot", body = Vec<VisitorPassDay>),
+        (status = 400, description = "Invalid date range"),
+        (status = 403, description = "Not allowed on this lot"),
+        (status = 404, description = "Lot not found"),
+    )
+)]
+pub async fn list_visitor_pass_blocks(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<Uuid>,
+    Query(query): Query<BlockRangeQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<VisitorPassDay>>>) {
+    if let Err(response) = check_reception(&auth_user, lot_id) {
+        return response;
+    }
+    let from = match query.from.as_deref() {
+        Some(value) => parse_date(value),
+        None => Some(Utc::now().date_naive()),
+    };
+    let Some(from) = from else {
+        return invalid_date();
+    };
+    let to = match query.to.as_deref() {
+        Some(value) => parse_date(value),
+        None => Some(from + Duration::days(30)),
+    };
+    let Some(to) = to else {
+        return invalid_date();
+    };
+    if to < from || (to - from).num_days() > MAX_BLOCK_RANGE_DAYS {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidDateRange,
+                format!("to must be 0-{MAX_BLOCK_RANGE_DAYS} days after from"),
+            )),
+        );
+    }
+
+    let state_guard = state.read().await;
+    if let Err(response) = load_lot(&state_guard.db, &lot_id.to_string()).await {
+        return response;
+    }
+    let db = &state_guard.db;
+    let (blocks, passes) = match (
+        db.list_visitor_pass_blocks(lot_id, from, to).await,
+        db.list_visitor_passes(lot_id, from, to).await,
+    ) {
+        (Ok(blocks), Ok(passes)) => (blocks, passes),
+        (Err(e), _) | (_, Err(e)) => {
+            tracing::error!("Failed to list visitor pass blocks: {e}");
+            return server_error("Internal server error");
+        }
+    };
+    let days = blocks
+        .iter()
+        .map(|block| VisitorPassDay::new(block, &passes))
+        .collect();
+    (StatusCode::OK, Json(ApiResponse::success(days)))
+}
+
+/// `PUT /api/v1/lots/{id}/visitor-pass-blocks/{date}` — set a day's block.
+#[utoipa::path(put, path = "/api/v1/lots/{id}/visitor-pass-blocks/{date}", tag = "Lots",
+    summary = "Set a visitor pass block",
+    description = "Sets how many visitor passes reception may issue for the lot on `date`. \
+        The block cannot be set below the passes already issued for the day; 0 closes a \
+        day without issued passes. Admin or lot manager.",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = String, Path, description = "Lot ID"),
+        ("date" = String, Path, description = "Day (YYYY-MM-DD)"),
+    ),
+    request_body = VisitorPassBlockRequest,
+    responses(
+        (status = 200, description = "Block set", body = VisitorPassDay),
+        (status = 400, description = "Invalid date or number of passes"),
+        (status = 403, description = "Admin or lot manager access required"),
+        (status = 404, description = "Lot not found"),
+        (status = 409, description = "More passes already issued for the day"),
+    )
+)]
+pub async fn set_visitor_pass_block(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((lot_id, date)): Path<(Uuid, String)>,
+    ValidatedJson(req): ValidatedJson<VisitorPassBlockRequest>,
+) -> (StatusCode, Json<ApiResponse<VisitorPassDay>>) {
+    if let Err((status, msg)) = check_lot_admin(&auth_user, &lot_id.to_string()) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
+    }
+    let Some(date) = parse_date(&date) else {
+        return invalid_date();
+    };
+    if date < Utc::now().date_naive() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidDate,
+                "Blocks cannot be set for past days",
+            )),
+        );
+    }
+
+    let state_guard = state.read().await;
+    if let Err(response) = load_lot(&state_guard.db, &lot_id.to_string()).await {
+        return response;
+    }
+    let block = VisitorPassBlock {
+        lot_id,
+        date,
+        passes: req.passes,
+        updated_by: auth_user.user_id,
+        updated_at: Utc::now(),
+    };
+    match state_guard.db.save_visitor_pass_block(&block).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::Conflict,
+                    "More passes are already issued for this day",
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Failed to save visitor pass block: {e}");
+            return server_error("Failed to save block");
+        }
+    }
+    let passes = state_guard
+        .db
+        .list_visitor_passes(lot_id, date, date)
+        .await
+        .unwrap_or_default();
+
+    let username = username_of(&state_guard.db, auth_user.user_id).await;
+    AuditEntry::new(AuditEventType::LotUpdated)
+        .user(auth_user.user_id, &username)
+        .resource("lot", &lot_id.to_string())
+        .detail(&format!(
+            "Visitor pass block for {date} set to {}",
+            req.passes
+        ))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (
+        StatusCode::OK,
+        Json(ApiResponse::success(VisitorPassDay::new(&block, &passes))),
+    )
+}
+
+/// `GET /api/v1/lots/{id}/visitor-passes` — the passes of one day.
+#[utoipa::path(get, path = "/api/v1/lots/{id}/visitor-passes", tag = "Lots",
+    summary = "List visitor passes",
+    description = "The visitor passes issued for the lot on `date` (default today), in \
+        the order they were issued, cancelled ones included. Admins, lot managers and \
+        users who may book for visitors on the lot.",
+    security(("bearer_auth" = [])),
+    params(
+        ("id" = String, Path, description = "Lot ID"),
+        ("date" = Option<String>, Query, description = "Day (YYYY-MM-DD)"),
+    ),
+    responses(
+        (status = 200, description = "Passes of the day", body = Vec<VisitorPass>),
+        (status = 400, description = "Invalid date"),
+        (status = 403, description = "Not allowed on this lot"),
+    )
+)]
+pub async fn list_visitor_passes(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<Uuid>,
+    Query(query): Query<PassDayQuery>,
+) -> (StatusCode, Json<ApiResponse<Vec<VisitorPass>>>) {
+    if let Err(response) = check_reception(&auth_user, lot_id) {
+        return response;
+    }
+    let date = match query.date.as_deref() {
+        Some(value) => parse_date(value),
+        None => Some(Utc::now().date_naive()),
+    };
+    let Some(date) = date else {
+        return invalid_date();
+    };
+
+    let state_guard = state.read().await;
+    match state_guard.db.list_visitor_passes(lot_id, date, date).await {
+        Ok(passes) => (StatusCode::OK, Json(ApiResponse::success(passes))),
+        Err(e) => {
+            tracing::error!("Failed to list visitor passes: {e}");
+            server_error("Internal server error")
+        }
+    }
+}
+
+/// `POST /api/v1/lots/{id}/visitor-passes` — issue a pass from the block.
+#[utoipa::path(post, path = "/api/v1/lots/{id}/visitor-passes", tag = "Lots",
+    summary = "Issue a visitor pass",
+    description = "Issues a day pass for `date` to a visitor by name and license plate. \
+        The pass takes a place in the day's block but no slot; a slot is assigned at \
+        check-in. Admins, lot managers and users who may book for visitors on the lot.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Lot ID")),
+    request_body = IssueVisitorPassRequest,
+    responses(
+        (status = 201, description = "Pass issued", body = VisitorPass),
+        (status = 400, description = "Invalid request or past date"),
+        (status = 403, description = "Not allowed on this lot"),
+        (status = 404, description = "Lot not found"),
+        (status = 409, description = "No passes left for the day, or lot archived"),
+    )
+)]
+pub async fn issue_visitor_pass(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<Uuid>,
+    ValidatedJson(req): ValidatedJson<IssueVisitorPassRequest>,
+) -> (StatusCode, Json<ApiResponse<VisitorPass>>) {
+    if let Err(response) = check_reception(&auth_user, lot_id) {
+        return response;
+    }
+    if req.date < Utc::now().date_naive() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidDate,
+                "Passes cannot be issued for past days",
+            )),
+        );
+    }
+
+    let state_guard = state.read().await;
+    if let Err(response) = load_lot(&state_guard.db, &lot_id.to_string()).await {
+        return response;
+    }
+    if super::lot_archive::is_archived(&state_guard.db, lot_id).await {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::LotArchived,
+                "This parking lot is archived and no longer accepts visitors",
+            )),
+        );
+    }
+
+    let pass = VisitorPass {
+        id: Uuid::new_v4(),
+        lot_id,
+        date: req.date,
+        visitor_name: req.visitor_name.trim().to_string(),
+        license_plate: req.license_plate.trim().to_string(),
+        issued_by: auth_user.user_id,
+        issued_at: Utc::now(),
+        status: VisitorPassStatus::Issued,
+        booking_id: None,
+        slot_id: None,
+        slot_number: None,
+        checked_in_at: None,
+    };
+    match state_guard.db.issue_visitor_pass(&pass).await {
+        Ok(true) => {}
+        Ok(false) => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::VisitorPassesExhausted,
+                    format!("No visitor passes left for {}", req.date),
+                )),
+            );
+        }
+        Err(e) => {
+            tracing::error!("Failed to issue visitor pass: {e}");
+            return server_error("Failed to issue pass");
+        }
+    }
+
+    let username = username_of(&state_guard.db, auth_user.user_id).await;
+    AuditEntry::new(AuditEventType::BookingCreated)
+        .user(auth_user.user_id, &username)
+        .resource("visitor_pass", &pass.id.to_string())
+        .detail(&format!(
+            "Visitor pass for lot {lot_id} on {} issued",
+            pass.date
+        ))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::CREATED, Json(ApiResponse::success(pass)))
+}
+
+/// `POST /api/v1/visitor-passes/{id}/check-in` — assign the visitor a slot.
+#[utoipa::path(post, path = "/api/v1/visitor-passes/{id}/check-in", tag = "Lots",
+    summary = "Check in a visitor pass",
+    description = "Checks the visitor in on the pass's day: assigns the first free slot of \
+        the lot and creates an active booking for the visitor, owned by the issuer, that \
+        runs until the end of the day. Admins, lot managers and users who may book for \
+        visitors on the lot.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Visitor pass ID")),
+    responses(
+        (status = 200, description = "Visitor checked in", body = VisitorPass),
+        (status = 403, description = "Not allowed on this lot"),
+        (status = 404, description = "Pass not found"),
+        (status = 409, description = "Not the pass's day, pass not open, or no free slot"),
+    )
+)]
+pub async fn check_in_visitor_pass(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<VisitorPass>>) {
+    // Write lock: no slot holds or bookings change while a slot is picked
+    let state_guard = state.write().await;
+    let pass = match load_pass(&state_guard.db, &auth_user, &id).await {
+        Ok(pass) => pass,
+        Err(response) => return response,
+    };
+    match pass.status {
+        VisitorPassStatus::Issued => {}
+        VisitorPassStatus::CheckedIn => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::AlreadyCheckedIn,
+                    "Visitor is already checked in",
+                )),
+            );
+        }
+        VisitorPassStatus::Cancelled => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::AlreadyCancelled,
+                    "Visitor pass was cancelled",
+                )),
+            );
+        }
+    }
+    let now = Utc::now();
+    if pass.date != now.date_naive() {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidDate,
+                format!("This pass is valid on {}", pass.date),
+            )),
+        );
+    }
+    let lot = match load_lot(&state_guard.db, &pass.lot_id.to_string()).await {
+        Ok(lot) => lot,
+        Err(response) => return response,
+    };
+    if super::lot_archive::is_archived(&state_guard.db, pass.lot_id).await {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::LotArchived,
+                "This parking lot is archived and no longer accepts visitors",
+            )),
+        );
+    }
+
+    // The booking runs from now until the end of the pass's day
+    let end_time = pass
+        .date
+        .succ_opt()
+        .and_then(|day| day.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc());
+    let Some(range) = end_time.and_then(|end| TimeRange::new(now, end)) else {
+        return server_error("Failed to compute booking time");
+    };
+    let candidates = match free_slots(&state_guard.db, &pass.lot_id.to_string(), &range).await {
+        Ok(slots) => slots,
+        Err(e) => {
+            tracing::error!("Failed to list free slots: {e}");
+            return server_error("Internal server error");
+        }
+    };
+    let on_hold = match super::slot_holds::slots_held_by_others(
+        &state_guard.db,
+        pass.lot_id,
+        auth_user.user_id,
+        now,
+    )
+    .await
+    {
+        Ok(held) => held,
+        Err(e) => {
+            tracing::error!("Failed to read slot holds: {e}");
+            return server_error("Internal server error");
+        }
+    };
+    let tenant_id = super::resolve_tenant_id(&state_guard, pass.issued_by).await;
+
+    let mut checked_in = None;
+    for slot in candidates
+        .into_iter()
+        .filter(|s| s.status == SlotStatus::Available && !on_hold.contains(&s.id))
+    {
+        let floor_name = lot
+            .floors
+            .iter()
+            .find(|f| f.id == slot.floor_id)
+            .map_or_else(|| "Level 1".to_string(), |f| f.name.clone());
+        let booking = Booking {
+            id: Uuid::new_v4(),
+            user_id: pass.issued_by,
+            lot_id: pass.lot_id,
+            slot_id: slot.id,
+            slot_number: slot.slot_number,
+            floor_name,
+            vehicle: Vehicle {
+                // The visitor's car is not one of the issuer's vehicles
+                id: Uuid::nil(),
+                user_id: pass.issued_by,
+                license_plate: pass.license_plate.clone(),
+                make: None,
+                model: None,
+                color: None,
+                vehicle_type: VehicleType::Car,
+                fuel_type: FuelType::Unknown,
+                is_default: false,
+                created_at: now,
+            },
+            start_time: range.start,
+            end_time: range.end,
+            status: BookingStatus::Active,
+            pricing: BookingPricing {
+                base_price: 0.0,
+                discount: 0.0,
+                tax: 0.0,
+                total: 0.0,
+                currency: lot.pricing.currency.clone(),
+                payment_status: PaymentStatus::Paid,
+                payment_method: None,
+                cancellation_fee: None,
+            },
+            created_at: now,
+            updated_at: now,
+            check_in_time: Some(now),
+            check_out_time: None,
+            qr_code: Some(Uuid::new_v4().to_string()),
+            notes: Some(format!("Visitor pass {}", pass.id)),
+            tenant_id: tenant_id.clone(),
+            tags: BTreeMap::new(),
+            booked_for: Some(BookedFor {
+                name: pass.visitor_name.clone(),
+                license_plate: pass.license_plate.clone(),
+                email: None,
+            }),
+        };
+        let mut reserved = slot.clone();
+        reserved.status = SlotStatus::Reserved;
+        let mut updated = pass.clone();
+        updated.status = VisitorPassStatus::CheckedIn;
+        updated.booking_id = Some(booking.id);
+        updated.slot_id = Some(slot.id);
+        updated.slot_number = Some(slot.slot_number);
+        updated.checked_in_at = Some(now);
+
+        let mut batch = WriteBatch::new();
+        batch
+            .claim_slot(&booking)
+            .save_parking_slot(
+                &reserved,
+                &SlotChangeContext::user(auth_user.user_id, "booking")
+                    .reason(format!("Visitor pass {}", pass.id)),
+            )
+            .save_visitor_pass(&updated);
+        match state_guard.db.apply_batch(&batch).await {
+            Ok(true) => {
+                checked_in = Some(updated);
+                break;
+            }
+            // Taken since the slots were listed; try the next one
+            Ok(false) => {}
+            Err(e) => {
+                tracing::error!("Failed to check in visitor pass: {e}");
+                return server_error("Failed to check in visitor");
+            }
+        }
+    }
+    let Some(pass) = checked_in else {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::NoSlotsAvailable,
+                "No free slot in this lot for the rest of the day",
+            )),
+        );
+    };
+
+    let username = username_of(&state_guard.db, auth_user.user_id).await;
+    AuditEntry::new(AuditEventType::CheckIn)
+        .user(auth_user.user_id, &username)
+        .resource("visitor_pass", &pass.id.to_string())
+        .detail(&format!(
+            "Visitor checked in on slot {}",
+            pass.slot_number.unwrap_or_default()
+        ))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(pass)))
+}
+
+/// `DELETE /api/v1/visitor-passes/{id}` — cancel a pass before check-in.
+#[utoipa::path(delete, path = "/api/v1/visitor-passes/{id}", tag = "Lots",
+    summary = "Cancel a visitor pass",
+    description = "Cancels a pass that is not checked in yet, giving its place in the \
+        day's block back. Checked-in visitors are handled through their booking. Admins, \
+        lot managers and users who may book for visitors on the lot.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Visitor pass ID")),
+    responses(
+        (status = 200, description = "Pass cancelled", body = VisitorPass),
+        (status = 403, description = "Not allowed on this lot"),
+        (status = 404, description = "Pass not found"),
+        (status = 409, description = "Pass already checked in or cancelled"),
+    )
+)]
+pub async fn cancel_visitor_pass(
+    State(state): State<SharedState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<String>,
+) -> (StatusCode, Json<ApiResponse<VisitorPass>>) {
+    let state_guard = state.write().await;
+    let mut pass = match load_pass(&state_guard.db, &auth_user, &id).await {
+        Ok(pass) => pass,
+        Err(response) => return response,
+    };
+    match pass.status {
+        VisitorPassStatus::Issued => {}
+        VisitorPassStatus::CheckedIn => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::AlreadyCheckedIn,
+                    "Visitor is already checked in; cancel their booking instead",
+                )),
+            );
+        }
+        VisitorPassStatus::Cancelled => {
+            return (
+                StatusCode::CONFLICT,
+                Json(ApiResponse::error(
+                    ErrorCode::AlreadyCancelled,
+                    "Visitor pass is already cancelled",
+                )),
+            );
+        }
+    }
+
+    pass.status = VisitorPassStatus::Cancelled;
+    if let Err(e) = state_guard.db.save_visitor_pass(&pass).await {
+        tracing::error!("Failed to cancel visitor pass: {e}");
+        return server_error("Failed to cancel pass");
+    }
+
+    let username = username_of(&state_guard.db, auth_user.user_id).await;
+    AuditEntry::new(AuditEventType::BookingCancelled)
+        .user(auth_user.user_id, &username)
+        .resource("visitor_pass", &pass.id.to_string())
+        .detail(&format!(
+            "Visitor pass for lot {} on {} cancelled",
+            pass.lot_id, pass.date
+        ))
+        .log()
+        .persist(&state_guard.db)
+        .await;
+
+    (StatusCode::OK, Json(ApiResponse::success(pass)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pass(date: NaiveDate, status: VisitorPassStatus) -> VisitorPass {
+        VisitorPass {
+            id: Uuid::new_v4(),
+            lot_id: Uuid::nil(),
+            date,
+            visitor_name: "Ada".to_string(),
+            license_plate: "B-AB 123".to_string(),
+            issued_by: Uuid::nil(),
+            issued_at: Utc::now(),
+            status,
+            booking_id: None,
+            slot_id: None,
+            slot_number: None,
+            checked_in_at: None,
+        }
+    }
+
+    #[test]
+    fn test_day_counts_passes_of_its_date_only() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        let block = VisitorPassBlock {
+            lot_id: Uuid::nil(),
+            date,
+            passes: 5,
+            updated_by: Uuid::nil(),
+            updated_at: Utc::now(),
+        };
+        let passes = [
+            pass(date, VisitorPassStatus::Issued),
+            pass(date, VisitorPassStatus::CheckedIn),
+            pass(date, VisitorPassStatus::Cancelled),
+            pass(date.succ_opt().unwrap(), VisitorPassStatus::Issued),
+        ];
+
+        let day = VisitorPassDay::new(&block, &passes);
+        assert_eq!(day.issued, 2);
+        assert_eq!(day.checked_in, 1);
+        assert_eq!(day.remaining, 3);
+    }
+}
@@ -16,8 +16,8 @@ use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
 use parkhub_common::models::{Visitor, VisitorStatus};
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use super::settings::read_admin_setting;
 use super::{AuthUser, SharedState, check_admin};
@@ -92,7 +92,7 @@ pub async fn register_visitor(
         return (
             StatusCode::UNPROCESSABLE_ENTITY,
             Json(ApiResponse::error(
-                "VISITORS_DISABLED",
+                ErrorCode::VisitorsDisabled,
                 "Visitor pre-registration is not enabled",
             )),
         );
@@ -103,7 +103,7 @@ pub async fn register_visitor(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "Name and email are required",
             )),
         );
@@ -134,7 +134,7 @@ pub async fn register_visitor(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to register visitor",
             )),
         );
@@ -176,7 +176,7 @@ pub async fn list_my_visitors(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list visitors",
                 )),
             )
@@ -199,8 +199,8 @@ pub async fn admin_list_visitors(
     Query(query): Query<VisitorQuery>,
 ) -> (StatusCode, Json<ApiResponse<Vec<Visitor>>>) {
     let state_guard = state.read().await;
-    if let Err((status, msg)) = check_admin(&state_guard, &auth_user).await {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+    if let Err((status, msg)) = check_admin(&auth_user) {
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     match state_guard.db.list_all_visitors().await {
@@ -226,7 +226,7 @@ pub async fn admin_list_visitors(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list visitors",
                 )),
             )
@@ -255,30 +255,33 @@ pub async fn check_in_visitor(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Visitor not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Visitor not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
     // Only the host or an admin can check in a visitor
     if visitor.host_user_id != auth_user.user_id
-        && let Err((status, msg)) = check_admin(&state_guard, &auth_user).await
+        && let Err((status, msg)) = check_admin(&auth_user)
     {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     if visitor.status == VisitorStatus::CheckedIn {
         return (
             StatusCode::CONFLICT,
             Json(ApiResponse::error(
-                "ALREADY_CHECKED_IN",
+                ErrorCode::AlreadyCheckedIn,
                 "Visitor is already checked in",
             )),
         );
@@ -293,7 +296,7 @@ pub async fn check_in_visitor(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to check in visitor",
             )),
         );
@@ -323,23 +326,26 @@ pub async fn cancel_visitor(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Visitor not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Visitor not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
 
     // Only the host or an admin can cancel
     if visitor.host_user_id != auth_user.user_id
-        && let Err((status, msg)) = check_admin(&state_guard, &auth_user).await
+        && let Err((status, msg)) = check_admin(&auth_user)
     {
-        return (status, Json(ApiResponse::error("FORBIDDEN", msg)));
+        return (status, Json(ApiResponse::error(ErrorCode::Forbidden, msg)));
     }
 
     visitor.status = VisitorStatus::Cancelled;
@@ -350,7 +356,7 @@ pub async fn cancel_visitor(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to cancel visitor",
             )),
         );
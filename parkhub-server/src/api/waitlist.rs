@@ -13,34 +13,42 @@ use chrono::Utc;
 use serde::Deserialize;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
-use parkhub_common::models::{WaitlistEntry, WaitlistStatus};
+use parkhub_common::models::{WaitlistEntry, WaitlistPosition, WaitlistStatus};
+use parkhub_common::{ApiResponse, ErrorCode};
 
+use super::feature_flags::{RequireFlag, WaitlistFlag};
 use super::settings::read_admin_setting;
+use super::waitlist_position::positions_of;
 use super::{AuthUser, SharedState};
 
-/// `GET /api/v1/waitlist` — list current user's waitlist entries
+/// `GET /api/v1/waitlist` — list current user's waitlist entries with their
+/// place in line
 #[utoipa::path(get, path = "/api/v1/waitlist", tag = "Waitlist",
     summary = "List waitlist entries",
-    description = "Returns waitlist entries for the authenticated user.",
+    description = "Returns waitlist entries for the authenticated user, each with its place in \
+        line and when a slot is expected to free up, estimated from the lot's booking turnover \
+        over the last two weeks.",
     security(("bearer_auth" = [])),
-    responses((status = 200, description = "Success"))
+    responses((status = 200, description = "Success", body = Vec<WaitlistPosition>))
 )]
 pub async fn list_waitlist(
     State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
     Extension(auth_user): Extension<AuthUser>,
-) -> Json<ApiResponse<Vec<WaitlistEntry>>> {
+) -> Json<ApiResponse<Vec<WaitlistPosition>>> {
     let state_guard = state.read().await;
     match state_guard
         .db
         .list_waitlist_by_user(&auth_user.user_id.to_string())
         .await
     {
-        Ok(entries) => Json(ApiResponse::success(entries)),
+        Ok(entries) => Json(ApiResponse::success(
+            positions_of(&state_guard.db, &entries).await,
+        )),
         Err(e) => {
             tracing::error!("Failed to list waitlist entries: {}", e);
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to list waitlist entries",
             ))
         }
@@ -62,6 +70,7 @@ pub struct JoinWaitlistRequest {
 )]
 pub async fn join_waitlist(
     State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
     Extension(auth_user): Extension<AuthUser>,
     Json(req): Json<JoinWaitlistRequest>,
 ) -> (StatusCode, Json<ApiResponse<WaitlistEntry>>) {
@@ -73,7 +82,7 @@ pub async fn join_waitlist(
         return (
             StatusCode::UNPROCESSABLE_ENTITY,
             Json(ApiResponse::error(
-                "WAITLIST_DISABLED",
+                ErrorCode::WaitlistDisabled,
                 "Waitlist is not enabled",
             )),
         );
@@ -98,6 +107,9 @@ pub async fn join_waitlist(
         status: WaitlistStatus::Waiting,
         offer_expires_at: None,
         accepted_booking_id: None,
+        start_time: None,
+        end_time: None,
+        offered_slot_id: None,
     };
 
     if let Err(e) = state_guard.db.save_waitlist_entry(&entry).await {
@@ -105,7 +117,7 @@ pub async fn join_waitlist(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to join waitlist",
             )),
         );
@@ -123,6 +135,7 @@ pub async fn join_waitlist(
 )]
 pub async fn leave_waitlist(
     State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
     Extension(auth_user): Extension<AuthUser>,
     Path(id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
@@ -134,21 +147,27 @@ pub async fn leave_waitlist(
             if entry.user_id != auth_user.user_id {
                 return (
                     StatusCode::FORBIDDEN,
-                    Json(ApiResponse::error("FORBIDDEN", "Access denied")),
+                    Json(ApiResponse::error(ErrorCode::Forbidden, "Access denied")),
                 );
             }
         }
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Waitlist entry not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Waitlist entry not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     }
@@ -157,14 +176,17 @@ pub async fn leave_waitlist(
         Ok(true) => (StatusCode::OK, Json(ApiResponse::success(()))),
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Waitlist entry not found")),
+            Json(ApiResponse::error(
+                ErrorCode::NotFound,
+                "Waitlist entry not found",
+            )),
         ),
         Err(e) => {
             tracing::error!("Failed to delete waitlist entry: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to leave waitlist",
                 )),
             )
@@ -1,9 +1,13 @@
 //! Enhanced Waitlist with Notifications.
 //!
 //! Priority-based waitlist with auto-notification when slots become available.
+//! Entries queued for a time window are matched against freed slots by the
+//! waitlist job: the first entry in line gets the slot held for its window.
 //!
+//! - `POST /api/v1/lots/:id/waitlist`           — queue for a time window
 //! - `POST /api/v1/lots/:id/waitlist/subscribe` — join with priority
 //! - `GET  /api/v1/lots/:id/waitlist`           — view position + estimated wait
+//!   (see `waitlist_position.rs`)
 //! - `DELETE /api/v1/lots/:id/waitlist`          — leave waitlist
 //! - `POST /api/v1/lots/:id/waitlist/:entry_id/accept`  — accept offered slot
 //! - `POST /api/v1/lots/:id/waitlist/:entry_id/decline` — decline, move to next
@@ -17,14 +21,19 @@ use axum::{
     extract::{Path, State},
     http::StatusCode,
 };
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
-use parkhub_common::models::{Notification, NotificationType, WaitlistEntry, WaitlistStatus};
+use parkhub_common::models::{
+    Notification, NotificationType, WaitlistEntry, WaitlistPosition, WaitlistStatus,
+};
+use parkhub_common::{ApiResponse, ErrorCode, MAX_BOOKING_MINUTES, TimeRange};
 
-use super::{AuthUser, SharedState};
+use super::feature_flags::{RequireFlag, WaitlistFlag};
+use super::settings::read_admin_setting;
+use super::waitlist_position::{lot_turnover, position_in};
+use super::{AuthUser, SharedState, availability, noshow};
 
 /// Offer expiry duration in minutes
 const OFFER_EXPIRY_MINUTES: i64 = 15;
@@ -48,6 +57,15 @@ fn default_priority() -> u8 {
     3
 }
 
+/// Request body for joining a lot's waitlist for a time window
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct JoinLotWaitlistRequest {
+    /// Start of the wanted booking.
+    pub start_time: DateTime<Utc>,
+    /// End of the wanted booking.
+    pub end_time: DateTime<Utc>,
+}
+
 /// Response for waitlist position
 #[derive(Debug, Serialize, utoipa::ToSchema)]
 pub struct WaitlistPositionResponse {
@@ -55,6 +73,19 @@ pub struct WaitlistPositionResponse {
     pub position: usize,
     pub total_ahead: usize,
     pub estimated_wait_minutes: Option<i64>,
+    pub estimated_available_at: Option<DateTime<Utc>>,
+}
+
+impl From<WaitlistPosition> for WaitlistPositionResponse {
+    fn from(position: WaitlistPosition) -> Self {
+        Self {
+            entry: position.entry,
+            position: position.position,
+            total_ahead: position.total_ahead,
+            estimated_wait_minutes: position.estimated_wait_minutes,
+            estimated_available_at: position.estimated_available_at,
+        }
+    }
 }
 
 /// Response for waitlist overview (GET)
@@ -81,6 +112,7 @@ pub struct WaitlistOverviewResponse {
 )]
 pub async fn subscribe_waitlist(
     State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
     Extension(auth_user): Extension<AuthUser>,
     Path(lot_id): Path<Uuid>,
     Json(req): Json<SubscribeWaitlistRequest>,
@@ -98,7 +130,7 @@ pub async fn subscribe_waitlist(
     {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Lot not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Lot not found")),
         );
     }
 
@@ -109,20 +141,12 @@ pub async fn subscribe_waitlist(
         .await
         .unwrap_or_default();
 
+    let turnover = lot_turnover(&state_guard.db, lot_id).await;
     if let Some(entry) = existing.iter().find(|e| e.user_id == auth_user.user_id) {
-        let position = existing
-            .iter()
-            .filter(|e| e.status == WaitlistStatus::Waiting)
-            .position(|e| e.id == entry.id)
-            .unwrap_or(0);
+        let position = position_in(&existing, entry, turnover, Utc::now());
         return (
             StatusCode::CONFLICT,
-            Json(ApiResponse::success(WaitlistPositionResponse {
-                entry: entry.clone(),
-                position: position + 1,
-                total_ahead: position,
-                estimated_wait_minutes: estimate_wait(position),
-            })),
+            Json(ApiResponse::success(position.into())),
         );
     }
 
@@ -136,6 +160,9 @@ pub async fn subscribe_waitlist(
         status: WaitlistStatus::Waiting,
         offer_expires_at: None,
         accepted_booking_id: None,
+        start_time: None,
+        end_time: None,
+        offered_slot_id: None,
     };
 
     if let Err(e) = state_guard.db.save_waitlist_entry(&entry).await {
@@ -143,41 +170,193 @@ pub async fn subscribe_waitlist(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to join waitlist",
             )),
         );
     }
 
-    // Calculate position (new entry is at the end)
-    let position = existing
-        .iter()
-        .filter(|e| e.status == WaitlistStatus::Waiting)
-        .count();
+    // The new entry is at the end of the line
+    let mut line = existing;
+    line.push(entry.clone());
+    let position = position_in(&line, &entry, turnover, Utc::now());
 
     tracing::info!(
         "User {} joined waitlist for lot {} with priority {} at position {}",
         auth_user.user_id,
         lot_id,
         priority,
-        position + 1
+        position.position
     );
 
     (
         StatusCode::CREATED,
-        Json(ApiResponse::success(WaitlistPositionResponse {
-            entry,
-            position: position + 1,
-            total_ahead: position,
-            estimated_wait_minutes: estimate_wait(position),
-        })),
+        Json(ApiResponse::success(position.into())),
+    )
+}
+
+/// `POST /api/v1/lots/:id/waitlist` — wait for a slot in a fully booked lot
+#[utoipa::path(post, path = "/api/v1/lots/{id}/waitlist", tag = "Waitlist",
+    summary = "Join waitlist for a time window",
+    description = "Queue for a lot that has no free slot for the window. When a cancellation \
+        frees a slot for it, the slot is held for the first entry in line and offered by \
+        notification and email; the offer must be claimed within the lot's claim window.",
+    security(("bearer_auth" = [])),
+    params(("id" = String, Path, description = "Lot ID")),
+    request_body = JoinLotWaitlistRequest,
+    responses(
+        (status = 201, description = "Queued", body = WaitlistPositionResponse),
+        (status = 400, description = "Invalid time window"),
+        (status = 404, description = "Lot not found"),
+        (status = 409, description = "A slot is free for the window, or already queued for it"),
+        (status = 422, description = "Waitlist disabled"),
+    )
+)]
+pub async fn join_lot_waitlist(
+    State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(lot_id): Path<Uuid>,
+    Json(req): Json<JoinLotWaitlistRequest>,
+) -> (StatusCode, Json<ApiResponse<WaitlistPositionResponse>>) {
+    let state_guard = state.read().await;
+
+    if read_admin_setting(&state_guard.db, "waitlist_enabled").await != "true" {
+        return (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            Json(ApiResponse::error(
+                ErrorCode::WaitlistDisabled,
+                "Waitlist is not enabled",
+            )),
+        );
+    }
+
+    let now = Utc::now();
+    let Some(range) = TimeRange::new(req.start_time, req.end_time).filter(|range| {
+        range.end > now && range.duration().num_minutes() <= i64::from(MAX_BOOKING_MINUTES)
+    }) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse::error(
+                ErrorCode::InvalidInput,
+                format!(
+                    "end_time must be in the future, after start_time and at most {} days later",
+                    MAX_BOOKING_MINUTES / (24 * 60)
+                ),
+            )),
+        );
+    };
+
+    if state_guard
+        .db
+        .get_parking_lot(&lot_id.to_string())
+        .await
+        .ok()
+        .flatten()
+        .is_none()
+    {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(ApiResponse::error(ErrorCode::NotFound, "Lot not found")),
+        );
+    }
+
+    // Only a lot with nothing left to book for the window has a queue.
+    let free = match availability::free_slots(&state_guard.db, &lot_id.to_string(), &range).await {
+        Ok(free) => free,
+        Err(e) => {
+            tracing::error!("Failed to compute free slots: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
+            );
+        }
+    };
+    let held = noshow::slots_held_for_others(&state_guard, lot_id, &range, auth_user.user_id).await;
+    if free.iter().any(|slot| !held.contains(&slot.id)) {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::SlotsAvailable,
+                "A slot is free for this window; book it directly",
+            )),
+        );
+    }
+
+    let existing = state_guard
+        .db
+        .list_waitlist_by_lot(&lot_id.to_string())
+        .await
+        .unwrap_or_default();
+    let already_queued = existing.iter().any(|e| {
+        e.user_id == auth_user.user_id
+            && matches!(e.status, WaitlistStatus::Waiting | WaitlistStatus::Offered)
+            && noshow::entry_window(e).is_none_or(|window| window.overlaps(&range))
+    });
+    if already_queued {
+        return (
+            StatusCode::CONFLICT,
+            Json(ApiResponse::error(
+                ErrorCode::AlreadyWaitlisted,
+                "You are already on the waitlist for this window",
+            )),
+        );
+    }
+
+    let entry = WaitlistEntry {
+        id: Uuid::new_v4(),
+        user_id: auth_user.user_id,
+        lot_id,
+        created_at: now,
+        notified_at: None,
+        status: WaitlistStatus::Waiting,
+        offer_expires_at: None,
+        accepted_booking_id: None,
+        start_time: Some(range.start),
+        end_time: Some(range.end),
+        offered_slot_id: None,
+    };
+
+    if let Err(e) = state_guard.db.save_waitlist_entry(&entry).await {
+        tracing::error!("Failed to save waitlist entry: {}", e);
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to join waitlist",
+            )),
+        );
+    }
+
+    // The new entry is at the end of the line
+    let mut line = existing;
+    line.push(entry.clone());
+    let turnover = lot_turnover(&state_guard.db, lot_id).await;
+    let position = position_in(&line, &entry, turnover, now);
+
+    tracing::info!(
+        "User {} joined waitlist for lot {} ({} – {}) at position {}",
+        auth_user.user_id,
+        lot_id,
+        range.start,
+        range.end,
+        position.position
+    );
+
+    (
+        StatusCode::CREATED,
+        Json(ApiResponse::success(position.into())),
     )
 }
 
 /// `GET /api/v1/lots/:id/waitlist` — view waitlist position + estimated wait
 #[utoipa::path(get, path = "/api/v1/lots/{id}/waitlist", tag = "Waitlist",
     summary = "View waitlist",
-    description = "View the current user's waitlist position and estimated wait time for a lot.",
+    description = "View the current user's waitlist position for a lot, with the estimated wait \
+        and time a slot frees up from the lot's booking turnover over the last two weeks.",
     security(("bearer_auth" = [])),
     responses(
         (status = 200, description = "Waitlist overview"),
@@ -185,6 +364,7 @@ pub async fn subscribe_waitlist(
 )]
 pub async fn get_lot_waitlist(
     State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
     Extension(auth_user): Extension<AuthUser>,
     Path(lot_id): Path<Uuid>,
 ) -> Json<ApiResponse<WaitlistOverviewResponse>> {
@@ -201,16 +381,16 @@ pub async fn get_lot_waitlist(
         .filter(|e| e.status == WaitlistStatus::Waiting || e.status == WaitlistStatus::Offered)
         .collect();
 
+    let turnover = if waiting.iter().any(|e| e.user_id == auth_user.user_id) {
+        lot_turnover(&state_guard.db, lot_id).await
+    } else {
+        None
+    };
+    let now = Utc::now();
     let positions: Vec<WaitlistPositionResponse> = waiting
         .iter()
-        .enumerate()
-        .filter(|(_, e)| e.user_id == auth_user.user_id)
-        .map(|(i, e)| WaitlistPositionResponse {
-            entry: (*e).clone(),
-            position: i + 1,
-            total_ahead: i,
-            estimated_wait_minutes: estimate_wait(i),
-        })
+        .filter(|e| e.user_id == auth_user.user_id)
+        .map(|e| position_in(&entries, e, turnover, now).into())
         .collect();
 
     Json(ApiResponse::success(WaitlistOverviewResponse {
@@ -231,6 +411,7 @@ pub async fn get_lot_waitlist(
 )]
 pub async fn leave_lot_waitlist(
     State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
     Extension(auth_user): Extension<AuthUser>,
     Path(lot_id): Path<Uuid>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
@@ -257,7 +438,7 @@ pub async fn leave_lot_waitlist(
                 return (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     Json(ApiResponse::error(
-                        "SERVER_ERROR",
+                        ErrorCode::ServerError,
                         "Failed to leave waitlist",
                     )),
                 );
@@ -272,7 +453,7 @@ pub async fn leave_lot_waitlist(
         None => (
             StatusCode::NOT_FOUND,
             Json(ApiResponse::error(
-                "NOT_FOUND",
+                ErrorCode::NotFound,
                 "Not on waitlist for this lot",
             )),
         ),
@@ -293,6 +474,7 @@ pub async fn leave_lot_waitlist(
 )]
 pub async fn accept_waitlist_offer(
     State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
     Extension(auth_user): Extension<AuthUser>,
     Path((_lot_id, entry_id)): Path<(Uuid, Uuid)>,
 ) -> (StatusCode, Json<ApiResponse<WaitlistEntry>>) {
@@ -307,7 +489,10 @@ pub async fn accept_waitlist_offer(
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Waitlist entry not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Waitlist entry not found",
+                )),
             );
         }
     };
@@ -316,7 +501,10 @@ pub async fn accept_waitlist_offer(
     if entry.user_id != auth_user.user_id {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Not your waitlist entry")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Not your waitlist entry",
+            )),
         );
     }
 
@@ -325,7 +513,7 @@ pub async fn accept_waitlist_offer(
         return (
             StatusCode::UNPROCESSABLE_ENTITY,
             Json(ApiResponse::error(
-                "NOT_OFFERED",
+                ErrorCode::NotOffered,
                 "This entry has not been offered a slot",
             )),
         );
@@ -341,7 +529,10 @@ pub async fn accept_waitlist_offer(
         let _ = state_guard.db.save_waitlist_entry(&expired_entry).await;
         return (
             StatusCode::GONE,
-            Json(ApiResponse::error("OFFER_EXPIRED", "The offer has expired")),
+            Json(ApiResponse::error(
+                ErrorCode::OfferExpired,
+                "The offer has expired",
+            )),
         );
     }
 
@@ -354,7 +545,10 @@ pub async fn accept_waitlist_offer(
         tracing::error!("Failed to accept waitlist offer: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to accept offer")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to accept offer",
+            )),
         );
     }
 
@@ -380,6 +574,7 @@ pub async fn accept_waitlist_offer(
 )]
 pub async fn decline_waitlist_offer(
     State(state): State<SharedState>,
+    _: RequireFlag<WaitlistFlag>,
     Extension(auth_user): Extension<AuthUser>,
     Path((lot_id, entry_id)): Path<(Uuid, Uuid)>,
 ) -> (StatusCode, Json<ApiResponse<()>>) {
@@ -394,7 +589,10 @@ pub async fn decline_waitlist_offer(
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Waitlist entry not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Waitlist entry not found",
+                )),
             );
         }
     };
@@ -402,7 +600,10 @@ pub async fn decline_waitlist_offer(
     if entry.user_id != auth_user.user_id {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Not your waitlist entry")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Not your waitlist entry",
+            )),
         );
     }
 
@@ -410,7 +611,7 @@ pub async fn decline_waitlist_offer(
         return (
             StatusCode::UNPROCESSABLE_ENTITY,
             Json(ApiResponse::error(
-                "NOT_OFFERED",
+                ErrorCode::NotOffered,
                 "This entry has not been offered a slot",
             )),
         );
@@ -424,7 +625,7 @@ pub async fn decline_waitlist_offer(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to decline offer",
             )),
         );
@@ -446,14 +647,6 @@ pub async fn decline_waitlist_offer(
 // HELPERS
 // ═══════════════════════════════════════════════════════════════════════════════
 
-/// Estimate wait time based on queue position (rough: 30 min per position)
-fn estimate_wait(positions_ahead: usize) -> Option<i64> {
-    if positions_ahead == 0 {
-        return Some(0);
-    }
-    Some(positions_ahead as i64 * 30)
-}
-
 /// Notify the next waiting user in line for a lot
 async fn notify_next_in_line(state: &crate::AppState, lot_id: &Uuid) {
     let entries = state
@@ -524,18 +717,6 @@ mod tests {
         assert!(req.notes.is_none());
     }
 
-    #[test]
-    fn test_estimate_wait_zero() {
-        assert_eq!(estimate_wait(0), Some(0));
-    }
-
-    #[test]
-    fn test_estimate_wait_positions() {
-        assert_eq!(estimate_wait(1), Some(30));
-        assert_eq!(estimate_wait(3), Some(90));
-        assert_eq!(estimate_wait(10), Some(300));
-    }
-
     #[test]
     fn test_waitlist_position_response_serialize() {
         let entry = WaitlistEntry {
@@ -547,12 +728,16 @@ mod tests {
             status: WaitlistStatus::Waiting,
             offer_expires_at: None,
             accepted_booking_id: None,
+            start_time: None,
+            end_time: None,
+            offered_slot_id: None,
         };
         let resp = WaitlistPositionResponse {
             entry,
             position: 3,
             total_ahead: 2,
             estimated_wait_minutes: Some(60),
+            estimated_available_at: None,
         };
         let json = serde_json::to_string(&resp).unwrap();
         assert!(json.contains("\"position\":3"));
@@ -582,6 +767,9 @@ mod tests {
             status: WaitlistStatus::Waiting,
             offer_expires_at: None,
             accepted_booking_id: None,
+            start_time: None,
+            end_time: None,
+            offered_slot_id: None,
         };
         entry.status = WaitlistStatus::Offered;
         entry.notified_at = Some(Utc::now());
@@ -601,6 +789,9 @@ mod tests {
             status: WaitlistStatus::Offered,
             offer_expires_at: Some(Utc::now() + Duration::minutes(15)),
             accepted_booking_id: None,
+            start_time: None,
+            end_time: None,
+            offered_slot_id: None,
         };
         entry.status = WaitlistStatus::Accepted;
         entry.accepted_booking_id = Some(Uuid::new_v4());
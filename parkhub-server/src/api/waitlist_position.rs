@@ -0,0 +1,273 @@
+//! Place in line and estimated availability of waitlist entries.
+//!
+//! Entries are served first come, first served (see `noshow.rs`), so an
+//! entry's place in line is the number of earlier entries still waiting or
+//! holding an offer. Only entries whose windows overlap compete; an entry
+//! without a window competes with all of them.
+//!
+//! How soon a slot frees up is estimated from the lot's turnover over the
+//! last [`TURNOVER_DAYS`] days, i.e. how often its bookings ended or were
+//! cancelled. With `n` entries ahead, an entry expects a slot after `n + 1`
+//! such releases, spaced by the mean time between them.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+
+use parkhub_common::models::{WaitlistEntry, WaitlistPosition, WaitlistStatus};
+use parkhub_common::{Booking, BookingStatus};
+
+use crate::db::Database;
+
+use super::noshow::entry_window;
+
+/// Days of booking history the turnover is measured over
+const TURNOVER_DAYS: i64 = 14;
+
+/// When `booking` gave its slot back, if it has by `now`
+fn released_at(booking: &Booking, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    match booking.status {
+        BookingStatus::Completed => Some(booking.check_out_time.unwrap_or(booking.end_time)),
+        BookingStatus::Cancelled | BookingStatus::Expired | BookingStatus::NoShow => {
+            Some(booking.updated_at)
+        }
+        BookingStatus::Pending | BookingStatus::Confirmed | BookingStatus::Active => {
+            (booking.end_time <= now).then_some(booking.end_time)
+        }
+    }
+}
+
+/// Mean time between slot releases in lot `lot_id` over the last
+/// [`TURNOVER_DAYS`] days; `None` if no slot was released in that time.
+pub fn turnover_interval(
+    bookings: &[Booking],
+    lot_id: Uuid,
+    now: DateTime<Utc>,
+) -> Option<Duration> {
+    let period = Duration::days(TURNOVER_DAYS);
+    let since = now - period;
+    let releases = bookings
+        .iter()
+        .filter(|b| b.lot_id == lot_id)
+        .filter_map(|b| released_at(b, now))
+        .filter(|at| *at > since && *at <= now)
+        .count();
+    let releases = i32::try_from(releases).ok().filter(|n| *n > 0)?;
+    Some((period / releases).max(Duration::minutes(1)))
+}
+
+const fn in_line(entry: &WaitlistEntry) -> bool {
+    matches!(
+        entry.status,
+        WaitlistStatus::Waiting | WaitlistStatus::Offered
+    )
+}
+
+/// `entry`'s place in `line`, the entries of its lot in order of arrival,
+/// with a slot expected every `turnover` from `now`.
+pub fn position_in(
+    line: &[WaitlistEntry],
+    entry: &WaitlistEntry,
+    turnover: Option<Duration>,
+    now: DateTime<Utc>,
+) -> WaitlistPosition {
+    if !in_line(entry) {
+        return WaitlistPosition {
+            entry: entry.clone(),
+            position: 0,
+            total_ahead: 0,
+            estimated_wait_minutes: None,
+            estimated_available_at: None,
+        };
+    }
+
+    let window = entry_window(entry);
+    let ahead = line
+        .iter()
+        .take_while(|e| e.id != entry.id)
+        .filter(|e| {
+            in_line(e)
+                && match (entry_window(e), &window) {
+                    (Some(theirs), Some(ours)) => theirs.overlaps(ours),
+                    _ => true,
+                }
+        })
+        .count();
+    let wait = if entry.status == WaitlistStatus::Offered {
+        Some(Duration::zero())
+    } else {
+        turnover.and_then(|every| every.checked_mul(i32::try_from(ahead + 1).ok()?))
+    };
+
+    WaitlistPosition {
+        entry: entry.clone(),
+        position: ahead + 1,
+        total_ahead: ahead,
+        estimated_wait_minutes: wait.map(|wait| wait.num_minutes()),
+        estimated_available_at: wait.map(|wait| now + wait),
+    }
+}
+
+/// Positions of `entries`, which may belong to different lots, read from
+/// the lots' waitlists and booking history.
+pub async fn positions_of(db: &Database, entries: &[WaitlistEntry]) -> Vec<WaitlistPosition> {
+    let now = Utc::now();
+    let bookings = if entries.iter().any(in_line) {
+        db.list_bookings().await.unwrap_or_else(|e| {
+            tracing::warn!("Failed to load bookings for waitlist estimates: {e}");
+            Vec::new()
+        })
+    } else {
+        Vec::new()
+    };
+
+    let mut lots: HashMap<Uuid, (Vec<WaitlistEntry>, Option<Duration>)> = HashMap::new();
+    let mut positions = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let (line, turnover) = match lots.entry(entry.lot_id) {
+            Entry::Occupied(known) => known.into_mut(),
+            Entry::Vacant(vacant) => {
+                let line = db
+                    .list_waitlist_by_lot(&entry.lot_id.to_string())
+                    .await
+                    .unwrap_or_default();
+                vacant.insert((line, turnover_interval(&bookings, entry.lot_id, now)))
+            }
+        };
+        positions.push(position_in(line, entry, *turnover, now));
+    }
+    positions
+}
+
+/// Turnover of lot `lot_id` as [`turnover_interval`] measures it, from the
+/// stored bookings.
+pub async fn lot_turnover(db: &Database, lot_id: Uuid) -> Option<Duration> {
+    match db.list_bookings().await {
+        Ok(bookings) => turnover_interval(&bookings, lot_id, Utc::now()),
+        Err(e) => {
+            tracing::warn!("Failed to load bookings for waitlist estimates: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn booking(lot_id: Uuid, status: BookingStatus, end_time: DateTime<Utc>) -> Booking {
+        use parkhub_common::{BookingPricing, PaymentStatus, Vehicle};
+        let start_time = end_time - Duration::hours(2);
+        Booking {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            lot_id,
+            slot_id: Uuid::new_v4(),
+            slot_number: 1,
+            floor_name: "Ground".to_string(),
+            vehicle: Vehicle {
+                id: Uuid::new_v4(),
+                user_id: Uuid::new_v4(),
+                license_plate: "M-AB 123".to_string(),
+                make: None,
+                model: None,
+                color: None,
+                vehicle_type: parkhub_common::VehicleType::Car,
+                fuel_type: parkhub_common::FuelType::Unknown,
+                is_default: true,
+                created_at: start_time,
+            },
+            start_time,
+            end_time,
+            status,
+            pricing: BookingPricing {
+                base_price: 0.0,
+                discount: 0.0,
+                tax: 0.0,
+                total: 0.0,
+                currency: "EUR".to_string(),
+                payment_status: PaymentStatus::Pending,
+                payment_method: None,
+                cancellation_fee: None,
+            },
+            created_at: start_time,
+            updated_at: end_time,
+            check_in_time: None,
+            check_out_time: None,
+            qr_code: None,
+            notes: None,
+            tenant_id: None,
+            tags: std::collections::BTreeMap::new(),
+            booked_for: None,
+        }
+    }
+
+    fn entry(lot_id: Uuid, status: WaitlistStatus, window: Option<(i64, i64)>) -> WaitlistEntry {
+        let now = Utc::now();
+        WaitlistEntry {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            lot_id,
+            created_at: now,
+            notified_at: None,
+            status,
+            offer_expires_at: None,
+            accepted_booking_id: None,
+            start_time: window.map(|(start, _)| now + Duration::hours(start)),
+            end_time: window.map(|(_, end)| now + Duration::hours(end)),
+            offered_slot_id: None,
+        }
+    }
+
+    #[test]
+    fn turnover_counts_recent_releases_of_the_lot() {
+        let now = Utc::now();
+        let lot = Uuid::new_v4();
+        let bookings = [
+            booking(lot, BookingStatus::Completed, now - Duration::hours(1)),
+            booking(lot, BookingStatus::Cancelled, now - Duration::days(1)),
+            booking(lot, BookingStatus::Confirmed, now - Duration::days(2)),
+            // Still running, too old, or another lot
+            booking(lot, BookingStatus::Active, now + Duration::hours(1)),
+            booking(lot, BookingStatus::Completed, now - Duration::days(20)),
+            booking(Uuid::new_v4(), BookingStatus::Completed, now),
+        ];
+        assert_eq!(
+            turnover_interval(&bookings, lot, now),
+            Some(Duration::days(TURNOVER_DAYS) / 3)
+        );
+        assert_eq!(turnover_interval(&bookings[3..], lot, now), None);
+    }
+
+    #[test]
+    fn position_counts_competing_entries_ahead() {
+        let lot = Uuid::new_v4();
+        let now = Utc::now();
+        let line = [
+            entry(lot, WaitlistStatus::Offered, None),
+            entry(lot, WaitlistStatus::Expired, None),
+            entry(lot, WaitlistStatus::Waiting, Some((10, 12))),
+            entry(lot, WaitlistStatus::Waiting, Some((1, 3))),
+        ];
+
+        let first = position_in(&line, &line[0], None, now);
+        assert_eq!((first.position, first.total_ahead), (1, 0));
+        assert_eq!(first.estimated_wait_minutes, Some(0));
+
+        // The entry for 10–12 h does not compete with the one for 1–3 h
+        let last = position_in(&line, &line[3], Some(Duration::minutes(30)), now);
+        assert_eq!((last.position, last.total_ahead), (2, 1));
+        assert_eq!(last.estimated_wait_minutes, Some(60));
+        assert_eq!(last.estimated_available_at, Some(now + Duration::hours(1)));
+        assert_eq!(
+            position_in(&line, &line[3], None, now).estimated_wait_minutes,
+            None
+        );
+
+        let expired = position_in(&line, &line[1], Some(Duration::minutes(30)), now);
+        assert_eq!(expired.position, 0);
+        assert!(expired.estimated_available_at.is_none());
+    }
+}
@@ -15,12 +15,12 @@ use sha2::Sha256;
 use std::net::IpAddr;
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use crate::circuit_breaker::{self, Error as CbError};
 use crate::db::Webhook;
 
-use super::{AuthUser, SharedState};
+use super::{AuthUser, SharedState, check_admin};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Request / Response types
@@ -199,18 +199,14 @@ pub async fn list_webhooks(
     let state_guard = state.read().await;
 
     // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     match state_guard.db.list_webhooks().await {
@@ -224,7 +220,7 @@ pub async fn list_webhooks(
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to list webhooks",
                 )),
             )
@@ -254,25 +250,21 @@ pub async fn create_webhook(
     let state_guard = state.read().await;
 
     // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     // Validate URL
     if let Some(err) = validate_webhook_url(&req.url) {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("VALIDATION_ERROR", err)),
+            Json(ApiResponse::error(ErrorCode::ValidationError, err)),
         );
     }
 
@@ -282,7 +274,7 @@ pub async fn create_webhook(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "Invalid event type. Valid: booking.created, booking.cancelled, booking.updated, user.created, user.deleted, lot.created, lot.updated, lot.deleted, test",
                 )),
             );
@@ -293,7 +285,7 @@ pub async fn create_webhook(
         return (
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "At least one event type is required",
             )),
         );
@@ -315,7 +307,7 @@ pub async fn create_webhook(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to create webhook",
             )),
         );
@@ -355,18 +347,14 @@ pub async fn update_webhook(
     let state_guard = state.read().await;
 
     // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     // Fetch existing webhook
@@ -375,14 +363,17 @@ pub async fn update_webhook(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Webhook not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Webhook not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -392,7 +383,7 @@ pub async fn update_webhook(
         if let Some(err) = validate_webhook_url(url) {
             return (
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("VALIDATION_ERROR", err)),
+                Json(ApiResponse::error(ErrorCode::ValidationError, err)),
             );
         }
         webhook.url = url.clone();
@@ -403,7 +394,10 @@ pub async fn update_webhook(
             if !VALID_EVENTS.contains(&event.as_str()) {
                 return (
                     StatusCode::BAD_REQUEST,
-                    Json(ApiResponse::error("VALIDATION_ERROR", "Invalid event type")),
+                    Json(ApiResponse::error(
+                        ErrorCode::ValidationError,
+                        "Invalid event type",
+                    )),
                 );
             }
         }
@@ -411,7 +405,7 @@ pub async fn update_webhook(
             return (
                 StatusCode::BAD_REQUEST,
                 Json(ApiResponse::error(
-                    "VALIDATION_ERROR",
+                    ErrorCode::ValidationError,
                     "At least one event type is required",
                 )),
             );
@@ -434,7 +428,7 @@ pub async fn update_webhook(
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ApiResponse::error(
-                "SERVER_ERROR",
+                ErrorCode::ServerError,
                 "Failed to update webhook",
             )),
         );
@@ -471,18 +465,14 @@ pub async fn delete_webhook(
     let state_guard = state.read().await;
 
     // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     match state_guard.db.delete_webhook(&id).await {
@@ -492,14 +482,14 @@ pub async fn delete_webhook(
         }
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Webhook not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Webhook not found")),
         ),
         Err(e) => {
             tracing::error!("Failed to delete webhook: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(ApiResponse::error(
-                    "SERVER_ERROR",
+                    ErrorCode::ServerError,
                     "Failed to delete webhook",
                 )),
             )
@@ -529,18 +519,14 @@ pub async fn test_webhook(
     let state_guard = state.read().await;
 
     // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     let webhook = match state_guard.db.get_webhook(&id).await {
@@ -548,14 +534,17 @@ pub async fn test_webhook(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Webhook not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Webhook not found")),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -25,7 +25,7 @@ use serde::{Deserialize, Serialize};
 use sha2::Sha256;
 use uuid::Uuid;
 
-use parkhub_common::ApiResponse;
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use crate::audit::{AuditEntry, AuditEventType};
 use crate::circuit_breaker::{self, Error as CbError};
@@ -383,7 +383,7 @@ pub async fn create_webhook_v2(
     if let Some(err) = validate_url(&req.url) {
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("VALIDATION_ERROR", err)),
+            Json(ApiResponse::error(ErrorCode::ValidationError, err)),
         ));
     }
 
@@ -392,7 +392,7 @@ pub async fn create_webhook_v2(
         return Err((
             StatusCode::BAD_REQUEST,
             Json(ApiResponse::error(
-                "VALIDATION_ERROR",
+                ErrorCode::ValidationError,
                 "At least one event type is required",
             )),
         ));
@@ -400,7 +400,7 @@ pub async fn create_webhook_v2(
     if let Some(err) = validate_events(&req.events) {
         return Err((
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("VALIDATION_ERROR", &err)),
+            Json(ApiResponse::error(ErrorCode::ValidationError, &err)),
         ));
     }
 
@@ -451,7 +451,7 @@ pub async fn update_webhook_v2(
         .ok_or_else(|| {
             (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Webhook not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Webhook not found")),
             )
         })?;
 
@@ -459,7 +459,7 @@ pub async fn update_webhook_v2(
         if let Some(err) = validate_url(url) {
             return Err((
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("VALIDATION_ERROR", err)),
+                Json(ApiResponse::error(ErrorCode::ValidationError, err)),
             ));
         }
         wh.url = url.clone();
@@ -468,7 +468,7 @@ pub async fn update_webhook_v2(
         if let Some(err) = validate_events(events) {
             return Err((
                 StatusCode::BAD_REQUEST,
-                Json(ApiResponse::error("VALIDATION_ERROR", &err)),
+                Json(ApiResponse::error(ErrorCode::ValidationError, &err)),
             ));
         }
         wh.events = events.clone();
@@ -512,7 +512,7 @@ pub async fn delete_webhook_v2(
     if webhooks.len() == initial_len {
         return Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Webhook not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Webhook not found")),
         ));
     }
 
@@ -544,7 +544,7 @@ pub async fn test_webhook_v2(
         .ok_or_else(|| {
             (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Webhook not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Webhook not found")),
             )
         })?;
 
@@ -576,7 +576,7 @@ pub async fn list_deliveries_v2(
     if !webhooks.iter().any(|w| w.id == webhook_id) {
         return Err((
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Webhook not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Webhook not found")),
         ));
     }
 
@@ -18,9 +18,9 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode};
 
-use super::{AuthUser, SharedState};
+use super::{AuthUser, SharedState, check_admin};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // TYPES
@@ -177,14 +177,6 @@ fn default_layout(user_id: Uuid) -> WidgetLayout {
     }
 }
 
-/// Check if user is admin
-async fn is_admin(state: &crate::AppState, auth_user: &AuthUser) -> bool {
-    match state.db.get_user(&auth_user.user_id.to_string()).await {
-        Ok(Some(u)) => u.role == UserRole::Admin || u.role == UserRole::SuperAdmin,
-        _ => false,
-    }
-}
-
 /// Generate sample widget data
 fn generate_widget_data(widget_type: &WidgetType) -> serde_json::Value {
     match widget_type {
@@ -255,10 +247,13 @@ pub async fn get_widget_layout(
 ) -> (StatusCode, Json<ApiResponse<WidgetLayout>>) {
     let state_guard = state.read().await;
 
-    if !is_admin(&state_guard, &auth_user).await {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -289,10 +284,13 @@ pub async fn save_widget_layout(
 ) -> (StatusCode, Json<ApiResponse<WidgetLayout>>) {
     let state_guard = state.read().await;
 
-    if !is_admin(&state_guard, &auth_user).await {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -308,7 +306,10 @@ pub async fn save_widget_layout(
             tracing::error!("Failed to serialize widget layout: {e}");
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Serialization error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Serialization error",
+                )),
             );
         }
     };
@@ -317,7 +318,10 @@ pub async fn save_widget_layout(
         tracing::error!("Failed to save widget layout: {e}");
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to save layout")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to save layout",
+            )),
         );
     }
 
@@ -336,16 +340,17 @@ pub async fn save_widget_layout(
     )
 )]
 pub async fn get_widget_data(
-    State(state): State<SharedState>,
+    State(_state): State<SharedState>,
     Extension(auth_user): Extension<AuthUser>,
     Path(widget_id): Path<String>,
 ) -> (StatusCode, Json<ApiResponse<WidgetData>>) {
-    let state_guard = state.read().await;
-
-    if !is_admin(&state_guard, &auth_user).await {
+    if check_admin(&auth_user).is_err() {
         return (
             StatusCode::FORBIDDEN,
-            Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
         );
     }
 
@@ -362,7 +367,7 @@ pub async fn get_widget_data(
         _ => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Widget not found")),
+                Json(ApiResponse::error(ErrorCode::NotFound, "Widget not found")),
             );
         }
     };
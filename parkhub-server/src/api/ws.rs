@@ -1,14 +1,29 @@
-//! WebSocket real-time event support.
+//! WebSocket transport for the live event bus.
 //!
-//! Provides a `/api/v1/ws` endpoint that upgrades HTTP connections to WebSocket.
-//! Events are distributed via a `tokio::sync::broadcast` channel for fan-out to
-//! all connected clients.
+//! Provides a `/api/v1/ws` endpoint that upgrades HTTP connections to WebSocket
+//! and forwards the [`LiveEvent`]s of [`super::events`]. Clients behind proxies
+//! that break WebSockets can use the SSE stream at `/api/v1/events` instead.
 //!
 //! ## Authentication
 //!
 //! Clients authenticate via a query parameter `?token=...` containing a valid
-//! session token. The token is validated on upgrade; unauthenticated upgrades
-//! are rejected with `401 Unauthorized`.
+//! session token. The token is validated on upgrade; invalid tokens are
+//! rejected with `401 Unauthorized`. Connections without a token receive
+//! only public events.
+//!
+//! ## Lot subscriptions
+//!
+//! A client receives events for every lot unless it narrows them down, with
+//! `?lots=<id>,<id>` on upgrade or at any time with a text message:
+//!
+//! ```json
+//! {"action": "subscribe", "lots": ["<lot id>"]}
+//! {"action": "unsubscribe", "lots": ["<lot id>"]}
+//! ```
+//!
+//! Subscribing adds lots to the filter and sends their current occupancy;
+//! unsubscribing removes them. Events that are not about a lot, such as
+//! announcements, reach every client.
 //!
 //! ## Heartbeat
 //!
@@ -24,18 +39,16 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
 };
-use chrono::Utc;
+use futures_util::stream::SplitSink;
 use parkhub_common::ApiResponse;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use std::sync::Arc;
 use tokio::sync::{RwLock, broadcast};
 use tracing::{debug, warn};
 
 use crate::AppState;
 
-/// Capacity of the broadcast channel. Slow readers that fall behind will miss
-/// messages (lagged), which is acceptable for real-time UI updates.
-const BROADCAST_CAPACITY: usize = 256;
+use super::events::{LiveEvent, LotFilter, check_token, occupancy_snapshot};
 
 /// Heartbeat interval in seconds.
 const HEARTBEAT_INTERVAL_SECS: u64 = 30;
@@ -44,139 +57,15 @@ const HEARTBEAT_INTERVAL_SECS: u64 = 30;
 const MAX_MISSED_PONGS: u8 = 3;
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Event types
-// ─────────────────────────────────────────────────────────────────────────────
-
-/// Event types that can be broadcast over the WebSocket.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "snake_case")]
-pub enum WsEventType {
-    BookingCreated,
-    BookingCancelled,
-    OccupancyChanged,
-    AnnouncementPublished,
-    SlotStatusChange,
-}
-
-/// A WebSocket event message sent to connected clients.
-///
-/// The `data` field carries event-specific detail as freeform JSON.
-/// This allows adding new event types without changing the wire format.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WsEvent {
-    pub event: WsEventType,
-    pub data: serde_json::Value,
-    pub timestamp: String,
-}
-
-impl WsEvent {
-    /// Create a new event with the current UTC timestamp.
-    pub fn new(event: WsEventType, data: serde_json::Value) -> Self {
-        Self {
-            event,
-            data,
-            timestamp: Utc::now().to_rfc3339(),
-        }
-    }
-
-    /// Create a `BookingCreated` event.
-    pub fn booking_created(lot_id: &str, slot_id: &str, user_id: &str) -> Self {
-        Self::new(
-            WsEventType::BookingCreated,
-            serde_json::json!({
-                "lot_id": lot_id,
-                "slot_id": slot_id,
-                "user_id": user_id,
-            }),
-        )
-    }
-
-    /// Create a `BookingCancelled` event.
-    pub fn booking_cancelled(lot_id: &str, slot_id: &str) -> Self {
-        Self::new(
-            WsEventType::BookingCancelled,
-            serde_json::json!({
-                "lot_id": lot_id,
-                "slot_id": slot_id,
-            }),
-        )
-    }
-
-    /// Create an `OccupancyChanged` event.
-    pub fn occupancy_update(lot_id: &str, available: u32, total: u32) -> Self {
-        Self::new(
-            WsEventType::OccupancyChanged,
-            serde_json::json!({
-                "lot_id": lot_id,
-                "available": available,
-                "total": total,
-            }),
-        )
-    }
-
-    /// Create an `AnnouncementPublished` event.
-    pub fn announcement_published(id: &str, title: &str) -> Self {
-        Self::new(
-            WsEventType::AnnouncementPublished,
-            serde_json::json!({
-                "id": id,
-                "title": title,
-            }),
-        )
-    }
-
-    /// Create a `SlotStatusChange` event.
-    pub fn slot_status_change(lot_id: &str, slot_id: &str, status: &str) -> Self {
-        Self::new(
-            WsEventType::SlotStatusChange,
-            serde_json::json!({
-                "lot_id": lot_id,
-                "slot_id": slot_id,
-                "status": status,
-            }),
-        )
-    }
-}
-
-// ─────────────────────────────────────────────────────────────────────────────
-// Broadcast channel
+// Client messages
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Holds the broadcast sender for WebSocket events.
-#[derive(Debug, Clone)]
-pub struct EventBroadcaster {
-    sender: broadcast::Sender<WsEvent>,
-}
-
-impl EventBroadcaster {
-    /// Create a new broadcaster with the default channel capacity.
-    pub fn new() -> Self {
-        let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
-        Self { sender }
-    }
-
-    /// Broadcast an event to all connected WebSocket clients.
-    /// Returns the number of receivers that will get the message.
-    /// Returns 0 if there are no active subscribers (which is fine).
-    pub fn broadcast(&self, event: WsEvent) -> usize {
-        self.sender.send(event).unwrap_or_default()
-    }
-
-    /// Subscribe to receive events.
-    pub fn subscribe(&self) -> broadcast::Receiver<WsEvent> {
-        self.sender.subscribe()
-    }
-
-    /// Get the current number of active receivers.
-    pub fn receiver_count(&self) -> usize {
-        self.sender.receiver_count()
-    }
-}
-
-impl Default for EventBroadcaster {
-    fn default() -> Self {
-        Self::new()
-    }
+/// A text message from the client changing its lot subscriptions.
+#[derive(Debug, Deserialize, PartialEq, Eq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Subscribe { lots: Vec<String> },
+    Unsubscribe { lots: Vec<String> },
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -189,6 +78,8 @@ pub struct WsQuery {
     /// Session token for authentication (optional — allows unauthenticated
     /// connections for public occupancy display).
     pub token: Option<String>,
+    /// Comma-separated lot IDs to receive events for (default: all lots).
+    pub lots: Option<String>,
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
@@ -200,52 +91,36 @@ type SharedState = Arc<RwLock<AppState>>;
 /// Handler for GET /api/v1/ws — upgrades to WebSocket.
 ///
 /// Authentication is performed via the `?token=...` query parameter.
-/// If a token is provided it must be a valid, non-expired session.
+/// If a token is provided it must be a valid, non-expired access token.
 /// Connections without a token are allowed but receive only public events.
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
     State(state): State<SharedState>,
     Query(params): Query<WsQuery>,
 ) -> Result<impl IntoResponse, (StatusCode, Json<ApiResponse<()>>)> {
-    // Validate token if provided
-    if let Some(ref token) = params.token {
-        let state_guard = state.read().await;
-        match state_guard.db.get_session(token).await {
-            Ok(Some(s)) if !s.is_expired() => {
-                // Valid session — check user is active
-                match state_guard.db.get_user(&s.user_id.to_string()).await {
-                    Ok(Some(u)) if u.is_active => {
-                        debug!(user_id = %s.user_id, "WebSocket authenticated");
-                    }
-                    _ => {
-                        return Err((
-                            StatusCode::UNAUTHORIZED,
-                            Json(ApiResponse::error(
-                                "UNAUTHORIZED",
-                                "Invalid or disabled user",
-                            )),
-                        ));
-                    }
-                }
-            }
-            _ => {
-                return Err((
-                    StatusCode::UNAUTHORIZED,
-                    Json(ApiResponse::error(
-                        "UNAUTHORIZED",
-                        "Invalid or expired token",
-                    )),
-                ));
-            }
+    check_token(&state, params.token.as_deref()).await?;
+
+    let filter = LotFilter::from_query(params.lots.as_deref());
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, state, filter)))
+}
+
+/// Send `events` to the client; `false` once it has disconnected.
+async fn send_events(sender: &mut SplitSink<WebSocket, Message>, events: Vec<LiveEvent>) -> bool {
+    use futures_util::SinkExt;
+
+    for event in events {
+        if let Ok(json) = serde_json::to_string(&event)
+            && sender.send(Message::Text(json.into())).await.is_err()
+        {
+            return false;
         }
     }
-
-    Ok(ws.on_upgrade(move |socket| handle_socket(socket, state)))
+    true
 }
 
 /// Manages a single WebSocket connection: subscribes to the broadcast channel,
-/// forwards events to the client, and sends periodic pings.
-async fn handle_socket(socket: WebSocket, state: SharedState) {
+/// forwards the events `filter` lets through, and sends periodic pings.
+async fn handle_socket(socket: WebSocket, state: SharedState, mut filter: LotFilter) {
     use futures_util::{SinkExt, StreamExt};
 
     let broadcaster = {
@@ -256,29 +131,9 @@ async fn handle_socket(socket: WebSocket, state: SharedState) {
 
     let (mut sender, mut receiver) = socket.split();
 
-    // Send initial occupancy snapshot for all lots
-    {
-        let s = state.read().await;
-        if let Ok(lots) = s.db.list_parking_lots().await {
-            for lot in &lots {
-                if let Ok(slots) = s.db.list_slots_by_lot(&lot.id.to_string()).await {
-                    let total = u32::try_from(slots.len()).unwrap_or(u32::MAX);
-                    let available = u32::try_from(
-                        slots
-                            .iter()
-                            .filter(|sl| sl.status == parkhub_common::SlotStatus::Available)
-                            .count(),
-                    )
-                    .unwrap_or(u32::MAX);
-                    let snapshot = WsEvent::occupancy_update(&lot.id.to_string(), available, total);
-                    if let Ok(json) = serde_json::to_string(&snapshot)
-                        && sender.send(Message::Text(json.into())).await.is_err()
-                    {
-                        return; // Client disconnected during snapshot
-                    }
-                }
-            }
-        }
+    // Send initial occupancy snapshot for the subscribed lots
+    if !send_events(&mut sender, occupancy_snapshot(&state, &filter).await).await {
+        return; // Client disconnected during snapshot
     }
 
     // Heartbeat timer
@@ -293,12 +148,13 @@ async fn handle_socket(socket: WebSocket, state: SharedState) {
             // Forward broadcast events to this client
             event = rx.recv() => {
                 match event {
-                    Ok(ws_event) => {
+                    Ok(ws_event) if filter.matches(&ws_event) => {
                         if let Ok(json) = serde_json::to_string(&ws_event)
                             && sender.send(Message::Text(json.into())).await.is_err() {
                                 break; // Client disconnected
                             }
                     }
+                    Ok(_) => {} // Another lot
                     Err(broadcast::error::RecvError::Lagged(n)) => {
                         warn!("WebSocket client lagged, skipped {n} messages");
                     }
@@ -332,7 +188,22 @@ async fn handle_socket(socket: WebSocket, state: SharedState) {
                     Some(Err(_)) => {
                         break; // Connection error
                     }
-                    _ => {} // Text/binary from client — ignore
+                    Some(Ok(Message::Text(text))) => {
+                        match serde_json::from_str::<ClientMessage>(&text) {
+                            Ok(ClientMessage::Subscribe { lots }) if !lots.is_empty() => {
+                                filter.subscribe(&lots);
+                                let added = LotFilter::only(lots);
+                                let snapshot = occupancy_snapshot(&state, &added).await;
+                                if !send_events(&mut sender, snapshot).await {
+                                    break; // Client disconnected
+                                }
+                            }
+                            Ok(ClientMessage::Subscribe { .. }) => {}
+                            Ok(ClientMessage::Unsubscribe { lots }) => filter.unsubscribe(&lots),
+                            Err(e) => debug!("Ignoring WebSocket message: {e}"),
+                        }
+                    }
+                    _ => {} // Binary from client — ignore
                 }
             }
         }
@@ -348,177 +219,6 @@ async fn handle_socket(socket: WebSocket, state: SharedState) {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn event_serialization() {
-        let event = WsEvent::new(
-            WsEventType::BookingCreated,
-            serde_json::json!({"booking_id": "abc-123", "slot": 5}),
-        );
-        let json = serde_json::to_string(&event).unwrap();
-        assert!(json.contains("\"event\":\"booking_created\""));
-        assert!(json.contains("\"booking_id\":\"abc-123\""));
-        assert!(json.contains("\"timestamp\""));
-
-        // Roundtrip
-        let parsed: WsEvent = serde_json::from_str(&json).unwrap();
-        assert_eq!(parsed.event, WsEventType::BookingCreated);
-    }
-
-    #[test]
-    fn event_types_serialize_snake_case() {
-        let cases = vec![
-            (WsEventType::BookingCreated, "\"booking_created\""),
-            (WsEventType::BookingCancelled, "\"booking_cancelled\""),
-            (WsEventType::OccupancyChanged, "\"occupancy_changed\""),
-            (
-                WsEventType::AnnouncementPublished,
-                "\"announcement_published\"",
-            ),
-            (WsEventType::SlotStatusChange, "\"slot_status_change\""),
-        ];
-        for (variant, expected) in cases {
-            let json = serde_json::to_string(&variant).unwrap();
-            assert_eq!(json, expected);
-        }
-    }
-
-    #[test]
-    fn broadcaster_no_receivers() {
-        let broadcaster = EventBroadcaster::new();
-        let event = WsEvent::new(
-            WsEventType::OccupancyChanged,
-            serde_json::json!({"lot_id": "lot-1", "available": 42}),
-        );
-        assert_eq!(broadcaster.broadcast(event), 0);
-        assert_eq!(broadcaster.receiver_count(), 0);
-    }
-
-    #[test]
-    fn broadcaster_fan_out() {
-        let broadcaster = EventBroadcaster::new();
-        let mut rx1 = broadcaster.subscribe();
-        let mut rx2 = broadcaster.subscribe();
-
-        let event = WsEvent::new(
-            WsEventType::BookingCancelled,
-            serde_json::json!({"booking_id": "xyz-789"}),
-        );
-
-        let count = broadcaster.broadcast(event.clone());
-        assert_eq!(count, 2);
-
-        let received1 = rx1.try_recv().unwrap();
-        assert_eq!(received1.event, WsEventType::BookingCancelled);
-
-        let received2 = rx2.try_recv().unwrap();
-        assert_eq!(received2.event, WsEventType::BookingCancelled);
-    }
-
-    #[test]
-    fn broadcaster_lagged_receiver() {
-        let (sender, _) = broadcast::channel::<WsEvent>(2);
-        let broadcaster = EventBroadcaster { sender };
-
-        let mut rx = broadcaster.subscribe();
-
-        for i in 0..3 {
-            broadcaster.broadcast(WsEvent::new(
-                WsEventType::OccupancyChanged,
-                serde_json::json!({"seq": i}),
-            ));
-        }
-
-        match rx.try_recv() {
-            Err(broadcast::error::TryRecvError::Lagged(n)) => {
-                assert_eq!(n, 1);
-            }
-            other => panic!("Expected Lagged, got {:?}", other),
-        }
-
-        let event = rx.try_recv().unwrap();
-        assert_eq!(event.data["seq"], 1);
-    }
-
-    #[test]
-    fn broadcaster_default_impl() {
-        let b = EventBroadcaster::default();
-        assert_eq!(b.receiver_count(), 0);
-    }
-
-    #[test]
-    fn broadcaster_dropped_receiver() {
-        let broadcaster = EventBroadcaster::new();
-        let rx = broadcaster.subscribe();
-        assert_eq!(broadcaster.receiver_count(), 1);
-
-        drop(rx);
-        assert_eq!(broadcaster.receiver_count(), 0);
-
-        let event = WsEvent::new(WsEventType::BookingCreated, serde_json::json!({}));
-        assert_eq!(broadcaster.broadcast(event), 0);
-    }
-
-    #[test]
-    fn booking_created_event_factory() {
-        let event = WsEvent::booking_created("lot-1", "slot-2", "user-3");
-        assert_eq!(event.event, WsEventType::BookingCreated);
-        assert_eq!(event.data["lot_id"], "lot-1");
-        assert_eq!(event.data["slot_id"], "slot-2");
-        assert_eq!(event.data["user_id"], "user-3");
-    }
-
-    #[test]
-    fn booking_cancelled_event_factory() {
-        let event = WsEvent::booking_cancelled("lot-1", "slot-2");
-        assert_eq!(event.event, WsEventType::BookingCancelled);
-        assert_eq!(event.data["lot_id"], "lot-1");
-        assert_eq!(event.data["slot_id"], "slot-2");
-    }
-
-    #[test]
-    fn occupancy_update_event_factory() {
-        let event = WsEvent::occupancy_update("lot-1", 5, 10);
-        assert_eq!(event.event, WsEventType::OccupancyChanged);
-        assert_eq!(event.data["lot_id"], "lot-1");
-        assert_eq!(event.data["available"], 5);
-        assert_eq!(event.data["total"], 10);
-    }
-
-    #[test]
-    fn announcement_published_event_factory() {
-        let event = WsEvent::announcement_published("ann-1", "Important Notice");
-        assert_eq!(event.event, WsEventType::AnnouncementPublished);
-        assert_eq!(event.data["id"], "ann-1");
-        assert_eq!(event.data["title"], "Important Notice");
-    }
-
-    #[test]
-    fn slot_status_change_event_factory() {
-        let event = WsEvent::slot_status_change("lot-1", "slot-2", "maintenance");
-        assert_eq!(event.event, WsEventType::SlotStatusChange);
-        assert_eq!(event.data["lot_id"], "lot-1");
-        assert_eq!(event.data["slot_id"], "slot-2");
-        assert_eq!(event.data["status"], "maintenance");
-    }
-
-    #[test]
-    fn event_roundtrip_all_types() {
-        let events = vec![
-            WsEvent::booking_created("l", "s", "u"),
-            WsEvent::booking_cancelled("l", "s"),
-            WsEvent::occupancy_update("l", 1, 2),
-            WsEvent::announcement_published("a", "t"),
-            WsEvent::slot_status_change("l", "s", "ok"),
-        ];
-        for event in events {
-            let json = serde_json::to_string(&event).unwrap();
-            let parsed: WsEvent = serde_json::from_str(&json).unwrap();
-            assert_eq!(parsed.event, event.event);
-            assert_eq!(parsed.data, event.data);
-        }
-    }
-
     #[test]
     fn ws_query_deserialize_with_token() {
         let q: WsQuery = serde_json::from_str(r#"{"token":"abc123"}"#).unwrap();
@@ -529,5 +229,22 @@ mod tests {
     fn ws_query_deserialize_without_token() {
         let q: WsQuery = serde_json::from_str("{}").unwrap();
         assert!(q.token.is_none());
+        assert!(q.lots.is_none());
+    }
+
+    #[test]
+    fn client_message_deserialize() {
+        let msg: ClientMessage =
+            serde_json::from_str(r#"{"action":"subscribe","lots":["lot-1"]}"#).unwrap();
+        assert_eq!(
+            msg,
+            ClientMessage::Subscribe {
+                lots: vec!["lot-1".to_string()]
+            }
+        );
+        let msg: ClientMessage =
+            serde_json::from_str(r#"{"action":"unsubscribe","lots":[]}"#).unwrap();
+        assert_eq!(msg, ClientMessage::Unsubscribe { lots: vec![] });
+        assert!(serde_json::from_str::<ClientMessage>(r#"{"action":"ping"}"#).is_err());
     }
 }
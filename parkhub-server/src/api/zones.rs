@@ -13,11 +13,11 @@ use chrono::Utc;
 use serde::Deserialize;
 use uuid::Uuid;
 
-use parkhub_common::{ApiResponse, UserRole};
+use parkhub_common::{ApiResponse, ErrorCode};
 
 use crate::db::Zone;
 
-use super::{AuthUser, SharedState};
+use super::{AuthUser, SharedState, check_admin};
 
 // ─────────────────────────────────────────────────────────────────────────────
 // Request DTOs
@@ -60,7 +60,10 @@ pub async fn list_zones(
         Ok(zones) => Json(ApiResponse::success(zones)),
         Err(e) => {
             tracing::error!("Database error: {}", e);
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to list zones"))
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to list zones",
+            ))
         }
     }
 }
@@ -90,18 +93,14 @@ pub async fn create_zone(
     let state_guard = state.read().await;
 
     // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     // Verify lot exists
@@ -110,14 +109,20 @@ pub async fn create_zone(
         Ok(None) => {
             return (
                 StatusCode::NOT_FOUND,
-                Json(ApiResponse::error("NOT_FOUND", "Parking lot not found")),
+                Json(ApiResponse::error(
+                    ErrorCode::NotFound,
+                    "Parking lot not found",
+                )),
             );
         }
         Err(e) => {
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     }
@@ -125,7 +130,10 @@ pub async fn create_zone(
     let Ok(lot_uuid) = lot_id.parse::<Uuid>() else {
         return (
             StatusCode::BAD_REQUEST,
-            Json(ApiResponse::error("VALIDATION_ERROR", "Invalid lot ID")),
+            Json(ApiResponse::error(
+                ErrorCode::ValidationError,
+                "Invalid lot ID",
+            )),
         );
     };
 
@@ -142,7 +150,10 @@ pub async fn create_zone(
         tracing::error!("Failed to save zone: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to create zone")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to create zone",
+            )),
         );
     }
     drop(state_guard);
@@ -198,18 +209,14 @@ pub async fn update_zone(
     let state_guard = state.write().await;
 
     // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     // Load zones for the lot and find the target zone
@@ -219,7 +226,10 @@ pub async fn update_zone(
             tracing::error!("Database error: {}", e);
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Internal server error")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Internal server error",
+                )),
             );
         }
     };
@@ -227,7 +237,7 @@ pub async fn update_zone(
     let Some(mut zone) = zones.into_iter().find(|z| z.id.to_string() == zone_id) else {
         return (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Zone not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Zone not found")),
         );
     };
 
@@ -246,7 +256,10 @@ pub async fn update_zone(
         tracing::error!("Failed to update zone: {}", e);
         return (
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(ApiResponse::error("SERVER_ERROR", "Failed to update zone")),
+            Json(ApiResponse::error(
+                ErrorCode::ServerError,
+                "Failed to update zone",
+            )),
         );
     }
 
@@ -285,18 +298,14 @@ pub async fn delete_zone(
     let state_guard = state.read().await;
 
     // Admin check
-    match state_guard
-        .db
-        .get_user(&auth_user.user_id.to_string())
-        .await
-    {
-        Ok(Some(u)) if u.role == UserRole::Admin || u.role == UserRole::SuperAdmin => {}
-        _ => {
-            return (
-                StatusCode::FORBIDDEN,
-                Json(ApiResponse::error("FORBIDDEN", "Admin access required")),
-            );
-        }
+    if check_admin(&auth_user).is_err() {
+        return (
+            StatusCode::FORBIDDEN,
+            Json(ApiResponse::error(
+                ErrorCode::Forbidden,
+                "Admin access required",
+            )),
+        );
     }
 
     match state_guard.db.delete_zone(&lot_id, &zone_id).await {
@@ -306,13 +315,16 @@ pub async fn delete_zone(
         }
         Ok(false) => (
             StatusCode::NOT_FOUND,
-            Json(ApiResponse::error("NOT_FOUND", "Zone not found")),
+            Json(ApiResponse::error(ErrorCode::NotFound, "Zone not found")),
         ),
         Err(e) => {
             tracing::error!("Failed to delete zone: {}", e);
             (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                Json(ApiResponse::error("SERVER_ERROR", "Failed to delete zone")),
+                Json(ApiResponse::error(
+                    ErrorCode::ServerError,
+                    "Failed to delete zone",
+                )),
             )
         }
     }
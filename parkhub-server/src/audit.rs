@@ -27,6 +27,10 @@ pub enum AuditEventType {
     UserDeactivated,
     UserActivated,
     RoleChanged,
+    InviteCreated,
+    InviteRevoked,
+    RectificationRequested,
+    RectificationResolved,
 
     // Bookings
     BookingCreated,
@@ -46,6 +50,8 @@ pub enum AuditEventType {
     LotDeleted,
     SlotStatusChanged,
     ConfigChanged,
+    BackupCreated,
+    BackupRestored,
 
     // Settings
     SettingsChanged,
@@ -56,10 +62,16 @@ pub enum AuditEventType {
     // Security
     TwoFactorEnabled,
     TwoFactorDisabled,
+    PasskeyRegistered,
+    PasskeyRemoved,
     ApiKeyCreated,
     ApiKeyRevoked,
+    ImpersonationStarted,
+    ImpersonatedRequest,
+    SessionsRevoked,
     RateLimitExceeded,
     InvalidTokenUsed,
+    RefreshTokenReused,
     UnauthorizedAccess,
     SuspiciousActivity,
 }
@@ -406,6 +418,10 @@ mod tests {
             AuditEventType::UserDeactivated,
             AuditEventType::UserActivated,
             AuditEventType::RoleChanged,
+            AuditEventType::InviteCreated,
+            AuditEventType::InviteRevoked,
+            AuditEventType::RectificationRequested,
+            AuditEventType::RectificationResolved,
             AuditEventType::BookingCreated,
             AuditEventType::BookingUpdated,
             AuditEventType::BookingCancelled,
@@ -419,14 +435,22 @@ mod tests {
             AuditEventType::LotDeleted,
             AuditEventType::SlotStatusChanged,
             AuditEventType::ConfigChanged,
+            AuditEventType::BackupCreated,
+            AuditEventType::BackupRestored,
             AuditEventType::SettingsChanged,
             AuditEventType::PaymentCompleted,
             AuditEventType::TwoFactorEnabled,
             AuditEventType::TwoFactorDisabled,
+            AuditEventType::PasskeyRegistered,
+            AuditEventType::PasskeyRemoved,
             AuditEventType::ApiKeyCreated,
             AuditEventType::ApiKeyRevoked,
+            AuditEventType::ImpersonationStarted,
+            AuditEventType::ImpersonatedRequest,
+            AuditEventType::SessionsRevoked,
             AuditEventType::RateLimitExceeded,
             AuditEventType::InvalidTokenUsed,
+            AuditEventType::RefreshTokenReused,
             AuditEventType::UnauthorizedAccess,
             AuditEventType::SuspiciousActivity,
         ];
@@ -0,0 +1,197 @@
+//! Scheduled automatic backups.
+//!
+//! While `auto_backup_enabled` is on, a cron job on `backup_schedule` (daily
+//! at 03:00 UTC by default) writes a snapshot of the database with
+//! [`Database::backup`] to `<data dir>/backups/parkhub-YYYYMMDD-HHMMSS.redb`
+//! and then deletes the oldest ones beyond `backup_retention_count`. Only
+//! files named like that are counted or deleted: dot-prefixed temporary files
+//! of a running download or restore (see `crate::api::backup`) and anything
+//! an operator put there are left alone.
+//!
+//! A backup is a plain redb file, encrypted at rest like the live database.
+//! To go back to one, stop the server and put it in place of `parkhub.redb`.
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use tracing::{error, info, warn};
+
+use parkhub_common::BackupStatus;
+
+use crate::config::ServerConfig;
+use crate::db::Database;
+
+/// Start of an automatic backup's file name; the UTC time it was taken follows.
+const FILE_PREFIX: &str = "parkhub-";
+
+/// End of an automatic backup's file name.
+const FILE_SUFFIX: &str = ".redb";
+
+/// Format of the time in an automatic backup's file name.
+const TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// When the last scheduled backup failed; cleared by the next success.
+static LAST_FAILURE: Mutex<Option<DateTime<Utc>>> = Mutex::new(None);
+
+/// An automatic backup in the backups directory.
+#[derive(Debug)]
+struct BackupFile {
+    path: PathBuf,
+    taken_at: DateTime<Utc>,
+    bytes: u64,
+}
+
+fn file_name(taken_at: DateTime<Utc>) -> String {
+    format!("{FILE_PREFIX}{}{FILE_SUFFIX}", taken_at.format(TIME_FORMAT))
+}
+
+/// The time in an automatic backup's file name, or `None` for any other file.
+fn parse_file_name(name: &str) -> Option<DateTime<Utc>> {
+    let stamp = name.strip_prefix(FILE_PREFIX)?.strip_suffix(FILE_SUFFIX)?;
+    NaiveDateTime::parse_from_str(stamp, TIME_FORMAT)
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// The automatic backups in `dir`, newest first.
+fn list_backups(dir: &Path) -> Vec<BackupFile> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<BackupFile> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let taken_at = parse_file_name(entry.file_name().to_str()?)?;
+            let metadata = entry.metadata().ok().filter(std::fs::Metadata::is_file)?;
+            Some(BackupFile {
+                path: entry.path(),
+                taken_at,
+                bytes: metadata.len(),
+            })
+        })
+        .collect();
+    backups.sort_by(|a, b| b.taken_at.cmp(&a.taken_at));
+    backups
+}
+
+/// Delete the automatic backups in `dir` beyond the newest `keep`.
+/// Returns the number deleted.
+fn prune_backups(dir: &Path, keep: usize) -> usize {
+    let mut deleted = 0;
+    for backup in list_backups(dir).iter().skip(keep) {
+        match std::fs::remove_file(&backup.path) {
+            Ok(()) => deleted += 1,
+            Err(e) => warn!("Failed to delete old backup {}: {e}", backup.path.display()),
+        }
+    }
+    deleted
+}
+
+/// Write a backup stamped `now`, then delete the oldest beyond `retention`
+/// (the new one is always kept). Returns the new backup's path.
+pub async fn create_backup(db: &Database, retention: u32, now: DateTime<Utc>) -> Result<PathBuf> {
+    let dir = db.backup_dir();
+    let path = dir.join(file_name(now));
+    db.backup(&path).await?;
+
+    let keep = usize::try_from(retention.max(1)).unwrap_or(usize::MAX);
+    let pruned = prune_backups(&dir, keep);
+    if pruned > 0 {
+        info!("Deleted {pruned} backup(s) beyond the retention count of {keep}");
+    }
+    Ok(path)
+}
+
+/// Scheduled backup run: back up and remember the outcome for [`status`].
+pub async fn run_scheduled_backup(db: &Database, retention: u32) {
+    let result = create_backup(db, retention, Utc::now()).await;
+    let mut last_failure = LAST_FAILURE.lock().unwrap_or_else(PoisonError::into_inner);
+    match result {
+        Ok(path) => {
+            *last_failure = None;
+            info!("Automatic backup written to {}", path.display());
+        }
+        Err(e) => {
+            *last_failure = Some(Utc::now());
+            error!("Automatic backup failed: {e}");
+        }
+    }
+}
+
+/// Where automatic backups stand, for `/status` and the status window.
+pub fn status(db: &Database, config: &ServerConfig) -> BackupStatus {
+    let backups = list_backups(&db.backup_dir());
+    let newest = backups.first();
+    BackupStatus {
+        enabled: config.auto_backup_enabled,
+        schedule: config.backup_schedule.clone(),
+        last_backup_at: newest.map(|b| b.taken_at),
+        last_backup_bytes: newest.map(|b| b.bytes),
+        backup_count: u32::try_from(backups.len()).unwrap_or(u32::MAX),
+        last_failure_at: *LAST_FAILURE.lock().unwrap_or_else(PoisonError::into_inner),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DatabaseConfig;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_file_names_round_trip_and_skip_other_files() {
+        let at = Utc.with_ymd_and_hms(2026, 5, 4, 3, 0, 9).unwrap();
+        assert_eq!(file_name(at), "parkhub-20260504-030009.redb");
+        assert_eq!(parse_file_name(&file_name(at)), Some(at));
+        assert_eq!(parse_file_name("parkhub.redb"), None);
+        assert_eq!(parse_file_name(".download-1234.redb"), None);
+        assert_eq!(parse_file_name("parkhub-20260504-030009.redb.bak"), None);
+    }
+
+    #[tokio::test]
+    async fn test_create_backup_keeps_the_newest() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&DatabaseConfig {
+            path: dir.path().to_path_buf(),
+            encryption_enabled: false,
+            passphrase: None,
+            create_if_missing: true,
+        })
+        .unwrap();
+        let backups = db.backup_dir();
+        std::fs::create_dir_all(&backups).unwrap();
+        std::fs::write(backups.join(".upload-1234.redb"), b"in progress").unwrap();
+        std::fs::write(backups.join("before-upgrade.redb"), b"manual").unwrap();
+
+        let start = Utc.with_ymd_and_hms(2026, 5, 1, 3, 0, 0).unwrap();
+        for day in 0..4 {
+            create_backup(&db, 2, start + chrono::Duration::days(day))
+                .await
+                .unwrap();
+        }
+
+        let kept: Vec<_> = list_backups(&backups)
+            .into_iter()
+            .map(|b| b.taken_at)
+            .collect();
+        assert_eq!(
+            kept,
+            vec![
+                start + chrono::Duration::days(3),
+                start + chrono::Duration::days(2)
+            ]
+        );
+        assert!(backups.join(".upload-1234.redb").exists());
+        assert!(backups.join("before-upgrade.redb").exists());
+
+        let reported = status(&db, &ServerConfig::default());
+        assert_eq!(reported.backup_count, 2);
+        assert_eq!(
+            reported.last_backup_at,
+            Some(start + chrono::Duration::days(3))
+        );
+        assert!(reported.last_backup_bytes.is_some_and(|bytes| bytes > 0));
+    }
+}
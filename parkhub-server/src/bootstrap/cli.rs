@@ -18,6 +18,8 @@ pub(crate) struct CliArgs {
     pub(crate) headless: bool,
     /// Run in unattended mode (auto-configure with defaults)
     pub(crate) unattended: bool,
+    /// Run the interactive terminal setup on first start
+    pub(crate) setup: bool,
     /// Custom port to listen on
     pub(crate) port: Option<u16>,
     /// Custom data directory
@@ -27,6 +29,32 @@ pub(crate) struct CliArgs {
     /// Perform a health check against the running server and exit 0/1.
     /// Used as the Docker HEALTHCHECK command (works in distroless images).
     pub(crate) health_check: bool,
+    /// Run the offline self-test (`parkhub-server doctor`) and exit 0/1.
+    pub(crate) doctor: bool,
+    /// List or purge the dummy accounts generated during setup
+    /// (`parkhub-server dummy-users [list|purge]`) and exit.
+    pub(crate) dummy_users: Option<DummyUsersAction>,
+    /// Export or import a configuration profile
+    /// (`parkhub-server config export|import`) and exit.
+    pub(crate) config_profile: Option<ConfigProfileAction>,
+}
+
+/// What `parkhub-server dummy-users` does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DummyUsersAction {
+    List,
+    Purge,
+}
+
+/// What `parkhub-server config` does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ConfigProfileAction {
+    /// Write the profile to the file, or to stdout
+    Export(Option<PathBuf>),
+    /// Merge the profile file into `config.toml`
+    Import(PathBuf),
+    /// Missing or unknown subcommand
+    Usage,
 }
 
 impl CliArgs {
@@ -37,10 +65,14 @@ impl CliArgs {
             debug: false,
             headless: false,
             unattended: false,
+            setup: false,
             port: None,
             data_dir: None,
             version: false,
             health_check: false,
+            doctor: false,
+            dummy_users: None,
+            config_profile: None,
         };
 
         let mut i = 1;
@@ -51,7 +83,36 @@ impl CliArgs {
                 "-d" | "--debug" => cli.debug = true,
                 "--headless" => cli.headless = true,
                 "--unattended" => cli.unattended = true,
+                "--setup" => cli.setup = true,
                 "--health-check" => cli.health_check = true,
+                "doctor" | "--doctor" => cli.doctor = true,
+                "dummy-users" => {
+                    cli.dummy_users = Some(DummyUsersAction::List);
+                    match args.get(i + 1).map(String::as_str) {
+                        Some("list") => i += 1,
+                        Some("purge") => {
+                            cli.dummy_users = Some(DummyUsersAction::Purge);
+                            i += 1;
+                        }
+                        _ => {}
+                    }
+                }
+                "config" => {
+                    let command = args.get(i + 1).map(String::as_str);
+                    let file = args.get(i + 2).filter(|a| !a.starts_with('-'));
+                    cli.config_profile = Some(match (command, file) {
+                        (Some("export"), file) => {
+                            ConfigProfileAction::Export(file.map(PathBuf::from))
+                        }
+                        (Some("import"), Some(file)) => {
+                            ConfigProfileAction::Import(PathBuf::from(file))
+                        }
+                        _ => ConfigProfileAction::Usage,
+                    });
+                    if matches!(command, Some("export" | "import")) {
+                        i += 1 + usize::from(file.is_some());
+                    }
+                }
                 "-p" | "--port" => {
                     if i + 1 < args.len() {
                         cli.port = args[i + 1].parse().ok();
@@ -77,6 +138,10 @@ impl CliArgs {
         println!();
         println!("USAGE:");
         println!("    parkhub-server [OPTIONS]");
+        println!("    parkhub-server doctor [--data-dir PATH] [--port PORT]");
+        println!("    parkhub-server dummy-users [list|purge] [--data-dir PATH]");
+        println!("    parkhub-server config export [FILE] [--data-dir PATH]");
+        println!("    parkhub-server config import FILE [--data-dir PATH]");
         println!();
         println!("OPTIONS:");
         println!("    -h, --help         Show this help message");
@@ -84,12 +149,23 @@ impl CliArgs {
         println!("    -d, --debug        Enable debug logging");
         println!("    --headless         Run without GUI (console only)");
         println!("    --unattended       Auto-configure with defaults (no setup wizard)");
+        println!("    --setup            Answer the first-run setup questions on the terminal");
         println!("    -p, --port PORT    Set the server port (default: 7878)");
         println!("    --data-dir PATH    Set custom data directory");
         println!("    --health-check     Check if a running server is healthy (exits 0/1)");
         println!();
+        println!("COMMANDS:");
+        println!("    doctor             Check config, data dir, port, TLS, SMTP, mDNS and");
+        println!("                       database health, print a report, and exit 0/1");
+        println!("    dummy-users list   List the dummy accounts generated during setup");
+        println!("    dummy-users purge  Delete them (stop the server first)");
+        println!("    config export      Print the configuration as a profile for other");
+        println!("                       servers (no secrets), or write it to FILE");
+        println!("    config import      Apply a profile to this server's config.toml");
+        println!();
         println!("ENVIRONMENT VARIABLES:");
         println!("    PARKHUB_DB_PASSPHRASE    Database encryption passphrase");
+        println!("    PARKHUB_ADMIN_PASSWORD   Admin password for --unattended setup");
         println!("    PORT                     Server port (overridden by --port flag)");
         println!("    SEED_DEMO_DATA           Seed demo lots/users on first start (true/1)");
         println!("    DEMO_MODE                Enable demo UI and seed data on first start");
@@ -100,13 +176,19 @@ impl CliArgs {
         println!("    parkhub-server --headless         # Start in console mode");
         println!("    parkhub-server --debug            # Start with debug logging");
         println!("    parkhub-server --unattended       # Auto-configure and start");
+        println!("    parkhub-server --headless --setup # Configure interactively and start");
         println!("    parkhub-server -p 8080            # Use port 8080");
         println!("    parkhub-server --health-check     # Docker HEALTHCHECK probe");
+        println!("    parkhub-server doctor             # Self-test for support tickets");
+        println!("    parkhub-server dummy-users purge  # Remove test accounts before going live");
+        println!("    parkhub-server config export branch.toml  # Template for other sites");
     }
 
     pub(crate) fn print_version() {
-        println!("ParkHub Server v{}", env!("CARGO_PKG_VERSION"));
+        println!("ParkHub Server v{}", crate::build_info::VERSION);
+        println!("Commit: {}", crate::build_info::GIT_HASH);
         println!("Protocol Version: {}", parkhub_common::PROTOCOL_VERSION);
+        println!("Storage: {}", crate::build_info::STORAGE_BACKEND);
         #[cfg(feature = "gui")]
         println!("GUI: enabled");
         #[cfg(not(feature = "gui"))]
@@ -0,0 +1,227 @@
+//! `parkhub-server config export|import` — configuration profiles for
+//! setting up several servers alike, e.g. one per branch office.
+//!
+//! A profile is the server's `config.toml` minus what belongs to one
+//! installation: its name, admin credentials, database encryption and data
+//! directory layout. Secrets are not written out; the profile refers to an
+//! environment variable instead (`client_secret = "${PARKHUB_OIDC_CLIENT_SECRET}"`)
+//! which is read on import. Importing merges the profile into the existing
+//! `config.toml`, so settings the profile leaves out keep their values.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use toml::{Table, Value};
+
+use crate::config::ServerConfig;
+
+use super::cli::{CliArgs, ConfigProfileAction};
+use super::paths::get_data_directory;
+
+/// Top-level settings that stay as configured on each server.
+pub(crate) const LOCAL_KEYS: &[&str] = &[
+    "server_name",
+    "admin_username",
+    "admin_password_hash",
+    "encryption_enabled",
+    "portable_mode",
+];
+
+/// Secret settings (table, key) and the environment variable a profile
+/// names in their place.
+const SECRETS: &[(&str, &str, &str)] = &[("oidc", "client_secret", "PARKHUB_OIDC_CLIENT_SECRET")];
+
+/// Run the `config` command. Returns the process exit code.
+pub(crate) fn run_config_profile(cli: &CliArgs, action: &ConfigProfileAction) -> i32 {
+    let data_dir = match cli.data_dir.clone() {
+        Some(dir) => dir,
+        None => match get_data_directory(None) {
+            Ok(dir) => dir,
+            Err(e) => {
+                eprintln!("Cannot resolve data directory: {e}");
+                return 1;
+            }
+        },
+    };
+    let config_path = data_dir.join("config.toml");
+    let result = match action {
+        ConfigProfileAction::Export(output) => export_to(&config_path, output.as_deref()),
+        ConfigProfileAction::Import(profile) => import_from(&config_path, profile),
+        ConfigProfileAction::Usage => {
+            eprintln!("Usage: parkhub-server config export [FILE] | config import FILE");
+            return 1;
+        }
+    };
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{e:#}");
+            1
+        }
+    }
+}
+
+fn load_existing(config_path: &Path) -> Result<ServerConfig> {
+    if !config_path.exists() {
+        bail!(
+            "No configuration at {} — set the server up first",
+            config_path.display()
+        );
+    }
+    ServerConfig::load(config_path)
+        .with_context(|| format!("Cannot read {}", config_path.display()))
+}
+
+fn export_to(config_path: &Path, output: Option<&Path>) -> Result<()> {
+    let profile = export_profile(&load_existing(config_path)?)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, profile)
+                .with_context(|| format!("Cannot write {}", path.display()))?;
+            eprintln!("Profile written to {}", path.display());
+        }
+        None => print!("{profile}"),
+    }
+    Ok(())
+}
+
+fn import_from(config_path: &Path, profile_path: &Path) -> Result<()> {
+    let current = load_existing(config_path)?;
+    let profile = std::fs::read_to_string(profile_path)
+        .with_context(|| format!("Cannot read {}", profile_path.display()))?;
+    let (config, ignored) = import_profile(&current, &profile, |name| std::env::var(name).ok())?;
+    for key in ignored {
+        eprintln!("Ignoring `{key}`: it stays as configured on this server");
+    }
+
+    let backup = config_path.with_extension("toml.bak");
+    std::fs::copy(config_path, &backup)
+        .with_context(|| format!("Cannot back up {}", config_path.display()))?;
+    config.save(config_path)?;
+    println!(
+        "Configuration updated: {} (previous version in {}). Restart the server to apply it.",
+        config_path.display(),
+        backup.display()
+    );
+    Ok(())
+}
+
+/// The profile of `config` as TOML, with a header explaining what it holds.
+pub(crate) fn export_profile(config: &ServerConfig) -> Result<String> {
+    let Value::Table(mut table) = Value::try_from(config)? else {
+        bail!("Configuration did not serialize to a table");
+    };
+    for key in LOCAL_KEYS {
+        table.remove(*key);
+    }
+    for (section, key, var) in SECRETS {
+        if let Some(Value::Table(section)) = table.get_mut(*section)
+            && section
+                .get(*key)
+                .and_then(Value::as_str)
+                .is_some_and(|secret| !secret.is_empty())
+        {
+            section.insert((*key).to_string(), Value::String(format!("${{{var}}}")));
+        }
+    }
+
+    Ok(format!(
+        "# ParkHub configuration profile, exported from \"{}\" by ParkHub Server v{}.\n\
+         # Apply with `parkhub-server config import FILE`. Values written as ${{VAR}}\n\
+         # are read from the environment on import. Server name, admin account,\n\
+         # database encryption and data directory stay as set up on each server.\n\n{}",
+        config.server_name,
+        env!("CARGO_PKG_VERSION"),
+        toml::to_string_pretty(&table)?
+    ))
+}
+
+/// Merge `profile` into `current`. `${VAR}` references are looked up with
+/// `env`; an unset variable keeps the current value if there is one.
+/// Returns the new configuration and the local settings the profile tried
+/// to change, which are left alone.
+pub(crate) fn import_profile(
+    current: &ServerConfig,
+    profile: &str,
+    env: impl Fn(&str) -> Option<String>,
+) -> Result<(ServerConfig, Vec<String>)> {
+    let mut profile: Table = toml::from_str(profile).context("The profile is not valid TOML")?;
+    let Value::Table(mut merged) = Value::try_from(current)? else {
+        bail!("Configuration did not serialize to a table");
+    };
+
+    let mut ignored = Vec::new();
+    for key in LOCAL_KEYS {
+        if profile.remove(*key).is_some() {
+            ignored.push((*key).to_string());
+        }
+    }
+    if let Some(unknown) = profile.keys().find(|key| !merged.contains_key(*key)) {
+        bail!("Unknown setting `{unknown}` in the profile");
+    }
+    resolve_references(&mut profile, &merged, &env, "")?;
+    merge_tables(&mut merged, profile);
+
+    let mut config: ServerConfig = Value::Table(merged)
+        .try_into()
+        .context("The profile has a setting of the wrong type")?;
+    config
+        .encryption_passphrase
+        .clone_from(&current.encryption_passphrase);
+    Ok((config, ignored))
+}
+
+/// Replace `${VAR}` strings in `profile` with the variable's value, or the
+/// non-empty value at the same place in `current` when it is unset.
+fn resolve_references(
+    profile: &mut Table,
+    current: &Table,
+    env: &impl Fn(&str) -> Option<String>,
+    path: &str,
+) -> Result<()> {
+    for (key, value) in profile.iter_mut() {
+        let key_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        match value {
+            Value::Table(table) => {
+                let empty = Table::new();
+                let current = current.get(key).and_then(Value::as_table).unwrap_or(&empty);
+                resolve_references(table, current, env, &key_path)?;
+            }
+            Value::String(text) => {
+                let Some(var) = text
+                    .strip_prefix("${")
+                    .and_then(|rest| rest.strip_suffix('}'))
+                else {
+                    continue;
+                };
+                let kept = current
+                    .get(key)
+                    .and_then(Value::as_str)
+                    .filter(|value| !value.is_empty());
+                match (env(var), kept) {
+                    (Some(secret), _) => *text = secret,
+                    (None, Some(kept)) => *text = kept.to_string(),
+                    (None, None) => bail!("`{key_path}` needs the environment variable {var}"),
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Copy `overlay` into `base`, descending into tables present in both.
+fn merge_tables(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base)), Value::Table(overlay)) => merge_tables(base, overlay),
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
@@ -0,0 +1,451 @@
+//! `parkhub-server doctor` — offline self-test for support tickets.
+//!
+//! Runs a fixed list of environment checks without starting the server and
+//! prints a pass/fail report that self-hosters can paste into an issue.
+//! Nothing is modified on disk apart from a short-lived probe file in the
+//! data directory. Exits 1 when any check fails.
+
+use std::path::Path;
+use std::time::Duration;
+
+use crate::config::ServerConfig;
+use crate::db::{Database, DatabaseConfig};
+
+use super::cli::CliArgs;
+use super::health::perform_health_check;
+use super::paths::get_data_directory;
+
+/// Outcome of a single check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+    Skip,
+}
+
+impl CheckStatus {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Pass => "PASS",
+            Self::Warn => "WARN",
+            Self::Fail => "FAIL",
+            Self::Skip => "SKIP",
+        }
+    }
+}
+
+/// One line of the doctor report.
+#[derive(Debug, Clone)]
+pub(crate) struct CheckResult {
+    pub(crate) name: &'static str,
+    pub(crate) status: CheckStatus,
+    pub(crate) detail: String,
+}
+
+impl CheckResult {
+    fn new(name: &'static str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Timeout for network probes (SMTP).
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Run every check and print the report. Returns the process exit code.
+pub(crate) async fn run_doctor(cli: &CliArgs) -> i32 {
+    println!(
+        "ParkHub Server v{} ({}, protocol {}) — doctor",
+        crate::build_info::VERSION,
+        crate::build_info::GIT_HASH,
+        parkhub_common::PROTOCOL_VERSION
+    );
+    println!();
+
+    let mut results = Vec::new();
+
+    let data_dir = match cli.data_dir.clone() {
+        Some(dir) => Ok(dir),
+        None => get_data_directory(None),
+    };
+    let data_dir = match data_dir {
+        Ok(dir) => {
+            results.push(check_data_dir(&dir));
+            Some(dir)
+        }
+        Err(e) => {
+            results.push(CheckResult::new(
+                "Data directory",
+                CheckStatus::Fail,
+                format!("cannot resolve data directory: {e}"),
+            ));
+            None
+        }
+    };
+
+    let (config_result, mut config) = match &data_dir {
+        Some(dir) => check_config(&dir.join("config.toml")),
+        None => (
+            CheckResult::new("Configuration", CheckStatus::Skip, "no data directory"),
+            ServerConfig::default(),
+        ),
+    };
+    results.push(config_result);
+
+    // Same precedence as a normal start: --port, then PORT, then config.toml.
+    if let Some(port) = cli
+        .port
+        .or_else(|| std::env::var("PORT").ok().and_then(|p| p.parse().ok()))
+    {
+        config.port = port;
+    }
+
+    results.push(check_port(config.port));
+    if let Some(dir) = &data_dir {
+        results.push(check_tls(&config, dir));
+    }
+    results.push(check_smtp().await);
+    results.push(check_mdns(&config));
+    if let Some(dir) = &data_dir {
+        results.push(check_database(&config, dir).await);
+    }
+
+    print_report(&results);
+    exit_code(&results)
+}
+
+/// Print the report table and summary line.
+fn print_report(results: &[CheckResult]) {
+    let width = results.iter().map(|r| r.name.len()).max().unwrap_or(0);
+    for r in results {
+        println!("  [{}] {:<width$}  {}", r.status.label(), r.name, r.detail);
+    }
+    let (pass, warn, fail) = summarize(results);
+    println!();
+    println!("{pass} passed, {warn} warnings, {fail} failed");
+}
+
+/// Count (passed, warnings, failed). Skipped checks are not counted.
+pub(crate) fn summarize(results: &[CheckResult]) -> (usize, usize, usize) {
+    let count = |s| results.iter().filter(|r| r.status == s).count();
+    (
+        count(CheckStatus::Pass),
+        count(CheckStatus::Warn),
+        count(CheckStatus::Fail),
+    )
+}
+
+/// `1` if any check failed, `0` otherwise.
+pub(crate) fn exit_code(results: &[CheckResult]) -> i32 {
+    i32::from(results.iter().any(|r| r.status == CheckStatus::Fail))
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Checks
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The data directory must exist (or be creatable) and be writable.
+pub(crate) fn check_data_dir(dir: &Path) -> CheckResult {
+    const NAME: &str = "Data directory";
+
+    if !dir.exists() {
+        return CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            format!(
+                "{} does not exist yet — created on first start",
+                dir.display()
+            ),
+        );
+    }
+    if !dir.is_dir() {
+        return CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{} is not a directory", dir.display()),
+        );
+    }
+
+    let probe = dir.join(".parkhub-doctor-probe");
+    if let Err(e) = std::fs::write(&probe, b"ok") {
+        return CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{} is not writable: {e}", dir.display()),
+        );
+    }
+    let _ = std::fs::remove_file(&probe);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(meta) = std::fs::metadata(dir)
+            && meta.permissions().mode() & 0o002 != 0
+        {
+            return CheckResult::new(
+                NAME,
+                CheckStatus::Warn,
+                format!("{} is world-writable", dir.display()),
+            );
+        }
+    }
+
+    CheckResult::new(
+        NAME,
+        CheckStatus::Pass,
+        format!("{} is writable", dir.display()),
+    )
+}
+
+/// Load `config.toml` and sanity-check it. Returns the config to use for the
+/// remaining checks (defaults when missing or broken).
+pub(crate) fn check_config(path: &Path) -> (CheckResult, ServerConfig) {
+    const NAME: &str = "Configuration";
+
+    if !path.exists() {
+        return (
+            CheckResult::new(
+                NAME,
+                CheckStatus::Warn,
+                "no config.toml — defaults apply until setup runs",
+            ),
+            ServerConfig::default(),
+        );
+    }
+
+    let config = match ServerConfig::load(path) {
+        Ok(c) => c,
+        Err(e) => {
+            return (
+                CheckResult::new(
+                    NAME,
+                    CheckStatus::Fail,
+                    format!("{} is invalid: {e}", path.display()),
+                ),
+                ServerConfig::default(),
+            );
+        }
+    };
+
+    let mut problems = Vec::new();
+    if config.port == 0 {
+        problems.push("port is 0");
+    }
+    if config.admin_password_hash.is_empty() {
+        problems.push("admin password hash is empty");
+    }
+
+    let result = if !problems.is_empty() {
+        CheckResult::new(NAME, CheckStatus::Fail, problems.join("; "))
+    } else if config.encryption_enabled && std::env::var("PARKHUB_DB_PASSPHRASE").is_err() {
+        // The GUI prompts for it; headless starts refuse to run without it.
+        CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            "encryption is enabled but PARKHUB_DB_PASSPHRASE is not set",
+        )
+    } else {
+        CheckResult::new(
+            NAME,
+            CheckStatus::Pass,
+            format!("{} loaded", path.display()),
+        )
+    };
+    (result, config)
+}
+
+/// The listen port must be free — or already served by a healthy ParkHub.
+pub(crate) fn check_port(port: u16) -> CheckResult {
+    const NAME: &str = "Port";
+
+    match std::net::TcpListener::bind(("0.0.0.0", port)) {
+        Ok(_) => CheckResult::new(NAME, CheckStatus::Pass, format!("{port} is available")),
+        Err(_) if perform_health_check(port) == 0 => CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            format!("{port} is in use by a running ParkHub server"),
+        ),
+        Err(e) => CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{port} is not available: {e}"),
+        ),
+    }
+}
+
+/// Certificate and key must both be present and PEM-encoded, with the key
+/// readable only by its owner.
+pub(crate) fn check_tls(config: &ServerConfig, data_dir: &Path) -> CheckResult {
+    const NAME: &str = "TLS";
+
+    if !config.enable_tls {
+        return CheckResult::new(NAME, CheckStatus::Skip, "TLS disabled");
+    }
+
+    let cert_path = data_dir.join("server.crt");
+    let key_path = data_dir.join("server.key");
+    match (cert_path.exists(), key_path.exists()) {
+        (false, false) => {
+            return CheckResult::new(
+                NAME,
+                CheckStatus::Warn,
+                "no certificate yet — a self-signed one is generated on first start",
+            );
+        }
+        (true, false) | (false, true) => {
+            return CheckResult::new(
+                NAME,
+                CheckStatus::Fail,
+                "only one of server.crt / server.key exists",
+            );
+        }
+        (true, true) => {}
+    }
+
+    let cert = std::fs::read_to_string(&cert_path).unwrap_or_default();
+    let key = std::fs::read_to_string(&key_path).unwrap_or_default();
+    if !cert.contains("BEGIN CERTIFICATE") {
+        return CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{} is not a PEM certificate", cert_path.display()),
+        );
+    }
+    if !key.contains("PRIVATE KEY") {
+        return CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{} is not a PEM private key", key_path.display()),
+        );
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(meta) = std::fs::metadata(&key_path)
+            && meta.permissions().mode() & 0o077 != 0
+        {
+            return CheckResult::new(
+                NAME,
+                CheckStatus::Warn,
+                format!("{} should be mode 0600", key_path.display()),
+            );
+        }
+    }
+
+    CheckResult::new(NAME, CheckStatus::Pass, "certificate and key present")
+}
+
+/// Connect to the configured SMTP relay.
+#[cfg(feature = "mod-email")]
+async fn check_smtp() -> CheckResult {
+    const NAME: &str = "SMTP";
+
+    let Some(config) = crate::email::SmtpConfig::from_env() else {
+        return CheckResult::new(NAME, CheckStatus::Skip, "SMTP_HOST not set");
+    };
+    let target = format!("{}:{}", config.host, config.port);
+    let transport = match crate::email::smtp_transport(&config) {
+        Ok(t) => t,
+        Err(e) => return CheckResult::new(NAME, CheckStatus::Fail, format!("{target}: {e}")),
+    };
+    match tokio::time::timeout(PROBE_TIMEOUT, transport.test_connection()).await {
+        Ok(Ok(true)) => CheckResult::new(NAME, CheckStatus::Pass, format!("{target} reachable")),
+        Ok(Ok(false)) => CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("{target} refused the connection"),
+        ),
+        Ok(Err(e)) => CheckResult::new(NAME, CheckStatus::Fail, format!("{target}: {e}")),
+        Err(_) => CheckResult::new(NAME, CheckStatus::Fail, format!("{target}: timed out")),
+    }
+}
+
+#[cfg(not(feature = "mod-email"))]
+#[allow(clippy::unused_async)]
+async fn check_smtp() -> CheckResult {
+    CheckResult::new("SMTP", CheckStatus::Skip, "email module not compiled in")
+}
+
+/// Start and stop an mDNS daemon to prove multicast sockets can be opened.
+fn check_mdns(config: &ServerConfig) -> CheckResult {
+    const NAME: &str = "mDNS";
+
+    if !config.enable_mdns {
+        return CheckResult::new(NAME, CheckStatus::Skip, "mDNS disabled");
+    }
+    match mdns_sd::ServiceDaemon::new() {
+        Ok(daemon) => {
+            let _ = daemon.shutdown();
+            CheckResult::new(NAME, CheckStatus::Pass, "multicast daemon started")
+        }
+        Err(e) => CheckResult::new(
+            NAME,
+            CheckStatus::Fail,
+            format!("cannot start mDNS daemon: {e}"),
+        ),
+    }
+}
+
+/// Open the existing database (never creating one) and read its stats.
+async fn check_database(config: &ServerConfig, data_dir: &Path) -> CheckResult {
+    const NAME: &str = "Database";
+
+    if !data_dir.join("parkhub.redb").exists() {
+        return CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            "no database yet — created on first start",
+        );
+    }
+
+    let passphrase = std::env::var("PARKHUB_DB_PASSPHRASE").ok();
+    if config.encryption_enabled && passphrase.is_none() {
+        return CheckResult::new(
+            NAME,
+            CheckStatus::Skip,
+            "encrypted and PARKHUB_DB_PASSPHRASE not set",
+        );
+    }
+
+    let db_config = DatabaseConfig {
+        path: data_dir.to_path_buf(),
+        encryption_enabled: config.encryption_enabled,
+        passphrase,
+        create_if_missing: false,
+    };
+    let db = match Database::open(&db_config) {
+        Ok(db) => db,
+        Err(e) => {
+            return CheckResult::new(
+                NAME,
+                CheckStatus::Fail,
+                format!("cannot open (is the server running?): {e:#}"),
+            );
+        }
+    };
+    match db.stats().await {
+        Ok(stats) if stats.dummy_users > 0 => CheckResult::new(
+            NAME,
+            CheckStatus::Warn,
+            format!(
+                "{} dummy users with the setup password remain — run \
+                 `parkhub-server dummy-users purge`",
+                stats.dummy_users
+            ),
+        ),
+        Ok(stats) => CheckResult::new(
+            NAME,
+            CheckStatus::Pass,
+            format!(
+                "{} users, {} lots, {} slots, {} bookings",
+                stats.users, stats.parking_lots, stats.slots, stats.bookings
+            ),
+        ),
+        Err(e) => CheckResult::new(NAME, CheckStatus::Fail, format!("stats failed: {e:#}")),
+    }
+}
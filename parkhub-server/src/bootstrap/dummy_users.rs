@@ -0,0 +1,100 @@
+//! `parkhub-server dummy-users [list|purge]` — offline removal of the test
+//! accounts generated during setup.
+//!
+//! Opens the database directly, so the server must be stopped first (redb
+//! allows one process at a time). Exits 1 when the database cannot be
+//! opened or a purge fails.
+
+use std::path::Path;
+
+use parkhub_common::models::User;
+
+use crate::config::ServerConfig;
+use crate::db::{Database, DatabaseConfig};
+
+use super::cli::{CliArgs, DummyUsersAction};
+use super::paths::get_data_directory;
+
+/// Run the `dummy-users` command. Returns the process exit code.
+pub(crate) async fn run_dummy_users(cli: &CliArgs, action: DummyUsersAction) -> i32 {
+    let data_dir = match cli.data_dir.clone() {
+        Some(dir) => dir,
+        None => match get_data_directory(None) {
+            Ok(dir) => dir,
+            Err(e) => {
+                eprintln!("Cannot resolve data directory: {e}");
+                return 1;
+            }
+        },
+    };
+    let db = match open_database(&data_dir) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!(
+                "Cannot open database in {} (is the server running?): {e:#}",
+                data_dir.display()
+            );
+            return 1;
+        }
+    };
+
+    match action {
+        DummyUsersAction::List => match db.list_dummy_users().await {
+            Ok(users) if users.is_empty() => {
+                println!("No dummy users left.");
+                0
+            }
+            Ok(users) => {
+                print_users(&users);
+                println!();
+                println!(
+                    "{} dummy user(s) share the generated setup password. \
+                     Run `parkhub-server dummy-users purge` before going live.",
+                    users.len()
+                );
+                0
+            }
+            Err(e) => {
+                eprintln!("Failed to list dummy users: {e:#}");
+                1
+            }
+        },
+        DummyUsersAction::Purge => match db.purge_dummy_users().await {
+            Ok(users) => {
+                print_users(&users);
+                println!("Deleted {} dummy user(s).", users.len());
+                0
+            }
+            Err(e) => {
+                eprintln!("Failed to purge dummy users: {e:#}");
+                1
+            }
+        },
+    }
+}
+
+/// Open the existing database in `data_dir` with the settings of its
+/// `config.toml`; the passphrase comes from `PARKHUB_DB_PASSPHRASE`.
+fn open_database(data_dir: &Path) -> anyhow::Result<Database> {
+    let config_path = data_dir.join("config.toml");
+    let config = if config_path.exists() {
+        ServerConfig::load(&config_path)?
+    } else {
+        ServerConfig::default()
+    };
+    let passphrase = std::env::var("PARKHUB_DB_PASSPHRASE")
+        .ok()
+        .or(config.encryption_passphrase);
+    Database::open(&DatabaseConfig {
+        path: data_dir.to_path_buf(),
+        encryption_enabled: config.encryption_enabled,
+        passphrase,
+        create_if_missing: false,
+    })
+}
+
+fn print_users(users: &[User]) {
+    for user in users {
+        println!("  {:<24} {:<32} {:?}", user.username, user.email, user.role);
+    }
+}
@@ -0,0 +1,245 @@
+//! Interactive first-run setup on a terminal (`parkhub-server --setup`).
+//!
+//! The console counterpart of the GUI setup wizard for headless installs:
+//! asks for the server name, admin credentials, TLS, mDNS and database
+//! encryption. The `validate_*` functions hold the rules both front ends
+//! apply. `--unattended` stays the non-interactive path for automation.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use anyhow::{Context, Result, bail};
+
+use crate::config::ServerConfig;
+
+use super::paths::hash_password;
+
+/// Minimum length of the database encryption passphrase, in characters.
+pub(crate) const MIN_PASSPHRASE_CHARS: usize = 8;
+
+pub(crate) fn validate_server_name(name: &str) -> Result<(), &'static str> {
+    if name.trim().is_empty() {
+        return Err("Server name cannot be empty");
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_admin_username(username: &str) -> Result<(), &'static str> {
+    if username.trim().is_empty() {
+        return Err("Admin username cannot be empty");
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_admin_password(password: &str) -> Result<(), &'static str> {
+    if password.is_empty() {
+        return Err("Admin password cannot be empty");
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_passphrase(passphrase: &str) -> Result<(), &'static str> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+        return Err("Encryption passphrase must be at least 8 characters");
+    }
+    Ok(())
+}
+
+/// Run the setup prompts on the process terminal.
+///
+/// Refuses to start when stdin is not a terminal, so a container that
+/// passes `--setup` by mistake fails instead of hanging on a prompt.
+pub(crate) fn run_interactive_setup() -> Result<ServerConfig> {
+    if !io::stdin().is_terminal() {
+        bail!(
+            "--setup needs an interactive terminal.\n\
+             Use --unattended (with PARKHUB_ADMIN_PASSWORD) for automated installs."
+        );
+    }
+    let default_name = hostname::get().map_or_else(
+        |_| "ParkHub Server".to_string(),
+        |h| h.to_string_lossy().to_string(),
+    );
+    let mut prompter = Prompter::new(io::stdin().lock(), io::stdout(), true);
+    prompt_setup(&mut prompter, &default_name)
+}
+
+/// Ask for the database encryption passphrase on the terminal, for
+/// headless starts of an encrypted install without `PARKHUB_DB_PASSPHRASE`.
+pub(crate) fn prompt_passphrase_tty() -> Result<String> {
+    let mut prompter = Prompter::new(io::stdin().lock(), io::stdout(), true);
+    prompter.ask_hidden("Database encryption passphrase")
+}
+
+/// Walk through the setup questions and build the resulting config.
+/// Fields that are not asked for keep their [`ServerConfig::default`] values.
+pub(crate) fn prompt_setup<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    default_name: &str,
+) -> Result<ServerConfig> {
+    prompter.say("ParkHub Server setup — press Enter to accept the [default].")?;
+
+    let server_name = prompter.ask("Server name", default_name, validate_server_name)?;
+    let admin_username = prompter.ask("Admin username", "admin", validate_admin_username)?;
+    let admin_password = prompter.ask_secret("Admin password", validate_admin_password)?;
+    let enable_tls = prompter.confirm(
+        "Enable HTTPS (a self-signed certificate is generated unless server.crt/server.key exist)",
+        true,
+    )?;
+    let enable_mdns = prompter.confirm("Announce the server on the local network (mDNS)", false)?;
+    let encryption_enabled = prompter.confirm("Encrypt the database at rest", true)?;
+    let encryption_passphrase = if encryption_enabled {
+        let passphrase = prompter.ask_secret("Encryption passphrase", validate_passphrase)?;
+        prompter.say(
+            "The passphrase is not stored. Set PARKHUB_DB_PASSPHRASE or enter it \
+             when the server starts.",
+        )?;
+        Some(passphrase)
+    } else {
+        None
+    };
+
+    Ok(ServerConfig {
+        server_name: server_name.trim().to_string(),
+        admin_username: admin_username.trim().to_string(),
+        admin_password_hash: hash_password(&admin_password)?,
+        enable_tls,
+        enable_mdns,
+        encryption_enabled,
+        encryption_passphrase,
+        ..ServerConfig::default()
+    })
+}
+
+/// Line-based question/answer loop over any reader and writer, so the
+/// setup flow can be driven from tests.
+pub(crate) struct Prompter<R, W> {
+    input: R,
+    output: W,
+    /// Turn terminal echo off while secrets are typed.
+    hide_secrets: bool,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    pub(crate) const fn new(input: R, output: W, hide_secrets: bool) -> Self {
+        Self {
+            input,
+            output,
+            hide_secrets,
+        }
+    }
+
+    fn say(&mut self, message: &str) -> Result<()> {
+        writeln!(self.output, "{message}")?;
+        Ok(())
+    }
+
+    fn read_line(&mut self) -> Result<String> {
+        self.output.flush()?;
+        let mut line = String::new();
+        let read = self
+            .input
+            .read_line(&mut line)
+            .context("Failed to read from terminal")?;
+        if read == 0 {
+            bail!("Setup aborted: input closed");
+        }
+        Ok(line.trim_end_matches(['\r', '\n']).to_string())
+    }
+
+    /// Ask until the answer (or `default` for an empty one) passes `validate`.
+    fn ask(
+        &mut self,
+        label: &str,
+        default: &str,
+        validate: fn(&str) -> Result<(), &'static str>,
+    ) -> Result<String> {
+        loop {
+            write!(self.output, "{label} [{default}]: ")?;
+            let answer = self.read_line()?;
+            let answer = if answer.trim().is_empty() {
+                default.to_string()
+            } else {
+                answer
+            };
+            match validate(&answer) {
+                Ok(()) => return Ok(answer),
+                Err(msg) => self.say(msg)?,
+            }
+        }
+    }
+
+    fn confirm(&mut self, label: &str, default: bool) -> Result<bool> {
+        let hint = if default { "Y/n" } else { "y/N" };
+        loop {
+            write!(self.output, "{label} [{hint}]: ")?;
+            match self.read_line()?.trim().to_ascii_lowercase().as_str() {
+                "" => return Ok(default),
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                _ => self.say("Please answer y or n")?,
+            }
+        }
+    }
+
+    /// Ask for a secret twice until it passes `validate` and both entries match.
+    fn ask_secret(
+        &mut self,
+        label: &str,
+        validate: fn(&str) -> Result<(), &'static str>,
+    ) -> Result<String> {
+        loop {
+            let secret = self.ask_hidden(label)?;
+            if let Err(msg) = validate(&secret) {
+                self.say(msg)?;
+                continue;
+            }
+            if self.ask_hidden(&format!("Confirm {}", label.to_lowercase()))? != secret {
+                self.say("Entries do not match")?;
+                continue;
+            }
+            return Ok(secret);
+        }
+    }
+
+    fn ask_hidden(&mut self, label: &str) -> Result<String> {
+        write!(self.output, "{label}: ")?;
+        if !self.hide_secrets {
+            return self.read_line();
+        }
+        let answer = {
+            let _echo = EchoOff::new();
+            self.read_line()
+        };
+        // The newline typed by the user was not echoed either
+        writeln!(self.output)?;
+        answer
+    }
+}
+
+/// Disables terminal echo for its lifetime via `stty`. Best effort: where
+/// `stty` is unavailable the secret is read with echo on.
+struct EchoOff;
+
+impl EchoOff {
+    fn new() -> Self {
+        set_echo(false);
+        Self
+    }
+}
+
+impl Drop for EchoOff {
+    fn drop(&mut self) {
+        set_echo(true);
+    }
+}
+
+#[cfg(unix)]
+fn set_echo(enabled: bool) {
+    let _ = std::process::Command::new("stty")
+        .arg(if enabled { "echo" } else { "-echo" })
+        .stdin(std::process::Stdio::inherit())
+        .status();
+}
+
+#[cfg(not(unix))]
+const fn set_echo(_enabled: bool) {}
@@ -2,14 +2,20 @@
 //!
 //! This module hosts the ancillary functions invoked by `async fn main()`
 //! at startup time — CLI parsing, data-directory resolution, password
-//! hashing, first-run seeding, the standalone health-check probe,
-//! revocation-store wiring, and the GUI status / setup-wizard windows.
+//! hashing, first-run seeding, the standalone health-check probe, the
+//! `doctor` self-test, the `dummy-users` and `config` commands, the
+//! terminal setup prompts, revocation-store wiring, and the GUI status /
+//! setup-wizard windows.
 //!
 //! `main.rs` keeps the top-level `#[tokio::main]` entry point plus the
 //! shared [`crate::AppState`] struct; everything else lives here to keep
 //! the binary entry point focused on wiring.
 
 pub(crate) mod cli;
+pub(crate) mod config_profile;
+pub(crate) mod doctor;
+pub(crate) mod dummy_users;
+pub(crate) mod headless_setup;
 pub(crate) mod health;
 pub(crate) mod paths;
 pub(crate) mod revocation;
@@ -47,6 +47,7 @@ pub(crate) async fn create_admin_user(db: &Database, config: &ServerConfig) -> R
         cost_center: None,
         department: Some("IT".to_string()),
         settings: None,
+        must_change_password: false,
     };
 
     db.save_user(&admin_user).await?;
@@ -58,6 +59,15 @@ pub(crate) async fn create_admin_user(db: &Database, config: &ServerConfig) -> R
     Ok(())
 }
 
+/// Make `username` pick a new password on their next login.
+pub(crate) async fn require_password_change(db: &Database, username: &str) -> Result<()> {
+    if let Some(mut user) = db.get_user_by_username(username).await? {
+        user.must_change_password = true;
+        db.save_user(&user).await?;
+    }
+    Ok(())
+}
+
 /// Username generation styles for dummy users
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UsernameStyle {
@@ -248,6 +258,8 @@ pub(crate) async fn generate_dummy_users(
                     cost_center: None,
                     department: None,
                     settings: None,
+                    // Everyone shares the seeded password
+                    must_change_password: true,
                 }
             })
             .collect()
@@ -256,6 +268,8 @@ pub(crate) async fn generate_dummy_users(
     for user in &users {
         db.save_user(user).await?;
     }
+    let ids: Vec<Uuid> = users.iter().map(|user| user.id).collect();
+    db.mark_dummy_users(&ids).await?;
 
     info!("Created 50 dummy users successfully");
     info!("Default login: any username with password '{default_password}'",);
@@ -695,6 +709,7 @@ pub(crate) async fn seed_demo_data(db: &Database) -> Result<()> {
                     cost_center: None,
                     department: None,
                     settings: None,
+                    must_change_password: false,
                 }
             })
             .collect()
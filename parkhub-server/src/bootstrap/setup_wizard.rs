@@ -10,9 +10,12 @@
 use anyhow::{Context, Result};
 use slint::ComponentHandle;
 
-use crate::config::ServerConfig;
+use crate::config::{OidcConfig, ServerConfig, SessionLimitPolicy};
 use crate::{PassphraseDialog, SetupWizard};
 
+use super::headless_setup::{
+    validate_admin_password, validate_admin_username, validate_passphrase, validate_server_name,
+};
 use super::paths::{get_local_ip, hash_password};
 
 pub(crate) fn run_setup_wizard() -> Result<ServerConfig> {
@@ -53,17 +56,12 @@ pub(crate) fn run_setup_wizard() -> Result<ServerConfig> {
         let enable_tls = ui.get_enable_tls();
         let enable_mdns = ui.get_enable_mdns();
 
-        // Validate inputs
-        if server_name.trim().is_empty() {
-            eprintln!("Server name cannot be empty");
-            return;
-        }
-        if admin_username.trim().is_empty() {
-            eprintln!("Admin username cannot be empty");
-            return;
-        }
-        if admin_password.is_empty() {
-            eprintln!("Admin password cannot be empty");
+        // Validate inputs (same rules as the terminal setup)
+        if let Err(msg) = validate_server_name(&server_name)
+            .and_then(|()| validate_admin_username(&admin_username))
+            .and_then(|()| validate_admin_password(&admin_password))
+        {
+            eprintln!("{msg}");
             return;
         }
 
@@ -81,8 +79,8 @@ pub(crate) fn run_setup_wizard() -> Result<ServerConfig> {
         let encryption_passphrase = if enable_encryption {
             let passphrase = ui.get_encryption_passphrase().to_string();
             // Additional validation on Rust side
-            if passphrase.len() < 8 {
-                eprintln!("Encryption passphrase must be at least 8 characters");
+            if let Err(msg) = validate_passphrase(&passphrase) {
+                eprintln!("{msg}");
                 return;
             }
             Some(passphrase)
@@ -114,11 +112,15 @@ pub(crate) fn run_setup_wizard() -> Result<ServerConfig> {
             username_style,
             license_plate_display,
             session_timeout_minutes: 60, // 1 hour default
+            session_lifetime_hours: 24,
+            booking_expiry_warning_minutes: 15,
             allow_self_registration: false,
             require_email_verification: false,
             max_concurrent_sessions: 0, // Unlimited
+            session_limit_policy: SessionLimitPolicy::EvictOldest,
             auto_backup_enabled: true,
             backup_retention_count: 7,
+            backup_schedule: "0 0 3 * * *".to_string(),
             audit_logging_enabled: true,
             default_language: "en".to_string(),
             organization_name: String::new(),
@@ -126,6 +128,7 @@ pub(crate) fn run_setup_wizard() -> Result<ServerConfig> {
             theme_mode: 0,
             font_scale: 1.0,
             reduce_motion: false,
+            oidc: OidcConfig::default(),
         };
 
         *result_clone.borrow_mut() = Some(config);
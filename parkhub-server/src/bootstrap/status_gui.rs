@@ -16,6 +16,7 @@ use tracing::{info, warn};
 
 use crate::AppState;
 use crate::config::ServerConfig;
+use crate::metrics;
 use crate::{ServerStatus, ThemeSettings};
 
 use super::paths::get_local_ip;
@@ -174,9 +175,10 @@ pub(crate) async fn run_status_gui(
         }
     };
 
-    // Set up periodic stats update
+    // Set up periodic stats and live metrics update
     let ui_weak = ui.as_weak();
-    let state_for_timer = state;
+    let state_for_timer = state.clone();
+    let history = Arc::new(std::sync::Mutex::new(metrics::MetricsHistory::default()));
     let timer = slint::Timer::default();
     timer.start(
         slint::TimerMode::Repeated,
@@ -184,27 +186,83 @@ pub(crate) async fn run_status_gui(
         move || {
             let ui_weak_clone = ui_weak.clone();
             let state_clone = state_for_timer.clone();
+            let history = history.clone();
             // Spawn async stats query without blocking
             tokio::spawn(async move {
                 if let Ok(state) = state_clone.try_read()
                     && let Ok(stats) = state.db.stats().await
                 {
+                    metrics::record_active_sessions(stats.sessions);
+                    let backup = crate::auto_backup::status(&state.db, &state.config);
+                    let last_backup = match backup.last_backup_at {
+                        Some(at) if backup.enabled => format!(
+                            "{} ({} kept)",
+                            at.format("%Y-%m-%d %H:%M UTC"),
+                            backup.backup_count
+                        ),
+                        None if backup.enabled => "None yet".to_string(),
+                        _ => "Off".to_string(),
+                    };
                     // Update UI from event loop thread
+                    let ui_weak_stats = ui_weak_clone.clone();
                     let _ = slint::invoke_from_event_loop(move || {
                         #[allow(clippy::cast_possible_truncation)]
-                        if let Some(ui) = ui_weak_clone.upgrade() {
+                        if let Some(ui) = ui_weak_stats.upgrade() {
                             ui.set_user_count(stats.users as i32);
                             ui.set_booking_count(stats.bookings as i32);
                             ui.set_parking_lot_count(stats.parking_lots as i32);
                             ui.set_slot_count(stats.slots as i32);
                             ui.set_session_count(stats.sessions as i32);
+                            ui.set_dummy_user_count(stats.dummy_users as i32);
+                            ui.set_last_backup(last_backup.into());
+                            ui.set_backup_failed(backup.last_failure_at.is_some());
                         }
                     });
                 }
+
+                let Ok(mut history) = history.lock() else {
+                    return;
+                };
+                let sample = history.push(std::time::Instant::now(), metrics::snapshot());
+                let paths = [
+                    metrics::sparkline_commands(&history.series(|s| s.request_rate)),
+                    metrics::sparkline_commands(&history.series(|s| s.error_rate)),
+                    metrics::sparkline_commands(&history.series(|s| s.p95_latency_ms)),
+                    metrics::sparkline_commands(&history.series(|s| s.active_sessions as f64)),
+                ];
+                drop(history);
+                let _ = slint::invoke_from_event_loop(move || {
+                    if let Some(ui) = ui_weak_clone.upgrade() {
+                        let [requests, errors, latency, sessions] = paths;
+                        ui.set_request_rate(format!("{:.1}/s", sample.request_rate).into());
+                        ui.set_error_rate(format!("{:.1}%", sample.error_rate).into());
+                        ui.set_p95_latency(format!("{:.0} ms", sample.p95_latency_ms).into());
+                        ui.set_active_sessions(sample.active_sessions.to_string().into());
+                        ui.set_request_rate_path(requests.into());
+                        ui.set_error_rate_path(errors.into());
+                        ui.set_p95_latency_path(latency.into());
+                        ui.set_active_sessions_path(sessions.into());
+                    }
+                });
             });
         },
     );
 
+    // Usage panel: bookings per day and current occupancy, once a minute
+    #[cfg(feature = "mod-analytics")]
+    let _usage_timer = {
+        refresh_usage_panel(ui.as_weak(), state.clone());
+        let ui_weak_usage = ui.as_weak();
+        let state_for_usage = state.clone();
+        let usage_timer = slint::Timer::default();
+        usage_timer.start(
+            slint::TimerMode::Repeated,
+            std::time::Duration::from_secs(60),
+            move || refresh_usage_panel(ui_weak_usage.clone(), state_for_usage.clone()),
+        );
+        usage_timer
+    };
+
     // Handle minimize to tray - minimize window (tray icon allows restore)
     let ui_weak_tray = ui.as_weak();
     ui.on_minimize_to_tray(move || {
@@ -320,6 +378,23 @@ pub(crate) async fn run_status_gui(
         }
     });
 
+    // Usage data panel: load the report preview, then show the panel
+    let ui_weak_telemetry = ui.as_weak();
+    let state_for_telemetry = state.clone();
+    ui.on_open_telemetry(move || {
+        refresh_telemetry_panel(ui_weak_telemetry.clone(), state_for_telemetry.clone(), None);
+    });
+
+    let ui_weak_telemetry_toggle = ui.as_weak();
+    let state_for_telemetry_toggle = state;
+    ui.on_set_telemetry_enabled(move |enabled| {
+        refresh_telemetry_panel(
+            ui_weak_telemetry_toggle.clone(),
+            state_for_telemetry_toggle.clone(),
+            Some(enabled),
+        );
+    });
+
     // Intercept window close button (X)
     let ui_weak_window_close = ui.as_weak();
     ui.window().on_close_requested(move || {
@@ -343,6 +418,96 @@ pub(crate) async fn run_status_gui(
     Ok(())
 }
 
+/// Days shown in the bookings-per-day chart of the usage panel.
+#[cfg(feature = "mod-analytics")]
+const USAGE_CHART_DAYS: i64 = 14;
+
+/// Push bookings per day and the current occupancy across all lots into the
+/// usage panel of the status window.
+#[cfg(feature = "mod-analytics")]
+fn refresh_usage_panel(ui_weak: slint::Weak<ServerStatus>, state: Arc<RwLock<AppState>>) {
+    tokio::spawn(async move {
+        let db = state.read().await.db.clone();
+        let (bookings, lots) = match (db.list_bookings().await, db.list_parking_lots().await) {
+            (Ok(bookings), Ok(lots)) => (bookings, lots),
+            (Err(e), _) | (_, Err(e)) => {
+                warn!("Failed to load usage statistics: {}", e);
+                return;
+            }
+        };
+        let now = chrono::Utc::now();
+
+        let daily: Vec<f64> =
+            crate::api::analytics::daily_booking_counts(&bookings, USAGE_CHART_DAYS, now)
+                .iter()
+                .map(|d| d.value)
+                .collect();
+        let total: f64 = daily.iter().sum();
+        let today = daily.last().copied().unwrap_or_default();
+        let chart = metrics::bar_chart_commands(&daily);
+
+        let (occupied, capacity) = lots.iter().fold((0, 0), |(occupied, capacity), lot| {
+            let slots = lot.total_slots.max(0);
+            let held = crate::api::misc::occupied_slots(&bookings, lot.id, now).min(slots);
+            (occupied + held, capacity + slots)
+        });
+        let fraction = if capacity > 0 {
+            f64::from(occupied) / f64::from(capacity)
+        } else {
+            0.0
+        };
+
+        let _ = slint::invoke_from_event_loop(move || {
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.set_usage_available(true);
+                ui.set_daily_bookings_path(chart.into());
+                ui.set_daily_bookings_total(format!("{total:.0}").into());
+                ui.set_bookings_today(format!("{today:.0} today").into());
+                ui.set_occupancy_now(
+                    format!("{:.0}% ({occupied} of {capacity} slots)", fraction * 100.0).into(),
+                );
+                #[allow(clippy::cast_possible_truncation)]
+                ui.set_occupancy_fraction(fraction as f32);
+            }
+        });
+    });
+}
+
+/// Optionally persist a new telemetry opt-in choice, then push the current
+/// settings and report preview into the status window and show the panel.
+fn refresh_telemetry_panel(
+    ui_weak: slint::Weak<ServerStatus>,
+    state: Arc<RwLock<AppState>>,
+    set_enabled: Option<bool>,
+) {
+    tokio::spawn(async move {
+        let db = state.read().await.db.clone();
+        let mut settings = crate::telemetry::load_settings(&db).await;
+        if let Some(enabled) = set_enabled {
+            settings.enabled = enabled;
+            if let Err(e) = crate::telemetry::save_settings(&db, &settings).await {
+                warn!("Failed to save telemetry settings: {}", e);
+            } else {
+                info!(
+                    "Usage telemetry {} from status window",
+                    if enabled { "enabled" } else { "disabled" }
+                );
+            }
+        }
+        let endpoint = settings.effective_endpoint().unwrap_or_default();
+        let preview = crate::telemetry::preview_json(&db).await;
+
+        let _ = slint::invoke_from_event_loop(move || {
+            if let Some(ui) = ui_weak.upgrade() {
+                ui.set_telemetry_enabled(settings.enabled);
+                ui.set_telemetry_endpoint(endpoint.into());
+                ui.set_telemetry_preview(preview.into());
+                ui.set_show_telemetry_panel(true);
+            }
+        });
+    });
+}
+
 /// Create icon data for the system tray (32x32 RGBA)
 /// Creates a professional parking icon with a blue rounded square and white "P"
 #[cfg(all(feature = "gui", windows))]
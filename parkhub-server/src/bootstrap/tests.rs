@@ -1,13 +1,19 @@
-//! Tests for CLI arg parsing, the standalone health-check probe, and
-//! the demo-mode seed path.
+//! Tests for CLI arg parsing, the standalone health-check probe, the
+//! terminal setup prompts, configuration profiles, and the demo-mode seed
+//! path.
 
 #![cfg(test)]
 
 use std::path::PathBuf;
 
-use super::cli::CliArgs;
+use super::cli::{CliArgs, ConfigProfileAction, DummyUsersAction};
+use super::config_profile::{export_profile, import_profile};
+use super::doctor::{
+    CheckResult, CheckStatus, check_config, check_data_dir, check_tls, exit_code, summarize,
+};
+use super::headless_setup::{Prompter, prompt_setup, validate_passphrase};
 use super::health::perform_health_check;
-use super::seed::seed_demo_data;
+use super::seed::{UsernameStyle, generate_dummy_users, seed_demo_data};
 
 // ---------------------------------------------------------------------------
 // CliArgs parsing
@@ -21,10 +27,14 @@ fn parse_args(args: &[&str]) -> CliArgs {
         debug: false,
         headless: false,
         unattended: false,
+        setup: false,
         port: None,
         data_dir: None,
         version: false,
         health_check: false,
+        doctor: false,
+        dummy_users: None,
+        config_profile: None,
     };
     let mut i = 0;
     let owned: Vec<String> = args.iter().map(std::string::ToString::to_string).collect();
@@ -35,7 +45,34 @@ fn parse_args(args: &[&str]) -> CliArgs {
             "-d" | "--debug" => cli.debug = true,
             "--headless" => cli.headless = true,
             "--unattended" => cli.unattended = true,
+            "--setup" => cli.setup = true,
             "--health-check" => cli.health_check = true,
+            "doctor" | "--doctor" => cli.doctor = true,
+            "dummy-users" => {
+                cli.dummy_users = Some(DummyUsersAction::List);
+                match owned.get(i + 1).map(String::as_str) {
+                    Some("list") => i += 1,
+                    Some("purge") => {
+                        cli.dummy_users = Some(DummyUsersAction::Purge);
+                        i += 1;
+                    }
+                    _ => {}
+                }
+            }
+            "config" => {
+                let command = owned.get(i + 1).map(String::as_str);
+                let file = owned.get(i + 2).filter(|a| !a.starts_with('-'));
+                cli.config_profile = Some(match (command, file) {
+                    (Some("export"), file) => ConfigProfileAction::Export(file.map(PathBuf::from)),
+                    (Some("import"), Some(file)) => {
+                        ConfigProfileAction::Import(PathBuf::from(file))
+                    }
+                    _ => ConfigProfileAction::Usage,
+                });
+                if matches!(command, Some("export" | "import")) {
+                    i += 1 + usize::from(file.is_some());
+                }
+            }
             "-p" | "--port" => {
                 if i + 1 < owned.len() {
                     cli.port = owned[i + 1].parse().ok();
@@ -91,6 +128,284 @@ fn data_dir_flag_parsed() {
     assert_eq!(cli.data_dir, Some(PathBuf::from("/tmp/mydata")));
 }
 
+#[test]
+fn doctor_subcommand_parsed() {
+    let cli = parse_args(&["doctor", "--data-dir", "/tmp/mydata"]);
+    assert!(cli.doctor);
+    assert_eq!(cli.data_dir, Some(PathBuf::from("/tmp/mydata")));
+    assert!(!parse_args(&["--headless"]).doctor);
+}
+
+#[test]
+fn dummy_users_subcommand_parsed() {
+    assert_eq!(
+        parse_args(&["dummy-users"]).dummy_users,
+        Some(DummyUsersAction::List)
+    );
+    let cli = parse_args(&["dummy-users", "purge", "--data-dir", "/tmp/mydata"]);
+    assert_eq!(cli.dummy_users, Some(DummyUsersAction::Purge));
+    assert_eq!(cli.data_dir, Some(PathBuf::from("/tmp/mydata")));
+    assert_eq!(parse_args(&["--headless"]).dummy_users, None);
+}
+
+#[test]
+fn setup_flag_parsed() {
+    assert!(parse_args(&["--headless", "--setup"]).setup);
+    assert!(!parse_args(&["--unattended"]).setup);
+}
+
+#[test]
+fn config_subcommand_parsed() {
+    assert_eq!(
+        parse_args(&["config", "export"]).config_profile,
+        Some(ConfigProfileAction::Export(None))
+    );
+    let cli = parse_args(&[
+        "config",
+        "import",
+        "branch.toml",
+        "--data-dir",
+        "/tmp/mydata",
+    ]);
+    assert_eq!(
+        cli.config_profile,
+        Some(ConfigProfileAction::Import(PathBuf::from("branch.toml")))
+    );
+    assert_eq!(cli.data_dir, Some(PathBuf::from("/tmp/mydata")));
+    assert_eq!(
+        parse_args(&["config", "import"]).config_profile,
+        Some(ConfigProfileAction::Usage)
+    );
+    assert_eq!(parse_args(&["--headless"]).config_profile, None);
+}
+
+// ---------------------------------------------------------------------------
+// doctor — individual checks and report summary
+// ---------------------------------------------------------------------------
+
+#[test]
+fn doctor_data_dir_writable_passes() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = check_data_dir(dir.path());
+    assert_eq!(result.status, CheckStatus::Pass, "{}", result.detail);
+    assert!(!dir.path().join(".parkhub-doctor-probe").exists());
+}
+
+#[test]
+fn doctor_data_dir_missing_warns() {
+    let dir = tempfile::tempdir().unwrap();
+    let result = check_data_dir(&dir.path().join("nope"));
+    assert_eq!(result.status, CheckStatus::Warn);
+}
+
+#[test]
+fn doctor_config_invalid_toml_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    std::fs::write(&path, "port = \"not a number\"").unwrap();
+    let (result, _) = check_config(&path);
+    assert_eq!(result.status, CheckStatus::Fail);
+}
+
+#[test]
+fn doctor_config_missing_warns_with_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let (result, config) = check_config(&dir.path().join("config.toml"));
+    assert_eq!(result.status, CheckStatus::Warn);
+    assert_eq!(config.port, parkhub_common::DEFAULT_PORT);
+}
+
+#[test]
+fn doctor_tls_half_pair_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = crate::config::ServerConfig::default();
+    assert_eq!(check_tls(&config, dir.path()).status, CheckStatus::Warn);
+
+    std::fs::write(dir.path().join("server.crt"), "-----BEGIN CERTIFICATE-----").unwrap();
+    assert_eq!(check_tls(&config, dir.path()).status, CheckStatus::Fail);
+}
+
+#[test]
+fn doctor_summary_and_exit_code() {
+    let results = vec![
+        CheckResult {
+            name: "a",
+            status: CheckStatus::Pass,
+            detail: String::new(),
+        },
+        CheckResult {
+            name: "b",
+            status: CheckStatus::Skip,
+            detail: String::new(),
+        },
+        CheckResult {
+            name: "c",
+            status: CheckStatus::Warn,
+            detail: String::new(),
+        },
+    ];
+    assert_eq!(summarize(&results), (1, 1, 0));
+    assert_eq!(exit_code(&results), 0);
+
+    let mut failing = results;
+    failing.push(CheckResult {
+        name: "d",
+        status: CheckStatus::Fail,
+        detail: String::new(),
+    });
+    assert_eq!(exit_code(&failing), 1);
+}
+
+// ---------------------------------------------------------------------------
+// --setup — terminal prompts
+// ---------------------------------------------------------------------------
+
+fn run_setup(input: &str) -> (anyhow::Result<crate::config::ServerConfig>, String) {
+    let mut output = Vec::new();
+    let mut prompter = Prompter::new(std::io::Cursor::new(input), &mut output, false);
+    let result = prompt_setup(&mut prompter, "lot-box");
+    (result, String::from_utf8(output).unwrap())
+}
+
+#[tokio::test]
+async fn setup_defaults_and_answers() {
+    // name, username, password + confirm, TLS, mDNS, encryption, passphrase + confirm
+    let (config, _) = run_setup("\n\nhunter2\nhunter2\nn\ny\n\nlongpassphrase\nlongpassphrase\n");
+    let config = config.unwrap();
+    assert_eq!(config.server_name, "lot-box");
+    assert_eq!(config.admin_username, "admin");
+    assert!(crate::api::verify_password("hunter2", &config.admin_password_hash).await);
+    assert!(!config.enable_tls);
+    assert!(config.enable_mdns);
+    assert!(config.encryption_enabled);
+    assert_eq!(
+        config.encryption_passphrase.as_deref(),
+        Some("longpassphrase")
+    );
+}
+
+#[test]
+fn setup_reprompts_invalid_answers() {
+    let (config, output) = run_setup(
+        "Garage\nops\n\nsecret\nsecreT\nsecret\nsecret\nmaybe\n\n\ny\nshort\n12345678\n12345678\n",
+    );
+    let config = config.unwrap();
+    assert_eq!(config.server_name, "Garage");
+    assert_eq!(config.admin_username, "ops");
+    assert!(config.enable_tls);
+    assert!(!config.enable_mdns);
+    assert_eq!(config.encryption_passphrase.as_deref(), Some("12345678"));
+    assert!(output.contains("Admin password cannot be empty"));
+    assert!(output.contains("Entries do not match"));
+    assert!(output.contains("Please answer y or n"));
+    assert!(output.contains("at least 8 characters"));
+}
+
+#[test]
+fn setup_without_encryption_skips_passphrase() {
+    let (config, _) = run_setup("\n\npw\npw\n\n\nno\n");
+    let config = config.unwrap();
+    assert!(!config.encryption_enabled);
+    assert!(config.encryption_passphrase.is_none());
+}
+
+#[test]
+fn setup_aborts_on_closed_input() {
+    let (config, _) = run_setup("\nadmin\n");
+    assert!(config.is_err());
+}
+
+#[test]
+fn passphrase_length_counts_characters() {
+    assert!(validate_passphrase("1234567").is_err());
+    assert!(validate_passphrase("äöüäöüäö").is_ok());
+}
+
+// ---------------------------------------------------------------------------
+// config export / import — profiles shared between servers
+// ---------------------------------------------------------------------------
+
+fn branch_config(name: &str) -> crate::config::ServerConfig {
+    crate::config::ServerConfig {
+        server_name: name.to_string(),
+        admin_password_hash: format!("hash-of-{name}"),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn exported_profile_leaves_out_local_settings_and_secrets() {
+    let mut config = branch_config("head-office");
+    config.session_timeout_minutes = 45;
+    config.oidc.issuer_url = "https://login.example.com".to_string();
+    config.oidc.client_id = "parkhub".to_string();
+    config.oidc.client_secret = "s3cret".to_string();
+
+    let profile = export_profile(&config).unwrap();
+    assert!(profile.starts_with("# ParkHub configuration profile"));
+    assert!(profile.contains("session_timeout_minutes = 45"));
+    assert!(profile.contains("client_secret = \"${PARKHUB_OIDC_CLIENT_SECRET}\""));
+    for leaked in [
+        "s3cret",
+        "hash-of-head-office",
+        "server_name",
+        "encryption_enabled",
+    ] {
+        assert!(!profile.contains(leaked), "profile contains {leaked}");
+    }
+}
+
+#[test]
+fn imported_profile_merges_into_local_config() {
+    let mut source = branch_config("head-office");
+    source.session_timeout_minutes = 45;
+    source.oidc.issuer_url = "https://login.example.com".to_string();
+    source.oidc.client_secret = "s3cret".to_string();
+    let profile = export_profile(&source).unwrap();
+
+    let mut target = branch_config("branch-7");
+    target.encryption_enabled = false;
+    let env =
+        |name: &str| (name == "PARKHUB_OIDC_CLIENT_SECRET").then(|| "branch-secret".to_string());
+    let (config, ignored) = import_profile(&target, &profile, env).unwrap();
+    assert!(ignored.is_empty());
+    assert_eq!(config.session_timeout_minutes, 45);
+    assert_eq!(config.oidc.issuer_url, "https://login.example.com");
+    assert_eq!(config.oidc.client_secret, "branch-secret");
+    assert_eq!(config.server_name, "branch-7");
+    assert_eq!(config.admin_password_hash, "hash-of-branch-7");
+    assert!(!config.encryption_enabled);
+
+    // Without the variable, a secret the server already has is kept
+    target.oidc.client_secret = "kept".to_string();
+    let (config, _) = import_profile(&target, &profile, |_| None).unwrap();
+    assert_eq!(config.oidc.client_secret, "kept");
+    target.oidc.client_secret.clear();
+    let err = import_profile(&target, &profile, |_| None).unwrap_err();
+    assert!(err.to_string().contains("PARKHUB_OIDC_CLIENT_SECRET"));
+}
+
+#[test]
+fn imported_profile_rejects_unknown_and_skips_local_settings() {
+    let target = branch_config("branch-7");
+    let (config, ignored) = import_profile(
+        &target,
+        "server_name = \"head-office\"\nport = 9000\n[oidc]\nclient_id = \"parkhub\"\n",
+        |_| None,
+    )
+    .unwrap();
+    assert_eq!(ignored, ["server_name"]);
+    assert_eq!(config.server_name, "branch-7");
+    assert_eq!(config.port, 9000);
+    assert_eq!(config.oidc.client_id, "parkhub");
+    // Keys the profile leaves out keep their values
+    assert_eq!(config.oidc.groups_claim, "groups");
+
+    let err = import_profile(&target, "sesion_timeout_minutes = 5", |_| None).unwrap_err();
+    assert!(err.to_string().contains("sesion_timeout_minutes"));
+    assert!(import_profile(&target, "port = \"high\"", |_| None).is_err());
+}
+
 // ---------------------------------------------------------------------------
 // perform_health_check — connection-refused path exits with 1
 // ---------------------------------------------------------------------------
@@ -179,3 +494,40 @@ async fn seed_demo_data_is_idempotent_when_called_twice() {
     let lots_second = db.list_parking_lots().await.unwrap().len();
     assert!(lots_second >= lots_first, "lot count must not decrease");
 }
+
+// ---------------------------------------------------------------------------
+// dummy users — marked on generation, purged with their sessions
+// ---------------------------------------------------------------------------
+
+#[tokio::test]
+async fn generated_dummy_users_are_marked_and_purged() {
+    use crate::db::{Database, DatabaseConfig};
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let db_config = DatabaseConfig {
+        path: dir.path().to_path_buf(),
+        encryption_enabled: false,
+        passphrase: None,
+        create_if_missing: true,
+    };
+    let db = Database::open(&db_config).expect("open test db");
+
+    generate_dummy_users(&db, UsernameStyle::FirstDotLast)
+        .await
+        .expect("generate_dummy_users must succeed");
+    let dummies = db.list_dummy_users().await.unwrap();
+    assert_eq!(dummies.len(), 50);
+    assert_eq!(db.stats().await.unwrap().dummy_users, 50);
+    // They share one seeded password, so each must pick their own
+    assert!(dummies.iter().all(|u| u.must_change_password));
+
+    // Deleting one account drops its marker
+    db.delete_user(&dummies[0].id.to_string()).await.unwrap();
+    assert_eq!(db.list_dummy_users().await.unwrap().len(), 49);
+
+    let purged = db.purge_dummy_users().await.unwrap();
+    assert_eq!(purged.len(), 49);
+    assert!(db.list_dummy_users().await.unwrap().is_empty());
+    assert!(db.list_users().await.unwrap().is_empty());
+    assert_eq!(db.stats().await.unwrap().dummy_users, 0);
+}
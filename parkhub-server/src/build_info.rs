@@ -0,0 +1,70 @@
+//! What this server binary is: version, commit, protocol and storage.
+//!
+//! Shared by the startup banner, `GET /api/v1/version`, the health
+//! endpoints and `parkhub-server doctor`, so all of them report the same
+//! build. The commit comes from the `GIT_SHA` env var at build time; the
+//! build script fills it from the git checkout when it is not set.
+
+use std::net::SocketAddr;
+use std::path::Path;
+
+use parkhub_common::{PROTOCOL_VERSION, ServerVersion};
+
+use crate::api::modules::module_registry;
+use crate::db::Database;
+
+/// Semantic version of this build.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Commit this binary was built from, or `unknown` outside a git checkout.
+pub const GIT_HASH: &str = match option_env!("GIT_SHA") {
+    Some(hash) => hash,
+    None => "unknown",
+};
+
+/// Storage engine behind [`Database`].
+pub const STORAGE_BACKEND: &str = "redb";
+
+/// Version and capabilities of the running server. `features` lists the
+/// modules that are compiled in and enabled at runtime.
+pub async fn server_version(db: &Database) -> ServerVersion {
+    ServerVersion {
+        version: VERSION.to_string(),
+        git_hash: GIT_HASH.to_string(),
+        protocol_version: PROTOCOL_VERSION.to_string(),
+        features: module_registry(db)
+            .await
+            .into_iter()
+            .filter(|module| module.runtime_enabled)
+            .map(|module| module.name)
+            .collect(),
+        storage_backend: STORAGE_BACKEND.to_string(),
+    }
+}
+
+/// Where the server is about to listen.
+pub struct Listener<'a> {
+    pub addr: SocketAddr,
+    pub tls: bool,
+    pub data_dir: &'a Path,
+}
+
+/// Log the startup banner: one event whose fields carry the whole build
+/// and listener description, so log pipelines can index it.
+pub async fn log_startup_banner(db: &Database, listener: &Listener<'_>) {
+    let version = server_version(db).await;
+    tracing::info!(
+        version = %version.version,
+        git_hash = %version.git_hash,
+        protocol_version = %version.protocol_version,
+        storage_backend = %version.storage_backend,
+        encrypted = db.is_encrypted(),
+        features = %version.features.join(","),
+        addr = %listener.addr,
+        tls = listener.tls,
+        data_dir = %listener.data_dir.display(),
+        "ParkHub Server {} ({}) ready",
+        version.version,
+        version.git_hash,
+    );
+}
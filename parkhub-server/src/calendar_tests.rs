@@ -50,7 +50,7 @@ async fn test_harness() -> TestHarness {
         db,
         mdns: None,
         scheduler: None,
-        ws_events: crate::api::ws::EventBroadcaster::new(),
+        ws_events: crate::api::events::EventBroadcaster::new(),
         fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
         revocation_store: crate::jwt::TokenRevocationList::new(),
     }));
@@ -1,7 +1,9 @@
 //! Server Configuration
 
 use anyhow::Result;
+use parkhub_common::UserRole;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
 /// Server configuration
@@ -20,6 +22,11 @@ pub struct ServerConfig {
     /// Enable mDNS autodiscovery
     pub enable_mdns: bool,
 
+    /// Advertise this server as a standby of the organization's primary.
+    /// Clients use it for reads while the primary is unreachable.
+    #[serde(default)]
+    pub standby: bool,
+
     /// Enable database encryption at rest
     #[serde(default = "default_true")]
     pub encryption_enabled: bool,
@@ -46,14 +53,24 @@ pub struct ServerConfig {
     #[serde(skip)]
     pub username_style: u8,
 
-    /// License plate display mode (0=show, 1=blur, 2=redact, 3=hide)
+    /// License plate display mode for other users' bookings (0=show, 1=blur,
+    /// 2=redact, 3=hide). See [`crate::api::plate_display`].
     #[serde(default)]
     pub license_plate_display: u8,
 
-    /// Session timeout in minutes (0 = never)
+    /// Minutes without a request after which a session ends (0 = never)
     #[serde(default = "default_session_timeout")]
     pub session_timeout_minutes: u32,
 
+    /// Absolute session lifetime in hours, regardless of activity
+    #[serde(default = "default_session_lifetime")]
+    pub session_lifetime_hours: u32,
+
+    /// Minutes before a booking ends at which clients warn about expiry
+    /// (reported as `countdown.expiring_soon`; 0 = never warn)
+    #[serde(default = "default_expiry_warning_minutes")]
+    pub booking_expiry_warning_minutes: u32,
+
     /// Allow user self-registration
     #[serde(default)]
     pub allow_self_registration: bool,
@@ -66,7 +83,12 @@ pub struct ServerConfig {
     #[serde(default)]
     pub max_concurrent_sessions: u32,
 
-    /// Enable automatic daily backups
+    /// What a sign-in does once a user has `max_concurrent_sessions`
+    /// sessions: end the oldest one, or be rejected
+    #[serde(default)]
+    pub session_limit_policy: SessionLimitPolicy,
+
+    /// Enable automatic backups into `<data dir>/backups`
     #[serde(default = "default_true")]
     pub auto_backup_enabled: bool,
 
@@ -74,6 +96,11 @@ pub struct ServerConfig {
     #[serde(default = "default_backup_count")]
     pub backup_retention_count: u32,
 
+    /// When automatic backups run, as a cron expression with seconds
+    /// (`sec min hour day month weekday`, UTC)
+    #[serde(default = "default_backup_schedule")]
+    pub backup_schedule: String,
+
     /// Enable audit logging
     #[serde(default = "default_true")]
     pub audit_logging_enabled: bool,
@@ -101,6 +128,93 @@ pub struct ServerConfig {
     /// Reduce motion animations
     #[serde(default)]
     pub reduce_motion: bool,
+
+    /// OpenID Connect single sign-on (`[oidc]` table)
+    #[serde(default)]
+    pub oidc: OidcConfig,
+}
+
+/// Behavior when a user signs in while at `max_concurrent_sessions`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionLimitPolicy {
+    /// End the user's oldest session to make room for the new one
+    #[default]
+    EvictOldest,
+    /// Refuse the sign-in until another session ends
+    Reject,
+}
+
+/// OpenID Connect login against an external identity provider.
+///
+/// Disabled until both `issuer_url` and `client_id` are set. Endpoints are
+/// discovered from `{issuer_url}/.well-known/openid-configuration`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcConfig {
+    /// Issuer URL exactly as it appears in the provider's `iss` claim
+    #[serde(default)]
+    pub issuer_url: String,
+
+    /// Client ID registered with the provider
+    #[serde(default)]
+    pub client_id: String,
+
+    /// Client secret (empty for public clients, which rely on PKCE alone)
+    #[serde(default)]
+    pub client_secret: String,
+
+    /// Public base URL of this server for the redirect URI
+    /// (defaults to `APP_URL`)
+    #[serde(default)]
+    pub redirect_base_url: String,
+
+    /// Scopes requested in addition to `openid`
+    #[serde(default = "default_oidc_scopes")]
+    pub scopes: Vec<String>,
+
+    /// ID-token claim holding the user's groups
+    #[serde(default = "default_oidc_groups_claim")]
+    pub groups_claim: String,
+
+    /// Group name → role. When non-empty, the role of an OIDC user is set
+    /// from their groups on every login (highest role wins, `user` if none
+    /// match).
+    #[serde(default)]
+    pub role_mappings: BTreeMap<String, UserRole>,
+
+    /// Create an account on first login for unknown users
+    #[serde(default = "default_true")]
+    pub auto_provision: bool,
+}
+
+fn default_oidc_scopes() -> Vec<String> {
+    vec!["email".to_string(), "profile".to_string()]
+}
+
+fn default_oidc_groups_claim() -> String {
+    "groups".to_string()
+}
+
+impl Default for OidcConfig {
+    fn default() -> Self {
+        Self {
+            issuer_url: String::new(),
+            client_id: String::new(),
+            client_secret: String::new(),
+            redirect_base_url: String::new(),
+            scopes: default_oidc_scopes(),
+            groups_claim: default_oidc_groups_claim(),
+            role_mappings: BTreeMap::new(),
+            auto_provision: true,
+        }
+    }
+}
+
+impl OidcConfig {
+    /// Whether OIDC login is configured.
+    pub const fn is_enabled(&self) -> bool {
+        !self.issuer_url.is_empty() && !self.client_id.is_empty()
+    }
 }
 
 const fn default_font_scale() -> f32 {
@@ -111,10 +225,22 @@ const fn default_session_timeout() -> u32 {
     60 // 1 hour default
 }
 
+const fn default_session_lifetime() -> u32 {
+    24
+}
+
+const fn default_expiry_warning_minutes() -> u32 {
+    15
+}
+
 const fn default_backup_count() -> u32 {
     7 // Keep 7 days of backups
 }
 
+fn default_backup_schedule() -> String {
+    "0 0 3 * * *".to_string() // Daily at 03:00
+}
+
 fn default_language() -> String {
     "en".to_string()
 }
@@ -134,6 +260,7 @@ impl Default for ServerConfig {
             port: parkhub_common::DEFAULT_PORT,
             enable_tls: true,
             enable_mdns: true,
+            standby: false,
             encryption_enabled: true,
             encryption_passphrase: None,
             admin_username: "admin".to_string(),
@@ -143,11 +270,15 @@ impl Default for ServerConfig {
             username_style: 0,        // FirstLastLetter by default
             license_plate_display: 0, // Show by default
             session_timeout_minutes: 60,
+            session_lifetime_hours: 24,
+            booking_expiry_warning_minutes: 15,
             allow_self_registration: false,
             require_email_verification: false,
             max_concurrent_sessions: 0, // Unlimited
+            session_limit_policy: SessionLimitPolicy::EvictOldest,
             auto_backup_enabled: true,
             backup_retention_count: 7,
+            backup_schedule: default_backup_schedule(),
             audit_logging_enabled: true,
             default_language: "en".to_string(),
             organization_name: String::new(),
@@ -155,6 +286,7 @@ impl Default for ServerConfig {
             theme_mode: 0, // Dark by default
             font_scale: 1.0,
             reduce_motion: false,
+            oidc: OidcConfig::default(),
         }
     }
 }
@@ -194,11 +326,14 @@ mod tests {
         assert_eq!(config.username_style, 0);
         assert_eq!(config.license_plate_display, 0);
         assert_eq!(config.session_timeout_minutes, 60);
+        assert_eq!(config.session_lifetime_hours, 24);
         assert!(!config.allow_self_registration);
         assert!(!config.require_email_verification);
         assert_eq!(config.max_concurrent_sessions, 0);
+        assert_eq!(config.session_limit_policy, SessionLimitPolicy::EvictOldest);
         assert!(config.auto_backup_enabled);
         assert_eq!(config.backup_retention_count, 7);
+        assert_eq!(config.backup_schedule, "0 0 3 * * *");
         assert!(config.audit_logging_enabled);
         assert_eq!(config.default_language, "en");
         assert_eq!(config.organization_name, "");
@@ -266,7 +401,10 @@ mod tests {
         assert!(config.encryption_enabled); // default_true
         assert!(config.portable_mode); // default_true
         assert_eq!(config.session_timeout_minutes, 60); // default
+        assert_eq!(config.session_lifetime_hours, 24); // default
+        assert_eq!(config.booking_expiry_warning_minutes, 15); // default
         assert_eq!(config.backup_retention_count, 7); // default
+        assert_eq!(config.backup_schedule, "0 0 3 * * *"); // default
         assert_eq!(config.default_language, "en"); // default
     }
 
@@ -340,11 +478,14 @@ mod tests {
             portable_mode: false,
             license_plate_display: 2,
             session_timeout_minutes: 120,
+            session_lifetime_hours: 12,
             allow_self_registration: true,
             require_email_verification: true,
             max_concurrent_sessions: 5,
+            session_limit_policy: SessionLimitPolicy::Reject,
             auto_backup_enabled: false,
             backup_retention_count: 14,
+            backup_schedule: "0 30 1 * * Sun".to_string(),
             audit_logging_enabled: false,
             default_language: "de".to_string(),
             organization_name: "ACME Corp".to_string(),
@@ -367,11 +508,14 @@ mod tests {
         assert_eq!(loaded.admin_username, "superadmin");
         assert_eq!(loaded.license_plate_display, 2);
         assert_eq!(loaded.session_timeout_minutes, 120);
+        assert_eq!(loaded.session_lifetime_hours, 12);
         assert!(loaded.allow_self_registration);
         assert!(loaded.require_email_verification);
         assert_eq!(loaded.max_concurrent_sessions, 5);
+        assert_eq!(loaded.session_limit_policy, SessionLimitPolicy::Reject);
         assert!(!loaded.auto_backup_enabled);
         assert_eq!(loaded.backup_retention_count, 14);
+        assert_eq!(loaded.backup_schedule, "0 30 1 * * Sun");
         assert!(!loaded.audit_logging_enabled);
         assert_eq!(loaded.default_language, "de");
         assert_eq!(loaded.organization_name, "ACME Corp");
@@ -446,6 +590,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_oidc_config_section() {
+        assert!(!ServerConfig::default().oidc.is_enabled());
+
+        let toml_with_oidc = r#"
+            server_name = "Test"
+            port = 8443
+            enable_tls = true
+            enable_mdns = true
+            admin_username = "admin"
+            admin_password_hash = "hash"
+
+            [oidc]
+            issuer_url = "https://login.example.com"
+            client_id = "parkhub"
+
+            [oidc.role_mappings]
+            parking-admins = "admin"
+        "#;
+        let config: ServerConfig = toml::from_str(toml_with_oidc).unwrap();
+        assert!(config.oidc.is_enabled());
+        assert_eq!(config.oidc.groups_claim, "groups");
+        assert_eq!(config.oidc.scopes, ["email", "profile"]);
+        assert!(config.oidc.auto_provision);
+        assert_eq!(
+            config.oidc.role_mappings.get("parking-admins"),
+            Some(&UserRole::Admin)
+        );
+
+        let temp_file = NamedTempFile::new().unwrap();
+        config.save(temp_file.path()).unwrap();
+        let loaded = ServerConfig::load(temp_file.path()).unwrap();
+        assert_eq!(loaded.oidc.role_mappings, config.oidc.role_mappings);
+    }
+
     #[test]
     fn test_config_font_scale_values() {
         for &scale in &[1.0f32, 1.25, 1.5] {
@@ -53,7 +53,7 @@ async fn test_harness() -> TestHarness {
         db,
         mdns: None,
         scheduler: None,
-        ws_events: crate::api::ws::EventBroadcaster::new(),
+        ws_events: crate::api::events::EventBroadcaster::new(),
         fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
         revocation_store: crate::jwt::TokenRevocationList::new(),
     }));
@@ -1163,7 +1163,8 @@ async fn test_revenue_report_empty() {
 
     assert_eq!(resp.status(), StatusCode::OK);
     let json = body_json(resp).await;
-    assert!(json["data"].is_array());
+    assert!(json["data"]["rows"].is_array());
+    assert_eq!(json["data"]["totals"]["booking_count"], 0);
 }
 
 #[tokio::test]
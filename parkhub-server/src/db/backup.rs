@@ -0,0 +1,320 @@
+//! Online backups of the whole database, and restores from them.
+//!
+//! [`Database::backup`] copies every table into a new redb file from a
+//! single read transaction. Read transactions are snapshots in redb, so the
+//! copy is consistent while the server keeps writing. [`Database::restore`]
+//! replaces every table with the contents of such a file in a single write
+//! transaction: requests see the database either before or after it, never
+//! in between.
+//!
+//! A backup file holds the records exactly as the database does, encrypted
+//! at rest or not. For download it is sealed into an archive
+//! ([`seal_backup`] / [`open_backup`]): a magic header, a random salt and
+//! the file encrypted with AES-256-GCM under a key derived from a
+//! passphrase the admin picks.
+//!
+//! A restore keeps the running server's access-token secret and at-rest
+//! encryption salt, so a backup is only accepted if its records decrypt with
+//! the server's key. Booking attachment files live outside the database and
+//! are not part of a backup.
+//!
+//! Sessions, which hold live refresh tokens, are left out of backups. A
+//! restore ends every session and revokes all access tokens issued before
+//! it, so everyone signs in again against the restored accounts.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Result, bail};
+use chrono::Utc;
+use parkhub_common::User;
+use rand::Rng;
+use redb::{
+    Database as RedbDatabase, Key, ReadTransaction, ReadableDatabase, ReadableTable,
+    TableDefinition, TableError, Value, WriteTransaction,
+};
+use tracing::info;
+
+use super::encryption::Encryptor;
+use super::lot_managers::move_legacy_lot_managers;
+use super::{
+    ABSENCES, ANNOUNCEMENTS, AUDIT_LOG, BOOKING_ATTACHMENTS, BOOKING_NOTES, BOOKING_SLOT_END_KEYS,
+    BOOKINGS, BOOKINGS_BY_SLOT_END, BOOKINGS_BY_START, BOOKINGS_BY_USER, BOOKINGS_BY_USER_START,
+    CHARGING_SESSIONS, CREDIT_TRANSACTIONS, DUMMY_USERS, Database, EMAIL_QUEUE, EV_CHARGERS,
+    FAVORITE_LOTS, FAVORITES, FEATURE_FLAGS, GUEST_BOOKINGS, INVITES, INVOICES, INVOICES_BY_USER,
+    LIST_ORDER_KEYS, LIVE_BOOKING_END_KEYS, LIVE_BOOKINGS_BY_END, LOGIN_HISTORY, LOT_MANAGERS,
+    LOTS_BY_NAME, NOTIFICATIONS, PARKING_LOTS, PARKING_SLOTS, PASSKEYS, PENDING_SLOT_ASSIGNMENTS,
+    PERMITS, PUSH_SUBSCRIPTIONS, RECTIFICATION_REQUESTS, RECURRING_BOOKINGS,
+    REFRESH_TOKEN_FAMILIES, ROLE_MEMBERS, ROLES, SEARCH_DOCS, SEARCH_INDEX, SESSION_REVOCATIONS,
+    SESSIONS, SESSIONS_BY_USER, SETTING_ENCRYPTION_SALT, SETTING_TOKEN_SECRET, SETTINGS,
+    SLOT_HOLDS, SLOT_HOLDS_BY_LOT, SLOT_STATUS_HISTORY, SLOT_TYPE_KEYS, SLOTS_BY_LOT,
+    SLOTS_BY_TYPE, STRIPE_EVENTS, SWAP_REQUESTS, TRANSLATION_OVERRIDES, TRANSLATION_PROPOSALS,
+    TRANSLATION_VOTES, USERS, USERS_BY_CREATED, USERS_BY_EMAIL, USERS_BY_USERNAME, VEHICLES,
+    VISITOR_PASS_BLOCKS, VISITOR_PASS_KEYS, VISITOR_PASSES, VISITORS, WAITLIST, WEBHOOKS, ZONES,
+    session_revocations,
+};
+
+/// First bytes of a backup archive; the last one is the format version.
+const ARCHIVE_MAGIC: &[u8] = b"PARKHUB-BACKUP\x01";
+
+/// Length of the random salt following [`ARCHIVE_MAGIC`].
+const ARCHIVE_SALT_LEN: usize = 32;
+
+/// Settings a restore keeps from the running server.
+const KEPT_SETTINGS: [&str; 2] = [SETTING_ENCRYPTION_SALT, SETTING_TOKEN_SECRET];
+
+/// Seal the backup file contents `snapshot` into an archive encrypted under
+/// `passphrase`.
+pub fn seal_backup(snapshot: &[u8], passphrase: &str) -> Result<Vec<u8>> {
+    let mut salt = [0u8; ARCHIVE_SALT_LEN];
+    rand::rng().fill_bytes(&mut salt);
+    let ciphertext = Encryptor::new(passphrase, &salt)?.encrypt(snapshot)?;
+
+    let mut archive = Vec::with_capacity(ARCHIVE_MAGIC.len() + salt.len() + ciphertext.len());
+    archive.extend_from_slice(ARCHIVE_MAGIC);
+    archive.extend_from_slice(&salt);
+    archive.extend(ciphertext);
+    Ok(archive)
+}
+
+/// The backup file sealed in `archive`, or `None` if it is not an archive,
+/// is damaged or `passphrase` is wrong.
+#[must_use]
+pub fn open_backup(archive: &[u8], passphrase: &str) -> Option<Vec<u8>> {
+    let rest = archive.strip_prefix(ARCHIVE_MAGIC)?;
+    if rest.len() < ARCHIVE_SALT_LEN {
+        return None;
+    }
+    let (salt, ciphertext) = rest.split_at(ARCHIVE_SALT_LEN);
+    Encryptor::new(passphrase, salt)
+        .ok()?
+        .decrypt(ciphertext)
+        .ok()
+}
+
+/// Replace `table` in `target` with its contents in `source`.
+fn copy_table<K: Key + 'static, V: Value + 'static>(
+    source: &ReadTransaction,
+    target: &WriteTransaction,
+    table: TableDefinition<K, V>,
+) -> Result<()> {
+    target.delete_table(table)?;
+    let mut target_table = target.open_table(table)?;
+    let source_table = match source.open_table(table) {
+        Ok(source_table) => source_table,
+        // Backups taken before the table was introduced
+        Err(TableError::TableDoesNotExist(_)) => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+    for entry in source_table.iter()? {
+        let (key, value) = entry?;
+        target_table.insert(key.value(), value.value())?;
+    }
+    Ok(())
+}
+
+/// Replace every table in `target` with its contents in `source`.
+fn copy_tables(source: &ReadTransaction, target: &WriteTransaction) -> Result<()> {
+    copy_table(source, target, USERS)?;
+    copy_table(source, target, USERS_BY_USERNAME)?;
+    copy_table(source, target, USERS_BY_EMAIL)?;
+    copy_table(source, target, BOOKINGS)?;
+    copy_table(source, target, BOOKINGS_BY_USER)?;
+    copy_table(source, target, PARKING_LOTS)?;
+    copy_table(source, target, PARKING_SLOTS)?;
+    copy_table(source, target, SLOTS_BY_LOT)?;
+    copy_table(source, target, VEHICLES)?;
+    copy_table(source, target, SETTINGS)?;
+    copy_table(source, target, CREDIT_TRANSACTIONS)?;
+    copy_table(source, target, ABSENCES)?;
+    copy_table(source, target, WAITLIST)?;
+    copy_table(source, target, GUEST_BOOKINGS)?;
+    copy_table(source, target, SWAP_REQUESTS)?;
+    copy_table(source, target, RECURRING_BOOKINGS)?;
+    copy_table(source, target, ANNOUNCEMENTS)?;
+    copy_table(source, target, NOTIFICATIONS)?;
+    copy_table(source, target, WEBHOOKS)?;
+    copy_table(source, target, PUSH_SUBSCRIPTIONS)?;
+    copy_table(source, target, ZONES)?;
+    copy_table(source, target, FAVORITES)?;
+    copy_table(source, target, FAVORITE_LOTS)?;
+    copy_table(source, target, AUDIT_LOG)?;
+    copy_table(source, target, TRANSLATION_PROPOSALS)?;
+    copy_table(source, target, TRANSLATION_VOTES)?;
+    copy_table(source, target, TRANSLATION_OVERRIDES)?;
+    copy_table(source, target, VISITORS)?;
+    copy_table(source, target, EV_CHARGERS)?;
+    copy_table(source, target, CHARGING_SESSIONS)?;
+    copy_table(source, target, STRIPE_EVENTS)?;
+    copy_table(source, target, SLOT_STATUS_HISTORY)?;
+    copy_table(source, target, EMAIL_QUEUE)?;
+    copy_table(source, target, LOGIN_HISTORY)?;
+    copy_table(source, target, PERMITS)?;
+    copy_table(source, target, SEARCH_INDEX)?;
+    copy_table(source, target, SEARCH_DOCS)?;
+    copy_table(source, target, INVOICES)?;
+    copy_table(source, target, INVOICES_BY_USER)?;
+    copy_table(source, target, PENDING_SLOT_ASSIGNMENTS)?;
+    copy_table(source, target, ROLES)?;
+    copy_table(source, target, ROLE_MEMBERS)?;
+    copy_table(source, target, LOT_MANAGERS)?;
+    copy_table(source, target, BOOKINGS_BY_START)?;
+    copy_table(source, target, BOOKINGS_BY_USER_START)?;
+    copy_table(source, target, USERS_BY_CREATED)?;
+    copy_table(source, target, LOTS_BY_NAME)?;
+    copy_table(source, target, LIST_ORDER_KEYS)?;
+    copy_table(source, target, PASSKEYS)?;
+    copy_table(source, target, INVITES)?;
+    copy_table(source, target, REFRESH_TOKEN_FAMILIES)?;
+    copy_table(source, target, BOOKINGS_BY_SLOT_END)?;
+    copy_table(source, target, BOOKING_SLOT_END_KEYS)?;
+    copy_table(source, target, LIVE_BOOKINGS_BY_END)?;
+    copy_table(source, target, LIVE_BOOKING_END_KEYS)?;
+    copy_table(source, target, DUMMY_USERS)?;
+    copy_table(source, target, SLOT_HOLDS)?;
+    copy_table(source, target, SLOT_HOLDS_BY_LOT)?;
+    copy_table(source, target, RECTIFICATION_REQUESTS)?;
+    copy_table(source, target, SLOTS_BY_TYPE)?;
+    copy_table(source, target, SLOT_TYPE_KEYS)?;
+    copy_table(source, target, SESSION_REVOCATIONS)?;
+    copy_table(source, target, BOOKING_NOTES)?;
+    copy_table(source, target, BOOKING_ATTACHMENTS)?;
+    copy_table(source, target, VISITOR_PASS_BLOCKS)?;
+    copy_table(source, target, VISITOR_PASSES)?;
+    copy_table(source, target, VISITOR_PASS_KEYS)?;
+    copy_table(source, target, FEATURE_FLAGS)?;
+    Ok(())
+}
+
+/// Delete every session in `txn` and revoke all access tokens its users were
+/// issued before `revoked_at` (Unix seconds).
+fn end_all_sessions(txn: &WriteTransaction, revoked_at: i64) -> Result<()> {
+    txn.delete_table(SESSIONS)?;
+    txn.open_table(SESSIONS)?;
+    txn.delete_table(SESSIONS_BY_USER)?;
+    txn.open_table(SESSIONS_BY_USER)?;
+    let users = txn.open_table(USERS)?;
+    let mut revocations = txn.open_table(SESSION_REVOCATIONS)?;
+    for entry in users.iter()? {
+        let (user_id, _) = entry?;
+        revocations.insert(user_id.value(), revoked_at)?;
+    }
+    Ok(())
+}
+
+/// Write the snapshot of `read_txn` to the new, empty `file`.
+fn write_backup(read_txn: &ReadTransaction, file: File) -> Result<()> {
+    let target = RedbDatabase::builder().create_file(file)?;
+    let write_txn = target.begin_write()?;
+    copy_tables(read_txn, &write_txn)?;
+    write_txn.commit()?;
+    Ok(())
+}
+
+impl Database {
+    /// Directory backups are written to.
+    pub fn backup_dir(&self) -> PathBuf {
+        self.data_dir.join("backups")
+    }
+
+    /// Write a consistent copy of the whole database to `path`, which must
+    /// not exist yet.
+    pub async fn backup(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let file = tokio::fs::File::create_new(path).await?.into_std().await;
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+
+        // Copying every table is blocking file I/O
+        let result = tokio::task::spawn_blocking(move || write_backup(&read_txn, file))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|written| written);
+        if result.is_err() {
+            let _ = tokio::fs::remove_file(path).await;
+        }
+        result
+    }
+
+    /// Replace the whole database with the backup at `path`, ending every
+    /// session and revoking the access tokens issued before it.
+    ///
+    /// Returns `false` without changing anything if the backup's records
+    /// cannot be read with this server's at-rest encryption (a backup of an
+    /// encrypted database with another key, or of an unencrypted one into
+    /// an encrypted server and vice versa).
+    pub async fn restore(&self, path: &Path) -> Result<bool> {
+        let db = self.clone();
+        let path = path.to_path_buf();
+        // Copying every table is blocking file I/O. Once started, the copy
+        // runs through to the commit and the revocation reload even if the
+        // caller stops waiting, so a committed restore always takes effect.
+        tokio::task::spawn_blocking(move || db.restore_blocking(&path)).await?
+    }
+
+    /// [`Self::restore`], on a thread that may block.
+    fn restore_blocking(&self, path: &Path) -> Result<bool> {
+        let source = RedbDatabase::builder().open_read_only(path)?;
+        let read_txn = source.begin_read()?;
+        if !self.backup_readable(&read_txn)? {
+            return Ok(false);
+        }
+
+        let db = self.inner.blocking_write();
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let mut kept = Vec::new();
+        {
+            let settings = write_txn.open_table(SETTINGS)?;
+            for key in KEPT_SETTINGS {
+                let value = settings.get(key)?.map(|v| v.value().to_string());
+                kept.push((key, value));
+            }
+        }
+        copy_tables(&read_txn, &write_txn)?;
+        end_all_sessions(&write_txn, Utc::now().timestamp())?;
+        // Backups taken before lot managers had their own table
+        move_legacy_lot_managers(&write_txn)?;
+        self.build_list_order(&write_txn)?;
+        self.build_search_index(&write_txn)?;
+        self.index_slot_holds(&write_txn)?;
+        {
+            let mut settings = write_txn.open_table(SETTINGS)?;
+            for (key, value) in &kept {
+                match value {
+                    Some(value) => settings.insert(*key, value.as_str())?,
+                    None => settings.remove(*key)?,
+                };
+            }
+        }
+        // Read before the commit, so nothing can fail once it is done
+        let revocations = session_revocations::read_session_revocations(
+            &write_txn.open_table(SESSION_REVOCATIONS)?,
+        )?;
+        write_txn.commit()?;
+        self.access_changed();
+        *self
+            .session_revocations
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = revocations;
+        info!("Database restored from backup {}", path.display());
+        Ok(true)
+    }
+
+    /// Whether this database's key reads the records of the backup behind
+    /// `read_txn`, probed with its first user.
+    fn backup_readable(&self, read_txn: &ReadTransaction) -> Result<bool> {
+        let users = match read_txn.open_table(USERS) {
+            Ok(users) => users,
+            Err(TableError::TableDoesNotExist(_)) => bail!("Backup has no users table"),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(match users.first()? {
+            Some((_, value)) => self.deserialize::<User>(value.value()).is_ok(),
+            None => true,
+        })
+    }
+}
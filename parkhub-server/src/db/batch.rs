@@ -0,0 +1,220 @@
+//! Multi-record writes applied in one redb write transaction.
+//!
+//! An operation that touches several records — a booking, the status of its
+//! slot, the user's credit balance and the matching credit transaction —
+//! used to commit each record separately, so a crash or error between two
+//! commits left them inconsistent (a booking on a slot still shown as free,
+//! credits deducted for a booking that was never stored). A [`WriteBatch`]
+//! collects those writes and [`Database::apply_batch`] commits them together:
+//! redb writes the new pages first and switches to them on commit, so after a
+//! crash either every write of the batch is visible or none is.
+
+use anyhow::Result;
+use tracing::debug;
+use uuid::Uuid;
+
+use parkhub_common::models::{
+    Booking, CreditTransaction, ParkingSlot, SlotType, User, WaitlistEntry,
+};
+
+use super::{Database, SlotChangeContext, VisitorPass};
+
+/// One write of a [`WriteBatch`].
+#[derive(Debug)]
+enum BatchOp {
+    ClaimSlot(Box<Booking>),
+    ClaimSlotType {
+        booking: Box<Booking>,
+        slot_type: SlotType,
+        slot_ids: Vec<Uuid>,
+    },
+    SaveBooking(Box<Booking>),
+    SaveSlot(Box<ParkingSlot>, SlotChangeContext),
+    RecountLotSlots(Uuid),
+    DeleteLot(Uuid),
+    SaveUser(Box<User>),
+    AnonymizeUser(Box<User>),
+    SaveCreditTransaction(CreditTransaction),
+    SaveWaitlistEntry(Box<WaitlistEntry>),
+    #[cfg_attr(not(feature = "mod-guest"), allow(dead_code))]
+    SaveVisitorPass(Box<VisitorPass>),
+}
+
+/// Writes to apply atomically with [`Database::apply_batch`], in the order
+/// they were added.
+#[derive(Debug, Default)]
+pub struct WriteBatch {
+    ops: Vec<BatchOp>,
+}
+
+impl WriteBatch {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store `booking` on its slot, as [`Database::claim_slot`]. The whole
+    /// batch is dropped if the slot is taken.
+    pub fn claim_slot(&mut self, booking: &Booking) -> &mut Self {
+        self.ops.push(BatchOp::ClaimSlot(Box::new(booking.clone())));
+        self
+    }
+
+    /// Store late-binding `booking` if one of `slot_ids`, the lot's slots of
+    /// `slot_type`, is left for its time range. The whole batch is dropped
+    /// otherwise.
+    pub fn claim_slot_type(
+        &mut self,
+        booking: &Booking,
+        slot_type: &SlotType,
+        slot_ids: &[Uuid],
+    ) -> &mut Self {
+        self.ops.push(BatchOp::ClaimSlotType {
+            booking: Box::new(booking.clone()),
+            slot_type: slot_type.clone(),
+            slot_ids: slot_ids.to_vec(),
+        });
+        self
+    }
+
+    /// Save `booking`, as [`Database::save_booking`].
+    pub fn save_booking(&mut self, booking: &Booking) -> &mut Self {
+        self.ops
+            .push(BatchOp::SaveBooking(Box::new(booking.clone())));
+        self
+    }
+
+    /// Save `slot`, as [`Database::save_parking_slot_with`].
+    pub fn save_parking_slot(&mut self, slot: &ParkingSlot, ctx: &SlotChangeContext) -> &mut Self {
+        self.ops
+            .push(BatchOp::SaveSlot(Box::new(slot.clone()), ctx.clone()));
+        self
+    }
+
+    /// Recompute the slot counts of lot `lot_id` and its floors from the
+    /// lot's slots as written by the batch so far.
+    pub fn recount_lot_slots(&mut self, lot_id: Uuid) -> &mut Self {
+        self.ops.push(BatchOp::RecountLotSlots(lot_id));
+        self
+    }
+
+    /// Delete lot `lot_id` with its slots, as [`Database::delete_parking_lot`].
+    pub fn delete_parking_lot(&mut self, lot_id: Uuid) -> &mut Self {
+        self.ops.push(BatchOp::DeleteLot(lot_id));
+        self
+    }
+
+    /// Save `user`, as [`Database::save_user`].
+    pub fn save_user(&mut self, user: &User) -> &mut Self {
+        self.ops.push(BatchOp::SaveUser(Box::new(user.clone())));
+        self
+    }
+
+    /// Anonymize `user`, as [`Database::anonymize_user`]. Their vehicles,
+    /// permits and other linked data are deleted after the batch commits.
+    pub fn anonymize_user(&mut self, user: &User) -> &mut Self {
+        self.ops
+            .push(BatchOp::AnonymizeUser(Box::new(user.clone())));
+        self
+    }
+
+    /// Save `tx`, as [`Database::save_credit_transaction`].
+    pub fn save_credit_transaction(&mut self, tx: &CreditTransaction) -> &mut Self {
+        self.ops.push(BatchOp::SaveCreditTransaction(tx.clone()));
+        self
+    }
+
+    /// Save `entry`, as [`Database::save_waitlist_entry`].
+    pub fn save_waitlist_entry(&mut self, entry: &WaitlistEntry) -> &mut Self {
+        self.ops
+            .push(BatchOp::SaveWaitlistEntry(Box::new(entry.clone())));
+        self
+    }
+
+    /// Save `pass`, as [`Database::save_visitor_pass`].
+    #[cfg_attr(not(feature = "mod-guest"), allow(dead_code))]
+    pub fn save_visitor_pass(&mut self, pass: &VisitorPass) -> &mut Self {
+        self.ops
+            .push(BatchOp::SaveVisitorPass(Box::new(pass.clone())));
+        self
+    }
+}
+
+impl Database {
+    /// Apply every write of `batch` in one write transaction.
+    ///
+    /// Returns `false` without writing anything if a slot claim of the batch
+    /// fails; an error also leaves the database untouched.
+    pub async fn apply_batch(&self, batch: &WriteBatch) -> Result<bool> {
+        let _timer = Self::time_write("batch", None);
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        for op in &batch.ops {
+            let applied = match op {
+                BatchOp::ClaimSlot(booking) => self.try_claim_slot(&write_txn, booking)?,
+                BatchOp::ClaimSlotType {
+                    booking,
+                    slot_type,
+                    slot_ids,
+                } => self.try_claim_slot_type(&write_txn, booking, slot_type, slot_ids)?,
+                BatchOp::SaveBooking(booking) => {
+                    self.put_booking(&write_txn, booking)?;
+                    true
+                }
+                BatchOp::SaveSlot(slot, ctx) => {
+                    self.put_parking_slot(&write_txn, slot, ctx)?;
+                    true
+                }
+                BatchOp::RecountLotSlots(lot_id) => {
+                    self.recount_lot_slots(&write_txn, *lot_id)?;
+                    true
+                }
+                BatchOp::DeleteLot(lot_id) => {
+                    self.remove_parking_lot(&write_txn, &lot_id.to_string())?;
+                    true
+                }
+                BatchOp::SaveUser(user) => {
+                    self.put_user(&write_txn, user)?;
+                    true
+                }
+                BatchOp::AnonymizeUser(user) => {
+                    self.put_anonymized_user(&write_txn, user)?;
+                    true
+                }
+                BatchOp::SaveCreditTransaction(tx) => {
+                    self.put_credit_transaction(&write_txn, tx)?;
+                    true
+                }
+                BatchOp::SaveWaitlistEntry(entry) => {
+                    self.put_waitlist_entry(&write_txn, entry)?;
+                    true
+                }
+                BatchOp::SaveVisitorPass(pass) => {
+                    self.put_visitor_pass(&write_txn, pass)?;
+                    true
+                }
+            };
+            if !applied {
+                // Dropping the transaction aborts it.
+                return Ok(false);
+            }
+        }
+        write_txn.commit()?;
+        debug!("Applied write batch of {} record(s)", batch.ops.len());
+        if batch.ops.iter().any(|op| {
+            matches!(
+                op,
+                BatchOp::SaveUser(_) | BatchOp::AnonymizeUser(_) | BatchOp::DeleteLot(_)
+            )
+        }) {
+            self.access_changed();
+        }
+        for op in &batch.ops {
+            if let BatchOp::AnonymizeUser(user) = op {
+                self.delete_anonymized_user_data(&user.id.to_string()).await;
+            }
+        }
+        Ok(true)
+    }
+}
@@ -0,0 +1,198 @@
+//! Files users attach to their bookings, e.g. a photo of a damaged slot.
+//!
+//! The metadata is stored in `BOOKING_ATTACHMENTS`, keyed
+//! `"{booking_id}:{attachment_id}"`; the contents are files under
+//! `attachments/{booking_id}/` in the data directory, encrypted like the
+//! database when encryption at rest is on. A file is written before its
+//! record and removed after it, so a record never points at a missing file.
+//! Deleting a booking removes its attachments.
+
+#![cfg_attr(not(feature = "mod-bookings"), allow(dead_code))]
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable, WriteTransaction};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::booking_notes::booking_range;
+use super::{BOOKING_ATTACHMENTS, BOOKING_NOTES, Database};
+
+/// A file attached to a booking.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BookingAttachment {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    /// User who uploaded the file
+    pub uploaded_by: Uuid,
+    /// File name as uploaded, without directories
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Drop the notes and attachment records of `booking_id` within `write_txn`.
+/// The attachment files are left to [`Database::remove_attachment_files`].
+pub(super) fn remove_booking_extras(write_txn: &WriteTransaction, booking_id: &str) -> Result<()> {
+    let (start, end) = booking_range(booking_id);
+    for table in [BOOKING_NOTES, BOOKING_ATTACHMENTS] {
+        write_txn
+            .open_table(table)?
+            .retain_in(start.as_str()..end.as_str(), |_, _| false)?;
+    }
+    Ok(())
+}
+
+impl Database {
+    fn attachments_dir(&self) -> PathBuf {
+        self.data_dir.join("attachments")
+    }
+
+    fn attachment_path(&self, attachment: &BookingAttachment) -> PathBuf {
+        self.attachments_dir()
+            .join(attachment.booking_id.to_string())
+            .join(attachment.id.to_string())
+    }
+
+    /// Store `attachment` with contents `bytes`.
+    pub async fn save_booking_attachment(
+        &self,
+        attachment: &BookingAttachment,
+        bytes: &[u8],
+    ) -> Result<()> {
+        let path = self.attachment_path(attachment);
+        if let Some(dir) = path.parent() {
+            tokio::fs::create_dir_all(dir)
+                .await
+                .context("Failed to create attachment directory")?;
+        }
+        let contents = match self.encryptor {
+            Some(ref enc) => enc.encrypt(bytes)?,
+            None => bytes.to_vec(),
+        };
+        tokio::fs::write(&path, contents)
+            .await
+            .context("Failed to write attachment")?;
+
+        let key = format!("{}:{}", attachment.booking_id, attachment.id);
+        let data = self.serialize(attachment)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        write_txn
+            .open_table(BOOKING_ATTACHMENTS)?
+            .insert(key.as_str(), data.as_slice())?;
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// The attachments of booking `booking_id`, oldest first.
+    pub async fn list_booking_attachments(
+        &self,
+        booking_id: &str,
+    ) -> Result<Vec<BookingAttachment>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(BOOKING_ATTACHMENTS)?;
+
+        let (start, end) = booking_range(booking_id);
+        let mut attachments: Vec<BookingAttachment> = Vec::new();
+        for entry in table.range(start.as_str()..end.as_str())? {
+            let (_, value) = entry?;
+            attachments.push(self.deserialize(value.value())?);
+        }
+        attachments.sort_by_key(|a| a.created_at);
+        Ok(attachments)
+    }
+
+    /// Attachment `attachment_id` of booking `booking_id`, if any.
+    pub async fn get_booking_attachment(
+        &self,
+        booking_id: &str,
+        attachment_id: &str,
+    ) -> Result<Option<BookingAttachment>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(BOOKING_ATTACHMENTS)?;
+        match table.get(format!("{booking_id}:{attachment_id}").as_str())? {
+            Some(value) => Ok(Some(self.deserialize(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The contents of `attachment`.
+    pub async fn read_booking_attachment(&self, attachment: &BookingAttachment) -> Result<Vec<u8>> {
+        let contents = tokio::fs::read(self.attachment_path(attachment))
+            .await
+            .context("Failed to read attachment")?;
+        match self.encryptor {
+            Some(ref enc) => enc.decrypt(&contents),
+            None => Ok(contents),
+        }
+    }
+
+    /// Delete `attachment` and its file. Returns whether it was stored.
+    pub async fn delete_booking_attachment(&self, attachment: &BookingAttachment) -> Result<bool> {
+        let key = format!("{}:{}", attachment.booking_id, attachment.id);
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let existed = write_txn
+            .open_table(BOOKING_ATTACHMENTS)?
+            .remove(key.as_str())?
+            .is_some();
+        write_txn.commit()?;
+
+        if let Err(e) = tokio::fs::remove_file(self.attachment_path(attachment)).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove attachment file {}: {e}", attachment.id);
+        }
+        Ok(existed)
+    }
+
+    /// Delete every attachment `user_id` uploaded. Returns how many there were.
+    pub async fn delete_booking_attachments_by_uploader(&self, user_id: Uuid) -> Result<usize> {
+        let uploaded: Vec<BookingAttachment> = {
+            let db = self.inner.read().await;
+            let read_txn = db.begin_read()?;
+            drop(db);
+            let table = read_txn.open_table(BOOKING_ATTACHMENTS)?;
+            let mut uploaded = Vec::new();
+            for entry in table.iter()? {
+                let (_, value) = entry?;
+                let attachment: BookingAttachment = self.deserialize(value.value())?;
+                if attachment.uploaded_by == user_id {
+                    uploaded.push(attachment);
+                }
+            }
+            uploaded
+        };
+        for attachment in &uploaded {
+            self.delete_booking_attachment(attachment).await?;
+        }
+        Ok(uploaded.len())
+    }
+
+    /// Remove the attachment files of `booking_id`, or of all bookings.
+    /// Failures are logged; the records are already gone.
+    pub(super) async fn remove_attachment_files(&self, booking_id: Option<&str>) {
+        let dir = match booking_id {
+            Some(booking_id) => self.attachments_dir().join(booking_id),
+            None => self.attachments_dir(),
+        };
+        if let Err(e) = tokio::fs::remove_dir_all(&dir).await
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!(
+                "Failed to remove attachment files in {}: {e}",
+                dir.display()
+            );
+        }
+    }
+}
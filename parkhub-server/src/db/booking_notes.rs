@@ -0,0 +1,62 @@
+//! Internal notes on bookings, written and read by admins only.
+//!
+//! `BOOKING_NOTES` is keyed `"{booking_id}:{note_id}"`, so one booking's
+//! notes are a range scan. Notes are never shown to the booking's owner and
+//! go with the booking when it is deleted.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use redb::ReadableDatabase;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{BOOKING_NOTES, Database};
+
+/// A note an admin left on a booking.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BookingNote {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    /// Admin who wrote the note
+    pub author_id: Uuid,
+    pub text: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Key range covering one booking's entries (`;` sorts right after `:`).
+pub(super) fn booking_range(booking_id: &str) -> (String, String) {
+    (format!("{booking_id}:"), format!("{booking_id};"))
+}
+
+impl Database {
+    /// Store `note`.
+    pub async fn save_booking_note(&self, note: &BookingNote) -> Result<()> {
+        let key = format!("{}:{}", note.booking_id, note.id);
+        let data = self.serialize(note)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        write_txn
+            .open_table(BOOKING_NOTES)?
+            .insert(key.as_str(), data.as_slice())?;
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// The notes on booking `booking_id`, oldest first.
+    pub async fn list_booking_notes(&self, booking_id: &str) -> Result<Vec<BookingNote>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(BOOKING_NOTES)?;
+
+        let (start, end) = booking_range(booking_id);
+        let mut notes: Vec<BookingNote> = Vec::new();
+        for entry in table.range(start.as_str()..end.as_str())? {
+            let (_, value) = entry?;
+            notes.push(self.deserialize(value.value())?);
+        }
+        notes.sort_by_key(|n| n.created_at);
+        Ok(notes)
+    }
+}
@@ -0,0 +1,253 @@
+//! Time index of booked slots, for availability searches.
+//!
+//! `BOOKINGS_BY_SLOT_END` holds every pending, confirmed or active booking
+//! that has a slot, keyed `"{slot_id}:{end_millis}:{booking_id}"` with the
+//! end time zero-padded so a slot's keys sort by it. The bookings of a slot
+//! overlapping a window are then found among the keys ending after the
+//! window starts, without reading the slot's past bookings. Claiming a slot
+//! checks for conflicts the same way.
+//! `BOOKING_SLOT_END_KEYS` remembers each booking's key so a rewrite can drop
+//! it.
+//!
+//! `LIVE_BOOKINGS_BY_END` holds the same bookings plus those still waiting
+//! for a slot, keyed `"{end_millis}:{booking_id}"`, so the expiry job reads
+//! only the bookings that have ended instead of every booking.
+//! `LIVE_BOOKING_END_KEYS` remembers their keys. All four are updated in the
+//! same write transaction as the booking.
+//!
+//! Lots with `buffer_minutes` in their booking rules keep that much time
+//! free between consecutive bookings of a slot. Claims read the buffer in
+//! their own transaction and look for conflicts in the padded range.
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use redb::{ReadableDatabase, ReadableTable, ReadableTableMetadata, WriteTransaction};
+use tracing::info;
+use uuid::Uuid;
+
+use parkhub_common::TimeRange;
+use parkhub_common::models::{Booking, LotBookingRules};
+
+use super::settings::lot_rules_key;
+use super::slot_assignments::is_live;
+use super::{
+    BOOKING_SLOT_END_KEYS, BOOKINGS, BOOKINGS_BY_SLOT_END, Database, LIVE_BOOKING_END_KEYS,
+    LIVE_BOOKINGS_BY_END, SETTINGS,
+};
+
+/// `time` as zero-padded Unix milliseconds; times before 1970 sort first.
+fn millis_key(time: DateTime<Utc>) -> String {
+    format!(
+        "{:020}",
+        u64::try_from(time.timestamp_millis()).unwrap_or(0)
+    )
+}
+
+/// Point the time indexes at `booking` as it is being stored.
+pub(super) fn index_booking_time(write_txn: &WriteTransaction, booking: &Booking) -> Result<()> {
+    let id = booking.id.to_string();
+    unindex_booking_time(write_txn, &id)?;
+    if !is_live(&booking.status) {
+        return Ok(());
+    }
+    index_live_booking_end(write_txn, booking)?;
+    if !booking.slot_id.is_nil() {
+        index_slot_booking_end(write_txn, booking)?;
+    }
+    Ok(())
+}
+
+fn index_slot_booking_end(write_txn: &WriteTransaction, booking: &Booking) -> Result<()> {
+    let id = booking.id.to_string();
+    let key = format!("{}:{}:{id}", booking.slot_id, millis_key(booking.end_time));
+    write_txn
+        .open_table(BOOKINGS_BY_SLOT_END)?
+        .insert(key.as_str(), id.as_str())?;
+    write_txn
+        .open_table(BOOKING_SLOT_END_KEYS)?
+        .insert(id.as_str(), key.as_str())?;
+    Ok(())
+}
+
+fn index_live_booking_end(write_txn: &WriteTransaction, booking: &Booking) -> Result<()> {
+    let id = booking.id.to_string();
+    let key = format!("{}:{id}", millis_key(booking.end_time));
+    write_txn
+        .open_table(LIVE_BOOKINGS_BY_END)?
+        .insert(key.as_str(), id.as_str())?;
+    write_txn
+        .open_table(LIVE_BOOKING_END_KEYS)?
+        .insert(id.as_str(), key.as_str())?;
+    Ok(())
+}
+
+/// Drop `booking_id` from the time indexes.
+pub(super) fn unindex_booking_time(write_txn: &WriteTransaction, booking_id: &str) -> Result<()> {
+    let old_key = write_txn
+        .open_table(BOOKING_SLOT_END_KEYS)?
+        .remove(booking_id)?
+        .map(|key| key.value().to_string());
+    if let Some(old_key) = old_key {
+        write_txn
+            .open_table(BOOKINGS_BY_SLOT_END)?
+            .remove(old_key.as_str())?;
+    }
+    let old_key = write_txn
+        .open_table(LIVE_BOOKING_END_KEYS)?
+        .remove(booking_id)?
+        .map(|key| key.value().to_string());
+    if let Some(old_key) = old_key {
+        write_txn
+            .open_table(LIVE_BOOKINGS_BY_END)?
+            .remove(old_key.as_str())?;
+    }
+    Ok(())
+}
+
+/// Bookings on `slot_id` overlapping `range`, read from `idx` and `bookings`
+/// of one transaction.
+fn slot_bookings_overlapping(
+    db: &Database,
+    idx: &impl ReadableTable<&'static str, &'static str>,
+    bookings: &impl ReadableTable<&'static str, &'static [u8]>,
+    slot_id: Uuid,
+    range: &TimeRange,
+) -> Result<Vec<Booking>> {
+    // Keys are truncated to milliseconds; the exact overlap check below
+    // drops the few extra bookings this admits.
+    let start = format!("{slot_id}:{}", millis_key(range.start));
+    let end = format!("{slot_id};");
+    let mut overlapping = Vec::new();
+    for entry in idx.range(start.as_str()..end.as_str())? {
+        let (_, booking_id) = entry?;
+        let Some(value) = bookings.get(booking_id.value())? else {
+            continue;
+        };
+        let booking: Booking = db.deserialize(value.value())?;
+        if is_live(&booking.status)
+            && TimeRange::new(booking.start_time, booking.end_time)
+                .is_some_and(|booked| booked.overlaps(range))
+        {
+            overlapping.push(booking);
+        }
+    }
+    Ok(overlapping)
+}
+
+/// Buffer kept free between bookings on the slots of `lot_id`, as seen
+/// by `write_txn`. Unset or unreadable rules mean no buffer.
+pub(super) fn lot_buffer(write_txn: &WriteTransaction, lot_id: Uuid) -> Result<Duration> {
+    let table = write_txn.open_table(SETTINGS)?;
+    let rules = table
+        .get(lot_rules_key(&lot_id.to_string()).as_str())?
+        .and_then(|raw| serde_json::from_str::<LotBookingRules>(raw.value()).ok())
+        .unwrap_or_default();
+    Ok(rules.buffer())
+}
+
+impl Database {
+    /// Pending, confirmed and active bookings on any of `slot_ids` that
+    /// overlap `range`.
+    pub async fn list_slot_bookings_overlapping(
+        &self,
+        slot_ids: &[Uuid],
+        range: &TimeRange,
+    ) -> Result<Vec<Booking>> {
+        let _timer = Self::time_read("bookings", None);
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let idx = read_txn.open_table(BOOKINGS_BY_SLOT_END)?;
+        let table = read_txn.open_table(BOOKINGS)?;
+
+        let mut bookings = Vec::new();
+        for slot_id in slot_ids {
+            bookings.extend(slot_bookings_overlapping(
+                self, &idx, &table, *slot_id, range,
+            )?);
+        }
+        Ok(bookings)
+    }
+
+    /// Pending, confirmed and active bookings, with or without a slot,
+    /// that ended at or before `now`, oldest end first.
+    pub async fn list_live_bookings_ended_by(&self, now: DateTime<Utc>) -> Result<Vec<Booking>> {
+        let _timer = Self::time_read("bookings", None);
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let idx = read_txn.open_table(LIVE_BOOKINGS_BY_END)?;
+        let table = read_txn.open_table(BOOKINGS)?;
+
+        // Keys are truncated to milliseconds; the exact check below drops a
+        // booking ending later within the same millisecond.
+        let end = format!("{};", millis_key(now));
+        let mut ended = Vec::new();
+        for entry in idx.range(..end.as_str())? {
+            let (_, booking_id) = entry?;
+            let Some(value) = table.get(booking_id.value())? else {
+                continue;
+            };
+            let booking: Booking = self.deserialize(value.value())?;
+            if is_live(&booking.status) && booking.end_time <= now {
+                ended.push(booking);
+            }
+        }
+        Ok(ended)
+    }
+
+    /// A live booking other than `booking_id` on `slot_id` that overlaps
+    /// `range`, as seen by `write_txn`. Must run before the transaction
+    /// opens `BOOKINGS` for writing.
+    pub(super) fn find_slot_conflict(
+        &self,
+        write_txn: &WriteTransaction,
+        slot_id: Uuid,
+        range: &TimeRange,
+        booking_id: &str,
+    ) -> Result<Option<Booking>> {
+        let idx = write_txn.open_table(BOOKINGS_BY_SLOT_END)?;
+        let table = write_txn.open_table(BOOKINGS)?;
+        Ok(
+            slot_bookings_overlapping(self, &idx, &table, slot_id, range)?
+                .into_iter()
+                .find(|existing| existing.id.to_string() != booking_id),
+        )
+    }
+
+    /// Index the bookings stored before the time indexes existed.
+    ///
+    /// Runs once at startup; fills each index that is still empty, so a
+    /// no-op once any booking is indexed in both. Undecodable records are
+    /// skipped rather than failing the open.
+    pub(super) fn index_existing_booking_times(&self) -> Result<()> {
+        let db = self
+            .inner
+            .try_read()
+            .map_err(|_| anyhow::anyhow!("Database is locked"))?;
+        let write_txn = db.begin_write()?;
+        let by_slot = write_txn.open_table(BOOKING_SLOT_END_KEYS)?.is_empty()?;
+        let by_end = write_txn.open_table(LIVE_BOOKING_END_KEYS)?.is_empty()?;
+        if !by_slot && !by_end {
+            return Ok(());
+        }
+
+        let bookings: Vec<Booking> = self.decode_all(&write_txn, BOOKINGS)?;
+        let mut indexed = 0usize;
+        for booking in bookings.iter().filter(|b| is_live(&b.status)) {
+            if by_end {
+                index_live_booking_end(&write_txn, booking)?;
+            }
+            if by_slot && !booking.slot_id.is_nil() {
+                index_slot_booking_end(&write_txn, booking)?;
+            }
+            indexed += 1;
+        }
+        write_txn.commit()?;
+
+        if indexed > 0 {
+            info!("Built booking time index for {} booking(s)", indexed);
+        }
+        Ok(())
+    }
+}
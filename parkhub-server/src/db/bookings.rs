@@ -2,31 +2,71 @@
 //! recurring bookings, and waitlist persistence.
 
 use anyhow::Result;
-use chrono::NaiveDate;
-use redb::{ReadableDatabase, ReadableTable, ReadableTableMetadata};
+use chrono::{DateTime, NaiveDate, Utc};
+use redb::{ReadableDatabase, ReadableTable, WriteTransaction};
 use tracing::debug;
 
+use parkhub_common::TimeRange;
 use parkhub_common::models::{
     Booking, BookingStatus, GuestBooking, RecurringBooking, SwapRequest, WaitlistEntry,
 };
 
+use super::booking_attachments::remove_booking_extras;
+use super::booking_times::{index_booking_time, lot_buffer, unindex_booking_time};
+use super::list_order::{index_booking_order, page_in_order, unindex_booking_order};
 use super::{
-    BOOKINGS, BOOKINGS_BY_USER, Database, GUEST_BOOKINGS, RECURRING_BOOKINGS, SWAP_REQUESTS,
-    WAITLIST, pagination_offset,
+    BOOKINGS, BOOKINGS_BY_START, BOOKINGS_BY_USER, BOOKINGS_BY_USER_START, Database,
+    GUEST_BOOKINGS, Page, PageQuery, RECURRING_BOOKINGS, SWAP_REQUESTS, SortBy, WAITLIST,
+    WriteBatch, paging,
 };
 
+/// License plate left on a booking once its personal data is stripped.
+pub(super) const ANONYMIZED_PLATE: &str = "[DELETED]";
+
+/// Strip the license plate, vehicle description and notes from `booking`.
+/// Returns `false` if it held none of them.
+fn strip_personal_data(booking: &mut Booking) -> bool {
+    let vehicle = &mut booking.vehicle;
+    let has_plate = !vehicle.license_plate.is_empty() && vehicle.license_plate != ANONYMIZED_PLATE;
+    let had_data = has_plate
+        || vehicle.make.is_some()
+        || vehicle.model.is_some()
+        || vehicle.color.is_some()
+        || booking.notes.is_some();
+    if has_plate {
+        vehicle.license_plate = ANONYMIZED_PLATE.to_string();
+    }
+    vehicle.make = None;
+    vehicle.model = None;
+    vehicle.color = None;
+    booking.notes = None;
+    had_data
+}
+
 impl Database {
     // ── Booking CRUD ──
 
     /// Save a booking
     pub async fn save_booking(&self, booking: &Booking) -> Result<()> {
-        let id = booking.id.to_string();
-        let user_id = booking.user_id.to_string();
-        let data = self.serialize(booking)?;
-
+        let _timer = Self::time_write("bookings", Some(&booking.id.to_string()));
         let db = self.inner.write().await;
         let write_txn = db.begin_write()?;
         drop(db);
+        self.put_booking(&write_txn, booking)?;
+        write_txn.commit()?;
+        debug!("Saved booking: {}", booking.id);
+        Ok(())
+    }
+
+    /// Write `booking` and its indexes within `write_txn`.
+    pub(super) fn put_booking(
+        &self,
+        write_txn: &WriteTransaction,
+        booking: &Booking,
+    ) -> Result<()> {
+        let id = booking.id.to_string();
+        let user_id = booking.user_id.to_string();
+        let data = self.serialize(booking)?;
         {
             let mut table = write_txn.open_table(BOOKINGS)?;
             table.insert(id.as_str(), data.as_slice())?;
@@ -36,13 +76,61 @@ impl Database {
             let idx_key = format!("{user_id}:{id}");
             idx.insert(idx_key.as_str(), id.as_str())?;
         }
+        self.index_booking(write_txn, booking)?;
+        index_booking_order(write_txn, booking)?;
+        index_booking_time(write_txn, booking)
+    }
+
+    /// Atomically claim `booking.slot_id` for `booking`'s time range.
+    ///
+    /// The overlap check and the insert run in one redb write transaction,
+    /// and redb admits a single writer at a time, so concurrent claims for
+    /// the same slot are serialised by the storage layer itself — no
+    /// application-level lock is required. Returns `false` without writing
+    /// anything if another pending, confirmed or active booking on the slot
+    /// overlaps the range, or ends or starts within the lot's buffer of it.
+    pub async fn claim_slot(&self, booking: &Booking) -> Result<bool> {
+        let _timer = Self::time_write("bookings", Some(&booking.id.to_string()));
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        if !self.try_claim_slot(&write_txn, booking)? {
+            // Dropping the transaction aborts it.
+            return Ok(false);
+        }
         write_txn.commit()?;
-        debug!("Saved booking: {}", booking.id);
-        Ok(())
+        debug!(
+            "Claimed slot {} for booking {}",
+            booking.slot_id, booking.id
+        );
+        Ok(true)
+    }
+
+    /// Write `booking` within `write_txn` unless another live booking on its
+    /// slot overlaps it; see [`Self::claim_slot`].
+    pub(super) fn try_claim_slot(
+        &self,
+        write_txn: &WriteTransaction,
+        booking: &Booking,
+    ) -> Result<bool> {
+        let id = booking.id.to_string();
+        let range = TimeRange::new(booking.start_time, booking.end_time)
+            .ok_or_else(|| anyhow::anyhow!("Booking {id} has an empty time range"))?
+            .padded(lot_buffer(write_txn, booking.lot_id)?);
+        if let Some(existing) = self.find_slot_conflict(write_txn, booking.slot_id, &range, &id)? {
+            debug!(
+                "Slot {} already claimed by booking {}",
+                booking.slot_id, existing.id
+            );
+            return Ok(false);
+        }
+        self.put_booking(write_txn, booking)?;
+        Ok(true)
     }
 
     /// Get a booking by ID (string)
     pub async fn get_booking(&self, id: &str) -> Result<Option<Booking>> {
+        let _timer = Self::time_read("bookings", Some(id));
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
@@ -56,6 +144,7 @@ impl Database {
 
     /// List all bookings
     pub async fn list_bookings(&self) -> Result<Vec<Booking>> {
+        let _timer = Self::time_read("bookings", None);
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
@@ -69,26 +158,38 @@ impl Database {
         Ok(bookings)
     }
 
-    /// List bookings with pagination. Returns (`page_items`, `total_count`).
-    pub async fn list_bookings_paginated(
+    /// The page of bookings selected by `query` among those `filter` accepts
+    /// (every booking for `None`), ordered by `sort`. The default order, by
+    /// start time, is read from `BOOKINGS_BY_START`.
+    pub async fn list_bookings_page(
         &self,
-        page: i32,
-        per_page: i32,
-    ) -> Result<(Vec<Booking>, usize)> {
-        let db = self.inner.read().await;
-        let read_txn = db.begin_read()?;
-        drop(db);
-        let table = read_txn.open_table(BOOKINGS)?;
-
-        let total = table.len()? as usize;
-        let (skip, per_page) = pagination_offset(page, per_page);
-
-        let mut bookings = Vec::with_capacity(per_page.min(total.saturating_sub(skip)));
-        for entry in table.iter()?.skip(skip).take(per_page) {
-            let (_, value) = entry?;
-            bookings.push(self.deserialize(value.value())?);
-        }
-        Ok((bookings, total))
+        filter: Option<impl Fn(&Booking) -> bool>,
+        sort: SortBy<Booking>,
+        query: &PageQuery,
+    ) -> Result<Page<Booking>> {
+        let SortBy::Key(sort_key) = sort else {
+            let _timer = Self::time_read("bookings", None);
+            let db = self.inner.read().await;
+            let read_txn = db.begin_read()?;
+            drop(db);
+            return page_in_order(
+                self,
+                &read_txn,
+                BOOKINGS_BY_START,
+                "",
+                BOOKINGS,
+                filter.as_ref(),
+                query,
+            );
+        };
+        let bookings = self.list_bookings().await?;
+        Ok(paging::page(
+            bookings,
+            |b| b.id.to_string(),
+            sort_key,
+            |b| filter.as_ref().is_none_or(|filter| filter(b)),
+            query,
+        ))
     }
 
     /// Get bookings for a user using the `BOOKINGS_BY_USER` secondary index.
@@ -119,8 +220,44 @@ impl Database {
         Ok(bookings)
     }
 
-    /// Count non-cancelled bookings for a user on a specific calendar day.
-    /// Uses the canonical BOOKINGS table so policy enforcement does not rely on
+    /// The page of `user_id`'s bookings selected by `query` among those
+    /// `filter` accepts (all of them for `None`), ordered by `sort`. The
+    /// default order, by start time, is read from `BOOKINGS_BY_USER_START`.
+    pub async fn list_bookings_by_user_page(
+        &self,
+        user_id: &str,
+        filter: Option<impl Fn(&Booking) -> bool>,
+        sort: SortBy<Booking>,
+        query: &PageQuery,
+    ) -> Result<Page<Booking>> {
+        let SortBy::Key(sort_key) = sort else {
+            let db = self.inner.read().await;
+            let read_txn = db.begin_read()?;
+            drop(db);
+            return page_in_order(
+                self,
+                &read_txn,
+                BOOKINGS_BY_USER_START,
+                &format!("{user_id}:"),
+                BOOKINGS,
+                filter.as_ref(),
+                query,
+            );
+        };
+        let bookings = self.list_bookings_by_user(user_id).await?;
+        Ok(paging::page(
+            bookings,
+            |b| b.id.to_string(),
+            sort_key,
+            |b| filter.as_ref().is_none_or(|filter| filter(b)),
+            query,
+        ))
+    }
+
+    /// Count non-cancelled bookings for a user that touch a specific calendar
+    /// day (UTC), including overnight and multi-day bookings that started
+    /// earlier. Bookings the user made for visitors are not counted. Uses the
+    /// canonical BOOKINGS table so policy enforcement does not rely on
     /// secondary-index freshness.
     pub async fn count_bookings_for_user_on_day(
         &self,
@@ -138,7 +275,9 @@ impl Database {
             let (_key, value) = entry?;
             let booking: Booking = self.deserialize(value.value())?;
             if booking.user_id.to_string() == user_id
-                && booking.start_time.date_naive() == booking_date
+                && booking.booked_for.is_none()
+                && TimeRange::new(booking.start_time, booking.end_time)
+                    .is_some_and(|range| range.touches_day(booking_date))
                 && booking.status != BookingStatus::Cancelled
             {
                 count += 1;
@@ -180,32 +319,75 @@ impl Database {
             }
             result.is_some()
         };
+        if existed {
+            self.unindex_booking(&write_txn, id)?;
+            unindex_booking_order(&write_txn, id)?;
+            unindex_booking_time(&write_txn, id)?;
+            remove_booking_extras(&write_txn, id)?;
+        }
         write_txn.commit()?;
         if existed {
+            self.remove_attachment_files(Some(id)).await;
             debug!("Deleted booking: {}", id);
         }
         Ok(existed)
     }
 
+    /// Data minimization: strip plates, vehicle descriptions and notes from
+    /// settled bookings (completed, cancelled, expired or no-show) that
+    /// ended before `cutoff`. Times, status and pricing stay for accounting;
+    /// the account itself is untouched, unlike [`Database::anonymize_user`].
+    /// All bookings are rewritten in one transaction. Returns how many held
+    /// personal data.
+    pub async fn anonymize_bookings_ended_before(&self, cutoff: DateTime<Utc>) -> Result<usize> {
+        let mut batch = WriteBatch::new();
+        let mut anonymized = 0;
+        for mut booking in self.list_bookings().await? {
+            let settled = matches!(
+                booking.status,
+                BookingStatus::Completed
+                    | BookingStatus::Cancelled
+                    | BookingStatus::Expired
+                    | BookingStatus::NoShow
+            );
+            if settled && booking.end_time < cutoff && strip_personal_data(&mut booking) {
+                batch.save_booking(&booking);
+                anonymized += 1;
+            }
+        }
+        if anonymized > 0 {
+            self.apply_batch(&batch).await?;
+            debug!("Anonymized {anonymized} booking(s) ended before {cutoff}");
+        }
+        Ok(anonymized)
+    }
+
     // ── Waitlist CRUD ──
 
     /// Save a waitlist entry
     pub async fn save_waitlist_entry(&self, entry: &WaitlistEntry) -> Result<()> {
-        let id = entry.id.to_string();
-        let data = self.serialize(entry)?;
-
         let db = self.inner.write().await;
         let write_txn = db.begin_write()?;
         drop(db);
-        {
-            let mut table = write_txn.open_table(WAITLIST)?;
-            table.insert(id.as_str(), data.as_slice())?;
-        }
+        self.put_waitlist_entry(&write_txn, entry)?;
         write_txn.commit()?;
         debug!("Saved waitlist entry: {}", entry.id);
         Ok(())
     }
 
+    /// Write `entry` within `write_txn`.
+    pub(super) fn put_waitlist_entry(
+        &self,
+        write_txn: &WriteTransaction,
+        entry: &WaitlistEntry,
+    ) -> Result<()> {
+        let data = self.serialize(entry)?;
+        write_txn
+            .open_table(WAITLIST)?
+            .insert(entry.id.to_string().as_str(), data.as_slice())?;
+        Ok(())
+    }
+
     /// Get a waitlist entry by ID
     pub async fn get_waitlist_entry(&self, id: &str) -> Result<Option<WaitlistEntry>> {
         let db = self.inner.read().await;
@@ -2,7 +2,7 @@
 //! subscriptions, webhooks, and credit transactions.
 
 use anyhow::Result;
-use redb::{ReadableDatabase, ReadableTable};
+use redb::{ReadableDatabase, ReadableTable, WriteTransaction};
 use tracing::debug;
 use uuid::Uuid;
 
@@ -149,18 +149,27 @@ impl Database {
         &self,
         tx: &parkhub_common::models::CreditTransaction,
     ) -> Result<()> {
-        let data = self.serialize(tx)?;
         let db = self.inner.write().await;
         let write_txn = db.begin_write()?;
         drop(db);
-        {
-            let mut table = write_txn.open_table(CREDIT_TRANSACTIONS)?;
-            table.insert(tx.id.to_string().as_str(), data.as_slice())?;
-        }
+        self.put_credit_transaction(&write_txn, tx)?;
         write_txn.commit()?;
         Ok(())
     }
 
+    /// Write `tx` within `write_txn`.
+    pub(super) fn put_credit_transaction(
+        &self,
+        write_txn: &WriteTransaction,
+        tx: &parkhub_common::models::CreditTransaction,
+    ) -> Result<()> {
+        let data = self.serialize(tx)?;
+        write_txn
+            .open_table(CREDIT_TRANSACTIONS)?
+            .insert(tx.id.to_string().as_str(), data.as_slice())?;
+        Ok(())
+    }
+
     pub async fn list_credit_transactions_for_user(
         &self,
         user_id: uuid::Uuid,
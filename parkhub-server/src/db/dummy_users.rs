@@ -0,0 +1,66 @@
+//! Marker for the dummy accounts generated during setup.
+//!
+//! `DUMMY_USERS` holds the ID of every user created by the setup wizard's
+//! "generate dummy users" option, with the time it was generated. The
+//! accounts share one password, so admins list and purge them before going
+//! live; deleting a user drops its marker.
+
+use anyhow::Result;
+use chrono::Utc;
+use redb::{ReadableDatabase, ReadableTable};
+use tracing::debug;
+use uuid::Uuid;
+
+use parkhub_common::models::User;
+
+use super::{DUMMY_USERS, Database, USERS};
+
+impl Database {
+    /// Mark `user_ids` as generated dummy accounts
+    pub async fn mark_dummy_users(&self, user_ids: &[Uuid]) -> Result<()> {
+        let marked_at = Utc::now().to_rfc3339();
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(DUMMY_USERS)?;
+            for id in user_ids {
+                table.insert(id.to_string().as_str(), marked_at.as_str())?;
+            }
+        }
+        write_txn.commit()?;
+        debug!("Marked {} dummy user(s)", user_ids.len());
+        Ok(())
+    }
+
+    /// The generated dummy accounts that still exist, by username
+    pub async fn list_dummy_users(&self) -> Result<Vec<User>> {
+        let _timer = Self::time_read("dummy_users", None);
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let markers = read_txn.open_table(DUMMY_USERS)?;
+        let table = read_txn.open_table(USERS)?;
+
+        let mut users = Vec::new();
+        for entry in markers.iter()? {
+            let (id, _) = entry?;
+            if let Some(value) = table.get(id.value())? {
+                users.push(self.deserialize::<User>(value.value())?);
+            }
+        }
+        users.sort_by(|a, b| a.username.cmp(&b.username));
+        Ok(users)
+    }
+
+    /// Delete every generated dummy account together with its sessions.
+    /// Returns the deleted users.
+    pub async fn purge_dummy_users(&self) -> Result<Vec<User>> {
+        let users = self.list_dummy_users().await?;
+        for user in &users {
+            self.delete_sessions_by_user(user.id).await?;
+            self.delete_user(&user.id.to_string()).await?;
+        }
+        Ok(users)
+    }
+}
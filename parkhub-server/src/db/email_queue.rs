@@ -0,0 +1,127 @@
+//! Persistent outbound email queue.
+//!
+//! Messages are stored before the first delivery attempt and removed once
+//! the SMTP server accepts them. Messages that exhaust their retries stay in
+//! the table as [`QueuedEmailStatus::Failed`] (dead letters) until an admin
+//! retries them or they age out. Delivery itself lives in
+//! [`crate::email_queue`].
+
+#![cfg_attr(not(feature = "mod-email"), allow(dead_code))]
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+
+use super::{Database, EMAIL_QUEUE};
+
+/// Delivery state of a queued email.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueuedEmailStatus {
+    /// Waiting for its next delivery attempt
+    Pending,
+    /// Retries exhausted; only sent again on manual retry
+    Failed,
+}
+
+/// An outbound email that has not been delivered yet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedEmail {
+    pub id: Uuid,
+    pub to: String,
+    pub subject: String,
+    pub html_body: String,
+    pub status: QueuedEmailStatus,
+    /// Delivery attempts made so far
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Earliest time the next attempt may run (only meaningful while pending)
+    pub next_attempt_at: DateTime<Utc>,
+}
+
+impl QueuedEmail {
+    /// A new pending message, due immediately.
+    #[must_use]
+    pub fn new(to: &str, subject: &str, html_body: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            to: to.to_string(),
+            subject: subject.to_string(),
+            html_body: html_body.to_string(),
+            status: QueuedEmailStatus::Pending,
+            attempts: 0,
+            last_error: None,
+            created_at: now,
+            updated_at: now,
+            next_attempt_at: now,
+        }
+    }
+}
+
+impl Database {
+    /// Insert or update a queued email
+    pub async fn save_queued_email(&self, email: &QueuedEmail) -> Result<()> {
+        let id = email.id.to_string();
+        let data = self.serialize(email)?;
+
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(EMAIL_QUEUE)?;
+            table.insert(id.as_str(), data.as_slice())?;
+        }
+        write_txn.commit()?;
+        debug!("Saved queued email: {}", email.id);
+        Ok(())
+    }
+
+    /// Get a queued email by ID
+    pub async fn get_queued_email(&self, id: &str) -> Result<Option<QueuedEmail>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(EMAIL_QUEUE)?;
+
+        match table.get(id)? {
+            Some(value) => Ok(Some(self.deserialize(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List all queued emails, oldest first
+    pub async fn list_queued_emails(&self) -> Result<Vec<QueuedEmail>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(EMAIL_QUEUE)?;
+
+        let mut emails: Vec<QueuedEmail> = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            emails.push(self.deserialize(value.value())?);
+        }
+        emails.sort_by_key(|e| e.created_at);
+        Ok(emails)
+    }
+
+    /// Delete a queued email
+    pub async fn delete_queued_email(&self, id: &str) -> Result<bool> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let existed = {
+            let mut table = write_txn.open_table(EMAIL_QUEUE)?;
+            let result = table.remove(id)?;
+            result.is_some()
+        };
+        write_txn.commit()?;
+        Ok(existed)
+    }
+}
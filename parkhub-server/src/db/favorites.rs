@@ -1,4 +1,4 @@
-//! Favorites: per-user pinned parking slots.
+//! Favorites: per-user pinned parking slots and lots.
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use tracing::debug;
 use uuid::Uuid;
 
-use super::{Database, FAVORITES};
+use super::{Database, FAVORITE_LOTS, FAVORITES};
 
 /// A user's favorite parking slot
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,6 +18,14 @@ pub struct Favorite {
     pub created_at: DateTime<Utc>,
 }
 
+/// A user's favorite parking lot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FavoriteLot {
+    pub user_id: Uuid,
+    pub lot_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
 impl Database {
     /// Save a favorite (user pins a parking slot)
     pub async fn save_favorite(&self, fav: &Favorite) -> Result<()> {
@@ -72,4 +80,79 @@ impl Database {
         }
         Ok(existed)
     }
+
+    /// List every favorite pinned on a slot, across all users
+    pub async fn list_favorites_by_slot(&self, slot_id: &str) -> Result<Vec<Favorite>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(FAVORITES)?;
+
+        let suffix = format!(":{slot_id}");
+        let mut favs = Vec::new();
+        for entry in table.iter()? {
+            let (key, value) = entry?;
+            if key.value().ends_with(&suffix) {
+                favs.push(self.deserialize(value.value())?);
+            }
+        }
+        Ok(favs)
+    }
+
+    /// Save a favorite lot (user pins a whole parking lot)
+    pub async fn save_favorite_lot(&self, fav: &FavoriteLot) -> Result<()> {
+        let key = format!("{}:{}", fav.user_id, fav.lot_id);
+        let data = self.serialize(fav)?;
+
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(FAVORITE_LOTS)?;
+            table.insert(key.as_str(), data.as_slice())?;
+        }
+        write_txn.commit()?;
+        debug!(
+            "Saved favorite lot: user={}, lot={}",
+            fav.user_id, fav.lot_id
+        );
+        Ok(())
+    }
+
+    /// List all favorite lots for a user
+    pub async fn list_favorite_lots_by_user(&self, user_id: &str) -> Result<Vec<FavoriteLot>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(FAVORITE_LOTS)?;
+
+        let prefix = format!("{user_id}:");
+        let mut favs = Vec::new();
+        for entry in table.iter()? {
+            let (key, value) = entry?;
+            if key.value().starts_with(&prefix) {
+                favs.push(self.deserialize(value.value())?);
+            }
+        }
+        Ok(favs)
+    }
+
+    /// Delete a favorite lot by `user_id` and `lot_id`
+    pub async fn delete_favorite_lot(&self, user_id: &str, lot_id: &str) -> Result<bool> {
+        let key = format!("{user_id}:{lot_id}");
+
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let existed = {
+            let mut table = write_txn.open_table(FAVORITE_LOTS)?;
+            let result = table.remove(key.as_str())?;
+            result.is_some()
+        };
+        write_txn.commit()?;
+        if existed {
+            debug!("Deleted favorite lot: user={}, lot={}", user_id, lot_id);
+        }
+        Ok(existed)
+    }
 }
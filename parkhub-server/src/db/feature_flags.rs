@@ -0,0 +1,66 @@
+//! Per-deployment feature flag switches.
+//!
+//! `FEATURE_FLAGS` keeps one row per flag an admin has switched, keyed by
+//! the flag's key (see `crate::api::feature_flags`). Flags without a row
+//! use their built-in default. Like settings, the rows survive
+//! `clear_all_data`.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Database, FEATURE_FLAGS};
+
+/// An admin's choice for one feature flag.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureFlagSwitch {
+    pub key: String,
+    pub enabled: bool,
+    /// Admin who last switched the flag
+    pub updated_by: Uuid,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Database {
+    /// Store `switch`, replacing the earlier one for its flag.
+    pub async fn save_feature_flag(&self, switch: &FeatureFlagSwitch) -> Result<()> {
+        let data = self.serialize(switch)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        write_txn
+            .open_table(FEATURE_FLAGS)?
+            .insert(switch.key.as_str(), data.as_slice())?;
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// The stored switch for flag `key`, if an admin ever set it.
+    pub async fn get_feature_flag(&self, key: &str) -> Result<Option<FeatureFlagSwitch>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(FEATURE_FLAGS)?;
+        match table.get(key)? {
+            Some(value) => Ok(Some(self.deserialize(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// All stored switches.
+    pub async fn list_feature_flags(&self) -> Result<Vec<FeatureFlagSwitch>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(FEATURE_FLAGS)?;
+
+        let mut switches = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            switches.push(self.deserialize(value.value())?);
+        }
+        Ok(switches)
+    }
+}
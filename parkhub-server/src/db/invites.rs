@@ -0,0 +1,125 @@
+//! Pending user invitations.
+//!
+//! `INVITES` holds invitations an admin sent but nobody has accepted yet,
+//! keyed by the SHA-256 of the one-time token mailed to the invitee — the
+//! token itself is never stored, so a copy of the database cannot be used
+//! to sign up.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use parkhub_common::UserRole;
+use redb::{ReadableDatabase, ReadableTable};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use uuid::Uuid;
+
+use super::{Database, INVITES};
+
+/// An invitation to create an account with a pre-assigned role.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Invite {
+    pub id: Uuid,
+    /// Address the invitation was sent to; the account is created with it
+    pub email: String,
+    pub role: UserRole,
+    /// Admin who sent the invitation
+    pub invited_by: Uuid,
+    /// Tenant the new account joins (the inviting admin's)
+    pub tenant_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl Invite {
+    pub fn is_expired(&self) -> bool {
+        Utc::now() > self.expires_at
+    }
+}
+
+fn invite_key(token: &str) -> String {
+    hex::encode(Sha256::digest(format!("invite:{token}").as_bytes()))
+}
+
+impl Database {
+    /// Store `invite` under its one-time `token`.
+    pub async fn save_invite(&self, token: &str, invite: &Invite) -> Result<()> {
+        let key = invite_key(token);
+        let data = self.serialize(invite)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(INVITES)?;
+            table.insert(key.as_str(), data.as_slice())?;
+        }
+        write_txn.commit()?;
+        debug!("Saved invite {} for {}", invite.id, invite.email);
+        Ok(())
+    }
+
+    /// Remove and return the invitation for `token`, so it can be used only
+    /// once. Expired invitations are returned too; the caller rejects them.
+    pub async fn take_invite(&self, token: &str) -> Result<Option<Invite>> {
+        let key = invite_key(token);
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let removed = {
+            let mut table = write_txn.open_table(INVITES)?;
+            let value = table.remove(key.as_str())?;
+            value.map(|v| v.value().to_vec())
+        };
+        write_txn.commit()?;
+        removed.map(|data| self.deserialize(&data)).transpose()
+    }
+
+    /// List pending invitations, newest first
+    pub async fn list_invites(&self) -> Result<Vec<Invite>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(INVITES)?;
+        let mut invites = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            invites.push(self.deserialize::<Invite>(value.value())?);
+        }
+        invites.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(invites)
+    }
+
+    /// Delete the invitations matching `filter`; returns how many were removed.
+    async fn delete_invites_where(&self, filter: impl Fn(&Invite) -> bool) -> Result<u64> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let removed = {
+            let mut table = write_txn.open_table(INVITES)?;
+            let mut keys = Vec::new();
+            for entry in table.iter()? {
+                let (key, value) = entry?;
+                if filter(&self.deserialize::<Invite>(value.value())?) {
+                    keys.push(key.value().to_string());
+                }
+            }
+            for key in &keys {
+                table.remove(key.as_str())?;
+            }
+            keys.len() as u64
+        };
+        write_txn.commit()?;
+        Ok(removed)
+    }
+
+    /// Revoke an invitation. Returns `false` if there is none with that ID.
+    pub async fn delete_invite(&self, id: Uuid) -> Result<bool> {
+        Ok(self.delete_invites_where(|invite| invite.id == id).await? > 0)
+    }
+
+    /// Revoke every pending invitation for `email` (case-insensitive).
+    pub async fn delete_invites_for_email(&self, email: &str) -> Result<u64> {
+        self.delete_invites_where(|invite| invite.email.eq_ignore_ascii_case(email))
+            .await
+    }
+}
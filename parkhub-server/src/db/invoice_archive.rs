@@ -0,0 +1,168 @@
+//! Archive of issued invoices.
+//!
+//! An invoice is rendered once, when its number is first allocated, and the
+//! rendered documents are stored together with the data they were rendered
+//! from. Later downloads serve the stored copy, so a price, tax or lot change
+//! never alters an invoice that was already issued.
+//!
+//! `INVOICES` is keyed by invoice number; `INVOICES_BY_USER` indexes them by
+//! recipient (`"{user_id}:{invoice_number}"`). Issued invoices are accounting
+//! records and are kept when the booking or the user account is deleted.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+
+use super::{Database, INVOICES, INVOICES_BY_USER};
+
+/// Everything printed on an invoice, captured when it was issued.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InvoiceDetails {
+    /// Issuing organisation
+    pub company: String,
+    pub customer_name: String,
+    pub customer_email: String,
+    pub lot_name: String,
+    pub slot_number: i32,
+    pub floor_name: String,
+    /// Plate as shown to the booking owner
+    pub license_plate: String,
+    /// Invoice date (the booking's creation time)
+    pub invoice_date: DateTime<Utc>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    /// Booking status at issue time
+    pub status: String,
+    pub currency: String,
+    pub net: f64,
+    pub vat: f64,
+    pub gross: f64,
+    /// VAT rate as a fraction (0.0 for reverse charge)
+    pub vat_rate: f64,
+    pub reverse_charge: bool,
+}
+
+/// An issued invoice with its rendered documents.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedInvoice {
+    pub invoice_number: String,
+    pub booking_id: Uuid,
+    /// Invoice recipient (the booking owner)
+    pub user_id: Uuid,
+    pub issued_at: DateTime<Utc>,
+    pub details: InvoiceDetails,
+    pub html: String,
+    /// Base64-encoded PDF; `None` when the server was built without PDF
+    /// invoices at issue time
+    #[serde(default)]
+    pub pdf_base64: Option<String>,
+}
+
+fn user_key(user_id: Uuid, invoice_number: &str) -> String {
+    format!("{user_id}:{invoice_number}")
+}
+
+impl Database {
+    /// Archive `invoice` unless its number is already archived.
+    ///
+    /// Returns the archived invoice: `invoice` itself, or the copy stored by
+    /// an earlier (or concurrent) issue of the same number.
+    pub async fn archive_invoice(&self, invoice: &ArchivedInvoice) -> Result<ArchivedInvoice> {
+        let data = self.serialize(invoice)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(INVOICES)?;
+            if let Some(existing) = table.get(invoice.invoice_number.as_str())? {
+                return self.deserialize(existing.value());
+            }
+            table.insert(invoice.invoice_number.as_str(), data.as_slice())?;
+
+            let mut idx = write_txn.open_table(INVOICES_BY_USER)?;
+            let key = user_key(invoice.user_id, &invoice.invoice_number);
+            idx.insert(key.as_str(), invoice.invoice_number.as_str())?;
+        }
+        write_txn.commit()?;
+        debug!(
+            "Archived invoice {} for booking {}",
+            invoice.invoice_number, invoice.booking_id
+        );
+        Ok(invoice.clone())
+    }
+
+    /// Store the PDF of an invoice archived without one.
+    ///
+    /// A stored PDF is never replaced. Returns the archived invoice, or
+    /// `None` if the number is not archived.
+    pub async fn archive_invoice_pdf(
+        &self,
+        invoice_number: &str,
+        pdf_base64: String,
+    ) -> Result<Option<ArchivedInvoice>> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let invoice = {
+            let mut table = write_txn.open_table(INVOICES)?;
+            let Some(mut invoice) = table
+                .get(invoice_number)?
+                .map(|value| self.deserialize::<ArchivedInvoice>(value.value()))
+                .transpose()?
+            else {
+                return Ok(None);
+            };
+            if invoice.pdf_base64.is_some() {
+                return Ok(Some(invoice));
+            }
+            invoice.pdf_base64 = Some(pdf_base64);
+            let data = self.serialize(&invoice)?;
+            table.insert(invoice_number, data.as_slice())?;
+            invoice
+        };
+        write_txn.commit()?;
+        Ok(Some(invoice))
+    }
+
+    /// Get an archived invoice by number
+    pub async fn get_archived_invoice(
+        &self,
+        invoice_number: &str,
+    ) -> Result<Option<ArchivedInvoice>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(INVOICES)?;
+        match table.get(invoice_number)? {
+            Some(value) => Ok(Some(self.deserialize(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List the invoices issued to a user, newest first
+    pub async fn list_archived_invoices_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<ArchivedInvoice>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let idx = read_txn.open_table(INVOICES_BY_USER)?;
+        let table = read_txn.open_table(INVOICES)?;
+
+        let start = format!("{user_id}:");
+        let end = format!("{user_id};");
+        let mut invoices = Vec::new();
+        for entry in idx.range(start.as_str()..end.as_str())? {
+            let (_, number) = entry?;
+            if let Some(value) = table.get(number.value())? {
+                invoices.push(self.deserialize::<ArchivedInvoice>(value.value())?);
+            }
+        }
+        invoices.sort_by(|a, b| b.issued_at.cmp(&a.issued_at));
+        Ok(invoices)
+    }
+}
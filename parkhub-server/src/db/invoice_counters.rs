@@ -119,6 +119,12 @@ impl Database {
 
         Ok(number)
     }
+
+    /// Invoice number already assigned to `booking_id`, without allocating
+    /// one. Reports use this so listing bookings never burns counter values.
+    pub async fn get_assigned_invoice_number(&self, booking_id: &str) -> Result<Option<String>> {
+        self.get_setting(&assigned_key(booking_id)).await
+    }
 }
 
 #[cfg(test)]
@@ -202,6 +208,26 @@ mod tests {
         assert_eq!(other, "2026-0000002");
     }
 
+    #[tokio::test]
+    async fn test_get_assigned_invoice_number_does_not_allocate() {
+        let (_dir, db) = test_db();
+        assert_eq!(
+            db.get_assigned_invoice_number("booking-abc").await.unwrap(),
+            None
+        );
+
+        let assigned = db
+            .get_or_assign_invoice_number("booking-abc", 2026)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get_assigned_invoice_number("booking-abc").await.unwrap(),
+            Some(assigned)
+        );
+        // Looking up an unassigned booking left the counter untouched.
+        assert_eq!(db.next_invoice_number(2026).await.unwrap(), "2026-0000002");
+    }
+
     #[tokio::test]
     async fn test_invoice_number_concurrent_writes_no_gap() {
         let (_dir, db) = test_db();
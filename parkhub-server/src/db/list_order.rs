@@ -0,0 +1,244 @@
+//! Default orders of the paged lists.
+//!
+//! Bookings are listed by start time, users by sign-up time and lots by
+//! name unless another sort is asked for. Each of those orders is kept as an
+//! order index whose keys sort like the list: `"{sort_key}\x1f{id}"`, the
+//! position a page cursor names (see [`Cursor::index_key`]). A page is read
+//! by walking the index from the cursor, so only the records on it are
+//! loaded and nothing is sorted. `BOOKINGS_BY_USER_START` prefixes the key
+//! with the user ID for a user's own bookings. `LIST_ORDER_KEYS` remembers
+//! each record's key per index so a rewrite can drop it; all are updated in
+//! the same write transaction as the record.
+//!
+//! Sorting by any other field is the exception: those pages go through
+//! [`paging::page`](super::paging::page), which reads the whole list.
+
+use std::ops::Bound;
+
+use anyhow::Result;
+use redb::{
+    ReadTransaction, ReadableTableMetadata, TableDefinition, TableHandle, WriteTransaction,
+};
+use serde::de::DeserializeOwned;
+use tracing::info;
+
+use parkhub_common::models::{Booking, ParkingLot, User};
+
+use super::paging::{Cursor, Page, PageQuery, PageStart, text_key, time_key};
+use super::{
+    BOOKINGS, BOOKINGS_BY_START, BOOKINGS_BY_USER_START, Database, LIST_ORDER_KEYS, LOTS_BY_NAME,
+    PARKING_LOTS, USERS, USERS_BY_CREATED,
+};
+
+type OrderIndex = TableDefinition<'static, &'static str, &'static str>;
+type RecordTable = TableDefinition<'static, &'static str, &'static [u8]>;
+
+/// Point `index` at record `id` under `key`, dropping its previous key;
+/// `None` only drops it.
+fn set_order_key(
+    write_txn: &WriteTransaction,
+    index: OrderIndex,
+    id: &str,
+    key: Option<&str>,
+) -> Result<()> {
+    let slot = format!("{}:{id}", index.name());
+    let mut keys = write_txn.open_table(LIST_ORDER_KEYS)?;
+    let mut idx = write_txn.open_table(index)?;
+    let old_key = keys
+        .remove(slot.as_str())?
+        .map(|key| key.value().to_string());
+    if let Some(old_key) = old_key {
+        idx.remove(old_key.as_str())?;
+    }
+    if let Some(key) = key {
+        idx.insert(key, id)?;
+        keys.insert(slot.as_str(), key)?;
+    }
+    Ok(())
+}
+
+/// Place `booking` in the booking orders as it is being stored.
+pub(super) fn index_booking_order(write_txn: &WriteTransaction, booking: &Booking) -> Result<()> {
+    let id = booking.id.to_string();
+    let key = Cursor::new(time_key(booking.start_time), id.clone()).index_key();
+    set_order_key(write_txn, BOOKINGS_BY_START, &id, Some(&key))?;
+    let user_key = format!("{}:{key}", booking.user_id);
+    set_order_key(write_txn, BOOKINGS_BY_USER_START, &id, Some(&user_key))
+}
+
+/// Drop `booking_id` from the booking orders.
+pub(super) fn unindex_booking_order(write_txn: &WriteTransaction, booking_id: &str) -> Result<()> {
+    set_order_key(write_txn, BOOKINGS_BY_START, booking_id, None)?;
+    set_order_key(write_txn, BOOKINGS_BY_USER_START, booking_id, None)
+}
+
+/// Place `user` in the user order as it is being stored.
+pub(super) fn index_user_order(write_txn: &WriteTransaction, user: &User) -> Result<()> {
+    let id = user.id.to_string();
+    let key = Cursor::new(time_key(user.created_at), id.clone()).index_key();
+    set_order_key(write_txn, USERS_BY_CREATED, &id, Some(&key))
+}
+
+/// Drop `user_id` from the user order.
+pub(super) fn unindex_user_order(write_txn: &WriteTransaction, user_id: &str) -> Result<()> {
+    set_order_key(write_txn, USERS_BY_CREATED, user_id, None)
+}
+
+/// Place `lot` in the lot order as it is being stored.
+pub(super) fn index_lot_order(write_txn: &WriteTransaction, lot: &ParkingLot) -> Result<()> {
+    let id = lot.id.to_string();
+    let key = Cursor::new(text_key(&lot.name), id.clone()).index_key();
+    set_order_key(write_txn, LOTS_BY_NAME, &id, Some(&key))
+}
+
+/// Drop `lot_id` from the lot order.
+pub(super) fn unindex_lot_order(write_txn: &WriteTransaction, lot_id: &str) -> Result<()> {
+    set_order_key(write_txn, LOTS_BY_NAME, lot_id, None)
+}
+
+/// The page of `records` selected by `query` among those `filter` accepts,
+/// in the order of `index`. `prefix` is empty or `"{user_id}:"` for a
+/// user's keys; index values are record IDs.
+///
+/// Without a filter the total is the number of keys and only the page's
+/// records are read. With one, every record is read once more to count the
+/// matches.
+pub(super) fn page_in_order<T: DeserializeOwned>(
+    db: &Database,
+    read_txn: &ReadTransaction,
+    index: OrderIndex,
+    prefix: &str,
+    records: RecordTable,
+    filter: Option<&impl Fn(&T) -> bool>,
+    query: &PageQuery,
+) -> Result<Page<T>> {
+    let idx = read_txn.open_table(index)?;
+    let table = read_txn.open_table(records)?;
+    let read = |id: &str| -> Result<Option<T>> {
+        table
+            .get(id)?
+            .map(|value| db.deserialize(value.value()))
+            .transpose()
+    };
+    let accepts = |record: &T| filter.is_none_or(|filter| filter(record));
+
+    // ';' sorts right after the ':' ending a user prefix
+    let first = Bound::Included(prefix.to_string());
+    let last = prefix
+        .strip_suffix(':')
+        .map_or(Bound::Unbounded, |user| Bound::Excluded(format!("{user};")));
+    let (lower, upper, mut skip) = match &query.start {
+        PageStart::Offset(skip) => (first.clone(), last.clone(), *skip),
+        PageStart::After(cursor) => {
+            let at = Bound::Excluded(format!("{prefix}{}", cursor.index_key()));
+            if query.descending {
+                (first.clone(), at, 0)
+            } else {
+                (at, last.clone(), 0)
+            }
+        }
+    };
+    let range = idx.range::<&str>((
+        lower.as_ref().map(String::as_str),
+        upper.as_ref().map(String::as_str),
+    ))?;
+    let entries: Box<dyn Iterator<Item = _>> = if query.descending {
+        Box::new(range.rev())
+    } else {
+        Box::new(range)
+    };
+
+    let mut items: Vec<(String, T)> = Vec::new();
+    for entry in entries {
+        let (key, id) = entry?;
+        let Some(record) = read(id.value())? else {
+            continue;
+        };
+        if !accepts(&record) {
+            continue;
+        }
+        if skip > 0 {
+            skip -= 1;
+            continue;
+        }
+        items.push((key.value().to_string(), record));
+        if items.len() > query.limit {
+            break;
+        }
+    }
+    let next_cursor = if items.len() > query.limit {
+        items.truncate(query.limit);
+        items
+            .last()
+            .and_then(|(key, _)| Cursor::from_index_key(key.strip_prefix(prefix)?))
+            .map(|position| position.encode())
+    } else {
+        None
+    };
+
+    let whole = (
+        first.as_ref().map(String::as_str),
+        last.as_ref().map(String::as_str),
+    );
+    let total = match filter {
+        None if prefix.is_empty() => usize::try_from(idx.len()?)?,
+        None => idx.range::<&str>(whole)?.count(),
+        Some(_) => {
+            let mut total = 0;
+            for entry in idx.range::<&str>(whole)? {
+                let (_, id) = entry?;
+                if read(id.value())?.is_some_and(|record| accepts(&record)) {
+                    total += 1;
+                }
+            }
+            total
+        }
+    };
+
+    Ok(Page {
+        items: items.into_iter().map(|(_, record)| record).collect(),
+        total,
+        next_cursor,
+    })
+}
+
+impl Database {
+    /// Order the bookings, users and lots stored before the order indexes
+    /// existed. A no-op once any record is ordered; returns how many were.
+    pub(super) fn build_list_order(&self, write_txn: &WriteTransaction) -> Result<usize> {
+        if !write_txn.open_table(LIST_ORDER_KEYS)?.is_empty()? {
+            return Ok(0);
+        }
+        let bookings: Vec<Booking> = self.decode_all(write_txn, BOOKINGS)?;
+        let users: Vec<User> = self.decode_all(write_txn, USERS)?;
+        let lots: Vec<ParkingLot> = self.decode_all(write_txn, PARKING_LOTS)?;
+        for booking in &bookings {
+            index_booking_order(write_txn, booking)?;
+        }
+        for user in &users {
+            index_user_order(write_txn, user)?;
+        }
+        for lot in &lots {
+            index_lot_order(write_txn, lot)?;
+        }
+        Ok(bookings.len() + users.len() + lots.len())
+    }
+
+    /// Build the order indexes for records stored before they existed.
+    ///
+    /// Runs once at startup. Undecodable records are skipped rather than
+    /// failing the open.
+    pub(super) fn index_existing_list_order(&self) -> Result<()> {
+        let db = self
+            .inner
+            .try_read()
+            .map_err(|_| anyhow::anyhow!("Database is locked"))?;
+        let write_txn = db.begin_write()?;
+        let indexed = self.build_list_order(&write_txn)?;
+        write_txn.commit()?;
+        if indexed > 0 {
+            info!("Built list order indexes for {} record(s)", indexed);
+        }
+        Ok(())
+    }
+}
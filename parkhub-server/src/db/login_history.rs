@@ -0,0 +1,160 @@
+//! Per-user login history: successful and failed sign-ins with the client's
+//! IP and user agent.
+//!
+//! Keys are `{user_id}:{at}:{id}` so one user's history is a contiguous,
+//! chronologically ordered range. Each user keeps at most
+//! [`MAX_LOGINS_PER_USER`] entries; the retention engine additionally purges
+//! entries past the `security_audit_log` TTL.
+
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use redb::{ReadableDatabase, ReadableTable};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Database, LOGIN_HISTORY};
+
+/// Oldest entries beyond this many per user are dropped on write.
+pub const MAX_LOGINS_PER_USER: usize = 200;
+
+/// One recorded login attempt.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct LoginEvent {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub at: DateTime<Utc>,
+    pub ip: String,
+    pub user_agent: Option<String>,
+    pub success: bool,
+    /// Why the attempt was rejected (`invalid_password`, `account_disabled`,
+    /// `invalid_2fa_code`, `session_limit_reached`); `None` for successful
+    /// logins
+    pub failure_reason: Option<String>,
+    /// Successful login from an IP/user-agent combination not seen before
+    pub new_device: bool,
+}
+
+fn login_key(event: &LoginEvent) -> String {
+    format!(
+        "{}:{}:{}",
+        event.user_id,
+        event.at.to_rfc3339_opts(SecondsFormat::Nanos, true),
+        event.id
+    )
+}
+
+/// Key range covering one user's history (`;` sorts right after `:`).
+fn user_range(user_id: &str) -> (String, String) {
+    (format!("{user_id}:"), format!("{user_id};"))
+}
+
+impl Database {
+    /// Record a login attempt, trimming the user's oldest entries beyond
+    /// [`MAX_LOGINS_PER_USER`].
+    pub async fn record_login_event(&self, event: &LoginEvent) -> Result<()> {
+        let data = self.serialize(event)?;
+
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(LOGIN_HISTORY)?;
+            table.insert(login_key(event).as_str(), data.as_slice())?;
+
+            let (start, end) = user_range(&event.user_id.to_string());
+            let keys: Vec<String> = table
+                .range(start.as_str()..end.as_str())?
+                .map(|entry| entry.map(|(k, _)| k.value().to_string()))
+                .collect::<Result<_, _>>()?;
+            if keys.len() > MAX_LOGINS_PER_USER {
+                for key in &keys[..keys.len() - MAX_LOGINS_PER_USER] {
+                    table.remove(key.as_str())?;
+                }
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// Login history of one user, newest first.
+    pub async fn list_login_events(&self, user_id: &str, limit: usize) -> Result<Vec<LoginEvent>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(LOGIN_HISTORY)?;
+
+        let (start, end) = user_range(user_id);
+        let mut events = Vec::new();
+        for entry in table.range(start.as_str()..end.as_str())?.rev() {
+            let (_, value) = entry?;
+            events.push(self.deserialize(value.value())?);
+            if events.len() >= limit {
+                break;
+            }
+        }
+        Ok(events)
+    }
+
+    /// All login events recorded before `cutoff` (for retention).
+    pub async fn list_login_events_before(&self, cutoff: DateTime<Utc>) -> Result<Vec<LoginEvent>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(LOGIN_HISTORY)?;
+
+        let mut events = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            let event: LoginEvent = self.deserialize(value.value())?;
+            if event.at < cutoff {
+                events.push(event);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Delete the given login events. Returns how many were removed.
+    pub async fn delete_login_events(&self, events: &[LoginEvent]) -> Result<u64> {
+        if events.is_empty() {
+            return Ok(0);
+        }
+
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+
+        let mut deleted = 0u64;
+        {
+            let mut table = write_txn.open_table(LOGIN_HISTORY)?;
+            for event in events {
+                if table.remove(login_key(event).as_str())?.is_some() {
+                    deleted += 1;
+                }
+            }
+        }
+        write_txn.commit()?;
+        Ok(deleted)
+    }
+
+    /// Delete a user's entire login history (account deletion).
+    pub async fn delete_login_history_for_user(&self, user_id: &str) -> Result<u64> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+
+        let deleted = {
+            let mut table = write_txn.open_table(LOGIN_HISTORY)?;
+            let (start, end) = user_range(user_id);
+            let keys: Vec<String> = table
+                .range(start.as_str()..end.as_str())?
+                .map(|entry| entry.map(|(k, _)| k.value().to_string()))
+                .collect::<Result<_, _>>()?;
+            for key in &keys {
+                table.remove(key.as_str())?;
+            }
+            keys.len() as u64
+        };
+        write_txn.commit()?;
+        Ok(deleted)
+    }
+}
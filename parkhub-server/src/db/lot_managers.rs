@@ -0,0 +1,186 @@
+//! Lot manager assignments.
+//!
+//! `LOT_MANAGERS` records which users manage which lots, keyed
+//! `"{user_id}:{lot_id}"` so a user's lots are one range scan away — the
+//! auth middleware resolves them on every request. Older databases kept all
+//! assignments as one JSON document under the `lot_managers` setting; it is
+//! moved into the table when the database is opened.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+use redb::{ReadableDatabase, ReadableTable, WriteTransaction};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::{Database, LOT_MANAGERS, SETTINGS};
+
+/// Settings key of the legacy JSON document (lot → manager user IDs).
+const LEGACY_SETTING_KEY: &str = "lot_managers";
+
+fn manager_key(user_id: Uuid, lot_id: Uuid) -> String {
+    format!("{user_id}:{lot_id}")
+}
+
+/// Drop every manager assignment of `lot_id` (lot deletion).
+pub(super) fn remove_lot_manager_rows(write_txn: &WriteTransaction, lot_id: &str) -> Result<()> {
+    let suffix = format!(":{lot_id}");
+    let mut table = write_txn.open_table(LOT_MANAGERS)?;
+    let keys: Vec<String> = table
+        .iter()?
+        .map(|entry| entry.map(|(k, _)| k.value().to_string()))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .filter(|key| key.ends_with(&suffix))
+        .collect();
+    for key in &keys {
+        table.remove(key.as_str())?;
+    }
+    Ok(())
+}
+
+/// Move assignments from the legacy `lot_managers` setting into
+/// `LOT_MANAGERS` and drop the setting; returns how many were moved. A
+/// document that does not parse is left in place rather than discarded.
+pub(super) fn move_legacy_lot_managers(write_txn: &WriteTransaction) -> Result<usize> {
+    let mut settings = write_txn.open_table(SETTINGS)?;
+    let Some(raw) = settings
+        .get(LEGACY_SETTING_KEY)?
+        .map(|v| v.value().to_string())
+    else {
+        return Ok(0);
+    };
+    let legacy: BTreeMap<Uuid, BTreeSet<Uuid>> = match serde_json::from_str(&raw) {
+        Ok(legacy) => legacy,
+        Err(e) => {
+            warn!("Leaving undecodable lot manager setting in place: {}", e);
+            return Ok(0);
+        }
+    };
+    let mut table = write_txn.open_table(LOT_MANAGERS)?;
+    let mut moved = 0usize;
+    for (lot_id, managers) in &legacy {
+        let lot = lot_id.to_string();
+        for user_id in managers {
+            let key = manager_key(*user_id, *lot_id);
+            table.insert(key.as_str(), lot.as_str())?;
+            moved += 1;
+        }
+    }
+    settings.remove(LEGACY_SETTING_KEY)?;
+    Ok(moved)
+}
+
+impl Database {
+    /// Make `user_id` a manager of `lot_id`. Returns `false` if they
+    /// already managed it.
+    pub async fn assign_lot_manager(&self, user_id: Uuid, lot_id: Uuid) -> Result<bool> {
+        let key = manager_key(user_id, lot_id);
+        let lot = lot_id.to_string();
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let added = {
+            let mut table = write_txn.open_table(LOT_MANAGERS)?;
+            table.insert(key.as_str(), lot.as_str())?.is_none()
+        };
+        write_txn.commit()?;
+        self.access_changed();
+        Ok(added)
+    }
+
+    /// Revoke `user_id`'s manager rights on `lot_id`. Returns `false` if
+    /// they did not manage it.
+    pub async fn unassign_lot_manager(&self, user_id: Uuid, lot_id: Uuid) -> Result<bool> {
+        let key = manager_key(user_id, lot_id);
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let removed = {
+            let mut table = write_txn.open_table(LOT_MANAGERS)?;
+            table.remove(key.as_str())?.is_some()
+        };
+        write_txn.commit()?;
+        self.access_changed();
+        Ok(removed)
+    }
+
+    /// List the IDs of the lots a user manages
+    pub async fn list_managed_lots(&self, user_id: Uuid) -> Result<Vec<Uuid>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(LOT_MANAGERS)?;
+
+        let start = format!("{user_id}:");
+        let end = format!("{user_id};");
+        let mut lot_ids = Vec::new();
+        for entry in table.range(start.as_str()..end.as_str())? {
+            let (_, lot_id) = entry?;
+            if let Ok(lot_id) = lot_id.value().parse() {
+                lot_ids.push(lot_id);
+            }
+        }
+        Ok(lot_ids)
+    }
+
+    /// List the IDs of the users managing a lot
+    pub async fn list_lot_managers(&self, lot_id: Uuid) -> Result<Vec<Uuid>> {
+        let suffix = format!(":{lot_id}");
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(LOT_MANAGERS)?;
+        let mut user_ids = Vec::new();
+        for entry in table.iter()? {
+            let (key, _) = entry?;
+            if let Some(user_id) = key.value().strip_suffix(&suffix)
+                && let Ok(user_id) = user_id.parse()
+            {
+                user_ids.push(user_id);
+            }
+        }
+        Ok(user_ids)
+    }
+
+    /// Drop all lot manager assignments of a user (account deletion).
+    pub async fn delete_lot_manager_assignments_for_user(&self, user_id: &str) -> Result<()> {
+        let start = format!("{user_id}:");
+        let end = format!("{user_id};");
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(LOT_MANAGERS)?;
+            let keys: Vec<String> = table
+                .range(start.as_str()..end.as_str())?
+                .map(|entry| entry.map(|(k, _)| k.value().to_string()))
+                .collect::<Result<_, _>>()?;
+            for key in &keys {
+                table.remove(key.as_str())?;
+            }
+        }
+        write_txn.commit()?;
+        self.access_changed();
+        Ok(())
+    }
+
+    /// Move assignments from the legacy `lot_managers` setting into
+    /// `LOT_MANAGERS` on open.
+    pub(super) fn migrate_legacy_lot_managers(&self) -> Result<()> {
+        let db = self
+            .inner
+            .try_read()
+            .map_err(|_| anyhow::anyhow!("Database is locked"))?;
+        let write_txn = db.begin_write()?;
+        let migrated = move_legacy_lot_managers(&write_txn)?;
+        write_txn.commit()?;
+        if migrated > 0 {
+            info!(
+                "Moved {} lot manager assignment(s) into their own table",
+                migrated
+            );
+        }
+        Ok(())
+    }
+}
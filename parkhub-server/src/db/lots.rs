@@ -1,15 +1,62 @@
 //! Parking lot, parking slot, and zone CRUD with slot-by-lot secondary index.
 
 use anyhow::Result;
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
 use chrono::{DateTime, Utc};
-use redb::{ReadableDatabase, ReadableTable};
+use redb::{ReadableDatabase, ReadableTable, WriteTransaction};
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 use uuid::Uuid;
 
-use parkhub_common::models::{ParkingLot, ParkingSlot};
+use parkhub_common::models::{ParkingFloor, ParkingLot, ParkingSlot, SlotStatus};
 
-use super::{Database, PARKING_LOTS, PARKING_SLOTS, SLOTS_BY_LOT, ZONES};
+use super::list_order::{index_lot_order, page_in_order, unindex_lot_order};
+use super::lot_managers::remove_lot_manager_rows;
+use super::slot_types::{index_slot_type, unindex_slot_type};
+use super::{
+    Database, LOTS_BY_NAME, PARKING_LOTS, PARKING_SLOTS, Page, PageQuery, SETTINGS, SLOTS_BY_LOT,
+    SlotChangeContext, SlotStatusChange, SortBy, ZONES, paging,
+};
+
+/// Settings-key prefix for the photos of one lot.
+fn lot_photo_prefix(lot_id: &str) -> String {
+    format!("lot_photo_{lot_id}_")
+}
+
+/// Remove `slot` and its index entries within `write_txn`.
+fn remove_parking_slot(write_txn: &WriteTransaction, slot: &ParkingSlot) -> Result<()> {
+    let id = slot.id.to_string();
+    let mut table = write_txn.open_table(PARKING_SLOTS)?;
+    table.remove(id.as_str())?;
+    let mut idx = write_txn.open_table(SLOTS_BY_LOT)?;
+    idx.remove(format!("{}:{id}", slot.lot_id).as_str())?;
+    unindex_slot_type(write_txn, &id)?;
+    Ok(())
+}
+
+/// Remove every slot of lot `lot_id` from `PARKING_SLOTS` and the slot
+/// indexes within `write_txn`. Returns how many were removed.
+fn remove_lot_slots(write_txn: &WriteTransaction, lot_id: &str) -> Result<usize> {
+    // key format is "lot_id:slot_id"; ';' sorts right after ':'
+    let start = format!("{lot_id}:");
+    let end = format!("{lot_id};");
+    let keys: Vec<String> = {
+        let idx_table = write_txn.open_table(SLOTS_BY_LOT)?;
+        idx_table
+            .range(start.as_str()..end.as_str())?
+            .map(|entry| entry.map(|(key, _)| key.value().to_string()))
+            .collect::<Result<_, _>>()?
+    };
+    let mut slots_table = write_txn.open_table(PARKING_SLOTS)?;
+    let mut idx_table = write_txn.open_table(SLOTS_BY_LOT)?;
+    for key in &keys {
+        let slot_id = &key[start.len()..];
+        slots_table.remove(slot_id)?;
+        idx_table.remove(key.as_str())?;
+        unindex_slot_type(write_txn, slot_id)?;
+    }
+    Ok(keys.len())
+}
 
 /// A zone within a parking lot (e.g., "Level A", "VIP Section")
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +84,8 @@ impl Database {
             let mut table = write_txn.open_table(PARKING_LOTS)?;
             table.insert(id.as_str(), data.as_slice())?;
         }
+        self.index_lot(&write_txn, lot)?;
+        index_lot_order(&write_txn, lot)?;
         write_txn.commit()?;
         debug!("Saved parking lot: {} ({})", lot.name, lot.id);
         Ok(())
@@ -44,6 +93,7 @@ impl Database {
 
     /// Get a parking lot by ID (string)
     pub async fn get_parking_lot(&self, id: &str) -> Result<Option<ParkingLot>> {
+        let _timer = Self::time_read("parking_lots", Some(id));
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
@@ -57,6 +107,7 @@ impl Database {
 
     /// List all parking lots
     pub async fn list_parking_lots(&self) -> Result<Vec<ParkingLot>> {
+        let _timer = Self::time_read("parking_lots", None);
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
@@ -70,51 +121,279 @@ impl Database {
         Ok(lots)
     }
 
-    /// Delete a parking lot
+    /// The page of parking lots selected by `query` among those `filter`
+    /// accepts (every lot for `None`), ordered by `sort`. The default order,
+    /// by name, is read from `LOTS_BY_NAME`.
+    pub async fn list_parking_lots_page(
+        &self,
+        filter: Option<impl Fn(&ParkingLot) -> bool>,
+        sort: SortBy<ParkingLot>,
+        query: &PageQuery,
+    ) -> Result<Page<ParkingLot>> {
+        let SortBy::Key(sort_key) = sort else {
+            let _timer = Self::time_read("parking_lots", None);
+            let db = self.inner.read().await;
+            let read_txn = db.begin_read()?;
+            drop(db);
+            return page_in_order(
+                self,
+                &read_txn,
+                LOTS_BY_NAME,
+                "",
+                PARKING_LOTS,
+                filter.as_ref(),
+                query,
+            );
+        };
+        let lots = self.list_parking_lots().await?;
+        Ok(paging::page(
+            lots,
+            |l| l.id.to_string(),
+            sort_key,
+            |l| filter.as_ref().is_none_or(|filter| filter(l)),
+            query,
+        ))
+    }
+
+    /// Delete a parking lot together with its slots
     pub async fn delete_parking_lot(&self, id: &str) -> Result<bool> {
         let db = self.inner.write().await;
         let write_txn = db.begin_write()?;
         drop(db);
+        let existed = self.remove_parking_lot(&write_txn, id)?;
+        write_txn.commit()?;
+        self.access_changed();
+        Ok(existed)
+    }
+
+    /// Remove lot `id`, its slots, their `SLOTS_BY_LOT` entries and the
+    /// lot's manager assignments within `write_txn`. Returns whether the lot
+    /// existed; slots are removed either way so none are left behind.
+    pub(super) fn remove_parking_lot(
+        &self,
+        write_txn: &WriteTransaction,
+        id: &str,
+    ) -> Result<bool> {
         let existed = {
             let mut table = write_txn.open_table(PARKING_LOTS)?;
             let result = table.remove(id)?;
             result.is_some()
         };
-        write_txn.commit()?;
         if existed {
-            debug!("Deleted parking lot: {}", id);
+            self.unindex_lot(write_txn, id)?;
+            unindex_lot_order(write_txn, id)?;
+        }
+        remove_lot_manager_rows(write_txn, id)?;
+        let slots = remove_lot_slots(write_txn, id)?;
+        if existed {
+            debug!("Deleted parking lot {id} with {slots} slots");
         }
         Ok(existed)
     }
 
+    /// Add `floor` to its lot, or replace the lot's floor with the same id,
+    /// and recount the lot's slots in the same transaction so the floor's
+    /// counts match its slots. Returns the updated lot, or `None` if the lot
+    /// does not exist.
+    pub async fn save_lot_floor(&self, floor: &ParkingFloor) -> Result<Option<ParkingLot>> {
+        let id = floor.lot_id.to_string();
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(PARKING_LOTS)?;
+            let Some(mut lot) = table
+                .get(id.as_str())?
+                .map(|v| self.deserialize::<ParkingLot>(v.value()))
+                .transpose()?
+            else {
+                return Ok(None);
+            };
+            match lot.floors.iter_mut().find(|f| f.id == floor.id) {
+                Some(existing) => *existing = floor.clone(),
+                None => lot.floors.push(floor.clone()),
+            }
+            lot.floors.sort_by_key(|f| f.floor_number);
+            lot.updated_at = Utc::now();
+            let data = self.serialize(&lot)?;
+            table.insert(id.as_str(), data.as_slice())?;
+        }
+        self.recount_lot_slots(&write_txn, floor.lot_id)?;
+        let lot = {
+            let table = write_txn.open_table(PARKING_LOTS)?;
+            table
+                .get(id.as_str())?
+                .map(|v| self.deserialize::<ParkingLot>(v.value()))
+                .transpose()?
+        };
+        write_txn.commit()?;
+        debug!("Saved floor {} of parking lot {id}", floor.name);
+        Ok(lot)
+    }
+
+    /// Store a processed lot photo (JPEG bytes) under `photo_id`.
+    pub async fn save_lot_photo(&self, lot_id: &str, photo_id: &str, jpeg: &[u8]) -> Result<()> {
+        let key = format!("{}{photo_id}", lot_photo_prefix(lot_id));
+        self.set_setting(&key, &BASE64.encode(jpeg)).await
+    }
+
+    /// Load a stored lot photo, if any.
+    pub async fn get_lot_photo(&self, lot_id: &str, photo_id: &str) -> Result<Option<Vec<u8>>> {
+        let key = format!("{}{photo_id}", lot_photo_prefix(lot_id));
+        match self.get_setting(&key).await? {
+            Some(encoded) if !encoded.is_empty() => Ok(Some(BASE64.decode(encoded)?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Remove one stored lot photo.
+    pub async fn delete_lot_photo(&self, lot_id: &str, photo_id: &str) -> Result<()> {
+        let key = format!("{}{photo_id}", lot_photo_prefix(lot_id));
+        self.set_setting(&key, "").await
+    }
+
+    /// Remove every stored photo of a lot (used when the lot is deleted).
+    pub async fn delete_lot_photos(&self, lot_id: &str) -> Result<()> {
+        let prefix = lot_photo_prefix(lot_id);
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(SETTINGS)?;
+            table.retain(|key, _| !key.starts_with(&prefix))?;
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+
     // ── Parking Slot CRUD ──
 
     /// Save a parking slot
     pub async fn save_parking_slot(&self, slot: &ParkingSlot) -> Result<()> {
-        let id = slot.id.to_string();
-        let lot_id = slot.lot_id.to_string();
-        let data = self.serialize(slot)?;
+        self.save_parking_slot_with(slot, &SlotChangeContext::system("system"))
+            .await
+    }
 
+    /// Save a parking slot, recording a status history entry attributed to
+    /// `ctx` if its status changed.
+    pub async fn save_parking_slot_with(
+        &self,
+        slot: &ParkingSlot,
+        ctx: &SlotChangeContext,
+    ) -> Result<()> {
+        let _timer = Self::time_write("parking_slots", Some(&slot.id.to_string()));
         let db = self.inner.write().await;
         let write_txn = db.begin_write()?;
         drop(db);
-        {
+        self.put_parking_slot(&write_txn, slot, ctx)?;
+        write_txn.commit()?;
+        debug!("Saved parking slot: {} (lot: {})", slot.id, slot.lot_id);
+        Ok(())
+    }
+
+    /// Write `slot` within `write_txn`, recording its status change as in
+    /// [`Self::save_parking_slot_with`].
+    pub(super) fn put_parking_slot(
+        &self,
+        write_txn: &WriteTransaction,
+        slot: &ParkingSlot,
+        ctx: &SlotChangeContext,
+    ) -> Result<()> {
+        let id = slot.id.to_string();
+        let lot_id = slot.lot_id.to_string();
+        let data = self.serialize(slot)?;
+        let previous_status = {
             // Save main slot data
             let mut table = write_txn.open_table(PARKING_SLOTS)?;
+            let previous_status = table
+                .get(id.as_str())?
+                .and_then(|v| self.deserialize::<ParkingSlot>(v.value()).ok())
+                .map(|s| s.status);
             table.insert(id.as_str(), data.as_slice())?;
 
             // Update lot->slots index
             let mut idx = write_txn.open_table(SLOTS_BY_LOT)?;
             let key = format!("{lot_id}:{id}");
             idx.insert(key.as_str(), data.as_slice())?;
+            previous_status
+        };
+        index_slot_type(write_txn, slot)?;
+        if let Some(from_status) = previous_status
+            && from_status != slot.status
+        {
+            let change = SlotStatusChange {
+                id: Uuid::new_v4(),
+                slot_id: slot.id,
+                lot_id: slot.lot_id,
+                from_status,
+                to_status: slot.status.clone(),
+                changed_at: Utc::now(),
+                changed_by: ctx.user_id,
+                source: ctx.source.to_string(),
+                reason: ctx.reason.clone(),
+            };
+            self.append_slot_history(write_txn, &change)?;
         }
-        write_txn.commit()?;
-        debug!("Saved parking slot: {} (lot: {})", slot.id, slot.lot_id);
+        Ok(())
+    }
+
+    /// Recompute `total_slots` and `available_slots` of lot `lot_id` and of
+    /// its floors from the slots in `SLOTS_BY_LOT`, within `write_txn`, so
+    /// slot writes earlier in the same transaction are counted. A missing
+    /// lot is left alone.
+    pub(super) fn recount_lot_slots(
+        &self,
+        write_txn: &WriteTransaction,
+        lot_id: Uuid,
+    ) -> Result<()> {
+        fn count<'a>(slots: impl Iterator<Item = &'a ParkingSlot>) -> (i32, i32) {
+            let (total, available) = slots.fold((0usize, 0usize), |(total, available), s| {
+                (
+                    total + 1,
+                    available + usize::from(s.status == SlotStatus::Available),
+                )
+            });
+            (
+                i32::try_from(total).unwrap_or(i32::MAX),
+                i32::try_from(available).unwrap_or(i32::MAX),
+            )
+        }
+
+        let id = lot_id.to_string();
+        let slots: Vec<ParkingSlot> = {
+            let idx = write_txn.open_table(SLOTS_BY_LOT)?;
+            // ';' sorts right after the ':' separator
+            let start = format!("{id}:");
+            let end = format!("{id};");
+            let mut slots = Vec::new();
+            for entry in idx.range(start.as_str()..end.as_str())? {
+                let (_, value) = entry?;
+                slots.push(self.deserialize(value.value())?);
+            }
+            slots
+        };
+
+        let mut table = write_txn.open_table(PARKING_LOTS)?;
+        let Some(mut lot) = table
+            .get(id.as_str())?
+            .map(|v| self.deserialize::<ParkingLot>(v.value()))
+            .transpose()?
+        else {
+            return Ok(());
+        };
+        (lot.total_slots, lot.available_slots) = count(slots.iter());
+        for floor in &mut lot.floors {
+            (floor.total_slots, floor.available_slots) =
+                count(slots.iter().filter(|s| s.floor_id == floor.id));
+        }
+        let data = self.serialize(&lot)?;
+        table.insert(id.as_str(), data.as_slice())?;
         Ok(())
     }
 
     /// Get a parking slot by ID (string)
     pub async fn get_parking_slot(&self, id: &str) -> Result<Option<ParkingSlot>> {
+        let _timer = Self::time_read("parking_slots", Some(id));
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
@@ -128,6 +407,7 @@ impl Database {
 
     /// Get all parking slots for a lot (`list_slots_by_lot`)
     pub async fn list_slots_by_lot(&self, lot_id: &str) -> Result<Vec<ParkingSlot>> {
+        let _timer = Self::time_read("slots_by_lot", Some(lot_id));
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
@@ -145,89 +425,27 @@ impl Database {
         Ok(slots)
     }
 
-    /// Delete all parking slots belonging to a lot (cascade delete).
-    /// Removes entries from both `PARKING_SLOTS` and `SLOTS_BY_LOT` index.
-    pub async fn delete_slots_by_lot(&self, lot_id: &str) -> Result<()> {
-        let prefix = format!("{lot_id}:");
-
-        let db = self.inner.write().await;
-
-        // First, collect all slot IDs and index keys from SLOTS_BY_LOT
-        let keys_to_delete: Vec<(String, String)> = {
-            let read_txn = db.begin_read()?;
-            let table = read_txn.open_table(SLOTS_BY_LOT)?;
-            let mut keys = Vec::new();
-            for entry in table.iter()? {
-                let (key, _value) = entry?;
-                let key_str = key.value().to_string();
-                if key_str.starts_with(&prefix) {
-                    // key format is "lot_id:slot_id"
-                    let slot_id = key_str[prefix.len()..].to_string();
-                    keys.push((key_str, slot_id));
-                }
-            }
-            keys
-        };
-
-        if keys_to_delete.is_empty() {
-            return Ok(());
-        }
-
-        // Delete all matching entries in a single write transaction
-        let write_txn = db.begin_write()?;
-        drop(db);
-        {
-            let mut slots_table = write_txn.open_table(PARKING_SLOTS)?;
-            let mut idx_table = write_txn.open_table(SLOTS_BY_LOT)?;
-            for (idx_key, slot_id) in &keys_to_delete {
-                slots_table.remove(slot_id.as_str())?;
-                idx_table.remove(idx_key.as_str())?;
-            }
-        }
-        write_txn.commit()?;
-        debug!(
-            "Cascade-deleted {} slots for lot {}",
-            keys_to_delete.len(),
-            lot_id
-        );
-        Ok(())
-    }
-
-    /// Delete a single parking slot by ID.
+    /// Delete a single parking slot by ID, removing its `SLOTS_BY_LOT`
+    /// entry and recounting its lot's slots in the same transaction.
     pub async fn delete_parking_slot(&self, id: &str) -> Result<bool> {
-        let id_suffix = format!(":{id}");
         let db = self.inner.write().await;
-
-        // First collect index keys to remove (read pass)
-        let keys_to_remove: Vec<String> = {
-            let read_txn = db.begin_read()?;
-            let idx_table = read_txn.open_table(SLOTS_BY_LOT)?;
-            let mut keys = Vec::new();
-            for entry in idx_table.iter()? {
-                let (key, _) = entry?;
-                if key.value().ends_with(&id_suffix) {
-                    keys.push(key.value().to_string());
-                }
-            }
-            keys
-        };
-
-        // Write pass: remove slot + index entries
         let write_txn = db.begin_write()?;
         drop(db);
-        let removed = {
-            let mut table = write_txn.open_table(PARKING_SLOTS)?;
-
-            table.remove(id)?.is_some()
+        let slot: Option<ParkingSlot> = {
+            let table = write_txn.open_table(PARKING_SLOTS)?;
+            table
+                .get(id)?
+                .map(|v| self.deserialize(v.value()))
+                .transpose()?
         };
-        if removed && !keys_to_remove.is_empty() {
-            let mut idx_table = write_txn.open_table(SLOTS_BY_LOT)?;
-            for key in &keys_to_remove {
-                idx_table.remove(key.as_str())?;
-            }
-        }
+        let Some(slot) = slot else {
+            return Ok(false);
+        };
+        remove_parking_slot(&write_txn, &slot)?;
+        self.recount_lot_slots(&write_txn, slot.lot_id)?;
         write_txn.commit()?;
-        Ok(removed)
+        debug!("Deleted parking slot: {} (lot: {})", slot.id, slot.lot_id);
+        Ok(true)
     }
 
     /// Save multiple parking slots in a single write transaction (batch insert).
@@ -259,23 +477,79 @@ impl Database {
                 idx.insert(key.as_str(), data.as_slice())?;
             }
         }
+        for slot in slots {
+            index_slot_type(&write_txn, slot)?;
+        }
         write_txn.commit()?;
         debug!("Batch-saved {} parking slots", slots.len());
         Ok(())
     }
 
-    /// Update slot status
+    /// Save lots together with their slots in a single write transaction.
+    ///
+    /// Either everything is written or nothing is — used by the bulk lot
+    /// import so a half-imported facility never becomes visible.
+    pub async fn import_lots(&self, lots: &[ParkingLot], slots: &[ParkingSlot]) -> Result<()> {
+        let lot_data: Vec<(String, Vec<u8>)> = lots
+            .iter()
+            .map(|lot| Ok((lot.id.to_string(), self.serialize(lot)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let slot_data: Vec<(String, String, Vec<u8>)> = slots
+            .iter()
+            .map(|slot| {
+                Ok((
+                    slot.id.to_string(),
+                    slot.lot_id.to_string(),
+                    self.serialize(slot)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut lot_table = write_txn.open_table(PARKING_LOTS)?;
+            for (id, data) in &lot_data {
+                lot_table.insert(id.as_str(), data.as_slice())?;
+            }
+            let mut table = write_txn.open_table(PARKING_SLOTS)?;
+            let mut idx = write_txn.open_table(SLOTS_BY_LOT)?;
+            for (id, lot_id, data) in &slot_data {
+                table.insert(id.as_str(), data.as_slice())?;
+                let key = format!("{lot_id}:{id}");
+                idx.insert(key.as_str(), data.as_slice())?;
+            }
+        }
+        for slot in slots {
+            index_slot_type(&write_txn, slot)?;
+        }
+        for lot in lots {
+            self.index_lot(&write_txn, lot)?;
+            index_lot_order(&write_txn, lot)?;
+        }
+        write_txn.commit()?;
+        debug!(
+            "Imported {} parking lots with {} slots",
+            lots.len(),
+            slots.len()
+        );
+        Ok(())
+    }
+
+    /// Update slot status, attributing the change to `ctx`
     pub async fn update_slot_status(
         &self,
         slot_id: &str,
         status: parkhub_common::models::SlotStatus,
+        ctx: &SlotChangeContext,
     ) -> Result<bool> {
         let Some(mut slot) = self.get_parking_slot(slot_id).await? else {
             return Ok(false);
         };
 
         slot.status = status;
-        self.save_parking_slot(&slot).await?;
+        self.save_parking_slot_with(&slot, ctx).await?;
         Ok(true)
     }
 
@@ -16,27 +16,56 @@ use redb::{
     TableDefinition,
 };
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::RwLock;
 use tracing::info;
 use uuid::Uuid;
 
 mod absences;
 mod audit_log;
+mod backup;
+mod batch;
+mod booking_attachments;
+mod booking_notes;
+mod booking_times;
 mod bookings;
 mod communications;
+mod dummy_users;
+mod email_queue;
 mod encryption;
 mod ev;
 mod favorites;
+mod feature_flags;
+mod invites;
+mod invoice_archive;
 mod invoice_counters;
+mod list_order;
+mod login_history;
+mod lot_managers;
 mod lots;
+mod paging;
+mod passkeys;
+mod permits;
+mod rectifications;
+mod refresh_tokens;
+mod roles;
+mod search;
+mod session_revocations;
 mod sessions;
 mod settings;
+mod slot_assignments;
+mod slot_history;
+mod slot_holds;
+mod slot_types;
+mod slow_ops;
 mod stripe_events;
 mod translations;
 mod users;
 mod vehicles;
+mod visitor_passes;
 mod visitors;
 
 #[cfg(test)]
@@ -44,9 +73,29 @@ mod tests;
 
 use encryption::Encryptor;
 
-pub use favorites::Favorite;
+pub use backup::{open_backup, seal_backup};
+pub use batch::WriteBatch;
+pub use booking_attachments::BookingAttachment;
+pub use booking_notes::BookingNote;
+pub use email_queue::{QueuedEmail, QueuedEmailStatus};
+pub use favorites::{Favorite, FavoriteLot};
+pub use feature_flags::FeatureFlagSwitch;
+pub use invites::Invite;
+pub use invoice_archive::{ArchivedInvoice, InvoiceDetails};
+pub use login_history::{LoginEvent, MAX_LOGINS_PER_USER};
 pub use lots::Zone;
+pub use paging::{Page, PageQuery, SortBy, count_key, page, text_key, time_key};
+pub use passkeys::StoredPasskey;
+pub use rectifications::{RectificationRequest, RectificationStatus};
+pub use search::{SearchHit, SearchKind};
 pub use sessions::Session;
+pub use settings::lot_rules_key;
+pub use slot_assignments::SlotAssignment;
+pub use slot_history::{MAX_HISTORY_PER_SLOT, SlotChangeContext, SlotStatusChange};
+pub use slot_holds::SlotHold;
+pub use visitor_passes::{VisitorPass, VisitorPassBlock, VisitorPassStatus};
+
+use slow_ops::{OpKind, OpTimer};
 
 // ═══════════════════════════════════════════════════════════════════════════════
 // TABLE DEFINITIONS
@@ -58,6 +107,8 @@ pub(crate) const USERS_BY_USERNAME: TableDefinition<&str, &str> =
 pub(crate) const USERS_BY_EMAIL: TableDefinition<&str, &str> =
     TableDefinition::new("users_by_email");
 pub(crate) const SESSIONS: TableDefinition<&str, &[u8]> = TableDefinition::new("sessions");
+pub(crate) const SESSIONS_BY_USER: TableDefinition<&str, &str> =
+    TableDefinition::new("sessions_by_user");
 pub(crate) const BOOKINGS: TableDefinition<&str, &[u8]> = TableDefinition::new("bookings");
 pub(crate) const BOOKINGS_BY_USER: TableDefinition<&str, &str> =
     TableDefinition::new("bookings_by_user");
@@ -86,6 +137,8 @@ pub(crate) const PUSH_SUBSCRIPTIONS: TableDefinition<&str, &[u8]> =
     TableDefinition::new("push_subscriptions");
 pub(crate) const ZONES: TableDefinition<&str, &[u8]> = TableDefinition::new("zones");
 pub(crate) const FAVORITES: TableDefinition<&str, &[u8]> = TableDefinition::new("favorites");
+pub(crate) const FAVORITE_LOTS: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("favorite_lots");
 pub(crate) const AUDIT_LOG: TableDefinition<&str, &[u8]> = TableDefinition::new("audit_log");
 pub(crate) const TRANSLATION_PROPOSALS: TableDefinition<&str, &[u8]> =
     TableDefinition::new("translation_proposals");
@@ -97,16 +150,136 @@ pub(crate) const VISITORS: TableDefinition<&str, &[u8]> = TableDefinition::new("
 pub(crate) const EV_CHARGERS: TableDefinition<&str, &[u8]> = TableDefinition::new("ev_chargers");
 pub(crate) const CHARGING_SESSIONS: TableDefinition<&str, &[u8]> =
     TableDefinition::new("charging_sessions");
+/// Append-only slot status history. Key: `{slot_id}:{changed_at}:{id}`.
+pub(crate) const SLOT_STATUS_HISTORY: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("slot_status_history");
+/// Outbound email queue (pending and dead-lettered messages). Key: message id.
+pub(crate) const EMAIL_QUEUE: TableDefinition<&str, &[u8]> = TableDefinition::new("email_queue");
+/// Per-user login attempts. Key: `{user_id}:{at}:{id}`.
+pub(crate) const LOGIN_HISTORY: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("login_history");
+/// Parking permits (resident, contractor). Key: permit id.
+pub(crate) const PERMITS: TableDefinition<&str, &[u8]> = TableDefinition::new("permits");
 /// Stripe webhook event log (idempotency). Key: Stripe `evt_...` id.
 /// Value: event type (e.g. `checkout.session.completed`). Presence of the key
 /// means the event was already processed — retries short-circuit to 200 OK
 /// before any credit mutation, preventing double-credit.
 pub(crate) const STRIPE_EVENTS: TableDefinition<&str, &str> = TableDefinition::new("stripe_events");
+/// Admin search token index. Key: `{token}\x1f{document}`, value: weight.
+/// See `search.rs`.
+pub(crate) const SEARCH_INDEX: TableDefinition<&str, u32> = TableDefinition::new("search_index");
+/// Tokens indexed per search document. Key: document (`{kind}:{id}`).
+pub(crate) const SEARCH_DOCS: TableDefinition<&str, &[u8]> = TableDefinition::new("search_docs");
+/// Issued invoices with their rendered documents. Key: invoice number.
+/// See `invoice_archive.rs`.
+pub(crate) const INVOICES: TableDefinition<&str, &[u8]> = TableDefinition::new("invoices");
+/// Invoices by recipient. Key: `{user_id}:{invoice_number}`, value: invoice number.
+pub(crate) const INVOICES_BY_USER: TableDefinition<&str, &str> =
+    TableDefinition::new("invoices_by_user");
+/// Late-binding bookings waiting for their slot. Key: booking ID.
+/// See `slot_assignments.rs`.
+pub(crate) const PENDING_SLOT_ASSIGNMENTS: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("pending_slot_assignments");
+/// Admin-defined custom roles. Key: role ID. See `roles.rs`.
+pub(crate) const ROLES: TableDefinition<&str, &[u8]> = TableDefinition::new("roles");
+/// Custom role memberships. Key: `{user_id}:{role_id}`, value: role ID.
+pub(crate) const ROLE_MEMBERS: TableDefinition<&str, &str> = TableDefinition::new("role_members");
+/// WebAuthn passkeys. Key: `{user_id}:{passkey_id}`. See `passkeys.rs`.
+pub(crate) const PASSKEYS: TableDefinition<&str, &[u8]> = TableDefinition::new("passkeys");
+/// Pending user invitations. Key: SHA-256 of the invite token. See `invites.rs`.
+pub(crate) const INVITES: TableDefinition<&str, &[u8]> = TableDefinition::new("invites");
+/// Refresh tokens already rotated out, per token family. Key: SHA-256 of
+/// the refresh token. See `refresh_tokens.rs`.
+pub(crate) const REFRESH_TOKEN_FAMILIES: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("refresh_token_families");
+/// Live bookings by slot and end time. Key:
+/// `{slot_id}:{end_millis}:{booking_id}`, value: booking ID.
+/// See `booking_times.rs`.
+pub(crate) const BOOKINGS_BY_SLOT_END: TableDefinition<&str, &str> =
+    TableDefinition::new("bookings_by_slot_end");
+/// Key of each booking in `BOOKINGS_BY_SLOT_END`. Key: booking ID.
+pub(crate) const BOOKING_SLOT_END_KEYS: TableDefinition<&str, &str> =
+    TableDefinition::new("booking_slot_end_keys");
+/// Live bookings by end time, with or without a slot. Key:
+/// `{end_millis}:{booking_id}`, value: booking ID. See `booking_times.rs`.
+pub(crate) const LIVE_BOOKINGS_BY_END: TableDefinition<&str, &str> =
+    TableDefinition::new("live_bookings_by_end");
+/// Key of each booking in `LIVE_BOOKINGS_BY_END`. Key: booking ID.
+pub(crate) const LIVE_BOOKING_END_KEYS: TableDefinition<&str, &str> =
+    TableDefinition::new("live_booking_end_keys");
+/// Dummy accounts generated during setup. Key: user ID, value: RFC 3339
+/// time of generation. See `dummy_users.rs`.
+pub(crate) const DUMMY_USERS: TableDefinition<&str, &str> = TableDefinition::new("dummy_users");
+/// Slots held while a user fills out the booking form. Key: slot ID.
+/// See `slot_holds.rs`.
+pub(crate) const SLOT_HOLDS: TableDefinition<&str, &[u8]> = TableDefinition::new("slot_holds");
+/// Slot holds by lot. Key: `{lot_id}:{slot_id}`, value: slot ID.
+/// See `slot_holds.rs`.
+pub(crate) const SLOT_HOLDS_BY_LOT: TableDefinition<&str, &str> =
+    TableDefinition::new("slot_holds_by_lot");
+/// Users' reports of wrong profile data. Key: request ID.
+/// See `rectifications.rs`.
+pub(crate) const RECTIFICATION_REQUESTS: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("rectification_requests");
+/// Slots by lot and type. Key: `{lot_id}:{slot_type}:{slot_id}`, value:
+/// slot ID. See `slot_types.rs`.
+pub(crate) const SLOTS_BY_TYPE: TableDefinition<&str, &str> = TableDefinition::new("slots_by_type");
+/// Key of each slot in `SLOTS_BY_TYPE`. Key: slot ID.
+pub(crate) const SLOT_TYPE_KEYS: TableDefinition<&str, &str> =
+    TableDefinition::new("slot_type_keys");
+/// Time before which a user's sessions are revoked. Key: user ID, value:
+/// Unix seconds. See `session_revocations.rs`.
+pub(crate) const SESSION_REVOCATIONS: TableDefinition<&str, i64> =
+    TableDefinition::new("session_revocations");
+/// Internal notes on bookings. Key: `{booking_id}:{note_id}`. See
+/// `booking_notes.rs`.
+pub(crate) const BOOKING_NOTES: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("booking_notes");
+/// Files attached to bookings; the contents live in the data directory.
+/// Key: `{booking_id}:{attachment_id}`. See `booking_attachments.rs`.
+pub(crate) const BOOKING_ATTACHMENTS: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("booking_attachments");
+/// Visitor passes a lot may issue per day. Key: `{lot_id}:{date}`. See
+/// `visitor_passes.rs`.
+pub(crate) const VISITOR_PASS_BLOCKS: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("visitor_pass_blocks");
+/// Visitor passes. Key: `{lot_id}:{date}:{pass_id}`.
+pub(crate) const VISITOR_PASSES: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("visitor_passes");
+/// Key of each pass in `VISITOR_PASSES`. Key: pass ID.
+pub(crate) const VISITOR_PASS_KEYS: TableDefinition<&str, &str> =
+    TableDefinition::new("visitor_pass_keys");
+/// Feature flags an admin has switched. Key: flag key. See
+/// `feature_flags.rs`.
+pub(crate) const FEATURE_FLAGS: TableDefinition<&str, &[u8]> =
+    TableDefinition::new("feature_flags");
+/// Lot manager assignments. Key: `{user_id}:{lot_id}`, value: lot ID. See
+/// `lot_managers.rs`.
+pub(crate) const LOT_MANAGERS: TableDefinition<&str, &str> = TableDefinition::new("lot_managers");
+/// Bookings by start time. Key: `{start}\x1f{booking_id}`, value: booking
+/// ID. See `list_order.rs`.
+pub(crate) const BOOKINGS_BY_START: TableDefinition<&str, &str> =
+    TableDefinition::new("bookings_by_start");
+/// Each user's bookings by start time. Key:
+/// `{user_id}:{start}\x1f{booking_id}`, value: booking ID.
+pub(crate) const BOOKINGS_BY_USER_START: TableDefinition<&str, &str> =
+    TableDefinition::new("bookings_by_user_start");
+/// Users by creation time. Key: `{created_at}\x1f{user_id}`, value: user ID.
+pub(crate) const USERS_BY_CREATED: TableDefinition<&str, &str> =
+    TableDefinition::new("users_by_created");
+/// Parking lots by lower-cased name. Key: `{name}\x1f{lot_id}`, value: lot
+/// ID.
+pub(crate) const LOTS_BY_NAME: TableDefinition<&str, &str> = TableDefinition::new("lots_by_name");
+/// Key of each record in the order indexes above. Key:
+/// `{index_name}:{record_id}`.
+pub(crate) const LIST_ORDER_KEYS: TableDefinition<&str, &str> =
+    TableDefinition::new("list_order_keys");
 
 // Settings keys
 const SETTING_SETUP_COMPLETED: &str = "setup_completed";
 const SETTING_DB_VERSION: &str = "db_version";
 const SETTING_ENCRYPTION_SALT: &str = "encryption_salt";
+const SETTING_TOKEN_SECRET: &str = "token_signing_secret";
 
 const CURRENT_DB_VERSION: &str = "1";
 
@@ -195,6 +368,8 @@ pub struct DatabaseStats {
     pub slots: u64,
     pub sessions: u64,
     pub vehicles: u64,
+    /// Generated dummy accounts not yet purged
+    pub dummy_users: u64,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -215,6 +390,13 @@ pub struct Database {
     pub(crate) inner: Arc<RwLock<RedbDatabase>>,
     encryptor: Option<Encryptor>,
     encryption_enabled: bool,
+    token_secret: Arc<str>,
+    /// In-memory copy of `SESSION_REVOCATIONS`
+    session_revocations: Arc<std::sync::RwLock<HashMap<Uuid, i64>>>,
+    /// Directory holding the database file and stored uploads
+    data_dir: Arc<Path>,
+    /// See [`Database::access_epoch`]
+    access_epoch: Arc<AtomicU64>,
 }
 
 impl Database {
@@ -246,6 +428,7 @@ impl Database {
             let _ = write_txn.open_table(USERS_BY_USERNAME)?;
             let _ = write_txn.open_table(USERS_BY_EMAIL)?;
             let _ = write_txn.open_table(SESSIONS)?;
+            let _ = write_txn.open_table(SESSIONS_BY_USER)?;
             let _ = write_txn.open_table(BOOKINGS)?;
             let _ = write_txn.open_table(BOOKINGS_BY_USER)?;
             let _ = write_txn.open_table(PARKING_LOTS)?;
@@ -265,6 +448,7 @@ impl Database {
             let _ = write_txn.open_table(PUSH_SUBSCRIPTIONS)?;
             let _ = write_txn.open_table(ZONES)?;
             let _ = write_txn.open_table(FAVORITES)?;
+            let _ = write_txn.open_table(FAVORITE_LOTS)?;
             let _ = write_txn.open_table(AUDIT_LOG)?;
             let _ = write_txn.open_table(TRANSLATION_PROPOSALS)?;
             let _ = write_txn.open_table(TRANSLATION_VOTES)?;
@@ -273,6 +457,43 @@ impl Database {
             let _ = write_txn.open_table(EV_CHARGERS)?;
             let _ = write_txn.open_table(CHARGING_SESSIONS)?;
             let _ = write_txn.open_table(STRIPE_EVENTS)?;
+            let _ = write_txn.open_table(SLOT_STATUS_HISTORY)?;
+            let _ = write_txn.open_table(EMAIL_QUEUE)?;
+            let _ = write_txn.open_table(LOGIN_HISTORY)?;
+            let _ = write_txn.open_table(PERMITS)?;
+            let _ = write_txn.open_table(SEARCH_INDEX)?;
+            let _ = write_txn.open_table(SEARCH_DOCS)?;
+            let _ = write_txn.open_table(INVOICES)?;
+            let _ = write_txn.open_table(INVOICES_BY_USER)?;
+            let _ = write_txn.open_table(PENDING_SLOT_ASSIGNMENTS)?;
+            let _ = write_txn.open_table(ROLES)?;
+            let _ = write_txn.open_table(ROLE_MEMBERS)?;
+            let _ = write_txn.open_table(LOT_MANAGERS)?;
+            let _ = write_txn.open_table(BOOKINGS_BY_START)?;
+            let _ = write_txn.open_table(BOOKINGS_BY_USER_START)?;
+            let _ = write_txn.open_table(USERS_BY_CREATED)?;
+            let _ = write_txn.open_table(LOTS_BY_NAME)?;
+            let _ = write_txn.open_table(LIST_ORDER_KEYS)?;
+            let _ = write_txn.open_table(PASSKEYS)?;
+            let _ = write_txn.open_table(INVITES)?;
+            let _ = write_txn.open_table(REFRESH_TOKEN_FAMILIES)?;
+            let _ = write_txn.open_table(BOOKINGS_BY_SLOT_END)?;
+            let _ = write_txn.open_table(BOOKING_SLOT_END_KEYS)?;
+            let _ = write_txn.open_table(LIVE_BOOKINGS_BY_END)?;
+            let _ = write_txn.open_table(LIVE_BOOKING_END_KEYS)?;
+            let _ = write_txn.open_table(DUMMY_USERS)?;
+            let _ = write_txn.open_table(SLOT_HOLDS)?;
+            let _ = write_txn.open_table(SLOT_HOLDS_BY_LOT)?;
+            let _ = write_txn.open_table(RECTIFICATION_REQUESTS)?;
+            let _ = write_txn.open_table(SLOTS_BY_TYPE)?;
+            let _ = write_txn.open_table(SLOT_TYPE_KEYS)?;
+            let _ = write_txn.open_table(SESSION_REVOCATIONS)?;
+            let _ = write_txn.open_table(BOOKING_NOTES)?;
+            let _ = write_txn.open_table(BOOKING_ATTACHMENTS)?;
+            let _ = write_txn.open_table(VISITOR_PASS_BLOCKS)?;
+            let _ = write_txn.open_table(VISITOR_PASSES)?;
+            let _ = write_txn.open_table(VISITOR_PASS_KEYS)?;
+            let _ = write_txn.open_table(FEATURE_FLAGS)?;
         }
         write_txn.commit()?;
 
@@ -311,6 +532,29 @@ impl Database {
             None
         };
 
+        // Get or create the access-token signing secret so issued tokens
+        // stay valid across restarts
+        let token_secret = {
+            let read_txn = db.begin_read()?;
+            let table = read_txn.open_table(SETTINGS)?;
+            if let Some(value) = table.get(SETTING_TOKEN_SECRET)? {
+                value.value().to_string()
+            } else {
+                let mut secret = [0u8; 32];
+                rand::rng().fill_bytes(&mut secret);
+                let secret = hex::encode(secret);
+
+                let write_txn = db.begin_write()?;
+                {
+                    let mut table = write_txn.open_table(SETTINGS)?;
+                    table.insert(SETTING_TOKEN_SECRET, secret.as_str())?;
+                }
+                write_txn.commit()?;
+
+                secret
+            }
+        };
+
         // Set database version if new
         if !db_exists {
             let write_txn = db.begin_write()?;
@@ -321,11 +565,25 @@ impl Database {
             write_txn.commit()?;
         }
 
-        Ok(Self {
+        let session_revocations = session_revocations::load_session_revocations(&db)?;
+
+        let database = Self {
             inner: Arc::new(RwLock::new(db)),
             encryptor,
             encryption_enabled: config.encryption_enabled,
-        })
+            token_secret: token_secret.into(),
+            session_revocations: Arc::new(std::sync::RwLock::new(session_revocations)),
+            data_dir: config.path.clone().into(),
+            access_epoch: Arc::new(AtomicU64::new(0)),
+        };
+        database.index_legacy_sessions()?;
+        database.index_existing_documents()?;
+        database.index_existing_booking_times()?;
+        database.index_existing_slot_types()?;
+        database.index_existing_slot_holds()?;
+        database.index_existing_list_order()?;
+        database.migrate_legacy_lot_managers()?;
+        Ok(database)
     }
 
     /// Check if encryption is enabled
@@ -333,6 +591,22 @@ impl Database {
         self.encryption_enabled
     }
 
+    /// Secret used to sign access tokens (generated once per database)
+    pub fn token_signing_secret(&self) -> &str {
+        &self.token_secret
+    }
+
+    /// Counter advanced after every write that can change what a user may
+    /// do: their account, custom roles and memberships, and lot manager
+    /// assignments. Caches of resolved access compare it to stay current.
+    pub fn access_epoch(&self) -> u64 {
+        self.access_epoch.load(Ordering::Acquire)
+    }
+
+    pub(super) fn access_changed(&self) {
+        self.access_epoch.fetch_add(1, Ordering::AcqRel);
+    }
+
     /// Clear all data tables for demo reset. Preserves DB structure and settings.
     /// Admin user must be re-created after calling this.
     pub async fn clear_all_data(&self) -> Result<()> {
@@ -363,12 +637,45 @@ impl Database {
         drain_table!(write_txn, USERS_BY_USERNAME);
         drain_table!(write_txn, USERS_BY_EMAIL);
         drain_table!(write_txn, SESSIONS);
+        drain_table!(write_txn, SESSIONS_BY_USER);
+        drain_table!(write_txn, SESSION_REVOCATIONS);
         drain_table!(write_txn, BOOKINGS);
         drain_table!(write_txn, BOOKINGS_BY_USER);
+        drain_table!(write_txn, BOOKING_NOTES);
+        drain_table!(write_txn, BOOKING_ATTACHMENTS);
+        drain_table!(write_txn, VISITOR_PASS_BLOCKS);
+        drain_table!(write_txn, VISITOR_PASSES);
+        drain_table!(write_txn, VISITOR_PASS_KEYS);
         drain_table!(write_txn, PARKING_LOTS);
         drain_table!(write_txn, PARKING_SLOTS);
         drain_table!(write_txn, SLOTS_BY_LOT);
+        drain_table!(write_txn, SLOTS_BY_TYPE);
+        drain_table!(write_txn, SLOT_TYPE_KEYS);
         drain_table!(write_txn, VEHICLES);
+        drain_table!(write_txn, SEARCH_INDEX);
+        drain_table!(write_txn, SEARCH_DOCS);
+        drain_table!(write_txn, INVOICES);
+        drain_table!(write_txn, INVOICES_BY_USER);
+        drain_table!(write_txn, PENDING_SLOT_ASSIGNMENTS);
+        drain_table!(write_txn, ROLES);
+        drain_table!(write_txn, ROLE_MEMBERS);
+        drain_table!(write_txn, LOT_MANAGERS);
+        drain_table!(write_txn, BOOKINGS_BY_START);
+        drain_table!(write_txn, BOOKINGS_BY_USER_START);
+        drain_table!(write_txn, USERS_BY_CREATED);
+        drain_table!(write_txn, LOTS_BY_NAME);
+        drain_table!(write_txn, LIST_ORDER_KEYS);
+        drain_table!(write_txn, PASSKEYS);
+        drain_table!(write_txn, INVITES);
+        drain_table!(write_txn, REFRESH_TOKEN_FAMILIES);
+        drain_table!(write_txn, BOOKINGS_BY_SLOT_END);
+        drain_table!(write_txn, BOOKING_SLOT_END_KEYS);
+        drain_table!(write_txn, LIVE_BOOKINGS_BY_END);
+        drain_table!(write_txn, LIVE_BOOKING_END_KEYS);
+        drain_table!(write_txn, DUMMY_USERS);
+        drain_table!(write_txn, SLOT_HOLDS);
+        drain_table!(write_txn, SLOT_HOLDS_BY_LOT);
+        drain_table!(write_txn, RECTIFICATION_REQUESTS);
         drain_table!(write_txn, CREDIT_TRANSACTIONS);
         drain_table!(write_txn, ABSENCES);
         drain_table!(write_txn, WAITLIST);
@@ -381,6 +688,7 @@ impl Database {
         drain_table!(write_txn, PUSH_SUBSCRIPTIONS);
         drain_table!(write_txn, ZONES);
         drain_table!(write_txn, FAVORITES);
+        drain_table!(write_txn, FAVORITE_LOTS);
         drain_table!(write_txn, AUDIT_LOG);
         drain_table!(write_txn, TRANSLATION_PROPOSALS);
         drain_table!(write_txn, TRANSLATION_VOTES);
@@ -389,8 +697,18 @@ impl Database {
         drain_table!(write_txn, EV_CHARGERS);
         drain_table!(write_txn, CHARGING_SESSIONS);
         drain_table!(write_txn, STRIPE_EVENTS);
+        drain_table!(write_txn, SLOT_STATUS_HISTORY);
+        drain_table!(write_txn, EMAIL_QUEUE);
+        drain_table!(write_txn, LOGIN_HISTORY);
+        drain_table!(write_txn, PERMITS);
         // Preserve SETTINGS table (encryption salt, setup status, etc.)
         write_txn.commit()?;
+        self.access_changed();
+        self.session_revocations
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clear();
+        self.remove_attachment_files(None).await;
         info!("All data tables cleared for demo reset");
         Ok(())
     }
@@ -434,6 +752,7 @@ impl Database {
             slots: read_txn.open_table(PARKING_SLOTS)?.len()?,
             sessions: read_txn.open_table(SESSIONS)?.len()?,
             vehicles: read_txn.open_table(VEHICLES)?.len()?,
+            dummy_users: read_txn.open_table(DUMMY_USERS)?.len()?,
         })
     }
 
@@ -450,7 +769,22 @@ impl Database {
         }
     }
 
+    /// Time a read transaction on `table`; see [`SlowOpThresholds`](slow_ops::SlowOpThresholds).
+    pub(crate) fn time_read(table: &'static str, key: Option<&str>) -> OpTimer {
+        OpTimer::start(OpKind::Read, table, key)
+    }
+
+    /// Time a write transaction on `table`; see [`SlowOpThresholds`](slow_ops::SlowOpThresholds).
+    pub(crate) fn time_write(table: &'static str, key: Option<&str>) -> OpTimer {
+        OpTimer::start(OpKind::Write, table, key)
+    }
+
     pub(crate) fn deserialize<T: serde::de::DeserializeOwned>(&self, data: &[u8]) -> Result<T> {
+        let _timer = OpTimer::start(
+            OpKind::Decode,
+            slow_ops::record_label(std::any::type_name::<T>()),
+            None,
+        );
         let json = if let Some(ref enc) = self.encryptor {
             enc.decrypt(data)?
         } else {
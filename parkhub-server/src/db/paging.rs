@@ -0,0 +1,292 @@
+//! Sorted, filtered pages of list endpoints.
+//!
+//! The list endpoints used to return whole tables. [`page`] keeps the records
+//! a filter accepts, sorts them by a key and returns one page, addressed
+//! either by page number or by the cursor handed out with the previous page.
+//! A cursor names the sort key and id of the last record returned, so paging
+//! through a list that gains or loses records in between requests neither
+//! skips nor repeats records, as offsets do.
+//!
+//! [`page`] reads and sorts every record, so it only serves ad-hoc sort
+//! keys. Each list's default order is kept in an order index instead and
+//! paged by walking it from the cursor (see `list_order.rs`).
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// Separates sort key and id in an encoded cursor.
+const CURSOR_SEPARATOR: char = '\u{1f}';
+
+/// Position of a record in a sorted list: its sort key, then its id.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Cursor {
+    key: String,
+    id: String,
+}
+
+impl Cursor {
+    /// Opaque, URL-safe form handed to clients.
+    #[must_use]
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.index_key())
+    }
+
+    /// Parse a cursor produced by [`Cursor::encode`]; `None` if malformed.
+    #[must_use]
+    pub fn decode(encoded: &str) -> Option<Self> {
+        let text = String::from_utf8(URL_SAFE_NO_PAD.decode(encoded).ok()?).ok()?;
+        Self::from_index_key(&text)
+    }
+
+    /// Position of record `id` with sort key `key`.
+    pub(super) const fn new(key: String, id: String) -> Self {
+        Self { key, id }
+    }
+
+    /// Key of this position in an order index: sort key, separator, id.
+    /// Index keys order like positions unless a sort key holds control
+    /// characters.
+    pub(super) fn index_key(&self) -> String {
+        format!("{}{CURSOR_SEPARATOR}{}", self.key, self.id)
+    }
+
+    /// Position named by an order index key.
+    pub(super) fn from_index_key(key: &str) -> Option<Self> {
+        let (key, id) = key.rsplit_once(CURSOR_SEPARATOR)?;
+        Some(Self {
+            key: key.to_string(),
+            id: id.to_string(),
+        })
+    }
+}
+
+/// Order of a list page.
+pub enum SortBy<T> {
+    /// The list's default order, read from its order index.
+    Default,
+    /// Any other order: every record is read and sorted by this key.
+    Key(fn(&T) -> String),
+}
+
+impl<T> Clone for SortBy<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SortBy<T> {}
+
+/// Where a page starts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageStart {
+    /// Skip this many records.
+    Offset(usize),
+    /// Start right after this record.
+    After(Cursor),
+}
+
+/// Which records of a list to return, and in which order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageQuery {
+    pub start: PageStart,
+    pub limit: usize,
+    pub descending: bool,
+}
+
+impl PageQuery {
+    /// Page `page` (1-based) of `per_page` records, or the `per_page` records
+    /// after `cursor` if one is given. `None` if the cursor is malformed.
+    #[must_use]
+    pub fn new(page: i32, per_page: i32, cursor: Option<&str>, descending: bool) -> Option<Self> {
+        let (skip, limit) = super::pagination_offset(page, per_page);
+        let start = match cursor {
+            Some(cursor) => PageStart::After(Cursor::decode(cursor)?),
+            None => PageStart::Offset(skip),
+        };
+        Some(Self {
+            start,
+            limit,
+            descending,
+        })
+    }
+
+    /// Every record, in order.
+    #[must_use]
+    pub const fn all(descending: bool) -> Self {
+        Self {
+            start: PageStart::Offset(0),
+            limit: usize::MAX,
+            descending,
+        }
+    }
+}
+
+/// One page of a list.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Records accepted by the filter, across all pages.
+    pub total: usize,
+    /// Cursor of the following page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// The page of `records` selected by `query`: those accepted by `filter`,
+/// ordered by `sort_key` with ties broken by `id`.
+///
+/// Holds and sorts every record; default orders are paged from their order
+/// index instead.
+pub fn page<T>(
+    records: impl IntoIterator<Item = T>,
+    id: impl Fn(&T) -> String,
+    sort_key: impl Fn(&T) -> String,
+    filter: impl Fn(&T) -> bool,
+    query: &PageQuery,
+) -> Page<T> {
+    let mut sorted: Vec<(Cursor, T)> = records
+        .into_iter()
+        .filter(|record| filter(record))
+        .map(|record| {
+            let position = Cursor {
+                key: sort_key(&record),
+                id: id(&record),
+            };
+            (position, record)
+        })
+        .collect();
+    sorted.sort_by(|a, b| a.0.cmp(&b.0));
+    if query.descending {
+        sorted.reverse();
+    }
+
+    let total = sorted.len();
+    let skip = match &query.start {
+        PageStart::Offset(skip) => *skip,
+        PageStart::After(cursor) => sorted.partition_point(|(position, _)| {
+            if query.descending {
+                position >= cursor
+            } else {
+                position <= cursor
+            }
+        }),
+    };
+    let mut items: Vec<(Cursor, T)> = sorted
+        .into_iter()
+        .skip(skip)
+        .take(query.limit.saturating_add(1))
+        .collect();
+    let next_cursor = if items.len() > query.limit {
+        items.truncate(query.limit);
+        items.last().map(|(position, _)| position.encode())
+    } else {
+        None
+    };
+
+    Page {
+        items: items.into_iter().map(|(_, record)| record).collect(),
+        total,
+        next_cursor,
+    }
+}
+
+/// Sort key of a timestamp; keys order as the timestamps do.
+#[must_use]
+pub fn time_key(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// Sort key of a count; keys order as the counts do.
+#[must_use]
+pub fn count_key(count: u64) -> String {
+    format!("{count:020}")
+}
+
+/// Sort key of text, ignoring case.
+#[must_use]
+pub fn text_key(text: &str) -> String {
+    text.to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers() -> Vec<u64> {
+        vec![5, 3, 9, 1, 7, 2, 8]
+    }
+
+    fn page_of(query: &PageQuery) -> Page<u64> {
+        page(
+            numbers(),
+            u64::to_string,
+            |n| count_key(*n),
+            |n| *n != 8,
+            query,
+        )
+    }
+
+    #[test]
+    fn offset_pages_are_sorted_and_filtered() {
+        let first = page_of(&PageQuery::new(1, 4, None, false).unwrap());
+        assert_eq!(first.items, vec![1, 2, 3, 5]);
+        assert_eq!(first.total, 6);
+        assert!(first.next_cursor.is_some());
+
+        let second = page_of(&PageQuery::new(2, 4, None, false).unwrap());
+        assert_eq!(second.items, vec![7, 9]);
+        assert!(second.next_cursor.is_none());
+    }
+
+    #[test]
+    fn cursor_continues_after_last_record_in_both_orders() {
+        for descending in [false, true] {
+            let mut seen = Vec::new();
+            let mut cursor: Option<String> = None;
+            loop {
+                let query = PageQuery::new(1, 2, cursor.as_deref(), descending).unwrap();
+                let page = page_of(&query);
+                seen.extend(page.items);
+                match page.next_cursor {
+                    Some(next) => cursor = Some(next),
+                    None => break,
+                }
+            }
+            let mut expected = vec![1, 2, 3, 5, 7, 9];
+            if descending {
+                expected.reverse();
+            }
+            assert_eq!(seen, expected);
+        }
+    }
+
+    #[test]
+    fn cursor_survives_removed_records() {
+        let first = page_of(&PageQuery::new(1, 3, None, false).unwrap());
+        assert_eq!(first.items, vec![1, 2, 3]);
+        let cursor = first.next_cursor.unwrap();
+
+        // Record 3, the last one returned, is gone before the next request.
+        let next = page(
+            numbers().into_iter().filter(|n| *n != 3),
+            u64::to_string,
+            |n| count_key(*n),
+            |_| true,
+            &PageQuery::new(1, 3, Some(&cursor), false).unwrap(),
+        );
+        assert_eq!(next.items, vec![5, 7, 8]);
+    }
+
+    #[test]
+    fn malformed_cursor_is_rejected() {
+        assert!(PageQuery::new(1, 20, Some("not a cursor!"), false).is_none());
+        assert!(Cursor::decode(&URL_SAFE_NO_PAD.encode("no separator")).is_none());
+    }
+
+    #[test]
+    fn time_keys_order_like_timestamps() {
+        let earlier = DateTime::parse_from_rfc3339("2026-01-02T09:00:00Z").unwrap();
+        let later = DateTime::parse_from_rfc3339("2026-01-02T10:00:00.5+00:00").unwrap();
+        assert!(time_key(earlier.to_utc()) < time_key(later.to_utc()));
+    }
+}
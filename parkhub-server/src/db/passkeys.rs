@@ -0,0 +1,103 @@
+//! WebAuthn passkeys.
+//!
+//! `PASSKEYS` holds each user's registered credentials keyed
+//! `"{user_id}:{passkey_id}"`, so the login ceremony loads a user's
+//! passkeys with one range scan.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+use webauthn_rs::prelude::Passkey;
+
+use super::{Database, PASSKEYS};
+
+/// A passkey registered by a user.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredPasskey {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Label chosen by the user, e.g. "Work laptop"
+    pub name: String,
+    /// Public key, credential ID and signature counter
+    pub credential: Passkey,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+fn passkey_key(user_id: Uuid, id: Uuid) -> String {
+    format!("{user_id}:{id}")
+}
+
+impl Database {
+    /// Save a passkey (insert or update)
+    pub async fn save_passkey(&self, passkey: &StoredPasskey) -> Result<()> {
+        let key = passkey_key(passkey.user_id, passkey.id);
+        let data = self.serialize(passkey)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(PASSKEYS)?;
+            table.insert(key.as_str(), data.as_slice())?;
+        }
+        write_txn.commit()?;
+        debug!("Saved passkey {} of user {}", passkey.id, passkey.user_id);
+        Ok(())
+    }
+
+    /// List a user's passkeys, oldest first
+    pub async fn list_passkeys_for_user(&self, user_id: Uuid) -> Result<Vec<StoredPasskey>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(PASSKEYS)?;
+        let start = format!("{user_id}:");
+        let end = format!("{user_id};");
+        let mut passkeys = Vec::new();
+        for entry in table.range(start.as_str()..end.as_str())? {
+            let (_, value) = entry?;
+            passkeys.push(self.deserialize::<StoredPasskey>(value.value())?);
+        }
+        passkeys.sort_by_key(|p| p.created_at);
+        Ok(passkeys)
+    }
+
+    /// Delete one of a user's passkeys. Returns `false` if the user has no
+    /// passkey with that ID.
+    pub async fn delete_passkey(&self, user_id: Uuid, id: Uuid) -> Result<bool> {
+        let key = passkey_key(user_id, id);
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let removed = {
+            let mut table = write_txn.open_table(PASSKEYS)?;
+            table.remove(key.as_str())?.is_some()
+        };
+        write_txn.commit()?;
+        Ok(removed)
+    }
+
+    /// Drop all passkeys of a user (account deletion).
+    pub async fn delete_passkeys_for_user(&self, user_id: &str) -> Result<()> {
+        let start = format!("{user_id}:");
+        let end = format!("{user_id};");
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(PASSKEYS)?;
+            let keys: Vec<String> = table
+                .range(start.as_str()..end.as_str())?
+                .map(|entry| entry.map(|(k, _)| k.value().to_string()))
+                .collect::<Result<_, _>>()?;
+            for key in &keys {
+                table.remove(key.as_str())?;
+            }
+        }
+        write_txn.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+//! Parking permits (resident and contractor permits with validity periods).
+
+use anyhow::Result;
+use redb::{ReadableDatabase, ReadableTable};
+use tracing::debug;
+
+use parkhub_common::models::Permit;
+
+use super::{Database, PERMITS};
+
+impl Database {
+    /// Save a permit (insert or replace)
+    pub async fn save_permit(&self, permit: &Permit) -> Result<()> {
+        let id = permit.id.to_string();
+        let data = self.serialize(permit)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(PERMITS)?;
+            table.insert(id.as_str(), data.as_slice())?;
+        }
+        write_txn.commit()?;
+        debug!("Saved permit: {}", permit.id);
+        Ok(())
+    }
+
+    /// Get a permit by ID
+    pub async fn get_permit(&self, id: &str) -> Result<Option<Permit>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(PERMITS)?;
+        match table.get(id)? {
+            Some(value) => Ok(Some(self.deserialize(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List all permits, soonest expiry first
+    pub async fn list_permits(&self) -> Result<Vec<Permit>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(PERMITS)?;
+
+        let mut permits = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            permits.push(self.deserialize(value.value())?);
+        }
+        permits.sort_by(|a: &Permit, b: &Permit| a.valid_until.cmp(&b.valid_until));
+        Ok(permits)
+    }
+
+    /// List the permits held by a user account
+    pub async fn list_permits_by_user(&self, user_id: &str) -> Result<Vec<Permit>> {
+        Ok(self
+            .list_permits()
+            .await?
+            .into_iter()
+            .filter(|p| p.user_id.is_some_and(|u| u.to_string() == user_id))
+            .collect())
+    }
+
+    /// Delete every permit held by a user account (account deletion).
+    /// Returns how many were removed.
+    pub async fn delete_permits_for_user(&self, user_id: &str) -> Result<usize> {
+        let permits = self.list_permits_by_user(user_id).await?;
+        for permit in &permits {
+            self.delete_permit(&permit.id.to_string()).await?;
+        }
+        Ok(permits.len())
+    }
+
+    /// Delete a permit by ID
+    pub async fn delete_permit(&self, id: &str) -> Result<bool> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let removed = {
+            let mut table = write_txn.open_table(PERMITS)?;
+            table.remove(id)?.is_some()
+        };
+        write_txn.commit()?;
+        Ok(removed)
+    }
+}
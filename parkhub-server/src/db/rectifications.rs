@@ -0,0 +1,140 @@
+//! Data rectification requests (GDPR Art. 16).
+//!
+//! `RECTIFICATION_REQUESTS` holds what users reported as wrong in fields
+//! they cannot edit themselves, keyed by request ID. The requests carry
+//! the old and the proposed value, so they are deleted with the account.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+
+use super::{Database, RECTIFICATION_REQUESTS};
+
+/// Where a rectification request stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RectificationStatus {
+    /// Waiting for an admin
+    Open,
+    /// The data was corrected
+    Corrected,
+    /// The data was left as it is; the note says why
+    Rejected,
+}
+
+/// A user's report that one of their profile fields is wrong.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RectificationRequest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Username at the time of the request
+    pub username: String,
+    /// Tenant of the user; admins of other tenants do not see the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tenant_id: Option<String>,
+    /// Profile field reported as wrong, e.g. `email`
+    pub field: String,
+    /// Value of the field when the request was made
+    pub current_value: Option<String>,
+    /// What the user says the value should be
+    pub requested_value: String,
+    pub reason: Option<String>,
+    pub status: RectificationStatus,
+    /// Admin's answer to the user
+    pub resolution_note: Option<String>,
+    pub resolved_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl Database {
+    /// Save a rectification request (insert or replace).
+    pub async fn save_rectification_request(&self, request: &RectificationRequest) -> Result<()> {
+        let id = request.id.to_string();
+        let data = self.serialize(request)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(RECTIFICATION_REQUESTS)?;
+            table.insert(id.as_str(), data.as_slice())?;
+        }
+        write_txn.commit()?;
+        debug!(
+            "Saved rectification request {} for user {}",
+            request.id, request.user_id
+        );
+        Ok(())
+    }
+
+    /// Get a rectification request by ID
+    pub async fn get_rectification_request(
+        &self,
+        id: &str,
+    ) -> Result<Option<RectificationRequest>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(RECTIFICATION_REQUESTS)?;
+        match table.get(id)? {
+            Some(value) => Ok(Some(self.deserialize(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List all rectification requests, newest first
+    pub async fn list_rectification_requests(&self) -> Result<Vec<RectificationRequest>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(RECTIFICATION_REQUESTS)?;
+        let mut requests = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            requests.push(self.deserialize::<RectificationRequest>(value.value())?);
+        }
+        requests.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(requests)
+    }
+
+    /// List the rectification requests of a user, newest first
+    pub async fn list_rectification_requests_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<RectificationRequest>> {
+        Ok(self
+            .list_rectification_requests()
+            .await?
+            .into_iter()
+            .filter(|r| r.user_id == user_id)
+            .collect())
+    }
+
+    /// Delete every rectification request of a user (account deletion).
+    /// Returns how many were removed.
+    pub async fn delete_rectification_requests_for_user(&self, user_id: &str) -> Result<u64> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let removed = {
+            let mut table = write_txn.open_table(RECTIFICATION_REQUESTS)?;
+            let mut ids = Vec::new();
+            for entry in table.iter()? {
+                let (id, value) = entry?;
+                let request = self.deserialize::<RectificationRequest>(value.value())?;
+                if request.user_id.to_string() == user_id {
+                    ids.push(id.value().to_string());
+                }
+            }
+            for id in &ids {
+                table.remove(id.as_str())?;
+            }
+            ids.len() as u64
+        };
+        write_txn.commit()?;
+        Ok(removed)
+    }
+}
@@ -0,0 +1,121 @@
+//! Refresh-token families and reuse detection.
+//!
+//! A refresh token is good for one refresh. Each refresh ends the session
+//! that owns it and starts a new session — with a new refresh token — in
+//! the same family (`Session::family_id`). `REFRESH_TOKEN_FAMILIES`
+//! remembers the tokens rotated out this way, keyed by their SHA-256 so a
+//! copy of the database does not leak them, until the session they belonged
+//! to would have expired. A remembered token presented again was copied
+//! from its owner, and the caller revokes the user's sessions.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use uuid::Uuid;
+
+use super::{Database, REFRESH_TOKEN_FAMILIES, SESSIONS, SESSIONS_BY_USER, Session};
+
+/// A refresh token that was already exchanged for a new one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotatedRefreshToken {
+    pub family_id: Uuid,
+    pub user_id: Uuid,
+    pub rotated_at: DateTime<Utc>,
+    /// Expiry of the session the token belonged to; the record is pruned
+    /// after it
+    pub expires_at: DateTime<Utc>,
+}
+
+fn refresh_token_key(refresh_token: &str) -> String {
+    hex::encode(Sha256::digest(
+        format!("refresh:{refresh_token}").as_bytes(),
+    ))
+}
+
+impl Database {
+    /// Rotate out the refresh token of `session`: delete the session stored
+    /// under `session_id` and remember its refresh token as used, in one
+    /// transaction.
+    ///
+    /// Returns `false` if the session was already gone, i.e. a concurrent
+    /// refresh with the same token got there first.
+    pub async fn rotate_refresh_token(&self, session_id: &str, session: &Session) -> Result<bool> {
+        let key = refresh_token_key(&session.refresh_token);
+        let record = self.serialize(&RotatedRefreshToken {
+            family_id: session.family_id,
+            user_id: session.user_id,
+            rotated_at: Utc::now(),
+            expires_at: session.expires_at,
+        })?;
+
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let rotated = {
+            let mut sessions = write_txn.open_table(SESSIONS)?;
+            if sessions.remove(session_id)?.is_some() {
+                let mut idx = write_txn.open_table(SESSIONS_BY_USER)?;
+                let idx_key = format!("{}:{session_id}", session.user_id);
+                idx.remove(idx_key.as_str())?;
+                let mut families = write_txn.open_table(REFRESH_TOKEN_FAMILIES)?;
+                families.insert(key.as_str(), record.as_slice())?;
+                true
+            } else {
+                false
+            }
+        };
+        write_txn.commit()?;
+        if rotated {
+            debug!(
+                "Rotated refresh token of family {} for user {}",
+                session.family_id, session.user_id
+            );
+        }
+        Ok(rotated)
+    }
+
+    /// Look up `refresh_token` among the tokens already rotated out.
+    pub async fn get_rotated_refresh_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<Option<RotatedRefreshToken>> {
+        let key = refresh_token_key(refresh_token);
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(REFRESH_TOKEN_FAMILIES)?;
+        table
+            .get(key.as_str())?
+            .map(|value| self.deserialize(value.value()))
+            .transpose()
+    }
+
+    /// Forget rotated-out tokens whose session has expired, since they
+    /// would be rejected anyway. Returns how many were removed.
+    pub async fn prune_rotated_refresh_tokens(&self) -> Result<u64> {
+        let now = Utc::now();
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let removed = {
+            let mut table = write_txn.open_table(REFRESH_TOKEN_FAMILIES)?;
+            let mut keys = Vec::new();
+            for entry in table.iter()? {
+                let (key, value) = entry?;
+                let record: RotatedRefreshToken = self.deserialize(value.value())?;
+                if record.expires_at < now {
+                    keys.push(key.value().to_string());
+                }
+            }
+            for key in &keys {
+                table.remove(key.as_str())?;
+            }
+            keys.len() as u64
+        };
+        write_txn.commit()?;
+        Ok(removed)
+    }
+}
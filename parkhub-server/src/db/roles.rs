@@ -0,0 +1,189 @@
+//! Custom roles and their members.
+//!
+//! `ROLES` holds the admin-defined [`Role`]s keyed by role ID.
+//! `ROLE_MEMBERS` records who holds which role, keyed
+//! `"{user_id}:{role_id}"` so a user's roles are one range scan away — the
+//! auth middleware resolves them on every request.
+
+use anyhow::Result;
+use redb::{ReadableDatabase, ReadableTable};
+use tracing::debug;
+use uuid::Uuid;
+
+use parkhub_common::Role;
+
+use super::{Database, ROLE_MEMBERS, ROLES};
+
+fn member_key(user_id: Uuid, role_id: Uuid) -> String {
+    format!("{user_id}:{role_id}")
+}
+
+impl Database {
+    /// Save a custom role (insert or update)
+    pub async fn save_role(&self, role: &Role) -> Result<()> {
+        let id = role.id.to_string();
+        let data = self.serialize(role)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(ROLES)?;
+            table.insert(id.as_str(), data.as_slice())?;
+        }
+        write_txn.commit()?;
+        self.access_changed();
+        debug!("Saved role: {} ({})", role.name, role.id);
+        Ok(())
+    }
+
+    /// Get a custom role by ID
+    pub async fn get_role(&self, role_id: Uuid) -> Result<Option<Role>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(ROLES)?;
+        match table.get(role_id.to_string().as_str())? {
+            Some(value) => Ok(Some(self.deserialize(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List all custom roles, by name
+    pub async fn list_roles(&self) -> Result<Vec<Role>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(ROLES)?;
+        let mut roles = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            roles.push(self.deserialize::<Role>(value.value())?);
+        }
+        roles.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+        Ok(roles)
+    }
+
+    /// Delete a custom role and all its memberships
+    pub async fn delete_role(&self, role_id: Uuid) -> Result<bool> {
+        let id = role_id.to_string();
+        let suffix = format!(":{id}");
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let existed = {
+            let mut table = write_txn.open_table(ROLES)?;
+            let existed = table.remove(id.as_str())?.is_some();
+
+            let mut members = write_txn.open_table(ROLE_MEMBERS)?;
+            let keys: Vec<String> = members
+                .iter()?
+                .map(|entry| entry.map(|(k, _)| k.value().to_string()))
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .filter(|key| key.ends_with(&suffix))
+                .collect();
+            for key in &keys {
+                members.remove(key.as_str())?;
+            }
+            existed
+        };
+        write_txn.commit()?;
+        self.access_changed();
+        debug!("Deleted role: {}", id);
+        Ok(existed)
+    }
+
+    /// Give `user_id` the role `role_id`. Returns `false` if they already
+    /// held it.
+    pub async fn assign_role(&self, user_id: Uuid, role_id: Uuid) -> Result<bool> {
+        let key = member_key(user_id, role_id);
+        let role = role_id.to_string();
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let added = {
+            let mut members = write_txn.open_table(ROLE_MEMBERS)?;
+            members.insert(key.as_str(), role.as_str())?.is_none()
+        };
+        write_txn.commit()?;
+        self.access_changed();
+        Ok(added)
+    }
+
+    /// Take the role `role_id` away from `user_id`. Returns `false` if they
+    /// did not hold it.
+    pub async fn unassign_role(&self, user_id: Uuid, role_id: Uuid) -> Result<bool> {
+        let key = member_key(user_id, role_id);
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let removed = {
+            let mut members = write_txn.open_table(ROLE_MEMBERS)?;
+            members.remove(key.as_str())?.is_some()
+        };
+        write_txn.commit()?;
+        self.access_changed();
+        Ok(removed)
+    }
+
+    /// List the custom roles held by a user
+    pub async fn list_roles_for_user(&self, user_id: Uuid) -> Result<Vec<Role>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let members = read_txn.open_table(ROLE_MEMBERS)?;
+        let table = read_txn.open_table(ROLES)?;
+
+        let start = format!("{user_id}:");
+        let end = format!("{user_id};");
+        let mut roles = Vec::new();
+        for entry in members.range(start.as_str()..end.as_str())? {
+            let (_, role_id) = entry?;
+            if let Some(value) = table.get(role_id.value())? {
+                roles.push(self.deserialize::<Role>(value.value())?);
+            }
+        }
+        Ok(roles)
+    }
+
+    /// List the IDs of the users holding a role
+    pub async fn list_role_members(&self, role_id: Uuid) -> Result<Vec<Uuid>> {
+        let suffix = format!(":{role_id}");
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let members = read_txn.open_table(ROLE_MEMBERS)?;
+        let mut user_ids = Vec::new();
+        for entry in members.iter()? {
+            let (key, _) = entry?;
+            if let Some(user_id) = key.value().strip_suffix(&suffix)
+                && let Ok(user_id) = user_id.parse()
+            {
+                user_ids.push(user_id);
+            }
+        }
+        Ok(user_ids)
+    }
+
+    /// Drop all role memberships of a user (account deletion).
+    pub async fn delete_role_memberships_for_user(&self, user_id: &str) -> Result<()> {
+        let start = format!("{user_id}:");
+        let end = format!("{user_id};");
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut members = write_txn.open_table(ROLE_MEMBERS)?;
+            let keys: Vec<String> = members
+                .range(start.as_str()..end.as_str())?
+                .map(|entry| entry.map(|(k, _)| k.value().to_string()))
+                .collect::<Result<_, _>>()?;
+            for key in &keys {
+                members.remove(key.as_str())?;
+            }
+        }
+        write_txn.commit()?;
+        self.access_changed();
+        Ok(())
+    }
+}
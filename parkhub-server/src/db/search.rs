@@ -0,0 +1,475 @@
+//! Token index for the admin search across users, bookings and lots.
+//!
+//! Every indexed record is a *document* keyed `"{kind}:{id}"`. Vehicles are
+//! indexed as part of their owner (`"user:{user_id}/vehicle:{vehicle_id}"`),
+//! so a plate search finds the user. License plates get a document of their
+//! own below their vehicle or booking (`".../plate"`), so searches on behalf
+//! of someone who may not see plates in full can leave them out. `SEARCH_INDEX` maps
+//! `"{token}\x1f{document}"` to the token's weight in that document, and
+//! `SEARCH_DOCS` keeps each document's tokens so a rewrite can drop the old
+//! ones. Both are updated in the same write transaction as the record.
+//!
+//! Like the username and email indexes, tokens are stored as plaintext keys
+//! even when the database is encrypted.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use redb::{
+    ReadableDatabase, ReadableTable, ReadableTableMetadata, TableDefinition, WriteTransaction,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use parkhub_common::models::{Booking, ParkingLot, User, Vehicle};
+
+use super::{
+    BOOKINGS, Database, PARKING_LOTS, SEARCH_DOCS, SEARCH_INDEX, SETTINGS, USERS, VEHICLES,
+};
+
+/// Separates the token from the document in index keys; sorts before any
+/// token character, so an exact token's entries precede longer tokens.
+const KEY_SEPARATOR: char = '\x1f';
+
+/// Query terms shorter than this only match whole tokens, not prefixes.
+const MIN_PREFIX_LEN: usize = 3;
+
+/// Query words that never narrow a search.
+const STOP_WORDS: &[&str] = &[
+    "a", "an", "and", "at", "by", "for", "from", "in", "of", "on", "the", "to", "with",
+];
+
+/// Settings key recording the layout of the index, and its current value.
+/// An index of another layout is rebuilt at startup and on restore.
+const SETTING_SEARCH_INDEX_VERSION: &str = "search_index_version";
+const SEARCH_INDEX_VERSION: &str = "2";
+
+/// Suffix of the documents holding license plates.
+const PLATE_DOC_SUFFIX: &str = "/plate";
+
+/// Field weights: identifiers and names beat descriptive fields.
+const WEIGHT_PRIMARY: u32 = 3;
+const WEIGHT_SECONDARY: u32 = 2;
+const WEIGHT_CONTEXT: u32 = 1;
+
+/// What a search hit refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchKind {
+    User,
+    Booking,
+    Lot,
+}
+
+impl SearchKind {
+    const fn name(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Booking => "booking",
+            Self::Lot => "lot",
+        }
+    }
+
+    /// Parse `user`, `booking` or `lot`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "user" => Some(Self::User),
+            "booking" => Some(Self::Booking),
+            "lot" => Some(Self::Lot),
+            _ => None,
+        }
+    }
+}
+
+/// A ranked search result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    pub kind: SearchKind,
+    pub id: String,
+    /// Number of query terms the record matched
+    pub matched_terms: usize,
+    /// Sum of the best match weight per term (whole-token matches count double)
+    pub score: u32,
+}
+
+/// Split `text` into lowercase search tokens.
+///
+/// Alphanumeric runs of two or more characters are tokens. A
+/// whitespace-separated chunk with inner punctuation also yields its
+/// compacted form, so `M-AB` matches `mab` and an email address matches as a
+/// whole.
+pub(crate) fn tokenize(text: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    for chunk in text.split_whitespace() {
+        let words: Vec<String> = chunk
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+        if words.len() > 1 {
+            tokens.push(words.concat());
+        }
+        tokens.extend(words.into_iter().filter(|w| w.chars().count() >= 2));
+    }
+    tokens
+}
+
+/// A license plate with its spacing removed, so `MAB1234` finds `M-AB 1234`.
+fn compact_plate(plate: &str) -> String {
+    plate.split_whitespace().collect()
+}
+
+fn user_fields(user: &User) -> Vec<(String, u32)> {
+    let mut fields = vec![
+        (user.name.clone(), WEIGHT_PRIMARY),
+        (user.username.clone(), WEIGHT_PRIMARY),
+        (user.email.clone(), WEIGHT_PRIMARY),
+    ];
+    if let Some(department) = &user.department {
+        fields.push((department.clone(), WEIGHT_CONTEXT));
+    }
+    fields
+}
+
+/// Make, model and colour of `vehicle`; its plate is in [`plate_fields`].
+fn vehicle_fields(vehicle: &Vehicle) -> Vec<(String, u32)> {
+    [&vehicle.make, &vehicle.model, &vehicle.color]
+        .into_iter()
+        .flatten()
+        .map(|detail| (detail.clone(), WEIGHT_SECONDARY))
+        .collect()
+}
+
+fn plate_fields(vehicle: &Vehicle) -> Vec<(String, u32)> {
+    vec![
+        (vehicle.license_plate.clone(), WEIGHT_PRIMARY),
+        (compact_plate(&vehicle.license_plate), WEIGHT_PRIMARY),
+    ]
+}
+
+fn booking_fields(booking: &Booking) -> Vec<(String, u32)> {
+    let mut fields = vec![(booking.id.to_string(), WEIGHT_PRIMARY)];
+    fields.extend(vehicle_fields(&booking.vehicle));
+    fields.push((booking.floor_name.clone(), WEIGHT_CONTEXT));
+    fields.push((day_label(booking.start_time), WEIGHT_CONTEXT));
+    fields
+}
+
+fn lot_fields(lot: &ParkingLot) -> Vec<(String, u32)> {
+    vec![
+        (lot.name.clone(), WEIGHT_PRIMARY),
+        (lot.address.clone(), WEIGHT_SECONDARY),
+    ]
+}
+
+/// Weekday, month and date of `at`, e.g. `Tuesday March 2026-03-03`.
+fn day_label(at: DateTime<Utc>) -> String {
+    at.format("%A %B %Y-%m-%d").to_string()
+}
+
+fn user_doc(user_id: &str) -> String {
+    format!("{}:{user_id}", SearchKind::User.name())
+}
+
+fn vehicle_doc(vehicle: &Vehicle) -> String {
+    format!(
+        "{}/vehicle:{}",
+        user_doc(&vehicle.user_id.to_string()),
+        vehicle.id
+    )
+}
+
+fn booking_doc(booking_id: &str) -> String {
+    format!("{}:{booking_id}", SearchKind::Booking.name())
+}
+
+fn lot_doc(lot_id: &str) -> String {
+    format!("{}:{lot_id}", SearchKind::Lot.name())
+}
+
+fn plate_doc(doc: &str) -> String {
+    format!("{doc}{PLATE_DOC_SUFFIX}")
+}
+
+fn index_key(token: &str, doc: &str) -> String {
+    format!("{token}{KEY_SEPARATOR}{doc}")
+}
+
+/// The record a document's hits belong to.
+fn hit_target(doc: &str) -> Option<(SearchKind, &str)> {
+    let record = doc.split('/').next()?;
+    let (prefix, id) = record.split_once(':')?;
+    Some((SearchKind::from_name(prefix)?, id))
+}
+
+impl Database {
+    /// Replace the indexed tokens of `doc` with those of `fields`.
+    fn index_document(
+        &self,
+        write_txn: &WriteTransaction,
+        doc: &str,
+        fields: &[(String, u32)],
+    ) -> Result<()> {
+        let mut weights: BTreeMap<String, u32> = BTreeMap::new();
+        for (text, weight) in fields {
+            for token in tokenize(text) {
+                let entry = weights.entry(token).or_insert(0);
+                *entry = (*entry).max(*weight);
+            }
+        }
+
+        self.unindex_document(write_txn, doc)?;
+        if weights.is_empty() {
+            return Ok(());
+        }
+        let mut index = write_txn.open_table(SEARCH_INDEX)?;
+        for (token, weight) in &weights {
+            index.insert(index_key(token, doc).as_str(), *weight)?;
+        }
+        let tokens: Vec<&String> = weights.keys().collect();
+        let data = self.serialize(&tokens)?;
+        write_txn
+            .open_table(SEARCH_DOCS)?
+            .insert(doc, data.as_slice())?;
+        Ok(())
+    }
+
+    /// Drop every indexed token of `doc`.
+    fn unindex_document(&self, write_txn: &WriteTransaction, doc: &str) -> Result<()> {
+        let mut docs = write_txn.open_table(SEARCH_DOCS)?;
+        let tokens: Vec<String> = docs
+            .remove(doc)?
+            .map(|value| self.deserialize(value.value()))
+            .transpose()?
+            .unwrap_or_default();
+        let mut index = write_txn.open_table(SEARCH_INDEX)?;
+        for token in &tokens {
+            index.remove(index_key(token, doc).as_str())?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn index_user(&self, write_txn: &WriteTransaction, user: &User) -> Result<()> {
+        self.index_document(
+            write_txn,
+            &user_doc(&user.id.to_string()),
+            &user_fields(user),
+        )
+    }
+
+    pub(super) fn unindex_user(&self, write_txn: &WriteTransaction, user_id: &str) -> Result<()> {
+        self.unindex_document(write_txn, &user_doc(user_id))
+    }
+
+    pub(super) fn index_vehicle(
+        &self,
+        write_txn: &WriteTransaction,
+        vehicle: &Vehicle,
+    ) -> Result<()> {
+        let doc = vehicle_doc(vehicle);
+        self.index_document(write_txn, &doc, &vehicle_fields(vehicle))?;
+        self.index_document(write_txn, &plate_doc(&doc), &plate_fields(vehicle))
+    }
+
+    pub(super) fn unindex_vehicle(
+        &self,
+        write_txn: &WriteTransaction,
+        vehicle: &Vehicle,
+    ) -> Result<()> {
+        let doc = vehicle_doc(vehicle);
+        self.unindex_document(write_txn, &doc)?;
+        self.unindex_document(write_txn, &plate_doc(&doc))
+    }
+
+    pub(super) fn index_booking(
+        &self,
+        write_txn: &WriteTransaction,
+        booking: &Booking,
+    ) -> Result<()> {
+        let doc = booking_doc(&booking.id.to_string());
+        self.index_document(write_txn, &doc, &booking_fields(booking))?;
+        self.index_document(write_txn, &plate_doc(&doc), &plate_fields(&booking.vehicle))
+    }
+
+    pub(super) fn unindex_booking(
+        &self,
+        write_txn: &WriteTransaction,
+        booking_id: &str,
+    ) -> Result<()> {
+        let doc = booking_doc(booking_id);
+        self.unindex_document(write_txn, &doc)?;
+        self.unindex_document(write_txn, &plate_doc(&doc))
+    }
+
+    pub(super) fn index_lot(&self, write_txn: &WriteTransaction, lot: &ParkingLot) -> Result<()> {
+        self.index_document(write_txn, &lot_doc(&lot.id.to_string()), &lot_fields(lot))
+    }
+
+    pub(super) fn unindex_lot(&self, write_txn: &WriteTransaction, lot_id: &str) -> Result<()> {
+        self.unindex_document(write_txn, &lot_doc(lot_id))
+    }
+
+    /// Search users, bookings and lots for `query`, best matches first.
+    ///
+    /// A record matches if any query term equals one of its tokens or, for
+    /// terms of [`MIN_PREFIX_LEN`] or more characters, starts one. Records
+    /// matching more terms rank first, then by score. License plates only
+    /// match if `plates` is set.
+    pub async fn search(
+        &self,
+        query: &str,
+        kinds: &[SearchKind],
+        limit: usize,
+        plates: bool,
+    ) -> Result<Vec<SearchHit>> {
+        let mut terms: Vec<String> = tokenize(query)
+            .into_iter()
+            .filter(|t| !STOP_WORDS.contains(&t.as_str()))
+            .collect();
+        terms.sort();
+        terms.dedup();
+        if terms.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let index = read_txn.open_table(SEARCH_INDEX)?;
+
+        // Best weight per (record, term)
+        let mut best: HashMap<(SearchKind, String), Vec<u32>> = HashMap::new();
+        for (i, term) in terms.iter().enumerate() {
+            let allow_prefix = term.chars().count() >= MIN_PREFIX_LEN;
+            for entry in index.range(term.as_str()..)? {
+                let (key, weight) = entry?;
+                let key = key.value();
+                if !key.starts_with(term.as_str()) {
+                    break;
+                }
+                let Some((token, doc)) = key.split_once(KEY_SEPARATOR) else {
+                    continue;
+                };
+                let exact = token == term;
+                if !exact && !allow_prefix {
+                    break;
+                }
+                if !plates && doc.ends_with(PLATE_DOC_SUFFIX) {
+                    continue;
+                }
+                let Some((kind, id)) = hit_target(doc) else {
+                    continue;
+                };
+                if !kinds.is_empty() && !kinds.contains(&kind) {
+                    continue;
+                }
+                let weight = if exact {
+                    weight.value() * 2
+                } else {
+                    weight.value()
+                };
+                let scores = best
+                    .entry((kind, id.to_string()))
+                    .or_insert_with(|| vec![0; terms.len()]);
+                scores[i] = scores[i].max(weight);
+            }
+        }
+
+        let mut hits: Vec<SearchHit> = best
+            .into_iter()
+            .map(|((kind, id), scores)| SearchHit {
+                kind,
+                id,
+                matched_terms: scores.iter().filter(|s| **s > 0).count(),
+                score: scores.iter().sum(),
+            })
+            .collect();
+        hits.sort_by(|a, b| {
+            b.matched_terms
+                .cmp(&a.matched_terms)
+                .then(b.score.cmp(&a.score))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        hits.truncate(limit);
+        Ok(hits)
+    }
+
+    /// Index every user, vehicle, booking and lot if the index is empty or
+    /// has an older layout. Returns how many records were indexed.
+    pub(super) fn build_search_index(&self, write_txn: &WriteTransaction) -> Result<usize> {
+        let current = write_txn
+            .open_table(SETTINGS)?
+            .get(SETTING_SEARCH_INDEX_VERSION)?
+            .is_some_and(|v| v.value() == SEARCH_INDEX_VERSION);
+        if current && !write_txn.open_table(SEARCH_DOCS)?.is_empty()? {
+            return Ok(0);
+        }
+        write_txn.delete_table(SEARCH_INDEX)?;
+        write_txn.open_table(SEARCH_INDEX)?;
+        write_txn.delete_table(SEARCH_DOCS)?;
+        write_txn.open_table(SEARCH_DOCS)?;
+
+        let users: Vec<User> = self.decode_all(write_txn, USERS)?;
+        let vehicles: Vec<Vehicle> = self.decode_all(write_txn, VEHICLES)?;
+        let bookings: Vec<Booking> = self.decode_all(write_txn, BOOKINGS)?;
+        let lots: Vec<ParkingLot> = self.decode_all(write_txn, PARKING_LOTS)?;
+        for user in &users {
+            self.index_user(write_txn, user)?;
+        }
+        for vehicle in &vehicles {
+            self.index_vehicle(write_txn, vehicle)?;
+        }
+        for booking in &bookings {
+            self.index_booking(write_txn, booking)?;
+        }
+        for lot in &lots {
+            self.index_lot(write_txn, lot)?;
+        }
+        write_txn
+            .open_table(SETTINGS)?
+            .insert(SETTING_SEARCH_INDEX_VERSION, SEARCH_INDEX_VERSION)?;
+        Ok(users.len() + vehicles.len() + bookings.len() + lots.len())
+    }
+
+    /// Index users, vehicles, bookings and lots stored before the search
+    /// index existed, or before its current layout.
+    ///
+    /// Runs once at startup; a no-op once the index is current.
+    /// Undecodable records are skipped rather than failing the open.
+    pub(super) fn index_existing_documents(&self) -> Result<()> {
+        let db = self
+            .inner
+            .try_read()
+            .map_err(|_| anyhow::anyhow!("Database is locked"))?;
+        let write_txn = db.begin_write()?;
+        let total = self.build_search_index(&write_txn)?;
+        write_txn.commit()?;
+
+        if total > 0 {
+            info!("Built search index for {} existing record(s)", total);
+        }
+        Ok(())
+    }
+
+    pub(super) fn decode_all<T: serde::de::DeserializeOwned>(
+        &self,
+        write_txn: &WriteTransaction,
+        table: TableDefinition<'static, &'static str, &'static [u8]>,
+    ) -> Result<Vec<T>> {
+        let table = write_txn.open_table(table)?;
+        let mut records = Vec::new();
+        for entry in table.iter()? {
+            let (key, value) = entry?;
+            match self.deserialize(value.value()) {
+                Ok(record) => records.push(record),
+                Err(e) => warn!(
+                    "Skipping undecodable record {} while indexing: {}",
+                    key.value(),
+                    e
+                ),
+            }
+        }
+        Ok(records)
+    }
+}
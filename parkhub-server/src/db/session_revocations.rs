@@ -0,0 +1,68 @@
+//! Per-user session revocation cut-offs.
+//!
+//! Ending all of a user's sessions records the time it happened in
+//! `SESSION_REVOCATIONS` (user ID → Unix seconds). Signed access tokens the
+//! user was issued before then are rejected even when their session's token
+//! family is not, or no longer, on the revocation list — e.g. after a restart
+//! emptied the in-memory list. The table is mirrored in memory on open, so
+//! checking a token costs no database read.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable};
+use uuid::Uuid;
+
+use super::{Database, SESSION_REVOCATIONS};
+
+/// Read the stored cut-offs, skipping keys that are not user IDs.
+pub(super) fn load_session_revocations(db: &redb::Database) -> Result<HashMap<Uuid, i64>> {
+    let read_txn = db.begin_read()?;
+    read_session_revocations(&read_txn.open_table(SESSION_REVOCATIONS)?)
+}
+
+/// The cut-offs in `table`, as [`load_session_revocations`] reads them.
+pub(super) fn read_session_revocations(
+    table: &impl ReadableTable<&'static str, i64>,
+) -> Result<HashMap<Uuid, i64>> {
+    let mut revocations = HashMap::new();
+    for entry in table.iter()? {
+        let (user_id, revoked_at) = entry?;
+        if let Ok(user_id) = Uuid::parse_str(user_id.value()) {
+            revocations.insert(user_id, revoked_at.value());
+        }
+    }
+    Ok(revocations)
+}
+
+impl Database {
+    /// Revoke every session `user_id` was issued before `at`.
+    pub async fn revoke_sessions_before(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<()> {
+        let revoked_at = at.timestamp();
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(SESSION_REVOCATIONS)?;
+            table.insert(user_id.to_string().as_str(), revoked_at)?;
+        }
+        write_txn.commit()?;
+
+        self.session_revocations
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(user_id, revoked_at);
+        Ok(())
+    }
+
+    /// Unix time before which `user_id`'s sessions are revoked, if they
+    /// ever were. Answered from memory.
+    pub fn sessions_revoked_before(&self, user_id: Uuid) -> Option<i64> {
+        self.session_revocations
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .get(&user_id)
+            .copied()
+    }
+}
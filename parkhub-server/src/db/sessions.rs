@@ -1,14 +1,22 @@
 //! Session storage: access-token keyed session records with refresh-token
 //! lookup, per-user deletion, and expiry semantics.
+//!
+//! `SESSIONS_BY_USER` indexes sessions by owner (`"{user_id}:{session_id}"`),
+//! so listing or ending one user's sessions does not scan every session.
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use redb::{ReadableDatabase, ReadableTable};
+use redb::{ReadableDatabase, ReadableTable, ReadableTableMetadata};
 use serde::{Deserialize, Serialize};
-use tracing::debug;
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use super::{Database, SESSIONS};
+use super::{Database, SESSIONS, SESSIONS_BY_USER};
+
+/// Key range covering one user's index entries (`;` sorts right after `:`).
+fn user_range(user_id: Uuid) -> (String, String) {
+    (format!("{user_id}:"), format!("{user_id};"))
+}
 
 /// User session for authentication
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +27,21 @@ pub struct Session {
     pub refresh_token: String,
     pub created_at: DateTime<Utc>,
     pub expires_at: DateTime<Utc>,
+    /// Last authenticated request (throttled; `None` until first use)
+    #[serde(default)]
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Client IP the session was last seen from
+    #[serde(default)]
+    pub ip_address: Option<String>,
+    #[serde(default)]
+    pub user_agent: Option<String>,
+    /// Admin acting as `user_id`, for impersonation sessions
+    #[serde(default)]
+    pub impersonator_id: Option<Uuid>,
+    /// Refresh-token family: shared by every session one sign-in rotates
+    /// through, so a replayed refresh token can be traced to its sign-in
+    #[serde(default = "Uuid::new_v4")]
+    pub family_id: Uuid,
 }
 
 impl Session {
@@ -39,13 +62,52 @@ impl Session {
             refresh_token,
             created_at: now,
             expires_at: now + chrono::Duration::hours(duration_hours),
+            last_used_at: None,
+            ip_address: None,
+            user_agent: None,
+            impersonator_id: None,
+            family_id: Uuid::new_v4(),
         }
     }
 
+    /// Continue the refresh-token family of the session this one replaces.
+    #[must_use]
+    pub const fn in_family(mut self, family_id: Uuid) -> Self {
+        self.family_id = family_id;
+        self
+    }
+
+    /// Record the client that opened the session.
+    #[must_use]
+    pub fn with_client(mut self, ip_address: Option<String>, user_agent: Option<String>) -> Self {
+        self.ip_address = ip_address;
+        self.user_agent = user_agent;
+        self
+    }
+
+    /// Mark the session as opened by `admin_id` to act as its user, ending
+    /// it after `lifetime` at the latest.
+    #[must_use]
+    pub fn impersonated_by(mut self, admin_id: Uuid, lifetime: chrono::Duration) -> Self {
+        self.impersonator_id = Some(admin_id);
+        self.expires_at = self.expires_at.min(self.created_at + lifetime);
+        self
+    }
+
     /// Check if the session has expired
     pub fn is_expired(&self) -> bool {
         self.expires_at < Utc::now()
     }
+
+    /// When the session was last used, or created if it never was.
+    pub fn last_activity(&self) -> DateTime<Utc> {
+        self.last_used_at.unwrap_or(self.created_at)
+    }
+
+    /// Check if the session has gone unused for longer than `idle_timeout`
+    pub fn is_idle(&self, idle_timeout: chrono::Duration) -> bool {
+        self.last_activity() + idle_timeout < Utc::now()
+    }
 }
 
 impl Database {
@@ -59,6 +121,10 @@ impl Database {
         {
             let mut table = write_txn.open_table(SESSIONS)?;
             table.insert(token, data.as_slice())?;
+
+            let mut idx = write_txn.open_table(SESSIONS_BY_USER)?;
+            let idx_key = format!("{}:{token}", session.user_id);
+            idx.insert(idx_key.as_str(), token)?;
         }
         write_txn.commit()?;
         debug!("Saved session for user: {}", session.username);
@@ -112,60 +178,118 @@ impl Database {
         Ok(None)
     }
 
+    /// Record that a session was just used, optionally from a new IP.
+    ///
+    /// Returns `false` if the session no longer exists.
+    pub async fn touch_session(&self, token: &str, ip_address: Option<&str>) -> Result<bool> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let touched = {
+            let mut table = write_txn.open_table(SESSIONS)?;
+            let existing = table
+                .get(token)?
+                .map(|value| self.deserialize::<Session>(value.value()))
+                .transpose()?;
+            match existing {
+                Some(mut session) => {
+                    session.last_used_at = Some(Utc::now());
+                    if let Some(ip) = ip_address {
+                        session.ip_address = Some(ip.to_string());
+                    }
+                    let data = self.serialize(&session)?;
+                    table.insert(token, data.as_slice())?;
+                    true
+                }
+                None => false,
+            }
+        };
+        write_txn.commit()?;
+        Ok(touched)
+    }
+
     /// Delete all sessions belonging to a specific user.
     ///
-    /// Scans every session, deserializes it, and removes entries whose
-    /// `user_id` matches the given ID. Returns the number of deleted sessions.
+    /// Uses the `SESSIONS_BY_USER` index. Returns the number of deleted
+    /// sessions.
     pub async fn delete_sessions_by_user(&self, user_id: Uuid) -> Result<u64> {
         let db = self.inner.write().await;
-        let read_txn = db.begin_read()?;
-        let table = read_txn.open_table(SESSIONS)?;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let count = {
+            let mut idx = write_txn.open_table(SESSIONS_BY_USER)?;
+            let (start, end) = user_range(user_id);
+            let entries: Vec<(String, String)> = idx
+                .range(start.as_str()..end.as_str())?
+                .map(|entry| entry.map(|(k, v)| (k.value().to_string(), v.value().to_string())))
+                .collect::<Result<_, _>>()?;
 
-        // Collect tokens to delete (cannot mutate while iterating)
-        let mut tokens_to_delete = Vec::new();
-        for entry in table.iter()? {
-            let (key, value) = entry?;
-            let session: Session = self.deserialize(value.value())?;
-            if session.user_id == user_id {
-                tokens_to_delete.push(key.value().to_string());
+            let mut table = write_txn.open_table(SESSIONS)?;
+            let mut count = 0u64;
+            for (idx_key, token) in &entries {
+                idx.remove(idx_key.as_str())?;
+                if table.remove(token.as_str())?.is_some() {
+                    count += 1;
+                }
             }
+            count
+        };
+        write_txn.commit()?;
+        if count > 0 {
+            debug!("Deleted {} session(s) for user {}", count, user_id);
         }
-        drop(table);
-        drop(read_txn);
+        Ok(count)
+    }
 
-        let count = tokens_to_delete.len() as u64;
-        if count > 0 {
-            let write_txn = db.begin_write()?;
-            drop(db);
-            {
-                let mut table = write_txn.open_table(SESSIONS)?;
-                for token in &tokens_to_delete {
-                    table.remove(token.as_str())?;
+    /// Number of active (non-expired) sessions of a user, via the
+    /// `SESSIONS_BY_USER` index.
+    pub async fn count_sessions_by_user(&self, user_id: Uuid) -> Result<usize> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let idx = read_txn.open_table(SESSIONS_BY_USER)?;
+        let table = read_txn.open_table(SESSIONS)?;
+        let now = Utc::now();
+
+        let (start, end) = user_range(user_id);
+        let mut count = 0;
+        for entry in idx.range(start.as_str()..end.as_str())? {
+            let (_, token) = entry?;
+            if let Some(value) = table.get(token.value())? {
+                let session: Session = self.deserialize(value.value())?;
+                if session.expires_at > now {
+                    count += 1;
                 }
             }
-            write_txn.commit()?;
-            debug!("Deleted {} session(s) for user {}", count, user_id);
         }
         Ok(count)
     }
 
-    /// List all active (non-expired) sessions for a user.
-    /// Returns `(access_token, Session)` pairs.
+    /// List all active (non-expired) sessions for a user via the
+    /// `SESSIONS_BY_USER` index. Returns `(access_token, Session)` pairs,
+    /// oldest first.
     pub async fn list_sessions_by_user(&self, user_id: Uuid) -> Result<Vec<(String, Session)>> {
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
+        let idx = read_txn.open_table(SESSIONS_BY_USER)?;
         let table = read_txn.open_table(SESSIONS)?;
         let now = Utc::now();
 
+        let (start, end) = user_range(user_id);
         let mut sessions = Vec::new();
-        for entry in table.iter()? {
-            let (key, value) = entry?;
+        for entry in idx.range(start.as_str()..end.as_str())? {
+            let (_, token) = entry?;
+            let token = token.value();
+            let Some(value) = table.get(token)? else {
+                continue;
+            };
             let session: Session = self.deserialize(value.value())?;
-            if session.user_id == user_id && session.expires_at > now {
-                sessions.push((key.value().to_string(), session));
+            if session.expires_at > now {
+                sessions.push((token.to_string(), session));
             }
         }
+        sessions.sort_by_key(|(_, s)| s.created_at);
         Ok(sessions)
     }
 
@@ -176,10 +300,60 @@ impl Database {
         drop(db);
         let existed = {
             let mut table = write_txn.open_table(SESSIONS)?;
-            let result = table.remove(token)?;
-            result.is_some()
+            let removed = table
+                .remove(token)?
+                .map(|value| self.deserialize::<Session>(value.value()));
+            match removed {
+                Some(session) => {
+                    if let Ok(session) = session {
+                        let mut idx = write_txn.open_table(SESSIONS_BY_USER)?;
+                        let idx_key = format!("{}:{token}", session.user_id);
+                        idx.remove(idx_key.as_str())?;
+                    }
+                    true
+                }
+                None => false,
+            }
         };
         write_txn.commit()?;
         Ok(existed)
     }
+
+    /// Index sessions saved before `SESSIONS_BY_USER` existed.
+    ///
+    /// Runs once at startup; a no-op when the index is already populated.
+    /// Undecodable sessions are skipped rather than failing the open.
+    pub(super) fn index_legacy_sessions(&self) -> Result<()> {
+        let db = self
+            .inner
+            .try_read()
+            .map_err(|_| anyhow::anyhow!("Database is locked"))?;
+        let write_txn = db.begin_write()?;
+        let indexed = {
+            let table = write_txn.open_table(SESSIONS)?;
+            let mut idx = write_txn.open_table(SESSIONS_BY_USER)?;
+            if !idx.is_empty()? {
+                return Ok(());
+            }
+            let mut indexed = 0usize;
+            for entry in table.iter()? {
+                let (key, value) = entry?;
+                let token = key.value();
+                match self.deserialize::<Session>(value.value()) {
+                    Ok(session) => {
+                        let idx_key = format!("{}:{token}", session.user_id);
+                        idx.insert(idx_key.as_str(), token)?;
+                        indexed += 1;
+                    }
+                    Err(e) => warn!("Skipping undecodable session while indexing: {}", e),
+                }
+            }
+            indexed
+        };
+        write_txn.commit()?;
+        if indexed > 0 {
+            info!("Indexed {} existing session(s) by user", indexed);
+        }
+        Ok(())
+    }
 }
@@ -5,9 +5,15 @@ use redb::ReadableDatabase;
 
 use super::{Database, SETTINGS};
 
+/// Settings key for a lot's booking rules.
+pub fn lot_rules_key(lot_id: &str) -> String {
+    format!("lot_booking_rules:{lot_id}")
+}
+
 impl Database {
     /// Get a setting value
     pub async fn get_setting(&self, key: &str) -> Result<Option<String>> {
+        let _timer = Self::time_read("settings", Some(key));
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
@@ -18,6 +24,7 @@ impl Database {
 
     /// Set a setting value
     pub async fn set_setting(&self, key: &str, value: &str) -> Result<()> {
+        let _timer = Self::time_write("settings", Some(key));
         let db = self.inner.write().await;
         let write_txn = db.begin_write()?;
         drop(db);
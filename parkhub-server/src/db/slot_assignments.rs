@@ -0,0 +1,333 @@
+//! Slot assignment for late-binding lots.
+//!
+//! Bookings in a late-binding lot are made for a slot type. They are stored
+//! with a nil `slot_id` plus a `PENDING_SLOT_ASSIGNMENTS` record (keyed by
+//! booking ID) until the concrete slot is picked shortly before the start.
+//! Like [`Database::claim_slot`], the capacity check at booking time and the
+//! assignment itself each run inside one redb write transaction, so
+//! concurrent bookings cannot oversell a slot type or share a slot. Both
+//! honour the lot's buffer between consecutive bookings of a slot.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use redb::{ReadableDatabase, ReadableTable, WriteTransaction};
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use uuid::Uuid;
+
+use parkhub_common::TimeRange;
+use parkhub_common::models::{Booking, BookingStatus, ParkingSlot, SlotType};
+
+use super::booking_times::{index_booking_time, lot_buffer};
+use super::list_order::index_booking_order;
+use super::{BOOKINGS, BOOKINGS_BY_USER, Database, PENDING_SLOT_ASSIGNMENTS};
+
+/// A late-binding booking still waiting for its slot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingAssignment {
+    pub booking_id: Uuid,
+    pub lot_id: Uuid,
+    pub slot_type: SlotType,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Outcome of [`Database::assign_pending_slot`].
+#[derive(Debug)]
+pub enum SlotAssignment {
+    /// The booking now holds a slot
+    Assigned(Box<Booking>),
+    /// Every candidate slot is taken for the booking's time range; the
+    /// booking stays pending
+    NoSlotFree,
+    /// Nothing to assign: the booking is gone, no longer live or already
+    /// holds a slot. Any stale pending record was removed.
+    NotPending,
+}
+
+/// Whether a booking in `status` occupies (or will occupy) its slot.
+pub(super) const fn is_live(status: &BookingStatus) -> bool {
+    matches!(
+        status,
+        BookingStatus::Pending | BookingStatus::Confirmed | BookingStatus::Active
+    )
+}
+
+/// The time `range` keeps its slot from other bookings: the booking itself
+/// plus the turnover `buffer` after it. Two bookings can follow each other
+/// on one slot exactly when these do not overlap.
+fn with_turnover(range: &TimeRange, buffer: Duration) -> TimeRange {
+    TimeRange {
+        start: range.start,
+        end: range.end + buffer,
+    }
+}
+
+/// Highest number of `ranges` in effect at the same instant within `window`.
+fn peak_overlap(window: &TimeRange, ranges: &[TimeRange]) -> usize {
+    // Ends sort before starts at the same instant: back-to-back bookings
+    // do not overlap.
+    let mut events: Vec<(DateTime<Utc>, i32)> = ranges
+        .iter()
+        .filter(|r| r.overlaps(window))
+        .flat_map(|r| [(r.start.max(window.start), 1), (r.end.min(window.end), -1)])
+        .collect();
+    events.sort_unstable();
+    let mut current = 0usize;
+    let mut peak = 0usize;
+    for (_, delta) in events {
+        if delta > 0 {
+            current += 1;
+            peak = peak.max(current);
+        } else {
+            current = current.saturating_sub(1);
+        }
+    }
+    peak
+}
+
+/// Pick the slot for `range` among `candidates` given the live bookings in
+/// `taken`. Only slots free for the whole range qualify; among those the
+/// slot whose previous booking ends closest before the start wins, so gaps
+/// stay short and long free stretches remain for later bookings. Slots
+/// without an earlier booking come last; ties go to the lowest slot number.
+fn best_fit(
+    range: &TimeRange,
+    candidates: &[(ParkingSlot, String)],
+    taken: &[(Uuid, TimeRange)],
+) -> Option<usize> {
+    candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, (slot, _))| {
+            !taken
+                .iter()
+                .any(|(slot_id, other)| *slot_id == slot.id && other.overlaps(range))
+        })
+        .min_by_key(|(_, (slot, _))| {
+            let gap = taken
+                .iter()
+                .filter(|(slot_id, other)| *slot_id == slot.id && other.end <= range.start)
+                .map(|(_, other)| range.start - other.end)
+                .min();
+            (gap.is_none(), gap, slot.slot_number)
+        })
+        .map(|(index, _)| index)
+}
+
+impl Database {
+    /// Atomically store late-binding `booking` if a slot of `slot_type` is
+    /// left for its whole time range.
+    ///
+    /// `slot_ids` are the lot's bookable slots of that type. The booking fits
+    /// if, at every instant of its range and the turnover after it, the live
+    /// bookings on those slots plus the live bookings still pending for the
+    /// same type (each with its turnover) leave one of them free. Returns
+    /// `false` without writing anything otherwise.
+    ///
+    /// Booking creation claims through a [`super::WriteBatch`] instead, so the
+    /// booking commits together with the credit deduction.
+    #[cfg(test)]
+    pub async fn claim_slot_type(
+        &self,
+        booking: &Booking,
+        slot_type: &SlotType,
+        slot_ids: &[Uuid],
+    ) -> Result<bool> {
+        let _timer = Self::time_write("bookings", Some(&booking.id.to_string()));
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        if !self.try_claim_slot_type(&write_txn, booking, slot_type, slot_ids)? {
+            // Dropping the transaction aborts it.
+            return Ok(false);
+        }
+        write_txn.commit()?;
+        debug!(
+            "Claimed a {:?} slot in lot {} for booking {}",
+            slot_type, booking.lot_id, booking.id
+        );
+        Ok(true)
+    }
+
+    /// Write late-binding `booking` within `write_txn` if a slot of
+    /// `slot_type` is left; see `claim_slot_type`.
+    pub(super) fn try_claim_slot_type(
+        &self,
+        write_txn: &WriteTransaction,
+        booking: &Booking,
+        slot_type: &SlotType,
+        slot_ids: &[Uuid],
+    ) -> Result<bool> {
+        let id = booking.id.to_string();
+        let user_id = booking.user_id.to_string();
+        let data = self.serialize(booking)?;
+        let buffer = lot_buffer(write_txn, booking.lot_id)?;
+        let range = TimeRange::new(booking.start_time, booking.end_time)
+            .ok_or_else(|| anyhow::anyhow!("Booking {id} has an empty time range"))?;
+        let pending = PendingAssignment {
+            booking_id: booking.id,
+            lot_id: booking.lot_id,
+            slot_type: slot_type.clone(),
+            created_at: Utc::now(),
+        };
+        let pending_data = self.serialize(&pending)?;
+        {
+            let mut pending_table = write_txn.open_table(PENDING_SLOT_ASSIGNMENTS)?;
+            let mut same_type = HashSet::new();
+            for entry in pending_table.iter()? {
+                let (key, value) = entry?;
+                let other: PendingAssignment = self.deserialize(value.value())?;
+                if other.lot_id == booking.lot_id && other.slot_type == *slot_type {
+                    same_type.insert(key.value().to_string());
+                }
+            }
+
+            let mut table = write_txn.open_table(BOOKINGS)?;
+            let mut ranges = Vec::new();
+            for entry in table.iter()? {
+                let (key, value) = entry?;
+                if key.value() == id {
+                    continue;
+                }
+                let existing: Booking = self.deserialize(value.value())?;
+                let competes = slot_ids.contains(&existing.slot_id)
+                    || (existing.slot_id.is_nil() && same_type.contains(key.value()));
+                if competes
+                    && is_live(&existing.status)
+                    && let Some(other) = TimeRange::new(existing.start_time, existing.end_time)
+                {
+                    ranges.push(with_turnover(&other, buffer));
+                }
+            }
+            if peak_overlap(&with_turnover(&range, buffer), &ranges) >= slot_ids.len() {
+                debug!(
+                    "No {:?} slot left in lot {} for booking {}",
+                    slot_type, booking.lot_id, booking.id
+                );
+                return Ok(false);
+            }
+            table.insert(id.as_str(), data.as_slice())?;
+
+            let mut idx = write_txn.open_table(BOOKINGS_BY_USER)?;
+            let idx_key = format!("{user_id}:{id}");
+            idx.insert(idx_key.as_str(), id.as_str())?;
+
+            pending_table.insert(id.as_str(), pending_data.as_slice())?;
+        }
+        self.index_booking(write_txn, booking)?;
+        index_booking_order(write_txn, booking)?;
+        index_booking_time(write_txn, booking)?;
+        Ok(true)
+    }
+
+    /// Assign a slot to the pending late-binding booking `booking_id`.
+    ///
+    /// `candidates` are the slots the booking may take, each with its floor
+    /// name. The choice and the write run in one transaction, so the chosen
+    /// slot is guaranteed free for the booking's whole range and the lot's
+    /// buffer around it.
+    pub async fn assign_pending_slot(
+        &self,
+        booking_id: Uuid,
+        candidates: &[(ParkingSlot, String)],
+    ) -> Result<SlotAssignment> {
+        let id = booking_id.to_string();
+        let _timer = Self::time_write("bookings", Some(&id));
+
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let outcome = {
+            let mut pending_table = write_txn.open_table(PENDING_SLOT_ASSIGNMENTS)?;
+            if pending_table.get(id.as_str())?.is_none() {
+                return Ok(SlotAssignment::NotPending);
+            }
+            let mut table = write_txn.open_table(BOOKINGS)?;
+            let booking = table
+                .get(id.as_str())?
+                .map(|value| self.deserialize::<Booking>(value.value()))
+                .transpose()?
+                .filter(|b| is_live(&b.status) && b.slot_id.is_nil());
+            let range = booking
+                .as_ref()
+                .and_then(|b| TimeRange::new(b.start_time, b.end_time));
+            match (booking, range) {
+                (Some(mut booking), Some(range)) => {
+                    let range = range.padded(lot_buffer(&write_txn, booking.lot_id)?);
+                    let mut taken = Vec::new();
+                    for entry in table.iter()? {
+                        let (_, value) = entry?;
+                        let existing: Booking = self.deserialize(value.value())?;
+                        if is_live(&existing.status)
+                            && candidates.iter().any(|(s, _)| s.id == existing.slot_id)
+                            && let Some(other) =
+                                TimeRange::new(existing.start_time, existing.end_time)
+                        {
+                            taken.push((existing.slot_id, other));
+                        }
+                    }
+                    let Some(index) = best_fit(&range, candidates, &taken) else {
+                        return Ok(SlotAssignment::NoSlotFree);
+                    };
+                    let (slot, floor_name) = &candidates[index];
+                    booking.slot_id = slot.id;
+                    booking.slot_number = slot.slot_number;
+                    booking.floor_name.clone_from(floor_name);
+                    booking.updated_at = Utc::now();
+                    let data = self.serialize(&booking)?;
+                    table.insert(id.as_str(), data.as_slice())?;
+                    pending_table.remove(id.as_str())?;
+                    SlotAssignment::Assigned(Box::new(booking))
+                }
+                _ => {
+                    pending_table.remove(id.as_str())?;
+                    SlotAssignment::NotPending
+                }
+            }
+        };
+        if let SlotAssignment::Assigned(ref booking) = outcome {
+            self.index_booking(&write_txn, booking)?;
+            index_booking_order(&write_txn, booking)?;
+            index_booking_time(&write_txn, booking)?;
+        }
+        write_txn.commit()?;
+        if let SlotAssignment::Assigned(ref booking) = outcome {
+            debug!(
+                "Assigned slot {} to booking {}",
+                booking.slot_number, booking.id
+            );
+        }
+        Ok(outcome)
+    }
+
+    /// Get the pending assignment of a booking, if it still waits for a slot
+    pub async fn get_pending_assignment(
+        &self,
+        booking_id: Uuid,
+    ) -> Result<Option<PendingAssignment>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(PENDING_SLOT_ASSIGNMENTS)?;
+        match table.get(booking_id.to_string().as_str())? {
+            Some(value) => Ok(Some(self.deserialize(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List all bookings still waiting for a slot
+    pub async fn list_pending_assignments(&self) -> Result<Vec<PendingAssignment>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(PENDING_SLOT_ASSIGNMENTS)?;
+        let mut pending = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            pending.push(self.deserialize(value.value())?);
+        }
+        Ok(pending)
+    }
+}
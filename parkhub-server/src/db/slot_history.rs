@@ -0,0 +1,186 @@
+//! Append-only slot status history: who or what changed a slot's status,
+//! when, and why.
+//!
+//! Entries are written by [`Database::save_parking_slot_with`] in the same
+//! transaction as the slot itself whenever the stored status changes. Keys
+//! are `{slot_id}:{changed_at}:{id}` so one slot's history is a contiguous,
+//! chronologically ordered range. Each slot keeps at most
+//! [`MAX_HISTORY_PER_SLOT`] entries; the retention engine additionally
+//! purges entries past the `operational_presence` TTL.
+
+use anyhow::Result;
+use chrono::{DateTime, SecondsFormat, Utc};
+use redb::{ReadableDatabase, ReadableTable, WriteTransaction};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use parkhub_common::models::SlotStatus;
+
+use super::{Database, SLOT_STATUS_HISTORY};
+
+/// Oldest entries beyond this many per slot are dropped on write.
+pub const MAX_HISTORY_PER_SLOT: usize = 500;
+
+/// Who or what is saving a slot; attributed to any resulting status change.
+#[derive(Debug, Clone)]
+pub struct SlotChangeContext {
+    pub user_id: Option<Uuid>,
+    pub source: &'static str,
+    pub reason: Option<String>,
+}
+
+impl SlotChangeContext {
+    /// A change made by the server itself (background job, import, ...).
+    #[must_use]
+    pub const fn system(source: &'static str) -> Self {
+        Self {
+            user_id: None,
+            source,
+            reason: None,
+        }
+    }
+
+    /// A change made on behalf of a user.
+    #[must_use]
+    pub const fn user(user_id: Uuid, source: &'static str) -> Self {
+        Self {
+            user_id: Some(user_id),
+            source,
+            reason: None,
+        }
+    }
+
+    /// Attach a free-text reason.
+    #[must_use]
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+/// One recorded slot status change.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SlotStatusChange {
+    pub id: Uuid,
+    pub slot_id: Uuid,
+    pub lot_id: Uuid,
+    #[schema(value_type = String)]
+    pub from_status: SlotStatus,
+    #[schema(value_type = String)]
+    pub to_status: SlotStatus,
+    pub changed_at: DateTime<Utc>,
+    /// `None` for changes made by the server itself
+    pub changed_by: Option<Uuid>,
+    /// What triggered the change (`booking`, `cancellation`, `admin`, `no_show`, ...)
+    pub source: String,
+    pub reason: Option<String>,
+}
+
+fn history_key(change: &SlotStatusChange) -> String {
+    format!(
+        "{}:{}:{}",
+        change.slot_id,
+        change
+            .changed_at
+            .to_rfc3339_opts(SecondsFormat::Nanos, true),
+        change.id
+    )
+}
+
+/// Key range covering one slot's history (`;` sorts right after `:`).
+fn slot_range(slot_id: &str) -> (String, String) {
+    (format!("{slot_id}:"), format!("{slot_id};"))
+}
+
+impl Database {
+    /// Append a history entry inside an open write transaction, trimming the
+    /// slot's oldest entries beyond [`MAX_HISTORY_PER_SLOT`].
+    pub(super) fn append_slot_history(
+        &self,
+        write_txn: &WriteTransaction,
+        change: &SlotStatusChange,
+    ) -> Result<()> {
+        let data = self.serialize(change)?;
+        let mut table = write_txn.open_table(SLOT_STATUS_HISTORY)?;
+        table.insert(history_key(change).as_str(), data.as_slice())?;
+
+        let (start, end) = slot_range(&change.slot_id.to_string());
+        let keys: Vec<String> = table
+            .range(start.as_str()..end.as_str())?
+            .map(|entry| entry.map(|(k, _)| k.value().to_string()))
+            .collect::<Result<_, _>>()?;
+        if keys.len() > MAX_HISTORY_PER_SLOT {
+            for key in &keys[..keys.len() - MAX_HISTORY_PER_SLOT] {
+                table.remove(key.as_str())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Status history of one slot, newest first.
+    pub async fn list_slot_history(
+        &self,
+        slot_id: &str,
+        limit: usize,
+    ) -> Result<Vec<SlotStatusChange>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(SLOT_STATUS_HISTORY)?;
+
+        let (start, end) = slot_range(slot_id);
+        let mut entries = Vec::new();
+        for entry in table.range(start.as_str()..end.as_str())?.rev() {
+            let (_, value) = entry?;
+            entries.push(self.deserialize(value.value())?);
+            if entries.len() >= limit {
+                break;
+            }
+        }
+        Ok(entries)
+    }
+
+    /// All history entries recorded before `cutoff` (for retention).
+    pub async fn list_slot_history_before(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> Result<Vec<SlotStatusChange>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(SLOT_STATUS_HISTORY)?;
+
+        let mut entries = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            let change: SlotStatusChange = self.deserialize(value.value())?;
+            if change.changed_at < cutoff {
+                entries.push(change);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Delete the given history entries. Returns how many were removed.
+    pub async fn delete_slot_history(&self, changes: &[SlotStatusChange]) -> Result<u64> {
+        if changes.is_empty() {
+            return Ok(0);
+        }
+
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+
+        let mut deleted = 0u64;
+        {
+            let mut table = write_txn.open_table(SLOT_STATUS_HISTORY)?;
+            for change in changes {
+                if table.remove(history_key(change).as_str())?.is_some() {
+                    deleted += 1;
+                }
+            }
+        }
+        write_txn.commit()?;
+        Ok(deleted)
+    }
+}
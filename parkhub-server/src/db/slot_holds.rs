@@ -0,0 +1,214 @@
+//! Short soft locks on slots while a user fills out the booking form.
+//!
+//! `SLOT_HOLDS` keeps at most one hold per slot, keyed by slot ID. A user
+//! holds at most one slot at a time: placing a hold drops the user's other
+//! holds in the same transaction. Expired holds stop counting straight
+//! away and are removed by the `ExpireSlotHolds` background job.
+//!
+//! `SLOT_HOLDS_BY_LOT` indexes holds by lot (`"{lot_id}:{slot_id}"`), so
+//! checking a booking against the holds on its lot does not read every hold.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use redb::{ReadableDatabase, ReadableTable, ReadableTableMetadata, Table, WriteTransaction};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use super::{Database, SLOT_HOLDS, SLOT_HOLDS_BY_LOT};
+
+/// Key range covering one lot's index entries (`;` sorts right after `:`).
+fn lot_range(lot_id: Uuid) -> (String, String) {
+    (format!("{lot_id}:"), format!("{lot_id};"))
+}
+
+fn lot_key(hold: &SlotHold) -> String {
+    format!("{}:{}", hold.lot_id, hold.slot_id)
+}
+
+/// Remove `hold` and its index entry.
+fn remove_hold(
+    table: &mut Table<&str, &[u8]>,
+    idx: &mut Table<&str, &str>,
+    hold: &SlotHold,
+) -> Result<()> {
+    table.remove(hold.slot_id.to_string().as_str())?;
+    idx.remove(lot_key(hold).as_str())?;
+    Ok(())
+}
+
+/// A slot kept aside for one user until `expires_at`.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SlotHold {
+    pub slot_id: Uuid,
+    pub lot_id: Uuid,
+    /// User the slot is held for
+    pub user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl SlotHold {
+    /// Whether the hold still keeps other users off the slot at `now`.
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        now < self.expires_at
+    }
+}
+
+impl Database {
+    /// Index the holds stored before the lot index existed, within
+    /// `write_txn`. A no-op once any hold is indexed; returns how many holds
+    /// were indexed.
+    pub(super) fn index_slot_holds(&self, write_txn: &WriteTransaction) -> Result<usize> {
+        if !write_txn.open_table(SLOT_HOLDS_BY_LOT)?.is_empty()? {
+            return Ok(0);
+        }
+        let holds: Vec<SlotHold> = self.decode_all(write_txn, SLOT_HOLDS)?;
+        let mut idx = write_txn.open_table(SLOT_HOLDS_BY_LOT)?;
+        for hold in &holds {
+            idx.insert(lot_key(hold).as_str(), hold.slot_id.to_string().as_str())?;
+        }
+        Ok(holds.len())
+    }
+
+    /// [`Self::index_slot_holds`] at startup.
+    pub(super) fn index_existing_slot_holds(&self) -> Result<()> {
+        let db = self
+            .inner
+            .try_read()
+            .map_err(|_| anyhow::anyhow!("Database is locked"))?;
+        let write_txn = db.begin_write()?;
+        let indexed = self.index_slot_holds(&write_txn)?;
+        write_txn.commit()?;
+
+        if indexed > 0 {
+            info!("Built slot hold index for {} hold(s)", indexed);
+        }
+        Ok(())
+    }
+
+    /// Store `hold`, replacing any earlier hold on its slot and the user's
+    /// holds on other slots.
+    pub async fn place_slot_hold(&self, hold: &SlotHold) -> Result<()> {
+        let key = hold.slot_id.to_string();
+        let data = self.serialize(hold)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(SLOT_HOLDS)?;
+            let mut idx = write_txn.open_table(SLOT_HOLDS_BY_LOT)?;
+            let mut previous = Vec::new();
+            for entry in table.iter()? {
+                let (_, value) = entry?;
+                let held = self.deserialize::<SlotHold>(value.value())?;
+                if held.user_id == hold.user_id {
+                    previous.push(held);
+                }
+            }
+            for held in &previous {
+                remove_hold(&mut table, &mut idx, held)?;
+            }
+            table.insert(key.as_str(), data.as_slice())?;
+            idx.insert(lot_key(hold).as_str(), key.as_str())?;
+        }
+        write_txn.commit()?;
+        debug!("Slot {} held for user {}", hold.slot_id, hold.user_id);
+        Ok(())
+    }
+
+    /// The hold on `slot_id`, expired or not.
+    pub async fn get_slot_hold(&self, slot_id: &str) -> Result<Option<SlotHold>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(SLOT_HOLDS)?;
+        match table.get(slot_id)? {
+            Some(value) => Ok(Some(self.deserialize(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// All stored holds, expired or not.
+    pub async fn list_slot_holds(&self) -> Result<Vec<SlotHold>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(SLOT_HOLDS)?;
+        let mut holds = Vec::new();
+        for entry in table.iter()? {
+            let (_, value) = entry?;
+            holds.push(self.deserialize(value.value())?);
+        }
+        Ok(holds)
+    }
+
+    /// Holds on slots of `lot_id`, expired or not.
+    pub async fn list_lot_slot_holds(&self, lot_id: Uuid) -> Result<Vec<SlotHold>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(SLOT_HOLDS)?;
+        let idx = read_txn.open_table(SLOT_HOLDS_BY_LOT)?;
+
+        let (start, end) = lot_range(lot_id);
+        let mut holds = Vec::new();
+        for entry in idx.range(start.as_str()..end.as_str())? {
+            let (_, slot_id) = entry?;
+            if let Some(value) = table.get(slot_id.value())? {
+                holds.push(self.deserialize(value.value())?);
+            }
+        }
+        Ok(holds)
+    }
+
+    /// Remove the hold on `slot_id` if it belongs to `user_id`. Returns
+    /// `false` if the slot is not held for that user.
+    pub async fn release_slot_hold(&self, slot_id: &str, user_id: Uuid) -> Result<bool> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let released = {
+            let mut table = write_txn.open_table(SLOT_HOLDS)?;
+            let mut idx = write_txn.open_table(SLOT_HOLDS_BY_LOT)?;
+            let held = match table.get(slot_id)? {
+                Some(value) => Some(self.deserialize::<SlotHold>(value.value())?),
+                None => None,
+            };
+            match held {
+                Some(held) if held.user_id == user_id => {
+                    remove_hold(&mut table, &mut idx, &held)?;
+                    true
+                }
+                _ => false,
+            }
+        };
+        write_txn.commit()?;
+        Ok(released)
+    }
+
+    /// Delete holds that expired before `now`; returns how many were removed.
+    pub async fn delete_expired_slot_holds(&self, now: DateTime<Utc>) -> Result<u64> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let removed = {
+            let mut table = write_txn.open_table(SLOT_HOLDS)?;
+            let mut idx = write_txn.open_table(SLOT_HOLDS_BY_LOT)?;
+            let mut expired = Vec::new();
+            for entry in table.iter()? {
+                let (_, value) = entry?;
+                let held = self.deserialize::<SlotHold>(value.value())?;
+                if !held.is_active(now) {
+                    expired.push(held);
+                }
+            }
+            for held in &expired {
+                remove_hold(&mut table, &mut idx, held)?;
+            }
+            expired.len() as u64
+        };
+        write_txn.commit()?;
+        Ok(removed)
+    }
+}
@@ -0,0 +1,106 @@
+//! Type index of parking slots, for filtered slot listings.
+//!
+//! `SLOTS_BY_TYPE` holds every slot keyed `"{lot_id}:{slot_type}:{slot_id}"`,
+//! with the type in its snake-case API spelling, so the electric or handicap
+//! slots of a lot are one range scan instead of a pass over all its slots.
+//! `SLOT_TYPE_KEYS` remembers each slot's key so a retyped or moved slot can
+//! drop it. Both are updated in the same write transaction as the slot.
+
+use anyhow::Result;
+use redb::{ReadableDatabase, ReadableTableMetadata, WriteTransaction};
+use tracing::info;
+
+use parkhub_common::models::{ParkingSlot, SlotType};
+
+use super::{Database, PARKING_SLOTS, SLOT_TYPE_KEYS, SLOTS_BY_TYPE};
+
+/// `slot_type` as it appears in index keys and API filters, e.g. `electric`.
+fn type_key(slot_type: &SlotType) -> String {
+    serde_json::to_value(slot_type)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Point the type index at `slot` as it is being stored.
+pub(super) fn index_slot_type(write_txn: &WriteTransaction, slot: &ParkingSlot) -> Result<()> {
+    let id = slot.id.to_string();
+    unindex_slot_type(write_txn, &id)?;
+    let key = format!("{}:{}:{id}", slot.lot_id, type_key(&slot.slot_type));
+    write_txn
+        .open_table(SLOTS_BY_TYPE)?
+        .insert(key.as_str(), id.as_str())?;
+    write_txn
+        .open_table(SLOT_TYPE_KEYS)?
+        .insert(id.as_str(), key.as_str())?;
+    Ok(())
+}
+
+/// Drop `slot_id` from the type index.
+pub(super) fn unindex_slot_type(write_txn: &WriteTransaction, slot_id: &str) -> Result<()> {
+    let old_key = write_txn
+        .open_table(SLOT_TYPE_KEYS)?
+        .remove(slot_id)?
+        .map(|key| key.value().to_string());
+    if let Some(old_key) = old_key {
+        write_txn
+            .open_table(SLOTS_BY_TYPE)?
+            .remove(old_key.as_str())?;
+    }
+    Ok(())
+}
+
+impl Database {
+    /// The slots of lot `lot_id` with type `slot_type`, in slot ID order.
+    pub async fn list_slots_by_lot_and_type(
+        &self,
+        lot_id: &str,
+        slot_type: &SlotType,
+    ) -> Result<Vec<ParkingSlot>> {
+        let _timer = Self::time_read("slots_by_type", Some(lot_id));
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let idx = read_txn.open_table(SLOTS_BY_TYPE)?;
+        let table = read_txn.open_table(PARKING_SLOTS)?;
+
+        // ';' sorts right after the ':' separator
+        let prefix = format!("{lot_id}:{}", type_key(slot_type));
+        let start = format!("{prefix}:");
+        let end = format!("{prefix};");
+        let mut slots = Vec::new();
+        for entry in idx.range(start.as_str()..end.as_str())? {
+            let (_, slot_id) = entry?;
+            if let Some(value) = table.get(slot_id.value())? {
+                slots.push(self.deserialize(value.value())?);
+            }
+        }
+        Ok(slots)
+    }
+
+    /// Index the slots stored before the type index existed.
+    ///
+    /// Runs once at startup; a no-op once any slot is indexed.
+    /// Undecodable records are skipped rather than failing the open.
+    pub(super) fn index_existing_slot_types(&self) -> Result<()> {
+        let db = self
+            .inner
+            .try_read()
+            .map_err(|_| anyhow::anyhow!("Database is locked"))?;
+        let write_txn = db.begin_write()?;
+        if !write_txn.open_table(SLOT_TYPE_KEYS)?.is_empty()? {
+            return Ok(());
+        }
+
+        let slots: Vec<ParkingSlot> = self.decode_all(&write_txn, PARKING_SLOTS)?;
+        for slot in &slots {
+            index_slot_type(&write_txn, slot)?;
+        }
+        write_txn.commit()?;
+
+        if !slots.is_empty() {
+            info!("Built slot type index for {} slot(s)", slots.len());
+        }
+        Ok(())
+    }
+}
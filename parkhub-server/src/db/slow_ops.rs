@@ -0,0 +1,177 @@
+//! Slow-operation tracking for the database layer.
+//!
+//! Every instrumented transaction and every decrypt/deserialize cycle is
+//! timed. Durations are exported to `/metrics`; anything over its threshold
+//! is additionally logged at `warn` with the table and key involved, so an
+//! operator can tell which access pattern is dragging the server down.
+//!
+//! Thresholds come from the environment (milliseconds, `0` disables the log
+//! line but keeps the metrics):
+//!
+//! - `PARKHUB_DB_SLOW_TXN_MS`    — transactions (default 250)
+//! - `PARKHUB_DB_SLOW_DECODE_MS` — decrypt + deserialize of one record (default 25)
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Slow-operation thresholds in milliseconds. `0` disables logging.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlowOpThresholds {
+    pub transaction_ms: u64,
+    pub decode_ms: u64,
+}
+
+impl SlowOpThresholds {
+    pub const DEFAULTS: Self = Self {
+        transaction_ms: 250,
+        decode_ms: 25,
+    };
+
+    /// Load from `PARKHUB_DB_SLOW_*_MS` env vars, falling back to
+    /// `DEFAULTS` on unset / unparsable values.
+    #[must_use]
+    pub fn from_env() -> Self {
+        fn parse(name: &str, default: u64) -> u64 {
+            std::env::var(name)
+                .ok()
+                .and_then(|s| s.trim().parse::<u64>().ok())
+                .unwrap_or(default)
+        }
+        let d = Self::DEFAULTS;
+        Self {
+            transaction_ms: parse("PARKHUB_DB_SLOW_TXN_MS", d.transaction_ms),
+            decode_ms: parse("PARKHUB_DB_SLOW_DECODE_MS", d.decode_ms),
+        }
+    }
+
+    /// Process-wide thresholds, read from the environment once.
+    pub fn global() -> Self {
+        static THRESHOLDS: OnceLock<SlowOpThresholds> = OnceLock::new();
+        *THRESHOLDS.get_or_init(Self::from_env)
+    }
+
+    const fn for_kind(self, kind: OpKind) -> u64 {
+        match kind {
+            OpKind::Read | OpKind::Write => self.transaction_ms,
+            OpKind::Decode => self.decode_ms,
+        }
+    }
+}
+
+impl Default for SlowOpThresholds {
+    fn default() -> Self {
+        Self::DEFAULTS
+    }
+}
+
+/// What is being timed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpKind {
+    Read,
+    Write,
+    Decode,
+}
+
+impl OpKind {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::Decode => "decode",
+        }
+    }
+}
+
+/// Whether `elapsed` exceeds `threshold_ms` (a zero threshold never does).
+#[must_use]
+pub fn is_slow(elapsed: Duration, threshold_ms: u64) -> bool {
+    threshold_ms > 0 && elapsed > Duration::from_millis(threshold_ms)
+}
+
+/// Times one database operation; reports when dropped so early returns via
+/// `?` are covered too.
+pub struct OpTimer {
+    kind: OpKind,
+    table: &'static str,
+    key: Option<String>,
+    start: Instant,
+}
+
+impl OpTimer {
+    pub fn start(kind: OpKind, table: &'static str, key: Option<&str>) -> Self {
+        Self {
+            kind,
+            table,
+            key: key.map(str::to_string),
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for OpTimer {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        crate::metrics::record_db_op_duration(self.kind.as_str(), self.table, elapsed);
+
+        let threshold_ms = SlowOpThresholds::global().for_kind(self.kind);
+        if is_slow(elapsed, threshold_ms) {
+            crate::metrics::record_db_slow_operation(self.kind.as_str(), self.table);
+            tracing::warn!(
+                op = self.kind.as_str(),
+                table = self.table,
+                key = self.key.as_deref().unwrap_or("-"),
+                elapsed_ms = u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX),
+                threshold_ms,
+                "Slow database operation"
+            );
+        }
+    }
+}
+
+/// Short record type name used as the `table` label for decode timings,
+/// e.g. `parkhub_common::models::Booking` → `Booking`,
+/// `alloc::vec::Vec<alloc::string::String>` → `Vec`.
+#[must_use]
+pub fn record_label(type_name: &'static str) -> &'static str {
+    let base = type_name.split('<').next().unwrap_or(type_name);
+    base.rsplit("::").next().unwrap_or(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_slow() {
+        assert!(is_slow(Duration::from_millis(300), 250));
+        assert!(!is_slow(Duration::from_millis(250), 250));
+        assert!(!is_slow(Duration::from_secs(10), 0));
+    }
+
+    #[test]
+    fn test_threshold_per_kind() {
+        let t = SlowOpThresholds {
+            transaction_ms: 100,
+            decode_ms: 5,
+        };
+        assert_eq!(t.for_kind(OpKind::Read), 100);
+        assert_eq!(t.for_kind(OpKind::Write), 100);
+        assert_eq!(t.for_kind(OpKind::Decode), 5);
+    }
+
+    #[test]
+    fn test_record_label() {
+        assert_eq!(record_label("parkhub_common::models::Booking"), "Booking");
+        assert_eq!(
+            record_label("alloc::vec::Vec<alloc::string::String>"),
+            "Vec"
+        );
+        assert_eq!(record_label("u32"), "u32");
+    }
+
+    #[test]
+    fn test_timer_drop_does_not_panic() {
+        let timer = OpTimer::start(OpKind::Read, "users", Some("abc"));
+        drop(timer);
+    }
+}
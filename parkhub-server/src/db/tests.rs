@@ -633,6 +633,7 @@ fn make_user(username: &str, email: &str) -> User {
         cost_center: None,
         department: None,
         settings: None,
+        must_change_password: false,
     }
 }
 
@@ -672,6 +673,7 @@ fn make_booking(user_id: Uuid, lot_id: Uuid, vehicle: &Vehicle) -> Booking {
             currency: "EUR".to_string(),
             payment_status: parkhub_common::models::PaymentStatus::Paid,
             payment_method: Some("card".to_string()),
+            cancellation_fee: None,
         },
         created_at: now,
         updated_at: now,
@@ -680,6 +682,8 @@ fn make_booking(user_id: Uuid, lot_id: Uuid, vehicle: &Vehicle) -> Booking {
         qr_code: None,
         notes: None,
         tenant_id: None,
+        tags: std::collections::BTreeMap::new(),
+        booked_for: None,
     }
 }
 
@@ -789,6 +793,55 @@ async fn test_user_crud() {
     );
 }
 
+#[tokio::test]
+async fn test_update_user_email_moves_index() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+
+    let mut user = make_user("alice", "alice@example.com");
+    db.save_user(&user).await.unwrap();
+
+    user.email = "alice@new.example.com".to_string();
+    db.update_user_email(&user, "alice@example.com")
+        .await
+        .unwrap();
+
+    assert!(
+        db.get_user_by_email("alice@example.com")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let by_email = db
+        .get_user_by_email("alice@new.example.com")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(by_email.id, user.id);
+    assert_eq!(by_email.email, "alice@new.example.com");
+}
+
+#[tokio::test]
+async fn test_user_picture_roundtrip() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+
+    let user = make_user("alice", "alice@example.com");
+    let id = user.id.to_string();
+    assert!(db.get_user_picture(&id).await.unwrap().is_none());
+
+    db.save_user_picture(&id, &[0x89, b'P', b'N', b'G'])
+        .await
+        .unwrap();
+    assert_eq!(
+        db.get_user_picture(&id).await.unwrap().as_deref(),
+        Some(&[0x89, b'P', b'N', b'G'][..])
+    );
+
+    db.delete_user_picture(&id).await.unwrap();
+    assert!(db.get_user_picture(&id).await.unwrap().is_none());
+}
+
 #[tokio::test]
 async fn test_user_list() {
     let dir = tempdir().unwrap();
@@ -881,6 +934,85 @@ async fn test_booking_crud() {
     );
 }
 
+#[tokio::test]
+async fn test_booking_notes_and_attachments_go_with_booking() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), true)).unwrap();
+
+    let user = make_user("parker", "parker@test.com");
+    let vehicle = make_vehicle(user.id, "M-PH 1234");
+    let booking = make_booking(user.id, Uuid::new_v4(), &vehicle);
+    let other = make_booking(user.id, Uuid::new_v4(), &vehicle);
+    db.save_booking(&booking).await.unwrap();
+    db.save_booking(&other).await.unwrap();
+    let booking_id = booking.id.to_string();
+
+    let note = BookingNote {
+        id: Uuid::new_v4(),
+        booking_id: booking.id,
+        author_id: Uuid::new_v4(),
+        text: "Slot damaged on arrival".to_string(),
+        created_at: Utc::now(),
+    };
+    db.save_booking_note(&note).await.unwrap();
+    let attachment = BookingAttachment {
+        id: Uuid::new_v4(),
+        booking_id: booking.id,
+        uploaded_by: user.id,
+        filename: "dent.pdf".to_string(),
+        content_type: "application/pdf".to_string(),
+        size_bytes: 9,
+        created_at: Utc::now(),
+    };
+    db.save_booking_attachment(&attachment, b"%PDF-1.4\n")
+        .await
+        .unwrap();
+
+    let notes = db.list_booking_notes(&booking_id).await.unwrap();
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].text, "Slot damaged on arrival");
+    assert!(
+        db.list_booking_notes(&other.id.to_string())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Stored encrypted, read back in the clear
+    let path = dir
+        .path()
+        .join("attachments")
+        .join(&booking_id)
+        .join(attachment.id.to_string());
+    assert_ne!(std::fs::read(&path).unwrap(), b"%PDF-1.4\n");
+    let stored = db
+        .get_booking_attachment(&booking_id, &attachment.id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        db.read_booking_attachment(&stored).await.unwrap(),
+        b"%PDF-1.4\n"
+    );
+    assert_eq!(
+        db.list_booking_attachments(&booking_id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    assert!(db.delete_booking(&booking_id).await.unwrap());
+    assert!(db.list_booking_notes(&booking_id).await.unwrap().is_empty());
+    assert!(
+        db.list_booking_attachments(&booking_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(!path.exists());
+}
+
 #[tokio::test]
 async fn test_booking_by_lot() {
     let dir = tempdir().unwrap();
@@ -1048,6 +1180,182 @@ async fn test_delete_sessions_by_user() {
     assert_eq!(bob.user_id, user_b);
 }
 
+#[tokio::test]
+async fn test_rotate_refresh_token_remembers_spent_token() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+
+    let user_id = Uuid::new_v4();
+    let session = Session::new(user_id, 24, "alice", "user");
+    db.save_session("sess_1", &session).await.unwrap();
+    assert!(
+        db.get_rotated_refresh_token(&session.refresh_token)
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // First rotation ends the session and records the token
+    assert!(db.rotate_refresh_token("sess_1", &session).await.unwrap());
+    assert!(db.get_session("sess_1").await.unwrap().is_none());
+    assert!(db.list_sessions_by_user(user_id).await.unwrap().is_empty());
+    let rotated = db
+        .get_rotated_refresh_token(&session.refresh_token)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(rotated.user_id, user_id);
+    assert_eq!(rotated.family_id, session.family_id);
+
+    // A second rotation of the same session loses the race
+    assert!(!db.rotate_refresh_token("sess_1", &session).await.unwrap());
+
+    // Records outlive their session only until it would have expired
+    assert_eq!(db.prune_rotated_refresh_tokens().await.unwrap(), 0);
+    let mut expired = Session::new(user_id, 1, "alice", "user");
+    expired.expires_at = Utc::now() - chrono::Duration::hours(1);
+    db.save_session("sess_2", &expired).await.unwrap();
+    assert!(db.rotate_refresh_token("sess_2", &expired).await.unwrap());
+    assert_eq!(db.prune_rotated_refresh_tokens().await.unwrap(), 1);
+    assert!(
+        db.get_rotated_refresh_token(&expired.refresh_token)
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_sessions_by_user_index() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+
+    let user_a = Uuid::new_v4();
+    let user_b = Uuid::new_v4();
+    let mut s1 = Session::new(user_a, 24, "alice", "user")
+        .with_client(Some("203.0.113.7".to_string()), Some("Firefox".to_string()));
+    s1.created_at -= chrono::Duration::minutes(5);
+    let s2 = Session::new(user_a, 24, "alice", "user");
+    let s3 = Session::new(user_b, 24, "bob", "user");
+    db.save_session("tok_a1", &s1).await.unwrap();
+    db.save_session("tok_a2", &s2).await.unwrap();
+    db.save_session("tok_b1", &s3).await.unwrap();
+
+    // Oldest first, only the user's own sessions, client details kept
+    let listed = db.list_sessions_by_user(user_a).await.unwrap();
+    let tokens: Vec<&str> = listed.iter().map(|(t, _)| t.as_str()).collect();
+    assert_eq!(tokens, ["tok_a1", "tok_a2"]);
+    assert_eq!(listed[0].1.ip_address.as_deref(), Some("203.0.113.7"));
+    assert_eq!(listed[0].1.user_agent.as_deref(), Some("Firefox"));
+    assert!(listed[0].1.last_used_at.is_none());
+
+    // Deleting a session drops its index entry
+    assert!(db.delete_session("tok_a1").await.unwrap());
+    let listed = db.list_sessions_by_user(user_a).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].0, "tok_a2");
+    assert_eq!(db.list_sessions_by_user(user_b).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_touch_session() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+
+    let session = Session::new(Uuid::new_v4(), 24, "alice", "user")
+        .with_client(Some("203.0.113.7".to_string()), None);
+    db.save_session("tok", &session).await.unwrap();
+
+    assert!(db.touch_session("tok", None).await.unwrap());
+    let touched = db.get_session("tok").await.unwrap().unwrap();
+    assert!(touched.last_used_at.is_some());
+    assert_eq!(touched.ip_address.as_deref(), Some("203.0.113.7"));
+
+    assert!(db.touch_session("tok", Some("198.51.100.2")).await.unwrap());
+    let touched = db.get_session("tok").await.unwrap().unwrap();
+    assert_eq!(touched.ip_address.as_deref(), Some("198.51.100.2"));
+
+    assert!(!db.touch_session("missing", None).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_session_idle_since_last_use() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let idle_timeout = chrono::Duration::minutes(30);
+
+    let mut session = Session::new(Uuid::new_v4(), 24, "alice", "user");
+    assert!(!session.is_idle(idle_timeout));
+
+    // Unused since creation: idle once the timeout has passed
+    session.created_at = Utc::now() - chrono::Duration::hours(1);
+    assert!(session.is_idle(idle_timeout));
+    assert!(!session.is_expired());
+
+    // A touch resets the idle clock, but not the absolute expiry
+    db.save_session("tok", &session).await.unwrap();
+    assert!(db.touch_session("tok", None).await.unwrap());
+    let touched = db.get_session("tok").await.unwrap().unwrap();
+    assert!(!touched.is_idle(idle_timeout));
+    assert_eq!(touched.expires_at, session.expires_at);
+}
+
+#[tokio::test]
+async fn test_legacy_sessions_indexed_on_open() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let user_id = Uuid::new_v4();
+    {
+        let db = Database::open(&config).unwrap();
+        db.save_session("tok_old", &Session::new(user_id, 24, "alice", "user"))
+            .await
+            .unwrap();
+
+        // Simulate a database written before the index existed
+        let inner = db.inner.write().await;
+        let write_txn = inner.begin_write().unwrap();
+        {
+            let mut idx = write_txn.open_table(SESSIONS_BY_USER).unwrap();
+            idx.remove(format!("{user_id}:tok_old").as_str()).unwrap();
+        }
+        write_txn.commit().unwrap();
+    }
+
+    let db = Database::open(&config).unwrap();
+    let listed = db.list_sessions_by_user(user_id).await.unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].0, "tok_old");
+}
+
+#[tokio::test]
+async fn test_session_revocation_survives_reopen() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let user_id = Uuid::new_v4();
+    let revoked_at = Utc::now();
+    {
+        let db = Database::open(&config).unwrap();
+        assert_eq!(db.sessions_revoked_before(user_id), None);
+        db.revoke_sessions_before(user_id, revoked_at)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.sessions_revoked_before(user_id),
+            Some(revoked_at.timestamp())
+        );
+    }
+
+    let db = Database::open(&config).unwrap();
+    assert_eq!(
+        db.sessions_revoked_before(user_id),
+        Some(revoked_at.timestamp())
+    );
+    assert_eq!(db.sessions_revoked_before(Uuid::new_v4()), None);
+
+    db.clear_all_data().await.unwrap();
+    assert_eq!(db.sessions_revoked_before(user_id), None);
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // SETTINGS OPERATIONS
 // ═══════════════════════════════════════════════════════════════════════════
@@ -1161,6 +1469,7 @@ async fn test_announcement_crud() {
         created_by: Some(Uuid::new_v4()),
         expires_at: Some(Utc::now() + chrono::Duration::days(7)),
         created_at: Utc::now(),
+        audience: parkhub_common::models::AnnouncementAudience::All,
     };
 
     // Save
@@ -1308,6 +1617,77 @@ async fn test_slot_batch_save() {
     }
 }
 
+#[tokio::test]
+async fn test_slot_type_index_follows_slot_writes() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let lot_id = Uuid::new_v4();
+    let floor_id = Uuid::new_v4();
+    let mut slots: Vec<ParkingSlot> = (1..=4).map(|n| make_slot(lot_id, floor_id, n)).collect();
+    slots[0].slot_type = SlotType::Electric;
+    slots[1].slot_type = SlotType::Electric;
+    let other_lot = make_slot(Uuid::new_v4(), floor_id, 1);
+    let electric_ids = |found: Vec<ParkingSlot>| {
+        let mut ids: Vec<Uuid> = found.into_iter().map(|s| s.id).collect();
+        ids.sort();
+        ids
+    };
+
+    {
+        let db = Database::open(&config).unwrap();
+        db.save_parking_slots_batch(&slots).await.unwrap();
+        let mut electric = other_lot.clone();
+        electric.slot_type = SlotType::Electric;
+        db.save_parking_slot(&electric).await.unwrap();
+
+        let mut expected = vec![slots[0].id, slots[1].id];
+        expected.sort();
+        let found = db
+            .list_slots_by_lot_and_type(&lot_id.to_string(), &SlotType::Electric)
+            .await
+            .unwrap();
+        assert_eq!(electric_ids(found), expected);
+
+        // Retyping moves the slot between types
+        let mut retyped = slots[0].clone();
+        retyped.slot_type = SlotType::Handicap;
+        db.save_parking_slot(&retyped).await.unwrap();
+        db.delete_parking_slot(&slots[1].id.to_string())
+            .await
+            .unwrap();
+        let found = db
+            .list_slots_by_lot_and_type(&lot_id.to_string(), &SlotType::Electric)
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+        let found = db
+            .list_slots_by_lot_and_type(&lot_id.to_string(), &SlotType::Handicap)
+            .await
+            .unwrap();
+        assert_eq!(electric_ids(found), vec![slots[0].id]);
+
+        // Simulate a database written before the index existed
+        let inner = db.inner.write().await;
+        let write_txn = inner.begin_write().unwrap();
+        write_txn.delete_table(SLOTS_BY_TYPE).unwrap();
+        write_txn.delete_table(SLOT_TYPE_KEYS).unwrap();
+        write_txn.commit().unwrap();
+    }
+
+    // Opening rebuilds the index from the stored slots
+    let db = Database::open(&config).unwrap();
+    let found = db
+        .list_slots_by_lot_and_type(&lot_id.to_string(), &SlotType::Standard)
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 2);
+    let found = db
+        .list_slots_by_lot_and_type(&other_lot.lot_id.to_string(), &SlotType::Electric)
+        .await
+        .unwrap();
+    assert_eq!(electric_ids(found), vec![other_lot.id]);
+}
+
 #[tokio::test]
 async fn test_slot_status_update() {
     let dir = tempdir().unwrap();
@@ -1327,7 +1707,11 @@ async fn test_slot_status_update() {
 
     // Update status
     let updated = db
-        .update_slot_status(&slot.id.to_string(), SlotStatus::Occupied)
+        .update_slot_status(
+            &slot.id.to_string(),
+            SlotStatus::Occupied,
+            &SlotChangeContext::system("test"),
+        )
         .await
         .unwrap();
     assert!(updated);
@@ -1341,12 +1725,65 @@ async fn test_slot_status_update() {
 
     // Update non-existent slot returns false
     let nope = db
-        .update_slot_status(&Uuid::new_v4().to_string(), SlotStatus::Maintenance)
+        .update_slot_status(
+            &Uuid::new_v4().to_string(),
+            SlotStatus::Maintenance,
+            &SlotChangeContext::system("test"),
+        )
         .await
         .unwrap();
     assert!(!nope);
 }
 
+#[tokio::test]
+async fn test_slot_status_history() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+
+    let mut slot = make_slot(Uuid::new_v4(), Uuid::new_v4(), 14);
+    let slot_id = slot.id.to_string();
+    db.save_parking_slot(&slot).await.unwrap();
+    // Creating a slot is not a status change.
+    assert!(db.list_slot_history(&slot_id, 10).await.unwrap().is_empty());
+
+    let admin = Uuid::new_v4();
+    slot.status = SlotStatus::Maintenance;
+    db.save_parking_slot_with(
+        &slot,
+        &SlotChangeContext::user(admin, "admin").reason("Barrier repair"),
+    )
+    .await
+    .unwrap();
+    // Saving without a status change records nothing.
+    slot.is_accessible = true;
+    db.save_parking_slot(&slot).await.unwrap();
+    db.update_slot_status(
+        &slot_id,
+        SlotStatus::Available,
+        &SlotChangeContext::system("test"),
+    )
+    .await
+    .unwrap();
+
+    let history = db.list_slot_history(&slot_id, 10).await.unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].to_status, SlotStatus::Available);
+    assert_eq!(history[0].changed_by, None);
+    assert_eq!(history[1].from_status, SlotStatus::Available);
+    assert_eq!(history[1].to_status, SlotStatus::Maintenance);
+    assert_eq!(history[1].changed_by, Some(admin));
+    assert_eq!(history[1].source, "admin");
+    assert_eq!(history[1].reason.as_deref(), Some("Barrier repair"));
+
+    let old = db
+        .list_slot_history_before(Utc::now() + chrono::Duration::seconds(1))
+        .await
+        .unwrap();
+    assert_eq!(old.len(), 2);
+    assert_eq!(db.delete_slot_history(&old).await.unwrap(), 2);
+    assert!(db.list_slot_history(&slot_id, 10).await.unwrap().is_empty());
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // STATS
 // ═══════════════════════════════════════════════════════════════════════════
@@ -1598,6 +2035,84 @@ async fn test_gdpr_anonymize_user() {
     assert_eq!(scrubbed.vehicle.license_plate, "[DELETED]");
 }
 
+#[tokio::test]
+async fn test_anonymize_bookings_ended_before() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+
+    let user = make_user("alice", "alice@example.com");
+    db.save_user(&user).await.unwrap();
+    let vehicle = make_vehicle(user.id, "M-AB 1234");
+    let mut booking = make_booking(user.id, Uuid::new_v4(), &vehicle);
+    booking.status = parkhub_common::models::BookingStatus::Completed;
+    booking.notes = Some("Guest of Dr. Example".to_string());
+    db.save_booking(&booking).await.unwrap();
+
+    let cutoff = booking.end_time + chrono::Duration::hours(1);
+    assert_eq!(db.anonymize_bookings_ended_before(cutoff).await.unwrap(), 1);
+    // Already stripped bookings are not rewritten again
+    assert_eq!(db.anonymize_bookings_ended_before(cutoff).await.unwrap(), 0);
+
+    let scrubbed = db
+        .get_booking(&booking.id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(scrubbed.vehicle.license_plate, "[DELETED]");
+    assert!(scrubbed.vehicle.make.is_none());
+    assert!(scrubbed.notes.is_none());
+    assert!((scrubbed.pricing.total - 5.95).abs() < f64::EPSILON);
+
+    // The account is untouched
+    let kept = db.get_user(&user.id.to_string()).await.unwrap().unwrap();
+    assert_eq!(kept.username, "alice");
+}
+
+#[tokio::test]
+async fn test_rectification_requests_deleted_on_anonymize() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+
+    let alice = make_user("alice", "alice@example.com");
+    let bob = make_user("bob", "bob@example.com");
+    db.save_user(&alice).await.unwrap();
+    db.save_user(&bob).await.unwrap();
+    let request = |user: &User| RectificationRequest {
+        id: Uuid::new_v4(),
+        user_id: user.id,
+        username: user.username.clone(),
+        tenant_id: None,
+        field: "department".to_string(),
+        current_value: None,
+        requested_value: "Sales".to_string(),
+        reason: None,
+        status: RectificationStatus::Open,
+        resolution_note: None,
+        resolved_by: None,
+        created_at: chrono::Utc::now(),
+        resolved_at: None,
+    };
+    let mine = request(&alice);
+    db.save_rectification_request(&mine).await.unwrap();
+    db.save_rectification_request(&request(&bob)).await.unwrap();
+
+    let listed = db
+        .list_rectification_requests_by_user(alice.id)
+        .await
+        .unwrap();
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].id, mine.id);
+
+    db.anonymize_user(&alice.id.to_string()).await.unwrap();
+    assert!(
+        db.get_rectification_request(&mine.id.to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert_eq!(db.list_rectification_requests().await.unwrap().len(), 1);
+}
+
 #[tokio::test]
 async fn test_gdpr_anonymize_nonexistent_user() {
     let dir = tempdir().unwrap();
@@ -1736,6 +2251,7 @@ async fn test_announcement_multiple_and_order() {
         created_by: None,
         expires_at: None,
         created_at: Utc::now(),
+        audience: parkhub_common::models::AnnouncementAudience::All,
     };
     let a2 = Announcement {
         id: Uuid::new_v4(),
@@ -1746,6 +2262,7 @@ async fn test_announcement_multiple_and_order() {
         created_by: Some(Uuid::new_v4()),
         expires_at: Some(Utc::now() + chrono::Duration::days(30)),
         created_at: Utc::now(),
+        audience: parkhub_common::models::AnnouncementAudience::All,
     };
 
     db.save_announcement(&a1).await.unwrap();
@@ -1850,3 +2367,1512 @@ async fn test_encrypted_data_roundtrip() {
     assert_eq!(fetched.username, "encrypted_alice");
     assert_eq!(fetched.email, "encrypted@test.com");
 }
+
+#[tokio::test]
+async fn test_email_queue_roundtrip() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), true)).unwrap();
+
+    let older = QueuedEmail::new("a@example.com", "First", "<p>1</p>");
+    let mut newer = QueuedEmail::new("b@example.com", "Second", "<p>2</p>");
+    newer.created_at = older.created_at + chrono::Duration::seconds(1);
+    db.save_queued_email(&newer).await.unwrap();
+    db.save_queued_email(&older).await.unwrap();
+
+    let listed = db.list_queued_emails().await.unwrap();
+    assert_eq!(
+        listed.iter().map(|e| e.id).collect::<Vec<_>>(),
+        vec![older.id, newer.id]
+    );
+
+    newer.status = QueuedEmailStatus::Failed;
+    newer.attempts = 6;
+    db.save_queued_email(&newer).await.unwrap();
+    let loaded = db
+        .get_queued_email(&newer.id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(loaded.status, QueuedEmailStatus::Failed);
+    assert_eq!(loaded.html_body, "<p>2</p>");
+
+    assert!(db.delete_queued_email(&older.id.to_string()).await.unwrap());
+    assert!(!db.delete_queued_email(&older.id.to_string()).await.unwrap());
+    assert_eq!(db.list_queued_emails().await.unwrap().len(), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_claim_slot_concurrent_same_range() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let user = make_user("claimer", "claimer@test.com");
+    let vehicle = make_vehicle(user.id, "CLM-001");
+    let lot_id = Uuid::new_v4();
+    let slot_id = Uuid::new_v4();
+    let template = make_booking(user.id, lot_id, &vehicle);
+
+    let tasks: Vec<_> = (0..48)
+        .map(|_| {
+            let db = db.clone();
+            let mut booking = template.clone();
+            booking.id = Uuid::new_v4();
+            booking.slot_id = slot_id;
+            tokio::spawn(async move { db.claim_slot(&booking).await.unwrap() })
+        })
+        .collect();
+
+    let mut claimed = 0;
+    for task in tasks {
+        if task.await.unwrap() {
+            claimed += 1;
+        }
+    }
+    assert_eq!(claimed, 1);
+
+    let on_slot = db
+        .list_bookings()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|b| b.slot_id == slot_id)
+        .count();
+    assert_eq!(on_slot, 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_claim_slot_concurrent_staggered_ranges() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), true)).unwrap();
+    let user = make_user("stagger", "stagger@test.com");
+    let vehicle = make_vehicle(user.id, "STG-001");
+    let slot_id = Uuid::new_v4();
+    let template = make_booking(user.id, Uuid::new_v4(), &vehicle);
+    let base = template.start_time;
+
+    // 36 tasks race for twelve back-to-back hours, three per hour; two-hour
+    // ranges starting every hour overlap their neighbours.
+    let tasks: Vec<_> = (0..36)
+        .map(|i| {
+            let db = db.clone();
+            let mut booking = template.clone();
+            booking.id = Uuid::new_v4();
+            booking.slot_id = slot_id;
+            booking.start_time = base + chrono::Duration::hours(i % 12);
+            booking.end_time = booking.start_time + chrono::Duration::hours(2);
+            tokio::spawn(async move { db.claim_slot(&booking).await.unwrap() })
+        })
+        .collect();
+    for task in tasks {
+        task.await.unwrap();
+    }
+
+    let mut held: Vec<_> = db
+        .list_bookings()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|b| b.slot_id == slot_id)
+        .collect();
+    assert!(!held.is_empty());
+    held.sort_by_key(|b| b.start_time);
+    for pair in held.windows(2) {
+        assert!(
+            pair[0].end_time <= pair[1].start_time,
+            "bookings {} and {} overlap",
+            pair[0].id,
+            pair[1].id
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_claim_slot_ignores_released_bookings() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let user = make_user("released", "released@test.com");
+    let vehicle = make_vehicle(user.id, "REL-001");
+    let mut first = make_booking(user.id, Uuid::new_v4(), &vehicle);
+    assert!(db.claim_slot(&first).await.unwrap());
+
+    let mut second = first.clone();
+    second.id = Uuid::new_v4();
+    assert!(!db.claim_slot(&second).await.unwrap());
+    assert!(
+        db.get_booking(&second.id.to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Re-claiming with the same booking id is not a conflict with itself.
+    first.notes = Some("updated".to_string());
+    assert!(db.claim_slot(&first).await.unwrap());
+
+    first.status = parkhub_common::models::BookingStatus::Cancelled;
+    db.save_booking(&first).await.unwrap();
+    assert!(db.claim_slot(&second).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_claim_slot_keeps_lot_buffer_free() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let user = make_user("buffer", "buffer@test.com");
+    let vehicle = make_vehicle(user.id, "BUF-001");
+    let lot_id = Uuid::new_v4();
+    db.set_setting(
+        &lot_rules_key(&lot_id.to_string()),
+        r#"{"buffer_minutes":15}"#,
+    )
+    .await
+    .unwrap();
+
+    let first = make_booking(user.id, lot_id, &vehicle);
+    assert!(db.claim_slot(&first).await.unwrap());
+
+    // Starting 10 minutes after the first ends is inside the buffer
+    let mut close = first.clone();
+    close.id = Uuid::new_v4();
+    close.start_time = first.end_time + chrono::Duration::minutes(10);
+    close.end_time = close.start_time + chrono::Duration::hours(1);
+    assert!(!db.claim_slot(&close).await.unwrap());
+
+    // Ending 10 minutes before the first starts is too
+    let mut before = close.clone();
+    before.end_time = first.start_time - chrono::Duration::minutes(10);
+    before.start_time = before.end_time - chrono::Duration::hours(1);
+    assert!(!db.claim_slot(&before).await.unwrap());
+
+    let mut after = close.clone();
+    after.start_time = first.end_time + chrono::Duration::minutes(15);
+    after.end_time = after.start_time + chrono::Duration::hours(1);
+    assert!(db.claim_slot(&after).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_slot_bookings_overlapping_follow_time_index() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let user = make_user("window", "window@test.com");
+    let vehicle = make_vehicle(user.id, "WIN-001");
+    let start = Utc::now() + chrono::Duration::days(1);
+    let window = |from: i64, to: i64| {
+        parkhub_common::TimeRange::new(
+            start + chrono::Duration::hours(from),
+            start + chrono::Duration::hours(to),
+        )
+        .unwrap()
+    };
+    let ids = |bookings: Vec<Booking>| -> Vec<Uuid> { bookings.iter().map(|b| b.id).collect() };
+
+    let mut booking = make_booking(user.id, Uuid::new_v4(), &vehicle);
+    booking.start_time = start;
+    booking.end_time = start + chrono::Duration::hours(2);
+    let slot = [booking.slot_id];
+    {
+        let db = Database::open(&config).unwrap();
+        assert!(db.claim_slot(&booking).await.unwrap());
+        assert_eq!(
+            ids(db
+                .list_slot_bookings_overlapping(&slot, &window(1, 3))
+                .await
+                .unwrap()),
+            vec![booking.id]
+        );
+        // Half-open ranges: touching windows do not overlap
+        assert!(
+            db.list_slot_bookings_overlapping(&slot, &window(2, 4))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            db.list_slot_bookings_overlapping(&[Uuid::new_v4()], &window(0, 2))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // Moving the booking leaves no entry at its old time
+        booking.start_time = start + chrono::Duration::hours(5);
+        booking.end_time = start + chrono::Duration::hours(6);
+        db.save_booking(&booking).await.unwrap();
+        assert!(
+            db.list_slot_bookings_overlapping(&slot, &window(0, 2))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            db.list_slot_bookings_overlapping(&slot, &window(0, 24))
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // Simulate a database written before the index existed
+        let inner = db.inner.write().await;
+        let write_txn = inner.begin_write().unwrap();
+        write_txn.delete_table(BOOKINGS_BY_SLOT_END).unwrap();
+        write_txn.delete_table(BOOKING_SLOT_END_KEYS).unwrap();
+        write_txn.commit().unwrap();
+    }
+
+    let db = Database::open(&config).unwrap();
+    assert_eq!(
+        ids(db
+            .list_slot_bookings_overlapping(&slot, &window(5, 6))
+            .await
+            .unwrap()),
+        vec![booking.id]
+    );
+
+    booking.status = parkhub_common::models::BookingStatus::Cancelled;
+    db.save_booking(&booking).await.unwrap();
+    assert!(
+        db.list_slot_bookings_overlapping(&slot, &window(0, 24))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    booking.status = parkhub_common::models::BookingStatus::Confirmed;
+    db.save_booking(&booking).await.unwrap();
+    db.delete_booking(&booking.id.to_string()).await.unwrap();
+    assert!(
+        db.list_slot_bookings_overlapping(&slot, &window(0, 24))
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_live_bookings_ended_by_follow_end_index() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let user = make_user("ended", "ended@test.com");
+    let vehicle = make_vehicle(user.id, "END-001");
+    let now = Utc::now();
+    let ids = |bookings: Vec<Booking>| -> Vec<Uuid> { bookings.iter().map(|b| b.id).collect() };
+
+    let mut ended = make_booking(user.id, Uuid::new_v4(), &vehicle);
+    ended.start_time = now - chrono::Duration::hours(3);
+    ended.end_time = now - chrono::Duration::hours(1);
+    // Bookings still waiting for a slot are indexed too
+    let mut unassigned = make_booking(user.id, Uuid::new_v4(), &vehicle);
+    unassigned.slot_id = Uuid::nil();
+    unassigned.start_time = now - chrono::Duration::hours(2);
+    unassigned.end_time = now - chrono::Duration::minutes(30);
+    let mut running = make_booking(user.id, Uuid::new_v4(), &vehicle);
+    running.start_time = now - chrono::Duration::hours(1);
+    running.end_time = now + chrono::Duration::hours(1);
+    {
+        let db = Database::open(&config).unwrap();
+        for booking in [&ended, &unassigned, &running] {
+            db.save_booking(booking).await.unwrap();
+        }
+        assert_eq!(
+            ids(db.list_live_bookings_ended_by(now).await.unwrap()),
+            vec![ended.id, unassigned.id]
+        );
+
+        // Settled bookings leave the index
+        ended.status = parkhub_common::models::BookingStatus::Completed;
+        db.save_booking(&ended).await.unwrap();
+        assert_eq!(
+            ids(db.list_live_bookings_ended_by(now).await.unwrap()),
+            vec![unassigned.id]
+        );
+
+        // Simulate a database written before the index existed
+        let inner = db.inner.write().await;
+        let write_txn = inner.begin_write().unwrap();
+        write_txn.delete_table(LIVE_BOOKINGS_BY_END).unwrap();
+        write_txn.delete_table(LIVE_BOOKING_END_KEYS).unwrap();
+        write_txn.commit().unwrap();
+    }
+
+    let db = Database::open(&config).unwrap();
+    assert_eq!(
+        ids(db
+            .list_live_bookings_ended_by(now + chrono::Duration::hours(2))
+            .await
+            .unwrap()),
+        vec![unassigned.id, running.id]
+    );
+    db.delete_booking(&unassigned.id.to_string()).await.unwrap();
+    assert_eq!(
+        ids(db
+            .list_live_bookings_ended_by(now + chrono::Duration::hours(2))
+            .await
+            .unwrap()),
+        vec![running.id]
+    );
+}
+// ═══════════════════════════════════════════════════════════════════════════
+// SEARCH INDEX
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[test]
+fn test_search_tokenize() {
+    assert_eq!(search::tokenize("M-AB 1234"), ["mab", "ab", "1234"]);
+    assert_eq!(
+        search::tokenize("Jo.Doe@Example.com"),
+        ["jodoeexamplecom", "jo", "doe", "example", "com"]
+    );
+    assert_eq!(search::tokenize("Müller, Käthe"), ["müller", "käthe"]);
+    assert!(search::tokenize("a - b").is_empty());
+}
+
+#[tokio::test]
+async fn test_search_users_by_name_email_and_plate() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let mut alice = make_user("alice", "alice@example.com");
+    alice.name = "Alice Müller".to_string();
+    let bob = make_user("bob", "bob@example.com");
+    db.save_user(&alice).await.unwrap();
+    db.save_user(&bob).await.unwrap();
+    db.save_vehicle(&make_vehicle(bob.id, "M-AB 1234"))
+        .await
+        .unwrap();
+
+    let hits = db.search("mül", &[], 10, true).await.unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].kind, SearchKind::User);
+    assert_eq!(hits[0].id, alice.id.to_string());
+
+    let hits = db.search("alice@example.com", &[], 10, true).await.unwrap();
+    assert_eq!(hits[0].id, alice.id.to_string());
+
+    // Plate search finds the owner, with or without spacing
+    for query in ["M-AB 1234", "mab1234"] {
+        let hits = db
+            .search(query, &[SearchKind::User], 10, true)
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1, "query {query}");
+        assert_eq!(hits[0].id, bob.id.to_string());
+    }
+    // ...unless plates are left out
+    assert!(
+        db.search("mab1234", &[], 10, false)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    // Stop words alone match nothing
+    assert!(
+        db.search("the and", &[], 10, true)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_search_ranks_by_matched_terms() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let user = make_user("carol", "carol@example.com");
+    let lot_id = Uuid::new_v4();
+    let tuesday = "2026-10-20T08:00:00Z"
+        .parse::<chrono::DateTime<Utc>>()
+        .unwrap();
+
+    let mut golf = make_vehicle(user.id, "B-RG 42");
+    golf.make = Some("Volkswagen".to_string());
+    golf.model = Some("Golf".to_string());
+    golf.color = Some("Red".to_string());
+    let mut red_tuesday = make_booking(user.id, lot_id, &golf);
+    red_tuesday.start_time = tuesday;
+    let mut red_friday = make_booking(user.id, lot_id, &golf);
+    red_friday.start_time = tuesday + chrono::Duration::days(3);
+    let mut white_tuesday = make_booking(user.id, lot_id, &make_vehicle(user.id, "B-WT 7"));
+    white_tuesday.start_time = tuesday;
+    for booking in [&red_tuesday, &red_friday, &white_tuesday] {
+        db.save_booking(booking).await.unwrap();
+    }
+
+    let hits = db
+        .search("red golf tuesday", &[SearchKind::Booking], 10, true)
+        .await
+        .unwrap();
+    assert_eq!(hits.len(), 3);
+    assert_eq!(hits[0].id, red_tuesday.id.to_string());
+    assert_eq!(hits[0].matched_terms, 3);
+    assert_eq!(hits[1].id, red_friday.id.to_string());
+    assert_eq!(hits[1].matched_terms, 2);
+    assert_eq!(hits[2].id, white_tuesday.id.to_string());
+
+    // Booking ID, type filter and limit
+    let hits = db
+        .search(&red_friday.id.to_string(), &[], 10, true)
+        .await
+        .unwrap();
+    assert_eq!(hits[0].id, red_friday.id.to_string());
+    assert!(
+        db.search("golf", &[SearchKind::Lot], 10, true)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(db.search("golf", &[], 1, true).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_search_index_follows_updates_and_deletes() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let mut lot = make_parking_lot();
+    lot.name = "Harbour Garage".to_string();
+    db.save_parking_lot(&lot).await.unwrap();
+    assert_eq!(db.search("harbour", &[], 10, true).await.unwrap().len(), 1);
+
+    lot.name = "Station Garage".to_string();
+    db.save_parking_lot(&lot).await.unwrap();
+    assert!(
+        db.search("harbour", &[], 10, true)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert_eq!(db.search("station", &[], 10, true).await.unwrap().len(), 1);
+
+    let user = make_user("dave", "dave@example.com");
+    db.save_user(&user).await.unwrap();
+    let vehicle = make_vehicle(user.id, "HH-DV 9");
+    db.save_vehicle(&vehicle).await.unwrap();
+    db.delete_vehicle(&vehicle.id.to_string()).await.unwrap();
+    assert!(db.search("hhdv9", &[], 10, true).await.unwrap().is_empty());
+
+    db.delete_parking_lot(&lot.id.to_string()).await.unwrap();
+    db.delete_user(&user.id.to_string()).await.unwrap();
+    assert!(
+        db.search("station", &[], 10, true)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(db.search("dave", &[], 10, true).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_search_index_built_on_open() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let user = make_user("erin", "erin@example.com");
+    {
+        let db = Database::open(&config).unwrap();
+        db.save_user(&user).await.unwrap();
+
+        // Simulate a database written before the index existed
+        let inner = db.inner.write().await;
+        let write_txn = inner.begin_write().unwrap();
+        write_txn.delete_table(SEARCH_INDEX).unwrap();
+        write_txn.delete_table(SEARCH_DOCS).unwrap();
+        write_txn.commit().unwrap();
+    }
+
+    let db = Database::open(&config).unwrap();
+    let hits = db.search("erin", &[], 10, true).await.unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].id, user.id.to_string());
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// LIST ORDER INDEXES
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn ids(page: &Page<Booking>) -> Vec<Uuid> {
+    page.items.iter().map(|b| b.id).collect()
+}
+
+#[tokio::test]
+async fn test_booking_pages_walk_start_order() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let db = Database::open(&config).unwrap();
+
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let vehicle = make_vehicle(alice, "PG-001");
+    let now = Utc::now();
+    let mut bookings = Vec::new();
+    for (user_id, hours) in [(alice, 3), (bob, 1), (alice, 2), (alice, 5)] {
+        let mut booking = make_booking(user_id, Uuid::new_v4(), &vehicle);
+        booking.start_time = now + chrono::Duration::hours(hours);
+        db.save_booking(&booking).await.unwrap();
+        bookings.push(booking);
+    }
+    let (a3, b1, a2, a5) = (
+        bookings[0].id,
+        bookings[1].id,
+        bookings[2].id,
+        bookings[3].id,
+    );
+    let none = None::<fn(&Booking) -> bool>;
+
+    // Cursor paging, both directions
+    let first = db
+        .list_bookings_page(
+            none,
+            SortBy::Default,
+            &PageQuery::new(1, 3, None, false).unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&first), [b1, a2, a3]);
+    assert_eq!(first.total, 4);
+    let cursor = first.next_cursor.unwrap();
+    let rest = db
+        .list_bookings_page(
+            none,
+            SortBy::Default,
+            &PageQuery::new(1, 3, Some(&cursor), false).unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&rest), [a5]);
+    assert!(rest.next_cursor.is_none());
+    let back = db
+        .list_bookings_page(
+            none,
+            SortBy::Default,
+            &PageQuery::new(1, 2, Some(&cursor), true).unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&back), [a2, b1]);
+
+    // A user's bookings, filtered and offset
+    let page = db
+        .list_bookings_by_user_page(
+            &alice.to_string(),
+            Some(|b: &Booking| b.id != a3),
+            SortBy::Default,
+            &PageQuery::new(2, 1, None, true).unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(ids(&page), [a2]);
+    assert_eq!(page.total, 2);
+
+    // Rescheduling moves a booking; deleting drops it
+    let mut moved = bookings[3].clone();
+    moved.start_time = now;
+    db.save_booking(&moved).await.unwrap();
+    db.delete_booking(&b1.to_string()).await.unwrap();
+    let all = db
+        .list_bookings_page(none, SortBy::Default, &PageQuery::all(false))
+        .await
+        .unwrap();
+    assert_eq!(ids(&all), [a5, a2, a3]);
+    assert_eq!(all.total, 3);
+}
+
+#[tokio::test]
+async fn test_list_order_built_on_open() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let mut lot = make_parking_lot();
+    lot.name = "Zeppelin".to_string();
+    let user = make_user("frank", "frank@example.com");
+    {
+        let db = Database::open(&config).unwrap();
+        db.save_parking_lot(&lot).await.unwrap();
+        db.save_user(&user).await.unwrap();
+
+        // Simulate a database written before the order indexes existed
+        let inner = db.inner.write().await;
+        let write_txn = inner.begin_write().unwrap();
+        write_txn.delete_table(LOTS_BY_NAME).unwrap();
+        write_txn.delete_table(USERS_BY_CREATED).unwrap();
+        write_txn.delete_table(LIST_ORDER_KEYS).unwrap();
+        write_txn.commit().unwrap();
+    }
+
+    let db = Database::open(&config).unwrap();
+    let mut other = make_parking_lot();
+    other.name = "airport".to_string();
+    db.save_parking_lot(&other).await.unwrap();
+    let lots = db
+        .list_parking_lots_page(
+            None::<fn(&ParkingLot) -> bool>,
+            SortBy::Default,
+            &PageQuery::all(false),
+        )
+        .await
+        .unwrap();
+    let names: Vec<&str> = lots.items.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, ["airport", "Zeppelin"]);
+    let users = db
+        .list_users_page(
+            None::<fn(&User) -> bool>,
+            SortBy::Default,
+            &PageQuery::all(false),
+        )
+        .await
+        .unwrap();
+    assert_eq!(users.items.len(), 1);
+    assert_eq!(users.items[0].id, user.id);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// INVOICE ARCHIVE
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn make_archived_invoice(user_id: Uuid, number: &str) -> ArchivedInvoice {
+    let now = Utc::now();
+    ArchivedInvoice {
+        invoice_number: number.to_string(),
+        booking_id: Uuid::new_v4(),
+        user_id,
+        issued_at: now,
+        details: InvoiceDetails {
+            company: "ParkHub".to_string(),
+            customer_name: "Alice".to_string(),
+            customer_email: "alice@example.com".to_string(),
+            lot_name: "Test Lot".to_string(),
+            slot_number: 1,
+            floor_name: "Ground".to_string(),
+            license_plate: "M-AB 1234".to_string(),
+            invoice_date: now,
+            start_time: now,
+            end_time: now + chrono::Duration::hours(1),
+            status: "Confirmed".to_string(),
+            currency: "EUR".to_string(),
+            net: 2.0,
+            vat: 0.38,
+            gross: 2.38,
+            vat_rate: 0.19,
+            reverse_charge: false,
+        },
+        html: format!("<html>{number}</html>"),
+        pdf_base64: None,
+    }
+}
+
+#[tokio::test]
+async fn test_invoice_archive_keeps_first_issue() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), true)).unwrap();
+    let user_id = Uuid::new_v4();
+
+    let first = make_archived_invoice(user_id, "2026-0000001");
+    db.archive_invoice(&first).await.unwrap();
+
+    let mut reissued = first.clone();
+    reissued.html = "<html>changed</html>".to_string();
+    reissued.details.gross = 99.0;
+    let stored = db.archive_invoice(&reissued).await.unwrap();
+    assert_eq!(stored.html, first.html);
+    assert!((stored.details.gross - 2.38).abs() < f64::EPSILON);
+
+    // A missing PDF is filled in once, never replaced
+    let with_pdf = db
+        .archive_invoice_pdf("2026-0000001", "cGRm".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(with_pdf.pdf_base64.as_deref(), Some("cGRm"));
+    let unchanged = db
+        .archive_invoice_pdf("2026-0000001", "b3RoZXI=".to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(unchanged.pdf_base64.as_deref(), Some("cGRm"));
+    assert!(
+        db.archive_invoice_pdf("2026-9999999", String::new())
+            .await
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_list_archived_invoices_by_user() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let user_id = Uuid::new_v4();
+
+    let mut older = make_archived_invoice(user_id, "2026-0000001");
+    older.issued_at -= chrono::Duration::days(1);
+    let newer = make_archived_invoice(user_id, "2026-0000002");
+    let foreign = make_archived_invoice(Uuid::new_v4(), "2026-0000003");
+    for invoice in [&older, &newer, &foreign] {
+        db.archive_invoice(invoice).await.unwrap();
+    }
+
+    let listed = db.list_archived_invoices_by_user(user_id).await.unwrap();
+    let numbers: Vec<&str> = listed.iter().map(|i| i.invoice_number.as_str()).collect();
+    assert_eq!(numbers, ["2026-0000002", "2026-0000001"]);
+    assert!(
+        db.get_archived_invoice("2026-0000003")
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// LATE-BINDING SLOT ASSIGNMENT
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn make_unassigned_booking(
+    lot_id: Uuid,
+    vehicle: &Vehicle,
+    start: chrono::DateTime<Utc>,
+    hours: i64,
+) -> Booking {
+    let mut booking = make_booking(vehicle.user_id, lot_id, vehicle);
+    booking.slot_id = Uuid::nil();
+    booking.slot_number = 0;
+    booking.floor_name = String::new();
+    booking.start_time = start;
+    booking.end_time = start + chrono::Duration::hours(hours);
+    booking
+}
+
+#[tokio::test]
+async fn test_claim_slot_type_enforces_capacity() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let lot_id = Uuid::new_v4();
+    let floor_id = Uuid::new_v4();
+    let slots = [
+        make_slot(lot_id, floor_id, 1),
+        make_slot(lot_id, floor_id, 2),
+    ];
+    let slot_ids: Vec<Uuid> = slots.iter().map(|s| s.id).collect();
+    let vehicle = make_vehicle(Uuid::new_v4(), "M-LB 1");
+    let nine = "2026-03-02T09:00:00Z"
+        .parse::<chrono::DateTime<Utc>>()
+        .unwrap();
+
+    // One slot taken by a fixed booking, one by a pending late-binding one
+    let mut fixed = make_booking(vehicle.user_id, lot_id, &vehicle);
+    fixed.slot_id = slots[0].id;
+    fixed.start_time = nine;
+    fixed.end_time = nine + chrono::Duration::hours(3);
+    db.save_booking(&fixed).await.unwrap();
+    let first = make_unassigned_booking(lot_id, &vehicle, nine, 2);
+    assert!(
+        db.claim_slot_type(&first, &SlotType::Standard, &slot_ids)
+            .await
+            .unwrap()
+    );
+
+    let full = make_unassigned_booking(lot_id, &vehicle, nine + chrono::Duration::hours(1), 1);
+    assert!(
+        !db.claim_slot_type(&full, &SlotType::Standard, &slot_ids)
+            .await
+            .unwrap()
+    );
+    assert!(
+        db.get_booking(&full.id.to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    // Back-to-back with the pending booking fits; other types do not compete
+    let later = make_unassigned_booking(lot_id, &vehicle, nine + chrono::Duration::hours(2), 1);
+    assert!(
+        db.claim_slot_type(&later, &SlotType::Standard, &slot_ids)
+            .await
+            .unwrap()
+    );
+    let electric = make_unassigned_booking(lot_id, &vehicle, nine, 1);
+    assert!(
+        db.claim_slot_type(&electric, &SlotType::Electric, &[Uuid::new_v4()])
+            .await
+            .unwrap()
+    );
+
+    // A cancelled pending booking frees its capacity
+    let mut cancelled = first.clone();
+    cancelled.status = parkhub_common::models::BookingStatus::Cancelled;
+    db.save_booking(&cancelled).await.unwrap();
+    assert!(
+        db.claim_slot_type(&full, &SlotType::Standard, &slot_ids)
+            .await
+            .unwrap()
+    );
+    assert_eq!(db.list_pending_assignments().await.unwrap().len(), 4);
+}
+
+#[tokio::test]
+async fn test_assign_pending_slot_picks_tightest_fit() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), true)).unwrap();
+    let lot_id = Uuid::new_v4();
+    let floor_id = Uuid::new_v4();
+    let slots = [
+        make_slot(lot_id, floor_id, 1),
+        make_slot(lot_id, floor_id, 2),
+    ];
+    let slot_ids: Vec<Uuid> = slots.iter().map(|s| s.id).collect();
+    let candidates: Vec<(ParkingSlot, String)> = slots
+        .iter()
+        .map(|s| (s.clone(), "Deck A".to_string()))
+        .collect();
+    let vehicle = make_vehicle(Uuid::new_v4(), "M-LB 2");
+    let nine = "2026-03-02T09:00:00Z"
+        .parse::<chrono::DateTime<Utc>>()
+        .unwrap();
+
+    // Slot 2 is busy until 09:00, so a 09:00 booking packs in right after it
+    let mut earlier = make_booking(vehicle.user_id, lot_id, &vehicle);
+    earlier.slot_id = slots[1].id;
+    earlier.start_time = nine - chrono::Duration::hours(2);
+    earlier.end_time = nine;
+    db.save_booking(&earlier).await.unwrap();
+
+    let booking = make_unassigned_booking(lot_id, &vehicle, nine, 2);
+    assert!(
+        db.claim_slot_type(&booking, &SlotType::Standard, &slot_ids)
+            .await
+            .unwrap()
+    );
+    let SlotAssignment::Assigned(assigned) = db
+        .assign_pending_slot(booking.id, &candidates)
+        .await
+        .unwrap()
+    else {
+        panic!("booking was not assigned");
+    };
+    assert_eq!(assigned.slot_id, slots[1].id);
+    assert_eq!(assigned.slot_number, 2);
+    assert_eq!(assigned.floor_name, "Deck A");
+    let stored = db
+        .get_booking(&booking.id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.slot_id, slots[1].id);
+    assert!(
+        db.get_pending_assignment(booking.id)
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(matches!(
+        db.assign_pending_slot(booking.id, &candidates)
+            .await
+            .unwrap(),
+        SlotAssignment::NotPending
+    ));
+
+    // An overlapping booking cannot have slot 2, but gets slot 1
+    let second = make_unassigned_booking(lot_id, &vehicle, nine, 1);
+    assert!(
+        db.claim_slot_type(&second, &SlotType::Standard, &slot_ids)
+            .await
+            .unwrap()
+    );
+    assert!(matches!(
+        db.assign_pending_slot(second.id, &candidates[1..])
+            .await
+            .unwrap(),
+        SlotAssignment::NoSlotFree
+    ));
+    assert!(
+        db.get_pending_assignment(second.id)
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(matches!(
+        db.assign_pending_slot(second.id, &candidates).await.unwrap(),
+        SlotAssignment::Assigned(ref b) if b.slot_id == slots[0].id
+    ));
+}
+
+#[tokio::test]
+async fn test_roles_and_memberships() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let db = Database::open(&config).unwrap();
+
+    let lot_id = Uuid::new_v4();
+    let role = parkhub_common::Role {
+        id: Uuid::new_v4(),
+        name: "North garage staff".to_string(),
+        description: None,
+        permissions: vec![parkhub_common::Permission::ManageBookings],
+        lot_ids: vec![lot_id],
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    };
+    db.save_role(&role).await.unwrap();
+    assert_eq!(db.get_role(role.id).await.unwrap(), Some(role.clone()));
+    assert_eq!(db.list_roles().await.unwrap().len(), 1);
+
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    assert!(db.assign_role(alice, role.id).await.unwrap());
+    assert!(!db.assign_role(alice, role.id).await.unwrap());
+    assert!(db.assign_role(bob, role.id).await.unwrap());
+    assert_eq!(
+        db.list_roles_for_user(alice).await.unwrap(),
+        vec![role.clone()]
+    );
+    assert_eq!(db.list_role_members(role.id).await.unwrap().len(), 2);
+
+    assert!(db.unassign_role(bob, role.id).await.unwrap());
+    assert!(!db.unassign_role(bob, role.id).await.unwrap());
+    assert!(db.list_roles_for_user(bob).await.unwrap().is_empty());
+
+    // Deleting the role takes it away from its remaining members
+    assert!(db.delete_role(role.id).await.unwrap());
+    assert!(!db.delete_role(role.id).await.unwrap());
+    assert!(db.list_roles_for_user(alice).await.unwrap().is_empty());
+    assert!(db.list_role_members(role.id).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_lot_manager_assignments() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let db = Database::open(&config).unwrap();
+
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let (lot_a, lot_b) = (Uuid::new_v4(), Uuid::new_v4());
+    assert!(db.assign_lot_manager(alice, lot_a).await.unwrap());
+    assert!(!db.assign_lot_manager(alice, lot_a).await.unwrap());
+    assert!(db.assign_lot_manager(alice, lot_b).await.unwrap());
+    assert!(db.assign_lot_manager(bob, lot_b).await.unwrap());
+
+    let mut managed = db.list_managed_lots(alice).await.unwrap();
+    managed.sort();
+    let mut expected = vec![lot_a, lot_b];
+    expected.sort();
+    assert_eq!(managed, expected);
+    assert_eq!(db.list_lot_managers(lot_b).await.unwrap().len(), 2);
+    assert!(
+        db.list_managed_lots(Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    assert!(db.unassign_lot_manager(alice, lot_a).await.unwrap());
+    assert!(!db.unassign_lot_manager(alice, lot_a).await.unwrap());
+    assert_eq!(db.list_managed_lots(alice).await.unwrap(), vec![lot_b]);
+
+    // Deleting the lot drops its managers; deleting a user drops theirs
+    db.delete_parking_lot(&lot_b.to_string()).await.unwrap();
+    assert!(db.list_lot_managers(lot_b).await.unwrap().is_empty());
+    assert!(db.list_managed_lots(bob).await.unwrap().is_empty());
+
+    assert!(db.assign_lot_manager(bob, lot_a).await.unwrap());
+    db.delete_lot_manager_assignments_for_user(&bob.to_string())
+        .await
+        .unwrap();
+    assert!(db.list_managed_lots(bob).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_legacy_lot_managers_migrated_on_open() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let (alice, lot_id) = (Uuid::new_v4(), Uuid::new_v4());
+    {
+        // Databases written before the table kept one JSON document
+        let db = Database::open(&config).unwrap();
+        db.set_setting("lot_managers", &format!(r#"{{"{lot_id}":["{alice}"]}}"#))
+            .await
+            .unwrap();
+    }
+
+    let db = Database::open(&config).unwrap();
+    assert_eq!(db.list_managed_lots(alice).await.unwrap(), vec![lot_id]);
+    assert_eq!(db.list_lot_managers(lot_id).await.unwrap(), vec![alice]);
+    assert_eq!(db.get_setting("lot_managers").await.unwrap(), None);
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// WRITE BATCHES
+// ═══════════════════════════════════════════════════════════════════════════
+
+fn make_credit_deduction(
+    user_id: Uuid,
+    booking_id: Uuid,
+) -> parkhub_common::models::CreditTransaction {
+    parkhub_common::models::CreditTransaction {
+        id: Uuid::new_v4(),
+        user_id,
+        booking_id: Some(booking_id),
+        amount: -1,
+        transaction_type: parkhub_common::models::CreditTransactionType::Deduction,
+        description: None,
+        granted_by: None,
+        created_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_apply_batch_commits_booking_slot_and_credits_together() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let lot_id = Uuid::new_v4();
+    let mut slot = make_slot(lot_id, Uuid::new_v4(), 1);
+    db.save_parking_slot(&slot).await.unwrap();
+    let mut user = make_user("batch", "batch@example.com");
+    user.credits_balance = 5;
+    db.save_user(&user).await.unwrap();
+    let vehicle = make_vehicle(user.id, "M-WB 1");
+    let mut booking = make_booking(user.id, lot_id, &vehicle);
+    booking.slot_id = slot.id;
+
+    slot.status = SlotStatus::Reserved;
+    user.credits_balance = 4;
+    let mut batch = WriteBatch::new();
+    batch
+        .claim_slot(&booking)
+        .save_parking_slot(&slot, &SlotChangeContext::user(user.id, "booking"))
+        .save_user(&user)
+        .save_credit_transaction(&make_credit_deduction(user.id, booking.id));
+    assert!(db.apply_batch(&batch).await.unwrap());
+
+    assert!(
+        db.get_booking(&booking.id.to_string())
+            .await
+            .unwrap()
+            .is_some()
+    );
+    let stored_slot = db
+        .get_parking_slot(&slot.id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored_slot.status, SlotStatus::Reserved);
+    let stored_user = db.get_user(&user.id.to_string()).await.unwrap().unwrap();
+    assert_eq!(stored_user.credits_balance, 4);
+    assert_eq!(
+        db.list_credit_transactions_for_user(user.id)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_apply_batch_writes_nothing_when_a_claim_fails() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let lot_id = Uuid::new_v4();
+    let mut slot = make_slot(lot_id, Uuid::new_v4(), 1);
+    db.save_parking_slot(&slot).await.unwrap();
+    let mut user = make_user("batchfail", "batchfail@example.com");
+    user.credits_balance = 5;
+    db.save_user(&user).await.unwrap();
+    let vehicle = make_vehicle(user.id, "M-WB 2");
+    let mut taken = make_booking(user.id, lot_id, &vehicle);
+    taken.slot_id = slot.id;
+    assert!(db.claim_slot(&taken).await.unwrap());
+
+    // The credit writes come first but are rolled back with the claim
+    let mut booking = make_booking(user.id, lot_id, &vehicle);
+    booking.slot_id = slot.id;
+    slot.status = SlotStatus::Reserved;
+    user.credits_balance = 4;
+    let mut batch = WriteBatch::new();
+    batch
+        .save_user(&user)
+        .save_credit_transaction(&make_credit_deduction(user.id, booking.id))
+        .claim_slot(&booking)
+        .save_parking_slot(&slot, &SlotChangeContext::user(user.id, "booking"));
+    assert!(!db.apply_batch(&batch).await.unwrap());
+
+    assert!(
+        db.get_booking(&booking.id.to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let stored_slot = db
+        .get_parking_slot(&slot.id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored_slot.status, SlotStatus::Available);
+    let stored_user = db.get_user(&user.id.to_string()).await.unwrap().unwrap();
+    assert_eq!(stored_user.credits_balance, 5);
+    assert!(
+        db.list_credit_transactions_for_user(user.id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_delete_parking_lot_removes_its_slots() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let lot = make_parking_lot();
+    let other = make_parking_lot();
+    db.save_parking_lot(&lot).await.unwrap();
+    db.save_parking_lot(&other).await.unwrap();
+    let floor = Uuid::new_v4();
+    let slots = [make_slot(lot.id, floor, 1), make_slot(lot.id, floor, 2)];
+    let kept = make_slot(other.id, floor, 1);
+    db.save_parking_slots_batch(&slots).await.unwrap();
+    db.save_parking_slot(&kept).await.unwrap();
+
+    assert!(db.delete_parking_lot(&lot.id.to_string()).await.unwrap());
+    assert!(
+        db.get_parking_lot(&lot.id.to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        db.list_slots_by_lot(&lot.id.to_string())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    for slot in &slots {
+        assert!(
+            db.get_parking_slot(&slot.id.to_string())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+    assert_eq!(
+        db.list_slots_by_lot(&other.id.to_string())
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(!db.delete_parking_lot(&lot.id.to_string()).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_slot_writes_recount_lot_and_floors() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let mut lot = make_parking_lot();
+    let (ground, upper) = (Uuid::new_v4(), Uuid::new_v4());
+    lot.floors = [ground, upper]
+        .into_iter()
+        .enumerate()
+        .map(|(i, id)| parkhub_common::models::ParkingFloor {
+            id,
+            lot_id: lot.id,
+            name: format!("Level {i}"),
+            floor_number: i32::try_from(i).unwrap(),
+            total_slots: 0,
+            available_slots: 0,
+            slots: vec![],
+        })
+        .collect();
+    db.save_parking_lot(&lot).await.unwrap();
+
+    let first = make_slot(lot.id, ground, 1);
+    let mut second = make_slot(lot.id, upper, 2);
+    second.status = SlotStatus::Maintenance;
+    let mut batch = WriteBatch::new();
+    batch
+        .save_parking_slot(&first, &SlotChangeContext::system("test"))
+        .save_parking_slot(&second, &SlotChangeContext::system("test"))
+        .recount_lot_slots(lot.id);
+    assert!(db.apply_batch(&batch).await.unwrap());
+
+    let stored = db
+        .get_parking_lot(&lot.id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((stored.total_slots, stored.available_slots), (2, 1));
+    let floor_counts: Vec<_> = stored
+        .floors
+        .iter()
+        .map(|f| (f.total_slots, f.available_slots))
+        .collect();
+    assert_eq!(floor_counts, [(1, 1), (1, 0)]);
+
+    // Deleting a slot recounts too
+    assert!(db.delete_parking_slot(&first.id.to_string()).await.unwrap());
+    let stored = db
+        .get_parking_lot(&lot.id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!((stored.total_slots, stored.available_slots), (1, 0));
+    assert_eq!(
+        (stored.floors[0].total_slots, stored.floors[1].total_slots),
+        (0, 1)
+    );
+}
+
+#[tokio::test]
+async fn test_save_lot_floor_adds_and_renames_with_counts() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let lot = make_parking_lot();
+    db.save_parking_lot(&lot).await.unwrap();
+
+    let mut floor = parkhub_common::models::ParkingFloor {
+        id: Uuid::new_v4(),
+        lot_id: lot.id,
+        name: "Upper Deck".to_string(),
+        floor_number: 2,
+        total_slots: 0,
+        available_slots: 0,
+        slots: vec![],
+    };
+    db.save_parking_slot(&make_slot(lot.id, floor.id, 1))
+        .await
+        .unwrap();
+    let stored = db.save_lot_floor(&floor).await.unwrap().unwrap();
+    assert_eq!(stored.floors.len(), 1);
+    assert_eq!(
+        (
+            stored.floors[0].total_slots,
+            stored.floors[0].available_slots
+        ),
+        (1, 1)
+    );
+
+    // Saving a floor with the same id replaces it
+    floor.name = "Roof".to_string();
+    let stored = db.save_lot_floor(&floor).await.unwrap().unwrap();
+    assert_eq!(stored.floors.len(), 1);
+    assert_eq!(stored.floors[0].name, "Roof");
+    assert_eq!(stored.floors[0].total_slots, 1);
+
+    floor.lot_id = Uuid::new_v4();
+    assert!(db.save_lot_floor(&floor).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_visitor_passes_are_limited_by_their_day_block() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), false)).unwrap();
+    let lot_id = Uuid::new_v4();
+    let day = chrono::NaiveDate::from_ymd_opt(2026, 5, 4).unwrap();
+    let next_day = day.succ_opt().unwrap();
+    let pass = |date| VisitorPass {
+        id: Uuid::new_v4(),
+        lot_id,
+        date,
+        visitor_name: "Visitor".to_string(),
+        license_plate: "M-VP 1".to_string(),
+        issued_by: Uuid::new_v4(),
+        issued_at: Utc::now(),
+        status: VisitorPassStatus::Issued,
+        booking_id: None,
+        slot_id: None,
+        slot_number: None,
+        checked_in_at: None,
+    };
+
+    // No block for the day: nothing can be issued
+    assert!(!db.issue_visitor_pass(&pass(day)).await.unwrap());
+
+    let mut block = VisitorPassBlock {
+        lot_id,
+        date: day,
+        passes: 2,
+        updated_by: Uuid::new_v4(),
+        updated_at: Utc::now(),
+    };
+    assert!(db.save_visitor_pass_block(&block).await.unwrap());
+    let mut first = pass(day);
+    assert!(db.issue_visitor_pass(&first).await.unwrap());
+    assert!(db.issue_visitor_pass(&pass(day)).await.unwrap());
+    assert!(!db.issue_visitor_pass(&pass(day)).await.unwrap());
+    assert!(!db.issue_visitor_pass(&pass(next_day)).await.unwrap());
+
+    // The block cannot shrink below the issued passes
+    block.passes = 1;
+    assert!(!db.save_visitor_pass_block(&block).await.unwrap());
+
+    // A cancelled pass gives its place back
+    first.status = VisitorPassStatus::Cancelled;
+    db.save_visitor_pass(&first).await.unwrap();
+    assert!(db.save_visitor_pass_block(&block).await.unwrap());
+    let stored = db
+        .get_visitor_pass(&first.id.to_string())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.status, VisitorPassStatus::Cancelled);
+
+    let passes = db.list_visitor_passes(lot_id, day, next_day).await.unwrap();
+    assert_eq!(passes.len(), 2);
+    let blocks = db
+        .list_visitor_pass_blocks(lot_id, day, next_day)
+        .await
+        .unwrap();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].passes, 1);
+}
+
+#[tokio::test]
+async fn test_backup_restores_data_but_keeps_server_secrets() {
+    let dir = tempdir().unwrap();
+    let source = Database::open(&test_config(dir.path().join("source"), false)).unwrap();
+    let target = Database::open(&test_config(dir.path().join("target"), false)).unwrap();
+    source.set_setting("company_name", "Before").await.unwrap();
+    target.set_setting("company_name", "Other").await.unwrap();
+    let target_secret = target.get_setting(SETTING_TOKEN_SECRET).await.unwrap();
+    let owner = make_user("owner", "owner@example.com");
+    source.save_user(&owner).await.unwrap();
+    let session = Session::new(owner.id, 8, "owner", "user");
+    source
+        .save_session("source-session", &session)
+        .await
+        .unwrap();
+    target
+        .save_session("target-session", &session)
+        .await
+        .unwrap();
+
+    let path = source.backup_dir().join("snapshot.redb");
+    source.backup(&path).await.unwrap();
+    source.set_setting("company_name", "After").await.unwrap();
+    assert!(source.backup(&path).await.is_err(), "never overwrites");
+
+    assert!(target.restore(&path).await.unwrap());
+    assert_eq!(
+        target.get_setting("company_name").await.unwrap().as_deref(),
+        Some("Before")
+    );
+    assert_eq!(
+        target.get_setting(SETTING_TOKEN_SECRET).await.unwrap(),
+        target_secret
+    );
+    // Sessions are neither backed up nor kept, and older tokens are revoked
+    assert!(
+        target
+            .get_session("source-session")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        target
+            .get_session("target-session")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    assert!(target.sessions_revoked_before(owner.id).is_some());
+
+    // Records of a plain database do not decrypt with a key
+    let path = source.backup_dir().join("with-user.redb");
+    source.backup(&path).await.unwrap();
+    let encrypted = Database::open(&test_config(dir.path().join("encrypted"), true)).unwrap();
+    assert!(!encrypted.restore(&path).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_slot_hold_lot_index_built_on_open() {
+    let dir = tempdir().unwrap();
+    let config = test_config(dir.path().to_path_buf(), false);
+    let now = Utc::now();
+    let hold = SlotHold {
+        slot_id: Uuid::new_v4(),
+        lot_id: Uuid::new_v4(),
+        user_id: Uuid::new_v4(),
+        created_at: now,
+        expires_at: now + chrono::Duration::minutes(2),
+    };
+    {
+        let db = Database::open(&config).unwrap();
+        db.place_slot_hold(&hold).await.unwrap();
+        assert_eq!(db.list_lot_slot_holds(hold.lot_id).await.unwrap().len(), 1);
+
+        // Simulate a database written before the lot index existed
+        let inner = db.inner.write().await;
+        let write_txn = inner.begin_write().unwrap();
+        write_txn.delete_table(SLOT_HOLDS_BY_LOT).unwrap();
+        write_txn.commit().unwrap();
+    }
+
+    let db = Database::open(&config).unwrap();
+    let holds = db.list_lot_slot_holds(hold.lot_id).await.unwrap();
+    assert_eq!(holds.len(), 1);
+    assert_eq!(holds[0].slot_id, hold.slot_id);
+    assert!(
+        db.list_lot_slot_holds(Uuid::new_v4())
+            .await
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[tokio::test]
+async fn test_restore_completes_when_caller_stops_waiting() {
+    let dir = tempdir().unwrap();
+    let source = Database::open(&test_config(dir.path().join("source"), false)).unwrap();
+    let target = Database::open(&test_config(dir.path().join("target"), false)).unwrap();
+    let owner = make_user("owner", "owner@example.com");
+    source.save_user(&owner).await.unwrap();
+    let path = source.backup_dir().join("snapshot.redb");
+    source.backup(&path).await.unwrap();
+
+    // Dropped after its first poll, as on a request timeout
+    let waited = tokio::time::timeout(std::time::Duration::ZERO, target.restore(&path)).await;
+    assert!(waited.is_err());
+
+    for _ in 0..100 {
+        if target.sessions_revoked_before(owner.id).is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    assert!(target.sessions_revoked_before(owner.id).is_some());
+    assert!(
+        target
+            .get_user(&owner.id.to_string())
+            .await
+            .unwrap()
+            .is_some()
+    );
+}
+
+#[test]
+fn test_backup_archive_needs_its_passphrase() {
+    let archive = seal_backup(b"snapshot", "correct horse battery").unwrap();
+    assert_eq!(
+        open_backup(&archive, "correct horse battery").as_deref(),
+        Some(b"snapshot".as_slice())
+    );
+    assert!(open_backup(&archive, "wrong horse battery").is_none());
+    assert!(open_backup(&archive[..20], "correct horse battery").is_none());
+    assert!(open_backup(b"snapshot", "correct horse battery").is_none());
+}
+
+#[tokio::test]
+async fn test_feature_flag_switches_survive_demo_reset() {
+    let dir = tempdir().unwrap();
+    let db = Database::open(&test_config(dir.path().to_path_buf(), true)).unwrap();
+    assert!(db.get_feature_flag("kiosk-mode").await.unwrap().is_none());
+
+    let admin = Uuid::new_v4();
+    for enabled in [false, true, false] {
+        db.save_feature_flag(&FeatureFlagSwitch {
+            key: "kiosk-mode".to_string(),
+            enabled,
+            updated_by: admin,
+            updated_at: Utc::now(),
+        })
+        .await
+        .unwrap();
+    }
+    db.clear_all_data().await.unwrap();
+
+    let switch = db.get_feature_flag("kiosk-mode").await.unwrap().unwrap();
+    assert!(!switch.enabled);
+    assert_eq!(switch.updated_by, admin);
+    assert_eq!(db.list_feature_flags().await.unwrap().len(), 1);
+}
@@ -1,23 +1,43 @@
 //! User CRUD, username/email secondary indexes, and GDPR anonymization.
 
 use anyhow::Result;
-use redb::{ReadableDatabase, ReadableTable, ReadableTableMetadata};
+use base64::{Engine, engine::general_purpose::STANDARD as BASE64};
+use redb::{ReadableDatabase, ReadableTable, WriteTransaction};
 use tracing::{debug, info};
 use uuid::Uuid;
 
 use parkhub_common::models::User;
 
-use super::{Database, USERS, USERS_BY_EMAIL, USERS_BY_USERNAME, pagination_offset};
+use super::list_order::{index_user_order, page_in_order, unindex_user_order};
+
+use super::{
+    DUMMY_USERS, Database, Page, PageQuery, SortBy, USERS, USERS_BY_CREATED, USERS_BY_EMAIL,
+    USERS_BY_USERNAME, paging,
+};
+
+/// Settings key under which a user's uploaded profile picture is stored.
+fn user_picture_key(user_id: &str) -> String {
+    format!("user_picture_{user_id}")
+}
 
 impl Database {
     /// Save a user to the database
     pub async fn save_user(&self, user: &User) -> Result<()> {
-        let id = user.id.to_string();
-        let data = self.serialize(user)?;
-
+        let _timer = Self::time_write("users", Some(&user.id.to_string()));
         let db = self.inner.write().await;
         let write_txn = db.begin_write()?;
         drop(db);
+        self.put_user(&write_txn, user)?;
+        write_txn.commit()?;
+        self.access_changed();
+        debug!("Saved user: {} ({})", user.username, user.id);
+        Ok(())
+    }
+
+    /// Write `user` and its indexes within `write_txn`.
+    pub(super) fn put_user(&self, write_txn: &WriteTransaction, user: &User) -> Result<()> {
+        let id = user.id.to_string();
+        let data = self.serialize(user)?;
         {
             let mut table = write_txn.open_table(USERS)?;
             table.insert(id.as_str(), data.as_slice())?;
@@ -30,13 +50,57 @@ impl Database {
             let mut email_idx = write_txn.open_table(USERS_BY_EMAIL)?;
             email_idx.insert(user.email.as_str(), id.as_str())?;
         }
+        index_user_order(write_txn, user)?;
+        self.index_user(write_txn, user)
+    }
+
+    /// Change a user's email address, moving the email index entry in the
+    /// same transaction so the old address stops resolving to this user.
+    pub async fn update_user_email(&self, user: &User, old_email: &str) -> Result<()> {
+        let id = user.id.to_string();
+        let _timer = Self::time_write("users", Some(&id));
+        let data = self.serialize(user)?;
+
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        {
+            let mut table = write_txn.open_table(USERS)?;
+            table.insert(id.as_str(), data.as_slice())?;
+
+            let mut email_idx = write_txn.open_table(USERS_BY_EMAIL)?;
+            let _ = email_idx.remove(old_email)?;
+            email_idx.insert(user.email.as_str(), id.as_str())?;
+        }
+        self.index_user(&write_txn, user)?;
         write_txn.commit()?;
-        debug!("Saved user: {} ({})", user.username, user.id);
+        self.access_changed();
+        debug!("Changed email of user {}", user.id);
         Ok(())
     }
 
+    /// Store a user's processed profile picture (PNG bytes).
+    pub async fn save_user_picture(&self, user_id: &str, png: &[u8]) -> Result<()> {
+        self.set_setting(&user_picture_key(user_id), &BASE64.encode(png))
+            .await
+    }
+
+    /// Load a user's stored profile picture, if any.
+    pub async fn get_user_picture(&self, user_id: &str) -> Result<Option<Vec<u8>>> {
+        match self.get_setting(&user_picture_key(user_id)).await? {
+            Some(encoded) if !encoded.is_empty() => Ok(Some(BASE64.decode(encoded)?)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Remove a user's stored profile picture.
+    pub async fn delete_user_picture(&self, user_id: &str) -> Result<()> {
+        self.set_setting(&user_picture_key(user_id), "").await
+    }
+
     /// Get a user by ID (string)
     pub async fn get_user(&self, id: &str) -> Result<Option<User>> {
+        let _timer = Self::time_read("users", Some(id));
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
@@ -50,6 +114,7 @@ impl Database {
 
     /// Get a user by username
     pub async fn get_user_by_username(&self, username: &str) -> Result<Option<User>> {
+        let _timer = Self::time_read("users_by_username", None);
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
@@ -92,6 +157,7 @@ impl Database {
 
     /// List all users
     pub async fn list_users(&self) -> Result<Vec<User>> {
+        let _timer = Self::time_read("users", None);
         let db = self.inner.read().await;
         let read_txn = db.begin_read()?;
         drop(db);
@@ -105,26 +171,38 @@ impl Database {
         Ok(users)
     }
 
-    /// List users with pagination. Returns (page_items, total_count).
-    pub async fn list_users_paginated(
+    /// The page of users selected by `query` among those `filter` accepts
+    /// (every user for `None`), ordered by `sort`. The default order, by
+    /// creation time, is read from `USERS_BY_CREATED`.
+    pub async fn list_users_page(
         &self,
-        page: i32,
-        per_page: i32,
-    ) -> Result<(Vec<User>, usize)> {
-        let db = self.inner.read().await;
-        let read_txn = db.begin_read()?;
-        drop(db);
-        let table = read_txn.open_table(USERS)?;
-
-        let total = table.len()? as usize;
-        let (skip, per_page) = pagination_offset(page, per_page);
-
-        let mut users = Vec::with_capacity(per_page.min(total.saturating_sub(skip)));
-        for entry in table.iter()?.skip(skip).take(per_page) {
-            let (_, value) = entry?;
-            users.push(self.deserialize(value.value())?);
-        }
-        Ok((users, total))
+        filter: Option<impl Fn(&User) -> bool>,
+        sort: SortBy<User>,
+        query: &PageQuery,
+    ) -> Result<Page<User>> {
+        let SortBy::Key(sort_key) = sort else {
+            let _timer = Self::time_read("users", None);
+            let db = self.inner.read().await;
+            let read_txn = db.begin_read()?;
+            drop(db);
+            return page_in_order(
+                self,
+                &read_txn,
+                USERS_BY_CREATED,
+                "",
+                USERS,
+                filter.as_ref(),
+                query,
+            );
+        };
+        let users = self.list_users().await?;
+        Ok(paging::page(
+            users,
+            |u| u.id.to_string(),
+            sort_key,
+            |u| filter.as_ref().is_none_or(|filter| filter(u)),
+            query,
+        ))
     }
 
     /// Delete a user
@@ -146,8 +224,32 @@ impl Database {
 
             let mut email_idx = write_txn.open_table(USERS_BY_EMAIL)?;
             email_idx.remove(user.email.as_str())?;
+
+            let mut dummy = write_txn.open_table(DUMMY_USERS)?;
+            dummy.remove(id)?;
         }
+        self.unindex_user(&write_txn, id)?;
+        unindex_user_order(&write_txn, id)?;
         write_txn.commit()?;
+        self.access_changed();
+        if let Err(e) = self.delete_login_history_for_user(id).await {
+            tracing::warn!("Failed to delete login history of {id}: {e}");
+        }
+        if let Err(e) = self.delete_permits_for_user(id).await {
+            tracing::warn!("Failed to delete permits of {id}: {e}");
+        }
+        if let Err(e) = self.delete_role_memberships_for_user(id).await {
+            tracing::warn!("Failed to delete role memberships of {id}: {e}");
+        }
+        if let Err(e) = self.delete_lot_manager_assignments_for_user(id).await {
+            tracing::warn!("Failed to delete lot manager assignments of {id}: {e}");
+        }
+        if let Err(e) = self.delete_passkeys_for_user(id).await {
+            tracing::warn!("Failed to delete passkeys of {id}: {e}");
+        }
+        if let Err(e) = self.delete_rectification_requests_for_user(id).await {
+            tracing::warn!("Failed to delete rectification requests of {id}: {e}");
+        }
         debug!("Deleted user: {}", id);
         Ok(true)
     }
@@ -160,38 +262,74 @@ impl Database {
             return Ok(false);
         };
 
-        let old_username = user.username.clone();
-        let old_email = user.email.clone();
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let anon_id = self.put_anonymized_user(&write_txn, &user)?;
+        write_txn.commit()?;
+        self.access_changed();
+        self.delete_anonymized_user_data(user_id).await;
+
+        info!(
+            "GDPR anonymization completed for user: {} → {}",
+            user_id, anon_id
+        );
+        Ok(true)
+    }
+
+    /// Overwrite `user` with placeholder values within `write_txn` and move
+    /// its index entries. Returns the placeholder username.
+    pub(super) fn put_anonymized_user(
+        &self,
+        write_txn: &WriteTransaction,
+        user: &User,
+    ) -> Result<String> {
+        let user_id = user.id.to_string();
         let anon_id = format!("deleted-{}", Uuid::new_v4());
         let anon_email = format!("{anon_id}@deleted.invalid");
         let anon_password = format!("DELETED_{}", Uuid::new_v4());
 
         // Anonymize user record + clean indexes atomically
-        let mut anon_user = user;
+        let mut anon_user = user.clone();
         anon_user.name = "[Deleted User]".to_string();
-        anon_user.email = anon_email.clone();
-        anon_user.username = anon_id.clone();
+        anon_user.email.clone_from(&anon_email);
+        anon_user.username.clone_from(&anon_id);
         anon_user.password_hash = anon_password;
+        anon_user.picture = None;
 
         let user_data = self.serialize(&anon_user)?;
-        let db = self.inner.write().await;
-        let write_txn = db.begin_write()?;
-        drop(db);
         {
             // Overwrite user record
             let mut table = write_txn.open_table(USERS)?;
-            table.insert(user_id, user_data.as_slice())?;
+            table.insert(user_id.as_str(), user_data.as_slice())?;
 
             // Remove stale index entries and add anonymized ones
             let mut idx = write_txn.open_table(USERS_BY_USERNAME)?;
-            let _ = idx.remove(old_username.as_str());
-            idx.insert(anon_id.as_str(), user_id)?;
+            let _ = idx.remove(user.username.as_str());
+            idx.insert(anon_id.as_str(), user_id.as_str())?;
 
             let mut email_idx = write_txn.open_table(USERS_BY_EMAIL)?;
-            let _ = email_idx.remove(old_email.as_str());
-            email_idx.insert(anon_email.as_str(), user_id)?;
+            let _ = email_idx.remove(user.email.as_str());
+            email_idx.insert(anon_email.as_str(), user_id.as_str())?;
+
+            // The shared dummy password is gone, so the account no longer
+            // counts as a dummy user
+            let mut dummy = write_txn.open_table(DUMMY_USERS)?;
+            dummy.remove(user_id.as_str())?;
+        }
+        // Drops the old name and email from the search index
+        self.index_user(write_txn, &anon_user)?;
+        Ok(anon_id)
+    }
+
+    /// Delete the personal data linked to an anonymized user: picture,
+    /// vehicles, login history, permits, rectification requests, the files
+    /// they attached to bookings and the plates on their bookings.
+    /// Failures are logged; the account itself is already anonymized.
+    pub(super) async fn delete_anonymized_user_data(&self, user_id: &str) {
+        if let Err(e) = self.delete_user_picture(user_id).await {
+            tracing::warn!("GDPR: failed to delete profile picture of {user_id}: {e}");
         }
-        write_txn.commit()?;
 
         // Delete all vehicles (personal data — can be deleted per GDPR Art. 17)
         let vehicles = self
@@ -204,22 +342,38 @@ impl Database {
             }
         }
 
+        // Login history holds IP addresses and user agents
+        if let Err(e) = self.delete_login_history_for_user(user_id).await {
+            tracing::warn!("GDPR: failed to delete login history of {user_id}: {e}");
+        }
+
+        // Permits name the holder and their plates
+        if let Err(e) = self.delete_permits_for_user(user_id).await {
+            tracing::warn!("GDPR: failed to delete permits of {user_id}: {e}");
+        }
+
+        // Rectification requests quote the old and the corrected values
+        if let Err(e) = self.delete_rectification_requests_for_user(user_id).await {
+            tracing::warn!("GDPR: failed to delete rectification requests of {user_id}: {e}");
+        }
+
+        // Booking attachments are usually photos taken by the user
+        if let Ok(uuid) = Uuid::parse_str(user_id)
+            && let Err(e) = self.delete_booking_attachments_by_uploader(uuid).await
+        {
+            tracing::warn!("GDPR: failed to delete booking attachments of {user_id}: {e}");
+        }
+
         // Scrub license plate from bookings (keep records for accounting, strip PII)
         let bookings = self
             .list_bookings_by_user(user_id)
             .await
             .unwrap_or_default();
         for mut booking in bookings {
-            booking.vehicle.license_plate = "[DELETED]".to_string();
+            booking.vehicle.license_plate = super::bookings::ANONYMIZED_PLATE.to_string();
             if let Err(e) = self.save_booking(&booking).await {
                 tracing::warn!("GDPR: failed to scrub booking {}: {e}", booking.id);
             }
         }
-
-        info!(
-            "GDPR anonymization completed for user: {} → {}",
-            user_id, anon_id
-        );
-        Ok(true)
     }
 }
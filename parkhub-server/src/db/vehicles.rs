@@ -21,6 +21,7 @@ impl Database {
             let mut table = write_txn.open_table(VEHICLES)?;
             table.insert(id.as_str(), data.as_slice())?;
         }
+        self.index_vehicle(&write_txn, vehicle)?;
         write_txn.commit()?;
         debug!("Saved vehicle: {} ({})", vehicle.license_plate, vehicle.id);
         Ok(())
@@ -77,13 +78,17 @@ impl Database {
         let db = self.inner.write().await;
         let write_txn = db.begin_write()?;
         drop(db);
-        {
+        let removed: Option<Vehicle> = {
             let mut table = write_txn.open_table(VEHICLES)?;
-            let removed = table.remove(id)?.is_some();
-            if !removed {
-                return Ok(false);
-            }
-        }
+            table
+                .remove(id)?
+                .map(|value| self.deserialize(value.value()))
+                .transpose()?
+        };
+        let Some(vehicle) = removed else {
+            return Ok(false);
+        };
+        self.unindex_vehicle(&write_txn, &vehicle)?;
         write_txn.commit()?;
         debug!("Deleted vehicle: {}", id);
         Ok(true)
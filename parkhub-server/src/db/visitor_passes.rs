@@ -0,0 +1,242 @@
+//! Daily blocks of visitor passes and the passes issued from them.
+//!
+//! `VISITOR_PASS_BLOCKS` is keyed `"{lot_id}:{date}"` and holds how many
+//! passes reception may issue for a lot on a day. `VISITOR_PASSES` is keyed
+//! `"{lot_id}:{date}:{pass_id}"`, so a lot's passes for a day or a range of
+//! days are a range scan; `VISITOR_PASS_KEYS` maps a pass ID to that key.
+//! Dates are `YYYY-MM-DD` and sort as strings.
+//!
+//! Every pass that is not cancelled counts against its day's block, whether
+//! or not the visitor has checked in yet. Issuing and shrinking a block
+//! check that count in the same write transaction.
+
+#![cfg_attr(not(feature = "mod-guest"), allow(dead_code))]
+
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use redb::{ReadableDatabase, ReadableTable, WriteTransaction};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{Database, VISITOR_PASS_BLOCKS, VISITOR_PASS_KEYS, VISITOR_PASSES};
+
+/// Number of visitor passes reception may issue for a lot on one day.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct VisitorPassBlock {
+    pub lot_id: Uuid,
+    pub date: NaiveDate,
+    pub passes: u32,
+    /// Admin who last set the block
+    pub updated_by: Uuid,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Where a visitor pass stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VisitorPassStatus {
+    /// Issued; the visitor has not arrived yet and holds no slot
+    Issued,
+    /// The visitor arrived and was given a slot
+    CheckedIn,
+    /// Withdrawn before check-in; no longer counts against the block
+    Cancelled,
+}
+
+/// A day pass issued to a visitor from a lot's block.
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct VisitorPass {
+    pub id: Uuid,
+    pub lot_id: Uuid,
+    pub date: NaiveDate,
+    pub visitor_name: String,
+    pub license_plate: String,
+    /// Reception user who issued the pass
+    pub issued_by: Uuid,
+    pub issued_at: DateTime<Utc>,
+    pub status: VisitorPassStatus,
+    /// Booking created at check-in
+    pub booking_id: Option<Uuid>,
+    /// Slot assigned at check-in
+    pub slot_id: Option<Uuid>,
+    pub slot_number: Option<i32>,
+    pub checked_in_at: Option<DateTime<Utc>>,
+}
+
+impl VisitorPass {
+    fn key(&self) -> String {
+        format!("{}:{}:{}", self.lot_id, self.date, self.id)
+    }
+
+    /// Whether the pass takes up a place in its day's block.
+    #[must_use]
+    pub fn counts_against_block(&self) -> bool {
+        self.status != VisitorPassStatus::Cancelled
+    }
+}
+
+fn block_key(lot_id: Uuid, date: NaiveDate) -> String {
+    format!("{lot_id}:{date}")
+}
+
+/// Key range covering a lot's passes from `from` to `to`, both inclusive
+/// (`;` sorts right after `:`).
+fn passes_range(lot_id: Uuid, from: NaiveDate, to: NaiveDate) -> (String, String) {
+    (format!("{lot_id}:{from}:"), format!("{lot_id}:{to};"))
+}
+
+impl Database {
+    /// The blocks of lot `lot_id` from `from` to `to`, both inclusive,
+    /// ordered by date.
+    pub async fn list_visitor_pass_blocks(
+        &self,
+        lot_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<VisitorPassBlock>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(VISITOR_PASS_BLOCKS)?;
+
+        let start = block_key(lot_id, from);
+        let end = block_key(lot_id, to);
+        let mut blocks = Vec::new();
+        for entry in table.range(start.as_str()..=end.as_str())? {
+            let (_, value) = entry?;
+            blocks.push(self.deserialize(value.value())?);
+        }
+        Ok(blocks)
+    }
+
+    /// Store `block`, replacing the lot's earlier block for the day.
+    ///
+    /// Returns `false` without writing anything if more passes than
+    /// `block.passes` are already issued for the day.
+    pub async fn save_visitor_pass_block(&self, block: &VisitorPassBlock) -> Result<bool> {
+        let data = self.serialize(block)?;
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        if self.count_visitor_passes(&write_txn, block.lot_id, block.date)? > block.passes {
+            return Ok(false);
+        }
+        write_txn.open_table(VISITOR_PASS_BLOCKS)?.insert(
+            block_key(block.lot_id, block.date).as_str(),
+            data.as_slice(),
+        )?;
+        write_txn.commit()?;
+        Ok(true)
+    }
+
+    /// Store the new `pass` if its day's block has a place left.
+    ///
+    /// Returns `false` without writing anything if the lot has no block for
+    /// the day or all of its passes are issued.
+    pub async fn issue_visitor_pass(&self, pass: &VisitorPass) -> Result<bool> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        let passes = match write_txn
+            .open_table(VISITOR_PASS_BLOCKS)?
+            .get(block_key(pass.lot_id, pass.date).as_str())?
+        {
+            Some(value) => self.deserialize::<VisitorPassBlock>(value.value())?.passes,
+            None => 0,
+        };
+        if self.count_visitor_passes(&write_txn, pass.lot_id, pass.date)? >= passes {
+            return Ok(false);
+        }
+        self.put_visitor_pass(&write_txn, pass)?;
+        write_txn.commit()?;
+        Ok(true)
+    }
+
+    /// Passes of lot `lot_id` on `date` that count against its block.
+    fn count_visitor_passes(
+        &self,
+        write_txn: &WriteTransaction,
+        lot_id: Uuid,
+        date: NaiveDate,
+    ) -> Result<u32> {
+        let table = write_txn.open_table(VISITOR_PASSES)?;
+        let (start, end) = passes_range(lot_id, date, date);
+        let mut count = 0;
+        for entry in table.range(start.as_str()..end.as_str())? {
+            let (_, value) = entry?;
+            if self
+                .deserialize::<VisitorPass>(value.value())?
+                .counts_against_block()
+            {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Save `pass`, replacing its earlier version.
+    pub async fn save_visitor_pass(&self, pass: &VisitorPass) -> Result<()> {
+        let db = self.inner.write().await;
+        let write_txn = db.begin_write()?;
+        drop(db);
+        self.put_visitor_pass(&write_txn, pass)?;
+        write_txn.commit()?;
+        Ok(())
+    }
+
+    /// Write `pass` within `write_txn`.
+    pub(super) fn put_visitor_pass(
+        &self,
+        write_txn: &WriteTransaction,
+        pass: &VisitorPass,
+    ) -> Result<()> {
+        let key = pass.key();
+        let data = self.serialize(pass)?;
+        write_txn
+            .open_table(VISITOR_PASSES)?
+            .insert(key.as_str(), data.as_slice())?;
+        write_txn
+            .open_table(VISITOR_PASS_KEYS)?
+            .insert(pass.id.to_string().as_str(), key.as_str())?;
+        Ok(())
+    }
+
+    /// Get a visitor pass by ID.
+    pub async fn get_visitor_pass(&self, id: &str) -> Result<Option<VisitorPass>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let keys = read_txn.open_table(VISITOR_PASS_KEYS)?;
+        let Some(key) = keys.get(id)? else {
+            return Ok(None);
+        };
+        let table = read_txn.open_table(VISITOR_PASSES)?;
+        match table.get(key.value())? {
+            Some(value) => Ok(Some(self.deserialize(value.value())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The passes of lot `lot_id` from `from` to `to`, both inclusive,
+    /// ordered by date and then by issue time.
+    pub async fn list_visitor_passes(
+        &self,
+        lot_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<VisitorPass>> {
+        let db = self.inner.read().await;
+        let read_txn = db.begin_read()?;
+        drop(db);
+        let table = read_txn.open_table(VISITOR_PASSES)?;
+
+        let (start, end) = passes_range(lot_id, from, to);
+        let mut passes: Vec<VisitorPass> = Vec::new();
+        for entry in table.range(start.as_str()..end.as_str())? {
+            let (_, value) = entry?;
+            passes.push(self.deserialize(value.value())?);
+        }
+        passes.sort_by_key(|p| (p.date, p.issued_at));
+        Ok(passes)
+    }
+}
@@ -19,14 +19,7 @@ impl MdnsService {
     pub fn new(config: &ServerConfig) -> Result<Self> {
         let daemon = ServiceDaemon::new()?;
 
-        // Build service properties
-        let mut properties = HashMap::new();
-        properties.insert("version".to_string(), env!("CARGO_PKG_VERSION").to_string());
-        properties.insert(
-            "protocol".to_string(),
-            parkhub_common::PROTOCOL_VERSION.to_string(),
-        );
-        properties.insert("tls".to_string(), config.enable_tls.to_string());
+        let properties = service_properties(config);
 
         // Get hostname
         let hostname = hostname::get().map_or_else(
@@ -63,6 +56,27 @@ impl MdnsService {
     }
 }
 
+/// TXT record of the service. Clients group servers by `org` and fail over
+/// from the primary to a server with `role=standby`.
+fn service_properties(config: &ServerConfig) -> HashMap<String, String> {
+    let mut properties = HashMap::new();
+    properties.insert("version".to_string(), env!("CARGO_PKG_VERSION").to_string());
+    properties.insert(
+        "protocol".to_string(),
+        parkhub_common::PROTOCOL_VERSION.to_string(),
+    );
+    properties.insert("tls".to_string(), config.enable_tls.to_string());
+    if !config.organization_name.trim().is_empty() {
+        properties.insert(
+            "org".to_string(),
+            config.organization_name.trim().to_string(),
+        );
+    }
+    let role = if config.standby { "standby" } else { "primary" };
+    properties.insert("role".to_string(), role.to_string());
+    properties
+}
+
 impl Drop for MdnsService {
     fn drop(&mut self) {
         let _ = self.unregister();
@@ -87,13 +101,7 @@ mod tests {
     #[test]
     fn service_properties_include_version_and_protocol() {
         let config = test_config();
-        let mut properties = HashMap::new();
-        properties.insert("version".to_string(), env!("CARGO_PKG_VERSION").to_string());
-        properties.insert(
-            "protocol".to_string(),
-            parkhub_common::PROTOCOL_VERSION.to_string(),
-        );
-        properties.insert("tls".to_string(), config.enable_tls.to_string());
+        let properties = service_properties(&config);
 
         assert_eq!(
             properties.get("version").unwrap(),
@@ -106,6 +114,20 @@ mod tests {
         assert_eq!(properties.get("tls").unwrap(), "false");
     }
 
+    #[test]
+    fn service_properties_advertise_organization_and_role() {
+        let mut config = test_config();
+        let properties = service_properties(&config);
+        assert_eq!(properties.get("org"), None);
+        assert_eq!(properties.get("role").unwrap(), "primary");
+
+        config.organization_name = " ACME Corp ".into();
+        config.standby = true;
+        let properties = service_properties(&config);
+        assert_eq!(properties.get("org").unwrap(), "ACME Corp");
+        assert_eq!(properties.get("role").unwrap(), "standby");
+    }
+
     #[test]
     fn service_properties_tls_flag_reflects_config() {
         let mut config = test_config();
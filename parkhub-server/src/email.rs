@@ -49,6 +49,20 @@ impl SmtpConfig {
     }
 }
 
+/// Build the STARTTLS SMTP transport for `config`.
+pub fn smtp_transport(config: &SmtpConfig) -> Result<AsyncSmtpTransport<Tokio1Executor>> {
+    Ok(
+        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+            .context("Failed to create SMTP transport")?
+            .port(config.port)
+            .credentials(Credentials::new(
+                config.username.clone(),
+                config.password.clone(),
+            ))
+            .build(),
+    )
+}
+
 /// Send an HTML email.
 ///
 /// If SMTP is not configured (`SMTP_HOST` env var is absent) the call is a
@@ -72,29 +86,21 @@ pub async fn send_email(to: &str, subject: &str, html_body: &str) -> Result<()>
         .body(html_body.to_string())
         .context("Failed to build email message")?;
 
-    let mailer: AsyncSmtpTransport<Tokio1Executor> =
-        AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
-            .context("Failed to create SMTP transport")?
-            .port(config.port)
-            .credentials(Credentials::new(
-                config.username.clone(),
-                config.password.clone(),
-            ))
-            .build();
-
+    let mailer = smtp_transport(&config)?;
     mailer.send(message).await.context("Failed to send email")?;
 
     info!(to = %to, subject = %subject, "Email sent successfully");
     Ok(())
 }
 
-/// Build a booking confirmation email body.
+/// Build a booking confirmation email body. `slot_number` is `None` for
+/// late-binding bookings, whose slot is assigned shortly before the start.
 #[allow(clippy::too_many_arguments)]
 pub fn build_booking_confirmation_email(
     user_name: &str,
     booking_id: &str,
     floor_name: &str,
-    slot_number: i32,
+    slot_number: Option<i32>,
     start_time: &str,
     end_time: &str,
     org_name: &str,
@@ -108,7 +114,13 @@ pub fn build_booking_confirmation_email(
     let org = html_escape(org_raw);
     let user_name = html_escape(user_name);
     let booking_id = html_escape(booking_id);
-    let floor_name = html_escape(floor_name);
+    let (floor_name, slot_number) = match slot_number {
+        Some(number) => (html_escape(floor_name), number.to_string()),
+        None => (
+            "—".to_string(),
+            "Assigned shortly before the start".to_string(),
+        ),
+    };
     let start_time = html_escape(start_time);
     let end_time = html_escape(end_time);
     format!(
@@ -199,6 +211,155 @@ pub fn build_password_reset_email(reset_url: &str, org_name: &str) -> String {
     )
 }
 
+/// Build the confirmation email sent to a new address after a user asks to
+/// change their account email.
+pub fn build_email_change_email(confirm_url: &str, org_name: &str) -> String {
+    use crate::utils::html_escape;
+    let org_raw = if org_name.is_empty() {
+        "ParkHub"
+    } else {
+        org_name
+    };
+    let org = html_escape(org_raw);
+    let confirm_url = html_escape(confirm_url);
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8" />
+  <title>Confirm Email Change — {org}</title>
+  <style>
+    body {{ font-family: Arial, sans-serif; background: #f4f4f4; margin: 0; padding: 0; }}
+    .container {{ max-width: 600px; margin: 40px auto; background: #ffffff; border-radius: 8px;
+                  padding: 40px; box-shadow: 0 2px 8px rgba(0,0,0,0.1); }}
+    h1 {{ color: #1a73e8; margin-top: 0; }}
+    p  {{ color: #333333; line-height: 1.6; }}
+    .btn {{ display: inline-block; background: #1a73e8; color: #ffffff; padding: 14px 28px;
+            border-radius: 6px; text-decoration: none; font-weight: bold; margin: 20px 0; }}
+    .footer {{ margin-top: 40px; font-size: 12px; color: #888888; border-top: 1px solid #eeeeee;
+               padding-top: 16px; }}
+  </style>
+</head>
+<body>
+  <div class="container">
+    <h1>{org} — Confirm Your New Email</h1>
+    <p>Someone asked to use this address for their <strong>{org}</strong> account.</p>
+    <p>Click the button below to confirm. The link is valid for <strong>24 hours</strong>.</p>
+    <a href="{confirm_url}" class="btn">Confirm Email</a>
+    <p>If you did not request this, ignore this email. No account will be changed.</p>
+    <div class="footer">
+      <p>This email was sent by {org}. If you have questions, contact your administrator.</p>
+    </div>
+  </div>
+</body>
+</html>"#,
+    )
+}
+
+/// Build the invitation sent to a new user an admin invited.
+pub fn build_invite_email(invite_url: &str, org_name: &str, valid_hours: i64) -> String {
+    use crate::utils::html_escape;
+    let org_raw = if org_name.is_empty() {
+        "ParkHub"
+    } else {
+        org_name
+    };
+    let org = html_escape(org_raw);
+    let invite_url = html_escape(invite_url);
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8" />
+  <title>You're Invited — {org}</title>
+  <style>
+    body {{ font-family: Arial, sans-serif; background: #f4f4f4; margin: 0; padding: 0; }}
+    .container {{ max-width: 600px; margin: 40px auto; background: #ffffff; border-radius: 8px;
+                  padding: 40px; box-shadow: 0 2px 8px rgba(0,0,0,0.1); }}
+    h1 {{ color: #1a73e8; margin-top: 0; }}
+    p  {{ color: #333333; line-height: 1.6; }}
+    .btn {{ display: inline-block; background: #1a73e8; color: #ffffff; padding: 14px 28px;
+            border-radius: 6px; text-decoration: none; font-weight: bold; margin: 20px 0; }}
+    .footer {{ margin-top: 40px; font-size: 12px; color: #888888; border-top: 1px solid #eeeeee;
+               padding-top: 16px; }}
+  </style>
+</head>
+<body>
+  <div class="container">
+    <h1>{org} — You're Invited</h1>
+    <p>You have been invited to create an account on <strong>{org}</strong>.</p>
+    <p>Click the button below to choose your password. The link can be used once and is valid for <strong>{valid_hours} hours</strong>.</p>
+    <a href="{invite_url}" class="btn">Accept Invitation</a>
+    <p>If you were not expecting this invitation, you can ignore this email.</p>
+    <div class="footer">
+      <p>This email was sent by {org}. If you have questions, contact your administrator.</p>
+    </div>
+  </div>
+</body>
+</html>"#,
+    )
+}
+
+/// Build the alert sent when an account signs in from a new device or IP.
+pub fn build_new_login_email(
+    user_name: &str,
+    login_time: &str,
+    ip: &str,
+    user_agent: &str,
+    org_name: &str,
+) -> String {
+    use crate::utils::html_escape;
+    let org_raw = if org_name.is_empty() {
+        "ParkHub"
+    } else {
+        org_name
+    };
+    let org = html_escape(org_raw);
+    let user_name = html_escape(user_name);
+    let login_time = html_escape(login_time);
+    let ip = html_escape(ip);
+    let user_agent = html_escape(user_agent);
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8" />
+  <title>New Sign-in — {org}</title>
+  <style>
+    body {{ font-family: Arial, sans-serif; background: #f4f4f4; margin: 0; padding: 0; }}
+    .container {{ max-width: 600px; margin: 40px auto; background: #ffffff; border-radius: 8px;
+                  padding: 40px; box-shadow: 0 2px 8px rgba(0,0,0,0.1); }}
+    h1 {{ color: #1a73e8; margin-top: 0; }}
+    p  {{ color: #333333; line-height: 1.6; }}
+    table {{ width: 100%; border-collapse: collapse; margin: 20px 0; }}
+    td {{ padding: 10px 12px; border-bottom: 1px solid #eeeeee; color: #333333; }}
+    td:first-child {{ font-weight: bold; width: 35%; color: #555555; }}
+    .footer {{ margin-top: 40px; font-size: 12px; color: #888888; border-top: 1px solid #eeeeee;
+               padding-top: 16px; }}
+  </style>
+</head>
+<body>
+  <div class="container">
+    <h1>{org} — New Sign-in</h1>
+    <p>Hello {user_name},</p>
+    <p>Your account was just signed in to from a device or network we have not seen before.</p>
+    <table>
+      <tr><td>Time</td><td>{login_time}</td></tr>
+      <tr><td>IP Address</td><td>{ip}</td></tr>
+      <tr><td>Device</td><td>{user_agent}</td></tr>
+    </table>
+    <p>If this was you, no action is needed.</p>
+    <p>If you do not recognise this sign-in, change your password right away and sign out
+    of all other sessions.</p>
+    <div class="footer">
+      <p>This email was sent by {org}. If you have questions, contact your administrator.</p>
+    </div>
+  </div>
+</body>
+</html>"#,
+    )
+}
+
 /// Build a welcome email body for new user registrations.
 pub fn build_welcome_email(user_name: &str, org_name: &str) -> String {
     use crate::utils::html_escape;
@@ -327,11 +488,13 @@ pub fn build_booking_reminder_email(
 /// Build a waitlist-slot-available notification email body.
 ///
 /// Sent to the first user on the waitlist when a slot in their desired lot
-/// becomes available (e.g. after a cancellation).
+/// becomes available (e.g. after a cancellation). The offer is held for
+/// `claim_minutes`.
 pub fn build_waitlist_slot_available_email(
     user_name: &str,
     lot_name: &str,
     org_name: &str,
+    claim_minutes: i64,
 ) -> String {
     use crate::utils::html_escape;
     let org_raw = if org_name.is_empty() {
@@ -366,9 +529,9 @@ pub fn build_waitlist_slot_available_email(
     <p>Dear <strong>{user_name}</strong>,</p>
     <div class="highlight">
       <p>Good news! A parking slot has become available at <strong>{lot_name}</strong>.</p>
-      <p>You are on the waitlist for this parking lot. Log in now to book your slot before it is taken.</p>
+      <p>You are on the waitlist for this parking lot. Log in and claim the offer within <strong>{claim_minutes} minutes</strong> to book the slot.</p>
     </div>
-    <p>Please note that slots are available on a first-come, first-served basis. Act quickly to secure your spot.</p>
+    <p>If you do not claim it in time, the slot is offered to the next person on the waitlist.</p>
     <div class="footer">
       <p>This email was sent by {org}. You received this because you are on the waitlist for {lot_name}.</p>
       <p>To remove yourself from the waitlist, log in to your account.</p>
@@ -380,6 +543,9 @@ pub fn build_waitlist_slot_available_email(
 }
 
 /// Build a booking cancellation confirmation email body.
+///
+/// `cancellation_fee` is the preformatted fee charged under the lot's
+/// cancellation policy, if any.
 #[allow(clippy::too_many_arguments)]
 pub fn build_booking_cancellation_email(
     user_name: &str,
@@ -389,6 +555,7 @@ pub fn build_booking_cancellation_email(
     start_time: &str,
     end_time: &str,
     org_name: &str,
+    cancellation_fee: Option<&str>,
 ) -> String {
     use crate::utils::html_escape;
     let org_raw = if org_name.is_empty() {
@@ -402,6 +569,26 @@ pub fn build_booking_cancellation_email(
     let floor_name = html_escape(floor_name);
     let start_time = html_escape(start_time);
     let end_time = html_escape(end_time);
+    let (fee_row, refund_note) = cancellation_fee.map_or_else(
+        || {
+            (
+                String::new(),
+                "If credits were deducted for this booking, they have been refunded to your account."
+                    .to_string(),
+            )
+        },
+        |fee| {
+            let fee = html_escape(fee);
+            (
+                format!("\n      <tr><td>Cancellation Fee</td><td>{fee}</td></tr>"),
+                format!(
+                    "This booking was cancelled late, so a cancellation fee of {fee} applies under \
+                     the parking lot's cancellation policy. Any remaining amount or credits have \
+                     been refunded to your account."
+                ),
+            )
+        },
+    );
     format!(
         r#"<!DOCTYPE html>
 <html lang="en">
@@ -434,9 +621,9 @@ pub fn build_booking_cancellation_email(
       <tr><td>Slot Number</td><td>{slot_number}</td></tr>
       <tr><td>Original Start</td><td>{start_time}</td></tr>
       <tr><td>Original End</td><td>{end_time}</td></tr>
-      <tr><td>Status</td><td>Cancelled</td></tr>
+      <tr><td>Status</td><td>Cancelled</td></tr>{fee_row}
     </table>
-    <p>If credits were deducted for this booking, they have been refunded to your account.</p>
+    <p>{refund_note}</p>
     <div class="footer">
       <p>This email was sent by {org}. If you have questions, contact your administrator.</p>
     </div>
@@ -446,6 +633,247 @@ pub fn build_booking_cancellation_email(
     )
 }
 
+/// Build the email copy of an admin announcement (maintenance, closures).
+/// Line breaks in `message` are preserved.
+pub fn build_announcement_email(
+    user_name: &str,
+    title: &str,
+    message: &str,
+    org_name: &str,
+) -> String {
+    use crate::utils::html_escape;
+    let org_raw = if org_name.is_empty() {
+        "ParkHub"
+    } else {
+        org_name
+    };
+    let org = html_escape(org_raw);
+    let user_name = html_escape(user_name);
+    let title = html_escape(title);
+    let message = html_escape(message).replace('\n', "<br />");
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8" />
+  <title>{title} — {org}</title>
+  <style>
+    body {{ font-family: Arial, sans-serif; background: #f4f4f4; margin: 0; padding: 0; }}
+    .container {{ max-width: 600px; margin: 40px auto; background: #ffffff; border-radius: 8px;
+                  padding: 40px; box-shadow: 0 2px 8px rgba(0,0,0,0.1); }}
+    h1 {{ color: #1a73e8; margin-top: 0; }}
+    p  {{ color: #333333; line-height: 1.6; }}
+    .footer {{ margin-top: 40px; font-size: 12px; color: #888888; border-top: 1px solid #eeeeee;
+               padding-top: 16px; }}
+  </style>
+</head>
+<body>
+  <div class="container">
+    <h1>{title}</h1>
+    <p>Hello {user_name},</p>
+    <p>{message}</p>
+    <div class="footer">
+      <p>This announcement was sent by {org}. If you have questions, contact your administrator.</p>
+    </div>
+  </div>
+</body>
+</html>"#,
+    )
+}
+
+/// Build a reminder that a parking permit is about to expire.
+///
+/// All caller-supplied values are HTML-escaped.
+pub fn build_permit_expiry_email(
+    user_name: &str,
+    permit_type: &str,
+    expires_at: &str,
+    org_name: &str,
+) -> String {
+    use crate::utils::html_escape;
+    let org_raw = if org_name.is_empty() {
+        "ParkHub"
+    } else {
+        org_name
+    };
+    let org = html_escape(org_raw);
+    let user_name = html_escape(user_name);
+    let permit_type = html_escape(permit_type);
+    let expires_at = html_escape(expires_at);
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8" />
+  <title>Parking Permit Expiring — {org}</title>
+  <style>
+    body {{ font-family: Arial, sans-serif; background: #f4f4f4; margin: 0; padding: 0; }}
+    .container {{ max-width: 600px; margin: 40px auto; background: #ffffff; border-radius: 8px;
+                  padding: 40px; box-shadow: 0 2px 8px rgba(0,0,0,0.1); }}
+    h1 {{ color: #1a73e8; margin-top: 0; }}
+    p  {{ color: #333333; line-height: 1.6; }}
+    .footer {{ margin-top: 40px; font-size: 12px; color: #888888; border-top: 1px solid #eeeeee;
+               padding-top: 16px; }}
+  </style>
+</head>
+<body>
+  <div class="container">
+    <h1>Your parking permit expires soon</h1>
+    <p>Hello {user_name},</p>
+    <p>Your {permit_type} parking permit expires on <strong>{expires_at}</strong>.
+       After that you can no longer book restricted lots or pass their gates with it.</p>
+    <p>Please contact your administrator if you need the permit renewed.</p>
+    <div class="footer">
+      <p>This reminder was sent by {org}.</p>
+    </div>
+  </div>
+</body>
+</html>"#,
+    )
+}
+
+/// Build a user's summary of the previous month.
+///
+/// `energy` is the kWh charged and the estimated kg of CO2; the EV section
+/// is left out when it is `None`. All caller-supplied text is HTML-escaped.
+pub fn build_monthly_summary_email(
+    user_name: &str,
+    month: &str,
+    bookings: u32,
+    hours: f64,
+    energy: Option<(f64, f64)>,
+    org_name: &str,
+) -> String {
+    use crate::utils::html_escape;
+    let org_raw = if org_name.is_empty() {
+        "ParkHub"
+    } else {
+        org_name
+    };
+    let org = html_escape(org_raw);
+    let user_name = html_escape(user_name);
+    let month = html_escape(month);
+    let energy_section = energy.map_or_else(String::new, |(kwh, co2_kg)| {
+        format!(
+            r#"
+    <h2>EV charging</h2>
+    <table class="detail-table">
+      <tr><td>Energy charged</td><td>{kwh:.1} kWh</td></tr>
+      <tr><td>Estimated CO2</td><td>{co2_kg:.1} kg</td></tr>
+    </table>"#
+        )
+    });
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8" />
+  <title>Your Parking Summary — {org}</title>
+  <style>
+    body {{ font-family: Arial, sans-serif; background: #f4f4f4; margin: 0; padding: 0; }}
+    .container {{ max-width: 600px; margin: 40px auto; background: #ffffff; border-radius: 8px;
+                  padding: 40px; box-shadow: 0 2px 8px rgba(0,0,0,0.1); }}
+    h1 {{ color: #1a73e8; margin-top: 0; }}
+    h2 {{ color: #333333; font-size: 18px; margin-bottom: 0; }}
+    p  {{ color: #333333; line-height: 1.6; }}
+    .detail-table {{ width: 100%; border-collapse: collapse; margin: 12px 0 20px; }}
+    .detail-table td {{ padding: 10px 12px; border-bottom: 1px solid #eeeeee; font-size: 14px; color: #333333; }}
+    .detail-table td:first-child {{ font-weight: bold; width: 40%; color: #555555; }}
+    .footer {{ margin-top: 40px; font-size: 12px; color: #888888; border-top: 1px solid #eeeeee;
+               padding-top: 16px; }}
+  </style>
+</head>
+<body>
+  <div class="container">
+    <h1>Your summary for {month}</h1>
+    <p>Hello {user_name},</p>
+    <p>Here is how you parked in {month}.</p>
+    <h2>Parking</h2>
+    <table class="detail-table">
+      <tr><td>Bookings</td><td>{bookings}</td></tr>
+      <tr><td>Hours parked</td><td>{hours:.1}</td></tr>
+    </table>{energy_section}
+    <div class="footer">
+      <p>This summary was sent by {org}. Your administrator can turn monthly summaries off.</p>
+    </div>
+  </div>
+</body>
+</html>"#,
+    )
+}
+
+/// Build the parking pass sent to a visitor booked by someone else.
+///
+/// `qr_data` is the pass's QR code as a `data:` URI. All other values are
+/// HTML-escaped.
+#[allow(clippy::too_many_arguments)]
+pub fn build_visitor_pass_email(
+    visitor_name: &str,
+    license_plate: &str,
+    lot_name: &str,
+    slot_number: &str,
+    start_time: &str,
+    end_time: &str,
+    qr_data: &str,
+    org_name: &str,
+) -> String {
+    use crate::utils::html_escape;
+    let org_raw = if org_name.is_empty() {
+        "ParkHub"
+    } else {
+        org_name
+    };
+    let org = html_escape(org_raw);
+    let visitor_name = html_escape(visitor_name);
+    let license_plate = html_escape(license_plate);
+    let lot_name = html_escape(lot_name);
+    let slot_number = html_escape(slot_number);
+    let start_time = html_escape(start_time);
+    let end_time = html_escape(end_time);
+    let qr_data = html_escape(qr_data);
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+  <meta charset="UTF-8" />
+  <title>Your Parking Pass — {org}</title>
+  <style>
+    body {{ font-family: Arial, sans-serif; background: #f4f4f4; margin: 0; padding: 0; }}
+    .container {{ max-width: 600px; margin: 40px auto; background: #ffffff; border-radius: 8px;
+                  padding: 40px; box-shadow: 0 2px 8px rgba(0,0,0,0.1); }}
+    h1 {{ color: #1a73e8; margin-top: 0; }}
+    p  {{ color: #333333; line-height: 1.6; }}
+    .detail-table {{ width: 100%; border-collapse: collapse; margin: 20px 0; }}
+    .detail-table td {{ padding: 10px 12px; border-bottom: 1px solid #eeeeee; font-size: 14px; color: #333333; }}
+    .detail-table td:first-child {{ font-weight: bold; width: 40%; color: #555555; }}
+    .qr {{ text-align: center; margin: 24px 0; }}
+    .footer {{ margin-top: 40px; font-size: 12px; color: #888888; border-top: 1px solid #eeeeee;
+               padding-top: 16px; }}
+  </style>
+</head>
+<body>
+  <div class="container">
+    <h1>{org} — Parking Pass</h1>
+    <p>Dear <strong>{visitor_name}</strong>,</p>
+    <p>A parking space has been booked for you. Show this pass at the entrance:</p>
+    <div class="qr"><img src="{qr_data}" alt="Parking pass QR code" width="200" height="200" /></div>
+    <table class="detail-table">
+      <tr><td>License Plate</td><td>{license_plate}</td></tr>
+      <tr><td>Parking Lot</td><td>{lot_name}</td></tr>
+      <tr><td>Slot Number</td><td>{slot_number}</td></tr>
+      <tr><td>Valid From</td><td>{start_time}</td></tr>
+      <tr><td>Valid Until</td><td>{end_time}</td></tr>
+    </table>
+    <p>You can also print this email and bring it with you.</p>
+    <div class="footer">
+      <p>This pass was sent by {org} on behalf of your host.</p>
+    </div>
+  </div>
+</body>
+</html>"#,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -513,7 +941,7 @@ mod tests {
             "Alice",
             "BK-001",
             "Ground Floor",
-            5,
+            Some(5),
             "2026-03-20 09:00",
             "2026-03-20 17:00",
             "Acme",
@@ -528,8 +956,15 @@ mod tests {
 
     #[test]
     fn booking_email_defaults_org_to_parkhub() {
-        let html =
-            build_booking_confirmation_email("Bob", "BK-002", "Level 2", 3, "09:00", "12:00", "");
+        let html = build_booking_confirmation_email(
+            "Bob",
+            "BK-002",
+            "Level 2",
+            Some(3),
+            "09:00",
+            "12:00",
+            "",
+        );
         assert!(html.contains("ParkHub"));
         assert!(!html.contains("Acme"));
     }
@@ -540,7 +975,7 @@ mod tests {
             "<script>alert(1)</script>",
             "BK-XSS",
             "Floor",
-            1,
+            Some(1),
             "09:00",
             "10:00",
             "",
@@ -552,15 +987,35 @@ mod tests {
     #[test]
     fn booking_email_contains_slot_number() {
         let html = build_booking_confirmation_email(
-            "Carol", "BK-003", "Deck A", 42, "08:00", "18:00", "ParkCo",
+            "Carol",
+            "BK-003",
+            "Deck A",
+            Some(42),
+            "08:00",
+            "18:00",
+            "ParkCo",
         );
         assert!(html.contains("42"));
     }
 
+    #[test]
+    fn booking_email_without_slot_says_when_it_is_assigned() {
+        let html = build_booking_confirmation_email(
+            "Erin", "BK-005", "", None, "08:00", "18:00", "ParkCo",
+        );
+        assert!(html.contains("Assigned shortly before the start"));
+    }
+
     #[test]
     fn booking_email_is_valid_html() {
         let html = build_booking_confirmation_email(
-            "Dave", "BK-004", "B1", 7, "10:00", "11:00", "TestOrg",
+            "Dave",
+            "BK-004",
+            "B1",
+            Some(7),
+            "10:00",
+            "11:00",
+            "TestOrg",
         );
         assert!(html.starts_with("<!DOCTYPE html>"));
         assert!(html.contains("</html>"));
@@ -610,6 +1065,26 @@ mod tests {
         assert!(html.contains("1 hour"));
     }
 
+    // ── build_email_change_email ──
+
+    #[test]
+    fn email_change_email_contains_escaped_url() {
+        let html = build_email_change_email("https://example.com/confirm?t=a&b=c", "Corp");
+        assert!(html.contains("https://example.com/confirm?t=a&amp;b=c"));
+        assert!(html.contains("Corp"));
+        assert!(html.contains("24 hours"));
+    }
+
+    // ── build_invite_email ──
+
+    #[test]
+    fn invite_email_contains_escaped_url_and_validity() {
+        let html = build_invite_email("https://example.com/accept-invite?token=a&b", "Corp", 72);
+        assert!(html.contains("https://example.com/accept-invite?token=a&amp;b"));
+        assert!(html.contains("Corp"));
+        assert!(html.contains("72 hours"));
+    }
+
     // ── build_welcome_email ──
 
     #[test]
@@ -705,7 +1180,7 @@ mod tests {
 
     #[test]
     fn waitlist_email_contains_lot_name() {
-        let html = build_waitlist_slot_available_email("Alice", "Lot A", "ParkCo");
+        let html = build_waitlist_slot_available_email("Alice", "Lot A", "ParkCo", 15);
         assert!(html.contains("Alice"));
         assert!(html.contains("Lot A"));
         assert!(html.contains("ParkCo"));
@@ -713,20 +1188,20 @@ mod tests {
 
     #[test]
     fn waitlist_email_defaults_org_to_parkhub() {
-        let html = build_waitlist_slot_available_email("Bob", "Lot B", "");
+        let html = build_waitlist_slot_available_email("Bob", "Lot B", "", 15);
         assert!(html.contains("ParkHub"));
     }
 
     #[test]
     fn waitlist_email_escapes_html() {
-        let html = build_waitlist_slot_available_email("<script>xss</script>", "Lot", "");
+        let html = build_waitlist_slot_available_email("<script>xss</script>", "Lot", "", 15);
         assert!(!html.contains("<script>xss"));
         assert!(html.contains("&lt;script&gt;"));
     }
 
     #[test]
     fn waitlist_email_is_valid_html() {
-        let html = build_waitlist_slot_available_email("Carol", "Main Lot", "TestOrg");
+        let html = build_waitlist_slot_available_email("Carol", "Main Lot", "TestOrg", 15);
         assert!(html.starts_with("<!DOCTYPE html>"));
         assert!(html.contains("</html>"));
         assert!(html.contains("Parking Slot Available"));
@@ -734,10 +1209,16 @@ mod tests {
 
     #[test]
     fn waitlist_email_mentions_waitlist() {
-        let html = build_waitlist_slot_available_email("Dave", "Lot D", "");
+        let html = build_waitlist_slot_available_email("Dave", "Lot D", "", 15);
         assert!(html.contains("waitlist"));
     }
 
+    #[test]
+    fn waitlist_email_states_claim_window() {
+        let html = build_waitlist_slot_available_email("Erin", "Lot E", "", 20);
+        assert!(html.contains("20 minutes"));
+    }
+
     // ── build_booking_cancellation_email ──
 
     #[test]
@@ -750,6 +1231,7 @@ mod tests {
             "2026-03-20 09:00",
             "2026-03-20 17:00",
             "Acme",
+            None,
         );
         assert!(html.contains("Alice"));
         assert!(html.contains("BK-001"));
@@ -760,15 +1242,24 @@ mod tests {
 
     #[test]
     fn cancellation_email_defaults_org_to_parkhub() {
-        let html =
-            build_booking_cancellation_email("Bob", "BK-002", "Level 2", 3, "09:00", "12:00", "");
+        let html = build_booking_cancellation_email(
+            "Bob", "BK-002", "Level 2", 3, "09:00", "12:00", "", None,
+        );
         assert!(html.contains("ParkHub"));
     }
 
     #[test]
     fn cancellation_email_escapes_html() {
-        let html =
-            build_booking_cancellation_email("<img src=x>", "BK-XSS", "F", 1, "09:00", "10:00", "");
+        let html = build_booking_cancellation_email(
+            "<img src=x>",
+            "BK-XSS",
+            "F",
+            1,
+            "09:00",
+            "10:00",
+            "",
+            None,
+        );
         assert!(!html.contains("<img src=x>"));
         assert!(html.contains("&lt;img"));
     }
@@ -776,7 +1267,7 @@ mod tests {
     #[test]
     fn cancellation_email_is_valid_html() {
         let html = build_booking_cancellation_email(
-            "Carol", "BK-003", "A", 42, "08:00", "18:00", "ParkCo",
+            "Carol", "BK-003", "A", 42, "08:00", "18:00", "ParkCo", None,
         );
         assert!(html.starts_with("<!DOCTYPE html>"));
         assert!(html.contains("</html>"));
@@ -785,8 +1276,100 @@ mod tests {
 
     #[test]
     fn cancellation_email_mentions_credit_refund() {
-        let html = build_booking_cancellation_email("Eve", "BK-004", "B1", 7, "10:00", "11:00", "");
+        let html =
+            build_booking_cancellation_email("Eve", "BK-004", "B1", 7, "10:00", "11:00", "", None);
         assert!(html.contains("refunded"));
+        assert!(!html.contains("Cancellation Fee"));
+    }
+
+    #[test]
+    fn cancellation_email_shows_fee() {
+        let html = build_booking_cancellation_email(
+            "Finn",
+            "BK-005",
+            "B1",
+            7,
+            "10:00",
+            "11:00",
+            "",
+            Some("2.50 EUR (50%)"),
+        );
+        assert!(html.contains("Cancellation Fee"));
+        assert!(html.contains("2.50 EUR (50%)"));
+        assert!(html.contains("cancellation policy"));
+    }
+
+    #[test]
+    fn new_login_email_escapes_user_agent() {
+        let html = build_new_login_email(
+            "Gina",
+            "2026-03-01 08:00 UTC",
+            "203.0.113.7",
+            "<script>x</script>",
+            "",
+        );
+        assert!(html.contains("203.0.113.7"));
+        assert!(html.contains("ParkHub"));
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+    }
+
+    #[test]
+    fn announcement_email_escapes_and_keeps_line_breaks() {
+        let html = build_announcement_email(
+            "Gina",
+            "Garage <closed>",
+            "Level 2 closed.\nUse level 3.",
+            "Acme",
+        );
+        assert!(html.contains("Garage &lt;closed&gt;"));
+        assert!(html.contains("Level 2 closed.<br />Use level 3."));
+        assert!(html.contains("sent by Acme"));
+    }
+
+    #[test]
+    fn permit_expiry_email_contains_type_and_date() {
+        let html = build_permit_expiry_email("Hana", "resident", "2026-06-30 00:00 UTC", "");
+        assert!(html.contains("Hello Hana"));
+        assert!(html.contains("Your resident parking permit"));
+        assert!(html.contains("2026-06-30 00:00 UTC"));
+        assert!(html.contains("sent by ParkHub"));
+    }
+
+    #[test]
+    fn visitor_pass_email_shows_pass_details() {
+        let html = build_visitor_pass_email(
+            "Ida <Guest>",
+            "M-AB 123",
+            "Garage A",
+            "42",
+            "2026-06-30 09:00 UTC",
+            "2026-06-30 17:00 UTC",
+            "data:image/png;base64,AAAA",
+            "",
+        );
+        assert!(html.contains("Dear <strong>Ida &lt;Guest&gt;</strong>"));
+        assert!(html.contains("M-AB 123"));
+        assert!(html.contains(r#"src="data:image/png;base64,AAAA""#));
+        assert!(html.contains("sent by ParkHub"));
+    }
+
+    #[test]
+    fn monthly_summary_email_energy_section_is_optional() {
+        let html = build_monthly_summary_email("Hana <H>", "March 2026", 4, 31.5, None, "");
+        assert!(html.contains("Hello Hana &lt;H&gt;"));
+        assert!(html.contains("Your summary for March 2026"));
+        assert!(html.contains("<td>Bookings</td><td>4</td>"));
+        assert!(html.contains("<td>31.5</td>"));
+        assert!(!html.contains("EV charging"));
+        assert!(html.contains("sent by ParkHub"));
+
+        let html =
+            build_monthly_summary_email("Hana", "March 2026", 0, 0.0, Some((42.0, 16.8)), "Acme");
+        assert!(html.contains("EV charging"));
+        assert!(html.contains("42.0 kWh"));
+        assert!(html.contains("16.8 kg"));
+        assert!(html.contains("sent by Acme"));
     }
 
     // ── send_email (no SMTP configured) ──
@@ -0,0 +1,225 @@
+//! Persistent outbound email queue with retry and dead-lettering.
+//!
+//! Every outgoing email goes through [`enqueue`]: the message is stored
+//! first, then an immediate delivery attempt runs in the background. On
+//! failure the message stays queued and the `email_queue` background job
+//! retries it with exponential backoff ([`backoff`]). After
+//! [`MAX_ATTEMPTS`] failures the message is marked
+//! [`QueuedEmailStatus::Failed`] and only sent again when an admin retries
+//! it (`POST /api/v1/admin/email-queue/{id}/retry`). Delivered messages are
+//! removed, so password-reset links do not linger on disk.
+
+use std::collections::HashSet;
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::Result;
+use chrono::{Duration, Utc};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::db::{Database, QueuedEmail, QueuedEmailStatus};
+
+/// Delivery attempts before a message is dead-lettered.
+pub const MAX_ATTEMPTS: u32 = 6;
+
+/// Delay before the first retry; doubles with each further failure.
+const BASE_BACKOFF_SECS: i64 = 60;
+
+/// Upper bound on the delay between two attempts.
+const MAX_BACKOFF_SECS: i64 = 3600;
+
+/// Dead letters older than this are dropped by [`process_queue`].
+const FAILED_RETENTION_DAYS: i64 = 30;
+
+/// Messages currently being delivered, so the immediate attempt and the
+/// background job never send the same message twice.
+fn in_flight() -> &'static Mutex<HashSet<Uuid>> {
+    static IN_FLIGHT: OnceLock<Mutex<HashSet<Uuid>>> = OnceLock::new();
+    IN_FLIGHT.get_or_init(|| Mutex::new(HashSet::new()))
+}
+
+/// Releases a message's in-flight claim when dropped.
+struct InFlightGuard(Uuid);
+
+impl InFlightGuard {
+    fn claim(id: Uuid) -> Option<Self> {
+        let claimed = in_flight()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(id);
+        // Only build a guard on success: dropping one releases the claim.
+        if claimed { Some(Self(id)) } else { None }
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        in_flight()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(&self.0);
+    }
+}
+
+/// Delay before the next attempt after `attempts` failed ones.
+#[must_use]
+pub fn backoff(attempts: u32) -> Duration {
+    let exp = attempts.saturating_sub(1).min(16);
+    Duration::seconds((BASE_BACKOFF_SECS << exp).min(MAX_BACKOFF_SECS))
+}
+
+/// Queue an email and start delivering it in the background.
+///
+/// Returns once the message is persisted; delivery failures are retried by
+/// the background job rather than reported to the caller.
+pub async fn enqueue(db: &Database, to: &str, subject: &str, html_body: &str) -> Result<Uuid> {
+    let email = QueuedEmail::new(to, subject, html_body);
+    db.save_queued_email(&email).await?;
+
+    let id = email.id;
+    let db = db.clone();
+    tokio::spawn(async move {
+        if let Err(e) = deliver(&db, email).await {
+            error!("Failed to update queued email {id}: {e}");
+        }
+    });
+    Ok(id)
+}
+
+/// Apply the outcome of a delivery attempt. Returns `None` when the message
+/// was delivered and should be removed, otherwise the updated message.
+fn record_attempt(mut email: QueuedEmail, result: Result<()>) -> Option<QueuedEmail> {
+    let err = result.err()?;
+    let now = Utc::now();
+    email.attempts += 1;
+    email.last_error = Some(format!("{err:#}"));
+    email.updated_at = now;
+    if email.attempts >= MAX_ATTEMPTS {
+        email.status = QueuedEmailStatus::Failed;
+    } else {
+        email.next_attempt_at = now + backoff(email.attempts);
+    }
+    Some(email)
+}
+
+/// Make one delivery attempt and persist the outcome. Skips messages that
+/// are already being delivered.
+async fn deliver(db: &Database, email: QueuedEmail) -> Result<()> {
+    let Some(_guard) = InFlightGuard::claim(email.id) else {
+        return Ok(());
+    };
+
+    let id = email.id;
+    let result = crate::email::send_email(&email.to, &email.subject, &email.html_body).await;
+    match record_attempt(email, result) {
+        None => {
+            db.delete_queued_email(&id.to_string()).await?;
+        }
+        Some(email) => {
+            if email.status == QueuedEmailStatus::Failed {
+                error!(
+                    to = %email.to,
+                    subject = %email.subject,
+                    attempts = email.attempts,
+                    "Email dead-lettered: {}",
+                    email.last_error.as_deref().unwrap_or("")
+                );
+            } else {
+                warn!(
+                    to = %email.to,
+                    attempts = email.attempts,
+                    "Email delivery failed, retrying at {}",
+                    email.next_attempt_at
+                );
+            }
+            db.save_queued_email(&email).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Attempt every pending message that is due and drop expired dead letters.
+/// Returns the number of attempts made.
+pub async fn process_queue(db: &Database) -> Result<usize> {
+    let now = Utc::now();
+    let cutoff = now - Duration::days(FAILED_RETENTION_DAYS);
+    let mut attempted = 0;
+    for email in db.list_queued_emails().await? {
+        match email.status {
+            QueuedEmailStatus::Pending if email.next_attempt_at <= now => {
+                deliver(db, email).await?;
+                attempted += 1;
+            }
+            QueuedEmailStatus::Failed if email.updated_at < cutoff => {
+                db.delete_queued_email(&email.id.to_string()).await?;
+            }
+            _ => {}
+        }
+    }
+    Ok(attempted)
+}
+
+/// Reset a message to pending with a fresh retry budget and attempt it in
+/// the background. Returns the updated message, or `None` if it no longer
+/// exists.
+pub async fn retry(db: &Database, id: &str) -> Result<Option<QueuedEmail>> {
+    let Some(mut email) = db.get_queued_email(id).await? else {
+        return Ok(None);
+    };
+    email.status = QueuedEmailStatus::Pending;
+    email.attempts = 0;
+    email.next_attempt_at = Utc::now();
+    email.updated_at = email.next_attempt_at;
+    db.save_queued_email(&email).await?;
+
+    let db = db.clone();
+    let queued = email.clone();
+    tokio::spawn(async move {
+        let id = queued.id;
+        if let Err(e) = deliver(&db, queued).await {
+            error!("Failed to update queued email {id}: {e}");
+        }
+    });
+    Ok(Some(email))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        assert_eq!(backoff(1), Duration::seconds(60));
+        assert_eq!(backoff(2), Duration::seconds(120));
+        assert_eq!(backoff(3), Duration::seconds(240));
+        assert_eq!(backoff(10), Duration::seconds(MAX_BACKOFF_SECS));
+        assert_eq!(backoff(u32::MAX), Duration::seconds(MAX_BACKOFF_SECS));
+    }
+
+    #[test]
+    fn test_record_attempt_retries_then_dead_letters() {
+        let email = QueuedEmail::new("a@example.com", "Hi", "<p>Hi</p>");
+        assert!(record_attempt(email.clone(), Ok(())).is_none());
+
+        let mut email = email;
+        for attempt in 1..MAX_ATTEMPTS {
+            email = record_attempt(email, Err(anyhow::anyhow!("connection refused"))).unwrap();
+            assert_eq!(email.attempts, attempt);
+            assert_eq!(email.status, QueuedEmailStatus::Pending);
+            assert!(email.next_attempt_at > Utc::now());
+        }
+        let email = record_attempt(email, Err(anyhow::anyhow!("connection refused"))).unwrap();
+        assert_eq!(email.status, QueuedEmailStatus::Failed);
+        assert_eq!(email.attempts, MAX_ATTEMPTS);
+        assert_eq!(email.last_error.as_deref(), Some("connection refused"));
+    }
+
+    #[test]
+    fn test_in_flight_guard_is_exclusive() {
+        let id = Uuid::new_v4();
+        let guard = InFlightGuard::claim(id).unwrap();
+        assert!(InFlightGuard::claim(id).is_none());
+        drop(guard);
+        assert!(InFlightGuard::claim(id).is_some());
+    }
+}
@@ -7,6 +7,7 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use parkhub_common::ErrorCode;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use utoipa::ToSchema;
@@ -15,7 +16,8 @@ use utoipa::ToSchema;
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ApiError {
     /// Error code for programmatic handling
-    pub code: String,
+    #[schema(value_type = String)]
+    pub code: ErrorCode,
     /// Human-readable error message
     pub message: String,
     /// Optional field-level errors (for validation)
@@ -92,24 +94,24 @@ pub enum AppError {
 
 impl AppError {
     /// Get error code for this error type
-    pub const fn code(&self) -> &'static str {
+    pub const fn code(&self) -> ErrorCode {
         match self {
-            Self::InvalidCredentials => "INVALID_CREDENTIALS",
-            Self::TokenExpired => "TOKEN_EXPIRED",
-            Self::InvalidToken => "INVALID_TOKEN",
-            Self::Unauthorized => "UNAUTHORIZED",
-            Self::Forbidden => "FORBIDDEN",
-            Self::ValidationFailed(_) => "VALIDATION_FAILED",
-            Self::InvalidInput(_) => "INVALID_INPUT",
-            Self::NotFound(_) => "NOT_FOUND",
-            Self::AlreadyExists(_) => "ALREADY_EXISTS",
-            Self::Conflict(_) => "CONFLICT",
-            Self::SlotNotAvailable => "SLOT_NOT_AVAILABLE",
-            Self::BookingNotModifiable => "BOOKING_NOT_MODIFIABLE",
-            Self::InvalidBookingTime => "INVALID_BOOKING_TIME",
-            Self::RateLimited => "RATE_LIMITED",
-            Self::Database(_) => "DATABASE_ERROR",
-            Self::Internal => "INTERNAL_ERROR",
+            Self::InvalidCredentials => ErrorCode::InvalidCredentials,
+            Self::TokenExpired => ErrorCode::TokenExpired,
+            Self::InvalidToken => ErrorCode::InvalidToken,
+            Self::Unauthorized => ErrorCode::Unauthorized,
+            Self::Forbidden => ErrorCode::Forbidden,
+            Self::ValidationFailed(_) => ErrorCode::ValidationFailed,
+            Self::InvalidInput(_) => ErrorCode::InvalidInput,
+            Self::NotFound(_) => ErrorCode::NotFound,
+            Self::AlreadyExists(_) => ErrorCode::AlreadyExists,
+            Self::Conflict(_) => ErrorCode::Conflict,
+            Self::SlotNotAvailable => ErrorCode::SlotNotAvailable,
+            Self::BookingNotModifiable => ErrorCode::BookingNotModifiable,
+            Self::InvalidBookingTime => ErrorCode::InvalidBookingTime,
+            Self::RateLimited => ErrorCode::RateLimited,
+            Self::Database(_) => ErrorCode::DatabaseError,
+            Self::Internal => ErrorCode::InternalError,
         }
     }
 
@@ -143,7 +145,7 @@ impl IntoResponse for AppError {
         };
 
         let body = ApiError {
-            code: self.code().to_string(),
+            code: self.code(),
             message: self.to_string(),
             details,
         };
@@ -175,21 +177,7 @@ impl From<serde_json::Error> for AppError {
 
 impl From<validator::ValidationErrors> for AppError {
     fn from(errors: validator::ValidationErrors) -> Self {
-        let field_errors: Vec<FieldError> = errors
-            .field_errors()
-            .iter()
-            .flat_map(|(field, errors)| {
-                errors.iter().map(move |e| FieldError {
-                    field: field.to_string(),
-                    message: e
-                        .message
-                        .as_ref()
-                        .map_or_else(|| e.code.to_string(), std::string::ToString::to_string),
-                })
-            })
-            .collect();
-
-        Self::ValidationFailed(field_errors)
+        Self::ValidationFailed(crate::validation::FieldErrors::from(errors).into_vec())
     }
 }
 
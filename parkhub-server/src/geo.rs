@@ -0,0 +1,156 @@
+//! Geographic helpers: coordinate validation, distances, optional geocoding.
+//!
+//! Lots store `latitude`/`longitude` with `(0.0, 0.0)` meaning "not set".
+//! Geocoding is opt-in: set `PARKHUB_GEOCODER_URL` to a Nominatim-compatible
+//! base URL (e.g. `https://nominatim.openstreetmap.org`) and lots created with
+//! an address but without coordinates are resolved on creation. Nothing is
+//! sent to a third party unless the operator configures it.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+/// Mean Earth radius in meters.
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// Timeout for a single geocoder lookup — lot creation must not hang on it.
+const GEOCODER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Haversine great-circle distance between two points in meters.
+#[must_use]
+pub fn haversine_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let d_lat = (lat2 - lat1).to_radians();
+    let d_lon = (lon2 - lon1).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+    let c = 2.0 * a.sqrt().atan2((1.0 - a).sqrt());
+    EARTH_RADIUS_M * c
+}
+
+/// Validate a latitude/longitude pair.
+///
+/// # Errors
+/// Returns a human-readable message when either value is non-finite or out
+/// of range.
+pub fn validate_coordinates(lat: f64, lon: f64) -> Result<(), &'static str> {
+    if !lat.is_finite() || !(-90.0..=90.0).contains(&lat) {
+        return Err("Latitude must be between -90 and 90");
+    }
+    if !lon.is_finite() || !(-180.0..=180.0).contains(&lon) {
+        return Err("Longitude must be between -180 and 180");
+    }
+    Ok(())
+}
+
+/// Whether a stored coordinate pair is the `(0.0, 0.0)` "not set" sentinel.
+#[must_use]
+pub fn is_unset(lat: f64, lon: f64) -> bool {
+    lat.abs() < f64::EPSILON && lon.abs() < f64::EPSILON
+}
+
+#[derive(Debug, Deserialize)]
+struct NominatimPlace {
+    lat: String,
+    lon: String,
+}
+
+fn geocoder_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(GEOCODER_TIMEOUT)
+            .user_agent(concat!("parkhub-server/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// Resolve an address to `(lat, lon)` via the configured geocoder.
+///
+/// Returns `None` when no geocoder is configured, the address is blank, the
+/// lookup fails, or the result is not a valid coordinate pair. Failures are
+/// logged and never propagated — geocoding is a convenience, not a
+/// requirement for creating a lot.
+pub async fn geocode_address(address: &str) -> Option<(f64, f64)> {
+    let base = std::env::var("PARKHUB_GEOCODER_URL").ok()?;
+    let address = address.trim();
+    if address.is_empty() {
+        return None;
+    }
+
+    let url = match url::Url::parse_with_params(
+        &format!("{}/search", base.trim_end_matches('/')),
+        &[("q", address), ("format", "json"), ("limit", "1")],
+    ) {
+        Ok(url) => url,
+        Err(e) => {
+            tracing::warn!(error = %e, "PARKHUB_GEOCODER_URL is not a valid URL");
+            return None;
+        }
+    };
+    let resp = geocoder_client()
+        .get(url)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status);
+    let places: Vec<NominatimPlace> = match resp {
+        Ok(r) => match r.json().await {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::warn!(error = %e, "Geocoder returned an unreadable response");
+                return None;
+            }
+        },
+        Err(e) => {
+            tracing::warn!(error = %e, "Geocoder lookup failed");
+            return None;
+        }
+    };
+
+    let place = places.into_iter().next()?;
+    let lat = place.lat.parse::<f64>().ok()?;
+    let lon = place.lon.parse::<f64>().ok()?;
+    validate_coordinates(lat, lon).ok()?;
+    Some((lat, lon))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_haversine_same_point_is_zero() {
+        assert!(haversine_distance(48.1351, 11.5820, 48.1351, 11.5820).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_haversine_munich_berlin() {
+        let d = haversine_distance(48.1351, 11.5820, 52.5200, 13.4050);
+        assert!((d - 504_000.0).abs() < 5_000.0, "got {d}");
+    }
+
+    #[test]
+    fn test_validate_coordinates_ranges() {
+        assert!(validate_coordinates(48.1, 11.5).is_ok());
+        assert!(validate_coordinates(-90.0, 180.0).is_ok());
+        assert!(validate_coordinates(90.1, 0.0).is_err());
+        assert!(validate_coordinates(0.0, -180.5).is_err());
+        assert!(validate_coordinates(f64::NAN, 0.0).is_err());
+        assert!(validate_coordinates(0.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_is_unset_sentinel() {
+        assert!(is_unset(0.0, 0.0));
+        assert!(!is_unset(0.0, 11.5));
+        assert!(!is_unset(48.1, 0.0));
+    }
+
+    #[test]
+    fn test_nominatim_place_deserialize() {
+        let json = r#"[{"lat":"48.1371","lon":"11.5754","display_name":"München"}]"#;
+        let places: Vec<NominatimPlace> = serde_json::from_str(json).unwrap();
+        assert_eq!(places[0].lat, "48.1371");
+    }
+}
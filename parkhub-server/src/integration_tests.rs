@@ -56,7 +56,7 @@ async fn test_harness() -> TestHarness {
         db,
         mdns: None,
         scheduler: None,
-        ws_events: crate::api::ws::EventBroadcaster::new(),
+        ws_events: crate::api::events::EventBroadcaster::new(),
         fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
         revocation_store: crate::jwt::TokenRevocationList::new(),
     }));
@@ -437,6 +437,9 @@ async fn server_status_returns_success() {
     let json = body_json(resp).await;
     assert_eq!(json["success"], true);
     assert!(json["data"]["total_users"].as_u64().unwrap() >= 1); // admin exists
+    assert_eq!(json["data"]["backup"]["enabled"], true);
+    assert_eq!(json["data"]["backup"]["backup_count"], 0);
+    assert!(json["data"]["backup"]["last_backup_at"].is_null());
 }
 
 #[tokio::test]
@@ -468,6 +471,41 @@ async fn handshake_matching_protocol_succeeds() {
     );
 }
 
+#[tokio::test]
+async fn handshake_negotiates_degraded_modes_for_other_versions() {
+    let state = test_state().await;
+
+    for (version, mode) in [
+        ("1.9.0", "compatible"),
+        ("2.0.0", "read_only"),
+        (parkhub_common::PROTOCOL_VERSION, "full"),
+    ] {
+        let body = serde_json::json!({
+            "client_version": "9.9.9",
+            "protocol_version": version,
+        });
+        let resp = router(state.clone())
+            .oneshot(
+                Request::post("/handshake")
+                    .header("content-type", "application/json")
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let json = body_json(resp).await;
+        assert_eq!(json["success"], true, "{version}");
+        assert_eq!(json["data"]["compatibility"], mode, "{version}");
+        assert!(
+            json["data"]["features"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .any(|f| f == "bookings")
+        );
+    }
+}
+
 #[tokio::test]
 async fn handshake_mismatched_protocol_returns_error() {
     let state = test_state().await;
@@ -734,6 +772,171 @@ async fn register_with_overly_long_password_returns_400() {
     assert_eq!(json["error"]["code"], "INVALID_INPUT");
 }
 
+#[tokio::test]
+async fn logout_revokes_current_session_or_all_sessions() {
+    let state = test_state().await;
+    let tok_a = admin_token_it(state.clone()).await;
+    let tok_b = admin_token_it(state.clone()).await;
+    let tok_c = admin_token_it(state.clone()).await;
+
+    let me_status = |tok: String| {
+        let state = state.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::get("/api/v1/users/me")
+                        .header("authorization", format!("Bearer {tok}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+                .status()
+        }
+    };
+    let logout = |tok: String, uri: &'static str| {
+        let state = state.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post(uri)
+                        .header("authorization", format!("Bearer {tok}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+                .status()
+        }
+    };
+
+    // Plain logout only ends the calling session.
+    assert_eq!(
+        logout(tok_a.clone(), "/api/v1/auth/logout").await,
+        StatusCode::OK
+    );
+    assert_eq!(me_status(tok_a).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(me_status(tok_b.clone()).await, StatusCode::OK);
+
+    // `all=true` ends every session of the user.
+    assert_eq!(
+        logout(tok_b.clone(), "/api/v1/auth/logout?all=true").await,
+        StatusCode::OK
+    );
+    assert_eq!(me_status(tok_b).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(me_status(tok_c).await, StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn logout_invalidates_the_refresh_token() {
+    let state = test_state().await;
+    let body = serde_json::json!({"username": "admin", "password": "admin123"});
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/auth/login")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let json = body_json(resp).await;
+    let access = json["data"]["tokens"]["access_token"].as_str().unwrap();
+    let refresh = json["data"]["tokens"]["refresh_token"].as_str().unwrap();
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/auth/logout")
+                .header("authorization", format!("Bearer {access}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/auth/refresh")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({ "refresh_token": refresh })).unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        body_json(resp).await["error"]["code"],
+        "INVALID_REFRESH_TOKEN"
+    );
+}
+
+#[tokio::test]
+async fn signed_access_token_is_revoked_with_its_session() {
+    let state = test_state().await;
+    let body = serde_json::json!({"username": "admin", "password": "admin123"});
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/auth/login")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let json = body_json(resp).await;
+    let access = json["data"]["tokens"]["access_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let refresh = json["data"]["tokens"]["refresh_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(access.split('.').count(), 3, "access token should be a JWT");
+
+    let me_status = |tok: String| {
+        let state = state.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::get("/api/v1/users/me")
+                        .header("authorization", format!("Bearer {tok}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+                .status()
+        }
+    };
+    assert_eq!(me_status(access.clone()).await, StatusCode::OK);
+
+    // A truncated signature is rejected.
+    let tampered = access[..access.len() - 4].to_string();
+    assert_eq!(me_status(tampered).await, StatusCode::UNAUTHORIZED);
+
+    // Refresh rotates the session: the new token works, the old one is revoked.
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/auth/refresh")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({"refresh_token": refresh})).unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let rotated = json["data"]["access_token"].as_str().unwrap().to_string();
+    assert_eq!(me_status(rotated).await, StatusCode::OK);
+    assert_eq!(me_status(access).await, StatusCode::UNAUTHORIZED);
+}
+
 // ═════════════════════════════════════════════════════════════════════════════
 // 4. PROTECTED ENDPOINTS (auth required)
 // ═════════════════════════════════════════════════════════════════════════════
@@ -1944,3 +2147,6853 @@ async fn test_rate_limit_allows_after_window() {
     );
     assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
 }
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 15. LOT GEOSEARCH
+// ═════════════════════════════════════════════════════════════════════════════
+
+/// Helper: create a lot at the given coordinates and return its ID.
+async fn create_lot_at(
+    state: Arc<RwLock<AppState>>,
+    admin_tok: &str,
+    name: &str,
+    lat: f64,
+    lon: f64,
+) -> String {
+    let body = serde_json::json!({
+        "name": name,
+        "latitude": lat,
+        "longitude": lon,
+        "total_slots": 1,
+        "currency": "EUR",
+    });
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/lots")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED, "create lot failed");
+    body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+#[tokio::test]
+async fn test_lot_search_sorts_by_distance_within_radius() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    // Munich city centre, ~1.2 km away, and Berlin (far outside the radius).
+    let near = create_lot_at(state.clone(), &admin_tok, "Marienplatz", 48.1374, 11.5755).await;
+    let further = create_lot_at(state.clone(), &admin_tok, "Stachus", 48.1390, 11.5653).await;
+    create_lot_at(
+        state.clone(),
+        &admin_tok,
+        "Alexanderplatz",
+        52.5219,
+        13.4132,
+    )
+    .await;
+
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/lots/search?lat=48.1372&lon=11.5760&radius=5000")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let results = json["data"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["id"], near.as_str());
+    assert_eq!(results[1]["id"], further.as_str());
+    assert!(
+        results[0]["distance_meters"].as_f64().unwrap()
+            < results[1]["distance_meters"].as_f64().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn test_lot_search_rejects_invalid_coordinates() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/lots/search?lat=91&lon=11.5")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn test_create_lot_rejects_unpaired_coordinates() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let body = serde_json::json!({"name": "Half", "latitude": 48.1, "currency": "EUR"});
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/lots")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+#[cfg(feature = "mod-map")]
+#[tokio::test]
+async fn test_lots_geojson_returns_feature_collection() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let lot_id = create_lot_at(state.clone(), &admin_tok, "Marienplatz", 48.1374, 11.5755).await;
+
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/lots.geojson")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers().get("content-type").unwrap(),
+        "application/geo+json"
+    );
+    let json = body_json(resp).await;
+    assert_eq!(json["type"], "FeatureCollection");
+    let feature = &json["features"][0];
+    assert_eq!(feature["id"], lot_id.as_str());
+    assert_eq!(feature["geometry"]["type"], "Point");
+    assert_eq!(feature["geometry"]["coordinates"][0], 11.5755);
+    assert_eq!(feature["properties"]["total_slots"], 1);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 16. LOT CLONING
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_clone_lot_copies_layout_without_bookings() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+
+    // Occupy one slot in the source lot; the clone must not inherit it.
+    {
+        let guard = state.read().await;
+        guard
+            .db
+            .update_slot_status(
+                &slot_id,
+                parkhub_common::SlotStatus::Occupied,
+                &crate::db::SlotChangeContext::system("test"),
+            )
+            .await
+            .unwrap();
+    }
+
+    let body = serde_json::json!({"name": "Test Lot (Level 2)"});
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post(format!("/api/v1/admin/lots/{lot_id}/clone"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = body_json(resp).await;
+    let clone_id = json["data"]["id"].as_str().unwrap().to_string();
+    assert_ne!(clone_id, lot_id);
+    assert_eq!(json["data"]["name"], "Test Lot (Level 2)");
+    assert_eq!(json["data"]["pricing"]["currency"], "EUR");
+
+    let guard = state.read().await;
+    let slots = guard.db.list_slots_by_lot(&clone_id).await.unwrap();
+    assert_eq!(slots.len(), 5);
+    assert!(
+        slots
+            .iter()
+            .all(|s| s.status == parkhub_common::SlotStatus::Available)
+    );
+    let floor_id = slots[0].floor_id.to_string();
+    assert_eq!(json["data"]["floors"][0]["id"], floor_id.as_str());
+}
+
+#[tokio::test]
+async fn test_clone_lot_rejects_duplicate_name() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, _) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+
+    let body = serde_json::json!({"name": "test lot"});
+    let resp = router(state)
+        .oneshot(
+            Request::post(format!("/api/v1/admin/lots/{lot_id}/clone"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 17. LOT ARCHIVAL
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_archive_lot_migrates_bookings_and_hides_lot() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (old_lot, old_slot) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (new_lot, _) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+
+    let start_time = (chrono::Utc::now() + TimeDelta::days(1))
+        .date_naive()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+        .and_utc();
+    let booking_body = serde_json::json!({
+        "lot_id": old_lot,
+        "slot_id": old_slot,
+        "start_time": start_time,
+        "duration_minutes": 60,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "ARCH-001",
+    });
+    let booking_id = {
+        let resp = router(state.clone())
+            .oneshot(
+                Request::post("/api/v1/bookings")
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {admin_tok}"))
+                    .body(Body::from(serde_json::to_vec(&booking_body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        body_json(resp).await["data"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post(format!("/api/v1/admin/lots/{old_lot}/archive"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({"target_lot_id": new_lot})).unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"]["migrated"].as_array().unwrap().len(), 1);
+    assert_eq!(
+        json["data"]["migrated"][0]["booking_id"],
+        booking_id.as_str()
+    );
+
+    {
+        let guard = state.read().await;
+        let booking = guard.db.get_booking(&booking_id).await.unwrap().unwrap();
+        assert_eq!(booking.lot_id.to_string(), new_lot);
+        assert_eq!(booking.status, parkhub_common::BookingStatus::Confirmed);
+        // History is preserved: the archived lot is still resolvable.
+        assert!(guard.db.get_parking_lot(&old_lot).await.unwrap().is_some());
+    }
+
+    // Hidden from the lot listing.
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/lots")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let json = body_json(resp).await;
+    let ids: Vec<&str> = json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|l| l["id"].as_str().unwrap())
+        .collect();
+    assert!(!ids.contains(&old_lot.as_str()));
+    assert!(ids.contains(&new_lot.as_str()));
+
+    // Closed to new bookings.
+    let slots = {
+        let guard = state.read().await;
+        guard.db.list_slots_by_lot(&old_lot).await.unwrap()
+    };
+    let free_slot = slots
+        .iter()
+        .find(|s| s.status == parkhub_common::SlotStatus::Available)
+        .unwrap();
+    let booking_body = serde_json::json!({
+        "lot_id": old_lot,
+        "slot_id": free_slot.id,
+        "start_time": start_time,
+        "duration_minutes": 60,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "ARCH-002",
+    });
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&booking_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 18. LOT MANAGERS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_lot_manager_scoped_to_assigned_lot() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (managed_lot, managed_slot) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (other_lot, other_slot) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (manager_tok, manager_id) = register_user_it(state.clone(), "manager@example.com").await;
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post(format!("/api/v1/admin/lots/{managed_lot}/managers"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({"user_id": manager_id})).unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/users/me/managed-lots")
+                .header("authorization", format!("Bearer {manager_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let json = body_json(resp).await;
+    assert_eq!(json["data"], serde_json::json!([managed_lot]));
+
+    // Slots: allowed in the managed lot, refused elsewhere.
+    let resp = router(state.clone())
+        .oneshot(
+            Request::delete(format!("/api/v1/lots/{managed_lot}/slots/{managed_slot}"))
+                .header("authorization", format!("Bearer {manager_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::delete(format!("/api/v1/lots/{other_lot}/slots/{other_slot}"))
+                .header("authorization", format!("Bearer {manager_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Lot-scoped admin endpoints open up; global ones stay closed.
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/admin/bookings")
+                .header("authorization", format!("Bearer {manager_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/admin/users")
+                .header("authorization", format!("Bearer {manager_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Revoking the assignment removes access again.
+    let resp = router(state.clone())
+        .oneshot(
+            Request::delete(format!(
+                "/api/v1/admin/lots/{managed_lot}/managers/{manager_id}"
+            ))
+            .header("authorization", format!("Bearer {admin_tok}"))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/admin/bookings")
+                .header("authorization", format!("Bearer {manager_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 19. TELEMETRY
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_telemetry_off_by_default_and_previewable() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/admin/telemetry")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"]["enabled"], false);
+    assert!(json["data"]["preview"]["version"].is_string());
+    assert!(json["data"]["preview"]["users"].is_string());
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put("/api/v1/admin/telemetry")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(
+                    serde_json::to_vec(
+                        &serde_json::json!({"enabled": true, "endpoint": "not-a-url"}),
+                    )
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = router(state)
+        .oneshot(
+            Request::put("/api/v1/admin/telemetry")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({
+                        "enabled": true,
+                        "endpoint": "https://telemetry.example.com/v1/report"
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"]["enabled"], true);
+    assert_eq!(
+        json["data"]["endpoint"],
+        "https://telemetry.example.com/v1/report"
+    );
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 20. PROFILE SELF-SERVICE (PATCH /users/me)
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_patch_me_whitelists_fields_and_defers_email_change() {
+    let state = test_state().await;
+    let (tok, user_id) = register_user_it(state.clone(), "patcher@example.com").await;
+
+    let patch = |body: serde_json::Value| {
+        let state = state.clone();
+        let tok = tok.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::patch("/api/v1/users/me")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {tok}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    // Privilege fields are refused outright.
+    let resp = patch(serde_json::json!({"name": "X", "role": "admin"})).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let json = body_json(resp).await;
+    assert_eq!(json["error"]["code"], "FIELD_NOT_EDITABLE");
+
+    // Whitelisted fields apply; the email stays until confirmed.
+    let resp = patch(serde_json::json!({
+        "name": "Patched Name",
+        "email": "patched@example.com",
+    }))
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"]["user"]["name"], "Patched Name");
+    assert_eq!(json["data"]["user"]["email"], "patcher@example.com");
+    assert_eq!(json["data"]["pending_email"], "patched@example.com");
+    assert_eq!(json["data"]["user"]["role"], "user");
+
+    let stored = state
+        .read()
+        .await
+        .db
+        .get_user(&user_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.name, "Patched Name");
+    assert_eq!(stored.email, "patcher@example.com");
+}
+
+#[tokio::test]
+async fn test_profile_picture_upload_and_serve() {
+    let state = test_state().await;
+    let (tok, user_id) = register_user_it(state.clone(), "avatar@example.com").await;
+
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::new_rgb8(400, 300)
+        .write_to(&mut png, image::ImageFormat::Png)
+        .unwrap();
+    let boundary = "parkhub-test-boundary";
+    let mut body = format!(
+        "--{boundary}\r\nContent-Disposition: form-data; name=\"picture\"; \
+         filename=\"me.png\"\r\nContent-Type: image/png\r\n\r\n"
+    )
+    .into_bytes();
+    body.extend_from_slice(png.get_ref());
+    body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put("/api/v1/users/me/picture")
+                .header(
+                    "content-type",
+                    format!("multipart/form-data; boundary={boundary}"),
+                )
+                .header("authorization", format!("Bearer {tok}"))
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let url = format!("/api/v1/users/{user_id}/picture");
+    assert_eq!(json["data"]["picture"], url.as_str());
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get(url.as_str())
+                .header("authorization", format!("Bearer {tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "image/png");
+    let etag = resp.headers()["etag"].to_str().unwrap().to_string();
+    let served = image::load_from_memory(&body_bytes(resp).await).unwrap();
+    assert_eq!((served.width(), served.height()), (256, 256));
+
+    let resp = router(state)
+        .oneshot(
+            Request::get(url.as_str())
+                .header("authorization", format!("Bearer {tok}"))
+                .header("if-none-match", etag)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 21. PER-LOT BOOKING RULES
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_lot_rules_enforced_on_booking_and_cancellation() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "rules@example.com").await;
+
+    let rules = serde_json::json!({
+        "max_duration_minutes": 120,
+        "cancellation_cutoff_minutes": 24 * 60,
+    });
+
+    // Regular users can read but not change the rules.
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put(format!("/api/v1/lots/{lot_id}/rules"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::from(serde_json::to_vec(&rules).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put(format!("/api/v1/lots/{lot_id}/rules"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&rules).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get(format!("/api/v1/lots/{lot_id}/rules"))
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"]["max_duration_minutes"], 120);
+    assert_eq!(json["data"]["same_day_only"], false);
+
+    let book = |duration: i32| {
+        let state = state.clone();
+        let user_tok = user_tok.clone();
+        let body = serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": chrono::Utc::now() + TimeDelta::hours(2),
+            "duration_minutes": duration,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "RULE-001",
+        });
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post("/api/v1/bookings")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {user_tok}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let resp = book(180).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let json = body_json(resp).await;
+    assert_eq!(json["error"]["code"], "DURATION_TOO_LONG");
+
+    let resp = book(60).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let booking_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Starts in two hours, inside the 24h cutoff.
+    let resp = router(state)
+        .oneshot(
+            Request::delete(format!("/api/v1/bookings/{booking_id}"))
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let json = body_json(resp).await;
+    assert_eq!(json["error"]["code"], "CANCELLATION_CUTOFF");
+}
+
+#[tokio::test]
+async fn test_late_cancellation_charges_policy_fee() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "latecancel@example.com").await;
+
+    let rules = serde_json::json!({
+        "cancellation_policy": {"free_until_minutes": 24 * 60, "fee_percent": 50},
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put(format!("/api/v1/lots/{lot_id}/rules"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&rules).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": chrono::Utc::now() + TimeDelta::hours(2),
+        "duration_minutes": 60,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "FEE-001",
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = body_json(resp).await;
+    let booking_id = json["data"]["id"].as_str().unwrap().to_string();
+    let total = json["data"]["pricing"]["total"].as_f64().unwrap();
+
+    // Inside the free window: cancellation still works, but half is kept.
+    let resp = router(state.clone())
+        .oneshot(
+            Request::delete(format!("/api/v1/bookings/{booking_id}"))
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let stored = state
+        .read()
+        .await
+        .db
+        .get_booking(&booking_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let fee = stored.pricing.cancellation_fee.unwrap();
+    assert!((fee - (total * 50.0).round() / 100.0).abs() < 1e-9);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 22. SLOT STATUS HISTORY
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_slot_status_history_records_admin_change() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+
+    let body = serde_json::json!({"status": "maintenance", "reason": "Barrier repair"});
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put(format!("/api/v1/lots/{lot_id}/slots/{slot_id}"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get(format!("/api/v1/admin/slots/{slot_id}/history"))
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let history = json["data"].as_array().unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0]["from_status"], "available");
+    assert_eq!(history[0]["to_status"], "maintenance");
+    assert_eq!(history[0]["source"], "admin");
+    assert_eq!(history[0]["reason"], "Barrier repair");
+    assert_eq!(history[0]["changed_by_name"], "admin");
+
+    // Regular users cannot read it.
+    let (user_tok, _) = register_user_it(state.clone(), "slothist@example.com").await;
+    let resp = router(state)
+        .oneshot(
+            Request::get(format!("/api/v1/admin/slots/{slot_id}/history"))
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 23. EMAIL QUEUE
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_email_queue_lists_dead_letters_and_retries() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+
+    let mut dead =
+        crate::db::QueuedEmail::new("bounce@example.com", "Reset your password", "<p>secret</p>");
+    dead.status = crate::db::QueuedEmailStatus::Failed;
+    dead.attempts = crate::email_queue::MAX_ATTEMPTS;
+    dead.last_error = Some("Connection refused".to_string());
+    state
+        .read()
+        .await
+        .db
+        .save_queued_email(&dead)
+        .await
+        .unwrap();
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/admin/email-queue?status=failed")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let queue = json["data"].as_array().unwrap();
+    assert_eq!(queue.len(), 1);
+    assert_eq!(queue[0]["to"], "bounce@example.com");
+    assert_eq!(queue[0]["last_error"], "Connection refused");
+    assert!(queue[0]["next_attempt_at"].is_null());
+    assert!(queue[0].get("html_body").is_none());
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post(format!("/api/v1/admin/email-queue/{}/retry", dead.id))
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"]["status"], "pending");
+    assert_eq!(json["data"]["attempts"], 0);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post(format!(
+                "/api/v1/admin/email-queue/{}/retry",
+                Uuid::new_v4()
+            ))
+            .header("authorization", format!("Bearer {admin_tok}"))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    // Regular users cannot see the queue.
+    let (user_tok, _) = register_user_it(state.clone(), "mailq@example.com").await;
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/admin/email-queue")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 24. LOGIN HISTORY
+// ═════════════════════════════════════════════════════════════════════════════
+
+async fn login_with_agent(
+    state: Arc<RwLock<AppState>>,
+    email: &str,
+    password: &str,
+    user_agent: &str,
+) -> StatusCode {
+    let body = serde_json::json!({"username": email, "password": password});
+    router(state)
+        .oneshot(
+            Request::post("/api/v1/auth/login")
+                .header("content-type", "application/json")
+                .header("user-agent", user_agent)
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_login_history_records_attempts_and_new_devices() {
+    let state = test_state().await;
+    let email = "logins@example.com";
+    let (user_tok, _) = register_user_it(state.clone(), email).await;
+
+    let status = login_with_agent(state.clone(), email, "WrongPass1!", "Laptop/1.0").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let status = login_with_agent(state.clone(), email, "SecurePass1!", "Laptop/1.0").await;
+    assert_eq!(status, StatusCode::OK);
+    let status = login_with_agent(state.clone(), email, "SecurePass1!", "Phone/2.0").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/users/me/logins")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let logins = json["data"].as_array().unwrap();
+    assert_eq!(logins.len(), 3);
+
+    assert_eq!(logins[0]["user_agent"], "Phone/2.0");
+    assert_eq!(logins[0]["success"], true);
+    assert_eq!(logins[0]["new_device"], true);
+
+    assert_eq!(logins[1]["user_agent"], "Laptop/1.0");
+    assert_eq!(logins[1]["success"], true);
+    // The first successful login has nothing to compare against.
+    assert_eq!(logins[1]["new_device"], false);
+
+    assert_eq!(logins[2]["success"], false);
+    assert_eq!(logins[2]["failure_reason"], "invalid_password");
+    assert_eq!(logins[2]["ip"], "127.0.0.1");
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 25. LICENSE PLATE DISPLAY
+// ═════════════════════════════════════════════════════════════════════════════
+
+async fn slot_plate(
+    state: Arc<RwLock<AppState>>,
+    token: &str,
+    lot_id: &str,
+    slot_id: &str,
+) -> serde_json::Value {
+    let resp = router(state)
+        .oneshot(
+            Request::get(format!("/api/v1/lots/{lot_id}/slots"))
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|s| s["id"] == slot_id)
+        .cloned()
+        .unwrap()["current_booking"]
+        .clone()
+}
+
+#[tokio::test]
+async fn test_slot_plates_masked_for_other_users() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (owner_tok, _) = register_user_it(state.clone(), "plate-owner@example.com").await;
+    let (other_tok, _) = register_user_it(state.clone(), "plate-other@example.com").await;
+
+    let booking_body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": chrono::Utc::now() + TimeDelta::minutes(5),
+        "duration_minutes": 120,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "M-AB 1234",
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {owner_tok}"))
+                .body(Body::from(serde_json::to_vec(&booking_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let booking_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Move the booking's start into the past so it occupies the slot now.
+    {
+        let guard = state.read().await;
+        let mut booking = guard.db.get_booking(&booking_id).await.unwrap().unwrap();
+        booking.start_time = chrono::Utc::now() - TimeDelta::minutes(5);
+        guard.db.save_booking(&booking).await.unwrap();
+    }
+
+    state.write().await.config.license_plate_display = 1;
+    let own = slot_plate(state.clone(), &owner_tok, &lot_id, &slot_id).await;
+    assert_eq!(own["license_plate"], "M-AB 1234");
+    assert_eq!(own["is_own_booking"], true);
+    let other = slot_plate(state.clone(), &other_tok, &lot_id, &slot_id).await;
+    assert_eq!(other["license_plate"], "M-A* ****");
+    assert_eq!(other["is_own_booking"], false);
+
+    state.write().await.config.license_plate_display = 3;
+    let other = slot_plate(state.clone(), &other_tok, &lot_id, &slot_id).await;
+    assert_eq!(other["license_plate"], "");
+
+    state.write().await.config.license_plate_display = 0;
+    let other = slot_plate(state, &other_tok, &lot_id, &slot_id).await;
+    assert_eq!(other["license_plate"], "M-AB 1234");
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 26. ANNOUNCEMENTS
+// ═════════════════════════════════════════════════════════════════════════════
+
+async fn my_announcement_titles(state: Arc<RwLock<AppState>>, token: &str) -> Vec<String> {
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/announcements")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    body_json(resp).await["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a["title"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_lot_announcement_targets_booked_users_until_dismissed() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (booked_tok, booked_id) = register_user_it(state.clone(), "booked@example.com").await;
+    let (other_tok, other_id) = register_user_it(state.clone(), "elsewhere@example.com").await;
+
+    let booking_body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": chrono::Utc::now() + TimeDelta::hours(2),
+        "duration_minutes": 60,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "ANN-001",
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {booked_tok}"))
+                .body(Body::from(serde_json::to_vec(&booking_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let body = serde_json::json!({
+        "title": "Garage closed",
+        "message": "Level 1 is closed on Saturday.",
+        "severity": "warning",
+        "audience": {"type": "lot", "lot_id": lot_id},
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/admin/announcements")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let announcement_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Only the user with a booking in the lot is addressed.
+    assert_eq!(
+        my_announcement_titles(state.clone(), &booked_tok).await,
+        ["Garage closed"]
+    );
+    assert!(
+        my_announcement_titles(state.clone(), &other_tok)
+            .await
+            .is_empty()
+    );
+    {
+        let guard = state.read().await;
+        let booked = guard
+            .db
+            .list_notifications_by_user(&booked_id)
+            .await
+            .unwrap();
+        assert!(booked.iter().any(|n| n.title == "Garage closed"));
+        let other = guard
+            .db
+            .list_notifications_by_user(&other_id)
+            .await
+            .unwrap();
+        assert!(!other.iter().any(|n| n.title == "Garage closed"));
+    }
+
+    // Lot-targeted announcements stay off the public feed.
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/announcements/active")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(body_json(resp).await["data"], serde_json::json!([]));
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post(format!("/api/v1/announcements/{announcement_id}/dismiss"))
+                .header("authorization", format!("Bearer {booked_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(
+        my_announcement_titles(state.clone(), &booked_tok)
+            .await
+            .is_empty()
+    );
+
+    let resp = router(state)
+        .oneshot(
+            Request::post(format!("/api/v1/announcements/{}/dismiss", Uuid::new_v4()))
+                .header("authorization", format!("Bearer {booked_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 27. DEPARTMENT QUOTAS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_department_quota_enforced_and_reported() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, first_slot) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (first_tok, first_id) = register_user_it(state.clone(), "eng1@example.com").await;
+    let (second_tok, second_id) = register_user_it(state.clone(), "eng2@example.com").await;
+    {
+        let guard = state.read().await;
+        for id in [&first_id, &second_id] {
+            let mut user = guard.db.get_user(id).await.unwrap().unwrap();
+            user.department = Some("Engineering".to_string());
+            guard.db.save_user(&user).await.unwrap();
+        }
+    }
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get(format!("/api/v1/lots/{lot_id}/slots"))
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let second_slot = body_json(resp).await["data"][1]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let quotas = serde_json::json!([{"department": " engineering ", "slots_per_day": 1}]);
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put("/api/v1/admin/department-quotas")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {first_tok}"))
+                .body(Body::from(serde_json::to_vec(&quotas).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put("/api/v1/admin/department-quotas")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&quotas).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        body_json(resp).await["data"][0]["department"],
+        "engineering"
+    );
+
+    let start = chrono::Utc::now() + TimeDelta::hours(2);
+    let book = |token: String, slot_id: String| {
+        let state = state.clone();
+        let body = serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": start,
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "DEPT-001",
+        });
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post("/api/v1/bookings")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let resp = book(first_tok, first_slot).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    // The department's single slot for the day is taken.
+    let resp = book(second_tok, second_slot).await;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    let json = body_json(resp).await;
+    assert_eq!(json["error"]["code"], "DEPARTMENT_QUOTA_REACHED");
+
+    let day = start.date_naive();
+    let resp = router(state)
+        .oneshot(
+            Request::get(format!(
+                "/api/v1/admin/reports/department-utilization?start_date={day}&end_date={day}"
+            ))
+            .header("authorization", format!("Bearer {admin_tok}"))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let departments = json["data"]["departments"].as_array().unwrap();
+    let engineering = departments
+        .iter()
+        .find(|d| d["department"] == "engineering")
+        .unwrap();
+    assert_eq!(engineering["user_count"], 2);
+    assert_eq!(engineering["allocated_slots"], 1);
+    assert_eq!(engineering["used_slots"], 1);
+    assert_eq!(engineering["utilization_percent"], 100.0);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 28. PARKING PERMITS
+// ═════════════════════════════════════════════════════════════════════════════
+
+async fn gate_check_it(
+    state: Arc<RwLock<AppState>>,
+    token: &str,
+    lot_id: &str,
+    plate: &str,
+) -> serde_json::Value {
+    let body = serde_json::json!({"lot_id": lot_id, "license_plate": plate});
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/gate/check")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    body_json(resp).await["data"].clone()
+}
+
+#[tokio::test]
+async fn test_permit_required_lot_and_gate_check() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, user_id) = register_user_it(state.clone(), "resident@example.com").await;
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put(format!("/api/v1/lots/{lot_id}/rules"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({"permit_required": true})).unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let book = || {
+        let state = state.clone();
+        let user_tok = user_tok.clone();
+        let body = serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": chrono::Utc::now() + TimeDelta::hours(2),
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "RES-001",
+        });
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post("/api/v1/bookings")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {user_tok}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let resp = book().await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    assert_eq!(body_json(resp).await["error"]["code"], "PERMIT_REQUIRED");
+
+    let issue = |permit: serde_json::Value| {
+        let state = state.clone();
+        let admin_tok = admin_tok.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post("/api/v1/admin/permits")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {admin_tok}"))
+                        .body(Body::from(serde_json::to_vec(&permit).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+    let now = chrono::Utc::now();
+    let resp = issue(serde_json::json!({
+        "permit_type": "resident",
+        "user_id": user_id,
+        "lot_ids": [lot_id],
+        "valid_from": now - TimeDelta::days(1),
+        "valid_until": now + TimeDelta::days(90),
+    }))
+    .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let resp = book().await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/permits/me")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let permits = body_json(resp).await;
+    assert_eq!(permits["data"].as_array().unwrap().len(), 1);
+    assert_eq!(permits["data"][0]["permit_type"], "resident");
+
+    // Plate-only contractor permit, matched regardless of spacing and case.
+    let resp = issue(serde_json::json!({
+        "permit_type": "contractor",
+        "license_plate": "B-XY 99",
+        "holder_name": "Acme Plumbing",
+        "valid_from": now - TimeDelta::hours(1),
+        "valid_until": now + TimeDelta::days(3),
+    }))
+    .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let decision = gate_check_it(state.clone(), &admin_tok, &lot_id, "bxy99").await;
+    assert_eq!(decision["allowed"], true);
+    assert_eq!(decision["decision"], "permit");
+    assert_eq!(decision["holder_name"], "Acme Plumbing");
+
+    let decision = gate_check_it(state.clone(), &admin_tok, &lot_id, "ZZ-999").await;
+    assert_eq!(decision["allowed"], false);
+    assert_eq!(decision["decision"], "denied");
+
+    // Regular users cannot drive the gate.
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/gate/check")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({
+                        "lot_id": lot_id,
+                        "license_plate": "bxy99",
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 29. MULTI-DAY BOOKINGS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_multi_day_booking_quote_and_calendar() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "traveller@example.com").await;
+
+    // Tomorrow 22:00 until the day after next 10:00 touches three days.
+    let tomorrow = chrono::Utc::now().date_naive() + chrono::Days::new(1);
+    let start = tomorrow.and_hms_opt(22, 0, 0).unwrap().and_utc();
+    let post = |path: &'static str, body: serde_json::Value| {
+        let state = state.clone();
+        let user_tok = user_tok.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post(path)
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {user_tok}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let resp = post(
+        "/api/v1/bookings/quote",
+        serde_json::json!({"lot_id": lot_id, "start_time": start, "duration_minutes": 36 * 60}),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let quote = body_json(resp).await;
+    let days = quote["data"]["days"].as_array().unwrap();
+    assert_eq!(days.len(), 3);
+    assert_eq!(days[0]["minutes"], 120);
+    assert_eq!(days[1]["minutes"], 24 * 60);
+    assert_eq!(days[2]["minutes"], 600);
+
+    let booking = |duration_minutes: i32| {
+        serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": start,
+            "duration_minutes": duration_minutes,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "TRIP-1",
+        })
+    };
+    let resp = post("/api/v1/bookings", booking(15 * 24 * 60)).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = post("/api/v1/bookings", booking(36 * 60)).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let resp = router(state)
+        .oneshot(
+            Request::get(format!(
+                "/api/v1/lots/{lot_id}/calendar?start_date={tomorrow}&days=4"
+            ))
+            .header("authorization", format!("Bearer {user_tok}"))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let calendar = body_json(resp).await;
+    let free: Vec<u64> = calendar["data"]["days"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["free_slots"].as_u64().unwrap())
+        .collect();
+    assert_eq!(free, vec![4, 4, 4, 5]);
+    assert_eq!(calendar["data"]["days"][0]["total_slots"], 5);
+    assert_eq!(calendar["data"]["days"][0]["bookable"], true);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 30. CHECK-IN WINDOW AND OCCUPIED SLOTS
+// ═════════════════════════════════════════════════════════════════════════════
+
+async fn checkin_it(
+    state: Arc<RwLock<AppState>>,
+    token: &str,
+    booking_id: &str,
+) -> http::Response<Body> {
+    router(state)
+        .oneshot(
+            Request::post(format!("/api/v1/bookings/{booking_id}/checkin"))
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_early_checkin_window_and_occupied_slot_reassignment() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "early@example.com").await;
+
+    let set_window = |minutes: i32| {
+        let state = state.clone();
+        let admin_tok = admin_tok.clone();
+        let lot_id = lot_id.clone();
+        async move {
+            let resp = router(state)
+                .oneshot(
+                    Request::put(format!("/api/v1/lots/{lot_id}/rules"))
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {admin_tok}"))
+                        .body(Body::from(
+                            serde_json::to_vec(
+                                &serde_json::json!({"early_checkin_minutes": minutes}),
+                            )
+                            .unwrap(),
+                        ))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+        }
+    };
+    set_window(15).await;
+
+    let body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": chrono::Utc::now() + TimeDelta::hours(2),
+        "duration_minutes": 60,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "EARLY-1",
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let booking = body_json(resp).await["data"].clone();
+    let booking_id = booking["id"].as_str().unwrap().to_string();
+    let booked_number = booking["slot_number"].as_i64().unwrap();
+
+    let resp = checkin_it(state.clone(), &user_tok, &booking_id).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(body_json(resp).await["error"]["code"], "CHECKIN_TOO_EARLY");
+
+    // Someone else is still checked in to the slot when the window opens.
+    set_window(180).await;
+    {
+        let guard = state.read().await;
+        let mut previous = guard.db.get_booking(&booking_id).await.unwrap().unwrap();
+        previous.id = Uuid::new_v4();
+        previous.user_id = Uuid::new_v4();
+        previous.status = parkhub_common::BookingStatus::Active;
+        previous.start_time = chrono::Utc::now() - TimeDelta::hours(3);
+        previous.end_time = chrono::Utc::now() - TimeDelta::minutes(10);
+        previous.check_in_time = Some(previous.start_time);
+        guard.db.save_booking(&previous).await.unwrap();
+    }
+
+    let resp = checkin_it(state.clone(), &user_tok, &booking_id).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let checked_in = body_json(resp).await["data"].clone();
+    assert_eq!(checked_in["status"], "active");
+    assert_ne!(checked_in["slot_id"], slot_id.as_str());
+    assert_ne!(checked_in["slot_number"].as_i64().unwrap(), booked_number);
+
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/notifications")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let notifications = body_json(resp).await;
+    assert!(
+        notifications["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|n| n["title"] == "Slot changed")
+    );
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 31. ALTERNATIVE SLOTS ON CONFLICT
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_slot_unavailable_suggests_alternatives() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (first_tok, _) = register_user_it(state.clone(), "first@example.com").await;
+    let (second_tok, _) = register_user_it(state.clone(), "second@example.com").await;
+
+    let book = |token: String, plate: &'static str| {
+        let state = state.clone();
+        let body = serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": chrono::Utc::now() + TimeDelta::hours(1),
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": plate,
+        });
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post("/api/v1/bookings")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let resp = book(first_tok, "ALT-1").await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let resp = book(second_tok, "ALT-2").await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let json = body_json(resp).await;
+    assert_eq!(json["error"]["code"], "SLOT_UNAVAILABLE");
+    let alternatives = json["error"]["details"]["alternatives"]
+        .as_array()
+        .unwrap()
+        .clone();
+    assert!(!alternatives.is_empty());
+    assert!(alternatives.len() <= 3);
+    for alternative in &alternatives {
+        assert_ne!(alternative["slot_id"], slot_id.as_str());
+        assert_eq!(alternative["slot_type"], "standard");
+    }
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 32. REVENUE REPORT
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_revenue_report_matches_invoices() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "revenue@example.com").await;
+
+    let body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": chrono::Utc::now() + TimeDelta::hours(1),
+        "duration_minutes": 120,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "REV-1",
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let booking = body_json(resp).await["data"].clone();
+    let booking_id = booking["id"].as_str().unwrap().to_string();
+    let net = booking["pricing"]["base_price"].as_f64().unwrap();
+
+    // Issuing the invoice assigns its number.
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get(format!("/api/v1/bookings/{booking_id}/invoice"))
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let invoice_number = state
+        .read()
+        .await
+        .db
+        .get_assigned_invoice_number(&booking_id)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let report = |query: &'static str| {
+        let state = state.clone();
+        let admin_tok = admin_tok.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::get(format!("/api/v1/admin/reports/revenue{query}"))
+                        .header("authorization", format!("Bearer {admin_tok}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let resp = report("?group_by=lot").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let data = body_json(resp).await["data"].clone();
+    let rows = data["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["key"], lot_id.as_str());
+    assert_eq!(rows[0]["label"], "Test Lot");
+    assert_eq!(rows[0]["booking_count"], 1);
+    assert_eq!(rows[0]["invoice_numbers"][0], invoice_number.as_str());
+    let round = |v: f64| (v * 100.0).round() / 100.0;
+    assert_eq!(data["totals"]["net"].as_f64().unwrap(), round(net));
+    assert_eq!(data["totals"]["gross"].as_f64().unwrap(), round(net * 1.19));
+    assert_eq!(data["totals"]["outstanding"], data["totals"]["gross"]);
+
+    let resp = report("?from=2000-01-01&to=2000-01-31").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["data"]["totals"]["booking_count"], 0);
+
+    assert_eq!(
+        report("?group_by=slot").await.status(),
+        StatusCode::BAD_REQUEST
+    );
+    assert_eq!(
+        report("?from=2026-02-01&to=2026-01-01").await.status(),
+        StatusCode::BAD_REQUEST
+    );
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 33. CONCURRENT BOOKING CLAIMS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 8)]
+async fn test_concurrent_bookings_claim_slot_once() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+
+    let start_time = (chrono::Utc::now() + TimeDelta::days(1))
+        .date_naive()
+        .and_hms_opt(9, 0, 0)
+        .unwrap()
+        .and_utc();
+    let booking_body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": start_time,
+        "duration_minutes": 120,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "RACE-001",
+    });
+
+    // Build every router up front: the helper takes a non-blocking read lock.
+    let apps: Vec<_> = (0..24).map(|_| router(state.clone())).collect();
+    let tasks: Vec<_> = apps
+        .into_iter()
+        .map(|app| {
+            let body = serde_json::to_vec(&booking_body).unwrap();
+            let token = admin_tok.clone();
+            tokio::spawn(async move {
+                app.oneshot(
+                    Request::post("/api/v1/bookings")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+                .status()
+            })
+        })
+        .collect();
+
+    let mut created = 0;
+    for task in tasks {
+        match task.await.unwrap() {
+            StatusCode::CREATED => created += 1,
+            status => assert_eq!(status, StatusCode::CONFLICT),
+        }
+    }
+    assert_eq!(created, 1);
+
+    let guard = state.read().await;
+    let on_slot = guard
+        .db
+        .list_bookings()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|b| b.slot_id.to_string() == slot_id)
+        .count();
+    assert_eq!(on_slot, 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_booking_other_slot_proceeds_while_slot_is_locked() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, busy_slot) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let free_slot = {
+        let resp = router(state.clone())
+            .oneshot(
+                Request::get(format!("/api/v1/lots/{lot_id}/slots"))
+                    .header("authorization", format!("Bearer {admin_tok}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        body_json(resp).await["data"][1]["id"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+    let (first_tok, _) = register_user_it(state.clone(), "first@example.com").await;
+    let (second_tok, _) = register_user_it(state.clone(), "second@example.com").await;
+
+    let start_time = (chrono::Utc::now() + TimeDelta::days(1))
+        .date_naive()
+        .and_hms_opt(9, 0, 0)
+        .unwrap()
+        .and_utc();
+    let book = |token: String, slot_id: String| {
+        let app = router(state.clone());
+        let body = serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": start_time,
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "PAR-001",
+        });
+        async move {
+            app.oneshot(
+                Request::post("/api/v1/bookings")
+                    .header("content-type", "application/json")
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+        }
+    };
+
+    // A booking in progress on one slot ...
+    let slot_uuid = Uuid::parse_str(&busy_slot).unwrap();
+    let held =
+        crate::api::booking_locks::lock([crate::api::booking_locks::slot_key(slot_uuid)]).await;
+    let waiting = tokio::spawn(book(first_tok, busy_slot));
+
+    // ... does not hold up a booking on another slot
+    let status = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        book(second_tok, free_slot),
+    )
+    .await
+    .expect("booking another slot must not wait for the locked slot");
+    assert_eq!(status, StatusCode::CREATED);
+    assert!(!waiting.is_finished());
+
+    drop(held);
+    assert_eq!(waiting.await.unwrap(), StatusCode::CREATED);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 34. OPENID CONNECT LOGIN
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[cfg(feature = "mod-oauth")]
+#[tokio::test]
+async fn test_oidc_requires_configuration_and_flow_state() {
+    let state = test_state().await;
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/auth/oidc/start")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        body_json(resp).await["error"]["code"],
+        "OIDC_NOT_CONFIGURED"
+    );
+
+    {
+        let mut guard = state.write().await;
+        guard.config.oidc.issuer_url = "https://sso.invalid".to_string();
+        guard.config.oidc.client_id = "parkhub".to_string();
+    }
+
+    // A callback without the flow cookie is rejected before any provider call.
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/auth/oidc/callback?code=abc&state=forged")
+                .header("cookie", "oidc_flow=other.nonce.verifier")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(body_json(resp).await["error"]["code"], "OIDC_ERROR");
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 35. LOT PHOTOS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_lot_photo_upload_and_public_serve() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, _) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "photos@example.com").await;
+
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::new_rgb8(2400, 1200)
+        .write_to(&mut png, image::ImageFormat::Png)
+        .unwrap();
+    let boundary = "parkhub-test-boundary";
+    let multipart_body = || {
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"photo\"; \
+             filename=\"lot.png\"\r\nContent-Type: image/png\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(png.get_ref());
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        body
+    };
+    let upload = |tok: &str| {
+        Request::post(format!("/api/v1/admin/lots/{lot_id}/photos"))
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .header("authorization", format!("Bearer {tok}"))
+            .body(Body::from(multipart_body()))
+            .unwrap()
+    };
+
+    // Regular users cannot upload.
+    let resp = router(state.clone())
+        .oneshot(upload(&user_tok))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = router(state.clone())
+        .oneshot(upload(&admin_tok))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let images = json["data"]["images"].as_array().unwrap();
+    assert_eq!(images.len(), 1);
+    let url = images[0].as_str().unwrap().to_string();
+    assert!(url.starts_with(&format!("/api/v1/lots/{lot_id}/photos/")));
+
+    // Re-uploading the same image does not add a duplicate.
+    let resp = router(state.clone())
+        .oneshot(upload(&admin_tok))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        body_json(resp).await["data"]["images"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    // Served without authentication, downscaled and cacheable.
+    let resp = router(state.clone())
+        .oneshot(Request::get(url.as_str()).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "image/jpeg");
+    assert!(
+        resp.headers()["cache-control"]
+            .to_str()
+            .unwrap()
+            .contains("immutable")
+    );
+    let etag = resp.headers()["etag"].to_str().unwrap().to_string();
+    let served = image::load_from_memory(&body_bytes(resp).await).unwrap();
+    assert_eq!((served.width(), served.height()), (1600, 800));
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get(url.as_str())
+                .header("if-none-match", etag)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+
+    // Deleting the photo removes it from the lot and the public route.
+    let photo_id = url.rsplit('/').next().unwrap();
+    let resp = router(state.clone())
+        .oneshot(
+            Request::delete(format!("/api/v1/admin/lots/{lot_id}/photos/{photo_id}"))
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(
+        body_json(resp).await["data"]["images"]
+            .as_array()
+            .unwrap()
+            .is_empty()
+    );
+
+    let resp = router(state)
+        .oneshot(Request::get(url.as_str()).body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 36. BOOKING COUNTDOWN AND SERVER TIME
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_server_time_endpoint_is_public() {
+    let state = test_state().await;
+    state.write().await.config.booking_expiry_warning_minutes = 20;
+
+    let before = chrono::Utc::now().timestamp_millis();
+    let resp = router(state)
+        .oneshot(Request::get("/api/v1/time").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let millis = json["data"]["unix_millis"].as_i64().unwrap();
+    assert!(millis >= before && millis <= chrono::Utc::now().timestamp_millis());
+    assert_eq!(json["data"]["expiry_warning_minutes"], 20);
+    assert!(json["data"]["server_time"].is_string());
+}
+
+#[tokio::test]
+async fn test_booking_responses_carry_countdown() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+
+    let start_time = chrono::Utc::now() + TimeDelta::hours(2);
+    let booking_body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": start_time,
+        "duration_minutes": 60,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "CNT-001",
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&booking_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = body_json(resp).await;
+    let booking_id = json["data"]["id"].as_str().unwrap().to_string();
+    let countdown = &json["data"]["countdown"];
+    let starts_in = countdown["starts_in_seconds"].as_i64().unwrap();
+    assert!((7190..=7200).contains(&starts_in), "starts_in={starts_in}");
+    let remaining = countdown["remaining_seconds"].as_i64().unwrap();
+    assert!(
+        (10790..=10800).contains(&remaining),
+        "remaining={remaining}"
+    );
+    assert_eq!(countdown["expiring_soon"], false);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get(format!("/api/v1/bookings/{booking_id}"))
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(body_json(resp).await["data"]["countdown"]["server_time"].is_string());
+
+    // A booking ending inside the warning window is flagged in the list.
+    {
+        let guard = state.read().await;
+        let mut booking = guard.db.get_booking(&booking_id).await.unwrap().unwrap();
+        booking.start_time = chrono::Utc::now() - TimeDelta::hours(1);
+        booking.end_time = chrono::Utc::now() + TimeDelta::minutes(5);
+        guard.db.save_booking(&booking).await.unwrap();
+    }
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/bookings")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let json = body_json(resp).await;
+    let listed = &json["data"][0];
+    assert_eq!(listed["id"], booking_id.as_str());
+    assert_eq!(listed["countdown"]["starts_in_seconds"], 0);
+    assert_eq!(listed["countdown"]["expiring_soon"], true);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 37. ACCESSIBILITY PREFERENCES
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_accessibility_preferences_sync() {
+    let state = test_state().await;
+    let (tok, _) = register_user_it(state.clone(), "a11y@example.com").await;
+
+    let put = |body: serde_json::Value| {
+        Request::put("/api/v1/user/preferences")
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {tok}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+
+    // Never synced: the field is absent so clients upload their local copy.
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/user/preferences")
+                .header("authorization", format!("Bearer {tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(body_json(resp).await["data"]["accessibility"].is_null());
+
+    let resp = router(state.clone())
+        .oneshot(put(serde_json::json!({
+            "accessibility": { "theme_mode": 9, "font_scale": 1.0, "reduce_motion": false }
+        })))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = router(state.clone())
+        .oneshot(put(serde_json::json!({
+            "accessibility": { "theme_mode": 2, "font_scale": 1.25, "reduce_motion": true }
+        })))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Applied on the next login from any machine.
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/auth/login")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({
+                        "username": "a11y@example.com",
+                        "password": "SecurePass1!",
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let a11y = &json["data"]["user"]["preferences"]["accessibility"];
+    assert_eq!(a11y["theme_mode"], 2);
+    assert_eq!(a11y["font_scale"], 1.25);
+    assert_eq!(a11y["reduce_motion"], true);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 38. SESSION LISTING AND REVOCATION
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_list_and_revoke_own_sessions() {
+    let state = test_state().await;
+    let (first, _) = register_user_it(state.clone(), "sessions@example.com").await;
+    let (other_user, _) = register_user_it(state.clone(), "bystander@example.com").await;
+
+    // Second sign-in from another device
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/auth/login")
+                .header("content-type", "application/json")
+                .header("user-agent", "ParkHub-Desktop/1.0")
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({
+                        "username": "sessions@example.com",
+                        "password": "SecurePass1!",
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let second = body_json(resp).await["data"]["tokens"]["access_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let list = |tok: &str| {
+        Request::get("/api/v1/users/me/sessions")
+            .header("authorization", format!("Bearer {tok}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+    let revoke = |tok: &str, token_id: &str| {
+        Request::delete(format!("/api/v1/users/me/sessions/{token_id}"))
+            .header("authorization", format!("Bearer {tok}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let resp = router(state.clone()).oneshot(list(&first)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let sessions = body_json(resp).await["data"].as_array().unwrap().clone();
+    assert_eq!(sessions.len(), 2);
+    assert_eq!(sessions[0]["is_current"], true);
+    assert!(sessions[0]["last_used_at"].is_string());
+    assert_eq!(sessions[1]["is_current"], false);
+    assert_eq!(sessions[1]["user_agent"], "ParkHub-Desktop/1.0");
+    assert!(sessions[1]["ip_address"].is_string());
+    let second_id = sessions[1]["token_id"].as_str().unwrap().to_string();
+    assert!(!second.contains(&second_id));
+
+    // Another user cannot revoke it
+    let resp = router(state.clone())
+        .oneshot(revoke(&other_user, &second_id))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let resp = router(state.clone())
+        .oneshot(revoke(&first, &second_id))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // The revoked token stops working at once; the caller's keeps working
+    let resp = router(state.clone()).oneshot(list(&second)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let resp = router(state.clone()).oneshot(list(&first)).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["data"].as_array().unwrap().len(), 1);
+
+    let resp = router(state)
+        .oneshot(revoke(&first, &second_id))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 39. CONCURRENT SESSION LIMIT
+// ═════════════════════════════════════════════════════════════════════════════
+
+/// Log `email` in and return the response status and access token, if any.
+async fn login_it(state: Arc<RwLock<AppState>>, email: &str) -> (StatusCode, Option<String>) {
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/auth/login")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({
+                        "username": email,
+                        "password": "SecurePass1!",
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    let json = body_json(resp).await;
+    let token = json["data"]["tokens"]["access_token"]
+        .as_str()
+        .map(str::to_string);
+    (status, token)
+}
+
+/// Status of an authenticated request made with `token`.
+async fn auth_status_it(state: Arc<RwLock<AppState>>, token: &str) -> StatusCode {
+    router(state)
+        .oneshot(
+            Request::get("/api/v1/users/me")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_session_limit_evicts_oldest() {
+    let state = test_state().await;
+    state.write().await.config.max_concurrent_sessions = 2;
+    let (oldest, _) = register_user_it(state.clone(), "evict@example.com").await;
+
+    let (status, second) = login_it(state.clone(), "evict@example.com").await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, third) = login_it(state.clone(), "evict@example.com").await;
+    assert_eq!(status, StatusCode::OK);
+
+    assert_eq!(
+        auth_status_it(state.clone(), &oldest).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        auth_status_it(state.clone(), &second.unwrap()).await,
+        StatusCode::OK
+    );
+    assert_eq!(auth_status_it(state, &third.unwrap()).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_session_limit_rejects_new_login() {
+    let state = test_state().await;
+    {
+        let mut guard = state.write().await;
+        guard.config.max_concurrent_sessions = 1;
+        guard.config.session_limit_policy = crate::config::SessionLimitPolicy::Reject;
+    }
+    let (first, _) = register_user_it(state.clone(), "reject@example.com").await;
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/auth/login")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({
+                        "username": "reject@example.com",
+                        "password": "SecurePass1!",
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(
+        body_json(resp).await["error"]["code"],
+        "SESSION_LIMIT_REACHED"
+    );
+    assert_eq!(auth_status_it(state.clone(), &first).await, StatusCode::OK);
+
+    // Signing out frees the slot
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/auth/logout")
+                .header("authorization", format!("Bearer {first}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(resp.status().is_success());
+    let (status, _) = login_it(state, "reject@example.com").await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 40. ADMIN SEARCH
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_admin_search_finds_users() {
+    let state = test_state().await;
+    let admin = admin_token_it(state.clone()).await;
+    let (user_token, user_id) = register_user_it(state.clone(), "searchable@example.com").await;
+
+    let search = |token: String, query: &'static str| {
+        let state = state.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::get(format!("/api/v1/admin/search?{query}"))
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let resp = search(admin.clone(), "q=searchable&types=user").await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_json(resp).await;
+    let results = body["data"].as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["type"], "user");
+    assert_eq!(results[0]["id"], user_id);
+    assert_eq!(results[0]["subtitle"], "searchable@example.com");
+
+    let resp = search(admin.clone(), "q=searchable&types=vehicle").await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = search(admin, "q=%20").await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = search(user_token, "q=searchable").await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_admin_search_masks_plates_for_plain_admins() {
+    use parkhub_common::UserRole;
+
+    let state = test_state().await;
+    let super_admin = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &super_admin).await;
+    let (owner_tok, _) = register_user_it(state.clone(), "search-owner@example.com").await;
+    let (admin, admin_id) = register_user_it(state.clone(), "search-admin@example.com").await;
+    set_role_it(&state, &admin_id, UserRole::Admin).await;
+
+    let booking_body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": chrono::Utc::now() + TimeDelta::hours(1),
+        "duration_minutes": 60,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "M-AB 1234",
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {owner_tok}"))
+                .body(Body::from(serde_json::to_vec(&booking_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let booking_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let search = |token: String, q: String| {
+        let state = state.clone();
+        async move {
+            let resp = router(state)
+                .oneshot(
+                    Request::get(format!("/api/v1/admin/search?q={q}&types=booking"))
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), StatusCode::OK);
+            body_json(resp).await["data"].as_array().unwrap().clone()
+        }
+    };
+
+    state.write().await.config.license_plate_display = 1;
+    let results = search(admin.clone(), booking_id.clone()).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["title"], "M-A* ****");
+    // A plate match would confirm the plate
+    assert!(
+        search(admin.clone(), "mab1234".to_string())
+            .await
+            .is_empty()
+    );
+
+    state.write().await.config.license_plate_display = 3;
+    let results = search(admin.clone(), booking_id.clone()).await;
+    assert_eq!(results[0]["title"], "");
+
+    // Super-admins see and search plates in full
+    let results = search(super_admin.clone(), booking_id).await;
+    assert_eq!(results[0]["title"], "M-AB 1234");
+    let results = search(super_admin, "mab1234".to_string()).await;
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["title"], "M-AB 1234");
+
+    state.write().await.config.license_plate_display = 0;
+    assert_eq!(search(admin, "mab1234".to_string()).await.len(), 1);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 41. INVOICE ARCHIVE
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_issued_invoice_is_served_from_archive() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "archive@example.com").await;
+    let (other_tok, _) = register_user_it(state.clone(), "other@example.com").await;
+
+    let body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": chrono::Utc::now() + TimeDelta::hours(1),
+        "duration_minutes": 60,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "ARC-1",
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let booking_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let get = |token: String, uri: String| {
+        let state = state.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::get(uri)
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let resp = get(
+        user_tok.clone(),
+        format!("/api/v1/bookings/{booking_id}/invoice"),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let issued = String::from_utf8(body_bytes(resp).await).unwrap();
+
+    // Price and organisation change after issue
+    {
+        let mut guard = state.write().await;
+        guard.config.organization_name = "Renamed GmbH".to_string();
+        let mut booking = guard.db.get_booking(&booking_id).await.unwrap().unwrap();
+        booking.pricing.base_price = 99.0;
+        guard.db.save_booking(&booking).await.unwrap();
+    }
+
+    let resp = get(
+        user_tok.clone(),
+        format!("/api/v1/bookings/{booking_id}/invoice"),
+    )
+    .await;
+    let again = String::from_utf8(body_bytes(resp).await).unwrap();
+    assert_eq!(again, issued);
+    assert!(!again.contains("Renamed GmbH"));
+
+    let resp = get(user_tok.clone(), "/api/v1/users/me/invoices".to_string()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let list = body_json(resp).await["data"].clone();
+    let list = list.as_array().unwrap();
+    assert_eq!(list.len(), 1);
+    assert_eq!(list[0]["booking_id"], booking_id.as_str());
+    assert!(list[0]["net"].as_f64().unwrap() < 99.0);
+    let number = list[0]["invoice_number"].as_str().unwrap().to_string();
+
+    let resp = get(user_tok, format!("/api/v1/users/me/invoices/{number}")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(String::from_utf8(body_bytes(resp).await).unwrap(), issued);
+
+    // Other users neither see nor fetch it
+    let resp = get(other_tok.clone(), "/api/v1/users/me/invoices".to_string()).await;
+    assert!(body_json(resp).await["data"].as_array().unwrap().is_empty());
+    let resp = get(other_tok, format!("/api/v1/users/me/invoices/{number}")).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 42. LATE-BINDING SLOT ASSIGNMENT
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_late_binding_lot_assigns_slot_before_start() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, _) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, user_id) = register_user_it(state.clone(), "late@example.com").await;
+
+    let book = |body: serde_json::Value| {
+        let state = state.clone();
+        let user_tok = user_tok.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post("/api/v1/bookings")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {user_tok}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+    let soon = chrono::Utc::now() + TimeDelta::hours(1);
+    let later = soon + TimeDelta::hours(3);
+    let by_type = |start: chrono::DateTime<chrono::Utc>| {
+        serde_json::json!({
+            "lot_id": lot_id,
+            "slot_type": "standard",
+            "start_time": start,
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "LATE-1",
+        })
+    };
+
+    // Fixed-slot lots do not take slot types
+    let resp = book(by_type(soon)).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put(format!("/api/v1/lots/{lot_id}/rules"))
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(r#"{"slot_assignment":"late_binding"}"#))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Leave a single bookable slot
+    {
+        let guard = state.read().await;
+        let slots = guard.db.list_slots_by_lot(&lot_id).await.unwrap();
+        for mut slot in slots.into_iter().skip(1) {
+            slot.status = parkhub_common::SlotStatus::Maintenance;
+            guard.db.save_parking_slot(&slot).await.unwrap();
+        }
+    }
+
+    let resp = book(by_type(soon)).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let first = body_json(resp).await["data"].clone();
+    assert_eq!(first["slot_number"], 0);
+    assert_eq!(first["slot_id"], Uuid::nil().to_string());
+    let first_id = first["id"].as_str().unwrap().to_string();
+
+    let resp = book(by_type(soon + TimeDelta::minutes(30))).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(body_json(resp).await["error"]["code"], "NO_SLOT_AVAILABLE");
+
+    let resp = book(by_type(later)).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let second_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Check-in assigns the slot right away
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post(format!("/api/v1/bookings/{first_id}/checkin"))
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let checked_in = body_json(resp).await["data"].clone();
+    assert!(checked_in["slot_number"].as_i64().unwrap() > 0);
+
+    // The job assigns the other booking once its lead time is reached
+    let db = state.read().await.db.clone();
+    let assign = crate::api::slot_assignment::assign_due_slots;
+    assert_eq!(assign(&db, chrono::Utc::now()).await.unwrap(), 0);
+    assert_eq!(assign(&db, later).await.unwrap(), 1);
+    let second = db.get_booking(&second_id).await.unwrap().unwrap();
+    assert_eq!(
+        second.slot_id.to_string(),
+        checked_in["slot_id"].as_str().unwrap()
+    );
+
+    let notes = db.list_notifications_by_user(&user_id).await.unwrap();
+    assert_eq!(
+        notes.iter().filter(|n| n.title == "Slot assigned").count(),
+        2
+    );
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 43. PERMISSION MATRIX
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_permission_matrix_gates_admin_capabilities() {
+    let state = test_state().await;
+    let admin = admin_token_it(state.clone()).await;
+    let (user_token, _) = register_user_it(state.clone(), "no-perms@example.com").await;
+    let (lot_id, _) = setup_lot_and_slot(state.clone(), &admin).await;
+
+    let call = |method: &'static str, uri: String, token: String| {
+        let state = state.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::builder()
+                        .method(method)
+                        .uri(uri)
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+                .status()
+        }
+    };
+
+    // manage_users, view_all_bookings, manage_config
+    for uri in [
+        "/api/v1/admin/users",
+        "/api/v1/admin/stats",
+        "/api/v1/admin/settings",
+    ] {
+        assert_eq!(
+            call("GET", uri.to_string(), user_token.clone()).await,
+            StatusCode::FORBIDDEN,
+            "{uri}"
+        );
+        assert_eq!(
+            call("GET", uri.to_string(), admin.clone()).await,
+            StatusCode::OK,
+            "{uri}"
+        );
+    }
+
+    // manage_lots
+    let lot_uri = format!("/api/v1/lots/{lot_id}");
+    assert_eq!(
+        call("DELETE", lot_uri.clone(), user_token).await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(call("DELETE", lot_uri, admin).await, StatusCode::OK);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 44. CUSTOM ROLES
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_custom_role_scopes_permissions_to_its_lots() {
+    let state = test_state().await;
+    let admin = admin_token_it(state.clone()).await;
+    let (staff, staff_id) = register_user_it(state.clone(), "lot-staff@example.com").await;
+    let (lot_a, slot_a) = setup_lot_and_slot(state.clone(), &admin).await;
+    let (lot_b, slot_b) = setup_lot_and_slot(state.clone(), &admin).await;
+
+    let call = |method: &'static str, uri: String, token: String, body: serde_json::Value| {
+        let state = state.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::builder()
+                        .method(method)
+                        .uri(uri)
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    // The admin books a slot in each lot
+    let start_time = (chrono::Utc::now() + TimeDelta::days(1))
+        .date_naive()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+        .and_utc();
+    let mut bookings = Vec::new();
+    for (lot_id, slot_id) in [(&lot_a, &slot_a), (&lot_b, &slot_b)] {
+        let resp = call(
+            "POST",
+            "/api/v1/bookings".to_string(),
+            admin.clone(),
+            serde_json::json!({
+                "lot_id": lot_id,
+                "slot_id": slot_id,
+                "start_time": start_time,
+                "duration_minutes": 60,
+                "vehicle_id": Uuid::nil(),
+                "license_plate": "ROLE-001",
+            }),
+        )
+        .await;
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let id = body_json(resp).await["data"]["id"]
+            .as_str()
+            .unwrap()
+            .to_string();
+        bookings.push(id);
+    }
+    let (booking_a, booking_b) = (&bookings[0], &bookings[1]);
+
+    // Custom roles only grant lot-scoped permissions
+    let resp = call(
+        "POST",
+        "/api/v1/admin/custom-roles".to_string(),
+        admin.clone(),
+        serde_json::json!({"name": "Too much", "permissions": ["manage_users"]}),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    let resp = call(
+        "POST",
+        "/api/v1/admin/custom-roles".to_string(),
+        admin.clone(),
+        serde_json::json!({
+            "name": "Lot A staff",
+            "permissions": ["manage_lots", "manage_bookings"],
+            "lot_ids": [lot_a],
+        }),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let role_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Before the assignment, the user may not touch lot A
+    let slots_a = format!("/api/v1/lots/{lot_a}/slots");
+    let slots_b = format!("/api/v1/lots/{lot_b}/slots");
+    let none = serde_json::json!({});
+    let resp = call("POST", slots_a.clone(), staff.clone(), none.clone()).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = call(
+        "POST",
+        format!("/api/v1/admin/custom-roles/{role_id}/members"),
+        admin.clone(),
+        serde_json::json!({"user_id": staff_id}),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = call(
+        "GET",
+        "/api/v1/users/me/permissions".to_string(),
+        staff.clone(),
+        none.clone(),
+    )
+    .await;
+    let grants = body_json(resp).await["data"]["grants"].clone();
+    assert_eq!(grants["manage_bookings"]["lots"][0], lot_a.as_str());
+    assert!(grants["manage_users"].is_null());
+
+    // Slots: lot A only
+    let resp = call("POST", slots_a.clone(), staff.clone(), none.clone()).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let resp = call("POST", slots_b, staff.clone(), none.clone()).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Lots themselves still need the permission on every lot
+    let resp = call(
+        "DELETE",
+        format!("/api/v1/lots/{lot_a}"),
+        staff.clone(),
+        none.clone(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Other users' bookings: lot A only
+    let resp = call(
+        "GET",
+        format!("/api/v1/bookings/{booking_a}"),
+        staff.clone(),
+        none.clone(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = call(
+        "DELETE",
+        format!("/api/v1/bookings/{booking_b}"),
+        staff.clone(),
+        none.clone(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let resp = call(
+        "DELETE",
+        format!("/api/v1/bookings/{booking_a}"),
+        staff.clone(),
+        none.clone(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // Custom roles do not open the admin area
+    let resp = call(
+        "GET",
+        "/api/v1/admin/users".to_string(),
+        staff.clone(),
+        none.clone(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Deleting the role revokes its grants on the next request
+    let resp = call(
+        "DELETE",
+        format!("/api/v1/admin/custom-roles/{role_id}"),
+        admin,
+        none.clone(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = call("POST", slots_a, staff, none).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 45. PASSKEYS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_passkey_ceremonies() {
+    let state = test_state().await;
+    let (token, _) = register_user_it(state.clone(), "passkey@example.com").await;
+
+    let call = |uri: &'static str, token: Option<String>, body: serde_json::Value| {
+        let state = state.clone();
+        async move {
+            let mut req = Request::post(uri).header("content-type", "application/json");
+            if let Some(token) = token {
+                req = req.header("authorization", format!("Bearer {token}"));
+            }
+            router(state)
+                .oneshot(
+                    req.body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    // Registration needs a session and returns creation options
+    let start = "/api/v1/auth/webauthn/register/start";
+    let resp = call(start, None, serde_json::json!({})).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let resp = call(start, Some(token.clone()), serde_json::json!({})).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let data = body_json(resp).await["data"].clone();
+    assert!(!data["challenge_id"].as_str().unwrap().is_empty());
+    assert!(data["options"]["publicKey"]["challenge"].is_string());
+    assert_eq!(data["options"]["publicKey"]["user"]["name"], "passkey");
+
+    // Nothing is registered yet; a passkey-less account and an unknown one
+    // get the same kind of challenge, naming a credential no one holds
+    let login_start = |username: &'static str| {
+        let call = &call;
+        async move {
+            let resp = call(
+                "/api/v1/auth/webauthn/login/start",
+                None,
+                serde_json::json!({ "username": username }),
+            )
+            .await;
+            assert_eq!(resp.status(), StatusCode::OK);
+            body_json(resp).await["data"].clone()
+        }
+    };
+    let allowed = |data: &serde_json::Value| {
+        let allowed = data["options"]["publicKey"]["allowCredentials"].clone();
+        assert_eq!(allowed.as_array().unwrap().len(), 1);
+        allowed[0]["id"].as_str().unwrap().to_string()
+    };
+    let existing = login_start("passkey").await;
+    let unknown = login_start("nobody").await;
+    assert!(existing["options"]["publicKey"]["challenge"].is_string());
+    assert_eq!(
+        existing["options"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        unknown["options"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>()
+    );
+    // Stable per username, like a real credential
+    assert_eq!(allowed(&unknown), allowed(&login_start("nobody").await));
+    assert_ne!(allowed(&existing), allowed(&unknown));
+
+    // Whatever comes back is refused like a rejected passkey. Ceremonies
+    // live in the router's passkey service, so both calls share one.
+    let app = router(state.clone());
+    let post = |uri: &str, body: serde_json::Value| {
+        Request::post(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+    let resp = app
+        .clone()
+        .oneshot(post(
+            "/api/v1/auth/webauthn/login/start",
+            serde_json::json!({ "username": "nobody" }),
+        ))
+        .await
+        .unwrap();
+    let unknown = body_json(resp).await["data"].clone();
+    let resp = app
+        .oneshot(post(
+            "/api/v1/auth/webauthn/login/finish",
+            serde_json::json!({
+                "challenge_id": unknown["challenge_id"],
+                "credential": {
+                    "id": allowed(&unknown),
+                    "rawId": allowed(&unknown),
+                    "type": "public-key",
+                    "response": {
+                        "authenticatorData": "AA",
+                        "clientDataJSON": "AA",
+                        "signature": "AA",
+                    },
+                    "extensions": {},
+                },
+            }),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        body_json(resp).await["error"]["code"],
+        "INVALID_CREDENTIALS"
+    );
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/auth/webauthn/credentials")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["data"], serde_json::json!([]));
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::delete(format!(
+                "/api/v1/auth/webauthn/credentials/{}",
+                Uuid::new_v4()
+            ))
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 46. SESSION IDLE TIMEOUT
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_idle_session_is_ended() {
+    let state = test_state().await;
+    state.write().await.config.session_timeout_minutes = 30;
+    let (idle, user_id) = register_user_it(state.clone(), "idle@example.com").await;
+    let (_, active) = login_it(state.clone(), "idle@example.com").await;
+    let active = active.unwrap();
+
+    // Backdate the first session past the idle timeout; the absolute
+    // lifetime has hours left
+    {
+        let guard = state.read().await;
+        let (session_id, mut session) = guard
+            .db
+            .list_sessions_by_user(user_id.parse().unwrap())
+            .await
+            .unwrap()
+            .remove(0);
+        session.created_at = chrono::Utc::now() - TimeDelta::hours(2);
+        assert!(!session.is_expired());
+        guard.db.save_session(&session_id, &session).await.unwrap();
+    }
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/users/me")
+                .header("authorization", format!("Bearer {idle}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        body_json(resp).await["error"]["code"],
+        "SESSION_IDLE_TIMEOUT"
+    );
+    // Ended for good, and the user's other session is unaffected
+    assert_eq!(
+        auth_status_it(state.clone(), &idle).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(auth_status_it(state, &active).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_token_outliving_its_session_is_refused_as_revoked() {
+    let state = test_state().await;
+    let (token, user_id) = register_user_it(state.clone(), "gone@example.com").await;
+
+    // A session ended while its token family was not on the revocation
+    // list, as after a restart
+    {
+        let guard = state.read().await;
+        let (session_id, _) = guard
+            .db
+            .list_sessions_by_user(user_id.parse().unwrap())
+            .await
+            .unwrap()
+            .remove(0);
+        guard.db.delete_session(&session_id).await.unwrap();
+    }
+
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/users/me")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(body_json(resp).await["error"]["code"], "SESSION_REVOKED");
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 47. BRUTE-FORCE PROTECTION
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_repeated_login_failures_delay_the_account() {
+    let state = test_state().await;
+    register_user_it(state.clone(), "guarded@example.com").await;
+    // One router, so the failure counts carry over between requests
+    let app = router(state);
+    let attempt = |password: &str| {
+        Request::post("/api/v1/auth/login")
+            .header("content-type", "application/json")
+            .body(Body::from(
+                serde_json::to_vec(&serde_json::json!({
+                    "username": "guarded@example.com",
+                    "password": password,
+                }))
+                .unwrap(),
+            ))
+            .unwrap()
+    };
+
+    for _ in 0..crate::api::auth_guard::FREE_FAILURES {
+        let resp = app.clone().oneshot(attempt("WrongPass1!")).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    // Even the right password has to wait for the delay to run out
+    let resp = app.oneshot(attempt("SecurePass1!")).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(resp.headers()["retry-after"], "1");
+    assert_eq!(body_json(resp).await["error"]["code"], "TOO_MANY_ATTEMPTS");
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 48. ROLE CHANGES
+// ═════════════════════════════════════════════════════════════════════════════
+
+/// Set the built-in role of `user_id` directly in the database.
+async fn set_role_it(state: &Arc<RwLock<AppState>>, user_id: &str, role: parkhub_common::UserRole) {
+    let guard = state.write().await;
+    let mut user = guard.db.get_user(user_id).await.unwrap().unwrap();
+    user.role = role;
+    guard.db.save_user(&user).await.unwrap();
+}
+
+/// Status of `GET /api/v1/admin/users` for `token`.
+async fn admin_users_status_it(state: Arc<RwLock<AppState>>, token: &str) -> StatusCode {
+    router(state)
+        .oneshot(
+            Request::get("/api/v1/admin/users")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_role_change_applies_to_existing_session() {
+    use parkhub_common::UserRole;
+
+    let state = test_state().await;
+    let (token, user_id) = register_user_it(state.clone(), "promoted@example.com").await;
+    assert_eq!(
+        admin_users_status_it(state.clone(), &token).await,
+        StatusCode::FORBIDDEN
+    );
+
+    // The role is read on every request, so the same token picks it up
+    set_role_it(&state, &user_id, UserRole::Admin).await;
+    assert_eq!(
+        admin_users_status_it(state.clone(), &token).await,
+        StatusCode::OK
+    );
+
+    set_role_it(&state, &user_id, UserRole::User).await;
+    assert_eq!(
+        admin_users_status_it(state.clone(), &token).await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 49. IMPERSONATION
+// ═════════════════════════════════════════════════════════════════════════════
+
+/// `POST /api/v1/admin/users/{user_id}/impersonate` as `token`.
+async fn impersonate_it(
+    state: Arc<RwLock<AppState>>,
+    token: &str,
+    user_id: &str,
+) -> (StatusCode, serde_json::Value) {
+    let resp = router(state)
+        .oneshot(
+            Request::post(format!("/api/v1/admin/users/{user_id}/impersonate"))
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+#[tokio::test]
+async fn test_impersonation_acts_as_user_and_is_audited() {
+    let state = test_state().await;
+    let admin_token = admin_token_it(state.clone()).await;
+    let (_, user_id) = register_user_it(state.clone(), "supported@example.com").await;
+
+    let (status, body) = impersonate_it(state.clone(), &admin_token, &user_id).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["data"]["user"]["id"], user_id.as_str());
+    let token = body["data"]["access_token"].as_str().unwrap().to_string();
+    let admin_id = body["data"]["impersonator_id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // The token acts as the user, with the user's privileges
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/users/me")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["data"]["id"], user_id.as_str());
+    assert_eq!(
+        admin_users_status_it(state.clone(), &token).await,
+        StatusCode::FORBIDDEN
+    );
+
+    let entries = state.read().await.db.list_audit_log(100).await.unwrap();
+    let started = entries
+        .iter()
+        .find(|e| e.event_type == "ImpersonationStarted")
+        .expect("impersonation start audited");
+    assert_eq!(
+        started.user_id.map(|id| id.to_string()),
+        Some(admin_id.clone())
+    );
+    assert_eq!(started.target_id.as_deref(), Some(user_id.as_str()));
+    let requests: Vec<_> = entries
+        .iter()
+        .filter(|e| e.event_type == "ImpersonatedRequest")
+        .collect();
+    assert_eq!(requests.len(), 2);
+    for entry in requests {
+        assert_eq!(
+            entry.user_id.map(|id| id.to_string()),
+            Some(user_id.clone())
+        );
+        assert!(entry.details.as_deref().unwrap().contains(&admin_id));
+    }
+}
+
+#[tokio::test]
+async fn test_impersonation_requires_admin_and_another_user() {
+    let state = test_state().await;
+    let admin_token = admin_token_it(state.clone()).await;
+    let (user_token, user_id) = register_user_it(state.clone(), "curious@example.com").await;
+    let admin_id = state
+        .read()
+        .await
+        .db
+        .get_user_by_username("admin")
+        .await
+        .unwrap()
+        .unwrap()
+        .id
+        .to_string();
+
+    let (status, _) = impersonate_it(state.clone(), &user_token, &admin_id).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = impersonate_it(state.clone(), &admin_token, &admin_id).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) =
+        impersonate_it(state.clone(), &admin_token, &Uuid::new_v4().to_string()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Impersonation tokens cannot be refreshed
+    let (status, _) = impersonate_it(state.clone(), &admin_token, &user_id).await;
+    assert_eq!(status, StatusCode::OK);
+    let guard = state.read().await;
+    let sessions = guard
+        .db
+        .list_sessions_by_user(user_id.parse().unwrap())
+        .await
+        .unwrap();
+    let (_, session) = sessions
+        .iter()
+        .find(|(_, s)| s.impersonator_id.is_some())
+        .expect("impersonation session stored");
+    assert!(session.expires_at <= chrono::Utc::now() + chrono::Duration::minutes(30));
+    let refresh_token = session.refresh_token.clone();
+    drop(guard);
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/auth/refresh")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({ "refresh_token": refresh_token }))
+                        .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 50. INVITATIONS
+// ═════════════════════════════════════════════════════════════════════════════
+
+/// `POST /api/v1/admin/invites` as `token`.
+async fn invite_it(
+    state: Arc<RwLock<AppState>>,
+    token: &str,
+    body: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/admin/invites")
+                .header("authorization", format!("Bearer {token}"))
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+/// `POST /api/v1/auth/accept-invite` for `invite_token` with `password`.
+async fn accept_invite_it(
+    state: Arc<RwLock<AppState>>,
+    invite_token: &str,
+    password: &str,
+) -> (StatusCode, serde_json::Value) {
+    let body = serde_json::json!({
+        "token": invite_token,
+        "name": "Invited User",
+        "password": "Invited123",
+        "password_confirmation": password,
+    });
+    let resp = router(state)
+        .oneshot(
+            Request::post("/api/v1/auth/accept-invite")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+/// The one-time token in an invite's signup link.
+fn invite_token_of(body: &serde_json::Value) -> String {
+    let url = body["data"]["invite_url"].as_str().unwrap();
+    url.split_once("token=").unwrap().1.to_string()
+}
+
+#[tokio::test]
+async fn test_invite_creates_account_with_assigned_role() {
+    let state = test_state().await;
+    // Invitations work with self-registration turned off
+    state.write().await.config.allow_self_registration = false;
+    let admin_token = admin_token_it(state.clone()).await;
+
+    let (status, body) = invite_it(
+        state.clone(),
+        &admin_token,
+        serde_json::json!({ "email": "invited@example.com", "role": "premium" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["role"], "premium");
+    assert_eq!(body["data"]["expired"], false);
+    let invite_token = invite_token_of(&body);
+
+    // A failed attempt does not use up the invitation
+    let (status, body) = accept_invite_it(state.clone(), &invite_token, "Mismatch123").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "PASSWORD_MISMATCH");
+
+    let (status, body) = accept_invite_it(state.clone(), &invite_token, "Invited123").await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(body["data"]["user"]["email"], "invited@example.com");
+    assert_eq!(body["data"]["user"]["role"], "premium");
+    assert!(body["data"]["tokens"]["access_token"].is_string());
+
+    // The link works once
+    let (status, body) = accept_invite_it(state.clone(), &invite_token, "Invited123").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "INVALID_TOKEN");
+
+    let guard = state.read().await;
+    assert!(guard.db.list_invites().await.unwrap().is_empty());
+    let entries = guard.db.list_audit_log(100).await.unwrap();
+    assert!(entries.iter().any(|e| e.event_type == "InviteCreated"));
+}
+
+#[tokio::test]
+async fn test_invite_requires_admin_and_can_be_revoked() {
+    let state = test_state().await;
+    let admin_token = admin_token_it(state.clone()).await;
+    let (user_token, _) = register_user_it(state.clone(), "member@example.com").await;
+
+    let (status, _) = invite_it(
+        state.clone(),
+        &user_token,
+        serde_json::json!({ "email": "friend@example.com" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, body) = invite_it(
+        state.clone(),
+        &admin_token,
+        serde_json::json!({ "email": "member@example.com" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body["error"]["code"], "EMAIL_EXISTS");
+
+    let (status, body) = invite_it(
+        state.clone(),
+        &admin_token,
+        serde_json::json!({ "email": "friend@example.com", "role": "root" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error"]["code"], "INVALID_INPUT");
+
+    let (status, body) = invite_it(
+        state.clone(),
+        &admin_token,
+        serde_json::json!({ "email": "friend@example.com" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let invite_id = body["data"]["id"].as_str().unwrap().to_string();
+    let invite_token = invite_token_of(&body);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::delete(format!("/api/v1/admin/invites/{invite_id}"))
+                .header("authorization", format!("Bearer {admin_token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let (status, _) = accept_invite_it(state, &invite_token, "Invited123").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 51. VERSION ENDPOINT
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_version_endpoint_describes_the_build() {
+    let state = test_state().await;
+    let resp = router(state)
+        .oneshot(Request::get("/api/v1/version").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let data = &json["data"];
+    assert_eq!(data["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(data["protocol_version"], parkhub_common::PROTOCOL_VERSION);
+    assert_eq!(data["storage_backend"], "redb");
+    assert!(!data["git_hash"].as_str().unwrap().is_empty());
+    let features = data["features"].as_array().unwrap();
+    assert!(features.iter().any(|f| f == "bookings"));
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 52. BOOKING TAGS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_booking_tags_flow_into_exports_and_revenue_report() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+
+    let tag_sets = serde_json::json!([
+        {"key": "cost_center", "label": "Cost center", "values": ["CC-100", "CC-200"]},
+        {"key": "project", "label": "Project"},
+    ]);
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put("/api/v1/admin/booking-tags")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&tag_sets).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let (user_tok, _) = register_user_it(state.clone(), "tagger@example.com").await;
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/bookings/tags")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"][0]["values"][1], "CC-200");
+
+    let start_time = (chrono::Utc::now() + TimeDelta::days(1))
+        .date_naive()
+        .and_hms_opt(9, 0, 0)
+        .unwrap()
+        .and_utc();
+    let book = |tags: serde_json::Value| {
+        let state = state.clone();
+        let user_tok = user_tok.clone();
+        let body = serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": start_time,
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "TAG-001",
+            "tags": tags,
+        });
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post("/api/v1/bookings")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {user_tok}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let resp = book(serde_json::json!({"cost_center": "CC-999"})).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(body_json(resp).await["error"]["code"], "INVALID_TAGS");
+
+    let resp = book(serde_json::json!({"cost_center": "cc-100", "project": " Apollo "})).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let booking = body_json(resp).await["data"].clone();
+    assert_eq!(booking["tags"]["cost_center"], "CC-100");
+    assert_eq!(booking["tags"]["project"], "Apollo");
+    let booking_id = booking["id"].as_str().unwrap().to_string();
+
+    // The owner can retag the booking; unknown tags are still rejected
+    let patch = |tags: serde_json::Value| {
+        let state = state.clone();
+        let user_tok = user_tok.clone();
+        let booking_id = booking_id.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::patch(format!("/api/v1/bookings/{booking_id}"))
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {user_tok}"))
+                        .body(Body::from(
+                            serde_json::to_vec(&serde_json::json!({"tags": tags})).unwrap(),
+                        ))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+    let resp = patch(serde_json::json!({"team": "ops"})).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = patch(serde_json::json!({"cost_center": "CC-200"})).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(
+        json["data"]["tags"],
+        serde_json::json!({"cost_center": "CC-200"})
+    );
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/admin/reports/revenue?group_by=tag:cost_center")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let data = body_json(resp).await["data"].clone();
+    assert_eq!(data["group_by"], "tag:cost_center");
+    let rows = data["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["key"], "CC-200");
+    assert_eq!(rows[0]["booking_count"], 1);
+
+    let resp = router(state)
+        .oneshot(
+            Request::get("/api/v1/admin/export/bookings")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let csv = String::from_utf8(body_bytes(resp).await).unwrap();
+    let mut lines = csv.lines();
+    assert!(
+        lines
+            .next()
+            .unwrap()
+            .ends_with(",payment_status,tag:cost_center,tag:project")
+    );
+    assert!(lines.next().unwrap().ends_with(",CC-200,"));
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 53. REFRESH TOKEN ROTATION
+// ═════════════════════════════════════════════════════════════════════════════
+
+async fn refresh_it(state: Arc<RwLock<AppState>>, refresh_token: &str) -> axum::response::Response {
+    let body = serde_json::json!({ "refresh_token": refresh_token });
+    router(state)
+        .oneshot(
+            Request::post("/api/v1/auth/refresh")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn me_status_it(state: Arc<RwLock<AppState>>, access_token: &str) -> StatusCode {
+    router(state)
+        .oneshot(
+            Request::get("/api/v1/users/me")
+                .header("authorization", format!("Bearer {access_token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_reused_refresh_token_revokes_all_sessions() {
+    let state = test_state().await;
+
+    let login = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/auth/login")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({
+                        "username": "admin",
+                        "password": "admin123",
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let json = body_json(login).await;
+    let first_refresh = json["data"]["tokens"]["refresh_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let first_access = json["data"]["tokens"]["access_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // A refresh hands out a new pair and retires the old one
+    let resp = refresh_it(state.clone(), &first_refresh).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let second_refresh = json["data"]["refresh_token"].as_str().unwrap().to_string();
+    let second_access = json["data"]["access_token"].as_str().unwrap().to_string();
+    assert_ne!(second_refresh, first_refresh);
+    assert_eq!(
+        me_status_it(state.clone(), &first_access).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        me_status_it(state.clone(), &second_access).await,
+        StatusCode::OK
+    );
+
+    // Replaying the spent token signs the user out everywhere
+    let resp = refresh_it(state.clone(), &first_refresh).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let json = body_json(resp).await;
+    assert_eq!(json["error"]["code"], "REFRESH_TOKEN_REUSED");
+    assert_eq!(
+        me_status_it(state.clone(), &second_access).await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    let resp = refresh_it(state.clone(), &second_refresh).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let json = body_json(resp).await;
+    assert_eq!(json["error"]["code"], "REFRESH_TOKEN_REUSED");
+
+    let entries = state.read().await.db.list_audit_log(100).await.unwrap();
+    assert!(entries.iter().any(|e| e.event_type == "RefreshTokenReused"));
+}
+
+#[tokio::test]
+async fn test_unknown_refresh_token_is_not_reuse() {
+    let state = test_state().await;
+    let resp = refresh_it(state, "not-a-refresh-token").await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let json = body_json(resp).await;
+    assert_eq!(json["error"]["code"], "INVALID_REFRESH_TOKEN");
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 54. REPORTER ROLE
+// ═════════════════════════════════════════════════════════════════════════════
+
+/// Status of `method path` with `token` and an empty JSON body.
+async fn status_it(
+    state: Arc<RwLock<AppState>>,
+    token: &str,
+    method: &str,
+    path: &str,
+) -> StatusCode {
+    router(state)
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri(path)
+                .header("authorization", format!("Bearer {token}"))
+                .header("content-type", "application/json")
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn test_reporter_reads_reports_but_cannot_modify() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, _) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (token, user_id) = register_user_it(state.clone(), "finance@example.com").await;
+    let (_, other_id) = register_user_it(state.clone(), "driver@example.com").await;
+    set_role_it(&state, &user_id, parkhub_common::UserRole::Reporter).await;
+
+    for path in [
+        "/api/v1/admin/bookings",
+        "/api/v1/admin/stats",
+        "/api/v1/admin/reports",
+        "/api/v1/admin/reports/revenue",
+        "/api/v1/admin/reports/occupancy",
+        "/api/v1/admin/dashboard/charts",
+        "/api/v1/admin/analytics/overview",
+        "/api/v1/admin/analytics/revenue",
+        "/api/v1/admin/export/bookings",
+        "/api/v1/admin/export/revenue",
+        "/api/v1/admin/data/export/bookings",
+    ] {
+        assert_eq!(
+            status_it(state.clone(), &token, "GET", path).await,
+            StatusCode::OK,
+            "GET {path}"
+        );
+    }
+
+    for (method, path) in [
+        ("GET", "/api/v1/admin/users".to_string()),
+        ("GET", "/api/v1/admin/export/users".to_string()),
+        ("PATCH", format!("/api/v1/admin/users/{other_id}/status")),
+        ("DELETE", format!("/api/v1/admin/users/{other_id}")),
+        ("PUT", format!("/api/v1/lots/{lot_id}")),
+        ("DELETE", format!("/api/v1/lots/{lot_id}")),
+        ("PUT", "/api/v1/admin/settings/booking-policies".to_string()),
+    ] {
+        assert_eq!(
+            status_it(state.clone(), &token, method, &path).await,
+            StatusCode::FORBIDDEN,
+            "{method} {path}"
+        );
+    }
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 55. TIME-WINDOW AVAILABILITY
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_lot_availability_checks_overlapping_bookings() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "planner@example.com").await;
+
+    let tomorrow = chrono::Utc::now().date_naive() + chrono::Days::new(1);
+    let at = |hour: u32| {
+        tomorrow
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+            .and_utc()
+            .format("%Y-%m-%dT%H:%M:%SZ")
+            .to_string()
+    };
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({
+                        "lot_id": lot_id,
+                        "slot_id": slot_id,
+                        "start_time": at(10),
+                        "duration_minutes": 120,
+                        "vehicle_id": Uuid::nil(),
+                        "license_plate": "PLAN-1",
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let availability = |lot: String, start: String, end: String| {
+        let state = state.clone();
+        let user_tok = user_tok.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::get(format!(
+                        "/api/v1/lots/{lot}/availability?start={start}&end={end}"
+                    ))
+                    .header("authorization", format!("Bearer {user_tok}"))
+                    .body(Body::empty())
+                    .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+    let slot_entry = |body: &serde_json::Value| {
+        body["data"]["slots"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|s| s["slot_id"] == slot_id)
+            .cloned()
+            .unwrap()
+    };
+
+    let resp = availability(lot_id.clone(), at(11), at(13)).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_json(resp).await;
+    assert_eq!(body["data"]["total_slots"], 5);
+    assert_eq!(body["data"]["available_slots"], 4);
+    assert_eq!(body["data"]["bookable"], true);
+    let slot = slot_entry(&body);
+    assert_eq!(slot["available"], false);
+    assert_eq!(slot["booked"].as_array().unwrap().len(), 1);
+
+    // The slot is reserved now, but free again once the booking ends
+    let body = body_json(availability(lot_id.clone(), at(12), at(14)).await).await;
+    assert_eq!(body["data"]["available_slots"], 5);
+    assert_eq!(slot_entry(&body)["available"], true);
+
+    let resp = availability(lot_id.clone(), at(14), at(12)).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = availability(Uuid::new_v4().to_string(), at(12), at(14)).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 56. BOOKING CONFLICTS BY TIME OVERLAP
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_same_slot_bookable_at_different_times() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "shifts@example.com").await;
+
+    let tomorrow = chrono::Utc::now().date_naive() + chrono::Days::new(1);
+    let book = |hour: u32, minutes: i32| {
+        let state = state.clone();
+        let user_tok = user_tok.clone();
+        let body = serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": tomorrow.and_hms_opt(hour, 0, 0).unwrap().and_utc(),
+            "duration_minutes": minutes,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "SHIFT-1",
+        });
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post("/api/v1/bookings")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {user_tok}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+    let slot_status = || {
+        let state = state.clone();
+        let slot_id = slot_id.clone();
+        async move {
+            let guard = state.read().await;
+            guard
+                .db
+                .get_parking_slot(&slot_id)
+                .await
+                .unwrap()
+                .unwrap()
+                .status
+        }
+    };
+
+    // Morning and afternoon on the same slot, back to back
+    let resp = book(8, 240).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let morning = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = book(12, 240).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    assert_eq!(slot_status().await, parkhub_common::SlotStatus::Reserved);
+
+    // Overlapping either one is a conflict, with alternatives free then
+    let resp = book(11, 120).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    let json = body_json(resp).await;
+    assert_eq!(json["error"]["code"], "SLOT_UNAVAILABLE");
+    let alternatives = json["error"]["details"]["alternatives"].as_array().unwrap();
+    assert!(!alternatives.is_empty());
+    assert!(
+        alternatives
+            .iter()
+            .all(|a| a["slot_id"] != slot_id.as_str())
+    );
+
+    // Cancelling one booking keeps the slot reserved for the other
+    let resp = router(state.clone())
+        .oneshot(
+            Request::delete(format!("/api/v1/bookings/{morning}"))
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(slot_status().await, parkhub_common::SlotStatus::Reserved);
+    let resp = book(9, 60).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 57. PER-SLOT CALENDAR
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_slot_calendar_shows_booked_segments() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "regular@example.com").await;
+
+    let tomorrow = chrono::Utc::now().date_naive() + chrono::Days::new(1);
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({
+                        "lot_id": lot_id,
+                        "slot_id": slot_id,
+                        "start_time": tomorrow.and_hms_opt(9, 0, 0).unwrap().and_utc(),
+                        "duration_minutes": 120,
+                        "vehicle_id": Uuid::nil(),
+                        "license_plate": "USUAL-1",
+                    }))
+                    .unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+
+    let calendar = |slot: String, month: String| {
+        let state = state.clone();
+        let user_tok = user_tok.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::get(format!("/api/v1/slots/{slot}/calendar?month={month}"))
+                        .header("authorization", format!("Bearer {user_tok}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let month = tomorrow.format("%Y-%m").to_string();
+    let resp = calendar(slot_id.clone(), month.clone()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_json(resp).await;
+    assert_eq!(body["data"]["in_service"], true);
+    let days = body["data"]["days"].as_array().unwrap();
+    assert_eq!(days[0]["date"], format!("{month}-01"));
+    let day = days
+        .iter()
+        .find(|d| d["date"] == tomorrow.to_string())
+        .unwrap();
+    assert_eq!(day["free_minutes"], 24 * 60 - 120);
+    let segments = day["segments"].as_array().unwrap();
+    assert_eq!(segments.len(), 3);
+    assert_eq!(segments[1]["booked"], true);
+    assert!(
+        days.iter()
+            .filter(|d| d["date"] != tomorrow.to_string())
+            .all(|d| d["free_minutes"] == 24 * 60)
+    );
+
+    let resp = calendar(slot_id.clone(), "2026-13".to_string()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = calendar(Uuid::new_v4().to_string(), month).await;
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 58. DUMMY USER REMOVAL
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_admin_lists_and_purges_dummy_users() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (user_tok, _) = register_user_it(state.clone(), "real@example.com").await;
+    let (dummy_tok, dummy_id) = register_user_it(state.clone(), "dummy1@example.com").await;
+    let (_, other_dummy_id) = register_user_it(state.clone(), "dummy2@example.com").await;
+    {
+        let guard = state.read().await;
+        guard
+            .db
+            .mark_dummy_users(&[
+                Uuid::parse_str(&dummy_id).unwrap(),
+                Uuid::parse_str(&other_dummy_id).unwrap(),
+            ])
+            .await
+            .unwrap();
+    }
+
+    let call = |method: &str, token: &str| {
+        Request::builder()
+            .method(method)
+            .uri("/api/v1/admin/dummy-users")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let resp = router(state.clone())
+        .oneshot(call("GET", &user_tok))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    let resp = router(state.clone())
+        .oneshot(call("GET", &admin_tok))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = body_json(resp).await;
+    assert_eq!(body["data"]["count"], 2);
+    let ids: Vec<&str> = body["data"]["users"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|u| u["id"].as_str().unwrap())
+        .collect();
+    assert!(ids.contains(&dummy_id.as_str()));
+    assert!(ids.contains(&other_dummy_id.as_str()));
+
+    let resp = router(state.clone())
+        .oneshot(call("DELETE", &admin_tok))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["data"]["purged"], 2);
+
+    let body = body_json(
+        router(state.clone())
+            .oneshot(call("GET", &admin_tok))
+            .await
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(body["data"]["count"], 0);
+    {
+        let guard = state.read().await;
+        assert!(guard.db.get_user(&dummy_id).await.unwrap().is_none());
+        assert!(guard.db.get_user(&other_dummy_id).await.unwrap().is_none());
+        assert_eq!(guard.db.stats().await.unwrap().dummy_users, 0);
+    }
+
+    // The purged account's session no longer works; real users are untouched
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/users/me")
+                .header("authorization", format!("Bearer {dummy_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/users/me")
+                .header("authorization", format!("Bearer {user_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 59. BOOKING MODIFICATION
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_patch_booking_extends_reprices_and_changes_vehicle() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "extend@example.com").await;
+
+    let tomorrow = chrono::Utc::now().date_naive() + chrono::Days::new(1);
+    let at = |hour: u32| tomorrow.and_hms_opt(hour, 0, 0).unwrap().and_utc();
+    let send = |request: Request<Body>| {
+        let state = state.clone();
+        async move { router(state).oneshot(request).await.unwrap() }
+    };
+    let book = |hour: u32| {
+        let body = serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": at(hour),
+            "duration_minutes": 120,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "EXT-1",
+        });
+        Request::post("/api/v1/bookings")
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {user_tok}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+    let patch = |id: &str, body: serde_json::Value| {
+        Request::patch(format!("/api/v1/bookings/{id}"))
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {user_tok}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+
+    // 08:00–10:00, and someone else's slot time from 12:00
+    let resp = send(book(8)).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = body_json(resp).await;
+    let id = json["data"]["id"].as_str().unwrap().to_string();
+    let old_total = json["data"]["pricing"]["total"].as_f64().unwrap();
+    assert_eq!(send(book(12)).await.status(), StatusCode::CREATED);
+
+    // Extending to 11:00 fits and costs more
+    let resp = send(patch(&id, serde_json::json!({ "end_time": at(11) }))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(
+        json["data"]["end_time"].as_str().unwrap(),
+        serde_json::to_value(at(11)).unwrap().as_str().unwrap()
+    );
+    assert!(json["data"]["pricing"]["total"].as_f64().unwrap() > old_total);
+
+    // Extending into the 12:00 booking is a conflict and changes nothing
+    let resp = send(patch(&id, serde_json::json!({ "end_time": at(13) }))).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(body_json(resp).await["error"]["code"], "SLOT_UNAVAILABLE");
+    let stored = state
+        .read()
+        .await
+        .db
+        .get_booking(&id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.end_time, at(11));
+
+    // Ending before it starts is rejected
+    let resp = send(patch(&id, serde_json::json!({ "end_time": at(7) }))).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+    // Switch to one of the user's own vehicles and update the notes
+    let vehicle_body = serde_json::json!({ "license_plate": "NEW-42" });
+    let resp = send(
+        Request::post("/api/v1/vehicles")
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {user_tok}"))
+            .body(Body::from(serde_json::to_vec(&vehicle_body).unwrap()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let vehicle_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = send(patch(
+        &id,
+        serde_json::json!({ "vehicle_id": vehicle_id, "notes": "Visitor parking" }),
+    ))
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"]["vehicle"]["license_plate"], "NEW-42");
+    assert_eq!(json["data"]["notes"], "Visitor parking");
+
+    // Someone else's vehicle cannot be booked
+    let (other_tok, _) = register_user_it(state.clone(), "other-car@example.com").await;
+    let resp = send(
+        Request::post("/api/v1/vehicles")
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {other_tok}"))
+            .body(Body::from(serde_json::to_vec(&vehicle_body).unwrap()))
+            .unwrap(),
+    )
+    .await;
+    let other_vehicle = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = send(patch(
+        &id,
+        serde_json::json!({ "vehicle_id": other_vehicle }),
+    ))
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn test_patch_booking_settles_credits_and_checks_lot_rules() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, user_id) = register_user_it(state.clone(), "extend-credits@example.com").await;
+    {
+        let guard = state.write().await;
+        guard
+            .db
+            .set_setting("credits_enabled", "true")
+            .await
+            .unwrap();
+        guard
+            .db
+            .set_setting("credits_per_booking", "4")
+            .await
+            .unwrap();
+        let mut user = guard.db.get_user(&user_id).await.unwrap().unwrap();
+        user.credits_balance = 10;
+        guard.db.save_user(&user).await.unwrap();
+    }
+    let set_balance = |credits: i32| {
+        let state = state.clone();
+        let user_id = user_id.clone();
+        async move {
+            let guard = state.write().await;
+            let mut user = guard.db.get_user(&user_id).await.unwrap().unwrap();
+            user.credits_balance = credits;
+            guard.db.save_user(&user).await.unwrap();
+        }
+    };
+    let balance = || {
+        let state = state.clone();
+        let user_id = user_id.clone();
+        async move {
+            let guard = state.read().await;
+            let user = guard.db.get_user(&user_id).await.unwrap().unwrap();
+            user.credits_balance
+        }
+    };
+
+    let tomorrow = chrono::Utc::now().date_naive() + chrono::Days::new(1);
+    let at = |hour: u32| tomorrow.and_hms_opt(hour, 0, 0).unwrap().and_utc();
+    let send = |request: Request<Body>| {
+        let state = state.clone();
+        async move { router(state).oneshot(request).await.unwrap() }
+    };
+    let patch = |id: &str, body: serde_json::Value| {
+        Request::patch(format!("/api/v1/bookings/{id}"))
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {user_tok}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+
+    // 08:00–10:00 for 4 credits
+    let body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": at(8),
+        "duration_minutes": 120,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "CRED-1",
+    });
+    let resp = send(
+        Request::post("/api/v1/bookings")
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {user_tok}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(balance().await, 6);
+
+    // Extending costs credits in proportion to the higher price
+    let resp = send(patch(&id, serde_json::json!({ "end_time": at(12) }))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let extended = balance().await;
+    assert!(extended < 6, "extension was free");
+
+    // Shortening gives credits back
+    let resp = send(patch(&id, serde_json::json!({ "end_time": at(9) }))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let shortened = balance().await;
+    assert!(shortened > extended, "nothing refunded");
+
+    // Without the credits for it, the booking keeps its times
+    set_balance(0).await;
+    let resp = send(patch(&id, serde_json::json!({ "end_time": at(12) }))).await;
+    assert_eq!(resp.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        body_json(resp).await["error"]["code"],
+        "INSUFFICIENT_CREDITS"
+    );
+    set_balance(10).await;
+
+    // The lot's booking rules apply to the new times
+    let rules = serde_json::json!({ "max_duration_minutes": 120 });
+    let resp = send(
+        Request::put(format!("/api/v1/lots/{lot_id}/rules"))
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {admin_tok}"))
+            .body(Body::from(serde_json::to_vec(&rules).unwrap()))
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let resp = send(patch(&id, serde_json::json!({ "end_time": at(12) }))).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let stored = state
+        .read()
+        .await
+        .db
+        .get_booking(&id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.end_time, at(9));
+    assert_eq!(balance().await, 10);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 60. PER-LOT SETTINGS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_lot_settings_override_global_settings() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "lot-settings@example.com").await;
+
+    let send = |request: Request<Body>| {
+        let state = state.clone();
+        async move { router(state).oneshot(request).await.unwrap() }
+    };
+    let put_settings = |body: serde_json::Value| {
+        Request::put(format!("/api/v1/lots/{lot_id}/settings"))
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {admin_tok}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+    let book_without_plate = || {
+        let body = serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": chrono::Utc::now() + chrono::TimeDelta::hours(2),
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "",
+        });
+        Request::post("/api/v1/bookings")
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {user_tok}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+
+    // Globally the plate is optional; this lot requires it
+    let resp = send(put_settings(
+        serde_json::json!({ "license_plate_mode": "required" }),
+    ))
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"]["overrides"]["license_plate_mode"], "required");
+    assert_eq!(json["data"]["effective"]["license_plate_mode"], "required");
+    assert_eq!(json["data"]["effective"]["require_vehicle"], false);
+
+    let resp = send(book_without_plate()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body_json(resp).await["error"]["code"],
+        "LICENSE_PLATE_REQUIRED"
+    );
+
+    // Invalid values are rejected, users cannot read or change the settings
+    let resp = send(put_settings(
+        serde_json::json!({ "license_plate_mode": "mandatory" }),
+    ))
+    .await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = send(
+        Request::get(format!("/api/v1/lots/{lot_id}/settings"))
+            .header("authorization", format!("Bearer {user_tok}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    // Removing the override falls back to the global setting
+    let resp = send(put_settings(serde_json::json!({}))).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        body_json(resp).await["data"]["effective"]["license_plate_mode"],
+        "optional"
+    );
+    assert_eq!(
+        send(book_without_plate()).await.status(),
+        StatusCode::CREATED
+    );
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 61. WAITLIST FOR A TIME WINDOW
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_waitlist_window_offers_freed_slot_and_holds_it() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (waiter_tok, _) = register_user_it(state.clone(), "waiter@example.com").await;
+    let (other_tok, _) = register_user_it(state.clone(), "other@example.com").await;
+
+    let send = |request: Request<Body>| {
+        let state = state.clone();
+        async move { router(state).oneshot(request).await.unwrap() }
+    };
+    let post = |uri: String, token: &str, body: serde_json::Value| {
+        Request::post(uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap()
+    };
+
+    let resp = send(post(
+        "/api/v1/lots".to_string(),
+        &admin_tok,
+        serde_json::json!({ "name": "One Slot", "total_slots": 1, "currency": "EUR" }),
+    ))
+    .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let lot_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let resp = send(
+        Request::get(format!("/api/v1/lots/{lot_id}/slots"))
+            .header("authorization", format!("Bearer {admin_tok}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    let slot_id = body_json(resp).await["data"][0]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let start = chrono::Utc::now() + TimeDelta::hours(3);
+    let end = start + TimeDelta::hours(1);
+    let window = serde_json::json!({ "start_time": start, "end_time": end });
+    let booking = |plate: &str| {
+        serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": start,
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": plate,
+        })
+    };
+    let waitlist_uri = format!("/api/v1/lots/{lot_id}/waitlist");
+
+    // A free slot is booked directly, not waited for
+    let resp = send(post(waitlist_uri.clone(), &waiter_tok, window.clone())).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(body_json(resp).await["error"]["code"], "SLOTS_AVAILABLE");
+
+    let resp = send(post(
+        "/api/v1/bookings".to_string(),
+        &admin_tok,
+        booking("WL-001"),
+    ))
+    .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let booking_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Fully booked: queue for the window, once
+    let resp = send(post(waitlist_uri.clone(), &waiter_tok, window.clone())).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"]["position"], 1);
+    let entry_id = json["data"]["entry"]["id"].as_str().unwrap().to_string();
+    let resp = send(post(waitlist_uri, &waiter_tok, window)).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(body_json(resp).await["error"]["code"], "ALREADY_WAITLISTED");
+    let my_waitlist = || {
+        Request::get("/api/v1/waitlist")
+            .header("authorization", format!("Bearer {waiter_tok}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+    let json = body_json(send(my_waitlist()).await).await;
+    assert_eq!(json["data"][0]["id"], entry_id.as_str());
+    assert_eq!(json["data"][0]["position"], 1);
+    // No booking of the lot has ended yet, so there is no turnover to go by
+    assert!(json["data"][0]["estimated_wait_minutes"].is_null());
+
+    // The cancellation offers the freed slot to the waiting user and holds it
+    let resp = send(
+        Request::delete(format!("/api/v1/bookings/{booking_id}"))
+            .header("authorization", format!("Bearer {admin_tok}"))
+            .body(Body::empty())
+            .unwrap(),
+    )
+    .await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let entry = state
+        .read()
+        .await
+        .db
+        .get_waitlist_entry(&entry_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        entry.status,
+        parkhub_common::models::WaitlistStatus::Offered
+    );
+    assert_eq!(
+        entry.offered_slot_id.map(|id| id.to_string()),
+        Some(slot_id.clone())
+    );
+    let json = body_json(send(my_waitlist()).await).await;
+    assert_eq!(json["data"][0]["status"], "offered");
+    assert_eq!(json["data"][0]["estimated_wait_minutes"], 0);
+
+    let resp = send(post(
+        "/api/v1/bookings".to_string(),
+        &other_tok,
+        booking("WL-002"),
+    ))
+    .await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+    assert_eq!(body_json(resp).await["error"]["code"], "SLOT_HELD");
+
+    // Claiming books the held slot for the queued window
+    let resp = send(post(
+        format!("/api/v1/waitlist/offers/{entry_id}/claim"),
+        &waiter_tok,
+        serde_json::json!({}),
+    ))
+    .await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = body_json(resp).await;
+    assert_eq!(json["data"]["slot_id"], slot_id.as_str());
+    assert_eq!(
+        json["data"]["end_time"]
+            .as_str()
+            .unwrap()
+            .parse::<chrono::DateTime<chrono::Utc>>()
+            .unwrap(),
+        end
+    );
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 62. LIST PAGING, SORTING AND FILTERS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_admin_users_sorted_filtered_and_cursor_paged() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    for name in ["carol", "alice", "bob"] {
+        register_user_it(state.clone(), &format!("{name}@example.com")).await;
+    }
+
+    let get = |uri: String| {
+        let state = state.clone();
+        let token = admin_tok.clone();
+        async move {
+            let resp = router(state)
+                .oneshot(
+                    Request::get(uri)
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+    let emails = |json: &serde_json::Value| -> Vec<String> {
+        json["data"]["items"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|u| u["email"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // Walk the regular users two at a time, by email
+    let (status, json) =
+        get("/api/v1/admin/users?role=user&sort=email&per_page=2".to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["total"], 3);
+    assert_eq!(json["data"]["total_pages"], 2);
+    assert_eq!(emails(&json), ["alice@example.com", "bob@example.com"]);
+    let cursor = json["data"]["next_cursor"].as_str().unwrap().to_string();
+
+    let (_, json) = get(format!(
+        "/api/v1/admin/users?role=user&sort=email&per_page=2&cursor={cursor}"
+    ))
+    .await;
+    assert_eq!(emails(&json), ["carol@example.com"]);
+    assert!(json["data"]["next_cursor"].is_null());
+
+    let (_, json) = get("/api/v1/admin/users?role=user&sort=email&order=desc".to_string()).await;
+    assert_eq!(
+        emails(&json),
+        ["carol@example.com", "bob@example.com", "alice@example.com"]
+    );
+
+    let (_, json) = get("/api/v1/admin/users?search=BOB%40".to_string()).await;
+    assert_eq!(emails(&json), ["bob@example.com"]);
+
+    let (status, json) = get("/api/v1/admin/users?sort=password_hash".to_string()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"]["code"], "INVALID_SORT");
+    let (status, json) = get("/api/v1/admin/users?cursor=%21%21".to_string()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"]["code"], "INVALID_CURSOR");
+}
+
+#[tokio::test]
+async fn test_list_bookings_pages_only_on_request() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+
+    let send = |request: Request<Body>| {
+        let state = state.clone();
+        async move {
+            let resp = router(state).oneshot(request).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+    let get = |uri: &str| {
+        Request::get(uri)
+            .header("authorization", format!("Bearer {admin_tok}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let now = chrono::Utc::now();
+    for hours in [5, 1, 3] {
+        let body = serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": now + TimeDelta::hours(hours),
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "PG-001",
+        });
+        let (status, _) = send(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+    }
+    let starts = |json: &serde_json::Value| -> Vec<chrono::DateTime<chrono::Utc>> {
+        json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|b| b["start_time"].as_str().unwrap().parse().unwrap())
+            .collect()
+    };
+
+    // Unpaged: every booking, soonest first, no paging metadata
+    let (status, json) = send(get("/api/v1/bookings")).await;
+    assert_eq!(status, StatusCode::OK);
+    let all = starts(&json);
+    assert_eq!(all.len(), 3);
+    assert!(all.windows(2).all(|w| w[0] < w[1]));
+    assert!(json["meta"].is_null());
+
+    // Paged: one page plus totals and a cursor
+    let (_, json) = send(get("/api/v1/bookings?per_page=2&order=desc")).await;
+    assert_eq!(starts(&json), [all[2], all[1]]);
+    assert_eq!(json["meta"]["total"], 3);
+    let cursor = json["meta"]["next_cursor"].as_str().unwrap().to_string();
+    let (_, json) = send(get(&format!(
+        "/api/v1/bookings?per_page=2&order=desc&cursor={cursor}"
+    )))
+    .await;
+    assert_eq!(starts(&json), [all[0]]);
+
+    // Date range filter
+    let to = (now + TimeDelta::hours(2)).to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let (_, json) = send(get(&format!("/api/v1/bookings?to_date={to}"))).await;
+    assert_eq!(starts(&json), [all[0]]);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 63. BULK ADMIN ACTIONS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_bulk_user_action_is_all_or_nothing() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (_, alice) = register_user_it(state.clone(), "alice@example.com").await;
+    let (_, bob) = register_user_it(state.clone(), "bob@example.com").await;
+
+    let post = |body: serde_json::Value| {
+        let state = state.clone();
+        let token = admin_tok.clone();
+        async move {
+            let resp = router(state)
+                .oneshot(
+                    Request::post("/api/v1/admin/users/bulk")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+    let is_active = |id: String| {
+        let state = state.clone();
+        async move {
+            let guard = state.read().await;
+            guard.db.get_user(&id).await.unwrap().unwrap().is_active
+        }
+    };
+
+    // One unknown id rejects the whole request
+    let unknown = Uuid::new_v4().to_string();
+    let (status, json) = post(serde_json::json!({
+        "ids": [alice, bob, unknown],
+        "action": "deactivate",
+    }))
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(json["error"]["code"], "BULK_REJECTED");
+    assert_eq!(json["data"]["applied"], false);
+    assert_eq!(json["data"]["failed"], 1);
+    assert_eq!(json["data"]["results"][2]["code"], "NOT_FOUND");
+    assert!(is_active(alice.clone()).await);
+
+    let (status, json) = post(serde_json::json!({
+        "ids": [alice, bob],
+        "action": "deactivate",
+    }))
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"]["applied"], true);
+    assert_eq!(json["data"]["succeeded"], 2);
+    assert!(!is_active(alice.clone()).await);
+    assert!(!is_active(bob.clone()).await);
+
+    let (status, json) = post(serde_json::json!({
+        "ids": [alice],
+        "action": "change_role",
+    }))
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"]["code"], "MISSING_ROLE");
+}
+
+#[tokio::test]
+async fn test_bulk_cancel_bookings_frees_slot_once() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+
+    let send = |uri: &str, body: serde_json::Value| {
+        let state = state.clone();
+        let request = Request::post(uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {admin_tok}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        async move {
+            let resp = router(state).oneshot(request).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let now = chrono::Utc::now();
+    let mut ids = Vec::new();
+    for hours in [1, 4] {
+        let (status, json) = send(
+            "/api/v1/bookings",
+            serde_json::json!({
+                "lot_id": lot_id,
+                "slot_id": slot_id,
+                "start_time": now + TimeDelta::hours(hours),
+                "duration_minutes": 60,
+                "vehicle_id": Uuid::nil(),
+                "license_plate": "BK-001",
+            }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        ids.push(json["data"]["id"].as_str().unwrap().to_string());
+    }
+
+    let (status, json) = send(
+        "/api/v1/admin/bookings/bulk",
+        serde_json::json!({ "ids": ids, "action": "cancel" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert_eq!(json["data"]["succeeded"], 2);
+    {
+        let guard = state.read().await;
+        for id in &ids {
+            let booking = guard.db.get_booking(id).await.unwrap().unwrap();
+            assert_eq!(booking.status, parkhub_common::BookingStatus::Cancelled);
+        }
+        let slot = guard.db.get_parking_slot(&slot_id).await.unwrap().unwrap();
+        assert_ne!(slot.status, parkhub_common::SlotStatus::Reserved);
+    }
+
+    // Cancelling again is rejected per item and changes nothing
+    let (status, json) = send(
+        "/api/v1/admin/bookings/bulk",
+        serde_json::json!({ "ids": [ids[0], ids[0]], "action": "cancel" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(json["data"]["results"][0]["code"], "ALREADY_CANCELLED");
+    assert_eq!(json["data"]["results"][1]["code"], "DUPLICATE_ID");
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 64. FORCED PASSWORD CHANGE
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_admin_reset_password_forces_change_on_next_login() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (_, alice) = register_user_it(state.clone(), "alice@example.com").await;
+
+    let send = |method: &str, uri: String, token: Option<String>, body: serde_json::Value| {
+        let state = state.clone();
+        let mut req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json");
+        if let Some(token) = token {
+            req = req.header("authorization", format!("Bearer {token}"));
+        }
+        let req = req
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+    let login = |password: &'static str| {
+        let send = &send;
+        async move {
+            let (status, json) = send(
+                "POST",
+                "/api/v1/auth/login".to_string(),
+                None,
+                serde_json::json!({"username": "alice@example.com", "password": password}),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK, "{json}");
+            json
+        }
+    };
+
+    let (status, _) = send(
+        "POST",
+        format!("/api/v1/admin/users/{alice}/reset-password"),
+        Some(admin_tok),
+        serde_json::json!({"new_password": "TempPass123"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let json = login("TempPass123").await;
+    assert_eq!(json["data"]["user"]["must_change_password"], true);
+    let token = json["data"]["tokens"]["access_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    // Everything but the password change is refused
+    let (status, json) = send(
+        "GET",
+        "/api/v1/users/me".to_string(),
+        Some(token.clone()),
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(json["error"]["code"], "PASSWORD_CHANGE_REQUIRED");
+
+    // Keeping the admin-set password does not count
+    let (status, json) = send(
+        "PATCH",
+        "/api/v1/users/me/password".to_string(),
+        Some(token.clone()),
+        serde_json::json!({"current_password": "TempPass123", "new_password": "TempPass123"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"]["code"], "PASSWORD_UNCHANGED");
+
+    let (status, _) = send(
+        "PATCH",
+        "/api/v1/users/me/password".to_string(),
+        Some(token),
+        serde_json::json!({"current_password": "TempPass123", "new_password": "MyOwnPass456"}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let json = login("MyOwnPass456").await;
+    assert_eq!(json["data"]["user"]["must_change_password"], false);
+    let token = json["data"]["tokens"]["access_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let (status, _) = send(
+        "GET",
+        "/api/v1/users/me".to_string(),
+        Some(token),
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 65. SLOT CRUD
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_slot_crud_keeps_lot_counts_in_sync() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (user_tok, _) = register_user_it(state.clone(), "driver@example.com").await;
+
+    let send = |method: &str, uri: String, token: &str, body: serde_json::Value| {
+        let state = state.clone();
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let (status, json) = send(
+        "POST",
+        "/api/v1/lots".to_string(),
+        &admin_tok,
+        serde_json::json!({ "name": "Two Slots", "total_slots": 2, "currency": "EUR" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let lot_id = json["data"]["id"].as_str().unwrap().to_string();
+    let slots = format!("/api/v1/lots/{lot_id}/slots");
+    let counts = || {
+        let send = &send;
+        let uri = format!("/api/v1/lots/{lot_id}");
+        let token = admin_tok.clone();
+        async move {
+            let (_, json) = send("GET", uri, &token, serde_json::Value::Null).await;
+            (
+                json["data"]["total_slots"].clone(),
+                json["data"]["available_slots"].clone(),
+            )
+        }
+    };
+
+    // Regular users cannot manage slots
+    let (status, _) = send("POST", slots.clone(), &user_tok, serde_json::json!({})).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, json) = send(
+        "POST",
+        slots.clone(),
+        &admin_tok,
+        serde_json::json!({ "slot_type": "electric" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{json}");
+    assert_eq!(json["data"]["slot_number"], 3);
+    let slot_id = json["data"]["id"].as_str().unwrap().to_string();
+    assert_eq!(counts().await, (3.into(), 3.into()));
+
+    let (status, json) = send(
+        "POST",
+        slots.clone(),
+        &admin_tok,
+        serde_json::json!({ "slot_number": 3 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["error"]["code"], "DUPLICATE");
+    let (status, _) = send(
+        "POST",
+        slots.clone(),
+        &admin_tok,
+        serde_json::json!({ "slot_type": "hovercraft" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, json) = send(
+        "PATCH",
+        format!("{slots}/{slot_id}"),
+        &admin_tok,
+        serde_json::json!({ "status": "maintenance" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert_eq!(counts().await, (3.into(), 2.into()));
+
+    let (status, _) = send(
+        "DELETE",
+        format!("{slots}/{slot_id}"),
+        &admin_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(counts().await, (2.into(), 2.into()));
+    let (_, json) = send("GET", slots, &admin_tok, serde_json::Value::Null).await;
+    assert_eq!(json["data"].as_array().unwrap().len(), 2);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 66. LOT DELETE CASCADE
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_delete_lot_with_bookings_needs_force_and_cascades() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (user_tok, _) = register_user_it(state.clone(), "booker@example.com").await;
+
+    let send = |method: &str, uri: String, token: &str, body: serde_json::Value| {
+        let state = state.clone();
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let (status, json) = send(
+        "POST",
+        "/api/v1/lots".to_string(),
+        &admin_tok,
+        serde_json::json!({ "name": "Closing Lot", "total_slots": 2, "currency": "EUR" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let lot_id = json["data"]["id"].as_str().unwrap().to_string();
+    let (_, json) = send(
+        "GET",
+        format!("/api/v1/lots/{lot_id}/slots"),
+        &admin_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    let slot_id = json["data"][0]["id"].as_str().unwrap().to_string();
+
+    let (status, json) = send(
+        "POST",
+        "/api/v1/bookings".to_string(),
+        &user_tok,
+        serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": chrono::Utc::now() + TimeDelta::hours(2),
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "DEL-001",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{json}");
+    let booking_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    // Only admins, and not while the booking is live
+    let lot_uri = format!("/api/v1/lots/{lot_id}");
+    let (status, _) = send(
+        "DELETE",
+        lot_uri.clone(),
+        &user_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, json) = send(
+        "DELETE",
+        lot_uri.clone(),
+        &admin_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["error"]["code"], "LOT_HAS_ACTIVE_BOOKINGS");
+
+    let (status, _) = send(
+        "DELETE",
+        format!("{lot_uri}?force=true"),
+        &admin_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = send("GET", lot_uri, &admin_tok, serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let guard = state.read().await;
+    assert!(
+        guard
+            .db
+            .list_slots_by_lot(&lot_id)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(guard.db.get_parking_slot(&slot_id).await.unwrap().is_none());
+    let booking = guard.db.get_booking(&booking_id).await.unwrap().unwrap();
+    assert_eq!(booking.status, parkhub_common::BookingStatus::Cancelled);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 67. SLOT HOLDS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_slot_hold_keeps_others_from_booking() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (first_tok, _) = register_user_it(state.clone(), "holder@example.com").await;
+    let (second_tok, _) = register_user_it(state.clone(), "latecomer@example.com").await;
+
+    let send = |method: &str, uri: String, token: &str, body: serde_json::Value| {
+        let state = state.clone();
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let (status, json) = send(
+        "POST",
+        "/api/v1/lots".to_string(),
+        &admin_tok,
+        serde_json::json!({ "name": "Hold Lot", "total_slots": 1, "currency": "EUR" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let lot_id = json["data"]["id"].as_str().unwrap().to_string();
+    let (_, json) = send(
+        "GET",
+        format!("/api/v1/lots/{lot_id}/slots"),
+        &admin_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    let slot_id = json["data"][0]["id"].as_str().unwrap().to_string();
+    let hold_uri = format!("/api/v1/slots/{slot_id}/hold");
+    let booking = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": chrono::Utc::now() + TimeDelta::hours(2),
+        "duration_minutes": 60,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "HLD-001",
+    });
+
+    let (status, json) = send(
+        "POST",
+        hold_uri.clone(),
+        &first_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert!(json["data"]["expires_at"].is_string());
+
+    // The slot is taken for everybody else while the hold runs
+    let (status, json) = send(
+        "POST",
+        hold_uri.clone(),
+        &second_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["error"]["code"], "SLOT_HELD");
+    let (status, json) = send(
+        "POST",
+        "/api/v1/bookings".to_string(),
+        &second_tok,
+        booking.clone(),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["error"]["code"], "SLOT_HELD");
+
+    // The holder books it, which releases the hold
+    let (status, json) = send("POST", "/api/v1/bookings".to_string(), &first_tok, booking).await;
+    assert_eq!(status, StatusCode::CREATED, "{json}");
+    let guard = state.read().await;
+    assert!(guard.db.get_slot_hold(&slot_id).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_expired_slot_holds_are_ignored_and_swept() {
+    let state = test_state().await;
+    let guard = state.read().await;
+    let db = &guard.db;
+    let lot_id = Uuid::new_v4();
+    let holder = Uuid::new_v4();
+    let now = chrono::Utc::now();
+    let hold = |slot_id: Uuid, expires_at| crate::db::SlotHold {
+        slot_id,
+        lot_id,
+        user_id: holder,
+        created_at: now - TimeDelta::minutes(5),
+        expires_at,
+    };
+
+    let expired = Uuid::new_v4();
+    db.place_slot_hold(&hold(expired, now - TimeDelta::seconds(1)))
+        .await
+        .unwrap();
+    let held = crate::api::slot_holds::slots_held_by_others(db, lot_id, Uuid::new_v4(), now)
+        .await
+        .unwrap();
+    assert!(held.is_empty());
+
+    // A user holds one slot at a time
+    let current = Uuid::new_v4();
+    db.place_slot_hold(&hold(current, now + TimeDelta::minutes(2)))
+        .await
+        .unwrap();
+    assert!(
+        db.get_slot_hold(&expired.to_string())
+            .await
+            .unwrap()
+            .is_none()
+    );
+    let held = crate::api::slot_holds::slots_held_by_others(db, lot_id, Uuid::new_v4(), now)
+        .await
+        .unwrap();
+    assert_eq!(held.into_iter().collect::<Vec<_>>(), vec![current]);
+    assert!(
+        crate::api::slot_holds::slots_held_by_others(db, lot_id, holder, now)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    // Only holds on the lot's own slots count
+    assert!(
+        crate::api::slot_holds::slots_held_by_others(db, Uuid::new_v4(), Uuid::new_v4(), now)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+
+    assert_eq!(db.delete_expired_slot_holds(now).await.unwrap(), 0);
+    assert_eq!(
+        db.delete_expired_slot_holds(now + TimeDelta::minutes(3))
+            .await
+            .unwrap(),
+        1
+    );
+    assert!(db.list_slot_holds().await.unwrap().is_empty());
+    assert!(db.list_lot_slot_holds(lot_id).await.unwrap().is_empty());
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 68. VISITOR BOOKINGS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[cfg(feature = "mod-guest")]
+#[tokio::test]
+async fn test_premium_user_books_slot_for_visitor_with_pass() {
+    use parkhub_common::UserRole;
+
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (token, user_id) = register_user_it(state.clone(), "host@example.com").await;
+
+    let start_time = (chrono::Utc::now() + TimeDelta::days(1))
+        .date_naive()
+        .and_hms_opt(9, 0, 0)
+        .unwrap()
+        .and_utc();
+    let body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": start_time,
+        "duration_minutes": 240,
+        "visitor_name": "Ida Visitor",
+        "license_plate": "M-IV 42",
+        "email": "ida@example.com",
+    });
+    let post = |token: String| {
+        let state = state.clone();
+        let body = body.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::post("/api/v1/bookings/visitor")
+                        .header("content-type", "application/json")
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::from(serde_json::to_vec(&body).unwrap()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    // Regular users may not book for visitors
+    let resp = post(token.clone()).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+    set_role_it(&state, &user_id, UserRole::Premium).await;
+    let resp = post(token.clone()).await;
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let json = body_json(resp).await;
+    let booking = &json["data"]["booking"];
+    assert_eq!(booking["user_id"], user_id.as_str());
+    assert_eq!(booking["booked_for"]["name"], "Ida Visitor");
+    assert_eq!(booking["booked_for"]["license_plate"], "M-IV 42");
+    assert_eq!(booking["vehicle"]["license_plate"], "M-IV 42");
+    let pass = &json["data"]["pass"];
+    assert_eq!(pass["user_name"], "Ida Visitor");
+    assert_eq!(pass["status"], "active");
+    assert!(
+        pass["qr_data"]
+            .as_str()
+            .unwrap()
+            .starts_with("data:image/png;base64,")
+    );
+
+    // The booking holds the slot like any other
+    let resp = post(token.clone()).await;
+    assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+    // The host can fetch the visitor's pass again later
+    let booking_id = booking["id"].as_str().unwrap();
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get(format!("/api/v1/bookings/{booking_id}/pass"))
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(body_json(resp).await["data"]["user_name"], "Ida Visitor");
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 69. ACTIVITY FEED
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_activity_feed_follows_a_booking_newest_first() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (token, _) = register_user_it(state.clone(), "feed@example.com").await;
+    let (other_token, _) = register_user_it(state.clone(), "other@example.com").await;
+
+    let booking_body = serde_json::json!({
+        "lot_id": lot_id,
+        "slot_id": slot_id,
+        "start_time": chrono::Utc::now() + TimeDelta::hours(2),
+        "duration_minutes": 60,
+        "vehicle_id": Uuid::nil(),
+        "license_plate": "ACT-001",
+    });
+    let resp = router(state.clone())
+        .oneshot(
+            Request::post("/api/v1/bookings")
+                .header("content-type", "application/json")
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::from(serde_json::to_vec(&booking_body).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::CREATED);
+    let booking_id = body_json(resp).await["data"]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::delete(format!("/api/v1/bookings/{booking_id}"))
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let feed = |token: String, query: String| {
+        let state = state.clone();
+        async move {
+            router(state)
+                .oneshot(
+                    Request::get(format!("/api/v1/users/me/activity{query}"))
+                        .header("authorization", format!("Bearer {token}"))
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+                .await
+                .unwrap()
+        }
+    };
+
+    let resp = feed(token.clone(), format!("?booking_id={booking_id}")).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let entries = json["data"].as_array().unwrap();
+    assert_eq!(json["meta"]["total"], entries.len());
+    assert!(
+        entries
+            .iter()
+            .all(|e| e["booking"]["id"] == booking_id.as_str())
+    );
+    let kinds: Vec<&str> = entries
+        .iter()
+        .map(|e| e["kind"].as_str().unwrap())
+        .collect();
+    let created = kinds.iter().position(|k| *k == "booking_created").unwrap();
+    let cancelled = kinds
+        .iter()
+        .position(|k| *k == "booking_cancelled")
+        .unwrap();
+    assert!(cancelled < created, "newest first: {kinds:?}");
+    assert_eq!(entries[created]["booking"]["status"], "cancelled");
+    assert_eq!(entries[created]["booking"]["lot_name"], "Test Lot");
+
+    // Other users do not see it
+    let resp = feed(other_token, format!("?booking_id={booking_id}")).await;
+    assert_eq!(body_json(resp).await["meta"]["total"], 0);
+
+    let resp = feed(token, "?cursor=not-a-cursor".to_string()).await;
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 70. LOT FLOORS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_lot_floors_group_slots() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (user_tok, _) = register_user_it(state.clone(), "floors@example.com").await;
+
+    let send = |method: &str, uri: String, token: &str, body: serde_json::Value| {
+        let state = state.clone();
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let (status, json) = send(
+        "POST",
+        "/api/v1/lots".to_string(),
+        &admin_tok,
+        serde_json::json!({ "name": "Garage", "total_slots": 2, "currency": "EUR" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let lot_id = json["data"]["id"].as_str().unwrap().to_string();
+    let ground_id = json["data"]["floors"][0]["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let floors = format!("/api/v1/lots/{lot_id}/floors");
+
+    // Regular users can list floors but not add them
+    let (status, _) = send(
+        "POST",
+        floors.clone(),
+        &user_tok,
+        serde_json::json!({ "name": "Roof" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, json) = send(
+        "POST",
+        floors.clone(),
+        &admin_tok,
+        serde_json::json!({ "name": " Upper Deck " }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{json}");
+    assert_eq!(json["data"]["name"], "Upper Deck");
+    assert_eq!(json["data"]["floor_number"], 2);
+    assert_eq!(json["data"]["total_slots"], 0);
+    let upper_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, json) = send(
+        "POST",
+        floors.clone(),
+        &admin_tok,
+        serde_json::json!({ "name": "Duplicate", "floor_number": 1 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["error"]["code"], "DUPLICATE");
+    let (status, _) = send(
+        "POST",
+        floors.clone(),
+        &admin_tok,
+        serde_json::json!({ "name": "  " }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // A slot added to the upper floor counts there
+    let (status, json) = send(
+        "POST",
+        format!("/api/v1/lots/{lot_id}/slots"),
+        &admin_tok,
+        serde_json::json!({ "floor_id": upper_id }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{json}");
+    assert_eq!(json["data"]["floor_id"], upper_id.as_str());
+    let (status, _) = send(
+        "POST",
+        format!("/api/v1/lots/{lot_id}/slots"),
+        &admin_tok,
+        serde_json::json!({ "floor_id": Uuid::new_v4() }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, json) = send(
+        "PATCH",
+        format!("{floors}/{upper_id}"),
+        &admin_tok,
+        serde_json::json!({ "name": "Roof", "floor_number": 3 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert_eq!(json["data"]["name"], "Roof");
+    assert_eq!(json["data"]["total_slots"], 1);
+    let (status, _) = send(
+        "PATCH",
+        format!("{floors}/{upper_id}"),
+        &admin_tok,
+        serde_json::json!({ "floor_number": 1 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, json) = send("GET", floors.clone(), &user_tok, serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::OK);
+    let listed: Vec<(&str, i64)> = json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|f| {
+            (
+                f["name"].as_str().unwrap(),
+                f["total_slots"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(listed, [("Ground Floor", 2), ("Roof", 1)]);
+
+    let (status, json) = send(
+        "GET",
+        format!("{floors}/{ground_id}/slots"),
+        &user_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"].as_array().unwrap().len(), 2);
+    let (_, json) = send(
+        "GET",
+        format!("{floors}/{upper_id}/slots"),
+        &user_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+    let (status, _) = send(
+        "GET",
+        format!("{floors}/{}/slots", Uuid::new_v4()),
+        &user_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 71. SLOT TYPE FILTER
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_lot_slots_filter_by_type() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+
+    let send = |method: &str, uri: String, body: serde_json::Value| {
+        let state = state.clone();
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {admin_tok}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let (status, json) = send(
+        "POST",
+        "/api/v1/lots".to_string(),
+        serde_json::json!({ "name": "EV Garage", "total_slots": 2, "currency": "EUR" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED);
+    let lot_id = json["data"]["id"].as_str().unwrap().to_string();
+    let slots = format!("/api/v1/lots/{lot_id}/slots");
+
+    let mut electric = Vec::new();
+    for _ in 0..2 {
+        let (status, json) = send(
+            "POST",
+            slots.clone(),
+            serde_json::json!({ "slot_type": "electric" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED, "{json}");
+        electric.push(json["data"]["id"].as_str().unwrap().to_string());
+    }
+    let (status, _) = send(
+        "PATCH",
+        format!("{slots}/{}", electric[1]),
+        serde_json::json!({ "status": "maintenance" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let ids = |json: &serde_json::Value| -> Vec<String> {
+        json["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["id"].as_str().unwrap().to_string())
+            .collect()
+    };
+    let (status, json) = send(
+        "GET",
+        format!("{slots}?type=electric"),
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let mut found = ids(&json);
+    found.sort();
+    let mut expected = electric.clone();
+    expected.sort();
+    assert_eq!(found, expected);
+
+    let (_, json) = send(
+        "GET",
+        format!("{slots}?type=electric&status=available"),
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(ids(&json), [electric[0].clone()]);
+    let (_, json) = send(
+        "GET",
+        format!("{slots}?slot_type=standard"),
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(json["data"].as_array().unwrap().len(), 2);
+    let (status, _) = send(
+        "GET",
+        format!("{slots}?type=hovercraft"),
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 72. ADMIN FORCE-LOGOUT
+// ═════════════════════════════════════════════════════════════════════════════
+
+async fn revoke_sessions_it(
+    state: Arc<RwLock<AppState>>,
+    token: &str,
+    user_id: &str,
+) -> (StatusCode, serde_json::Value) {
+    let resp = router(state)
+        .oneshot(
+            Request::delete(format!("/api/v1/admin/users/{user_id}/sessions"))
+                .header("authorization", format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let status = resp.status();
+    (status, body_json(resp).await)
+}
+
+#[tokio::test]
+async fn test_admin_revokes_all_sessions_of_user() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (user_tok, user_id) = register_user_it(state.clone(), "leaver@example.com").await;
+    let (other_tok, _) = register_user_it(state.clone(), "stayer@example.com").await;
+    let (status, _) = login_it(state.clone(), "leaver@example.com").await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Only admins may sign others out
+    let (status, _) = revoke_sessions_it(state.clone(), &other_tok, &user_id).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(me_status_it(state.clone(), &user_tok).await, StatusCode::OK);
+
+    let (status, json) = revoke_sessions_it(state.clone(), &admin_tok, &user_id).await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert_eq!(json["data"]["revoked_sessions"], 2);
+    assert_eq!(
+        me_status_it(state.clone(), &user_tok).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        me_status_it(state.clone(), &other_tok).await,
+        StatusCode::OK
+    );
+
+    let sessions = state
+        .read()
+        .await
+        .db
+        .list_sessions_by_user(Uuid::parse_str(&user_id).unwrap())
+        .await
+        .unwrap();
+    assert!(sessions.is_empty());
+    let entries = state.read().await.db.list_audit_log(100).await.unwrap();
+    assert!(
+        entries.iter().any(|e| e.event_type == "SessionsRevoked"
+            && e.target_id.as_deref() == Some(user_id.as_str()))
+    );
+
+    // The user can sign in again afterwards
+    let (status, token) = login_it(state.clone(), "leaver@example.com").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        me_status_it(state.clone(), &token.unwrap()).await,
+        StatusCode::OK
+    );
+
+    let (status, _) =
+        revoke_sessions_it(state.clone(), &admin_tok, &Uuid::new_v4().to_string()).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_deactivating_user_ends_their_sessions() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (user_tok, user_id) = register_user_it(state.clone(), "deactivated@example.com").await;
+    assert_eq!(me_status_it(state.clone(), &user_tok).await, StatusCode::OK);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::put(format!("/api/v1/admin/users/{user_id}/update"))
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    serde_json::to_vec(&serde_json::json!({ "is_active": false })).unwrap(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        me_status_it(state.clone(), &user_tok).await,
+        StatusCode::UNAUTHORIZED
+    );
+    let sessions = state
+        .read()
+        .await
+        .db
+        .list_sessions_by_user(Uuid::parse_str(&user_id).unwrap())
+        .await
+        .unwrap();
+    assert!(sessions.is_empty());
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 73. BOOKING NOTES AND ATTACHMENTS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_booking_attachments_and_internal_notes() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, slot_id) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (owner_tok, _) = register_user_it(state.clone(), "dented@example.com").await;
+    let (other_tok, _) = register_user_it(state.clone(), "nosy@example.com").await;
+
+    let send = |method: &str, uri: String, token: &str, body: serde_json::Value| {
+        let state = state.clone();
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let start_time = (chrono::Utc::now() + TimeDelta::days(1))
+        .date_naive()
+        .and_hms_opt(12, 0, 0)
+        .unwrap()
+        .and_utc();
+    let (status, json) = send(
+        "POST",
+        "/api/v1/bookings".to_string(),
+        &owner_tok,
+        serde_json::json!({
+            "lot_id": lot_id,
+            "slot_id": slot_id,
+            "start_time": start_time,
+            "duration_minutes": 60,
+            "vehicle_id": Uuid::nil(),
+            "license_plate": "DENT-01",
+        }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{json}");
+    let booking_id = json["data"]["id"].as_str().unwrap().to_string();
+    let attachments = format!("/api/v1/bookings/{booking_id}/attachments");
+
+    let upload = |token: &str, filename: &str, contents: &[u8]| {
+        let boundary = "parkhub-test-boundary";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"file\"; \
+             filename=\"{filename}\"\r\nContent-Type: application/octet-stream\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(contents);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        let req = Request::post(attachments.as_str())
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(body))
+            .unwrap();
+        let state = state.clone();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let mut png = std::io::Cursor::new(Vec::new());
+    image::DynamicImage::new_rgb8(64, 48)
+        .write_to(&mut png, image::ImageFormat::Png)
+        .unwrap();
+    let png = png.into_inner();
+
+    // Only people who may manage the booking can attach files
+    let (status, _) = upload(&other_tok, "dent.png", &png).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, json) = upload(&owner_tok, "../../dent.png", &png).await;
+    assert_eq!(status, StatusCode::CREATED, "{json}");
+    assert_eq!(json["data"]["content_type"], "image/jpeg");
+    assert_eq!(json["data"]["filename"], "dent.jpg");
+    let attachment_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    // The type comes from the contents, not the name
+    let (status, json) = upload(&owner_tok, "dent.png", b"<script>alert(1)</script>").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"]["code"], "INVALID_INPUT");
+    let mut oversized = b"%PDF-1.4\n".to_vec();
+    oversized.resize(3 * 1024 * 1024 + 1, b' ');
+    let (status, json) = upload(&owner_tok, "big.pdf", &oversized).await;
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(json["error"]["code"], "PAYLOAD_TOO_LARGE");
+
+    let (status, json) = send(
+        "GET",
+        attachments.clone(),
+        &owner_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"].as_array().unwrap().len(), 1);
+    let (status, _) = send(
+        "GET",
+        attachments.clone(),
+        &other_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let download = |token: &str| {
+        Request::get(format!("{attachments}/{attachment_id}"))
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap()
+    };
+    let resp = router(state.clone())
+        .oneshot(download(&other_tok))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let resp = router(state.clone())
+        .oneshot(download(&owner_tok))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(resp.headers()["content-type"], "image/jpeg");
+    assert_eq!(resp.headers()["x-content-type-options"], "nosniff");
+    assert_eq!(
+        resp.headers()["content-disposition"],
+        "attachment; filename=\"dent.jpg\""
+    );
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert_eq!(
+        image::guess_format(&bytes).unwrap(),
+        image::ImageFormat::Jpeg
+    );
+
+    // Internal notes are for admins only
+    let notes = format!("/api/v1/admin/bookings/{booking_id}/notes");
+    let (status, _) = send(
+        "POST",
+        notes.clone(),
+        &owner_tok,
+        serde_json::json!({ "text": "Let me in" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send(
+        "POST",
+        notes.clone(),
+        &admin_tok,
+        serde_json::json!({ "text": "   " }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, json) = send(
+        "POST",
+        notes.clone(),
+        &admin_tok,
+        serde_json::json!({ "text": "Damage confirmed, refund agreed" }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CREATED, "{json}");
+    let (status, json) = send("GET", notes.clone(), &admin_tok, serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"][0]["text"], "Damage confirmed, refund agreed");
+    let (status, _) = send("GET", notes, &owner_tok, serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, json) = send(
+        "GET",
+        format!("/api/v1/bookings/{booking_id}"),
+        &owner_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(!json.to_string().contains("refund agreed"));
+
+    let (status, _) = send(
+        "DELETE",
+        format!("{attachments}/{attachment_id}"),
+        &owner_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let resp = router(state.clone())
+        .oneshot(download(&owner_tok))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 74. VISITOR DAY PASSES
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_visitor_passes_consume_block_and_get_slot_at_check_in() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, _) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, _) = register_user_it(state.clone(), "frontdesk@example.com").await;
+
+    let send = |method: &str, uri: String, token: &str, body: serde_json::Value| {
+        let state = state.clone();
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let today = chrono::Utc::now().date_naive();
+    let blocks = format!("/api/v1/lots/{lot_id}/visitor-pass-blocks");
+    let passes = format!("/api/v1/lots/{lot_id}/visitor-passes");
+    let visitor = serde_json::json!({
+        "date": today,
+        "visitor_name": "Grace Visitor",
+        "license_plate": "M-GV 42",
+    });
+
+    // No block yet
+    let (status, json) = send("POST", passes.clone(), &admin_tok, visitor.clone()).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["error"]["code"], "VISITOR_PASSES_EXHAUSTED");
+
+    // Only lot admins set blocks
+    let (status, _) = send(
+        "PUT",
+        format!("{blocks}/{today}"),
+        &user_tok,
+        serde_json::json!({ "passes": 1 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, json) = send(
+        "PUT",
+        format!("{blocks}/{today}"),
+        &admin_tok,
+        serde_json::json!({ "passes": 1 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert_eq!(json["data"]["remaining"], 1);
+
+    // Users without the visitor permission cannot issue passes
+    let (status, _) = send("POST", passes.clone(), &user_tok, visitor.clone()).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let (status, json) = send("POST", passes.clone(), &admin_tok, visitor.clone()).await;
+    assert_eq!(status, StatusCode::CREATED, "{json}");
+    assert_eq!(json["data"]["status"], "issued");
+    assert!(json["data"]["slot_id"].is_null());
+    let pass_id = json["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, json) = send("POST", passes.clone(), &admin_tok, visitor).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(json["error"]["code"], "VISITOR_PASSES_EXHAUSTED");
+    let (status, _) = send(
+        "PUT",
+        format!("{blocks}/{today}"),
+        &admin_tok,
+        serde_json::json!({ "passes": 0 }),
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Check-in assigns a slot and books it for the rest of the day
+    let (status, json) = send(
+        "POST",
+        format!("/api/v1/visitor-passes/{pass_id}/check-in"),
+        &admin_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert_eq!(json["data"]["status"], "checked_in");
+    assert!(json["data"]["slot_number"].is_number());
+    let booking_id = json["data"]["booking_id"].as_str().unwrap().to_string();
+    let (status, json) = send(
+        "GET",
+        format!("/api/v1/bookings/{booking_id}"),
+        &admin_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert_eq!(json["data"]["status"], "active");
+
+    let (status, json) = send("GET", blocks, &admin_tok, serde_json::Value::Null).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(json["data"][0]["issued"], 1);
+    assert_eq!(json["data"][0]["checked_in"], 1);
+    assert_eq!(json["data"][0]["remaining"], 0);
+
+    // Checked-in passes are handled through their booking
+    let (status, _) = send(
+        "DELETE",
+        format!("/api/v1/visitor-passes/{pass_id}"),
+        &admin_tok,
+        serde_json::Value::Null,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 75. ENERGY REPORTS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_energy_reports_per_lot_and_user() {
+    use chrono::TimeZone;
+    use parkhub_common::models::{
+        ChargingSession, ChargingSessionStatus, ConnectorType, EvCharger, EvChargerStatus,
+    };
+
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (lot_id, _) = setup_lot_and_slot(state.clone(), &admin_tok).await;
+    let (user_tok, user_id) = register_user_it(state.clone(), "driver@example.com").await;
+    let user_id = uuid::Uuid::parse_str(&user_id).unwrap();
+
+    let march = chrono::Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+    {
+        let guard = state.read().await;
+        let charger = EvCharger {
+            id: uuid::Uuid::new_v4(),
+            lot_id: uuid::Uuid::parse_str(&lot_id).unwrap(),
+            label: "CP-1".to_string(),
+            connector_type: ConnectorType::Type2,
+            power_kw: 11.0,
+            status: EvChargerStatus::Available,
+            location_hint: None,
+            created_at: march,
+        };
+        guard.db.save_charger(&charger).await.unwrap();
+        for (kwh, ended) in [
+            (12.0, march),
+            (8.0, march),
+            (30.0, march + chrono::Duration::days(30)),
+        ] {
+            let session = ChargingSession {
+                id: uuid::Uuid::new_v4(),
+                charger_id: charger.id,
+                user_id,
+                booking_id: None,
+                start_time: ended - chrono::Duration::hours(2),
+                end_time: Some(ended),
+                kwh_consumed: kwh,
+                status: ChargingSessionStatus::Completed,
+                created_at: ended,
+            };
+            guard.db.save_charging_session(&session).await.unwrap();
+        }
+        guard
+            .db
+            .set_setting("ev_co2_grams_per_kwh", "500")
+            .await
+            .unwrap();
+    }
+
+    let get = |uri: &str, token: &str| {
+        let req = Request::get(uri)
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::empty())
+            .unwrap();
+        let state = state.clone();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let (status, json) = get("/api/v1/user/energy?month=2026-03", &user_tok).await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert_eq!(json["data"]["month"], "2026-03");
+    assert_eq!(json["data"]["totals"]["sessions"], 2);
+    assert_eq!(json["data"]["totals"]["kwh"], 20.0);
+    assert_eq!(json["data"]["totals"]["co2_kg"], 10.0);
+    assert_eq!(json["data"]["lots"][0]["lot_id"], lot_id);
+    assert_eq!(json["data"]["lots"][0]["lot_name"], "Test Lot");
+    assert!(json["data"].get("users").is_none());
+
+    let (status, _) = get("/api/v1/user/energy?month=March", &user_tok).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Per-user figures are for admins only
+    let (status, _) = get("/api/v1/admin/analytics/energy?month=2026-03", &user_tok).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, json) = get("/api/v1/admin/analytics/energy?month=2026-04", &admin_tok).await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert_eq!(json["data"]["totals"]["sessions"], 1);
+    assert_eq!(json["data"]["users"][0]["user_id"], user_id.to_string());
+    assert_eq!(json["data"]["users"][0]["kwh"], 30.0);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 76. BACKUP AND RESTORE
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_backup_download_and_restore() {
+    // Keeps the data directory, where backups are written, until the end
+    let h = test_harness().await;
+    let state = h.state;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (user_tok, user_id) = register_user_it(state.clone(), "before@example.com").await;
+
+    let backup = |token: &str, passphrase: &str| {
+        let req = Request::post("/api/v1/admin/backup")
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(
+                serde_json::to_vec(&serde_json::json!({ "passphrase": passphrase })).unwrap(),
+            ))
+            .unwrap();
+        router(state.clone()).oneshot(req)
+    };
+
+    let resp = backup(&user_tok, "correct horse battery").await.unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    // Admins below SuperAdmin get neither password hashes nor a restore
+    set_role_it(&state, &user_id, parkhub_common::UserRole::Admin).await;
+    let resp = backup(&user_tok, "correct horse battery").await.unwrap();
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+    let resp = backup(&admin_tok, "short").await.unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let resp = backup(&admin_tok, "correct horse battery").await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(
+        resp.headers()["content-disposition"]
+            .to_str()
+            .unwrap()
+            .contains("parkhub-backup-")
+    );
+    let archive = body_bytes(resp).await;
+
+    // Changed after the backup; gone again after the restore
+    register_user_it(state.clone(), "after@example.com").await;
+
+    let restore = |token: &str, archive: &[u8], passphrase: &str, confirm: &str| {
+        let boundary = "parkhub-test-boundary";
+        let mut body = format!(
+            "--{boundary}\r\nContent-Disposition: form-data; name=\"passphrase\"\r\n\r\n\
+             {passphrase}\r\n--{boundary}\r\nContent-Disposition: form-data; \
+             name=\"confirm\"\r\n\r\n{confirm}\r\n--{boundary}\r\nContent-Disposition: \
+             form-data; name=\"file\"; filename=\"backup.phbak\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n"
+        )
+        .into_bytes();
+        body.extend_from_slice(archive);
+        body.extend_from_slice(format!("\r\n--{boundary}--\r\n").as_bytes());
+        let req = Request::post("/api/v1/admin/restore")
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={boundary}"),
+            )
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(body))
+            .unwrap();
+        let state = state.clone();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+
+    let (status, _) = restore(&user_tok, &archive, "correct horse battery", "RESTORE").await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, json) = restore(&admin_tok, &archive, "correct horse battery", "yes").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"]["code"], "CONFIRMATION_REQUIRED");
+    let (status, json) = restore(&admin_tok, &archive, "wrong horse battery", "RESTORE").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(json["error"]["code"], "INVALID_INPUT");
+    let (status, _) = restore(
+        &admin_tok,
+        b"not an archive",
+        "correct horse battery",
+        "RESTORE",
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    {
+        let guard = state.read().await;
+        assert!(
+            guard
+                .db
+                .get_user_by_email("after@example.com")
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    let (status, json) = restore(&admin_tok, &archive, "correct horse battery", "RESTORE").await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    // Everyone signs in again, the caller included
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/users/me")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    let guard = state.read().await;
+    assert!(
+        guard
+            .db
+            .get_user_by_email("before@example.com")
+            .await
+            .unwrap()
+            .is_some()
+    );
+    assert!(
+        guard
+            .db
+            .get_user_by_email("after@example.com")
+            .await
+            .unwrap()
+            .is_none()
+    );
+    // Temporary files are cleaned up
+    let leftovers = std::fs::read_dir(guard.db.backup_dir()).unwrap().count();
+    assert_eq!(leftovers, 0);
+}
+
+// ═════════════════════════════════════════════════════════════════════════════
+// 77. FEATURE FLAGS
+// ═════════════════════════════════════════════════════════════════════════════
+
+#[tokio::test]
+async fn test_feature_flag_hides_subsystem_and_capability() {
+    let state = test_state().await;
+    let admin_tok = admin_token_it(state.clone()).await;
+    let (user_tok, _) = register_user_it(state.clone(), "flags@example.com").await;
+
+    let set_flag = |token: &str, key: &str, enabled: bool| {
+        let req = Request::put(format!("/api/v1/admin/feature-flags/{key}"))
+            .header("content-type", "application/json")
+            .header("authorization", format!("Bearer {token}"))
+            .body(Body::from(
+                serde_json::to_vec(&serde_json::json!({ "enabled": enabled })).unwrap(),
+            ))
+            .unwrap();
+        let state = state.clone();
+        async move {
+            let resp = router(state).oneshot(req).await.unwrap();
+            (resp.status(), body_json(resp).await)
+        }
+    };
+    let features = || {
+        let body = serde_json::json!({
+            "client_version": "1.0.0",
+            "protocol_version": parkhub_common::PROTOCOL_VERSION,
+        });
+        let req = Request::post("/handshake")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body).unwrap()))
+            .unwrap();
+        let state = state.clone();
+        async move {
+            let json = body_json(router(state).oneshot(req).await.unwrap()).await;
+            json["data"]["features"].as_array().unwrap().clone()
+        }
+    };
+    let signage_status = || {
+        let req = Request::get("/api/v1/public/occupancy")
+            .body(Body::empty())
+            .unwrap();
+        let state = state.clone();
+        async move { router(state).oneshot(req).await.unwrap().status() }
+    };
+
+    assert!(
+        features()
+            .await
+            .contains(&serde_json::json!("public-signage"))
+    );
+    assert_eq!(signage_status().await, StatusCode::OK);
+
+    let (status, _) = set_flag(&user_tok, "public-signage", false).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = set_flag(&admin_tok, "no-such-flag", false).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, json) = set_flag(&admin_tok, "public-signage", false).await;
+    assert_eq!(status, StatusCode::OK, "{json}");
+    assert_eq!(json["data"]["enabled"], false);
+    assert_eq!(json["data"]["default_enabled"], true);
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/public/occupancy")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    assert_eq!(body_json(resp).await["error"]["code"], "MODULE_DISABLED");
+    assert!(
+        !features()
+            .await
+            .contains(&serde_json::json!("public-signage"))
+    );
+
+    let resp = router(state.clone())
+        .oneshot(
+            Request::get("/api/v1/admin/feature-flags")
+                .header("authorization", format!("Bearer {admin_tok}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let json = body_json(resp).await;
+    let signage = json["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|flag| flag["key"] == "public-signage")
+        .unwrap();
+    assert_eq!(signage["enabled"], false);
+    assert!(signage["updated_by"].is_string());
+
+    set_flag(&admin_tok, "public-signage", true).await;
+    assert_eq!(signage_status().await, StatusCode::OK);
+}
@@ -4,11 +4,32 @@
 //! - **`AutoRelease`** (every 5 min): cancel no-show bookings after the configured threshold;
 //!   per-lot `check_in_deadline_minutes` overrides the global `auto_release_minutes`; after
 //!   releasing, the next FIFO waitlist entry is promoted to Offered status (P1-1 + P1-2).
+//! - **`ExpireBookings`** (every 1 min): settle bookings whose end time has passed —
+//!   checked-in ones complete, unconfirmed ones expire, never-checked-in ones become
+//!   no-shows where no-show release is on for the lot — and restore their slots' status
 //! - **`ExpireWaitlistOffers`** (every 5 min): expire outstanding waitlist offers whose
-//!   `offer_expires_at` has passed and promote the next Waiting entry (P1-2).
+//!   `offer_expires_at` has passed and promote the next Waiting entry (P1-2), then offer
+//!   freed slots to entries waiting for a time window.
+//! - **`ExpireSlotHolds`** (every 30 s): remove booking-form slot holds that ran out
+//!   (see `crate::api::slot_holds`)
+//! - **`AssignSlots`** (every 1 min): give late-binding bookings their concrete slot once the
+//!   lot's assignment lead time before the start is reached (see `crate::api::slot_assignment`)
 //! - **`ExpandRecurring`** (every 1 h): create future booking instances for recurring series
 //! - **`PurgeExpired`** (every 24 h): remove old cancelled/expired bookings beyond retention period
+//! - **`AnonymizeBookings`** (every 24 h): strip plates, vehicle details and notes from settled
+//!   bookings older than the `booking_anonymize_after_days` admin setting, keeping pricing
 //! - **`AggregateOccupancy`** (every 15 min): persist aggregated occupancy stats to settings
+//! - **`PruneRefreshTokens`** (every 24 h): forget rotated-out refresh tokens whose session
+//!   has expired (see `crate::db::Database::rotate_refresh_token`)
+//! - **`Telemetry`** (every 24 h): send the opt-in usage report; a no-op unless an operator
+//!   enabled telemetry and configured an endpoint (see `crate::telemetry`)
+//! - **`EmailQueue`** (every 1 min, `mod-email` only): retry queued emails whose backoff
+//!   has elapsed and drop expired dead letters (see `crate::email_queue`)
+//! - **`PermitReminders`** (every 1 h, `mod-email` only): email holders whose parking
+//!   permit expires within a week (see `crate::api::permits`)
+//! - **`MonthlySummaries`** (every 1 h, `mod-email` only): once a month, email users their
+//!   parking and EV charging summary of the previous month while the
+//!   `monthly_summary_emails` admin setting is on (see `crate::api::monthly_summary`)
 
 // Background jobs hold read/write guards within tight scoped blocks by design.
 // Clippy flags the contained scope as "not tight enough" but the block is the
@@ -23,6 +44,7 @@ use tracing::{error, info, warn};
 use uuid::Uuid;
 
 use crate::AppState;
+use crate::db::SlotChangeContext;
 use crate::metrics;
 
 pub type SharedState = Arc<RwLock<AppState>>;
@@ -39,6 +61,33 @@ pub fn start_background_jobs(state: SharedState) {
         |s| Box::pin(async move { auto_release_no_shows(&s).await }),
     );
 
+    // ── ExpireBookings: every minute ─────────────────────────────────────────
+    spawn_recurring_job(
+        "expire_bookings",
+        state.clone(),
+        None,
+        tokio::time::Duration::from_secs(60),
+        |s| Box::pin(async move { expire_ended_bookings(&s).await }),
+    );
+
+    // ── ExpireSlotHolds: every 30 seconds ───────────────────────────────────
+    spawn_recurring_job(
+        "expire_slot_holds",
+        state.clone(),
+        None,
+        tokio::time::Duration::from_secs(30),
+        |s| Box::pin(async move { expire_slot_holds(&s).await }),
+    );
+
+    // ── AssignSlots: every minute ────────────────────────────────────────────
+    spawn_recurring_job(
+        "assign_slots",
+        state.clone(),
+        None,
+        tokio::time::Duration::from_secs(60),
+        |s| Box::pin(async move { assign_late_binding_slots(&s).await }),
+    );
+
     // ── ExpandRecurring: every hour ──────────────────────────────────────────
     spawn_recurring_job(
         "expand_recurring",
@@ -57,6 +106,15 @@ pub fn start_background_jobs(state: SharedState) {
         |s| Box::pin(async move { purge_expired_bookings(&s).await }),
     );
 
+    // ── AnonymizeBookings: every 24 hours (first run after 75 s) ────────────
+    spawn_recurring_job(
+        "anonymize_bookings",
+        state.clone(),
+        Some(tokio::time::Duration::from_secs(75)),
+        tokio::time::Duration::from_secs(86400),
+        |s| Box::pin(async move { anonymize_old_bookings(&s).await }),
+    );
+
     // ── RetentionPurge: every 24 hours (first run after 90 s) ───────────────
     spawn_recurring_job(
         "retention_purge",
@@ -84,10 +142,60 @@ pub fn start_background_jobs(state: SharedState) {
         |s| Box::pin(async move { aggregate_occupancy_stats(&s).await }),
     );
 
+    // ── PruneRefreshTokens: every 24 hours (first run after 5 min) ──────────
+    spawn_recurring_job(
+        "prune_refresh_tokens",
+        state.clone(),
+        Some(tokio::time::Duration::from_secs(300)),
+        tokio::time::Duration::from_secs(86400),
+        |s| Box::pin(async move { prune_refresh_tokens(&s).await }),
+    );
+
+    // ── Telemetry: every 24 hours (first run after 10 min) ──────────────────
+    spawn_recurring_job(
+        "telemetry",
+        state.clone(),
+        Some(tokio::time::Duration::from_secs(600)),
+        tokio::time::Duration::from_secs(86400),
+        |s| Box::pin(async move { send_telemetry(&s).await }),
+    );
+
+    // ── EmailQueue: every minute ─────────────────────────────────────────────
+    #[cfg(feature = "mod-email")]
+    spawn_recurring_job(
+        "email_queue",
+        state.clone(),
+        None,
+        tokio::time::Duration::from_secs(60),
+        |s| Box::pin(async move { process_email_queue(&s).await }),
+    );
+
+    // ── PermitReminders: every hour (first run after 2 min) ─────────────────
+    #[cfg(feature = "mod-email")]
+    spawn_recurring_job(
+        "permit_reminders",
+        state.clone(),
+        Some(tokio::time::Duration::from_secs(120)),
+        tokio::time::Duration::from_secs(3600),
+        |s| Box::pin(async move { send_permit_reminders(&s).await }),
+    );
+
+    // ── MonthlySummaries: every hour (first run after 3 min) ────────────────
+    #[cfg(feature = "mod-email")]
+    spawn_recurring_job(
+        "monthly_summaries",
+        state.clone(),
+        Some(tokio::time::Duration::from_secs(180)),
+        tokio::time::Duration::from_secs(3600),
+        |s| Box::pin(async move { send_monthly_summaries(&s).await }),
+    );
+
     info!(
-        "Background jobs started: AutoRelease (5m), ExpireWaitlistOffers (5m), \
-         ExpandRecurring (1h), PurgeExpired (24h), AggregateOccupancy (15m), \
-         RetentionPurge (24h)"
+        "Background jobs started: AutoRelease (5m), ExpireBookings (1m), \
+         ExpireWaitlistOffers (5m), ExpireSlotHolds (30s), AssignSlots (1m), ExpandRecurring (1h), PurgeExpired (24h), \
+         AnonymizeBookings (24h), AggregateOccupancy (15m), \
+         RetentionPurge (24h), PruneRefreshTokens (24h), Telemetry (24h, opt-in), \
+         EmailQueue (1m), PermitReminders (1h), MonthlySummaries (1h)"
     );
 }
 
@@ -248,11 +356,15 @@ async fn auto_release_no_shows(state: &SharedState) -> anyhow::Result<()> {
             error!("AutoRelease: failed to save booking {}: {e}", booking.id);
             continue;
         }
-        // Free the slot.
-        if let Err(e) = guard
-            .db
-            .update_slot_status(&slot_id, parkhub_common::SlotStatus::Available)
-            .await
+        metrics::record_no_show(&lot_id.to_string());
+        // Free the slot unless another booking holds it.
+        if let Err(e) = restore_slot_status(
+            &guard,
+            booking.slot_id,
+            &SlotChangeContext::system("no_show")
+                .reason(format!("Booking {} auto-released", booking.id)),
+        )
+        .await
         {
             warn!(
                 "AutoRelease: failed to free slot {slot_id} for booking {}: {e}",
@@ -275,10 +387,219 @@ async fn auto_release_no_shows(state: &SharedState) -> anyhow::Result<()> {
     Ok(())
 }
 
-/// Expire outstanding waitlist offers and promote the next in line.
+/// Status an ended booking settles in. Checked-in bookings complete and
+/// unconfirmed ones expire. A confirmed booking nobody checked in to is a
+/// no-show where `no_shows_tracked`; elsewhere check-in is not in use, so it
+/// completes.
+fn settled_status(
+    booking: &parkhub_common::Booking,
+    no_shows_tracked: bool,
+) -> parkhub_common::BookingStatus {
+    use parkhub_common::BookingStatus;
+
+    match booking.status {
+        BookingStatus::Pending => BookingStatus::Expired,
+        BookingStatus::Confirmed if booking.check_in_time.is_none() && no_shows_tracked => {
+            BookingStatus::NoShow
+        }
+        _ => BookingStatus::Completed,
+    }
+}
+
+/// Whether `booking` is still pending, confirmed or active although its
+/// end time has passed by `now`.
+fn is_unsettled_past_end(booking: &parkhub_common::Booking, now: chrono::DateTime<Utc>) -> bool {
+    matches!(
+        booking.status,
+        parkhub_common::BookingStatus::Pending
+            | parkhub_common::BookingStatus::Confirmed
+            | parkhub_common::BookingStatus::Active
+    ) && booking.end_time <= now
+}
+
+/// Settle every pending, confirmed or active booking whose end time has
+/// passed (see [`settled_status`]) and restore its slot's status.
+///
+/// No-shows are tracked where no-show release is on: `auto_release_enabled`
+/// globally and a lot deadline other than `0`. Those lots release most
+/// no-shows early in [`auto_release_no_shows`]; this catches bookings that
+/// ended before the deadline ran out.
+async fn expire_ended_bookings(state: &SharedState) -> anyhow::Result<()> {
+    let now = Utc::now();
+    let (release_enabled, ended) = {
+        let guard = state.read().await;
+        let release_enabled = guard
+            .db
+            .get_setting("auto_release_enabled")
+            .await
+            .unwrap_or(None)
+            .unwrap_or_default()
+            .parse::<bool>()
+            .unwrap_or(false);
+        let ended = guard.db.list_live_bookings_ended_by(now).await?;
+        (release_enabled, ended)
+    };
+    if ended.is_empty() {
+        return Ok(());
+    }
+
+    let mut tracked_lots: std::collections::HashMap<Uuid, bool> = std::collections::HashMap::new();
+    let mut settled = 0;
+    for snapshot in ended {
+        let guard = state.write().await;
+        // The snapshot may be stale by now (checkout, cancellation, admin
+        // edit): settle the stored booking, and only if it still needs it.
+        let mut booking = match guard.db.get_booking(&snapshot.id.to_string()).await {
+            Ok(Some(booking)) if is_unsettled_past_end(&booking, now) => booking,
+            Ok(_) => continue,
+            Err(e) => {
+                error!(
+                    "ExpireBookings: failed to reload booking {}: {e}",
+                    snapshot.id
+                );
+                continue;
+            }
+        };
+        let no_shows_tracked = if release_enabled {
+            if let Some(&tracked) = tracked_lots.get(&booking.lot_id) {
+                tracked
+            } else {
+                let tracked = guard
+                    .db
+                    .get_setting(&crate::api::noshow::lot_deadline_key(
+                        &booking.lot_id.to_string(),
+                    ))
+                    .await
+                    .unwrap_or(None)
+                    .and_then(|v| v.parse::<i64>().ok())
+                    != Some(0);
+                tracked_lots.insert(booking.lot_id, tracked);
+                tracked
+            }
+        } else {
+            false
+        };
+
+        let status = settled_status(&booking, no_shows_tracked);
+        if booking.status == parkhub_common::BookingStatus::Active
+            && booking.check_out_time.is_none()
+        {
+            booking.check_out_time = Some(booking.end_time);
+        }
+        booking.status = status.clone();
+        booking.updated_at = now;
+        if let Err(e) = guard.db.save_booking(&booking).await {
+            error!("ExpireBookings: failed to save booking {}: {e}", booking.id);
+            continue;
+        }
+        match status {
+            parkhub_common::BookingStatus::NoShow => {
+                metrics::record_no_show(&booking.lot_id.to_string());
+            }
+            parkhub_common::BookingStatus::Expired => metrics::record_booking_event("expired"),
+            _ => metrics::record_booking_event("completed"),
+        }
+        settled += 1;
+
+        if let Err(e) = restore_slot_status(
+            &guard,
+            booking.slot_id,
+            &SlotChangeContext::system("booking_ended")
+                .reason(format!("Booking {} ended", booking.id)),
+        )
+        .await
+        {
+            warn!(
+                "ExpireBookings: failed to restore slot {} after booking {}: {e}",
+                booking.slot_id, booking.id
+            );
+        }
+    }
+
+    info!("ExpireBookings: settled {settled} ended booking(s)");
+    Ok(())
+}
+
+/// Status a slot shows given its current and upcoming `bookings`: occupied
+/// while someone is checked in, reserved while a booking is running or
+/// upcoming, available otherwise.
+fn slot_status_for(
+    bookings: &[parkhub_common::Booking],
+    now: chrono::DateTime<Utc>,
+) -> parkhub_common::SlotStatus {
+    if bookings
+        .iter()
+        .any(|b| b.status == parkhub_common::BookingStatus::Active && b.start_time <= now)
+    {
+        parkhub_common::SlotStatus::Occupied
+    } else if bookings.is_empty() {
+        parkhub_common::SlotStatus::Available
+    } else {
+        parkhub_common::SlotStatus::Reserved
+    }
+}
+
+/// Bring a reserved or occupied slot's status in line with the bookings
+/// still holding it, after one of them ended or was released. Slots in
+/// maintenance or disabled, and unassigned late-binding bookings, are left
+/// alone.
+async fn restore_slot_status(
+    state: &AppState,
+    slot_id: Uuid,
+    change: &SlotChangeContext,
+) -> anyhow::Result<()> {
+    if slot_id.is_nil() {
+        return Ok(());
+    }
+    let Some(slot) = state.db.get_parking_slot(&slot_id.to_string()).await? else {
+        return Ok(());
+    };
+    if !matches!(
+        slot.status,
+        parkhub_common::SlotStatus::Reserved | parkhub_common::SlotStatus::Occupied
+    ) {
+        return Ok(());
+    }
+    let now = Utc::now();
+    let Some(from_now) = parkhub_common::TimeRange::new(now, chrono::DateTime::<Utc>::MAX_UTC)
+    else {
+        return Ok(());
+    };
+    let bookings = state
+        .db
+        .list_slot_bookings_overlapping(&[slot_id], &from_now)
+        .await?;
+    let status = slot_status_for(&bookings, now);
+    if status != slot.status {
+        state
+            .db
+            .update_slot_status(&slot_id.to_string(), status, change)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Remove slot holds whose two minutes are up. Bookings already ignore
+/// them; this keeps the table from collecting abandoned holds.
+async fn expire_slot_holds(state: &SharedState) -> anyhow::Result<()> {
+    let removed = {
+        let guard = state.read().await;
+        guard.db.delete_expired_slot_holds(Utc::now()).await?
+    };
+    if removed > 0 {
+        info!("Expired {removed} slot hold(s)");
+    }
+    Ok(())
+}
+
+/// Expire outstanding waitlist offers and promote the next in line, then
+/// offer slots freed for the windows of waiting entries.
 async fn expire_waitlist_offers_job(state: &SharedState) -> anyhow::Result<()> {
-    let guard = state.read().await;
-    crate::api::noshow::expire_outstanding_offers(&guard).await
+    // The write lock keeps bookings from taking a slot between the match and
+    // the offer that holds it.
+    let guard = state.write().await;
+    crate::api::noshow::expire_outstanding_offers(&guard).await?;
+    crate::api::noshow::match_waitlist_windows(&guard).await
 }
 
 /// For every active recurring booking, ensure single-booking instances exist for
@@ -455,6 +776,7 @@ async fn expand_recurring_bookings(state: &SharedState) -> anyhow::Result<()> {
                             currency: "EUR".to_string(),
                             payment_status: parkhub_common::PaymentStatus::Pending,
                             payment_method: None,
+                            cancellation_fee: None,
                         },
                         created_at: Utc::now(),
                         updated_at: Utc::now(),
@@ -466,6 +788,8 @@ async fn expand_recurring_bookings(state: &SharedState) -> anyhow::Result<()> {
                         // user's tenant so background-created rows are scoped
                         // the same way as user-created ones.
                         tenant_id: user.tenant_id.clone(),
+                        tags: std::collections::BTreeMap::new(),
+                        booked_for: None,
                     };
 
                     let guard = state.write().await;
@@ -544,6 +868,27 @@ async fn purge_expired_bookings(state: &SharedState) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Strip personal data from settled bookings that ended more than
+/// `booking_anonymize_after_days` ago; a no-op while the setting is `0`.
+/// Unlike account anonymization the user keeps their account, and the
+/// bookings keep their times and pricing for accounting.
+async fn anonymize_old_bookings(state: &SharedState) -> anyhow::Result<()> {
+    let guard = state.write().await;
+    let days = crate::api::settings::read_admin_setting(&guard.db, "booking_anonymize_after_days")
+        .await
+        .parse::<i64>()
+        .unwrap_or(0);
+    if days <= 0 {
+        return Ok(());
+    }
+    let cutoff = Utc::now() - Duration::days(days);
+    let anonymized = guard.db.anonymize_bookings_ended_before(cutoff).await?;
+    if anonymized > 0 {
+        info!("AnonymizeBookings: stripped personal data from {anonymized} booking(s)");
+    }
+    Ok(())
+}
+
 /// Run the GDPR retention engine across all registered surfaces.
 ///
 /// Uses `dry_run = false` — this is the scheduled production purge. Evidence
@@ -560,6 +905,76 @@ async fn retention_purge(state: &SharedState) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Forget rotated-out refresh tokens that have expired; they only serve
+/// reuse detection while they could still have been valid.
+async fn prune_refresh_tokens(state: &SharedState) -> anyhow::Result<()> {
+    let db = state.read().await.db.clone();
+    let pruned = db.prune_rotated_refresh_tokens().await?;
+    if pruned > 0 {
+        info!("PruneRefreshTokens: {pruned} rotated token(s) forgotten");
+    }
+    Ok(())
+}
+
+/// Send the opt-in usage report. Clones the DB handle so the state lock is
+/// not held across the HTTP request.
+async fn send_telemetry(state: &SharedState) -> anyhow::Result<()> {
+    let db = state.read().await.db.clone();
+    crate::telemetry::send_if_enabled(&db).await?;
+    Ok(())
+}
+
+/// Retry due queued emails. Clones the DB handle so the state lock is not
+/// held across SMTP round-trips.
+#[cfg(feature = "mod-email")]
+async fn process_email_queue(state: &SharedState) -> anyhow::Result<()> {
+    let db = state.read().await.db.clone();
+    let attempted = crate::email_queue::process_queue(&db).await?;
+    if attempted > 0 {
+        info!("Email queue: {attempted} delivery attempt(s)");
+    }
+    Ok(())
+}
+
+/// Queue expiry reminders for parking permits running out within a week.
+#[cfg(feature = "mod-email")]
+async fn send_permit_reminders(state: &SharedState) -> anyhow::Result<()> {
+    let (db, org_name) = {
+        let guard = state.read().await;
+        (guard.db.clone(), guard.config.organization_name.clone())
+    };
+    let sent = crate::api::permits::send_expiry_reminders(&db, &org_name, Utc::now()).await?;
+    if sent > 0 {
+        info!("Permit reminders: {sent} queued");
+    }
+    Ok(())
+}
+
+/// Queue last month's summary emails once the month is over.
+#[cfg(feature = "mod-email")]
+async fn send_monthly_summaries(state: &SharedState) -> anyhow::Result<()> {
+    let (db, org_name) = {
+        let guard = state.read().await;
+        (guard.db.clone(), guard.config.organization_name.clone())
+    };
+    let sent =
+        crate::api::monthly_summary::send_monthly_summaries(&db, &org_name, Utc::now()).await?;
+    if sent > 0 {
+        info!("Monthly summaries: {sent} queued");
+    }
+    Ok(())
+}
+
+/// Assign slots to late-binding bookings that are due.
+async fn assign_late_binding_slots(state: &SharedState) -> anyhow::Result<()> {
+    let db = state.read().await.db.clone();
+    let assigned = crate::api::slot_assignment::assign_due_slots(&db, Utc::now()).await?;
+    if assigned > 0 {
+        info!("Slot assignment: {assigned} booking(s) assigned");
+    }
+    Ok(())
+}
+
 /// Compute and persist basic occupancy stats per lot into the settings store.
 /// Key: `occupancy_stats_<lot_id>`, value: `<occupied>/<total>`.
 async fn aggregate_occupancy_stats(state: &SharedState) -> anyhow::Result<()> {
@@ -638,7 +1053,7 @@ mod tests {
             db,
             mdns: None,
             scheduler: None,
-            ws_events: crate::api::ws::EventBroadcaster::new(),
+            ws_events: crate::api::events::EventBroadcaster::new(),
             fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
             revocation_store: crate::jwt::TokenRevocationList::new(),
         }));
@@ -727,6 +1142,7 @@ mod tests {
                 currency: "EUR".to_string(),
                 payment_status: parkhub_common::PaymentStatus::Pending,
                 payment_method: None,
+                cancellation_fee: None,
             },
             created_at: Utc::now() - Duration::hours(3),
             updated_at: Utc::now() - Duration::hours(3),
@@ -735,6 +1151,8 @@ mod tests {
             qr_code: None,
             notes: None,
             tenant_id: None,
+            tags: std::collections::BTreeMap::new(),
+            booked_for: None,
         };
 
         {
@@ -796,6 +1214,7 @@ mod tests {
                 currency: "EUR".to_string(),
                 payment_status: parkhub_common::PaymentStatus::Pending,
                 payment_method: None,
+                cancellation_fee: None,
             },
             created_at: now - Duration::days(updated_offset_days),
             updated_at: now - Duration::days(updated_offset_days),
@@ -804,6 +1223,8 @@ mod tests {
             qr_code: None,
             notes: None,
             tenant_id: None,
+            tags: std::collections::BTreeMap::new(),
+            booked_for: None,
         }
     }
 
@@ -1074,6 +1495,114 @@ mod tests {
         assert!(result.is_some(), "active booking must never be purged");
     }
 
+    #[tokio::test]
+    async fn anonymize_bookings_strips_old_settled_bookings_only() {
+        let (state, _dir) = job_test_state();
+        {
+            let guard = state.read().await;
+            guard
+                .db
+                .set_setting("booking_anonymize_after_days", "30")
+                .await
+                .unwrap();
+        }
+
+        let ids = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut old_completed = make_booking(
+            ids.0,
+            ids.1,
+            ids.2,
+            parkhub_common::BookingStatus::Completed,
+            -24 * 40, // ended 40 days ago
+            40,
+        );
+        old_completed.notes = Some("Visiting Dr. Example".to_string());
+        old_completed.vehicle.color = Some("Red".to_string());
+        old_completed.pricing.total = 12.5;
+        let recent_completed = make_booking(
+            ids.0,
+            ids.1,
+            ids.2,
+            parkhub_common::BookingStatus::Completed,
+            -24 * 10,
+            10,
+        );
+        let old_active = make_booking(
+            ids.0,
+            ids.1,
+            ids.2,
+            parkhub_common::BookingStatus::Active,
+            -24 * 40,
+            40,
+        );
+
+        {
+            let guard = state.read().await;
+            for booking in [&old_completed, &recent_completed, &old_active] {
+                guard.db.save_booking(booking).await.unwrap();
+            }
+        }
+
+        anonymize_old_bookings(&state).await.unwrap();
+
+        let guard = state.read().await;
+        let scrubbed = guard
+            .db
+            .get_booking(&old_completed.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(scrubbed.vehicle.license_plate, "[DELETED]");
+        assert!(scrubbed.vehicle.color.is_none());
+        assert!(scrubbed.notes.is_none());
+        assert!((scrubbed.pricing.total - 12.5).abs() < f64::EPSILON);
+        assert_eq!(scrubbed.status, parkhub_common::BookingStatus::Completed);
+        assert_eq!(scrubbed.user_id, old_completed.user_id);
+
+        for kept in [&recent_completed, &old_active] {
+            let booking = guard
+                .db
+                .get_booking(&kept.id.to_string())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                booking.vehicle.license_plate, "TEST-001",
+                "recent or unsettled bookings must keep their plate"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn anonymize_bookings_is_off_by_default() {
+        let (state, _dir) = job_test_state();
+
+        let ids = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let old_completed = make_booking(
+            ids.0,
+            ids.1,
+            ids.2,
+            parkhub_common::BookingStatus::Completed,
+            -24 * 400,
+            400,
+        );
+        {
+            let guard = state.read().await;
+            guard.db.save_booking(&old_completed).await.unwrap();
+        }
+
+        anonymize_old_bookings(&state).await.unwrap();
+
+        let guard = state.read().await;
+        let kept = guard
+            .db
+            .get_booking(&old_completed.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(kept.vehicle.license_plate, "TEST-001");
+    }
+
     #[tokio::test]
     async fn aggregate_occupancy_writes_stats_for_lot() {
         let (state, _dir) = job_test_state();
@@ -1376,6 +1905,9 @@ mod tests {
                 status: parkhub_common::models::WaitlistStatus::Waiting,
                 offer_expires_at: None,
                 accepted_booking_id: None,
+                start_time: None,
+                end_time: None,
+                offered_slot_id: None,
             };
             entry_id = entry.id;
             guard.db.save_waitlist_entry(&entry).await.unwrap();
@@ -1400,4 +1932,171 @@ mod tests {
             "offer_expires_at must be set on promoted entry"
         );
     }
+
+    /// Helper: save a slot of `lot_id` with the given status.
+    async fn save_slot(
+        state: &SharedState,
+        lot_id: Uuid,
+        status: parkhub_common::SlotStatus,
+    ) -> Uuid {
+        let slot = parkhub_common::ParkingSlot {
+            id: Uuid::new_v4(),
+            lot_id,
+            floor_id: Uuid::new_v4(),
+            slot_number: 1,
+            row: 1,
+            column: 1,
+            slot_type: parkhub_common::SlotType::Standard,
+            status,
+            current_booking: None,
+            features: Vec::new(),
+            position: parkhub_common::SlotPosition {
+                x: 0.0,
+                y: 0.0,
+                width: 2.5,
+                height: 5.0,
+                rotation: 0.0,
+            },
+            is_accessible: false,
+        };
+        state
+            .read()
+            .await
+            .db
+            .save_parking_slot(&slot)
+            .await
+            .unwrap();
+        slot.id
+    }
+
+    #[test]
+    fn settled_status_by_booking_state() {
+        use parkhub_common::BookingStatus;
+
+        let (user, lot, slot) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let pending = make_booking(user, lot, slot, BookingStatus::Pending, -2, 0);
+        let active = make_booking(user, lot, slot, BookingStatus::Active, -2, 0);
+        let confirmed = make_booking(user, lot, slot, BookingStatus::Confirmed, -2, 0);
+        let mut checked_in = confirmed.clone();
+        checked_in.check_in_time = Some(checked_in.start_time);
+
+        assert_eq!(settled_status(&pending, true), BookingStatus::Expired);
+        assert_eq!(settled_status(&active, true), BookingStatus::Completed);
+        assert_eq!(settled_status(&checked_in, true), BookingStatus::Completed);
+        assert_eq!(settled_status(&confirmed, true), BookingStatus::NoShow);
+        assert_eq!(settled_status(&confirmed, false), BookingStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn expire_bookings_completes_ended_booking_and_frees_slot() {
+        let (state, _dir) = job_test_state();
+        let (user_id, lot_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let slot_id = save_slot(&state, lot_id, parkhub_common::SlotStatus::Occupied).await;
+        let mut active = make_booking(
+            user_id,
+            lot_id,
+            slot_id,
+            parkhub_common::BookingStatus::Active,
+            -2,
+            0,
+        );
+        active.check_in_time = Some(active.start_time);
+        let pending = make_booking(
+            user_id,
+            lot_id,
+            Uuid::nil(),
+            parkhub_common::BookingStatus::Pending,
+            -3,
+            0,
+        );
+        {
+            let guard = state.read().await;
+            guard.db.save_booking(&active).await.unwrap();
+            guard.db.save_booking(&pending).await.unwrap();
+        }
+
+        expire_ended_bookings(&state).await.unwrap();
+
+        let guard = state.read().await;
+        let completed = guard
+            .db
+            .get_booking(&active.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(completed.status, parkhub_common::BookingStatus::Completed);
+        assert_eq!(completed.check_out_time, Some(active.end_time));
+        let expired = guard
+            .db
+            .get_booking(&pending.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(expired.status, parkhub_common::BookingStatus::Expired);
+        let slot = guard
+            .db
+            .get_parking_slot(&slot_id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(slot.status, parkhub_common::SlotStatus::Available);
+    }
+
+    #[tokio::test]
+    async fn expire_bookings_marks_no_show_and_keeps_slot_reserved_for_next_booking() {
+        let (state, _dir) = job_test_state();
+        let (user_id, lot_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let slot_id = save_slot(&state, lot_id, parkhub_common::SlotStatus::Reserved).await;
+        let ended = make_booking(
+            user_id,
+            lot_id,
+            slot_id,
+            parkhub_common::BookingStatus::Confirmed,
+            -2,
+            0,
+        );
+        let upcoming = make_booking(
+            user_id,
+            lot_id,
+            slot_id,
+            parkhub_common::BookingStatus::Confirmed,
+            2,
+            0,
+        );
+        {
+            let guard = state.read().await;
+            guard
+                .db
+                .set_setting("auto_release_enabled", "true")
+                .await
+                .unwrap();
+            guard.db.save_booking(&ended).await.unwrap();
+            guard.db.save_booking(&upcoming).await.unwrap();
+        }
+
+        expire_ended_bookings(&state).await.unwrap();
+
+        let guard = state.read().await;
+        let no_show = guard
+            .db
+            .get_booking(&ended.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(no_show.status, parkhub_common::BookingStatus::NoShow);
+        let next = guard
+            .db
+            .get_booking(&upcoming.id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.status, parkhub_common::BookingStatus::Confirmed);
+        let slot = guard
+            .db
+            .get_parking_slot(&slot_id.to_string())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(slot.status, parkhub_common::SlotStatus::Reserved);
+    }
 }
@@ -20,7 +20,7 @@ use axum::{
     extract::FromRequestParts,
     http::{header::AUTHORIZATION, request::Parts},
 };
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, TokenData, Validation, decode, encode};
 use rand::RngExt;
 use serde::{Deserialize, Serialize};
@@ -338,6 +338,9 @@ pub struct Claims {
     /// still parse cleanly.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub family_id: Option<String>,
+    /// ID of the admin acting as `sub`, on impersonation tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub impersonator: Option<String>,
 }
 
 /// Token type
@@ -422,6 +425,7 @@ impl JwtManager {
             token_type: TokenType::Access,
             jti: Uuid::new_v4().to_string(),
             family_id: Some(family_id.to_string()),
+            impersonator: None,
         };
 
         let access_token = encode(&Header::default(), &access_claims, &self.encoding_key)
@@ -439,6 +443,7 @@ impl JwtManager {
             token_type: TokenType::Refresh,
             jti: Uuid::new_v4().to_string(),
             family_id: Some(family_id.to_string()),
+            impersonator: None,
         };
 
         let refresh_token = encode(&Header::default(), &refresh_claims, &self.encoding_key)
@@ -452,6 +457,41 @@ impl JwtManager {
         })
     }
 
+    /// Mint a standalone access token in `family_id`, valid for
+    /// `access_token_expiry_hours` but never past `expires_at`.
+    ///
+    /// Used for session-backed logins: the refresh token lives server-side
+    /// with the session, so only the access token is signed. `expires_at` is
+    /// the session's own expiry. `impersonator` is set when an admin acts as
+    /// the user.
+    pub fn generate_access_token(
+        &self,
+        user_id: &Uuid,
+        username: &str,
+        role: &str,
+        family_id: &str,
+        expires_at: DateTime<Utc>,
+        impersonator: Option<&Uuid>,
+    ) -> Result<String, AppError> {
+        let now = Utc::now();
+        let exp = expires_at.min(now + Duration::hours(self.config.access_token_expiry_hours));
+        let claims = Claims {
+            sub: user_id.to_string(),
+            username: username.to_string(),
+            role: role.to_string(),
+            iat: now.timestamp(),
+            exp: exp.timestamp(),
+            iss: self.config.issuer.clone(),
+            token_type: TokenType::Access,
+            jti: Uuid::new_v4().to_string(),
+            family_id: Some(family_id.to_string()),
+            impersonator: impersonator.map(ToString::to_string),
+        };
+
+        encode(&Header::default(), &claims, &self.encoding_key)
+            .map_err(|e| AppError::InvalidInput(format!("Failed to create token: {e}")))
+    }
+
     /// Validate a token and return the claims.
     ///
     /// Pass `Some(revocation_list)` to also check whether the token — or its
@@ -726,6 +766,52 @@ mod tests {
         assert_eq!(tokens.token_type, "Bearer");
     }
 
+    #[tokio::test]
+    async fn test_session_access_token_is_short_lived_and_in_family() {
+        let jwt = JwtManager::with_random_secret();
+        let user_id = Uuid::new_v4();
+        let expires_at = Utc::now() + Duration::hours(8);
+
+        let token = jwt
+            .generate_access_token(&user_id, "alice", "admin", "sess-1", expires_at, None)
+            .unwrap();
+        let claims = jwt.validate_token(&token, None).await.unwrap();
+        assert_eq!(claims.sub, user_id.to_string());
+        assert_eq!(claims.role, "admin");
+        assert_eq!(claims.exp, claims.iat + 3600);
+        assert_eq!(claims.token_type, TokenType::Access);
+        assert_eq!(claims.family_id.as_deref(), Some("sess-1"));
+        assert!(claims.impersonator.is_none());
+
+        let rl = TokenRevocationList::new();
+        rl.revoke_family("sess-1").await;
+        assert!(jwt.validate_token(&token, Some(&rl)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_impersonation_token_names_the_admin() {
+        let jwt = JwtManager::with_random_secret();
+        let user_id = Uuid::new_v4();
+        let admin_id = Uuid::new_v4();
+        let expires_at = Utc::now() + Duration::minutes(30);
+
+        let token = jwt
+            .generate_access_token(
+                &user_id,
+                "alice",
+                "user",
+                "sess-2",
+                expires_at,
+                Some(&admin_id),
+            )
+            .unwrap();
+        let claims = jwt.validate_token(&token, None).await.unwrap();
+        assert_eq!(claims.sub, user_id.to_string());
+        assert_eq!(claims.impersonator, Some(admin_id.to_string()));
+        // A session ending sooner ends its token with it
+        assert_eq!(claims.exp, expires_at.timestamp());
+    }
+
     #[tokio::test]
     async fn test_access_token_has_correct_claims() {
         let jwt = JwtManager::with_random_secret();
@@ -828,6 +914,7 @@ mod tests {
             token_type: TokenType::Access,
             jti: Uuid::new_v4().to_string(),
             family_id: Some(Uuid::new_v4().to_string()),
+            impersonator: None,
         };
         let token = encode(
             &Header::default(),
@@ -10,6 +10,7 @@
 #![allow(clippy::significant_drop_tightening)]
 
 use anyhow::{Context, Result};
+use std::io::IsTerminal;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -18,7 +19,9 @@ use tracing::{info, warn};
 mod api;
 #[allow(dead_code)]
 mod audit;
+mod auto_backup;
 mod bootstrap;
+mod build_info;
 #[allow(dead_code)]
 mod circuit_breaker;
 mod config;
@@ -27,11 +30,14 @@ mod demo;
 mod discovery;
 #[cfg(feature = "mod-email")]
 mod email;
+#[cfg(feature = "mod-email")]
+mod email_queue;
 #[cfg(feature = "mod-email-templates")]
 #[allow(dead_code)]
 mod email_templates;
 #[allow(dead_code)]
 mod error;
+mod geo;
 #[allow(dead_code)]
 mod health;
 #[cfg(feature = "mod-jobs")]
@@ -50,6 +56,8 @@ mod requests;
 #[allow(dead_code)]
 mod static_files;
 #[allow(dead_code)]
+mod telemetry;
+#[allow(dead_code)]
 mod tls;
 pub mod utils;
 #[allow(dead_code)]
@@ -74,7 +82,9 @@ use bootstrap::cli::CliArgs;
 use bootstrap::health::perform_health_check;
 use bootstrap::paths::{get_data_directory, get_local_ip};
 use bootstrap::revocation::build_revocation_store;
-use bootstrap::seed::{UsernameStyle, generate_dummy_users, seed_demo_data};
+use bootstrap::seed::{
+    UsernameStyle, generate_dummy_users, require_password_change, seed_demo_data,
+};
 use config::ServerConfig;
 use db::{Database, DatabaseConfig};
 use discovery::MdnsService;
@@ -106,7 +116,7 @@ pub struct AppState {
     /// Dropping it will cancel scheduled jobs.
     pub scheduler: Option<tokio_cron_scheduler::JobScheduler>,
     /// Broadcast channel for WebSocket real-time events.
-    pub ws_events: api::ws::EventBroadcaster,
+    pub ws_events: api::events::EventBroadcaster,
     /// T-1946 — Broadcast channel for Server-Sent fleet events.
     /// Consumed by `/api/v1/events/fleet` subscribers; produced by the
     /// check-in / swap / EV-charging / guest-booking mutation handlers AFTER
@@ -148,6 +158,21 @@ async fn main() -> Result<()> {
         std::process::exit(perform_health_check(port));
     }
 
+    // `parkhub-server doctor`: offline self-test, prints a report and exits 0/1.
+    if cli.doctor {
+        std::process::exit(bootstrap::doctor::run_doctor(&cli).await);
+    }
+
+    // `parkhub-server dummy-users [list|purge]`: offline, prints and exits 0/1.
+    if let Some(action) = cli.dummy_users {
+        std::process::exit(bootstrap::dummy_users::run_dummy_users(&cli, action).await);
+    }
+
+    // `parkhub-server config export|import`: offline, exits 0/1.
+    if let Some(ref action) = cli.config_profile {
+        std::process::exit(bootstrap::config_profile::run_config_profile(&cli, action));
+    }
+
     // Set DPI awareness before creating any windows (Windows-specific)
     #[cfg(all(feature = "gui", windows))]
     if !cli.headless {
@@ -183,7 +208,11 @@ async fn main() -> Result<()> {
         .with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE)
         .init();
 
-    info!("Starting ParkHub Server v{}", env!("CARGO_PKG_VERSION"));
+    info!(
+        "Starting ParkHub Server v{} ({})",
+        build_info::VERSION,
+        build_info::GIT_HASH
+    );
     if cli.debug {
         info!("Debug mode enabled");
     }
@@ -206,8 +235,26 @@ async fn main() -> Result<()> {
 
     // Load or create configuration
     let config_path = data_dir.join("config.toml");
+    // Set when the admin password was generated or read from the environment
+    // instead of being chosen in a setup wizard
+    let mut admin_password_seeded = false;
     let mut config = if config_path.exists() {
+        if cli.setup {
+            warn!(
+                "--setup ignored: {} already exists (remove it to run setup again)",
+                config_path.display()
+            );
+        }
         ServerConfig::load(&config_path)?
+    } else if cli.setup {
+        let config = bootstrap::headless_setup::run_interactive_setup()?;
+        config.save(&config_path)?;
+        info!(
+            "Configuration saved to: {}. Admin user: {}",
+            config_path.display(),
+            config.admin_username
+        );
+        config
     } else if cli.unattended || cli.headless {
         // Unattended/headless mode - auto-configure with defaults
         info!("Auto-configuring with defaults (unattended mode)...");
@@ -231,6 +278,7 @@ async fn main() -> Result<()> {
             generated
         });
         config.admin_password_hash = hash_password(&admin_password)?;
+        admin_password_seeded = true;
         config.encryption_enabled = false; // Disable encryption for unattended setup
         config.enable_tls = false; // Disable TLS for easier initial setup
         config.generate_dummy_users = true;
@@ -241,6 +289,9 @@ async fn main() -> Result<()> {
         config.enable_mdns = false;
         config.save(&config_path)?;
         info!("Default config saved. Admin user: admin");
+        if !cli.unattended {
+            info!("Run with --setup to choose the admin account, TLS and encryption instead");
+        }
         config
     } else {
         info!("No configuration found, running setup...");
@@ -277,6 +328,7 @@ async fn main() -> Result<()> {
                 generated
             });
             config.admin_password_hash = hash_password(&admin_password)?;
+            admin_password_seeded = true;
             // Use environment variable for encryption passphrase in headless mode
             config.encryption_passphrase = std::env::var("PARKHUB_DB_PASSPHRASE").ok();
             if config.encryption_enabled && config.encryption_passphrase.is_none() {
@@ -289,6 +341,7 @@ async fn main() -> Result<()> {
             }
             config.save(&config_path)?;
             info!("Default config saved. Admin user: admin");
+            info!("Run with --setup to choose the admin account, TLS and encryption instead");
             config
         }
     };
@@ -308,6 +361,13 @@ async fn main() -> Result<()> {
     // If encryption is enabled but no passphrase, try environment variable
     if config.encryption_enabled && config.encryption_passphrase.is_none() {
         config.encryption_passphrase = std::env::var("PARKHUB_DB_PASSPHRASE").ok();
+        if config.encryption_passphrase.is_none()
+            && (cli.headless || cfg!(not(feature = "gui")))
+            && std::io::stdin().is_terminal()
+        {
+            config.encryption_passphrase =
+                Some(bootstrap::headless_setup::prompt_passphrase_tty()?);
+        }
         if config.encryption_passphrase.is_none() {
             #[cfg(feature = "gui")]
             {
@@ -341,6 +401,11 @@ async fn main() -> Result<()> {
     if db.is_fresh().await? {
         info!("Creating admin user...");
         create_admin_user(&db, &config).await?;
+        // Public demos share the admin login, so only real installs force a change
+        let demo_mode = std::env::var("DEMO_MODE").is_ok_and(|v| v == "true" || v == "1");
+        if admin_password_seeded && !demo_mode {
+            require_password_change(&db, &config.admin_username).await?;
+        }
 
         // Also create a sample parking lot
         create_sample_parking_lot(&db).await?;
@@ -386,6 +451,18 @@ async fn main() -> Result<()> {
         }
     }
 
+    // Dummy users share one password; nag until they are purged
+    if let Ok(stats) = db.stats().await
+        && stats.dummy_users > 0
+    {
+        warn!(
+            "{} dummy user(s) generated during setup still exist and share one password. \
+             Purge them before going live: `parkhub-server dummy-users purge` or \
+             DELETE /api/v1/admin/dummy-users",
+            stats.dummy_users
+        );
+    }
+
     // Start mDNS service for autodiscovery
     let mdns = if config.enable_mdns {
         match MdnsService::new(&config) {
@@ -413,7 +490,7 @@ async fn main() -> Result<()> {
         db,
         mdns,
         scheduler: None,
-        ws_events: api::ws::EventBroadcaster::new(),
+        ws_events: api::events::EventBroadcaster::new(),
         fleet_events: api::sse::FleetEventBroadcaster::new(),
         revocation_store: revocation_store.clone(),
     }));
@@ -425,7 +502,15 @@ async fn main() -> Result<()> {
 
     // Determine bind address
     let addr: SocketAddr = format!("0.0.0.0:{}", config.port).parse()?;
-    info!("Server listening on {}", addr);
+    {
+        let state_guard = state.read().await;
+        let listener = build_info::Listener {
+            addr,
+            tls: config.enable_tls,
+            data_dir: &data_dir,
+        };
+        build_info::log_startup_banner(&state_guard.db, &listener).await;
+    }
     info!(
         "Access URL: {}://{}:{}",
         if config.enable_tls { "https" } else { "http" },
@@ -656,11 +741,16 @@ async fn main() -> Result<()> {
                             );
                             let subject =
                                 format!("Parking reminder: your booking starts in {minutes_until} minutes — ParkHub");
-                            if let Err(e) =
-                                crate::email::send_email(&user.email, &subject, &email_html).await
+                            if let Err(e) = crate::email_queue::enqueue(
+                                &state_guard.db,
+                                &user.email,
+                                &subject,
+                                &email_html,
+                            )
+                            .await
                             {
                                 tracing::warn!(
-                                    "Failed to send booking reminder (booking {}): {}",
+                                    "Failed to queue booking reminder (booking {}): {}",
                                     booking.id,
                                     e
                                 );
@@ -678,7 +768,7 @@ async fn main() -> Result<()> {
                                 tracing::info!(
                                     booking_id = %booking.id,
                                     user_id = %user.id,
-                                    "Booking reminder sent"
+                                    "Booking reminder queued"
                                 );
                             }
                         }
@@ -779,54 +869,60 @@ async fn main() -> Result<()> {
         }
     }
 
-    // Periodic metrics gauge update (every 5 minutes) — lot occupancy, active bookings
+    // Business gauges (occupancy per lot, active bookings, email queue
+    // depth) are refreshed every minute so alerts fire close to real time
     {
         use tokio_cron_scheduler::{Job, JobScheduler};
 
+        metrics::update_business_gauges(&state.read().await.db).await;
+
         let sched = JobScheduler::new().await?;
         let state_for_metrics = state.clone();
         sched
-            .add(Job::new_async("0 */5 * * * *", move |_uuid, _lock| {
+            .add(Job::new_async("0 * * * * *", move |_uuid, _lock| {
                 let state = state_for_metrics.clone();
                 Box::pin(async move {
                     let state_guard = state.read().await;
-                    // Active bookings count
-                    let now = chrono::Utc::now();
-                    if let Ok(bookings) = state_guard.db.list_bookings().await {
-                        let active = bookings
-                            .iter()
-                            .filter(|b| {
-                                b.status == parkhub_common::BookingStatus::Confirmed
-                                    && b.start_time <= now
-                                    && b.end_time >= now
-                            })
-                            .count();
-                        metrics::record_active_bookings(active as u64);
-                    }
-                    // Registered users count
-                    if let Ok(users) = state_guard.db.list_users().await {
-                        metrics::record_registered_users(users.len() as u64);
-                    }
-                    // Lot occupancy
-                    if let Ok(lots) = state_guard.db.list_parking_lots().await {
-                        for lot in &lots {
-                            #[allow(clippy::cast_sign_loss)] // values are clamped to >= 0
-                            let total = lot.total_slots.max(0) as u64;
-                            #[allow(clippy::cast_sign_loss)]
-                            let occupied = (lot.total_slots - lot.available_slots).max(0) as u64;
-                            metrics::record_lot_occupancy(
-                                &lot.id.to_string(),
-                                &lot.name,
-                                total,
-                                occupied,
-                            );
-                        }
-                    }
+                    metrics::update_business_gauges(&state_guard.db).await;
                 })
             })?)
             .await?;
         sched.start().await?;
-        info!("Metrics gauge updater started (runs every 5 minutes)");
+        info!("Metrics gauge updater started (runs every minute)");
+    }
+
+    // Automatic backups into <data dir>/backups on the configured schedule
+    if config.auto_backup_enabled {
+        use tokio_cron_scheduler::{Job, JobScheduler};
+
+        let state_for_backup = state.clone();
+        match Job::new_async(config.backup_schedule.as_str(), move |_uuid, _lock| {
+            let state = state_for_backup.clone();
+            Box::pin(async move {
+                let (db, retention) = {
+                    let state_guard = state.read().await;
+                    (
+                        state_guard.db.clone(),
+                        state_guard.config.backup_retention_count,
+                    )
+                };
+                auto_backup::run_scheduled_backup(&db, retention).await;
+            })
+        }) {
+            Ok(job) => {
+                let sched = JobScheduler::new().await?;
+                sched.add(job).await?;
+                sched.start().await?;
+                info!(
+                    "Automatic backups scheduled ({}, keeping {})",
+                    config.backup_schedule, config.backup_retention_count
+                );
+            }
+            Err(e) => tracing::error!(
+                "Automatic backups disabled: invalid backup_schedule {:?}: {e}",
+                config.backup_schedule
+            ),
+        }
     }
 
     // Start background jobs (AutoRelease, ExpandRecurring, PurgeExpired, AggregateOccupancy)
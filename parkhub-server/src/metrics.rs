@@ -3,8 +3,13 @@
 //! Exposes application metrics in Prometheus format.
 
 use axum::{http::StatusCode, response::IntoResponse};
+use chrono::{DateTime, Utc};
 use metrics::{counter, gauge, histogram};
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use parkhub_common::{Booking, BookingStatus};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 
 /// Initialize the Prometheus metrics exporter.
@@ -51,6 +56,10 @@ pub fn record_http_request(method: &str, path: &str, status: u16, duration: std:
 
     counter!("http_requests_total", &labels).increment(1);
     histogram!("http_request_duration_seconds", &labels).record(duration.as_secs_f64());
+
+    if let Ok(mut totals) = REQUEST_TOTALS.lock() {
+        totals.record(status, duration);
+    }
 }
 
 /// Record a database operation
@@ -70,10 +79,23 @@ pub fn record_db_operation(
     histogram!("db_operation_duration_seconds", &labels).record(duration.as_secs_f64());
 }
 
+/// Record how long an instrumented database transaction or record decode took.
+pub fn record_db_op_duration(op: &str, table: &str, duration: std::time::Duration) {
+    let labels = [("op", op.to_string()), ("table", table.to_string())];
+    histogram!("db_op_duration_seconds", &labels).record(duration.as_secs_f64());
+}
+
+/// Record a database operation that exceeded its slow-op threshold.
+pub fn record_db_slow_operation(op: &str, table: &str) {
+    let labels = [("op", op.to_string()), ("table", table.to_string())];
+    counter!("db_slow_operations_total", &labels).increment(1);
+}
+
 /// Record active sessions
 #[allow(clippy::cast_precision_loss)]
 pub fn record_active_sessions(count: u64) {
     gauge!("active_sessions").set(count as f64);
+    ACTIVE_SESSIONS.store(count, Ordering::Relaxed);
 }
 
 /// Record active bookings
@@ -109,12 +131,49 @@ pub fn record_auth_event(event_type: &str, success: bool) {
     counter!("auth_events_total", &labels).increment(1);
 }
 
+/// Record an authentication failure counted by the brute-force guard.
+///
+/// `scope` is `"login"` or `"password_reset"`.
+pub fn record_auth_guard_failure(scope: &str) {
+    let labels = [("scope", scope.to_string())];
+    counter!("auth_guard_failures_total", &labels).increment(1);
+}
+
+/// Record an attempt the brute-force guard rejected because its account's
+/// delay was still running.
+pub fn record_auth_guard_blocked(scope: &str) {
+    let labels = [("scope", scope.to_string())];
+    counter!("auth_guard_blocked_total", &labels).increment(1);
+}
+
+/// Record a request cut off by the timeout middleware.
+///
+/// `kind` is `"handler"` or `"body_read"`.
+pub fn record_request_timeout(kind: &str) {
+    let labels = [("kind", kind.to_string())];
+    counter!("http_request_timeouts_total", &labels).increment(1);
+}
+
 /// Record booking events
 pub fn record_booking_event(event_type: &str) {
     let labels = [("event", event_type.to_string())];
     counter!("booking_events_total", &labels).increment(1);
 }
 
+/// Record a booking released as a no-show
+pub fn record_no_show(lot_id: &str) {
+    let labels = [("lot_id", lot_id.to_string())];
+    counter!("booking_no_shows_total", &labels).increment(1);
+}
+
+/// Record the outbound email queue depth: messages waiting for delivery
+/// and dead letters waiting for an admin retry
+#[allow(clippy::cast_precision_loss)]
+pub fn record_email_queue_depth(pending: u64, failed: u64) {
+    gauge!("email_queue_depth", "status" => "pending").set(pending as f64);
+    gauge!("email_queue_depth", "status" => "failed").set(failed as f64);
+}
+
 /// Record the total number of registered users
 #[allow(clippy::cast_precision_loss)]
 pub fn record_registered_users(count: u64) {
@@ -138,6 +197,63 @@ pub fn record_job_duration(job: &str, duration: std::time::Duration) {
     histogram!("job_duration_seconds", &labels).record(duration.as_secs_f64());
 }
 
+/// Whether `booking` holds its slot at `now`: confirmed or checked in, and
+/// within its booked time.
+fn is_live_at(booking: &Booking, now: DateTime<Utc>) -> bool {
+    matches!(
+        booking.status,
+        BookingStatus::Confirmed | BookingStatus::Active
+    ) && booking.start_time <= now
+        && now < booking.end_time
+}
+
+/// Live bookings at `now` per lot. A slot holds one booking at a time, so
+/// this is the number of occupied slots of each lot.
+fn live_bookings_by_lot(bookings: &[Booking], now: DateTime<Utc>) -> HashMap<uuid::Uuid, u64> {
+    let mut by_lot = HashMap::new();
+    for booking in bookings.iter().filter(|b| is_live_at(b, now)) {
+        *by_lot.entry(booking.lot_id).or_insert(0) += 1;
+    }
+    by_lot
+}
+
+/// Refresh the business gauges: active bookings, occupancy per lot,
+/// registered users and the email queue depth. Runs at startup and then
+/// every minute; a failed read leaves the previous values in place.
+pub async fn update_business_gauges(db: &crate::db::Database) {
+    let now = Utc::now();
+    let live = match db.list_bookings().await {
+        Ok(bookings) => {
+            let live = live_bookings_by_lot(&bookings, now);
+            record_active_bookings(live.values().sum());
+            Some(live)
+        }
+        Err(e) => {
+            tracing::warn!("Metrics: failed to list bookings: {e}");
+            None
+        }
+    };
+    if let Some(live) = live
+        && let Ok(lots) = db.list_parking_lots().await
+    {
+        for lot in &lots {
+            let total = u64::try_from(lot.total_slots).unwrap_or(0);
+            let occupied = live.get(&lot.id).copied().unwrap_or(0).min(total);
+            record_lot_occupancy(&lot.id.to_string(), &lot.name, total, occupied);
+        }
+    }
+    if let Ok(users) = db.list_users().await {
+        record_registered_users(users.len() as u64);
+    }
+    if let Ok(emails) = db.list_queued_emails().await {
+        let pending = emails
+            .iter()
+            .filter(|e| e.status == crate::db::QueuedEmailStatus::Pending)
+            .count();
+        record_email_queue_depth(pending as u64, (emails.len() - pending) as u64);
+    }
+}
+
 /// Timer for measuring operation duration
 pub struct MetricsTimer {
     start: Instant,
@@ -159,6 +275,199 @@ impl MetricsTimer {
     }
 }
 
+// === Status Window Snapshot ===
+//
+// The Prometheus recorder only renders text, and its summaries are kept per
+// label set, so the values the status window needs are also tracked here.
+
+/// Request latencies kept for the p95 in [`snapshot`].
+const LATENCY_WINDOW: usize = 512;
+
+/// Samples kept per sparkline (two minutes at the status window's 2 s tick).
+pub const SPARKLINE_SAMPLES: usize = 60;
+
+struct RequestTotals {
+    requests: u64,
+    errors: u64,
+    latencies_ms: VecDeque<f64>,
+}
+
+impl RequestTotals {
+    fn record(&mut self, status: u16, duration: std::time::Duration) {
+        self.requests += 1;
+        if status >= 500 {
+            self.errors += 1;
+        }
+        if self.latencies_ms.len() == LATENCY_WINDOW {
+            self.latencies_ms.pop_front();
+        }
+        self.latencies_ms.push_back(duration.as_secs_f64() * 1000.0);
+    }
+}
+
+static REQUEST_TOTALS: Mutex<RequestTotals> = Mutex::new(RequestTotals {
+    requests: 0,
+    errors: 0,
+    latencies_ms: VecDeque::new(),
+});
+
+static ACTIVE_SESSIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Point-in-time view of the request and session metrics.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricsSnapshot {
+    /// HTTP requests served since startup
+    pub requests_total: u64,
+    /// Requests answered with a 5xx status since startup
+    pub errors_total: u64,
+    /// 95th percentile latency of the most recent requests, in milliseconds
+    pub p95_latency_ms: f64,
+    /// Last value passed to [`record_active_sessions`]
+    pub active_sessions: u64,
+}
+
+/// Current request and session metrics.
+pub fn snapshot() -> MetricsSnapshot {
+    let (requests_total, errors_total, p95_latency_ms) = REQUEST_TOTALS
+        .lock()
+        .map(|totals| {
+            let latencies: Vec<f64> = totals.latencies_ms.iter().copied().collect();
+            (totals.requests, totals.errors, percentile(&latencies, 0.95))
+        })
+        .unwrap_or_default();
+    MetricsSnapshot {
+        requests_total,
+        errors_total,
+        p95_latency_ms,
+        active_sessions: ACTIVE_SESSIONS.load(Ordering::Relaxed),
+    }
+}
+
+/// Nearest-rank percentile (`p` in 0..=1); 0 for no values.
+fn percentile(values: &[f64], p: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Rates derived from two consecutive snapshots.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MetricsSample {
+    /// Requests per second since the previous snapshot
+    pub request_rate: f64,
+    /// Share of those requests that failed with a 5xx, in percent
+    pub error_rate: f64,
+    pub p95_latency_ms: f64,
+    pub active_sessions: u64,
+}
+
+/// Rolling sample history behind the status window sparklines.
+#[derive(Debug, Default)]
+pub struct MetricsHistory {
+    last: Option<(Instant, MetricsSnapshot)>,
+    samples: VecDeque<MetricsSample>,
+}
+
+impl MetricsHistory {
+    /// Derive a sample from `snapshot` taken at `at` and append it, keeping
+    /// the newest [`SPARKLINE_SAMPLES`].
+    pub fn push(&mut self, at: Instant, snapshot: MetricsSnapshot) -> MetricsSample {
+        let (request_rate, error_rate) = match self.last {
+            Some((prev_at, prev)) => {
+                let secs = at.duration_since(prev_at).as_secs_f64();
+                let requests = snapshot.requests_total.saturating_sub(prev.requests_total);
+                let errors = snapshot.errors_total.saturating_sub(prev.errors_total);
+                let rate = if secs > 0.0 {
+                    requests as f64 / secs
+                } else {
+                    0.0
+                };
+                let error_rate = if requests > 0 {
+                    errors as f64 / requests as f64 * 100.0
+                } else {
+                    0.0
+                };
+                (rate, error_rate)
+            }
+            None => (0.0, 0.0),
+        };
+        self.last = Some((at, snapshot));
+
+        let sample = MetricsSample {
+            request_rate,
+            error_rate,
+            p95_latency_ms: snapshot.p95_latency_ms,
+            active_sessions: snapshot.active_sessions,
+        };
+        if self.samples.len() == SPARKLINE_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+        sample
+    }
+
+    /// Values of one sample field, oldest first.
+    pub fn series(&self, field: impl Fn(&MetricsSample) -> f64) -> Vec<f64> {
+        self.samples.iter().map(field).collect()
+    }
+}
+
+/// SVG path commands drawing `values` as a line in a 100 x 24 viewbox,
+/// scaled to the largest value. Flat at the bottom when there is no data.
+pub fn sparkline_commands(values: &[f64]) -> String {
+    const WIDTH: f64 = 100.0;
+    const HEIGHT: f64 = 24.0;
+    if values.len() < 2 {
+        return format!("M 0 {HEIGHT} L {WIDTH} {HEIGHT}");
+    }
+    let max = values.iter().copied().fold(0.0_f64, f64::max);
+    let step = WIDTH / (values.len() - 1) as f64;
+    let mut commands = String::new();
+    for (i, value) in values.iter().enumerate() {
+        let x = step * i as f64;
+        let y = if max > 0.0 {
+            HEIGHT * (1.0 - value / max)
+        } else {
+            HEIGHT
+        };
+        let op = if i == 0 { "M" } else { " L" };
+        commands.push_str(&format!("{op} {x:.1} {y:.1}"));
+    }
+    commands
+}
+
+/// SVG path commands drawing `values` as bars in a 100 x 24 viewbox, scaled
+/// to the largest value. Zero values draw no bar; with no bars at all the
+/// path is a flat line at the bottom.
+pub fn bar_chart_commands(values: &[f64]) -> String {
+    const WIDTH: f64 = 100.0;
+    const HEIGHT: f64 = 24.0;
+    let max = values.iter().copied().fold(0.0_f64, f64::max);
+    if max <= 0.0 {
+        return format!("M 0 {HEIGHT} L {WIDTH} {HEIGHT}");
+    }
+    let slot = WIDTH / values.len() as f64;
+    let bar = slot * 0.6;
+    let mut commands = String::new();
+    for (i, value) in values.iter().enumerate().filter(|(_, v)| **v > 0.0) {
+        let left = slot * i as f64 + (slot - bar) / 2.0;
+        let right = left + bar;
+        let top = HEIGHT * (1.0 - value / max);
+        if !commands.is_empty() {
+            commands.push(' ');
+        }
+        commands.push_str(&format!(
+            "M {left:.1} {HEIGHT:.1} L {left:.1} {top:.1} L {right:.1} {top:.1} L {right:.1} {HEIGHT:.1} Z"
+        ));
+    }
+    commands
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -267,6 +576,165 @@ mod tests {
         record_active_bookings(100);
     }
 
+    #[test]
+    fn test_record_no_show_and_email_queue_depth_no_panic() {
+        record_no_show("lot-1");
+        record_email_queue_depth(0, 0);
+        record_email_queue_depth(12, 3);
+    }
+
+    #[test]
+    fn test_live_bookings_by_lot_counts_bookings_holding_a_slot_now() {
+        use chrono::TimeDelta;
+
+        let now = Utc::now();
+        let lot = uuid::Uuid::new_v4();
+        let other_lot = uuid::Uuid::new_v4();
+        let booking = |lot_id, status, from: i64, to: i64| Booking {
+            id: uuid::Uuid::new_v4(),
+            user_id: uuid::Uuid::new_v4(),
+            lot_id,
+            slot_id: uuid::Uuid::new_v4(),
+            slot_number: 1,
+            floor_name: "Level 1".to_string(),
+            vehicle: parkhub_common::Vehicle {
+                id: uuid::Uuid::new_v4(),
+                user_id: uuid::Uuid::new_v4(),
+                license_plate: "M-AB 123".to_string(),
+                make: None,
+                model: None,
+                color: None,
+                vehicle_type: parkhub_common::VehicleType::Car,
+                fuel_type: parkhub_common::FuelType::Unknown,
+                is_default: false,
+                created_at: now,
+            },
+            start_time: now + TimeDelta::hours(from),
+            end_time: now + TimeDelta::hours(to),
+            status,
+            pricing: parkhub_common::BookingPricing {
+                base_price: 0.0,
+                discount: 0.0,
+                tax: 0.0,
+                total: 0.0,
+                currency: "EUR".to_string(),
+                payment_status: parkhub_common::PaymentStatus::Pending,
+                payment_method: None,
+                cancellation_fee: None,
+            },
+            created_at: now,
+            updated_at: now,
+            check_in_time: None,
+            check_out_time: None,
+            qr_code: None,
+            notes: None,
+            tenant_id: None,
+            tags: std::collections::BTreeMap::new(),
+            booked_for: None,
+        };
+        let bookings = vec![
+            booking(lot, BookingStatus::Confirmed, -1, 1),
+            booking(lot, BookingStatus::Active, -2, 2),
+            booking(lot, BookingStatus::Confirmed, 1, 2),
+            booking(lot, BookingStatus::Cancelled, -1, 1),
+            booking(lot, BookingStatus::Completed, -2, -1),
+            booking(other_lot, BookingStatus::Active, -1, 1),
+        ];
+
+        let live = live_bookings_by_lot(&bookings, now);
+        assert_eq!(live.get(&lot), Some(&2));
+        assert_eq!(live.get(&other_lot), Some(&1));
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[], 0.95), 0.0);
+        assert_eq!(percentile(&[7.0], 0.95), 7.0);
+        let values: Vec<f64> = (1..=100).map(f64::from).collect();
+        assert_eq!(percentile(&values, 0.95), 95.0);
+        assert_eq!(percentile(&values, 0.5), 50.0);
+    }
+
+    #[test]
+    fn test_request_totals_window() {
+        let mut totals = RequestTotals {
+            requests: 0,
+            errors: 0,
+            latencies_ms: VecDeque::new(),
+        };
+        for i in 0..LATENCY_WINDOW + 10 {
+            let status = if i % 10 == 0 { 503 } else { 200 };
+            totals.record(status, std::time::Duration::from_millis(5));
+        }
+        assert_eq!(totals.requests, (LATENCY_WINDOW + 10) as u64);
+        assert_eq!(totals.errors, 53);
+        assert_eq!(totals.latencies_ms.len(), LATENCY_WINDOW);
+    }
+
+    #[test]
+    fn test_metrics_history_rates() {
+        let start = Instant::now();
+        let mut history = MetricsHistory::default();
+        let first = history.push(
+            start,
+            MetricsSnapshot {
+                requests_total: 100,
+                errors_total: 1,
+                p95_latency_ms: 12.0,
+                active_sessions: 3,
+            },
+        );
+        assert_eq!(first.request_rate, 0.0);
+
+        let second = history.push(
+            start + std::time::Duration::from_secs(2),
+            MetricsSnapshot {
+                requests_total: 120,
+                errors_total: 2,
+                p95_latency_ms: 15.0,
+                active_sessions: 4,
+            },
+        );
+        assert_eq!(second.request_rate, 10.0);
+        assert_eq!(second.error_rate, 5.0);
+        assert_eq!(second.p95_latency_ms, 15.0);
+        assert_eq!(history.series(|s| s.active_sessions as f64), vec![3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_metrics_history_keeps_sparkline_window() {
+        let start = Instant::now();
+        let mut history = MetricsHistory::default();
+        for i in 0..SPARKLINE_SAMPLES + 5 {
+            history.push(
+                start + std::time::Duration::from_secs(i as u64),
+                MetricsSnapshot::default(),
+            );
+        }
+        assert_eq!(history.series(|s| s.request_rate).len(), SPARKLINE_SAMPLES);
+    }
+
+    #[test]
+    fn test_sparkline_commands() {
+        assert_eq!(sparkline_commands(&[]), "M 0 24 L 100 24");
+        assert_eq!(sparkline_commands(&[0.0, 0.0]), "M 0.0 24.0 L 100.0 24.0");
+        assert_eq!(
+            sparkline_commands(&[0.0, 5.0, 10.0]),
+            "M 0.0 24.0 L 50.0 12.0 L 100.0 0.0"
+        );
+    }
+
+    #[test]
+    fn test_bar_chart_commands() {
+        assert_eq!(bar_chart_commands(&[]), "M 0 24 L 100 24");
+        assert_eq!(bar_chart_commands(&[0.0, 0.0]), "M 0 24 L 100 24");
+        assert_eq!(
+            bar_chart_commands(&[2.0, 0.0, 4.0, 0.0]),
+            "M 5.0 24.0 L 5.0 12.0 L 20.0 12.0 L 20.0 24.0 Z \
+             M 55.0 24.0 L 55.0 0.0 L 70.0 0.0 L 70.0 24.0 Z"
+        );
+    }
+
     #[test]
     fn test_record_registered_users_no_panic() {
         record_registered_users(0);
@@ -55,7 +55,7 @@ async fn test_harness() -> TestHarness {
         db,
         mdns: None,
         scheduler: None,
-        ws_events: crate::api::ws::EventBroadcaster::new(),
+        ws_events: crate::api::events::EventBroadcaster::new(),
         fleet_events: crate::api::sse::FleetEventBroadcaster::new(),
         revocation_store: crate::jwt::TokenRevocationList::new(),
     }));
@@ -726,6 +726,7 @@ async fn insert_booking_direct(
             currency: "EUR".to_string(),
             payment_status: PaymentStatus::Pending,
             payment_method: None,
+            cancellation_fee: None,
         },
         created_at: now,
         updated_at: now,
@@ -734,6 +735,8 @@ async fn insert_booking_direct(
         qr_code: None,
         notes: None,
         tenant_id: None,
+        tags: std::collections::BTreeMap::new(),
+        booked_for: None,
     };
 
     let guard = state.read().await;
@@ -10,7 +10,7 @@ use crate::{
     api::{
         admin::AdminUserResponse,
         credits::AdminGrantCreditsRequest,
-        favorites::AddFavoriteRequest,
+        favorites::{AddFavoriteRequest, AddUserFavoriteRequest, UserFavoritesResponse},
         push::{PushKeys, SubscribeRequest, SubscriptionResponse, VapidKeyResponse},
         setup::{SetupRequest, SetupStatus},
         webhooks::{CreateWebhookRequest, UpdateWebhookRequest, WebhookResponse},
@@ -20,9 +20,10 @@ use crate::{
     health::{ComponentHealth, HealthResponse, HealthStatus, ReadyResponse},
     jwt::TokenPair,
     requests::{
-        BookingFiltersParams, ChangePasswordRequest, CreateBookingRequest, CreateParkingLotRequest,
-        ExtendBookingRequest, LoginRequest, PaginationParams, RefreshTokenRequest, RegisterRequest,
-        UpdateBookingRequest, UpdateParkingLotRequest, UpdatePreferencesRequest,
+        BookingFiltersParams, ChangePasswordRequest, CloneParkingLotRequest, CreateBookingRequest,
+        CreateFloorRequest, CreateParkingLotRequest, ExtendBookingRequest, LoginRequest,
+        PaginationParams, RefreshTokenRequest, RegisterRequest, UpdateBookingRequest,
+        UpdateFloorRequest, UpdateParkingLotRequest, UpdatePreferencesRequest,
         UpdateProfileRequest, UpdateQuotaRequest, VehicleRequest,
     },
 };
@@ -71,7 +72,7 @@ use crate::{
         (name = "Security", description = "Password policy, login history, and active session management"),
         (name = "2FA", description = "Two-factor authentication setup, verification, and login flow"),
         (name = "API Keys", description = "Personal API key creation and revocation"),
-        (name = "OAuth", description = "Third-party OAuth sign-in (Google, GitHub)"),
+        (name = "OAuth", description = "Third-party OAuth sign-in (Google, GitHub, OpenID Connect)"),
         (name = "RBAC", description = "Role-based access control: roles, permissions, and assignments"),
         (name = "Branding", description = "Tenant branding — colors, fonts, and logo upload"),
         (name = "Maintenance", description = "Scheduled lot/slot maintenance windows"),
@@ -92,7 +93,7 @@ use crate::{
         (name = "Admin Widgets", description = "Admin dashboard widget layout + data"),
         (name = "Stripe", description = "Stripe payments (checkout, webhook, history, config)"),
         (name = "Audit Export", description = "Enhanced audit-log export with signed download tokens"),
-        (name = "Invoices", description = "Per-booking invoice PDF rendering")
+        (name = "Invoices", description = "Per-booking invoice PDF rendering and the archive of issued invoices")
     ),
     components(
         schemas(
@@ -132,6 +133,18 @@ use crate::{
             ExtendBookingRequest,
             UpdateBookingRequest,
             BookingFiltersParams,
+            parkhub_common::BookingQuoteRequest,
+            parkhub_common::BookingQuote,
+            parkhub_common::BookingQuoteDay,
+            parkhub_common::LotCalendar,
+            parkhub_common::LotCalendarDay,
+            parkhub_common::LotAvailability,
+            parkhub_common::SlotAvailability,
+            parkhub_common::BookedPeriod,
+            parkhub_common::SlotCalendar,
+            parkhub_common::SlotCalendarDay,
+            parkhub_common::SlotSegment,
+            parkhub_common::SlotAlternative,
 
             // Vehicles
             VehicleRequest,
@@ -142,12 +155,79 @@ use crate::{
 
             // Admin
             CreateParkingLotRequest,
+            CloneParkingLotRequest,
+            CreateFloorRequest,
+            UpdateFloorRequest,
+            crate::api::lot_archive::ArchiveLotRequest,
+            crate::api::lot_archive::LotArchiveRecord,
+            crate::api::lot_archive::MigratedBooking,
+            crate::api::search::SearchResult,
+            crate::api::invoice_archive::InvoiceSummary,
+            crate::db::SearchKind,
+            crate::api::lot_managers::AssignLotManagerRequest,
+            crate::api::lot_managers::LotManagerEntry,
+            crate::api::roles::RoleRequest,
+            crate::api::roles::AssignRoleRequest,
+            crate::api::roles::RoleMember,
+            parkhub_common::Role,
+            parkhub_common::Permission,
+            parkhub_common::LotScope,
+            parkhub_common::ResolvedPermissions,
+            parkhub_common::LotBookingRules,
+            crate::api::lot_settings::LotSettingsOverrides,
+            crate::api::lot_settings::LotConfig,
+            crate::api::lot_settings::LotSettingsResponse,
+            parkhub_common::CancellationPolicy,
+            parkhub_common::SlotAssignmentMode,
+            parkhub_common::SlotType,
+            crate::api::slot_history::SlotHistoryEntry,
+            crate::db::SlotStatusChange,
+            crate::db::SlotHold,
+            crate::db::BookingNote,
+            crate::db::BookingAttachment,
+            crate::requests::AddBookingNoteRequest,
+            crate::db::RectificationRequest,
+            crate::db::RectificationStatus,
+            crate::requests::SubmitRectificationRequest,
+            crate::requests::ResolveRectificationRequest,
+            crate::api::email_queue::QueuedEmailSummary,
+            crate::api::booking_tags::BookingTagSet,
+            crate::api::department_quotas::DepartmentQuota,
+            parkhub_common::Permit,
+            parkhub_common::PermitType,
+            crate::api::permits::PermitRequest,
+            crate::api::permits::GateCheckRequest,
+            crate::api::permits::GateCheckResponse,
+            crate::api::permits::GateDecision,
+            crate::api::department_quotas::DepartmentUtilization,
+            crate::api::department_quotas::DepartmentUtilizationReport,
+            crate::db::QueuedEmailStatus,
+            crate::api::users::PatchProfileRequest,
+            crate::api::users::PatchProfileResponse,
+            crate::api::users::ConfirmEmailChangeRequest,
+            crate::api::telemetry::TelemetryStatus,
+            crate::api::telemetry::UpdateTelemetryRequest,
+            crate::telemetry::TelemetryReport,
             UpdateParkingLotRequest,
             crate::api::lots::UpdateLotPricingRequest,
             AdminUserResponse,
+            crate::api::dummy_users::DummyUsersResponse,
+            crate::api::dummy_users::DummyUsersPurged,
+            crate::api::bulk::BulkUserRequest,
+            crate::api::bulk::BulkUserAction,
+            crate::api::bulk::BulkBookingRequest,
+            crate::api::bulk::BulkBookingAction,
+            crate::api::bulk::BulkReport,
+            crate::api::bulk::BulkItemResult,
             UpdateQuotaRequest,
             crate::api::import::ImportResult,
             crate::api::import::ImportError,
+            crate::api::import::LotImportDocument,
+            crate::api::import::LotImportDef,
+            crate::api::import::FloorImportDef,
+            crate::api::import::SlotImportDef,
+            crate::api::import::LotImportReport,
+            crate::api::import::LotImportError,
 
             // Credits
             AdminGrantCreditsRequest,
@@ -162,6 +242,8 @@ use crate::{
 
             // Favorites
             AddFavoriteRequest,
+            AddUserFavoriteRequest,
+            UserFavoritesResponse,
 
             // Push
             SubscribeRequest,
@@ -177,6 +259,7 @@ use crate::{
             crate::api::noshow::LotNoshowConfig,
             crate::api::noshow::UpdateLotNoshowConfigRequest,
             crate::api::noshow::ClaimOfferRequest,
+            crate::api::waitlist_ext::JoinLotWaitlistRequest,
 
             // Common
             PaginationParams,
@@ -196,10 +279,35 @@ use crate::{
 
             // Guest Bookings
             crate::api::guest::CreateGuestBookingRequest,
+            crate::requests::VisitorBookingRequest,
+            crate::api::visitor_bookings::VisitorBookingResponse,
+            parkhub_common::BookedFor,
+
+            // Visitor day passes
+            crate::db::VisitorPassBlock,
+            crate::db::VisitorPass,
+            crate::db::VisitorPassStatus,
+            crate::api::visitor_passes::VisitorPassDay,
+            crate::requests::VisitorPassBlockRequest,
+            crate::requests::IssueVisitorPassRequest,
+            crate::api::energy_reports::EnergyReport,
+            crate::api::energy_reports::EnergyTotals,
+            crate::api::energy_reports::LotEnergy,
+            crate::api::energy_reports::UserEnergy,
+            crate::requests::BackupRequest,
+            crate::requests::SetFeatureFlagRequest,
+            crate::api::feature_flags::FeatureFlagInfo,
+
+            // Activity feed
+            parkhub_common::ActivityEntry,
+            parkhub_common::ActivityKind,
+            parkhub_common::ActivityBooking,
+            parkhub_common::BookingStatus,
 
             // Announcements
             crate::api::announcements::CreateAnnouncementRequest,
             crate::api::announcements::UpdateAnnouncementRequest,
+            parkhub_common::models::AnnouncementAudience,
 
             // Admin Settings
             crate::api::admin_handlers::AutoReleaseSettingsRequest,
@@ -252,8 +360,19 @@ use crate::{
             crate::api::security::TwoFactorLoginRequest,
             crate::api::security::TwoFactorVerifyRequest,
             crate::api::security::TwoFactorDisableRequest,
+            crate::api::passkeys::PasskeyChallenge,
+            crate::api::passkeys::FinishPasskeyRegistrationRequest,
+            crate::api::passkeys::StartPasskeyLoginRequest,
+            crate::api::passkeys::FinishPasskeyLoginRequest,
+            crate::api::passkeys::PasskeyInfo,
+            crate::api::impersonation::ImpersonationResponse,
+            crate::api::invites::CreateInviteRequest,
+            crate::api::invites::AcceptInviteRequest,
+            crate::api::invites::InviteResponse,
+            crate::api::invites::CreatedInviteResponse,
             crate::api::security::CreateApiKeyRequest,
             crate::api::security::PasswordPolicy,
+            crate::db::LoginEvent,
 
             // T-1739 pass 1 — RBAC
             crate::api::rbac::CreateRoleRequest,
@@ -268,6 +387,9 @@ use crate::{
             crate::api::admin_ext::BulkDeleteRequest,
             crate::api::admin_ext::BookingPolicies,
             crate::api::admin_ext::NotificationPreferences,
+            crate::api::admin_ext::RevenueReport,
+            crate::api::admin_ext::RevenueReportRow,
+            crate::api::admin_ext::RevenueTotals,
 
             // T-1739 pass 2 — Dynamic pricing
             crate::api::dynamic_pricing::UpdateDynamicPricingRequest,
@@ -277,6 +399,7 @@ use crate::{
 
             // T-1739 pass 2 — Map / Parking zones
             crate::api::map::SetLocationRequest,
+            crate::api::map::SetBoundaryRequest,
             crate::api::parking_zones::SetZonePricingRequest,
 
             // T-1739 pass 2 — Stripe / Checkout
@@ -294,17 +417,62 @@ use crate::{
         crate::api::auth::refresh_token_alias,
         crate::api::auth::forgot_password,
         crate::api::auth::reset_password,
+        crate::api::invites::accept_invite,
 
         // Lots & Slots
         crate::api::lots::list_lots,
+        crate::api::lots::search_lots,
+        crate::api::lots::clone_lot,
+        crate::api::lot_archive::archive_lot,
+        crate::api::lot_archive::unarchive_lot,
+        crate::api::lot_archive::list_archived_lots,
+        crate::api::lot_managers::list_lot_managers,
+        crate::api::lot_managers::assign_lot_manager,
+        crate::api::lot_managers::remove_lot_manager,
+        crate::api::lot_managers::my_managed_lots,
+        crate::api::roles::list_roles,
+        crate::api::roles::create_role,
+        crate::api::roles::update_role,
+        crate::api::roles::delete_role,
+        crate::api::roles::list_role_members,
+        crate::api::roles::assign_role,
+        crate::api::roles::unassign_role,
+        crate::api::roles::my_permissions,
+        crate::api::lot_rules::get_lot_rules,
+        crate::api::lot_rules::update_lot_rules,
+        crate::api::lot_settings::get_lot_settings,
+        crate::api::lot_settings::update_lot_settings,
+        crate::api::telemetry::get_telemetry,
+        crate::api::telemetry::update_telemetry,
+        crate::api::slot_history::get_slot_history,
+        crate::api::email_queue::list_email_queue,
+        crate::api::email_queue::retry_queued_email,
+        crate::api::booking_tags::list_booking_tag_sets,
+        crate::api::booking_tags::update_booking_tag_sets,
+        crate::api::department_quotas::get_department_quotas,
+        crate::api::department_quotas::update_department_quotas,
+        crate::api::department_quotas::department_utilization_report,
+        crate::api::permits::admin_list_permits,
+        crate::api::permits::admin_create_permit,
+        crate::api::permits::admin_update_permit,
+        crate::api::permits::admin_delete_permit,
+        crate::api::permits::list_my_permits,
+        crate::api::permits::gate_check,
         crate::api::lots::create_lot,
         crate::api::lots::get_lot,
         crate::api::lots::update_lot,
         crate::api::lots::delete_lot,
+        crate::api::lot_photos::upload_lot_photo,
+        crate::api::lot_photos::delete_lot_photo,
+        crate::api::lot_photos::get_lot_photo,
         crate::api::lots::get_lot_slots,
         crate::api::lots::create_slot,
         crate::api::lots::update_slot,
         crate::api::lots::delete_slot,
+        crate::api::lot_floors::list_floors,
+        crate::api::lot_floors::create_floor,
+        crate::api::lot_floors::update_floor,
+        crate::api::lot_floors::get_floor_slots,
         crate::api::lots::get_lot_pricing,
         crate::api::lots::update_lot_pricing,
 
@@ -330,6 +498,9 @@ use crate::{
         crate::api::favorites::list_favorites,
         crate::api::favorites::add_favorite,
         crate::api::favorites::remove_favorite,
+        crate::api::favorites::list_my_favorites,
+        crate::api::favorites::add_my_favorite,
+        crate::api::favorites::remove_my_favorite_lot,
 
         // Push notifications
         crate::api::push::get_vapid_key,
@@ -346,6 +517,7 @@ use crate::{
         crate::api::export::admin_export_revenue_csv,
         // Import
         crate::api::import::import_users_csv,
+        crate::api::import::import_lots,
 
         // Health & Discovery (mod.rs)
         crate::api::system::health_check,
@@ -357,6 +529,8 @@ use crate::{
         crate::api::system::v1_health_info,
         crate::api::system::v1_health_detailed,
         crate::api::system::v1_discover,
+        crate::api::system::v1_time,
+        crate::api::system::v1_version,
         crate::api::system::handshake,
         crate::api::system::server_status,
         crate::api::system::v1_server_status,
@@ -364,6 +538,11 @@ use crate::{
         // Users (mod.rs)
         crate::api::users::get_current_user,
         crate::api::users::update_current_user,
+        crate::api::users::patch_current_user,
+        crate::api::users::confirm_email_change,
+        crate::api::avatars::upload_my_picture,
+        crate::api::avatars::delete_my_picture,
+        crate::api::avatars::get_user_picture,
         crate::api::users::get_user,
         crate::api::users::change_password,
         crate::api::users::auth_change_password,
@@ -372,6 +551,9 @@ use crate::{
         crate::api::users::update_user_preferences,
         crate::api::users::gdpr_export_data,
         crate::api::users::gdpr_delete_account,
+        crate::api::rectification::submit_rectification,
+        crate::api::rectification::list_my_rectifications,
+        crate::api::activity::my_activity,
         crate::api::admin_ext::get_design_theme_preference,
         crate::api::admin_ext::update_design_theme_preference,
 
@@ -382,7 +564,17 @@ use crate::{
         crate::api::bookings::cancel_booking,
         crate::api::bookings::get_booking_invoice,
         crate::api::bookings::quick_book,
+        crate::api::bookings::quote_booking,
+        crate::api::bookings::lot_calendar,
+        crate::api::availability::lot_availability,
+        crate::api::availability::slot_calendar,
+        crate::api::slot_holds::hold_slot,
+        crate::api::slot_holds::release_slot_hold,
         crate::api::bookings::booking_checkin,
+        crate::api::booking_attachments::upload_booking_attachment,
+        crate::api::booking_attachments::list_booking_attachments,
+        crate::api::booking_attachments::download_booking_attachment,
+        crate::api::booking_attachments::delete_booking_attachment,
 
         // Vehicles
         crate::api::vehicles::list_vehicles,
@@ -399,7 +591,14 @@ use crate::{
         crate::api::admin_handlers::admin_update_user_role,
         crate::api::admin_handlers::admin_update_user_status,
         crate::api::admin_handlers::admin_delete_user,
+        crate::api::dummy_users::list_dummy_users,
+        crate::api::dummy_users::purge_dummy_users,
+        crate::api::bulk::bulk_users,
         crate::api::admin_handlers::admin_list_bookings,
+        crate::api::bulk::bulk_bookings,
+        crate::api::booking_notes::list_booking_notes,
+        crate::api::booking_notes::add_booking_note,
+        crate::api::search::admin_search,
         crate::api::settings::admin_get_settings,
         crate::api::settings::admin_update_settings,
         crate::api::settings::admin_get_features,
@@ -411,6 +610,10 @@ use crate::{
         crate::api::admin_handlers::admin_audit_log,
         crate::api::admin_handlers::admin_audit_log_export,
         crate::api::admin_handlers::admin_reset,
+        crate::api::backup::admin_backup,
+        crate::api::backup::admin_restore,
+        crate::api::feature_flags::admin_list_feature_flags,
+        crate::api::feature_flags::admin_set_feature_flag,
         crate::api::misc::get_impressum_admin,
         crate::api::misc::update_impressum,
         crate::api::announcements::admin_list_announcements,
@@ -468,6 +671,15 @@ use crate::{
         crate::api::guest::create_guest_booking,
         crate::api::guest::admin_list_guest_bookings,
         crate::api::guest::admin_cancel_guest_booking,
+        crate::api::visitor_bookings::create_visitor_booking,
+
+        // Visitor day passes
+        crate::api::visitor_passes::list_visitor_pass_blocks,
+        crate::api::visitor_passes::set_visitor_pass_block,
+        crate::api::visitor_passes::list_visitor_passes,
+        crate::api::visitor_passes::issue_visitor_pass,
+        crate::api::visitor_passes::check_in_visitor_pass,
+        crate::api::visitor_passes::cancel_visitor_pass,
 
         // Absences — additional
         crate::api::absences::list_team_absences,
@@ -478,6 +690,8 @@ use crate::{
         crate::api::announcements::admin_create_announcement,
         crate::api::announcements::admin_update_announcement,
         crate::api::announcements::admin_delete_announcement,
+        crate::api::announcements::get_my_announcements,
+        crate::api::announcements::dismiss_announcement,
 
         // Admin — additional settings
         crate::api::settings::admin_get_use_case,
@@ -519,14 +733,24 @@ use crate::{
         crate::api::security::two_factor_verify,
         crate::api::security::two_factor_disable,
         crate::api::security::two_factor_status,
+        crate::api::passkeys::start_registration,
+        crate::api::passkeys::finish_registration,
+        crate::api::passkeys::start_login,
+        crate::api::passkeys::finish_login,
+        crate::api::passkeys::list_passkeys,
+        crate::api::passkeys::delete_passkey,
 
         // Security — password policy, login history, sessions, API keys
         crate::api::security::get_password_policy,
         crate::api::security::update_password_policy,
         crate::api::security::get_login_history,
+        crate::api::security::list_my_logins,
         crate::api::security::admin_get_login_history,
         crate::api::security::list_sessions,
         crate::api::security::revoke_session,
+        crate::api::security::list_my_sessions,
+        crate::api::security::revoke_my_session,
+        crate::api::security::admin_revoke_user_sessions,
         crate::api::security::create_api_key,
         crate::api::security::list_api_keys,
         crate::api::security::revoke_api_key,
@@ -537,6 +761,8 @@ use crate::{
         crate::api::oauth::oauth_google_callback,
         crate::api::oauth::oauth_github_redirect,
         crate::api::oauth::oauth_github_callback,
+        crate::api::oidc::oidc_start,
+        crate::api::oidc::oidc_callback,
 
         // RBAC
         crate::api::rbac::list_roles,
@@ -611,6 +837,18 @@ use crate::{
         // Admin — password reset
         crate::api::admin_handlers::admin_reset_user_password,
 
+        // Admin — impersonation
+        crate::api::impersonation::impersonate_user,
+
+        // Admin — invitations
+        crate::api::invites::create_invite,
+        crate::api::invites::list_invites,
+        crate::api::invites::revoke_invite,
+
+        // Admin — rectification requests
+        crate::api::rectification::admin_list_rectifications,
+        crate::api::rectification::resolve_rectification,
+
         // API Docs / Swagger UI / Postman collection
         crate::api::api_docs::api_docs_ui,
         crate::api::api_docs::api_docs_openapi_json,
@@ -645,6 +883,8 @@ use crate::{
         crate::api::ev_charging::charging_history,
         crate::api::ev_charging::admin_charger_overview,
         crate::api::ev_charging::admin_add_charger,
+        crate::api::energy_reports::admin_energy_report,
+        crate::api::energy_reports::my_energy_report,
 
         // Fleet management (admin)
         crate::api::fleet::admin_fleet_list,
@@ -657,8 +897,11 @@ use crate::{
         // Import — absences iCal
         crate::api::import::import_absences_ical,
 
-        // Invoices — PDF
+        // Invoices — PDF and archive
         crate::api::invoices::get_booking_invoice_pdf,
+        crate::api::invoices::get_my_invoice_pdf,
+        crate::api::invoice_archive::list_my_invoices,
+        crate::api::invoice_archive::get_my_invoice,
 
         // Lobby (public display)
         crate::api::lobby::lot_display,
@@ -666,6 +909,8 @@ use crate::{
         // Map (lot markers + admin location)
         crate::api::map::list_lot_markers,
         crate::api::map::set_lot_location,
+        crate::api::map::lots_geojson,
+        crate::api::map::set_lot_boundary,
 
         // Mobile quick endpoints
         crate::api::mobile::nearby_lots,
@@ -721,7 +966,8 @@ use crate::{
         crate::api::stripe::payment_history,
         crate::api::stripe::stripe_config,
 
-        // Waitlist extended (subscribe, list, leave, accept, decline)
+        // Waitlist extended (join, subscribe, list, leave, accept, decline)
+        crate::api::waitlist_ext::join_lot_waitlist,
         crate::api::waitlist_ext::subscribe_waitlist,
         crate::api::waitlist_ext::get_lot_waitlist,
         crate::api::waitlist_ext::leave_lot_waitlist,
@@ -887,7 +1133,14 @@ mod tests {
     fn test_openapi_has_lot_paths() {
         let doc = ApiDoc::openapi();
         let json = doc.to_json().unwrap();
-        for path in ["/lots", "/lots/{id}", "/lots/{lot_id}/slots"] {
+        for path in [
+            "/lots",
+            "/lots/{id}",
+            "/lots/{lot_id}/slots",
+            "/lots/{id}/floors",
+            "/lots/{id}/floors/{floor_id}",
+            "/lots/{id}/floors/{floor_id}/slots",
+        ] {
             assert!(json.contains(path), "Missing path: {path}");
         }
     }
@@ -1011,6 +1264,15 @@ mod tests {
             "/api/v1/users/me/password",
             "/api/v1/users/me/export",
             "/api/v1/users/me/delete",
+            "/api/v1/users/me/rectification",
+            "/api/v1/users/me/activity",
+            "/api/v1/users/me/picture",
+            "/api/v1/users/{id}/picture",
+            "/api/v1/users/me/logins",
+            "/api/v1/users/me/sessions",
+            "/api/v1/users/me/sessions/{token_id}",
+            "/api/v1/users/me/invoices",
+            "/api/v1/users/me/invoices/{number}",
             "/api/v1/user/stats",
             "/api/v1/user/preferences",
         ] {
@@ -1027,6 +1289,8 @@ mod tests {
             "/api/v1/bookings/{id}",
             "/api/v1/bookings/{id}/checkin",
             "/api/v1/bookings/quick",
+            "/api/v1/bookings/quote",
+            "/api/v1/lots/{id}/calendar",
         ] {
             assert!(json.contains(path), "Missing path: {path}");
         }
@@ -1053,9 +1317,17 @@ mod tests {
         for path in [
             "/api/v1/admin/users",
             "/api/v1/admin/bookings",
+            "/api/v1/admin/search",
             "/api/v1/admin/stats",
             "/api/v1/admin/settings",
             "/api/v1/admin/audit-log",
+            "/api/v1/admin/slots/{id}/history",
+            "/api/v1/admin/email-queue",
+            "/api/v1/admin/email-queue/{id}/retry",
+            "/api/v1/admin/department-quotas",
+            "/api/v1/admin/reports/department-utilization",
+            "/api/v1/admin/permits",
+            "/api/v1/admin/permits/{id}",
         ] {
             assert!(json.contains(path), "Missing path: {path}");
         }
@@ -1090,6 +1362,7 @@ mod tests {
             "SetupRequest",
             "SubscribeRequest",
             "AddFavoriteRequest",
+            "AddUserFavoriteRequest",
             "CreateZoneRequest",
             "VapidKeyResponse",
         ] {
@@ -1124,7 +1397,11 @@ mod tests {
     fn test_openapi_has_guest_booking_paths() {
         let doc = ApiDoc::openapi();
         let json = doc.to_json().unwrap();
-        for path in ["/api/v1/bookings/guest", "/api/v1/admin/guest-bookings"] {
+        for path in [
+            "/api/v1/bookings/guest",
+            "/api/v1/admin/guest-bookings",
+            "/api/v1/bookings/visitor",
+        ] {
             assert!(json.contains(path), "Missing path: {path}");
         }
     }
@@ -1133,8 +1410,11 @@ mod tests {
     fn test_openapi_has_announcement_paths() {
         let doc = ApiDoc::openapi();
         let json = doc.to_json().unwrap();
-        {
-            let path = "/api/v1/admin/announcements";
+        for path in [
+            "/api/v1/admin/announcements",
+            "/api/v1/announcements",
+            "/api/v1/announcements/{id}/dismiss",
+        ] {
             assert!(json.contains(path), "Missing path: {path}");
         }
     }
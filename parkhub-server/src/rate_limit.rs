@@ -213,6 +213,8 @@ pub struct EndpointRateLimiters {
     pub qr_pass: Arc<per_ip::IpRateLimiter>,
     /// Lobby display — 10 per minute per IP
     pub lobby_display: Arc<per_ip::IpRateLimiter>,
+    /// Public lot photos — 60 per minute per IP
+    pub lot_photos: Arc<per_ip::IpRateLimiter>,
     /// General API (relaxed global limiter)
     pub general: Arc<GlobalRateLimiter>,
     /// Per-identity layered limiters (T-1743).  Applied *on top* of the
@@ -295,6 +297,9 @@ impl EndpointRateLimiters {
             qr_pass: per_ip::create_ip_rate_limiter(rpm(10)),
             // 10 lobby display requests per minute per IP
             lobby_display: per_ip::create_ip_rate_limiter(rpm(10)),
+            // 60 lot photo requests per minute per IP (a selection screen
+            // loads one photo per lot)
+            lot_photos: per_ip::create_ip_rate_limiter(rpm(60)),
             // 100 requests per second globally
             general: create_rate_limiter(&RateLimitConfig::default()),
             // Per-identity layered limiters (T-1743)
@@ -798,6 +803,7 @@ mod tests {
         assert!(limiters.forgot_password.check_key(&test_ip).is_ok());
         assert!(limiters.password_reset.check_key(&test_ip).is_ok());
         assert!(limiters.lobby_display.check_key(&test_ip).is_ok());
+        assert!(limiters.lot_photos.check_key(&test_ip).is_ok());
         assert!(limiters.general.check().is_ok());
         // Per-identity bundle is also present
         let user = per_identity::Identity::User(uuid::Uuid::nil());
@@ -2,14 +2,16 @@
 //!
 //! Defines all API request payloads with built-in validation.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::Deserialize;
 use utoipa::ToSchema;
 use uuid::Uuid;
 use validator::Validate;
 
+use crate::db::{RectificationStatus, SortBy, count_key, text_key, time_key};
 use crate::validation::{
-    validate_booking_duration, validate_license_plate, validate_password_strength,
+    validate_booking_duration, validate_email_address, validate_license_plate, validate_not_nil,
+    validate_password_strength,
 };
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -92,7 +94,7 @@ pub struct CreateBookingRequest {
     /// Booking start time (must be in future)
     pub start_time: DateTime<Utc>,
 
-    /// Booking duration in minutes (15 min - 24 hours)
+    /// Booking duration in minutes (15 min - 14 days)
     #[validate(custom(function = "validate_booking_duration"))]
     pub duration_minutes: i32,
 
@@ -134,6 +136,66 @@ pub struct UpdateBookingRequest {
     pub notes: Option<String>,
 }
 
+/// Book a slot for a visitor without an account
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct VisitorBookingRequest {
+    /// Parking lot ID
+    #[validate(custom(function = "validate_not_nil"))]
+    pub lot_id: Uuid,
+
+    /// Parking slot ID
+    #[validate(custom(function = "validate_not_nil"))]
+    pub slot_id: Uuid,
+
+    /// Booking start time (must be in future)
+    pub start_time: DateTime<Utc>,
+
+    /// Booking duration in minutes (15 min - 14 days)
+    #[validate(custom(function = "validate_booking_duration"))]
+    pub duration_minutes: i32,
+
+    /// Visitor's name, printed on the pass
+    #[validate(length(min = 1, max = 100, message = "Visitor name must be 1-100 characters"))]
+    pub visitor_name: String,
+
+    /// Visitor's license plate
+    #[validate(custom(function = "validate_license_plate"))]
+    pub license_plate: String,
+
+    /// Send the pass to this address (optional)
+    #[serde(default)]
+    #[validate(custom(function = "validate_email_address"))]
+    pub email: Option<String>,
+
+    /// Optional notes
+    #[serde(default)]
+    #[validate(length(max = 500, message = "Notes too long"))]
+    pub notes: Option<String>,
+}
+
+/// Set how many visitor passes a lot may issue on a day (lot admin)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct VisitorPassBlockRequest {
+    /// Number of passes; 0 stops further passes for the day
+    #[validate(range(max = 10000, message = "Passes must be 0-10000"))]
+    pub passes: u32,
+}
+
+/// Issue a visitor day pass from a lot's block
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct IssueVisitorPassRequest {
+    /// Day the pass is valid for (YYYY-MM-DD)
+    pub date: NaiveDate,
+
+    /// Visitor's name
+    #[validate(length(min = 1, max = 100, message = "Visitor name must be 1-100 characters"))]
+    pub visitor_name: String,
+
+    /// Visitor's license plate
+    #[validate(custom(function = "validate_license_plate"))]
+    pub license_plate: String,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // VEHICLE REQUESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -174,6 +236,11 @@ pub struct VehicleRequest {
     /// Set as default vehicle
     #[serde(default)]
     pub is_default: bool,
+
+    /// Country of registration (ISO 3166-1 alpha-2, e.g. `DE`); when given,
+    /// the plate must match that country's format
+    #[serde(default)]
+    pub country: Option<String>,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -222,6 +289,26 @@ pub struct UpdatePreferencesRequest {
     pub theme: Option<String>,
 }
 
+/// Report a wrong value in a profile field the user cannot edit
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SubmitRectificationRequest {
+    /// Profile field, e.g. `email` or `department`
+    pub field: String,
+
+    /// The correct value
+    #[validate(length(
+        min = 1,
+        max = 254,
+        message = "Requested value must be 1-254 characters"
+    ))]
+    pub requested_value: String,
+
+    /// Why the current value is wrong (optional)
+    #[serde(default)]
+    #[validate(length(max = 1000, message = "Reason too long"))]
+    pub reason: Option<String>,
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // ADMIN REQUESTS
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -234,6 +321,18 @@ pub struct UpdateQuotaRequest {
     pub monthly_quota: i32,
 }
 
+/// Close a rectification request (admin)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct ResolveRectificationRequest {
+    /// `corrected` or `rejected`
+    pub status: RectificationStatus,
+
+    /// Answer shown to the user; required when rejecting
+    #[serde(default)]
+    #[validate(length(max = 1000, message = "Note too long"))]
+    pub note: Option<String>,
+}
+
 /// Create parking lot request (admin)
 #[derive(Debug, Deserialize, Validate, ToSchema)]
 pub struct CreateParkingLotRequest {
@@ -246,12 +345,13 @@ pub struct CreateParkingLotRequest {
     #[validate(length(max = 500, message = "Address too long"))]
     pub address: Option<String>,
 
-    /// Latitude (optional, defaults to 0.0)
+    /// Latitude (optional; must be paired with `longitude`). When both are
+    /// omitted the address is geocoded if a geocoder is configured.
     #[serde(default)]
     #[validate(range(min = -90.0, max = 90.0, message = "Invalid latitude"))]
     pub latitude: Option<f64>,
 
-    /// Longitude (optional, defaults to 0.0)
+    /// Longitude (optional; must be paired with `latitude`)
     #[serde(default)]
     #[validate(range(min = -180.0, max = 180.0, message = "Invalid longitude"))]
     pub longitude: Option<f64>,
@@ -329,6 +429,79 @@ pub struct UpdateParkingLotRequest {
     pub status: Option<String>,
 }
 
+/// Clone parking lot request (admin)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CloneParkingLotRequest {
+    /// Name of the new lot
+    #[validate(length(min = 1, max = 100, message = "Name must be 1-100 characters"))]
+    pub name: String,
+
+    /// Address (defaults to the source lot's address)
+    #[serde(default)]
+    #[validate(length(max = 500, message = "Address too long"))]
+    pub address: Option<String>,
+
+    /// Latitude (defaults to the source lot's; must be paired with `longitude`)
+    #[serde(default)]
+    #[validate(range(min = -90.0, max = 90.0, message = "Invalid latitude"))]
+    pub latitude: Option<f64>,
+
+    /// Longitude (defaults to the source lot's; must be paired with `latitude`)
+    #[serde(default)]
+    #[validate(range(min = -180.0, max = 180.0, message = "Invalid longitude"))]
+    pub longitude: Option<f64>,
+}
+
+/// Add a floor to a parking lot (admin or lot manager)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct CreateFloorRequest {
+    /// Floor name, e.g. "Level -1"
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    pub name: String,
+
+    /// Position of the floor; negative for basement levels. Defaults to one
+    /// above the lot's highest floor.
+    #[serde(default)]
+    #[validate(range(min = -20, max = 200, message = "Floor number must be -20 to 200"))]
+    pub floor_number: Option<i32>,
+}
+
+/// Rename or renumber a floor (admin or lot manager)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct UpdateFloorRequest {
+    /// New floor name
+    #[serde(default)]
+    #[validate(length(min = 1, max = 50, message = "Name must be 1-50 characters"))]
+    pub name: Option<String>,
+
+    /// New floor number
+    #[serde(default)]
+    #[validate(range(min = -20, max = 200, message = "Floor number must be -20 to 200"))]
+    pub floor_number: Option<i32>,
+}
+
+/// Add an internal note to a booking (admin)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct AddBookingNoteRequest {
+    /// Note text, never shown to the booking's owner
+    #[validate(length(min = 1, max = 2000, message = "Note must be 1-2000 characters"))]
+    pub text: String,
+}
+
+/// Download a backup of the database (admin)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct BackupRequest {
+    /// Passphrase the archive is encrypted with; needed again to restore it
+    #[validate(length(min = 12, max = 256, message = "Passphrase must be 12-256 characters"))]
+    pub passphrase: String,
+}
+
+/// Switch a feature flag on or off (admin)
+#[derive(Debug, Deserialize, Validate, ToSchema)]
+pub struct SetFeatureFlagRequest {
+    pub enabled: bool,
+}
+
 fn default_currency() -> String {
     "EUR".to_string()
 }
@@ -375,6 +548,250 @@ const fn default_per_page() -> i32 {
     20
 }
 
+/// Sort direction of a list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, ToSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Paging and sorting of list endpoints: `?page=&per_page=&sort=&order=&cursor=`
+#[derive(Debug, Deserialize, utoipa::IntoParams, Default)]
+#[into_params(parameter_in = Query)]
+pub struct ListParams {
+    /// Page number (1-based, default 1)
+    pub page: Option<i32>,
+
+    /// Items per page (default 20)
+    pub per_page: Option<i32>,
+
+    /// Field to sort by; each endpoint documents the fields it accepts
+    pub sort: Option<String>,
+
+    /// Sort direction (`asc` or `desc`)
+    #[serde(default)]
+    #[param(inline)]
+    pub order: SortOrder,
+
+    /// `next_cursor` of the previous page; takes precedence over `page`
+    pub cursor: Option<String>,
+}
+
+/// Sortable fields of a list endpoint with the order of each; the first is
+/// the default, read from the list's order index.
+pub type SortFields<T> = [(&'static str, SortBy<T>)];
+
+impl ListParams {
+    /// Whether the caller asked for a page rather than the whole list.
+    pub const fn is_paged(&self) -> bool {
+        self.page.is_some() || self.per_page.is_some() || self.cursor.is_some()
+    }
+
+    pub fn page(&self) -> i32 {
+        self.page.unwrap_or_else(default_page).max(1)
+    }
+
+    pub fn per_page(&self) -> i32 {
+        self.per_page.unwrap_or_else(default_per_page).max(1)
+    }
+
+    /// Pages needed for `total` items (minimum 1).
+    pub fn total_pages(&self, total: usize) -> i32 {
+        let per_page = self.per_page();
+        ((i32::try_from(total).unwrap_or(i32::MAX) - 1) / per_page + 1).max(1)
+    }
+
+    /// The requested page. Endpoints not `paged_by_default` return every
+    /// item unless a page was asked for. `None` if the cursor is malformed.
+    pub fn page_query(&self, paged_by_default: bool) -> Option<crate::db::PageQuery> {
+        let descending = self.order == SortOrder::Desc;
+        if paged_by_default || self.is_paged() {
+            crate::db::PageQuery::new(
+                self.page(),
+                self.per_page(),
+                self.cursor.as_deref(),
+                descending,
+            )
+        } else {
+            Some(crate::db::PageQuery::all(descending))
+        }
+    }
+
+    /// Order of the requested field among `fields`. `None` for a field the
+    /// endpoint cannot sort by.
+    pub fn sort_by<T>(&self, fields: &SortFields<T>) -> Option<SortBy<T>> {
+        match self.sort.as_deref() {
+            None => fields.first().map(|(_, sort)| *sort),
+            Some(sort) => fields
+                .iter()
+                .find(|(name, _)| *name == sort)
+                .map(|(_, sort)| *sort),
+        }
+    }
+
+    /// Pagination metadata for a page of `total` matching items.
+    pub fn meta(&self, total: usize, next_cursor: Option<String>) -> parkhub_common::ResponseMeta {
+        parkhub_common::ResponseMeta {
+            page: Some(self.page()),
+            per_page: Some(self.per_page()),
+            total: Some(i32::try_from(total).unwrap_or(i32::MAX)),
+            total_pages: Some(self.total_pages(total)),
+            next_cursor,
+        }
+    }
+}
+
+/// Sortable fields of booking lists.
+pub const BOOKING_SORT_FIELDS: &SortFields<parkhub_common::Booking> = &[
+    ("start_time", SortBy::Default),
+    ("end_time", SortBy::Key(|b| time_key(b.end_time))),
+    ("created_at", SortBy::Key(|b| time_key(b.created_at))),
+    ("status", SortBy::Key(|b| format!("{:?}", b.status))),
+];
+
+/// Sortable fields of user lists.
+pub const USER_SORT_FIELDS: &SortFields<parkhub_common::User> = &[
+    ("created_at", SortBy::Default),
+    ("username", SortBy::Key(|u| text_key(&u.username))),
+    ("email", SortBy::Key(|u| text_key(&u.email))),
+    ("name", SortBy::Key(|u| text_key(&u.name))),
+    ("role", SortBy::Key(|u| format!("{:?}", u.role))),
+];
+
+/// Sortable fields of parking lot lists.
+pub const LOT_SORT_FIELDS: &SortFields<parkhub_common::ParkingLot> = &[
+    ("name", SortBy::Default),
+    ("created_at", SortBy::Key(|l| time_key(l.created_at))),
+    (
+        "total_slots",
+        SortBy::Key(|l| count_key(u64::try_from(l.total_slots).unwrap_or_default())),
+    ),
+    (
+        "available_slots",
+        SortBy::Key(|l| count_key(u64::try_from(l.available_slots).unwrap_or_default())),
+    ),
+];
+
+/// Booking list filters of `GET /api/v1/bookings` and `GET /api/v1/admin/bookings`
+#[derive(Debug, Deserialize, utoipa::IntoParams, Default)]
+#[into_params(parameter_in = Query)]
+pub struct BookingListFilters {
+    /// Only bookings in this status
+    pub status: Option<parkhub_common::BookingStatus>,
+
+    /// Only bookings in this lot
+    pub lot_id: Option<Uuid>,
+
+    /// Only bookings ending after this time
+    pub from_date: Option<DateTime<Utc>>,
+
+    /// Only bookings starting before this time
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+impl BookingListFilters {
+    /// Whether no filter was given.
+    pub const fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.lot_id.is_none()
+            && self.from_date.is_none()
+            && self.to_date.is_none()
+    }
+
+    pub fn matches(&self, booking: &parkhub_common::Booking) -> bool {
+        self.status.as_ref().is_none_or(|s| *s == booking.status)
+            && self.lot_id.is_none_or(|id| id == booking.lot_id)
+            && self.from_date.is_none_or(|from| booking.end_time > from)
+            && self.to_date.is_none_or(|to| booking.start_time < to)
+    }
+}
+
+/// User list filters of `GET /api/v1/admin/users`
+#[derive(Debug, Deserialize, utoipa::IntoParams, Default)]
+#[into_params(parameter_in = Query)]
+pub struct UserListFilters {
+    /// Only users with this role
+    #[param(value_type = Option<String>)]
+    pub role: Option<parkhub_common::UserRole>,
+
+    /// Only active (`true`) or deactivated (`false`) users
+    pub is_active: Option<bool>,
+
+    /// Only users whose username, email or name contains this text
+    /// (case-insensitive)
+    pub search: Option<String>,
+}
+
+impl UserListFilters {
+    /// Whether no filter was given.
+    pub const fn is_empty(&self) -> bool {
+        self.role.is_none() && self.is_active.is_none() && self.search.is_none()
+    }
+
+    pub fn matches(&self, user: &parkhub_common::User) -> bool {
+        self.role.as_ref().is_none_or(|role| *role == user.role)
+            && self.is_active.is_none_or(|active| active == user.is_active)
+            && self.search.as_deref().is_none_or(|search| {
+                let search = search.to_lowercase();
+                [&user.username, &user.email, &user.name]
+                    .iter()
+                    .any(|field| field.to_lowercase().contains(&search))
+            })
+    }
+}
+
+/// Lot list filters of `GET /api/v1/lots`
+#[derive(Debug, Deserialize, utoipa::IntoParams, Default)]
+#[into_params(parameter_in = Query)]
+pub struct LotListFilters {
+    /// Only lots in this status
+    #[param(value_type = Option<String>)]
+    pub status: Option<parkhub_common::models::LotStatus>,
+}
+
+impl LotListFilters {
+    /// Whether no filter was given.
+    pub const fn is_empty(&self) -> bool {
+        self.status.is_none()
+    }
+
+    pub fn matches(&self, lot: &parkhub_common::ParkingLot) -> bool {
+        self.status
+            .as_ref()
+            .is_none_or(|status| *status == lot.status)
+    }
+}
+
+/// Activity feed filters of `GET /api/v1/users/me/activity`
+#[derive(Debug, Deserialize, utoipa::IntoParams, Default)]
+#[into_params(parameter_in = Query)]
+pub struct ActivityFilters {
+    /// Only entries of this kind
+    pub kind: Option<parkhub_common::ActivityKind>,
+
+    /// Only entries concerning this booking
+    pub booking_id: Option<Uuid>,
+
+    /// Only entries at or after this time
+    pub from_date: Option<DateTime<Utc>>,
+
+    /// Only entries before this time
+    pub to_date: Option<DateTime<Utc>>,
+}
+
+impl ActivityFilters {
+    pub fn matches(&self, entry: &parkhub_common::ActivityEntry) -> bool {
+        self.kind.is_none_or(|kind| kind == entry.kind)
+            && self
+                .booking_id
+                .is_none_or(|id| entry.booking.as_ref().is_some_and(|b| b.id == id))
+            && self.from_date.is_none_or(|from| entry.at >= from)
+            && self.to_date.is_none_or(|to| entry.at < to)
+    }
+}
+
 /// Booking list filters
 #[derive(Debug, Deserialize, Validate, ToSchema, Default)]
 pub struct BookingFiltersParams {
@@ -759,12 +1176,12 @@ mod tests {
         };
         assert!(min_valid.validate().is_ok());
 
-        // Max valid duration: 1440 min (24 hours)
+        // Max valid duration: 20160 min (14 days)
         let max_valid = CreateBookingRequest {
             lot_id: Uuid::new_v4(),
             slot_id: Uuid::new_v4(),
             start_time: Utc::now() + chrono::Duration::hours(1),
-            duration_minutes: 1440,
+            duration_minutes: 20160,
             vehicle_id: None,
             license_plate: Some("AB-CD-123".to_string()),
             notes: None,
@@ -855,6 +1272,7 @@ mod tests {
             vehicle_type: Some("suv".to_string()),
             fuel_type: None,
             is_default: true,
+            country: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -870,6 +1288,7 @@ mod tests {
 
             fuel_type: None,
             is_default: false,
+            country: None,
         };
         assert!(req.validate().is_err());
     }
@@ -1066,12 +1485,12 @@ mod tests {
     }
 
     #[test]
-    fn test_create_booking_request_duration_1441_minutes_fails() {
+    fn test_create_booking_request_duration_over_14_days_fails() {
         let req = CreateBookingRequest {
             lot_id: Uuid::new_v4(),
             slot_id: Uuid::new_v4(),
             start_time: chrono::Utc::now() + chrono::Duration::hours(1),
-            duration_minutes: 1441,
+            duration_minutes: 20161,
             vehicle_id: None,
             license_plate: Some("AB-CD-123".to_string()),
             notes: None,
@@ -1121,6 +1540,7 @@ mod tests {
 
             fuel_type: None,
             is_default: false,
+            country: None,
         };
         assert!(req.validate().is_err());
     }
@@ -1136,6 +1556,7 @@ mod tests {
 
             fuel_type: None,
             is_default: false,
+            country: None,
         };
         assert!(req.validate().is_err());
     }
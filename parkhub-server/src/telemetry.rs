@@ -0,0 +1,234 @@
+//! Opt-in anonymous usage telemetry.
+//!
+//! Off by default and never enabled implicitly. When an operator turns it
+//! on (status window or `PUT /api/v1/admin/telemetry`) the server posts one
+//! small JSON report per day to the configured endpoint. There is no
+//! built-in endpoint: without one, nothing is sent even when enabled.
+//!
+//! The report contains only the server version, OS/architecture, compiled
+//! feature modules, and bucketed counts (e.g. `"11-50"` users) — no names,
+//! e-mail addresses, plates, hostnames, or IPs. [`build_report`] is what
+//! gets sent; the GUI and admin API show its output verbatim before
+//! enabling.
+//!
+//! `PARKHUB_TELEMETRY_URL` overrides the stored endpoint.
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::Database;
+
+/// Settings key holding [`TelemetrySettings`] as JSON.
+const TELEMETRY_SETTINGS_KEY: &str = "telemetry";
+
+/// Timeout for a single report upload.
+const REPORT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Persisted telemetry preferences.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+    /// Where reports are posted. `None` means "nowhere".
+    pub endpoint: Option<String>,
+    /// Random per-install identifier so repeated reports can be
+    /// de-duplicated. Not derived from any machine or user data.
+    pub install_id: Uuid,
+    pub last_sent_at: Option<DateTime<Utc>>,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: None,
+            install_id: Uuid::new_v4(),
+            last_sent_at: None,
+        }
+    }
+}
+
+impl TelemetrySettings {
+    /// The endpoint to post to, honouring `PARKHUB_TELEMETRY_URL`.
+    pub fn effective_endpoint(&self) -> Option<String> {
+        std::env::var("PARKHUB_TELEMETRY_URL")
+            .ok()
+            .or_else(|| self.endpoint.clone())
+            .filter(|u| !u.trim().is_empty())
+    }
+}
+
+/// The exact payload sent to the telemetry endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, utoipa::ToSchema)]
+pub struct TelemetryReport {
+    pub install_id: Uuid,
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    /// Compiled-in feature modules (slugs without the `mod-` prefix).
+    pub features: Vec<String>,
+    pub users: String,
+    pub bookings: String,
+    pub parking_lots: String,
+    pub slots: String,
+}
+
+/// Coarse bucket for a count so exact sizes are never reported.
+#[must_use]
+pub const fn bucket(n: u64) -> &'static str {
+    match n {
+        0 => "0",
+        1..=10 => "1-10",
+        11..=50 => "11-50",
+        51..=200 => "51-200",
+        201..=1000 => "201-1000",
+        1001..=5000 => "1001-5000",
+        _ => "5000+",
+    }
+}
+
+/// Load telemetry settings, creating (and persisting) a disabled default
+/// with a fresh install ID on first use.
+pub async fn load_settings(db: &Database) -> TelemetrySettings {
+    if let Ok(Some(raw)) = db.get_setting(TELEMETRY_SETTINGS_KEY).await
+        && let Ok(settings) = serde_json::from_str(&raw)
+    {
+        return settings;
+    }
+    let settings = TelemetrySettings::default();
+    if let Err(e) = save_settings(db, &settings).await {
+        tracing::warn!("Failed to persist telemetry settings: {e}");
+    }
+    settings
+}
+
+/// Persist telemetry settings.
+pub async fn save_settings(db: &Database, settings: &TelemetrySettings) -> anyhow::Result<()> {
+    db.set_setting(TELEMETRY_SETTINGS_KEY, &serde_json::to_string(settings)?)
+        .await
+}
+
+/// Build the report that would be sent right now.
+pub async fn build_report(db: &Database, install_id: Uuid) -> TelemetryReport {
+    let stats = db.stats().await.unwrap_or_default();
+    let features = crate::api::modules::module_registry_static()
+        .into_iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.name)
+        .collect();
+    TelemetryReport {
+        install_id,
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        features,
+        users: bucket(stats.users).to_string(),
+        bookings: bucket(stats.bookings).to_string(),
+        parking_lots: bucket(stats.parking_lots).to_string(),
+        slots: bucket(stats.slots).to_string(),
+    }
+}
+
+/// Pretty-printed report for display before opting in.
+pub async fn preview_json(db: &Database) -> String {
+    let settings = load_settings(db).await;
+    let report = build_report(db, settings.install_id).await;
+    serde_json::to_string_pretty(&report).unwrap_or_default()
+}
+
+fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .timeout(REPORT_TIMEOUT)
+            .user_agent(concat!("parkhub-server/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default()
+    })
+}
+
+/// Send one report if telemetry is enabled and an endpoint is configured.
+///
+/// Returns `Ok(false)` when nothing was sent.
+///
+/// # Errors
+/// Returns an error when the upload fails or the endpoint rejects it.
+pub async fn send_if_enabled(db: &Database) -> anyhow::Result<bool> {
+    let mut settings = load_settings(db).await;
+    if !settings.enabled {
+        return Ok(false);
+    }
+    let Some(endpoint) = settings.effective_endpoint() else {
+        return Ok(false);
+    };
+
+    let report = build_report(db, settings.install_id).await;
+    client()
+        .post(&endpoint)
+        .json(&report)
+        .send()
+        .await?
+        .error_for_status()?;
+
+    settings.last_sent_at = Some(Utc::now());
+    save_settings(db, &settings).await?;
+    tracing::info!("Telemetry report sent");
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_boundaries() {
+        assert_eq!(bucket(0), "0");
+        assert_eq!(bucket(1), "1-10");
+        assert_eq!(bucket(10), "1-10");
+        assert_eq!(bucket(11), "11-50");
+        assert_eq!(bucket(200), "51-200");
+        assert_eq!(bucket(1000), "201-1000");
+        assert_eq!(bucket(5000), "1001-5000");
+        assert_eq!(bucket(5001), "5000+");
+    }
+
+    #[test]
+    fn test_default_settings_are_off() {
+        let settings = TelemetrySettings::default();
+        assert!(!settings.enabled);
+        assert!(settings.endpoint.is_none());
+        assert!(settings.last_sent_at.is_none());
+    }
+
+    #[test]
+    fn test_report_has_no_identifying_fields() {
+        let report = TelemetryReport {
+            install_id: Uuid::nil(),
+            version: "1.0.0".into(),
+            os: "linux".into(),
+            arch: "x86_64".into(),
+            features: vec!["bookings".into()],
+            users: bucket(42).into(),
+            bookings: bucket(0).into(),
+            parking_lots: bucket(1).into(),
+            slots: bucket(12).into(),
+        };
+        let json = serde_json::to_value(&report).unwrap();
+        let keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        for forbidden in ["hostname", "ip", "email", "server_name"] {
+            assert!(
+                !keys.contains(&forbidden),
+                "{forbidden} must not be reported"
+            );
+        }
+        assert_eq!(json["users"], "11-50");
+    }
+}
@@ -1,43 +1,188 @@
 //! Input Validation
 //!
 //! Provides validation for API request payloads using the validator crate.
+//!
+//! Payloads implement [`ValidateRequest`] and are extracted with
+//! [`ValidatedJson`], so bad input is answered before the handler runs:
+//! `INVALID_INPUT` when the body is not valid JSON of the expected shape
+//! (including malformed UUIDs), `VALIDATION_ERROR` with one entry per
+//! offending field in `error.details.fields` when it parses but breaks a
+//! rule.
 
 use axum::{
     Json,
     extract::{FromRequest, Request, rejection::JsonRejection},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use parkhub_common::{
+    ApiError, ApiResponse, CreateBookingRequest, ErrorCode, MAX_BOOKING_MINUTES, RegisterRequest,
 };
 use serde::de::DeserializeOwned;
-use validator::Validate;
-
-use crate::error::AppError;
+use uuid::Uuid;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::api::rectification::RECTIFIABLE_FIELDS;
+use crate::db::RectificationStatus;
+use crate::error::FieldError;
+use crate::requests::{
+    AddBookingNoteRequest, BackupRequest, CreateFloorRequest, CreateParkingLotRequest,
+    IssueVisitorPassRequest, ResolveRectificationRequest, SetFeatureFlagRequest,
+    SubmitRectificationRequest, UpdateFloorRequest, VehicleRequest, VisitorBookingRequest,
+    VisitorPassBlockRequest,
+};
 
 /// Validated JSON extractor
 ///
 /// Extracts and validates JSON payloads in a single step.
-/// Returns proper error responses for both parsing and validation failures.
+/// Rejects with an `ApiResponse` error for both parsing and validation failures.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct ValidatedJson<T>(pub T);
 
 impl<T, S> FromRequest<S> for ValidatedJson<T>
 where
-    T: DeserializeOwned + Validate,
+    T: DeserializeOwned + ValidateRequest,
     S: Send + Sync,
 {
-    type Rejection = AppError;
+    type Rejection = Response;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
         // Extract JSON
-        let Json(value): Json<T> = Json::from_request(req, state)
-            .await
-            .map_err(|e: JsonRejection| AppError::InvalidInput(e.to_string()))?;
+        let Json(value): Json<T> =
+            Json::from_request(req, state)
+                .await
+                .map_err(|e: JsonRejection| {
+                    (
+                        e.status(),
+                        Json(ApiResponse::<()>::error(
+                            ErrorCode::InvalidInput,
+                            e.body_text(),
+                        )),
+                    )
+                        .into_response()
+                })?;
 
         // Validate
-        value.validate()?;
+        value
+            .validate_request()
+            .map_err(|errors| errors.response::<()>().into_response())?;
 
         Ok(Self(value))
     }
 }
 
+/// A request payload with rules beyond what deserializing it enforces.
+pub trait ValidateRequest {
+    /// Check the payload.
+    ///
+    /// # Errors
+    /// Returns every field that breaks a rule.
+    fn validate_request(&self) -> Result<(), FieldErrors>;
+}
+
+/// Field-level problems found in a request payload
+#[derive(Debug, Default)]
+pub struct FieldErrors(Vec<FieldError>);
+
+impl FieldErrors {
+    /// Record a problem with `field`.
+    pub fn add(&mut self, field: &str, message: impl Into<String>) {
+        self.0.push(FieldError {
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Record the failure of a validator run on `field`, if it failed.
+    pub fn check(&mut self, field: &str, result: Result<(), ValidationError>) {
+        if let Err(e) = result {
+            self.add(field, error_message(&e));
+        }
+    }
+
+    /// Record a problem if `value` is longer than `max` characters.
+    pub fn max_chars(&mut self, field: &str, value: &str, max: usize) {
+        if value.chars().count() > max {
+            self.add(field, format!("{field} must be at most {max} characters"));
+        }
+    }
+
+    /// Whether a problem was recorded for `field`.
+    pub fn has(&self, field: &str) -> bool {
+        self.0.iter().any(|e| e.field == field)
+    }
+
+    /// `Ok` if nothing was recorded.
+    ///
+    /// # Errors
+    /// Returns `self` if any field has a problem.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.0.is_empty() { Ok(()) } else { Err(self) }
+    }
+
+    /// The recorded problems, in the order they were found.
+    pub fn into_vec(self) -> Vec<FieldError> {
+        self.0
+    }
+
+    /// The `VALIDATION_ERROR` response listing these problems.
+    pub fn response<T>(self) -> (StatusCode, Json<ApiResponse<T>>) {
+        let message = self
+            .0
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ApiResponse {
+                success: false,
+                data: None,
+                error: Some(ApiError {
+                    code: ErrorCode::ValidationError,
+                    message,
+                    details: Some(serde_json::json!({ "fields": self.0 })),
+                }),
+                meta: None,
+            }),
+        )
+    }
+}
+
+impl From<ValidationErrors> for FieldErrors {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut fields: Vec<FieldError> = errors
+            .field_errors()
+            .iter()
+            .flat_map(|(field, errors)| {
+                errors.iter().map(move |e| FieldError {
+                    field: field.to_string(),
+                    message: error_message(e),
+                })
+            })
+            .collect();
+        fields.sort_by(|a, b| a.field.cmp(&b.field));
+        Self(fields)
+    }
+}
+
+/// The message of `error`, or its code when it has none.
+fn error_message(error: &ValidationError) -> String {
+    error
+        .message
+        .as_ref()
+        .map_or_else(|| error.code.to_string(), std::string::ToString::to_string)
+}
+
+/// Problems `validate()` finds in `payload`, as [`FieldErrors`] to add to.
+fn derived_errors(payload: &impl Validate) -> FieldErrors {
+    payload
+        .validate()
+        .err()
+        .map(FieldErrors::from)
+        .unwrap_or_default()
+}
+
 // === Common Validation Rules ===
 
 use regex::Regex;
@@ -55,28 +200,164 @@ pub static LICENSE_PLATE_REGEX: LazyLock<Regex> =
 pub static USERNAME_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"^[a-zA-Z][a-zA-Z0-9_]{2,29}$").unwrap());
 
+/// A plate without case and separators, as the plate validators see it.
+fn normalize_plate(plate: &str) -> String {
+    plate.to_uppercase().replace(['-', ' '], "")
+}
+
 /// Custom validator for license plates
+///
+/// Accepts 2-10 letters or digits once `-` and spaces are removed; letters
+/// outside ASCII (e.g. the umlauts of German district codes) count too.
 pub fn validate_license_plate(plate: &str) -> Result<(), validator::ValidationError> {
-    let normalized = plate.to_uppercase().replace(['-', ' '], "");
-    if normalized.len() < 2 || normalized.len() > 10 {
-        return Err(validator::ValidationError::new("invalid_license_plate"));
+    let normalized = normalize_plate(plate);
+    let length = normalized.chars().count();
+    if !(2..=10).contains(&length) || !normalized.chars().all(char::is_alphanumeric) {
+        let mut err = validator::ValidationError::new("invalid_license_plate");
+        err.message = Some("Invalid license plate".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Countries whose license plate format is checked exactly
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlateCountry {
+    Austria,
+    France,
+    Germany,
+    Italy,
+    Netherlands,
+    Spain,
+    Switzerland,
+    UnitedKingdom,
+}
+
+impl PlateCountry {
+    /// The country for an ISO 3166-1 alpha-2 code (any case); `None` for
+    /// countries without a known plate format.
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code.to_ascii_uppercase().as_str() {
+            "AT" => Some(Self::Austria),
+            "FR" => Some(Self::France),
+            "DE" => Some(Self::Germany),
+            "IT" => Some(Self::Italy),
+            "NL" => Some(Self::Netherlands),
+            "ES" => Some(Self::Spain),
+            "CH" => Some(Self::Switzerland),
+            "GB" | "UK" => Some(Self::UnitedKingdom),
+            _ => None,
+        }
+    }
+
+    const ALL: [Self; 8] = [
+        Self::Austria,
+        Self::France,
+        Self::Germany,
+        Self::Italy,
+        Self::Netherlands,
+        Self::Spain,
+        Self::Switzerland,
+        Self::UnitedKingdom,
+    ];
+
+    /// English name of the country
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Austria => "Austria",
+            Self::France => "France",
+            Self::Germany => "Germany",
+            Self::Italy => "Italy",
+            Self::Netherlands => "the Netherlands",
+            Self::Spain => "Spain",
+            Self::Switzerland => "Switzerland",
+            Self::UnitedKingdom => "the United Kingdom",
+        }
     }
-    if !normalized.chars().all(|c| c.is_ascii_alphanumeric()) {
-        return Err(validator::ValidationError::new("invalid_license_plate"));
+
+    /// Current plate format of the country, matched against a normalized
+    /// plate (upper case, no `-` or spaces).
+    const fn pattern(self) -> &'static str {
+        match self {
+            // District, then digits and letters: W 12345 A, L 8423 AB
+            Self::Austria => r"^[A-Z]{1,2}[0-9A-Z]{3,6}$",
+            // SIV since 2009: AB-123-CD
+            Self::France => r"^[A-Z]{2}[0-9]{3}[A-Z]{2}$",
+            // District, letters, number, optional E (electric) or H
+            // (historic): M-AB 1234, MÜ-X 12E
+            Self::Germany => r"^[A-ZÄÖÜ]{1,3}[A-Z]{1,2}[1-9][0-9]{0,3}[EH]?$",
+            // Since 1994: AB 123 CD
+            Self::Italy => r"^[A-Z]{2}[0-9]{3}[A-Z]{2}$",
+            // Six characters in one of the sidecode groupings: 12-ABC-3
+            Self::Netherlands => r"^[0-9A-Z]{6}$",
+            // Since 2000: 1234 BCD, consonants only
+            Self::Spain => r"^[0-9]{4}[B-DF-HJ-NP-TV-Z]{3}$",
+            // Canton and up to six digits: ZH 123456
+            Self::Switzerland => {
+                r"^(AG|AI|AR|BE|BL|BS|FR|GE|GL|GR|JU|LU|NE|NW|OW|SG|SH|SO|SZ|TG|TI|UR|VD|VS|ZG|ZH)[0-9]{1,6}$"
+            }
+            // Since 2001: AB12 CDE
+            Self::UnitedKingdom => r"^[A-Z]{2}[0-9]{2}[A-Z]{3}$",
+        }
+    }
+
+    fn matches(self, plate: &str) -> bool {
+        static PATTERNS: LazyLock<Vec<(PlateCountry, Regex)>> = LazyLock::new(|| {
+            PlateCountry::ALL
+                .iter()
+                .map(|country| (*country, Regex::new(country.pattern()).unwrap()))
+                .collect()
+        });
+        PATTERNS
+            .iter()
+            .find(|(country, _)| *country == self)
+            .is_some_and(|(_, pattern)| pattern.is_match(&normalize_plate(plate)))
+    }
+}
+
+/// Custom validator for license plates registered in `country`
+pub fn validate_license_plate_in(
+    plate: &str,
+    country: PlateCountry,
+) -> Result<(), validator::ValidationError> {
+    if !country.matches(plate) {
+        let mut err = validator::ValidationError::new("invalid_license_plate");
+        err.message = Some(format!("Not a valid license plate for {}", country.name()).into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Custom validator for IDs that must name something
+pub fn validate_not_nil(id: &Uuid) -> Result<(), validator::ValidationError> {
+    if id.is_nil() {
+        let mut err = validator::ValidationError::new("nil_uuid");
+        err.message = Some("ID must not be the nil UUID".into());
+        return Err(err);
+    }
+    Ok(())
+}
+
+/// Custom validator for email addresses
+pub fn validate_email_address(email: &str) -> Result<(), validator::ValidationError> {
+    if !parkhub_common::is_valid_email(email) {
+        let mut err = validator::ValidationError::new("invalid_email");
+        err.message = Some("Invalid email address".into());
+        return Err(err);
     }
     Ok(())
 }
 
 /// Custom validator for booking duration
 pub fn validate_booking_duration(minutes: i32) -> Result<(), validator::ValidationError> {
-    if minutes < 15 {
+    if minutes < parkhub_common::MIN_BOOKING_MINUTES {
         let mut err = validator::ValidationError::new("too_short");
         err.message = Some("Minimum booking duration is 15 minutes".into());
         return Err(err);
     }
-    if minutes > 24 * 60 {
+    if minutes > parkhub_common::MAX_BOOKING_MINUTES {
         let mut err = validator::ValidationError::new("too_long");
-        err.message = Some("Maximum booking duration is 24 hours".into());
+        err.message = Some("Maximum booking duration is 14 days".into());
         return Err(err);
     }
     Ok(())
@@ -115,6 +396,178 @@ pub fn validate_password_strength(password: &str) -> Result<(), validator::Valid
     Ok(())
 }
 
+// === Request Payloads ===
+
+impl ValidateRequest for RegisterRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        let mut errors = FieldErrors::default();
+        errors.check("email", validate_email_address(&self.email));
+        if self.name.trim().is_empty() {
+            errors.add("name", "Name is required");
+        }
+        errors.max_chars("name", &self.name, 100);
+        errors.into_result()
+    }
+}
+
+impl ValidateRequest for CreateBookingRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        let mut errors = FieldErrors::default();
+        errors.check("lot_id", validate_not_nil(&self.lot_id));
+        // Late-binding lots book a slot type instead of a slot
+        if self.slot_type.is_none() {
+            errors.check("slot_id", validate_not_nil(&self.slot_id));
+        }
+        if !(1..=MAX_BOOKING_MINUTES).contains(&self.duration_minutes) {
+            errors.add(
+                "duration_minutes",
+                "Duration must be between 1 minute and 14 days",
+            );
+        }
+        // Bookings with a registered vehicle may leave the plate empty
+        if !self.license_plate.trim().is_empty() {
+            errors.check("license_plate", validate_license_plate(&self.license_plate));
+        }
+        if let Some(notes) = &self.notes {
+            errors.max_chars("notes", notes, 500);
+        }
+        errors.into_result()
+    }
+}
+
+impl ValidateRequest for VisitorBookingRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        let mut errors = derived_errors(self);
+        if self.visitor_name.trim().is_empty() && !errors.has("visitor_name") {
+            errors.add("visitor_name", "Visitor name is required");
+        }
+        errors.into_result()
+    }
+}
+
+impl ValidateRequest for VehicleRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        let mut errors = derived_errors(self);
+        if let Some(code) = &self.country {
+            match PlateCountry::from_code(code) {
+                Some(country) if !errors.has("license_plate") => errors.check(
+                    "license_plate",
+                    validate_license_plate_in(&self.license_plate, country),
+                ),
+                Some(_) => {}
+                None => errors.add("country", format!("No known plate format for `{code}`")),
+            }
+        }
+        errors.into_result()
+    }
+}
+
+impl ValidateRequest for CreateParkingLotRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        let mut errors = derived_errors(self);
+        if !errors.has("currency") && !self.currency.chars().all(|c| c.is_ascii_alphabetic()) {
+            errors.add("currency", "Currency must be a 3-letter code");
+        }
+        errors.into_result()
+    }
+}
+
+impl ValidateRequest for CreateFloorRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        let mut errors = derived_errors(self);
+        if self.name.trim().is_empty() && !errors.has("name") {
+            errors.add("name", "Name is required");
+        }
+        errors.into_result()
+    }
+}
+
+impl ValidateRequest for UpdateFloorRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        let mut errors = derived_errors(self);
+        if self.name.as_deref().is_some_and(|n| n.trim().is_empty()) && !errors.has("name") {
+            errors.add("name", "Name must not be blank");
+        }
+        errors.into_result()
+    }
+}
+
+impl ValidateRequest for SubmitRectificationRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        let mut errors = derived_errors(self);
+        if !RECTIFIABLE_FIELDS.contains(&self.field.as_str()) {
+            errors.add(
+                "field",
+                format!(
+                    "Field must be one of {}; edit other profile fields yourself",
+                    RECTIFIABLE_FIELDS.join(", ")
+                ),
+            );
+        } else if self.field == "email" && !errors.has("requested_value") {
+            errors.check(
+                "requested_value",
+                validate_email_address(self.requested_value.trim()),
+            );
+        }
+        if self.requested_value.trim().is_empty() && !errors.has("requested_value") {
+            errors.add("requested_value", "Requested value is required");
+        }
+        errors.into_result()
+    }
+}
+
+impl ValidateRequest for ResolveRectificationRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        let mut errors = derived_errors(self);
+        match self.status {
+            RectificationStatus::Open => {
+                errors.add("status", "Status must be corrected or rejected");
+            }
+            RectificationStatus::Rejected
+                if self.note.as_deref().is_none_or(|n| n.trim().is_empty()) =>
+            {
+                errors.add("note", "A note is required when rejecting");
+            }
+            _ => {}
+        }
+        errors.into_result()
+    }
+}
+
+impl ValidateRequest for AddBookingNoteRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        derived_errors(self).into_result()
+    }
+}
+
+impl ValidateRequest for VisitorPassBlockRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        derived_errors(self).into_result()
+    }
+}
+
+impl ValidateRequest for IssueVisitorPassRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        let mut errors = derived_errors(self);
+        if self.visitor_name.trim().is_empty() && !errors.has("visitor_name") {
+            errors.add("visitor_name", "Visitor name is required");
+        }
+        errors.into_result()
+    }
+}
+
+impl ValidateRequest for BackupRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        derived_errors(self).into_result()
+    }
+}
+
+impl ValidateRequest for SetFeatureFlagRequest {
+    fn validate_request(&self) -> Result<(), FieldErrors> {
+        derived_errors(self).into_result()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,12 +618,188 @@ mod tests {
         assert!(validate_license_plate("").is_err());
     }
 
+    #[test]
+    fn test_license_plate_allows_umlauts() {
+        assert!(validate_license_plate("MÜ-AB 12").is_ok());
+        assert!(validate_license_plate("mü-ab 12").is_ok());
+    }
+
+    #[test]
+    fn test_license_plate_per_country() {
+        let de = PlateCountry::from_code("de").unwrap();
+        assert!(validate_license_plate_in("M-AB 1234", de).is_ok());
+        assert!(validate_license_plate_in("MÜ-X 12E", de).is_ok());
+        assert!(validate_license_plate_in("M-AB 0123", de).is_err());
+        assert!(validate_license_plate_in("AB-123-CD", de).is_err());
+
+        let fr = PlateCountry::from_code("FR").unwrap();
+        assert!(validate_license_plate_in("AB-123-CD", fr).is_ok());
+        assert!(validate_license_plate_in("M-AB 1234", fr).is_err());
+
+        let ch = PlateCountry::from_code("CH").unwrap();
+        assert!(validate_license_plate_in("ZH 123456", ch).is_ok());
+        assert!(validate_license_plate_in("XX 123", ch).is_err());
+
+        let gb = PlateCountry::from_code("UK").unwrap();
+        assert_eq!(gb, PlateCountry::UnitedKingdom);
+        assert!(validate_license_plate_in("AB12 CDE", gb).is_ok());
+
+        let es = PlateCountry::from_code("ES").unwrap();
+        assert!(validate_license_plate_in("1234 BCD", es).is_ok());
+        assert!(validate_license_plate_in("1234 ABC", es).is_err());
+
+        assert!(PlateCountry::from_code("US").is_none());
+    }
+
+    #[test]
+    fn test_validate_not_nil() {
+        assert!(validate_not_nil(&Uuid::new_v4()).is_ok());
+        assert_eq!(
+            validate_not_nil(&Uuid::nil()).unwrap_err().code.as_ref(),
+            "nil_uuid"
+        );
+    }
+
+    fn booking_request() -> CreateBookingRequest {
+        CreateBookingRequest {
+            lot_id: Uuid::new_v4(),
+            slot_id: Uuid::new_v4(),
+            slot_type: None,
+            start_time: chrono::Utc::now() + chrono::Duration::hours(1),
+            duration_minutes: 60,
+            vehicle_id: Uuid::nil(),
+            license_plate: "M-AB 123".to_string(),
+            notes: None,
+            tags: std::collections::BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_create_booking_request_field_errors() {
+        assert!(booking_request().validate_request().is_ok());
+
+        let bad = CreateBookingRequest {
+            lot_id: Uuid::nil(),
+            duration_minutes: 0,
+            license_plate: "A!".to_string(),
+            notes: Some("x".repeat(501)),
+            ..booking_request()
+        };
+        let fields: Vec<String> = bad
+            .validate_request()
+            .unwrap_err()
+            .into_vec()
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(
+            fields,
+            ["lot_id", "duration_minutes", "license_plate", "notes"]
+        );
+
+        // A slot type stands in for the slot; a vehicle for the plate
+        let by_type = CreateBookingRequest {
+            slot_id: Uuid::nil(),
+            slot_type: Some(parkhub_common::models::SlotType::Electric),
+            license_plate: String::new(),
+            ..booking_request()
+        };
+        assert!(by_type.validate_request().is_ok());
+    }
+
+    #[test]
+    fn test_register_request_field_errors() {
+        let request = RegisterRequest {
+            email: "not-an-email".to_string(),
+            password: "SecurePass1!".to_string(),
+            password_confirmation: "SecurePass1!".to_string(),
+            name: " ".to_string(),
+        };
+        let errors = request.validate_request().unwrap_err();
+        assert!(errors.has("email"));
+        assert!(errors.has("name"));
+
+        let (status, Json(body)) = errors.response::<()>();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let error = body.error.unwrap();
+        assert_eq!(error.code, "VALIDATION_ERROR");
+        assert_eq!(error.details.unwrap()["fields"][0]["field"], "email");
+    }
+
+    #[test]
+    fn test_vehicle_request_country() {
+        let request = |plate: &str, country: Option<&str>| VehicleRequest {
+            license_plate: plate.to_string(),
+            make: None,
+            model: None,
+            color: None,
+            vehicle_type: None,
+            fuel_type: None,
+            is_default: false,
+            country: country.map(str::to_string),
+        };
+        assert!(request("AB-123-CD", None).validate_request().is_ok());
+        assert!(request("AB-123-CD", Some("fr")).validate_request().is_ok());
+        let errors = request("AB-123-CD", Some("de"))
+            .validate_request()
+            .unwrap_err();
+        assert!(errors.has("license_plate"));
+        let errors = request("AB-123-CD", Some("xx"))
+            .validate_request()
+            .unwrap_err();
+        assert!(errors.has("country"));
+    }
+
+    #[test]
+    fn test_rectification_requests() {
+        let submit = |field: &str, value: &str| SubmitRectificationRequest {
+            field: field.to_string(),
+            requested_value: value.to_string(),
+            reason: None,
+        };
+        assert!(submit("department", "Sales").validate_request().is_ok());
+        assert!(
+            submit("email", "jane@example.com")
+                .validate_request()
+                .is_ok()
+        );
+        let errors = submit("email", "jane").validate_request().unwrap_err();
+        assert!(errors.has("requested_value"));
+        let errors = submit("phone", "123").validate_request().unwrap_err();
+        assert!(errors.has("field"));
+        let errors = submit("name", "  ").validate_request().unwrap_err();
+        assert!(errors.has("requested_value"));
+
+        let resolve = |status, note: Option<&str>| ResolveRectificationRequest {
+            status,
+            note: note.map(str::to_string),
+        };
+        assert!(
+            resolve(RectificationStatus::Corrected, None)
+                .validate_request()
+                .is_ok()
+        );
+        assert!(
+            resolve(RectificationStatus::Rejected, Some("Matches HR records"))
+                .validate_request()
+                .is_ok()
+        );
+        let errors = resolve(RectificationStatus::Rejected, Some(" "))
+            .validate_request()
+            .unwrap_err();
+        assert!(errors.has("note"));
+        let errors = resolve(RectificationStatus::Open, None)
+            .validate_request()
+            .unwrap_err();
+        assert!(errors.has("status"));
+    }
+
     #[test]
     fn test_validate_booking_duration() {
         assert!(validate_booking_duration(30).is_ok());
         assert!(validate_booking_duration(120).is_ok());
         assert!(validate_booking_duration(10).is_err()); // Too short
-        assert!(validate_booking_duration(25 * 60).is_err()); // Too long
+        assert!(validate_booking_duration(15 * 24 * 60).is_err()); // Too long
     }
 
     #[test]
@@ -179,10 +808,13 @@ mod tests {
         assert!(validate_booking_duration(15).is_ok());
         // 14 minutes = too short
         assert!(validate_booking_duration(14).is_err());
-        // Exactly 24*60 = 1440 minutes = OK (maximum)
+        // Overnight and multi-day durations = OK
         assert!(validate_booking_duration(24 * 60).is_ok());
-        // 1441 minutes = too long
-        assert!(validate_booking_duration(24 * 60 + 1).is_err());
+        assert!(validate_booking_duration(24 * 60 + 1).is_ok());
+        // Exactly 14 days = OK (maximum)
+        assert!(validate_booking_duration(14 * 24 * 60).is_ok());
+        // One minute more = too long
+        assert!(validate_booking_duration(14 * 24 * 60 + 1).is_err());
     }
 
     #[test]
@@ -191,9 +823,9 @@ mod tests {
         assert_eq!(err.code.as_ref(), "too_short");
         assert!(err.message.as_ref().unwrap().contains("15 minutes"));
 
-        let err = validate_booking_duration(2000).unwrap_err();
+        let err = validate_booking_duration(30 * 24 * 60).unwrap_err();
         assert_eq!(err.code.as_ref(), "too_long");
-        assert!(err.message.as_ref().unwrap().contains("24 hours"));
+        assert!(err.message.as_ref().unwrap().contains("14 days"));
     }
 
     #[test]
@@ -383,10 +1015,10 @@ mod proptests {
     // ── validate_booking_duration ─────────────────────────────────────────
 
     proptest! {
-        /// `[15, 1440]` is the documented closed interval — every value
-        /// inside accepts.
+        /// `[15, 20160]` (15 minutes to 14 days) is the documented closed
+        /// interval — every value inside accepts.
         #[test]
-        fn booking_duration_inside_bounds_accepted(minutes in 15i32..=24*60) {
+        fn booking_duration_inside_bounds_accepted(minutes in 15i32..=14*24*60) {
             prop_assert!(validate_booking_duration(minutes).is_ok());
         }
 
@@ -398,10 +1030,10 @@ mod proptests {
             prop_assert_eq!(err.code.as_ref(), "too_short");
         }
 
-        /// Anything strictly above 1440 (including `i32::MAX`) is
+        /// Anything strictly above 20160 (including `i32::MAX`) is
         /// rejected with `too_long`.
         #[test]
-        fn booking_duration_above_1440_rejected(minutes in (24*60 + 1)..=i32::MAX) {
+        fn booking_duration_above_max_rejected(minutes in (14*24*60 + 1)..=i32::MAX) {
             let err = validate_booking_duration(minutes).unwrap_err();
             prop_assert_eq!(err.code.as_ref(), "too_long");
         }
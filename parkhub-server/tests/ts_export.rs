@@ -35,7 +35,8 @@ use std::io::Write;
 use std::path::{Path, PathBuf};
 
 use parkhub_common::models::{
-    Absence, AbsencePattern, AbsenceType, Announcement, AnnouncementSeverity, AuthTokens, Booking,
+    Absence, AbsencePattern, AbsenceType, ActivityBooking, ActivityEntry, ActivityKind,
+    Announcement, AnnouncementAudience, AnnouncementSeverity, AuthTokens, BookedFor, Booking,
     BookingPricing, BookingStatus, ChargingSession, ChargingSessionStatus, ConnectorType,
     CreditTransaction, CreditTransactionType, DayHours, DynamicPriceResult, DynamicPricingRules,
     EvCharger, EvChargerStatus, FleetEvent, FleetEventType, FuelType, GuestBooking, LotStatus,
@@ -310,6 +311,7 @@ fn export_all_types() {
 
     // ── Bookings + vehicles ─────────────────────────────────────────────
     export::<Booking>(&dir).unwrap();
+    export::<BookedFor>(&dir).unwrap();
     export::<BookingStatus>(&dir).unwrap();
     export::<BookingPricing>(&dir).unwrap();
     export::<PaymentStatus>(&dir).unwrap();
@@ -324,9 +326,13 @@ fn export_all_types() {
     export::<SwapRequest>(&dir).unwrap();
     export::<SwapRequestStatus>(&dir).unwrap();
     export::<Announcement>(&dir).unwrap();
+    export::<AnnouncementAudience>(&dir).unwrap();
     export::<AnnouncementSeverity>(&dir).unwrap();
     export::<Notification>(&dir).unwrap();
     export::<NotificationType>(&dir).unwrap();
+    export::<ActivityEntry>(&dir).unwrap();
+    export::<ActivityKind>(&dir).unwrap();
+    export::<ActivityBooking>(&dir).unwrap();
     export::<AbsenceType>(&dir).unwrap();
     export::<Absence>(&dir).unwrap();
     export::<AbsencePattern>(&dir).unwrap();
@@ -782,6 +782,23 @@ describe('API client', () => {
     expect(firstCall()[1].method).toBe('DELETE');
   });
 
+  it('calls user announcement endpoints', async () => {
+    globalThis.fetch = vi.fn().mockResolvedValue({
+      ok: true, status: 200,
+      json: () => Promise.resolve({ success: true, data: [] }),
+    });
+    await api.getMyAnnouncements();
+    expect(firstCall()[0]).toBe('/api/v1/announcements');
+
+    globalThis.fetch = vi.fn().mockResolvedValue({
+      ok: true, status: 200,
+      json: () => Promise.resolve({ success: true, data: null }),
+    });
+    await api.dismissAnnouncement('ann1');
+    expect(firstCall()[0]).toBe('/api/v1/announcements/ann1/dismiss');
+    expect(firstCall()[1].method).toBe('POST');
+  });
+
   it('calls notification endpoints', async () => {
     globalThis.fetch = vi.fn().mockResolvedValue({
       ok: true, status: 200,
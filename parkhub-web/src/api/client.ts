@@ -23,6 +23,7 @@ import type { AbsenceType as GeneratedAbsenceType } from '../generated/types/Abs
 import type { ProposalStatus as GeneratedProposalStatus } from '../generated/types/ProposalStatus';
 import type { SwapRequestStatus as GeneratedSwapRequestStatus } from '../generated/types/SwapRequestStatus';
 import type { AnnouncementSeverity as GeneratedAnnouncementSeverity } from '../generated/types/AnnouncementSeverity';
+import type { AnnouncementAudience as GeneratedAnnouncementAudience } from '../generated/types/AnnouncementAudience';
 import type { VehicleType as GeneratedVehicleType } from '../generated/types/VehicleType';
 import type { FuelType as GeneratedFuelType } from '../generated/types/FuelType';
 import type { BookingStatus as GeneratedBookingStatus } from '../generated/types/BookingStatus';
@@ -55,6 +56,7 @@ export type AbsenceType = GeneratedAbsenceType;
 export type ProposalStatus = GeneratedProposalStatus;
 export type SwapRequestStatus = GeneratedSwapRequestStatus;
 export type AnnouncementSeverity = GeneratedAnnouncementSeverity;
+export type AnnouncementAudience = GeneratedAnnouncementAudience;
 export type VehicleType = GeneratedVehicleType;
 export type FuelType = GeneratedFuelType;
 export type BookingStatus = GeneratedBookingStatus;
@@ -307,6 +309,25 @@ export const api = {
   updateMe: (data: Partial<User>) =>
     request<User>('/api/v1/users/me', { method: 'PUT', body: JSON.stringify(data) }),
 
+  /** Upload a profile picture (JPEG/PNG, max 2 MB); the server crops it to 256×256. */
+  uploadMyPicture: async (file: File): Promise<ApiResponse<User>> => {
+    const fd = new FormData();
+    fd.append('picture', file);
+    const token = _inMemoryToken;
+    const res = await fetch(`${BASE_URL}/api/v1/users/me/picture`, {
+      method: 'PUT', body: fd,
+      credentials: 'include',
+      headers: {
+        'X-Requested-With': 'XMLHttpRequest',
+        ...(token ? { Authorization: `Bearer ${token}` } : {}),
+      },
+    });
+    return res.json();
+  },
+
+  deleteMyPicture: () =>
+    request<User>('/api/v1/users/me/picture', { method: 'DELETE' }),
+
   /**
    * Per-user v5 customization settings (theme, sidebar variant, density,
    * font, feature toggles, notifications, privacy). Backed by the
@@ -445,6 +466,9 @@ export const api = {
   adminUsers: () => request<PaginatedResponse<User>>('/api/v1/admin/users'),
   adminUpdateUser: (id: string, data: UpdateUserPayload) => request<User>(`/api/v1/admin/users/${id}`, { method: 'PUT', body: JSON.stringify(data) }),
   adminDeleteUser: (id: string) => request<void>(`/api/v1/admin/users/${id}`, { method: 'DELETE' }),
+  adminDummyUsers: () => request<DummyUsers>('/api/v1/admin/dummy-users'),
+  adminPurgeDummyUsers: () =>
+    request<{ purged: number }>('/api/v1/admin/dummy-users', { method: 'DELETE' }),
   adminUpdateUserRole: (id: string, role: string) =>
     request<User>(`/api/v1/admin/users/${id}/role`, { method: 'PATCH', body: JSON.stringify({ role }) }),
   adminGrantCredits: (userId: string, amount: number, description?: string) =>
@@ -488,12 +512,16 @@ export const api = {
 
   // ── Admin Announcements ──
   adminListAnnouncements: () => request<Announcement[]>('/api/v1/admin/announcements'),
-  adminCreateAnnouncement: (data: { title: string; message: string; severity: string; active: boolean; expires_at?: string }) =>
+  adminCreateAnnouncement: (data: AnnouncementPayload) =>
     request<Announcement>('/api/v1/admin/announcements', { method: 'POST', body: JSON.stringify(data) }),
-  adminUpdateAnnouncement: (id: string, data: { title: string; message: string; severity: string; active: boolean; expires_at?: string }) =>
+  adminUpdateAnnouncement: (id: string, data: AnnouncementPayload) =>
     request<Announcement>(`/api/v1/admin/announcements/${id}`, { method: 'PUT', body: JSON.stringify(data) }),
   adminDeleteAnnouncement: (id: string) =>
     request<void>(`/api/v1/admin/announcements/${id}`, { method: 'DELETE' }),
+  /** Live announcements addressed to the current user that they have not dismissed */
+  getMyAnnouncements: () => request<Announcement[]>('/api/v1/announcements'),
+  dismissAnnouncement: (id: string) =>
+    request<void>(`/api/v1/announcements/${id}/dismiss`, { method: 'POST' }),
 
   // ── Notifications ──
   getNotifications: () => request<Notification[]>('/api/v1/notifications'),
@@ -895,7 +923,7 @@ export interface User {
   name: string;
   picture?: string;
   phone?: string;
-  role: 'user' | 'premium' | 'admin' | 'superadmin';
+  role: 'user' | 'premium' | 'reporter' | 'admin' | 'superadmin';
   preferences: Record<string, any>;
   is_active: boolean;
   department?: string;
@@ -995,6 +1023,14 @@ export interface ParkingSlot {
   is_accessible?: boolean;
 }
 
+/** Free slot suggested in `error.details.alternatives` when a slot is taken. */
+export interface SlotAlternative {
+  slot_id: string;
+  slot_number: number;
+  floor_id: string;
+  slot_type: string;
+}
+
 export interface Favorite {
   user_id: string;
   slot_id: string;
@@ -1158,6 +1194,12 @@ export interface Co2Summary {
   saved_kg: number;
 }
 
+/** Test accounts generated during setup that still exist. */
+export interface DummyUsers {
+  count: number;
+  users: Pick<User, 'id' | 'username' | 'email' | 'name'>[];
+}
+
 export interface AdminStats {
   total_users: number;
   total_lots: number;
@@ -1259,6 +1301,19 @@ export interface Announcement {
   active: boolean;
   expires_at?: string;
   created_at: string;
+  /** Missing on servers that predate audience targeting — treat as everyone */
+  audience?: AnnouncementAudience;
+}
+
+export interface AnnouncementPayload {
+  title: string;
+  message: string;
+  severity: string;
+  active: boolean;
+  expires_at?: string;
+  audience?: AnnouncementAudience;
+  /** Also email recipients when the announcement goes live */
+  send_email?: boolean;
 }
 
 // `LotStatus` is re-exported from the generated types at the top of this
@@ -1402,6 +1457,8 @@ export interface NotificationPreferences {
   whatsapp_booking_reminder: boolean;
   whatsapp_booking_cancelled: boolean;
   phone_number?: string;
+  /** Email when an account signs in from a new device or IP (server default: on) */
+  email_new_login?: boolean;
 }
 
 // ── Bulk Result ──
@@ -0,0 +1,64 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { render, screen, waitFor } from '@testing-library/react';
+import userEvent from '@testing-library/user-event';
+
+const { mockGetMyAnnouncements, mockDismissAnnouncement } = vi.hoisted(() => ({
+  mockGetMyAnnouncements: vi.fn(),
+  mockDismissAnnouncement: vi.fn(),
+}));
+
+vi.mock('../api/client', () => ({
+  api: {
+    getMyAnnouncements: mockGetMyAnnouncements,
+    dismissAnnouncement: mockDismissAnnouncement,
+  },
+}));
+
+vi.mock('react-i18next', () => ({
+  useTranslation: () => ({ t: (key: string) => key }),
+}));
+
+vi.mock('@phosphor-icons/react', () => {
+  const C = (props: any) => <span {...props} />;
+  return { InfoIcon: C, WarningIcon: C, WarningCircleIcon: C, CheckCircleIcon: C, XIcon: C };
+});
+
+import { AnnouncementBanner } from './AnnouncementBanner';
+
+const announcements = [
+  { id: 'a1', title: 'Garage closed', message: 'Level 1 closed on Saturday', severity: 'warning', active: true, created_at: '2026-04-01T00:00:00Z' },
+  { id: 'a2', title: 'New chargers', message: 'Four new EV chargers', severity: 'info', active: true, created_at: '2026-03-01T00:00:00Z' },
+];
+
+describe('AnnouncementBanner', () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+    mockDismissAnnouncement.mockResolvedValue({ success: true });
+  });
+
+  it('renders nothing without announcements', async () => {
+    mockGetMyAnnouncements.mockResolvedValue({ success: true, data: [] });
+    render(<AnnouncementBanner />);
+    await waitFor(() => expect(mockGetMyAnnouncements).toHaveBeenCalled());
+    expect(screen.queryByTestId('announcement-banner')).not.toBeInTheDocument();
+  });
+
+  it('shows announcements addressed to the user', async () => {
+    mockGetMyAnnouncements.mockResolvedValue({ success: true, data: announcements });
+    render(<AnnouncementBanner />);
+    await waitFor(() => expect(screen.getByText('Garage closed')).toBeInTheDocument());
+    expect(screen.getByText('Four new EV chargers')).toBeInTheDocument();
+  });
+
+  it('hides an announcement once dismissed', async () => {
+    mockGetMyAnnouncements.mockResolvedValue({ success: true, data: announcements });
+    render(<AnnouncementBanner />);
+    await waitFor(() => expect(screen.getByText('Garage closed')).toBeInTheDocument());
+
+    await userEvent.click(screen.getAllByLabelText('common.dismiss')[0]!);
+
+    expect(mockDismissAnnouncement).toHaveBeenCalledWith('a1');
+    expect(screen.queryByText('Garage closed')).not.toBeInTheDocument();
+    expect(screen.getByText('New chargers')).toBeInTheDocument();
+  });
+});
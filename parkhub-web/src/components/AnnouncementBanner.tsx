@@ -0,0 +1,55 @@
+import { useState, useEffect } from 'react';
+import { InfoIcon, WarningIcon, WarningCircleIcon, CheckCircleIcon, XIcon } from '@phosphor-icons/react';
+import { useTranslation } from 'react-i18next';
+import { api, type Announcement } from '../api/client';
+
+const severityStyles: Record<string, { className: string; icon: typeof InfoIcon }> = {
+  info:    { className: 'bg-blue-50 dark:bg-blue-900/30 text-blue-800 dark:text-blue-200 border-blue-200 dark:border-blue-800',       icon: InfoIcon },
+  warning: { className: 'bg-amber-50 dark:bg-amber-900/30 text-amber-800 dark:text-amber-200 border-amber-200 dark:border-amber-800', icon: WarningIcon },
+  error:   { className: 'bg-red-50 dark:bg-red-900/30 text-red-800 dark:text-red-200 border-red-200 dark:border-red-800',             icon: WarningCircleIcon },
+  success: { className: 'bg-green-50 dark:bg-green-900/30 text-green-800 dark:text-green-200 border-green-200 dark:border-green-800', icon: CheckCircleIcon },
+};
+
+/** Admin announcements addressed to the current user, shown until dismissed. */
+export function AnnouncementBanner() {
+  const { t } = useTranslation();
+  const [announcements, setAnnouncements] = useState<Announcement[]>([]);
+
+  useEffect(() => {
+    api.getMyAnnouncements()
+      .then(res => { if (res.success && res.data) setAnnouncements(res.data); })
+      .catch(() => {});
+  }, []);
+
+  async function dismiss(id: string) {
+    setAnnouncements(prev => prev.filter(a => a.id !== id));
+    await api.dismissAnnouncement(id).catch(() => {});
+  }
+
+  if (announcements.length === 0) return null;
+
+  return (
+    <div className="space-y-2 mb-4" data-testid="announcement-banner">
+      {announcements.map(a => {
+        const style = severityStyles[a.severity] || severityStyles.info!;
+        const Icon = style.icon;
+        return (
+          <div key={a.id} role="status" className={`flex items-start gap-3 px-4 py-3 rounded-xl border ${style.className}`}>
+            <Icon weight="fill" className="w-5 h-5 mt-0.5 shrink-0" aria-hidden="true" />
+            <div className="flex-1 min-w-0">
+              <p className="font-semibold text-sm">{a.title}</p>
+              <p className="text-sm whitespace-pre-line">{a.message}</p>
+            </div>
+            <button
+              onClick={() => dismiss(a.id)}
+              className="p-1 rounded-lg hover:bg-black/5 dark:hover:bg-white/10 transition-colors"
+              aria-label={t('common.dismiss')}
+            >
+              <XIcon weight="bold" className="w-4 h-4" aria-hidden="true" />
+            </button>
+          </div>
+        );
+      })}
+    </div>
+  );
+}
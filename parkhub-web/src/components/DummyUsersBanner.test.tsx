@@ -0,0 +1,56 @@
+import { describe, it, expect, vi, beforeEach } from 'vitest';
+import { render, screen, waitFor } from '@testing-library/react';
+import userEvent from '@testing-library/user-event';
+
+const { mockAdminDummyUsers, mockAdminPurgeDummyUsers } = vi.hoisted(() => ({
+  mockAdminDummyUsers: vi.fn(),
+  mockAdminPurgeDummyUsers: vi.fn(),
+}));
+
+vi.mock('../api/client', () => ({
+  api: {
+    adminDummyUsers: mockAdminDummyUsers,
+    adminPurgeDummyUsers: mockAdminPurgeDummyUsers,
+  },
+}));
+
+vi.mock('react-i18next', () => ({
+  useTranslation: () => ({ t: (key: string) => key }),
+}));
+
+vi.mock('react-hot-toast', () => ({
+  default: { success: vi.fn(), error: vi.fn() },
+}));
+
+vi.mock('@phosphor-icons/react', () => ({
+  WarningIcon: (props: any) => <span {...props} />,
+}));
+
+import { DummyUsersBanner } from './DummyUsersBanner';
+
+describe('DummyUsersBanner', () => {
+  beforeEach(() => {
+    vi.clearAllMocks();
+    vi.spyOn(window, 'confirm').mockReturnValue(true);
+  });
+
+  it('renders nothing once no dummy users remain', async () => {
+    mockAdminDummyUsers.mockResolvedValue({ success: true, data: { count: 0, users: [] } });
+    render(<DummyUsersBanner />);
+    await waitFor(() => expect(mockAdminDummyUsers).toHaveBeenCalled());
+    expect(screen.queryByTestId('dummy-users-banner')).not.toBeInTheDocument();
+  });
+
+  it('warns while dummy users remain and hides after purging them', async () => {
+    mockAdminDummyUsers.mockResolvedValue({ success: true, data: { count: 50, users: [] } });
+    mockAdminPurgeDummyUsers.mockResolvedValue({ success: true, data: { purged: 50 } });
+    render(<DummyUsersBanner />);
+    await waitFor(() => expect(screen.getByTestId('dummy-users-banner')).toBeInTheDocument());
+    expect(screen.getByText('admin.dummyUsersWarning')).toBeInTheDocument();
+
+    await userEvent.click(screen.getByText('admin.dummyUsersPurge'));
+
+    expect(mockAdminPurgeDummyUsers).toHaveBeenCalled();
+    await waitFor(() => expect(screen.queryByTestId('dummy-users-banner')).not.toBeInTheDocument());
+  });
+});
@@ -0,0 +1,51 @@
+import { useState, useEffect } from 'react';
+import { WarningIcon } from '@phosphor-icons/react';
+import { useTranslation } from 'react-i18next';
+import toast from 'react-hot-toast';
+import { api } from '../api/client';
+
+/** Warns admins while the dummy users generated during setup still exist. */
+export function DummyUsersBanner() {
+  const { t } = useTranslation();
+  const [count, setCount] = useState(0);
+  const [purging, setPurging] = useState(false);
+
+  useEffect(() => {
+    api.adminDummyUsers()
+      .then(res => { if (res.success && res.data) setCount(res.data.count); })
+      .catch(() => {});
+  }, []);
+
+  async function purge() {
+    if (!confirm(t('admin.dummyUsersPurgeConfirm', { count }))) return;
+    setPurging(true);
+    const res = await api.adminPurgeDummyUsers().catch(() => null);
+    setPurging(false);
+    if (res?.success && res.data) {
+      toast.success(t('admin.dummyUsersPurged', { count: res.data.purged }));
+      setCount(0);
+    } else {
+      toast.error(res?.error?.message || t('common.error'));
+    }
+  }
+
+  if (count === 0) return null;
+
+  return (
+    <div
+      role="alert"
+      data-testid="dummy-users-banner"
+      className="mb-4 flex items-start gap-3 rounded-xl border border-amber-200 bg-amber-50 px-4 py-3 text-amber-800 dark:border-amber-800 dark:bg-amber-900/30 dark:text-amber-200"
+    >
+      <WarningIcon weight="fill" className="mt-0.5 h-5 w-5 shrink-0" aria-hidden="true" />
+      <p className="flex-1 text-sm">{t('admin.dummyUsersWarning', { count })}</p>
+      <button
+        onClick={purge}
+        disabled={purging}
+        className="shrink-0 rounded-lg bg-amber-600 px-3 py-1.5 text-sm font-medium text-white hover:bg-amber-700 disabled:opacity-50"
+      >
+        {t('admin.dummyUsersPurge')}
+      </button>
+    </div>
+  );
+}
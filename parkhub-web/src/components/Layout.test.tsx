@@ -157,6 +157,10 @@ vi.mock('./ThemeSwitcher', () => ({
   ),
 }));
 
+vi.mock('./AnnouncementBanner', () => ({
+  AnnouncementBanner: () => null,
+}));
+
 vi.mock('./NotificationCenter', () => ({
   NotificationCenter: () => <div data-testid="notification-center">Notifications</div>,
 }));
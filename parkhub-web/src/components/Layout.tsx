@@ -13,6 +13,7 @@ import { useKeyboardShortcuts } from '../hooks/useKeyboardShortcuts';
 import { usePageTitle } from '../hooks/usePageTitle';
 import { CommandPalette } from './CommandPalette';
 import { NotificationCenter } from './NotificationCenter';
+import { AnnouncementBanner } from './AnnouncementBanner';
 import { ShortcutsHelp } from './ShortcutsHelp';
 // Assistant pulls the live-data reply builder + framer-motion dialog
 // styles; lazy-load so the Layout critical chunk stays lean. Suspense
@@ -36,6 +37,7 @@ const FloatingDock = lazy(() => import('./nav/FloatingDock').then(m => ({ defaul
 const TopTabs = lazy(() => import('./nav/TopTabs').then(m => ({ default: m.TopTabs })));
 const SidebarV3 = lazy(() => import('./nav/SidebarV3').then(m => ({ default: m.SidebarV3 })));
 import { APP_VERSION } from '../lib/appVersion';
+import { UserAvatar } from './UserAvatar';
 
 export type NavItem = {
   to: string;
@@ -494,9 +496,7 @@ export function Layout() {
           <div className="flex items-center gap-3 px-3 mb-3">
             <div className="relative">
               <div className="w-9 h-9 rounded-full bg-gradient-to-br from-primary-200 to-primary-100 dark:from-primary-800 dark:to-primary-900 flex items-center justify-center ring-2 ring-primary-500/20 dark:ring-primary-400/20">
-                <span className="text-sm font-bold text-primary-700 dark:text-primary-300">
-                  {(user?.name || user?.username || 'U').charAt(0).toUpperCase()}
-                </span>
+                <UserAvatar user={user} textClassName="text-sm font-bold text-primary-700 dark:text-primary-300" />
               </div>
               <span className="absolute -bottom-0.5 -right-0.5 w-3 h-3 rounded-full bg-emerald-500 border-2 border-white dark:border-surface-900" />
             </div>
@@ -607,6 +607,7 @@ export function Layout() {
             <NotificationCenter />
           </div>
           <div className="lg:hidden"><Breadcrumb /></div>
+          <AnnouncementBanner />
           <Outlet />
         </main>
         <footer className={`py-3 text-center text-xs text-surface-400 dark:text-surface-600 border-t border-surface-200/40 dark:border-surface-800/40 ${useDock ? 'pb-24' : ''}`}>
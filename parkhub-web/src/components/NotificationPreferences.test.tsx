@@ -40,6 +40,7 @@ vi.mock('@phosphor-icons/react', () => ({
   DeviceMobileIcon: (props: any) => <span data-testid="icon-DeviceMobile" {...props} />,
   ChatCircleDotsIcon: (props: any) => <span data-testid="icon-ChatCircleDots" {...props} />,
   PhoneIcon: (props: any) => <span data-testid="icon-Phone" {...props} />,
+  ShieldCheckIcon: (props: any) => <span data-testid="icon-ShieldCheck" {...props} />,
 }));
 
 import { NotificationPreferencesComponent } from './NotificationPreferences';
@@ -330,4 +331,28 @@ describe('NotificationPreferencesComponent', () => {
       }));
     });
   });
+
+  it('toggles the new sign-in alert', async () => {
+    const user = userEvent.setup();
+    mockGetNotificationPreferences.mockResolvedValue({ success: true, data: defaultPrefs });
+    mockUpdateNotificationPreferences.mockResolvedValue({ success: true, data: defaultPrefs });
+
+    render(<NotificationPreferencesComponent />);
+
+    await waitFor(() => {
+      expect(screen.queryByText('Loading preferences...')).not.toBeInTheDocument();
+    });
+
+    // Preferences saved before the alert existed have no field: shown as on.
+    const toggle = screen.getByText('Email me when I sign in from a new device').closest('label')!.querySelector('button')!;
+    expect(toggle).toHaveAttribute('aria-checked', 'true');
+    await user.click(toggle);
+    await user.click(screen.getByText('Save Preferences'));
+
+    await waitFor(() => {
+      expect(mockUpdateNotificationPreferences).toHaveBeenCalledWith(expect.objectContaining({
+        email_new_login: false,
+      }));
+    });
+  });
 });
@@ -1,5 +1,5 @@
 import { useState, useEffect } from 'react';
-import { BellIcon, SpinnerGapIcon, FloppyDiskIcon, EnvelopeSimpleIcon, DeviceMobileIcon, ChatCircleDotsIcon, PhoneIcon } from '@phosphor-icons/react';
+import { BellIcon, SpinnerGapIcon, FloppyDiskIcon, EnvelopeSimpleIcon, DeviceMobileIcon, ChatCircleDotsIcon, PhoneIcon, ShieldCheckIcon } from '@phosphor-icons/react';
 import { api, type NotificationPreferences } from '../api/client';
 import { useTranslation } from 'react-i18next';
 import toast from 'react-hot-toast';
@@ -36,6 +36,7 @@ export function NotificationPreferencesComponent() {
     whatsapp_booking_reminder: false,
     whatsapp_booking_cancelled: false,
     phone_number: undefined,
+    email_new_login: true,
   });
   const [loading, setLoading] = useState(true);
   const [saving, setSaving] = useState(false);
@@ -190,6 +191,21 @@ export function NotificationPreferencesComponent() {
         </div>
       </div>
 
+      {/* Security alerts (email) */}
+      <div className="space-y-1">
+        <div className="flex items-center gap-2 text-sm font-medium text-gray-500 mb-2">
+          <ShieldCheckIcon size={16} />
+          <span>{t('notifications.securityTitle', 'Security Alerts')}</span>
+        </div>
+        <div className="pl-6">
+          <Toggle
+            checked={prefs.email_new_login ?? true}
+            onChange={v => update('email_new_login', v)}
+            label={t('notifications.newLogin', 'Email me when I sign in from a new device')}
+          />
+        </div>
+      </div>
+
       {dirty && (
         <button
           onClick={handleSave}
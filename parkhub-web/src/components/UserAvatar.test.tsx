@@ -0,0 +1,29 @@
+import { fireEvent, render, screen } from '@testing-library/react';
+import { describe, expect, it } from 'vitest';
+import { UserAvatar, userInitial } from './UserAvatar';
+
+describe('UserAvatar', () => {
+  it('falls back to the first letter without a picture', () => {
+    render(<UserAvatar user={{ name: 'lena krug', username: 'lena' }} />);
+    expect(screen.getByText('L')).toBeInTheDocument();
+    expect(document.querySelector('img')).toBeNull();
+  });
+
+  it('renders the uploaded picture and reverts to the initial on error', () => {
+    render(
+      <UserAvatar
+        user={{ name: 'Max', username: 'max', picture: '/api/v1/users/42/picture' }}
+      />,
+    );
+    const img = document.querySelector('img');
+    expect(img).toHaveAttribute('src', '/api/v1/users/42/picture');
+
+    fireEvent.error(img!);
+    expect(screen.getByText('M')).toBeInTheDocument();
+  });
+
+  it('uses the username when the name is empty', () => {
+    expect(userInitial({ name: '', username: 'nina' })).toBe('N');
+    expect(userInitial(null)).toBe('U');
+  });
+});
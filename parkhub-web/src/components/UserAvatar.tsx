@@ -0,0 +1,54 @@
+/**
+ * User avatar — the uploaded profile picture when there is one, otherwise
+ * the first letter of the user's name.
+ *
+ * Renders only the content; callers keep their own sized, rounded wrapper
+ * (so existing ring/gradient/status-dot styling stays put). Falls back to
+ * the initial if the image fails to load, e.g. after the picture was
+ * removed in another tab.
+ */
+import { useEffect, useState } from 'react';
+import type { User } from '../api/client';
+
+const BASE_URL = import.meta.env?.VITE_API_URL || '';
+
+/**
+ * Resolve a `User.picture` value to an `<img src>`. Uploaded pictures are
+ * server-relative (`/api/v1/users/{id}/picture`) and authenticated via the
+ * session cookie; external URLs pass through unchanged.
+ */
+export function pictureSrc(picture?: string | null): string | undefined {
+  if (!picture) return undefined;
+  return picture.startsWith('/') ? `${BASE_URL}${picture}` : picture;
+}
+
+interface UserAvatarProps {
+  user: Pick<User, 'name' | 'username' | 'picture'> | null | undefined;
+  /** Classes for the initial-letter fallback */
+  textClassName?: string;
+  /** Override the fallback text (defaults to the first letter of the name) */
+  fallback?: string;
+}
+
+export function userInitial(user: UserAvatarProps['user']): string {
+  return (user?.name || user?.username || 'U').charAt(0).toUpperCase();
+}
+
+export function UserAvatar({ user, textClassName, fallback }: UserAvatarProps) {
+  const src = pictureSrc(user?.picture);
+  const [failed, setFailed] = useState(false);
+
+  useEffect(() => setFailed(false), [src]);
+
+  if (src && !failed) {
+    return (
+      <img
+        src={src}
+        alt=""
+        className="w-full h-full rounded-[inherit] object-cover"
+        onError={() => setFailed(true)}
+      />
+    );
+  }
+  return <span className={textClassName}>{fallback ?? userInitial(user)}</span>;
+}
@@ -25,6 +25,7 @@ import { NotificationBadge } from '../ui/NotificationBadge';
 import { preloadRoute } from '../../lib/routePreload';
 import { NAV_SECTIONS, type NavItem } from '../Layout';
 import { isActivePath } from './navActive';
+import { UserAvatar } from '../UserAvatar';
 
 interface RailSidebarProps {
   unreadCount: number;
@@ -116,9 +117,7 @@ export function RailSidebar({ unreadCount, onLogout, isAdmin }: RailSidebarProps
           title={user?.name || user?.username}
         >
           <div className="w-9 h-9 rounded-full bg-gradient-to-br from-primary-200 to-primary-100 dark:from-primary-800 dark:to-primary-900 flex items-center justify-center ring-2 ring-primary-500/20 dark:ring-primary-400/20">
-            <span className="text-sm font-bold text-primary-700 dark:text-primary-300">
-              {(user?.name || user?.username || 'U').charAt(0).toUpperCase()}
-            </span>
+            <UserAvatar user={user} textClassName="text-sm font-bold text-primary-700 dark:text-primary-300" />
           </div>
           <span className="absolute -bottom-0.5 -right-0.5 w-2.5 h-2.5 rounded-full bg-emerald-500 border-2 border-white dark:border-surface-900" />
         </div>
@@ -47,6 +47,7 @@ import {
 import { api, type Booking, type ParkingLot, type ParkingSlot } from '../../api/client';
 import { useAuth } from '../../context/AuthContext';
 import { isActivePath } from './navActive';
+import { UserAvatar } from '../UserAvatar';
 
 type IconComponent = React.ComponentType<{ size?: number; weight?: 'regular' | 'bold' | 'fill' | 'duotone' | 'thin' | 'light' }>;
 
@@ -278,7 +279,6 @@ export function SidebarV3() {
     ? activeLot.available_slots / activeLot.total_slots
     : 0;
 
-  const initial = (user?.name || user?.username || 'U').charAt(0).toUpperCase();
   const isAdmin = !!(user?.role && ['admin', 'superadmin'].includes(user.role));
 
   const openCommandPalette = useCallback(() => {
@@ -389,13 +389,14 @@ export function SidebarV3() {
               background: 'linear-gradient(135deg, var(--color-primary-400, #818cf8), var(--color-primary-600, #4f46e5))',
               fontSize: 11,
               letterSpacing: '0',
+              overflow: 'hidden',
               border: '1.5px solid oklch(0.17 0.02 260)',
               boxShadow: userMenuOpen
                 ? '0 0 0 1.5px var(--color-primary-500, #6366f1)'
                 : '0 0 0 1.5px transparent',
             }}
           >
-            {initial}
+            <UserAvatar user={user} />
           </button>
         </div>
       </div>
@@ -26,6 +26,7 @@ import { NotificationBadge } from '../ui/NotificationBadge';
 import { preloadRoute } from '../../lib/routePreload';
 import { NAV_SECTIONS, type NavItem } from '../Layout';
 import { isActivePath } from './navActive';
+import { UserAvatar } from '../UserAvatar';
 
 interface TopTabsProps {
   unreadCount: number;
@@ -216,8 +217,6 @@ function UserMenu({ user, onLogout }: { user: { name?: string; username?: string
     return () => document.removeEventListener('mousedown', onDocClick);
   }, []);
 
-  const initial = (user?.name || user?.username || 'U').charAt(0).toUpperCase();
-
   return (
     <div ref={ref} className="relative">
       <button
@@ -225,7 +224,7 @@ function UserMenu({ user, onLogout }: { user: { name?: string; username?: string
         aria-expanded={open}
         className="relative flex items-center justify-center w-9 h-9 rounded-full bg-gradient-to-br from-primary-200 to-primary-100 dark:from-primary-800 dark:to-primary-900 ring-2 ring-primary-500/20 dark:ring-primary-400/20 hover:scale-105 transition-transform"
       >
-        <span className="text-sm font-bold text-primary-700 dark:text-primary-300">{initial}</span>
+        <UserAvatar user={user} textClassName="text-sm font-bold text-primary-700 dark:text-primary-300" />
         <span className="absolute -bottom-0.5 -right-0.5 w-2.5 h-2.5 rounded-full bg-emerald-500 border-2 border-white dark:border-surface-900" />
       </button>
 
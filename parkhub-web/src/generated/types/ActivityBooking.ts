@@ -0,0 +1,17 @@
+// Auto-generated by ts-rs. DO NOT EDIT.
+// Regenerate with:
+//   cargo test --features gen-types -p parkhub-server --test ts_export -- --nocapture
+// Source of truth: parkhub-common/src/models.rs + protocol.rs
+// (plus parkhub-server/src/api/{calendar,team}.rs for server-side DTOs).
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BookingStatus } from "./BookingStatus";
+
+/**
+ * Booking details shown with an activity feed entry
+ */
+export type ActivityBooking = { id: string, lot_name: string, 
+/**
+ * 0 while a late-binding booking waits for its slot
+ */
+slot_number: number, start_time: string, end_time: string, status: BookingStatus, total: number, currency: string, };
@@ -0,0 +1,34 @@
+// Auto-generated by ts-rs. DO NOT EDIT.
+// Regenerate with:
+//   cargo test --features gen-types -p parkhub-server --test ts_export -- --nocapture
+// Source of truth: parkhub-common/src/models.rs + protocol.rs
+// (plus parkhub-server/src/api/{calendar,team}.rs for server-side DTOs).
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ActivityBooking } from "./ActivityBooking";
+import type { ActivityKind } from "./ActivityKind";
+
+/**
+ * One event in a user's activity feed (`GET /api/v1/users/me/activity`)
+ */
+export type ActivityEntry = { 
+/**
+ * Unique within the feed: the kind and the id of the record behind it
+ */
+id: string, kind: ActivityKind, at: string, 
+/**
+ * The booking the event concerns, as it is now
+ */
+booking: ActivityBooking | null, 
+/**
+ * Credits a payment added (positive) or spent (negative)
+ */
+credits: number | null, 
+/**
+ * A notification's title, or a payment's description
+ */
+title: string | null, 
+/**
+ * A notification's text
+ */
+message: string | null, };
@@ -5,9 +5,14 @@
 // (plus parkhub-server/src/api/{calendar,team}.rs for server-side DTOs).
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AnnouncementAudience } from "./AnnouncementAudience";
 import type { AnnouncementSeverity } from "./AnnouncementSeverity";
 
 /**
  * System announcement
  */
-export type Announcement = { id: string, title: string, message: string, severity: AnnouncementSeverity, active: boolean, created_by: string | null, expires_at: string | null, created_at: string, };
+export type Announcement = { id: string, title: string, message: string, severity: AnnouncementSeverity, active: boolean, created_by: string | null, expires_at: string | null, created_at: string, 
+/**
+ * Who sees the announcement; older records default to everyone
+ */
+audience: AnnouncementAudience, };
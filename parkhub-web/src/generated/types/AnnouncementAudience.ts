@@ -0,0 +1,12 @@
+// Auto-generated by ts-rs. DO NOT EDIT.
+// Regenerate with:
+//   cargo test --features gen-types -p parkhub-server --test ts_export -- --nocapture
+// Source of truth: parkhub-common/src/models.rs + protocol.rs
+// (plus parkhub-server/src/api/{calendar,team}.rs for server-side DTOs).
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Audience of an announcement
+ */
+export type AnnouncementAudience = { "type": "all" } | { "type": "lot", lot_id: string, };
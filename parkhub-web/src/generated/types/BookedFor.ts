@@ -0,0 +1,16 @@
+// Auto-generated by ts-rs. DO NOT EDIT.
+// Regenerate with:
+//   cargo test --features gen-types -p parkhub-server --test ts_export -- --nocapture
+// Source of truth: parkhub-common/src/models.rs + protocol.rs
+// (plus parkhub-server/src/api/{calendar,team}.rs for server-side DTOs).
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * A visitor without an account a booking was made for
+ */
+export type BookedFor = { name: string, license_plate: string, 
+/**
+ * Where the visitor's pass was sent, if anywhere
+ */
+email: string | null, };
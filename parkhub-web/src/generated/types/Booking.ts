@@ -5,6 +5,7 @@
 // (plus parkhub-server/src/api/{calendar,team}.rs for server-side DTOs).
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { BookedFor } from "./BookedFor";
 import type { BookingPricing } from "./BookingPricing";
 import type { BookingStatus } from "./BookingStatus";
 import type { Vehicle } from "./Vehicle";
@@ -16,4 +17,14 @@ export type Booking = { id: string, user_id: string, lot_id: string, slot_id: st
 /**
  * Multi-tenant isolation: tenant ID (None = global scope)
  */
-tenant_id: string | null, };
+tenant_id: string | null, 
+/**
+ * Cost attribution tags (e.g. `cost_center` → `CC-4711`), keyed by
+ * an admin-defined booking tag set
+ */
+tags: { [key in string]?: string }, 
+/**
+ * Visitor the booking was made for; `None` when `user_id` parks
+ * themselves. `user_id` stays the account that booked and manages it.
+ */
+booked_for: BookedFor | null, };
@@ -10,4 +10,8 @@ import type { PaymentStatus } from "./PaymentStatus";
 /**
  * Pricing details for a booking
  */
-export type BookingPricing = { base_price: number, discount: number, tax: number, total: number, currency: string, payment_status: PaymentStatus, payment_method: string | null, };
+export type BookingPricing = { base_price: number, discount: number, tax: number, total: number, currency: string, payment_status: PaymentStatus, payment_method: string | null, 
+/**
+ * Fee kept under the lot's cancellation policy (set on cancellation)
+ */
+cancellation_fee: number | null, };
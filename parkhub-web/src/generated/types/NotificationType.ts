@@ -9,4 +9,4 @@
 /**
  * Notification type
  */
-export type NotificationType = "booking_confirmed" | "booking_reminder" | "booking_expiring" | "booking_cancelled" | "payment_received" | "payment_failed" | "promotion_available" | "system_message" | "waitlist_offer";
+export type NotificationType = "booking_confirmed" | "booking_reminder" | "booking_expiring" | "booking_cancelled" | "payment_received" | "payment_failed" | "promotion_available" | "system_message" | "waitlist_offer" | "favorite_slot_available";
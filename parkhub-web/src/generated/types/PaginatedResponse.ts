@@ -9,4 +9,8 @@
 /**
  * Paginated response
  */
-export type PaginatedResponse<T> = { items: Array<T>, page: number, per_page: number, total: number, total_pages: number, };
+export type PaginatedResponse<T> = { items: Array<T>, page: number, per_page: number, total: number, total_pages: number, 
+/**
+ * Cursor of the following page; `None` on the last page.
+ */
+next_cursor: string | null, };
@@ -9,4 +9,8 @@
 /**
  * Response metadata for pagination
  */
-export type ResponseMeta = { page: number | null, per_page: number | null, total: number | null, total_pages: number | null, };
+export type ResponseMeta = { page: number | null, per_page: number | null, total: number | null, total_pages: number | null, 
+/**
+ * Cursor of the following page; `None` on the last page.
+ */
+next_cursor: string | null, };
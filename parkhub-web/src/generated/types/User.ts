@@ -35,4 +35,10 @@ department: string | null,
  * stored opaquely as JSON. `None` means "user has not customized";
  * clients fall back to factory defaults defined in the frontend.
  */
-settings: Record<string, unknown> | null, };
+settings: Record<string, unknown> | null, 
+/**
+ * Set on accounts whose password was seeded, imported or reset by an
+ * admin. Until the user picks a new one, only the password-change
+ * endpoint accepts their session.
+ */
+must_change_password: boolean, };
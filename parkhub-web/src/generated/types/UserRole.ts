@@ -9,4 +9,4 @@
 /**
  * User role for access control
  */
-export type UserRole = "user" | "premium" | "admin" | "superadmin";
+export type UserRole = "user" | "premium" | "reporter" | "admin" | "superadmin";
@@ -520,6 +520,13 @@ export default {
       announcementMessage: 'Nachricht',
       severity: 'Schweregrad',
       expiresAt: 'Lauft ab am (optional)',
+      audience: 'Zielgruppe',
+      audienceAll: 'Alle Benutzer',
+      sendEmail: 'Zusätzlich per E-Mail senden',
+      dummyUsersWarning: '{{count}} beim Setup erzeugte Testbenutzer existieren noch und teilen sich ein Passwort. Entfernen Sie sie vor dem Produktivbetrieb.',
+      dummyUsersPurge: 'Testbenutzer entfernen',
+      dummyUsersPurgeConfirm: 'Alle {{count}} Testbenutzer löschen? Dies kann nicht rückgängig gemacht werden.',
+      dummyUsersPurged: '{{count}} Testbenutzer entfernt.',
       announcementCreated: 'Ankuendigung erstellt.',
       announcementUpdated: 'Ankuendigung aktualisiert.',
       announcementSaveFailed: 'Speichern fehlgeschlagen.',
@@ -759,6 +766,8 @@ export default {
       whatsappTitle: 'WhatsApp-Benachrichtigungen',
       phoneNumber: 'Telefonnummer',
       phoneHint: 'Erforderlich für SMS- und WhatsApp-Benachrichtigungen',
+      securityTitle: 'Sicherheitshinweise',
+      newLogin: 'E-Mail bei Anmeldung von einem neuen Gerät',
       bookingConfirm: 'Buchungsbestatigungen',
       bookingReminder: 'Buchungserinnerungen',
       bookingCancelled: 'Buchungsstornierungen',
@@ -932,6 +941,11 @@ export default {
       currentPasswordRequired: 'Aktuelles Passwort eingeben',
       minChars: 'Mind. 8 Zeichen',
       passwordsNoMatch: 'Passwörter stimmen nicht überein',
+      uploadPicture: 'Profilbild hochladen',
+      removePicture: 'Entfernen',
+      pictureUpdated: 'Profilbild aktualisiert',
+      pictureRemoved: 'Profilbild entfernt',
+      pictureTooLarge: 'Bild ist größer als 2 MB',
       dangerZone: 'Gefahrenzone',
       deleteAccount: 'Konto löschen',
       deleteWarning: 'Diese Aktion kann nicht ruckgangig gemacht werden.',
@@ -1075,6 +1089,7 @@ export default {
       confirm: 'Buchung bestatigen',
       confirming: 'Wird gebucht...',
       success: 'Buchung bestatigt!',
+      slotTakenAlternative: 'Stellplatz {{taken}} wurde gerade vergeben – stattdessen ist Stellplatz {{alternative}} ausgewählt. Bestätigen Sie, um ihn zu buchen.',
       progress: 'Buchungsfortschritt',
       summary: 'Buchungszusammenfassung',
     },
@@ -488,6 +488,13 @@ export default {
       announcementMessage: 'Message',
       severity: 'Severity',
       expiresAt: 'Expires at (optional)',
+      audience: 'Audience',
+      audienceAll: 'Everyone',
+      sendEmail: 'Also send by email',
+      dummyUsersWarning: '{{count}} dummy users generated during setup still exist and share one password. Remove them before going live.',
+      dummyUsersPurge: 'Remove dummy users',
+      dummyUsersPurgeConfirm: 'Delete all {{count}} dummy users? This cannot be undone.',
+      dummyUsersPurged: '{{count}} dummy users removed.',
       announcementCreated: 'Announcement created.',
       announcementUpdated: 'Announcement updated.',
       announcementSaveFailed: 'Failed to save.',
@@ -725,6 +732,8 @@ export default {
       whatsappTitle: 'WhatsApp Notifications',
       phoneNumber: 'Phone Number',
       phoneHint: 'Required for SMS and WhatsApp notifications',
+      securityTitle: 'Security Alerts',
+      newLogin: 'Email me when I sign in from a new device',
       bookingConfirm: 'Booking confirmations',
       bookingReminder: 'Booking reminders',
       bookingCancelled: 'Booking cancellations',
@@ -887,6 +896,11 @@ export default {
       currentPasswordRequired: 'Enter current password',
       minChars: 'Min. 8 characters',
       passwordsNoMatch: 'Passwords do not match',
+      uploadPicture: 'Upload profile picture',
+      removePicture: 'Remove',
+      pictureUpdated: 'Profile picture updated',
+      pictureRemoved: 'Profile picture removed',
+      pictureTooLarge: 'Picture is larger than 2 MB',
       dangerZone: 'Danger Zone',
       deleteAccount: 'Delete Account',
       deleteWarning: 'This action cannot be undone. All your data will be permanently deleted.',
@@ -1027,6 +1041,7 @@ export default {
       confirm: 'Confirm Booking',
       confirming: 'Booking...',
       success: 'Booking confirmed!',
+      slotTakenAlternative: 'Slot {{taken}} was just taken — slot {{alternative}} is selected instead. Confirm to book it.',
       progress: 'Booking progress',
       summary: 'Booking summary',
     },
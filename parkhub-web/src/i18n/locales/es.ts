@@ -477,6 +477,13 @@ export default {
       announcementMessage: 'Mensaje',
       severity: 'Severidad',
       expiresAt: 'Expira el (opcional)',
+      audience: 'Destinatarios',
+      audienceAll: 'Todos',
+      sendEmail: 'Enviar también por correo',
+      dummyUsersWarning: 'Aún existen {{count}} usuarios de prueba creados durante la instalación que comparten una contraseña. Elimínelos antes de pasar a producción.',
+      dummyUsersPurge: 'Eliminar usuarios de prueba',
+      dummyUsersPurgeConfirm: '¿Eliminar los {{count}} usuarios de prueba? No se puede deshacer.',
+      dummyUsersPurged: '{{count}} usuarios de prueba eliminados.',
       announcementCreated: 'Anuncio creado.',
       announcementUpdated: 'Anuncio actualizado.',
       announcementSaveFailed: 'Error al guardar.',
@@ -714,6 +721,8 @@ export default {
       whatsappTitle: 'Notificaciones WhatsApp',
       phoneNumber: 'Número de teléfono',
       phoneHint: 'Requerido para notificaciones SMS y WhatsApp',
+      securityTitle: 'Alertas de seguridad',
+      newLogin: 'Avisarme por correo al iniciar sesión desde un dispositivo nuevo',
       bookingConfirm: 'Confirmaciones de reserva',
       bookingReminder: 'Recordatorios de reserva',
       bookingCancelled: 'Cancelaciones de reserva',
@@ -929,6 +938,7 @@ export default {
       confirm: 'Confirmar reserva',
       confirming: 'Reservando...',
       success: '¡Reserva confirmada!',
+      slotTakenAlternative: 'La plaza {{taken}} acaba de ocuparse; en su lugar se ha seleccionado la plaza {{alternative}}. Confirma para reservarla.',
       progress: 'Progreso de la reserva',
       summary: 'Resumen de la reserva',
     },
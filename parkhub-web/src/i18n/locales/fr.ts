@@ -477,6 +477,13 @@ export default {
       announcementMessage: 'Message',
       severity: 'Sévérité',
       expiresAt: 'Expire le (optionnel)',
+      audience: 'Destinataires',
+      audienceAll: 'Tout le monde',
+      sendEmail: 'Envoyer aussi par e-mail',
+      dummyUsersWarning: '{{count}} utilisateurs de test créés lors de l\'installation existent encore et partagent un mot de passe. Supprimez-les avant la mise en production.',
+      dummyUsersPurge: 'Supprimer les utilisateurs de test',
+      dummyUsersPurgeConfirm: 'Supprimer les {{count}} utilisateurs de test ? Cette action est irréversible.',
+      dummyUsersPurged: '{{count}} utilisateurs de test supprimés.',
       announcementCreated: 'Annonce créée.',
       announcementUpdated: 'Annonce mise à jour.',
       announcementSaveFailed: 'Échec de l\'enregistrement.',
@@ -714,6 +721,8 @@ export default {
       whatsappTitle: 'Notifications WhatsApp',
       phoneNumber: 'Numéro de téléphone',
       phoneHint: 'Requis pour les notifications SMS et WhatsApp',
+      securityTitle: 'Alertes de sécurité',
+      newLogin: 'M\'avertir par e-mail lors d\'une connexion depuis un nouvel appareil',
       bookingConfirm: 'Confirmations de réservation',
       bookingReminder: 'Rappels de réservation',
       bookingCancelled: 'Annulations de réservation',
@@ -929,6 +938,7 @@ export default {
       confirm: 'Confirmer la réservation',
       confirming: 'Réservation...',
       success: 'Réservation confirmée !',
+      slotTakenAlternative: 'La place {{taken}} vient d\'être prise — la place {{alternative}} est sélectionnée à la place. Confirmez pour la réserver.',
       progress: 'Progression de la réservation',
       summary: 'Résumé de la réservation',
     },
@@ -477,6 +477,13 @@ export default {
       announcementMessage: 'Messaggio',
       severity: 'Gravità',
       expiresAt: 'Scade il (opzionale)',
+      audience: 'Destinatari',
+      audienceAll: 'Tutti',
+      sendEmail: 'Invia anche via email',
+      dummyUsersWarning: 'Esistono ancora {{count}} utenti di prova creati durante l\'installazione che condividono una password. Rimuovili prima di andare in produzione.',
+      dummyUsersPurge: 'Rimuovi utenti di prova',
+      dummyUsersPurgeConfirm: 'Eliminare tutti i {{count}} utenti di prova? L\'operazione è irreversibile.',
+      dummyUsersPurged: '{{count}} utenti di prova rimossi.',
       announcementCreated: 'Annuncio creato.',
       announcementUpdated: 'Annuncio aggiornato.',
       announcementSaveFailed: 'Salvataggio fallito.',
@@ -714,6 +721,8 @@ export default {
       whatsappTitle: 'Notifiche WhatsApp',
       phoneNumber: 'Numero di telefono',
       phoneHint: 'Richiesto per notifiche SMS e WhatsApp',
+      securityTitle: 'Avvisi di sicurezza',
+      newLogin: 'Avvisami via email quando accedo da un nuovo dispositivo',
       bookingConfirm: 'Conferme di prenotazione',
       bookingReminder: 'Promemoria prenotazione',
       bookingCancelled: 'Annullamenti prenotazione',
@@ -929,6 +938,7 @@ export default {
       confirm: 'Conferma prenotazione',
       confirming: 'Prenotazione...',
       success: 'Prenotazione confermata!',
+      slotTakenAlternative: 'Il posto {{taken}} è appena stato preso: è stato selezionato il posto {{alternative}}. Conferma per prenotarlo.',
       progress: 'Progresso prenotazione',
       summary: 'Riepilogo prenotazione',
     },
@@ -23,7 +23,7 @@ export default {
       systemSettings: 'システム設定', general: '一般', companyName: '会社名', useCaseLabel: '用途', selfRegistration: 'セルフ登録', selfRegistrationDesc: 'ユーザーの自己登録を許可', bookingRules: '予約ルール', maxBookingsPerDay: '1日あたりの最大予約数', maxBookingsUnlimited: '0 = 無制限', allowGuestBookings: 'ゲスト予約', allowGuestBookingsDesc: 'ゲストはアカウントなしで管理者が予約可能', requireVehicle: '車両必須', requireVehicleDesc: '予約に車両/ナンバープレートが必要', saveSettings: '設定を保存', settingsSaved: '設定が保存されました', settingsSaveFailed: '設定の保存に失敗', settingsLoadFailed: '設定の読込に失敗',
       autoRelease: '自動解放', autoReleaseMinutes: '自動解放まで（分）', autoReleaseDesc: 'チェックインのない予約はこの時間後に解放されます。0 = 無効。', waitlist: 'ウェイトリスト', enableWaitlist: 'ウェイトリストを有効化', enableWaitlistDesc: '駐車場が満車の場合、ユーザーはウェイトリストに参加できます', creditsSystem: 'クレジットシステム', enableCredits: 'クレジットを有効化', enableCreditsDesc: 'ユーザーは予約にクレジットが必要', creditsPerBooking: '予約あたりのクレジット',
       licensePlate: 'ナンバープレート', licensePlateMode: 'ナンバープレートモード', licensePlateLabelRequired: '必須', licensePlateLabelOptional: '任意', licensePlateLabelDisabled: '無効', licensePlateModeRequired: '全予約でナンバープレートが必要', licensePlateModeOptional: 'ナンバープレートは任意で入力可能', licensePlateModeDisabled: 'ナンバープレート欄は非表示',
-      newAnnouncement: '新規お知らせ', editAnnouncement: 'お知らせを編集', announcementTitle: 'タイトル', announcementMessage: 'メッセージ', severity: '重要度', expiresAt: '有効期限（任意）', announcementCreated: 'お知らせが作成されました。', announcementUpdated: 'お知らせが更新されました。', announcementSaveFailed: '保存に失敗。', announcementDeleted: 'お知らせが削除されました。', announcementDeleteFailed: '削除に失敗。', announcementDeleteConfirm: 'このお知らせを削除しますか？', announcementTitleRequired: 'タイトルとメッセージは必須です。', noAnnouncements: 'お知らせはまだありません。', announcementCreatedAt: '作成日:', announcementExpiresAt: '有効期限:', severityInfo: '情報', severityWarning: '警告', severityError: 'エラー', severitySuccess: '成功', expired: '期限切れ',
+      newAnnouncement: '新規お知らせ', editAnnouncement: 'お知らせを編集', announcementTitle: 'タイトル', announcementMessage: 'メッセージ', severity: '重要度', expiresAt: '有効期限（任意）', audience: '対象', audienceAll: '全員', sendEmail: 'メールでも送信', announcementCreated: 'お知らせが作成されました。', announcementUpdated: 'お知らせが更新されました。', announcementSaveFailed: '保存に失敗。', announcementDeleted: 'お知らせが削除されました。', announcementDeleteFailed: '削除に失敗。', announcementDeleteConfirm: 'このお知らせを削除しますか？', announcementTitleRequired: 'タイトルとメッセージは必須です。', noAnnouncements: 'お知らせはまだありません。', announcementCreatedAt: '作成日:', announcementExpiresAt: '有効期限:', severityInfo: '情報', severityWarning: '警告', severityError: 'エラー', severitySuccess: '成功', expired: '期限切れ',
       totalUsers: 'ユーザー合計', totalLots: '駐車場合計', totalBookings: '予約合計', activeBookings: 'アクティブな予約', utilizationRate: '稼働率', avgBookingsPerUser: 'ユーザーあたり平均予約数', activeBookingRate: 'アクティブ予約率', bookingsThisWeek: '今週の予約', lotOccupancy: '駐車場稼働率', exportCsv: 'CSV出力', exportBookings: '予約を出力', exportUsers: 'ユーザーを出力', exportOccupancy: '稼働率を出力',
       useCaseCompany: '企業', useCaseResidential: '住宅', useCaseShared: '共有駐車場', useCaseRental: '賃貸', useCasePersonal: '個人', translations: '翻訳', rateLimits: 'レート制限', tenants: 'テナント', auditLog: '監査ログ', dataManagement: 'データ', fleet: '車両管理', plugins: 'プラグイン', graphql: 'GraphQL',
       bulkAction: '一括操作', bulkActionConfirm: '{{count}}人のユーザーを{{action}}してもよろしいですか？', bulkDeleted: '{{succeeded}}/{{total}}人のユーザーを削除', bulkUpdated: '{{succeeded}}/{{total}}人のユーザーを更新', bulkDeleteFailed: '一括削除に失敗', bulkUpdateFailed: '一括更新に失敗', selectedCount: '{{count}}件選択中', selectAction: '操作を選択...', bulkActivate: '有効化', bulkDeactivate: '無効化', bulkChangeRole: '役割変更', bulkDelete: '削除', bulkApply: '適用', bulkClear: 'クリア',
@@ -34,7 +34,7 @@ export default {
     useCase: { title: 'ParkHubをどのように使いますか？', subtitle: '用途に合わせた機能を取得するために選択してください', continue: '続ける', skip: '今はスキップ', applying: '適用中...', business: { name: 'ビジネス', desc: 'オフィスや企業向けの駐車場管理', feature1: '従業員への駐車場割当', feature2: 'クレジット型予約システム', feature3: 'チーム不在管理' }, residential: { name: '住宅', desc: 'マンションや住宅コミュニティ', feature1: '住民のセルフ登録', feature2: '来客用駐車場管理', feature3: '共有スペースのスケジューリング' }, personal: { name: '個人', desc: '個人ガレージと家族共有駐車場', feature1: 'シンプルなスペース予約', feature2: '家族メンバーのアクセス', feature3: 'クイックアクションダッシュボード' } },
     features: { title: '機能管理', subtitle: 'インスタンスの機能を有効/無効化', onboardingTitle: '機能をカスタマイズ', onboardingSubtitle: 'ニーズに応じて有効/無効化', enabled: '有効', disabled: '無効', saved: '機能が保存されました', saveChanges: '変更を保存', enableAll: 'すべて有効化', disableAll: 'すべて無効化', resetToPreset: 'プリセットに戻す', categories: { core: 'コア', collaboration: 'コラボレーション', billing: '課金', admin: '管理', experience: '体験' }, modules: { credits: { name: 'クレジット', desc: '月間クォータ付きクレジット予約システム', help: 'ユーザーは予約にクレジットが必要です。管理者がクレジット付与とクォータ設定を行えます。' }, absences: { name: '不在', desc: 'リモートワーク、休暇、病欠の追跡', help: 'チームの不在とリモートワークパターンを管理。' }, vehicles: { name: '車両', desc: 'ナンバープレート付き車両の登録・管理', help: 'ユーザーが車両を登録可能。パスでのナンバー表示対応。' }, analytics: { name: '分析', desc: '利用統計、ヒートマップ、レポート', help: '予約分析、稼働データ、エクスポート可能なレポートの管理ダッシュボード。' }, team_view: { name: 'チームビュー', desc: 'オフィスまたはリモートの誰かを確認', help: '不在と予約データに基づくチーム在席状況を表示。' }, booking_types: { name: '予約タイプ', desc: '単発、複数日、定期予約のサポート', help: 'シンプルな予約以外の予約タイプを有効化。' }, invoices: { name: '請求書', desc: '予約のPDF請求書生成', help: '完了した予約の請求書を自動生成・ダウンロード。' }, self_registration: { name: 'セルフ登録', desc: '管理者の承認なしでアカウント作成を許可', help: '新規ユーザーが自己登録可能。招待制にする場合は無効化。' }, generative_bg: { name: '公開ページ背景', desc: '公開ページの落ち着いた運用背景', help: 'ログイン、ウェルカム、オンボーディング画面を装飾効果なしで整然と保ちます。' }, micro_animations: { name: 'マイクロアニメーション', desc: 'UIでの滑らかな遷移とホバーエフェクト', help: '物理アニメーション、段階的リスト、ホバーフィードバックを有効化。' }, fab_quick_actions: { name: 'クイックアクション', desc: 'よく使うタスクへの素早いアクセス用フローティングボタン', help: 'コマンドパレット（Ctrl+K）とクイックナビゲーション用フローティングボタンを表示。' }, rich_empty_states: { name: 'リッチ空状態', desc: '役立つ提案付きイラスト空状態', help: 'リストが空の場合にイラストとアクションボタンを表示。' }, onboarding_hints: { name: 'オンボーディングヒント', desc: '新規ユーザー向けコンテキストヒント', help: '初回ユーザー向けのガイドツールチップを表示。' }, themes: { name: 'デザインテーマ', desc: 'ビジュアルデザインテーマを切り替え', help: '10テーマから選択: Classic, Glass, Bento, Brutalist, Neon, Warm, Liquid, Mono, Ocean, Forest。' } }, compliance: { title: 'ビルトインコンプライアンス', gdpr: 'GDPR Art.15 エクスポート', audit: '監査ログ', encryption: 'AES-256暗号化' } },
     demo: { badge: 'デモ', timer: 'リセットまで', viewers: '閲覧者', voteReset: 'リセットに投票', votesNeeded: '{{current}}/{{needed}}票', resetting: 'デモデータをリセット中...', lastReset: '最終リセット', nextReset: '次回リセット' },
-    notifications: { title: '通知', allRead: 'すべて確認済み', markAllRead: 'すべて既読にする', allMarkedRead: 'すべて既読にしました', empty: '通知なし', unread: '未読', unreadCount: '{{count}}件未読', pushTitle: 'プッシュ通知', pushEnabled: 'プッシュ通知を有効化', smsTitle: 'SMS通知', whatsappTitle: 'WhatsApp通知', phoneNumber: '電話番号', phoneHint: 'SMSとWhatsApp通知に必要', bookingConfirm: '予約確認', bookingReminder: '予約リマインダー', bookingCancelled: '予約キャンセル', stub: 'プロバイダーが必要', prefsSaved: '通知設定が保存されました', prefsSaveFailed: '通知設定の保存に失敗' },
+    notifications: { title: '通知', allRead: 'すべて確認済み', markAllRead: 'すべて既読にする', allMarkedRead: 'すべて既読にしました', empty: '通知なし', unread: '未読', unreadCount: '{{count}}件未読', pushTitle: 'プッシュ通知', pushEnabled: 'プッシュ通知を有効化', smsTitle: 'SMS通知', whatsappTitle: 'WhatsApp通知', phoneNumber: '電話番号', phoneHint: 'SMSとWhatsApp通知に必要', securityTitle: 'セキュリティ通知', newLogin: '新しいデバイスからのサインイン時にメールで通知', bookingConfirm: '予約確認', bookingReminder: '予約リマインダー', bookingCancelled: '予約キャンセル', stub: 'プロバイダーが必要', prefsSaved: '通知設定が保存されました', prefsSaveFailed: '通知設定の保存に失敗' },
     calendar: { title: 'カレンダー', noBookings: 'この日の予約はありません', selectDay: '日をクリックして予約を表示', events: 'イベント', previousMonth: '前月', nextMonth: '翌月', subscribe: '購読', subscribeTitle: 'カレンダーを購読', subscribeDesc: 'このURLを使用して、カレンダーアプリから駐車カレンダーを購読できます。', copyLink: 'コピー', linkCopied: 'リンクをコピーしました', instructions: '購読方法', instructionGoogle: '設定 > カレンダーを追加 > URLから > リンクを貼り付け', instructionOutlook: 'カレンダーを追加 > Webから購読 > リンクを貼り付け', instructionApple: 'ファイル > 新規カレンダー購読 > リンクを貼り付け' },
     rateLimits: { title: 'レート制限', allClear: 'ブロックされたリクエストなし', blockedTotal: '{{count}}件ブロック（直近1時間）', blockedHistory: 'ブロックされたリクエスト（24時間）', perMinute: '分', blocked: 'ブロック', now: '現在' },
     fleet: { title: '車両管理', subtitle: '全ユーザーの全車両', totalVehicles: '車両合計', electricCount: '電気自動車', electricRatio: 'EV比率', flaggedCount: 'フラグ付き', byType: 'タイプ別', search: 'ナンバー、メーカー、車種...', allTypes: 'すべてのタイプ', colPlate: 'ナンバー', colType: 'タイプ', colOwner: '所有者', colMakeModel: 'メーカー/車種', colBookings: '予約', colLastUsed: '最終使用', colActions: '操作', empty: '車両なし', flag: 'フラグ', unflag: '解除', flagged: '車両にフラグ付けしました', unflagged: 'フラグを解除しました' },
@@ -49,7 +49,7 @@ export default {
     ui: { breadcrumb: 'パンくずリスト', sortAsc: '昇順', sortDesc: '降順', required: '必須', confirmAction: '操作を確認', swipeToClose: 'スワイプして閉じる' },
     common: { refresh: '更新', filter: 'フィルター', save: '保存', cancel: 'キャンセル', delete: '削除', edit: '編集', close: '閉じる', loading: '読込中...', error: 'エラーが発生しました', success: '成功', noData: 'データがありません', back: '戻る', next: '次へ', dismiss: '閉じる' },
     confirm: { cancelBookingTitle: '予約をキャンセル', cancelBookingMessage: 'この予約をキャンセルしてもよろしいですか？', cancelBookingConfirm: 'はい、キャンセル' },
-    book: { title: 'スペースを予約', step1Label: '駐車場を選択', step2Label: 'スペースと時間を選択', step3Label: '確認して予約', stepName1: '駐車場選択', stepName2: 'スペースと時間', stepName3: '確認', noLots: '利用可能な駐車場がありません', availableSlots: '{{count}} / {{total}} 空き', full: '満車', openNow: '営業中', closedNow: '閉鎖中', selectSlot: 'スペースを選択', noAvailableSlots: 'この駐車場に空きスペースがありません', startTime: '開始時間', duration: '時間', vehicle: '車両', noVehicle: '車両なし', continue: '続ける', lot: '駐車場', slot: 'スペース', from: '開始', to: '終了', estimatedCost: '見積費用', surgePricing: 'ピーク料金適用中', discountPricing: '割引料金適用中', dynamicPrice: '{{currency}}{{price}}/時', confirm: '予約を確認', confirming: '予約中...', success: '予約が確認されました！', progress: '予約の進捗', summary: '予約概要' },
+    book: { title: 'スペースを予約', step1Label: '駐車場を選択', step2Label: 'スペースと時間を選択', step3Label: '確認して予約', stepName1: '駐車場選択', stepName2: 'スペースと時間', stepName3: '確認', noLots: '利用可能な駐車場がありません', availableSlots: '{{count}} / {{total}} 空き', full: '満車', openNow: '営業中', closedNow: '閉鎖中', selectSlot: 'スペースを選択', noAvailableSlots: 'この駐車場に空きスペースがありません', startTime: '開始時間', duration: '時間', vehicle: '車両', noVehicle: '車両なし', continue: '続ける', lot: '駐車場', slot: 'スペース', from: '開始', to: '終了', estimatedCost: '見積費用', surgePricing: 'ピーク料金適用中', discountPricing: '割引料金適用中', dynamicPrice: '{{currency}}{{price}}/時', confirm: '予約を確認', confirming: '予約中...', success: '予約が確認されました！', slotTakenAlternative: 'スペース{{taken}}は先ほど予約されました。代わりにスペース{{alternative}}を選択しました。確認して予約してください。', progress: '予約の進捗', summary: '予約概要' },
     bookingFilters: { searchLot: '駐車場を検索...', statusAll: 'すべて', statusActive: 'アクティブ', statusConfirmed: '確認済', statusCancelled: 'キャンセル', statusCompleted: '完了', dateFrom: '開始', dateTo: '終了', totalCount: '{{count}}件の予約' },
     homeoffice: { weekdaysShort: { mon: '月', tue: '火', wed: '水', thu: '木', fri: '金', sat: '土', sun: '日' } },
     notFound: { title: 'ページが見つかりません', description: 'お探しのページは存在しないか、移動されました。', backToDashboard: 'ダッシュボードに戻る' },
@@ -23,7 +23,7 @@ export default {
       systemSettings: 'Ustawienia systemowe', general: 'Ogólne', companyName: 'Nazwa firmy', useCaseLabel: 'Scenariusz użycia', selfRegistration: 'Samodzielna rejestracja', selfRegistrationDesc: 'Pozwól użytkownikom na samodzielną rejestrację', bookingRules: 'Zasady rezerwacji', maxBookingsPerDay: 'Maks. rezerwacji dziennie', maxBookingsUnlimited: '0 = bez limitu', allowGuestBookings: 'Rezerwacje gości', allowGuestBookingsDesc: 'Goście mogą być rezerwowani przez adminów bez konta', requireVehicle: 'Wymagany pojazd', requireVehicleDesc: 'Pojazd / tablica rejestracyjna wymagana do rezerwacji', saveSettings: 'Zapisz ustawienia', settingsSaved: 'Ustawienia zapisane', settingsSaveFailed: 'Nie udało się zapisać ustawień', settingsLoadFailed: 'Nie udało się załadować ustawień',
       autoRelease: 'Automatyczne zwolnienie', autoReleaseMinutes: 'Automatyczne zwolnienie po (minuty)', autoReleaseDesc: 'Rezerwacje bez zameldowania są zwalniane po tym czasie. 0 = wyłączone.', waitlist: 'Lista oczekujących', enableWaitlist: 'Włącz listę oczekujących', enableWaitlistDesc: 'Użytkownicy mogą dołączyć do listy oczekujących, gdy parking jest pełny', creditsSystem: 'System kredytów', enableCredits: 'Włącz kredyty', enableCreditsDesc: 'Użytkownicy potrzebują kredytów, aby rezerwować', creditsPerBooking: 'Kredyty za rezerwację',
       licensePlate: 'Tablica rejestracyjna', licensePlateMode: 'Tryb tablicy rejestracyjnej', licensePlateLabelRequired: 'Wymagana', licensePlateLabelOptional: 'Opcjonalna', licensePlateLabelDisabled: 'Wyłączona', licensePlateModeRequired: 'Tablica rejestracyjna musi być podana przy każdej rezerwacji', licensePlateModeOptional: 'Tablica rejestracyjna może być podana opcjonalnie', licensePlateModeDisabled: 'Pole tablicy rejestracyjnej jest ukryte',
-      newAnnouncement: 'Nowe ogłoszenie', editAnnouncement: 'Edytuj ogłoszenie', announcementTitle: 'Tytuł', announcementMessage: 'Wiadomość', severity: 'Ważność', expiresAt: 'Wygasa (opcjonalnie)', announcementCreated: 'Ogłoszenie utworzone.', announcementUpdated: 'Ogłoszenie zaktualizowane.', announcementSaveFailed: 'Nie udało się zapisać.', announcementDeleted: 'Ogłoszenie usunięte.', announcementDeleteFailed: 'Nie udało się usunąć.', announcementDeleteConfirm: 'Usunąć to ogłoszenie?', announcementTitleRequired: 'Tytuł i wiadomość są wymagane.', noAnnouncements: 'Brak ogłoszeń.', announcementCreatedAt: 'Utworzono:', announcementExpiresAt: 'Wygasa:', severityInfo: 'Info', severityWarning: 'Ostrzeżenie', severityError: 'Błąd', severitySuccess: 'Sukces', expired: 'Wygasło',
+      newAnnouncement: 'Nowe ogłoszenie', editAnnouncement: 'Edytuj ogłoszenie', announcementTitle: 'Tytuł', announcementMessage: 'Wiadomość', severity: 'Ważność', expiresAt: 'Wygasa (opcjonalnie)', audience: 'Odbiorcy', audienceAll: 'Wszyscy', sendEmail: 'Wyślij także e-mailem', announcementCreated: 'Ogłoszenie utworzone.', announcementUpdated: 'Ogłoszenie zaktualizowane.', announcementSaveFailed: 'Nie udało się zapisać.', announcementDeleted: 'Ogłoszenie usunięte.', announcementDeleteFailed: 'Nie udało się usunąć.', announcementDeleteConfirm: 'Usunąć to ogłoszenie?', announcementTitleRequired: 'Tytuł i wiadomość są wymagane.', noAnnouncements: 'Brak ogłoszeń.', announcementCreatedAt: 'Utworzono:', announcementExpiresAt: 'Wygasa:', severityInfo: 'Info', severityWarning: 'Ostrzeżenie', severityError: 'Błąd', severitySuccess: 'Sukces', expired: 'Wygasło',
       totalUsers: 'Łącznie użytkowników', totalLots: 'Łącznie parkingów', totalBookings: 'Łącznie rezerwacji', activeBookings: 'Aktywne rezerwacje', utilizationRate: 'Wskaźnik wykorzystania', avgBookingsPerUser: 'Śr. rezerwacji na użytkownika', activeBookingRate: 'Wskaźnik aktywnych rezerwacji', bookingsThisWeek: 'Rezerwacje w tym tygodniu', lotOccupancy: 'Zajętość parkingu', exportCsv: 'Eksportuj CSV', exportBookings: 'Eksportuj rezerwacje', exportUsers: 'Eksportuj użytkowników', exportOccupancy: 'Eksportuj zajętość',
       useCaseCompany: 'Firma', useCaseResidential: 'Mieszkalny', useCaseShared: 'Parking współdzielony', useCaseRental: 'Wynajem', useCasePersonal: 'Osobisty', translations: 'Tłumaczenia', rateLimits: 'Limity zapytań', tenants: 'Najemcy', auditLog: 'Dziennik audytu', dataManagement: 'Dane', fleet: 'Flota', plugins: 'Wtyczki', graphql: 'GraphQL',
       bulkAction: 'Akcja zbiorcza', bulkActionConfirm: 'Czy na pewno chcesz {{action}} {{count}} użytkownika(ów)?', bulkDeleted: '{{succeeded}}/{{total}} użytkowników usunięto', bulkUpdated: '{{succeeded}}/{{total}} użytkowników zaktualizowano', bulkDeleteFailed: 'Usuwanie zbiorcze nie powiodło się', bulkUpdateFailed: 'Aktualizacja zbiorcza nie powiodła się', selectedCount: '{{count}} zaznaczono', selectAction: 'Wybierz akcję...', bulkActivate: 'Aktywuj', bulkDeactivate: 'Dezaktywuj', bulkChangeRole: 'Zmień rolę', bulkDelete: 'Usuń', bulkApply: 'Zastosuj', bulkClear: 'Wyczyść',
@@ -34,7 +34,7 @@ export default {
     useCase: { title: 'Jak będziesz używać ParkHub?', subtitle: 'Wybierz scenariusz użycia, aby otrzymać dopasowane funkcje', continue: 'Kontynuuj', skip: 'Pomiń na razie', applying: 'Stosowanie...', business: { name: 'Firma', desc: 'Zarządzanie parkingiem dla biur i firm', feature1: 'Przydział miejsc pracownikom', feature2: 'System rezerwacji oparty na kredytach', feature3: 'Zarządzanie nieobecnościami zespołu' }, residential: { name: 'Mieszkalny', desc: 'Kompleksy mieszkaniowe i wspólnoty', feature1: 'Samodzielna rejestracja mieszkańców', feature2: 'Zarządzanie parkingiem gości', feature3: 'Planowanie miejsc współdzielonych' }, personal: { name: 'Osobisty', desc: 'Garaż osobisty i współdzielony parking rodzinny', feature1: 'Prosta rezerwacja miejsc', feature2: 'Dostęp dla członków rodziny', feature3: 'Panel szybkich akcji' } },
     features: { title: 'Zarządzanie funkcjami', subtitle: 'Włącz lub wyłącz funkcje swojej instancji', onboardingTitle: 'Dostosuj swoje funkcje', onboardingSubtitle: 'Włącz lub wyłącz w zależności od potrzeb', enabled: 'Włączone', disabled: 'Wyłączone', saved: 'Funkcje zapisane', saveChanges: 'Zapisz zmiany', enableAll: 'Włącz wszystko', disableAll: 'Wyłącz wszystko', resetToPreset: 'Przywróć domyślne', categories: { core: 'Podstawowe', collaboration: 'Współpraca', billing: 'Rozliczenia', admin: 'Administracja', experience: 'Doświadczenie' }, modules: { credits: { name: 'Kredyty', desc: 'System rezerwacji oparty na kredytach z miesięcznymi kwotami', help: 'Użytkownicy potrzebują kredytów, aby rezerwować. Admini mogą przyznawać kredyty i ustawiać kwoty.' }, absences: { name: 'Nieobecności', desc: 'Śledzenie pracy zdalnej, urlopów i zwolnień', help: 'Zarządzaj nieobecnościami i cyklicznymi wzorcami pracy zdalnej dla zespołu.' }, vehicles: { name: 'Pojazdy', desc: 'Rejestracja i zarządzanie pojazdami z tablicami', help: 'Użytkownicy mogą rejestrować swoje pojazdy. Wsparcie tablic na kartach.' }, analytics: { name: 'Analizy', desc: 'Statystyki użycia, mapy ciepła i raporty', help: 'Panele admin z analizami rezerwacji, danymi o zajętości i raportami do eksportu.' }, team_view: { name: 'Widok zespołu', desc: 'Sprawdź, kto jest w biurze lub pracuje zdalnie', help: 'Pokazuje obecność zespołu na podstawie nieobecności i rezerwacji.' }, booking_types: { name: 'Typy rezerwacji', desc: 'Wsparcie rezerwacji jednorazowych, wielodniowych i cyklicznych', help: 'Włącz różne typy rezerwacji poza prostymi.' }, invoices: { name: 'Faktury', desc: 'Generowanie faktur PDF dla rezerwacji', help: 'Automatycznie generuj i pobieraj faktury za zakończone rezerwacje.' }, self_registration: { name: 'Samodzielna rejestracja', desc: 'Pozwól na tworzenie kont bez zatwierdzenia admina', help: 'Nowi użytkownicy mogą się rejestrować. Wyłącz dla dostępu tylko na zaproszenie.' }, generative_bg: { name: 'Tło stron publicznych', desc: 'Subtelne operacyjne tło na stronach publicznych', help: 'Utrzymuje ekrany logowania, powitania i konfiguracji w spokojnej strukturze bez efektów dekoracyjnych.' }, micro_animations: { name: 'Mikro-animacje', desc: 'Płynne przejścia i efekty hover w interfejsie', help: 'Włącza animacje fizyczne, listy stopniowe i wizualny feedback przy hover.' }, fab_quick_actions: { name: 'Szybkie akcje', desc: 'Pływający przycisk szybkiego dostępu do zadań', help: 'Pokazuje paletę poleceń (Ctrl+K) i pływający przycisk do szybkiej nawigacji.' }, rich_empty_states: { name: 'Bogate stany puste', desc: 'Ilustrowane stany puste z przydatnymi sugestiami', help: 'Pokazuje ilustracje i przyciski akcji, gdy listy są puste.' }, onboarding_hints: { name: 'Wskazówki powitalne', desc: 'Kontekstowe porady dla nowych użytkowników', help: 'Pokazuje prowadzone dymki dla początkujących użytkowników.' }, themes: { name: 'Motywy projektowe', desc: 'Wybierz spośród motywów wizualnych', help: 'Wybierz spośród 10 motywów: Classic, Glass, Bento, Brutalist, Neon, Warm, Liquid, Mono, Ocean, Forest.' } }, compliance: { title: 'Wbudowana zgodność', gdpr: 'Eksport RODO Art. 15', audit: 'Dziennik audytu', encryption: 'Szyfrowanie AES-256' } },
     demo: { badge: 'DEMO', timer: 'Reset za', viewers: 'odwiedzających', voteReset: 'Głosuj za resetem', votesNeeded: '{{current}}/{{needed}} głosów', resetting: 'Resetowanie danych demo...', lastReset: 'Ostatni reset', nextReset: 'Następny reset' },
-    notifications: { title: 'Powiadomienia', allRead: 'Wszystko aktualne', markAllRead: 'Oznacz wszystko jako przeczytane', allMarkedRead: 'Wszystko oznaczone jako przeczytane', empty: 'Brak powiadomień', unread: 'Nieprzeczytane', unreadCount: '{{count}} nieprzeczytanych', pushTitle: 'Powiadomienia push', pushEnabled: 'Włącz powiadomienia push', smsTitle: 'Powiadomienia SMS', whatsappTitle: 'Powiadomienia WhatsApp', phoneNumber: 'Numer telefonu', phoneHint: 'Wymagany dla powiadomień SMS i WhatsApp', bookingConfirm: 'Potwierdzenia rezerwacji', bookingReminder: 'Przypomnienia o rezerwacji', bookingCancelled: 'Anulacje rezerwacji', stub: 'Wymagany dostawca', prefsSaved: 'Preferencje powiadomień zapisane', prefsSaveFailed: 'Nie udało się zapisać preferencji powiadomień' },
+    notifications: { title: 'Powiadomienia', allRead: 'Wszystko aktualne', markAllRead: 'Oznacz wszystko jako przeczytane', allMarkedRead: 'Wszystko oznaczone jako przeczytane', empty: 'Brak powiadomień', unread: 'Nieprzeczytane', unreadCount: '{{count}} nieprzeczytanych', pushTitle: 'Powiadomienia push', pushEnabled: 'Włącz powiadomienia push', smsTitle: 'Powiadomienia SMS', whatsappTitle: 'Powiadomienia WhatsApp', phoneNumber: 'Numer telefonu', phoneHint: 'Wymagany dla powiadomień SMS i WhatsApp', securityTitle: 'Alerty bezpieczeństwa', newLogin: 'Powiadom mnie e-mailem o logowaniu z nowego urządzenia', bookingConfirm: 'Potwierdzenia rezerwacji', bookingReminder: 'Przypomnienia o rezerwacji', bookingCancelled: 'Anulacje rezerwacji', stub: 'Wymagany dostawca', prefsSaved: 'Preferencje powiadomień zapisane', prefsSaveFailed: 'Nie udało się zapisać preferencji powiadomień' },
     calendar: { title: 'Kalendarz', noBookings: 'Brak rezerwacji w tym dniu', selectDay: 'Kliknij dzień, aby zobaczyć rezerwacje', events: 'wydarzenia', previousMonth: 'Poprzedni miesiąc', nextMonth: 'Następny miesiąc', subscribe: 'Subskrybuj', subscribeTitle: 'Subskrybuj kalendarz', subscribeDesc: 'Użyj tego URL, aby subskrybować kalendarz parkingowy w dowolnej aplikacji.', copyLink: 'Kopiuj', linkCopied: 'Link skopiowany', instructions: 'Jak subskrybować', instructionGoogle: 'Ustawienia > Dodaj kalendarz > Z URL > wklej link', instructionOutlook: 'Dodaj kalendarz > Subskrybuj z sieci > wklej link', instructionApple: 'Plik > Nowa subskrypcja kalendarza > wklej link' },
     rateLimits: { title: 'Limity zapytan', allClear: 'Brak zablokowanych zapytan', blockedTotal: '{{count}} zablokowanych (ostatnia godzina)', blockedHistory: 'Zablokowane zapytania (24h)', perMinute: 'min', blocked: 'zablokowane', now: 'teraz' },
     fleet: { title: 'Zarzadzanie flota', subtitle: 'Wszystkie pojazdy', totalVehicles: 'Lacznie pojazdow', electricCount: 'Elektryczne', electricRatio: 'Udzial elektrycznych', flaggedCount: 'Oznaczone', byType: 'Wedlug typu', search: 'Tablica, marka, model...', allTypes: 'Wszystkie typy', colPlate: 'Tablica', colType: 'Typ', colOwner: 'Wlasciciel', colMakeModel: 'Marka/Model', colBookings: 'Rezerwacje', colLastUsed: 'Ostatnie uzycie', colActions: 'Akcje', empty: 'Brak pojazdow', flag: 'Oznacz', unflag: 'Usun', flagged: 'Pojazd oznaczony', unflagged: 'Oznaczenie usuniete' },
@@ -49,7 +49,7 @@ export default {
     ui: { breadcrumb: 'Okruchy chleba', sortAsc: 'Sortuj rosnąco', sortDesc: 'Sortuj malejąco', required: 'Wymagane', confirmAction: 'Potwierdź akcję', swipeToClose: 'Przesuń, aby zamknąć' },
     common: { refresh: 'Odśwież', filter: 'Filtruj', save: 'Zapisz', cancel: 'Anuluj', delete: 'Usuń', edit: 'Edytuj', close: 'Zamknij', loading: 'Ładowanie...', error: 'Wystąpił błąd', success: 'Sukces', noData: 'Brak dostępnych danych', back: 'Wstecz', next: 'Dalej', dismiss: 'Odrzuć' },
     confirm: { cancelBookingTitle: 'Anuluj rezerwację', cancelBookingMessage: 'Czy na pewno chcesz anulować tę rezerwację?', cancelBookingConfirm: 'Tak, anuluj' },
-    book: { title: 'Zarezerwuj miejsce', step1Label: 'Wybierz parking', step2Label: 'Wybierz miejsce i czas', step3Label: 'Sprawdź i potwierdź', stepName1: 'Wybór parkingu', stepName2: 'Miejsce i czas', stepName3: 'Potwierdzenie', noLots: 'Brak dostępnych parkingów', availableSlots: '{{count}} / {{total}} dostępnych', full: 'Pełny', openNow: 'Otwarty', closedNow: 'Zamknięty', selectSlot: 'Wybierz miejsce', noAvailableSlots: 'Brak dostępnych miejsc na tym parkingu', startTime: 'Godzina rozpoczęcia', duration: 'Czas trwania', vehicle: 'Pojazd', noVehicle: 'Brak pojazdu', continue: 'Kontynuuj', lot: 'Parking', slot: 'Miejsce', from: 'Od', to: 'Do', estimatedCost: 'Szacunkowy koszt', surgePricing: 'Cennik szczytowy aktywny', discountPricing: 'Cennik rabatowy aktywny', dynamicPrice: '{{currency}}{{price}}/godz.', confirm: 'Potwierdź rezerwację', confirming: 'Rezerwacja...', success: 'Rezerwacja potwierdzona!', progress: 'Postęp rezerwacji', summary: 'Podsumowanie rezerwacji' },
+    book: { title: 'Zarezerwuj miejsce', step1Label: 'Wybierz parking', step2Label: 'Wybierz miejsce i czas', step3Label: 'Sprawdź i potwierdź', stepName1: 'Wybór parkingu', stepName2: 'Miejsce i czas', stepName3: 'Potwierdzenie', noLots: 'Brak dostępnych parkingów', availableSlots: '{{count}} / {{total}} dostępnych', full: 'Pełny', openNow: 'Otwarty', closedNow: 'Zamknięty', selectSlot: 'Wybierz miejsce', noAvailableSlots: 'Brak dostępnych miejsc na tym parkingu', startTime: 'Godzina rozpoczęcia', duration: 'Czas trwania', vehicle: 'Pojazd', noVehicle: 'Brak pojazdu', continue: 'Kontynuuj', lot: 'Parking', slot: 'Miejsce', from: 'Od', to: 'Do', estimatedCost: 'Szacunkowy koszt', surgePricing: 'Cennik szczytowy aktywny', discountPricing: 'Cennik rabatowy aktywny', dynamicPrice: '{{currency}}{{price}}/godz.', confirm: 'Potwierdź rezerwację', confirming: 'Rezerwacja...', success: 'Rezerwacja potwierdzona!', slotTakenAlternative: 'Miejsce {{taken}} zostało właśnie zajęte — zamiast niego wybrano miejsce {{alternative}}. Potwierdź, aby je zarezerwować.', progress: 'Postęp rezerwacji', summary: 'Podsumowanie rezerwacji' },
     bookingFilters: { searchLot: 'Szukaj parkingu...', statusAll: 'Wszystkie', statusActive: 'Aktywne', statusConfirmed: 'Potwierdzone', statusCancelled: 'Anulowane', statusCompleted: 'Zakończone', dateFrom: 'Od', dateTo: 'Do', totalCount: '{{count}} rezerwacji' },
     homeoffice: { weekdaysShort: { mon: 'Pon', tue: 'Wt', wed: 'Śr', thu: 'Czw', fri: 'Pt', sat: 'Sob', sun: 'Ndz' } },
     notFound: { title: 'Strona nie znaleziona', description: 'Strona, której szukasz, nie istnieje lub została przeniesiona.', backToDashboard: 'Wróć do panelu' },
@@ -477,6 +477,13 @@ export default {
       announcementMessage: 'Mensagem',
       severity: 'Gravidade',
       expiresAt: 'Expira em (opcional)',
+      audience: 'Destinatários',
+      audienceAll: 'Todos',
+      sendEmail: 'Enviar também por e-mail',
+      dummyUsersWarning: 'Ainda existem {{count}} usuários de teste criados na instalação que compartilham uma senha. Remova-os antes de entrar em produção.',
+      dummyUsersPurge: 'Remover usuários de teste',
+      dummyUsersPurgeConfirm: 'Excluir todos os {{count}} usuários de teste? Isso não pode ser desfeito.',
+      dummyUsersPurged: '{{count}} usuários de teste removidos.',
       announcementCreated: 'Anúncio criado.',
       announcementUpdated: 'Anúncio atualizado.',
       announcementSaveFailed: 'Falha ao guardar.',
@@ -626,7 +633,7 @@ export default {
     demo: { badge: 'DEMO', timer: 'Reinício em', viewers: 'visitantes', voteReset: 'Votar para reiniciar', votesNeeded: '{{current}}/{{needed}} votos', resetting: 'A reiniciar dados de demonstração...', lastReset: 'Último reinício', nextReset: 'Próximo reinício' },
     notifications: {
       title: 'Notificações', allRead: 'Tudo em dia', markAllRead: 'Marcar tudo como lido', allMarkedRead: 'Tudo marcado como lido', empty: 'Sem notificações', unread: 'Não lidas', unreadCount: '{{count}} não lida(s)',
-      pushTitle: 'Notificações push', pushEnabled: 'Ativar notificações push', smsTitle: 'Notificações SMS', whatsappTitle: 'Notificações WhatsApp', phoneNumber: 'Número de telefone', phoneHint: 'Necessário para notificações SMS e WhatsApp',
+      pushTitle: 'Notificações push', pushEnabled: 'Ativar notificações push', smsTitle: 'Notificações SMS', whatsappTitle: 'Notificações WhatsApp', phoneNumber: 'Número de telefone', phoneHint: 'Necessário para notificações SMS e WhatsApp', securityTitle: 'Alertas de segurança', newLogin: 'Avisar-me por e-mail ao entrar a partir de um novo dispositivo',
       bookingConfirm: 'Confirmações de reserva', bookingReminder: 'Lembretes de reserva', bookingCancelled: 'Cancelamentos de reserva', stub: 'Fornecedor necessario', prefsSaved: 'Preferências de notificação guardadas', prefsSaveFailed: 'Falha ao guardar preferências',
     },
     calendar: { title: 'Calendário', noBookings: 'Sem reservas neste dia', selectDay: 'Clique num dia para ver as reservas', events: 'eventos', previousMonth: 'Mês anterior', nextMonth: 'Mês seguinte', subscribe: 'Subscrever', subscribeTitle: 'Subscrever calendário', subscribeDesc: 'Use este URL para subscrever o seu calendário de estacionamento em qualquer app.', copyLink: 'Copiar', linkCopied: 'Link copiado', instructions: 'Como subscrever', instructionGoogle: 'Definições > Adicionar calendário > Por URL > colar o link', instructionOutlook: 'Adicionar calendário > Subscrever da web > colar o link', instructionApple: 'Ficheiro > Nova subscrição de calendário > colar o link' },
@@ -659,7 +666,7 @@ export default {
       title: 'Reservar uma vaga', step1Label: 'Escolha um estacionamento', step2Label: 'Selecione vaga e horário', step3Label: 'Reveja e confirme', stepName1: 'Selecionar estacionamento', stepName2: 'Vaga e horário', stepName3: 'Confirmar',
       noLots: 'Sem estacionamentos disponíveis', availableSlots: '{{count}} / {{total}} disponíveis', full: 'Lotado', openNow: 'Aberto', closedNow: 'Fechado', selectSlot: 'Selecione uma vaga', noAvailableSlots: 'Sem vagas disponíveis neste estacionamento',
       startTime: 'Hora de início', duration: 'Duração', vehicle: 'Veículo', noVehicle: 'Sem veículo', continue: 'Continuar', lot: 'Estacionamento', slot: 'Vaga', from: 'De', to: 'Até', estimatedCost: 'Custo estimado',
-      surgePricing: 'Tarifação de pico ativa', discountPricing: 'Tarifação com desconto ativa', dynamicPrice: '{{currency}}{{price}}/h', confirm: 'Confirmar reserva', confirming: 'A reservar...', success: 'Reserva confirmada!', progress: 'Progresso da reserva', summary: 'Resumo da reserva',
+      surgePricing: 'Tarifação de pico ativa', discountPricing: 'Tarifação com desconto ativa', dynamicPrice: '{{currency}}{{price}}/h', confirm: 'Confirmar reserva', confirming: 'A reservar...', success: 'Reserva confirmada!', slotTakenAlternative: 'A vaga {{taken}} acabou de ser ocupada — a vaga {{alternative}} foi selecionada. Confirme para reservá-la.', progress: 'Progresso da reserva', summary: 'Resumo da reserva',
     },
     bookingFilters: { searchLot: 'Pesquisar estacionamento...', statusAll: 'Todos', statusActive: 'Ativo', statusConfirmed: 'Confirmado', statusCancelled: 'Cancelado', statusCompleted: 'Concluído', dateFrom: 'De', dateTo: 'Até', totalCount: '{{count}} reservas' },
     homeoffice: { weekdaysShort: { mon: 'Seg', tue: 'Ter', wed: 'Qua', thu: 'Qui', fri: 'Sex', sat: 'Sáb', sun: 'Dom' } },
@@ -23,7 +23,7 @@ export default {
       systemSettings: 'Sistem Ayarları', general: 'Genel', companyName: 'Şirket Adı', useCaseLabel: 'Kullanım Şekli', selfRegistration: 'Serbest Kayıt', selfRegistrationDesc: 'Kullanıcıların kendilerini kaydetmesine izin ver', bookingRules: 'Rezervasyon Kuralları', maxBookingsPerDay: 'Günlük Maks Rezervasyon', maxBookingsUnlimited: '0 = sınırsız', allowGuestBookings: 'Misafir Rezervasyonları', allowGuestBookingsDesc: 'Misafirler hesap olmadan yöneticiler tarafından rezerve edilebilir', requireVehicle: 'Araç Zorunlu', requireVehicleDesc: 'Rezervasyon için araç / plaka gerekli', saveSettings: 'Ayarları Kaydet', settingsSaved: 'Ayarlar kaydedildi', settingsSaveFailed: 'Ayarlar kaydedilemedi', settingsLoadFailed: 'Ayarlar yüklenemedi',
       autoRelease: 'Otomatik Serbest Bırakma', autoReleaseMinutes: 'Otomatik serbest bırakma süresi (dakika)', autoReleaseDesc: 'Giriş yapılmayan rezervasyonlar bu süre sonunda serbest bırakılır. 0 = devre dışı.', waitlist: 'Bekleme Listesi', enableWaitlist: 'Bekleme Listesini Etkinleştir', enableWaitlistDesc: 'Otopark dolduğunda kullanıcılar bekleme listesine katılabilir', creditsSystem: 'Kredi Sistemi', enableCredits: 'Kredileri Etkinleştir', enableCreditsDesc: 'Kullanıcıların rezervasyon için krediye ihtiyacı var', creditsPerBooking: 'Rezervasyon Başına Kredi',
       licensePlate: 'Plaka', licensePlateMode: 'Plaka Modu', licensePlateLabelRequired: 'Zorunlu', licensePlateLabelOptional: 'İsteğe Bağlı', licensePlateLabelDisabled: 'Devre Dışı', licensePlateModeRequired: 'Her rezervasyon için plaka gerekli', licensePlateModeOptional: 'Plaka isteğe bağlı olarak sağlanabilir', licensePlateModeDisabled: 'Plaka alanı gizli',
-      newAnnouncement: 'Yeni Duyuru', editAnnouncement: 'Duyuruyu Düzenle', announcementTitle: 'Başlık', announcementMessage: 'Mesaj', severity: 'Önem', expiresAt: 'Bitiş tarihi (isteğe bağlı)', announcementCreated: 'Duyuru oluşturuldu.', announcementUpdated: 'Duyuru güncellendi.', announcementSaveFailed: 'Kayıt başarısız.', announcementDeleted: 'Duyuru silindi.', announcementDeleteFailed: 'Silme başarısız.', announcementDeleteConfirm: 'Bu duyuruyu silmek istiyor musunuz?', announcementTitleRequired: 'Başlık ve mesaj gereklidir.', noAnnouncements: 'Henüz duyuru yok.', announcementCreatedAt: 'Oluşturulma:', announcementExpiresAt: 'Bitiş:', severityInfo: 'Bilgi', severityWarning: 'Uyarı', severityError: 'Hata', severitySuccess: 'Başarılı', expired: 'Süresi doldu',
+      newAnnouncement: 'Yeni Duyuru', editAnnouncement: 'Duyuruyu Düzenle', announcementTitle: 'Başlık', announcementMessage: 'Mesaj', severity: 'Önem', expiresAt: 'Bitiş tarihi (isteğe bağlı)', audience: 'Hedef kitle', audienceAll: 'Herkes', sendEmail: 'E-posta ile de gönder', announcementCreated: 'Duyuru oluşturuldu.', announcementUpdated: 'Duyuru güncellendi.', announcementSaveFailed: 'Kayıt başarısız.', announcementDeleted: 'Duyuru silindi.', announcementDeleteFailed: 'Silme başarısız.', announcementDeleteConfirm: 'Bu duyuruyu silmek istiyor musunuz?', announcementTitleRequired: 'Başlık ve mesaj gereklidir.', noAnnouncements: 'Henüz duyuru yok.', announcementCreatedAt: 'Oluşturulma:', announcementExpiresAt: 'Bitiş:', severityInfo: 'Bilgi', severityWarning: 'Uyarı', severityError: 'Hata', severitySuccess: 'Başarılı', expired: 'Süresi doldu',
       totalUsers: 'Toplam Kullanıcı', totalLots: 'Toplam Otopark', totalBookings: 'Toplam Rezervasyon', activeBookings: 'Aktif Rezervasyonlar', utilizationRate: 'Kullanım Oranı', avgBookingsPerUser: 'Kullanıcı Başına Ort. Rezervasyon', activeBookingRate: 'Aktif Rezervasyon Oranı', bookingsThisWeek: 'Bu Hafta Rezervasyonlar', lotOccupancy: 'Otopark Doluluk', exportCsv: 'CSV Dışa Aktar', exportBookings: 'Rezervasyonları Dışa Aktar', exportUsers: 'Kullanıcıları Dışa Aktar', exportOccupancy: 'Doluluk Dışa Aktar',
       useCaseCompany: 'Şirket', useCaseResidential: 'Konut', useCaseShared: 'Paylaşımlı Otopark', useCaseRental: 'Kiralık', useCasePersonal: 'Kişisel', translations: 'Çeviriler', rateLimits: 'Hız Limitleri', tenants: 'Kiracılar', auditLog: 'Denetim Gunlugu', dataManagement: 'Veri', fleet: 'Filo', plugins: 'Eklentiler', graphql: 'GraphQL',
       bulkAction: 'Toplu İşlem', bulkActionConfirm: '{{count}} kullanıcıyı {{action}} etmek istediğinizden emin misiniz?', bulkDeleted: '{{succeeded}}/{{total}} kullanıcı silindi', bulkUpdated: '{{succeeded}}/{{total}} kullanıcı güncellendi', bulkDeleteFailed: 'Toplu silme başarısız', bulkUpdateFailed: 'Toplu güncelleme başarısız', selectedCount: '{{count}} seçili', selectAction: 'İşlem seçin...', bulkActivate: 'Etkinleştir', bulkDeactivate: 'Devre Dışı Bırak', bulkChangeRole: 'Rol Değiştir', bulkDelete: 'Sil', bulkApply: 'Uygula', bulkClear: 'Temizle',
@@ -34,7 +34,7 @@ export default {
     useCase: { title: 'ParkHub\'ı nasıl kullanacaksınız?', subtitle: 'Özelleştirilmiş özellikler için bir kullanım şekli seçin', continue: 'Devam Et', skip: 'Şimdilik atla', applying: 'Uygulanıyor...', business: { name: 'İş', desc: 'Ofisler ve şirketler için otopark yönetimi', feature1: 'Çalışanlara yer tahsisi', feature2: 'Kredi tabanlı rezervasyon sistemi', feature3: 'Ekip devamsızlık yönetimi' }, residential: { name: 'Konut', desc: 'Apartman kompleksleri ve site toplulukları', feature1: 'Sakin özerk kaydı', feature2: 'Ziyaretçi otopark yönetimi', feature3: 'Paylaşımlı yer planlaması' }, personal: { name: 'Kişisel', desc: 'Kişisel garaj ve paylaşımlı aile otoparkı', feature1: 'Basit yer rezervasyonu', feature2: 'Aile üyesi erişimi', feature3: 'Hızlı işlemler paneli' } },
     features: { title: 'Özellik Yönetimi', subtitle: 'Örneğinizin özelliklerini etkinleştirin veya devre dışı bırakın', onboardingTitle: 'Özelliklerinizi Özelleştirin', onboardingSubtitle: 'İhtiyaçlarınıza göre etkinleştirin veya devre dışı bırakın', enabled: 'Etkin', disabled: 'Devre Dışı', saved: 'Özellikler kaydedildi', saveChanges: 'Değişiklikleri Kaydet', enableAll: 'Tümünü Etkinleştir', disableAll: 'Tümünü Devre Dışı Bırak', resetToPreset: 'Varsayılana Sıfırla', categories: { core: 'Temel', collaboration: 'İş Birliği', billing: 'Faturalandırma', admin: 'Yönetim', experience: 'Deneyim' }, modules: { credits: { name: 'Krediler', desc: 'Aylık kotalarla kredi tabanlı rezervasyon sistemi', help: 'Kullanıcılar rezervasyon için krediye ihtiyaç duyar. Yöneticiler kredi tahsis edebilir ve kota belirleyebilir.' }, absences: { name: 'Devamsızlıklar', desc: 'Uzaktan çalışma, tatil ve hastalık takibi', help: 'Ekibiniz için devamsızlıkları ve tekrarlayan uzaktan çalışma düzenlerini yönetin.' }, vehicles: { name: 'Araçlar', desc: 'Plakalarla araçları kaydedin ve yönetin', help: 'Kullanıcılar araçlarını kaydedebilir. Kartlarda plaka desteği.' }, analytics: { name: 'Analizler', desc: 'Kullanım istatistikleri, ısı haritaları ve raporlar', help: 'Rezervasyon analizleri, doluluk verileri ve dışa aktarılabilir raporlarla yönetici panelleri.' }, team_view: { name: 'Ekip Görünümü', desc: 'Ofiste veya uzaktan çalışanları görün', help: 'Devamsızlık ve rezervasyon verilerine dayalı ekip varlığını gösterir.' }, booking_types: { name: 'Rezervasyon Türleri', desc: 'Tekil, çok günlü ve tekrarlayan rezervasyon desteği', help: 'Basit rezervasyonların ötesinde farklı rezervasyon türlerini etkinleştirin.' }, invoices: { name: 'Faturalar', desc: 'Rezervasyonlar için PDF fatura oluşturma', help: 'Tamamlanan rezervasyonlar için otomatik fatura oluşturun ve indirin.' }, self_registration: { name: 'Serbest Kayıt', desc: 'Yönetici onayı olmadan hesap oluşturmaya izin ver', help: 'Yeni kullanıcılar kendileri kaydolabilir. Yalnızca davetli erişim için devre dışı bırakın.' }, generative_bg: { name: 'Genel Sayfa Arka Planı', desc: 'Genel sayfalarda sade operasyonel arka plan', help: 'Giriş, karşılama ve başlangıç ekranlarını dekoratif efektler olmadan düzenli tutar.' }, micro_animations: { name: 'Mikro Animasyonlar', desc: 'Arayüzde akıcı geçişler ve hover efektleri', help: 'Fizik animasyonları, kademeli listeler ve hover geri bildirimi etkinleştirir.' }, fab_quick_actions: { name: 'Hızlı İşlemler', desc: 'Yaygın görevlere hızlı erişim için kayan düğme', help: 'Komut paleti (Ctrl+K) ve hızlı navigasyon için kayan düğme gösterir.' }, rich_empty_states: { name: 'Zengin Boş Durumlar', desc: 'Yararlı önerilerle resimli boş durumlar', help: 'Listeler boş olduğunda resimler ve eylem düğmeleri gösterir.' }, onboarding_hints: { name: 'Başlangıç İpuçları', desc: 'Yeni kullanıcılar için bağlamsal ipuçları', help: 'Yeni başlayan kullanıcılar için rehberli araç ipuçları gösterir.' }, themes: { name: 'Tasarım Temaları', desc: 'Görsel tasarım temaları arasından seçin', help: '10 tema arasından seçin: Classic, Glass, Bento, Brutalist, Neon, Warm, Liquid, Mono, Ocean, Forest.' } }, compliance: { title: 'Yerleşik Uyumluluk', gdpr: 'KVKK Dışa Aktarım', audit: 'Denetim Kaydı', encryption: 'AES-256 Şifreleme' } },
     demo: { badge: 'DEMO', timer: 'Sıfırlanma', viewers: 'ziyaretçi', voteReset: 'Sıfırlama için Oyla', votesNeeded: '{{current}}/{{needed}} oy', resetting: 'Demo verileri sıfırlanıyor...', lastReset: 'Son sıfırlama', nextReset: 'Sonraki sıfırlama' },
-    notifications: { title: 'Bildirimler', allRead: 'Tümü güncel', markAllRead: 'Tümünü okundu işaretle', allMarkedRead: 'Tümü okundu işaretlendi', empty: 'Bildirim yok', unread: 'Okunmamış', unreadCount: '{{count}} okunmamış', pushTitle: 'Push Bildirimleri', pushEnabled: 'Push bildirimlerini etkinleştir', smsTitle: 'SMS Bildirimleri', whatsappTitle: 'WhatsApp Bildirimleri', phoneNumber: 'Telefon Numarası', phoneHint: 'SMS ve WhatsApp bildirimleri için gerekli', bookingConfirm: 'Rezervasyon onayları', bookingReminder: 'Rezervasyon hatırlatmaları', bookingCancelled: 'Rezervasyon iptalleri', stub: 'Sağlayıcı gerekli', prefsSaved: 'Bildirim tercihleri kaydedildi', prefsSaveFailed: 'Bildirim tercihleri kaydedilemedi' },
+    notifications: { title: 'Bildirimler', allRead: 'Tümü güncel', markAllRead: 'Tümünü okundu işaretle', allMarkedRead: 'Tümü okundu işaretlendi', empty: 'Bildirim yok', unread: 'Okunmamış', unreadCount: '{{count}} okunmamış', pushTitle: 'Push Bildirimleri', pushEnabled: 'Push bildirimlerini etkinleştir', smsTitle: 'SMS Bildirimleri', whatsappTitle: 'WhatsApp Bildirimleri', phoneNumber: 'Telefon Numarası', phoneHint: 'SMS ve WhatsApp bildirimleri için gerekli', securityTitle: 'Güvenlik uyarıları', newLogin: 'Yeni bir cihazdan oturum açtığımda bana e-posta gönder', bookingConfirm: 'Rezervasyon onayları', bookingReminder: 'Rezervasyon hatırlatmaları', bookingCancelled: 'Rezervasyon iptalleri', stub: 'Sağlayıcı gerekli', prefsSaved: 'Bildirim tercihleri kaydedildi', prefsSaveFailed: 'Bildirim tercihleri kaydedilemedi' },
     calendar: { title: 'Takvim', noBookings: 'Bu gün rezervasyon yok', selectDay: 'Rezervasyonları görmek için bir güne tıklayın', events: 'etkinlik', previousMonth: 'Önceki ay', nextMonth: 'Sonraki ay', subscribe: 'Abone ol', subscribeTitle: 'Takvime abone ol', subscribeDesc: 'Bu URL\'yi kullanarak park takviminize herhangi bir takvim uygulamasından abone olabilirsiniz.', copyLink: 'Kopyala', linkCopied: 'Link kopyalandı', instructions: 'Nasıl abone olunur', instructionGoogle: 'Ayarlar > Takvim ekle > URL\'den > linki yapıştır', instructionOutlook: 'Takvim ekle > Web\'den abone ol > linki yapıştır', instructionApple: 'Dosya > Yeni takvim aboneliği > linki yapıştır' },
     rateLimits: { title: 'Hiz Limitleri', allClear: 'Engellenen istek yok', blockedTotal: '{{count}} engellendi (son saat)', blockedHistory: 'Engellenen istekler (24s)', perMinute: 'dk', blocked: 'engellendi', now: 'simdi' },
     fleet: { title: 'Filo Yonetimi', subtitle: 'Tum araclar', totalVehicles: 'Toplam arac', electricCount: 'Elektrikli', electricRatio: 'Elektrikli orani', flaggedCount: 'Isaretli', byType: 'Tipe gore', search: 'Plaka, marka, model...', allTypes: 'Tum tipler', colPlate: 'Plaka', colType: 'Tip', colOwner: 'Sahibi', colMakeModel: 'Marka/Model', colBookings: 'Rezervasyonlar', colLastUsed: 'Son kullanim', colActions: 'Islemler', empty: 'Arac yok', flag: 'Isaretle', unflag: 'Kaldir', flagged: 'Arac isaretlendi', unflagged: 'Isaret kaldirildi' },
@@ -49,7 +49,7 @@ export default {
     ui: { breadcrumb: 'Gezinti Yolu', sortAsc: 'Artan sıralama', sortDesc: 'Azalan sıralama', required: 'Zorunlu', confirmAction: 'İşlemi onayla', swipeToClose: 'Kapatmak için kaydırın' },
     common: { refresh: 'Yenile', filter: 'Filtrele', save: 'Kaydet', cancel: 'İptal', delete: 'Sil', edit: 'Düzenle', close: 'Kapat', loading: 'Yükleniyor...', error: 'Bir hata oluştu', success: 'Başarılı', noData: 'Veri mevcut değil', back: 'Geri', next: 'İleri', dismiss: 'Kapat' },
     confirm: { cancelBookingTitle: 'Rezervasyonu İptal Et', cancelBookingMessage: 'Bu rezervasyonu iptal etmek istediğinizden emin misiniz?', cancelBookingConfirm: 'Evet, İptal Et' },
-    book: { title: 'Yer Ayırt', step1Label: 'Bir otopark seçin', step2Label: 'Yer ve saat seçin', step3Label: 'İnceleyin ve onaylayın', stepName1: 'Otopark Seç', stepName2: 'Yer ve Saat', stepName3: 'Onayla', noLots: 'Uygun otopark yok', availableSlots: '{{count}} / {{total}} müsait', full: 'Dolu', openNow: 'Şimdi Açık', closedNow: 'Kapalı', selectSlot: 'Bir yer seçin', noAvailableSlots: 'Bu otoparkta müsait yer yok', startTime: 'Başlangıç saati', duration: 'Süre', vehicle: 'Araç', noVehicle: 'Araç yok', continue: 'Devam Et', lot: 'Otopark', slot: 'Yer', from: 'Başlangıç', to: 'Bitiş', estimatedCost: 'Tahmini maliyet', surgePricing: 'Yoğunluk fiyatlandırması aktif', discountPricing: 'İndirimli fiyatlandırma aktif', dynamicPrice: '{{currency}}{{price}}/sa', confirm: 'Rezervasyonu Onayla', confirming: 'Rezervasyon yapılıyor...', success: 'Rezervasyon onaylandı!', progress: 'Rezervasyon ilerlemesi', summary: 'Rezervasyon özeti' },
+    book: { title: 'Yer Ayırt', step1Label: 'Bir otopark seçin', step2Label: 'Yer ve saat seçin', step3Label: 'İnceleyin ve onaylayın', stepName1: 'Otopark Seç', stepName2: 'Yer ve Saat', stepName3: 'Onayla', noLots: 'Uygun otopark yok', availableSlots: '{{count}} / {{total}} müsait', full: 'Dolu', openNow: 'Şimdi Açık', closedNow: 'Kapalı', selectSlot: 'Bir yer seçin', noAvailableSlots: 'Bu otoparkta müsait yer yok', startTime: 'Başlangıç saati', duration: 'Süre', vehicle: 'Araç', noVehicle: 'Araç yok', continue: 'Devam Et', lot: 'Otopark', slot: 'Yer', from: 'Başlangıç', to: 'Bitiş', estimatedCost: 'Tahmini maliyet', surgePricing: 'Yoğunluk fiyatlandırması aktif', discountPricing: 'İndirimli fiyatlandırma aktif', dynamicPrice: '{{currency}}{{price}}/sa', confirm: 'Rezervasyonu Onayla', confirming: 'Rezervasyon yapılıyor...', success: 'Rezervasyon onaylandı!', slotTakenAlternative: '{{taken}} numaralı yer az önce alındı — bunun yerine {{alternative}} numaralı yer seçildi. Ayırtmak için onaylayın.', progress: 'Rezervasyon ilerlemesi', summary: 'Rezervasyon özeti' },
     bookingFilters: { searchLot: 'Otopark ara...', statusAll: 'Tümü', statusActive: 'Aktif', statusConfirmed: 'Onaylı', statusCancelled: 'İptal', statusCompleted: 'Tamamlandı', dateFrom: 'Başlangıç', dateTo: 'Bitiş', totalCount: '{{count}} rezervasyon' },
     homeoffice: { weekdaysShort: { mon: 'Pzt', tue: 'Sal', wed: 'Çar', thu: 'Per', fri: 'Cum', sat: 'Cmt', sun: 'Paz' } },
     notFound: { title: 'Sayfa bulunamadı', description: 'Aradığınız sayfa mevcut değil veya taşınmış.', backToDashboard: 'Panele Dön' },
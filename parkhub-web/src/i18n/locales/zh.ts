@@ -23,7 +23,7 @@ export default {
       systemSettings: '系统设置', general: '常规', companyName: '公司名称', useCaseLabel: '使用场景', selfRegistration: '自助注册', selfRegistrationDesc: '允许用户自行注册', bookingRules: '预订规则', maxBookingsPerDay: '每日最大预订数', maxBookingsUnlimited: '0 = 不限', allowGuestBookings: '访客预订', allowGuestBookingsDesc: '管理员可以为无账户的访客预订', requireVehicle: '车辆必填', requireVehicleDesc: '预订需要车辆/车牌号', saveSettings: '保存设置', settingsSaved: '设置已保存', settingsSaveFailed: '设置保存失败', settingsLoadFailed: '设置加载失败',
       autoRelease: '自动释放', autoReleaseMinutes: '自动释放时间（分钟）', autoReleaseDesc: '未签到的预订在此时间后释放。0 = 禁用。', waitlist: '候补名单', enableWaitlist: '启用候补名单', enableWaitlistDesc: '停车场满时用户可以加入候补名单', creditsSystem: '积分系统', enableCredits: '启用积分', enableCreditsDesc: '用户需要积分来预订', creditsPerBooking: '每次预订积分',
       licensePlate: '车牌号', licensePlateMode: '车牌模式', licensePlateLabelRequired: '必填', licensePlateLabelOptional: '可选', licensePlateLabelDisabled: '禁用', licensePlateModeRequired: '每次预订必须提供车牌号', licensePlateModeOptional: '车牌号可选填', licensePlateModeDisabled: '车牌号字段隐藏',
-      newAnnouncement: '新建公告', editAnnouncement: '编辑公告', announcementTitle: '标题', announcementMessage: '消息', severity: '严重程度', expiresAt: '过期时间（可选）', announcementCreated: '公告已创建。', announcementUpdated: '公告已更新。', announcementSaveFailed: '保存失败。', announcementDeleted: '公告已删除。', announcementDeleteFailed: '删除失败。', announcementDeleteConfirm: '删除此公告？', announcementTitleRequired: '标题和消息为必填项。', noAnnouncements: '暂无公告。', announcementCreatedAt: '创建时间：', announcementExpiresAt: '过期时间：', severityInfo: '信息', severityWarning: '警告', severityError: '错误', severitySuccess: '成功', expired: '已过期',
+      newAnnouncement: '新建公告', editAnnouncement: '编辑公告', announcementTitle: '标题', announcementMessage: '消息', severity: '严重程度', expiresAt: '过期时间（可选）', audience: '受众', audienceAll: '所有人', sendEmail: '同时通过邮件发送', announcementCreated: '公告已创建。', announcementUpdated: '公告已更新。', announcementSaveFailed: '保存失败。', announcementDeleted: '公告已删除。', announcementDeleteFailed: '删除失败。', announcementDeleteConfirm: '删除此公告？', announcementTitleRequired: '标题和消息为必填项。', noAnnouncements: '暂无公告。', announcementCreatedAt: '创建时间：', announcementExpiresAt: '过期时间：', severityInfo: '信息', severityWarning: '警告', severityError: '错误', severitySuccess: '成功', expired: '已过期',
       totalUsers: '用户总数', totalLots: '停车场总数', totalBookings: '预订总数', activeBookings: '活跃预订', utilizationRate: '使用率', avgBookingsPerUser: '每用户平均预订', activeBookingRate: '活跃预订率', bookingsThisWeek: '本周预订', lotOccupancy: '停车场占用率', exportCsv: '导出 CSV', exportBookings: '导出预订', exportUsers: '导出用户', exportOccupancy: '导出占用率',
       useCaseCompany: '企业', useCaseResidential: '住宅', useCaseShared: '共享停车', useCaseRental: '租赁', useCasePersonal: '个人', translations: '翻译', rateLimits: '速率限制', tenants: '租户', auditLog: '审计日志', dataManagement: '数据', fleet: '车队', plugins: '插件', graphql: 'GraphQL',
       bulkAction: '批量操作', bulkActionConfirm: '确定要对 {{count}} 个用户执行 {{action}} 操作吗？', bulkDeleted: '已删除 {{succeeded}}/{{total}} 个用户', bulkUpdated: '已更新 {{succeeded}}/{{total}} 个用户', bulkDeleteFailed: '批量删除失败', bulkUpdateFailed: '批量更新失败', selectedCount: '已选择 {{count}} 个', selectAction: '选择操作...', bulkActivate: '启用', bulkDeactivate: '停用', bulkChangeRole: '更改角色', bulkDelete: '删除', bulkApply: '应用', bulkClear: '清除',
@@ -34,7 +34,7 @@ export default {
     useCase: { title: '您将如何使用 ParkHub？', subtitle: '选择使用场景以获取定制功能', continue: '继续', skip: '暂时跳过', applying: '应用中...', business: { name: '企业', desc: '办公室和企业的停车管理', feature1: '员工车位分配', feature2: '积分制预订系统', feature3: '团队缺勤管理' }, residential: { name: '住宅', desc: '公寓楼和住宅社区', feature1: '住户自助注册', feature2: '访客停车管理', feature3: '共享车位调度' }, personal: { name: '个人', desc: '个人车库和家庭共享停车', feature1: '简单车位预订', feature2: '家庭成员访问', feature3: '快捷操作仪表盘' } },
     features: { title: '功能管理', subtitle: '启用或禁用实例的功能', onboardingTitle: '自定义您的功能', onboardingSubtitle: '根据需要启用或禁用', enabled: '已启用', disabled: '已禁用', saved: '功能已保存', saveChanges: '保存更改', enableAll: '全部启用', disableAll: '全部禁用', resetToPreset: '恢复默认', categories: { core: '核心', collaboration: '协作', billing: '计费', admin: '管理', experience: '体验' }, modules: { credits: { name: '积分', desc: '带月度配额的积分预订系统', help: '用户需要积分来预订。管理员可以分配积分和设置配额。' }, absences: { name: '缺勤', desc: '追踪远程办公、休假和病假', help: '为团队管理缺勤记录和定期远程办公模式。' }, vehicles: { name: '车辆', desc: '注册和管理带车牌号的车辆', help: '用户可以注册车辆。支持通行证上的车牌显示。' }, analytics: { name: '分析', desc: '使用统计、热力图和报告', help: '管理面板提供预订分析、占用数据和可导出报告。' }, team_view: { name: '团队视图', desc: '查看谁在办公室或远程办公', help: '基于缺勤和预订数据显示团队出勤情况。' }, booking_types: { name: '预订类型', desc: '支持单次、多日和定期预订', help: '启用简单预订之外的不同预订类型。' }, invoices: { name: '发票', desc: '为预订生成 PDF 发票', help: '自动生成和下载已完成预订的发票。' }, self_registration: { name: '自助注册', desc: '允许无需管理员批准即可创建账户', help: '新用户可以自行注册。禁用以实现仅限邀请访问。' }, generative_bg: { name: '公共页面背景', desc: '公共页面上的低调运营背景', help: '让登录、欢迎和引导页面保持清晰结构，避免装饰性效果。' }, micro_animations: { name: '微动画', desc: 'UI 中的流畅过渡和悬停效果', help: '启用物理动画、渐进列表和悬停反馈。' }, fab_quick_actions: { name: '快捷操作', desc: '浮动按钮快速访问常用任务', help: '显示命令面板（Ctrl+K）和浮动按钮以快速导航。' }, rich_empty_states: { name: '丰富空状态', desc: '带实用建议的插图空状态', help: '当列表为空时显示插图和操作按钮。' }, onboarding_hints: { name: '入门提示', desc: '为新用户提供上下文提示', help: '为初次使用的用户显示引导提示。' }, themes: { name: '设计主题', desc: '在视觉设计主题之间切换', help: '从 10 个主题中选择：Classic、Glass、Bento、Brutalist、Neon、Warm、Liquid、Mono、Ocean、Forest。' } }, compliance: { title: '内置合规', gdpr: 'GDPR 第15条导出', audit: '审计日志', encryption: 'AES-256 加密' } },
     demo: { badge: '演示', timer: '重置倒计时', viewers: '访问者', voteReset: '投票重置', votesNeeded: '{{current}}/{{needed}} 票', resetting: '正在重置演示数据...', lastReset: '上次重置', nextReset: '下次重置' },
-    notifications: { title: '通知', allRead: '全部已读', markAllRead: '全部标记为已读', allMarkedRead: '全部已标记为已读', empty: '没有通知', unread: '未读', unreadCount: '{{count}} 条未读', pushTitle: '推送通知', pushEnabled: '启用推送通知', smsTitle: '短信通知', whatsappTitle: 'WhatsApp 通知', phoneNumber: '电话号码', phoneHint: '短信和 WhatsApp 通知需要', bookingConfirm: '预订确认', bookingReminder: '预订提醒', bookingCancelled: '预订取消', stub: '需要服务提供商', prefsSaved: '通知偏好已保存', prefsSaveFailed: '通知偏好保存失败' },
+    notifications: { title: '通知', allRead: '全部已读', markAllRead: '全部标记为已读', allMarkedRead: '全部已标记为已读', empty: '没有通知', unread: '未读', unreadCount: '{{count}} 条未读', pushTitle: '推送通知', pushEnabled: '启用推送通知', smsTitle: '短信通知', whatsappTitle: 'WhatsApp 通知', phoneNumber: '电话号码', phoneHint: '短信和 WhatsApp 通知需要', securityTitle: '安全提醒', newLogin: '从新设备登录时通过邮件通知我', bookingConfirm: '预订确认', bookingReminder: '预订提醒', bookingCancelled: '预订取消', stub: '需要服务提供商', prefsSaved: '通知偏好已保存', prefsSaveFailed: '通知偏好保存失败' },
     calendar: { title: '日历', noBookings: '此日无预订', selectDay: '点击日期查看预订', events: '事件', previousMonth: '上月', nextMonth: '下月', subscribe: '订阅', subscribeTitle: '订阅日历', subscribeDesc: '使用此URL从任何日历应用订阅您的停车日历。', copyLink: '复制', linkCopied: '链接已复制', instructions: '如何订阅', instructionGoogle: '设置 > 添加日历 > 从URL > 粘贴链接', instructionOutlook: '添加日历 > 从网络订阅 > 粘贴链接', instructionApple: '文件 > 新建日历订阅 > 粘贴链接' },
     rateLimits: { title: '速率限制', allClear: '无被阻止的请求', blockedTotal: '{{count}}个被阻止（最近一小时）', blockedHistory: '被阻止的请求（24小时）', perMinute: '分钟', blocked: '被阻止', now: '现在' },
     fleet: { title: '车队管理', subtitle: '所有用户的全部车辆', totalVehicles: '车辆总数', electricCount: '电动车', electricRatio: '电动车比例', flaggedCount: '已标记', byType: '按类型', search: '车牌、品牌、型号...', allTypes: '所有类型', colPlate: '车牌', colType: '类型', colOwner: '车主', colMakeModel: '品牌/型号', colBookings: '预订', colLastUsed: '最后使用', colActions: '操作', empty: '无车辆', flag: '标记', unflag: '取消', flagged: '车辆已标记', unflagged: '标记已移除' },
@@ -49,7 +49,7 @@ export default {
     ui: { breadcrumb: '面包屑', sortAsc: '升序排列', sortDesc: '降序排列', required: '必填', confirmAction: '确认操作', swipeToClose: '滑动关闭' },
     common: { refresh: '刷新', filter: '筛选', save: '保存', cancel: '取消', delete: '删除', edit: '编辑', close: '关闭', loading: '加载中...', error: '发生错误', success: '成功', noData: '无可用数据', back: '返回', next: '下一步', dismiss: '忽略' },
     confirm: { cancelBookingTitle: '取消预订', cancelBookingMessage: '确定要取消此预订吗？', cancelBookingConfirm: '是的，取消' },
-    book: { title: '预订车位', step1Label: '选择停车场', step2Label: '选择车位和时间', step3Label: '确认预订', stepName1: '选择停车场', stepName2: '车位和时间', stepName3: '确认', noLots: '没有可用的停车场', availableSlots: '{{count}} / {{total}} 可用', full: '满位', openNow: '营业中', closedNow: '已关闭', selectSlot: '选择一个车位', noAvailableSlots: '此停车场没有可用车位', startTime: '开始时间', duration: '时长', vehicle: '车辆', noVehicle: '无车辆', continue: '继续', lot: '停车场', slot: '车位', from: '从', to: '至', estimatedCost: '预估费用', surgePricing: '高峰定价生效中', discountPricing: '折扣定价生效中', dynamicPrice: '{{currency}}{{price}}/时', confirm: '确认预订', confirming: '预订中...', success: '预订已确认！', progress: '预订进度', summary: '预订摘要' },
+    book: { title: '预订车位', step1Label: '选择停车场', step2Label: '选择车位和时间', step3Label: '确认预订', stepName1: '选择停车场', stepName2: '车位和时间', stepName3: '确认', noLots: '没有可用的停车场', availableSlots: '{{count}} / {{total}} 可用', full: '满位', openNow: '营业中', closedNow: '已关闭', selectSlot: '选择一个车位', noAvailableSlots: '此停车场没有可用车位', startTime: '开始时间', duration: '时长', vehicle: '车辆', noVehicle: '无车辆', continue: '继续', lot: '停车场', slot: '车位', from: '从', to: '至', estimatedCost: '预估费用', surgePricing: '高峰定价生效中', discountPricing: '折扣定价生效中', dynamicPrice: '{{currency}}{{price}}/时', confirm: '确认预订', confirming: '预订中...', success: '预订已确认！', slotTakenAlternative: '车位 {{taken}} 刚被预订，已为您改选车位 {{alternative}}。确认即可预订。', progress: '预订进度', summary: '预订摘要' },
     bookingFilters: { searchLot: '搜索停车场...', statusAll: '全部', statusActive: '活跃', statusConfirmed: '已确认', statusCancelled: '已取消', statusCompleted: '已完成', dateFrom: '从', dateTo: '至', totalCount: '{{count}} 个预订' },
     homeoffice: { weekdaysShort: { mon: '一', tue: '二', wed: '三', thu: '四', fri: '五', sat: '六', sun: '日' } },
     notFound: { title: '页面未找到', description: '您查找的页面不存在或已被移动。', backToDashboard: '返回仪表盘' },
@@ -76,6 +76,10 @@ vi.mock('@phosphor-icons/react', () => ({
   ArrowSquareOutIcon: (props: any) => <span data-testid="icon-arrow-square-out" {...props} />,
 }));
 
+vi.mock('../components/DummyUsersBanner', () => ({
+  DummyUsersBanner: () => null,
+}));
+
 import { AdminPage } from './Admin';
 
 describe('AdminPage', () => {
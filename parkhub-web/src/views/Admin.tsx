@@ -7,6 +7,7 @@ import {
   BuildingsIcon, ClockCounterClockwiseIcon, DatabaseIcon, CarIcon, WheelchairIcon, WrenchIcon, CurrencyDollarIcon, UserPlusIcon, LightningIcon,
   PuzzlePieceIcon, GraphicsCardIcon, ShieldCheckIcon, LockKeyIcon, MapTrifoldIcon, ArrowsClockwiseIcon, ListIcon, XIcon, ArrowSquareOutIcon,
 } from '@phosphor-icons/react';
+import { DummyUsersBanner } from '../components/DummyUsersBanner';
 
 type AdminIcon = ComponentType<{ className?: string; weight?: 'regular' | 'fill' | 'bold' | 'duotone' }>;
 
@@ -246,6 +247,7 @@ export function AdminPage() {
         )}
 
         <main className="min-w-0 flex-1">
+          <DummyUsersBanner />
           <Outlet />
         </main>
       </div>
@@ -7,6 +7,7 @@ const mockListAnnouncements = vi.fn();
 const mockCreateAnnouncement = vi.fn();
 const mockUpdateAnnouncement = vi.fn();
 const mockDeleteAnnouncement = vi.fn();
+const mockGetLots = vi.fn();
 
 vi.mock('../api/client', () => ({
   api: {
@@ -14,6 +15,7 @@ vi.mock('../api/client', () => ({
     adminCreateAnnouncement: (...a: any[]) => mockCreateAnnouncement(...a),
     adminUpdateAnnouncement: (...a: any[]) => mockUpdateAnnouncement(...a),
     adminDeleteAnnouncement: (...a: any[]) => mockDeleteAnnouncement(...a),
+    getLots: (...a: any[]) => mockGetLots(...a),
   },
 }));
 
@@ -49,6 +51,7 @@ describe('AdminAnnouncementsPage', () => {
     mockCreateAnnouncement.mockResolvedValue({ success: true });
     mockUpdateAnnouncement.mockResolvedValue({ success: true });
     mockDeleteAnnouncement.mockResolvedValue({ success: true });
+    mockGetLots.mockResolvedValue({ success: true, data: [{ id: 'lot-1', name: 'Garage North' }] });
   });
   afterEach(() => vi.restoreAllMocks());
 
@@ -85,6 +88,23 @@ describe('AdminAnnouncementsPage', () => {
     expect(toast.success).toHaveBeenCalledWith('admin.announcementCreated');
   });
 
+  it('creates lot-targeted announcement with email delivery', async () => {
+    render(<AdminAnnouncementsPage />);
+    await waitFor(() => expect(screen.getByText('admin.newAnnouncement')).toBeInTheDocument());
+    fireEvent.click(screen.getByText('admin.newAnnouncement'));
+    await waitFor(() => expect(screen.getByRole('option', { name: 'Garage North' })).toBeInTheDocument());
+    fireEvent.change(screen.getByPlaceholderText('admin.announcementTitle'), { target: { value: 'Closed' } });
+    fireEvent.change(screen.getByPlaceholderText('admin.announcementMessage'), { target: { value: 'Level 1 closed' } });
+    fireEvent.change(screen.getByLabelText('admin.audience'), { target: { value: 'lot-1' } });
+    fireEvent.click(screen.getByLabelText('admin.sendEmail'));
+    const saveBtn = screen.getAllByText('admin.create').find(el => el.closest('button')?.className.includes('btn-primary'));
+    if (saveBtn) fireEvent.click(saveBtn);
+    await waitFor(() => expect(mockCreateAnnouncement).toHaveBeenCalledWith(expect.objectContaining({
+      audience: { type: 'lot', lot_id: 'lot-1' },
+      send_email: true,
+    })));
+  });
+
   it('edits announcement', async () => {
     render(<AdminAnnouncementsPage />);
     await waitFor(() => expect(screen.getByText('Maintenance')).toBeInTheDocument());
@@ -4,7 +4,7 @@ import {
   MegaphoneIcon, PlusIcon, PencilSimpleIcon, TrashIcon, SpinnerGapIcon, CheckIcon, XIcon,
   InfoIcon, WarningIcon, WarningCircleIcon, CheckCircleIcon, ClockIcon,
 } from '@phosphor-icons/react';
-import { api, type Announcement } from '../api/client';
+import { api, type Announcement, type AnnouncementAudience, type ParkingLot } from '../api/client';
 import { useTranslation } from 'react-i18next';
 import toast from 'react-hot-toast';
 import { ConfirmDialog } from '../components/ui/ConfirmDialog';
@@ -18,6 +18,9 @@ interface AnnouncementForm {
   severity: Severity;
   active: boolean;
   expires_at: string;
+  /** Target lot ID; empty means everyone */
+  audience_lot_id: string;
+  send_email: boolean;
 }
 
 const emptyForm: AnnouncementForm = {
@@ -26,6 +29,8 @@ const emptyForm: AnnouncementForm = {
   severity: 'info',
   active: true,
   expires_at: '',
+  audience_lot_id: '',
+  send_email: false,
 };
 
 const severityIcons: Record<Severity, { color: string; bg: string; icon: typeof InfoIcon }> = {
@@ -74,6 +79,7 @@ function StatusBadge({ active, expiresAt, t }: { active: boolean; expiresAt?: st
 export function AdminAnnouncementsPage() {
   const { t } = useTranslation();
   const [announcements, setAnnouncements] = useState<Announcement[]>([]);
+  const [lots, setLots] = useState<ParkingLot[]>([]);
   const [loading, setLoading] = useState(true);
   const [showForm, setShowForm] = useState(false);
   const [editingId, setEditingId] = useState<string | null>(null);
@@ -83,6 +89,9 @@ export function AdminAnnouncementsPage() {
   const [confirmState, setConfirmState] = useState<{open: boolean, action: () => void}>({open: false, action: () => {}});
 
   useEffect(() => { load(); }, []);
+  useEffect(() => {
+    api.getLots().then(res => { if (res.success && res.data) setLots(res.data); }).catch(() => {});
+  }, []);
 
   async function load() {
     try {
@@ -107,6 +116,8 @@ export function AdminAnnouncementsPage() {
       severity: a.severity as Severity,
       active: a.active,
       expires_at: a.expires_at ? a.expires_at.slice(0, 16) : '',
+      audience_lot_id: a.audience?.type === 'lot' ? a.audience.lot_id : '',
+      send_email: false,
     });
     setShowForm(true);
   }
@@ -130,6 +141,10 @@ export function AdminAnnouncementsPage() {
         severity: form.severity,
         active: form.active,
         expires_at: form.expires_at || undefined,
+        audience: (form.audience_lot_id
+          ? { type: 'lot', lot_id: form.audience_lot_id }
+          : { type: 'all' }) as AnnouncementAudience,
+        send_email: form.send_email,
       };
       const res = editingId
         ? await api.adminUpdateAnnouncement(editingId, payload)
@@ -298,6 +313,33 @@ export function AdminAnnouncementsPage() {
                 </div>
               </div>
 
+              <div className="grid grid-cols-1 sm:grid-cols-2 gap-5">
+                {/* Audience */}
+                <div>
+                  <label htmlFor="announcement-audience" className="block text-sm font-medium text-surface-700 dark:text-surface-300 mb-2">{t('admin.audience')}</label>
+                  <select
+                    id="announcement-audience"
+                    value={form.audience_lot_id}
+                    onChange={e => setForm(prev => ({ ...prev, audience_lot_id: e.target.value }))}
+                    className="input"
+                  >
+                    <option value="">{t('admin.audienceAll')}</option>
+                    {lots.map(lot => <option key={lot.id} value={lot.id}>{lot.name}</option>)}
+                  </select>
+                </div>
+
+                {/* Email delivery */}
+                <label className="flex items-center gap-2 text-sm font-medium text-surface-700 dark:text-surface-300 sm:mt-8">
+                  <input
+                    type="checkbox"
+                    checked={form.send_email}
+                    onChange={e => setForm(prev => ({ ...prev, send_email: e.target.checked }))}
+                    className="rounded border-surface-300 dark:border-surface-600"
+                  />
+                  {t('admin.sendEmail')}
+                </label>
+              </div>
+
               {/* Actions */}
               <div className="flex gap-3 pt-2">
                 <button onClick={handleSave} disabled={saving} className="btn btn-primary">
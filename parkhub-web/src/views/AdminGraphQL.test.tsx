@@ -83,6 +83,10 @@ vi.mock('@phosphor-icons/react', async (importOriginal) => {
   };
 });
 
+vi.mock('../components/DummyUsersBanner', () => ({
+  DummyUsersBanner: () => null,
+}));
+
 import { AdminPage } from './Admin';
 
 describe('Admin GraphQL navigation', () => {
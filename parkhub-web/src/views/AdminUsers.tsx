@@ -204,6 +204,7 @@ export function AdminUsersPage() {
             <div className="flex items-center gap-2">
               <select value={editRole} onChange={e => setEditRole(e.target.value)} className="input text-xs py-1 px-2 w-28">
                 <option value="user">user</option>
+                <option value="reporter">reporter</option>
                 <option value="admin">admin</option>
                 <option value="superadmin">superadmin</option>
               </select>
@@ -288,11 +288,14 @@ describe('BookPage', () => {
       expect(screen.getByText('1h')).toBeInTheDocument();
     });
 
-    // All four duration buttons should be visible
+    // Hourly and multi-day duration buttons should be visible
     expect(screen.getByText('1h')).toBeInTheDocument();
     expect(screen.getByText('2h')).toBeInTheDocument();
     expect(screen.getByText('4h')).toBeInTheDocument();
     expect(screen.getByText('8h')).toBeInTheDocument();
+    expect(screen.getByText('1d')).toBeInTheDocument();
+    expect(screen.getByText('3d')).toBeInTheDocument();
+    expect(screen.getByText('1w')).toBeInTheDocument();
 
     // Click 4h duration
     await user.click(screen.getByText('4h'));
@@ -633,6 +636,46 @@ describe('BookPage', () => {
     });
   });
 
+  it('preselects a suggested alternative when the slot was just taken', async () => {
+    const user = userEvent.setup();
+    mockGetLots.mockResolvedValue({ success: true, data: [makeLot()] });
+    mockGetVehicles.mockResolvedValue({ success: true, data: [] });
+    mockGetLotSlots.mockResolvedValue({
+      success: true,
+      data: [makeSlot(), makeSlot({ id: 'slot-2', slot_number: 'A2' })],
+    });
+    mockCreateBooking.mockResolvedValueOnce({
+      success: false,
+      data: null,
+      error: {
+        code: 'SLOT_UNAVAILABLE',
+        message: 'This slot is not available',
+        details: { alternatives: [{ slot_id: 'slot-2', slot_number: 2, floor_id: 'f-1', slot_type: 'standard' }] },
+      },
+    });
+
+    render(<BookPage />);
+
+    await waitFor(() => expect(screen.getByText('Garage Alpha')).toBeInTheDocument());
+    await user.click(screen.getByText('Garage Alpha'));
+    await waitFor(() => expect(screen.getByText('A1')).toBeInTheDocument());
+    await user.click(screen.getByText('A1'));
+    await user.click(screen.getByText('Continue'));
+    await waitFor(() => expect(screen.getByText('Confirm Booking')).toBeInTheDocument());
+    await user.click(screen.getByText('Confirm Booking'));
+
+    await waitFor(() => {
+      expect(mockToastError).toHaveBeenCalledWith('book.slotTakenAlternative');
+    });
+    expect(screen.getByText('A2')).toBeInTheDocument();
+
+    mockCreateBooking.mockResolvedValueOnce({ success: true, data: { id: 'b-1' } });
+    await user.click(screen.getByText('Confirm Booking'));
+    await waitFor(() => {
+      expect(mockCreateBooking).toHaveBeenLastCalledWith(expect.objectContaining({ slot_id: 'slot-2' }));
+    });
+  });
+
   it('shows slot type icons', async () => {
     const user = userEvent.setup();
     const lot = makeLot();
@@ -9,7 +9,7 @@ import {
   TrendUpIcon, TrendDownIcon,
 } from '@phosphor-icons/react';
 import type { Icon } from '@phosphor-icons/react';
-import { api, type ParkingLot, type ParkingSlot, type Vehicle, type CreateBookingPayload, type DynamicPriceResult, type OperatingHoursData, type Booking } from '../api/client';
+import { api, type ParkingLot, type ParkingSlot, type Vehicle, type CreateBookingPayload, type DynamicPriceResult, type OperatingHoursData, type Booking, type SlotAlternative } from '../api/client';
 import { SkeletonCard } from '../components/Skeleton';
 import { findOverlappingBooking } from '../hooks/useConflictCheck';
 import toast from 'react-hot-toast';
@@ -22,6 +22,9 @@ const DURATIONS = [
   { label: '2h', hours: 2 },
   { label: '4h', hours: 4 },
   { label: '8h', hours: 8 },
+  { label: '1d', hours: 24 },
+  { label: '3d', hours: 72 },
+  { label: '1w', hours: 168 },
 ];
 
 export function BookPage() {
@@ -109,6 +112,13 @@ export function BookPage() {
       setConfirmed(true);
       toast.success(t('book.success'));
       navigate('/bookings');
+    } else if (res.error?.code === 'SLOT_UNAVAILABLE' && pickAlternative(res.error.details)) {
+      // The server suggests free slots of the same type; preselect the nearest
+      // one so the user can confirm it without reloading the slot list.
+      const alternative = pickAlternative(res.error.details)!;
+      setSlots(prev => prev.map(s => s.id === selectedSlot.id ? { ...s, status: 'reserved' } : s));
+      setSelectedSlot(alternative);
+      toast.error(t('book.slotTakenAlternative', { taken: selectedSlot.slot_number, alternative: alternative.slot_number }));
     } else {
       const msg = res.error?.code === 'INSUFFICIENT_CREDITS'
         ? t('bookings.insufficientCredits') : res.error?.message || t('common.error');
@@ -117,10 +127,17 @@ export function BookPage() {
     setSubmitting(false);
   }
 
+  function pickAlternative(details: unknown): ParkingSlot | undefined {
+    const alternatives = (details as { alternatives?: SlotAlternative[] } | null)?.alternatives ?? [];
+    return alternatives
+      .map(a => slots.find(s => s.id === a.slot_id))
+      .find((s): s is ParkingSlot => !!s);
+  }
+
   const start = new Date(startDate);
   const end = new Date(start.getTime() + duration * 60 * 60 * 1000);
   const effectiveRate = dynamicPrice?.dynamic_pricing_active ? dynamicPrice.current_price : selectedLot?.hourly_rate;
-  const estimatedCost = effectiveRate ? (effectiveRate * duration).toFixed(2) : null;
+  const estimatedCost = effectiveRate ? estimatePrice(effectiveRate, selectedLot?.daily_max, start, end).toFixed(2) : null;
 
   const slideVariants = {
     enter: (dir: number) => ({ x: dir > 0 ? 80 : -80, opacity: 0 }),
@@ -251,6 +268,21 @@ export function BookPage() {
   );
 }
 
+/** Price of [start, end) with the daily maximum applied per UTC calendar day, as the server prices it. */
+function estimatePrice(rate: number, dailyMax: number | undefined, start: Date, end: Date): number {
+  let total = 0;
+  let cursor = start.getTime();
+  while (cursor < end.getTime()) {
+    const day = new Date(cursor);
+    const nextMidnight = Date.UTC(day.getUTCFullYear(), day.getUTCMonth(), day.getUTCDate() + 1);
+    const pieceEnd = Math.min(nextMidnight, end.getTime());
+    const dayPrice = rate * (pieceEnd - cursor) / 3_600_000;
+    total += dailyMax ? Math.min(dayPrice, dailyMax) : dayPrice;
+    cursor = pieceEnd;
+  }
+  return total;
+}
+
 /** Check if a lot is currently open based on operating_hours. */
 function isLotOpenNow(hours?: OperatingHoursData): boolean {
   if (!hours || hours.is_24h) return true;
@@ -140,6 +140,7 @@ vi.mock('framer-motion', () => ({
 
 vi.mock('@phosphor-icons/react', () => ({
   UserCircleIcon: (props: any) => <span data-testid="icon-user" {...props} />,
+  CameraIcon: (props: any) => <span data-testid="icon-camera" {...props} />,
   EnvelopeIcon: (props: any) => <span data-testid="icon-envelope" {...props} />,
   PencilSimpleIcon: (props: any) => <span data-testid="icon-pencil" {...props} />,
   FloppyDiskIcon: (props: any) => <span data-testid="icon-save" {...props} />,
@@ -3,7 +3,7 @@ import { motion } from 'framer-motion';
 import {
   UserCircleIcon, EnvelopeIcon, PencilSimpleIcon, FloppyDiskIcon, SpinnerGapIcon, LockIcon,
   DownloadSimpleIcon, TrashIcon, CaretDownIcon, CaretUpIcon,
-  MapPinIcon, QuestionIcon,
+  MapPinIcon, QuestionIcon, CameraIcon,
 } from '@phosphor-icons/react';
 import { useAuth } from '../context/AuthContext';
 import { api, type UserStats } from '../api/client';
@@ -16,10 +16,11 @@ import { NotificationPreferencesComponent } from '../components/NotificationPref
 import { LoginHistoryComponent } from '../components/LoginHistory';
 import { ProfileThemeSection } from '../components/ProfileThemeSection';
 import { HeroEyebrow } from '../components/v11/HeroEyebrow';
+import { UserAvatar } from '../components/UserAvatar';
 
 export function ProfilePage() {
   const { t } = useTranslation();
-  const { user, logout } = useAuth();
+  const { user, logout, refreshUser } = useAuth();
   const [editing, setEditing] = useState(false);
   const [formData, setFormData] = useState({ name: user?.name || '', email: user?.email || '' });
   const [saving, setSaving] = useState(false);
@@ -32,6 +33,10 @@ export function ProfilePage() {
   const [pwForm, setPwForm] = useState({ current: '', newPw: '', confirm: '' });
   const [pwSaving, setPwSaving] = useState(false);
 
+  // Profile picture
+  const pictureInput = useRef<HTMLInputElement>(null);
+  const [pictureBusy, setPictureBusy] = useState(false);
+
   useEffect(() => {
     api.getUserStats().then(res => { if (res.success && res.data) setStats(res.data); }).catch(() => {});
   }, []);
@@ -49,6 +54,41 @@ export function ProfilePage() {
     } finally { setSaving(false); }
   }
 
+  async function handlePictureSelected(file: File | undefined) {
+    if (!file) return;
+    if (file.size > 2 * 1024 * 1024) {
+      toast.error(t('profile.pictureTooLarge', 'Bild ist gr\u00f6\u00dfer als 2 MB'));
+      return;
+    }
+    setPictureBusy(true);
+    try {
+      const res = await api.uploadMyPicture(file);
+      if (res.success) {
+        await refreshUser();
+        toast.success(t('profile.pictureUpdated', 'Profilbild aktualisiert'));
+      } else {
+        toast.error(res.error?.message || t('common.error'));
+      }
+    } catch { toast.error(t('common.error')); }
+    finally {
+      setPictureBusy(false);
+      if (pictureInput.current) pictureInput.current.value = '';
+    }
+  }
+
+  async function handleRemovePicture() {
+    setPictureBusy(true);
+    try {
+      const res = await api.deleteMyPicture();
+      if (res.success) {
+        await refreshUser();
+        toast.success(t('profile.pictureRemoved', 'Profilbild entfernt'));
+      } else {
+        toast.error(res.error?.message || t('common.error'));
+      }
+    } finally { setPictureBusy(false); }
+  }
+
   async function handleChangePassword() {
     // Defensive guards — the submit button is disabled while these fail so the
     // happy path in the UI can never reach the toast.error branches below.
@@ -144,8 +184,35 @@ export function ProfilePage() {
       {/* Profile card */}
       <motion.div variants={item} className="bg-white dark:bg-surface-900 rounded-xl border border-surface-200 dark:border-surface-800 p-6">
         <div className="flex items-start gap-5">
-          <div className="w-14 h-14 rounded-lg bg-surface-100 dark:bg-surface-800 flex items-center justify-center flex-shrink-0">
-            <span className="text-xl font-bold text-surface-600 dark:text-surface-300">{initials}</span>
+          <div className="flex flex-col items-center gap-1.5 flex-shrink-0">
+            <button
+              type="button"
+              onClick={() => pictureInput.current?.click()}
+              disabled={pictureBusy}
+              aria-label={t('profile.uploadPicture', 'Profilbild hochladen')}
+              title={t('profile.uploadPicture', 'Profilbild hochladen')}
+              className="group relative w-14 h-14 rounded-lg bg-surface-100 dark:bg-surface-800 flex items-center justify-center"
+            >
+              <UserAvatar user={user} fallback={initials} textClassName="text-xl font-bold text-surface-600 dark:text-surface-300" />
+              <span className="absolute inset-0 rounded-lg bg-black/40 flex items-center justify-center opacity-0 group-hover:opacity-100 transition-opacity">
+                {pictureBusy
+                  ? <SpinnerGapIcon weight="bold" className="w-5 h-5 text-white animate-spin" />
+                  : <CameraIcon weight="bold" className="w-5 h-5 text-white" />}
+              </span>
+            </button>
+            <input
+              ref={pictureInput}
+              type="file"
+              accept="image/jpeg,image/png"
+              className="hidden"
+              data-testid="profile-picture-input"
+              onChange={e => handlePictureSelected(e.target.files?.[0])}
+            />
+            {user?.picture && (
+              <button onClick={handleRemovePicture} disabled={pictureBusy} className="text-xs text-surface-500 hover:text-red-500 transition-colors">
+                {t('profile.removePicture', 'Entfernen')}
+              </button>
+            )}
           </div>
           <div className="flex-1">
             {editing ? (
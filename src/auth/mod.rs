@@ -1,7 +1,8 @@
 //! Authentication module
 //!
-//! This module will handle Google OAuth and session management.
-//! Currently a placeholder for future implementation.
+//! Placeholder from the original single-crate layout. Login is implemented
+//! by the server: social OAuth in `parkhub-server/src/api/oauth.rs` and
+//! OpenID Connect in `parkhub-server/src/api/oidc.rs`.
 
 #![allow(dead_code)]
 
@@ -376,12 +376,25 @@ pub async fn create_test_lot(srv: &TestServer, token: &str, name: &str) -> Strin
 }
 
 /// Create a parking slot in a lot (requires admin token).  Returns `slot_id`.
+///
+/// Slot numbers are unique per lot, so if the lot already has slot
+/// `slot_number` (lots from [`create_test_lot`] come with generated slots),
+/// that slot is returned instead.
 pub async fn create_test_slot(
     srv: &TestServer,
     token: &str,
     lot_id: &str,
     slot_number: i32,
 ) -> String {
+    let (_, existing) = auth_get(srv, token, &format!("/api/v1/lots/{lot_id}/slots")).await;
+    if let Some(slot) = existing["data"].as_array().and_then(|slots| {
+        slots
+            .iter()
+            .find(|s| s["slot_number"].as_i64() == Some(i64::from(slot_number)))
+    }) {
+        return slot["id"].as_str().expect("slot id").to_string();
+    }
+
     let resp = srv
         .client
         .post(format!("{}/api/v1/lots/{}/slots", srv.url, lot_id))